@@ -0,0 +1,290 @@
+//! Request latency for the hot read paths: timeline, list pages and image
+//! GETs. Each benchmark drives a real server over HTTP against a seeded
+//! `SQLite` database, so results include routing, templating and queries.
+//!
+//! Run with `cargo bench --bench endpoints`.
+
+#![allow(
+    clippy::expect_used,
+    clippy::unwrap_used,
+    clippy::cast_possible_truncation
+)]
+
+use std::sync::Arc;
+
+use brewlog::application::services::{ImagePurger, StatsInvalidator, TimelineInvalidator};
+use brewlog::application::{AppState, AppStateConfig, RepositoryBackend, app_router};
+use brewlog::domain::clock::SystemClock;
+use brewlog::domain::tokens::NewToken;
+use brewlog::domain::users::NewUser;
+use brewlog::infrastructure::auth::{TokenHasher, generate_token, hash_token};
+use brewlog::infrastructure::database::Database;
+use brewlog::infrastructure::image_processing::ImageSettings;
+use criterion::{Criterion, criterion_group, criterion_main};
+use serde_json::{Value, json};
+use tokio::net::TcpListener;
+use tokio::runtime::Runtime;
+use webauthn_rs::prelude::*;
+
+const ROASTERS: usize = 20;
+const ROASTS_PER_ROASTER: usize = 3;
+const BREWS_PER_BAG: usize = 5;
+
+struct BenchApp {
+    address: String,
+    client: reqwest::Client,
+    image_roaster_id: i64,
+    _db_dir: tempfile::TempDir,
+}
+
+impl BenchApp {
+    async fn get(&self, path: &str) {
+        let response = self
+            .client
+            .get(format!("{}{path}", self.address))
+            .send()
+            .await
+            .expect("request failed");
+        assert!(
+            response.status().is_success(),
+            "{path} returned {}",
+            response.status()
+        );
+        response.bytes().await.expect("failed to read body");
+    }
+}
+
+async fn spawn_app() -> BenchApp {
+    let db_dir = tempfile::tempdir().expect("failed to create temp dir");
+    let database_url = format!("sqlite://{}", db_dir.path().join("bench.db").display());
+    let database = Database::connect(&database_url)
+        .await
+        .expect("failed to connect to database");
+
+    let rp_origin = url::Url::parse("http://localhost:3000").unwrap();
+    let webauthn = Arc::new(
+        WebauthnBuilder::new("localhost", &rp_origin)
+            .unwrap()
+            .build()
+            .unwrap(),
+    );
+    let (stats_tx, _stats_rx) = tokio::sync::mpsc::channel(1);
+    let (timeline_tx, _timeline_rx) = tokio::sync::mpsc::channel(1);
+    let (image_purge_tx, _image_purge_rx) = tokio::sync::mpsc::channel(1);
+    let state = AppState::from_database(
+        &database,
+        AppStateConfig {
+            webauthn,
+            insecure_cookies: true,
+            token_hasher: TokenHasher::sha256(),
+            foursquare_url: String::new(),
+            foursquare_api_key: String::new(),
+            openrouter_url: String::new(),
+            openrouter_api_key: String::new(),
+            openrouter_model: String::new(),
+            stats_invalidator: StatsInvalidator::new(stats_tx),
+            timeline_invalidator: TimelineInvalidator::new(timeline_tx),
+            image_purger: ImagePurger::new(image_purge_tx),
+            clock: Arc::new(SystemClock),
+            repositories: RepositoryBackend::Sql,
+            image_settings: ImageSettings::default(),
+        },
+    );
+
+    let user = state
+        .user_repo
+        .insert(NewUser::new(
+            "bench".to_string(),
+            uuid::Uuid::new_v4().to_string(),
+        ))
+        .await
+        .expect("failed to create user");
+    let token = generate_token().expect("failed to generate token");
+    state
+        .token_repo
+        .insert(NewToken::new(
+            user.id,
+            hash_token(&token),
+            "bench".to_string(),
+        ))
+        .await
+        .expect("failed to create token");
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = format!("http://{}", listener.local_addr().unwrap());
+    let app = app_router(state);
+    tokio::spawn(async move { axum::serve(listener, app).await });
+
+    let client = reqwest::Client::new();
+    let image_roaster_id = seed(&client, &address, &token).await;
+
+    BenchApp {
+        address,
+        client,
+        image_roaster_id,
+        _db_dir: db_dir,
+    }
+}
+
+async fn post(
+    client: &reqwest::Client,
+    address: &str,
+    token: &str,
+    path: &str,
+    body: Value,
+) -> i64 {
+    let response: Value = client
+        .post(format!("{address}/api/v1{path}"))
+        .bearer_auth(token)
+        .json(&body)
+        .send()
+        .await
+        .expect("seed request failed")
+        .error_for_status()
+        .expect("seed request rejected")
+        .json()
+        .await
+        .expect("seed response was not JSON");
+    response["id"].as_i64().expect("seed response had no id")
+}
+
+/// Create roasters, roasts, bags and brews, plus one roaster image.
+/// Returns the ID of the roaster with the image.
+async fn seed(client: &reqwest::Client, address: &str, token: &str) -> i64 {
+    let grinder_id = post(
+        client,
+        address,
+        token,
+        "/gear",
+        json!({ "category": "grinder", "make": "Comandante", "model": "C40" }),
+    )
+    .await;
+    let brewer_id = post(
+        client,
+        address,
+        token,
+        "/gear",
+        json!({ "category": "brewer", "make": "Hario", "model": "V60" }),
+    )
+    .await;
+
+    let mut first_roaster_id = None;
+    for r in 0..ROASTERS {
+        let roaster_id = post(
+            client,
+            address,
+            token,
+            "/roasters",
+            json!({ "name": format!("Roaster {r}"), "country": "UK" }),
+        )
+        .await;
+        first_roaster_id.get_or_insert(roaster_id);
+
+        for n in 0..ROASTS_PER_ROASTER {
+            let roast_id = post(
+                client,
+                address,
+                token,
+                "/roasts",
+                json!({
+                    "roaster_id": roaster_id,
+                    "name": format!("Roast {r}-{n}"),
+                    "origin": "Ethiopia",
+                    "region": "Yirgacheffe",
+                    "producer": "Coop",
+                    "tasting_notes": ["Blueberry", "Jasmine"],
+                    "process": "Washed",
+                }),
+            )
+            .await;
+            let bag_id = post(
+                client,
+                address,
+                token,
+                "/bags",
+                json!({ "roast_id": roast_id, "amount": 250.0 }),
+            )
+            .await;
+            for _ in 0..BREWS_PER_BAG {
+                post(
+                    client,
+                    address,
+                    token,
+                    "/brews",
+                    json!({
+                        "bag_id": bag_id,
+                        "coffee_weight": 15.0,
+                        "grinder_id": grinder_id,
+                        "grind_setting": 24.0,
+                        "brewer_id": brewer_id,
+                        "water_volume": 250,
+                        "water_temp": 92.0,
+                        "quick_notes": [],
+                    }),
+                )
+                .await;
+            }
+        }
+    }
+
+    let roaster_id = first_roaster_id.expect("no roasters seeded");
+    client
+        .put(format!("{address}/api/v1/roaster/{roaster_id}/image"))
+        .bearer_auth(token)
+        .json(&json!({ "image": sample_png_data_url() }))
+        .send()
+        .await
+        .expect("image upload failed")
+        .error_for_status()
+        .expect("image upload rejected");
+
+    roaster_id
+}
+
+/// A 256x256 gradient PNG, large enough to produce a real thumbnail.
+fn sample_png_data_url() -> String {
+    use base64::Engine;
+    use image::{ImageBuffer, Rgb};
+
+    let img = ImageBuffer::from_fn(256, 256, |x, y| Rgb([x as u8, y as u8, 128]));
+    let mut buf = Vec::new();
+    let encoder = image::codecs::png::PngEncoder::new(&mut buf);
+    image::ImageEncoder::write_image(
+        encoder,
+        img.as_raw(),
+        256,
+        256,
+        image::ColorType::Rgb8.into(),
+    )
+    .expect("failed to encode PNG");
+
+    let b64 = base64::engine::general_purpose::STANDARD.encode(&buf);
+    format!("data:image/png;base64,{b64}")
+}
+
+fn endpoints(c: &mut Criterion) {
+    let runtime = Runtime::new().expect("failed to start runtime");
+    let app = runtime.block_on(spawn_app());
+    let image = format!("/api/v1/roaster/{}/image", app.image_roaster_id);
+    let thumbnail = format!("/api/v1/roaster/{}/thumbnail", app.image_roaster_id);
+
+    let paths = [
+        "/timeline",
+        "/data?type=roasters",
+        "/data?type=roasts",
+        "/data?type=brews",
+        "/api/v1/roasters",
+        "/api/v1/brews",
+        image.as_str(),
+        thumbnail.as_str(),
+    ];
+
+    let mut group = c.benchmark_group("endpoints");
+    for path in paths {
+        group.bench_function(path, |b| b.to_async(&runtime).iter(|| app.get(path)));
+    }
+    group.finish();
+}
+
+criterion_group!(benches, endpoints);
+criterion_main!(benches);
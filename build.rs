@@ -1,9 +1,11 @@
+use std::fmt::Write as _;
 use std::path::Path;
 use std::process::Command;
 
 fn main() {
     git_hash();
     tailwind();
+    asset_hashes();
 }
 
 fn git_hash() {
@@ -50,3 +52,75 @@ fn tailwind() {
         std::fs::write(output, "/* tailwindcss not available */").ok();
     }
 }
+
+/// Write `$OUT_DIR/asset_hashes.rs`: a `(path, hash)` table of every file
+/// under `static/`, used to build content-hashed asset URLs.
+fn asset_hashes() {
+    // styles.css is regenerated by tailwind() on every run, so watching it
+    // would make every build dirty. Its inputs are already watched above.
+    println!("cargo:rerun-if-changed=static/js/");
+    for file in [
+        "favicon-light.svg",
+        "favicon-dark.svg",
+        "og-image.png",
+        "app-icon-192.png",
+        "app-icon-512.png",
+        "site.webmanifest",
+    ] {
+        println!("cargo:rerun-if-changed=static/{file}");
+    }
+
+    let mut files = Vec::new();
+    collect_files(Path::new("static"), &mut files);
+    files.sort();
+
+    let mut table = String::from("&[\n");
+    for path in files {
+        let relative = path
+            .strip_prefix("static")
+            .unwrap_or(&path)
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy().into_owned())
+            .collect::<Vec<_>>()
+            .join("/");
+        if relative == "css/input.css" {
+            continue;
+        }
+        let Ok(contents) = std::fs::read(&path) else {
+            eprintln!("cargo:warning=failed to read {}", path.display());
+            continue;
+        };
+        let _ = writeln!(table, "    ({relative:?}, \"{:016x}\"),", fnv1a(&contents));
+    }
+    table.push(']');
+
+    let out_dir = std::env::var("OUT_DIR").unwrap_or_default();
+    let out = Path::new(&out_dir).join("asset_hashes.rs");
+    if let Err(e) = std::fs::write(&out, table) {
+        eprintln!("cargo:warning=failed to write {}: {e}", out.display());
+    }
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<std::path::PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_files(&path, files);
+        } else {
+            files.push(path);
+        }
+    }
+}
+
+/// 64-bit FNV-1a. Only needs to change when the content does.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in bytes {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    hash
+}
@@ -0,0 +1,12 @@
+//! Decoding a single NDJSON backup line, as the streaming restore does.
+#![no_main]
+
+use brewlog::infrastructure::backup::{BackupData, BackupRecord};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &[u8]| {
+    if let Ok(record) = serde_json::from_slice::<BackupRecord>(input) {
+        let _ = serde_json::to_string(&record).expect("decoded records re-encode");
+    }
+    let _ = serde_json::from_slice::<BackupData>(input);
+});
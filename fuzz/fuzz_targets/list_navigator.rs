@@ -0,0 +1,26 @@
+//! Query-string building: `encode_uri_component` via filters and search, and
+//! `page_size_from_text` via the rows selector.
+#![no_main]
+
+use brewlog::domain::listing::ListRequest;
+use brewlog::domain::roasters::RoasterSortKey;
+use brewlog::presentation::web::views::ListNavigator;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let (search, rest) = input.split_once('\0').unwrap_or((input, ""));
+    let (filter, rows) = rest.split_once('\0').unwrap_or((rest, ""));
+
+    let navigator = ListNavigator::new(
+        "/roasters",
+        "/data/roasters#roaster-list",
+        ListRequest::<RoasterSortKey>::default_query(),
+        Some(search.to_string()),
+    )
+    .with_filter("country", filter);
+
+    let query = navigator.query_for_rows(rows);
+    assert!(query.is_ascii());
+    assert!(!query.contains(' '));
+    let _ = navigator.filter_href("country", filter);
+});
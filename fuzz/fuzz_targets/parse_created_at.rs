@@ -0,0 +1,11 @@
+//! The `--created-at` / `--finished-at` CLI date parsers.
+#![no_main]
+
+use brewlog::presentation::cli::{parse_created_at, parse_finished_at};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let created = parse_created_at(input);
+    let finished = parse_finished_at(input);
+    assert_eq!(created.is_ok(), finished.is_ok());
+});
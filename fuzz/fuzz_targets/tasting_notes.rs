@@ -0,0 +1,13 @@
+//! Tasting note splitting and the exact, substring and Levenshtein matchers.
+#![no_main]
+
+use brewlog::presentation::web::views::tasting_notes::parse_and_categorize;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let notes = vec![input.to_string()];
+    for view in parse_and_categorize(&notes) {
+        assert!(!view.label.is_empty());
+        assert_eq!(view.label.trim(), view.label);
+    }
+});
@@ -0,0 +1,11 @@
+-- Per-user defaults for list pages, applied when a request omits
+-- page size or sort parameters. A page_size of 0 means "show all".
+CREATE TABLE list_preferences (
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    list TEXT NOT NULL CHECK (list IN ('roasters', 'roasts', 'bags', 'gear', 'brews', 'cafes', 'cups')),
+    page_size INTEGER,
+    sort_key TEXT,
+    sort_direction TEXT CHECK (sort_direction IN ('asc', 'desc')),
+    updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    PRIMARY KEY (user_id, list)
+);
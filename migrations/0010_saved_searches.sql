@@ -0,0 +1,16 @@
+-- Named combinations of search text, filters and sort for a list page.
+-- Filters are stored as a JSON object of query parameters.
+CREATE TABLE saved_searches (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    list TEXT NOT NULL CHECK (list IN ('roasters', 'roasts', 'bags', 'gear', 'brews', 'cafes', 'cups')),
+    search TEXT,
+    filters TEXT NOT NULL DEFAULT '{}',
+    sort_key TEXT,
+    sort_direction TEXT CHECK (sort_direction IN ('asc', 'desc')),
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    UNIQUE (user_id, name)
+);
+CREATE INDEX idx_saved_searches_user_id ON saved_searches(user_id);
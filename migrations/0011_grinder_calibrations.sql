@@ -0,0 +1,13 @@
+-- Reference points pairing equivalent settings on two grinders. Points are
+-- symmetric: either grinder can be the source of a conversion.
+CREATE TABLE grinder_calibrations (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    grinder_a_id INTEGER NOT NULL REFERENCES gear(id) ON DELETE CASCADE,
+    setting_a REAL NOT NULL,
+    grinder_b_id INTEGER NOT NULL REFERENCES gear(id) ON DELETE CASCADE,
+    setting_b REAL NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    CHECK (grinder_a_id <> grinder_b_id)
+);
+CREATE INDEX idx_grinder_calibrations_grinder_a_id ON grinder_calibrations(grinder_a_id);
+CREATE INDEX idx_grinder_calibrations_grinder_b_id ON grinder_calibrations(grinder_b_id);
@@ -0,0 +1,8 @@
+-- User-defined quick notes offered on the brew form alongside the built-ins.
+-- Brews store notes by label, so a label identifies its note.
+CREATE TABLE custom_quick_notes (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    label TEXT NOT NULL UNIQUE COLLATE NOCASE,
+    emoji TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+);
@@ -0,0 +1,12 @@
+-- Public read-only links to a single brew. Only the hash of the link token
+-- is stored; the token itself is shown once, when the link is created.
+CREATE TABLE brew_shares (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    brew_id INTEGER NOT NULL REFERENCES brews(id) ON DELETE CASCADE,
+    token_hash TEXT NOT NULL UNIQUE,
+    expires_at TEXT,
+    revoked_at TEXT,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+);
+
+CREATE INDEX idx_brew_shares_brew_id ON brew_shares(brew_id);
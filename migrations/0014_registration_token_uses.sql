@@ -0,0 +1,7 @@
+-- Invite links can be used more than once and revoked before they expire.
+-- used_at and used_by_user_id now record the most recent use.
+ALTER TABLE registration_tokens ADD COLUMN max_uses INTEGER NOT NULL DEFAULT 1;
+ALTER TABLE registration_tokens ADD COLUMN use_count INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE registration_tokens ADD COLUMN revoked_at TEXT;
+
+UPDATE registration_tokens SET use_count = 1 WHERE used_at IS NOT NULL;
@@ -0,0 +1,24 @@
+-- Free-text name shown on the profile page. Falls back to the username.
+ALTER TABLE users ADD COLUMN display_name TEXT;
+
+-- Allow avatars in entity_images. SQLite cannot alter a CHECK constraint, so
+-- the table is rebuilt with 'user' added to the allowed entity types.
+CREATE TABLE entity_images_new (
+    id INTEGER PRIMARY KEY,
+    entity_type TEXT NOT NULL CHECK (entity_type IN ('roaster', 'roast', 'gear', 'cafe', 'brew', 'cup', 'user')),
+    entity_id INTEGER NOT NULL,
+    content_type TEXT NOT NULL,
+    image_data BLOB NOT NULL,
+    thumbnail_data BLOB NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    UNIQUE(entity_type, entity_id)
+);
+
+INSERT INTO entity_images_new (id, entity_type, entity_id, content_type, image_data, thumbnail_data, created_at)
+SELECT id, entity_type, entity_id, content_type, image_data, thumbnail_data, created_at
+FROM entity_images;
+
+DROP TABLE entity_images;
+ALTER TABLE entity_images_new RENAME TO entity_images;
+
+CREATE INDEX idx_entity_images_lookup ON entity_images (entity_type, entity_id);
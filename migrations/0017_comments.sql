@@ -0,0 +1,15 @@
+-- Comment threads on brews and roasts. Each comment belongs to exactly one
+-- of the two, and is removed along with it.
+CREATE TABLE comments (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    brew_id INTEGER REFERENCES brews(id) ON DELETE CASCADE,
+    roast_id INTEGER REFERENCES roasts(id) ON DELETE CASCADE,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    body TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    CHECK ((brew_id IS NULL) != (roast_id IS NULL))
+);
+
+CREATE INDEX idx_comments_brew_id ON comments(brew_id);
+CREATE INDEX idx_comments_roast_id ON comments(roast_id);
@@ -0,0 +1,13 @@
+-- In-app notifications, one row per recipient. `link` points at the page the
+-- notification is about.
+CREATE TABLE notifications (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    kind TEXT NOT NULL CHECK (kind IN ('bag_running_low', 'comment_received')),
+    title TEXT NOT NULL,
+    link TEXT,
+    read_at TEXT,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+);
+
+CREATE INDEX idx_notifications_user_id ON notifications(user_id, read_at);
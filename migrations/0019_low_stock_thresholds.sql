@@ -0,0 +1,10 @@
+-- Instance-wide settings, one row per key. Missing keys use the defaults
+-- defined in the domain layer.
+CREATE TABLE settings (
+    key TEXT PRIMARY KEY,
+    value TEXT NOT NULL,
+    updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+);
+
+-- Per-bag override of the instance-wide low-stock threshold, in grams.
+ALTER TABLE bags ADD COLUMN low_stock_threshold REAL;
@@ -0,0 +1,14 @@
+-- Manual corrections to a bag's remaining weight. `delta` is the change that
+-- was applied and `remaining` the weight left afterwards, so the history
+-- reads on its own even after later brews.
+CREATE TABLE bag_adjustments (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    bag_id INTEGER NOT NULL REFERENCES bags(id) ON DELETE CASCADE,
+    delta REAL NOT NULL,
+    remaining REAL NOT NULL,
+    reason TEXT NOT NULL,
+    created_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+);
+
+CREATE INDEX idx_bag_adjustments_bag_id ON bag_adjustments(bag_id);
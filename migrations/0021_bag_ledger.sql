@@ -0,0 +1,87 @@
+-- Bag weights become an append-only ledger. Every change to what is left in
+-- a bag is an entry: the opening amount, each brew, manual corrections, and
+-- transfers between bags. `bags.remaining` is the running balance, kept in
+-- step by triggers and never written directly.
+CREATE TABLE bag_ledger (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    bag_id INTEGER NOT NULL REFERENCES bags(id) ON DELETE CASCADE,
+    kind TEXT NOT NULL CHECK (kind IN ('initial', 'brew', 'adjustment', 'transfer')),
+    delta REAL NOT NULL,
+    -- Not a foreign key: the coffee was used even if the brew is deleted later.
+    brew_id INTEGER,
+    -- The other side of a transfer.
+    counterpart_bag_id INTEGER REFERENCES bags(id) ON DELETE SET NULL,
+    reason TEXT,
+    created_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+);
+
+CREATE INDEX idx_bag_ledger_bag_id ON bag_ledger(bag_id);
+
+-- Backfill each bag's history: its opening amount, then brews and recorded
+-- corrections in the order they happened.
+INSERT INTO bag_ledger (bag_id, kind, delta, brew_id, reason, created_by, created_at)
+SELECT bag_id, kind, delta, brew_id, reason, created_by, created_at
+FROM (
+    SELECT id AS bag_id, 'initial' AS kind, amount AS delta, NULL AS brew_id,
+           NULL AS reason, created_by, created_at, 0 AS position
+    FROM bags
+    UNION ALL
+    SELECT bag_id, 'brew', -coffee_weight, id, NULL, created_by, created_at, 1
+    FROM brews
+    UNION ALL
+    SELECT bag_id, 'adjustment', delta, NULL, reason, created_by, created_at, 1
+    FROM bag_adjustments
+)
+ORDER BY bag_id, position, created_at;
+
+-- Brews used to be clamped at zero and the remaining weight could be edited
+-- freely, so carry any difference over as a correction.
+INSERT INTO bag_ledger (bag_id, kind, delta, reason, created_at)
+SELECT b.id, 'adjustment', b.remaining - l.total,
+       'Balance carried over from before the ledger', b.updated_at
+FROM bags b
+JOIN (SELECT bag_id, SUM(delta) AS total FROM bag_ledger GROUP BY bag_id) l
+    ON l.bag_id = b.id
+WHERE ABS(b.remaining - l.total) > 0.0001;
+
+UPDATE bags
+SET remaining = (SELECT SUM(delta) FROM bag_ledger WHERE bag_id = bags.id);
+
+DROP TABLE bag_adjustments;
+
+-- Every new bag opens with its full amount.
+CREATE TRIGGER bag_ledger_opening AFTER INSERT ON bags
+BEGIN
+    INSERT INTO bag_ledger (bag_id, kind, delta, created_by, created_at)
+    VALUES (NEW.id, 'initial', NEW.amount, NEW.created_by, NEW.created_at);
+END;
+
+-- Keep the balance on the bag, bumping `updated_at` for anything but the
+-- opening entry.
+CREATE TRIGGER bag_ledger_balance AFTER INSERT ON bag_ledger
+BEGIN
+    UPDATE bags
+    SET remaining = (SELECT SUM(delta) FROM bag_ledger WHERE bag_id = NEW.bag_id),
+        updated_at = CASE WHEN NEW.kind = 'initial' THEN updated_at ELSE CURRENT_TIMESTAMP END
+    WHERE id = NEW.bag_id;
+END;
+
+CREATE TRIGGER bags_remaining_from_ledger BEFORE UPDATE OF remaining ON bags
+WHEN NEW.remaining IS NOT (SELECT SUM(delta) FROM bag_ledger WHERE bag_id = NEW.id)
+BEGIN
+    SELECT RAISE(ABORT, 'bag remaining is derived from bag_ledger');
+END;
+
+CREATE TRIGGER bag_ledger_no_update
+BEFORE UPDATE OF bag_id, kind, delta, brew_id, reason, created_at ON bag_ledger
+BEGIN
+    SELECT RAISE(ABORT, 'bag_ledger is append-only');
+END;
+
+-- Entries only go away with their bag.
+CREATE TRIGGER bag_ledger_no_delete BEFORE DELETE ON bag_ledger
+WHEN EXISTS (SELECT 1 FROM bags WHERE id = OLD.bag_id)
+BEGIN
+    SELECT RAISE(ABORT, 'bag_ledger is append-only');
+END;
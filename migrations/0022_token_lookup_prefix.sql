@@ -0,0 +1,5 @@
+-- Argon2 token hashes are salted, so they can't be found by exact match.
+-- Rows hashed that way keep the first characters of the raw token here to
+-- narrow the search. SHA-256 rows leave it NULL and are still found by hash.
+ALTER TABLE tokens ADD COLUMN lookup_prefix TEXT;
+CREATE INDEX idx_tokens_lookup_prefix ON tokens(lookup_prefix);
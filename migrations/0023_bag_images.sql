@@ -0,0 +1,22 @@
+-- Allow bags their own photo, separate from the roast artwork. SQLite cannot
+-- alter a CHECK constraint, so the table is rebuilt with 'bag' added to the
+-- allowed entity types.
+CREATE TABLE entity_images_new (
+    id INTEGER PRIMARY KEY,
+    entity_type TEXT NOT NULL CHECK (entity_type IN ('roaster', 'roast', 'bag', 'gear', 'cafe', 'brew', 'cup', 'user')),
+    entity_id INTEGER NOT NULL,
+    content_type TEXT NOT NULL,
+    image_data BLOB NOT NULL,
+    thumbnail_data BLOB NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    UNIQUE(entity_type, entity_id)
+);
+
+INSERT INTO entity_images_new (id, entity_type, entity_id, content_type, image_data, thumbnail_data, created_at)
+SELECT id, entity_type, entity_id, content_type, image_data, thumbnail_data, created_at
+FROM entity_images;
+
+DROP TABLE entity_images;
+ALTER TABLE entity_images_new RENAME TO entity_images;
+
+CREATE INDEX idx_entity_images_lookup ON entity_images (entity_type, entity_id);
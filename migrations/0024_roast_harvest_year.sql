@@ -0,0 +1,3 @@
+-- Roasters sell the same coffee again each harvest. Each harvest is its own
+-- roast row ("lot"), told apart by year and linked to the others by name.
+ALTER TABLE roasts ADD COLUMN harvest_year INTEGER;
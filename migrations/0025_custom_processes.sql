@@ -0,0 +1,8 @@
+-- User-defined processing methods offered alongside the built-in taxonomy.
+-- Roasts store processes by name, so a name identifies its process.
+CREATE TABLE custom_processes (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL UNIQUE COLLATE NOCASE,
+    family TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+);
@@ -0,0 +1,23 @@
+-- Cups can be drunk away from a cafe, e.g. a takeaway brewed from one of
+-- your own bags. SQLite cannot drop a NOT NULL constraint, so the table is
+-- rebuilt with cafe_id optional and an optional link to the bag.
+CREATE TABLE cups_new (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    roast_id INTEGER NOT NULL REFERENCES roasts(id) ON DELETE RESTRICT,
+    cafe_id INTEGER REFERENCES cafes(id) ON DELETE RESTRICT,
+    bag_id INTEGER REFERENCES bags(id) ON DELETE SET NULL,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    created_by INTEGER REFERENCES users(id) ON DELETE SET NULL
+);
+
+INSERT INTO cups_new (id, roast_id, cafe_id, created_at, updated_at, created_by)
+SELECT id, roast_id, cafe_id, created_at, updated_at, created_by
+FROM cups;
+
+DROP TABLE cups;
+ALTER TABLE cups_new RENAME TO cups;
+
+CREATE INDEX idx_cups_roast_id ON cups(roast_id);
+CREATE INDEX idx_cups_cafe_id ON cups(cafe_id);
+CREATE INDEX idx_cups_bag_id ON cups(bag_id);
@@ -0,0 +1,2 @@
+-- Water added after brewing, e.g. to dilute a concentrated brew.
+ALTER TABLE brews ADD COLUMN bypass_water_ml INTEGER;
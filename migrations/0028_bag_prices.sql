@@ -0,0 +1,2 @@
+-- What was paid for a bag, in whatever currency the buyer uses.
+ALTER TABLE bags ADD COLUMN price REAL;
@@ -0,0 +1,14 @@
+-- Results of the scheduled database maintenance: WAL checkpoint,
+-- `PRAGMA optimize` and an optional VACUUM. Only recent runs are kept.
+CREATE TABLE maintenance_runs (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    ran_at TEXT NOT NULL,
+    duration_ms INTEGER NOT NULL,
+    wal_frames INTEGER NOT NULL,
+    checkpointed_frames INTEGER NOT NULL,
+    checkpoint_busy BOOLEAN NOT NULL,
+    vacuumed BOOLEAN NOT NULL,
+    bytes_before INTEGER NOT NULL,
+    bytes_after INTEGER NOT NULL,
+    error TEXT
+);
@@ -0,0 +1,31 @@
+-- Green coffee bought for roasting at home, and each batch roasted from it.
+-- A lot's remaining stock is its amount less the weight in of its batches.
+CREATE TABLE green_coffees (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL,
+    origin TEXT,
+    process TEXT,
+    supplier TEXT,
+    amount REAL NOT NULL,
+    price REAL,
+    purchased_on TEXT,
+    notes TEXT,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+);
+
+CREATE TABLE roast_batches (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    green_coffee_id INTEGER NOT NULL REFERENCES green_coffees(id) ON DELETE CASCADE,
+    bag_id INTEGER REFERENCES bags(id) ON DELETE SET NULL,
+    roasted_at TEXT NOT NULL,
+    weight_in REAL NOT NULL,
+    weight_out REAL NOT NULL,
+    total_time INTEGER,
+    development_time INTEGER,
+    curve_notes TEXT,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+);
+CREATE INDEX idx_roast_batches_green_coffee_id ON roast_batches(green_coffee_id);
+CREATE INDEX idx_roast_batches_bag_id ON roast_batches(bag_id);
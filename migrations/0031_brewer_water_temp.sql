@@ -0,0 +1,2 @@
+-- Water temperature a brewer's brews start at, in Celsius.
+ALTER TABLE gear ADD COLUMN water_temp REAL;
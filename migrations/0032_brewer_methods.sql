@@ -0,0 +1,4 @@
+-- How a brewer brews and whether it takes a filter paper. Left NULL, both are
+-- recognised from the brewer's name where possible.
+ALTER TABLE gear ADD COLUMN brew_method TEXT CHECK (brew_method IN ('immersion', 'percolation'));
+ALTER TABLE gear ADD COLUMN uses_filter INTEGER;
@@ -0,0 +1,78 @@
+-- Coffee events attended: cuppings, festivals, tastings. Coffees tried are a
+-- JSON array of free text, since most won't be roasts in the library; the
+-- roasters and cafes met there are linked.
+CREATE TABLE events (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL,
+    venue TEXT,
+    event_date TEXT NOT NULL,
+    notes TEXT,
+    coffees_json TEXT,
+    created_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+);
+CREATE INDEX idx_events_event_date ON events(event_date);
+
+CREATE TABLE event_roasters (
+    event_id INTEGER NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+    roaster_id INTEGER NOT NULL REFERENCES roasters(id) ON DELETE CASCADE,
+    PRIMARY KEY (event_id, roaster_id)
+);
+CREATE INDEX idx_event_roasters_roaster_id ON event_roasters(roaster_id);
+
+CREATE TABLE event_cafes (
+    event_id INTEGER NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+    cafe_id INTEGER NOT NULL REFERENCES cafes(id) ON DELETE CASCADE,
+    PRIMARY KEY (event_id, cafe_id)
+);
+CREATE INDEX idx_event_cafes_cafe_id ON event_cafes(cafe_id);
+
+-- Events go on the timeline and can carry a photo. SQLite cannot alter a CHECK
+-- constraint, so both tables are rebuilt with 'event' added to the allowed
+-- entity types.
+CREATE TABLE timeline_events_new (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    entity_type TEXT NOT NULL CHECK (entity_type IN ('roaster', 'roast', 'bag', 'gear', 'brew', 'cafe', 'cup', 'event')),
+    entity_id INTEGER NOT NULL,
+    action TEXT NOT NULL,
+    occurred_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    title TEXT NOT NULL,
+    details_json TEXT,
+    tasting_notes_json TEXT,
+    slug TEXT,
+    roaster_slug TEXT,
+    brew_data_json TEXT,
+    created_by INTEGER REFERENCES users(id) ON DELETE SET NULL
+);
+
+INSERT INTO timeline_events_new (id, entity_type, entity_id, action, occurred_at, title, details_json, tasting_notes_json, slug, roaster_slug, brew_data_json, created_by)
+SELECT id, entity_type, entity_id, action, occurred_at, title, details_json, tasting_notes_json, slug, roaster_slug, brew_data_json, created_by
+FROM timeline_events;
+
+DROP TABLE timeline_events;
+ALTER TABLE timeline_events_new RENAME TO timeline_events;
+
+CREATE INDEX idx_timeline_events_entity ON timeline_events(entity_type, entity_id);
+CREATE INDEX idx_timeline_events_occurred_at ON timeline_events(occurred_at DESC);
+CREATE INDEX idx_timeline_events_created_by ON timeline_events(created_by);
+
+CREATE TABLE entity_images_new (
+    id INTEGER PRIMARY KEY,
+    entity_type TEXT NOT NULL CHECK (entity_type IN ('roaster', 'roast', 'bag', 'gear', 'cafe', 'brew', 'cup', 'user', 'event')),
+    entity_id INTEGER NOT NULL,
+    content_type TEXT NOT NULL,
+    image_data BLOB NOT NULL,
+    thumbnail_data BLOB NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    UNIQUE(entity_type, entity_id)
+);
+
+INSERT INTO entity_images_new (id, entity_type, entity_id, content_type, image_data, thumbnail_data, created_at)
+SELECT id, entity_type, entity_id, content_type, image_data, thumbnail_data, created_at
+FROM entity_images;
+
+DROP TABLE entity_images;
+ALTER TABLE entity_images_new RENAME TO entity_images;
+
+CREATE INDEX idx_entity_images_lookup ON entity_images (entity_type, entity_id);
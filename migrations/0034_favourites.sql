@@ -0,0 +1,5 @@
+-- Roasts, roasters and cafes can be starred as favourites, which pins them to
+-- the top of pickers and onto the home page.
+ALTER TABLE roasters ADD COLUMN favourite INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE roasts ADD COLUMN favourite INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE cafes ADD COLUMN favourite INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,6 @@
+-- Cups can be rated out of five stars.
+ALTER TABLE cups ADD COLUMN rating INTEGER CHECK (rating BETWEEN 1 AND 5);
+
+-- Average stars across a roast's rated brews and cups. Derived, and refreshed
+-- alongside the stats cache.
+ALTER TABLE roasts ADD COLUMN rating REAL;
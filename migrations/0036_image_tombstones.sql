@@ -0,0 +1,6 @@
+-- Deleting an image only marks it; a background task removes marked rows in
+-- batches so deletes don't wait on rewriting large blobs.
+ALTER TABLE entity_images ADD COLUMN deleted_at TEXT;
+
+CREATE INDEX idx_entity_images_deleted ON entity_images (deleted_at)
+    WHERE deleted_at IS NOT NULL;
@@ -0,0 +1,3 @@
+-- Blends list each origin with its share of the beans, as a JSON array of
+-- {"origin", "percentage"} objects. Single-origin roasts leave it NULL.
+ALTER TABLE roasts ADD COLUMN components TEXT;
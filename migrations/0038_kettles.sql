@@ -0,0 +1,45 @@
+-- no-transaction
+-- Kettles join the gear, with how far the water reaching the coffee strays
+-- from the temperature they're set to and how hard the water they're filled
+-- with is. SQLite cannot alter a CHECK constraint, so the gear table is
+-- rebuilt; foreign keys are switched off meanwhile so that dropping the old
+-- table doesn't cascade into the brews and calibrations that point at it,
+-- which can't happen inside a transaction.
+PRAGMA foreign_keys = OFF;
+
+BEGIN;
+
+CREATE TABLE gear_new (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    category TEXT NOT NULL CHECK (category IN ('grinder', 'brewer', 'filter_paper', 'kettle')),
+    make TEXT NOT NULL,
+    model TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    created_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    water_temp REAL,
+    brew_method TEXT CHECK (brew_method IN ('immersion', 'percolation')),
+    uses_filter INTEGER,
+    temp_offset REAL,
+    water_hardness INTEGER
+);
+
+INSERT INTO gear_new (id, category, make, model, created_at, updated_at, created_by, water_temp, brew_method, uses_filter)
+SELECT id, category, make, model, created_at, updated_at, created_by, water_temp, brew_method, uses_filter
+FROM gear;
+
+DROP TABLE gear;
+ALTER TABLE gear_new RENAME TO gear;
+
+CREATE INDEX idx_gear_category ON gear(category);
+
+-- The temperature a brew is recorded at is what the kettle was set to; the
+-- actual temperature is what reached the coffee, measured or worked out from
+-- the kettle's offset.
+ALTER TABLE brews ADD COLUMN kettle_id INTEGER REFERENCES gear(id) ON DELETE SET NULL;
+ALTER TABLE brews ADD COLUMN actual_water_temp REAL;
+CREATE INDEX idx_brews_kettle_id ON brews(kettle_id);
+
+COMMIT;
+
+PRAGMA foreign_keys = ON;
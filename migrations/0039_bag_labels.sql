@@ -0,0 +1,4 @@
+-- Details printed on a bag's label: the date to drink it by and the
+-- roaster's batch code, for tracing a bag back to its roast.
+ALTER TABLE bags ADD COLUMN best_before TEXT;
+ALTER TABLE bags ADD COLUMN batch_code TEXT;
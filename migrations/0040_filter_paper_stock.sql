@@ -0,0 +1,4 @@
+-- How many filter papers are left in the box, counted down as brews use
+-- them, and how few can be left before it's time to reorder.
+ALTER TABLE gear ADD COLUMN stock_count INTEGER;
+ALTER TABLE gear ADD COLUMN low_stock_threshold INTEGER;
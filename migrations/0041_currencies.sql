@@ -0,0 +1,9 @@
+-- The currency a bag's price was paid in; NULL means the base currency.
+ALTER TABLE bags ADD COLUMN currency TEXT;
+
+-- How much of the base currency one unit of each foreign currency buys.
+CREATE TABLE exchange_rates (
+    currency TEXT PRIMARY KEY,
+    rate REAL NOT NULL CHECK (rate > 0),
+    updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+);
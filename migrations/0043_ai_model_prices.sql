@@ -0,0 +1,9 @@
+-- What each AI model costs, in US dollars per million tokens. Usage of a
+-- priced model is costed from its tokens; other models keep the cost
+-- OpenRouter reports.
+CREATE TABLE ai_model_prices (
+    model TEXT PRIMARY KEY,
+    prompt_price REAL NOT NULL CHECK (prompt_price >= 0),
+    completion_price REAL NOT NULL CHECK (completion_price >= 0),
+    updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+);
@@ -0,0 +1,18 @@
+-- Other names roasters and roasts have gone by, so searches and scans still
+-- find them after a rebrand, rename or merge. Each alias belongs to exactly
+-- one of the two, and is removed along with it. Aliases are matched by slug.
+CREATE TABLE aliases (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    roaster_id INTEGER REFERENCES roasters(id) ON DELETE CASCADE,
+    roast_id INTEGER REFERENCES roasts(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    slug TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    CHECK ((roaster_id IS NULL) != (roast_id IS NULL))
+);
+
+CREATE UNIQUE INDEX idx_aliases_roaster_slug ON aliases(roaster_id, slug)
+    WHERE roaster_id IS NOT NULL;
+CREATE UNIQUE INDEX idx_aliases_roast_slug ON aliases(roast_id, slug)
+    WHERE roast_id IS NOT NULL;
+CREATE INDEX idx_aliases_slug ON aliases(slug);
@@ -0,0 +1,9 @@
+-- Sessions remember the device they were signed in on: a hash of its user
+-- agent, with version numbers dropped so updates don't sign anyone out, and
+-- a readable label for the session list. A session presented from a device
+-- with a different fingerprint is ended. Where and when each session was last
+-- used is kept so stale or unfamiliar ones can be spotted and revoked.
+ALTER TABLE sessions ADD COLUMN device_fingerprint TEXT;
+ALTER TABLE sessions ADD COLUMN device_label TEXT;
+ALTER TABLE sessions ADD COLUMN last_seen_ip TEXT;
+ALTER TABLE sessions ADD COLUMN last_seen_at TEXT;
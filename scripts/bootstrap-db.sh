@@ -60,7 +60,7 @@ fi
 ./target/debug/brewlog roast add \
   --roaster-id "$(./target/debug/brewlog roaster list | jq -r '.[] | select(.name=="Square Mile Coffee") | .id')" \
   --name "Red Brick Espresso" \
-  --origin "Ethiopia, Colombia" \
+  --blend "Ethiopia 60%, Colombia 40%" \
   --region "Multiple Origins" \
   --producer "Various" \
   --process "Washed, Natural" \
@@ -561,6 +561,7 @@ fi
 ./target/debug/brewlog cup add \
   --roast-id "$(./target/debug/brewlog roast list | jq -r '.[] | select(.name=="Red Brick Espresso") | .id')" \
   --cafe-id "$(./target/debug/brewlog cafe list | jq -r '.[] | select(.name=="Monmouth Coffee") | .id')" \
+  --rating 4 \
   --created-at "2025-08-12T11:30:00Z"
 
 ./target/debug/brewlog cup add \
@@ -572,11 +573,13 @@ fi
 ./target/debug/brewlog cup add \
   --roast-id "$(./target/debug/brewlog roast list | jq -r '.[] | select(.name=="Kochere") | .id')" \
   --cafe-id "$(./target/debug/brewlog cafe list | jq -r '.[] | select(.name=="Prufrock Coffee") | .id')" \
+  --rating 5 \
   --created-at "2025-08-20T14:45:00Z"
 
 ./target/debug/brewlog cup add \
   --roast-id "$(./target/debug/brewlog roast list | jq -r '.[] | select(.name=="La Laja") | .id')" \
   --cafe-id "$(./target/debug/brewlog cafe list | jq -r '.[] | select(.name=="Prufrock Coffee") | .id')" \
+  --rating 3 \
   --created-at "2025-08-20T15:30:00Z"
 
 ./target/debug/brewlog cup add \
@@ -588,6 +591,7 @@ fi
 ./target/debug/brewlog cup add \
   --roast-id "$(./target/debug/brewlog roast list | jq -r '.[] | select(.name=="Gatomboya") | .id')" \
   --cafe-id "$(./target/debug/brewlog cafe list | jq -r '.[] | select(.name=="The Barn") | .id')" \
+  --rating 4 \
   --created-at "2025-09-08T10:30:00Z"
 
 ./target/debug/brewlog cup add \
@@ -754,6 +758,8 @@ fi
   --roast-id "$(./target/debug/brewlog roast list | jq -r '.[] | select(.name=="Simbi") | .id')" \
   --roast-date "2026-01-18" \
   --amount 450 \
+  --best-before "2026-04-18" \
+  --batch-code "SIM-0118" \
   --created-at "2026-01-22T15:00:00Z"
 
 # ============================================================================
@@ -838,8 +844,19 @@ fi
   --category "filter_paper" \
   --make "Fellow" \
   --model "Stagg XF Filters" \
+  --stock-count 30 \
+  --low-stock-threshold 10 \
   --created-at "2025-10-12T16:15:00Z"
 
+# Kettles
+./target/debug/brewlog gear add \
+  --category "kettle" \
+  --make "Fellow" \
+  --model "Stagg EKG" \
+  --temp-offset -2.0 \
+  --water-hardness 80 \
+  --created-at "2025-08-05T09:00:00Z"
+
 # ============================================================================
 # Brews - Sample brews using open bags with realistic ratios (1:15 to 1:17)
 # ============================================================================
@@ -854,6 +871,7 @@ fi
   --filter-paper-id "$(./target/debug/brewlog gear list | jq -r '.[] | select(.model=="V60 Tabbed 02") | .id')" \
   --water-volume 250 \
   --water-temp 92.0 \
+  --kettle-id "$(./target/debug/brewlog gear list | jq -r '.[] | select(.model=="Stagg EKG") | .id')" \
   --quick-notes good \
   --brew-time 135 \
   --created-at "2025-11-18T08:30:00Z"
@@ -0,0 +1,47 @@
+#!/usr/bin/env bash
+set -euo pipefail
+
+# Load-test the hot read endpoints of a running server with oha.
+#
+# Usage: scripts/load-test.sh [base-url]
+#
+# The server should already be seeded, e.g. with scripts/bootstrap-db.sh.
+# Tune the run with these environment variables:
+#   DURATION     - how long to hit each endpoint (default: 15s)
+#   CONNECTIONS  - concurrent connections (default: 50)
+#   HTTP2        - set to "true" to use HTTP/2 (default: false)
+#   IMAGE_PATH   - an image URL to include, e.g. /api/v1/roaster/1/image
+
+BASE_URL="${1:-${BREWLOG_URL:-http://localhost:3000}}"
+DURATION="${DURATION:-15s}"
+CONNECTIONS="${CONNECTIONS:-50}"
+HTTP2="${HTTP2:-false}"
+
+if ! command -v oha >/dev/null 2>&1; then
+  echo "Error: oha is not installed. Run 'mise install' to fetch it."
+  exit 1
+fi
+
+paths=(
+  "/timeline"
+  "/data?type=roasters"
+  "/data?type=roasts"
+  "/data?type=brews"
+  "/api/v1/roasters"
+  "/api/v1/brews"
+)
+
+if [[ -n ${IMAGE_PATH:-} ]]; then
+  paths+=("$IMAGE_PATH" "${IMAGE_PATH%/image}/thumbnail")
+fi
+
+oha_args=(--no-tui -z "$DURATION" -c "$CONNECTIONS")
+if [[ $HTTP2 == "true" ]]; then
+  oha_args+=(--http2)
+fi
+
+for path in "${paths[@]}"; do
+  echo "==> GET ${path}"
+  oha "${oha_args[@]}" "${BASE_URL}${path}"
+  echo
+done
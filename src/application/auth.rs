@@ -1,13 +1,22 @@
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+
 use axum::{
-    extract::{FromRequestParts, Request},
-    http::{StatusCode, header, request::Parts},
+    extract::{ConnectInfo, FromRequestParts, Request, State},
+    http::{HeaderMap, StatusCode, header, request::Parts},
+    middleware::Next,
+    response::Response,
 };
 use tower_cookies::Cookies;
-use tracing::warn;
+use tracing::{info, warn};
 
 use crate::application::state::AppState;
+use crate::domain::ids::TokenId;
+use crate::domain::repositories::TokenRepository;
+use crate::domain::sessions::{SessionDevice, describe_user_agent, user_agent_family};
+use crate::domain::tokens::Token;
 use crate::domain::users::User;
-use crate::infrastructure::auth::hash_token;
+use crate::infrastructure::auth::{TokenHasher, hash_token, token_lookup_prefix};
 
 pub(crate) const SESSION_COOKIE_NAME: &str = "brewlog_session";
 
@@ -50,31 +59,29 @@ impl FromRequestParts<AppState> for AuthenticatedUser {
             .strip_prefix("Bearer ")
             .ok_or(StatusCode::UNAUTHORIZED)?;
 
-        // Hash the token to look it up in the database
-        let token_hash = hash_token(token);
-
-        // Look up the token
-        let token_record = state
-            .token_repo
-            .get_by_token_hash(&token_hash)
+        let token_record = find_bearer_token(state, token)
             .await
-            .map_err(|err| {
-                warn!(error = %err, "bearer token lookup failed");
-                StatusCode::UNAUTHORIZED
-            })?;
+            .ok_or(StatusCode::UNAUTHORIZED)?;
 
         // Check if token is revoked
         if token_record.is_revoked() {
             return Err(StatusCode::UNAUTHORIZED);
         }
 
-        // Update last used timestamp (fire and forget)
+        // Update last used timestamp and upgrade the stored hash if the
+        // configured scheme has changed (fire and forget)
         let token_repo = state.token_repo.clone();
+        let token_hasher = state.token_hasher.clone();
         let token_id = token_record.id;
+        let stored_hash = token_record.token_hash.clone();
+        let raw_token = token.to_string();
         tokio::spawn(async move {
             if let Err(err) = token_repo.update_last_used(token_id).await {
                 warn!(error = %err, %token_id, "failed to update token last_used");
             }
+            if token_hasher.needs_rehash(&stored_hash) {
+                rehash_token(&token_repo, &token_hasher, token_id, &raw_token).await;
+            }
         });
 
         // Get the user
@@ -91,8 +98,62 @@ impl FromRequestParts<AppState> for AuthenticatedUser {
     }
 }
 
+/// Find the token row for a raw bearer token. Salted hashes are found by
+/// lookup prefix and verified one by one; SHA-256 hashes by exact match.
+async fn find_bearer_token(state: &AppState, token: &str) -> Option<Token> {
+    match state
+        .token_repo
+        .list_by_lookup_prefix(token_lookup_prefix(token))
+        .await
+    {
+        Ok(candidates) => {
+            for candidate in candidates {
+                if state
+                    .token_hasher
+                    .verify(token, &candidate.token_hash)
+                    .await
+                {
+                    return Some(candidate);
+                }
+            }
+        }
+        Err(err) => warn!(error = %err, "bearer token prefix lookup failed"),
+    }
+
+    match state.token_repo.get_by_token_hash(&hash_token(token)).await {
+        Ok(record) => Some(record),
+        Err(err) => {
+            warn!(error = %err, "bearer token lookup failed");
+            None
+        }
+    }
+}
+
+/// Re-hash a token with the configured scheme after a successful login.
+async fn rehash_token(
+    token_repo: &Arc<dyn TokenRepository>,
+    token_hasher: &TokenHasher,
+    token_id: TokenId,
+    raw_token: &str,
+) {
+    let stored = match token_hasher.hash(raw_token).await {
+        Ok(stored) => stored,
+        Err(err) => {
+            warn!(error = %err, %token_id, "failed to rehash token");
+            return;
+        }
+    };
+    match token_repo
+        .update_hash(token_id, &stored.hash, stored.lookup_prefix.as_deref())
+        .await
+    {
+        Ok(()) => info!(%token_id, scheme = %token_hasher.scheme(), "token hash upgraded"),
+        Err(err) => warn!(error = %err, %token_id, "failed to store rehashed token"),
+    }
+}
+
 /// Authenticate via session cookie
-async fn authenticate_via_session(state: &AppState, cookies: &Cookies) -> Option<User> {
+pub(crate) async fn authenticate_via_session(state: &AppState, cookies: &Cookies) -> Option<User> {
     let cookie = cookies.get(SESSION_COOKIE_NAME)?;
     let session_token = cookie.value();
     let session_token_hash = hash_token(session_token);
@@ -110,7 +171,7 @@ async fn authenticate_via_session(state: &AppState, cookies: &Cookies) -> Option
         }
     };
 
-    if session.is_expired() {
+    if session.is_expired(state.clock.now()) {
         return None;
     }
 
@@ -124,6 +185,120 @@ async fn authenticate_via_session(state: &AppState, cookies: &Cookies) -> Option
     }
 }
 
+/// The device a request comes from, identified by its user agent.
+pub(crate) fn request_device(headers: &HeaderMap) -> SessionDevice {
+    let user_agent = headers
+        .get(header::USER_AGENT)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default();
+    SessionDevice {
+        fingerprint: hash_token(&user_agent_family(user_agent)),
+        label: describe_user_agent(user_agent),
+    }
+}
+
+/// The address a request comes from. Forwarding headers are only believed
+/// from a proxy on the same machine: a loopback peer, or a Unix socket.
+pub(crate) fn client_ip(headers: &HeaderMap, peer: Option<SocketAddr>) -> Option<String> {
+    let peer = peer.map(|addr| addr.ip());
+    if let Some(ip) = peer.filter(|ip| !ip.is_loopback()) {
+        return Some(ip.to_string());
+    }
+    let header_ip = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.split(',').next())
+            .and_then(|value| value.trim().parse::<IpAddr>().ok())
+    };
+    header_ip("x-forwarded-for")
+        .or_else(|| header_ip("x-real-ip"))
+        .or(peer)
+        .map(|ip| ip.to_string())
+}
+
+/// The [`client_ip`] of a request.
+pub(crate) struct ClientIp(pub Option<String>);
+
+impl<S: Send + Sync> FromRequestParts<S> for ClientIp {
+    type Rejection = std::convert::Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let peer = parts
+            .extensions
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(addr)| *addr);
+        Ok(Self(client_ip(&parts.headers, peer)))
+    }
+}
+
+/// Holds session cookies to the device they were issued to. A session used
+/// from a different device is ended before the request is handled, so it
+/// reaches the handler unauthenticated; otherwise it is bound to the device
+/// if it isn't yet, and where and when it was last seen is kept up to date.
+pub(crate) async fn enforce_session_device(
+    State(state): State<AppState>,
+    cookies: Cookies,
+    ClientIp(ip): ClientIp,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some(cookie) = cookies.get(SESSION_COOKIE_NAME) else {
+        return next.run(request).await;
+    };
+    let Ok(session) = state
+        .session_repo
+        .get_by_token_hash(&hash_token(cookie.value()))
+        .await
+    else {
+        return next.run(request).await;
+    };
+    let now = state.clock.now();
+    if session.is_expired(now) {
+        return next.run(request).await;
+    }
+
+    let device = request_device(request.headers());
+
+    if !session.matches_device(&device.fingerprint) {
+        warn!(
+            session_id = %session.id,
+            user_id = %session.user_id,
+            bound_to = session.device_label.as_deref().unwrap_or_default(),
+            used_from = %device.label,
+            ip = ip.as_deref().unwrap_or_default(),
+            "session used from a different device, ending it"
+        );
+        if let Err(err) = state.session_repo.delete(session.id).await {
+            warn!(error = %err, session_id = %session.id, "failed to end session");
+        }
+        return next.run(request).await;
+    }
+
+    // Bound before the request goes on, so a second device can't slip in
+    if session.device_fingerprint.is_none()
+        && let Err(err) = state
+            .session_repo
+            .bind_device(session.id, &device.fingerprint, &device.label)
+            .await
+    {
+        warn!(error = %err, session_id = %session.id, "failed to bind session to device");
+    }
+
+    if session.needs_touch(ip.as_deref(), now) {
+        // Fire and forget, as for bearer tokens
+        let session_repo = state.session_repo.clone();
+        let session_id = session.id;
+        tokio::spawn(async move {
+            if let Err(err) = session_repo.touch(session_id, ip.as_deref(), now).await {
+                warn!(error = %err, %session_id, "failed to update session last seen");
+            }
+        });
+    }
+
+    next.run(request).await
+}
+
 /// Helper to extract authenticated user from request extensions
 pub fn get_authenticated_user(request: &Request) -> Option<&User> {
     request
@@ -5,21 +5,37 @@ use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use tracing::error;
 
-use crate::domain::RepositoryError;
+use crate::domain::validation::{FieldError, ValidationErrors};
+use crate::domain::{ErrorCode, RepositoryError};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ErrorResponse {
+    /// What went wrong, for clients that need to react to a specific error.
+    #[serde(default)]
+    pub code: ErrorCode,
     pub message: String,
+    /// Each invalid field, for `422 Unprocessable Entity` responses.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<FieldError>,
 }
 
 impl ErrorResponse {
-    pub fn new<T: ToString>(message: T) -> Self {
+    pub fn new<T: ToString>(code: ErrorCode, message: T) -> Self {
         Self {
+            code,
             message: message.to_string(),
+            fields: Vec::new(),
         }
     }
 }
 
+/// How `/api/v2` sends an [`ErrorResponse`]: inside an `error` object, so
+/// success and error bodies can't be mistaken for each other.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ErrorEnvelope {
+    pub error: ErrorResponse,
+}
+
 pub struct ApiError(AppError);
 
 impl From<AppError> for ApiError {
@@ -30,10 +46,20 @@ impl From<AppError> for ApiError {
 
 impl IntoResponse for ApiError {
     fn into_response(self) -> Response {
+        let code = self.0.code();
         let (status, message) = match self.0 {
-            AppError::Validation(message) => (StatusCode::BAD_REQUEST, message),
-            AppError::Conflict(message) => (StatusCode::CONFLICT, message),
+            AppError::Invalid(errors) => {
+                let response = ErrorResponse {
+                    code,
+                    message: errors.to_string(),
+                    fields: errors.into_errors(),
+                };
+                return (StatusCode::UNPROCESSABLE_ENTITY, Json(response)).into_response();
+            }
+            AppError::Validation(_, message) => (StatusCode::BAD_REQUEST, message),
+            AppError::Conflict(_, message) => (StatusCode::CONFLICT, message),
             AppError::NotFound => (StatusCode::NOT_FOUND, "entity not found".to_string()),
+            AppError::Unavailable(_, message) => (StatusCode::SERVICE_UNAVAILABLE, message),
             AppError::Unexpected(message) => {
                 error!(error = %message, "unexpected application error");
                 (
@@ -43,15 +69,17 @@ impl IntoResponse for ApiError {
             }
         };
 
-        (status, Json(ErrorResponse::new(message))).into_response()
+        (status, Json(ErrorResponse::new(code, message))).into_response()
     }
 }
 
 pub fn map_app_error(err: AppError) -> StatusCode {
     match err {
-        AppError::Validation(_) => StatusCode::BAD_REQUEST,
-        AppError::Conflict(_) => StatusCode::CONFLICT,
+        AppError::Invalid(_) => StatusCode::UNPROCESSABLE_ENTITY,
+        AppError::Validation(..) => StatusCode::BAD_REQUEST,
+        AppError::Conflict(..) => StatusCode::CONFLICT,
         AppError::NotFound => StatusCode::NOT_FOUND,
+        AppError::Unavailable(..) => StatusCode::SERVICE_UNAVAILABLE,
         AppError::Unexpected(_) => StatusCode::INTERNAL_SERVER_ERROR,
     }
 }
@@ -60,30 +88,60 @@ pub type AppResult<T> = Result<T, AppError>;
 
 #[derive(Debug, Error)]
 pub enum AppError {
-    #[error("validation failed: {0}")]
-    Validation(String),
-    #[error("conflict: {0}")]
-    Conflict(String),
+    #[error("validation failed: {1}")]
+    Validation(ErrorCode, String),
+    /// Field-level failures from [`Validate`](crate::domain::validation::Validate).
+    #[error("invalid fields: {0}")]
+    Invalid(ValidationErrors),
+    #[error("conflict: {1}")]
+    Conflict(ErrorCode, String),
     #[error("entity not found")]
     NotFound,
+    /// A service the request depends on can't be reached right now.
+    #[error("unavailable: {1}")]
+    Unavailable(ErrorCode, String),
     #[error("unexpected error: {0}")]
     Unexpected(String),
 }
 
 impl AppError {
     pub fn validation<T: ToString>(msg: T) -> Self {
-        Self::Validation(msg.to_string())
+        Self::Validation(ErrorCode::BadRequest, msg.to_string())
+    }
+
+    /// A `400 Bad Request` with a more specific [`ErrorCode`] than
+    /// [`ErrorCode::BadRequest`].
+    pub fn validation_with<T: ToString>(code: ErrorCode, msg: T) -> Self {
+        Self::Validation(code, msg.to_string())
     }
 
     pub fn unexpected<T: ToString>(msg: T) -> Self {
         Self::Unexpected(msg.to_string())
     }
+
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            Self::Validation(code, _) | Self::Conflict(code, _) | Self::Unavailable(code, _) => {
+                *code
+            }
+            Self::Invalid(_) => ErrorCode::InvalidFields,
+            Self::NotFound => ErrorCode::NotFound,
+            Self::Unexpected(_) => ErrorCode::Unexpected,
+        }
+    }
 }
+
+impl From<ValidationErrors> for AppError {
+    fn from(value: ValidationErrors) -> Self {
+        Self::Invalid(value)
+    }
+}
+
 impl From<RepositoryError> for AppError {
     fn from(value: RepositoryError) -> Self {
         match value {
             RepositoryError::NotFound => Self::NotFound,
-            RepositoryError::Conflict(msg) => Self::Conflict(msg),
+            RepositoryError::Conflict(code, msg) => Self::Conflict(code, msg),
             RepositoryError::Unexpected(msg) => Self::Unexpected(msg),
         }
     }
@@ -4,7 +4,8 @@ pub mod routes;
 pub mod server;
 pub mod services;
 pub mod state;
+pub mod tenants;
 
 pub use routes::app_router;
-pub use server::{ServerConfig, serve};
-pub use state::{AppState, AppStateConfig};
+pub use server::{HttpConfig, ServerConfig, serve};
+pub use state::{AppState, AppStateConfig, RepositoryBackend};
@@ -1 +1,2 @@
+pub(crate) mod quick_actions;
 pub(crate) mod stats;
@@ -0,0 +1,37 @@
+use axum::Json;
+use axum::extract::{Query, State};
+use serde::Deserialize;
+
+use crate::application::auth::AuthenticatedUser;
+use crate::application::errors::{ApiError, AppError};
+use crate::application::state::AppState;
+use crate::domain::quick_actions::{DEFAULT_QUICK_ACTION_LIMIT, QuickAction};
+
+/// The most suggestions a single request can ask for.
+const MAX_LIMIT: usize = 10;
+
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct QuickActionsQuery {
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+/// GET /api/v1/quick-actions — shortcuts suggested from recent brews and
+/// check-ins at this time of day
+#[tracing::instrument(skip(state, _auth_user))]
+pub(crate) async fn list_quick_actions(
+    State(state): State<AppState>,
+    _auth_user: AuthenticatedUser,
+    Query(query): Query<QuickActionsQuery>,
+) -> Result<Json<Vec<QuickAction>>, ApiError> {
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_QUICK_ACTION_LIMIT)
+        .clamp(1, MAX_LIMIT);
+    let actions = state
+        .quick_action_service
+        .suggest(limit)
+        .await
+        .map_err(AppError::from)?;
+    Ok(Json(actions))
+}
@@ -1,11 +1,76 @@
 use axum::Json;
-use axum::extract::State;
+use axum::extract::{Query, State};
+use serde::Deserialize;
 
 use crate::application::auth::AuthenticatedUser;
 use crate::application::errors::{ApiError, AppError};
 use crate::application::services::stats::compute_all_stats;
 use crate::application::state::AppState;
-use crate::domain::stats::CachedStats;
+use crate::domain::listing::{ListRequest, SortKey};
+use crate::domain::stats::{
+    BrewingHabits, CachedStats, RoasterLeaderboardEntry, RoasterLeaderboardSortKey,
+    sort_leaderboard,
+};
+
+/// GET /api/v1/stats — the latest stats snapshot, computed live on a cache miss
+#[tracing::instrument(skip(state))]
+pub(crate) async fn get_stats(
+    State(state): State<AppState>,
+) -> Result<Json<CachedStats>, ApiError> {
+    Ok(Json(cached_or_computed(&state).await?))
+}
+
+/// GET /api/v1/stats/habits — brews per weekday and hour of the day
+#[tracing::instrument(skip(state))]
+pub(crate) async fn get_habits(
+    State(state): State<AppState>,
+) -> Result<Json<BrewingHabits>, ApiError> {
+    Ok(Json(cached_or_computed(&state).await?.habits))
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct LeaderboardQuery {
+    #[serde(default)]
+    pub sort: Option<String>,
+    #[serde(default)]
+    pub dir: Option<String>,
+}
+
+impl LeaderboardQuery {
+    /// The requested order, falling back to each key's default direction.
+    pub(crate) fn request(&self) -> ListRequest<RoasterLeaderboardSortKey> {
+        let key = self
+            .sort
+            .as_deref()
+            .and_then(RoasterLeaderboardSortKey::from_query)
+            .unwrap_or_else(RoasterLeaderboardSortKey::default);
+        let direction = self
+            .dir
+            .as_deref()
+            .and_then(|dir| dir.parse().ok())
+            .unwrap_or_else(|| key.default_direction());
+        ListRequest::show_all(key, direction)
+    }
+}
+
+/// GET /api/v1/stats/roasters — roasters ranked by coffee brewed
+#[tracing::instrument(skip(state))]
+pub(crate) async fn get_roaster_leaderboard(
+    State(state): State<AppState>,
+    Query(query): Query<LeaderboardQuery>,
+) -> Result<Json<Vec<RoasterLeaderboardEntry>>, ApiError> {
+    let request = query.request();
+    let mut entries = cached_or_computed(&state).await?.roaster_leaderboard;
+    sort_leaderboard(&mut entries, request.sort_key(), request.sort_direction());
+    Ok(Json(entries))
+}
+
+async fn cached_or_computed(state: &AppState) -> Result<CachedStats, AppError> {
+    if let Some(cached) = state.stats_repo.get_cached().await? {
+        return Ok(cached);
+    }
+    Ok(compute_all_stats(&*state.stats_repo, state.clock.now()).await?)
+}
 
 /// Force an immediate stats recomputation, bypassing the debounce timer.
 #[tracing::instrument(skip(state, _auth_user))]
@@ -13,7 +78,13 @@ pub(crate) async fn recompute_stats(
     State(state): State<AppState>,
     _auth_user: AuthenticatedUser,
 ) -> Result<Json<CachedStats>, ApiError> {
-    let cached = compute_all_stats(&*state.stats_repo)
+    state
+        .stats_repo
+        .refresh_roast_ratings()
+        .await
+        .map_err(AppError::from)?;
+
+    let cached = compute_all_stats(&*state.stats_repo, state.clock.now())
         .await
         .map_err(AppError::from)?;
 
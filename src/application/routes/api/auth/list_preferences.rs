@@ -0,0 +1,84 @@
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use serde::Deserialize;
+
+use crate::application::auth::AuthenticatedUser;
+use crate::application::errors::{ApiError, AppError};
+use crate::application::state::AppState;
+use crate::domain::list_preferences::{ListName, ListPreference, NewListPreference};
+use crate::domain::listing::SortDirection;
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateListPreferenceRequest {
+    /// Default page size; `0` shows all items.
+    #[serde(default)]
+    pub page_size: Option<u32>,
+    #[serde(default)]
+    pub sort_key: Option<String>,
+    #[serde(default)]
+    pub sort_direction: Option<SortDirection>,
+}
+
+fn parse_list(list: &str) -> Result<ListName, ApiError> {
+    list.parse()
+        .map_err(|()| AppError::validation(format!("unknown list '{list}'")).into())
+}
+
+#[tracing::instrument(skip(state, auth_user))]
+pub(crate) async fn list_list_preferences(
+    State(state): State<AppState>,
+    auth_user: AuthenticatedUser,
+) -> Result<Json<Vec<ListPreference>>, ApiError> {
+    let preferences = state
+        .list_preference_repo
+        .list_by_user(auth_user.0.id)
+        .await
+        .map_err(AppError::from)?;
+
+    Ok(Json(preferences))
+}
+
+#[tracing::instrument(skip(state, auth_user, payload))]
+pub(crate) async fn update_list_preference(
+    State(state): State<AppState>,
+    auth_user: AuthenticatedUser,
+    Path(list): Path<String>,
+    Json(payload): Json<UpdateListPreferenceRequest>,
+) -> Result<Json<ListPreference>, ApiError> {
+    let list = parse_list(&list)?;
+
+    let new_preference = NewListPreference {
+        user_id: auth_user.0.id,
+        list,
+        page_size: payload.page_size,
+        sort_key: payload.sort_key.filter(|key| !key.trim().is_empty()),
+        sort_direction: payload.sort_direction,
+    };
+    new_preference.validate().map_err(AppError::validation)?;
+
+    let preference = state
+        .list_preference_repo
+        .upsert(new_preference)
+        .await
+        .map_err(AppError::from)?;
+
+    Ok(Json(preference))
+}
+
+#[tracing::instrument(skip(state, auth_user))]
+pub(crate) async fn delete_list_preference(
+    State(state): State<AppState>,
+    auth_user: AuthenticatedUser,
+    Path(list): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    let list = parse_list(&list)?;
+
+    state
+        .list_preference_repo
+        .delete(auth_user.0.id, list)
+        .await
+        .map_err(AppError::from)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -1,2 +1,7 @@
+pub(crate) mod list_preferences;
+pub(crate) mod notifications;
+pub(crate) mod profile;
+pub(crate) mod registration_tokens;
+pub(crate) mod saved_searches;
 pub(crate) mod tokens;
 pub(crate) mod webauthn;
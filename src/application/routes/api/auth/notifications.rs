@@ -0,0 +1,75 @@
+use axum::Json;
+use axum::extract::{Path, State};
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use crate::application::auth::AuthenticatedUser;
+use crate::application::errors::{ApiError, AppError};
+use crate::application::state::AppState;
+use crate::domain::ids::NotificationId;
+use crate::domain::notifications::{NOTIFICATION_LIST_LIMIT, Notification};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NotificationsResponse {
+    pub unread: u64,
+    /// The most recent notifications, newest first.
+    pub notifications: Vec<Notification>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MarkAllReadResponse {
+    pub updated: u64,
+}
+
+/// GET /api/v1/notifications — the signed-in user's notifications
+#[tracing::instrument(skip(state, auth_user))]
+pub(crate) async fn list_notifications(
+    State(state): State<AppState>,
+    auth_user: AuthenticatedUser,
+) -> Result<Json<NotificationsResponse>, ApiError> {
+    let user_id = auth_user.0.id;
+    let (unread, notifications) = tokio::try_join!(
+        state.notification_repo.count_unread(user_id),
+        state
+            .notification_repo
+            .list_by_user(user_id, NOTIFICATION_LIST_LIMIT),
+    )
+    .map_err(AppError::from)?;
+
+    Ok(Json(NotificationsResponse {
+        unread,
+        notifications,
+    }))
+}
+
+/// POST /api/v1/notifications/{id}/read — mark one notification read
+#[tracing::instrument(skip(state, auth_user))]
+pub(crate) async fn mark_notification_read(
+    State(state): State<AppState>,
+    auth_user: AuthenticatedUser,
+    Path(id): Path<NotificationId>,
+) -> Result<Json<Notification>, ApiError> {
+    let notification = state
+        .notification_repo
+        .mark_read(auth_user.0.id, id)
+        .await
+        .map_err(AppError::from)?;
+
+    Ok(Json(notification))
+}
+
+/// POST /api/v1/notifications/read-all — mark every notification read
+#[tracing::instrument(skip(state, auth_user))]
+pub(crate) async fn mark_all_notifications_read(
+    State(state): State<AppState>,
+    auth_user: AuthenticatedUser,
+) -> Result<Json<MarkAllReadResponse>, ApiError> {
+    let updated = state
+        .notification_repo
+        .mark_all_read(auth_user.0.id)
+        .await
+        .map_err(AppError::from)?;
+
+    info!(user_id = %auth_user.0.id, updated, "notifications marked read");
+    Ok(Json(MarkAllReadResponse { updated }))
+}
@@ -0,0 +1,66 @@
+use axum::Json;
+use axum::extract::State;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use crate::application::auth::AuthenticatedUser;
+use crate::application::errors::{ApiError, AppError};
+use crate::application::routes::api::images::resolve_image_url;
+use crate::application::state::AppState;
+use crate::domain::entity_type::EntityType;
+use crate::domain::ids::UserId;
+use crate::domain::users::{UpdateProfile, User};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProfileResponse {
+    pub id: UserId,
+    pub username: String,
+    pub display_name: Option<String>,
+    /// The display name if set, otherwise the username.
+    pub name: String,
+    pub avatar_url: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+async fn profile_response(state: &AppState, user: User) -> ProfileResponse {
+    let avatar_url = resolve_image_url(state, EntityType::User, i64::from(user.id)).await;
+    ProfileResponse {
+        id: user.id,
+        name: user.name().to_string(),
+        username: user.username,
+        display_name: user.display_name,
+        avatar_url,
+        created_at: user.created_at,
+    }
+}
+
+/// GET /api/v1/me — the signed-in user's profile (requires authentication)
+#[tracing::instrument(skip(state, auth_user))]
+pub(crate) async fn get_me(
+    State(state): State<AppState>,
+    auth_user: AuthenticatedUser,
+) -> Json<ProfileResponse> {
+    Json(profile_response(&state, auth_user.0).await)
+}
+
+/// PUT /api/v1/me — update the signed-in user's profile (requires authentication)
+#[tracing::instrument(skip(state, auth_user, payload))]
+pub(crate) async fn update_me(
+    State(state): State<AppState>,
+    auth_user: AuthenticatedUser,
+    Json(payload): Json<UpdateProfile>,
+) -> Result<Json<ProfileResponse>, ApiError> {
+    let display_name = payload
+        .normalized_display_name()
+        .map_err(AppError::validation)?;
+
+    let user = state
+        .user_repo
+        .update_display_name(auth_user.0.id, display_name)
+        .await
+        .map_err(AppError::from)?;
+
+    info!(user_id = %user.id, "profile updated");
+    Ok(Json(profile_response(&state, user).await))
+}
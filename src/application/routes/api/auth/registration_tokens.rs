@@ -0,0 +1,85 @@
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use crate::application::auth::AuthenticatedUser;
+use crate::application::errors::{ApiError, AppError};
+use crate::application::state::AppState;
+use crate::domain::ids::RegistrationTokenId;
+use crate::domain::registration_tokens::{CreateRegistrationToken, RegistrationToken};
+use crate::infrastructure::auth::{generate_session_token, hash_token};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateRegistrationTokenResponse {
+    pub token: RegistrationToken,
+    /// The invite link. Only the token's hash is stored, so this is the only
+    /// time it is available.
+    pub url: String,
+}
+
+/// POST /api/v1/registration-tokens — create an invite link (requires authentication)
+#[tracing::instrument(skip(state, _auth_user, payload))]
+pub(crate) async fn create_registration_token(
+    State(state): State<AppState>,
+    _auth_user: AuthenticatedUser,
+    payload: Option<Json<CreateRegistrationToken>>,
+) -> Result<Response, ApiError> {
+    let payload = payload.map(|Json(payload)| payload).unwrap_or_default();
+
+    let token = generate_session_token();
+    let new_token = payload
+        .into_new_token(hash_token(&token), state.clock.now())
+        .map_err(AppError::validation)?;
+
+    let created = state
+        .registration_token_repo
+        .insert(new_token)
+        .await
+        .map_err(AppError::from)?;
+
+    info!(
+        token_id = %created.id,
+        max_uses = created.max_uses,
+        "registration token created"
+    );
+    let response = CreateRegistrationTokenResponse {
+        token: created,
+        url: format!("{}/register/{token}", crate::base_url()),
+    };
+    Ok((StatusCode::CREATED, Json(response)).into_response())
+}
+
+/// GET /api/v1/registration-tokens — list invite links with usage (requires authentication)
+#[tracing::instrument(skip(state, _auth_user))]
+pub(crate) async fn list_registration_tokens(
+    State(state): State<AppState>,
+    _auth_user: AuthenticatedUser,
+) -> Result<Json<Vec<RegistrationToken>>, ApiError> {
+    let tokens = state
+        .registration_token_repo
+        .list()
+        .await
+        .map_err(AppError::from)?;
+
+    Ok(Json(tokens))
+}
+
+/// POST /api/v1/registration-tokens/{id}/revoke — revoke an invite link (requires authentication)
+#[tracing::instrument(skip(state, _auth_user))]
+pub(crate) async fn revoke_registration_token(
+    State(state): State<AppState>,
+    _auth_user: AuthenticatedUser,
+    Path(id): Path<RegistrationTokenId>,
+) -> Result<Json<RegistrationToken>, ApiError> {
+    let token = state
+        .registration_token_repo
+        .revoke(id)
+        .await
+        .map_err(AppError::from)?;
+
+    info!(token_id = %id, "registration token revoked");
+    Ok(Json(token))
+}
@@ -0,0 +1,135 @@
+use std::collections::BTreeMap;
+
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use serde::Deserialize;
+use tracing::info;
+
+use crate::application::auth::AuthenticatedUser;
+use crate::application::errors::{ApiError, AppError};
+use crate::application::state::AppState;
+use crate::domain::ids::{SavedSearchId, UserId};
+use crate::domain::listing::SortDirection;
+use crate::domain::saved_searches::{NewSavedSearch, SavedSearch};
+
+#[derive(Debug, Deserialize)]
+pub struct SavedSearchSubmission {
+    pub name: String,
+    /// List the search applies to, e.g. `bags`.
+    pub list: String,
+    #[serde(default)]
+    pub search: Option<String>,
+    #[serde(default)]
+    pub filters: BTreeMap<String, String>,
+    #[serde(default)]
+    pub sort_key: Option<String>,
+    #[serde(default)]
+    pub sort_direction: Option<SortDirection>,
+}
+
+impl SavedSearchSubmission {
+    fn into_new_saved_search(self, user_id: UserId) -> Result<NewSavedSearch, AppError> {
+        let list = self
+            .list
+            .parse()
+            .map_err(|()| AppError::validation(format!("unknown list '{}'", self.list)))?;
+
+        let new_search = NewSavedSearch {
+            user_id,
+            name: self.name,
+            list,
+            search: self.search,
+            filters: self.filters,
+            sort_key: self.sort_key,
+            sort_direction: self.sort_direction,
+        }
+        .normalize();
+        new_search.validate().map_err(AppError::validation)?;
+
+        Ok(new_search)
+    }
+}
+
+#[tracing::instrument(skip(state, auth_user))]
+pub(crate) async fn list_saved_searches(
+    State(state): State<AppState>,
+    auth_user: AuthenticatedUser,
+) -> Result<Json<Vec<SavedSearch>>, ApiError> {
+    let searches = state
+        .saved_search_repo
+        .list_by_user(auth_user.0.id)
+        .await
+        .map_err(AppError::from)?;
+
+    Ok(Json(searches))
+}
+
+#[tracing::instrument(skip(state, auth_user, payload))]
+pub(crate) async fn create_saved_search(
+    State(state): State<AppState>,
+    auth_user: AuthenticatedUser,
+    Json(payload): Json<SavedSearchSubmission>,
+) -> Result<Response, ApiError> {
+    let new_search = payload.into_new_saved_search(auth_user.0.id)?;
+
+    let search = state
+        .saved_search_repo
+        .insert(new_search)
+        .await
+        .map_err(AppError::from)?;
+
+    info!(saved_search_id = %search.id, list = %search.list, "saved search created");
+    Ok((StatusCode::CREATED, Json(search)).into_response())
+}
+
+#[tracing::instrument(skip(state, auth_user))]
+pub(crate) async fn get_saved_search(
+    State(state): State<AppState>,
+    auth_user: AuthenticatedUser,
+    Path(id): Path<SavedSearchId>,
+) -> Result<Json<SavedSearch>, ApiError> {
+    let search = state
+        .saved_search_repo
+        .get(auth_user.0.id, id)
+        .await
+        .map_err(AppError::from)?;
+
+    Ok(Json(search))
+}
+
+#[tracing::instrument(skip(state, auth_user, payload))]
+pub(crate) async fn update_saved_search(
+    State(state): State<AppState>,
+    auth_user: AuthenticatedUser,
+    Path(id): Path<SavedSearchId>,
+    Json(payload): Json<SavedSearchSubmission>,
+) -> Result<Json<SavedSearch>, ApiError> {
+    let new_search = payload.into_new_saved_search(auth_user.0.id)?;
+
+    let search = state
+        .saved_search_repo
+        .update(id, new_search)
+        .await
+        .map_err(AppError::from)?;
+
+    info!(saved_search_id = %search.id, "saved search updated");
+    Ok(Json(search))
+}
+
+#[tracing::instrument(skip(state, auth_user))]
+pub(crate) async fn delete_saved_search(
+    State(state): State<AppState>,
+    auth_user: AuthenticatedUser,
+    Path(id): Path<SavedSearchId>,
+) -> Result<StatusCode, ApiError> {
+    state
+        .saved_search_repo
+        .delete(auth_user.0.id, id)
+        .await
+        .map_err(AppError::from)?;
+
+    info!(saved_search_id = %id, "saved search deleted");
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -14,7 +14,7 @@ use crate::application::routes::support::{
 use crate::application::state::AppState;
 use crate::domain::ids::{TokenId, UserId};
 use crate::domain::tokens::{NewToken, Token};
-use crate::infrastructure::auth::{generate_token, hash_token};
+use crate::infrastructure::auth::generate_token;
 
 #[derive(Debug, Deserialize)]
 pub struct CreateTokenRequest {
@@ -64,9 +64,13 @@ pub async fn create_token(
         error!(error = %err, "failed to generate token");
         ApiError::from(AppError::unexpected("failed to generate token"))
     })?;
-    let token_hash_value = hash_token(&token_value);
+    let stored_hash = state.token_hasher.hash(&token_value).await.map_err(|err| {
+        error!(error = %err, "failed to hash token");
+        ApiError::from(AppError::unexpected("failed to hash token"))
+    })?;
 
-    let new_token = NewToken::new(auth_user.0.id, token_hash_value, payload.name.clone());
+    let new_token = NewToken::new(auth_user.0.id, stored_hash.hash, payload.name.clone())
+        .with_lookup_prefix(stored_hash.lookup_prefix);
 
     let stored_token = state.token_repo.insert(new_token).await.map_err(|err| {
         error!(error = %err, "failed to store token");
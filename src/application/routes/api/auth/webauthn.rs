@@ -1,7 +1,7 @@
 use axum::Json;
 use axum::extract::{Query, State};
-use axum::http::StatusCode;
-use chrono::{Duration, Utc};
+use axum::http::{HeaderMap, StatusCode};
+use chrono::Duration;
 use serde::{Deserialize, Serialize};
 use tower_cookies::{Cookie, Cookies};
 use tracing::{error, info, warn};
@@ -9,12 +9,13 @@ use uuid::Uuid;
 use webauthn_rs::prelude::*;
 use webauthn_rs_proto::ResidentKeyRequirement;
 
-use crate::application::auth::{AuthenticatedUser, SESSION_COOKIE_NAME};
+use crate::application::auth::{AuthenticatedUser, ClientIp, SESSION_COOKIE_NAME, request_device};
 use crate::application::state::AppState;
 use crate::domain::passkey_credentials::NewPasskeyCredential;
 use crate::domain::sessions::NewSession;
 use crate::domain::tokens::NewToken;
 use crate::domain::users::NewUser;
+use crate::domain::validation::Validate;
 use crate::infrastructure::auth::{generate_session_token, generate_token, hash_token};
 use crate::infrastructure::webauthn::CliCallbackInfo;
 
@@ -81,23 +82,35 @@ pub(crate) async fn register_start(
             StatusCode::UNAUTHORIZED
         })?;
 
-    if !reg_token.is_valid() {
+    if !reg_token.is_valid(state.clock.now()) {
         return Err(StatusCode::GONE);
     }
 
     // Create the user
     let user_uuid = Uuid::new_v4().to_string();
     let new_user = NewUser::new(payload.display_name, user_uuid.clone());
-    if let Err(msg) = new_user.validate() {
-        warn!(error = msg, "invalid username during registration");
+    if let Err(err) = new_user.validate() {
+        warn!(error = %err, "invalid username during registration");
         return Err(StatusCode::BAD_REQUEST);
     }
+
+    // Claim a use before creating the user so concurrent registrations
+    // cannot exceed the token's max uses.
+    state
+        .registration_token_repo
+        .claim_use(reg_token.id)
+        .await
+        .map_err(|err| {
+            warn!(error = %err, token_id = %reg_token.id, "registration token has no uses left");
+            StatusCode::GONE
+        })?;
+
     let user = state.user_repo.insert(new_user).await.map_err(|err| {
         error!(error = %err, "failed to create user during registration");
         StatusCode::INTERNAL_SERVER_ERROR
     })?;
 
-    // Mark registration token as used
+    // Record who used the registration token
     if let Err(err) = state
         .registration_token_repo
         .mark_used(reg_token.id, user.id)
@@ -142,10 +155,12 @@ pub(crate) async fn register_start(
 
 // --- Registration finish ---
 
-#[tracing::instrument(skip(state, cookies, payload))]
+#[tracing::instrument(skip(state, cookies, headers, payload))]
 pub(crate) async fn register_finish(
     State(state): State<AppState>,
     cookies: Cookies,
+    headers: HeaderMap,
+    ClientIp(ip): ClientIp,
     Json(payload): Json<RegisterFinishRequest>,
 ) -> Result<Json<AuthFinishResponse>, StatusCode> {
     // Retrieve ceremony state
@@ -182,7 +197,7 @@ pub(crate) async fn register_finish(
     info!(user_id = %user_id, "passkey registered successfully");
 
     // Create session for the new user
-    create_session(&state, &cookies, user_id).await;
+    create_session(&state, &cookies, user_id, &headers, ip).await;
 
     Ok(Json(AuthFinishResponse { redirect: None }))
 }
@@ -251,10 +266,12 @@ pub(crate) async fn auth_start(
 // --- Authentication finish ---
 
 #[allow(clippy::too_many_lines)]
-#[tracing::instrument(skip(state, cookies, payload))]
+#[tracing::instrument(skip(state, cookies, headers, payload))]
 pub(crate) async fn auth_finish(
     State(state): State<AppState>,
     cookies: Cookies,
+    headers: HeaderMap,
+    ClientIp(ip): ClientIp,
     Json(payload): Json<AuthFinishRequest>,
 ) -> Result<Json<AuthFinishResponse>, StatusCode> {
     // Retrieve ceremony state
@@ -337,8 +354,12 @@ pub(crate) async fn auth_finish(
             error!(error = %err, "failed to generate CLI bearer token");
             StatusCode::INTERNAL_SERVER_ERROR
         })?;
-        let token_hash_value = hash_token(&token_value);
-        let new_token = NewToken::new(user_id, token_hash_value, cli_info.token_name);
+        let stored_hash = state.token_hasher.hash(&token_value).await.map_err(|err| {
+            error!(error = %err, "failed to hash CLI bearer token");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+        let new_token = NewToken::new(user_id, stored_hash.hash, cli_info.token_name)
+            .with_lookup_prefix(stored_hash.lookup_prefix);
         state.token_repo.insert(new_token).await.map_err(|err| {
             error!(error = %err, "failed to store CLI bearer token");
             StatusCode::INTERNAL_SERVER_ERROR
@@ -356,7 +377,7 @@ pub(crate) async fn auth_finish(
     }
 
     // Normal web login: create session
-    create_session(&state, &cookies, user_id).await;
+    create_session(&state, &cookies, user_id, &headers, ip).await;
 
     info!(user_id = %user_id, "user authenticated via passkey");
 
@@ -512,10 +533,12 @@ pub(crate) async fn discoverable_auth_start(
     }))
 }
 
-#[tracing::instrument(skip(state, cookies, payload))]
+#[tracing::instrument(skip(state, cookies, headers, payload))]
 pub(crate) async fn discoverable_auth_finish(
     State(state): State<AppState>,
     cookies: Cookies,
+    headers: HeaderMap,
+    ClientIp(ip): ClientIp,
     Json(payload): Json<AuthFinishRequest>,
 ) -> Result<Json<AuthFinishResponse>, StatusCode> {
     let auth_state = state
@@ -608,7 +631,7 @@ pub(crate) async fn discoverable_auth_finish(
         }
     }
 
-    create_session(&state, &cookies, user.id).await;
+    create_session(&state, &cookies, user.id, &headers, ip).await;
 
     info!(user_id = %user.id, "user authenticated via discoverable passkey");
 
@@ -644,16 +667,20 @@ fn validate_cli_callback_url(url_str: &str) -> Result<(), ()> {
     }
 }
 
-async fn create_session(state: &AppState, cookies: &Cookies, user_id: crate::domain::ids::UserId) {
+/// Signs the user in on the device the request came from.
+async fn create_session(
+    state: &AppState,
+    cookies: &Cookies,
+    user_id: crate::domain::ids::UserId,
+    headers: &HeaderMap,
+    ip: Option<String>,
+) {
     let session_token = generate_session_token();
     let session_token_hash = hash_token(&session_token);
 
-    let new_session = NewSession::new(
-        user_id,
-        session_token_hash,
-        Utc::now(),
-        Utc::now() + Duration::days(30),
-    );
+    let now = state.clock.now();
+    let new_session = NewSession::new(user_id, session_token_hash, now, now + Duration::days(30))
+        .on_device(request_device(headers), ip);
 
     if let Err(err) = state.session_repo.insert(new_session).await {
         error!(error = %err, "failed to create session");
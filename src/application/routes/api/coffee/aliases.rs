@@ -0,0 +1,217 @@
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use tracing::{info, warn};
+
+use crate::application::auth::AuthenticatedUser;
+use crate::application::errors::{ApiError, AppError};
+use crate::application::state::AppState;
+use crate::domain::aliases::{Alias, AliasInput, AliasTarget, MergeInput, NewAlias};
+use crate::domain::entity_type::EntityType;
+use crate::domain::errors::RepositoryError;
+use crate::domain::ids::{AliasId, RoastId, RoasterId};
+use crate::domain::roasters::Roaster;
+use crate::domain::roasts::{Roast, RoastWithRoaster};
+
+/// The roaster with this slug, or failing that the one that used to go by a
+/// name with it.
+pub(crate) async fn find_roaster(state: &AppState, slug: &str) -> Result<Roaster, RepositoryError> {
+    match state.roaster_repo.get_by_slug(slug).await {
+        Err(RepositoryError::NotFound) => match state.alias_repo.find_roaster(slug).await? {
+            Some(id) => state.roaster_repo.get(id).await,
+            None => Err(RepositoryError::NotFound),
+        },
+        result => result,
+    }
+}
+
+/// The roast of `roaster_id` with this slug, or failing that the one that
+/// used to go by a name with it.
+pub(crate) async fn find_roast(
+    state: &AppState,
+    roaster_id: RoasterId,
+    slug: &str,
+) -> Result<Roast, RepositoryError> {
+    match state.roast_repo.get_by_slug(roaster_id, slug).await {
+        Err(RepositoryError::NotFound) => {
+            match state.alias_repo.find_roast(roaster_id, slug).await? {
+                Some(id) => state.roast_repo.get(id).await,
+                None => Err(RepositoryError::NotFound),
+            }
+        }
+        result => result,
+    }
+}
+
+async fn list_aliases(state: &AppState, target: AliasTarget) -> Result<Vec<Alias>, ApiError> {
+    match target {
+        AliasTarget::Roaster(id) => state.roaster_repo.get(id).await.map(|_| ()),
+        AliasTarget::Roast(id) => state.roast_repo.get(id).await.map(|_| ()),
+    }
+    .map_err(AppError::from)?;
+
+    Ok(state
+        .alias_repo
+        .list_for(target)
+        .await
+        .map_err(AppError::from)?)
+}
+
+async fn create_alias(
+    state: &AppState,
+    target: AliasTarget,
+    payload: &AliasInput,
+) -> Result<Response, ApiError> {
+    let name = payload.normalized_name().map_err(AppError::validation)?;
+
+    let alias = state
+        .alias_repo
+        .insert(NewAlias { target, name })
+        .await
+        .map_err(AppError::from)?;
+
+    info!(alias_id = %alias.id, name = %alias.name, "alias created");
+    Ok((StatusCode::CREATED, Json(alias)).into_response())
+}
+
+#[tracing::instrument(skip(state))]
+pub(crate) async fn list_roaster_aliases(
+    State(state): State<AppState>,
+    Path(roaster_id): Path<RoasterId>,
+) -> Result<Json<Vec<Alias>>, ApiError> {
+    list_aliases(&state, AliasTarget::Roaster(roaster_id))
+        .await
+        .map(Json)
+}
+
+#[tracing::instrument(skip(state, _auth_user, payload))]
+pub(crate) async fn create_roaster_alias(
+    State(state): State<AppState>,
+    _auth_user: AuthenticatedUser,
+    Path(roaster_id): Path<RoasterId>,
+    Json(payload): Json<AliasInput>,
+) -> Result<Response, ApiError> {
+    create_alias(&state, AliasTarget::Roaster(roaster_id), &payload).await
+}
+
+#[tracing::instrument(skip(state))]
+pub(crate) async fn list_roast_aliases(
+    State(state): State<AppState>,
+    Path(roast_id): Path<RoastId>,
+) -> Result<Json<Vec<Alias>>, ApiError> {
+    list_aliases(&state, AliasTarget::Roast(roast_id))
+        .await
+        .map(Json)
+}
+
+#[tracing::instrument(skip(state, _auth_user, payload))]
+pub(crate) async fn create_roast_alias(
+    State(state): State<AppState>,
+    _auth_user: AuthenticatedUser,
+    Path(roast_id): Path<RoastId>,
+    Json(payload): Json<AliasInput>,
+) -> Result<Response, ApiError> {
+    create_alias(&state, AliasTarget::Roast(roast_id), &payload).await
+}
+
+/// DELETE /api/v1/aliases/{id} — forget an alias
+#[tracing::instrument(skip(state, _auth_user))]
+pub(crate) async fn delete_alias(
+    State(state): State<AppState>,
+    _auth_user: AuthenticatedUser,
+    Path(id): Path<AliasId>,
+) -> Result<StatusCode, ApiError> {
+    state.alias_repo.delete(id).await.map_err(AppError::from)?;
+
+    info!(alias_id = %id, "alias deleted");
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Tidy up after `source` was merged into `into`: the merged record's photo
+/// and timeline events go, and `into`'s events are refreshed to pick up
+/// what moved across.
+async fn after_merge(state: &AppState, entity_type: EntityType, source: i64, into: i64) {
+    if let Err(err) = state.image_repo.delete(entity_type, source).await {
+        warn!(%source, error = %err, "failed to delete merged entity image");
+    }
+    if let Err(err) = state
+        .timeline_repo
+        .delete_by_entity(entity_type, source)
+        .await
+    {
+        warn!(%source, error = %err, "failed to delete merged timeline event");
+    }
+    if let Err(err) = state.timeline_repo.delete_orphaned().await {
+        warn!(%source, error = %err, "failed to prune orphaned timeline events");
+    }
+    state.timeline_invalidator.invalidate(entity_type, into);
+    state.stats_invalidator.invalidate();
+    // The merge rewrote rows in SQL, past the cached listings.
+    state.read_cache.invalidate_all();
+    state.image_purger.schedule();
+}
+
+/// POST /api/v1/roasters/{id}/merge — fold this roaster into another,
+/// keeping its name as an alias
+#[tracing::instrument(skip(state, _auth_user))]
+pub(crate) async fn merge_roaster(
+    State(state): State<AppState>,
+    _auth_user: AuthenticatedUser,
+    Path(id): Path<RoasterId>,
+    Json(payload): Json<MergeInput<RoasterId>>,
+) -> Result<Json<Roaster>, ApiError> {
+    state
+        .alias_repo
+        .merge_roasters(id, payload.into)
+        .await
+        .map_err(AppError::from)?;
+
+    info!(%id, into = %payload.into, "roaster merged");
+    after_merge(
+        &state,
+        EntityType::Roaster,
+        i64::from(id),
+        i64::from(payload.into),
+    )
+    .await;
+
+    let roaster = state
+        .roaster_repo
+        .get(payload.into)
+        .await
+        .map_err(AppError::from)?;
+    Ok(Json(roaster))
+}
+
+/// POST /api/v1/roasts/{id}/merge — fold this roast into another, keeping
+/// its name as an alias
+#[tracing::instrument(skip(state, _auth_user))]
+pub(crate) async fn merge_roast(
+    State(state): State<AppState>,
+    _auth_user: AuthenticatedUser,
+    Path(id): Path<RoastId>,
+    Json(payload): Json<MergeInput<RoastId>>,
+) -> Result<Json<RoastWithRoaster>, ApiError> {
+    state
+        .alias_repo
+        .merge_roasts(id, payload.into)
+        .await
+        .map_err(AppError::from)?;
+
+    info!(%id, into = %payload.into, "roast merged");
+    after_merge(
+        &state,
+        EntityType::Roast,
+        i64::from(id),
+        i64::from(payload.into),
+    )
+    .await;
+
+    let roast = state
+        .roast_repo
+        .get_with_roaster(payload.into)
+        .await
+        .map_err(AppError::from)?;
+    Ok(Json(roast))
+}
@@ -3,23 +3,32 @@ use axum::extract::{Path, Query, State};
 use axum::http::{HeaderMap, StatusCode};
 use axum::response::{IntoResponse, Redirect, Response};
 use chrono::{DateTime, Utc};
-use serde::Deserialize;
+use serde::{Deserialize, Deserializer};
 use tracing::info;
 
 use crate::application::auth::AuthenticatedUser;
 use crate::application::errors::{ApiError, AppError};
-use crate::application::routes::api::images::save_deferred_image;
+use crate::application::routes::api::images::{resolve_thumbnail_urls, save_deferred_image};
 use crate::application::routes::api::macros::{define_delete_handler, define_enriched_get_handler};
+use crate::application::routes::api::version::{ApiVersion, PageQuery};
 use crate::application::routes::support::{
     FlexiblePayload, ListQuery, PayloadSource, impl_has_changes, is_datastar_request,
     validate_update,
 };
 use crate::application::state::AppState;
+use crate::domain::ErrorCode;
+use crate::domain::bag_ledger::{
+    BagCorrection, BagLedgerEntry, BagTransfer, BagTransferRequest, NewBagAdjustment,
+};
 use crate::domain::bags::{BagFilter, BagSortKey, BagWithRoast, NewBag, UpdateBag};
+use crate::domain::brews::{BrewFilter, BrewSeriesPoint, BrewSortKey};
+use crate::domain::currencies::normalize_currency;
 use crate::domain::entity_type::EntityType;
+use crate::domain::formatting::format_weight;
 use crate::domain::ids::{BagId, RoastId};
 use crate::domain::images::ImageData;
 use crate::domain::listing::{ListRequest, SortDirection};
+use crate::domain::validation::Validate;
 use crate::presentation::web::templates::BagListTemplate;
 use crate::presentation::web::views::{BagView, ListNavigator, Paginated};
 
@@ -31,51 +40,92 @@ pub(crate) struct BagPageData {
     pub(crate) navigator: ListNavigator<BagSortKey>,
 }
 
-#[tracing::instrument(skip(state))]
 pub(crate) async fn load_bag_page(
     state: &AppState,
     request: ListRequest<BagSortKey>,
     search: Option<&str>,
 ) -> Result<BagPageData, AppError> {
+    load_filtered_bag_page(state, BagFilter::all(), request, search).await
+}
+
+#[tracing::instrument(skip(state))]
+pub(crate) async fn load_filtered_bag_page(
+    state: &AppState,
+    filter: BagFilter,
+    request: ListRequest<BagSortKey>,
+    search: Option<&str>,
+) -> Result<BagPageData, AppError> {
+    let status = filter
+        .closed
+        .map(|closed| if closed { "closed" } else { "open" });
+
     let page = state
         .bag_repo
-        .list(BagFilter::all(), &request, search)
+        .list(filter, &request, search)
         .await
         .map_err(AppError::from)?;
+    let settings = state.settings_repo.get().await.map_err(AppError::from)?;
+
+    let bag_ids: Vec<i64> = page.items.iter().map(|item| item.bag.id.into()).collect();
+    let roast_ids: Vec<i64> = page
+        .items
+        .iter()
+        .map(|item| item.bag.roast_id.into())
+        .collect();
+    let (mut bag_thumbnails, roast_thumbnails) = tokio::join!(
+        resolve_thumbnail_urls(state, EntityType::Bag, &bag_ids),
+        resolve_thumbnail_urls(state, EntityType::Roast, &roast_ids),
+    );
 
-    let (bags, navigator) = crate::application::routes::support::build_page_view(
+    let (bags, mut navigator) = crate::application::routes::support::build_page_view(
         page,
         request,
-        BagView::from,
+        |bag| {
+            let thumbnail = bag_thumbnails
+                .remove(&i64::from(bag.bag.id))
+                .or_else(|| roast_thumbnails.get(&i64::from(bag.bag.roast_id)).cloned());
+            BagView::new(bag, settings.low_stock_threshold).with_thumbnail(thumbnail)
+        },
         BAG_PAGE_PATH,
         BAG_FRAGMENT_PATH,
         search.map(String::from),
     );
+    if let Some(status) = status {
+        navigator = navigator.with_filter("status", status);
+    }
 
     Ok(BagPageData { bags, navigator })
 }
 
-#[tracing::instrument(skip(state, _auth_user, headers, query))]
+#[tracing::instrument(skip(state, auth_user, headers, query))]
 pub(crate) async fn create_bag(
     State(state): State<AppState>,
-    _auth_user: AuthenticatedUser,
+    auth_user: AuthenticatedUser,
     headers: HeaderMap,
     Query(query): Query<ListQuery>,
     payload: FlexiblePayload<NewBagSubmission>,
 ) -> Result<Response, ApiError> {
     let (request, search) = query.into_request_and_search::<BagSortKey>();
     let (submission, source) = payload.into_parts();
-    let new_bag = submission.into_new_bag().map_err(ApiError::from)?;
+    let (new_bag, image_data_url) = submission.into_parts().map_err(ApiError::from)?;
 
     let bag = state
         .bag_service
-        .create(new_bag)
+        .create(new_bag, Some(auth_user.0.id))
         .await
         .map_err(AppError::from)?;
 
     info!(bag_id = %bag.id, "bag created");
     state.stats_invalidator.invalidate();
 
+    save_deferred_image(
+        &state,
+        EntityType::Bag,
+        i64::from(bag.id),
+        image_data_url.as_deref(),
+    )
+    .await;
+
     let detail_url = format!("/bags/{}", bag.id);
 
     if is_datastar_request(&headers) {
@@ -107,23 +157,83 @@ pub(crate) async fn create_bag(
 #[tracing::instrument(skip(state))]
 pub(crate) async fn list_bags(
     State(state): State<AppState>,
+    version: ApiVersion,
     Query(params): Query<BagsQuery>,
-) -> Result<Json<Vec<BagWithRoast>>, ApiError> {
+    Query(paging): Query<PageQuery>,
+) -> Result<Response, ApiError> {
     let filter = match params.roast_id {
         Some(roast_id) => BagFilter::for_roast(roast_id),
         None => BagFilter::all(),
     };
-    let request = ListRequest::show_all(BagSortKey::RoastDate, SortDirection::Desc);
+    let request = version.list_request(&paging, BagSortKey::RoastDate, SortDirection::Desc);
     let page = state
         .bag_repo
         .list(filter, &request, None)
         .await
         .map_err(AppError::from)?;
-    Ok(Json(page.items))
+    Ok(version.list_response(page))
 }
 
 define_enriched_get_handler!(get_bag, BagId, BagWithRoast, bag_repo, get_with_roast);
 
+/// Deserializes an optional price, treating empty strings (from HTML forms) as None.
+fn deserialize_optional_price<'de, D>(deserializer: D) -> Result<Option<f64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value: Option<serde_json::Value> = Option::deserialize(deserializer)?;
+    match value {
+        None | Some(serde_json::Value::Null) => Ok(None),
+        Some(serde_json::Value::String(s)) if s.trim().is_empty() => Ok(None),
+        Some(serde_json::Value::Number(n)) => n
+            .as_f64()
+            .map(Some)
+            .ok_or_else(|| serde::de::Error::custom("invalid price")),
+        Some(serde_json::Value::String(s)) => s
+            .trim()
+            .parse::<f64>()
+            .map(Some)
+            .map_err(|_| serde::de::Error::custom("invalid price")),
+        Some(_) => Err(serde::de::Error::custom("invalid price")),
+    }
+}
+
+/// Deserializes an optional `YYYY-MM-DD` date, treating empty strings as None.
+pub(crate) fn deserialize_optional_date<'de, D>(
+    deserializer: D,
+) -> Result<Option<chrono::NaiveDate>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value: Option<String> = Option::deserialize(deserializer)?;
+    match value.as_deref().map(str::trim) {
+        None | Some("") => Ok(None),
+        Some(s) => chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d")
+            .map(Some)
+            .map_err(|_| serde::de::Error::custom(format!("invalid date: {s}"))),
+    }
+}
+
+/// Deserializes optional free text, trimmed, treating blank strings as None.
+pub(crate) fn deserialize_optional_text<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value: Option<String> = Option::deserialize(deserializer)?;
+    Ok(value
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty()))
+}
+
+/// Deserializes an optional currency code, upper-cased, treating blank
+/// strings as None.
+fn deserialize_optional_currency<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(deserialize_optional_text(deserializer)?.map(|code| normalize_currency(&code)))
+}
+
 #[derive(Debug, Deserialize)]
 pub(crate) struct UpdateBagSubmission {
     #[serde(default)]
@@ -144,6 +254,16 @@ pub(crate) struct UpdateBagSubmission {
     #[serde(default)]
     created_at: Option<DateTime<Utc>>,
     #[serde(default)]
+    low_stock_threshold: Option<f64>,
+    #[serde(default, deserialize_with = "deserialize_optional_price")]
+    price: Option<f64>,
+    #[serde(default, deserialize_with = "deserialize_optional_currency")]
+    currency: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_optional_date")]
+    best_before: Option<chrono::NaiveDate>,
+    #[serde(default, deserialize_with = "deserialize_optional_text")]
+    batch_code: Option<String>,
+    #[serde(default)]
     image: ImageData,
 }
 
@@ -157,6 +277,11 @@ impl UpdateBagSubmission {
             closed: self.closed,
             finished_at: self.finished_at,
             created_at: self.created_at,
+            low_stock_threshold: self.low_stock_threshold,
+            price: self.price,
+            currency: self.currency,
+            best_before: self.best_before,
+            batch_code: self.batch_code,
         };
         (update, self.image.into_inner())
     }
@@ -170,13 +295,18 @@ impl_has_changes!(
     remaining,
     closed,
     finished_at,
-    created_at
+    created_at,
+    low_stock_threshold,
+    price,
+    currency,
+    best_before,
+    batch_code
 );
 
-#[tracing::instrument(skip(state, _auth_user, headers, query))]
+#[tracing::instrument(skip(state, auth_user, headers, query))]
 pub(crate) async fn update_bag(
     State(state): State<AppState>,
-    _auth_user: AuthenticatedUser,
+    auth_user: AuthenticatedUser,
     headers: HeaderMap,
     Path(id): Path<BagId>,
     Query(query): Query<ListQuery>,
@@ -194,18 +324,50 @@ pub(crate) async fn update_bag(
         remaining: body_update.remaining.or(update_params.remaining),
         closed: body_update.closed.or(update_params.closed),
         finished_at: body_update.finished_at.or(update_params.finished_at),
-        created_at: body_update.created_at,
+        low_stock_threshold: body_update
+            .low_stock_threshold
+            .or(update_params.low_stock_threshold),
+        price: body_update.price.or(update_params.price),
+        currency: body_update.currency.or(update_params.currency),
+        // Creation dates and label details only come in the body.
+        ..body_update
     };
 
     validate_update(&update, image_data_url.as_ref())?;
+    update.validate().map_err(AppError::from)?;
 
-    // When the bag amount changes, recompute remaining based on how much has been consumed.
-    if let Some(new_amount) = update.amount
-        && update.remaining.is_none()
-    {
+    // The remaining weight lives in the bag's ledger, so record any change to
+    // it as a correction before applying the rest of the update.
+    let requested = update.remaining.take();
+    if requested.is_some() || update.amount.is_some() {
         let current = state.bag_repo.get(id).await.map_err(AppError::from)?;
-        let consumed = current.amount - current.remaining;
-        update.remaining = Some((new_amount - consumed).max(0.0));
+        let (remaining, reason) = match requested {
+            Some(remaining) if update.closed == Some(true) => {
+                (remaining, "Left over when the bag was finished".to_string())
+            }
+            Some(remaining) => (remaining, "Remaining weight edited".to_string()),
+            // Keep how much has been used when the bag amount changes.
+            None => {
+                let amount = update.amount.unwrap_or(current.amount);
+                (
+                    (amount - (current.amount - current.remaining)).max(0.0),
+                    format!("Bag amount changed to {}", format_weight(amount)),
+                )
+            }
+        };
+
+        if (remaining - current.remaining).abs() >= f64::EPSILON {
+            state
+                .bag_repo
+                .adjust(NewBagAdjustment {
+                    bag_id: id,
+                    remaining,
+                    reason,
+                    created_by: Some(auth_user.0.id),
+                })
+                .await
+                .map_err(AppError::from)?;
+        }
     }
 
     let bag = if let Some(true) = update.closed {
@@ -264,6 +426,133 @@ pub(crate) async fn update_bag(
     }
 }
 
+/// POST /api/v1/bags/{id}/reopen — undo finishing a bag
+#[tracing::instrument(skip(state, _auth_user))]
+pub(crate) async fn reopen_bag(
+    State(state): State<AppState>,
+    _auth_user: AuthenticatedUser,
+    Path(id): Path<BagId>,
+) -> Result<Json<BagWithRoast>, ApiError> {
+    state.bag_service.reopen(id).await.map_err(AppError::from)?;
+
+    info!(%id, "bag reopened");
+    state.stats_invalidator.invalidate();
+    state
+        .timeline_invalidator
+        .invalidate(EntityType::Bag, i64::from(id));
+
+    let enriched = state
+        .bag_repo
+        .get_with_roast(id)
+        .await
+        .map_err(AppError::from)?;
+    Ok(Json(enriched))
+}
+
+/// GET /api/v1/bags/{id}/ledger — every change to the bag's weight, newest first
+#[tracing::instrument(skip(state))]
+pub(crate) async fn list_bag_ledger(
+    State(state): State<AppState>,
+    Path(id): Path<BagId>,
+) -> Result<Json<Vec<BagLedgerEntry>>, ApiError> {
+    state.bag_repo.get(id).await.map_err(AppError::from)?;
+    let entries = state
+        .bag_repo
+        .list_ledger(id)
+        .await
+        .map_err(AppError::from)?;
+    Ok(Json(entries))
+}
+
+/// GET /api/v1/bags/{id}/brew-series — grind setting, ratio and rating for
+/// each brew from the bag, oldest first, to chart how it was dialed in
+#[tracing::instrument(skip(state))]
+pub(crate) async fn bag_brew_series(
+    State(state): State<AppState>,
+    Path(id): Path<BagId>,
+) -> Result<Json<Vec<BrewSeriesPoint>>, ApiError> {
+    state.bag_repo.get(id).await.map_err(AppError::from)?;
+    let request = ListRequest::show_all(BrewSortKey::CreatedAt, SortDirection::Asc);
+    let brews = state
+        .brew_repo
+        .list(BrewFilter::for_bag(id), &request, None)
+        .await
+        .map_err(AppError::from)?;
+    Ok(Json(
+        brews
+            .items
+            .iter()
+            .map(|brew| BrewSeriesPoint::from(&brew.brew))
+            .collect(),
+    ))
+}
+
+/// POST /api/v1/bags/{id}/adjustments — correct the remaining weight, keeping
+/// a record of the change and why it was made
+#[tracing::instrument(skip(state, auth_user, payload))]
+pub(crate) async fn create_bag_adjustment(
+    State(state): State<AppState>,
+    auth_user: AuthenticatedUser,
+    Path(id): Path<BagId>,
+    Json(payload): Json<BagCorrection>,
+) -> Result<Response, ApiError> {
+    let bag = state.bag_repo.get(id).await.map_err(AppError::from)?;
+    if bag.closed {
+        return Err(AppError::Conflict(
+            ErrorCode::BagClosed,
+            "reopen the bag before correcting it".to_string(),
+        )
+        .into());
+    }
+    let new_adjustment = payload
+        .into_adjustment(&bag, Some(auth_user.0.id))
+        .map_err(AppError::validation)?;
+
+    let adjustment = state
+        .bag_repo
+        .adjust(new_adjustment)
+        .await
+        .map_err(AppError::from)?;
+
+    info!(%id, delta = adjustment.delta, "bag remaining corrected");
+    state.stats_invalidator.invalidate();
+    Ok((StatusCode::CREATED, Json(adjustment)).into_response())
+}
+
+/// POST /api/v1/bags/{id}/transfers — move coffee from this bag into another
+#[tracing::instrument(skip(state, auth_user, payload))]
+pub(crate) async fn create_bag_transfer(
+    State(state): State<AppState>,
+    auth_user: AuthenticatedUser,
+    Path(id): Path<BagId>,
+    Json(payload): Json<BagTransferRequest>,
+) -> Result<Response, ApiError> {
+    let from = state.bag_repo.get(id).await.map_err(AppError::from)?;
+    let to = state
+        .bag_repo
+        .get(payload.to_bag_id)
+        .await
+        .map_err(|err| match err {
+            crate::domain::RepositoryError::NotFound => {
+                AppError::validation("the receiving bag does not exist")
+            }
+            other => AppError::from(other),
+        })?;
+    let new_transfer = payload
+        .into_transfer(&from, &to, Some(auth_user.0.id))
+        .map_err(|(code, message)| AppError::validation_with(code, message))?;
+
+    let transfer: BagTransfer = state
+        .bag_repo
+        .transfer(new_transfer)
+        .await
+        .map_err(AppError::from)?;
+
+    info!(from = %id, to = %to.id, amount = transfer.to.delta, "coffee transferred between bags");
+    state.stats_invalidator.invalidate();
+    Ok((StatusCode::CREATED, Json(transfer)).into_response())
+}
+
 define_delete_handler!(
     delete_bag,
     BagId,
@@ -272,7 +561,7 @@ define_delete_handler!(
     render_bag_list_fragment,
     "type=bags",
     "/data?type=bags",
-    entity_type: crate::domain::entity_type::EntityType::Bag
+    image_type: crate::domain::entity_type::EntityType::Bag
 );
 
 #[derive(Debug, Deserialize)]
@@ -285,17 +574,22 @@ pub(crate) struct NewBagSubmission {
     roast_id: RoastId,
     roast_date: Option<String>,
     amount: f64,
+    #[serde(default, deserialize_with = "deserialize_optional_price")]
+    price: Option<f64>,
+    #[serde(default, deserialize_with = "deserialize_optional_currency")]
+    currency: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_optional_date")]
+    best_before: Option<chrono::NaiveDate>,
+    #[serde(default, deserialize_with = "deserialize_optional_text")]
+    batch_code: Option<String>,
     #[serde(default)]
     created_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    image: ImageData,
 }
 
 impl NewBagSubmission {
-    fn into_new_bag(self) -> Result<NewBag, AppError> {
-        let roast_id = self.roast_id;
-        if roast_id.into_inner() <= 0 {
-            return Err(AppError::validation("invalid roast id"));
-        }
-
+    fn into_parts(self) -> Result<(NewBag, Option<String>), AppError> {
         let roast_date = match self.roast_date {
             Some(date_str) if !date_str.is_empty() => Some(
                 chrono::NaiveDate::parse_from_str(&date_str, "%Y-%m-%d")
@@ -304,16 +598,18 @@ impl NewBagSubmission {
             _ => None,
         };
 
-        if self.amount <= 0.0 {
-            return Err(AppError::validation("amount must be positive"));
-        }
-
-        Ok(NewBag {
-            roast_id,
+        let bag = NewBag {
+            roast_id: self.roast_id,
             roast_date,
             amount: self.amount,
+            price: self.price,
+            currency: self.currency,
+            best_before: self.best_before,
+            batch_code: self.batch_code,
             created_at: self.created_at,
-        })
+        };
+        bag.validate()?;
+        Ok((bag, self.image.into_inner()))
     }
 }
 
@@ -0,0 +1,180 @@
+use std::fmt::Write;
+
+use axum::Json;
+use axum::extract::State;
+use serde::Deserialize;
+use tracing::info;
+
+use crate::application::auth::AuthenticatedUser;
+use crate::application::errors::{ApiError, AppError};
+use crate::application::routes::support::record_ai_usage;
+use crate::application::state::AppState;
+use crate::domain::ErrorCode;
+use crate::domain::bags::{BagFilter, BagSortKey};
+use crate::domain::brew_parsing::{
+    BrewCandidates, BrewMeasurements, BrewText, MatchCandidate, ParsedBrew,
+};
+use crate::domain::gear::{Gear, GearCategory, GearFilter, GearSortKey};
+use crate::domain::ids::GearId;
+use crate::domain::listing::{ListRequest, SortDirection};
+use crate::infrastructure::ai::{self, ExtractedBrew, ExtractionInput};
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct ParseBrewRequest {
+    text: String,
+    /// Ask the AI to fill in whatever the rules couldn't.
+    #[serde(default)]
+    ai: bool,
+}
+
+/// Read a brew description into a draft brew, matching the coffee and gear
+/// it names against open bags and gear.
+#[tracing::instrument(skip(state, auth_user, request))]
+pub(crate) async fn parse_brew(
+    State(state): State<AppState>,
+    auth_user: AuthenticatedUser,
+    Json(request): Json<ParseBrewRequest>,
+) -> Result<Json<ParsedBrew>, ApiError> {
+    let description = request.text.trim();
+    if description.is_empty() {
+        return Err(AppError::validation("describe the brew to parse").into());
+    }
+
+    let candidates = load_candidates(&state).await?;
+    let mut text = BrewText::parse(description);
+    let mut parsed = candidates.resolve(&text);
+
+    if request.ai && !parsed.ambiguities.is_empty() {
+        let input = ExtractionInput {
+            image: None,
+            prompt: Some(ai_prompt(description, &candidates)),
+        };
+        match ai::extract_brew(&state.openrouter(), &input).await {
+            Ok((extracted, usage)) => {
+                record_ai_usage(
+                    state.ai_usage_repo.clone(),
+                    auth_user.0.id,
+                    &state.openrouter_model,
+                    "parse-brew",
+                    usage,
+                );
+                apply_suggestion(&mut text, &extracted);
+                parsed = candidates.resolve(&text);
+                parsed.used_ai = true;
+            }
+            // The rules-based draft still stands; the ambiguities go to the user.
+            Err(AppError::Unavailable(ErrorCode::AiUnavailable, _)) => {
+                parsed.ai_unavailable = true;
+            }
+            Err(err) => return Err(err.into()),
+        }
+    }
+
+    info!(
+        ambiguities = parsed.ambiguities.len(),
+        used_ai = parsed.used_ai,
+        ai_unavailable = parsed.ai_unavailable,
+        "brew description parsed"
+    );
+    Ok(Json(parsed))
+}
+
+async fn load_candidates(state: &AppState) -> Result<BrewCandidates, AppError> {
+    let bag_request = ListRequest::show_all(BagSortKey::RoastDate, SortDirection::Desc);
+    let gear_request = ListRequest::show_all(GearSortKey::Make, SortDirection::Asc);
+    let load_gear = |category| {
+        let gear_request = &gear_request;
+        async move {
+            state
+                .gear_repo
+                .list(GearFilter::for_category(category), gear_request, None)
+                .await
+                .map(|page| page.items)
+                .map_err(AppError::from)
+        }
+    };
+
+    let (bags, grinders, brewers, filter_papers) = tokio::try_join!(
+        async {
+            state
+                .bag_repo
+                .list(BagFilter::open(), &bag_request, None)
+                .await
+                .map_err(AppError::from)
+        },
+        load_gear(GearCategory::Grinder),
+        load_gear(GearCategory::Brewer),
+        load_gear(GearCategory::FilterPaper),
+    )?;
+
+    Ok(BrewCandidates {
+        bags: bags
+            .items
+            .iter()
+            .map(|bag| MatchCandidate {
+                id: bag.bag.id,
+                label: format!("{} - {}", bag.roaster_name, bag.roast_name),
+            })
+            .collect(),
+        grinders: grinders.iter().map(gear_candidate).collect(),
+        brewer_filters: brewers
+            .iter()
+            .filter_map(|gear| Some((gear.id, gear.brewer_profile().uses_filter?)))
+            .collect(),
+        brewers: brewers.iter().map(gear_candidate).collect(),
+        filter_papers: filter_papers.iter().map(gear_candidate).collect(),
+    })
+}
+
+fn gear_candidate(gear: &Gear) -> MatchCandidate<GearId> {
+    MatchCandidate {
+        id: gear.id,
+        label: format!("{} {}", gear.make, gear.model),
+    }
+}
+
+/// The description, followed by what it could be referring to so the model
+/// can name it exactly.
+fn ai_prompt(description: &str, candidates: &BrewCandidates) -> String {
+    let mut prompt = description.to_string();
+    append_list(&mut prompt, "Coffees", &candidates.bags);
+    append_list(&mut prompt, "Grinders", &candidates.grinders);
+    append_list(&mut prompt, "Brewers", &candidates.brewers);
+    append_list(&mut prompt, "Filter papers", &candidates.filter_papers);
+    prompt
+}
+
+fn append_list<Id>(prompt: &mut String, heading: &str, candidates: &[MatchCandidate<Id>]) {
+    if candidates.is_empty() {
+        return;
+    }
+    let _ = write!(prompt, "\n\n{heading}:");
+    for candidate in candidates {
+        let _ = write!(prompt, "\n- {}", candidate.label);
+    }
+}
+
+/// Take what the model found for anything the rules left out.
+fn apply_suggestion(text: &mut BrewText, extracted: &ExtractedBrew) {
+    #[allow(clippy::cast_possible_truncation)]
+    let suggested = BrewMeasurements {
+        coffee_weight: extracted.coffee_weight,
+        water_volume: extracted.water_volume.map(|ml| ml.round() as i32),
+        water_temp: extracted.water_temp,
+        grind_setting: extracted.grind_setting,
+        brew_time: extracted.brew_time.map(|secs| secs.round() as i32),
+    };
+    text.measurements.fill_from(&suggested);
+
+    for name in [
+        &extracted.bag,
+        &extracted.grinder,
+        &extracted.brewer,
+        &extracted.filter_paper,
+    ]
+    .into_iter()
+    .flatten()
+    {
+        text.mention(name);
+    }
+}
@@ -0,0 +1,91 @@
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use crate::application::auth::AuthenticatedUser;
+use crate::application::errors::{ApiError, AppError};
+use crate::application::state::AppState;
+use crate::domain::brew_shares::{BrewShare, CreateBrewShare, NewBrewShare};
+use crate::domain::ids::{BrewId, BrewShareId};
+use crate::infrastructure::auth::{generate_session_token, hash_token};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateBrewShareResponse {
+    pub share: BrewShare,
+    /// The public link. The token in it is not stored, so this is the only
+    /// time it is available.
+    pub url: String,
+}
+
+/// Public path for a share token.
+pub(crate) fn share_path(token: &str) -> String {
+    format!("/share/{token}")
+}
+
+#[tracing::instrument(skip(state, _auth_user, payload))]
+pub(crate) async fn create_brew_share(
+    State(state): State<AppState>,
+    _auth_user: AuthenticatedUser,
+    Path(brew_id): Path<BrewId>,
+    payload: Option<Json<CreateBrewShare>>,
+) -> Result<Response, ApiError> {
+    let payload = payload.map(|Json(payload)| payload).unwrap_or_default();
+    let expires_at = payload
+        .expires_at(state.clock.now())
+        .map_err(AppError::validation)?;
+
+    // Surface a missing brew as a 404 rather than a constraint failure.
+    state.brew_repo.get(brew_id).await.map_err(AppError::from)?;
+
+    let token = generate_session_token();
+    let share = state
+        .brew_share_repo
+        .insert(NewBrewShare {
+            brew_id,
+            token_hash: hash_token(&token),
+            expires_at,
+        })
+        .await
+        .map_err(AppError::from)?;
+
+    info!(brew_id = %brew_id, brew_share_id = %share.id, "brew share link created");
+    let response = CreateBrewShareResponse {
+        share,
+        url: format!("{}{}", crate::base_url(), share_path(&token)),
+    };
+    Ok((StatusCode::CREATED, Json(response)).into_response())
+}
+
+#[tracing::instrument(skip(state, _auth_user))]
+pub(crate) async fn list_brew_shares(
+    State(state): State<AppState>,
+    _auth_user: AuthenticatedUser,
+    Path(brew_id): Path<BrewId>,
+) -> Result<Json<Vec<BrewShare>>, ApiError> {
+    let shares = state
+        .brew_share_repo
+        .list_for_brew(brew_id)
+        .await
+        .map_err(AppError::from)?;
+
+    Ok(Json(shares))
+}
+
+#[tracing::instrument(skip(state, _auth_user))]
+pub(crate) async fn revoke_brew_share(
+    State(state): State<AppState>,
+    _auth_user: AuthenticatedUser,
+    Path(id): Path<BrewShareId>,
+) -> Result<Json<BrewShare>, ApiError> {
+    let share = state
+        .brew_share_repo
+        .revoke(id)
+        .await
+        .map_err(AppError::from)?;
+
+    info!(brew_share_id = %id, "brew share link revoked");
+    Ok(Json(share))
+}
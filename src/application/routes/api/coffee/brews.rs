@@ -3,27 +3,33 @@ use axum::extract::{Path, Query, State};
 use axum::http::{HeaderMap, StatusCode};
 use axum::response::{IntoResponse, Redirect, Response};
 use chrono::{DateTime, Utc};
-use serde::{Deserialize, Deserializer};
+use serde::{Deserialize, Deserializer, Serialize};
 use tracing::info;
 
 use crate::application::auth::AuthenticatedUser;
 use crate::application::errors::{ApiError, AppError};
+use crate::application::routes::api::coffee::gear::deserialize_optional_temp;
 use crate::application::routes::api::images::save_deferred_image;
 use crate::application::routes::api::macros::{define_delete_handler, define_enriched_get_handler};
+use crate::application::routes::api::version::{ApiVersion, PageQuery};
 use crate::application::routes::support::{
     FlexiblePayload, ListQuery, PayloadSource, impl_has_changes, is_datastar_request,
-    update_response, validate_update,
+    load_open_bag_options_by_usage, update_response, validate_update,
 };
+use crate::application::services::CreatedBrew;
 use crate::application::state::AppState;
-use crate::domain::bags::BagFilter;
+use crate::domain::RepositoryError;
+use crate::domain::bags::BagFinishSuggestion;
 use crate::domain::brews::{
-    BrewFilter, BrewSortKey, BrewWithDetails, NewBrew, QuickNote, UpdateBrew,
+    BrewFilter, BrewSortKey, BrewUsage, BrewWithDetails, NewBrew, QuickNote, UpdateBrew,
 };
 use crate::domain::entity_type::EntityType;
-use crate::domain::gear::{GearCategory, GearFilter, GearSortKey};
+use crate::domain::gear::{Gear, GearCategory, GearFilter, GearSortKey};
 use crate::domain::ids::{BagId, BrewId, GearId};
 use crate::domain::images::ImageData;
 use crate::domain::listing::{ListRequest, PageSize, SortDirection};
+use crate::domain::quick_notes::CustomQuickNote;
+use crate::domain::validation::Validate;
 use crate::presentation::web::templates::BrewListTemplate;
 use crate::presentation::web::views::{
     BagOptionView, BrewDefaultsView, BrewView, GearOptionView, ListNavigator, Paginated,
@@ -43,32 +49,24 @@ pub(crate) struct BrewFormData {
     pub(crate) grinder_options: Vec<GearOptionView>,
     pub(crate) brewer_options: Vec<GearOptionView>,
     pub(crate) filter_paper_options: Vec<GearOptionView>,
+    pub(crate) kettle_options: Vec<GearOptionView>,
     pub(crate) defaults: BrewDefaultsView,
     pub(crate) quick_note_options: Vec<QuickNoteView>,
 }
 
 pub(crate) async fn load_brew_form_data(state: &AppState) -> Result<BrewFormData, AppError> {
-    let open_bags_request = ListRequest::show_all(
-        crate::domain::bags::BagSortKey::RoastDate,
-        SortDirection::Desc,
-    );
-    let open_bags = state
-        .bag_repo
-        .list(BagFilter::open(), &open_bags_request, None)
-        .await
-        .map_err(AppError::from)?;
-    let bag_options: Vec<BagOptionView> = open_bags
-        .items
-        .into_iter()
-        .map(BagOptionView::from)
-        .collect();
+    // The likeliest picks lead: recently brewed bags and much-used gear.
+    let usage = state.brew_repo.usage().await.map_err(AppError::from)?;
+    let bag_options = load_open_bag_options_by_usage(state, &usage).await?;
 
     let gear_request = ListRequest::show_all(GearSortKey::Make, SortDirection::Asc);
+    let usage = Some(&usage);
 
-    let (grinder_options, brewer_options, filter_paper_options) = tokio::try_join!(
-        load_gear_options(state, GearCategory::Grinder, &gear_request),
-        load_gear_options(state, GearCategory::Brewer, &gear_request),
-        load_gear_options(state, GearCategory::FilterPaper, &gear_request),
+    let (grinder_options, brewer_options, filter_paper_options, kettle_options) = tokio::try_join!(
+        load_gear_options(state, GearCategory::Grinder, &gear_request, usage),
+        load_gear_options(state, GearCategory::Brewer, &gear_request, usage),
+        load_gear_options(state, GearCategory::FilterPaper, &gear_request, usage),
+        load_gear_options(state, GearCategory::Kettle, &gear_request, usage),
     )?;
 
     let last_brew_request = ListRequest::new(
@@ -89,10 +87,11 @@ pub(crate) async fn load_brew_form_data(state: &AppState) -> Result<BrewFormData
         .map(BrewDefaultsView::from)
         .unwrap_or_default();
 
+    let custom_quick_notes = state.quick_note_repo.list().await.map_err(AppError::from)?;
     let quick_note_options = QuickNote::all()
         .iter()
-        .copied()
         .map(QuickNoteView::from)
+        .chain(custom_quick_notes.iter().map(QuickNoteView::from))
         .collect();
 
     Ok(BrewFormData {
@@ -100,22 +99,30 @@ pub(crate) async fn load_brew_form_data(state: &AppState) -> Result<BrewFormData
         grinder_options,
         brewer_options,
         filter_paper_options,
+        kettle_options,
         defaults,
         quick_note_options,
     })
 }
 
+/// Gear of one category for a picker, in the request's order. Given the
+/// brew usage, the most used gear comes first instead.
 pub(crate) async fn load_gear_options(
     state: &AppState,
     category: GearCategory,
     request: &ListRequest<GearSortKey>,
+    usage: Option<&BrewUsage>,
 ) -> Result<Vec<GearOptionView>, AppError> {
-    let page = state
+    let mut gear = state
         .gear_repo
         .list(GearFilter::for_category(category), request, None)
         .await
-        .map_err(AppError::from)?;
-    Ok(page.items.into_iter().map(GearOptionView::from).collect())
+        .map_err(AppError::from)?
+        .items;
+    if let Some(usage) = usage {
+        usage.sort_gear(&mut gear, |item| item.id);
+    }
+    Ok(gear.into_iter().map(GearOptionView::from).collect())
 }
 
 #[tracing::instrument(skip(state))]
@@ -163,6 +170,29 @@ where
     }
 }
 
+/// Deserializes an optional millilitre amount, treating empty strings (from HTML forms) as None.
+fn deserialize_optional_ml<'de, D>(deserializer: D) -> Result<Option<i32>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value: Option<serde_json::Value> = Option::deserialize(deserializer)?;
+    match value {
+        None | Some(serde_json::Value::Null) => Ok(None),
+        Some(serde_json::Value::String(s)) if s.trim().is_empty() => Ok(None),
+        Some(serde_json::Value::Number(n)) => n
+            .as_i64()
+            .and_then(|ml| i32::try_from(ml).ok())
+            .map(Some)
+            .ok_or_else(|| serde::de::Error::custom("invalid water amount")),
+        Some(serde_json::Value::String(s)) => s
+            .trim()
+            .parse::<i32>()
+            .map(Some)
+            .map_err(|_| serde::de::Error::custom("invalid water amount")),
+        Some(_) => Err(serde::de::Error::custom("invalid water amount")),
+    }
+}
+
 fn deserialize_quick_notes<'de, D>(deserializer: D) -> Result<Vec<QuickNote>, D::Error>
 where
     D: Deserializer<'de>,
@@ -171,18 +201,77 @@ where
     match value {
         None | Some(serde_json::Value::Null) => Ok(Vec::new()),
         Some(serde_json::Value::String(s)) if s.is_empty() => Ok(Vec::new()),
-        Some(serde_json::Value::String(s)) => Ok(s
-            .split(',')
-            .filter_map(|v| QuickNote::from_str_value(v.trim()))
-            .collect()),
+        Some(serde_json::Value::String(s)) => {
+            Ok(s.split(',').filter_map(QuickNote::from_value).collect())
+        }
         Some(serde_json::Value::Array(arr)) => Ok(arr
             .iter()
-            .filter_map(|v| v.as_str().and_then(QuickNote::from_str_value))
+            .filter_map(|v| v.as_str().and_then(QuickNote::from_value))
             .collect()),
         Some(_) => Err(serde::de::Error::custom("invalid quick_notes")),
     }
 }
 
+/// Match custom notes to their definitions, adopting the defined label's
+/// casing, and drop duplicates. Notes that aren't defined are rejected.
+pub(crate) async fn resolve_quick_notes(
+    state: &AppState,
+    notes: Vec<QuickNote>,
+) -> Result<Vec<QuickNote>, AppError> {
+    let defined = if notes.iter().any(QuickNote::is_custom) {
+        state.quick_note_repo.list().await?
+    } else {
+        Vec::new()
+    };
+
+    let mut resolved = Vec::with_capacity(notes.len());
+    for note in notes {
+        let note = match note {
+            QuickNote::Custom(label) => defined
+                .iter()
+                .find(|d| d.label.eq_ignore_ascii_case(&label))
+                .map(CustomQuickNote::note)
+                .ok_or_else(|| AppError::validation(format!("unknown quick note \"{label}\"")))?,
+            built_in => built_in,
+        };
+        if !resolved.contains(&note) {
+            resolved.push(note);
+        }
+    }
+    Ok(resolved)
+}
+
+/// Fetch the brewer a brew was made with.
+pub(crate) async fn load_brewer(state: &AppState, brewer_id: GearId) -> Result<Gear, AppError> {
+    state
+        .gear_repo
+        .get(brewer_id)
+        .await
+        .map_err(|err| match err {
+            RepositoryError::NotFound => AppError::validation("the brewer does not exist"),
+            other => AppError::from(other),
+        })
+}
+
+/// Fetch the kettle a brew's water was heated in.
+pub(crate) async fn load_kettle(state: &AppState, kettle_id: GearId) -> Result<Gear, AppError> {
+    let kettle = state
+        .gear_repo
+        .get(kettle_id)
+        .await
+        .map_err(|err| match err {
+            RepositoryError::NotFound => AppError::validation("the kettle does not exist"),
+            other => AppError::from(other),
+        })?;
+    if kettle.category != GearCategory::Kettle {
+        return Err(AppError::validation(format!(
+            "{} {} is not a kettle",
+            kettle.make, kettle.model
+        )));
+    }
+    Ok(kettle)
+}
+
 #[derive(Debug, Deserialize)]
 pub(crate) struct NewBrewSubmission {
     bag_id: BagId,
@@ -194,6 +283,12 @@ pub(crate) struct NewBrewSubmission {
     filter_paper_id: Option<GearId>,
     water_volume: i32,
     water_temp: f64,
+    #[serde(default, deserialize_with = "deserialize_optional_ml")]
+    bypass_water_ml: Option<i32>,
+    #[serde(default, deserialize_with = "deserialize_optional_gear_id")]
+    kettle_id: Option<GearId>,
+    #[serde(default, deserialize_with = "deserialize_optional_temp")]
+    actual_water_temp: Option<f64>,
     #[serde(default, deserialize_with = "deserialize_quick_notes")]
     quick_notes: Vec<QuickNote>,
     #[serde(default)]
@@ -206,64 +301,69 @@ pub(crate) struct NewBrewSubmission {
 
 impl NewBrewSubmission {
     fn into_parts(self) -> Result<(NewBrew, Option<String>), AppError> {
-        if self.coffee_weight <= 0.0 {
-            return Err(AppError::validation("coffee weight must be positive"));
-        }
-        if self.grind_setting < 0.0 {
-            return Err(AppError::validation("grind setting must be non-negative"));
-        }
-        if self.water_volume <= 0 {
-            return Err(AppError::validation("water volume must be positive"));
-        }
-        if self.water_temp <= 0.0 || self.water_temp > 100.0 {
-            return Err(AppError::validation(
-                "water temperature must be between 0 and 100",
-            ));
-        }
-        if let Some(bt) = self.brew_time
-            && bt <= 0
-        {
-            return Err(AppError::validation("brew time must be positive"));
-        }
+        let brew = NewBrew {
+            bag_id: self.bag_id,
+            coffee_weight: self.coffee_weight,
+            grinder_id: self.grinder_id,
+            grind_setting: self.grind_setting,
+            brewer_id: self.brewer_id,
+            filter_paper_id: self.filter_paper_id,
+            water_volume: self.water_volume,
+            water_temp: self.water_temp,
+            bypass_water_ml: self.bypass_water_ml,
+            kettle_id: self.kettle_id,
+            actual_water_temp: self.actual_water_temp,
+            quick_notes: self.quick_notes,
+            brew_time: self.brew_time,
+            created_at: self.created_at,
+        };
+        brew.validate()?;
 
-        Ok((
-            NewBrew {
-                bag_id: self.bag_id,
-                coffee_weight: self.coffee_weight,
-                grinder_id: self.grinder_id,
-                grind_setting: self.grind_setting,
-                brewer_id: self.brewer_id,
-                filter_paper_id: self.filter_paper_id,
-                water_volume: self.water_volume,
-                water_temp: self.water_temp,
-                quick_notes: self.quick_notes,
-                brew_time: self.brew_time,
-                created_at: self.created_at,
-            },
-            self.image.into_inner(),
-        ))
+        Ok((brew, self.image.into_inner()))
     }
 }
 
-#[tracing::instrument(skip(state, _auth_user, headers, query))]
+#[tracing::instrument(skip(state, auth_user, headers, query))]
 pub(crate) async fn create_brew(
     State(state): State<AppState>,
-    _auth_user: AuthenticatedUser,
+    auth_user: AuthenticatedUser,
     headers: HeaderMap,
     Query(query): Query<ListQuery>,
     payload: FlexiblePayload<NewBrewSubmission>,
 ) -> Result<Response, ApiError> {
     let (request, search) = query.into_request_and_search::<BrewSortKey>();
     let (submission, source) = payload.into_parts();
-    let (new_brew, image_data_url) = submission.into_parts().map_err(ApiError::from)?;
+    let (mut new_brew, image_data_url) = submission.into_parts().map_err(ApiError::from)?;
+    let brewer = load_brewer(&state, new_brew.brewer_id)
+        .await
+        .map_err(ApiError::from)?;
+    new_brew
+        .validate_for_brewer(&brewer)
+        .map_err(AppError::from)?;
+    if let Some(kettle_id) = new_brew.kettle_id {
+        let kettle = load_kettle(&state, kettle_id)
+            .await
+            .map_err(ApiError::from)?;
+        new_brew.apply_kettle(&kettle);
+    }
+    new_brew.quick_notes = resolve_quick_notes(&state, new_brew.quick_notes)
+        .await
+        .map_err(ApiError::from)?;
 
-    let enriched = state
+    let CreatedBrew {
+        brew: enriched,
+        finish_suggestion,
+    } = state
         .brew_service
-        .create(new_brew)
+        .create(new_brew, Some(auth_user.0.id))
         .await
         .map_err(AppError::from)?;
 
-    info!(brew_id = %enriched.brew.id, "brew created");
+    info!(
+        brew_id = %enriched.brew.id,
+        finish_suggested = finish_suggestion.is_some(),
+        "brew created"
+    );
     state.stats_invalidator.invalidate();
 
     save_deferred_image(
@@ -274,7 +374,12 @@ pub(crate) async fn create_brew(
     )
     .await;
 
-    let detail_url = format!("/brews/{}", enriched.brew.id);
+    // The detail page asks whether to finish the bag when this is set.
+    let detail_url = if finish_suggestion.is_some() {
+        format!("/brews/{}?finish_bag=true", enriched.brew.id)
+    } else {
+        format!("/brews/{}", enriched.brew.id)
+    };
 
     if is_datastar_request(&headers) {
         // If the request came from a page that has #brew-list, return the updated fragment.
@@ -295,10 +400,24 @@ pub(crate) async fn create_brew(
     } else if matches!(source, PayloadSource::Form) {
         Ok(Redirect::to(&detail_url).into_response())
     } else {
-        Ok((StatusCode::CREATED, Json(enriched)).into_response())
+        let body = CreateBrewResponse {
+            brew: enriched,
+            bag_finish_suggestion: finish_suggestion,
+        };
+        Ok((StatusCode::CREATED, Json(body)).into_response())
     }
 }
 
+/// JSON body for a new brew: the brew itself, plus a hint when its bag is
+/// down to the dust and could be marked finished.
+#[derive(Debug, Serialize)]
+pub(crate) struct CreateBrewResponse {
+    #[serde(flatten)]
+    brew: BrewWithDetails,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bag_finish_suggestion: Option<BagFinishSuggestion>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct BrewsQuery {
     pub bag_id: Option<BagId>,
@@ -307,19 +426,21 @@ pub struct BrewsQuery {
 #[tracing::instrument(skip(state))]
 pub(crate) async fn list_brews(
     State(state): State<AppState>,
+    version: ApiVersion,
     Query(params): Query<BrewsQuery>,
-) -> Result<Json<Vec<BrewWithDetails>>, ApiError> {
+    Query(paging): Query<PageQuery>,
+) -> Result<Response, ApiError> {
     let filter = match params.bag_id {
         Some(bag_id) => BrewFilter::for_bag(bag_id),
         None => BrewFilter::all(),
     };
-    let request = ListRequest::show_all(BrewSortKey::CreatedAt, SortDirection::Desc);
+    let request = version.list_request(&paging, BrewSortKey::CreatedAt, SortDirection::Desc);
     let page = state
         .brew_repo
         .list(filter, &request, None)
         .await
         .map_err(AppError::from)?;
-    Ok(Json(page.items))
+    Ok(version.list_response(page))
 }
 
 define_enriched_get_handler!(
@@ -348,6 +469,12 @@ pub(crate) struct UpdateBrewSubmission {
     water_volume: Option<i32>,
     #[serde(default)]
     water_temp: Option<f64>,
+    #[serde(default, deserialize_with = "deserialize_optional_ml")]
+    bypass_water_ml: Option<i32>,
+    #[serde(default, deserialize_with = "deserialize_optional_gear_id")]
+    kettle_id: Option<GearId>,
+    #[serde(default, deserialize_with = "deserialize_optional_temp")]
+    actual_water_temp: Option<f64>,
     #[serde(default, deserialize_with = "deserialize_quick_notes")]
     quick_notes: Vec<QuickNote>,
     #[serde(default)]
@@ -369,6 +496,9 @@ impl UpdateBrewSubmission {
             filter_paper_id: self.filter_paper_id,
             water_volume: self.water_volume,
             water_temp: self.water_temp,
+            bypass_water_ml: self.bypass_water_ml,
+            kettle_id: self.kettle_id,
+            actual_water_temp: self.actual_water_temp,
             quick_notes: if self.quick_notes.is_empty() {
                 None
             } else {
@@ -391,6 +521,9 @@ impl_has_changes!(
     filter_paper_id,
     water_volume,
     water_temp,
+    bypass_water_ml,
+    kettle_id,
+    actual_water_temp,
     quick_notes,
     brew_time,
     created_at
@@ -405,9 +538,37 @@ pub(crate) async fn update_brew(
     payload: FlexiblePayload<UpdateBrewSubmission>,
 ) -> Result<Response, ApiError> {
     let (submission, source) = payload.into_parts();
-    let (update, image_data_url) = submission.into_parts();
+    let (mut update, image_data_url) = submission.into_parts();
 
     validate_update(&update, image_data_url.as_ref())?;
+    update.validate().map_err(AppError::from)?;
+    if update.changes_brewer_fit() || update.changes_kettle_temp() {
+        let existing = state.brew_repo.get(id).await.map_err(AppError::from)?;
+        if update.changes_brewer_fit() {
+            let brewer = load_brewer(&state, update.brewer_id.unwrap_or(existing.brewer_id))
+                .await
+                .map_err(ApiError::from)?;
+            update
+                .validate_for_brewer(&brewer, &existing)
+                .map_err(AppError::from)?;
+        }
+        // A new kettle or setting moves the actual temperature with it.
+        if update.changes_kettle_temp()
+            && let Some(kettle_id) = update.kettle_id.or(existing.kettle_id)
+        {
+            let kettle = load_kettle(&state, kettle_id)
+                .await
+                .map_err(ApiError::from)?;
+            update.apply_kettle(&kettle, &existing);
+        }
+    }
+    if let Some(notes) = update.quick_notes.take() {
+        update.quick_notes = Some(
+            resolve_quick_notes(&state, notes)
+                .await
+                .map_err(ApiError::from)?,
+        );
+    }
 
     state
         .brew_repo
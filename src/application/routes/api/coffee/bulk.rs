@@ -0,0 +1,115 @@
+use axum::Json;
+use axum::extract::State;
+use serde::Deserialize;
+use tracing::info;
+
+use crate::application::auth::AuthenticatedUser;
+use crate::application::errors::{ApiError, AppError};
+use crate::application::routes::api::brews::resolve_quick_notes;
+use crate::application::state::AppState;
+use crate::domain::RepositoryError;
+use crate::infrastructure::bulk::{BulkAction, BulkEntity, BulkReport, MAX_BULK_ITEMS};
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct BulkRequest {
+    ids: Vec<i64>,
+    #[serde(flatten)]
+    action: BulkAction,
+}
+
+/// Check the request, then apply it to every selected row. Rows that fail are
+/// reported alongside the ones that went through.
+async fn run_bulk(
+    state: &AppState,
+    entity: BulkEntity,
+    request: BulkRequest,
+) -> Result<BulkReport, AppError> {
+    let BulkRequest { mut ids, action } = request;
+
+    let mut seen = std::collections::HashSet::new();
+    ids.retain(|id| seen.insert(*id));
+    if ids.is_empty() {
+        return Err(AppError::validation("select at least one row"));
+    }
+    if ids.len() > MAX_BULK_ITEMS {
+        return Err(AppError::validation(format!(
+            "at most {MAX_BULK_ITEMS} rows can be changed at once"
+        )));
+    }
+    if !action.applies_to(entity) {
+        return Err(AppError::validation(format!(
+            "{} can't be applied to {}",
+            action.name(),
+            entity.entity_type().as_str()
+        )));
+    }
+
+    let action = match action {
+        BulkAction::Tag { note } => {
+            let note = resolve_quick_notes(state, vec![note])
+                .await?
+                .into_iter()
+                .next()
+                .ok_or_else(|| AppError::validation("a quick note is required"))?;
+            BulkAction::Tag { note }
+        }
+        BulkAction::AssignRoaster { roaster_id } => {
+            state
+                .roaster_repo
+                .get(roaster_id)
+                .await
+                .map_err(|err| match err {
+                    RepositoryError::NotFound => AppError::validation("the roaster does not exist"),
+                    other => AppError::from(other),
+                })?;
+            BulkAction::AssignRoaster { roaster_id }
+        }
+        other => other,
+    };
+
+    let report = state
+        .bulk_service
+        .apply(entity, &action, &ids, state.clock.now())
+        .await
+        .map_err(|e| AppError::unexpected(e.to_string()))?;
+
+    info!(
+        entity = entity.entity_type().as_str(),
+        action = action.name(),
+        succeeded = report.succeeded,
+        failed = report.failed,
+        "bulk action applied"
+    );
+    if report.succeeded > 0 {
+        state.stats_invalidator.invalidate();
+        state.timeline_invalidator.rebuild_all();
+        // Bulk changes are written straight to the database, past the cached
+        // roaster and gear listings as well as image presence.
+        state.read_cache.invalidate_all();
+        state.image_purger.schedule();
+    }
+    Ok(report)
+}
+
+/// Generates a `POST /api/v1/{list}/bulk` handler for one data list.
+macro_rules! define_bulk_handler {
+    ($fn_name:ident, $entity:expr) => {
+        #[tracing::instrument(skip(state, _auth_user, request))]
+        pub(crate) async fn $fn_name(
+            State(state): State<AppState>,
+            _auth_user: AuthenticatedUser,
+            Json(request): Json<BulkRequest>,
+        ) -> Result<Json<BulkReport>, ApiError> {
+            let report = run_bulk(&state, $entity, request).await?;
+            Ok(Json(report))
+        }
+    };
+}
+
+define_bulk_handler!(bulk_roasters, BulkEntity::Roasters);
+define_bulk_handler!(bulk_roasts, BulkEntity::Roasts);
+define_bulk_handler!(bulk_bags, BulkEntity::Bags);
+define_bulk_handler!(bulk_gear, BulkEntity::Gear);
+define_bulk_handler!(bulk_brews, BulkEntity::Brews);
+define_bulk_handler!(bulk_cafes, BulkEntity::Cafes);
+define_bulk_handler!(bulk_cups, BulkEntity::Cups);
@@ -6,14 +6,16 @@ use serde::Deserialize;
 
 use crate::application::auth::AuthenticatedUser;
 use crate::application::errors::{ApiError, AppError};
-use crate::application::routes::api::images::save_deferred_image;
+use crate::application::routes::api::images::{resolve_thumbnail_urls, save_deferred_image};
 use crate::application::routes::api::macros::{
-    define_delete_handler, define_get_handler, define_list_fragment_renderer,
+    define_delete_handler, define_delete_preview_handler, define_favourite_handler,
+    define_get_handler, define_list_fragment_renderer,
 };
+use crate::application::routes::api::version::{ApiVersion, PageQuery};
 use crate::application::routes::support::impl_has_changes;
 use crate::application::routes::support::{
-    FlexiblePayload, ListQuery, PayloadSource, is_datastar_request, render_redirect_script,
-    update_response, validate_update,
+    FlexiblePayload, FragmentQuery, ListQuery, PatchMode, PayloadSource, is_datastar_request,
+    new_row_mode, render_redirect_script, render_row_fragment, update_response, validate_update,
 };
 use crate::application::state::AppState;
 use crate::domain::cafes::{Cafe, CafeSortKey, NewCafe, UpdateCafe};
@@ -21,13 +23,15 @@ use crate::domain::entity_type::EntityType;
 use crate::domain::ids::CafeId;
 use crate::domain::images::ImageData;
 use crate::domain::listing::{ListRequest, SortDirection};
+use crate::domain::validation::Validate;
 use crate::infrastructure::foursquare;
-use crate::presentation::web::templates::{CafeListTemplate, NearbyCafesFragment};
+use crate::presentation::web::templates::{CafeListTemplate, CafeRowTemplate, NearbyCafesFragment};
 use crate::presentation::web::views::{CafeView, ListNavigator, NearbyCafeView, Paginated};
 use tracing::info;
 
 const CAFE_PAGE_PATH: &str = "/data?type=cafes";
 const CAFE_FRAGMENT_PATH: &str = "/data?type=cafes#cafe-list";
+const CAFE_ROWS_SELECTOR: &str = "#cafe-list tbody";
 
 #[tracing::instrument(skip(state))]
 pub(crate) async fn load_cafe_page(
@@ -40,11 +44,16 @@ pub(crate) async fn load_cafe_page(
         .list(&request, search)
         .await
         .map_err(AppError::from)?;
+    let ids: Vec<i64> = page.items.iter().map(|cafe| cafe.id.into()).collect();
+    let mut thumbnails = resolve_thumbnail_urls(state, EntityType::Cafe, &ids).await;
 
     Ok(crate::application::routes::support::build_page_view(
         page,
         request,
-        CafeView::from,
+        |cafe| {
+            let thumbnail = thumbnails.remove(&i64::from(cafe.id));
+            CafeView::from(cafe).with_thumbnail(thumbnail)
+        },
         CAFE_PAGE_PATH,
         CAFE_FRAGMENT_PATH,
         search.map(String::from),
@@ -52,13 +61,18 @@ pub(crate) async fn load_cafe_page(
 }
 
 #[tracing::instrument(skip(state))]
-pub(crate) async fn list_cafes(State(state): State<AppState>) -> Result<Json<Vec<Cafe>>, ApiError> {
-    let cafes = state
+pub(crate) async fn list_cafes(
+    State(state): State<AppState>,
+    version: ApiVersion,
+    Query(paging): Query<PageQuery>,
+) -> Result<Response, ApiError> {
+    let request = version.list_request(&paging, CafeSortKey::Name, SortDirection::Asc);
+    let page = state
         .cafe_repo
-        .list_all_sorted(CafeSortKey::Name, SortDirection::Asc)
+        .list(&request, None)
         .await
         .map_err(AppError::from)?;
-    Ok(Json(cafes))
+    Ok(version.list_response(page))
 }
 
 #[derive(Debug, Deserialize)]
@@ -91,21 +105,23 @@ impl NewCafeSubmission {
     }
 }
 
-#[tracing::instrument(skip(state, _auth_user, headers, query))]
+#[tracing::instrument(skip(state, auth_user, headers, query, fragment))]
 pub(crate) async fn create_cafe(
     State(state): State<AppState>,
-    _auth_user: AuthenticatedUser,
+    auth_user: AuthenticatedUser,
     headers: HeaderMap,
     Query(query): Query<ListQuery>,
+    Query(fragment): Query<FragmentQuery>,
     payload: FlexiblePayload<NewCafeSubmission>,
 ) -> Result<Response, ApiError> {
     let (request, search) = query.into_request_and_search::<CafeSortKey>();
     let (submission, source) = payload.into_parts();
     let (new_cafe, image_data_url) = submission.into_parts();
     let new_cafe = new_cafe.normalize();
+    new_cafe.validate().map_err(AppError::from)?;
     let cafe = state
         .cafe_service
-        .create(new_cafe)
+        .create(new_cafe, Some(auth_user.0.id))
         .await
         .map_err(AppError::from)?;
 
@@ -122,6 +138,20 @@ pub(crate) async fn create_cafe(
 
     let detail_url = format!("/cafes/{}", cafe.slug);
 
+    if fragment.wants_row(&headers, "type=cafes")
+        && let Some(mode) = new_row_mode(&request, search.as_deref())
+    {
+        let cafe_id = i64::from(cafe.id);
+        let thumbnail = resolve_thumbnail_urls(&state, EntityType::Cafe, &[cafe_id])
+            .await
+            .remove(&cafe_id);
+        let row = CafeRowTemplate {
+            is_authenticated: true,
+            cafe: CafeView::from(cafe).with_thumbnail(thumbnail),
+        };
+        return render_row_fragment(row, CAFE_ROWS_SELECTOR, mode).map_err(ApiError::from);
+    }
+
     if is_datastar_request(&headers) {
         let from_data_page = headers
             .get("referer")
@@ -183,12 +213,13 @@ impl_has_changes!(
     UpdateCafe, name, city, country, latitude, longitude, website, created_at
 );
 
-#[tracing::instrument(skip(state, _auth_user, headers))]
+#[tracing::instrument(skip(state, _auth_user, headers, fragment))]
 pub(crate) async fn update_cafe(
     State(state): State<AppState>,
     _auth_user: AuthenticatedUser,
     headers: HeaderMap,
     Path(id): Path<CafeId>,
+    Query(fragment): Query<FragmentQuery>,
     payload: FlexiblePayload<UpdateCafeSubmission>,
 ) -> Result<Response, ApiError> {
     let (submission, source) = payload.into_parts();
@@ -196,6 +227,7 @@ pub(crate) async fn update_cafe(
     let update = update.normalize();
 
     validate_update(&update, image_data_url.as_ref())?;
+    update.validate().map_err(AppError::from)?;
 
     let cafe = state
         .cafe_repo
@@ -216,6 +248,19 @@ pub(crate) async fn update_cafe(
     )
     .await;
 
+    if fragment.wants_row(&headers, "type=cafes") {
+        let selector = format!("#cafe-row-{}", cafe.id);
+        let cafe_id = i64::from(cafe.id);
+        let thumbnail = resolve_thumbnail_urls(&state, EntityType::Cafe, &[cafe_id])
+            .await
+            .remove(&cafe_id);
+        let row = CafeRowTemplate {
+            is_authenticated: true,
+            cafe: CafeView::from(cafe).with_thumbnail(thumbnail),
+        };
+        return render_row_fragment(row, &selector, PatchMode::Replace).map_err(ApiError::from);
+    }
+
     let detail_url = format!("/cafes/{}", cafe.slug);
     update_response(&headers, source, &detail_url, Json(cafe).into_response())
 }
@@ -231,6 +276,10 @@ define_delete_handler!(
     image_type: crate::domain::entity_type::EntityType::Cafe
 );
 
+define_delete_preview_handler!(cafe_delete_preview, CafeId, cafe_repo);
+
+define_favourite_handler!(favourite_cafe, CafeId, cafe_repo, "cafes");
+
 define_list_fragment_renderer!(
     render_cafe_list_fragment,
     CafeSortKey,
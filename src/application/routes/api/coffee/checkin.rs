@@ -6,6 +6,7 @@ use serde::Deserialize;
 
 use crate::application::auth::AuthenticatedUser;
 use crate::application::errors::{ApiError, AppError};
+use crate::application::routes::api::coffee::cups::check_cup_bag;
 use crate::application::routes::api::images::save_deferred_image;
 use crate::application::routes::support::{
     FlexiblePayload, PayloadSource, is_datastar_request, render_redirect_script,
@@ -14,8 +15,10 @@ use crate::application::state::AppState;
 use crate::domain::cafes::NewCafe;
 use crate::domain::cups::NewCup;
 use crate::domain::entity_type::EntityType;
-use crate::domain::ids::{CafeId, RoastId};
+use crate::domain::ids::{BagId, CafeId, RoastId};
 use crate::domain::images::ImageData;
+use crate::domain::ratings::MAX_STARS;
+use crate::domain::validation::Validate;
 
 #[derive(Debug, Deserialize)]
 pub(crate) struct CheckInSubmission {
@@ -33,33 +36,74 @@ pub(crate) struct CheckInSubmission {
     cafe_lng: f64,
     #[serde(default)]
     cafe_website: Option<String>,
+    #[serde(default)]
     roast_id: String,
+    /// Set when the coffee was drunk somewhere other than a cafe.
+    #[serde(default)]
+    no_cafe: Option<String>,
+    #[serde(default)]
+    bag_id: Option<String>,
+    /// Stars out of five, if the cup was rated.
+    #[serde(default)]
+    rating: Option<String>,
     #[serde(default)]
     cafe_image: ImageData,
     #[serde(default)]
     cup_image: ImageData,
 }
 
-#[tracing::instrument(skip(state, _auth_user, headers))]
+#[tracing::instrument(skip(state, auth_user, headers))]
 pub(crate) async fn submit_checkin(
     State(state): State<AppState>,
-    _auth_user: AuthenticatedUser,
+    auth_user: AuthenticatedUser,
     headers: HeaderMap,
     payload: FlexiblePayload<CheckInSubmission>,
 ) -> Result<Response, ApiError> {
     let (submission, source) = payload.into_parts();
 
-    let roast_id: i64 = submission
-        .roast_id
-        .parse()
-        .map_err(|_| AppError::validation("invalid roast ID"))?;
+    let roast_id = match submission.roast_id.as_str() {
+        "" => None,
+        id => Some(RoastId::from(
+            id.parse::<i64>()
+                .map_err(|_| AppError::validation("invalid roast ID"))?,
+        )),
+    };
+
+    let bag_id = match submission.bag_id.as_deref().filter(|s| !s.is_empty()) {
+        Some(id) => Some(BagId::from(
+            id.parse::<i64>()
+                .map_err(|_| AppError::validation("invalid bag ID"))?,
+        )),
+        None => None,
+    };
+
+    let rating = match submission.rating.as_deref().filter(|s| !s.is_empty()) {
+        Some(stars) => Some(
+            stars
+                .parse::<u8>()
+                .ok()
+                .filter(|stars| (1..=MAX_STARS).contains(stars))
+                .ok_or_else(|| AppError::validation("rating must be between 1 and 5 stars"))?,
+        ),
+        None => None,
+    };
+
+    // A bag is enough to know which roast was drunk.
+    let roast_id = match bag_id {
+        Some(bag_id) => check_cup_bag(&state, bag_id, roast_id).await?.roast_id,
+        None => roast_id.ok_or_else(|| AppError::validation("invalid roast ID"))?,
+    };
+
+    let no_cafe = matches!(submission.no_cafe.as_deref(), Some("true" | "on"));
 
-    // Use existing cafe or create a new one
-    let cafe_id = if let Some(id) = submission.cafe_id.as_deref().filter(|s| !s.is_empty()) {
+    // Use existing cafe, create a new one, or skip it for cups drunk elsewhere
+    let cafe_id = if no_cafe {
+        None
+    } else if let Some(id) = submission.cafe_id.as_deref().filter(|s| !s.is_empty()) {
         let parsed: i64 = id
             .parse()
             .map_err(|_| AppError::validation("invalid cafe ID"))?;
-        CafeId::from(parsed)
+        Some(CafeId::from(parsed))
     } else {
         let name = submission
             .cafe_name
@@ -77,10 +121,11 @@ pub(crate) async fn submit_checkin(
             created_at: None,
         }
         .normalize();
+        new_cafe.validate().map_err(AppError::from)?;
 
         let cafe = state
             .cafe_service
-            .create(new_cafe)
+            .create(new_cafe, Some(auth_user.0.id))
             .await
             .map_err(AppError::from)?;
 
@@ -92,20 +137,23 @@ pub(crate) async fn submit_checkin(
         )
         .await;
 
-        cafe.id
+        Some(cafe.id)
     };
 
     let new_cup = NewCup {
-        roast_id: RoastId::from(roast_id),
+        roast_id,
         cafe_id,
+        bag_id,
+        rating,
         created_at: None,
     };
 
     let cup = state
         .cup_service
-        .create(new_cup)
+        .create(new_cup, Some(auth_user.0.id))
         .await
         .map_err(AppError::from)?;
+    state.stats_invalidator.invalidate();
 
     save_deferred_image(
         &state,
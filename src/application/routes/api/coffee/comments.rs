@@ -0,0 +1,124 @@
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use tracing::info;
+
+use crate::application::auth::AuthenticatedUser;
+use crate::application::errors::{ApiError, AppError};
+use crate::application::state::AppState;
+use crate::domain::comments::{Comment, CommentInput, CommentTarget, NewComment};
+use crate::domain::ids::{BrewId, CommentId, RoastId};
+
+async fn list_comments(state: &AppState, target: CommentTarget) -> Result<Vec<Comment>, ApiError> {
+    match target {
+        CommentTarget::Brew(id) => state.brew_repo.get(id).await.map(|_| ()),
+        CommentTarget::Roast(id) => state.roast_repo.get(id).await.map(|_| ()),
+    }
+    .map_err(AppError::from)?;
+
+    Ok(state
+        .comment_repo
+        .list_for(target)
+        .await
+        .map_err(AppError::from)?)
+}
+
+async fn create_comment(
+    state: &AppState,
+    auth_user: AuthenticatedUser,
+    target: CommentTarget,
+    payload: CommentInput,
+) -> Result<Response, ApiError> {
+    let body = payload.normalized_body().map_err(AppError::validation)?;
+
+    let comment = state
+        .comment_service
+        .create(NewComment {
+            target,
+            user_id: auth_user.0.id,
+            body,
+        })
+        .await
+        .map_err(AppError::from)?;
+
+    info!(comment_id = %comment.id, "comment created");
+    Ok((StatusCode::CREATED, Json(comment)).into_response())
+}
+
+#[tracing::instrument(skip(state))]
+pub(crate) async fn list_brew_comments(
+    State(state): State<AppState>,
+    Path(brew_id): Path<BrewId>,
+) -> Result<Json<Vec<Comment>>, ApiError> {
+    list_comments(&state, CommentTarget::Brew(brew_id))
+        .await
+        .map(Json)
+}
+
+#[tracing::instrument(skip(state, auth_user, payload))]
+pub(crate) async fn create_brew_comment(
+    State(state): State<AppState>,
+    auth_user: AuthenticatedUser,
+    Path(brew_id): Path<BrewId>,
+    Json(payload): Json<CommentInput>,
+) -> Result<Response, ApiError> {
+    create_comment(&state, auth_user, CommentTarget::Brew(brew_id), payload).await
+}
+
+#[tracing::instrument(skip(state))]
+pub(crate) async fn list_roast_comments(
+    State(state): State<AppState>,
+    Path(roast_id): Path<RoastId>,
+) -> Result<Json<Vec<Comment>>, ApiError> {
+    list_comments(&state, CommentTarget::Roast(roast_id))
+        .await
+        .map(Json)
+}
+
+#[tracing::instrument(skip(state, auth_user, payload))]
+pub(crate) async fn create_roast_comment(
+    State(state): State<AppState>,
+    auth_user: AuthenticatedUser,
+    Path(roast_id): Path<RoastId>,
+    Json(payload): Json<CommentInput>,
+) -> Result<Response, ApiError> {
+    create_comment(&state, auth_user, CommentTarget::Roast(roast_id), payload).await
+}
+
+/// PUT /api/v1/comments/{id} — edit one of your own comments
+#[tracing::instrument(skip(state, auth_user, payload))]
+pub(crate) async fn update_comment(
+    State(state): State<AppState>,
+    auth_user: AuthenticatedUser,
+    Path(id): Path<CommentId>,
+    Json(payload): Json<CommentInput>,
+) -> Result<Json<Comment>, ApiError> {
+    let body = payload.normalized_body().map_err(AppError::validation)?;
+
+    let comment = state
+        .comment_repo
+        .update(id, auth_user.0.id, body)
+        .await
+        .map_err(AppError::from)?;
+
+    info!(comment_id = %id, "comment updated");
+    Ok(Json(comment))
+}
+
+/// DELETE /api/v1/comments/{id} — delete one of your own comments
+#[tracing::instrument(skip(state, auth_user))]
+pub(crate) async fn delete_comment(
+    State(state): State<AppState>,
+    auth_user: AuthenticatedUser,
+    Path(id): Path<CommentId>,
+) -> Result<StatusCode, ApiError> {
+    state
+        .comment_repo
+        .delete(id, auth_user.0.id)
+        .await
+        .map_err(AppError::from)?;
+
+    info!(comment_id = %id, "comment deleted");
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -11,17 +11,21 @@ use crate::application::routes::api::images::save_deferred_image;
 use crate::application::routes::api::macros::{
     define_delete_handler, define_enriched_get_handler, define_list_fragment_renderer,
 };
+use crate::application::routes::api::version::{ApiVersion, PageQuery};
 use crate::application::routes::support::impl_has_changes;
 use crate::application::routes::support::{
     FlexiblePayload, ListQuery, PayloadSource, is_datastar_request, render_redirect_script,
     update_response, validate_update,
 };
 use crate::application::state::AppState;
+use crate::domain::RepositoryError;
+use crate::domain::bags::Bag;
 use crate::domain::cups::{CupFilter, CupSortKey, CupWithDetails, NewCup, UpdateCup};
 use crate::domain::entity_type::EntityType;
-use crate::domain::ids::{CafeId, CupId, RoastId};
+use crate::domain::ids::{BagId, CafeId, CupId, RoastId};
 use crate::domain::images::ImageData;
 use crate::domain::listing::{ListRequest, SortDirection};
+use crate::domain::validation::Validate;
 use crate::presentation::web::templates::CupListTemplate;
 use crate::presentation::web::views::{CupView, ListNavigator, Paginated};
 use tracing::info;
@@ -51,20 +55,40 @@ pub(crate) async fn load_cup_page(
     ))
 }
 
-#[tracing::instrument(skip(state, _auth_user, headers, query))]
+/// Fetch the bag a cup was brewed from, rejecting bags of a different roast.
+pub(crate) async fn check_cup_bag(
+    state: &AppState,
+    bag_id: BagId,
+    roast_id: Option<RoastId>,
+) -> Result<Bag, AppError> {
+    let bag = state.bag_repo.get(bag_id).await.map_err(|err| match err {
+        RepositoryError::NotFound => AppError::validation("the bag does not exist"),
+        other => AppError::from(other),
+    })?;
+    if roast_id.is_some_and(|roast_id| roast_id != bag.roast_id) {
+        return Err(AppError::validation("the bag holds a different roast"));
+    }
+    Ok(bag)
+}
+
+#[tracing::instrument(skip(state, auth_user, headers, query))]
 pub(crate) async fn create_cup(
     State(state): State<AppState>,
-    _auth_user: AuthenticatedUser,
+    auth_user: AuthenticatedUser,
     headers: HeaderMap,
     Query(query): Query<ListQuery>,
     payload: FlexiblePayload<NewCup>,
 ) -> Result<Response, ApiError> {
     let (_request, _search) = query.into_request_and_search::<CupSortKey>();
     let (new_cup, source) = payload.into_parts();
+    new_cup.validate().map_err(AppError::from)?;
+    if let Some(bag_id) = new_cup.bag_id {
+        check_cup_bag(&state, bag_id, Some(new_cup.roast_id)).await?;
+    }
 
     let cup = state
         .cup_service
-        .create(new_cup)
+        .create(new_cup, Some(auth_user.0.id))
         .await
         .map_err(AppError::from)?;
 
@@ -85,24 +109,40 @@ pub(crate) async fn create_cup(
 #[tracing::instrument(skip(state))]
 pub(crate) async fn list_cups(
     State(state): State<AppState>,
-) -> Result<Json<Vec<CupWithDetails>>, ApiError> {
-    let request = ListRequest::show_all(CupSortKey::CreatedAt, SortDirection::Desc);
+    version: ApiVersion,
+    Query(paging): Query<PageQuery>,
+) -> Result<Response, ApiError> {
+    let request = version.list_request(&paging, CupSortKey::CreatedAt, SortDirection::Desc);
     let page = state
         .cup_repo
         .list(CupFilter::all(), &request, None)
         .await
         .map_err(AppError::from)?;
-    Ok(Json(page.items))
+    Ok(version.list_response(page))
 }
 
 define_enriched_get_handler!(get_cup, CupId, CupWithDetails, cup_repo, get_with_details);
 
+#[allow(clippy::option_option)]
 #[derive(Debug, Deserialize)]
 pub(crate) struct UpdateCupSubmission {
     #[serde(default)]
     roast_id: Option<RoastId>,
-    #[serde(default)]
-    cafe_id: Option<CafeId>,
+    #[serde(
+        default,
+        deserialize_with = "crate::domain::cups::deserialize_clearable_id"
+    )]
+    cafe_id: Option<Option<CafeId>>,
+    #[serde(
+        default,
+        deserialize_with = "crate::domain::cups::deserialize_clearable_id"
+    )]
+    bag_id: Option<Option<BagId>>,
+    #[serde(
+        default,
+        deserialize_with = "crate::domain::cups::deserialize_clearable_id"
+    )]
+    rating: Option<Option<u8>>,
     #[serde(default)]
     created_at: Option<DateTime<Utc>>,
     #[serde(default)]
@@ -114,13 +154,15 @@ impl UpdateCupSubmission {
         let update = UpdateCup {
             roast_id: self.roast_id,
             cafe_id: self.cafe_id,
+            bag_id: self.bag_id,
+            rating: self.rating,
             created_at: self.created_at,
         };
         (update, self.image.into_inner())
     }
 }
 
-impl_has_changes!(UpdateCup, roast_id, cafe_id, created_at);
+impl_has_changes!(UpdateCup, roast_id, cafe_id, bag_id, rating, created_at);
 
 #[tracing::instrument(skip(state, _auth_user, headers))]
 pub(crate) async fn update_cup(
@@ -131,9 +173,25 @@ pub(crate) async fn update_cup(
     payload: FlexiblePayload<UpdateCupSubmission>,
 ) -> Result<Response, ApiError> {
     let (submission, source) = payload.into_parts();
-    let (update, image_data_url) = submission.into_parts();
+    let (mut update, image_data_url) = submission.into_parts();
 
     validate_update(&update, image_data_url.as_ref())?;
+    update.validate().map_err(AppError::from)?;
+
+    if update.roast_id.is_some() || matches!(update.bag_id, Some(Some(_))) {
+        let existing = state.cup_repo.get(id).await.map_err(AppError::from)?;
+        let roast_id = update.roast_id.unwrap_or(existing.roast_id);
+        match update.bag_id {
+            Some(Some(bag_id)) => {
+                check_cup_bag(&state, bag_id, Some(roast_id)).await?;
+            }
+            // A bag of the old roast no longer fits once the roast changes.
+            None if existing.bag_id.is_some() && roast_id != existing.roast_id => {
+                update.bag_id = Some(None);
+            }
+            _ => {}
+        }
+    }
 
     let cup = state
         .cup_repo
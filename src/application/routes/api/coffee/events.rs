@@ -0,0 +1,167 @@
+use axum::Json;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use serde::Deserialize;
+use tracing::{info, warn};
+
+use crate::application::auth::AuthenticatedUser;
+use crate::application::errors::{ApiError, AppError};
+use crate::application::state::AppState;
+use crate::domain::RepositoryError;
+use crate::domain::entity_type::EntityType;
+use crate::domain::events::{Event, EventFilter, NewEvent, UpdateEvent};
+use crate::domain::ids::{CafeId, EventId, RoasterId};
+use crate::domain::validation::Validate;
+
+#[derive(Debug, Deserialize)]
+pub struct EventQuery {
+    /// Only return events this roaster was met at.
+    #[serde(default)]
+    pub roaster: Option<RoasterId>,
+    /// Only return events this cafe was met at.
+    #[serde(default)]
+    pub cafe: Option<CafeId>,
+}
+
+#[tracing::instrument(skip(state))]
+pub(crate) async fn list_events(
+    State(state): State<AppState>,
+    Query(query): Query<EventQuery>,
+) -> Result<Json<Vec<Event>>, ApiError> {
+    let filter = EventFilter {
+        roaster_id: query.roaster,
+        cafe_id: query.cafe,
+    };
+    let events = state
+        .event_repo
+        .list(filter)
+        .await
+        .map_err(AppError::from)?;
+
+    Ok(Json(events))
+}
+
+/// Reject links to roasters or cafes that don't exist, rather than failing
+/// on the foreign key.
+async fn ensure_links(
+    state: &AppState,
+    roaster_ids: &[RoasterId],
+    cafe_ids: &[CafeId],
+) -> Result<(), AppError> {
+    for roaster_id in roaster_ids {
+        state
+            .roaster_repo
+            .get(*roaster_id)
+            .await
+            .map_err(|err| match err {
+                RepositoryError::NotFound => {
+                    AppError::validation(format!("roaster {roaster_id} does not exist"))
+                }
+                other => AppError::from(other),
+            })?;
+    }
+    for cafe_id in cafe_ids {
+        state
+            .cafe_repo
+            .get(*cafe_id)
+            .await
+            .map_err(|err| match err {
+                RepositoryError::NotFound => {
+                    AppError::validation(format!("cafe {cafe_id} does not exist"))
+                }
+                other => AppError::from(other),
+            })?;
+    }
+    Ok(())
+}
+
+#[tracing::instrument(skip(state, auth_user))]
+pub(crate) async fn create_event(
+    State(state): State<AppState>,
+    auth_user: AuthenticatedUser,
+    Json(payload): Json<NewEvent>,
+) -> Result<Response, ApiError> {
+    let payload = payload.normalize();
+    payload.validate().map_err(AppError::from)?;
+    ensure_links(&state, &payload.roaster_ids, &payload.cafe_ids).await?;
+
+    let event = state
+        .event_service
+        .create(payload, Some(auth_user.0.id))
+        .await
+        .map_err(AppError::from)?;
+
+    info!(event_id = %event.id, name = %event.name, "event created");
+    state.stats_invalidator.invalidate();
+    Ok((StatusCode::CREATED, Json(event)).into_response())
+}
+
+#[tracing::instrument(skip(state))]
+pub(crate) async fn get_event(
+    State(state): State<AppState>,
+    Path(id): Path<EventId>,
+) -> Result<Json<Event>, ApiError> {
+    let event = state.event_repo.get(id).await.map_err(AppError::from)?;
+
+    Ok(Json(event))
+}
+
+#[tracing::instrument(skip(state, _auth_user))]
+pub(crate) async fn update_event(
+    State(state): State<AppState>,
+    _auth_user: AuthenticatedUser,
+    Path(id): Path<EventId>,
+    Json(payload): Json<UpdateEvent>,
+) -> Result<Json<Event>, ApiError> {
+    let payload = payload.normalize();
+    payload.validate().map_err(AppError::from)?;
+    ensure_links(
+        &state,
+        payload.roaster_ids.as_deref().unwrap_or_default(),
+        payload.cafe_ids.as_deref().unwrap_or_default(),
+    )
+    .await?;
+
+    let event = state
+        .event_repo
+        .update(id, payload)
+        .await
+        .map_err(AppError::from)?;
+
+    info!(event_id = %event.id, "event updated");
+    state.stats_invalidator.invalidate();
+    state
+        .timeline_invalidator
+        .invalidate(EntityType::Event, i64::from(event.id));
+    Ok(Json(event))
+}
+
+#[tracing::instrument(skip(state, _auth_user))]
+pub(crate) async fn delete_event(
+    State(state): State<AppState>,
+    _auth_user: AuthenticatedUser,
+    Path(id): Path<EventId>,
+) -> Result<StatusCode, ApiError> {
+    state.event_repo.delete(id).await.map_err(AppError::from)?;
+
+    if let Err(err) = state
+        .image_repo
+        .delete(EntityType::Event, i64::from(id))
+        .await
+    {
+        warn!(event_id = %id, error = %err, "failed to delete event image");
+    }
+    if let Err(err) = state
+        .timeline_repo
+        .delete_by_entity(EntityType::Event, i64::from(id))
+        .await
+    {
+        warn!(event_id = %id, error = %err, "failed to delete event timeline event");
+    }
+
+    info!(event_id = %id, "event deleted");
+    state.stats_invalidator.invalidate();
+    state.image_purger.schedule();
+    Ok(StatusCode::NO_CONTENT)
+}
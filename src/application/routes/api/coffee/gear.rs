@@ -5,7 +5,7 @@ use axum::extract::{Path, Query, State};
 use axum::http::{HeaderMap, StatusCode};
 use axum::response::{IntoResponse, Redirect, Response};
 use chrono::{DateTime, Utc};
-use serde::Deserialize;
+use serde::{Deserialize, Deserializer};
 use tracing::info;
 
 use crate::application::auth::AuthenticatedUser;
@@ -14,51 +14,73 @@ use crate::application::routes::api::images::save_deferred_image;
 use crate::application::routes::api::macros::{
     define_delete_handler, define_get_handler, define_list_fragment_renderer,
 };
+use crate::application::routes::api::version::{ApiVersion, PageQuery};
 use crate::application::routes::support::impl_has_changes;
 use crate::application::routes::support::{
-    FlexiblePayload, ListQuery, PayloadSource, is_datastar_request, render_redirect_script,
-    update_response, validate_update,
+    FlexiblePayload, FragmentQuery, ListQuery, PatchMode, PayloadSource, is_datastar_request,
+    new_row_mode, render_redirect_script, render_row_fragment, update_response, validate_update,
 };
 use crate::application::state::AppState;
 use crate::domain::entity_type::EntityType;
-use crate::domain::gear::{Gear, GearCategory, GearFilter, GearSortKey, NewGear, UpdateGear};
+use crate::domain::gear::{
+    BrewMethod, Gear, GearCategory, GearFilter, GearSortKey, NewGear, Restock, UpdateGear,
+};
 use crate::domain::ids::GearId;
 use crate::domain::images::ImageData;
 use crate::domain::listing::{ListRequest, SortDirection};
-use crate::presentation::web::templates::GearListTemplate;
+use crate::domain::validation::Validate;
+use crate::presentation::web::templates::{GearListTemplate, GearRowTemplate};
 use crate::presentation::web::views::{GearView, ListNavigator, Paginated};
 
 const GEAR_PAGE_PATH: &str = "/data?type=gear";
 const GEAR_FRAGMENT_PATH: &str = "/data?type=gear#gear-list";
+const GEAR_ROWS_SELECTOR: &str = "#gear-list tbody";
 
-#[tracing::instrument(skip(state))]
 pub(crate) async fn load_gear_page(
     state: &AppState,
     request: ListRequest<GearSortKey>,
     search: Option<&str>,
 ) -> Result<(Paginated<GearView>, ListNavigator<GearSortKey>), AppError> {
+    load_filtered_gear_page(state, GearFilter::all(), request, search).await
+}
+
+#[tracing::instrument(skip(state))]
+pub(crate) async fn load_filtered_gear_page(
+    state: &AppState,
+    filter: GearFilter,
+    request: ListRequest<GearSortKey>,
+    search: Option<&str>,
+) -> Result<(Paginated<GearView>, ListNavigator<GearSortKey>), AppError> {
+    let category = filter.category;
+
     let page = state
         .gear_repo
-        .list(GearFilter::all(), &request, search)
+        .list(filter, &request, search)
         .await
         .map_err(AppError::from)?;
 
-    Ok(crate::application::routes::support::build_page_view(
+    let (gear, mut navigator) = crate::application::routes::support::build_page_view(
         page,
         request,
         GearView::from,
         GEAR_PAGE_PATH,
         GEAR_FRAGMENT_PATH,
         search.map(String::from),
-    ))
+    );
+    if let Some(category) = category {
+        navigator = navigator.with_filter("category", category.as_str());
+    }
+
+    Ok((gear, navigator))
 }
 
-#[tracing::instrument(skip(state, _auth_user, headers, query))]
+#[tracing::instrument(skip(state, auth_user, headers, query, fragment))]
 pub(crate) async fn create_gear(
     State(state): State<AppState>,
-    _auth_user: AuthenticatedUser,
+    auth_user: AuthenticatedUser,
     headers: HeaderMap,
     Query(query): Query<ListQuery>,
+    Query(fragment): Query<FragmentQuery>,
     payload: FlexiblePayload<NewGearSubmission>,
 ) -> Result<Response, ApiError> {
     let (request, search) = query.into_request_and_search::<GearSortKey>();
@@ -67,7 +89,7 @@ pub(crate) async fn create_gear(
 
     let gear = state
         .gear_service
-        .create(new_gear)
+        .create(new_gear, Some(auth_user.0.id))
         .await
         .map_err(AppError::from)?;
 
@@ -84,6 +106,15 @@ pub(crate) async fn create_gear(
 
     let detail_url = format!("/gear/{}", gear.id);
 
+    if fragment.wants_row(&headers, "type=gear")
+        && let Some(mode) = new_row_mode(&request, search.as_deref())
+    {
+        let row = GearRowTemplate {
+            item: GearView::from(gear),
+        };
+        return render_row_fragment(row, GEAR_ROWS_SELECTOR, mode).map_err(ApiError::from);
+    }
+
     if is_datastar_request(&headers) {
         let from_data_page = headers
             .get("referer")
@@ -107,8 +138,10 @@ pub(crate) async fn create_gear(
 #[tracing::instrument(skip(state))]
 pub(crate) async fn list_gear(
     State(state): State<AppState>,
+    version: ApiVersion,
     Query(params): Query<GearQuery>,
-) -> Result<Json<Vec<Gear>>, ApiError> {
+    Query(paging): Query<PageQuery>,
+) -> Result<Response, ApiError> {
     let filter = match params.category {
         Some(ref cat_str) => {
             let category = GearCategory::from_str(cat_str)
@@ -117,23 +150,134 @@ pub(crate) async fn list_gear(
         }
         None => GearFilter::all(),
     };
-    let request = ListRequest::show_all(GearSortKey::Make, SortDirection::Asc);
+    let request = version.list_request(&paging, GearSortKey::Make, SortDirection::Asc);
     let page = state
         .gear_repo
         .list(filter, &request, None)
         .await
         .map_err(AppError::from)?;
-    Ok(Json(page.items))
+    Ok(version.list_response(page))
 }
 
 define_get_handler!(get_gear, GearId, Gear, gear_repo);
 
+/// Deserializes an optional water temperature, treating empty strings (from
+/// HTML forms) as None.
+pub(crate) fn deserialize_optional_temp<'de, D>(deserializer: D) -> Result<Option<f64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value: Option<serde_json::Value> = Option::deserialize(deserializer)?;
+    match value {
+        None | Some(serde_json::Value::Null) => Ok(None),
+        Some(serde_json::Value::String(s)) if s.trim().is_empty() => Ok(None),
+        Some(serde_json::Value::Number(n)) => n
+            .as_f64()
+            .map(Some)
+            .ok_or_else(|| serde::de::Error::custom("invalid water temperature")),
+        Some(serde_json::Value::String(s)) => s
+            .trim()
+            .parse::<f64>()
+            .map(Some)
+            .map_err(|_| serde::de::Error::custom("invalid water temperature")),
+        Some(_) => Err(serde::de::Error::custom("invalid water temperature")),
+    }
+}
+
+/// Deserializes an optional whole number, treating empty strings (from HTML
+/// forms) as None. `what` names the field in the error.
+fn deserialize_optional_int<'de, D>(deserializer: D, what: &str) -> Result<Option<i32>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let invalid = || serde::de::Error::custom(format!("invalid {what}"));
+    let value: Option<serde_json::Value> = Option::deserialize(deserializer)?;
+    match value {
+        None | Some(serde_json::Value::Null) => Ok(None),
+        Some(serde_json::Value::String(s)) if s.trim().is_empty() => Ok(None),
+        Some(serde_json::Value::Number(n)) => n
+            .as_i64()
+            .and_then(|value| i32::try_from(value).ok())
+            .map(Some)
+            .ok_or_else(invalid),
+        Some(serde_json::Value::String(s)) => {
+            s.trim().parse::<i32>().map(Some).map_err(|_| invalid())
+        }
+        Some(_) => Err(invalid()),
+    }
+}
+
+/// Deserializes an optional water hardness in ppm.
+fn deserialize_optional_hardness<'de, D>(deserializer: D) -> Result<Option<i32>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserialize_optional_int(deserializer, "water hardness")
+}
+
+/// Deserializes an optional filter paper count.
+fn deserialize_optional_count<'de, D>(deserializer: D) -> Result<Option<i32>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserialize_optional_int(deserializer, "filter paper count")
+}
+
+/// Deserializes an optional brew method, treating empty strings (from HTML
+/// forms) as None.
+fn deserialize_optional_method<'de, D>(deserializer: D) -> Result<Option<BrewMethod>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value: Option<String> = Option::deserialize(deserializer)?;
+    match value.as_deref().map(str::trim) {
+        None | Some("") => Ok(None),
+        Some(method) => BrewMethod::from_str(method)
+            .map(Some)
+            .map_err(|()| serde::de::Error::custom("invalid brew method")),
+    }
+}
+
+/// Deserializes an optional yes/no, accepting form strings ("true", "false")
+/// and treating empty strings as None.
+fn deserialize_optional_flag<'de, D>(deserializer: D) -> Result<Option<bool>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value: Option<serde_json::Value> = Option::deserialize(deserializer)?;
+    match value {
+        None | Some(serde_json::Value::Null) => Ok(None),
+        Some(serde_json::Value::Bool(flag)) => Ok(Some(flag)),
+        Some(serde_json::Value::String(s)) => match s.trim() {
+            "" => Ok(None),
+            "true" => Ok(Some(true)),
+            "false" => Ok(Some(false)),
+            _ => Err(serde::de::Error::custom("expected true or false")),
+        },
+        Some(_) => Err(serde::de::Error::custom("expected true or false")),
+    }
+}
+
 #[derive(Debug, Deserialize)]
 pub(crate) struct UpdateGearSubmission {
     #[serde(default)]
     make: Option<String>,
     #[serde(default)]
     model: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_optional_temp")]
+    water_temp: Option<f64>,
+    #[serde(default, deserialize_with = "deserialize_optional_method")]
+    brew_method: Option<BrewMethod>,
+    #[serde(default, deserialize_with = "deserialize_optional_flag")]
+    uses_filter: Option<bool>,
+    #[serde(default, deserialize_with = "deserialize_optional_temp")]
+    temp_offset: Option<f64>,
+    #[serde(default, deserialize_with = "deserialize_optional_hardness")]
+    water_hardness: Option<i32>,
+    #[serde(default, deserialize_with = "deserialize_optional_count")]
+    stock_count: Option<i32>,
+    #[serde(default, deserialize_with = "deserialize_optional_count")]
+    low_stock_threshold: Option<i32>,
     #[serde(default)]
     created_at: Option<DateTime<Utc>>,
     #[serde(default)]
@@ -145,26 +289,69 @@ impl UpdateGearSubmission {
         let update = UpdateGear {
             make: self.make,
             model: self.model,
+            water_temp: self.water_temp,
+            brew_method: self.brew_method,
+            uses_filter: self.uses_filter,
+            temp_offset: self.temp_offset,
+            water_hardness: self.water_hardness,
+            stock_count: self.stock_count,
+            low_stock_threshold: self.low_stock_threshold,
             created_at: self.created_at,
         };
         (update, self.image.into_inner())
     }
 }
 
-impl_has_changes!(UpdateGear, make, model, created_at);
+impl_has_changes!(
+    UpdateGear,
+    make,
+    model,
+    water_temp,
+    brew_method,
+    uses_filter,
+    temp_offset,
+    water_hardness,
+    stock_count,
+    low_stock_threshold,
+    created_at
+);
 
-#[tracing::instrument(skip(state, _auth_user, headers))]
+#[tracing::instrument(skip(state, _auth_user, headers, fragment))]
 pub(crate) async fn update_gear(
     State(state): State<AppState>,
     _auth_user: AuthenticatedUser,
     headers: HeaderMap,
     Path(id): Path<GearId>,
+    Query(fragment): Query<FragmentQuery>,
     payload: FlexiblePayload<UpdateGearSubmission>,
 ) -> Result<Response, ApiError> {
     let (submission, source) = payload.into_parts();
     let (update, image_data_url) = submission.into_parts();
 
     validate_update(&update, image_data_url.as_ref())?;
+    update.validate().map_err(AppError::from)?;
+    let brewer_settings =
+        update.water_temp.is_some() || update.brew_method.is_some() || update.uses_filter.is_some();
+    let kettle_settings = update.temp_offset.is_some() || update.water_hardness.is_some();
+    let stock_settings = update.stock_count.is_some() || update.low_stock_threshold.is_some();
+    if brewer_settings || kettle_settings || stock_settings {
+        let existing = state.gear_repo.get(id).await.map_err(AppError::from)?;
+        if brewer_settings && existing.category != GearCategory::Brewer {
+            return Err(AppError::validation(
+                "only brewers have a water temperature or brew method",
+            )
+            .into());
+        }
+        if kettle_settings && existing.category != GearCategory::Kettle {
+            return Err(AppError::validation(
+                "only kettles have a temperature offset or water hardness",
+            )
+            .into());
+        }
+        if stock_settings && existing.category != GearCategory::FilterPaper {
+            return Err(AppError::validation("only filter papers have a stock count").into());
+        }
+    }
 
     let gear = state
         .gear_repo
@@ -186,10 +373,43 @@ pub(crate) async fn update_gear(
     )
     .await;
 
+    if fragment.wants_row(&headers, "type=gear") {
+        let selector = format!("#gear-row-{}", gear.id);
+        let row = GearRowTemplate {
+            item: GearView::from(gear),
+        };
+        return render_row_fragment(row, &selector, PatchMode::Replace).map_err(ApiError::from);
+    }
+
     let detail_url = format!("/gear/{}", gear.id);
     update_response(&headers, source, &detail_url, Json(gear).into_response())
 }
 
+/// Adds newly bought filter papers to what's left in stock.
+#[tracing::instrument(skip(state, _auth_user))]
+pub(crate) async fn restock_gear(
+    State(state): State<AppState>,
+    _auth_user: AuthenticatedUser,
+    Path(id): Path<GearId>,
+    Json(restock): Json<Restock>,
+) -> Result<Json<Gear>, ApiError> {
+    restock.validate().map_err(AppError::from)?;
+    let existing = state.gear_repo.get(id).await.map_err(AppError::from)?;
+    if existing.category != GearCategory::FilterPaper {
+        return Err(AppError::validation("only filter papers have a stock count").into());
+    }
+
+    let gear = state
+        .gear_repo
+        .restock(id, restock.count)
+        .await
+        .map_err(AppError::from)?;
+
+    info!(%id, count = restock.count, "filter papers restocked");
+    state.stats_invalidator.invalidate();
+    Ok(Json(gear))
+}
+
 define_delete_handler!(
     delete_gear,
     GearId,
@@ -211,6 +431,20 @@ pub(crate) struct NewGearSubmission {
     category: String,
     make: String,
     model: String,
+    #[serde(default, deserialize_with = "deserialize_optional_temp")]
+    water_temp: Option<f64>,
+    #[serde(default, deserialize_with = "deserialize_optional_method")]
+    brew_method: Option<BrewMethod>,
+    #[serde(default, deserialize_with = "deserialize_optional_flag")]
+    uses_filter: Option<bool>,
+    #[serde(default, deserialize_with = "deserialize_optional_temp")]
+    temp_offset: Option<f64>,
+    #[serde(default, deserialize_with = "deserialize_optional_hardness")]
+    water_hardness: Option<i32>,
+    #[serde(default, deserialize_with = "deserialize_optional_count")]
+    stock_count: Option<i32>,
+    #[serde(default, deserialize_with = "deserialize_optional_count")]
+    low_stock_threshold: Option<i32>,
     #[serde(default)]
     created_at: Option<DateTime<Utc>>,
     #[serde(default)]
@@ -222,23 +456,22 @@ impl NewGearSubmission {
         let category = GearCategory::from_str(&self.category)
             .map_err(|()| AppError::validation("invalid category"))?;
 
-        if self.make.trim().is_empty() {
-            return Err(AppError::validation("make cannot be empty"));
-        }
-
-        if self.model.trim().is_empty() {
-            return Err(AppError::validation("model cannot be empty"));
-        }
+        let gear = NewGear {
+            category,
+            make: self.make,
+            model: self.model,
+            water_temp: self.water_temp,
+            brew_method: self.brew_method,
+            uses_filter: self.uses_filter,
+            temp_offset: self.temp_offset,
+            water_hardness: self.water_hardness,
+            stock_count: self.stock_count,
+            low_stock_threshold: self.low_stock_threshold,
+            created_at: self.created_at,
+        };
+        gear.validate()?;
 
-        Ok((
-            NewGear {
-                category,
-                make: self.make,
-                model: self.model,
-                created_at: self.created_at,
-            },
-            self.image.into_inner(),
-        ))
+        Ok((gear, self.image.into_inner()))
     }
 }
 
@@ -0,0 +1,93 @@
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use tracing::info;
+
+use crate::application::auth::AuthenticatedUser;
+use crate::application::errors::{ApiError, AppError};
+use crate::application::state::AppState;
+use crate::domain::green_coffees::{GreenCoffee, NewGreenCoffee, UpdateGreenCoffee};
+use crate::domain::ids::GreenCoffeeId;
+use crate::domain::validation::Validate;
+
+#[tracing::instrument(skip(state))]
+pub(crate) async fn list_green_coffees(
+    State(state): State<AppState>,
+) -> Result<Json<Vec<GreenCoffee>>, ApiError> {
+    let green_coffees = state
+        .green_coffee_repo
+        .list()
+        .await
+        .map_err(AppError::from)?;
+
+    Ok(Json(green_coffees))
+}
+
+#[tracing::instrument(skip(state, _auth_user))]
+pub(crate) async fn create_green_coffee(
+    State(state): State<AppState>,
+    _auth_user: AuthenticatedUser,
+    Json(payload): Json<NewGreenCoffee>,
+) -> Result<Response, ApiError> {
+    let payload = payload.normalize();
+    payload.validate().map_err(AppError::from)?;
+
+    let green = state
+        .green_coffee_repo
+        .insert(payload)
+        .await
+        .map_err(AppError::from)?;
+
+    info!(green_coffee_id = %green.id, name = %green.name, "green coffee created");
+    Ok((StatusCode::CREATED, Json(green)).into_response())
+}
+
+#[tracing::instrument(skip(state))]
+pub(crate) async fn get_green_coffee(
+    State(state): State<AppState>,
+    Path(id): Path<GreenCoffeeId>,
+) -> Result<Json<GreenCoffee>, ApiError> {
+    let green = state
+        .green_coffee_repo
+        .get(id)
+        .await
+        .map_err(AppError::from)?;
+
+    Ok(Json(green))
+}
+
+#[tracing::instrument(skip(state, _auth_user))]
+pub(crate) async fn update_green_coffee(
+    State(state): State<AppState>,
+    _auth_user: AuthenticatedUser,
+    Path(id): Path<GreenCoffeeId>,
+    Json(payload): Json<UpdateGreenCoffee>,
+) -> Result<Json<GreenCoffee>, ApiError> {
+    payload.validate().map_err(AppError::from)?;
+
+    let green = state
+        .green_coffee_repo
+        .update(id, payload)
+        .await
+        .map_err(AppError::from)?;
+
+    info!(green_coffee_id = %green.id, "green coffee updated");
+    Ok(Json(green))
+}
+
+#[tracing::instrument(skip(state, _auth_user))]
+pub(crate) async fn delete_green_coffee(
+    State(state): State<AppState>,
+    _auth_user: AuthenticatedUser,
+    Path(id): Path<GreenCoffeeId>,
+) -> Result<StatusCode, ApiError> {
+    state
+        .green_coffee_repo
+        .delete(id)
+        .await
+        .map_err(AppError::from)?;
+
+    info!(green_coffee_id = %id, "green coffee deleted");
+    Ok(StatusCode::NO_CONTENT)
+}
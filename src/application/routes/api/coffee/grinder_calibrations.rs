@@ -0,0 +1,122 @@
+use axum::Json;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use serde::Deserialize;
+use tracing::info;
+
+use crate::application::auth::AuthenticatedUser;
+use crate::application::errors::{ApiError, AppError};
+use crate::application::state::AppState;
+use crate::domain::gear::GearCategory;
+use crate::domain::grinder_calibrations::{
+    GrindConversion, GrinderCalibration, NewGrinderCalibration, convert_setting,
+};
+use crate::domain::ids::{GearId, GrinderCalibrationId};
+
+#[derive(Debug, Deserialize)]
+pub struct CalibrationQuery {
+    /// Only return points involving this grinder.
+    #[serde(default)]
+    pub grinder: Option<GearId>,
+}
+
+#[tracing::instrument(skip(state))]
+pub(crate) async fn list_grinder_calibrations(
+    State(state): State<AppState>,
+    Query(query): Query<CalibrationQuery>,
+) -> Result<Json<Vec<GrinderCalibration>>, ApiError> {
+    let calibrations = state
+        .grinder_calibration_repo
+        .list(query.grinder)
+        .await
+        .map_err(AppError::from)?;
+
+    Ok(Json(calibrations))
+}
+
+async fn ensure_grinder(state: &AppState, id: GearId) -> Result<(), AppError> {
+    let gear = state.gear_repo.get(id).await?;
+    if gear.category != GearCategory::Grinder {
+        return Err(AppError::validation(format!(
+            "{} {} is not a grinder",
+            gear.make, gear.model
+        )));
+    }
+    Ok(())
+}
+
+#[tracing::instrument(skip(state, _auth_user))]
+pub(crate) async fn create_grinder_calibration(
+    State(state): State<AppState>,
+    _auth_user: AuthenticatedUser,
+    Json(payload): Json<NewGrinderCalibration>,
+) -> Result<Response, ApiError> {
+    payload.validate().map_err(AppError::validation)?;
+    ensure_grinder(&state, payload.grinder_a_id).await?;
+    ensure_grinder(&state, payload.grinder_b_id).await?;
+
+    let calibration = state
+        .grinder_calibration_repo
+        .insert(payload)
+        .await
+        .map_err(AppError::from)?;
+
+    info!(calibration_id = %calibration.id, "grinder calibration created");
+    Ok((StatusCode::CREATED, Json(calibration)).into_response())
+}
+
+#[tracing::instrument(skip(state, _auth_user))]
+pub(crate) async fn delete_grinder_calibration(
+    State(state): State<AppState>,
+    _auth_user: AuthenticatedUser,
+    Path(id): Path<GrinderCalibrationId>,
+) -> Result<StatusCode, ApiError> {
+    state
+        .grinder_calibration_repo
+        .delete(id)
+        .await
+        .map_err(AppError::from)?;
+
+    info!(calibration_id = %id, "grinder calibration deleted");
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ConvertQuery {
+    pub from: GearId,
+    pub to: GearId,
+    pub setting: f64,
+}
+
+#[tracing::instrument(skip(state))]
+pub(crate) async fn convert_grind_setting(
+    State(state): State<AppState>,
+    Query(query): Query<ConvertQuery>,
+) -> Result<Json<GrindConversion>, ApiError> {
+    if query.from == query.to {
+        return Err(
+            AppError::validation("choose two different grinders to convert between").into(),
+        );
+    }
+
+    let points: Vec<(f64, f64)> = state
+        .grinder_calibration_repo
+        .list_for_pair(query.from, query.to)
+        .await
+        .map_err(AppError::from)?
+        .iter()
+        .filter_map(|point| point.oriented(query.from, query.to))
+        .collect();
+
+    let to_setting = convert_setting(&points, query.setting)
+        .ok_or_else(|| AppError::validation("no calibration points between these grinders"))?;
+
+    Ok(Json(GrindConversion {
+        from_grinder_id: query.from,
+        to_grinder_id: query.to,
+        from_setting: query.setting,
+        to_setting,
+        reference_points: points.len(),
+    }))
+}
@@ -1,9 +1,21 @@
+pub(crate) mod aliases;
 pub(crate) mod bags;
+pub(crate) mod brew_parsing;
+pub(crate) mod brew_shares;
 pub(crate) mod brews;
+pub(crate) mod bulk;
 pub(crate) mod cafes;
 pub(crate) mod checkin;
+pub(crate) mod comments;
 pub(crate) mod cups;
+pub(crate) mod events;
 pub(crate) mod gear;
+pub(crate) mod green_coffees;
+pub(crate) mod grinder_calibrations;
+pub(crate) mod processes;
+pub(crate) mod quick_notes;
+pub(crate) mod roast_batches;
 pub(crate) mod roasters;
 pub(crate) mod roasts;
 pub(crate) mod scan;
+pub(crate) mod trips;
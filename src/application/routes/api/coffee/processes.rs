@@ -0,0 +1,88 @@
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use tracing::info;
+
+use crate::application::auth::AuthenticatedUser;
+use crate::application::errors::{ApiError, AppError};
+use crate::application::state::AppState;
+use crate::domain::ids::CustomProcessId;
+use crate::domain::processes::{NewCustomProcess, ProcessOption, ProcessTaxonomy};
+
+/// The built-in processes plus the user's own.
+pub(crate) async fn load_process_taxonomy(state: &AppState) -> Result<ProcessTaxonomy, AppError> {
+    let custom = state.process_repo.list().await?;
+    Ok(ProcessTaxonomy::new(custom))
+}
+
+/// The stored name for a submitted process. Blank stays blank; anything
+/// outside the taxonomy is rejected.
+pub(crate) async fn resolve_process(state: &AppState, value: &str) -> Result<String, AppError> {
+    if value.trim().is_empty() {
+        return Ok(String::new());
+    }
+    load_process_taxonomy(state)
+        .await?
+        .canonical(value)
+        .map_err(AppError::validation)
+}
+
+/// Map an AI-extracted process onto the taxonomy so the form is prefilled
+/// with something it will accept.
+pub(crate) async fn closest_process(
+    state: &AppState,
+    value: Option<String>,
+) -> Result<Option<String>, AppError> {
+    match value.as_deref().map(str::trim) {
+        None | Some("") => Ok(value),
+        Some(process) => Ok(Some(load_process_taxonomy(state).await?.closest(process))),
+    }
+}
+
+#[tracing::instrument(skip(state))]
+pub(crate) async fn list_processes(
+    State(state): State<AppState>,
+) -> Result<Json<Vec<ProcessOption>>, ApiError> {
+    let taxonomy = load_process_taxonomy(&state).await?;
+
+    Ok(Json(taxonomy.options()))
+}
+
+#[tracing::instrument(skip(state, _auth_user))]
+pub(crate) async fn create_process(
+    State(state): State<AppState>,
+    _auth_user: AuthenticatedUser,
+    Json(payload): Json<NewCustomProcess>,
+) -> Result<Response, ApiError> {
+    let payload = payload.normalize().map_err(AppError::validation)?;
+
+    let process = state
+        .process_repo
+        .insert(payload)
+        .await
+        .map_err(AppError::from)?;
+
+    state.stats_invalidator.invalidate();
+    info!(process_id = %process.id, "custom process created");
+    Ok((StatusCode::CREATED, Json(process)).into_response())
+}
+
+/// Roasts keep the process they were logged with; it just stops being
+/// offered, and its stats fall back to the family its name suggests.
+#[tracing::instrument(skip(state, _auth_user))]
+pub(crate) async fn delete_process(
+    State(state): State<AppState>,
+    _auth_user: AuthenticatedUser,
+    Path(id): Path<CustomProcessId>,
+) -> Result<StatusCode, ApiError> {
+    state
+        .process_repo
+        .delete(id)
+        .await
+        .map_err(AppError::from)?;
+
+    state.stats_invalidator.invalidate();
+    info!(process_id = %id, "custom process deleted");
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,56 @@
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use tracing::info;
+
+use crate::application::auth::AuthenticatedUser;
+use crate::application::errors::{ApiError, AppError};
+use crate::application::state::AppState;
+use crate::domain::ids::CustomQuickNoteId;
+use crate::domain::quick_notes::{CustomQuickNote, NewCustomQuickNote};
+
+#[tracing::instrument(skip(state))]
+pub(crate) async fn list_quick_notes(
+    State(state): State<AppState>,
+) -> Result<Json<Vec<CustomQuickNote>>, ApiError> {
+    let notes = state.quick_note_repo.list().await.map_err(AppError::from)?;
+
+    Ok(Json(notes))
+}
+
+#[tracing::instrument(skip(state, _auth_user))]
+pub(crate) async fn create_quick_note(
+    State(state): State<AppState>,
+    _auth_user: AuthenticatedUser,
+    Json(payload): Json<NewCustomQuickNote>,
+) -> Result<Response, ApiError> {
+    let payload = payload.normalize().map_err(AppError::validation)?;
+
+    let note = state
+        .quick_note_repo
+        .insert(payload)
+        .await
+        .map_err(AppError::from)?;
+
+    info!(quick_note_id = %note.id, "quick note created");
+    Ok((StatusCode::CREATED, Json(note)).into_response())
+}
+
+/// Brews keep any notes they were logged with; the label just stops being
+/// offered on the brew form.
+#[tracing::instrument(skip(state, _auth_user))]
+pub(crate) async fn delete_quick_note(
+    State(state): State<AppState>,
+    _auth_user: AuthenticatedUser,
+    Path(id): Path<CustomQuickNoteId>,
+) -> Result<StatusCode, ApiError> {
+    state
+        .quick_note_repo
+        .delete(id)
+        .await
+        .map_err(AppError::from)?;
+
+    info!(quick_note_id = %id, "quick note deleted");
+    Ok(StatusCode::NO_CONTENT)
+}
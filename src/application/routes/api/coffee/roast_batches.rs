@@ -0,0 +1,171 @@
+use axum::Json;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use serde::Deserialize;
+use tracing::info;
+
+use crate::application::auth::AuthenticatedUser;
+use crate::application::errors::{ApiError, AppError};
+use crate::application::state::AppState;
+use crate::domain::formatting::format_weight;
+use crate::domain::green_coffees::GreenCoffee;
+use crate::domain::ids::{BagId, GreenCoffeeId, RoastBatchId};
+use crate::domain::roast_batches::{NewRoastBatch, RoastBatch, UpdateRoastBatch};
+use crate::domain::validation::Validate;
+use crate::domain::{ErrorCode, RepositoryError};
+
+#[derive(Debug, Deserialize)]
+pub struct RoastBatchQuery {
+    /// Only return batches roasted from this green coffee.
+    #[serde(default)]
+    pub green_coffee: Option<GreenCoffeeId>,
+}
+
+#[tracing::instrument(skip(state))]
+pub(crate) async fn list_roast_batches(
+    State(state): State<AppState>,
+    Query(query): Query<RoastBatchQuery>,
+) -> Result<Json<Vec<RoastBatch>>, ApiError> {
+    let batches = state
+        .roast_batch_repo
+        .list(query.green_coffee)
+        .await
+        .map_err(AppError::from)?;
+
+    Ok(Json(batches))
+}
+
+/// Reject a link to a bag that doesn't exist, rather than failing on the
+/// foreign key.
+async fn ensure_bag(state: &AppState, bag_id: Option<BagId>) -> Result<(), AppError> {
+    if let Some(bag_id) = bag_id {
+        state.bag_repo.get(bag_id).await.map_err(|err| match err {
+            RepositoryError::NotFound => AppError::validation("the bag does not exist"),
+            other => AppError::from(other),
+        })?;
+    }
+    Ok(())
+}
+
+/// Reject a batch that would roast more green coffee than is left, counting
+/// what the batch already took as available again.
+fn ensure_green_left(
+    green: &GreenCoffee,
+    weight_in: f64,
+    already_taken: f64,
+) -> Result<(), AppError> {
+    let available = green.remaining + already_taken;
+    if weight_in > available {
+        return Err(AppError::validation_with(
+            ErrorCode::GreenCoffeeInsufficient,
+            format!(
+                "only {} of {} is left to roast",
+                format_weight(available),
+                green.name
+            ),
+        ));
+    }
+    Ok(())
+}
+
+#[tracing::instrument(skip(state, _auth_user))]
+pub(crate) async fn create_roast_batch(
+    State(state): State<AppState>,
+    _auth_user: AuthenticatedUser,
+    Json(payload): Json<NewRoastBatch>,
+) -> Result<Response, ApiError> {
+    payload.validate().map_err(AppError::from)?;
+
+    let green = state
+        .green_coffee_repo
+        .get(payload.green_coffee_id)
+        .await
+        .map_err(AppError::from)?;
+    ensure_green_left(&green, payload.weight_in, 0.0)?;
+    ensure_bag(&state, payload.bag_id).await?;
+
+    let batch = state
+        .roast_batch_repo
+        .insert(payload)
+        .await
+        .map_err(AppError::from)?;
+
+    info!(roast_batch_id = %batch.id, green_coffee_id = %batch.green_coffee_id, "roast batch created");
+    Ok((StatusCode::CREATED, Json(batch)).into_response())
+}
+
+#[tracing::instrument(skip(state))]
+pub(crate) async fn get_roast_batch(
+    State(state): State<AppState>,
+    Path(id): Path<RoastBatchId>,
+) -> Result<Json<RoastBatch>, ApiError> {
+    let batch = state
+        .roast_batch_repo
+        .get(id)
+        .await
+        .map_err(AppError::from)?;
+
+    Ok(Json(batch))
+}
+
+#[tracing::instrument(skip(state, _auth_user))]
+pub(crate) async fn update_roast_batch(
+    State(state): State<AppState>,
+    _auth_user: AuthenticatedUser,
+    Path(id): Path<RoastBatchId>,
+    Json(payload): Json<UpdateRoastBatch>,
+) -> Result<Json<RoastBatch>, ApiError> {
+    payload.validate().map_err(AppError::from)?;
+
+    // Check the weights and times against what's already stored, so an
+    // update can't leave the batch in a state a new one couldn't have.
+    let existing = state
+        .roast_batch_repo
+        .get(id)
+        .await
+        .map_err(AppError::from)?;
+    UpdateRoastBatch {
+        weight_in: Some(payload.weight_in.unwrap_or(existing.weight_in)),
+        weight_out: Some(payload.weight_out.unwrap_or(existing.weight_out)),
+        total_time: payload.total_time.or(existing.total_time),
+        development_time: payload.development_time.or(existing.development_time),
+        ..UpdateRoastBatch::default()
+    }
+    .validate()
+    .map_err(AppError::from)?;
+    if let Some(weight_in) = payload.weight_in {
+        let green = state
+            .green_coffee_repo
+            .get(existing.green_coffee_id)
+            .await
+            .map_err(AppError::from)?;
+        ensure_green_left(&green, weight_in, existing.weight_in)?;
+    }
+    ensure_bag(&state, payload.bag_id.flatten()).await?;
+
+    let batch = state
+        .roast_batch_repo
+        .update(id, payload)
+        .await
+        .map_err(AppError::from)?;
+
+    info!(roast_batch_id = %batch.id, "roast batch updated");
+    Ok(Json(batch))
+}
+
+#[tracing::instrument(skip(state, _auth_user))]
+pub(crate) async fn delete_roast_batch(
+    State(state): State<AppState>,
+    _auth_user: AuthenticatedUser,
+    Path(id): Path<RoastBatchId>,
+) -> Result<StatusCode, ApiError> {
+    state
+        .roast_batch_repo
+        .delete(id)
+        .await
+        .map_err(AppError::from)?;
+
+    info!(roast_batch_id = %id, "roast batch deleted");
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -6,28 +6,38 @@ use serde::Deserialize;
 
 use crate::application::auth::AuthenticatedUser;
 use crate::application::errors::{ApiError, AppError};
-use crate::application::routes::api::images::save_deferred_image;
+use crate::application::routes::api::aliases::find_roaster;
+use crate::application::routes::api::images::{prepare_extraction_image, save_deferred_image};
 use crate::application::routes::api::macros::{
-    define_delete_handler, define_get_handler, define_list_fragment_renderer,
+    define_delete_handler, define_delete_preview_handler, define_favourite_handler,
+    define_get_handler, define_list_fragment_renderer,
 };
+use crate::application::routes::api::version::{ApiVersion, PageQuery};
 use crate::application::routes::support::impl_has_changes;
 use crate::application::routes::support::{
-    FlexiblePayload, ListQuery, PayloadSource, is_datastar_request, render_redirect_script,
-    update_response, validate_update,
+    FlexiblePayload, FragmentQuery, ListQuery, PatchMode, PayloadSource, is_datastar_request,
+    load_roaster_options, new_row_mode, render_fragment, render_redirect_script,
+    render_row_fragment, update_response, validate_update,
 };
 use crate::application::state::AppState;
+use crate::domain::countries::{Country, suggest_countries};
 use crate::domain::entity_type::EntityType;
+use crate::domain::errors::RepositoryError;
 use crate::domain::ids::RoasterId;
 use crate::domain::images::ImageData;
 use crate::domain::listing::{ListRequest, SortDirection};
 use crate::domain::roasters::{NewRoaster, Roaster, RoasterSortKey, UpdateRoaster};
+use crate::domain::validation::Validate;
 use crate::infrastructure::ai::{self, ExtractionInput};
-use crate::presentation::web::templates::RoasterListTemplate;
+use crate::presentation::web::templates::{
+    RoasterListTemplate, RoasterRowTemplate, RoasterSelectTemplate,
+};
 use crate::presentation::web::views::{ListNavigator, Paginated, RoasterView};
 use tracing::info;
 
 const ROASTER_PAGE_PATH: &str = "/data?type=roasters";
 const ROASTER_FRAGMENT_PATH: &str = "/data?type=roasters#roaster-list";
+const ROASTER_ROWS_SELECTOR: &str = "#roaster-list tbody";
 
 #[tracing::instrument(skip(state))]
 pub(crate) async fn load_roaster_page(
@@ -54,13 +64,16 @@ pub(crate) async fn load_roaster_page(
 #[tracing::instrument(skip(state))]
 pub(crate) async fn list_roasters(
     State(state): State<AppState>,
-) -> Result<Json<Vec<Roaster>>, ApiError> {
-    let roasters = state
+    version: ApiVersion,
+    Query(paging): Query<PageQuery>,
+) -> Result<Response, ApiError> {
+    let request = version.list_request(&paging, RoasterSortKey::Name, SortDirection::Asc);
+    let page = state
         .roaster_repo
-        .list_all_sorted(RoasterSortKey::Name, SortDirection::Asc)
+        .list(&request, None)
         .await
         .map_err(AppError::from)?;
-    Ok(Json(roasters))
+    Ok(version.list_response(page))
 }
 
 #[derive(Debug, Deserialize)]
@@ -90,21 +103,23 @@ impl NewRoasterSubmission {
     }
 }
 
-#[tracing::instrument(skip(state, _auth_user, headers, query))]
+#[tracing::instrument(skip(state, auth_user, headers, query, fragment))]
 pub(crate) async fn create_roaster(
     State(state): State<AppState>,
-    _auth_user: AuthenticatedUser,
+    auth_user: AuthenticatedUser,
     headers: HeaderMap,
     Query(query): Query<ListQuery>,
+    Query(fragment): Query<FragmentQuery>,
     payload: FlexiblePayload<NewRoasterSubmission>,
 ) -> Result<Response, ApiError> {
     let (request, search) = query.into_request_and_search::<RoasterSortKey>();
     let (submission, source) = payload.into_parts();
     let (new_roaster, image_data_url) = submission.into_parts();
     let new_roaster = new_roaster.normalize();
+    new_roaster.validate().map_err(AppError::from)?;
     let roaster = state
         .roaster_service
-        .create(new_roaster)
+        .create(new_roaster, Some(auth_user.0.id))
         .await
         .map_err(AppError::from)?;
 
@@ -121,6 +136,16 @@ pub(crate) async fn create_roaster(
 
     let detail_url = format!("/roasters/{}", roaster.slug);
 
+    if fragment.wants_row(&headers, "type=roasters")
+        && let Some(mode) = new_row_mode(&request, search.as_deref())
+    {
+        let row = RoasterRowTemplate {
+            is_authenticated: true,
+            roaster: RoasterView::from(roaster),
+        };
+        return render_row_fragment(row, ROASTER_ROWS_SELECTOR, mode).map_err(ApiError::from);
+    }
+
     if is_datastar_request(&headers) {
         let from_data_page = headers
             .get("referer")
@@ -141,6 +166,47 @@ pub(crate) async fn create_roaster(
     }
 }
 
+/// Adds a roaster from the roast form without leaving it. The roaster picker
+/// comes back with the roaster selected; one that already exists under the
+/// same name, or used to, is picked rather than duplicated.
+#[tracing::instrument(skip(state, auth_user, headers, payload))]
+pub(crate) async fn quick_add_roaster(
+    State(state): State<AppState>,
+    auth_user: AuthenticatedUser,
+    headers: HeaderMap,
+    payload: FlexiblePayload<NewRoasterSubmission>,
+) -> Result<Response, ApiError> {
+    let (submission, _) = payload.into_parts();
+    let (new_roaster, _) = submission.into_parts();
+    let new_roaster = new_roaster.normalize();
+    new_roaster.validate().map_err(AppError::from)?;
+
+    let (roaster, status) = match find_roaster(&state, &new_roaster.slug()).await {
+        Ok(existing) => (existing, StatusCode::OK),
+        Err(RepositoryError::NotFound) => {
+            let roaster = state
+                .roaster_service
+                .create(new_roaster, Some(auth_user.0.id))
+                .await
+                .map_err(AppError::from)?;
+            info!(roaster_id = %roaster.id, name = %roaster.name, "roaster quick-added");
+            state.stats_invalidator.invalidate();
+            (roaster, StatusCode::CREATED)
+        }
+        Err(err) => return Err(AppError::from(err).into()),
+    };
+
+    if !is_datastar_request(&headers) {
+        return Ok((status, Json(roaster)).into_response());
+    }
+
+    let template = RoasterSelectTemplate {
+        roaster_options: load_roaster_options(&state).await?,
+        selected_roaster_id: roaster.id.to_string(),
+    };
+    render_fragment(template, "#roast-roaster-select").map_err(ApiError::from)
+}
+
 define_get_handler!(get_roaster, RoasterId, Roaster, roaster_repo);
 
 #[derive(Debug, Deserialize)]
@@ -174,12 +240,13 @@ impl UpdateRoasterSubmission {
 
 impl_has_changes!(UpdateRoaster, name, country, city, homepage, created_at);
 
-#[tracing::instrument(skip(state, _auth_user, headers))]
+#[tracing::instrument(skip(state, _auth_user, headers, fragment))]
 pub(crate) async fn update_roaster(
     State(state): State<AppState>,
     _auth_user: AuthenticatedUser,
     headers: HeaderMap,
     Path(id): Path<RoasterId>,
+    Query(fragment): Query<FragmentQuery>,
     payload: FlexiblePayload<UpdateRoasterSubmission>,
 ) -> Result<Response, ApiError> {
     let (submission, source) = payload.into_parts();
@@ -187,6 +254,7 @@ pub(crate) async fn update_roaster(
     let update = update.normalize();
 
     validate_update(&update, image_data_url.as_ref())?;
+    update.validate().map_err(AppError::from)?;
 
     let roaster = state
         .roaster_repo
@@ -207,6 +275,15 @@ pub(crate) async fn update_roaster(
     )
     .await;
 
+    if fragment.wants_row(&headers, "type=roasters") {
+        let selector = format!("#roaster-row-{}", roaster.id);
+        let row = RoasterRowTemplate {
+            is_authenticated: true,
+            roaster: RoasterView::from(roaster),
+        };
+        return render_row_fragment(row, &selector, PatchMode::Replace).map_err(ApiError::from);
+    }
+
     let detail_url = format!("/roasters/{}", roaster.slug);
     update_response(&headers, source, &detail_url, Json(roaster).into_response())
 }
@@ -222,6 +299,10 @@ define_delete_handler!(
     image_type: crate::domain::entity_type::EntityType::Roaster
 );
 
+define_delete_preview_handler!(roaster_delete_preview, RoasterId, roaster_repo);
+
+define_favourite_handler!(favourite_roaster, RoasterId, roaster_repo, "roasters");
+
 #[tracing::instrument(skip(state, auth_user, headers, payload))]
 pub(crate) async fn extract_roaster(
     State(state): State<AppState>,
@@ -229,16 +310,12 @@ pub(crate) async fn extract_roaster(
     headers: HeaderMap,
     payload: FlexiblePayload<ExtractionInput>,
 ) -> Result<Response, ApiError> {
-    let (input, _) = payload.into_parts();
-    let (result, usage) = ai::extract_roaster(
-        &state.http_client,
-        &state.openrouter_url,
-        &state.openrouter_api_key,
-        &state.openrouter_model,
-        &input,
-    )
-    .await
-    .map_err(ApiError::from)?;
+    let (mut input, _) = payload.into_parts();
+    prepare_extraction_image(&state, &mut input).await?;
+    let (result, usage) = match ai::extract_roaster(&state.openrouter(), &input).await {
+        Ok(extracted) => extracted,
+        Err(err) => return crate::application::routes::support::ai_fallback(&headers, err),
+    };
 
     crate::application::routes::support::record_ai_usage(
         state.ai_usage_repo.clone(),
@@ -282,3 +359,16 @@ define_list_fragment_renderer!(
     RoasterListTemplate { roasters },
     "#roaster-list"
 );
+
+#[derive(Debug, Deserialize)]
+pub struct CountriesQuery {
+    #[serde(default)]
+    pub q: String,
+}
+
+#[tracing::instrument]
+pub(crate) async fn list_countries(
+    Query(query): Query<CountriesQuery>,
+) -> Json<Vec<&'static Country>> {
+    Json(suggest_countries(&query.q))
+}
@@ -3,31 +3,49 @@ use axum::extract::{Path, Query, State};
 use axum::http::{HeaderMap, StatusCode};
 use axum::response::{IntoResponse, Redirect, Response};
 use chrono::{DateTime, Utc};
-use serde::Deserialize;
+use serde::{Deserialize, Deserializer};
 
 use crate::application::auth::AuthenticatedUser;
 use crate::application::errors::{ApiError, AppError};
-use crate::application::routes::api::images::save_deferred_image;
+use crate::application::routes::api::images::{
+    prepare_extraction_image, resolve_thumbnail_urls, save_deferred_image,
+};
 use crate::application::routes::api::macros::{
-    define_delete_handler, define_enriched_get_handler, define_list_fragment_renderer,
+    define_delete_handler, define_delete_preview_handler, define_enriched_get_handler,
+    define_favourite_handler, define_list_fragment_renderer,
+};
+use crate::application::routes::api::processes::{
+    closest_process, load_process_taxonomy, resolve_process,
 };
+use crate::application::routes::api::version::{ApiVersion, PageQuery};
 use crate::application::routes::support::{
-    FlexiblePayload, ListQuery, PayloadSource, impl_has_changes, is_datastar_request,
-    render_redirect_script, update_response, validate_update,
+    FlexiblePayload, FragmentQuery, ListQuery, PatchMode, PayloadSource, impl_has_changes,
+    is_datastar_request, new_row_mode, render_redirect_script, render_row_fragment,
+    update_response, validate_update,
 };
 use crate::application::state::AppState;
+use crate::domain::blends::{BlendComponent, blend_label, blend_origin, parse_blend};
+use crate::domain::brews::RoastBrewStats;
+use crate::domain::countries::regions::{ProducingRegion, normalize_region, suggest_regions};
 use crate::domain::entity_type::EntityType;
 use crate::domain::ids::{RoastId, RoasterId};
 use crate::domain::images::ImageData;
-use crate::domain::listing::{ListRequest, SortDirection};
-use crate::domain::roasts::{NewRoast, RoastSortKey, RoastWithRoaster, UpdateRoast};
+use crate::domain::listing::{ListRequest, Page, SortDirection};
+use crate::domain::roasts::{
+    FieldSuggestion, NewLotRequest, NewRoast, RoastComparison, RoastField, RoastSortKey,
+    RoastWithRoaster, UpdateRoast,
+};
+use crate::domain::validation::Validate;
 use crate::infrastructure::ai::{self, ExtractionInput};
-use crate::presentation::web::templates::{RoastListTemplate, RoastOptionsTemplate};
+use crate::presentation::web::templates::{
+    RoastListTemplate, RoastOptionsTemplate, RoastRowTemplate,
+};
 use crate::presentation::web::views::{ListNavigator, Paginated, RoastView};
 use tracing::info;
 
 const ROAST_PAGE_PATH: &str = "/data?type=roasts";
 const ROAST_FRAGMENT_PATH: &str = "/data?type=roasts#roast-list";
+const ROAST_ROWS_SELECTOR: &str = "#roast-list tbody";
 
 #[tracing::instrument(skip(state))]
 pub(crate) async fn load_roast_page(
@@ -40,38 +58,45 @@ pub(crate) async fn load_roast_page(
         .list(&request, search)
         .await
         .map_err(AppError::from)?;
+    let ids: Vec<i64> = page.items.iter().map(|item| item.roast.id.into()).collect();
+    let mut thumbnails = resolve_thumbnail_urls(state, EntityType::Roast, &ids).await;
 
     Ok(crate::application::routes::support::build_page_view(
         page,
         request,
-        RoastView::from_list_item,
+        |item| {
+            let thumbnail = thumbnails.remove(&i64::from(item.roast.id));
+            RoastView::from_list_item(item).with_thumbnail(thumbnail)
+        },
         ROAST_PAGE_PATH,
         ROAST_FRAGMENT_PATH,
         search.map(String::from),
     ))
 }
 
-#[tracing::instrument(skip(state, _auth_user, headers, query))]
+#[tracing::instrument(skip(state, auth_user, headers, query, fragment))]
 pub(crate) async fn create_roast(
     State(state): State<AppState>,
-    _auth_user: AuthenticatedUser,
+    auth_user: AuthenticatedUser,
     headers: HeaderMap,
     Query(query): Query<ListQuery>,
+    Query(fragment): Query<FragmentQuery>,
     payload: FlexiblePayload<NewRoastSubmission>,
 ) -> Result<Response, ApiError> {
     let (request, search) = query.into_request_and_search::<RoastSortKey>();
     let (submission, source) = payload.into_parts();
-    let (new_roast, image_data_url) = submission.into_parts().map_err(ApiError::from)?;
+    let (mut new_roast, image_data_url) = submission.into_parts().map_err(ApiError::from)?;
 
     state
         .roaster_repo
         .get(new_roast.roaster_id)
         .await
         .map_err(|err| ApiError::from(AppError::from(err)))?;
+    new_roast.process = resolve_process(&state, &new_roast.process).await?;
 
     let roast = state
         .roast_service
-        .create(new_roast)
+        .create(new_roast, Some(auth_user.0.id))
         .await
         .map_err(AppError::from)?;
 
@@ -93,6 +118,21 @@ pub(crate) async fn create_roast(
         .map_err(AppError::from)?;
     let detail_url = format!("/roasters/{}/roasts/{}", roaster.slug, roast.slug);
 
+    if fragment.wants_row(&headers, "type=roasts")
+        && let Some(mode) = new_row_mode(&request, search.as_deref())
+    {
+        let roast_id = i64::from(roast.id);
+        let thumbnail = resolve_thumbnail_urls(&state, EntityType::Roast, &[roast_id])
+            .await
+            .remove(&roast_id);
+        let row = RoastRowTemplate {
+            is_authenticated: true,
+            roast: RoastView::from_domain(roast, &roaster.name, &roaster.slug)
+                .with_thumbnail(thumbnail),
+        };
+        return render_row_fragment(row, ROAST_ROWS_SELECTOR, mode).map_err(ApiError::from);
+    }
+
     if is_datastar_request(&headers) {
         let from_data_page = headers
             .get("referer")
@@ -121,8 +161,10 @@ pub(crate) async fn create_roast(
 #[tracing::instrument(skip(state, headers))]
 pub(crate) async fn list_roasts(
     State(state): State<AppState>,
+    version: ApiVersion,
     headers: HeaderMap,
     Query(params): Query<RoastsQuery>,
+    Query(paging): Query<PageQuery>,
 ) -> Result<Response, ApiError> {
     let roaster_id = match params.roaster_id.as_deref() {
         Some(s) if !s.is_empty() => {
@@ -151,11 +193,16 @@ pub(crate) async fn list_roasts(
     };
 
     if is_datastar_request(&headers) {
+        // Favourites lead the picker; the sort is stable so name order holds.
+        let mut roasts = roasts;
+        roasts.sort_by_key(|r| !r.roast.favourite);
         let template = RoastOptionsTemplate { roasts };
         crate::application::routes::support::render_fragment(template, "#roast-select-options")
             .map_err(ApiError::from)
     } else {
-        Ok(Json(roasts).into_response())
+        // The roasts are already in list order; this only cuts out the page.
+        let request = version.list_request(&paging, RoastSortKey::CreatedAt, SortDirection::Desc);
+        Ok(version.list_response(Page::from_sorted(roasts, &request)))
     }
 }
 
@@ -178,6 +225,123 @@ define_delete_handler!(
     image_type: crate::domain::entity_type::EntityType::Roast
 );
 
+define_delete_preview_handler!(roast_delete_preview, RoastId, roast_repo);
+
+define_favourite_handler!(favourite_roast, RoastId, roast_repo, "roasts");
+
+/// POST /api/v1/roasts/{id}/lots — start a new lot of this roast for another harvest
+#[tracing::instrument(skip(state, auth_user, headers, payload))]
+pub(crate) async fn create_roast_lot(
+    State(state): State<AppState>,
+    auth_user: AuthenticatedUser,
+    headers: HeaderMap,
+    Path(id): Path<RoastId>,
+    payload: FlexiblePayload<NewLotRequest>,
+) -> Result<Response, ApiError> {
+    let (request, source) = payload.into_parts();
+    let original = state.roast_repo.get(id).await.map_err(AppError::from)?;
+    let new_lot = original.new_lot(request.harvest_year);
+    new_lot.validate().map_err(AppError::from)?;
+
+    let lot = state
+        .roast_service
+        .create(new_lot, Some(auth_user.0.id))
+        .await
+        .map_err(AppError::from)?;
+
+    info!(roast_id = %lot.id, from = %id, harvest_year = request.harvest_year, "roast lot created");
+    state.stats_invalidator.invalidate();
+
+    let enriched = state
+        .roast_repo
+        .get_with_roaster(lot.id)
+        .await
+        .map_err(AppError::from)?;
+    let detail_url = format!(
+        "/roasters/{}/roasts/{}",
+        enriched.roaster_slug, enriched.roast.slug
+    );
+
+    if is_datastar_request(&headers) {
+        render_redirect_script(&detail_url).map_err(ApiError::from)
+    } else if matches!(source, PayloadSource::Form) {
+        Ok(Redirect::to(&detail_url).into_response())
+    } else {
+        Ok((StatusCode::CREATED, Json(enriched)).into_response())
+    }
+}
+
+/// Upper bound on the number of roasts shown side by side.
+pub(crate) const MAX_COMPARED_ROASTS: usize = 4;
+
+/// Collect the distinct `roast` IDs from a query string, in the order given.
+pub(crate) fn parse_compared_roasts(params: &[(String, String)]) -> Result<Vec<RoastId>, String> {
+    let mut ids: Vec<RoastId> = Vec::new();
+    for (key, value) in params {
+        if key != "roast" || value.trim().is_empty() {
+            continue;
+        }
+        let id = value
+            .trim()
+            .parse()
+            .map_err(|_| format!("invalid roast id '{value}'"))?;
+        if !ids.contains(&id) {
+            ids.push(id);
+        }
+    }
+
+    if ids.len() > MAX_COMPARED_ROASTS {
+        return Err(format!(
+            "at most {MAX_COMPARED_ROASTS} roasts can be compared"
+        ));
+    }
+    Ok(ids)
+}
+
+pub(crate) async fn load_roast_comparison(
+    state: &AppState,
+    ids: &[RoastId],
+) -> Result<Vec<RoastComparison>, AppError> {
+    let mut comparison = Vec::with_capacity(ids.len());
+    for &id in ids {
+        let roast = state.roast_repo.get_with_roaster(id).await?;
+        let brew_stats = state.brew_repo.roast_stats(id).await?;
+        comparison.push(RoastComparison { roast, brew_stats });
+    }
+    Ok(comparison)
+}
+
+#[tracing::instrument(skip(state))]
+pub(crate) async fn compare_roasts(
+    State(state): State<AppState>,
+    Query(params): Query<Vec<(String, String)>>,
+) -> Result<Json<Vec<RoastComparison>>, ApiError> {
+    let ids = parse_compared_roasts(&params).map_err(AppError::validation)?;
+    if ids.len() < 2 {
+        return Err(AppError::validation("select at least two roasts to compare").into());
+    }
+
+    let comparison = load_roast_comparison(&state, &ids).await?;
+    Ok(Json(comparison))
+}
+
+#[tracing::instrument(skip(state))]
+pub(crate) async fn roast_brew_stats(
+    State(state): State<AppState>,
+    Path(id): Path<RoastId>,
+) -> Result<Json<RoastBrewStats>, ApiError> {
+    // Resolve the roast first so unknown IDs 404 rather than returning zeros.
+    state.roast_repo.get(id).await.map_err(AppError::from)?;
+
+    let brew_stats = state
+        .brew_repo
+        .roast_stats(id)
+        .await
+        .map_err(AppError::from)?;
+
+    Ok(Json(brew_stats))
+}
+
 #[derive(Debug, Deserialize)]
 pub(crate) struct UpdateRoastSubmission {
     #[serde(default)]
@@ -195,24 +359,35 @@ pub(crate) struct UpdateRoastSubmission {
     #[serde(default)]
     process: Option<String>,
     #[serde(default)]
+    components: Option<BlendInput>,
+    #[serde(default, deserialize_with = "deserialize_optional_year")]
+    harvest_year: Option<i32>,
+    #[serde(default)]
     created_at: Option<DateTime<Utc>>,
     #[serde(default)]
     image: ImageData,
 }
 
 impl UpdateRoastSubmission {
-    fn into_parts(self) -> (UpdateRoast, Option<String>) {
+    fn into_parts(self) -> Result<(UpdateRoast, Option<String>), AppError> {
+        let components = self.components.map(BlendInput::into_vec).transpose()?;
+        let origin = match &components {
+            Some(components) if !components.is_empty() => Some(blend_origin(components)),
+            _ => self.origin,
+        };
         let update = UpdateRoast {
             roaster_id: self.roaster_id,
             name: self.name,
-            origin: self.origin,
+            origin,
             region: self.region,
             producer: self.producer,
             tasting_notes: self.tasting_notes.map(TastingNotesInput::into_vec),
             process: self.process,
+            components,
+            harvest_year: self.harvest_year,
             created_at: self.created_at,
         };
-        (update, self.image.into_inner())
+        Ok((update, self.image.into_inner()))
     }
 }
 
@@ -225,21 +400,27 @@ impl_has_changes!(
     producer,
     tasting_notes,
     process,
+    components,
+    harvest_year,
     created_at
 );
 
-#[tracing::instrument(skip(state, _auth_user, headers))]
+#[tracing::instrument(skip(state, _auth_user, headers, fragment))]
 pub(crate) async fn update_roast(
     State(state): State<AppState>,
     _auth_user: AuthenticatedUser,
     headers: HeaderMap,
     Path(id): Path<RoastId>,
+    Query(fragment): Query<FragmentQuery>,
     payload: FlexiblePayload<UpdateRoastSubmission>,
 ) -> Result<Response, ApiError> {
     let (submission, source) = payload.into_parts();
-    let (update, image_data_url) = submission.into_parts();
+    let (mut update, image_data_url) = submission.into_parts().map_err(ApiError::from)?;
 
     validate_update(&update, image_data_url.as_ref())?;
+    update.validate().map_err(AppError::from)?;
+    normalize_update_region(&state, id, &mut update).await?;
+    normalize_update_process(&state, id, &mut update).await?;
 
     state
         .roast_repo
@@ -267,6 +448,18 @@ pub(crate) async fn update_roast(
         .await
         .map_err(AppError::from)?;
 
+    if fragment.wants_row(&headers, "type=roasts") {
+        let selector = format!("#roast-row-{id}");
+        let thumbnail = resolve_thumbnail_urls(&state, EntityType::Roast, &[id.into()])
+            .await
+            .remove(&i64::from(id));
+        let row = RoastRowTemplate {
+            is_authenticated: true,
+            roast: RoastView::from_list_item(enriched).with_thumbnail(thumbnail),
+        };
+        return render_row_fragment(row, &selector, PatchMode::Replace).map_err(ApiError::from);
+    }
+
     let detail_url = format!(
         "/roasters/{}/roasts/{}",
         enriched.roaster_slug, enriched.roast.slug
@@ -280,6 +473,118 @@ pub(crate) async fn update_roast(
     )
 }
 
+/// Check an updated origin or region against the producing-region data,
+/// filling in whichever side is unchanged from the stored roast.
+async fn normalize_update_region(
+    state: &AppState,
+    id: RoastId,
+    update: &mut UpdateRoast,
+) -> Result<(), ApiError> {
+    if update.origin.is_none() && update.region.is_none() {
+        return Ok(());
+    }
+
+    let existing = state.roast_repo.get(id).await.map_err(AppError::from)?;
+    let origin = update
+        .origin
+        .as_deref()
+        .or(existing.origin.as_deref())
+        .unwrap_or_default();
+    let Some(region) = update.region.as_deref().or(existing.region.as_deref()) else {
+        return Ok(());
+    };
+
+    let normalized = normalize_region(origin, region).map_err(AppError::validation)?;
+    if update.region.is_some() {
+        update.region = Some(normalized);
+    }
+    Ok(())
+}
+
+/// Roasts logged before the taxonomy can be edited without touching their
+/// process; a changed process has to be one the taxonomy knows.
+async fn normalize_update_process(
+    state: &AppState,
+    id: RoastId,
+    update: &mut UpdateRoast,
+) -> Result<(), ApiError> {
+    let Some(process) = update.process.as_deref() else {
+        return Ok(());
+    };
+
+    let existing = state.roast_repo.get(id).await.map_err(AppError::from)?;
+    let unchanged = existing
+        .process
+        .as_deref()
+        .is_some_and(|current| current.eq_ignore_ascii_case(process.trim()));
+    if !unchanged {
+        update.process = Some(resolve_process(state, process).await?);
+    }
+    Ok(())
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RegionsQuery {
+    #[serde(default)]
+    pub q: String,
+    /// Comma-separated origin countries used to narrow the suggestions.
+    #[serde(default)]
+    pub origin: Option<String>,
+}
+
+#[tracing::instrument]
+pub(crate) async fn list_regions(
+    Query(query): Query<RegionsQuery>,
+) -> Json<Vec<&'static ProducingRegion>> {
+    Json(suggest_regions(&query.q, query.origin.as_deref()))
+}
+
+const DEFAULT_AUTOCOMPLETE_LIMIT: u32 = 10;
+const MAX_AUTOCOMPLETE_LIMIT: u32 = 50;
+
+#[derive(Debug, Deserialize)]
+pub struct AutocompleteQuery {
+    /// One of `origin`, `process`, `producer` or `tasting_note`.
+    pub field: String,
+    #[serde(default)]
+    pub q: String,
+    #[serde(default)]
+    pub limit: Option<u32>,
+}
+
+#[tracing::instrument(skip(state))]
+pub(crate) async fn autocomplete(
+    State(state): State<AppState>,
+    Query(query): Query<AutocompleteQuery>,
+) -> Result<Json<Vec<FieldSuggestion>>, ApiError> {
+    let field: RoastField = query.field.parse().map_err(|()| {
+        AppError::validation(format!("unknown autocomplete field '{}'", query.field))
+    })?;
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_AUTOCOMPLETE_LIMIT)
+        .clamp(1, MAX_AUTOCOMPLETE_LIMIT);
+
+    if field == RoastField::Process {
+        // Offer the taxonomy, ranked by how often each process has been used.
+        let used = state
+            .roast_repo
+            .suggest(field, "", MAX_AUTOCOMPLETE_LIMIT)
+            .await
+            .map_err(AppError::from)?;
+        let taxonomy = load_process_taxonomy(&state).await?;
+        return Ok(Json(taxonomy.suggest(&query.q, &used, limit as usize)));
+    }
+
+    let suggestions = state
+        .roast_repo
+        .suggest(field, &query.q, limit)
+        .await
+        .map_err(AppError::from)?;
+
+    Ok(Json(suggestions))
+}
+
 #[derive(Debug, Deserialize)]
 pub struct RoastsQuery {
     pub roaster_id: Option<String>,
@@ -289,57 +594,74 @@ pub struct RoastsQuery {
 pub(crate) struct NewRoastSubmission {
     roaster_id: RoasterId,
     name: String,
+    /// May be blank for a blend, whose origins it then lists.
     origin: String,
     region: String,
     producer: String,
     tasting_notes: TastingNotesInput,
     process: String,
     #[serde(default)]
+    components: Option<BlendInput>,
+    #[serde(default, deserialize_with = "deserialize_optional_year")]
+    harvest_year: Option<i32>,
+    #[serde(default)]
     created_at: Option<DateTime<Utc>>,
     #[serde(default)]
     image: ImageData,
 }
 
+/// Deserializes an optional harvest year, treating empty strings (from HTML forms) as None.
+fn deserialize_optional_year<'de, D>(deserializer: D) -> Result<Option<i32>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value: Option<serde_json::Value> = Option::deserialize(deserializer)?;
+    match value {
+        None | Some(serde_json::Value::Null) => Ok(None),
+        Some(serde_json::Value::String(s)) if s.trim().is_empty() => Ok(None),
+        Some(serde_json::Value::Number(n)) => n
+            .as_i64()
+            .and_then(|year| i32::try_from(year).ok())
+            .map(Some)
+            .ok_or_else(|| serde::de::Error::custom("invalid harvest year")),
+        Some(serde_json::Value::String(s)) => s
+            .trim()
+            .parse::<i32>()
+            .map(Some)
+            .map_err(|_| serde::de::Error::custom("invalid harvest year")),
+        Some(_) => Err(serde::de::Error::custom("invalid harvest year")),
+    }
+}
+
 impl NewRoastSubmission {
     fn into_parts(self) -> Result<(NewRoast, Option<String>), AppError> {
-        fn require(field: &str, value: String) -> Result<String, AppError> {
-            let trimmed = value.trim();
-            if trimmed.is_empty() {
-                Err(AppError::validation(format!("{field} is required")))
-            } else {
-                Ok(trimmed.to_string())
-            }
-        }
-
-        let roaster_id = self.roaster_id;
-        if roaster_id.into_inner() <= 0 {
-            return Err(AppError::validation("invalid roaster id"));
-        }
-        let name = require("name", self.name)?;
-        let origin = require("origin", self.origin)?;
-        let region = require("region", self.region)?;
-        let producer = require("producer", self.producer)?;
-        let process = require("process", self.process)?;
-
-        let tasting_notes = self.tasting_notes.into_vec();
-
-        if tasting_notes.is_empty() {
-            return Err(AppError::validation("tasting notes are required"));
-        }
+        let components = self
+            .components
+            .map(BlendInput::into_vec)
+            .transpose()?
+            .unwrap_or_default();
+        let origin = if components.is_empty() {
+            self.origin.trim().to_string()
+        } else {
+            blend_origin(&components)
+        };
+        let mut roast = NewRoast {
+            roaster_id: self.roaster_id,
+            name: self.name.trim().to_string(),
+            origin,
+            region: self.region.trim().to_string(),
+            producer: self.producer.trim().to_string(),
+            tasting_notes: self.tasting_notes.into_vec(),
+            process: self.process.trim().to_string(),
+            components,
+            harvest_year: self.harvest_year,
+            created_at: self.created_at,
+        };
+        roast.validate()?;
+        roast.region =
+            normalize_region(&roast.origin, &roast.region).map_err(AppError::validation)?;
 
-        Ok((
-            NewRoast {
-                roaster_id,
-                name,
-                origin,
-                region,
-                producer,
-                tasting_notes,
-                process,
-                created_at: self.created_at,
-            },
-            self.image.into_inner(),
-        ))
+        Ok((roast, self.image.into_inner()))
     }
 }
 
@@ -367,6 +689,30 @@ impl TastingNotesInput {
     }
 }
 
+/// A blend as a list of components, or as text such as
+/// `Ethiopia 60%, Colombia 40%` from a form.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub(crate) enum BlendInput {
+    List(Vec<BlendComponent>),
+    Text(String),
+}
+
+impl BlendInput {
+    pub(crate) fn into_vec(self) -> Result<Vec<BlendComponent>, AppError> {
+        match self {
+            BlendInput::List(components) => Ok(components
+                .into_iter()
+                .map(|component| BlendComponent {
+                    origin: component.origin.trim().to_string(),
+                    ..component
+                })
+                .collect()),
+            BlendInput::Text(text) => parse_blend(&text).map_err(AppError::validation),
+        }
+    }
+}
+
 #[tracing::instrument(skip(state, auth_user, headers, payload))]
 pub(crate) async fn extract_roast_info(
     State(state): State<AppState>,
@@ -374,16 +720,13 @@ pub(crate) async fn extract_roast_info(
     headers: HeaderMap,
     payload: FlexiblePayload<ExtractionInput>,
 ) -> Result<Response, ApiError> {
-    let (input, _) = payload.into_parts();
-    let (result, usage) = ai::extract_roast(
-        &state.http_client,
-        &state.openrouter_url,
-        &state.openrouter_api_key,
-        &state.openrouter_model,
-        &input,
-    )
-    .await
-    .map_err(ApiError::from)?;
+    let (mut input, _) = payload.into_parts();
+    prepare_extraction_image(&state, &mut input).await?;
+    let (mut result, usage) = match ai::extract_roast(&state.openrouter(), &input).await {
+        Ok(extracted) => extracted,
+        Err(err) => return crate::application::routes::support::ai_fallback(&headers, err),
+    };
+    result.process = closest_process(&state, result.process).await?;
 
     crate::application::routes::support::record_ai_usage(
         state.ai_usage_repo.clone(),
@@ -427,6 +770,10 @@ pub(crate) async fn extract_roast_info(
                 Value::String(result.process.unwrap_or_default()),
             ),
             ("_tasting-notes", Value::String(tasting_notes)),
+            (
+                "_components",
+                Value::String(blend_label(&result.components.unwrap_or_default())),
+            ),
             ("_roaster-id", Value::String(roaster_id)),
             ("_extracted", Value::Bool(true)),
         ];
@@ -449,10 +796,17 @@ async fn match_roaster_id(state: &AppState, roaster_name: &str) -> Option<String
 
     let lower = roaster_name.to_lowercase();
 
-    // Exact match first
+    // Exact match first, then a name the roaster used to go by
     if let Some(roaster) = roasters.iter().find(|r| r.name.to_lowercase() == lower) {
         return Some(roaster.id.to_string());
     }
+    if let Ok(Some(id)) = state
+        .alias_repo
+        .find_roaster(&slug::slugify(roaster_name))
+        .await
+    {
+        return Some(id.to_string());
+    }
 
     // Substring match (either direction)
     roasters
@@ -2,22 +2,33 @@ use axum::Json;
 use axum::extract::State;
 use axum::http::{HeaderMap, StatusCode};
 use axum::response::{IntoResponse, Response};
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 use tracing::info;
 
 use crate::application::auth::AuthenticatedUser;
 use crate::application::errors::{ApiError, AppError};
-use crate::application::routes::api::images::{resolve_image_url, save_deferred_image};
-use crate::application::routes::api::roasts::TastingNotesInput;
+use crate::application::routes::api::aliases::find_roaster;
+use crate::application::routes::api::bags::{deserialize_optional_date, deserialize_optional_text};
+use crate::application::routes::api::images::{
+    prepare_extraction_image, resolve_image_url, save_deferred_image,
+};
+use crate::application::routes::api::processes::{
+    closest_process, load_process_taxonomy, resolve_process,
+};
+use crate::application::routes::api::roasts::{BlendInput, TastingNotesInput};
 use crate::application::routes::support::{FlexiblePayload, is_datastar_request};
 use crate::application::state::AppState;
 use crate::domain::bags::NewBag;
+use crate::domain::blends::{blend_label, blend_origin};
 use crate::domain::entity_type::EntityType;
 use crate::domain::errors::RepositoryError;
-use crate::domain::ids::RoastId;
+use crate::domain::ids::{BagId, RoastId, RoasterId, UserId};
 use crate::domain::images::ImageData;
+use crate::domain::matching::ScanMatches;
 use crate::domain::roasters::NewRoaster;
 use crate::domain::roasts::NewRoast;
+use crate::domain::validation::Validate;
 use crate::infrastructure::ai::{self, ExtractionInput, Usage};
 
 #[tracing::instrument(skip(state, auth_user, headers, payload))]
@@ -27,16 +38,13 @@ pub(crate) async fn extract_bag_scan(
     headers: HeaderMap,
     payload: FlexiblePayload<ExtractionInput>,
 ) -> Result<Response, ApiError> {
-    let (input, _) = payload.into_parts();
-    let (result, usage) = ai::extract_bag_scan(
-        &state.http_client,
-        &state.openrouter_url,
-        &state.openrouter_api_key,
-        &state.openrouter_model,
-        &input,
-    )
-    .await
-    .map_err(ApiError::from)?;
+    let (mut input, _) = payload.into_parts();
+    prepare_extraction_image(&state, &mut input).await?;
+    let (mut result, usage) = match ai::extract_bag_scan(&state.openrouter(), &input).await {
+        Ok(extracted) => extracted,
+        Err(err) => return crate::application::routes::support::ai_fallback(&headers, err),
+    };
+    result.roast.process = closest_process(&state, result.roast.process).await?;
 
     crate::application::routes::support::record_ai_usage(
         state.ai_usage_repo.clone(),
@@ -46,12 +54,21 @@ pub(crate) async fn extract_bag_scan(
         usage,
     );
 
-    // Try to match existing roaster/roast by slug
-    let (matched_roaster_id, matched_roast_id) = match_existing_entities(&state, &result).await;
+    let matches = match_existing_entities(&state, &result).await?;
 
     if is_datastar_request(&headers) {
         use serde_json::Value;
 
+        // A confident match shows the record's own name, not the label's.
+        let (matched_roaster_id, roaster_name) = match matches.confident_roaster() {
+            Some(roaster) => (roaster.id.to_string(), Some(roaster.name.clone())),
+            None => (String::new(), result.roaster.name),
+        };
+        let (matched_roast_id, roast_name) = match matches.confident_roast() {
+            Some(roast) => (roast.roast.id.to_string(), Some(roast.roast.name.clone())),
+            None => (String::new(), result.roast.name),
+        };
+
         let tasting_notes = result
             .roast
             .tasting_notes
@@ -62,7 +79,7 @@ pub(crate) async fn extract_bag_scan(
         let signals = vec![
             (
                 "_roaster-name",
-                Value::String(result.roaster.name.unwrap_or_default()),
+                Value::String(roaster_name.unwrap_or_default()),
             ),
             (
                 "_roaster-country",
@@ -76,10 +93,7 @@ pub(crate) async fn extract_bag_scan(
                 "_roaster-homepage",
                 Value::String(result.roaster.homepage.unwrap_or_default()),
             ),
-            (
-                "_roast-name",
-                Value::String(result.roast.name.unwrap_or_default()),
-            ),
+            ("_roast-name", Value::String(roast_name.unwrap_or_default())),
             (
                 "_origin",
                 Value::String(result.roast.origin.unwrap_or_default()),
@@ -97,60 +111,62 @@ pub(crate) async fn extract_bag_scan(
                 Value::String(result.roast.process.unwrap_or_default()),
             ),
             ("_tasting-notes", Value::String(tasting_notes)),
+            (
+                "_components",
+                Value::String(blend_label(&result.roast.components.unwrap_or_default())),
+            ),
+            (
+                "_best-before",
+                Value::String(
+                    result
+                        .bag
+                        .best_before
+                        .map(|d| d.to_string())
+                        .unwrap_or_default(),
+                ),
+            ),
+            (
+                "_batch-code",
+                Value::String(result.bag.batch_code.unwrap_or_default()),
+            ),
             ("_scan-extracted", Value::Bool(true)),
             ("_matched-roaster-id", Value::String(matched_roaster_id)),
             ("_matched-roast-id", Value::String(matched_roast_id)),
+            ("_roaster-candidates", candidates_signal(&matches.roasters)?),
+            ("_roast-candidates", candidates_signal(&matches.roasts)?),
         ];
         crate::application::routes::support::render_signals_json(&signals).map_err(ApiError::from)
     } else {
-        Ok(Json(result).into_response())
+        Ok(Json(ScanExtraction { result, matches }).into_response())
     }
 }
 
-/// Check if the extracted roaster/roast already exist by slug matching.
-/// Returns `(matched_roaster_id, matched_roast_id)` as strings (empty if no match).
+fn candidates_signal<T: Serialize>(candidates: &[T]) -> Result<serde_json::Value, AppError> {
+    serde_json::to_value(candidates)
+        .map_err(|err| AppError::unexpected(format!("failed to serialize candidates: {err}")))
+}
+
+/// What a bag scan read off the label, alongside the existing roasters and
+/// roasts it might name.
+#[derive(Debug, Serialize)]
+struct ScanExtraction {
+    #[serde(flatten)]
+    result: ai::ExtractedBagScan,
+    matches: ScanMatches,
+}
+
+/// Rank existing roasters and roasts against the extracted names, counting
+/// the names they went by before.
 async fn match_existing_entities(
     state: &AppState,
     result: &ai::ExtractedBagScan,
-) -> (String, String) {
+) -> Result<ScanMatches, AppError> {
     let roaster_name = result.roaster.name.as_deref().unwrap_or_default();
-    let roaster_country = result.roaster.country.as_deref().unwrap_or_default();
-    if roaster_name.is_empty() {
-        return (String::new(), String::new());
-    }
-
-    let temp_roaster = NewRoaster {
-        name: roaster_name.to_string(),
-        country: roaster_country.to_string(),
-        city: result.roaster.city.clone(),
-        homepage: None,
-        created_at: None,
-    }
-    .normalize();
-    let roaster_slug = temp_roaster.slug();
-
-    let Ok(existing_roaster) = state.roaster_repo.get_by_slug(&roaster_slug).await else {
-        return (String::new(), String::new());
-    };
-
-    let matched_roaster_id = existing_roaster.id.into_inner().to_string();
-
     let roast_name = result.roast.name.as_deref().unwrap_or_default();
-    if roast_name.is_empty() {
-        return (matched_roaster_id, String::new());
-    }
-
-    let roast_slug = slug::slugify(roast_name);
-    let matched_roast_id = match state
-        .roast_repo
-        .get_by_slug(existing_roaster.id, &roast_slug)
-        .await
-    {
-        Ok(r) => r.id.into_inner().to_string(),
-        Err(_) => String::new(),
-    };
-
-    (matched_roaster_id, matched_roast_id)
+    Ok(state
+        .matching_service
+        .match_scan(roaster_name, roast_name)
+        .await?)
 }
 
 fn default_tasting_notes() -> TastingNotesInput {
@@ -182,9 +198,17 @@ pub(crate) struct BagScanSubmission {
     #[serde(default = "default_tasting_notes")]
     tasting_notes: TastingNotesInput,
     #[serde(default)]
+    components: Option<BlendInput>,
+    #[serde(default)]
     open_bag: Option<String>,
     #[serde(default)]
     bag_amount: Option<f64>,
+    #[serde(default, deserialize_with = "deserialize_optional_date")]
+    best_before: Option<NaiveDate>,
+    #[serde(default, deserialize_with = "deserialize_optional_text")]
+    batch_code: Option<String>,
+    #[serde(default)]
+    matched_roaster_id: Option<String>,
     #[serde(default)]
     matched_roast_id: Option<String>,
     #[serde(default)]
@@ -202,20 +226,13 @@ struct ScanResult {
 async fn extract_into_submission(
     state: &AppState,
     submission: &mut BagScanSubmission,
-) -> Result<Option<Usage>, ApiError> {
-    let input = ExtractionInput {
+) -> Result<Option<Usage>, AppError> {
+    let mut input = ExtractionInput {
         image: submission.image.take(),
         prompt: submission.prompt.take(),
     };
-    let (result, usage) = ai::extract_bag_scan(
-        &state.http_client,
-        &state.openrouter_url,
-        &state.openrouter_api_key,
-        &state.openrouter_model,
-        &input,
-    )
-    .await
-    .map_err(ApiError::from)?;
+    prepare_extraction_image(state, &mut input).await?;
+    let (result, usage) = ai::extract_bag_scan(&state.openrouter(), &input).await?;
 
     if let Some(name) = result.roaster.name {
         submission.roaster_name = name;
@@ -247,6 +264,15 @@ async fn extract_into_submission(
     if let Some(notes) = result.roast.tasting_notes {
         submission.tasting_notes = TastingNotesInput::Text(notes.join(", "));
     }
+    if let Some(components) = result.roast.components {
+        submission.components = Some(BlendInput::List(components));
+    }
+    if result.bag.best_before.is_some() {
+        submission.best_before = result.bag.best_before;
+    }
+    if result.bag.batch_code.is_some() {
+        submission.batch_code = result.bag.batch_code;
+    }
 
     Ok(usage)
 }
@@ -262,9 +288,19 @@ pub(crate) async fn submit_scan(
     let (mut submission, _) = payload.into_parts();
 
     // If the roast already exists (matched during extraction), skip creation
-    if let Some(roast_id) = parse_matched_roast_id(submission.matched_roast_id.as_ref()) {
+    if let Some(roast_id) =
+        parse_matched_id(submission.matched_roast_id.as_ref()).map(RoastId::from)
+    {
         let scan_image = submission.scan_image.take();
-        return submit_existing_roast(&state, &headers, roast_id, &submission, scan_image).await;
+        return submit_existing_roast(
+            &state,
+            &headers,
+            auth_user.0.id,
+            roast_id,
+            &submission,
+            scan_image,
+        )
+        .await;
     }
 
     // Check for raw input (image/prompt triggers extraction first)
@@ -280,7 +316,10 @@ pub(crate) async fn submit_scan(
         .filter(|s| !s.is_empty());
 
     if has_raw_input {
-        let usage = extract_into_submission(&state, &mut submission).await?;
+        let usage = match extract_into_submission(&state, &mut submission).await {
+            Ok(usage) => usage,
+            Err(err) => return crate::application::routes::support::ai_fallback(&headers, err),
+        };
         crate::application::routes::support::record_ai_usage(
             state.ai_usage_repo.clone(),
             auth_user.0.id,
@@ -302,14 +341,22 @@ pub(crate) async fn submit_scan(
 
     let slug = new_roaster.slug();
 
-    // Try to find existing roaster by slug, otherwise create
-    let roaster = match state.roaster_repo.get_by_slug(&slug).await {
+    // Use the roaster picked in review, else find one by slug or a previous
+    // name, otherwise create
+    let matched_roaster = match parse_matched_id(submission.matched_roaster_id.as_ref()) {
+        Some(id) => state.roaster_repo.get(RoasterId::from(id)).await,
+        None => find_roaster(&state, &slug).await,
+    };
+    let roaster = match matched_roaster {
         Ok(existing) => existing,
-        Err(RepositoryError::NotFound) => state
-            .roaster_service
-            .create(new_roaster)
-            .await
-            .map_err(AppError::from)?,
+        Err(RepositoryError::NotFound) => {
+            new_roaster.validate().map_err(AppError::from)?;
+            state
+                .roaster_service
+                .create(new_roaster, Some(auth_user.0.id))
+                .await
+                .map_err(AppError::from)?
+        }
         Err(err) => return Err(AppError::from(err).into()),
     };
 
@@ -318,6 +365,16 @@ pub(crate) async fn submit_scan(
     if !has_raw_input && tasting_notes.is_empty() {
         return Err(AppError::validation("tasting notes are required").into());
     }
+    let components = submission
+        .components
+        .map(BlendInput::into_vec)
+        .transpose()?
+        .unwrap_or_default();
+    let origin = if components.is_empty() {
+        submission.origin
+    } else {
+        blend_origin(&components)
+    };
 
     let new_roast = if has_raw_input {
         if submission.roast_name.trim().is_empty() {
@@ -325,14 +382,22 @@ pub(crate) async fn submit_scan(
                 AppError::validation("could not extract a roast name from the image/text").into(),
             );
         }
+        // The extracted wording can't be corrected first, so settle for the
+        // nearest process in the taxonomy rather than rejecting the scan.
+        let process = match submission.process.trim() {
+            "" => String::new(),
+            process => load_process_taxonomy(&state).await?.closest(process),
+        };
         NewRoast {
             roaster_id: roaster.id,
             name: submission.roast_name.trim().to_string(),
-            origin: submission.origin.trim().to_string(),
+            origin: origin.trim().to_string(),
             region: submission.region.trim().to_string(),
             producer: submission.producer.trim().to_string(),
-            process: submission.process.trim().to_string(),
+            process,
             tasting_notes,
+            components,
+            harvest_year: None,
             created_at: None,
         }
     } else {
@@ -348,50 +413,36 @@ pub(crate) async fn submit_scan(
         NewRoast {
             roaster_id: roaster.id,
             name: require("roast name", &submission.roast_name)?,
-            origin: require("origin", &submission.origin)?,
+            origin: require("origin", &origin)?,
             region: require("region", &submission.region)?,
             producer: require("producer", &submission.producer)?,
-            process: require("process", &submission.process)?,
+            process: resolve_process(&state, &require("process", &submission.process)?).await?,
             tasting_notes,
+            components,
+            harvest_year: None,
             created_at: None,
         }
     };
 
     let roast = state
         .roast_service
-        .create(new_roast)
+        .create(new_roast, Some(auth_user.0.id))
         .await
         .map_err(AppError::from)?;
 
     info!(roaster_id = %roaster.id, roast_id = %roast.id, roast_name = %roast.name, "scan created roast");
 
-    save_deferred_image(
+    let bag_id = open_bag_if_requested(
         &state,
-        EntityType::Roast,
-        roast.id.into_inner(),
-        scan_image.as_deref(),
+        submission.open_bag.as_deref(),
+        submission.bag_amount,
+        submission.best_before,
+        submission.batch_code,
+        roast.id,
+        auth_user.0.id,
     )
-    .await;
-
-    // Optionally create a bag
-    let wants_bag = submission
-        .open_bag
-        .as_deref()
-        .is_some_and(|v| v == "true" || v == "on");
-    if wants_bag {
-        let amount = submission.bag_amount.unwrap_or(250.0);
-        let new_bag = NewBag {
-            roast_id: roast.id,
-            roast_date: None,
-            amount,
-            created_at: None,
-        };
-        state
-            .bag_service
-            .create(new_bag)
-            .await
-            .map_err(AppError::from)?;
-    }
+    .await?;
+    save_scan_image(&state, roast.id, bag_id, scan_image.as_deref()).await;
 
     let redirect = format!("/roasters/{}/roasts/{}", roaster.slug, roast.slug);
     let roast_id = roast.id.into_inner();
@@ -409,18 +460,72 @@ pub(crate) async fn submit_scan(
     }
 }
 
-fn parse_matched_roast_id(value: Option<&String>) -> Option<RoastId> {
+/// Create a bag of the roast when the submission's `open_bag` field asks for one.
+async fn open_bag_if_requested(
+    state: &AppState,
+    open_bag: Option<&str>,
+    bag_amount: Option<f64>,
+    best_before: Option<NaiveDate>,
+    batch_code: Option<String>,
+    roast_id: RoastId,
+    user_id: UserId,
+) -> Result<Option<BagId>, ApiError> {
+    if !open_bag.is_some_and(|v| v == "true" || v == "on") {
+        return Ok(None);
+    }
+    let new_bag = NewBag {
+        roast_id,
+        roast_date: None,
+        amount: bag_amount.unwrap_or(250.0),
+        price: None,
+        currency: None,
+        best_before,
+        batch_code,
+        created_at: None,
+    };
+    new_bag.validate().map_err(AppError::from)?;
+    let bag = state
+        .bag_service
+        .create(new_bag, Some(user_id))
+        .await
+        .map_err(AppError::from)?;
+    Ok(Some(bag.id))
+}
+
+/// A scan photo shows the bag in hand, so it belongs to the bag it opened.
+/// The roast also takes it as artwork if it has none yet.
+async fn save_scan_image(
+    state: &AppState,
+    roast_id: RoastId,
+    bag_id: Option<BagId>,
+    scan_image: Option<&str>,
+) {
+    if scan_image.is_none_or(str::is_empty) {
+        return;
+    }
+    if let Some(bag_id) = bag_id {
+        save_deferred_image(state, EntityType::Bag, bag_id.into_inner(), scan_image).await;
+    }
+    if resolve_image_url(state, EntityType::Roast, roast_id.into_inner())
+        .await
+        .is_none()
+    {
+        save_deferred_image(state, EntityType::Roast, roast_id.into_inner(), scan_image).await;
+    }
+}
+
+fn parse_matched_id(value: Option<&String>) -> Option<i64> {
     value
         .map(String::as_str)
         .filter(|s| !s.is_empty())
         .and_then(|s| s.parse::<i64>().ok())
-        .map(RoastId::from)
 }
 
 /// Handle submission when the roast already exists — only create a bag if requested.
 async fn submit_existing_roast(
     state: &AppState,
     headers: &HeaderMap,
+    user_id: UserId,
     roast_id: RoastId,
     submission: &BagScanSubmission,
     scan_image: Option<String>,
@@ -434,39 +539,20 @@ async fn submit_existing_roast(
     let roast = &roast_with_roaster.roast;
     let roaster_slug = &roast_with_roaster.roaster_slug;
 
-    // Save scan image if roast doesn't have one yet
-    if resolve_image_url(state, EntityType::Roast, roast.id.into_inner())
-        .await
-        .is_none()
-    {
-        save_deferred_image(
-            state,
-            EntityType::Roast,
-            roast.id.into_inner(),
-            scan_image.as_deref(),
-        )
-        .await;
-    }
-
-    let wants_bag = submission
-        .open_bag
-        .as_deref()
-        .is_some_and(|v| v == "true" || v == "on");
-    if wants_bag {
-        let amount = submission.bag_amount.unwrap_or(250.0);
-        let new_bag = NewBag {
-            roast_id: roast.id,
-            roast_date: None,
-            amount,
-            created_at: None,
-        };
-        state
-            .bag_service
-            .create(new_bag)
-            .await
-            .map_err(AppError::from)?;
+    let bag_id = open_bag_if_requested(
+        state,
+        submission.open_bag.as_deref(),
+        submission.bag_amount,
+        submission.best_before,
+        submission.batch_code.clone(),
+        roast.id,
+        user_id,
+    )
+    .await?;
+    if bag_id.is_some() {
         info!(roast_id = %roast.id, roast_name = %roast.name, "scan opened bag for existing roast");
     }
+    save_scan_image(state, roast.id, bag_id, scan_image.as_deref()).await;
 
     let redirect = format!("/roasters/{}/roasts/{}", roaster_slug, roast.slug);
     let roast_id_raw = roast.id.into_inner();
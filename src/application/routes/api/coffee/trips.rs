@@ -0,0 +1,142 @@
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use tracing::{info, warn};
+
+use crate::application::auth::AuthenticatedUser;
+use crate::application::errors::{ApiError, AppError};
+use crate::application::state::AppState;
+use crate::domain::cups::{CupFilter, CupSortKey};
+use crate::domain::entity_type::EntityType;
+use crate::domain::ids::TripId;
+use crate::domain::listing::{ListRequest, SortDirection};
+use crate::domain::trips::{NewTrip, Trip, TripSummary, UpdateTrip};
+use crate::domain::validation::Validate;
+
+#[tracing::instrument(skip(state))]
+pub(crate) async fn list_trips(State(state): State<AppState>) -> Result<Json<Vec<Trip>>, ApiError> {
+    let trips = state.trip_repo.list().await.map_err(AppError::from)?;
+
+    Ok(Json(trips))
+}
+
+#[tracing::instrument(skip(state, auth_user))]
+pub(crate) async fn create_trip(
+    State(state): State<AppState>,
+    auth_user: AuthenticatedUser,
+    Json(payload): Json<NewTrip>,
+) -> Result<Response, ApiError> {
+    let payload = payload.normalize();
+    payload.validate().map_err(AppError::from)?;
+
+    let trip = state
+        .trip_service
+        .create(payload, Some(auth_user.0.id))
+        .await
+        .map_err(AppError::from)?;
+
+    info!(trip_id = %trip.id, name = %trip.name, "trip created");
+    Ok((StatusCode::CREATED, Json(trip)).into_response())
+}
+
+#[tracing::instrument(skip(state))]
+pub(crate) async fn get_trip(
+    State(state): State<AppState>,
+    Path(id): Path<TripId>,
+) -> Result<Json<Trip>, ApiError> {
+    let trip = state.trip_repo.get(id).await.map_err(AppError::from)?;
+
+    Ok(Json(trip))
+}
+
+#[tracing::instrument(skip(state, _auth_user))]
+pub(crate) async fn update_trip(
+    State(state): State<AppState>,
+    _auth_user: AuthenticatedUser,
+    Path(id): Path<TripId>,
+    Json(payload): Json<UpdateTrip>,
+) -> Result<Json<Trip>, ApiError> {
+    let mut payload = payload.normalize();
+    // Check a lone new start or end date against the stored other end.
+    let existing = state.trip_repo.get(id).await.map_err(AppError::from)?;
+    payload.start_date.get_or_insert(existing.start_date);
+    payload.end_date.get_or_insert(existing.end_date);
+    payload.validate().map_err(AppError::from)?;
+
+    let trip = state
+        .trip_repo
+        .update(id, payload)
+        .await
+        .map_err(AppError::from)?;
+
+    info!(trip_id = %trip.id, "trip updated");
+    state
+        .timeline_invalidator
+        .invalidate(EntityType::Trip, i64::from(trip.id));
+    Ok(Json(trip))
+}
+
+#[tracing::instrument(skip(state, _auth_user))]
+pub(crate) async fn delete_trip(
+    State(state): State<AppState>,
+    _auth_user: AuthenticatedUser,
+    Path(id): Path<TripId>,
+) -> Result<StatusCode, ApiError> {
+    state.trip_repo.delete(id).await.map_err(AppError::from)?;
+
+    if let Err(err) = state
+        .timeline_repo
+        .delete_by_entity(EntityType::Trip, i64::from(id))
+        .await
+    {
+        warn!(trip_id = %id, error = %err, "failed to delete trip timeline event");
+    }
+
+    info!(trip_id = %id, "trip deleted");
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[tracing::instrument(skip(state))]
+pub(crate) async fn get_trip_summary(
+    State(state): State<AppState>,
+    Path(id): Path<TripId>,
+) -> Result<Json<TripSummary>, ApiError> {
+    let trip = state.trip_repo.get(id).await.map_err(AppError::from)?;
+    let summary = load_trip_summary(&state, trip).await?;
+
+    Ok(Json(summary))
+}
+
+/// Gather the cups drunk during a trip, in the order they were drunk, and
+/// the cafes visited: those a cup was drunk at, plus any added while away.
+pub(crate) async fn load_trip_summary(
+    state: &AppState,
+    trip: Trip,
+) -> Result<TripSummary, AppError> {
+    let request = ListRequest::show_all(CupSortKey::CreatedAt, SortDirection::Asc);
+    let cups = state
+        .cup_repo
+        .list(
+            CupFilter::between(trip.start_date, trip.end_date),
+            &request,
+            None,
+        )
+        .await
+        .map_err(AppError::from)?
+        .items;
+
+    let cafes = state
+        .cafe_repo
+        .list_all()
+        .await
+        .map_err(AppError::from)?
+        .into_iter()
+        .filter(|cafe| {
+            trip.includes(cafe.created_at.date_naive())
+                || cups.iter().any(|cwd| cwd.cup.cafe_id == Some(cafe.id))
+        })
+        .collect();
+
+    Ok(TripSummary { trip, cups, cafes })
+}
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use axum::body::Body;
 use axum::extract::{Path, State};
 use axum::http::{HeaderMap, StatusCode, header};
@@ -9,9 +11,13 @@ use crate::application::auth::AuthenticatedUser;
 use crate::application::errors::{ApiError, AppError};
 use crate::application::routes::support::{FlexiblePayload, is_datastar_request, render_fragment};
 use crate::application::state::AppState;
+use crate::domain::ErrorCode;
 use crate::domain::entity_type::EntityType;
 use crate::domain::images::EntityImage;
-use crate::infrastructure::image_processing::process_data_url;
+use crate::infrastructure::ai::ExtractionInput;
+use crate::infrastructure::image_processing::{
+    ImageError, heic_data_url_to_jpeg, process_data_url,
+};
 use crate::presentation::web::templates::ImageUploadTemplate;
 
 #[derive(Debug, Deserialize)]
@@ -25,6 +31,42 @@ pub(crate) struct ImagePath {
     pub id: i64,
 }
 
+/// A `400 Bad Request` that says which limit an oversized image broke.
+pub(crate) fn image_error(err: ImageError) -> AppError {
+    if err.is_too_large() {
+        AppError::validation_with(ErrorCode::ImageTooLarge, err)
+    } else {
+        AppError::validation(format!("invalid image: {err}"))
+    }
+}
+
+/// Convert a HEIC photo in an AI extraction request to JPEG, which the
+/// models can read. Other images are sent as they are.
+pub(crate) async fn prepare_extraction_image(
+    state: &AppState,
+    input: &mut ExtractionInput,
+) -> Result<(), AppError> {
+    let Some(data_url) = input.image.clone().filter(|s| !s.trim().is_empty()) else {
+        return Ok(());
+    };
+    let _permit = state
+        .image_semaphore
+        .acquire()
+        .await
+        .map_err(|_| AppError::unexpected("image processing unavailable"))?;
+
+    let settings = state.image_settings;
+    let converted =
+        tokio::task::spawn_blocking(move || heic_data_url_to_jpeg(&data_url, &settings))
+            .await
+            .map_err(|e| AppError::unexpected(format!("image processing task failed: {e}")))?
+            .map_err(image_error)?;
+    if let Some(jpeg) = converted {
+        input.image = Some(jpeg);
+    }
+    Ok(())
+}
+
 fn parse_entity_type(entity_type: &str) -> Result<EntityType, ApiError> {
     entity_type
         .parse::<EntityType>()
@@ -36,7 +78,9 @@ async fn validate_entity_exists(
     entity_type: EntityType,
     id: i64,
 ) -> Result<(), ApiError> {
-    use crate::domain::ids::{BagId, BrewId, CafeId, CupId, GearId, RoastId, RoasterId};
+    use crate::domain::ids::{
+        BagId, BrewId, CafeId, CupId, EventId, GearId, RoastId, RoasterId, UserId,
+    };
 
     match entity_type {
         EntityType::Roaster => {
@@ -88,6 +132,23 @@ async fn validate_entity_exists(
                 .await
                 .map_err(AppError::from)?;
         }
+        EntityType::Event => {
+            state
+                .event_repo
+                .get(EventId::from(id))
+                .await
+                .map_err(AppError::from)?;
+        }
+        EntityType::Trip => {
+            return Err(AppError::validation("trips do not have images").into());
+        }
+        EntityType::User => {
+            state
+                .user_repo
+                .get(UserId::from(id))
+                .await
+                .map_err(AppError::from)?;
+        }
     }
 
     Ok(())
@@ -113,10 +174,11 @@ pub(crate) async fn upload_image(
         .map_err(|_| AppError::unexpected("image processing unavailable"))?;
 
     let image_data = upload.image;
-    let processed = tokio::task::spawn_blocking(move || process_data_url(&image_data))
+    let settings = state.image_settings;
+    let processed = tokio::task::spawn_blocking(move || process_data_url(&image_data, &settings))
         .await
         .map_err(|e| AppError::unexpected(format!("image processing task failed: {e}")))?
-        .map_err(|e| AppError::validation(format!("invalid image: {e}")))?;
+        .map_err(image_error)?;
 
     let image = EntityImage {
         entity_type,
@@ -199,6 +261,7 @@ pub(crate) async fn delete_image(
         .map_err(AppError::from)?;
 
     info!(entity_type = %path.entity_type, entity_id = path.id, "image deleted");
+    state.image_purger.schedule();
 
     if is_datastar_request(&headers) {
         render_fragment(
@@ -230,6 +293,30 @@ pub(crate) async fn resolve_image_url(
         .then(|| format!("/api/v1/{entity_type}/{entity_id}/image"))
 }
 
+/// Thumbnail URLs for a page of list rows, keyed by entity ID. Rows without
+/// an image are left out. A failed lookup only costs the thumbnails, so it is
+/// logged rather than failing the page.
+pub(crate) async fn resolve_thumbnail_urls(
+    state: &AppState,
+    entity_type: EntityType,
+    entity_ids: &[i64],
+) -> HashMap<i64, String> {
+    match state
+        .image_repo
+        .ids_with_images(entity_type, entity_ids)
+        .await
+    {
+        Ok(ids) => ids
+            .into_iter()
+            .map(|id| (id, format!("/api/v1/{entity_type}/{id}/thumbnail")))
+            .collect(),
+        Err(err) => {
+            tracing::warn!(entity_type = entity_type.as_str(), error = %err, "failed to look up list thumbnails");
+            HashMap::new()
+        }
+    }
+}
+
 /// Process and save a deferred image upload (from a create form).
 /// Called after entity creation when the form included an image data URL.
 /// Accepts `Option<&str>` and no-ops on `None` or empty strings.
@@ -253,7 +340,12 @@ pub(crate) async fn save_deferred_image(
     };
 
     let data_url = data_url.to_string();
-    let processed = match tokio::task::spawn_blocking(move || process_data_url(&data_url)).await {
+    let settings = state.image_settings;
+    let processed = match tokio::task::spawn_blocking(move || {
+        process_data_url(&data_url, &settings)
+    })
+    .await
+    {
         Ok(Ok(p)) => p,
         Ok(Err(err)) => {
             tracing::warn!(entity_type = entity_type_str, entity_id, error = %err, "failed to process deferred image");
@@ -57,12 +57,46 @@ macro_rules! define_enriched_get_handler {
     };
 }
 
+/// Generates a GET handler that reports what deleting an entity would affect.
+///
+/// # Arguments
+/// * `$fn_name` - Name of the generated handler function
+/// * `$id_type` - Type of the ID path parameter (e.g., `RoasterId`)
+/// * `$repo_field` - Name of the repository field on `AppState` (e.g., `roaster_repo`)
+///
+/// # Example
+/// ```ignore
+/// define_delete_preview_handler!(roaster_delete_preview, RoasterId, roaster_repo);
+/// ```
+macro_rules! define_delete_preview_handler {
+    ($fn_name:ident, $id_type:ty, $repo_field:ident) => {
+        #[tracing::instrument(skip(state, _auth_user))]
+        pub(crate) async fn $fn_name(
+            axum::extract::State(state): axum::extract::State<crate::application::state::AppState>,
+            _auth_user: crate::application::auth::AuthenticatedUser,
+            axum::extract::Path(id): axum::extract::Path<$id_type>,
+        ) -> Result<
+            axum::Json<crate::domain::delete_preview::DeletePreview>,
+            crate::application::errors::ApiError,
+        > {
+            let preview = state
+                .$repo_field
+                .delete_preview(id)
+                .await
+                .map_err(crate::application::errors::AppError::from)?;
+            Ok(axum::Json(preview))
+        }
+    };
+}
+
 /// Generates a DELETE handler with Datastar fragment re-rendering support.
 ///
 /// When a Datastar request arrives from the data/list page (detected via referer
 /// containing `$referer_match`), the handler re-renders the list fragment. When the
 /// request comes from elsewhere (e.g. a detail page), it returns a redirect script
 /// pointing at `$redirect_url`. Non-Datastar requests get a 204 No Content.
+/// The entity's timeline events are removed, along with any left pointing at
+/// rows the delete cascaded to.
 ///
 /// # Arguments
 /// * `$fn_name` - Name of the generated handler function
@@ -119,9 +153,15 @@ macro_rules! define_delete_handler {
             if let Err(err) = state.timeline_repo.delete_by_entity($entity_type, i64::from(id)).await {
                 tracing::warn!(%id, error = %err, "failed to delete timeline event");
             }
+            // Deleting a roaster, roast or bag cascades to the rows under it,
+            // whose events would otherwise be left behind.
+            if let Err(err) = state.timeline_repo.delete_orphaned().await {
+                tracing::warn!(%id, error = %err, "failed to prune orphaned timeline events");
+            }
 
             tracing::info!(%id, "entity deleted");
             state.stats_invalidator.invalidate();
+            state.image_purger.schedule();
 
             if crate::application::routes::support::is_datastar_request(&headers) {
                 let from_data_page = headers
@@ -144,6 +184,61 @@ macro_rules! define_delete_handler {
     };
 }
 
+/// Generates a PUT/DELETE handler that stars or unstars an entity.
+///
+/// `PUT` marks the entity as a favourite and `DELETE` clears it. Datastar
+/// requests get the re-rendered star toggle back; others get a 204 No Content.
+///
+/// # Arguments
+/// * `$fn_name` - Name of the generated handler function
+/// * `$id_type` - Type of the ID path parameter (e.g., `RoasterId`)
+/// * `$repo_field` - Name of the repository field on `AppState` (e.g., `roaster_repo`)
+/// * `$api_path` - Collection segment of the API path (e.g., `"roasters"`)
+///
+/// # Example
+/// ```ignore
+/// define_favourite_handler!(favourite_roaster, RoasterId, roaster_repo, "roasters");
+/// ```
+macro_rules! define_favourite_handler {
+    ($fn_name:ident, $id_type:ty, $repo_field:ident, $api_path:literal) => {
+        #[tracing::instrument(skip(state, _auth_user, headers))]
+        pub(crate) async fn $fn_name(
+            axum::extract::State(state): axum::extract::State<crate::application::state::AppState>,
+            _auth_user: crate::application::auth::AuthenticatedUser,
+            method: axum::http::Method,
+            headers: axum::http::HeaderMap,
+            axum::extract::Path(id): axum::extract::Path<$id_type>,
+        ) -> Result<axum::response::Response, crate::application::errors::ApiError> {
+            let favourite = method == axum::http::Method::PUT;
+            state
+                .$repo_field
+                .set_favourite(id, favourite)
+                .await
+                .map_err(crate::application::errors::AppError::from)?;
+
+            tracing::info!(%id, favourite, "favourite updated");
+
+            if crate::application::routes::support::is_datastar_request(&headers) {
+                let toggle = crate::presentation::web::templates::FavouriteToggleTemplate {
+                    api_path: $api_path,
+                    id: id.to_string(),
+                    favourite,
+                };
+                crate::application::routes::support::render_row_fragment(
+                    toggle,
+                    &format!("#favourite-{}-{id}", $api_path),
+                    crate::application::routes::support::PatchMode::Replace,
+                )
+                .map_err(crate::application::errors::ApiError::from)
+            } else {
+                Ok(axum::response::IntoResponse::into_response(
+                    axum::http::StatusCode::NO_CONTENT,
+                ))
+            }
+        }
+    };
+}
+
 /// Generates a list-fragment renderer for Datastar partial updates.
 ///
 /// Produces a function that loads a page via `$loader`, builds the given list
@@ -186,6 +281,8 @@ macro_rules! define_list_fragment_renderer {
 }
 
 pub(crate) use define_delete_handler;
+pub(crate) use define_delete_preview_handler;
 pub(crate) use define_enriched_get_handler;
+pub(crate) use define_favourite_handler;
 pub(crate) use define_get_handler;
 pub(crate) use define_list_fragment_renderer;
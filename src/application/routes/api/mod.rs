@@ -4,82 +4,225 @@ pub(crate) mod coffee;
 pub(crate) mod images;
 pub(crate) mod macros;
 pub(crate) mod system;
+pub mod version;
 
 // Re-exports for backward compatibility
-pub(crate) use analytics::stats;
-pub(crate) use auth::{tokens, webauthn};
-pub(crate) use coffee::{bags, brews, cafes, checkin, cups, gear, roasters, roasts, scan};
-pub(crate) use system::{admin, backup, timeline};
+pub(crate) use analytics::{quick_actions, stats};
+pub(crate) use auth::{
+    list_preferences, notifications, profile, registration_tokens, saved_searches, tokens, webauthn,
+};
+pub(crate) use coffee::{
+    aliases, bags, brew_parsing, brew_shares, brews, bulk, cafes, checkin, comments, cups, events,
+    gear, green_coffees, grinder_calibrations, processes, quick_notes, roast_batches, roasters,
+    roasts, scan, trips,
+};
+pub(crate) use system::{
+    admin, backfill, backup, export, import, integrity, nav_search, qr, settings, timeline,
+};
 
 use axum::extract::DefaultBodyLimit;
-use axum::routing::{get, post};
+use axum::routing::{delete, get, post, put};
 
 use crate::application::state::AppState;
 
+/// `image_body_limit` caps requests that carry an image; see
+/// [`ImageSettings::body_limit`](crate::infrastructure::image_processing::ImageSettings::body_limit).
 #[allow(clippy::too_many_lines)]
-pub(super) fn router() -> axum::Router<AppState> {
+pub(super) fn router(image_body_limit: usize) -> axum::Router<AppState> {
     axum::Router::new()
         .route(
             "/roasters",
             get(roasters::list_roasters).post(roasters::create_roaster),
         )
+        .route("/roasters/bulk", post(bulk::bulk_roasters))
+        .route("/roasters/quick-add", post(roasters::quick_add_roaster))
         .route(
             "/roasters/{id}",
             get(roasters::get_roaster)
                 .put(roasters::update_roaster)
                 .delete(roasters::delete_roaster),
         )
+        .route(
+            "/roasters/{id}/delete-preview",
+            get(roasters::roaster_delete_preview),
+        )
+        .route(
+            "/roasters/{id}/favourite",
+            put(roasters::favourite_roaster).delete(roasters::favourite_roaster),
+        )
+        .route(
+            "/roasters/{id}/aliases",
+            get(aliases::list_roaster_aliases).post(aliases::create_roaster_alias),
+        )
+        .route("/roasters/{id}/merge", post(aliases::merge_roaster))
         .route(
             "/roasts",
             get(roasts::list_roasts).post(roasts::create_roast),
         )
+        .route("/roasts/bulk", post(bulk::bulk_roasts))
         .route(
             "/roasts/{id}",
             get(roasts::get_roast)
                 .put(roasts::update_roast)
                 .delete(roasts::delete_roast),
         )
+        .route(
+            "/roasts/{id}/delete-preview",
+            get(roasts::roast_delete_preview),
+        )
+        .route(
+            "/roasts/{id}/favourite",
+            put(roasts::favourite_roast).delete(roasts::favourite_roast),
+        )
+        .route("/roasts/{id}/stats", get(roasts::roast_brew_stats))
+        .route("/roasts/{id}/lots", post(roasts::create_roast_lot))
+        .route(
+            "/roasts/{id}/aliases",
+            get(aliases::list_roast_aliases).post(aliases::create_roast_alias),
+        )
+        .route("/roasts/{id}/merge", post(aliases::merge_roast))
+        .route("/aliases/{id}", delete(aliases::delete_alias))
+        .route(
+            "/roasts/{id}/comments",
+            get(comments::list_roast_comments).post(comments::create_roast_comment),
+        )
+        .route("/compare", get(roasts::compare_roasts))
+        .route("/regions", get(roasts::list_regions))
+        .route("/autocomplete", get(roasts::autocomplete))
+        .route("/countries", get(roasters::list_countries))
         .route("/bags", get(bags::list_bags).post(bags::create_bag))
+        .route("/bags/bulk", post(bulk::bulk_bags))
         .route(
             "/bags/{id}",
             get(bags::get_bag)
                 .put(bags::update_bag)
                 .delete(bags::delete_bag),
         )
+        .route("/bags/{id}/reopen", post(bags::reopen_bag))
+        .route("/bags/{id}/ledger", get(bags::list_bag_ledger))
+        .route("/bags/{id}/brew-series", get(bags::bag_brew_series))
+        .route("/bags/{id}/adjustments", post(bags::create_bag_adjustment))
+        .route("/bags/{id}/transfers", post(bags::create_bag_transfer))
         .route("/gear", get(gear::list_gear).post(gear::create_gear))
+        .route("/gear/bulk", post(bulk::bulk_gear))
         .route(
             "/gear/{id}",
             get(gear::get_gear)
                 .put(gear::update_gear)
                 .delete(gear::delete_gear),
         )
+        .route("/gear/{id}/restock", post(gear::restock_gear))
+        .route(
+            "/grinder-calibrations",
+            get(grinder_calibrations::list_grinder_calibrations)
+                .post(grinder_calibrations::create_grinder_calibration),
+        )
+        .route(
+            "/grinder-calibrations/convert",
+            get(grinder_calibrations::convert_grind_setting),
+        )
+        .route(
+            "/grinder-calibrations/{id}",
+            delete(grinder_calibrations::delete_grinder_calibration),
+        )
+        .route(
+            "/green-coffees",
+            get(green_coffees::list_green_coffees).post(green_coffees::create_green_coffee),
+        )
+        .route(
+            "/green-coffees/{id}",
+            get(green_coffees::get_green_coffee)
+                .put(green_coffees::update_green_coffee)
+                .delete(green_coffees::delete_green_coffee),
+        )
+        .route(
+            "/events",
+            get(events::list_events).post(events::create_event),
+        )
+        .route(
+            "/events/{id}",
+            get(events::get_event)
+                .put(events::update_event)
+                .delete(events::delete_event),
+        )
+        .route("/trips", get(trips::list_trips).post(trips::create_trip))
+        .route(
+            "/trips/{id}",
+            get(trips::get_trip)
+                .put(trips::update_trip)
+                .delete(trips::delete_trip),
+        )
+        .route("/trips/{id}/summary", get(trips::get_trip_summary))
+        .route(
+            "/roast-batches",
+            get(roast_batches::list_roast_batches).post(roast_batches::create_roast_batch),
+        )
+        .route(
+            "/roast-batches/{id}",
+            get(roast_batches::get_roast_batch)
+                .put(roast_batches::update_roast_batch)
+                .delete(roast_batches::delete_roast_batch),
+        )
+        .route(
+            "/quick-notes",
+            get(quick_notes::list_quick_notes).post(quick_notes::create_quick_note),
+        )
+        .route("/quick-notes/{id}", delete(quick_notes::delete_quick_note))
+        .route(
+            "/processes",
+            get(processes::list_processes).post(processes::create_process),
+        )
+        .route("/processes/{id}", delete(processes::delete_process))
         .route("/brews", get(brews::list_brews).post(brews::create_brew))
+        .route("/brews/bulk", post(bulk::bulk_brews))
+        .route("/parse-brew", post(brew_parsing::parse_brew))
         .route(
             "/brews/{id}",
             get(brews::get_brew)
                 .put(brews::update_brew)
                 .delete(brews::delete_brew),
         )
+        .route("/brews/{id}/share", post(brew_shares::create_brew_share))
+        .route("/brews/{id}/shares", get(brew_shares::list_brew_shares))
+        .route("/shares/{id}/revoke", post(brew_shares::revoke_brew_share))
+        .route(
+            "/brews/{id}/comments",
+            get(comments::list_brew_comments).post(comments::create_brew_comment),
+        )
+        .route(
+            "/comments/{id}",
+            put(comments::update_comment).delete(comments::delete_comment),
+        )
         .route("/cafes", get(cafes::list_cafes).post(cafes::create_cafe))
+        .route("/cafes/bulk", post(bulk::bulk_cafes))
         .route(
             "/cafes/{id}",
             get(cafes::get_cafe)
                 .put(cafes::update_cafe)
                 .delete(cafes::delete_cafe),
         )
+        .route(
+            "/cafes/{id}/delete-preview",
+            get(cafes::cafe_delete_preview),
+        )
+        .route(
+            "/cafes/{id}/favourite",
+            put(cafes::favourite_cafe).delete(cafes::favourite_cafe),
+        )
         .route("/nearby-cafes", get(cafes::nearby_cafes))
         .route("/extract-roaster", post(roasters::extract_roaster))
         .route("/extract-roast", post(roasts::extract_roast_info))
         .route(
             "/extract-bag-scan",
-            post(scan::extract_bag_scan).layer(DefaultBodyLimit::max(10 * 1024 * 1024)),
+            post(scan::extract_bag_scan).layer(DefaultBodyLimit::max(image_body_limit)),
         )
         .route(
             "/scan",
-            post(scan::submit_scan).layer(DefaultBodyLimit::max(10 * 1024 * 1024)),
+            post(scan::submit_scan).layer(DefaultBodyLimit::max(image_body_limit)),
         )
         .route("/check-in", post(checkin::submit_checkin))
         .route("/cups", get(cups::list_cups).post(cups::create_cup))
+        .route("/cups/bulk", post(bulk::bulk_cups))
         .route(
             "/cups/{id}",
             get(cups::get_cup)
@@ -91,25 +234,107 @@ pub(super) fn router() -> axum::Router<AppState> {
             post(tokens::create_token).get(tokens::list_tokens),
         )
         .route("/tokens/{id}/revoke", post(tokens::revoke_token))
+        .route(
+            "/registration-tokens",
+            post(registration_tokens::create_registration_token)
+                .get(registration_tokens::list_registration_tokens),
+        )
+        .route(
+            "/registration-tokens/{id}/revoke",
+            post(registration_tokens::revoke_registration_token),
+        )
+        .route(
+            "/list-preferences",
+            get(list_preferences::list_list_preferences),
+        )
+        .route(
+            "/list-preferences/{list}",
+            axum::routing::put(list_preferences::update_list_preference)
+                .delete(list_preferences::delete_list_preference),
+        )
+        .route(
+            "/saved-searches",
+            get(saved_searches::list_saved_searches).post(saved_searches::create_saved_search),
+        )
+        .route(
+            "/saved-searches/{id}",
+            get(saved_searches::get_saved_search)
+                .put(saved_searches::update_saved_search)
+                .delete(saved_searches::delete_saved_search),
+        )
+        .route("/me", get(profile::get_me).put(profile::update_me))
+        .route("/notifications", get(notifications::list_notifications))
+        .route(
+            "/notifications/read-all",
+            post(notifications::mark_all_notifications_read),
+        )
+        .route(
+            "/notifications/{id}/read",
+            post(notifications::mark_notification_read),
+        )
         .route("/passkeys", get(admin::list_passkeys))
         .route(
             "/passkeys/{id}",
             axum::routing::delete(admin::delete_passkey),
         )
+        .route("/sessions", get(admin::list_sessions))
+        .route(
+            "/sessions/{id}",
+            axum::routing::delete(admin::revoke_session),
+        )
         .route("/backup", get(backup::export_backup))
         .route(
             "/backup/restore",
             post(backup::restore_backup).layer(DefaultBodyLimit::max(50 * 1024 * 1024)),
         )
+        .route(
+            "/backup/restore/stream",
+            post(backup::restore_backup_stream).layer(DefaultBodyLimit::disable()),
+        )
         .route("/backup/reset", post(backup::reset_database))
+        .route("/backfill", post(backfill::backfill_history))
+        .route("/export/beanconqueror", get(export::export_beanconqueror))
+        .route(
+            "/import/beanconqueror",
+            post(import::import_beanconqueror).layer(DefaultBodyLimit::max(50 * 1024 * 1024)),
+        )
+        .route("/admin/overview", get(admin::get_overview))
+        .route("/admin/routes", get(admin::list_routes))
+        .route("/admin/integrity", get(integrity::check_integrity))
+        .route("/admin/integrity/repair", post(integrity::repair_integrity))
+        .route(
+            "/admin/normalize-countries",
+            post(admin::normalize_roaster_countries),
+        )
+        .route(
+            "/settings",
+            get(settings::get_settings).put(settings::update_settings),
+        )
+        .route("/exchange-rates", get(settings::list_exchange_rates))
+        .route(
+            "/exchange-rates/{currency}",
+            put(settings::set_exchange_rate).delete(settings::delete_exchange_rate),
+        )
+        .route("/ai-prices", get(settings::list_ai_prices))
+        .route(
+            "/ai-prices/{*model}",
+            put(settings::set_ai_price).delete(settings::delete_ai_price),
+        )
+        .route("/quick-actions", get(quick_actions::list_quick_actions))
+        .route("/stats", get(stats::get_stats))
+        .route("/stats/habits", get(stats::get_habits))
+        .route("/stats/roasters", get(stats::get_roaster_leaderboard))
         .route("/stats/recompute", post(stats::recompute_stats))
+        .route("/timeline", get(timeline::list_timeline))
         .route("/timeline/rebuild", post(timeline::rebuild_timeline))
+        .route("/qr", get(qr::qr_code))
+        .route("/nav-search", get(nav_search::nav_search))
         .route(
             "/{entity_type}/{id}/image",
             get(images::get_image)
                 .put(images::upload_image)
                 .delete(images::delete_image)
-                .layer(DefaultBodyLimit::max(10 * 1024 * 1024)),
+                .layer(DefaultBodyLimit::max(image_body_limit)),
         )
         .route("/{entity_type}/{id}/thumbnail", get(images::get_thumbnail))
 }
@@ -3,11 +3,19 @@ use axum::extract::{Path, State};
 use axum::http::StatusCode;
 use chrono::{DateTime, Utc};
 use serde::Serialize;
-use tracing::error;
+use tower_cookies::Cookies;
+use tracing::{error, info};
 
-use crate::application::auth::AuthenticatedUser;
+use crate::application::auth::{AuthenticatedUser, SESSION_COOKIE_NAME};
+use crate::application::errors::{ApiError, AppError};
+use crate::application::routes::route_table::{API_ROUTES, RouteInfo};
 use crate::application::state::AppState;
-use crate::domain::ids::PasskeyCredentialId;
+use crate::domain::countries::canonical_country;
+use crate::domain::entity_type::EntityType;
+use crate::domain::ids::{PasskeyCredentialId, RoasterId, SessionId};
+use crate::domain::roasters::UpdateRoaster;
+use crate::infrastructure::auth::hash_token;
+use crate::infrastructure::overview::InstanceOverview;
 
 #[derive(Serialize)]
 pub struct PasskeyResponse {
@@ -17,6 +25,18 @@ pub struct PasskeyResponse {
     pub last_used_at: Option<DateTime<Utc>>,
 }
 
+#[derive(Serialize)]
+pub struct SessionResponse {
+    pub id: i64,
+    pub device: Option<String>,
+    pub last_seen_ip: Option<String>,
+    pub last_seen_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    /// Whether this is the session the request was made with.
+    pub current: bool,
+}
+
 pub(crate) async fn list_passkeys(
     State(state): State<AppState>,
     auth_user: AuthenticatedUser,
@@ -79,3 +99,149 @@ pub(crate) async fn delete_passkey(
 
     Ok(StatusCode::NO_CONTENT)
 }
+
+/// The hash of the session cookie the request carries, if any.
+pub(crate) fn current_session_hash(cookies: &Cookies) -> Option<String> {
+    cookies
+        .get(SESSION_COOKIE_NAME)
+        .map(|cookie| hash_token(cookie.value()))
+}
+
+/// GET /api/v1/sessions — the user's signed-in devices, most recently active
+/// first
+pub(crate) async fn list_sessions(
+    State(state): State<AppState>,
+    auth_user: AuthenticatedUser,
+    cookies: Cookies,
+) -> Result<Json<Vec<SessionResponse>>, StatusCode> {
+    let sessions = state
+        .session_repo
+        .list_by_user(auth_user.0.id)
+        .await
+        .map_err(|err| {
+            error!(error = %err, "failed to list sessions");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    let current = current_session_hash(&cookies);
+    let responses = sessions
+        .into_iter()
+        .map(|s| SessionResponse {
+            id: i64::from(s.id),
+            current: current.as_deref() == Some(s.session_token_hash.as_str()),
+            device: s.device_label,
+            last_seen_ip: s.last_seen_ip,
+            last_seen_at: s.last_seen_at,
+            created_at: s.created_at,
+            expires_at: s.expires_at,
+        })
+        .collect();
+
+    Ok(Json(responses))
+}
+
+/// DELETE /api/v1/sessions/{id} — sign a device out
+pub(crate) async fn revoke_session(
+    State(state): State<AppState>,
+    auth_user: AuthenticatedUser,
+    Path(session_id): Path<SessionId>,
+) -> Result<StatusCode, StatusCode> {
+    let session = state.session_repo.get(session_id).await.map_err(|err| {
+        error!(error = %err, %session_id, "failed to get session for revocation");
+        StatusCode::NOT_FOUND
+    })?;
+
+    if session.user_id != auth_user.0.id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    state.session_repo.delete(session_id).await.map_err(|err| {
+        error!(error = %err, %session_id, "failed to revoke session");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    info!(%session_id, user_id = %auth_user.0.id, "session revoked");
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// GET /api/v1/admin/overview — instance statistics (requires authentication)
+pub(crate) async fn get_overview(
+    State(state): State<AppState>,
+    _auth_user: AuthenticatedUser,
+) -> Result<Json<InstanceOverview>, ApiError> {
+    let overview = state
+        .overview_service
+        .overview()
+        .await
+        .map_err(|e| AppError::unexpected(e.to_string()))?;
+    Ok(Json(overview))
+}
+
+/// GET /api/v1/admin/routes — every API endpoint and the access it requires
+/// (requires authentication)
+pub(crate) async fn list_routes(_auth_user: AuthenticatedUser) -> Json<&'static [RouteInfo]> {
+    Json(API_ROUTES)
+}
+
+#[derive(Debug, Serialize)]
+pub struct UnrecognizedCountry {
+    pub roaster_id: RoasterId,
+    pub roaster_name: String,
+    pub country: String,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct CountryNormalizationReport {
+    pub updated: usize,
+    pub unrecognized: Vec<UnrecognizedCountry>,
+}
+
+/// POST /api/v1/admin/normalize-countries — rewrite roaster countries to their
+/// canonical names and list any that aren't recognised (requires authentication)
+#[tracing::instrument(skip(state, _auth_user))]
+pub(crate) async fn normalize_roaster_countries(
+    State(state): State<AppState>,
+    _auth_user: AuthenticatedUser,
+) -> Result<Json<CountryNormalizationReport>, ApiError> {
+    let roasters = state
+        .roaster_repo
+        .list_all()
+        .await
+        .map_err(AppError::from)?;
+
+    let mut report = CountryNormalizationReport::default();
+    for roaster in roasters {
+        let Some(country) = canonical_country(&roaster.country) else {
+            report.unrecognized.push(UnrecognizedCountry {
+                roaster_id: roaster.id,
+                roaster_name: roaster.name,
+                country: roaster.country,
+            });
+            continue;
+        };
+        if country.name == roaster.country {
+            continue;
+        }
+
+        let update = UpdateRoaster {
+            country: Some(country.name.to_string()),
+            ..UpdateRoaster::default()
+        };
+        state
+            .roaster_repo
+            .update(roaster.id, update)
+            .await
+            .map_err(AppError::from)?;
+        state
+            .timeline_invalidator
+            .invalidate(EntityType::Roaster, i64::from(roaster.id));
+        report.updated += 1;
+    }
+
+    if report.updated > 0 {
+        info!(updated = report.updated, "roaster countries normalized");
+        state.stats_invalidator.invalidate();
+    }
+
+    Ok(Json(report))
+}
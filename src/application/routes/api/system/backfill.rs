@@ -0,0 +1,91 @@
+use axum::Json;
+use axum::extract::State;
+use tracing::info;
+
+use crate::application::auth::AuthenticatedUser;
+use crate::application::errors::{ApiError, AppError};
+use crate::application::routes::api::coffee::brews::{
+    load_brewer, load_kettle, resolve_quick_notes,
+};
+use crate::application::routes::api::coffee::cups::check_cup_bag;
+use crate::application::services::{Backfill, BackfillReport, RecordRef};
+use crate::application::state::AppState;
+use crate::domain::validation::Validate;
+
+/// POST /api/v1/backfill — log historical brews and cups (requires authentication)
+///
+/// Every record is checked before any is logged, and the timeline is then
+/// rebuilt for just the months they fall in.
+#[tracing::instrument(skip(state, auth_user, backfill))]
+pub(crate) async fn backfill_history(
+    State(state): State<AppState>,
+    auth_user: AuthenticatedUser,
+    Json(mut backfill): Json<Backfill>,
+) -> Result<Json<BackfillReport>, ApiError> {
+    if backfill.brews.is_empty() && backfill.cups.is_empty() {
+        return Err(AppError::validation("nothing to backfill").into());
+    }
+    backfill
+        .check_dates(state.clock.now())
+        .map_err(AppError::from)?;
+
+    for (i, brew) in backfill.brews.iter_mut().enumerate() {
+        let record = RecordRef::brew(i);
+        brew.validate()
+            .map_err(|errors| AppError::Invalid(record.locate(errors)))?;
+        let brewer = load_brewer(&state, brew.brewer_id)
+            .await
+            .map_err(|err| for_record(record, err))?;
+        brew.validate_for_brewer(&brewer)
+            .map_err(|errors| AppError::Invalid(record.locate(errors)))?;
+        if let Some(kettle_id) = brew.kettle_id {
+            let kettle = load_kettle(&state, kettle_id)
+                .await
+                .map_err(|err| for_record(record, err))?;
+            brew.apply_kettle(&kettle);
+        }
+        brew.quick_notes = resolve_quick_notes(&state, std::mem::take(&mut brew.quick_notes))
+            .await
+            .map_err(|err| for_record(record, err))?;
+    }
+    for (i, cup) in backfill.cups.iter().enumerate() {
+        let record = RecordRef::cup(i);
+        cup.validate()
+            .map_err(|errors| AppError::Invalid(record.locate(errors)))?;
+        if let Some(bag_id) = cup.bag_id {
+            check_cup_bag(&state, bag_id, Some(cup.roast_id))
+                .await
+                .map_err(|err| for_record(record, err))?;
+        }
+    }
+
+    let months = backfill.months();
+    let report = state
+        .backfill_service
+        .backfill(backfill, Some(auth_user.0.id))
+        .await
+        .map_err(AppError::from)?;
+
+    info!(
+        brews = report.brews,
+        cups = report.cups,
+        months = report.months.len(),
+        "history backfilled"
+    );
+    state.stats_invalidator.invalidate();
+    state.timeline_invalidator.rebuild_months(months);
+
+    Ok(Json(report))
+}
+
+/// Name the record a lookup failure came from, e.g. "brew 3: the brewer
+/// does not exist".
+fn for_record(record: RecordRef, err: AppError) -> AppError {
+    match err {
+        AppError::Invalid(errors) => AppError::Invalid(record.locate(errors)),
+        AppError::Validation(code, message) => {
+            AppError::validation_with(code, format!("{}: {message}", record.label()))
+        }
+        other => other,
+    }
+}
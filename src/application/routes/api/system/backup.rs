@@ -1,20 +1,41 @@
 use axum::Json;
-use axum::extract::State;
+use axum::body::Body;
+use axum::extract::{Query, State};
 use axum::http::{StatusCode, header};
 use axum::response::{IntoResponse, Response};
+use futures_util::StreamExt;
+use serde::Deserialize;
 
 use crate::application::auth::AuthenticatedUser;
 use crate::application::errors::{ApiError, AppError};
 use crate::application::state::AppState;
-use crate::infrastructure::backup::BackupData;
+use crate::domain::ErrorCode;
+use crate::infrastructure::backup::{BackupData, BackupRecord, RestoreMode, StreamingRestore};
+
+/// `?dry_run=true` on a destructive endpoint reports what it would change
+/// instead of changing it.
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct DryRunQuery {
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct ExportQuery {
+    #[serde(default)]
+    format: Option<String>,
+}
 
 /// GET /api/v1/backup — export all data as JSON (requires authentication)
 ///
 /// Returns the backup with a `Content-Disposition: attachment` header so
 /// browsers trigger a file download while API/CLI consumers can ignore it.
+/// Pass `?format=ndjson` for the line-per-record format accepted by
+/// `/backup/restore/stream`.
 pub(crate) async fn export_backup(
     State(state): State<AppState>,
     _auth_user: AuthenticatedUser,
+    Query(query): Query<ExportQuery>,
 ) -> Result<Response, ApiError> {
     let data = state
         .backup_service
@@ -22,16 +43,31 @@ pub(crate) async fn export_backup(
         .await
         .map_err(|e| AppError::unexpected(e.to_string()))?;
 
-    let body = serde_json::to_string(&data).map_err(|e| AppError::unexpected(e.to_string()))?;
+    let (body, content_type, extension) = match query.format.as_deref() {
+        Some("ndjson") => (
+            data.to_ndjson()
+                .map_err(|e| AppError::unexpected(e.to_string()))?,
+            "application/x-ndjson",
+            "ndjson",
+        ),
+        None | Some("json") => (
+            serde_json::to_string(&data).map_err(|e| AppError::unexpected(e.to_string()))?,
+            "application/json",
+            "json",
+        ),
+        Some(other) => {
+            return Err(AppError::validation(format!("unsupported backup format: {other}")).into());
+        }
+    };
 
     let filename = format!(
-        "brewlog-backup-{}.json",
-        chrono::Utc::now().format("%Y-%m-%d")
+        "brewlog-backup-{}.{extension}",
+        state.clock.now().format("%Y-%m-%d")
     );
 
     Ok((
         [
-            (header::CONTENT_TYPE, "application/json".to_string()),
+            (header::CONTENT_TYPE, content_type.to_string()),
             (
                 header::CONTENT_DISPOSITION,
                 format!("attachment; filename=\"{filename}\""),
@@ -43,32 +79,159 @@ pub(crate) async fn export_backup(
 }
 
 /// POST /api/v1/backup/restore — restore from JSON backup (requires authentication)
+///
+/// With `?dry_run=true` the rows that would be written are returned per
+/// table, and nothing is kept.
 pub(crate) async fn restore_backup(
     State(state): State<AppState>,
     _auth_user: AuthenticatedUser,
+    Query(query): Query<DryRunQuery>,
     Json(payload): Json<BackupData>,
 ) -> Result<Response, ApiError> {
-    state.backup_service.restore(payload).await.map_err(|e| {
-        let msg = e.to_string();
-        if msg.contains("not empty") {
-            ApiError::from(AppError::Conflict(msg))
-        } else {
-            ApiError::from(AppError::unexpected(msg))
+    if query.dry_run {
+        let report = state
+            .backup_service
+            .preview_restore(payload)
+            .await
+            .map_err(restore_error)?;
+        return Ok(Json(report).into_response());
+    }
+
+    state
+        .backup_service
+        .restore(payload)
+        .await
+        .map_err(restore_error)?;
+    state.read_cache.invalidate_all();
+    Ok(StatusCode::NO_CONTENT.into_response())
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct StreamRestoreQuery {
+    #[serde(default)]
+    resume: bool,
+    #[serde(default)]
+    dry_run: bool,
+}
+
+/// POST /api/v1/backup/restore/stream — restore from an NDJSON backup (requires authentication)
+///
+/// Records are applied as they arrive and committed in batches, so the body
+/// is never held in memory. If the upload is interrupted, re-send the same
+/// file with `?resume=true` to skip the rows that were already written.
+/// `?dry_run=true` applies the whole file in one transaction, rolls it back,
+/// and returns the rows that would have been written per table.
+pub(crate) async fn restore_backup_stream(
+    State(state): State<AppState>,
+    _auth_user: AuthenticatedUser,
+    Query(query): Query<StreamRestoreQuery>,
+    body: Body,
+) -> Result<Response, ApiError> {
+    let mode = if query.resume {
+        RestoreMode::Resume
+    } else {
+        RestoreMode::Fresh
+    };
+
+    let mut restore = state
+        .backup_service
+        .begin_streaming_restore(mode)
+        .await
+        .map_err(restore_error)?;
+    if query.dry_run {
+        restore = restore.dry_run();
+    }
+
+    let mut stream = body.into_data_stream();
+    let mut buffer: Vec<u8> = Vec::new();
+    let mut line_number = 0;
+
+    while let Some(chunk) = stream.next().await {
+        let chunk =
+            chunk.map_err(|e| AppError::validation(format!("failed to read request body: {e}")))?;
+        buffer.extend_from_slice(&chunk);
+
+        while let Some(end) = buffer.iter().position(|b| *b == b'\n') {
+            let line: Vec<u8> = buffer.drain(..=end).collect();
+            line_number += 1;
+            apply_line(&mut restore, &line, line_number).await?;
         }
+    }
+
+    if !buffer.is_empty() {
+        line_number += 1;
+        apply_line(&mut restore, &buffer, line_number).await?;
+    }
+
+    if query.dry_run {
+        let report = restore.discard().await.map_err(restore_error)?;
+        return Ok(Json(report).into_response());
+    }
+
+    let summary = restore.finish().await.map_err(restore_error)?;
+    state.read_cache.invalidate_all();
+
+    tracing::info!(
+        restored = summary.restored,
+        skipped = summary.skipped,
+        "streaming backup restore complete"
+    );
+
+    Ok(Json(summary).into_response())
+}
+
+async fn apply_line(
+    restore: &mut StreamingRestore,
+    line: &[u8],
+    line_number: usize,
+) -> Result<(), ApiError> {
+    let line = line.trim_ascii();
+    if line.is_empty() {
+        return Ok(());
+    }
+
+    let record: BackupRecord = serde_json::from_slice(line).map_err(|e| {
+        AppError::validation(format!("invalid backup record on line {line_number}: {e}"))
     })?;
-    Ok(StatusCode::NO_CONTENT.into_response())
+
+    restore.apply(&record).await.map_err(restore_error)
+}
+
+fn restore_error(err: anyhow::Error) -> ApiError {
+    let msg = err.to_string();
+    if msg.contains("not empty") {
+        ApiError::from(AppError::Conflict(ErrorCode::DatabaseNotEmpty, msg))
+    } else {
+        ApiError::from(AppError::unexpected(msg))
+    }
 }
 
 /// POST /api/v1/backup/reset — delete all coffee data (requires authentication)
+///
+/// With `?dry_run=true` the rows that would be deleted are returned per
+/// table instead.
 pub(crate) async fn reset_database(
     State(state): State<AppState>,
     _auth_user: AuthenticatedUser,
+    Query(query): Query<DryRunQuery>,
 ) -> Result<Response, ApiError> {
+    if query.dry_run {
+        let report = state
+            .backup_service
+            .preview_reset()
+            .await
+            .map_err(|e| AppError::unexpected(e.to_string()))?;
+        return Ok(Json(report).into_response());
+    }
+
     state
         .backup_service
         .reset()
         .await
         .map_err(|e| AppError::unexpected(e.to_string()))?;
+    state.stats_invalidator.invalidate();
+    // The reset deleted roasters, gear and images in SQL, past the cache.
+    state.read_cache.invalidate_all();
 
     tracing::info!("database reset: all coffee data deleted");
 
@@ -0,0 +1,37 @@
+use axum::extract::State;
+use axum::http::header;
+use axum::response::{IntoResponse, Response};
+
+use crate::application::auth::AuthenticatedUser;
+use crate::application::errors::{ApiError, AppError};
+use crate::application::state::AppState;
+use crate::infrastructure::beanconqueror;
+
+/// GET /api/v1/export/beanconqueror — export as a `Beanconqueror.json` (requires authentication)
+///
+/// Served as a download so the file can be handed straight to the app's
+/// import.
+pub(crate) async fn export_beanconqueror(
+    State(state): State<AppState>,
+    _auth_user: AuthenticatedUser,
+) -> Result<Response, ApiError> {
+    let data = state
+        .backup_service
+        .export()
+        .await
+        .map_err(|e| AppError::unexpected(e.to_string()))?;
+    let body = serde_json::to_string(&beanconqueror::write_export(&data))
+        .map_err(|e| AppError::unexpected(e.to_string()))?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/json"),
+            (
+                header::CONTENT_DISPOSITION,
+                "attachment; filename=\"Beanconqueror.json\"",
+            ),
+        ],
+        body,
+    )
+        .into_response())
+}
@@ -0,0 +1,50 @@
+use axum::Json;
+use axum::body::Bytes;
+use axum::extract::{Query, State};
+use serde::Deserialize;
+use tracing::info;
+
+use crate::application::auth::AuthenticatedUser;
+use crate::application::errors::{ApiError, AppError};
+use crate::application::services::ImportReport;
+use crate::application::state::AppState;
+use crate::domain::roasters::validate_country;
+use crate::infrastructure::beanconqueror;
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct ImportQuery {
+    /// Country given to roasters the instance doesn't know yet; the source
+    /// apps don't record one.
+    roaster_country: String,
+}
+
+/// POST /api/v1/import/beanconqueror — import a Beanconqueror export (requires authentication)
+///
+/// The body is the export zip, or the `Beanconqueror.json` inside it. The
+/// response reports what was created and what had nowhere to go.
+pub(crate) async fn import_beanconqueror(
+    State(state): State<AppState>,
+    auth_user: AuthenticatedUser,
+    Query(query): Query<ImportQuery>,
+    body: Bytes,
+) -> Result<Json<ImportReport>, ApiError> {
+    validate_country(&query.roaster_country).map_err(AppError::validation)?;
+    let export =
+        beanconqueror::read_export(&body).map_err(|e| AppError::validation(format!("{e:#}")))?;
+
+    let report = state
+        .import_service
+        .import_beanconqueror(export, &query.roaster_country, Some(auth_user.0.id))
+        .await
+        .map_err(AppError::from)?;
+
+    info!(
+        roasts = report.roasts,
+        bags = report.bags,
+        brews = report.brews,
+        "beanconqueror import complete"
+    );
+    state.stats_invalidator.invalidate();
+
+    Ok(Json(report))
+}
@@ -0,0 +1,56 @@
+use axum::Json;
+use axum::extract::State;
+use tracing::{info, warn};
+
+use crate::application::auth::AuthenticatedUser;
+use crate::application::errors::{ApiError, AppError};
+use crate::application::state::AppState;
+use crate::domain::entity_type::EntityType;
+use crate::infrastructure::integrity::{IntegrityIssueKind, IntegrityReport};
+
+/// GET /api/v1/admin/integrity — report data integrity issues (requires authentication)
+#[tracing::instrument(skip(state, _auth_user))]
+pub(crate) async fn check_integrity(
+    State(state): State<AppState>,
+    _auth_user: AuthenticatedUser,
+) -> Result<Json<IntegrityReport>, ApiError> {
+    let report = state
+        .integrity_service
+        .check()
+        .await
+        .map_err(|e| AppError::unexpected(e.to_string()))?;
+
+    if !report.is_clean() {
+        warn!(issues = report.issues.len(), "integrity check found issues");
+    }
+
+    Ok(Json(report))
+}
+
+/// POST /api/v1/admin/integrity/repair — fix repairable integrity issues (requires authentication)
+#[tracing::instrument(skip(state, _auth_user))]
+pub(crate) async fn repair_integrity(
+    State(state): State<AppState>,
+    _auth_user: AuthenticatedUser,
+) -> Result<Json<IntegrityReport>, ApiError> {
+    let report = state
+        .integrity_service
+        .repair()
+        .await
+        .map_err(|e| AppError::unexpected(e.to_string()))?;
+
+    if report.repaired > 0 {
+        info!(repaired = report.repaired, "integrity issues repaired");
+        state.stats_invalidator.invalidate();
+        state.read_cache.invalidate_images();
+        for issue in &report.issues {
+            if issue.kind == IntegrityIssueKind::BagRemainingExceedsAmount {
+                state
+                    .timeline_invalidator
+                    .invalidate(EntityType::Bag, issue.entity_id);
+            }
+        }
+    }
+
+    Ok(Json(report))
+}
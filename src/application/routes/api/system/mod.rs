@@ -1,3 +1,10 @@
 pub(crate) mod admin;
+pub(crate) mod backfill;
 pub(crate) mod backup;
+pub(crate) mod export;
+pub(crate) mod import;
+pub(crate) mod integrity;
+pub(crate) mod nav_search;
+pub(crate) mod qr;
+pub(crate) mod settings;
 pub(crate) mod timeline;
@@ -0,0 +1,90 @@
+use axum::extract::{Query, State};
+use axum::response::Response;
+use serde::Deserialize;
+use tower_cookies::Cookies;
+
+use crate::application::errors::{ApiError, AppError};
+use crate::application::routes::support::render_fragment;
+use crate::application::state::AppState;
+use crate::domain::cafes::CafeSortKey;
+use crate::domain::listing::{ListRequest, PageSize, SortDirection};
+use crate::domain::roasters::RoasterSortKey;
+use crate::domain::roasts::RoastSortKey;
+use crate::presentation::web::templates::NavSearchResultsTemplate;
+use crate::presentation::web::views::NavSearchResultView;
+
+/// How many roasters, roasts and cafes are each offered for a query.
+const RESULTS_PER_KIND: u32 = 5;
+
+/// Pages the quick-nav can jump to: label, path, icon, and whether the page
+/// is only useful when signed in.
+const PAGES: &[(&str, &str, &str, bool)] = &[
+    ("Home", "/", "", false),
+    ("Data", "/data", "", false),
+    ("Timeline", "/timeline", "calendar", false),
+    ("Stats", "/stats", "", false),
+    ("Events", "/events", "calendar", false),
+    ("Roasting", "/roasting", "roaster", false),
+    ("Compare", "/compare", "roast", false),
+    ("Add", "/add", "", true),
+    ("Check-in", "/check-in", "cafe", true),
+    ("Profile", "/profile", "", true),
+    ("Admin", "/admin", "", true),
+];
+
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct NavSearchQuery {
+    #[serde(default)]
+    pub q: String,
+}
+
+/// GET /api/v1/nav-search?q= — pages, roasters, roasts and cafes matching
+/// `q`, rendered for the header's quick-nav box
+#[tracing::instrument(skip(state, cookies))]
+pub(crate) async fn nav_search(
+    State(state): State<AppState>,
+    cookies: Cookies,
+    Query(query): Query<NavSearchQuery>,
+) -> Result<Response, ApiError> {
+    let term = query.q.trim();
+    let mut results = Vec::new();
+
+    if !term.is_empty() {
+        let authenticated = crate::application::routes::is_authenticated(&state, &cookies).await;
+        let needle = term.to_lowercase();
+        results.extend(
+            PAGES
+                .iter()
+                .filter(|(label, _, _, auth_only)| {
+                    (authenticated || !auth_only) && label.to_lowercase().contains(&needle)
+                })
+                .map(|&(label, href, icon, _)| NavSearchResultView {
+                    icon,
+                    label: label.to_string(),
+                    detail: "Page".to_string(),
+                    href: href.to_string(),
+                }),
+        );
+
+        let page_size = PageSize::limited(RESULTS_PER_KIND);
+        let roaster_req = ListRequest::new(1, page_size, RoasterSortKey::Name, SortDirection::Asc);
+        let roast_req = ListRequest::new(1, page_size, RoastSortKey::Name, SortDirection::Asc);
+        let cafe_req = ListRequest::new(1, page_size, CafeSortKey::Name, SortDirection::Asc);
+        let (roasters, roasts, cafes) = tokio::try_join!(
+            state.roaster_repo.list(&roaster_req, Some(term)),
+            state.roast_repo.list(&roast_req, Some(term)),
+            state.cafe_repo.list(&cafe_req, Some(term)),
+        )
+        .map_err(AppError::from)?;
+
+        results.extend(roasters.items.into_iter().map(NavSearchResultView::from));
+        results.extend(roasts.items.into_iter().map(NavSearchResultView::from));
+        results.extend(cafes.items.into_iter().map(NavSearchResultView::from));
+    }
+
+    let template = NavSearchResultsTemplate {
+        query: term.to_string(),
+        results,
+    };
+    render_fragment(template, "#nav-search-results").map_err(ApiError::from)
+}
@@ -0,0 +1,30 @@
+use axum::extract::Query;
+use axum::http::header;
+use axum::response::IntoResponse;
+use serde::Deserialize;
+
+use crate::application::auth::AuthenticatedUser;
+use crate::application::errors::{ApiError, AppError};
+use crate::infrastructure::qr::render_svg;
+
+#[derive(Debug, Deserialize)]
+pub struct QrQuery {
+    pub data: String,
+}
+
+/// GET /api/v1/qr?data= — render `data` as an SVG QR code (requires authentication)
+#[tracing::instrument(skip(_auth_user, query))]
+pub(crate) async fn qr_code(
+    _auth_user: AuthenticatedUser,
+    Query(query): Query<QrQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let svg = render_svg(&query.data).map_err(|e| AppError::validation(e.to_string()))?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "image/svg+xml"),
+            (header::CACHE_CONTROL, "private, max-age=86400"),
+        ],
+        svg,
+    ))
+}
@@ -0,0 +1,178 @@
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use tracing::info;
+
+use crate::application::auth::AuthenticatedUser;
+use crate::application::errors::{ApiError, AppError};
+use crate::application::state::AppState;
+use crate::domain::ai_usage::{AiModelPrice, SetAiModelPrice, validate_model};
+use crate::domain::currencies::{
+    ExchangeRate, SetExchangeRate, normalize_currency, validate_currency,
+};
+use crate::domain::settings::{Settings, UpdateSettings};
+
+#[tracing::instrument(skip(state))]
+pub(crate) async fn get_settings(
+    State(state): State<AppState>,
+) -> Result<Json<Settings>, ApiError> {
+    let settings = state.settings_repo.get().await.map_err(AppError::from)?;
+
+    Ok(Json(settings))
+}
+
+/// PUT /api/v1/settings — change instance-wide settings
+#[tracing::instrument(skip(state, _auth_user))]
+pub(crate) async fn update_settings(
+    State(state): State<AppState>,
+    _auth_user: AuthenticatedUser,
+    Json(mut payload): Json<UpdateSettings>,
+) -> Result<Json<Settings>, ApiError> {
+    payload.base_currency = payload.base_currency.as_deref().map(normalize_currency);
+    payload.map_tile_url = payload.map_tile_url.map(|url| url.trim().to_string());
+    payload.map_attribution = payload
+        .map_attribution
+        .map(|attribution| attribution.trim().to_string());
+    payload.validate().map_err(AppError::validation)?;
+    let currency_changed = payload.base_currency.is_some();
+
+    let settings = state
+        .settings_repo
+        .update(payload)
+        .await
+        .map_err(AppError::from)?;
+
+    info!(
+        low_stock_threshold = settings.low_stock_threshold,
+        dust_threshold = settings.dust_threshold,
+        base_currency = settings.base_currency.as_deref(),
+        map_tile_url = settings.map_tile_url.as_deref(),
+        slug_strategy = settings.slug_strategy.as_str(),
+        "settings updated"
+    );
+    if currency_changed {
+        state.stats_invalidator.invalidate();
+    }
+    Ok(Json(settings))
+}
+
+#[tracing::instrument(skip(state))]
+pub(crate) async fn list_exchange_rates(
+    State(state): State<AppState>,
+) -> Result<Json<Vec<ExchangeRate>>, ApiError> {
+    let rates = state
+        .settings_repo
+        .list_exchange_rates()
+        .await
+        .map_err(AppError::from)?;
+
+    Ok(Json(rates))
+}
+
+/// PUT /api/v1/exchange-rates/{currency} — set how much of the base currency
+/// one unit of `currency` buys
+#[tracing::instrument(skip(state, _auth_user))]
+pub(crate) async fn set_exchange_rate(
+    State(state): State<AppState>,
+    _auth_user: AuthenticatedUser,
+    Path(currency): Path<String>,
+    Json(payload): Json<SetExchangeRate>,
+) -> Result<Json<ExchangeRate>, ApiError> {
+    let currency = normalize_currency(&currency);
+    validate_currency(&currency).map_err(AppError::validation)?;
+    payload.validate().map_err(AppError::validation)?;
+    let settings = state.settings_repo.get().await.map_err(AppError::from)?;
+    let Some(base) = settings.base_currency else {
+        return Err(AppError::validation("set a base currency first").into());
+    };
+    if currency == base {
+        return Err(AppError::validation(format!("{currency} is the base currency")).into());
+    }
+
+    let rate = state
+        .settings_repo
+        .set_exchange_rate(&currency, payload.rate)
+        .await
+        .map_err(AppError::from)?;
+
+    info!(currency = %rate.currency, rate = rate.rate, "exchange rate set");
+    state.stats_invalidator.invalidate();
+    Ok(Json(rate))
+}
+
+#[tracing::instrument(skip(state, _auth_user))]
+pub(crate) async fn delete_exchange_rate(
+    State(state): State<AppState>,
+    _auth_user: AuthenticatedUser,
+    Path(currency): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    let currency = normalize_currency(&currency);
+    state
+        .settings_repo
+        .delete_exchange_rate(&currency)
+        .await
+        .map_err(AppError::from)?;
+
+    info!(currency = %currency, "exchange rate deleted");
+    state.stats_invalidator.invalidate();
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[tracing::instrument(skip(state, _auth_user))]
+pub(crate) async fn list_ai_prices(
+    State(state): State<AppState>,
+    _auth_user: AuthenticatedUser,
+) -> Result<Json<Vec<AiModelPrice>>, ApiError> {
+    let prices = state
+        .ai_usage_repo
+        .list_prices()
+        .await
+        .map_err(AppError::from)?;
+
+    Ok(Json(prices))
+}
+
+/// PUT /api/v1/ai-prices/{model} — set what `model` costs per million
+/// tokens. Model IDs contain slashes, so the rest of the path is the model.
+#[tracing::instrument(skip(state, _auth_user))]
+pub(crate) async fn set_ai_price(
+    State(state): State<AppState>,
+    _auth_user: AuthenticatedUser,
+    Path(model): Path<String>,
+    Json(payload): Json<SetAiModelPrice>,
+) -> Result<Json<AiModelPrice>, ApiError> {
+    let model = model.trim();
+    validate_model(model).map_err(AppError::validation)?;
+    payload.validate().map_err(AppError::validation)?;
+
+    let price = state
+        .ai_usage_repo
+        .set_price(model, &payload)
+        .await
+        .map_err(AppError::from)?;
+
+    info!(
+        model = %price.model,
+        prompt_price = price.prompt_price,
+        completion_price = price.completion_price,
+        "AI price set"
+    );
+    Ok(Json(price))
+}
+
+#[tracing::instrument(skip(state, _auth_user))]
+pub(crate) async fn delete_ai_price(
+    State(state): State<AppState>,
+    _auth_user: AuthenticatedUser,
+    Path(model): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    let model = model.trim();
+    state
+        .ai_usage_repo
+        .delete_price(model)
+        .await
+        .map_err(AppError::from)?;
+
+    info!(model = %model, "AI model price deleted");
+    Ok(StatusCode::NO_CONTENT)
+}
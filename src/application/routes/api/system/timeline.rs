@@ -1,17 +1,72 @@
-use axum::extract::State;
+use axum::Json;
+use axum::extract::{Query, State};
 use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
+use serde::Deserialize;
 use tracing::info;
 
 use crate::application::auth::AuthenticatedUser;
-use crate::application::errors::ApiError;
+use crate::application::errors::{ApiError, AppError};
+use crate::application::routes::api::system::backup::DryRunQuery;
+use crate::application::services::timeline_refresh::preview_rebuild_all;
 use crate::application::state::AppState;
+use crate::domain::ids::{TimelineEventId, UserId};
+use crate::domain::timeline::{TimelineEvent, TimelineFilter};
 
+const DEFAULT_LOGGED_EVENTS: u32 = 10;
+const MAX_LOGGED_EVENTS: u32 = 100;
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct LoggedEventsQuery {
+    /// Only events logged after this one.
+    #[serde(default)]
+    after: Option<i64>,
+    #[serde(default)]
+    limit: Option<u32>,
+    #[serde(default)]
+    user: Option<i64>,
+}
+
+/// Timeline events in the order they were logged, oldest first. Pass the
+/// last seen ID as `after` to pick up only what has been logged since.
+#[tracing::instrument(skip(state))]
+pub(crate) async fn list_timeline(
+    State(state): State<AppState>,
+    Query(query): Query<LoggedEventsQuery>,
+) -> Result<Json<Vec<TimelineEvent>>, ApiError> {
+    let filter = query
+        .user
+        .map(UserId::from)
+        .map_or_else(TimelineFilter::all, TimelineFilter::for_user);
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_LOGGED_EVENTS)
+        .clamp(1, MAX_LOGGED_EVENTS);
+
+    let events = state
+        .timeline_repo
+        .list_logged(filter, query.after.map(TimelineEventId::from), limit)
+        .await
+        .map_err(AppError::from)?;
+
+    Ok(Json(events))
+}
+
+/// Rebuild every timeline event in the background. `?dry_run=true` reports
+/// how many events would be deleted and written back instead.
 #[tracing::instrument(skip(state, _auth_user))]
 pub(crate) async fn rebuild_timeline(
     State(state): State<AppState>,
     _auth_user: AuthenticatedUser,
+    Query(query): Query<DryRunQuery>,
 ) -> Result<Response, ApiError> {
+    if query.dry_run {
+        let report = preview_rebuild_all(&state.timeline_rebuilder())
+            .await
+            .map_err(AppError::from)?;
+        return Ok(Json(report).into_response());
+    }
+
     info!("timeline rebuild requested");
     state.timeline_invalidator.rebuild_all();
     Ok(StatusCode::NO_CONTENT.into_response())
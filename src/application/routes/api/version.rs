@@ -0,0 +1,247 @@
+//! The API versions served side by side.
+//!
+//! Both versions share one router and one set of handlers. Each is nested
+//! under its own prefix with [`versioned`] in front, which records the
+//! [`ApiVersion`] for handlers that answer differently, adds the version
+//! headers, and rewrites v2 error bodies. v1 stays as it is and is marked
+//! deprecated; v2 is where breaking changes land.
+
+use axum::Json;
+use axum::body::{Body, to_bytes};
+use axum::extract::{FromRequestParts, Path, Request};
+use axum::http::request::Parts;
+use axum::http::{HeaderValue, StatusCode, header};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use serde::{Deserialize, Serialize};
+
+use crate::application::errors::{ErrorEnvelope, ErrorResponse};
+use crate::domain::ErrorCode;
+use crate::domain::listing::{
+    DEFAULT_PAGE_SIZE, ListRequest, Page, PageSize, SortDirection, SortKey,
+};
+
+/// Response header naming the version that answered.
+pub const API_VERSION_HEADER: &str = "api-version";
+
+/// When v1 was deprecated, as an RFC 9745 `Deprecation` date (2026-10-17).
+const V1_DEPRECATED_AT: &str = "@1792195200";
+
+/// Error bodies larger than this are passed through untouched.
+const ERROR_BODY_LIMIT: usize = 64 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ApiVersion {
+    /// Unpaginated lists and flat error bodies. Deprecated.
+    V1,
+    /// Paginated lists and errors wrapped in an `error` object.
+    V2,
+}
+
+impl ApiVersion {
+    pub const ALL: [Self; 2] = [Self::V1, Self::V2];
+
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::V1 => "v1",
+            Self::V2 => "v2",
+        }
+    }
+
+    /// The path prefix the version is served under.
+    pub const fn prefix(self) -> &'static str {
+        match self {
+            Self::V1 => "/api/v1",
+            Self::V2 => "/api/v2",
+        }
+    }
+
+    fn from_segment(segment: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|v| v.as_str() == segment)
+    }
+
+    pub const fn is_deprecated(self) -> bool {
+        matches!(self, Self::V1)
+    }
+
+    /// The listing a list endpoint should load: everything for v1, the
+    /// requested page for v2.
+    pub fn list_request<K: SortKey>(
+        self,
+        query: &PageQuery,
+        sort_key: K,
+        direction: SortDirection,
+    ) -> ListRequest<K> {
+        match self {
+            Self::V1 => ListRequest::show_all(sort_key, direction),
+            Self::V2 => ListRequest::new(
+                query.page.unwrap_or(1),
+                PageSize::Limited(query.page_size.unwrap_or(DEFAULT_PAGE_SIZE).max(1)),
+                sort_key,
+                direction,
+            ),
+        }
+    }
+
+    /// A list endpoint's body: a bare array for v1, a [`PageEnvelope`] for v2.
+    pub fn list_response<T: Serialize>(self, page: Page<T>) -> Response {
+        match self {
+            Self::V1 => Json(page.items).into_response(),
+            Self::V2 => Json(PageEnvelope::from(page)).into_response(),
+        }
+    }
+}
+
+/// Handlers outside a versioned prefix get v1, which every route supports.
+impl<S: Send + Sync> FromRequestParts<S> for ApiVersion {
+    type Rejection = std::convert::Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(parts.extensions.get::<Self>().copied().unwrap_or(Self::V1))
+    }
+}
+
+/// Paging for v2 list endpoints. v1 ignores it and returns everything.
+#[derive(Debug, Default, Deserialize)]
+pub struct PageQuery {
+    pub page: Option<u32>,
+    /// Capped at [`MAX_PAGE_SIZE`](crate::domain::listing::MAX_PAGE_SIZE).
+    pub page_size: Option<u32>,
+}
+
+/// A page of a v2 list.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PageEnvelope<T> {
+    pub items: Vec<T>,
+    pub page: u32,
+    pub page_size: u32,
+    pub total: u64,
+    pub total_pages: u32,
+}
+
+impl<T> From<Page<T>> for PageEnvelope<T> {
+    fn from(page: Page<T>) -> Self {
+        let total_pages = page.total_pages();
+        Self {
+            items: page.items,
+            page: page.page,
+            page_size: page.page_size,
+            total: page.total,
+            total_pages,
+        }
+    }
+}
+
+/// Middleware for one version's routes.
+pub(crate) async fn versioned(version: ApiVersion, mut request: Request, next: Next) -> Response {
+    request.extensions_mut().insert(version);
+    let response = next.run(request).await;
+    finish(version, response).await
+}
+
+/// Add the version headers and, for v2, wrap any error.
+async fn finish(version: ApiVersion, mut response: Response) -> Response {
+    let headers = response.headers_mut();
+    headers.insert(
+        API_VERSION_HEADER,
+        HeaderValue::from_static(version.as_str()),
+    );
+    if version.is_deprecated() {
+        headers.insert("deprecation", HeaderValue::from_static(V1_DEPRECATED_AT));
+        headers.insert(
+            header::LINK,
+            HeaderValue::from_static("</api/v2>; rel=\"successor-version\""),
+        );
+    }
+
+    match version {
+        ApiVersion::V1 => response,
+        ApiVersion::V2 => wrap_error(response).await,
+    }
+}
+
+/// Put a v2 error in an `error` object. Errors that carry no body, such as a
+/// missing token, get one built from their status.
+async fn wrap_error(response: Response) -> Response {
+    let status = response.status();
+    if !(status.is_client_error() || status.is_server_error()) {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let Ok(bytes) = to_bytes(body, ERROR_BODY_LIMIT).await else {
+        return (status, "error body too large").into_response();
+    };
+    let error = if bytes.is_empty() {
+        ErrorResponse::new(
+            code_for_status(status),
+            status.canonical_reason().unwrap_or("error").to_lowercase(),
+        )
+    } else {
+        match serde_json::from_slice::<ErrorResponse>(&bytes) {
+            Ok(error) => error,
+            Err(_) => return Response::from_parts(parts, Body::from(bytes)),
+        }
+    };
+
+    parts.headers.remove(header::CONTENT_LENGTH);
+    parts.headers.remove(header::CONTENT_TYPE);
+    let mut response = Json(ErrorEnvelope { error }).into_response();
+    *response.status_mut() = status;
+    response.headers_mut().extend(parts.headers);
+    response
+}
+
+fn code_for_status(status: StatusCode) -> ErrorCode {
+    match status {
+        StatusCode::BAD_REQUEST => ErrorCode::BadRequest,
+        StatusCode::UNAUTHORIZED => ErrorCode::Unauthorized,
+        StatusCode::FORBIDDEN => ErrorCode::Forbidden,
+        StatusCode::NOT_FOUND => ErrorCode::NotFound,
+        StatusCode::CONFLICT => ErrorCode::Conflict,
+        StatusCode::UNPROCESSABLE_ENTITY => ErrorCode::InvalidFields,
+        status if status.is_server_error() => ErrorCode::Unexpected,
+        _ => ErrorCode::Unknown,
+    }
+}
+
+/// Answers `/api/<version>/...` when no versioned route matched: a plain
+/// `404 Not Found` for a known version, or an explanation for any other.
+pub(crate) async fn unsupported_version(Path((version, _)): Path<(String, String)>) -> Response {
+    if let Some(known) = ApiVersion::from_segment(&version) {
+        return finish(known, StatusCode::NOT_FOUND.into_response()).await;
+    }
+    let supported = ApiVersion::ALL.map(ApiVersion::as_str).join(", ");
+    (
+        StatusCode::NOT_FOUND,
+        Json(ErrorResponse::new(
+            ErrorCode::UnsupportedApiVersion,
+            format!("API version '{version}' is not served here; use one of {supported}"),
+        )),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::roasters::RoasterSortKey;
+
+    #[test]
+    fn only_v2_lists_are_paged() {
+        let query = PageQuery {
+            page: Some(3),
+            page_size: Some(500),
+        };
+
+        let v1 = ApiVersion::V1.list_request(&query, RoasterSortKey::Name, SortDirection::Asc);
+        let v2 = ApiVersion::V2.list_request(&query, RoasterSortKey::Name, SortDirection::Asc);
+
+        assert_eq!(v1.page_size(), PageSize::All);
+        assert_eq!(v2.page(), 3);
+        assert_eq!(
+            v2.page_size(),
+            PageSize::Limited(crate::domain::listing::MAX_PAGE_SIZE)
+        );
+    }
+}
@@ -3,12 +3,15 @@ use axum::http::StatusCode;
 use axum::response::{IntoResponse, Redirect, Response};
 use serde::Deserialize;
 
+use crate::application::auth::AuthenticatedUser;
 use crate::application::errors::map_app_error;
 use crate::application::routes::render_html;
 use crate::application::routes::support::{
-    load_cafe_options, load_roast_options, load_roaster_options,
+    load_cafe_options, load_map_tiles, load_roast_options, load_roaster_options,
+    render_signals_json,
 };
 use crate::application::state::AppState;
+use crate::domain::ids::GearId;
 use crate::presentation::web::templates::{AddTemplate, Tab};
 
 use crate::application::routes::api::brews::load_brew_form_data;
@@ -57,6 +60,8 @@ pub(crate) struct AddQuery {
     filter_paper_id: Option<String>,
     water_volume: Option<i32>,
     water_temp: Option<f64>,
+    bypass_water_ml: Option<i32>,
+    kettle_id: Option<String>,
     brew_time: Option<i32>,
     quick_notes: Option<String>,
 }
@@ -122,6 +127,12 @@ pub(crate) async fn add_page(
     if let Some(wt) = query.water_temp {
         defaults.water_temp = wt;
     }
+    if let Some(ml) = query.bypass_water_ml {
+        defaults.bypass_water_ml = Some(ml);
+    }
+    if let Some(ref kid) = query.kettle_id {
+        kid.clone_into(&mut defaults.kettle_id);
+    }
     if let Some(bt) = query.brew_time {
         defaults.brew_time = Some(bt);
     }
@@ -152,11 +163,40 @@ pub(crate) async fn add_page(
         grinder_options: brew_form.grinder_options,
         brewer_options: brew_form.brewer_options,
         filter_paper_options: brew_form.filter_paper_options,
+        kettle_options: brew_form.kettle_options,
         cafe_options,
         defaults,
         quick_note_options: brew_form.quick_note_options,
         pre_select_bag_id: query.bag_id,
+        map_tiles: load_map_tiles(&state).await,
     };
 
     render_html(template).map(IntoResponse::into_response)
 }
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct WaterTempQuery {
+    brewer_id: GearId,
+}
+
+/// Signal patch setting the brew form's water temperature to the chosen
+/// brewer's preset. Brewers without one leave the temperature alone.
+#[tracing::instrument(skip(state, _auth_user))]
+pub(crate) async fn water_temp_fragment(
+    State(state): State<AppState>,
+    _auth_user: AuthenticatedUser,
+    Query(query): Query<WaterTempQuery>,
+) -> Result<Response, StatusCode> {
+    let brewer = state
+        .gear_repo
+        .get(query.brewer_id)
+        .await
+        .map_err(|e| map_app_error(e.into()))?;
+
+    let signals: Vec<(&str, serde_json::Value)> = brewer
+        .water_temp
+        .map(|temp| ("_brew-temp", serde_json::json!(temp)))
+        .into_iter()
+        .collect();
+    render_signals_json(&signals).map_err(map_app_error)
+}
@@ -2,7 +2,7 @@ use askama::Template;
 use axum::extract::State;
 use axum::http::StatusCode;
 use axum::response::{IntoResponse, Redirect, Response};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::Serialize;
 use tower_cookies::Cookies;
 use tracing::{error, warn};
@@ -10,7 +10,16 @@ use tracing::{error, warn};
 use crate::application::auth::SESSION_COOKIE_NAME;
 use crate::application::routes::render_html;
 use crate::application::state::AppState;
+use crate::domain::ai_usage::{AiModelPrice, AiUsageMonth};
+use crate::domain::processes::PROCESS_FAMILIES;
+use crate::domain::registration_tokens::RegistrationToken;
+use crate::domain::slugs::SlugStrategy;
+use crate::infrastructure::ai::AiMetrics;
 use crate::infrastructure::auth::hash_token;
+use crate::infrastructure::image_purge::{ImagePurgeProgress, ImagePurgeRun};
+use crate::infrastructure::maintenance::MaintenanceRun;
+use crate::infrastructure::overview::InstanceOverview;
+use crate::infrastructure::repositories::query_timing::slow_query_threshold;
 
 // --- View types ---
 
@@ -21,6 +30,93 @@ pub struct AiUsageView {
     pub cost: String,
 }
 
+/// One model's share of a month's AI usage.
+#[derive(Serialize)]
+pub struct AiUsageModelView {
+    pub model: String,
+    pub calls: String,
+    pub prompt_tokens: String,
+    pub completion_tokens: String,
+    pub cost: String,
+}
+
+#[derive(Serialize)]
+pub struct AiUsageMonthView {
+    pub month: String,
+    pub cost: String,
+    pub models: Vec<AiUsageModelView>,
+}
+
+/// Group per-model rows, newest month first, into one view per month.
+fn ai_usage_months(rows: Vec<AiUsageMonth>) -> Vec<AiUsageMonthView> {
+    let mut months: Vec<(String, f64, Vec<AiUsageModelView>)> = Vec::new();
+    for row in rows {
+        let view = AiUsageModelView {
+            model: row.model,
+            calls: format_number(row.calls),
+            prompt_tokens: format_number(row.prompt_tokens),
+            completion_tokens: format_number(row.completion_tokens),
+            cost: format_cost(row.cost),
+        };
+        match months.last_mut() {
+            Some((month, cost, models)) if *month == row.month => {
+                *cost += row.cost;
+                models.push(view);
+            }
+            _ => months.push((row.month, row.cost, vec![view])),
+        }
+    }
+    months
+        .into_iter()
+        .map(|(month, cost, models)| AiUsageMonthView {
+            month: NaiveDate::parse_from_str(&format!("{month}-01"), "%Y-%m-%d")
+                .map_or(month, |date| date.format("%B %Y").to_string()),
+            cost: format_cost(cost),
+            models,
+        })
+        .collect()
+}
+
+#[derive(Serialize)]
+pub struct AiPriceView {
+    pub model: String,
+    pub prompt_price: String,
+    pub completion_price: String,
+    pub updated: String,
+}
+
+impl From<AiModelPrice> for AiPriceView {
+    fn from(price: AiModelPrice) -> Self {
+        Self {
+            model: price.model,
+            prompt_price: price.prompt_price.to_string(),
+            completion_price: price.completion_price.to_string(),
+            updated: format_date(price.updated_at),
+        }
+    }
+}
+
+/// How `OpenRouter` extractions have fared since the server started.
+#[derive(Serialize)]
+pub struct AiHealthView {
+    pub calls: String,
+    pub retries: String,
+    pub failure_rate: String,
+    pub circuit_open: bool,
+}
+
+impl From<AiMetrics> for AiHealthView {
+    fn from(metrics: AiMetrics) -> Self {
+        let count = |n: u64| format_number(i64::try_from(n).unwrap_or(i64::MAX));
+        Self {
+            calls: count(metrics.calls),
+            retries: count(metrics.retries),
+            failure_rate: format!("{:.1}%", metrics.failure_rate() * 100.0),
+            circuit_open: metrics.circuit_open,
+        }
+    }
+}
+
 fn format_cost(cost: f64) -> String {
     if cost < 0.01 {
         format!("${cost:.4}")
@@ -44,12 +140,185 @@ fn format_number(n: i64) -> String {
     result.chars().rev().collect()
 }
 
+fn format_bytes(bytes: i64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    #[allow(clippy::cast_precision_loss)] // Display only
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = UNITS[0];
+    for next in &UNITS[1..] {
+        if value < 1024.0 {
+            break;
+        }
+        value /= 1024.0;
+        unit = next;
+    }
+    format!("{value:.1} {unit}")
+}
+
+fn table_label(table: &str) -> &'static str {
+    match table {
+        "roasters" => "Roasters",
+        "roasts" => "Roasts",
+        "bags" => "Bags",
+        "gear" => "Gear",
+        "brews" => "Brews",
+        "cafes" => "Cafes",
+        "cups" => "Cups",
+        "timeline_events" => "Timeline events",
+        "entity_images" => "Images",
+        "users" => "Users",
+        "passkey_credentials" => "Passkeys",
+        "ai_usage" => "AI calls",
+        _ => "Other",
+    }
+}
+
+#[derive(Serialize)]
+pub struct TableCountView {
+    pub label: &'static str,
+    pub rows: String,
+}
+
+#[derive(Serialize)]
+pub struct InstanceView {
+    pub database_size: String,
+    pub image_storage: String,
+    pub image_count: String,
+    pub ai_calls_this_month: String,
+    pub ai_cost_this_month: String,
+    pub active_sessions: i64,
+    pub active_tokens: i64,
+    pub slow_queries: String,
+    /// The slow query threshold, or `None` when slow queries aren't logged.
+    pub slow_query_threshold: Option<String>,
+    pub table_counts: Vec<TableCountView>,
+    pub last_maintenance: Option<MaintenanceView>,
+    pub image_purge: ImagePurgeView,
+}
+
+#[derive(Serialize)]
+pub struct MaintenanceView {
+    pub ran_at: String,
+    pub checkpointed_frames: String,
+    pub size_change: String,
+    pub vacuumed: bool,
+    pub checkpoint_busy: bool,
+    pub error: Option<String>,
+}
+
+impl From<MaintenanceRun> for MaintenanceView {
+    fn from(run: MaintenanceRun) -> Self {
+        Self {
+            ran_at: run.ran_at.format("%Y-%m-%d %H:%M").to_string(),
+            checkpointed_frames: format_number(run.checkpointed_frames),
+            size_change: format!(
+                "{} → {}",
+                format_bytes(run.bytes_before),
+                format_bytes(run.bytes_after)
+            ),
+            vacuumed: run.vacuumed,
+            checkpoint_busy: run.checkpoint_busy,
+            error: run.error,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct ImagePurgeView {
+    pub awaiting: String,
+    /// Images removed so far by the pass in progress.
+    pub running: Option<String>,
+    pub last_run: Option<ImagePurgeRunView>,
+}
+
+#[derive(Serialize)]
+pub struct ImagePurgeRunView {
+    pub finished_at: String,
+    pub purged: String,
+    pub duration: String,
+    pub error: Option<String>,
+}
+
+impl From<ImagePurgeRun> for ImagePurgeRunView {
+    fn from(run: ImagePurgeRun) -> Self {
+        Self {
+            finished_at: run.finished_at.format("%Y-%m-%d %H:%M").to_string(),
+            purged: format_number(i64::try_from(run.purged).unwrap_or(i64::MAX)),
+            duration: format!("{} ms", format_number(run.duration_ms)),
+            error: run.error,
+        }
+    }
+}
+
+impl InstanceView {
+    /// Fill in the purge task's progress, which lives in memory rather than
+    /// in the overview.
+    fn with_image_purge(mut self, progress: ImagePurgeProgress) -> Self {
+        self.image_purge.running = progress
+            .running
+            .map(|purged| format_number(i64::try_from(purged).unwrap_or(i64::MAX)));
+        self.image_purge.last_run = progress.last_run.map(ImagePurgeRunView::from);
+        self
+    }
+}
+
+impl From<InstanceOverview> for InstanceView {
+    fn from(overview: InstanceOverview) -> Self {
+        Self {
+            database_size: format_bytes(overview.database_bytes),
+            image_storage: format_bytes(overview.image_bytes),
+            image_count: format_number(overview.image_count),
+            ai_calls_this_month: format_number(overview.ai_usage_this_month.calls),
+            ai_cost_this_month: format_cost(overview.ai_usage_this_month.cost),
+            active_sessions: overview.active_sessions,
+            active_tokens: overview.active_tokens,
+            slow_queries: format_number(i64::try_from(overview.slow_queries).unwrap_or(i64::MAX)),
+            slow_query_threshold: slow_query_threshold().map(|t| {
+                format!(
+                    "over {} ms",
+                    format_number(i64::try_from(t.as_millis()).unwrap_or(i64::MAX))
+                )
+            }),
+            table_counts: overview
+                .table_counts
+                .into_iter()
+                .map(|t| TableCountView {
+                    label: table_label(&t.table),
+                    rows: format_number(t.rows),
+                })
+                .collect(),
+            last_maintenance: overview.last_maintenance.map(MaintenanceView::from),
+            image_purge: ImagePurgeView {
+                awaiting: format_number(overview.images_awaiting_purge),
+                running: None,
+                last_run: None,
+            },
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct QuickNoteSettingView {
+    pub id: i64,
+    pub label: String,
+    pub emoji: String,
+}
+
 #[derive(Serialize)]
-pub struct PasskeyView {
+pub struct ProcessSettingView {
     pub id: i64,
     pub name: String,
-    pub created_at: String,
-    pub last_used_at: Option<String>,
+    pub family: String,
+}
+
+#[derive(Serialize)]
+pub struct ExchangeRateView {
+    pub currency: String,
+    pub rate: String,
+    pub updated: String,
 }
 
 #[derive(Serialize)]
@@ -60,7 +329,39 @@ pub struct TokenView {
     pub last_used_at: Option<String>,
 }
 
-fn format_date(dt: DateTime<Utc>) -> String {
+#[derive(Serialize)]
+pub struct InviteView {
+    pub id: i64,
+    pub created_at: String,
+    pub expires_at: String,
+    pub uses: String,
+    pub status: &'static str,
+    pub is_active: bool,
+}
+
+impl InviteView {
+    fn new(token: RegistrationToken, now: DateTime<Utc>) -> Self {
+        let status = if token.is_revoked() {
+            "Revoked"
+        } else if token.is_exhausted() {
+            "Used up"
+        } else if token.is_expired(now) {
+            "Expired"
+        } else {
+            "Active"
+        };
+        Self {
+            id: i64::from(token.id),
+            created_at: format_date(token.created_at),
+            expires_at: format_date(token.expires_at),
+            uses: format!("{} of {}", token.use_count, token.max_uses),
+            status,
+            is_active: token.is_valid(now),
+        }
+    }
+}
+
+pub(super) fn format_date(dt: DateTime<Utc>) -> String {
     dt.format("%Y-%m-%d").to_string()
 }
 
@@ -73,12 +374,101 @@ struct AdminTemplate {
     is_authenticated: bool,
     version_info: &'static crate::VersionInfo,
     ai_usage: Option<AiUsageView>,
-    passkeys: Vec<PasskeyView>,
+    ai_months: Vec<AiUsageMonthView>,
+    ai_prices: Vec<AiPriceView>,
+    ai_health: Option<AiHealthView>,
+    instance: Option<InstanceView>,
     tokens: Vec<TokenView>,
+    invites: Vec<InviteView>,
+    quick_notes: Vec<QuickNoteSettingView>,
+    processes: Vec<ProcessSettingView>,
+    process_families: &'static [&'static str],
+    low_stock_threshold: String,
+    dust_threshold: String,
+    base_currency: String,
+    exchange_rates: Vec<ExchangeRateView>,
+    map_tile_url: String,
+    map_attribution: String,
+    slug_strategies: &'static [SlugStrategy],
+    slug_strategy: SlugStrategy,
 }
 
 // --- Page handler ---
 
+async fn load_invites(state: &AppState) -> Result<Vec<InviteView>, StatusCode> {
+    let invites = state.registration_token_repo.list().await.map_err(|err| {
+        error!(error = %err, "failed to list registration tokens for admin page");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let now = state.clock.now();
+    Ok(invites
+        .into_iter()
+        .map(|token| InviteView::new(token, now))
+        .collect())
+}
+
+async fn load_quick_notes(state: &AppState) -> Result<Vec<QuickNoteSettingView>, StatusCode> {
+    let notes = state.quick_note_repo.list().await.map_err(|err| {
+        error!(error = %err, "failed to list quick notes for admin page");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(notes
+        .into_iter()
+        .map(|n| QuickNoteSettingView {
+            id: i64::from(n.id),
+            label: n.label,
+            emoji: n.emoji,
+        })
+        .collect())
+}
+
+async fn load_processes(state: &AppState) -> Result<Vec<ProcessSettingView>, StatusCode> {
+    let processes = state.process_repo.list().await.map_err(|err| {
+        error!(error = %err, "failed to list custom processes for admin page");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(processes
+        .into_iter()
+        .map(|p| ProcessSettingView {
+            id: i64::from(p.id),
+            name: p.name,
+            family: p.family,
+        })
+        .collect())
+}
+
+async fn load_exchange_rates(state: &AppState) -> Result<Vec<ExchangeRateView>, StatusCode> {
+    let rates = state
+        .settings_repo
+        .list_exchange_rates()
+        .await
+        .map_err(|err| {
+            error!(error = %err, "failed to list exchange rates for admin page");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    Ok(rates
+        .into_iter()
+        .map(|rate| ExchangeRateView {
+            currency: rate.currency,
+            rate: rate.rate.to_string(),
+            updated: rate.updated_at.format("%Y-%m-%d").to_string(),
+        })
+        .collect())
+}
+
+async fn load_ai_prices(state: &AppState) -> Result<Vec<AiPriceView>, StatusCode> {
+    let prices = state.ai_usage_repo.list_prices().await.map_err(|err| {
+        error!(error = %err, "failed to list AI prices for admin page");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(prices.into_iter().map(AiPriceView::from).collect())
+}
+
 #[tracing::instrument(skip(state, cookies))]
 pub(crate) async fn admin_page(
     State(state): State<AppState>,
@@ -94,7 +484,7 @@ pub(crate) async fn admin_page(
         .get_by_token_hash(&session_hash)
         .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    if session.is_expired() {
+    if session.is_expired(state.clock.now()) {
         return Ok(Redirect::to("/login").into_response());
     }
     let auth_user = state
@@ -103,23 +493,6 @@ pub(crate) async fn admin_page(
         .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
-    let passkeys = state
-        .passkey_repo
-        .list_by_user(auth_user.id)
-        .await
-        .map_err(|err| {
-            error!(error = %err, "failed to list passkeys for admin page");
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?
-        .into_iter()
-        .map(|p| PasskeyView {
-            id: i64::from(p.id),
-            name: p.name,
-            created_at: format_date(p.created_at),
-            last_used_at: p.last_used_at.map(format_date),
-        })
-        .collect();
-
     let tokens = state
         .token_repo
         .list_by_user(auth_user.id)
@@ -138,6 +511,15 @@ pub(crate) async fn admin_page(
         })
         .collect();
 
+    let invites = load_invites(&state).await?;
+    let quick_notes = load_quick_notes(&state).await?;
+    let processes = load_processes(&state).await?;
+    let exchange_rates = load_exchange_rates(&state).await?;
+    let settings = state.settings_repo.get().await.map_err(|err| {
+        error!(error = %err, "failed to load settings for admin page");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
     let ai_usage = match state.ai_usage_repo.summary_for_user(auth_user.id).await {
         Ok(summary) => Some(summary),
         Err(err) => {
@@ -151,14 +533,89 @@ pub(crate) async fn admin_page(
         cost: format_cost(s.total_cost),
     });
 
+    let ai_months = match state.ai_usage_repo.monthly_for_user(auth_user.id).await {
+        Ok(rows) => ai_usage_months(rows),
+        Err(err) => {
+            warn!(error = %err, "failed to load monthly AI usage");
+            Vec::new()
+        }
+    };
+    let ai_prices = load_ai_prices(&state).await?;
+
+    let ai_metrics = state.ai_guard.metrics();
+    let ai_health = (ai_metrics.calls > 0).then(|| AiHealthView::from(ai_metrics));
+
+    let instance = match state.overview_service.overview().await {
+        Ok(overview) => Some(
+            InstanceView::from(overview).with_image_purge(state.image_purge_service.progress()),
+        ),
+        Err(err) => {
+            warn!(error = %err, "failed to load instance overview");
+            None
+        }
+    };
+
     let template = AdminTemplate {
         nav_active: "admin",
         is_authenticated: true,
         version_info: &crate::VERSION_INFO,
         ai_usage,
-        passkeys,
+        ai_months,
+        ai_prices,
+        ai_health,
+        instance,
         tokens,
+        invites,
+        quick_notes,
+        processes,
+        process_families: &PROCESS_FAMILIES,
+        low_stock_threshold: settings.low_stock_threshold.to_string(),
+        dust_threshold: settings.dust_threshold.to_string(),
+        base_currency: settings.base_currency.unwrap_or_default(),
+        exchange_rates,
+        map_tile_url: settings.map_tile_url.unwrap_or_default(),
+        map_attribution: settings.map_attribution.unwrap_or_default(),
+        slug_strategies: &SlugStrategy::ALL,
+        slug_strategy: settings.slug_strategy,
     };
 
     render_html(template).map(IntoResponse::into_response)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_bytes_units() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KB");
+        assert_eq!(format_bytes(5 * 1024 * 1024), "5.0 MB");
+        assert_eq!(format_bytes(3 * 1024 * 1024 * 1024), "3.0 GB");
+    }
+
+    #[test]
+    fn ai_usage_is_grouped_by_month() {
+        let row = |month: &str, model: &str, cost| AiUsageMonth {
+            month: month.to_string(),
+            model: model.to_string(),
+            calls: 2,
+            prompt_tokens: 1_500,
+            completion_tokens: 300,
+            cost,
+        };
+        let months = ai_usage_months(vec![
+            row("2026-10", "google/gemini-3-flash-preview", 0.02),
+            row("2026-10", "openrouter/free", 0.0),
+            row("2026-09", "google/gemini-3-flash-preview", 0.5),
+        ]);
+
+        assert_eq!(months.len(), 2);
+        assert_eq!(months[0].month, "October 2026");
+        assert_eq!(months[0].cost, "$0.02");
+        assert_eq!(months[0].models.len(), 2);
+        assert_eq!(months[0].models[0].prompt_tokens, "1,500");
+        assert_eq!(months[1].month, "September 2026");
+        assert_eq!(months[1].cost, "$0.50");
+    }
+}
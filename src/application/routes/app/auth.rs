@@ -80,7 +80,7 @@ pub async fn is_authenticated(state: &AppState, cookies: &Cookies) -> bool {
         .get_by_token_hash(&session_token_hash)
         .await
     {
-        Ok(session) => !session.is_expired(),
+        Ok(session) => !session.is_expired(state.clock.now()),
         Err(err) => {
             warn!(error = %err, "session lookup failed during auth check");
             false
@@ -7,12 +7,14 @@ use crate::application::auth::AuthenticatedUser;
 use crate::application::errors::map_app_error;
 use crate::application::routes::api::images::resolve_image_url;
 use crate::application::routes::render_html;
-use crate::application::routes::support::load_roast_options;
+use crate::application::routes::support::{Authors, load_roast_options};
 use crate::application::state::AppState;
+use crate::domain::bag_ledger::BagLedgerKind;
 use crate::domain::entity_type::EntityType;
+use crate::domain::formatting::format_price;
 use crate::domain::ids::BagId;
 use crate::presentation::web::templates::{BagDetailTemplate, BagEditTemplate};
-use crate::presentation::web::views::BagDetailView;
+use crate::presentation::web::views::{BagDetailView, BagLedgerEntryView};
 
 #[tracing::instrument(skip(state, cookies))]
 pub(crate) async fn bag_detail_page(
@@ -28,7 +30,7 @@ pub(crate) async fn bag_detail_page(
         .await
         .map_err(|e| map_app_error(e.into()))?;
 
-    let (roast, image_url) = tokio::try_join!(
+    let (roast, image_url, roast_image_url) = tokio::try_join!(
         async {
             state
                 .roast_repo
@@ -36,6 +38,9 @@ pub(crate) async fn bag_detail_page(
                 .await
                 .map_err(|e| map_app_error(e.into()))
         },
+        async {
+            Ok::<_, StatusCode>(resolve_image_url(&state, EntityType::Bag, i64::from(id)).await)
+        },
         async {
             Ok::<_, StatusCode>(
                 resolve_image_url(&state, EntityType::Roast, i64::from(bag.bag.roast_id)).await,
@@ -49,6 +54,26 @@ pub(crate) async fn bag_detail_page(
         .await
         .map_err(|e| map_app_error(e.into()))?;
 
+    let ledger = state
+        .bag_repo
+        .list_ledger(id)
+        .await
+        .map_err(|e| map_app_error(e.into()))?;
+
+    let authors = Authors::load(&state).await;
+    let author = authors.name(bag.bag.created_by);
+    let (remaining_grams, amount_grams) = (bag.bag.remaining, bag.bag.amount);
+    let brew_count = ledger
+        .iter()
+        .filter(|entry| entry.kind == BagLedgerKind::Brew)
+        .count();
+    let ledger = ledger
+        .into_iter()
+        .map(|entry| {
+            let author = authors.name(entry.created_by);
+            BagLedgerEntryView::new(entry, author)
+        })
+        .collect();
     let view = BagDetailView::from_parts(bag, &roast, &roaster);
 
     let template = BagDetailTemplate {
@@ -60,7 +85,13 @@ pub(crate) async fn bag_detail_page(
         bag: view,
         roaster_slug: roaster.slug.clone(),
         roast_slug: roast.slug.clone(),
+        author,
         image_url,
+        roast_image_url,
+        remaining_grams,
+        amount_grams,
+        brew_count,
+        ledger,
     };
 
     render_html(template).map(IntoResponse::into_response)
@@ -79,6 +110,12 @@ pub(crate) async fn bag_edit_page(
         .map_err(|e| map_app_error(e.into()))?;
 
     let roast_options = load_roast_options(&state).await.map_err(map_app_error)?;
+    let image_url = resolve_image_url(&state, EntityType::Bag, i64::from(id)).await;
+    let settings = state
+        .settings_repo
+        .get()
+        .await
+        .map_err(|e| map_app_error(e.into()))?;
 
     let roast_date = bag
         .bag
@@ -106,7 +143,22 @@ pub(crate) async fn bag_edit_page(
         roast_date,
         amount: bag.bag.amount,
         remaining: bag.bag.remaining,
+        low_stock_threshold: bag
+            .bag
+            .low_stock_threshold
+            .map(|t| t.to_string())
+            .unwrap_or_default(),
+        default_low_stock_threshold: settings.low_stock_threshold.to_string(),
+        price: bag.bag.price.map(format_price).unwrap_or_default(),
+        currency: bag.bag.currency.clone().unwrap_or_default(),
+        best_before: bag
+            .bag
+            .best_before
+            .map(|d| d.to_string())
+            .unwrap_or_default(),
+        batch_code: bag.bag.batch_code.clone().unwrap_or_default(),
         roast_options,
+        image_url,
         signals_json,
     };
 
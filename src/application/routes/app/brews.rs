@@ -1,27 +1,40 @@
-use axum::extract::{Path, State};
+use axum::extract::{Path, Query, State};
 use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
+use serde::Deserialize;
 use tower_cookies::Cookies;
 
-use crate::application::auth::AuthenticatedUser;
+use crate::application::auth::{AuthenticatedUser, authenticate_via_session};
 use crate::application::errors::map_app_error;
 use crate::application::routes::api::brews::load_brew_form_data;
 use crate::application::routes::api::images::resolve_image_url;
 use crate::application::routes::render_html;
+use crate::application::routes::support::{Authors, load_comments};
 use crate::application::state::AppState;
+use crate::domain::bags::Bag;
+use crate::domain::brews::QuickNote;
+use crate::domain::comments::CommentTarget;
 use crate::domain::entity_type::EntityType;
-use crate::domain::ids::BrewId;
-use crate::presentation::web::templates::{BrewDetailTemplate, BrewEditTemplate};
-use crate::presentation::web::views::BrewDetailView;
+use crate::domain::ids::{BrewId, UserId};
+use crate::infrastructure::auth::hash_token;
+use crate::presentation::web::templates::{
+    BrewDetailTemplate, BrewEditTemplate, SharedBrewTemplate,
+};
+use crate::presentation::web::views::{
+    BagFinishPromptView, BagOptionView, BrewDetailView, BrewShareView,
+};
 
-#[tracing::instrument(skip(state, cookies))]
-pub(crate) async fn brew_detail_page(
-    State(state): State<AppState>,
-    cookies: Cookies,
-    Path(id): Path<BrewId>,
-) -> Result<Response, StatusCode> {
-    let is_authenticated = crate::application::routes::is_authenticated(&state, &cookies).await;
+/// A brew with the roast, roaster and image needed to render it.
+struct LoadedBrew {
+    view: BrewDetailView,
+    roaster_slug: String,
+    roast_slug: String,
+    image_url: Option<String>,
+    created_by: Option<UserId>,
+    bag: Bag,
+}
 
+async fn load_brew(state: &AppState, id: BrewId) -> Result<LoadedBrew, StatusCode> {
     let brew_details = state
         .brew_repo
         .get_with_details(id)
@@ -37,7 +50,7 @@ pub(crate) async fn brew_detail_page(
                 .map_err(|e| map_app_error(e.into()))
         },
         async {
-            Ok::<_, StatusCode>(resolve_image_url(&state, EntityType::Brew, i64::from(id)).await)
+            Ok::<_, StatusCode>(resolve_image_url(state, EntityType::Brew, i64::from(id)).await)
         },
     )?;
 
@@ -51,7 +64,7 @@ pub(crate) async fn brew_detail_page(
         },
         async {
             Ok::<_, StatusCode>(
-                resolve_image_url(&state, EntityType::Roast, i64::from(bag.roast_id)).await,
+                resolve_image_url(state, EntityType::Roast, i64::from(bag.roast_id)).await,
             )
         },
     )?;
@@ -62,9 +75,63 @@ pub(crate) async fn brew_detail_page(
         .await
         .map_err(|e| map_app_error(e.into()))?;
 
-    let image_url = brew_image_url.or(roast_image_url);
+    let created_by = brew_details.brew.created_by;
+    Ok(LoadedBrew {
+        view: BrewDetailView::from_parts(brew_details, &roast, &roaster),
+        roaster_slug: roaster.slug,
+        roast_slug: roast.slug,
+        image_url: brew_image_url.or(roast_image_url),
+        created_by,
+        bag,
+    })
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct BrewDetailQuery {
+    /// Set by the brew create redirect when the bag is down to the dust.
+    #[serde(default)]
+    finish_bag: bool,
+}
+
+#[tracing::instrument(skip(state, cookies))]
+pub(crate) async fn brew_detail_page(
+    State(state): State<AppState>,
+    cookies: Cookies,
+    Path(id): Path<BrewId>,
+    Query(query): Query<BrewDetailQuery>,
+) -> Result<Response, StatusCode> {
+    let user = authenticate_via_session(&state, &cookies).await;
+    let is_authenticated = user.is_some();
 
-    let view = BrewDetailView::from_parts(brew_details, &roast, &roaster);
+    let brew = load_brew(&state, id).await?;
+
+    let shares = if is_authenticated {
+        state
+            .brew_share_repo
+            .list_for_brew(id)
+            .await
+            .map_err(|e| map_app_error(e.into()))?
+            .iter()
+            .map(BrewShareView::from)
+            .collect()
+    } else {
+        Vec::new()
+    };
+    let comments = load_comments(&state, CommentTarget::Brew(id), user.map(|user| user.id)).await;
+
+    // Re-check the bag: it may have been finished since the redirect.
+    let finish_prompt = if is_authenticated && query.finish_bag {
+        let settings = state
+            .settings_repo
+            .get()
+            .await
+            .map_err(|e| map_app_error(e.into()))?;
+        brew.bag
+            .finish_suggestion(settings.dust_threshold)
+            .map(BagFinishPromptView::from)
+    } else {
+        None
+    };
 
     let template = BrewDetailTemplate {
         nav_active: "",
@@ -72,10 +139,45 @@ pub(crate) async fn brew_detail_page(
         version_info: &crate::VERSION_INFO,
         base_url: crate::base_url(),
         edit_url: format!("/brews/{id}/edit"),
-        brew: view,
-        roaster_slug: roaster.slug.clone(),
-        roast_slug: roast.slug.clone(),
-        image_url,
+        brew: brew.view,
+        roaster_slug: brew.roaster_slug,
+        roast_slug: brew.roast_slug,
+        image_url: brew.image_url,
+        author: Authors::load(&state).await.name(brew.created_by),
+        shares,
+        comments,
+        finish_prompt,
+    };
+
+    render_html(template).map(IntoResponse::into_response)
+}
+
+/// Public, read-only view of a brew reached through a share link. Unknown,
+/// expired and revoked links all look the same: not found.
+#[tracing::instrument(skip(state, token))]
+pub(crate) async fn shared_brew_page(
+    State(state): State<AppState>,
+    Path(token): Path<String>,
+) -> Result<Response, StatusCode> {
+    let share = state
+        .brew_share_repo
+        .get_by_token_hash(&hash_token(&token))
+        .await
+        .map_err(|e| map_app_error(e.into()))?;
+
+    if !share.is_active(state.clock.now()) {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    let brew = load_brew(&state, share.brew_id).await?;
+
+    let template = SharedBrewTemplate {
+        nav_active: "",
+        is_authenticated: false,
+        version_info: &crate::VERSION_INFO,
+        base_url: crate::base_url(),
+        brew: brew.view,
+        image_url: brew.image_url,
     };
 
     render_html(template).map(IntoResponse::into_response)
@@ -93,7 +195,20 @@ pub(crate) async fn brew_edit_page(
         .await
         .map_err(|e| map_app_error(e.into()))?;
 
-    let form_data = load_brew_form_data(&state).await.map_err(map_app_error)?;
+    let mut form_data = load_brew_form_data(&state).await.map_err(map_app_error)?;
+    // A brew from a finished bag keeps it pickable, after the open bags.
+    if !form_data
+        .bag_options
+        .iter()
+        .any(|option| option.id == brew.brew.bag_id.to_string())
+    {
+        let bag = state
+            .bag_repo
+            .get_with_roast(brew.brew.bag_id)
+            .await
+            .map_err(|e| map_app_error(e.into()))?;
+        form_data.bag_options.push(BagOptionView::from(bag));
+    }
 
     let image_url = resolve_image_url(&state, EntityType::Brew, i64::from(id)).await;
 
@@ -115,18 +230,30 @@ pub(crate) async fn brew_edit_page(
             .unwrap_or_default(),
         water_volume: brew.brew.water_volume,
         water_temp: brew.brew.water_temp,
+        kettle_id: brew
+            .brew
+            .kettle_id
+            .map(|id| id.to_string())
+            .unwrap_or_default(),
+        actual_water_temp: brew
+            .brew
+            .actual_water_temp
+            .map(|temp| format!("{temp:.1}"))
+            .unwrap_or_default(),
+        bypass_water_ml: brew.brew.bypass_water_ml.unwrap_or(0),
         brew_time: brew.brew.brew_time.unwrap_or(0),
         quick_notes: brew
             .brew
             .quick_notes
             .iter()
-            .map(|n| n.form_value())
+            .map(QuickNote::form_value)
             .collect::<Vec<_>>()
             .join(","),
         bag_options: form_data.bag_options,
         grinder_options: form_data.grinder_options,
         brewer_options: form_data.brewer_options,
         filter_paper_options: form_data.filter_paper_options,
+        kettle_options: form_data.kettle_options,
         quick_note_options: form_data.quick_note_options,
         image_url,
     };
@@ -7,6 +7,7 @@ use crate::application::auth::AuthenticatedUser;
 use crate::application::errors::map_app_error;
 use crate::application::routes::api::images::resolve_image_url;
 use crate::application::routes::render_html;
+use crate::application::routes::support::{Authors, load_map_tiles};
 use crate::application::state::AppState;
 use crate::domain::entity_type::EntityType;
 use crate::domain::ids::CafeId;
@@ -30,6 +31,19 @@ pub(crate) async fn cafe_detail_page(
     let image_url = resolve_image_url(&state, EntityType::Cafe, i64::from(cafe.id)).await;
     let edit_url = format!("/cafes/{}/edit", cafe.id);
 
+    let delete_warning = if is_authenticated {
+        match state.cafe_repo.delete_preview(cafe.id).await {
+            Ok(preview) => preview.warning(),
+            Err(err) => {
+                tracing::warn!(error = %err, "failed to load delete preview");
+                String::new()
+            }
+        }
+    } else {
+        String::new()
+    };
+
+    let author = Authors::load(&state).await.name(cafe.created_by);
     let view = CafeDetailView::from(cafe);
 
     let template = CafeDetailTemplate {
@@ -39,7 +53,10 @@ pub(crate) async fn cafe_detail_page(
         base_url: crate::base_url(),
         edit_url,
         cafe: view,
+        author,
         image_url,
+        delete_warning,
+        map_tiles: load_map_tiles(&state).await,
     };
 
     render_html(template).map(IntoResponse::into_response)
@@ -90,6 +107,7 @@ pub(crate) async fn cafe_edit_page(
         website,
         image_url,
         signals_json,
+        map_tiles: load_map_tiles(&state).await,
     };
 
     render_html(template).map(IntoResponse::into_response)
@@ -1,34 +1,53 @@
-use axum::extract::State;
+use axum::extract::{Query, State};
 use axum::http::StatusCode;
 use axum::response::{IntoResponse, Redirect, Response};
+use serde::Deserialize;
 
 use crate::application::errors::map_app_error;
 use crate::application::routes::render_html;
-use crate::application::routes::support::{load_cafe_options, load_roast_options};
+use crate::application::routes::support::{
+    load_cafe_options, load_map_tiles, load_open_bag_options, load_roast_options,
+};
 use crate::application::state::AppState;
 use crate::presentation::web::templates::CheckInTemplate;
 
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct CheckInQuery {
+    /// Skip straight to picking a coffee at this cafe.
+    #[serde(default)]
+    pub cafe_id: Option<String>,
+}
+
 #[tracing::instrument(skip(state, cookies))]
 pub(crate) async fn checkin_page(
     State(state): State<AppState>,
     cookies: tower_cookies::Cookies,
+    Query(query): Query<CheckInQuery>,
 ) -> Result<Response, StatusCode> {
     let is_authenticated = crate::application::routes::is_authenticated(&state, &cookies).await;
     if !is_authenticated {
         return Ok(Redirect::to("/login").into_response());
     }
 
-    let (roast_options, cafe_options) = tokio::try_join!(
+    let (roast_options, cafe_options, bag_options) = tokio::try_join!(
         async { load_roast_options(&state).await.map_err(map_app_error) },
         async { load_cafe_options(&state).await.map_err(map_app_error) },
+        async { load_open_bag_options(&state).await.map_err(map_app_error) },
     )?;
 
+    let selected_cafe = query
+        .cafe_id
+        .and_then(|id| cafe_options.iter().find(|cafe| cafe.id == id).cloned());
+
     let template = CheckInTemplate {
         nav_active: "checkin",
         is_authenticated: true,
         version_info: &crate::VERSION_INFO,
         roast_options,
         cafe_options,
+        bag_options,
+        selected_cafe,
+        map_tiles: load_map_tiles(&state).await,
     };
 
     render_html(template).map(IntoResponse::into_response)
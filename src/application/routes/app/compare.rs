@@ -0,0 +1,59 @@
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use tower_cookies::Cookies;
+
+use crate::application::errors::map_app_error;
+use crate::application::routes::api::roasts::{load_roast_comparison, parse_compared_roasts};
+use crate::application::routes::render_html;
+use crate::application::routes::support::load_roast_options;
+use crate::application::state::AppState;
+use crate::presentation::web::templates::CompareTemplate;
+use crate::presentation::web::views::RoastComparisonView;
+
+#[tracing::instrument(skip(state, cookies))]
+pub(crate) async fn compare_page(
+    State(state): State<AppState>,
+    cookies: Cookies,
+    Query(params): Query<Vec<(String, String)>>,
+) -> Result<Response, StatusCode> {
+    let is_authenticated = crate::application::routes::is_authenticated(&state, &cookies).await;
+
+    let (ids, error) = match parse_compared_roasts(&params) {
+        Ok(ids) => (ids, None),
+        Err(message) => (Vec::new(), Some(message)),
+    };
+
+    // A single roast (e.g. from a detail page link) pre-fills the first
+    // picker; the table only renders once there is something to compare.
+    let columns = if ids.len() >= 2 {
+        load_roast_comparison(&state, &ids)
+            .await
+            .map_err(map_app_error)?
+            .into_iter()
+            .map(RoastComparisonView::from)
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    let mut slots: Vec<String> = ids.iter().map(ToString::to_string).collect();
+    if slots.len() < 2 {
+        slots.resize(2, String::new());
+    }
+
+    let roast_options = load_roast_options(&state).await.map_err(map_app_error)?;
+
+    let template = CompareTemplate {
+        nav_active: "",
+        is_authenticated,
+        version_info: &crate::VERSION_INFO,
+        base_url: crate::base_url(),
+        roast_options,
+        slots,
+        columns,
+        error,
+    };
+
+    render_html(template).map(IntoResponse::into_response)
+}
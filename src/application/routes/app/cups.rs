@@ -7,7 +7,7 @@ use crate::application::auth::AuthenticatedUser;
 use crate::application::errors::map_app_error;
 use crate::application::routes::api::images::resolve_image_url;
 use crate::application::routes::render_html;
-use crate::application::routes::support::{load_cafe_options, load_roast_options};
+use crate::application::routes::support::{Authors, load_cafe_options, load_roast_options};
 use crate::application::state::AppState;
 use crate::domain::entity_type::EntityType;
 use crate::domain::ids::CupId;
@@ -37,11 +37,15 @@ pub(crate) async fn cup_detail_page(
                 .map_err(|e| map_app_error(e.into()))
         },
         async {
-            state
-                .cafe_repo
-                .get(cup_details.cup.cafe_id)
-                .await
-                .map_err(|e| map_app_error(e.into()))
+            match cup_details.cup.cafe_id {
+                Some(cafe_id) => state
+                    .cafe_repo
+                    .get(cafe_id)
+                    .await
+                    .map(Some)
+                    .map_err(|e| map_app_error(e.into())),
+                None => Ok(None),
+            }
         },
     )?;
 
@@ -51,12 +55,17 @@ pub(crate) async fn cup_detail_page(
         .await
         .map_err(|e| map_app_error(e.into()))?;
 
+    let cafe_image_url = match &cafe {
+        Some(cafe) => resolve_image_url(&state, EntityType::Cafe, i64::from(cafe.id)).await,
+        None => None,
+    };
     let image_url = resolve_image_url(&state, EntityType::Cup, i64::from(id))
         .await
-        .or(resolve_image_url(&state, EntityType::Cafe, i64::from(cafe.id)).await)
+        .or(cafe_image_url)
         .or(resolve_image_url(&state, EntityType::Roast, i64::from(roast.id)).await);
 
-    let view = CupDetailView::from_parts(cup_details, &roast, &roaster, &cafe);
+    let author = Authors::load(&state).await.name(cup_details.cup.created_by);
+    let view = CupDetailView::from_parts(cup_details, &roast, &roaster, cafe.as_ref());
 
     let template = CupDetailTemplate {
         nav_active: "",
@@ -67,7 +76,7 @@ pub(crate) async fn cup_detail_page(
         cup: view,
         roaster_slug: roaster.slug.clone(),
         roast_slug: roast.slug.clone(),
-        cafe_slug: cafe.slug.clone(),
+        author,
         image_url,
     };
 
@@ -101,8 +110,12 @@ pub(crate) async fn cup_edit_page(
         id: cup.cup.id.to_string(),
         roast_id: cup.cup.roast_id.to_string(),
         roast_label: format!("{} ({})", cup.roast_name, cup.roaster_name),
-        cafe_id: cup.cup.cafe_id.to_string(),
-        cafe_label: format!("{}, {}", cup.cafe_name, cup.cafe_city),
+        cafe_id: cup
+            .cup
+            .cafe_id
+            .map(|cafe_id| cafe_id.to_string())
+            .unwrap_or_default(),
+        rating: cup.cup.rating.unwrap_or_default(),
         roast_options,
         cafe_options,
         image_url,
@@ -1,16 +1,26 @@
+use std::str::FromStr;
+
 use axum::extract::{Query, State};
 use axum::http::{HeaderMap, StatusCode};
 use axum::response::{IntoResponse, Response};
 use serde::Deserialize;
+use tracing::warn;
 
+use crate::application::auth::authenticate_via_session;
 use crate::application::errors::{AppError, map_app_error};
 use crate::application::routes::render_html;
 use crate::application::routes::support::{ListQuery, is_datastar_request};
 use crate::application::state::AppState;
+use crate::domain::RepositoryError;
+use crate::domain::bags::BagFilter;
+use crate::domain::gear::{GearCategory, GearFilter};
+use crate::domain::ids::UserId;
+use crate::domain::list_preferences::ListName;
 use crate::presentation::web::templates::{
     BagListTemplate, BrewListTemplate, CafeListTemplate, CupListTemplate, DataTemplate,
     GearListTemplate, RoastListTemplate, RoasterListTemplate, Tab, render_template,
 };
+use crate::presentation::web::views::SavedSearchView;
 
 const TABS: &[Tab] = &[
     Tab {
@@ -53,21 +63,55 @@ fn default_type() -> String {
     "brews".to_string()
 }
 
-#[tracing::instrument(skip(state, cookies, headers, data_type, list_query))]
+/// Filter query parameters understood by the data lists. Each list ignores
+/// filters it does not support.
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct ListFilterQuery {
+    #[serde(default)]
+    status: Option<String>,
+    #[serde(default)]
+    category: Option<String>,
+}
+
+impl ListFilterQuery {
+    fn bag_filter(&self) -> BagFilter {
+        match self.status.as_deref() {
+            Some("open") => BagFilter::open(),
+            Some("closed") => BagFilter::closed(),
+            _ => BagFilter::all(),
+        }
+    }
+
+    fn gear_filter(&self) -> GearFilter {
+        self.category
+            .as_deref()
+            .and_then(|category| GearCategory::from_str(category).ok())
+            .map_or_else(GearFilter::all, GearFilter::for_category)
+    }
+}
+
+#[tracing::instrument(skip(state, cookies, headers, data_type, list_query, filters))]
 pub(crate) async fn data_page(
     State(state): State<AppState>,
     cookies: tower_cookies::Cookies,
     headers: HeaderMap,
     Query(data_type): Query<DataType>,
     Query(list_query): Query<ListQuery>,
+    Query(filters): Query<ListFilterQuery>,
 ) -> Result<Response, StatusCode> {
     let entity_type = data_type.entity_type;
-    let is_authenticated = crate::application::routes::is_authenticated(&state, &cookies).await;
+    let user = authenticate_via_session(&state, &cookies).await;
+    let is_authenticated = user.is_some();
+    let list_query = match &user {
+        Some(user) => apply_list_preference(&state, user.id, &entity_type, list_query).await,
+        None => list_query,
+    };
     let search_value = list_query.search_value();
 
-    let content = render_entity_content(&state, &entity_type, list_query, is_authenticated)
-        .await
-        .map_err(map_app_error)?;
+    let content =
+        render_entity_content(&state, &entity_type, list_query, &filters, is_authenticated)
+            .await
+            .map_err(map_app_error)?;
 
     if is_datastar_request(&headers) {
         use axum::http::header::HeaderValue;
@@ -84,6 +128,11 @@ pub(crate) async fn data_page(
         return Ok(response);
     }
 
+    let saved_searches = match &user {
+        Some(user) => load_saved_searches(&state, user.id).await,
+        None => Vec::new(),
+    };
+
     let tabs: Vec<Tab> = TABS
         .iter()
         .map(|t| Tab {
@@ -105,11 +154,41 @@ pub(crate) async fn data_page(
         tab_fetch_mode: "inner",
         content,
         search_value,
+        saved_searches,
     };
 
     render_html(template).map(IntoResponse::into_response)
 }
 
+/// Fill in the user's saved page size and sort for this list, if any.
+async fn apply_list_preference(
+    state: &AppState,
+    user_id: UserId,
+    entity_type: &str,
+    list_query: ListQuery,
+) -> ListQuery {
+    // Unknown types render the brews list, so they use its preference too.
+    let list = entity_type.parse().unwrap_or(ListName::Brews);
+    match state.list_preference_repo.get(user_id, list).await {
+        Ok(preference) => list_query.with_preference(&preference),
+        Err(RepositoryError::NotFound) => list_query,
+        Err(err) => {
+            warn!(error = %err, %list, "failed to load list preference");
+            list_query
+        }
+    }
+}
+
+async fn load_saved_searches(state: &AppState, user_id: UserId) -> Vec<SavedSearchView> {
+    match state.saved_search_repo.list_by_user(user_id).await {
+        Ok(searches) => searches.into_iter().map(SavedSearchView::from).collect(),
+        Err(err) => {
+            warn!(error = %err, "failed to load saved searches");
+            Vec::new()
+        }
+    }
+}
+
 fn render_list<T: askama::Template>(template: T, label: &str) -> Result<String, AppError> {
     render_template(template)
         .map_err(|err| AppError::unexpected(format!("failed to render {label}: {err}")))
@@ -119,6 +198,7 @@ async fn render_entity_content(
     state: &AppState,
     entity_type: &str,
     list_query: ListQuery,
+    filters: &ListFilterQuery,
     is_authenticated: bool,
 ) -> Result<String, AppError> {
     // Normalize unknown types to brews
@@ -130,8 +210,8 @@ async fn render_entity_content(
     match entity_type {
         "roasters" => render_roasters(state, list_query, is_authenticated).await,
         "roasts" => render_roasts(state, list_query, is_authenticated).await,
-        "bags" => render_bags(state, list_query, is_authenticated).await,
-        "gear" => render_gear(state, list_query, is_authenticated).await,
+        "bags" => render_bags(state, list_query, filters.bag_filter(), is_authenticated).await,
+        "gear" => render_gear(state, list_query, filters.gear_filter(), is_authenticated).await,
         "cafes" => render_cafes(state, list_query, is_authenticated).await,
         "cups" => render_cups(state, list_query, is_authenticated).await,
         _ => render_brews(state, list_query, is_authenticated).await,
@@ -204,13 +284,18 @@ async fn render_roasts(
 async fn render_bags(
     state: &AppState,
     list_query: ListQuery,
+    filter: BagFilter,
     is_authenticated: bool,
 ) -> Result<String, AppError> {
     use crate::domain::bags::BagSortKey;
     let (request, search) = list_query.into_request_and_search::<BagSortKey>();
-    let data =
-        crate::application::routes::api::bags::load_bag_page(state, request, search.as_deref())
-            .await?;
+    let data = crate::application::routes::api::bags::load_filtered_bag_page(
+        state,
+        filter,
+        request,
+        search.as_deref(),
+    )
+    .await?;
     render_list(
         BagListTemplate {
             is_authenticated,
@@ -224,13 +309,18 @@ async fn render_bags(
 async fn render_gear(
     state: &AppState,
     list_query: ListQuery,
+    filter: GearFilter,
     is_authenticated: bool,
 ) -> Result<String, AppError> {
     use crate::domain::gear::GearSortKey;
     let (request, search) = list_query.into_request_and_search::<GearSortKey>();
-    let (gear, navigator) =
-        crate::application::routes::api::gear::load_gear_page(state, request, search.as_deref())
-            .await?;
+    let (gear, navigator) = crate::application::routes::api::gear::load_filtered_gear_page(
+        state,
+        filter,
+        request,
+        search.as_deref(),
+    )
+    .await?;
     render_list(
         GearListTemplate {
             is_authenticated,
@@ -0,0 +1,73 @@
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use tower_cookies::Cookies;
+
+use crate::application::errors::map_app_error;
+use crate::application::routes::render_html;
+use crate::application::state::AppState;
+use crate::domain::cafes::CafeSortKey;
+use crate::domain::events::EventFilter;
+use crate::domain::listing::SortDirection;
+use crate::domain::roasters::RoasterSortKey;
+use crate::presentation::web::templates::EventsTemplate;
+use crate::presentation::web::views::{CafeOptionView, EventView, RoasterOptionView};
+
+#[tracing::instrument(skip(state, cookies))]
+pub(crate) async fn events_page(
+    State(state): State<AppState>,
+    cookies: Cookies,
+) -> Result<Response, StatusCode> {
+    let is_authenticated = crate::application::routes::is_authenticated(&state, &cookies).await;
+
+    let (events, roasters, cafes) = tokio::try_join!(
+        async {
+            state
+                .event_repo
+                .list(EventFilter::default())
+                .await
+                .map_err(|e| map_app_error(e.into()))
+        },
+        async {
+            state
+                .roaster_repo
+                .list_all_sorted(RoasterSortKey::Name, SortDirection::Asc)
+                .await
+                .map_err(|e| map_app_error(e.into()))
+        },
+        async {
+            state
+                .cafe_repo
+                .list_all_sorted(CafeSortKey::Name, SortDirection::Asc)
+                .await
+                .map_err(|e| map_app_error(e.into()))
+        },
+    )?;
+
+    let events = events
+        .iter()
+        .map(|event| EventView::new(event, &roasters, &cafes))
+        .collect();
+
+    // Only offer roasters and cafes to link when there's a form to offer them in.
+    let (roaster_options, cafe_options) = if is_authenticated {
+        (
+            roasters.into_iter().map(RoasterOptionView::from).collect(),
+            cafes.into_iter().map(CafeOptionView::from).collect(),
+        )
+    } else {
+        (Vec::new(), Vec::new())
+    };
+
+    let template = EventsTemplate {
+        nav_active: "",
+        is_authenticated,
+        version_info: &crate::VERSION_INFO,
+        base_url: crate::base_url(),
+        events,
+        roaster_options,
+        cafe_options,
+    };
+
+    render_html(template).map(IntoResponse::into_response)
+}
@@ -5,13 +5,17 @@ use tower_cookies::Cookies;
 
 use crate::application::auth::AuthenticatedUser;
 use crate::application::errors::map_app_error;
+use crate::application::routes::api::brews::load_gear_options;
 use crate::application::routes::api::images::resolve_image_url;
 use crate::application::routes::render_html;
+use crate::application::routes::support::Authors;
 use crate::application::state::AppState;
 use crate::domain::entity_type::EntityType;
+use crate::domain::gear::{BrewMethod, GearCategory, GearSortKey};
 use crate::domain::ids::GearId;
+use crate::domain::listing::{ListRequest, SortDirection};
 use crate::presentation::web::templates::{GearDetailTemplate, GearEditTemplate};
-use crate::presentation::web::views::GearDetailView;
+use crate::presentation::web::views::{GearDetailView, GrinderCalibrationView};
 
 #[tracing::instrument(skip(state, cookies))]
 pub(crate) async fn gear_detail_page(
@@ -29,6 +33,29 @@ pub(crate) async fn gear_detail_page(
 
     let image_url = resolve_image_url(&state, EntityType::Gear, i64::from(id)).await;
 
+    let is_grinder = gear.category == GearCategory::Grinder;
+    let (calibrations, other_grinders) = if is_grinder {
+        let request = ListRequest::show_all(GearSortKey::Make, SortDirection::Asc);
+        let grinders = load_gear_options(&state, GearCategory::Grinder, &request, None)
+            .await
+            .map_err(map_app_error)?;
+        let points = state
+            .grinder_calibration_repo
+            .list(Some(id))
+            .await
+            .map_err(|e| map_app_error(e.into()))?;
+        let calibrations = points
+            .iter()
+            .map(|point| GrinderCalibrationView::for_grinder(point, id, &grinders))
+            .collect();
+        let own_id = id.to_string();
+        let others = grinders.into_iter().filter(|g| g.id != own_id).collect();
+        (calibrations, others)
+    } else {
+        (Vec::new(), Vec::new())
+    };
+
+    let author = Authors::load(&state).await.name(gear.created_by);
     let view = GearDetailView::from(gear);
 
     let template = GearDetailTemplate {
@@ -38,7 +65,11 @@ pub(crate) async fn gear_detail_page(
         base_url: crate::base_url(),
         edit_url: format!("/gear/{id}/edit"),
         gear: view,
+        author,
         image_url,
+        is_grinder,
+        calibrations,
+        other_grinders,
     };
 
     render_html(template).map(IntoResponse::into_response)
@@ -58,6 +89,7 @@ pub(crate) async fn gear_edit_page(
 
     let image_url = resolve_image_url(&state, EntityType::Gear, i64::from(id)).await;
 
+    let profile = gear.brewer_profile();
     let make = gear.make;
     let model = gear.model;
 
@@ -78,6 +110,33 @@ pub(crate) async fn gear_edit_page(
         category: gear.category.display_label().to_string(),
         make,
         model,
+        is_brewer: gear.category == GearCategory::Brewer,
+        water_temp: gear
+            .water_temp
+            .map(|temp| format!("{temp:.1}"))
+            .unwrap_or_default(),
+        brew_method: profile.method.as_ref().map_or("", BrewMethod::as_str),
+        uses_filter: profile
+            .uses_filter
+            .map_or("", |uses| if uses { "true" } else { "false" }),
+        is_kettle: gear.category == GearCategory::Kettle,
+        temp_offset: gear
+            .temp_offset
+            .map(|offset| format!("{offset:.1}"))
+            .unwrap_or_default(),
+        water_hardness: gear
+            .water_hardness
+            .map(|ppm| ppm.to_string())
+            .unwrap_or_default(),
+        is_filter_paper: gear.category == GearCategory::FilterPaper,
+        stock_count: gear
+            .stock_count
+            .map(|count| count.to_string())
+            .unwrap_or_default(),
+        low_stock_threshold: gear
+            .low_stock_threshold
+            .map(|threshold| threshold.to_string())
+            .unwrap_or_default(),
         image_url,
         signals_json,
     };
@@ -4,16 +4,20 @@ use axum::response::{IntoResponse, Response};
 
 use crate::application::errors::{AppError, map_app_error};
 use crate::application::routes::render_html;
+use crate::application::routes::support::{Authors, render_fragment};
 use crate::application::state::AppState;
 use crate::domain::bags::{BagFilter, BagSortKey};
 use crate::domain::brews::{BrewFilter, BrewSortKey};
 use crate::domain::listing::{ListRequest, PageSize, SortDirection, SortKey};
-use crate::domain::timeline::TimelineSortKey;
+use crate::domain::quick_actions::DEFAULT_QUICK_ACTION_LIMIT;
+use crate::domain::timeline::{TimelineFilter, TimelineSortKey};
 use rand::seq::SliceRandom;
 
 use crate::domain::stats::CachedStats;
-use crate::presentation::web::templates::HomeTemplate;
-use crate::presentation::web::views::{BagView, BrewView, StatCard, StatsView, TimelineEventView};
+use crate::presentation::web::templates::{HomeTemplate, QuickActionsFragment};
+use crate::presentation::web::views::{
+    BagView, BrewView, CafeView, RoastView, RoasterView, StatCard, StatsView, TimelineEventView,
+};
 
 #[allow(clippy::similar_names)]
 #[tracing::instrument(skip(state, cookies))]
@@ -61,6 +65,9 @@ pub(crate) async fn home_page(
         recent_brews: content.recent_brews,
         open_bags: content.open_bags,
         recent_events: content.recent_events,
+        favourite_roasters: content.favourite_roasters,
+        favourite_roasts: content.favourite_roasts,
+        favourite_cafes: content.favourite_cafes,
         stats,
         stat_cards,
     };
@@ -68,10 +75,35 @@ pub(crate) async fn home_page(
     render_html(template).map(IntoResponse::into_response)
 }
 
+/// GET /quick-actions — suggested shortcuts, loaded into the home page by Datastar
+#[tracing::instrument(skip(state, cookies))]
+pub(crate) async fn quick_actions_fragment(
+    State(state): State<AppState>,
+    cookies: tower_cookies::Cookies,
+) -> Result<Response, StatusCode> {
+    if !crate::application::routes::is_authenticated(&state, &cookies).await {
+        return Err(StatusCode::UNAUTHORIZED);
+    }
+
+    let actions = state
+        .quick_action_service
+        .suggest(DEFAULT_QUICK_ACTION_LIMIT)
+        .await
+        .map_err(|err| map_app_error(AppError::from(err)))?
+        .into_iter()
+        .map(Into::into)
+        .collect();
+
+    render_fragment(QuickActionsFragment { actions }, "#quick-actions").map_err(map_app_error)
+}
+
 struct HomeContent {
     recent_brews: Vec<BrewView>,
     open_bags: Vec<BagView>,
     recent_events: Vec<TimelineEventView>,
+    favourite_roasters: Vec<RoasterView>,
+    favourite_roasts: Vec<RoastView>,
+    favourite_cafes: Vec<CafeView>,
 }
 
 async fn load_home_content(state: &AppState) -> Result<HomeContent, AppError> {
@@ -90,7 +122,13 @@ async fn load_home_content(state: &AppState) -> Result<HomeContent, AppError> {
         TimelineSortKey::default().default_direction(),
     );
 
-    let (recent_brews_page, open_bags_page, recent_events_page) = tokio::try_join!(
+    let (
+        recent_brews_page,
+        open_bags_page,
+        recent_events_page,
+        settings,
+        (favourite_roasters, favourite_roasts, favourite_cafes),
+    ) = tokio::try_join!(
         async {
             state
                 .brew_repo
@@ -108,10 +146,12 @@ async fn load_home_content(state: &AppState) -> Result<HomeContent, AppError> {
         async {
             state
                 .timeline_repo
-                .list(&recent_events_req)
+                .list(TimelineFilter::all(), &recent_events_req)
                 .await
                 .map_err(AppError::from)
         },
+        async { state.settings_repo.get().await.map_err(AppError::from) },
+        load_favourites(state),
     )?;
 
     let recent_brews: Vec<BrewView> = recent_brews_page
@@ -123,22 +163,50 @@ async fn load_home_content(state: &AppState) -> Result<HomeContent, AppError> {
     let open_bags = open_bags_page
         .items
         .into_iter()
-        .map(BagView::from)
+        .map(|bag| BagView::new(bag, settings.low_stock_threshold))
         .collect();
 
+    let authors = Authors::load(state).await;
     let recent_events = recent_events_page
         .items
         .into_iter()
-        .map(TimelineEventView::from)
+        .map(|event| {
+            let author = authors.name(event.created_by);
+            TimelineEventView::from(event).with_author(author)
+        })
         .collect();
 
     Ok(HomeContent {
         recent_brews,
         open_bags,
         recent_events,
+        favourite_roasters,
+        favourite_roasts,
+        favourite_cafes,
     })
 }
 
+/// Starred roasters, roasts and cafes, each by name.
+async fn load_favourites(
+    state: &AppState,
+) -> Result<(Vec<RoasterView>, Vec<RoastView>, Vec<CafeView>), AppError> {
+    let (roasters, roasts, cafes) = tokio::try_join!(
+        state.roaster_repo.list_favourites(),
+        state.roast_repo.list_favourites(),
+        state.cafe_repo.list_favourites(),
+    )
+    .map_err(AppError::from)?;
+
+    Ok((
+        roasters.into_iter().map(RoasterView::from).collect(),
+        roasts
+            .into_iter()
+            .map(|r| RoastView::from_domain(r.roast, &r.roaster_name, &r.roaster_slug))
+            .collect(),
+        cafes.into_iter().map(CafeView::from).collect(),
+    ))
+}
+
 fn build_stat_cards(cs: CachedStats) -> Vec<StatCard> {
     let mut cards = vec![
         StatCard {
@@ -0,0 +1,72 @@
+use axum::extract::{Path, State};
+use axum::http::header::{CACHE_CONTROL, CONTENT_TYPE, USER_AGENT};
+use axum::http::{HeaderValue, StatusCode};
+use axum::response::{IntoResponse, Response};
+use tracing::warn;
+
+use crate::application::errors::map_app_error;
+use crate::application::state::AppState;
+use crate::domain::map_tiles::MapTile;
+
+/// Tiles rarely change; a day keeps panning snappy without pinning stale
+/// imagery for long.
+const TILE_CACHE_CONTROL: &str = "public, max-age=86400";
+
+/// GET /map-tiles/{z}/{x}/{y} — a street-map tile from the tile server set in
+/// the settings. Proxying keeps the CSP at `img-src 'self'` and works with
+/// tile servers only this host can reach.
+#[tracing::instrument(skip(state))]
+pub(crate) async fn map_tile(
+    State(state): State<AppState>,
+    Path((z, x, y)): Path<(u8, u32, u32)>,
+) -> Result<Response, StatusCode> {
+    let tile = MapTile::new(z, x, y).ok_or(StatusCode::NOT_FOUND)?;
+    let settings = state
+        .settings_repo
+        .get()
+        .await
+        .map_err(|e| map_app_error(e.into()))?;
+    let Some(template) = settings.map_tile_url else {
+        return Err(StatusCode::NOT_FOUND);
+    };
+
+    let response = state
+        .http_client
+        .get(tile.url(&template))
+        .header(USER_AGENT, concat!("brewlog/", env!("CARGO_PKG_VERSION")))
+        .send()
+        .await
+        .map_err(|err| {
+            warn!(error = %err, "failed to fetch map tile");
+            StatusCode::BAD_GATEWAY
+        })?;
+    if !response.status().is_success() {
+        warn!(status = %response.status(), "tile server refused map tile");
+        return Err(StatusCode::BAD_GATEWAY);
+    }
+
+    // Only images are passed through: anything else would be served from
+    // this origin.
+    let content_type = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .filter(|value| value.as_bytes().starts_with(b"image/"))
+        .cloned()
+        .ok_or_else(|| {
+            warn!("tile server returned a non-image map tile");
+            StatusCode::BAD_GATEWAY
+        })?;
+    let body = response.bytes().await.map_err(|err| {
+        warn!(error = %err, "failed to read map tile");
+        StatusCode::BAD_GATEWAY
+    })?;
+
+    Ok((
+        [
+            (CONTENT_TYPE, content_type),
+            (CACHE_CONTROL, HeaderValue::from_static(TILE_CACHE_CONTROL)),
+        ],
+        body,
+    )
+        .into_response())
+}
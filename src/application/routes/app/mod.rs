@@ -5,17 +5,26 @@ mod bags;
 mod brews;
 mod cafes;
 mod checkin;
+mod compare;
 mod cups;
 mod data;
+mod events;
 mod gear;
 mod home;
+mod map_tiles;
+mod notifications;
+mod profile;
 mod roasters;
+mod roasting;
 mod roasts;
 mod stats;
 mod timeline;
+mod trips;
 mod webauthn;
 
-use axum::response::{IntoResponse, Redirect};
+use std::borrow::Cow;
+
+use axum::response::{IntoResponse, Redirect, Response};
 use axum::routing::{get, post};
 
 use crate::application::state::AppState;
@@ -23,21 +32,41 @@ use crate::application::state::AppState;
 pub(super) fn router() -> axum::Router<AppState> {
     axum::Router::new()
         .route("/", get(home::home_page))
+        .route("/quick-actions", get(home::quick_actions_fragment))
         .route("/login", get(auth::login_page))
         .route("/logout", post(auth::logout))
         .route("/admin", get(admin::admin_page))
+        .route("/profile", get(profile::profile_page))
+        .route(
+            "/notifications/menu",
+            get(notifications::notifications_menu),
+        )
         .route("/register/{token}", get(webauthn::register_page))
         .route("/auth/cli-callback", get(webauthn::cli_callback_page))
         .route("/data", get(data::data_page))
         .route("/add", get(add::add_page))
+        .route("/add/water-temp", get(add::water_temp_fragment))
         .route("/scan", get(scan_redirect))
         .route("/check-in", get(checkin::checkin_page))
+        .route("/compare", get(compare::compare_page))
         .route("/timeline", get(timeline::timeline_page))
+        .route("/timeline/archive", get(timeline::timeline_archive_page))
+        .route("/timeline/{year}", get(timeline::timeline_year_page))
+        .route(
+            "/timeline/{year}/{month}",
+            get(timeline::timeline_month_page),
+        )
         .route("/stats", get(stats::stats_page))
+        .route("/roasting", get(roasting::roasting_page))
+        .route("/events", get(events::events_page))
+        .route("/trips", get(trips::trips_page))
+        .route("/trips/{id}", get(trips::trip_detail_page))
+        .route("/map-tiles/{z}/{x}/{y}", get(map_tiles::map_tile))
         .route("/bags/{id}", get(bags::bag_detail_page))
         .route("/bags/{id}/edit", get(bags::bag_edit_page))
         .route("/brews/{id}", get(brews::brew_detail_page))
         .route("/brews/{id}/edit", get(brews::brew_edit_page))
+        .route("/share/{token}", get(brews::shared_brew_page))
         .route("/cafes/{slug}", get(cafes::cafe_detail_page))
         .route("/cafes/{id}/edit", get(cafes::cafe_edit_page))
         .route("/cups/{id}", get(cups::cup_detail_page))
@@ -51,81 +80,171 @@ pub(super) fn router() -> axum::Router<AppState> {
             get(roasts::roast_detail_page),
         )
         .route("/roasts/{id}/edit", get(roasts::roast_edit_page))
-        .route("/static/css/styles.css", get(styles))
-        .route("/static/js/webauthn.js", get(webauthn_js))
-        .route(
-            "/static/js/components/photo-capture.js",
-            get(photo_capture_js),
-        )
-        .route(
-            "/static/js/components/searchable-select.js",
-            get(searchable_select_js),
-        )
-        .route("/static/js/components/chip-scroll.js", get(chip_scroll_js))
-        .route("/static/js/location.js", get(location_js))
-        .route("/static/js/image-utils.js", get(image_utils_js))
-        .route("/static/js/components/world-map.js", get(world_map_js))
-        .route("/static/js/components/donut-chart.js", get(donut_chart_js))
-        .route(
-            "/static/js/components/image-upload.js",
-            get(image_upload_js),
-        )
-        .route("/static/favicon-light.svg", get(favicon_light))
-        .route("/static/favicon-dark.svg", get(favicon_dark))
-        .route("/static/og-image.png", get(og_image))
-        .route("/static/app-icon-192.png", get(app_icon_192))
-        .route("/static/app-icon-512.png", get(app_icon_512))
-        .route("/static/site.webmanifest", get(site_webmanifest))
         .route("/health", get(health))
+        .merge(static_routes())
 }
 
 async fn scan_redirect() -> Redirect {
     Redirect::permanent("/")
 }
 
-/// Generate a static-asset handler that returns embedded file content with a
-/// one-week cache header.
-macro_rules! static_asset {
-    ($name:ident, $content_type:expr, str $path:expr) => {
-        async fn $name() -> impl IntoResponse {
-            (
-                [
-                    ("content-type", $content_type),
-                    ("cache-control", "public, max-age=604800"),
-                ],
-                include_str!($path),
-            )
+/// Cache header for plain `/static/...` URLs, still linked from the web
+/// manifest and Open Graph tags.
+const STATIC_CACHE_CONTROL: &str = "public, max-age=604800";
+
+/// Cache header for content-hashed URLs, which never change.
+const HASHED_CACHE_CONTROL: &str = "public, max-age=31536000, immutable";
+
+/// A file embedded from `static/`.
+struct StaticAsset {
+    file: &'static str,
+    content_type: &'static str,
+    body: &'static [u8],
+}
+
+impl StaticAsset {
+    async fn response(&'static self, cache_control: &'static str) -> Response {
+        if let Some(themed) = crate::theme().and_then(|theme| theme.get(self.file)) {
+            return theme_response(&themed.body, self.content_type, cache_control);
+        }
+        if crate::dev_mode() {
+            return self.dev_response().await;
         }
+        (
+            [
+                ("content-type", self.content_type),
+                ("cache-control", cache_control),
+            ],
+            self.body,
+        )
+            .into_response()
+    }
+
+    /// Read the file from the checkout so CSS and JS edits show up on reload
+    /// without a rebuild. Falls back to the embedded copy if it can't be read.
+    async fn dev_response(&'static self) -> Response {
+        let path = format!("{}/static/{}", env!("CARGO_MANIFEST_DIR"), self.file);
+        let body = tokio::fs::read(&path)
+            .await
+            .map_or(Cow::Borrowed(self.body), Cow::Owned);
+        (
+            [
+                ("content-type", self.content_type),
+                ("cache-control", "no-store"),
+            ],
+            body,
+        )
+            .into_response()
+    }
+}
+
+/// Serve a file loaded from `--theme-dir`. Theme files are read at startup,
+/// so dev mode only turns off caching.
+fn theme_response(
+    body: &'static [u8],
+    content_type: &'static str,
+    cache_control: &'static str,
+) -> Response {
+    let cache_control = if crate::dev_mode() {
+        "no-store"
+    } else {
+        cache_control
     };
-    ($name:ident, $content_type:expr, bytes $path:expr) => {
-        async fn $name() -> impl IntoResponse {
-            (
-                [
-                    ("content-type", $content_type),
-                    ("cache-control", "public, max-age=604800"),
-                ],
-                include_bytes!($path).as_slice(),
-            )
+    (
+        [
+            ("content-type", content_type),
+            ("cache-control", cache_control),
+        ],
+        body,
+    )
+        .into_response()
+}
+
+/// Embed a file from `static/`. Its content hash comes from `build.rs` and is
+/// looked up with `crate::asset_url` when routes are registered.
+macro_rules! static_asset {
+    ($file:literal, $content_type:expr) => {
+        StaticAsset {
+            file: $file,
+            content_type: $content_type,
+            body: include_bytes!(concat!("../../../../static/", $file)),
         }
     };
 }
 
-static_asset!(styles, "text/css; charset=utf-8", str "../../../../static/css/styles.css");
-static_asset!(webauthn_js, "application/javascript; charset=utf-8", str "../../../../static/js/webauthn.js");
-static_asset!(location_js, "application/javascript; charset=utf-8", str "../../../../static/js/location.js");
-static_asset!(image_utils_js, "application/javascript; charset=utf-8", str "../../../../static/js/image-utils.js");
-static_asset!(photo_capture_js, "application/javascript; charset=utf-8", str "../../../../static/js/components/photo-capture.js");
-static_asset!(searchable_select_js, "application/javascript; charset=utf-8", str "../../../../static/js/components/searchable-select.js");
-static_asset!(chip_scroll_js, "application/javascript; charset=utf-8", str "../../../../static/js/components/chip-scroll.js");
-static_asset!(world_map_js, "application/javascript; charset=utf-8", str "../../../../static/js/components/world-map.js");
-static_asset!(donut_chart_js, "application/javascript; charset=utf-8", str "../../../../static/js/components/donut-chart.js");
-static_asset!(image_upload_js, "application/javascript; charset=utf-8", str "../../../../static/js/components/image-upload.js");
-static_asset!(favicon_light, "image/svg+xml", str "../../../../static/favicon-light.svg");
-static_asset!(favicon_dark, "image/svg+xml", str "../../../../static/favicon-dark.svg");
-static_asset!(og_image, "image/png", bytes "../../../../static/og-image.png");
-static_asset!(app_icon_192, "image/png", bytes "../../../../static/app-icon-192.png");
-static_asset!(app_icon_512, "image/png", bytes "../../../../static/app-icon-512.png");
-static_asset!(site_webmanifest, "application/manifest+json; charset=utf-8", str "../../../../static/site.webmanifest");
+const JS: &str = "application/javascript; charset=utf-8";
+
+static STATIC_ASSETS: &[StaticAsset] = &[
+    static_asset!("css/styles.css", "text/css; charset=utf-8"),
+    static_asset!("js/webauthn.js", JS),
+    static_asset!("js/location.js", JS),
+    static_asset!("js/image-utils.js", JS),
+    static_asset!("js/components/photo-capture.js", JS),
+    static_asset!("js/components/searchable-select.js", JS),
+    static_asset!("js/components/chip-scroll.js", JS),
+    static_asset!("js/components/autocomplete-input.js", JS),
+    static_asset!("js/components/nav-search.js", JS),
+    static_asset!("js/components/grind-converter.js", JS),
+    static_asset!("js/components/world-map.js", JS),
+    static_asset!("js/components/tile-map.js", JS),
+    static_asset!("js/components/donut-chart.js", JS),
+    static_asset!("js/components/line-chart.js", JS),
+    static_asset!("js/components/image-upload.js", JS),
+    static_asset!("favicon-light.svg", "image/svg+xml"),
+    static_asset!("favicon-dark.svg", "image/svg+xml"),
+    static_asset!("og-image.png", "image/png"),
+    static_asset!("app-icon-192.png", "image/png"),
+    static_asset!("app-icon-512.png", "image/png"),
+    static_asset!(
+        "site.webmanifest",
+        "application/manifest+json; charset=utf-8"
+    ),
+];
+
+/// Serve each asset at its plain URL and at its content-hashed URL, along
+/// with any extra files the theme adds.
+fn static_routes() -> axum::Router<AppState> {
+    let router = STATIC_ASSETS
+        .iter()
+        .fold(axum::Router::new(), |router, asset| {
+            let plain = format!("/static/{}", asset.file);
+            let hashed = crate::asset_url(asset.file);
+            let router = router.route(&plain, get(move || asset.response(STATIC_CACHE_CONTROL)));
+            if hashed == plain {
+                router
+            } else {
+                router.route(&hashed, get(move || asset.response(HASHED_CACHE_CONTROL)))
+            }
+        });
+
+    let Some(theme) = crate::theme() else {
+        return router;
+    };
+    theme
+        .paths()
+        .filter(|path| !STATIC_ASSETS.iter().any(|asset| asset.file == *path))
+        .fold(router, |router, path| {
+            let Some(themed) = theme.get(path) else {
+                return router;
+            };
+            let body = themed.body.as_slice();
+            let content_type = crate::infrastructure::theme::content_type(path);
+            let plain = format!("/static/{path}");
+            let hashed = crate::asset_url(path);
+            let router = router.route(
+                &plain,
+                get(move || async move { theme_response(body, content_type, STATIC_CACHE_CONTROL) }),
+            );
+            if hashed == plain {
+                router
+            } else {
+                router.route(
+                    &hashed,
+                    get(move || async move { theme_response(body, content_type, HASHED_CACHE_CONTROL) }),
+                )
+            }
+        })
+}
 
 async fn health() -> impl IntoResponse {
     ([("content-type", "application/json")], r#"{"status":"ok"}"#)
@@ -0,0 +1,45 @@
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::Response;
+use tower_cookies::Cookies;
+
+use crate::application::auth::authenticate_via_session;
+use crate::application::errors::{AppError, map_app_error};
+use crate::application::routes::support::render_fragment;
+use crate::application::state::AppState;
+use crate::presentation::web::templates::NotificationsMenuFragment;
+
+/// How many notifications the bell menu shows; the API returns more.
+const MENU_LIMIT: u32 = 10;
+
+/// GET /notifications/menu — the bell menu, loaded into the nav bar by Datastar
+#[tracing::instrument(skip(state, cookies))]
+pub(crate) async fn notifications_menu(
+    State(state): State<AppState>,
+    cookies: Cookies,
+) -> Result<Response, StatusCode> {
+    let Some(user) = authenticate_via_session(&state, &cookies).await else {
+        return Err(StatusCode::UNAUTHORIZED);
+    };
+
+    let unread = state
+        .notification_repo
+        .count_unread(user.id)
+        .await
+        .map_err(|err| map_app_error(AppError::from(err)))?;
+    let notifications = state
+        .notification_repo
+        .list_by_user(user.id, MENU_LIMIT)
+        .await
+        .map_err(|err| map_app_error(AppError::from(err)))?
+        .into_iter()
+        .map(Into::into)
+        .collect();
+
+    let template = NotificationsMenuFragment {
+        unread,
+        notifications,
+    };
+
+    render_fragment(template, "#notifications-menu").map_err(map_app_error)
+}
@@ -0,0 +1,125 @@
+use askama::Template;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Redirect, Response};
+use serde::Serialize;
+use tower_cookies::Cookies;
+use tracing::error;
+
+use super::admin::format_date;
+use crate::application::auth::authenticate_via_session;
+use crate::application::routes::api::images::resolve_image_url;
+use crate::application::routes::api::system::admin::current_session_hash;
+use crate::application::routes::render_html;
+use crate::application::state::AppState;
+use crate::domain::entity_type::EntityType;
+use crate::domain::formatting::format_relative_time;
+
+// --- View types ---
+
+#[derive(Serialize)]
+pub struct PasskeyView {
+    pub id: i64,
+    pub name: String,
+    pub created_at: String,
+    pub last_used_at: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct SessionView {
+    pub id: i64,
+    pub device: String,
+    pub last_seen_ip: Option<String>,
+    pub last_active: String,
+    pub created_at: String,
+    pub is_current: bool,
+}
+
+// --- Templates ---
+
+#[derive(Template)]
+#[template(path = "pages/profile.html")]
+struct ProfileTemplate {
+    nav_active: &'static str,
+    is_authenticated: bool,
+    version_info: &'static crate::VersionInfo,
+    user_id: i64,
+    username: String,
+    name: String,
+    display_name: String,
+    created_date: String,
+    image_url: Option<String>,
+    passkeys: Vec<PasskeyView>,
+    sessions: Vec<SessionView>,
+}
+
+// --- Page handler ---
+
+#[tracing::instrument(skip(state, cookies))]
+pub(crate) async fn profile_page(
+    State(state): State<AppState>,
+    cookies: Cookies,
+) -> Result<Response, StatusCode> {
+    // Web page: redirect to login instead of returning 401
+    let Some(user) = authenticate_via_session(&state, &cookies).await else {
+        return Ok(Redirect::to("/login").into_response());
+    };
+
+    let passkeys = state
+        .passkey_repo
+        .list_by_user(user.id)
+        .await
+        .map_err(|err| {
+            error!(error = %err, "failed to list passkeys for profile page");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .into_iter()
+        .map(|p| PasskeyView {
+            id: i64::from(p.id),
+            name: p.name,
+            created_at: format_date(p.created_at),
+            last_used_at: p.last_used_at.map(format_date),
+        })
+        .collect();
+
+    let now = state.clock.now();
+    let current = current_session_hash(&cookies);
+    let sessions = state
+        .session_repo
+        .list_by_user(user.id)
+        .await
+        .map_err(|err| {
+            error!(error = %err, "failed to list sessions for profile page");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .into_iter()
+        .map(|s| SessionView {
+            id: i64::from(s.id),
+            device: s
+                .device_label
+                .unwrap_or_else(|| "Unknown device".to_string()),
+            last_active: format_relative_time(s.last_seen_at.unwrap_or(s.created_at), now),
+            created_at: format_date(s.created_at),
+            is_current: current.as_deref() == Some(s.session_token_hash.as_str()),
+            last_seen_ip: s.last_seen_ip,
+        })
+        .collect();
+
+    let image_url = resolve_image_url(&state, EntityType::User, i64::from(user.id)).await;
+
+    let template = ProfileTemplate {
+        nav_active: "profile",
+        is_authenticated: true,
+        version_info: &crate::VERSION_INFO,
+        user_id: i64::from(user.id),
+        name: user.name().to_string(),
+        display_name: user.display_name.unwrap_or_default(),
+        username: user.username,
+        created_date: format_date(user.created_at),
+        image_url,
+        passkeys,
+        sessions,
+    };
+
+    render_html(template).map(IntoResponse::into_response)
+}
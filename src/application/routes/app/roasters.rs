@@ -5,13 +5,40 @@ use tower_cookies::Cookies;
 
 use crate::application::auth::AuthenticatedUser;
 use crate::application::errors::map_app_error;
+use crate::application::routes::api::aliases::find_roaster;
 use crate::application::routes::api::images::resolve_image_url;
 use crate::application::routes::render_html;
+use crate::application::routes::support::{Authors, load_aliases};
 use crate::application::state::AppState;
+use crate::domain::aliases::AliasTarget;
 use crate::domain::entity_type::EntityType;
 use crate::domain::ids::RoasterId;
+use crate::domain::listing::SortDirection;
+use crate::domain::roasters::{Roaster, RoasterSortKey};
 use crate::presentation::web::templates::{RoasterDetailTemplate, RoasterEditTemplate};
-use crate::presentation::web::views::RoasterDetailView;
+use crate::presentation::web::views::{MergeTargetView, RoasterDetailView};
+
+/// Every other roaster, by name, for the merge picker.
+async fn load_merge_targets(state: &AppState, roaster: &Roaster) -> Vec<MergeTargetView> {
+    match state
+        .roaster_repo
+        .list_all_sorted(RoasterSortKey::Name, SortDirection::Asc)
+        .await
+    {
+        Ok(roasters) => roasters
+            .into_iter()
+            .filter(|other| other.id != roaster.id)
+            .map(|other| MergeTargetView {
+                id: other.id.to_string(),
+                label: other.name,
+            })
+            .collect(),
+        Err(err) => {
+            tracing::warn!(error = %err, "failed to load merge targets");
+            Vec::new()
+        }
+    }
+}
 
 #[tracing::instrument(skip(state, cookies))]
 pub(crate) async fn roaster_detail_page(
@@ -21,15 +48,32 @@ pub(crate) async fn roaster_detail_page(
 ) -> Result<Response, StatusCode> {
     let is_authenticated = crate::application::routes::is_authenticated(&state, &cookies).await;
 
-    let roaster = state
-        .roaster_repo
-        .get_by_slug(&slug)
+    let roaster = find_roaster(&state, &slug)
         .await
         .map_err(|e| map_app_error(e.into()))?;
 
     let image_url = resolve_image_url(&state, EntityType::Roaster, i64::from(roaster.id)).await;
     let edit_url = format!("/roasters/{}/edit", roaster.id);
 
+    let delete_warning = if is_authenticated {
+        match state.roaster_repo.delete_preview(roaster.id).await {
+            Ok(preview) => preview.warning(),
+            Err(err) => {
+                tracing::warn!(error = %err, "failed to load delete preview");
+                String::new()
+            }
+        }
+    } else {
+        String::new()
+    };
+
+    let author = Authors::load(&state).await.name(roaster.created_by);
+    let aliases = load_aliases(&state, AliasTarget::Roaster(roaster.id)).await;
+    let merge_targets = if is_authenticated {
+        load_merge_targets(&state, &roaster).await
+    } else {
+        Vec::new()
+    };
     let view = RoasterDetailView::from(roaster);
 
     let template = RoasterDetailTemplate {
@@ -38,8 +82,12 @@ pub(crate) async fn roaster_detail_page(
         version_info: &crate::VERSION_INFO,
         base_url: crate::base_url(),
         roaster: view,
+        author,
         image_url,
         edit_url,
+        delete_warning,
+        aliases,
+        merge_targets,
     };
 
     render_html(template).map(IntoResponse::into_response)
@@ -0,0 +1,58 @@
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use tower_cookies::Cookies;
+
+use crate::application::errors::map_app_error;
+use crate::application::routes::render_html;
+use crate::application::routes::support::load_open_bag_options;
+use crate::application::state::AppState;
+use crate::presentation::web::templates::RoastingTemplate;
+use crate::presentation::web::views::{GreenCoffeeView, RoastBatchView};
+
+#[tracing::instrument(skip(state, cookies))]
+pub(crate) async fn roasting_page(
+    State(state): State<AppState>,
+    cookies: Cookies,
+) -> Result<Response, StatusCode> {
+    let is_authenticated = crate::application::routes::is_authenticated(&state, &cookies).await;
+
+    let (greens, batches) = tokio::try_join!(
+        async {
+            state
+                .green_coffee_repo
+                .list()
+                .await
+                .map_err(|e| map_app_error(e.into()))
+        },
+        async {
+            state
+                .roast_batch_repo
+                .list(None)
+                .await
+                .map_err(|e| map_app_error(e.into()))
+        },
+    )?;
+
+    // Only offer bags to link a batch to when there's a form to offer them in.
+    let bag_options = if is_authenticated {
+        load_open_bag_options(&state).await.map_err(map_app_error)?
+    } else {
+        Vec::new()
+    };
+
+    let template = RoastingTemplate {
+        nav_active: "",
+        is_authenticated,
+        version_info: &crate::VERSION_INFO,
+        base_url: crate::base_url(),
+        batches: batches
+            .iter()
+            .map(|batch| RoastBatchView::new(batch, &greens))
+            .collect(),
+        green_coffees: greens.iter().map(GreenCoffeeView::from).collect(),
+        bag_options,
+    };
+
+    render_html(template).map(IntoResponse::into_response)
+}
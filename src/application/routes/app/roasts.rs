@@ -3,16 +3,74 @@ use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
 use tower_cookies::Cookies;
 
-use crate::application::auth::AuthenticatedUser;
+use crate::application::auth::{AuthenticatedUser, authenticate_via_session};
 use crate::application::errors::map_app_error;
+use crate::application::routes::api::aliases::{find_roast, find_roaster};
 use crate::application::routes::api::images::resolve_image_url;
 use crate::application::routes::render_html;
-use crate::application::routes::support::load_roaster_options;
+use crate::application::routes::support::{
+    Authors, load_aliases, load_comments, load_roaster_options,
+};
 use crate::application::state::AppState;
+use crate::domain::aliases::AliasTarget;
+use crate::domain::brews::RoastBrewStats;
+use crate::domain::comments::CommentTarget;
 use crate::domain::entity_type::EntityType;
 use crate::domain::ids::RoastId;
+use crate::domain::roasts::{Roast, sort_lots};
 use crate::presentation::web::templates::{RoastDetailTemplate, RoastEditTemplate};
-use crate::presentation::web::views::RoastDetailView;
+use crate::presentation::web::views::{
+    MergeTargetView, RoastDetailView, RoastLotView, RoastOptionView,
+};
+
+/// Every other roast, for the merge picker.
+async fn load_merge_targets(state: &AppState, roast: &Roast) -> Vec<MergeTargetView> {
+    match state.roast_repo.list_all().await {
+        Ok(roasts) => roasts
+            .into_iter()
+            .filter(|other| other.roast.id != roast.id)
+            .map(|other| {
+                let option = RoastOptionView::from(other);
+                MergeTargetView {
+                    id: option.id,
+                    label: option.label,
+                }
+            })
+            .collect(),
+        Err(err) => {
+            tracing::warn!(error = %err, "failed to load merge targets");
+            Vec::new()
+        }
+    }
+}
+
+/// Every lot of this roast's coffee, itself included, newest harvest first.
+async fn load_lots(state: &AppState, roast: &Roast) -> Vec<RoastLotView> {
+    let mut same_coffee: Vec<_> = match state.roast_repo.list_by_roaster(roast.roaster_id).await {
+        Ok(candidates) => candidates
+            .into_iter()
+            .filter(|lot| lot.roast.id == roast.id || roast.is_lot_of(&lot.roast))
+            .collect(),
+        Err(err) => {
+            tracing::warn!(error = %err, "failed to load roast lots");
+            return Vec::new();
+        }
+    };
+    sort_lots(&mut same_coffee);
+
+    let mut views = Vec::with_capacity(same_coffee.len());
+    for lot in &same_coffee {
+        let brew_count = match state.brew_repo.roast_stats(lot.roast.id).await {
+            Ok(lot_stats) => lot_stats.brew_count,
+            Err(err) => {
+                tracing::warn!(error = %err, "failed to load lot brew stats");
+                0
+            }
+        };
+        views.push(RoastLotView::new(lot, brew_count, roast));
+    }
+    views
+}
 
 #[tracing::instrument(skip(state, cookies))]
 pub(crate) async fn roast_detail_page(
@@ -20,24 +78,62 @@ pub(crate) async fn roast_detail_page(
     cookies: Cookies,
     Path((roaster_slug, roast_slug)): Path<(String, String)>,
 ) -> Result<Response, StatusCode> {
-    let is_authenticated = crate::application::routes::is_authenticated(&state, &cookies).await;
+    let user = authenticate_via_session(&state, &cookies).await;
+    let is_authenticated = user.is_some();
 
-    let roaster = state
-        .roaster_repo
-        .get_by_slug(&roaster_slug)
+    let roaster = find_roaster(&state, &roaster_slug)
         .await
         .map_err(|e| map_app_error(e.into()))?;
 
-    let roast = state
-        .roast_repo
-        .get_by_slug(roaster.id, &roast_slug)
+    let roast = find_roast(&state, roaster.id, &roast_slug)
         .await
         .map_err(|e| map_app_error(e.into()))?;
 
     let image_url = resolve_image_url(&state, EntityType::Roast, i64::from(roast.id)).await;
     let edit_url = format!("/roasts/{}/edit", roast.id);
 
-    let view = RoastDetailView::from_parts(roast, &roaster);
+    let delete_warning = if is_authenticated {
+        match state.roast_repo.delete_preview(roast.id).await {
+            Ok(preview) => preview.warning(),
+            Err(err) => {
+                tracing::warn!(error = %err, "failed to load delete preview");
+                String::new()
+            }
+        }
+    } else {
+        String::new()
+    };
+
+    let brew_stats = match state.brew_repo.roast_stats(roast.id).await {
+        Ok(loaded) => loaded,
+        Err(err) => {
+            tracing::warn!(error = %err, "failed to load roast brew stats");
+            RoastBrewStats::default()
+        }
+    };
+
+    let author = Authors::load(&state).await.name(roast.created_by);
+    let comments = load_comments(
+        &state,
+        CommentTarget::Roast(roast.id),
+        user.map(|user| user.id),
+    )
+    .await;
+    let lots = load_lots(&state, &roast).await;
+    let aliases = load_aliases(&state, AliasTarget::Roast(roast.id)).await;
+    let merge_targets = if is_authenticated {
+        load_merge_targets(&state, &roast).await
+    } else {
+        Vec::new()
+    };
+    let purchases = match state.bag_repo.list_purchases(roast.id).await {
+        Ok(bags) => bags,
+        Err(err) => {
+            tracing::warn!(error = %err, "failed to load roast purchases");
+            Vec::new()
+        }
+    };
+    let view = RoastDetailView::from_parts(roast, &roaster, brew_stats, &purchases);
 
     let template = RoastDetailTemplate {
         nav_active: "",
@@ -46,8 +142,14 @@ pub(crate) async fn roast_detail_page(
         base_url: crate::base_url(),
         roast: view,
         roaster_slug,
+        author,
         image_url,
         edit_url,
+        delete_warning,
+        comments,
+        lots,
+        aliases,
+        merge_targets,
     };
 
     render_html(template).map(IntoResponse::into_response)
@@ -81,6 +183,11 @@ pub(crate) async fn roast_edit_page(
     let producer = roast.producer.unwrap_or_default();
     let process = roast.process.unwrap_or_default();
     let tasting_notes = roast.tasting_notes.join(", ");
+    let components = crate::domain::blends::blend_label(&roast.components);
+    let harvest_year = roast
+        .harvest_year
+        .map(|year| year.to_string())
+        .unwrap_or_default();
 
     use crate::presentation::web::views::build_signals_json;
     use serde_json::Value;
@@ -93,6 +200,8 @@ pub(crate) async fn roast_edit_page(
         ("_producer", Value::String(producer.clone())),
         ("_process", Value::String(process.clone())),
         ("_tasting-notes", Value::String(tasting_notes.clone())),
+        ("_components", Value::String(components.clone())),
+        ("_harvest-year", Value::String(harvest_year.clone())),
     ]);
 
     let template = RoastEditTemplate {
@@ -108,6 +217,8 @@ pub(crate) async fn roast_edit_page(
         producer,
         process,
         tasting_notes,
+        components,
+        harvest_year,
         roaster_options,
         image_url,
         signals_json,
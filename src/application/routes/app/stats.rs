@@ -2,19 +2,27 @@ use axum::extract::{Query, State};
 use axum::http::header::HeaderValue;
 use axum::http::{HeaderMap, StatusCode};
 use axum::response::{Html, IntoResponse, Response};
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use serde::Deserialize;
 
 use crate::application::errors::map_app_error;
+use crate::application::routes::api::stats::LeaderboardQuery;
 use crate::application::routes::render_html;
 use crate::application::routes::support::is_datastar_request;
 use crate::application::services::stats::compute_all_stats;
 use crate::application::state::AppState;
 use crate::domain::country_stats::GeoStats;
-use crate::domain::stats::CachedStats;
+use crate::domain::listing::ListRequest;
+use crate::domain::stats::{
+    CachedStats, RoasterLeaderboardEntry, RoasterLeaderboardSortKey, sort_leaderboard,
+};
 use crate::presentation::web::templates::{
-    StatsMapFragment, StatsPageTemplate, Tab, render_template,
+    RoasterLeaderboardFragment, StatsMapFragment, StatsPageTemplate, Tab, render_template,
 };
+use crate::presentation::web::views::{ListNavigator, RoasterLeaderboardView, SpendView};
+
+const LEADERBOARD_PAGE_PATH: &str = "/stats";
+const LEADERBOARD_FRAGMENT_PATH: &str = "/stats#roaster-leaderboard";
 
 const TABS: &[Tab] = &[
     Tab {
@@ -39,6 +47,10 @@ const TABS: &[Tab] = &[
 pub(crate) struct StatsQuery {
     #[serde(rename = "type", default = "default_type")]
     entity_type: String,
+    /// A Datastar request with a leaderboard sort is a re-sort rather than a
+    /// tab switch.
+    #[serde(flatten)]
+    leaderboard: LeaderboardQuery,
 }
 
 fn default_type() -> String {
@@ -52,43 +64,32 @@ pub(crate) async fn stats_page(
     headers: HeaderMap,
     Query(stats_query): Query<StatsQuery>,
 ) -> Result<Response, StatusCode> {
+    let leaderboard_request = stats_query.leaderboard.request();
     let entity_type = stats_query.entity_type;
     let is_authenticated = crate::application::routes::is_authenticated(&state, &cookies).await;
 
+    // Datastar leaderboard re-sort: only need the leaderboard table
+    if is_datastar_request(&headers) && stats_query.leaderboard.sort.is_some() {
+        let content = render_leaderboard(
+            load_or_compute(&state).await?.roaster_leaderboard,
+            leaderboard_request,
+        )?;
+        return Ok(fragment_response(
+            content,
+            "#roaster-leaderboard",
+            "replace",
+        ));
+    }
+
     // Datastar tab switch: only need geo stats for the selected tab
     if is_datastar_request(&headers) {
-        let geo_stats = geo_for_type(&load_or_compute(&state).await?, &entity_type);
-
-        let content = render_template(StatsMapFragment {
-            geo_stats: &geo_stats,
-        })
-        .map_err(|err| {
-            tracing::error!(error = %err, "failed to render stats fragment");
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
-
-        let mut response = Html(content).into_response();
-        response.headers_mut().insert(
-            "datastar-selector",
-            HeaderValue::from_static("#stats-content"),
-        );
-        response
-            .headers_mut()
-            .insert("datastar-mode", HeaderValue::from_static("inner"));
-        return Ok(response);
+        let content = render_map(&load_or_compute(&state).await?, &entity_type)?;
+        return Ok(fragment_response(content, "#stats-content", "inner"));
     }
 
     // Full page load: use cached stats or compute on the fly
     let cached = load_or_compute(&state).await?;
-    let geo_stats = geo_for_type(&cached, &entity_type);
-
-    let content = render_template(StatsMapFragment {
-        geo_stats: &geo_stats,
-    })
-    .map_err(|err| {
-        tracing::error!(error = %err, "failed to render stats fragment");
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    let content = render_map(&cached, &entity_type)?;
 
     let tabs: Vec<Tab> = TABS
         .iter()
@@ -98,7 +99,7 @@ pub(crate) async fn stats_page(
         })
         .collect();
 
-    let cache_age = format_cache_age(&cached.computed_at);
+    let cache_age = format_cache_age(&cached.computed_at, state.clock.now());
     let consumption_30d_weight =
         crate::domain::formatting::format_weight(cached.consumption.last_30_days_grams);
     let consumption_all_time_weight =
@@ -117,6 +118,12 @@ pub(crate) async fn stats_page(
         .collect();
     let max_grinder_weight = cached.brewing_summary.max_grinder_weight;
 
+    let leaderboard = if cached.roaster_leaderboard.is_empty() {
+        String::new()
+    } else {
+        render_leaderboard(cached.roaster_leaderboard, leaderboard_request)?
+    };
+
     let has_data = cached.consumption.brews_all_time > 0
         || cached.roast_summary.unique_origins > 0
         || !cached.brewing_summary.brewer_counts.is_empty();
@@ -137,10 +144,14 @@ pub(crate) async fn stats_page(
         roast_summary: cached.roast_summary,
         consumption: cached.consumption,
         brewing_summary: cached.brewing_summary,
+        tasting: cached.tasting,
+        habits: cached.habits,
         grinder_weights,
         max_grinder_weight,
         consumption_30d_weight,
         consumption_all_time_weight,
+        spend: SpendView::from(cached.spend),
+        leaderboard,
         cache_age,
         has_data,
     };
@@ -154,11 +165,58 @@ async fn load_or_compute(state: &AppState) -> Result<CachedStats, StatusCode> {
         return Ok(cached);
     }
     tracing::debug!("stats cache miss, computing live");
-    compute_all_stats(&*state.stats_repo)
+    compute_all_stats(&*state.stats_repo, state.clock.now())
         .await
         .map_err(|e| map_app_error(e.into()))
 }
 
+fn render_map(cached: &CachedStats, entity_type: &str) -> Result<String, StatusCode> {
+    render_template(StatsMapFragment {
+        geo_stats: &geo_for_type(cached, entity_type),
+    })
+    .map_err(|err| {
+        tracing::error!(error = %err, "failed to render stats fragment");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })
+}
+
+/// Wrap a rendered fragment with the Datastar headers that place it.
+fn fragment_response(content: String, selector: &'static str, mode: &'static str) -> Response {
+    let mut response = Html(content).into_response();
+    response
+        .headers_mut()
+        .insert("datastar-selector", HeaderValue::from_static(selector));
+    response
+        .headers_mut()
+        .insert("datastar-mode", HeaderValue::from_static(mode));
+    response
+}
+
+/// Sort the cached leaderboard as requested and render its table.
+fn render_leaderboard(
+    mut entries: Vec<RoasterLeaderboardEntry>,
+    request: ListRequest<RoasterLeaderboardSortKey>,
+) -> Result<String, StatusCode> {
+    sort_leaderboard(&mut entries, request.sort_key(), request.sort_direction());
+    render_template(RoasterLeaderboardFragment {
+        entries: entries
+            .into_iter()
+            .enumerate()
+            .map(|(index, entry)| RoasterLeaderboardView::from_entry(index + 1, entry))
+            .collect(),
+        navigator: ListNavigator::new(
+            LEADERBOARD_PAGE_PATH,
+            LEADERBOARD_FRAGMENT_PATH,
+            request,
+            None,
+        ),
+    })
+    .map_err(|err| {
+        tracing::error!(error = %err, "failed to render roaster leaderboard");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })
+}
+
 /// Select the geo stats for a given entity type from the cached snapshot.
 fn geo_for_type(cached: &CachedStats, entity_type: &str) -> GeoStats {
     match entity_type {
@@ -170,9 +228,9 @@ fn geo_for_type(cached: &CachedStats, entity_type: &str) -> GeoStats {
 }
 
 /// Format the cache timestamp as a relative age string (e.g. "Just now", "2m ago").
-fn format_cache_age(computed_at: &str) -> String {
+fn format_cache_age(computed_at: &str, now: DateTime<Utc>) -> String {
     let Ok(ts) = chrono::DateTime::parse_from_rfc3339(computed_at) else {
         return String::new();
     };
-    crate::domain::formatting::format_relative_time(ts.with_timezone(&Utc), Utc::now())
+    crate::domain::formatting::format_relative_time(ts.with_timezone(&Utc), now)
 }
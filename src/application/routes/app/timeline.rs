@@ -1,44 +1,118 @@
-use axum::extract::{Query, State};
+use axum::extract::{Path, Query, State};
 use axum::http::HeaderMap;
 use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
+use serde::Deserialize;
 
 use crate::application::errors::{AppError, map_app_error};
 use crate::application::routes::render_html;
-use crate::application::routes::support::{ListQuery, is_datastar_request, normalize_request};
+use crate::application::routes::support::{
+    Authors, ListQuery, is_datastar_request, normalize_request,
+};
 use crate::application::state::AppState;
+use crate::domain::ids::UserId;
 use crate::domain::listing::ListRequest;
-use crate::domain::timeline::{TimelineEvent, TimelineSortKey};
-use crate::presentation::web::templates::{TimelineChunkTemplate, TimelineTemplate};
+use crate::domain::timeline::{
+    TimelineEvent, TimelineFilter, TimelineMonth, TimelineMonthCount, TimelineSortKey,
+};
+use crate::presentation::web::templates::{
+    TimelineArchiveTemplate, TimelineChunkTemplate, TimelineTemplate,
+};
 use crate::presentation::web::views::{
-    ListNavigator, Paginated, TimelineEventView, TimelineMonthView,
+    ListNavigator, Paginated, TimelineArchiveYearView, TimelineEventView, TimelineMonthNavView,
+    TimelineMonthView,
 };
 
 const TIMELINE_PAGE_PATH: &str = "/timeline";
-const TIMELINE_FRAGMENT_PATH: &str = "/timeline";
 const TIMELINE_DEFAULT_PAGE_SIZE: u32 = 20;
 
-#[tracing::instrument(skip(state, cookies, headers, query))]
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct TimelineFilterQuery {
+    #[serde(default)]
+    user: Option<i64>,
+}
+
+impl TimelineFilterQuery {
+    fn filter(&self) -> TimelineFilter {
+        self.user
+            .map(UserId::from)
+            .map_or_else(TimelineFilter::all, TimelineFilter::for_user)
+    }
+
+    /// Keeps the user filter on archive links.
+    fn link_query(&self) -> String {
+        self.user
+            .map(|id| format!("?user={id}"))
+            .unwrap_or_default()
+    }
+}
+
+#[tracing::instrument(skip(state, cookies, headers, query, filter_query))]
 pub(crate) async fn timeline_page(
     State(state): State<AppState>,
     cookies: tower_cookies::Cookies,
     headers: HeaderMap,
     Query(query): Query<ListQuery>,
+    Query(filter_query): Query<TimelineFilterQuery>,
+) -> Result<Response, StatusCode> {
+    render_timeline(state, &cookies, &headers, query, &filter_query, None).await
+}
+
+/// The timeline narrowed to one month, e.g. `/timeline/2025/03`.
+#[tracing::instrument(skip(state, cookies, headers, query, filter_query))]
+pub(crate) async fn timeline_month_page(
+    State(state): State<AppState>,
+    cookies: tower_cookies::Cookies,
+    headers: HeaderMap,
+    Path((year, month)): Path<(i32, u32)>,
+    Query(query): Query<ListQuery>,
+    Query(filter_query): Query<TimelineFilterQuery>,
+) -> Result<Response, StatusCode> {
+    let month = TimelineMonth::new(year, month).ok_or(StatusCode::NOT_FOUND)?;
+    render_timeline(state, &cookies, &headers, query, &filter_query, Some(month)).await
+}
+
+async fn render_timeline(
+    state: AppState,
+    cookies: &tower_cookies::Cookies,
+    headers: &HeaderMap,
+    query: ListQuery,
+    filter_query: &TimelineFilterQuery,
+    month: Option<TimelineMonth>,
 ) -> Result<Response, StatusCode> {
     let (request, _search) =
         query.into_request_and_search_with_default::<TimelineSortKey>(TIMELINE_DEFAULT_PAGE_SIZE);
-    let is_authenticated = crate::application::routes::is_authenticated(&state, &cookies).await;
+    let mut filter = filter_query.filter();
+    let mut path = TIMELINE_PAGE_PATH.to_string();
+    if let Some(month) = month {
+        filter = filter.in_month(month);
+        path = format!("{TIMELINE_PAGE_PATH}/{}/{:02}", month.year(), month.month());
+    }
+    let is_authenticated = crate::application::routes::is_authenticated(&state, cookies).await;
+    let authors = Authors::load(&state).await;
 
-    if is_datastar_request(&headers) {
-        return render_timeline_chunk(state, request, is_authenticated)
+    if is_datastar_request(headers) {
+        return render_timeline_chunk(state, &authors, filter, request, &path, is_authenticated)
             .await
             .map_err(map_app_error);
     }
 
-    let data = load_timeline_page(&state, request)
+    let data = load_timeline_page(&state, &authors, filter, request, &path)
         .await
         .map_err(map_app_error)?;
 
+    let archive = match month {
+        Some(month) => {
+            let counts = load_month_counts(&state, filter).await?;
+            Some(TimelineMonthNavView::new(
+                month,
+                &counts,
+                &filter_query.link_query(),
+            ))
+        }
+        None => None,
+    };
+
     let template = TimelineTemplate {
         nav_active: "timeline",
         is_authenticated,
@@ -46,11 +120,84 @@ pub(crate) async fn timeline_page(
         events: data.events,
         navigator: data.navigator,
         months: data.months,
+        authors: authors.options(filter.created_by),
+        path,
+        archive,
     };
 
     render_html(template).map(IntoResponse::into_response)
 }
 
+/// Every year and month with events, and how many each holds.
+#[tracing::instrument(skip(state, cookies, filter_query))]
+pub(crate) async fn timeline_archive_page(
+    State(state): State<AppState>,
+    cookies: tower_cookies::Cookies,
+    Query(filter_query): Query<TimelineFilterQuery>,
+) -> Result<Response, StatusCode> {
+    let counts = load_month_counts(&state, filter_query.filter()).await?;
+
+    let template = TimelineArchiveTemplate {
+        nav_active: "timeline",
+        is_authenticated: crate::application::routes::is_authenticated(&state, &cookies).await,
+        version_info: &crate::VERSION_INFO,
+        heading: "Archive".to_string(),
+        years: TimelineArchiveYearView::group(&counts, &filter_query.link_query()),
+        previous_year: None,
+        next_year: None,
+    };
+
+    render_html(template).map(IntoResponse::into_response)
+}
+
+/// The months of one year with events, e.g. `/timeline/2025`.
+#[tracing::instrument(skip(state, cookies, filter_query))]
+pub(crate) async fn timeline_year_page(
+    State(state): State<AppState>,
+    cookies: tower_cookies::Cookies,
+    Path(year): Path<i32>,
+    Query(filter_query): Query<TimelineFilterQuery>,
+) -> Result<Response, StatusCode> {
+    TimelineMonth::new(year, 1).ok_or(StatusCode::NOT_FOUND)?;
+    let counts = load_month_counts(&state, filter_query.filter()).await?;
+
+    let years = TimelineArchiveYearView::group(&counts, &filter_query.link_query());
+    // Years are newest first, so the next year sits just before this one.
+    let index = years.iter().position(|entry| entry.number == year);
+    let next_year = index
+        .and_then(|index| index.checked_sub(1))
+        .map(|index| years[index].year.clone());
+    let previous_year = index
+        .and_then(|index| years.get(index + 1))
+        .map(|entry| entry.year.clone());
+
+    let template = TimelineArchiveTemplate {
+        nav_active: "timeline",
+        is_authenticated: crate::application::routes::is_authenticated(&state, &cookies).await,
+        version_info: &crate::VERSION_INFO,
+        heading: year.to_string(),
+        years: years
+            .into_iter()
+            .filter(|entry| entry.number == year)
+            .collect(),
+        previous_year,
+        next_year,
+    };
+
+    render_html(template).map(IntoResponse::into_response)
+}
+
+async fn load_month_counts(
+    state: &AppState,
+    filter: TimelineFilter,
+) -> Result<Vec<TimelineMonthCount>, StatusCode> {
+    state
+        .timeline_repo
+        .month_counts(filter)
+        .await
+        .map_err(|err| map_app_error(AppError::from(err)))
+}
+
 struct TimelinePreparedEvent {
     anchor: String,
     heading: String,
@@ -59,10 +206,13 @@ struct TimelinePreparedEvent {
 
 async fn render_timeline_chunk(
     state: AppState,
+    authors: &Authors,
+    filter: TimelineFilter,
     request: ListRequest<TimelineSortKey>,
+    path: &str,
     is_authenticated: bool,
 ) -> Result<Response, AppError> {
-    let data = load_timeline_page(&state, request).await?;
+    let data = load_timeline_page(&state, authors, filter, request, path).await?;
     let template = TimelineChunkTemplate {
         is_authenticated,
         events: data.events,
@@ -79,14 +229,17 @@ struct TimelinePageData {
     months: Vec<TimelineMonthView>,
 }
 
-#[tracing::instrument(skip(state))]
+#[tracing::instrument(skip(state, authors))]
 async fn load_timeline_page(
     state: &AppState,
+    authors: &Authors,
+    filter: TimelineFilter,
     request: ListRequest<TimelineSortKey>,
+    path: &str,
 ) -> Result<TimelinePageData, AppError> {
     let page = state
         .timeline_repo
-        .list(&request)
+        .list(filter, &request)
         .await
         .map_err(AppError::from)?;
 
@@ -95,7 +248,7 @@ async fn load_timeline_page(
     let prepared_events = page
         .items
         .into_iter()
-        .map(prepare_event)
+        .map(|event| prepare_event(event, authors))
         .collect::<Vec<_>>();
 
     let views = prepared_events
@@ -111,12 +264,10 @@ async fn load_timeline_page(
         page.showing_all,
     );
     let months = build_months(prepared_events);
-    let navigator = ListNavigator::new(
-        TIMELINE_PAGE_PATH,
-        TIMELINE_FRAGMENT_PATH,
-        normalized_request,
-        None,
-    );
+    let mut navigator = ListNavigator::new(path, path, normalized_request, None);
+    if let Some(user_id) = filter.created_by {
+        navigator = navigator.with_filter("user", user_id.to_string());
+    }
 
     Ok(TimelinePageData {
         events,
@@ -125,10 +276,11 @@ async fn load_timeline_page(
     })
 }
 
-fn prepare_event(event: TimelineEvent) -> TimelinePreparedEvent {
+fn prepare_event(event: TimelineEvent, authors: &Authors) -> TimelinePreparedEvent {
     let anchor = event.occurred_at.format("%Y-%m").to_string();
     let heading = event.occurred_at.format("%B %Y").to_string();
-    let view = TimelineEventView::from(event);
+    let author = authors.name(event.created_by);
+    let view = TimelineEventView::from(event).with_author(author);
 
     TimelinePreparedEvent {
         anchor,
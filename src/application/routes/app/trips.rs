@@ -0,0 +1,66 @@
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use tower_cookies::Cookies;
+
+use crate::application::errors::map_app_error;
+use crate::application::routes::api::trips::load_trip_summary;
+use crate::application::routes::render_html;
+use crate::application::routes::support::load_map_tiles;
+use crate::application::state::AppState;
+use crate::domain::ids::TripId;
+use crate::presentation::web::templates::{TripDetailTemplate, TripsTemplate};
+use crate::presentation::web::views::{TripDetailView, TripView};
+
+#[tracing::instrument(skip(state, cookies))]
+pub(crate) async fn trips_page(
+    State(state): State<AppState>,
+    cookies: Cookies,
+) -> Result<Response, StatusCode> {
+    let is_authenticated = crate::application::routes::is_authenticated(&state, &cookies).await;
+
+    let trips = state
+        .trip_repo
+        .list()
+        .await
+        .map_err(|e| map_app_error(e.into()))?;
+
+    let template = TripsTemplate {
+        nav_active: "",
+        is_authenticated,
+        version_info: &crate::VERSION_INFO,
+        base_url: crate::base_url(),
+        trips: trips.iter().map(TripView::from).collect(),
+    };
+
+    render_html(template).map(IntoResponse::into_response)
+}
+
+#[tracing::instrument(skip(state, cookies))]
+pub(crate) async fn trip_detail_page(
+    State(state): State<AppState>,
+    cookies: Cookies,
+    Path(id): Path<TripId>,
+) -> Result<Response, StatusCode> {
+    let is_authenticated = crate::application::routes::is_authenticated(&state, &cookies).await;
+
+    let trip = state
+        .trip_repo
+        .get(id)
+        .await
+        .map_err(|e| map_app_error(e.into()))?;
+    let summary = load_trip_summary(&state, trip)
+        .await
+        .map_err(map_app_error)?;
+
+    let template = TripDetailTemplate {
+        nav_active: "",
+        is_authenticated,
+        version_info: &crate::VERSION_INFO,
+        base_url: crate::base_url(),
+        trip: TripDetailView::from(summary),
+        map_tiles: load_map_tiles(&state).await,
+    };
+
+    render_html(template).map(IntoResponse::into_response)
+}
@@ -51,7 +51,7 @@ pub(crate) async fn register_page(
             StatusCode::NOT_FOUND
         })?;
 
-    if !reg_token.is_valid() {
+    if !reg_token.is_valid(state.clock.now()) {
         return Err(StatusCode::GONE);
     }
 
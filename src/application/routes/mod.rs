@@ -1,13 +1,18 @@
 pub mod api;
 pub mod app;
+pub mod route_table;
 pub mod support;
 
 pub(crate) use app::auth::is_authenticated;
 
+use std::sync::Arc;
+
 use askama::Template;
-use axum::extract::DefaultBodyLimit;
+use axum::Json;
+use axum::extract::{DefaultBodyLimit, Request, State};
 use axum::http::{HeaderValue, StatusCode};
-use axum::response::Html;
+use axum::middleware::{self, Next};
+use axum::response::{Html, IntoResponse, Response};
 use tower::ServiceBuilder;
 use tower_cookies::CookieManagerLayer;
 use tower_http::compression::CompressionLayer;
@@ -16,9 +21,14 @@ use tower_http::trace::{DefaultMakeSpan, DefaultOnResponse, TraceLayer};
 use tracing::Level;
 use tracing::error;
 
+use crate::application::auth::enforce_session_device;
+use crate::application::errors::ErrorResponse;
+use crate::application::routes::api::version::{self, ApiVersion};
 use crate::application::state::AppState;
+use crate::domain::ErrorCode;
 
 use crate::presentation::web::templates::render_template;
+use crate::presentation::web::views;
 
 /// 5 MB request body limit.
 const BODY_LIMIT_BYTES: usize = 5 * 1024 * 1024;
@@ -26,8 +36,12 @@ const BODY_LIMIT_BYTES: usize = 5 * 1024 * 1024;
 pub fn app_router(state: AppState) -> axum::Router {
     axum::Router::new()
         .merge(app::router())
-        .nest("/api/v1", api::router())
-        .nest("/api/v1/webauthn", api::webauthn_router())
+        .merge(versioned_api_router(state.image_settings.body_limit()))
+        .layer(middleware::from_fn_with_state(state.clone(), scope_clock))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            enforce_session_device,
+        ))
         .layer(
             ServiceBuilder::new()
                 .layer(
@@ -65,11 +79,56 @@ pub fn app_router(state: AppState) -> axum::Router {
                     axum::http::header::STRICT_TRANSPORT_SECURITY,
                     HeaderValue::from_static("max-age=63072000; includeSubDomains"),
                 ))
-                .layer(CompressionLayer::new().gzip(true)),
+                .layer(CompressionLayer::new().br(true).gzip(true)),
         )
         .with_state(state)
 }
 
+/// Every [`ApiVersion`] under its own prefix, sharing the same handlers.
+fn versioned_api_router(image_body_limit: usize) -> axum::Router<AppState> {
+    let mut router = axum::Router::new().route(
+        "/api/{version}/{*path}",
+        axum::routing::any(version::unsupported_version),
+    );
+    for api_version in ApiVersion::ALL {
+        let prefix = api_version.prefix();
+        router = router.nest(
+            prefix,
+            api::router(image_body_limit)
+                .nest("/webauthn", api::webauthn_router())
+                .layer(middleware::from_fn(move |request, next| {
+                    version::versioned(api_version, request, next)
+                })),
+        );
+    }
+    router
+}
+
+/// Views compute relative dates and expiry labels from the app's clock.
+async fn scope_clock(State(state): State<AppState>, request: Request, next: Next) -> Response {
+    views::with_clock(Arc::clone(&state.clock), next.run(request)).await
+}
+
+/// The router used by `serve --demo`: identical to [`app_router`], but any
+/// request that could change data is refused.
+pub fn demo_app_router(state: AppState) -> axum::Router {
+    app_router(state).layer(middleware::from_fn(reject_writes))
+}
+
+async fn reject_writes(request: Request, next: Next) -> Response {
+    if request.method().is_safe() {
+        return next.run(request).await;
+    }
+    (
+        StatusCode::FORBIDDEN,
+        Json(ErrorResponse::new(
+            ErrorCode::ReadOnly,
+            "this is a read-only demo instance",
+        )),
+    )
+        .into_response()
+}
+
 pub(crate) fn render_html<T: Template>(template: T) -> Result<Html<String>, StatusCode> {
     render_template(template).map(Html).map_err(|err| {
         error!(error = %err, "failed to render template");
@@ -0,0 +1,281 @@
+//! The API's route table with the access each endpoint requires.
+//!
+//! Axum can't list the routes it was built with, so this mirrors the routers
+//! in `api/mod.rs` by hand. It is served at
+//! `GET /api/v1/admin/routes`, and the integration tests walk it to check that
+//! every endpoint enforces what it claims. Add new endpoints here too.
+//!
+//! Paths are listed under `/api/v1`. `/api/v2` serves the same endpoints with
+//! the same access; see [`ApiVersion`](super::api::version::ApiVersion).
+
+use serde::Serialize;
+
+/// What a caller needs to reach an endpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuthLevel {
+    Public,
+    /// A session cookie or API token.
+    Authenticated,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct RouteInfo {
+    pub method: &'static str,
+    /// Full path, with Axum-style `{param}` placeholders.
+    pub path: &'static str,
+    pub auth: AuthLevel,
+}
+
+impl RouteInfo {
+    /// Whether the endpoint can change anything.
+    pub fn is_mutating(&self) -> bool {
+        self.method != "GET"
+    }
+}
+
+const fn public(method: &'static str, path: &'static str) -> RouteInfo {
+    RouteInfo {
+        method,
+        path,
+        auth: AuthLevel::Public,
+    }
+}
+
+const fn authenticated(method: &'static str, path: &'static str) -> RouteInfo {
+    RouteInfo {
+        method,
+        path,
+        auth: AuthLevel::Authenticated,
+    }
+}
+
+pub const API_ROUTES: &[RouteInfo] = &[
+    public("GET", "/api/v1/roasters"),
+    authenticated("POST", "/api/v1/roasters"),
+    authenticated("POST", "/api/v1/roasters/bulk"),
+    authenticated("POST", "/api/v1/roasters/quick-add"),
+    public("GET", "/api/v1/roasters/{id}"),
+    authenticated("PUT", "/api/v1/roasters/{id}"),
+    authenticated("DELETE", "/api/v1/roasters/{id}"),
+    authenticated("GET", "/api/v1/roasters/{id}/delete-preview"),
+    authenticated("PUT", "/api/v1/roasters/{id}/favourite"),
+    authenticated("DELETE", "/api/v1/roasters/{id}/favourite"),
+    public("GET", "/api/v1/roasters/{id}/aliases"),
+    authenticated("POST", "/api/v1/roasters/{id}/aliases"),
+    authenticated("POST", "/api/v1/roasters/{id}/merge"),
+    public("GET", "/api/v1/roasts"),
+    authenticated("POST", "/api/v1/roasts"),
+    authenticated("POST", "/api/v1/roasts/bulk"),
+    public("GET", "/api/v1/roasts/{id}"),
+    authenticated("PUT", "/api/v1/roasts/{id}"),
+    authenticated("DELETE", "/api/v1/roasts/{id}"),
+    authenticated("GET", "/api/v1/roasts/{id}/delete-preview"),
+    authenticated("PUT", "/api/v1/roasts/{id}/favourite"),
+    authenticated("DELETE", "/api/v1/roasts/{id}/favourite"),
+    public("GET", "/api/v1/roasts/{id}/stats"),
+    authenticated("POST", "/api/v1/roasts/{id}/lots"),
+    public("GET", "/api/v1/roasts/{id}/aliases"),
+    authenticated("POST", "/api/v1/roasts/{id}/aliases"),
+    authenticated("POST", "/api/v1/roasts/{id}/merge"),
+    authenticated("DELETE", "/api/v1/aliases/{id}"),
+    public("GET", "/api/v1/roasts/{id}/comments"),
+    authenticated("POST", "/api/v1/roasts/{id}/comments"),
+    public("GET", "/api/v1/compare"),
+    public("GET", "/api/v1/regions"),
+    public("GET", "/api/v1/autocomplete"),
+    public("GET", "/api/v1/countries"),
+    public("GET", "/api/v1/bags"),
+    authenticated("POST", "/api/v1/bags"),
+    authenticated("POST", "/api/v1/bags/bulk"),
+    public("GET", "/api/v1/bags/{id}"),
+    authenticated("PUT", "/api/v1/bags/{id}"),
+    authenticated("DELETE", "/api/v1/bags/{id}"),
+    authenticated("POST", "/api/v1/bags/{id}/reopen"),
+    public("GET", "/api/v1/bags/{id}/ledger"),
+    public("GET", "/api/v1/bags/{id}/brew-series"),
+    authenticated("POST", "/api/v1/bags/{id}/adjustments"),
+    authenticated("POST", "/api/v1/bags/{id}/transfers"),
+    public("GET", "/api/v1/gear"),
+    authenticated("POST", "/api/v1/gear"),
+    authenticated("POST", "/api/v1/gear/bulk"),
+    public("GET", "/api/v1/gear/{id}"),
+    authenticated("PUT", "/api/v1/gear/{id}"),
+    authenticated("DELETE", "/api/v1/gear/{id}"),
+    authenticated("POST", "/api/v1/gear/{id}/restock"),
+    public("GET", "/api/v1/grinder-calibrations"),
+    authenticated("POST", "/api/v1/grinder-calibrations"),
+    public("GET", "/api/v1/grinder-calibrations/convert"),
+    authenticated("DELETE", "/api/v1/grinder-calibrations/{id}"),
+    public("GET", "/api/v1/quick-notes"),
+    authenticated("POST", "/api/v1/quick-notes"),
+    authenticated("DELETE", "/api/v1/quick-notes/{id}"),
+    public("GET", "/api/v1/green-coffees"),
+    authenticated("POST", "/api/v1/green-coffees"),
+    public("GET", "/api/v1/green-coffees/{id}"),
+    authenticated("PUT", "/api/v1/green-coffees/{id}"),
+    authenticated("DELETE", "/api/v1/green-coffees/{id}"),
+    public("GET", "/api/v1/events"),
+    authenticated("POST", "/api/v1/events"),
+    public("GET", "/api/v1/events/{id}"),
+    authenticated("PUT", "/api/v1/events/{id}"),
+    authenticated("DELETE", "/api/v1/events/{id}"),
+    public("GET", "/api/v1/trips"),
+    authenticated("POST", "/api/v1/trips"),
+    public("GET", "/api/v1/trips/{id}"),
+    authenticated("PUT", "/api/v1/trips/{id}"),
+    authenticated("DELETE", "/api/v1/trips/{id}"),
+    public("GET", "/api/v1/trips/{id}/summary"),
+    public("GET", "/api/v1/roast-batches"),
+    authenticated("POST", "/api/v1/roast-batches"),
+    public("GET", "/api/v1/roast-batches/{id}"),
+    authenticated("PUT", "/api/v1/roast-batches/{id}"),
+    authenticated("DELETE", "/api/v1/roast-batches/{id}"),
+    public("GET", "/api/v1/processes"),
+    authenticated("POST", "/api/v1/processes"),
+    authenticated("DELETE", "/api/v1/processes/{id}"),
+    public("GET", "/api/v1/brews"),
+    authenticated("POST", "/api/v1/brews"),
+    authenticated("POST", "/api/v1/brews/bulk"),
+    authenticated("POST", "/api/v1/parse-brew"),
+    public("GET", "/api/v1/brews/{id}"),
+    authenticated("PUT", "/api/v1/brews/{id}"),
+    authenticated("DELETE", "/api/v1/brews/{id}"),
+    authenticated("POST", "/api/v1/brews/{id}/share"),
+    authenticated("GET", "/api/v1/brews/{id}/shares"),
+    authenticated("POST", "/api/v1/shares/{id}/revoke"),
+    public("GET", "/api/v1/brews/{id}/comments"),
+    authenticated("POST", "/api/v1/brews/{id}/comments"),
+    authenticated("PUT", "/api/v1/comments/{id}"),
+    authenticated("DELETE", "/api/v1/comments/{id}"),
+    public("GET", "/api/v1/cafes"),
+    authenticated("POST", "/api/v1/cafes"),
+    authenticated("POST", "/api/v1/cafes/bulk"),
+    public("GET", "/api/v1/cafes/{id}"),
+    authenticated("PUT", "/api/v1/cafes/{id}"),
+    authenticated("DELETE", "/api/v1/cafes/{id}"),
+    authenticated("GET", "/api/v1/cafes/{id}/delete-preview"),
+    authenticated("PUT", "/api/v1/cafes/{id}/favourite"),
+    authenticated("DELETE", "/api/v1/cafes/{id}/favourite"),
+    authenticated("GET", "/api/v1/nearby-cafes"),
+    authenticated("POST", "/api/v1/extract-roaster"),
+    authenticated("POST", "/api/v1/extract-roast"),
+    authenticated("POST", "/api/v1/extract-bag-scan"),
+    authenticated("POST", "/api/v1/scan"),
+    authenticated("POST", "/api/v1/check-in"),
+    public("GET", "/api/v1/cups"),
+    authenticated("POST", "/api/v1/cups"),
+    authenticated("POST", "/api/v1/cups/bulk"),
+    public("GET", "/api/v1/cups/{id}"),
+    authenticated("PUT", "/api/v1/cups/{id}"),
+    authenticated("DELETE", "/api/v1/cups/{id}"),
+    authenticated("POST", "/api/v1/tokens"),
+    authenticated("GET", "/api/v1/tokens"),
+    authenticated("POST", "/api/v1/tokens/{id}/revoke"),
+    authenticated("POST", "/api/v1/registration-tokens"),
+    authenticated("GET", "/api/v1/registration-tokens"),
+    authenticated("POST", "/api/v1/registration-tokens/{id}/revoke"),
+    authenticated("GET", "/api/v1/list-preferences"),
+    authenticated("PUT", "/api/v1/list-preferences/{list}"),
+    authenticated("DELETE", "/api/v1/list-preferences/{list}"),
+    authenticated("GET", "/api/v1/saved-searches"),
+    authenticated("POST", "/api/v1/saved-searches"),
+    authenticated("GET", "/api/v1/saved-searches/{id}"),
+    authenticated("PUT", "/api/v1/saved-searches/{id}"),
+    authenticated("DELETE", "/api/v1/saved-searches/{id}"),
+    authenticated("GET", "/api/v1/me"),
+    authenticated("PUT", "/api/v1/me"),
+    authenticated("GET", "/api/v1/notifications"),
+    authenticated("POST", "/api/v1/notifications/read-all"),
+    authenticated("POST", "/api/v1/notifications/{id}/read"),
+    authenticated("GET", "/api/v1/passkeys"),
+    authenticated("DELETE", "/api/v1/passkeys/{id}"),
+    authenticated("GET", "/api/v1/sessions"),
+    authenticated("DELETE", "/api/v1/sessions/{id}"),
+    authenticated("GET", "/api/v1/backup"),
+    authenticated("POST", "/api/v1/backup/restore"),
+    authenticated("POST", "/api/v1/backup/restore/stream"),
+    authenticated("POST", "/api/v1/backup/reset"),
+    authenticated("POST", "/api/v1/backfill"),
+    authenticated("GET", "/api/v1/export/beanconqueror"),
+    authenticated("POST", "/api/v1/import/beanconqueror"),
+    authenticated("GET", "/api/v1/admin/overview"),
+    authenticated("GET", "/api/v1/admin/routes"),
+    authenticated("GET", "/api/v1/admin/integrity"),
+    authenticated("POST", "/api/v1/admin/integrity/repair"),
+    authenticated("POST", "/api/v1/admin/normalize-countries"),
+    public("GET", "/api/v1/settings"),
+    authenticated("PUT", "/api/v1/settings"),
+    public("GET", "/api/v1/exchange-rates"),
+    authenticated("PUT", "/api/v1/exchange-rates/{currency}"),
+    authenticated("DELETE", "/api/v1/exchange-rates/{currency}"),
+    authenticated("GET", "/api/v1/ai-prices"),
+    authenticated("PUT", "/api/v1/ai-prices/{*model}"),
+    authenticated("DELETE", "/api/v1/ai-prices/{*model}"),
+    authenticated("GET", "/api/v1/quick-actions"),
+    public("GET", "/api/v1/stats"),
+    public("GET", "/api/v1/stats/habits"),
+    public("GET", "/api/v1/stats/roasters"),
+    authenticated("POST", "/api/v1/stats/recompute"),
+    public("GET", "/api/v1/timeline"),
+    authenticated("POST", "/api/v1/timeline/rebuild"),
+    authenticated("GET", "/api/v1/qr"),
+    public("GET", "/api/v1/nav-search"),
+    public("GET", "/api/v1/{entity_type}/{id}/image"),
+    authenticated("PUT", "/api/v1/{entity_type}/{id}/image"),
+    authenticated("DELETE", "/api/v1/{entity_type}/{id}/image"),
+    public("GET", "/api/v1/{entity_type}/{id}/thumbnail"),
+    public("POST", "/api/v1/webauthn/register/start"),
+    public("POST", "/api/v1/webauthn/register/finish"),
+    public("GET", "/api/v1/webauthn/auth/start"),
+    public("POST", "/api/v1/webauthn/auth/finish"),
+    authenticated("POST", "/api/v1/webauthn/passkey/start"),
+    authenticated("POST", "/api/v1/webauthn/passkey/finish"),
+    public("GET", "/api/v1/webauthn/auth/discoverable/start"),
+    public("POST", "/api/v1/webauthn/auth/discoverable/finish"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn routes_are_listed_once() {
+        for (i, route) in API_ROUTES.iter().enumerate() {
+            assert!(
+                !API_ROUTES[..i]
+                    .iter()
+                    .any(|r| r.method == route.method && r.path == route.path),
+                "{} {} is listed twice",
+                route.method,
+                route.path
+            );
+        }
+    }
+
+    #[test]
+    fn every_routed_path_is_listed() {
+        let router = include_str!("api/mod.rs");
+        for chunk in router.split(".route(").skip(1) {
+            let path = chunk.split('"').nth(1).unwrap_or_default();
+            assert!(
+                API_ROUTES.iter().any(|r| r.path.ends_with(path)),
+                "{path} is routed but missing from API_ROUTES"
+            );
+        }
+    }
+
+    #[test]
+    fn anonymous_callers_can_only_change_things_through_webauthn() {
+        for route in API_ROUTES {
+            if route.is_mutating() && route.auth == AuthLevel::Public {
+                assert!(
+                    route.path.starts_with("/api/v1/webauthn/"),
+                    "{} {} is public",
+                    route.method,
+                    route.path
+                );
+            }
+        }
+    }
+}
@@ -1,23 +1,33 @@
 use askama::Template;
-use axum::extract::{Form, FromRequest, Json as JsonPayload, Request};
+use axum::extract::{Form, FromRef, FromRequest, Json as JsonPayload, Multipart, Request};
 use axum::http::{HeaderMap, HeaderValue, header::CONTENT_TYPE};
 use axum::response::{Html, IntoResponse, Redirect, Response};
 use serde::Deserialize;
+use serde::de::DeserializeOwned;
 use tracing::warn;
 
 use crate::application::errors::{ApiError, AppError};
+use crate::application::routes::api::images::image_error;
 use crate::application::state::AppState;
+use crate::domain::aliases::AliasTarget;
+use crate::domain::brews::BrewUsage;
+use crate::domain::comments::CommentTarget;
+use crate::domain::ids::UserId;
+use crate::domain::list_preferences::ListPreference;
 use crate::domain::listing::{
     DEFAULT_PAGE_SIZE, ListRequest, Page, PageSize, SortDirection, SortKey,
 };
+use crate::infrastructure::image_processing::{ImageError, ImageSettings, encode_data_url};
 use crate::presentation::web::views::{
-    CafeOptionView, ListNavigator, Paginated, RoastOptionView, RoasterOptionView,
+    AliasView, AuthorOptionView, BagOptionView, CafeOptionView, CommentView, ListNavigator,
+    MapTilesView, Paginated, RoastOptionView, RoasterOptionView,
 };
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum PayloadSource {
     Json,
     Form,
+    Multipart,
 }
 
 #[derive(Debug)]
@@ -101,6 +111,24 @@ impl ListQuery {
         self.q.clone().unwrap_or_default()
     }
 
+    /// Fill in page size and sort from a saved preference where the request
+    /// omits them. The saved direction only applies alongside the saved sort
+    /// key, so an explicit `sort` keeps that key's own default direction.
+    pub fn with_preference(mut self, preference: &ListPreference) -> Self {
+        if self.page_size.is_none() {
+            self.page_size = preference.page_size.map(PageSizeParam::Number);
+        }
+        if self.sort_key.is_none() {
+            self.sort_key.clone_from(&preference.sort_key);
+            if self.sort_dir.is_none() {
+                self.sort_dir = preference
+                    .sort_direction
+                    .map(|direction| direction.as_str().to_string());
+            }
+        }
+        self
+    }
+
     pub fn into_request_and_search<K: SortKey>(self) -> (ListRequest<K>, Option<String>) {
         self.into_request_and_search_with_default::<K>(DEFAULT_PAGE_SIZE)
     }
@@ -208,6 +236,91 @@ pub fn render_fragment<T: Template>(
     Ok(response)
 }
 
+/// How a fragment is merged into the page by Datastar.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PatchMode {
+    Replace,
+    Append,
+    Prepend,
+}
+
+impl PatchMode {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            PatchMode::Replace => "replace",
+            PatchMode::Append => "append",
+            PatchMode::Prepend => "prepend",
+        }
+    }
+}
+
+/// How much of a list a create or update should re-render. Clients opt into
+/// row-level patches with `?fragment=row` once the table is on screen; the
+/// default re-renders the whole list, which also covers the empty state.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum FragmentScope {
+    #[default]
+    List,
+    Row,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct FragmentQuery {
+    #[serde(default)]
+    pub fragment: FragmentScope,
+}
+
+impl FragmentQuery {
+    /// True when a Datastar request from the matching data tab asked for a
+    /// single row rather than the whole list.
+    pub fn wants_row(&self, headers: &HeaderMap, referer_match: &str) -> bool {
+        self.fragment == FragmentScope::Row
+            && is_datastar_request(headers)
+            && headers
+                .get("referer")
+                .and_then(|v| v.to_str().ok())
+                .is_some_and(|r| r.contains(referer_match))
+    }
+}
+
+/// Where a newly created row goes in a list sorted by `request`, or `None`
+/// when only a full re-render can place it. Newest-first lists take it at
+/// the top of the first page; an unpaginated oldest-first list takes it at
+/// the bottom. Searches may not match the new row, so they always re-render.
+pub(crate) fn new_row_mode<K: SortKey>(
+    request: &ListRequest<K>,
+    search: Option<&str>,
+) -> Option<PatchMode> {
+    if search.is_some() || request.sort_key().query_value() != "created-at" {
+        return None;
+    }
+    match request.sort_direction() {
+        SortDirection::Desc if request.page() == 1 => Some(PatchMode::Prepend),
+        SortDirection::Asc if request.page_size().is_all() => Some(PatchMode::Append),
+        _ => None,
+    }
+}
+
+/// Render a single list row as a Datastar fragment patched into `selector`.
+pub fn render_row_fragment<T: Template>(
+    template: T,
+    selector: &str,
+    mode: PatchMode,
+) -> Result<Response, AppError> {
+    let html = crate::presentation::web::templates::render_template(template)
+        .map_err(|err| AppError::unexpected(format!("failed to render row: {err}")))?;
+    let selector = HeaderValue::from_str(selector)
+        .map_err(|err| AppError::unexpected(format!("invalid fragment selector: {err}")))?;
+
+    let mut response = Html(html).into_response();
+    let headers = response.headers_mut();
+    headers.insert(CONTENT_TYPE, HeaderValue::from_static("text/html"));
+    headers.insert("datastar-selector", selector);
+    headers.insert("datastar-mode", HeaderValue::from_static(mode.as_str()));
+    Ok(response)
+}
+
 fn page_size_from_text(value: &str) -> PageSize {
     if value.eq_ignore_ascii_case("all") {
         PageSize::All
@@ -226,10 +339,57 @@ fn parse_direction(value: &str) -> Option<SortDirection> {
     }
 }
 
+/// Collect the fields of a `multipart/form-data` body as form pairs. File
+/// parts become data URLs, read chunk by chunk so that an oversized image is
+/// refused before the rest of it is buffered.
+async fn read_multipart_fields(
+    mut multipart: Multipart,
+    settings: &ImageSettings,
+) -> Result<Vec<(String, String)>, ApiError> {
+    fn invalid(err: impl std::fmt::Display) -> ApiError {
+        warn!(error = %err, "failed to read multipart payload");
+        AppError::validation("invalid multipart payload").into()
+    }
+
+    let mut fields = Vec::new();
+    while let Some(mut field) = multipart.next_field().await.map_err(invalid)? {
+        let Some(name) = field.name().map(str::to_string) else {
+            continue;
+        };
+        if field.file_name().is_none() {
+            fields.push((name, field.text().await.map_err(invalid)?));
+            continue;
+        }
+
+        let content_type = field.content_type().map(str::to_string);
+        let mut bytes = Vec::new();
+        while let Some(chunk) = field.chunk().await.map_err(invalid)? {
+            let size = bytes.len() + chunk.len();
+            if size > settings.max_file_size {
+                return Err(image_error(ImageError::FileTooLarge {
+                    size,
+                    limit: settings.max_file_size,
+                })
+                .into());
+            }
+            bytes.extend_from_slice(&chunk);
+        }
+        // Browsers send an empty part for a file input left blank.
+        let value = if bytes.is_empty() {
+            String::new()
+        } else {
+            encode_data_url(&bytes, content_type.as_deref())
+        };
+        fields.push((name, value));
+    }
+    Ok(fields)
+}
+
 impl<S, T> FromRequest<S> for FlexiblePayload<T>
 where
     S: Send + Sync,
-    T: Send + 'static,
+    AppState: FromRef<S>,
+    T: DeserializeOwned + Send + 'static,
     JsonPayload<T>: FromRequest<S>,
     Form<T>: FromRequest<S>,
 {
@@ -271,37 +431,180 @@ where
             });
         }
 
+        if content_type.starts_with("multipart/form-data") {
+            let settings = AppState::from_ref(state).image_settings;
+            let multipart = Multipart::from_request(req, state).await.map_err(|_| {
+                warn!("failed to parse multipart payload");
+                ApiError::from(AppError::validation("invalid multipart payload"))
+            })?;
+            let fields = read_multipart_fields(multipart, &settings).await?;
+            let payload = serde_urlencoded::to_string(&fields)
+                .ok()
+                .and_then(|encoded| serde_urlencoded::from_str::<T>(&encoded).ok())
+                .ok_or_else(|| {
+                    warn!("failed to parse multipart payload");
+                    ApiError::from(AppError::validation("invalid multipart payload"))
+                })?;
+
+            return Ok(Self {
+                inner: payload,
+                source: PayloadSource::Multipart,
+            });
+        }
+
         Err(AppError::validation("unsupported content type").into())
     }
 }
 
+/// Roasters for a picker: favourites first, then by name.
 pub(crate) async fn load_roaster_options(
     state: &AppState,
 ) -> Result<Vec<RoasterOptionView>, AppError> {
     use crate::domain::roasters::RoasterSortKey;
-    let roasters = state
+    let mut roasters = state
         .roaster_repo
         .list_all_sorted(RoasterSortKey::Name, SortDirection::Asc)
         .await
         .map_err(AppError::from)?;
+    roasters.sort_by_key(|roaster| !roaster.favourite);
     Ok(roasters.into_iter().map(RoasterOptionView::from).collect())
 }
 
+/// Roasts for a picker, with favourites first.
 pub(crate) async fn load_roast_options(state: &AppState) -> Result<Vec<RoastOptionView>, AppError> {
-    let roasts = state.roast_repo.list_all().await.map_err(AppError::from)?;
+    let mut roasts = state.roast_repo.list_all().await.map_err(AppError::from)?;
+    roasts.sort_by_key(|r| !r.roast.favourite);
     Ok(roasts.into_iter().map(RoastOptionView::from).collect())
 }
 
+/// Cafes for a picker: favourites first, then by name.
 pub(crate) async fn load_cafe_options(state: &AppState) -> Result<Vec<CafeOptionView>, AppError> {
     use crate::domain::cafes::CafeSortKey;
-    let cafes = state
+    let mut cafes = state
         .cafe_repo
         .list_all_sorted(CafeSortKey::Name, SortDirection::Asc)
         .await
         .map_err(AppError::from)?;
+    cafes.sort_by_key(|cafe| !cafe.favourite);
     Ok(cafes.into_iter().map(CafeOptionView::from).collect())
 }
 
+/// Bags that still have coffee in them, most recently brewed first and then
+/// most recently roasted.
+pub(crate) async fn load_open_bag_options(
+    state: &AppState,
+) -> Result<Vec<BagOptionView>, AppError> {
+    let usage = state.brew_repo.usage().await.map_err(AppError::from)?;
+    load_open_bag_options_by_usage(state, &usage).await
+}
+
+/// As [`load_open_bag_options`], for callers that already have the usage.
+pub(crate) async fn load_open_bag_options_by_usage(
+    state: &AppState,
+    usage: &BrewUsage,
+) -> Result<Vec<BagOptionView>, AppError> {
+    use crate::domain::bags::{BagFilter, BagSortKey};
+    let request = ListRequest::show_all(BagSortKey::RoastDate, SortDirection::Desc);
+    let mut bags = state
+        .bag_repo
+        .list(BagFilter::open(), &request, None)
+        .await
+        .map_err(AppError::from)?
+        .items;
+    usage.sort_bags(&mut bags, |bag| bag.bag.id);
+    Ok(bags.into_iter().map(BagOptionView::from).collect())
+}
+
+/// Whether pages should draw tile maps. Falls back to the world map when
+/// the settings can't be read.
+pub(crate) async fn load_map_tiles(state: &AppState) -> Option<MapTilesView> {
+    match state.settings_repo.get().await {
+        Ok(settings) => MapTilesView::from_settings(&settings),
+        Err(err) => {
+            warn!(error = %err, "failed to load map tile settings");
+            None
+        }
+    }
+}
+
+/// Names of the users who log activity, used to attribute timeline events
+/// and entities. Empty on single-user instances, where attribution adds
+/// nothing.
+#[derive(Default)]
+pub(crate) struct Authors {
+    users: Vec<(UserId, String)>,
+}
+
+impl Authors {
+    pub(crate) async fn load(state: &AppState) -> Self {
+        match state.user_repo.list_all().await {
+            Ok(users) if users.len() > 1 => Self {
+                users: users
+                    .iter()
+                    .map(|user| (user.id, user.name().to_string()))
+                    .collect(),
+            },
+            Ok(_) => Self::default(),
+            Err(err) => {
+                warn!(error = %err, "failed to load users for attribution");
+                Self::default()
+            }
+        }
+    }
+
+    /// The name to show for an entity's creator, if attribution applies.
+    pub(crate) fn name(&self, user_id: Option<UserId>) -> Option<String> {
+        let user_id = user_id?;
+        self.users
+            .iter()
+            .find(|(id, _)| *id == user_id)
+            .map(|(_, name)| name.clone())
+    }
+
+    /// Options for filtering a list by author, with `selected` marked.
+    pub(crate) fn options(&self, selected: Option<UserId>) -> Vec<AuthorOptionView> {
+        self.users
+            .iter()
+            .map(|(id, name)| AuthorOptionView {
+                id: i64::from(*id),
+                name: name.clone(),
+                selected: selected == Some(*id),
+            })
+            .collect()
+    }
+}
+
+/// The comment thread for a detail page. A failure to load it is logged and
+/// shown as an empty thread rather than failing the page.
+pub(crate) async fn load_comments(
+    state: &AppState,
+    target: CommentTarget,
+    viewer: Option<UserId>,
+) -> Vec<CommentView> {
+    match state.comment_repo.list_for(target).await {
+        Ok(comments) => comments
+            .into_iter()
+            .map(|comment| CommentView::new(comment, viewer))
+            .collect(),
+        Err(err) => {
+            warn!(error = %err, "failed to load comments");
+            Vec::new()
+        }
+    }
+}
+
+/// The aliases for a detail page. A failure to load them is logged and shown
+/// as none rather than failing the page.
+pub(crate) async fn load_aliases(state: &AppState, target: AliasTarget) -> Vec<AliasView> {
+    match state.alias_repo.list_for(target).await {
+        Ok(aliases) => aliases.into_iter().map(AliasView::from).collect(),
+        Err(err) => {
+            warn!(error = %err, "failed to load aliases");
+            Vec::new()
+        }
+    }
+}
+
 /// Record AI usage in the background. Failures are logged but do not affect the response.
 pub fn record_ai_usage(
     repo: std::sync::Arc<dyn crate::domain::repositories::AiUsageRepository>,
@@ -327,6 +630,25 @@ pub fn record_ai_usage(
     });
 }
 
+/// Turn a failed AI extraction into the manual-entry fallback. Datastar forms
+/// get `_aiUnavailable` so they can ask for the details by hand; API clients
+/// get the `503` with [`ErrorCode::AiUnavailable`](crate::domain::ErrorCode).
+pub fn ai_fallback(
+    headers: &HeaderMap,
+    err: AppError,
+) -> Result<Response, crate::application::errors::ApiError> {
+    if is_datastar_request(headers)
+        && matches!(
+            err,
+            AppError::Unavailable(crate::domain::ErrorCode::AiUnavailable, _)
+        )
+    {
+        return render_signals_json(&[("_ai-unavailable", serde_json::Value::Bool(true))])
+            .map_err(Into::into);
+    }
+    Err(err.into())
+}
+
 pub fn is_datastar_request(headers: &HeaderMap) -> bool {
     headers
         .get("datastar-request")
@@ -417,6 +739,50 @@ mod tests {
         );
     }
 
+    #[test]
+    fn new_row_mode_prepends_to_first_newest_first_page() {
+        use crate::domain::roasters::RoasterSortKey;
+
+        let request = ListRequest::default_query();
+        assert_eq!(
+            new_row_mode::<RoasterSortKey>(&request, None),
+            Some(PatchMode::Prepend)
+        );
+        assert_eq!(new_row_mode(&request.with_page(2), None), None);
+        assert_eq!(new_row_mode(&request, Some("onyx")), None);
+    }
+
+    #[test]
+    fn new_row_mode_appends_only_to_unpaginated_oldest_first_lists() {
+        use crate::domain::roasters::RoasterSortKey;
+
+        let all = ListRequest::show_all(RoasterSortKey::CreatedAt, SortDirection::Asc);
+        assert_eq!(new_row_mode(&all, None), Some(PatchMode::Append));
+
+        let paged = all.with_page_size(PageSize::Limited(DEFAULT_PAGE_SIZE));
+        assert_eq!(new_row_mode(&paged, None), None);
+
+        let by_name = ListRequest::show_all(RoasterSortKey::Name, SortDirection::Asc);
+        assert_eq!(new_row_mode(&by_name, None), None);
+    }
+
+    #[test]
+    fn fragment_query_wants_row_requires_datastar_and_matching_referer() {
+        let query = FragmentQuery {
+            fragment: FragmentScope::Row,
+        };
+        let mut headers = HeaderMap::new();
+        headers.insert("datastar-request", HeaderValue::from_static("true"));
+        headers.insert(
+            "referer",
+            HeaderValue::from_static("http://localhost/data?type=gear"),
+        );
+
+        assert!(query.wants_row(&headers, "type=gear"));
+        assert!(!query.wants_row(&headers, "type=roasters"));
+        assert!(!FragmentQuery::default().wants_row(&headers, "type=gear"));
+    }
+
     #[test]
     fn kebab_to_camel_converts_simple_names() {
         assert_eq!(kebab_to_camel("roaster-name"), "roasterName");
@@ -494,6 +860,51 @@ mod tests {
         assert_eq!(parse_direction("invalid"), None);
     }
 
+    fn roast_preference() -> ListPreference {
+        use crate::domain::ids::UserId;
+        use crate::domain::list_preferences::ListName;
+
+        ListPreference {
+            user_id: UserId::new(1),
+            list: ListName::Roasts,
+            page_size: Some(25),
+            sort_key: Some("origin".to_string()),
+            sort_direction: Some(SortDirection::Desc),
+            updated_at: chrono::Utc::now(),
+        }
+    }
+
+    #[test]
+    fn with_preference_fills_missing_fields() {
+        use crate::domain::roasts::RoastSortKey;
+
+        let (request, _) = ListQuery::default()
+            .with_preference(&roast_preference())
+            .into_request_and_search::<RoastSortKey>();
+
+        assert_eq!(request.page_size, PageSize::Limited(25));
+        assert_eq!(request.sort_key, RoastSortKey::Origin);
+        assert_eq!(request.sort_direction, SortDirection::Desc);
+    }
+
+    #[test]
+    fn with_preference_keeps_explicit_sort() {
+        use crate::domain::roasts::RoastSortKey;
+
+        let query = ListQuery {
+            sort_key: Some("name".to_string()),
+            page_size: Some(PageSizeParam::Number(5)),
+            ..ListQuery::default()
+        };
+        let (request, _) = query
+            .with_preference(&roast_preference())
+            .into_request_and_search::<RoastSortKey>();
+
+        assert_eq!(request.page_size, PageSize::Limited(5));
+        assert_eq!(request.sort_key, RoastSortKey::Name);
+        assert_eq!(request.sort_direction, SortDirection::Asc);
+    }
+
     #[test]
     fn render_redirect_script_produces_correct_output() {
         let result = render_redirect_script("/brews/42");
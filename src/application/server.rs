@@ -1,48 +1,136 @@
-use std::net::SocketAddr;
 use std::sync::Arc;
 
 use anyhow::Context;
-use chrono::{Duration, Utc};
+use axum::Extension;
+use axum::extract::ConnectInfo;
+use axum::serve::{Listener, ListenerExt};
+use chrono::Duration;
+use hyper_util::rt::{TokioExecutor, TokioIo, TokioTimer};
+use hyper_util::server::conn::auto;
+use hyper_util::server::graceful::GracefulShutdown;
+use hyper_util::service::TowerToHyperService;
 use tokio::net::TcpListener;
 use tokio::signal;
-use tracing::info;
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
 use webauthn_rs::prelude::*;
 
-use crate::application::routes::app_router;
+use crate::application::routes::{app_router, demo_app_router};
+use crate::application::services::image_purge::image_purge_task;
+use crate::application::services::maintenance::{MaintenanceSchedule, maintenance_task};
 use crate::application::services::stats::stats_recomputation_task;
-use crate::application::services::timeline_refresh::{TimelineRebuilder, timeline_rebuild_task};
-use crate::application::services::{StatsInvalidator, TimelineInvalidator};
-use crate::application::state::{AppState, AppStateConfig};
+use crate::application::services::timeline_refresh::timeline_rebuild_task;
+use crate::application::services::{ImagePurger, StatsInvalidator, TimelineInvalidator};
+use crate::application::state::{AppState, AppStateConfig, RepositoryBackend};
+use crate::application::tenants::{TenantRegistry, TenantsConfig, tenant_router};
+use crate::domain::clock::SystemClock;
 use crate::domain::registration_tokens::NewRegistrationToken;
-use crate::domain::repositories::{RegistrationTokenRepository, UserRepository};
-use crate::infrastructure::auth::{generate_session_token, hash_token};
+use crate::domain::tokens::NewToken;
+use crate::domain::users::NewUser;
+use crate::infrastructure::auth::{
+    TokenHasher, generate_session_token, generate_token, hash_token,
+};
+use crate::infrastructure::backup::BackupService;
 use crate::infrastructure::database::Database;
+use crate::infrastructure::demo;
+use crate::infrastructure::image_processing::ImageSettings;
+use crate::infrastructure::listener::{BindAddress, BoundListener};
+use crate::infrastructure::maintenance::MaintenanceService;
+use crate::infrastructure::qr::render_terminal;
+use crate::infrastructure::systemd;
 
+#[derive(Clone)]
 pub struct ServerConfig {
-    pub bind_address: SocketAddr,
+    pub bind_address: BindAddress,
+    pub http: HttpConfig,
     pub database_url: String,
     pub rp_id: String,
     pub rp_origin: String,
     pub insecure_cookies: bool,
+    /// Serve the bundled sample data from memory and refuse all writes.
+    pub demo: bool,
+    pub token_hasher: TokenHasher,
     pub openrouter_api_key: String,
     pub openrouter_model: String,
     pub foursquare_api_key: String,
+    pub images: ImageSettings,
+    /// Periodic WAL checkpoint and optimisation; `None` disables it.
+    pub maintenance: Option<MaintenanceSchedule>,
+    /// Host a database per tenant instead of `database_url`.
+    pub tenants: Option<TenantsConfig>,
 }
 
-pub async fn serve(config: ServerConfig) -> anyhow::Result<()> {
-    let database = Database::connect(&config.database_url)
-        .await
-        .context("failed to connect to database")?;
+/// Connection-level HTTP settings.
+#[derive(Debug, Clone)]
+pub struct HttpConfig {
+    /// Accept HTTP/2 (h2c) alongside HTTP/1.1.
+    pub http2: bool,
+    /// Keep HTTP/1.1 connections open between requests.
+    pub keep_alive: bool,
+    /// How long a client may take to send request headers.
+    pub header_read_timeout: std::time::Duration,
+    /// Interval between HTTP/2 keep-alive pings; `None` disables them.
+    pub http2_keep_alive_interval: Option<std::time::Duration>,
+    pub http2_max_concurrent_streams: u32,
+}
 
-    let rp_origin = url::Url::parse(&config.rp_origin).context("invalid BREWLOG_RP_ORIGIN URL")?;
-    let webauthn = Arc::new(
-        WebauthnBuilder::new(&config.rp_id, &rp_origin)
+impl Default for HttpConfig {
+    fn default() -> Self {
+        Self {
+            http2: true,
+            keep_alive: true,
+            header_read_timeout: std::time::Duration::from_secs(30),
+            http2_keep_alive_interval: None,
+            http2_max_concurrent_streams: 200,
+        }
+    }
+}
+
+impl HttpConfig {
+    fn connection_builder(&self) -> auto::Builder<TokioExecutor> {
+        let mut builder = auto::Builder::new(TokioExecutor::new());
+        builder
+            .http1()
+            .timer(TokioTimer::new())
+            .keep_alive(self.keep_alive)
+            .header_read_timeout(self.header_read_timeout);
+        builder
+            .http2()
+            .timer(TokioTimer::new())
+            .keep_alive_interval(self.http2_keep_alive_interval)
+            .max_concurrent_streams(self.http2_max_concurrent_streams);
+        if self.http2 {
+            builder
+        } else {
+            builder.http1_only()
+        }
+    }
+}
+
+/// A running Brewlog: its state, and the background tasks that keep its
+/// caches and database in shape.
+pub(crate) struct Instance {
+    pub state: AppState,
+    pub tasks: Vec<JoinHandle<()>>,
+}
+
+pub(crate) fn build_webauthn(rp_id: &str, rp_origin: &str) -> anyhow::Result<Arc<Webauthn>> {
+    let rp_origin = url::Url::parse(rp_origin).context("invalid BREWLOG_RP_ORIGIN URL")?;
+    Ok(Arc::new(
+        WebauthnBuilder::new(rp_id, &rp_origin)
             .context("failed to build WebAuthn instance")?
             .rp_name("Brewlog")
             .build()
             .context("failed to build WebAuthn instance")?,
-    );
+    ))
+}
 
+/// Build the state for `database` and start its background tasks.
+pub(crate) async fn start_instance(
+    database: &Database,
+    webauthn: Arc<Webauthn>,
+    config: &ServerConfig,
+) -> Instance {
     let (stats_tx, stats_rx) = tokio::sync::mpsc::channel::<()>(32);
     let stats_invalidator = StatsInvalidator::new(stats_tx);
 
@@ -51,102 +139,280 @@ pub async fn serve(config: ServerConfig) -> anyhow::Result<()> {
     >(32);
     let timeline_invalidator = TimelineInvalidator::new(timeline_tx);
 
+    let (image_purge_tx, image_purge_rx) = tokio::sync::mpsc::channel::<()>(32);
+    let image_purger = ImagePurger::new(image_purge_tx);
+
     let state = AppState::from_database(
-        &database,
+        database,
         AppStateConfig {
             webauthn,
             insecure_cookies: config.insecure_cookies,
+            token_hasher: config.token_hasher.clone(),
             foursquare_url: crate::infrastructure::foursquare::FOURSQUARE_SEARCH_URL.to_string(),
-            foursquare_api_key: config.foursquare_api_key,
+            foursquare_api_key: config.foursquare_api_key.clone(),
             openrouter_url: crate::infrastructure::ai::OPENROUTER_URL.to_string(),
-            openrouter_api_key: config.openrouter_api_key,
-            openrouter_model: config.openrouter_model,
+            openrouter_api_key: config.openrouter_api_key.clone(),
+            openrouter_model: config.openrouter_model.clone(),
             stats_invalidator: stats_invalidator.clone(),
             timeline_invalidator,
+            image_purger,
+            clock: Arc::new(SystemClock),
+            repositories: RepositoryBackend::Sql,
+            image_settings: config.images,
         },
     );
+    let mut tasks = Vec::new();
 
     // Spawn background stats recomputation task
     let stats_repo = Arc::clone(&state.stats_repo);
-    tokio::spawn(stats_recomputation_task(
+    tasks.push(tokio::spawn(stats_recomputation_task(
         stats_rx,
         stats_repo,
+        Arc::clone(&state.clock),
         std::time::Duration::from_secs(2),
-    ));
+    )));
 
     // Spawn background timeline rebuild task
-    let rebuilder = TimelineRebuilder {
-        timeline_repo: Arc::clone(&state.timeline_repo),
-        roaster_repo: Arc::clone(&state.roaster_repo),
-        roast_repo: Arc::clone(&state.roast_repo),
-        bag_repo: Arc::clone(&state.bag_repo),
-        brew_repo: Arc::clone(&state.brew_repo),
-        cup_repo: Arc::clone(&state.cup_repo),
-        gear_repo: Arc::clone(&state.gear_repo),
-        cafe_repo: Arc::clone(&state.cafe_repo),
-    };
-    tokio::spawn(timeline_rebuild_task(
+    tasks.push(tokio::spawn(timeline_rebuild_task(
         timeline_rx,
-        rebuilder,
+        state.timeline_rebuilder(),
         std::time::Duration::from_secs(2),
-    ));
+    )));
+
+    // Spawn background image purge task
+    tasks.push(tokio::spawn(image_purge_task(
+        image_purge_rx,
+        Arc::clone(&state.image_purge_service),
+        std::time::Duration::from_secs(2),
+    )));
+
+    // The demo database lives in memory and has no WAL to keep in check
+    if let Some(schedule) = config.maintenance.filter(|_| !config.demo) {
+        tasks.push(tokio::spawn(maintenance_task(
+            MaintenanceService::new(database.clone_pool(), Arc::clone(&state.clock)),
+            schedule,
+        )));
+    }
 
     // Seed the stats cache on startup
     stats_invalidator.invalidate();
 
+    // The demo data ships without timeline events
+    if config.demo {
+        state.timeline_invalidator.rebuild_all();
+    }
+
+    // Finish purging images deleted before the last shutdown
+    state.image_purger.schedule();
+
     // Clean up expired sessions on startup
     if let Err(err) = state.session_repo.delete_expired().await {
         tracing::warn!(error = %err, "failed to clean up expired sessions on startup");
     }
 
-    // Bootstrap: if no users exist, generate a one-time registration token
-    bootstrap_registration(
-        &state.registration_token_repo,
-        &state.user_repo,
-        &config.rp_origin,
-    )
-    .await?;
+    Instance { state, tasks }
+}
 
-    let listener = TcpListener::bind(config.bind_address)
-        .await
-        .with_context(|| format!("failed to bind to {}", config.bind_address))?;
+/// The router for a server with a single database.
+async fn single_instance(config: &ServerConfig) -> anyhow::Result<axum::Router> {
+    let database = if config.demo {
+        let database = Database::in_memory()
+            .await
+            .context("failed to create demo database")?;
+        demo::load_sample(&BackupService::new(database.clone_pool())).await?;
+        database
+    } else {
+        Database::connect(&config.database_url)
+            .await
+            .context("failed to connect to database")?
+    };
 
-    let app = app_router(state);
+    let webauthn = build_webauthn(&config.rp_id, &config.rp_origin)?;
+    let Instance { state, .. } = start_instance(&database, webauthn, config).await;
+
+    // Bootstrap: if no users exist, generate a one-time registration token.
+    // Nobody can register on a demo instance, so don't offer one.
+    if !config.demo {
+        bootstrap_registration(&state, &config.rp_origin).await?;
+    }
+
+    Ok(if config.demo {
+        demo_app_router(state)
+    } else {
+        app_router(state)
+    })
+}
+
+pub async fn serve(config: ServerConfig) -> anyhow::Result<()> {
+    let (app, database) = if let Some(tenants) = &config.tenants {
+        let registry = TenantRegistry::new(config.clone())?;
+        (
+            tenant_router(Arc::new(registry)),
+            format!("{}/<tenant>.db", tenants.dir.display()),
+        )
+    } else if config.demo {
+        (
+            single_instance(&config).await?,
+            "sqlite::memory:".to_string(),
+        )
+    } else {
+        (single_instance(&config).await?, config.database_url.clone())
+    };
+
+    let listener = systemd::listener(&config.bind_address).await?;
+
+    if crate::dev_mode() {
+        warn!("development mode: static assets are read from disk and served uncached");
+    }
+    if config.demo {
+        warn!("demo mode: serving bundled sample data from memory; all writes are rejected");
+    }
 
     info!(
-        address = %config.bind_address,
-        database = %config.database_url,
+        address = %listener.address(),
+        %database,
+        http2 = config.http.http2,
+        keep_alive = config.http.keep_alive,
         "starting HTTP server"
     );
 
-    axum::serve(listener, app)
-        .with_graceful_shutdown(shutdown_signal())
-        .await
-        .context("server terminated unexpectedly")?;
+    systemd::notify_ready();
+    match listener {
+        BoundListener::Tcp(listener) => {
+            let listener = listener.tap_io(|stream| {
+                if let Err(err) = stream.set_nodelay(true) {
+                    debug!(error = %err, "failed to set TCP_NODELAY");
+                }
+            });
+            serve_connections(listener, app, &config.http).await;
+        }
+        BoundListener::Unix(listener, path) => {
+            serve_connections(listener, app, &config.http).await;
+            if let Some(path) = path
+                && let Err(err) = std::fs::remove_file(&path)
+            {
+                warn!(error = %err, path = %path.display(), "failed to remove socket");
+            }
+        }
+    }
 
     info!("server shutdown complete");
 
     Ok(())
 }
 
-async fn bootstrap_registration(
-    registration_token_repo: &Arc<dyn RegistrationTokenRepository>,
-    user_repo: &Arc<dyn UserRepository>,
+/// Start a throwaway instance for the CLI's `--dry-run`: an empty in-memory
+/// database served on a random local port until the process exits. Returns
+/// the instance's URL and an API token for it.
+pub async fn spawn_dry_run() -> anyhow::Result<(String, String)> {
+    let database = Database::in_memory()
+        .await
+        .context("failed to create dry-run database")?;
+
+    let rp_origin = url::Url::parse("http://localhost").context("invalid dry-run origin")?;
+    let webauthn = Arc::new(
+        WebauthnBuilder::new("localhost", &rp_origin)
+            .context("failed to build WebAuthn instance")?
+            .build()
+            .context("failed to build WebAuthn instance")?,
+    );
+
+    // Nothing outlives the command, so there's no cache or timeline to keep
+    // fresh, or deleted images to purge.
+    let (stats_tx, _stats_rx) = tokio::sync::mpsc::channel(1);
+    let (timeline_tx, _timeline_rx) = tokio::sync::mpsc::channel(1);
+    let (image_purge_tx, _image_purge_rx) = tokio::sync::mpsc::channel(1);
+    let state = AppState::from_database(
+        &database,
+        AppStateConfig {
+            webauthn,
+            insecure_cookies: true,
+            token_hasher: TokenHasher::sha256(),
+            foursquare_url: String::new(),
+            foursquare_api_key: String::new(),
+            openrouter_url: String::new(),
+            openrouter_api_key: String::new(),
+            openrouter_model: String::new(),
+            stats_invalidator: StatsInvalidator::new(stats_tx),
+            timeline_invalidator: TimelineInvalidator::new(timeline_tx),
+            image_purger: ImagePurger::new(image_purge_tx),
+            clock: Arc::new(SystemClock),
+            repositories: RepositoryBackend::Sql,
+            image_settings: ImageSettings::default(),
+        },
+    );
+
+    let user = state
+        .user_repo
+        .insert(NewUser::new(
+            "dry-run".to_string(),
+            uuid::Uuid::new_v4().to_string(),
+        ))
+        .await
+        .context("failed to create dry-run user")?;
+    let token = generate_token()?;
+    state
+        .token_repo
+        .insert(NewToken::new(
+            user.id,
+            hash_token(&token),
+            "dry-run".to_string(),
+        ))
+        .await
+        .context("failed to create dry-run token")?;
+
+    let listener = TcpListener::bind("127.0.0.1:0")
+        .await
+        .context("failed to bind dry-run server")?;
+    let address = format!("http://{}", listener.local_addr()?);
+    let app = app_router(state);
+    tokio::spawn(async move {
+        axum::serve(
+            listener,
+            app.into_make_service_with_connect_info::<std::net::SocketAddr>(),
+        )
+        .await
+    });
+
+    Ok((address, token))
+}
+
+async fn bootstrap_registration(state: &AppState, rp_origin: &str) -> anyhow::Result<()> {
+    let Some(registration_url) = registration_url(state, rp_origin).await? else {
+        return Ok(());
+    };
+
+    info!("No users found. Register the first user at:");
+    info!("  {registration_url}");
+    match render_terminal(&registration_url) {
+        Ok(qr) => info!("Or scan this QR code from a phone:\n{qr}"),
+        Err(err) => warn!(error = %err, "failed to render registration QR code"),
+    }
+    info!("This link expires in 1 hour.");
+
+    Ok(())
+}
+
+/// A one-time link for registering the first user, or `None` once anyone
+/// has registered.
+pub(crate) async fn registration_url(
+    state: &AppState,
     rp_origin: &str,
-) -> anyhow::Result<()> {
-    let users_exist = user_repo
+) -> anyhow::Result<Option<String>> {
+    let users_exist = state
+        .user_repo
         .exists()
         .await
         .context("failed to check if users exist")?;
 
     if users_exist {
-        return Ok(());
+        return Ok(None);
     }
 
     // Generate one-time registration token
     let token = generate_session_token();
     let token_hash = hash_token(&token);
-    let now = Utc::now();
+    let now = state.clock.now();
     #[allow(clippy::expect_used)]
     let expires_at = now
         .checked_add_signed(Duration::hours(1))
@@ -154,16 +420,51 @@ async fn bootstrap_registration(
 
     let new_token = NewRegistrationToken::new(token_hash, now, expires_at);
 
-    registration_token_repo
+    state
+        .registration_token_repo
         .insert(new_token)
         .await
         .context("failed to create registration token")?;
 
-    info!("No users found. Register the first user at:");
-    info!("  {}/register/{}", rp_origin, token);
-    info!("This link expires in 1 hour.");
+    Ok(Some(format!("{rp_origin}/register/{token}")))
+}
 
-    Ok(())
+/// Accept connections until a shutdown signal arrives, then wait for
+/// in-flight requests to finish. Each request carries the peer's address as
+/// [`ConnectInfo`].
+async fn serve_connections<L>(mut listener: L, app: axum::Router, http: &HttpConfig)
+where
+    L: Listener,
+    L::Addr: Clone + Send + Sync + std::fmt::Debug + 'static,
+{
+    let builder = http.connection_builder();
+    let graceful = GracefulShutdown::new();
+    let mut shutdown = std::pin::pin!(shutdown_signal());
+
+    loop {
+        let (stream, remote) = tokio::select! {
+            accepted = listener.accept() => accepted,
+            () = &mut shutdown => break,
+        };
+
+        let service = TowerToHyperService::new(tower::Layer::layer(
+            &Extension(ConnectInfo(remote.clone())),
+            app.clone(),
+        ));
+        let connection = builder
+            .serve_connection(TokioIo::new(stream), service)
+            .into_owned();
+        let connection = graceful.watch(connection);
+        tokio::spawn(async move {
+            if let Err(err) = connection.await {
+                debug!(error = %err, ?remote, "connection closed with error");
+            }
+        });
+    }
+
+    systemd::notify_stopping();
+    drop(listener);
+    graceful.shutdown().await;
 }
 
 #[allow(clippy::expect_used)] // Startup: panicking is appropriate if signal handlers fail
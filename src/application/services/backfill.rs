@@ -0,0 +1,180 @@
+use std::collections::BTreeSet;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::brews::NewBrew;
+use crate::domain::cups::NewCup;
+use crate::domain::errors::RepositoryError;
+use crate::domain::ids::UserId;
+use crate::domain::timeline::TimelineMonth;
+use crate::domain::validation::ValidationErrors;
+
+use super::{BrewService, CupService};
+
+/// Historical brews and cups to log in one go. Every record carries the
+/// `created_at` it really happened at.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Backfill {
+    #[serde(default)]
+    pub brews: Vec<NewBrew>,
+    #[serde(default)]
+    pub cups: Vec<NewCup>,
+}
+
+impl Backfill {
+    /// Every record needs a `created_at`, and none can be in the future.
+    pub fn check_dates(&self, now: DateTime<Utc>) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+        let dates = self
+            .brews
+            .iter()
+            .enumerate()
+            .map(|(i, brew)| (RecordRef::brew(i), brew.created_at))
+            .chain(
+                self.cups
+                    .iter()
+                    .enumerate()
+                    .map(|(i, cup)| (RecordRef::cup(i), cup.created_at)),
+            );
+        for (record, created_at) in dates {
+            match created_at {
+                None => record.add(&mut errors, "created_at", "a date is required"),
+                Some(at) if at > now => {
+                    record.add(&mut errors, "created_at", "the date is in the future");
+                }
+                Some(_) => {}
+            }
+        }
+        errors.into_result()
+    }
+
+    /// The months the records fall in.
+    pub fn months(&self) -> BTreeSet<TimelineMonth> {
+        self.brews
+            .iter()
+            .filter_map(|brew| brew.created_at)
+            .chain(self.cups.iter().filter_map(|cup| cup.created_at))
+            .map(TimelineMonth::containing)
+            .collect()
+    }
+}
+
+/// Points at one record of a [`Backfill`] in validation errors: fields as
+/// `brews[2].coffee_weight`, messages as "brew 3: ...".
+#[derive(Debug, Clone, Copy)]
+pub struct RecordRef {
+    kind: &'static str,
+    index: usize,
+}
+
+impl RecordRef {
+    pub fn brew(index: usize) -> Self {
+        Self {
+            kind: "brew",
+            index,
+        }
+    }
+
+    pub fn cup(index: usize) -> Self {
+        Self { kind: "cup", index }
+    }
+
+    pub fn add(self, errors: &mut ValidationErrors, field: &str, message: &str) {
+        errors.add(
+            &format!("{}s[{}].{field}", self.kind, self.index),
+            format!("{}: {message}", self.label()),
+        );
+    }
+
+    /// Re-point errors from validating the record on its own at it.
+    pub fn locate(self, errors: ValidationErrors) -> ValidationErrors {
+        let mut located = ValidationErrors::new();
+        for error in errors.into_errors() {
+            self.add(&mut located, &error.field, &error.message);
+        }
+        located
+    }
+
+    /// e.g. "brew 3", counting from 1.
+    pub fn label(self) -> String {
+        format!("{} {}", self.kind, self.index + 1)
+    }
+}
+
+/// What a backfill logged, and the timeline months it touched (`YYYY-MM`).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackfillReport {
+    pub brews: usize,
+    pub cups: usize,
+    pub months: Vec<String>,
+}
+
+/// A record waiting to be logged, so brews and cups can go in together in
+/// date order.
+enum Record {
+    Brew(NewBrew),
+    Cup(NewCup),
+}
+
+impl Record {
+    fn created_at(&self) -> Option<DateTime<Utc>> {
+        match self {
+            Self::Brew(brew) => brew.created_at,
+            Self::Cup(cup) => cup.created_at,
+        }
+    }
+}
+
+/// Logs historical records through the same services the API uses, oldest
+/// first, so bag balances run down in the order the coffee was drunk.
+#[derive(Clone)]
+pub struct BackfillService {
+    brews: BrewService,
+    cups: CupService,
+}
+
+impl BackfillService {
+    pub fn new(brews: BrewService, cups: CupService) -> Self {
+        Self { brews, cups }
+    }
+
+    /// Log every record in `backfill`, which the caller has already checked.
+    /// A failure part way through leaves the records before it logged.
+    pub async fn backfill(
+        &self,
+        backfill: Backfill,
+        created_by: Option<UserId>,
+    ) -> Result<BackfillReport, RepositoryError> {
+        let months = backfill
+            .months()
+            .into_iter()
+            .map(TimelineMonth::key)
+            .collect();
+        let mut records: Vec<Record> = backfill
+            .brews
+            .into_iter()
+            .map(Record::Brew)
+            .chain(backfill.cups.into_iter().map(Record::Cup))
+            .collect();
+        records.sort_by_key(Record::created_at);
+
+        let mut report = BackfillReport {
+            months,
+            ..BackfillReport::default()
+        };
+        for record in records {
+            match record {
+                Record::Brew(brew) => {
+                    self.brews.create(brew, created_by).await?;
+                    report.brews += 1;
+                }
+                Record::Cup(cup) => {
+                    self.cups.create(cup, created_by).await?;
+                    report.cups += 1;
+                }
+            }
+        }
+        Ok(report)
+    }
+}
@@ -3,8 +3,9 @@ use std::sync::Arc;
 use tracing::warn;
 
 use crate::domain::bags::{Bag, NewBag, UpdateBag, bag_timeline_event};
-use crate::domain::errors::RepositoryError;
-use crate::domain::ids::BagId;
+use crate::domain::clock::Clock;
+use crate::domain::errors::{ErrorCode, RepositoryError};
+use crate::domain::ids::{BagId, UserId};
 use crate::domain::repositories::{
     BagRepository, RoastRepository, RoasterRepository, TimelineEventRepository,
 };
@@ -16,6 +17,7 @@ pub struct BagService {
     roast_repo: Arc<dyn RoastRepository>,
     roaster_repo: Arc<dyn RoasterRepository>,
     timeline_repo: Arc<dyn TimelineEventRepository>,
+    clock: Arc<dyn Clock>,
 }
 
 impl BagService {
@@ -24,17 +26,31 @@ impl BagService {
         roast_repo: Arc<dyn RoastRepository>,
         roaster_repo: Arc<dyn RoasterRepository>,
         timeline_repo: Arc<dyn TimelineEventRepository>,
+        clock: Arc<dyn Clock>,
     ) -> Self {
         Self {
             bag_repo,
             roast_repo,
             roaster_repo,
             timeline_repo,
+            clock,
         }
     }
 
-    pub async fn create(&self, new: NewBag) -> Result<Bag, RepositoryError> {
-        let bag = self.bag_repo.insert(new).await?;
+    pub async fn create(
+        &self,
+        new: NewBag,
+        created_by: Option<UserId>,
+    ) -> Result<Bag, RepositoryError> {
+        let mut bag = self.bag_repo.insert(new).await?;
+        if let Some(user_id) = created_by {
+            match self.bag_repo.set_created_by(bag.id, user_id).await {
+                Ok(()) => bag.created_by = Some(user_id),
+                Err(err) => {
+                    warn!(error = %err, bag_id = %bag.id, "failed to attribute bag to its creator");
+                }
+            }
+        }
         self.record_timeline_event(&bag, "added").await;
         Ok(bag)
     }
@@ -43,13 +59,26 @@ impl BagService {
     /// repository, and records a "finished" timeline event.
     pub async fn finish(&self, id: BagId, mut update: UpdateBag) -> Result<Bag, RepositoryError> {
         if update.finished_at.is_none() {
-            update.finished_at = Some(chrono::Utc::now());
+            update.finished_at = Some(self.clock.now());
         }
         let bag = self.bag_repo.update(id, update).await?;
         self.record_timeline_event(&bag, "finished").await;
         Ok(bag)
     }
 
+    /// Reopen a finished bag. The "finished" timeline event goes away when
+    /// the caller invalidates the bag's timeline entries.
+    pub async fn reopen(&self, id: BagId) -> Result<Bag, RepositoryError> {
+        let bag = self.bag_repo.get(id).await?;
+        if !bag.closed {
+            return Err(RepositoryError::conflict_with(
+                ErrorCode::BagAlreadyOpen,
+                "bag is already open",
+            ));
+        }
+        self.bag_repo.reopen(id).await
+    }
+
     async fn record_timeline_event(&self, bag: &Bag, action: &str) {
         let roast = match self.roast_repo.get(bag.roast_id).await {
             Ok(r) => r,
@@ -2,31 +2,76 @@ use std::sync::Arc;
 
 use tracing::warn;
 
+use crate::domain::bags::BagFinishSuggestion;
 use crate::domain::brews::{BrewWithDetails, NewBrew};
 use crate::domain::errors::RepositoryError;
-use crate::domain::repositories::{BrewRepository, TimelineEventRepository};
+use crate::domain::ids::UserId;
+use crate::domain::notifications::NewNotification;
+use crate::domain::repositories::{
+    BagRepository, BrewRepository, SettingsRepository, TimelineEventRepository,
+};
+use crate::domain::settings::Settings;
+use crate::infrastructure::repositories::cached::ReadCache;
+
+use super::NotificationService;
+
+/// A newly logged brew, plus a prompt to finish its bag if the brew left
+/// only dust behind.
+#[derive(Debug, Clone)]
+pub struct CreatedBrew {
+    pub brew: BrewWithDetails,
+    pub finish_suggestion: Option<BagFinishSuggestion>,
+}
 
 #[derive(Clone)]
 pub struct BrewService {
     brew_repo: Arc<dyn BrewRepository>,
+    bag_repo: Arc<dyn BagRepository>,
     timeline_repo: Arc<dyn TimelineEventRepository>,
+    settings_repo: Arc<dyn SettingsRepository>,
+    notifications: NotificationService,
+    read_cache: ReadCache,
 }
 
 impl BrewService {
     pub fn new(
         brew_repo: Arc<dyn BrewRepository>,
+        bag_repo: Arc<dyn BagRepository>,
         timeline_repo: Arc<dyn TimelineEventRepository>,
+        settings_repo: Arc<dyn SettingsRepository>,
+        notifications: NotificationService,
+        read_cache: ReadCache,
     ) -> Self {
         Self {
             brew_repo,
+            bag_repo,
             timeline_repo,
+            settings_repo,
+            notifications,
+            read_cache,
         }
     }
 
-    /// Insert a brew, enrich it with related entity names, record a timeline
-    /// event, and return the enriched result.
-    pub async fn create(&self, new: NewBrew) -> Result<BrewWithDetails, RepositoryError> {
+    /// Insert a brew, attribute it to its creator, enrich it with related
+    /// entity names, record a timeline event, and return the enriched result.
+    /// The creator is notified if the brew leaves its bag running low, and
+    /// the result suggests finishing the bag if only dust is left.
+    pub async fn create(
+        &self,
+        new: NewBrew,
+        created_by: Option<UserId>,
+    ) -> Result<CreatedBrew, RepositoryError> {
         let brew = self.brew_repo.insert(new).await?;
+        if brew.filter_paper_id.is_some() {
+            // The insert took a paper from stock behind the cached gear
+            // repository's back.
+            self.read_cache.invalidate_gear();
+        }
+        if let Some(user_id) = created_by
+            && let Err(err) = self.brew_repo.set_created_by(brew.id, user_id).await
+        {
+            warn!(error = %err, brew_id = %brew.id, "failed to attribute brew to its creator");
+        }
         let enriched = self.brew_repo.get_with_details(brew.id).await?;
         if let Err(err) = self
             .timeline_repo
@@ -35,6 +80,46 @@ impl BrewService {
         {
             warn!(error = %err, brew_id = %brew.id, "failed to record brew timeline event");
         }
-        Ok(enriched)
+        let finish_suggestion = self.check_bag_stock(&enriched, created_by).await;
+        Ok(CreatedBrew {
+            brew: enriched,
+            finish_suggestion,
+        })
+    }
+
+    /// Apply the stock rules to the brew's bag now that the brew has been
+    /// taken out of it. Failures are logged rather than failing the brew.
+    async fn check_bag_stock(
+        &self,
+        brew: &BrewWithDetails,
+        created_by: Option<UserId>,
+    ) -> Option<BagFinishSuggestion> {
+        let settings = self.settings_repo.get().await.unwrap_or_else(|err| {
+            warn!(error = %err, "failed to load settings, using the default thresholds");
+            Settings::default()
+        });
+
+        let bag = match self.bag_repo.get(brew.brew.bag_id).await {
+            Ok(bag) => bag,
+            Err(err) => {
+                warn!(error = %err, bag_id = %brew.brew.bag_id, "failed to check bag stock");
+                return None;
+            }
+        };
+
+        if let Some(user_id) = created_by
+            && bag.just_ran_low(brew.brew.coffee_weight, settings.low_stock_threshold)
+        {
+            self.notifications
+                .notify(NewNotification::bag_running_low(
+                    user_id,
+                    bag.id,
+                    &brew.roast_name,
+                    bag.remaining,
+                ))
+                .await;
+        }
+
+        bag.finish_suggestion(settings.dust_threshold)
     }
 }
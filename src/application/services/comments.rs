@@ -0,0 +1,92 @@
+use std::sync::Arc;
+
+use crate::domain::comments::{Comment, CommentTarget, NewComment};
+use crate::domain::errors::RepositoryError;
+use crate::domain::ids::UserId;
+use crate::domain::notifications::NewNotification;
+use crate::domain::repositories::{BrewRepository, CommentRepository, RoastRepository};
+
+use super::NotificationService;
+
+/// What a thread is about, for notifying the people following it.
+struct ThreadSubject {
+    owner: Option<UserId>,
+    label: String,
+    link: String,
+}
+
+#[derive(Clone)]
+pub struct CommentService {
+    comment_repo: Arc<dyn CommentRepository>,
+    brew_repo: Arc<dyn BrewRepository>,
+    roast_repo: Arc<dyn RoastRepository>,
+    notifications: NotificationService,
+}
+
+impl CommentService {
+    pub fn new(
+        comment_repo: Arc<dyn CommentRepository>,
+        brew_repo: Arc<dyn BrewRepository>,
+        roast_repo: Arc<dyn RoastRepository>,
+        notifications: NotificationService,
+    ) -> Self {
+        Self {
+            comment_repo,
+            brew_repo,
+            roast_repo,
+            notifications,
+        }
+    }
+
+    /// Add a comment and notify whoever logged the brew or roast, plus anyone
+    /// else who has commented on it. The commenter is never notified.
+    pub async fn create(&self, new: NewComment) -> Result<Comment, RepositoryError> {
+        let subject = self.subject(new.target).await?;
+        let target = new.target;
+        let comment = self.comment_repo.insert(new).await?;
+
+        let mut recipients: Vec<UserId> = subject.owner.into_iter().collect();
+        if let Ok(thread) = self.comment_repo.list_for(target).await {
+            recipients.extend(thread.iter().map(|c| c.user_id));
+        }
+        recipients.sort_unstable_by_key(|id| id.into_inner());
+        recipients.dedup();
+
+        for user_id in recipients.into_iter().filter(|id| *id != comment.user_id) {
+            self.notifications
+                .notify(NewNotification::comment_received(
+                    user_id,
+                    &comment.author,
+                    &subject.label,
+                    subject.link.clone(),
+                ))
+                .await;
+        }
+
+        Ok(comment)
+    }
+
+    async fn subject(&self, target: CommentTarget) -> Result<ThreadSubject, RepositoryError> {
+        Ok(match target {
+            CommentTarget::Brew(id) => {
+                let brew = self.brew_repo.get_with_details(id).await?;
+                ThreadSubject {
+                    owner: brew.brew.created_by,
+                    label: format!("a {} brew", brew.roast_name),
+                    link: format!("/brews/{id}#comments"),
+                }
+            }
+            CommentTarget::Roast(id) => {
+                let roast = self.roast_repo.get_with_roaster(id).await?;
+                ThreadSubject {
+                    owner: roast.roast.created_by,
+                    link: format!(
+                        "/roasters/{}/roasts/{}#comments",
+                        roast.roaster_slug, roast.roast.slug
+                    ),
+                    label: roast.roast.name,
+                }
+            }
+        })
+    }
+}
@@ -4,6 +4,7 @@ use tracing::warn;
 
 use crate::domain::cups::{Cup, NewCup};
 use crate::domain::errors::RepositoryError;
+use crate::domain::ids::UserId;
 use crate::domain::repositories::{CupRepository, TimelineEventRepository};
 
 #[derive(Clone)]
@@ -23,8 +24,20 @@ impl CupService {
         }
     }
 
-    pub async fn create(&self, new: NewCup) -> Result<Cup, RepositoryError> {
-        let cup = self.cup_repo.insert(new).await?;
+    pub async fn create(
+        &self,
+        new: NewCup,
+        created_by: Option<UserId>,
+    ) -> Result<Cup, RepositoryError> {
+        let mut cup = self.cup_repo.insert(new).await?;
+        if let Some(user_id) = created_by {
+            match self.cup_repo.set_created_by(cup.id, user_id).await {
+                Ok(()) => cup.created_by = Some(user_id),
+                Err(err) => {
+                    warn!(error = %err, cup_id = %cup.id, "failed to attribute cup to its creator");
+                }
+            }
+        }
         match self.cup_repo.get_with_details(cup.id).await {
             Ok(enriched) => {
                 if let Err(err) = self
@@ -0,0 +1,56 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::mpsc;
+use tracing::{info, warn};
+
+use crate::infrastructure::image_purge::ImagePurgeService;
+
+/// Asks the background purge task to remove deleted images. Non-blocking
+/// and fire-and-forget, like [`StatsInvalidator`](super::StatsInvalidator).
+#[derive(Clone)]
+pub struct ImagePurger {
+    tx: mpsc::Sender<()>,
+}
+
+impl ImagePurger {
+    pub fn new(tx: mpsc::Sender<()>) -> Self {
+        Self { tx }
+    }
+
+    /// Signal that images were marked deleted, directly or by deleting the
+    /// entity that owned them.
+    pub fn schedule(&self) {
+        let _ = self.tx.try_send(());
+    }
+}
+
+/// Listens for purge signals, debounces so a burst of deletes is purged in
+/// one pass, and runs the purge. Runs as a long-lived background task —
+/// spawn with `tokio::spawn`.
+pub async fn image_purge_task(
+    mut rx: mpsc::Receiver<()>,
+    service: Arc<ImagePurgeService>,
+    debounce: Duration,
+) {
+    loop {
+        if rx.recv().await.is_none() {
+            break;
+        }
+
+        tokio::time::sleep(debounce).await;
+        while rx.try_recv().is_ok() {}
+
+        let run = service.run().await;
+        if let Some(error) = &run.error {
+            warn!(error, purged = run.purged, "image purge stopped early");
+        } else if run.purged > 0 {
+            info!(
+                duration_ms = run.duration_ms,
+                orphans_marked = run.orphans_marked,
+                purged = run.purged,
+                "deleted images purged"
+            );
+        }
+    }
+}
@@ -0,0 +1,345 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::domain::bags::UpdateBag;
+use crate::domain::errors::RepositoryError;
+use crate::domain::gear::{GearCategory, NewGear};
+use crate::domain::ids::{BagId, GearId, RoastId, RoasterId, UserId};
+use crate::domain::repositories::{
+    BrewRepository, GearRepository, RoastRepository, RoasterRepository,
+};
+use crate::domain::roasters::NewRoaster;
+use crate::infrastructure::beanconqueror::{BeanconquerorExport, PLACEHOLDER, make_and_model};
+
+use super::{BagService, BrewService, GearService, RoastService, RoasterService};
+
+/// What an import created, and what it couldn't bring across.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImportReport {
+    pub roasters: usize,
+    pub roasts: usize,
+    pub bags: usize,
+    pub gear: usize,
+    pub brews: usize,
+    /// Records left out, counted by reason.
+    #[serde(default)]
+    pub skipped: BTreeMap<String, usize>,
+    /// Required details the source left blank, filled with "Unknown".
+    #[serde(default)]
+    pub filled_in: BTreeMap<String, usize>,
+    /// Source fields and sections Brewlog has nowhere to put.
+    #[serde(default)]
+    pub unmapped: BTreeMap<String, usize>,
+}
+
+impl ImportReport {
+    fn skip(&mut self, reason: &str) {
+        *self.skipped.entry(reason.to_string()).or_default() += 1;
+    }
+}
+
+/// Brings other apps' histories into Brewlog through the same services the
+/// API uses, so imported records get timeline events like any other.
+/// Existing roasters, roasts and gear with matching names are reused.
+#[derive(Clone)]
+pub struct ImportService {
+    roasters: RoasterService,
+    roasts: RoastService,
+    bags: BagService,
+    gear: GearService,
+    brews: BrewService,
+    roaster_repo: Arc<dyn RoasterRepository>,
+    roast_repo: Arc<dyn RoastRepository>,
+    gear_repo: Arc<dyn GearRepository>,
+    brew_repo: Arc<dyn BrewRepository>,
+}
+
+/// Gear already in Brewlog or created by this import, by category, make and
+/// model (lowercased).
+type GearIndex = HashMap<(GearCategory, String, String), GearId>;
+
+impl ImportService {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        roasters: RoasterService,
+        roasts: RoastService,
+        bags: BagService,
+        gear: GearService,
+        brews: BrewService,
+        roaster_repo: Arc<dyn RoasterRepository>,
+        roast_repo: Arc<dyn RoastRepository>,
+        gear_repo: Arc<dyn GearRepository>,
+        brew_repo: Arc<dyn BrewRepository>,
+    ) -> Self {
+        Self {
+            roasters,
+            roasts,
+            bags,
+            gear,
+            brews,
+            roaster_repo,
+            roast_repo,
+            gear_repo,
+            brew_repo,
+        }
+    }
+
+    /// Import a Beanconqueror export. Each bean becomes a bag of a roast,
+    /// mills become grinders, preparations become brewers, and brews are
+    /// logged oldest first so bag stock runs down as it did. Roasters the
+    /// instance doesn't know yet are created in `roaster_country`.
+    pub async fn import_beanconqueror(
+        &self,
+        export: BeanconquerorExport,
+        roaster_country: &str,
+        created_by: Option<UserId>,
+    ) -> Result<ImportReport, RepositoryError> {
+        let mut report = ImportReport {
+            unmapped: export.unmapped.clone(),
+            ..ImportReport::default()
+        };
+
+        let (mut gear_index, equipment) = self
+            .import_equipment(&export, created_by, &mut report)
+            .await?;
+
+        let bags = self
+            .import_beans(&export, roaster_country, created_by, &mut report)
+            .await?;
+
+        let mut brews = export.brews;
+        brews.sort_by_key(|brew| brew.created_at);
+        let mut last_brewed: HashMap<BagId, DateTime<Utc>> = HashMap::new();
+        for brew in &brews {
+            let Some(&(bag_id, _)) = bags.get(&brew.bean) else {
+                report.skip("brew of a bean that wasn't imported");
+                continue;
+            };
+            if let Some(reason) = brew.missing() {
+                report.skip(reason);
+                continue;
+            }
+            // Brews logged without a grinder or method share an "Unknown" one.
+            let mut gear_for = async |uuid: &Option<String>, category| match uuid
+                .as_ref()
+                .and_then(|uuid| equipment.get(uuid))
+            {
+                Some(id) => Ok(*id),
+                None => {
+                    self.find_or_create_gear(
+                        &mut gear_index,
+                        PLACEHOLDER,
+                        category,
+                        created_by,
+                        &mut report,
+                    )
+                    .await
+                }
+            };
+            let grinder_id = gear_for(&brew.mill, GearCategory::Grinder).await?;
+            let brewer_id = gear_for(&brew.preparation, GearCategory::Brewer).await?;
+
+            let new_brew = match brew.new_brew(bag_id, grinder_id, brewer_id) {
+                Ok(new_brew) => new_brew,
+                Err(reason) => {
+                    report.skip(reason);
+                    continue;
+                }
+            };
+            // Low-stock notifications make no sense for history, so the
+            // brew is attributed after it's logged rather than through it.
+            let created = self.brews.create(new_brew, None).await?;
+            if let Some(user_id) = created_by
+                && let Err(err) = self
+                    .brew_repo
+                    .set_created_by(created.brew.brew.id, user_id)
+                    .await
+            {
+                warn!(error = %err, brew_id = %created.brew.brew.id, "failed to attribute imported brew");
+            }
+            if let Some(at) = brew.created_at {
+                last_brewed.insert(bag_id, at);
+            }
+            report.brews += 1;
+        }
+
+        for (bag_id, finished) in bags.into_values() {
+            if finished {
+                let update = UpdateBag {
+                    closed: Some(true),
+                    finished_at: last_brewed.get(&bag_id).copied(),
+                    ..UpdateBag::default()
+                };
+                self.bags.finish(bag_id, update).await?;
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Create gear for each mill and preparation not already known,
+    /// returning the gear index and each item's gear by Beanconqueror UUID.
+    async fn import_equipment(
+        &self,
+        export: &BeanconquerorExport,
+        created_by: Option<UserId>,
+        report: &mut ImportReport,
+    ) -> Result<(GearIndex, HashMap<String, GearId>), RepositoryError> {
+        let mut gear_index: GearIndex = self
+            .gear_repo
+            .list_all()
+            .await?
+            .into_iter()
+            .map(|gear| {
+                (
+                    (
+                        gear.category,
+                        gear.make.to_lowercase(),
+                        gear.model.to_lowercase(),
+                    ),
+                    gear.id,
+                )
+            })
+            .collect();
+        let mut equipment = HashMap::new();
+        for (items, category) in [
+            (&export.mills, GearCategory::Grinder),
+            (&export.preparations, GearCategory::Brewer),
+        ] {
+            for item in items {
+                let id = self
+                    .find_or_create_gear(&mut gear_index, &item.name, category, created_by, report)
+                    .await?;
+                equipment.insert(item.uuid.clone(), id);
+            }
+        }
+        Ok((gear_index, equipment))
+    }
+
+    /// Create a roaster, roast and bag for each bean, returning each bean's
+    /// bag and whether it was finished, by bean UUID.
+    async fn import_beans(
+        &self,
+        export: &BeanconquerorExport,
+        roaster_country: &str,
+        created_by: Option<UserId>,
+        report: &mut ImportReport,
+    ) -> Result<HashMap<String, (BagId, bool)>, RepositoryError> {
+        let mut roasters: HashMap<String, RoasterId> = self
+            .roaster_repo
+            .list_all()
+            .await?
+            .into_iter()
+            .map(|roaster| (roaster.name.to_lowercase(), roaster.id))
+            .collect();
+        let mut roasts: HashMap<(RoasterId, String), RoastId> = HashMap::new();
+        let mut bags = HashMap::new();
+
+        for bean in &export.beans {
+            let dose_total: f64 = export
+                .brews
+                .iter()
+                .filter(|brew| brew.bean == bean.uuid)
+                .filter_map(|brew| brew.dose)
+                .sum();
+
+            let roaster_name = bean.roaster.clone().unwrap_or_else(|| {
+                *report
+                    .filled_in
+                    .entry("roasters.name".to_string())
+                    .or_default() += 1;
+                PLACEHOLDER.to_string()
+            });
+            let roaster_id = if let Some(id) = roasters.get(&roaster_name.to_lowercase()) {
+                *id
+            } else {
+                let roaster = self
+                    .roasters
+                    .create(
+                        NewRoaster {
+                            name: roaster_name.clone(),
+                            country: roaster_country.to_string(),
+                            city: None,
+                            homepage: None,
+                            created_at: bean.created_at,
+                        }
+                        .normalize(),
+                        created_by,
+                    )
+                    .await?;
+                report.roasters += 1;
+                roasters.insert(roaster_name.to_lowercase(), roaster.id);
+                roaster.id
+            };
+
+            let new_roast = bean.new_roast(roaster_id, &mut report.filled_in);
+            let slug = new_roast.slug();
+            let roast_id = if let Some(id) = roasts.get(&(roaster_id, slug.clone())) {
+                *id
+            } else {
+                let id = match self.roast_repo.get_by_slug(roaster_id, &slug).await {
+                    Ok(existing) => existing.id,
+                    Err(RepositoryError::NotFound) => {
+                        report.roasts += 1;
+                        self.roasts.create(new_roast, created_by).await?.id
+                    }
+                    Err(err) => return Err(err),
+                };
+                roasts.insert((roaster_id, slug), id);
+                id
+            };
+
+            let Some(new_bag) = bean.new_bag(roast_id, dose_total) else {
+                report.skip("bean with no weight and no brews");
+                continue;
+            };
+            let bag = self.bags.create(new_bag, created_by).await?;
+            report.bags += 1;
+            bags.insert(bean.uuid.clone(), (bag.id, bean.finished));
+        }
+
+        Ok(bags)
+    }
+
+    async fn find_or_create_gear(
+        &self,
+        index: &mut GearIndex,
+        name: &str,
+        category: GearCategory,
+        created_by: Option<UserId>,
+        report: &mut ImportReport,
+    ) -> Result<GearId, RepositoryError> {
+        let (make, model) = make_and_model(name, category);
+        let key = (category, make.to_lowercase(), model.to_lowercase());
+        if let Some(id) = index.get(&key) {
+            return Ok(*id);
+        }
+
+        let gear = self
+            .gear
+            .create(
+                NewGear {
+                    category,
+                    make,
+                    model,
+                    water_temp: None,
+                    brew_method: None,
+                    uses_filter: None,
+                    temp_offset: None,
+                    water_hardness: None,
+                    stock_count: None,
+                    low_stock_threshold: None,
+                    created_at: None,
+                },
+                created_by,
+            )
+            .await?;
+        report.gear += 1;
+        index.insert(key, gear.id);
+        Ok(gear.id)
+    }
+}
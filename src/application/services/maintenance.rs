@@ -0,0 +1,39 @@
+use std::time::Duration;
+
+use tracing::{error, info, warn};
+
+use crate::infrastructure::maintenance::MaintenanceService;
+
+/// When database maintenance runs and how thorough it is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MaintenanceSchedule {
+    pub interval: Duration,
+    /// Also VACUUM, which rewrites the whole file and holds the write lock
+    /// while it does.
+    pub vacuum: bool,
+}
+
+/// Runs database maintenance every `schedule.interval`, logging each result.
+/// Runs as a long-lived background task — spawn with `tokio::spawn`.
+pub async fn maintenance_task(service: MaintenanceService, schedule: MaintenanceSchedule) {
+    loop {
+        tokio::time::sleep(schedule.interval).await;
+
+        match service.run(schedule.vacuum).await {
+            Ok(run) if run.error.is_some() => {
+                warn!(error = ?run.error, "database maintenance stopped early");
+            }
+            Ok(run) => info!(
+                duration_ms = run.duration_ms,
+                wal_frames = run.wal_frames,
+                checkpointed_frames = run.checkpointed_frames,
+                checkpoint_busy = run.checkpoint_busy,
+                vacuumed = run.vacuumed,
+                bytes_before = run.bytes_before,
+                bytes_after = run.bytes_after,
+                "database maintenance finished"
+            ),
+            Err(err) => error!(error = %err, "database maintenance failed"),
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::domain::aliases::Alias;
+use crate::domain::errors::RepositoryError;
+use crate::domain::ids::{RoastId, RoasterId};
+use crate::domain::matching::{
+    MAX_CANDIDATES, MIN_CONFIDENCE, Named, RoastMatch, ScanMatches, rank,
+};
+use crate::domain::repositories::{AliasRepository, RoastRepository, RoasterRepository};
+
+/// Ranks existing roasters and roasts against the names read off a bag, so
+/// a scan can reuse them rather than create near-duplicates.
+#[allow(clippy::struct_field_names)]
+#[derive(Clone)]
+pub struct MatchingService {
+    roaster_repo: Arc<dyn RoasterRepository>,
+    roast_repo: Arc<dyn RoastRepository>,
+    alias_repo: Arc<dyn AliasRepository>,
+}
+
+impl MatchingService {
+    pub fn new(
+        roaster_repo: Arc<dyn RoasterRepository>,
+        roast_repo: Arc<dyn RoastRepository>,
+        alias_repo: Arc<dyn AliasRepository>,
+    ) -> Self {
+        Self {
+            roaster_repo,
+            roast_repo,
+            alias_repo,
+        }
+    }
+
+    /// Candidates for `roaster_name`, and for `roast_name` among the roasts
+    /// of those roasters. A roast's confidence is scaled by its roaster's.
+    pub async fn match_scan(
+        &self,
+        roaster_name: &str,
+        roast_name: &str,
+    ) -> Result<ScanMatches, RepositoryError> {
+        if roaster_name.trim().is_empty() {
+            return Ok(ScanMatches::default());
+        }
+
+        let (roasters, aliases) =
+            tokio::try_join!(self.roaster_repo.list_all(), self.alias_repo.list_all())?;
+        let mut roaster_aliases: HashMap<RoasterId, Vec<String>> = HashMap::new();
+        let mut roast_aliases: HashMap<RoastId, Vec<String>> = HashMap::new();
+        for Alias {
+            roaster_id,
+            roast_id,
+            name,
+            ..
+        } in aliases
+        {
+            if let Some(id) = roaster_id {
+                roaster_aliases.entry(id).or_default().push(name);
+            } else if let Some(id) = roast_id {
+                roast_aliases.entry(id).or_default().push(name);
+            }
+        }
+
+        let roasters = rank(
+            roaster_name,
+            roasters.into_iter().map(|roaster| Named {
+                aliases: roaster_aliases.remove(&roaster.id).unwrap_or_default(),
+                id: roaster.id,
+                name: roaster.name,
+            }),
+        );
+
+        let mut roasts = Vec::new();
+        if !roast_name.trim().is_empty() {
+            for roaster in &roasters {
+                let candidates = self.roast_repo.list_by_roaster(roaster.id).await?;
+                let ranked = rank(
+                    roast_name,
+                    candidates.into_iter().map(|candidate| Named {
+                        aliases: roast_aliases
+                            .remove(&candidate.roast.id)
+                            .unwrap_or_default(),
+                        id: candidate.roast.id,
+                        name: candidate.roast.name,
+                    }),
+                );
+                roasts.extend(ranked.into_iter().map(|mut roast| {
+                    roast.confidence *= roaster.confidence;
+                    RoastMatch {
+                        roast,
+                        roaster_id: roaster.id,
+                        roaster_name: roaster.name.clone(),
+                    }
+                }));
+            }
+        }
+        roasts.retain(|candidate| candidate.roast.confidence >= MIN_CONFIDENCE);
+        roasts.sort_by(|a, b| b.roast.confidence.total_cmp(&a.roast.confidence));
+        roasts.truncate(MAX_CANDIDATES);
+
+        Ok(ScanMatches { roasters, roasts })
+    }
+}
@@ -1,13 +1,29 @@
+mod backfill;
 mod bags;
 mod brews;
+mod comments;
 mod cups;
+pub mod image_purge;
+mod import;
+pub mod maintenance;
+mod matching;
+mod notifications;
+mod quick_actions;
 mod roasts;
+mod slugs;
 pub mod stats;
 pub mod timeline_refresh;
 
+pub use backfill::{Backfill, BackfillReport, BackfillService, RecordRef};
 pub use bags::BagService;
-pub use brews::BrewService;
+pub use brews::{BrewService, CreatedBrew};
+pub use comments::CommentService;
 pub use cups::CupService;
+pub use image_purge::ImagePurger;
+pub use import::{ImportReport, ImportService};
+pub use matching::MatchingService;
+pub use notifications::NotificationService;
+pub use quick_actions::QuickActionService;
 pub use roasts::RoastService;
 pub use stats::StatsInvalidator;
 pub use timeline_refresh::TimelineInvalidator;
@@ -17,18 +33,26 @@ use std::sync::Arc;
 use tracing::warn;
 
 use crate::domain::errors::RepositoryError;
+use crate::domain::ids::UserId;
 use crate::domain::repositories::TimelineEventRepository;
 
 /// Generates a service struct with a `create` method that inserts via the
-/// repository and then records a timeline event (fire-and-forget).
+/// repository, attributes the entity to its creator, and then records a
+/// timeline event (fire-and-forget).
 ///
 /// Use this for entities whose `to_timeline_event()` method needs only `&self`
 /// (no related-entity lookups). For entities that need enrichment or
 /// cross-repo lookups, write the service by hand.
 ///
+/// Prefix the entity with `slugged` when its slug must be unique: a taken
+/// slug is then resolved under the configured
+/// [`SlugStrategy`](crate::domain::slugs::SlugStrategy), which needs the
+/// settings repository too.
+///
 /// # Example
 /// ```ignore
-/// define_simple_service!(RoasterService, RoasterRepository, Roaster, NewRoaster, "roaster");
+/// define_simple_service!(GearService, GearRepository, Gear, NewGear, "gear");
+/// define_simple_service!(slugged CafeService, CafeRepository, Cafe, NewCafe, "cafe");
 /// ```
 macro_rules! define_simple_service {
     ($service:ident, $repo_trait:path, $entity:ty, $new_entity:ty, $entity_name:literal) => {
@@ -52,8 +76,77 @@ macro_rules! define_simple_service {
             pub async fn create(
                 &self,
                 new: $new_entity,
+                created_by: Option<UserId>,
             ) -> Result<$entity, RepositoryError> {
                 let entity = self.repo.insert(new).await?;
+                Ok(self.record_created(entity, created_by).await)
+            }
+        }
+
+        define_simple_service!(@record_created $service, $entity, $entity_name);
+    };
+    (slugged $service:ident, $repo_trait:path, $entity:ty, $new_entity:ty, $entity_name:literal) => {
+        #[derive(Clone)]
+        pub struct $service {
+            repo: Arc<dyn $repo_trait>,
+            timeline_repo: Arc<dyn TimelineEventRepository>,
+            settings_repo: Arc<dyn SettingsRepository>,
+        }
+
+        impl $service {
+            pub fn new(
+                repo: Arc<dyn $repo_trait>,
+                timeline_repo: Arc<dyn TimelineEventRepository>,
+                settings_repo: Arc<dyn SettingsRepository>,
+            ) -> Self {
+                Self {
+                    repo,
+                    timeline_repo,
+                    settings_repo,
+                }
+            }
+
+            pub async fn create(
+                &self,
+                new: $new_entity,
+                created_by: Option<UserId>,
+            ) -> Result<$entity, RepositoryError> {
+                let new = new.normalize();
+                let base = new.slug();
+                let qualifiers = new.slug_qualifiers();
+                let entity = slugs::insert_with_free_slug(
+                    self.settings_repo.as_ref(),
+                    new,
+                    base,
+                    &qualifiers,
+                    |new, slug| self.repo.insert_with_slug(new, slug),
+                )
+                .await?;
+                Ok(self.record_created(entity, created_by).await)
+            }
+        }
+
+        define_simple_service!(@record_created $service, $entity, $entity_name);
+    };
+    (@record_created $service:ident, $entity:ty, $entity_name:literal) => {
+        impl $service {
+            /// Attribute the new entity to its creator and record its timeline
+            /// event; failures are logged, as the entity itself is saved.
+            async fn record_created(
+                &self,
+                mut entity: $entity,
+                created_by: Option<UserId>,
+            ) -> $entity {
+                if let Some(user_id) = created_by {
+                    match self.repo.set_created_by(entity.id, user_id).await {
+                        Ok(()) => entity.created_by = Some(user_id),
+                        Err(err) => warn!(
+                            error = %err,
+                            id = %entity.id,
+                            concat!("failed to attribute ", $entity_name, " to its creator"),
+                        ),
+                    }
+                }
                 if let Err(err) = self
                     .timeline_repo
                     .insert(entity.to_timeline_event())
@@ -65,23 +158,30 @@ macro_rules! define_simple_service {
                         concat!("failed to record ", $entity_name, " timeline event"),
                     );
                 }
-                Ok(entity)
+                entity
             }
         }
     };
 }
 
 use crate::domain::cafes::{Cafe, NewCafe};
+use crate::domain::events::{Event, NewEvent};
 use crate::domain::gear::{Gear, NewGear};
-use crate::domain::repositories::{CafeRepository, GearRepository, RoasterRepository};
+use crate::domain::repositories::{
+    CafeRepository, EventRepository, GearRepository, RoasterRepository, SettingsRepository,
+    TripRepository,
+};
 use crate::domain::roasters::{NewRoaster, Roaster};
+use crate::domain::trips::{NewTrip, Trip};
 
 define_simple_service!(
-    RoasterService,
+    slugged RoasterService,
     RoasterRepository,
     Roaster,
     NewRoaster,
     "roaster"
 );
-define_simple_service!(CafeService, CafeRepository, Cafe, NewCafe, "cafe");
+define_simple_service!(slugged CafeService, CafeRepository, Cafe, NewCafe, "cafe");
 define_simple_service!(GearService, GearRepository, Gear, NewGear, "gear");
+define_simple_service!(EventService, EventRepository, Event, NewEvent, "event");
+define_simple_service!(TripService, TripRepository, Trip, NewTrip, "trip");
@@ -0,0 +1,27 @@
+use std::sync::Arc;
+
+use tracing::warn;
+
+use crate::domain::notifications::NewNotification;
+use crate::domain::repositories::NotificationRepository;
+
+/// Records in-app notifications on behalf of other services. Delivery is
+/// best-effort: a failure is logged and never fails the action that caused it.
+#[derive(Clone)]
+pub struct NotificationService {
+    repo: Arc<dyn NotificationRepository>,
+}
+
+impl NotificationService {
+    pub fn new(repo: Arc<dyn NotificationRepository>) -> Self {
+        Self { repo }
+    }
+
+    pub async fn notify(&self, notification: NewNotification) {
+        let kind = notification.kind.as_str();
+        let user_id = notification.user_id;
+        if let Err(err) = self.repo.insert(notification).await {
+            warn!(error = %err, %user_id, kind, "failed to record notification");
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use crate::domain::bags::{BagFilter, BagSortKey};
+use crate::domain::brews::{BrewFilter, BrewSortKey};
+use crate::domain::clock::Clock;
+use crate::domain::cups::{CupFilter, CupSortKey};
+use crate::domain::errors::RepositoryError;
+use crate::domain::listing::{ListRequest, PageSize, SortDirection};
+use crate::domain::quick_actions::{QuickAction, suggest_quick_actions};
+use crate::domain::repositories::{BagRepository, BrewRepository, CupRepository};
+
+/// How many recent brews and cups the suggestions are drawn from.
+const HISTORY_SIZE: u32 = 50;
+
+/// Proposes home page shortcuts from recent brews and check-ins.
+#[derive(Clone)]
+pub struct QuickActionService {
+    brew_repo: Arc<dyn BrewRepository>,
+    cup_repo: Arc<dyn CupRepository>,
+    bag_repo: Arc<dyn BagRepository>,
+    clock: Arc<dyn Clock>,
+}
+
+impl QuickActionService {
+    pub fn new(
+        brew_repo: Arc<dyn BrewRepository>,
+        cup_repo: Arc<dyn CupRepository>,
+        bag_repo: Arc<dyn BagRepository>,
+        clock: Arc<dyn Clock>,
+    ) -> Self {
+        Self {
+            brew_repo,
+            cup_repo,
+            bag_repo,
+            clock,
+        }
+    }
+
+    pub async fn suggest(&self, limit: usize) -> Result<Vec<QuickAction>, RepositoryError> {
+        let brews_req = ListRequest::new(
+            1,
+            PageSize::limited(HISTORY_SIZE),
+            BrewSortKey::CreatedAt,
+            SortDirection::Desc,
+        );
+        let cups_req = ListRequest::new(
+            1,
+            PageSize::limited(HISTORY_SIZE),
+            CupSortKey::CreatedAt,
+            SortDirection::Desc,
+        );
+        let open_bags_req =
+            ListRequest::new(1, PageSize::All, BagSortKey::UpdatedAt, SortDirection::Desc);
+
+        let (brews, cups, open_bags) = tokio::try_join!(
+            self.brew_repo.list(BrewFilter::all(), &brews_req, None),
+            self.cup_repo.list(CupFilter::all(), &cups_req, None),
+            self.bag_repo.list(BagFilter::open(), &open_bags_req, None),
+        )?;
+
+        let open_bag_ids: HashSet<_> = open_bags.items.iter().map(|bag| bag.bag.id).collect();
+        Ok(suggest_quick_actions(
+            self.clock.now(),
+            &brews.items,
+            &cups.items,
+            &open_bag_ids,
+            limit,
+        ))
+    }
+}
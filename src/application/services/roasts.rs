@@ -2,8 +2,12 @@ use std::sync::Arc;
 
 use tracing::warn;
 
+use super::slugs::insert_with_free_slug;
 use crate::domain::errors::RepositoryError;
-use crate::domain::repositories::{RoastRepository, RoasterRepository, TimelineEventRepository};
+use crate::domain::ids::UserId;
+use crate::domain::repositories::{
+    RoastRepository, RoasterRepository, SettingsRepository, TimelineEventRepository,
+};
 use crate::domain::roasts::{NewRoast, Roast, roast_timeline_event};
 
 #[allow(clippy::struct_field_names)]
@@ -12,6 +16,7 @@ pub struct RoastService {
     roast_repo: Arc<dyn RoastRepository>,
     roaster_repo: Arc<dyn RoasterRepository>,
     timeline_repo: Arc<dyn TimelineEventRepository>,
+    settings_repo: Arc<dyn SettingsRepository>,
 }
 
 impl RoastService {
@@ -19,16 +24,47 @@ impl RoastService {
         roast_repo: Arc<dyn RoastRepository>,
         roaster_repo: Arc<dyn RoasterRepository>,
         timeline_repo: Arc<dyn TimelineEventRepository>,
+        settings_repo: Arc<dyn SettingsRepository>,
     ) -> Self {
         Self {
             roast_repo,
             roaster_repo,
             timeline_repo,
+            settings_repo,
         }
     }
 
-    pub async fn create(&self, new: NewRoast) -> Result<Roast, RepositoryError> {
-        let roast = self.roast_repo.insert(new).await?;
+    pub async fn create(
+        &self,
+        new: NewRoast,
+        created_by: Option<UserId>,
+    ) -> Result<Roast, RepositoryError> {
+        // The roaster is only needed if the strategy appends it; an unknown
+        // one fails the insert anyway.
+        let roaster_name = self
+            .roaster_repo
+            .get(new.roaster_id)
+            .await
+            .map(|roaster| roaster.name)
+            .unwrap_or_default();
+        let base = new.slug();
+        let qualifiers = new.slug_qualifiers(&roaster_name);
+        let mut roast = insert_with_free_slug(
+            self.settings_repo.as_ref(),
+            new,
+            base,
+            &qualifiers,
+            |new, slug| self.roast_repo.insert_with_slug(new, slug),
+        )
+        .await?;
+        if let Some(user_id) = created_by {
+            match self.roast_repo.set_created_by(roast.id, user_id).await {
+                Ok(()) => roast.created_by = Some(user_id),
+                Err(err) => {
+                    warn!(error = %err, roast_id = %roast.id, "failed to attribute roast to its creator");
+                }
+            }
+        }
         match self.roaster_repo.get(roast.roaster_id).await {
             Ok(roaster) => {
                 if let Err(err) = self
@@ -0,0 +1,47 @@
+use std::future::Future;
+
+use tracing::info;
+
+use crate::domain::repositories::SettingsRepository;
+use crate::domain::slugs::SlugQualifiers;
+use crate::domain::{ErrorCode, RepositoryError};
+
+fn is_duplicate_slug(err: &RepositoryError) -> bool {
+    matches!(err, RepositoryError::Conflict(ErrorCode::DuplicateSlug, _))
+}
+
+/// Insert `new` under `base`, and when that slug is taken, under each slug
+/// the configured [`SlugStrategy`](crate::domain::slugs::SlugStrategy) offers
+/// in turn. The unique index decides what's taken, so racing inserts can't
+/// share a slug.
+pub(crate) async fn insert_with_free_slug<N, T, F, Fut>(
+    settings_repo: &dyn SettingsRepository,
+    new: N,
+    base: String,
+    qualifiers: &SlugQualifiers,
+    insert: F,
+) -> Result<T, RepositoryError>
+where
+    N: Clone,
+    F: Fn(N, String) -> Fut,
+    Fut: Future<Output = Result<T, RepositoryError>>,
+{
+    let conflict = match insert(new.clone(), base.clone()).await {
+        Err(err) if is_duplicate_slug(&err) => err,
+        result => return result,
+    };
+
+    let strategy = settings_repo.get().await?.slug_strategy;
+    for slug in strategy.candidates(&base, qualifiers) {
+        match insert(new.clone(), slug.clone()).await {
+            Err(err) if is_duplicate_slug(&err) => {}
+            result => {
+                if result.is_ok() {
+                    info!(%base, %slug, strategy = strategy.as_str(), "slug taken, used another");
+                }
+                return result;
+            }
+        }
+    }
+    Err(conflict)
+}
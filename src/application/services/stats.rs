@@ -1,12 +1,14 @@
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
+use chrono::{DateTime, Datelike, Utc};
 use tokio::sync::mpsc;
 use tracing::{error, info};
 
+use crate::domain::clock::Clock;
 use crate::domain::country_stats::GeoStats;
 use crate::domain::repositories::StatsRepository;
-use crate::domain::stats::CachedStats;
+use crate::domain::stats::{CachedStats, StatsPeriod};
 
 /// Sends invalidation signals to the background stats recomputer.
 /// Non-blocking and fire-and-forget — safe to call from any handler.
@@ -31,6 +33,7 @@ impl StatsInvalidator {
 pub async fn stats_recomputation_task(
     mut rx: mpsc::Receiver<()>,
     stats_repo: Arc<dyn StatsRepository>,
+    clock: Arc<dyn Clock>,
     debounce: Duration,
 ) {
     loop {
@@ -42,7 +45,11 @@ pub async fn stats_recomputation_task(
         tokio::time::sleep(debounce).await;
         while rx.try_recv().is_ok() {}
 
-        match compute_all_stats(&*stats_repo).await {
+        if let Err(err) = stats_repo.refresh_roast_ratings().await {
+            error!(error = %err, "failed to refresh roast ratings");
+        }
+
+        match compute_all_stats(&*stats_repo, clock.now()).await {
             Ok(cached) => {
                 if let Err(err) = stats_repo.store_cached(&cached).await {
                     error!(error = %err, "failed to store stats cache");
@@ -53,10 +60,11 @@ pub async fn stats_recomputation_task(
     }
 }
 
-/// Runs all stats queries and assembles a complete `CachedStats` snapshot.
-/// Logs the total computation time on success.
+/// Runs all stats queries and assembles a complete `CachedStats` snapshot
+/// stamped with `computed_at`. Logs the total computation time on success.
 pub async fn compute_all_stats(
     repo: &dyn StatsRepository,
+    computed_at: DateTime<Utc>,
 ) -> Result<CachedStats, crate::domain::RepositoryError> {
     let start = Instant::now();
 
@@ -69,6 +77,12 @@ pub async fn compute_all_stats(
         cup_counts,
         cafe_counts,
         entity_counts,
+        tasting,
+        habits,
+        roaster_leaderboard,
+        last_month,
+        last_year,
+        spend,
     ) = tokio::join!(
         repo.roast_summary(),
         repo.consumption_summary(),
@@ -78,6 +92,12 @@ pub async fn compute_all_stats(
         repo.cup_country_counts(),
         repo.cafe_country_counts(),
         repo.entity_counts(),
+        repo.tasting_summary(computed_at.year()),
+        repo.brewing_habits(),
+        repo.roaster_leaderboard(),
+        repo.period_summary(StatsPeriod::Month, computed_at.date_naive()),
+        repo.period_summary(StatsPeriod::Year, computed_at.date_naive()),
+        repo.spend_summary(),
     );
 
     let cached = CachedStats {
@@ -88,8 +108,14 @@ pub async fn compute_all_stats(
         geo_roasts: GeoStats::from_counts(roast_counts?),
         geo_cups: GeoStats::from_counts(cup_counts?),
         geo_cafes: GeoStats::from_counts(cafe_counts?),
-        computed_at: chrono::Utc::now().to_rfc3339(),
+        computed_at: computed_at.to_rfc3339(),
         entity_counts: entity_counts?,
+        tasting: tasting?,
+        habits: habits?,
+        roaster_leaderboard: roaster_leaderboard?,
+        last_month: last_month?,
+        last_year: last_year?,
+        spend: spend?,
     };
 
     info!(duration_ms = start.elapsed().as_millis(), "stats computed");
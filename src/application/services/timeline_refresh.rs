@@ -1,4 +1,4 @@
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashSet};
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -7,12 +7,19 @@ use tracing::{error, info, warn};
 
 use crate::domain::bags::bag_timeline_event;
 use crate::domain::entity_type::EntityType;
-use crate::domain::ids::{BagId, BrewId, CafeId, CupId, GearId, RoastId, RoasterId};
+use crate::domain::events::EventFilter;
+use crate::domain::ids::{
+    BagId, BrewId, CafeId, CupId, EventId, GearId, RoastId, RoasterId, TripId,
+};
+use crate::domain::listing::{ListRequest, PageSize, SortDirection, SortKey};
 use crate::domain::repositories::{
-    BagRepository, BrewRepository, CafeRepository, CupRepository, GearRepository, RoastRepository,
-    RoasterRepository, TimelineEventRepository,
+    BagRepository, BrewRepository, CafeRepository, CupRepository, EventRepository, GearRepository,
+    RoastRepository, RoasterRepository, TimelineEventRepository, TripRepository,
 };
 use crate::domain::roasts::roast_timeline_event;
+use crate::domain::timeline::{NewTimelineEvent, TimelineFilter, TimelineMonth, TimelineSortKey};
+use crate::infrastructure::backup::DryRunReport;
+use crate::infrastructure::overview::TableCount;
 
 /// Invalidation signal sent by HTTP handlers to the background rebuild task.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -22,6 +29,8 @@ pub enum TimelineInvalidation {
         entity_type: EntityType,
         entity_id: i64,
     },
+    /// Rebuild the events that occurred in one month.
+    Month(TimelineMonth),
     /// Delete all timeline events and rebuild from scratch.
     Full,
 }
@@ -46,6 +55,14 @@ impl TimelineInvalidator {
         });
     }
 
+    /// Signal that the events in `months` need rebuilding, such as after
+    /// history has been backfilled into them.
+    pub fn rebuild_months(&self, months: impl IntoIterator<Item = TimelineMonth>) {
+        for month in months {
+            let _ = self.tx.try_send(TimelineInvalidation::Month(month));
+        }
+    }
+
     /// Signal a full rebuild of all timeline events.
     pub fn rebuild_all(&self) {
         let _ = self.tx.try_send(TimelineInvalidation::Full);
@@ -62,6 +79,8 @@ pub struct TimelineRebuilder {
     pub cup_repo: Arc<dyn CupRepository>,
     pub gear_repo: Arc<dyn GearRepository>,
     pub cafe_repo: Arc<dyn CafeRepository>,
+    pub event_repo: Arc<dyn EventRepository>,
+    pub trip_repo: Arc<dyn TripRepository>,
 }
 
 /// Listens for invalidation signals, debounces, and rebuilds affected timeline events.
@@ -80,18 +99,15 @@ pub async fn timeline_rebuild_task(
         tokio::time::sleep(debounce).await;
 
         let mut dirty = HashSet::new();
-        let mut full_rebuild = matches!(first, TimelineInvalidation::Full);
-        if let TimelineInvalidation::Entity {
-            entity_type,
-            entity_id,
-        } = first
-        {
-            dirty.insert((entity_type, entity_id));
-        }
-
-        while let Ok(signal) = rx.try_recv() {
-            match signal {
+        let mut months = BTreeSet::new();
+        let mut full_rebuild = false;
+        let mut signal = Some(first);
+        while let Some(next) = signal.take() {
+            match next {
                 TimelineInvalidation::Full => full_rebuild = true,
+                TimelineInvalidation::Month(month) => {
+                    months.insert(month);
+                }
                 TimelineInvalidation::Entity {
                     entity_type,
                     entity_id,
@@ -99,6 +115,7 @@ pub async fn timeline_rebuild_task(
                     dirty.insert((entity_type, entity_id));
                 }
             }
+            signal = rx.try_recv().ok();
         }
 
         if full_rebuild {
@@ -106,6 +123,12 @@ pub async fn timeline_rebuild_task(
                 error!(error = %err, "timeline full rebuild failed");
             }
         } else {
+            if !months.is_empty()
+                && let Err(err) = rebuild_months(&rebuilder, &months).await
+            {
+                error!(error = %err, "timeline month rebuild failed");
+            }
+
             // Expand cascade targets before refreshing
             let mut all_targets = HashSet::new();
             for (entity_type, entity_id) in &dirty {
@@ -114,13 +137,29 @@ pub async fn timeline_rebuild_task(
             }
 
             for (entity_type, entity_id) in &all_targets {
-                if let Err(err) = refresh_entity_event(&rebuilder, *entity_type, *entity_id).await {
-                    warn!(
+                match refresh_entity_event(&rebuilder, *entity_type, *entity_id).await {
+                    Ok(()) => {}
+                    // Deleted before the refresh got to it, so its events go too.
+                    Err(crate::domain::RepositoryError::NotFound) => {
+                        if let Err(err) = rebuilder
+                            .timeline_repo
+                            .delete_by_entity(*entity_type, *entity_id)
+                            .await
+                        {
+                            warn!(
+                                error = %err,
+                                entity_type = entity_type.as_str(),
+                                entity_id,
+                                "failed to delete timeline events for missing entity"
+                            );
+                        }
+                    }
+                    Err(err) => warn!(
                         error = %err,
                         entity_type = entity_type.as_str(),
                         entity_id,
                         "failed to refresh timeline event"
-                    );
+                    ),
                 }
             }
 
@@ -197,7 +236,11 @@ async fn collect_cascade_targets(
                 }
             }
         }
-        EntityType::Brew | EntityType::Cup => {
+        EntityType::Brew
+        | EntityType::Cup
+        | EntityType::Event
+        | EntityType::Trip
+        | EntityType::User => {
             // Leaf entities — no downstream cascade
         }
     }
@@ -340,6 +383,16 @@ async fn refresh_entity_event(
             let cafe = rebuilder.cafe_repo.get(CafeId::new(entity_id)).await?;
             cafe.to_timeline_event()
         }
+        EntityType::Event => {
+            let event = rebuilder.event_repo.get(EventId::new(entity_id)).await?;
+            event.to_timeline_event()
+        }
+        EntityType::Trip => {
+            let trip = rebuilder.trip_repo.get(TripId::new(entity_id)).await?;
+            trip.to_timeline_event()
+        }
+        // Users have no timeline events.
+        EntityType::User => return Ok(()),
     };
 
     rebuilder
@@ -355,15 +408,66 @@ pub async fn rebuild_all(
     let start = std::time::Instant::now();
 
     rebuilder.timeline_repo.delete_all().await?;
+    write_events(rebuilder, RebuildScope::All).await?;
+
+    info!(
+        duration_ms = start.elapsed().as_millis(),
+        "timeline events rebuilt"
+    );
+    Ok(())
+}
+
+/// Rebuild only the events that occurred in `months`, as after backfilling
+/// history, leaving the rest of the timeline alone.
+pub async fn rebuild_months(
+    rebuilder: &TimelineRebuilder,
+    months: &BTreeSet<TimelineMonth>,
+) -> Result<(), crate::domain::RepositoryError> {
+    let start = std::time::Instant::now();
+
+    for month in months {
+        rebuilder.timeline_repo.delete_in_month(*month).await?;
+    }
+    write_events(rebuilder, RebuildScope::Months(months)).await?;
+
+    info!(
+        months = months.len(),
+        duration_ms = start.elapsed().as_millis(),
+        "timeline months rebuilt"
+    );
+    Ok(())
+}
+
+/// Which events a rebuild writes back.
+#[derive(Debug, Clone, Copy)]
+enum RebuildScope<'a> {
+    All,
+    Months(&'a BTreeSet<TimelineMonth>),
+}
 
+/// Insert `event` unless it falls outside the rebuild's scope.
+async fn insert_in_scope(
+    rebuilder: &TimelineRebuilder,
+    scope: RebuildScope<'_>,
+    event: NewTimelineEvent,
+) -> Result<(), crate::domain::RepositoryError> {
+    if let RebuildScope::Months(months) = scope
+        && !months.contains(&TimelineMonth::containing(event.occurred_at))
+    {
+        return Ok(());
+    }
+    rebuilder.timeline_repo.insert(event).await.map(|_| ())
+}
+
+/// Write an event for every entity in `scope`, from current entity data.
+async fn write_events(
+    rebuilder: &TimelineRebuilder,
+    scope: RebuildScope<'_>,
+) -> Result<(), crate::domain::RepositoryError> {
     // Roasters
     let roasters = rebuilder.roaster_repo.list_all().await?;
     for roaster in &roasters {
-        if let Err(err) = rebuilder
-            .timeline_repo
-            .insert(roaster.to_timeline_event())
-            .await
-        {
+        if let Err(err) = insert_in_scope(rebuilder, scope, roaster.to_timeline_event()).await {
             warn!(error = %err, id = %roaster.id, "failed to rebuild roaster timeline event");
         }
     }
@@ -371,11 +475,7 @@ pub async fn rebuild_all(
     // Cafes
     let cafes = rebuilder.cafe_repo.list_all().await?;
     for cafe in &cafes {
-        if let Err(err) = rebuilder
-            .timeline_repo
-            .insert(cafe.to_timeline_event())
-            .await
-        {
+        if let Err(err) = insert_in_scope(rebuilder, scope, cafe.to_timeline_event()).await {
             warn!(error = %err, id = %cafe.id, "failed to rebuild cafe timeline event");
         }
     }
@@ -383,15 +483,27 @@ pub async fn rebuild_all(
     // Gear
     let gear_list = rebuilder.gear_repo.list_all().await?;
     for gear in &gear_list {
-        if let Err(err) = rebuilder
-            .timeline_repo
-            .insert(gear.to_timeline_event())
-            .await
-        {
+        if let Err(err) = insert_in_scope(rebuilder, scope, gear.to_timeline_event()).await {
             warn!(error = %err, id = %gear.id, "failed to rebuild gear timeline event");
         }
     }
 
+    // Events
+    let events = rebuilder.event_repo.list(EventFilter::default()).await?;
+    for event in &events {
+        if let Err(err) = insert_in_scope(rebuilder, scope, event.to_timeline_event()).await {
+            warn!(error = %err, id = %event.id, "failed to rebuild event timeline event");
+        }
+    }
+
+    // Trips
+    let trips = rebuilder.trip_repo.list().await?;
+    for trip in &trips {
+        if let Err(err) = insert_in_scope(rebuilder, scope, trip.to_timeline_event()).await {
+            warn!(error = %err, id = %trip.id, "failed to rebuild trip timeline event");
+        }
+    }
+
     // Roasts (need roaster for each)
     let roasts = rebuilder.roast_repo.list_all().await?;
     for rwr in &roasts {
@@ -402,25 +514,19 @@ pub async fn rebuild_all(
                 continue;
             }
         };
-        if let Err(err) = rebuilder
-            .timeline_repo
-            .insert(roast_timeline_event(&rwr.roast, &roaster))
-            .await
+        if let Err(err) =
+            insert_in_scope(rebuilder, scope, roast_timeline_event(&rwr.roast, &roaster)).await
         {
             warn!(error = %err, id = %rwr.roast.id, "failed to rebuild roast timeline event");
         }
     }
 
-    rebuild_bag_events(rebuilder).await?;
+    rebuild_bag_events(rebuilder, scope).await?;
 
     // Brews (get_with_details for enrichment)
     let brews = rebuilder.brew_repo.list_all().await?;
     for bwd in &brews {
-        if let Err(err) = rebuilder
-            .timeline_repo
-            .insert(bwd.to_timeline_event())
-            .await
-        {
+        if let Err(err) = insert_in_scope(rebuilder, scope, bwd.to_timeline_event()).await {
             warn!(error = %err, id = %bwd.brew.id, "failed to rebuild brew timeline event");
         }
     }
@@ -428,25 +534,64 @@ pub async fn rebuild_all(
     // Cups (list_all returns CupWithDetails)
     let cups = rebuilder.cup_repo.list_all().await?;
     for cwd in &cups {
-        if let Err(err) = rebuilder
-            .timeline_repo
-            .insert(cwd.to_timeline_event())
-            .await
-        {
+        if let Err(err) = insert_in_scope(rebuilder, scope, cwd.to_timeline_event()).await {
             warn!(error = %err, id = %cwd.cup.id, "failed to rebuild cup timeline event");
         }
     }
 
-    info!(
-        duration_ms = start.elapsed().as_millis(),
-        "timeline events rebuilt"
-    );
     Ok(())
 }
 
+/// What [`rebuild_all`] would do: the events it would delete, and how many
+/// it would write back, one per entity plus one for each finished bag.
+pub async fn preview_rebuild_all(
+    rebuilder: &TimelineRebuilder,
+) -> Result<DryRunReport, crate::domain::RepositoryError> {
+    let existing = rebuilder
+        .timeline_repo
+        .list(
+            TimelineFilter::all(),
+            &ListRequest::new(
+                1,
+                PageSize::limited(1),
+                TimelineSortKey::default(),
+                SortDirection::Desc,
+            ),
+        )
+        .await?
+        .total;
+
+    let bags = rebuilder.bag_repo.list_all().await?;
+    let finished = bags.iter().filter(|bwr| bwr.bag.closed).count();
+    let written = rebuilder.roaster_repo.list_all().await?.len()
+        + rebuilder.cafe_repo.list_all().await?.len()
+        + rebuilder.gear_repo.list_all().await?.len()
+        + rebuilder
+            .event_repo
+            .list(EventFilter::default())
+            .await?
+            .len()
+        + rebuilder.trip_repo.list().await?.len()
+        + rebuilder.roast_repo.list_all().await?.len()
+        + bags.len()
+        + finished
+        + rebuilder.brew_repo.list_all().await?.len()
+        + rebuilder.cup_repo.list_all().await?.len();
+
+    let count = |rows: u64| TableCount {
+        table: "timeline_events".to_string(),
+        rows: i64::try_from(rows).unwrap_or(i64::MAX),
+    };
+    Ok(DryRunReport {
+        deleted: vec![count(existing)],
+        written: vec![count(written as u64)],
+    })
+}
+
 /// Rebuild timeline events for all bags (need roast + roaster lookups for each).
 async fn rebuild_bag_events(
     rebuilder: &TimelineRebuilder,
+    scope: RebuildScope<'_>,
 ) -> Result<(), crate::domain::RepositoryError> {
     let bags = rebuilder.bag_repo.list_all().await?;
     for bwr in &bags {
@@ -464,16 +609,18 @@ async fn rebuild_bag_events(
                 continue;
             }
         };
-        if let Err(err) = rebuilder
-            .timeline_repo
-            .insert(bag_timeline_event(&bwr.bag, "added", &roast, &roaster))
-            .await
+        if let Err(err) = insert_in_scope(
+            rebuilder,
+            scope,
+            bag_timeline_event(&bwr.bag, "added", &roast, &roaster),
+        )
+        .await
         {
             warn!(error = %err, id = %bwr.bag.id, "failed to rebuild bag 'added' timeline event");
         }
         if bwr.bag.closed {
             let finished_event = bag_timeline_event(&bwr.bag, "finished", &roast, &roaster);
-            if let Err(err) = rebuilder.timeline_repo.insert(finished_event).await {
+            if let Err(err) = insert_in_scope(rebuilder, scope, finished_event).await {
                 warn!(error = %err, id = %bwr.bag.id, "failed to rebuild bag 'finished' timeline event");
             }
         }
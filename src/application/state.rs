@@ -2,33 +2,69 @@ use std::sync::Arc;
 
 use webauthn_rs::prelude::*;
 
+use crate::application::services::timeline_refresh::TimelineRebuilder;
 use crate::application::services::{
-    BagService, BrewService, CafeService, CupService, GearService, RoastService, RoasterService,
-    StatsInvalidator, TimelineInvalidator,
+    BackfillService, BagService, BrewService, CafeService, CommentService, CupService,
+    EventService, GearService, ImagePurger, ImportService, MatchingService, NotificationService,
+    QuickActionService, RoastService, RoasterService, StatsInvalidator, TimelineInvalidator,
+    TripService,
 };
+use crate::domain::clock::Clock;
 use crate::domain::repositories::{
-    AiUsageRepository, BagRepository, BrewRepository, CafeRepository, CupRepository,
-    GearRepository, ImageRepository, PasskeyCredentialRepository, RegistrationTokenRepository,
-    RoastRepository, RoasterRepository, SessionRepository, StatsRepository,
-    TimelineEventRepository, TokenRepository, UserRepository,
+    AiUsageRepository, AliasRepository, BagRepository, BrewRepository, BrewShareRepository,
+    CafeRepository, CommentRepository, CupRepository, CustomProcessRepository,
+    CustomQuickNoteRepository, EventRepository, GearRepository, GreenCoffeeRepository,
+    GrinderCalibrationRepository, ImageRepository, ListPreferenceRepository,
+    NotificationRepository, PasskeyCredentialRepository, RegistrationTokenRepository,
+    RoastBatchRepository, RoastRepository, RoasterRepository, SavedSearchRepository,
+    SessionRepository, SettingsRepository, StatsRepository, TimelineEventRepository,
+    TokenRepository, TripRepository, UserRepository,
 };
+use crate::infrastructure::ai::{AiGuard, OpenRouter};
+use crate::infrastructure::auth::TokenHasher;
 use crate::infrastructure::backup::BackupService;
+use crate::infrastructure::bulk::BulkService;
 use crate::infrastructure::database::Database;
+use crate::infrastructure::image_processing::ImageSettings;
+use crate::infrastructure::image_purge::ImagePurgeService;
+use crate::infrastructure::integrity::IntegrityService;
+use crate::infrastructure::overview::OverviewService;
 use crate::infrastructure::repositories::ai_usage::SqlAiUsageRepository;
+use crate::infrastructure::repositories::aliases::SqlAliasRepository;
 use crate::infrastructure::repositories::bags::SqlBagRepository;
+use crate::infrastructure::repositories::brew_shares::SqlBrewShareRepository;
 use crate::infrastructure::repositories::brews::SqlBrewRepository;
+use crate::infrastructure::repositories::cached::{
+    CachedGearRepository, CachedImageRepository, CachedRoasterRepository, ReadCache,
+};
 use crate::infrastructure::repositories::cafes::SqlCafeRepository;
+use crate::infrastructure::repositories::comments::SqlCommentRepository;
 use crate::infrastructure::repositories::cups::SqlCupRepository;
+use crate::infrastructure::repositories::events::SqlEventRepository;
 use crate::infrastructure::repositories::gear::SqlGearRepository;
+use crate::infrastructure::repositories::green_coffees::SqlGreenCoffeeRepository;
+use crate::infrastructure::repositories::grinder_calibrations::SqlGrinderCalibrationRepository;
 use crate::infrastructure::repositories::images::SqlImageRepository;
+use crate::infrastructure::repositories::list_preferences::SqlListPreferenceRepository;
+use crate::infrastructure::repositories::memory::{
+    MemoryBagRepository, MemoryGearRepository, MemoryRoastRepository, MemoryRoasterRepository,
+    MemoryStore,
+};
+use crate::infrastructure::repositories::notifications::SqlNotificationRepository;
 use crate::infrastructure::repositories::passkey_credentials::SqlPasskeyCredentialRepository;
+use crate::infrastructure::repositories::processes::SqlCustomProcessRepository;
+use crate::infrastructure::repositories::quick_notes::SqlCustomQuickNoteRepository;
 use crate::infrastructure::repositories::registration_tokens::SqlRegistrationTokenRepository;
+use crate::infrastructure::repositories::roast_batches::SqlRoastBatchRepository;
 use crate::infrastructure::repositories::roasters::SqlRoasterRepository;
 use crate::infrastructure::repositories::roasts::SqlRoastRepository;
+use crate::infrastructure::repositories::saved_searches::SqlSavedSearchRepository;
 use crate::infrastructure::repositories::sessions::SqlSessionRepository;
+use crate::infrastructure::repositories::settings::SqlSettingsRepository;
 use crate::infrastructure::repositories::stats::SqlStatsRepository;
 use crate::infrastructure::repositories::timeline_events::SqlTimelineEventRepository;
 use crate::infrastructure::repositories::tokens::SqlTokenRepository;
+use crate::infrastructure::repositories::trips::SqlTripRepository;
 use crate::infrastructure::repositories::users::SqlUserRepository;
 use crate::infrastructure::webauthn::ChallengeStore;
 
@@ -38,6 +74,7 @@ use crate::infrastructure::webauthn::ChallengeStore;
 pub struct AppStateConfig {
     pub webauthn: Arc<Webauthn>,
     pub insecure_cookies: bool,
+    pub token_hasher: TokenHasher,
     pub foursquare_url: String,
     pub foursquare_api_key: String,
     pub openrouter_url: String,
@@ -45,15 +82,48 @@ pub struct AppStateConfig {
     pub openrouter_model: String,
     pub stats_invalidator: StatsInvalidator,
     pub timeline_invalidator: TimelineInvalidator,
+    pub image_purger: ImagePurger,
+    pub clock: Arc<dyn Clock>,
+    pub repositories: RepositoryBackend,
+    pub image_settings: ImageSettings,
+}
+
+/// Where the catalogue repositories (roasters, roasts, bags and gear) keep
+/// their rows. Everything else always uses the database.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RepositoryBackend {
+    #[default]
+    Sql,
+    /// Plain in-memory collections, for tests that don't need `SQLite`. Brews,
+    /// cups and the pool-backed services can't see these rows.
+    Memory,
 }
 
+/// Roaster, roast, bag and gear repositories, which share a backend.
+type CatalogueRepositories = (
+    Arc<dyn RoasterRepository>,
+    Arc<dyn RoastRepository>,
+    Arc<dyn BagRepository>,
+    Arc<dyn GearRepository>,
+);
+
 #[derive(Clone)]
 pub struct AppState {
     pub roaster_repo: Arc<dyn RoasterRepository>,
     pub roast_repo: Arc<dyn RoastRepository>,
     pub bag_repo: Arc<dyn BagRepository>,
     pub gear_repo: Arc<dyn GearRepository>,
+    pub grinder_calibration_repo: Arc<dyn GrinderCalibrationRepository>,
+    pub quick_note_repo: Arc<dyn CustomQuickNoteRepository>,
+    pub process_repo: Arc<dyn CustomProcessRepository>,
+    pub green_coffee_repo: Arc<dyn GreenCoffeeRepository>,
+    pub roast_batch_repo: Arc<dyn RoastBatchRepository>,
+    pub event_repo: Arc<dyn EventRepository>,
+    pub trip_repo: Arc<dyn TripRepository>,
     pub brew_repo: Arc<dyn BrewRepository>,
+    pub brew_share_repo: Arc<dyn BrewShareRepository>,
+    pub comment_repo: Arc<dyn CommentRepository>,
+    pub alias_repo: Arc<dyn AliasRepository>,
     pub cafe_repo: Arc<dyn CafeRepository>,
     pub cup_repo: Arc<dyn CupRepository>,
     pub timeline_repo: Arc<dyn TimelineEventRepository>,
@@ -65,6 +135,10 @@ pub struct AppState {
     pub ai_usage_repo: Arc<dyn AiUsageRepository>,
     pub image_repo: Arc<dyn ImageRepository>,
     pub stats_repo: Arc<dyn StatsRepository>,
+    pub list_preference_repo: Arc<dyn ListPreferenceRepository>,
+    pub saved_search_repo: Arc<dyn SavedSearchRepository>,
+    pub notification_repo: Arc<dyn NotificationRepository>,
+    pub settings_repo: Arc<dyn SettingsRepository>,
     pub webauthn: Arc<Webauthn>,
     pub challenge_store: Arc<ChallengeStore>,
     pub http_client: reqwest::Client,
@@ -73,32 +147,89 @@ pub struct AppState {
     pub openrouter_url: String,
     pub openrouter_api_key: String,
     pub openrouter_model: String,
+    /// Retries and circuit breaking for `OpenRouter`; replace it to change the
+    /// policy.
+    pub ai_guard: Arc<AiGuard>,
     pub backup_service: Arc<BackupService>,
+    pub integrity_service: Arc<IntegrityService>,
+    pub bulk_service: Arc<BulkService>,
+    pub overview_service: Arc<OverviewService>,
+    pub image_purge_service: Arc<ImagePurgeService>,
     pub roaster_service: RoasterService,
     pub roast_service: RoastService,
     pub bag_service: BagService,
     pub brew_service: BrewService,
     pub gear_service: GearService,
     pub cafe_service: CafeService,
+    pub event_service: EventService,
+    pub trip_service: TripService,
     pub cup_service: CupService,
+    pub comment_service: CommentService,
+    pub quick_action_service: QuickActionService,
+    pub matching_service: MatchingService,
+    pub import_service: ImportService,
+    pub backfill_service: BackfillService,
     pub insecure_cookies: bool,
+    pub token_hasher: TokenHasher,
     pub stats_invalidator: StatsInvalidator,
     pub timeline_invalidator: TimelineInvalidator,
+    pub image_purger: ImagePurger,
+    pub read_cache: ReadCache,
     pub image_semaphore: Arc<tokio::sync::Semaphore>,
+    pub image_settings: ImageSettings,
+    pub clock: Arc<dyn Clock>,
 }
 
 impl AppState {
     /// Build the full application state from a database connection and config.
     /// Creates all repositories and services internally.
+    #[allow(clippy::too_many_lines)]
     pub fn from_database(database: &Database, config: AppStateConfig) -> Self {
         let pool = database.clone_pool();
 
-        let roaster_repo: Arc<dyn RoasterRepository> =
-            Arc::new(SqlRoasterRepository::new(pool.clone()));
-        let roast_repo: Arc<dyn RoastRepository> = Arc::new(SqlRoastRepository::new(pool.clone()));
-        let bag_repo: Arc<dyn BagRepository> = Arc::new(SqlBagRepository::new(pool.clone()));
-        let gear_repo: Arc<dyn GearRepository> = Arc::new(SqlGearRepository::new(pool.clone()));
+        let read_cache = ReadCache::default();
+        let (roaster_repo, roast_repo, bag_repo, gear_repo): CatalogueRepositories =
+            match config.repositories {
+                RepositoryBackend::Sql => (
+                    Arc::new(CachedRoasterRepository::new(
+                        Arc::new(SqlRoasterRepository::new(pool.clone())),
+                        read_cache.clone(),
+                    )),
+                    Arc::new(SqlRoastRepository::new(pool.clone())),
+                    Arc::new(SqlBagRepository::new(pool.clone())),
+                    Arc::new(CachedGearRepository::new(
+                        Arc::new(SqlGearRepository::new(pool.clone())),
+                        read_cache.clone(),
+                    )),
+                ),
+                RepositoryBackend::Memory => {
+                    let store = MemoryStore::new();
+                    (
+                        Arc::new(MemoryRoasterRepository::new(store.clone())),
+                        Arc::new(MemoryRoastRepository::new(store.clone())),
+                        Arc::new(MemoryBagRepository::new(store.clone())),
+                        Arc::new(MemoryGearRepository::new(store)),
+                    )
+                }
+            };
+        let grinder_calibration_repo: Arc<dyn GrinderCalibrationRepository> =
+            Arc::new(SqlGrinderCalibrationRepository::new(pool.clone()));
+        let quick_note_repo: Arc<dyn CustomQuickNoteRepository> =
+            Arc::new(SqlCustomQuickNoteRepository::new(pool.clone()));
+        let process_repo: Arc<dyn CustomProcessRepository> =
+            Arc::new(SqlCustomProcessRepository::new(pool.clone()));
+        let green_coffee_repo: Arc<dyn GreenCoffeeRepository> =
+            Arc::new(SqlGreenCoffeeRepository::new(pool.clone()));
+        let roast_batch_repo: Arc<dyn RoastBatchRepository> =
+            Arc::new(SqlRoastBatchRepository::new(pool.clone()));
+        let event_repo: Arc<dyn EventRepository> = Arc::new(SqlEventRepository::new(pool.clone()));
+        let trip_repo: Arc<dyn TripRepository> = Arc::new(SqlTripRepository::new(pool.clone()));
         let brew_repo: Arc<dyn BrewRepository> = Arc::new(SqlBrewRepository::new(pool.clone()));
+        let brew_share_repo: Arc<dyn BrewShareRepository> =
+            Arc::new(SqlBrewShareRepository::new(pool.clone()));
+        let comment_repo: Arc<dyn CommentRepository> =
+            Arc::new(SqlCommentRepository::new(pool.clone()));
+        let alias_repo: Arc<dyn AliasRepository> = Arc::new(SqlAliasRepository::new(pool.clone()));
         let cafe_repo: Arc<dyn CafeRepository> = Arc::new(SqlCafeRepository::new(pool.clone()));
         let cup_repo: Arc<dyn CupRepository> = Arc::new(SqlCupRepository::new(pool.clone()));
         let timeline_repo: Arc<dyn TimelineEventRepository> =
@@ -113,35 +244,113 @@ impl AppState {
             Arc::new(SqlRegistrationTokenRepository::new(pool.clone()));
         let ai_usage_repo: Arc<dyn AiUsageRepository> =
             Arc::new(SqlAiUsageRepository::new(pool.clone()));
-        let image_repo: Arc<dyn ImageRepository> = Arc::new(SqlImageRepository::new(pool.clone()));
+        let image_repo: Arc<dyn ImageRepository> = Arc::new(CachedImageRepository::new(
+            Arc::new(SqlImageRepository::new(pool.clone())),
+            read_cache.clone(),
+        ));
         let stats_repo: Arc<dyn StatsRepository> = Arc::new(SqlStatsRepository::new(pool.clone()));
+        let list_preference_repo: Arc<dyn ListPreferenceRepository> =
+            Arc::new(SqlListPreferenceRepository::new(pool.clone()));
+        let saved_search_repo: Arc<dyn SavedSearchRepository> =
+            Arc::new(SqlSavedSearchRepository::new(pool.clone()));
+        let notification_repo: Arc<dyn NotificationRepository> =
+            Arc::new(SqlNotificationRepository::new(pool.clone()));
+        let settings_repo: Arc<dyn SettingsRepository> =
+            Arc::new(SqlSettingsRepository::new(pool.clone()));
 
-        let backup_service = Arc::new(BackupService::new(pool));
+        let backup_service = Arc::new(BackupService::new(pool.clone()));
+        let integrity_service = Arc::new(IntegrityService::new(pool.clone()));
+        let bulk_service = Arc::new(BulkService::new(pool.clone()));
+        let image_purge_service = Arc::new(ImagePurgeService::new(
+            pool.clone(),
+            Arc::clone(&config.clock),
+        ));
+        let overview_service = Arc::new(OverviewService::new(pool, Arc::clone(&config.clock)));
 
-        let roaster_service =
-            RoasterService::new(Arc::clone(&roaster_repo), Arc::clone(&timeline_repo));
+        let roaster_service = RoasterService::new(
+            Arc::clone(&roaster_repo),
+            Arc::clone(&timeline_repo),
+            Arc::clone(&settings_repo),
+        );
         let roast_service = RoastService::new(
             Arc::clone(&roast_repo),
             Arc::clone(&roaster_repo),
             Arc::clone(&timeline_repo),
+            Arc::clone(&settings_repo),
         );
         let bag_service = BagService::new(
             Arc::clone(&bag_repo),
             Arc::clone(&roast_repo),
             Arc::clone(&roaster_repo),
             Arc::clone(&timeline_repo),
+            Arc::clone(&config.clock),
+        );
+        let notification_service = NotificationService::new(Arc::clone(&notification_repo));
+        let brew_service = BrewService::new(
+            Arc::clone(&brew_repo),
+            Arc::clone(&bag_repo),
+            Arc::clone(&timeline_repo),
+            Arc::clone(&settings_repo),
+            notification_service.clone(),
+            read_cache.clone(),
         );
-        let brew_service = BrewService::new(Arc::clone(&brew_repo), Arc::clone(&timeline_repo));
         let gear_service = GearService::new(Arc::clone(&gear_repo), Arc::clone(&timeline_repo));
-        let cafe_service = CafeService::new(Arc::clone(&cafe_repo), Arc::clone(&timeline_repo));
+        let cafe_service = CafeService::new(
+            Arc::clone(&cafe_repo),
+            Arc::clone(&timeline_repo),
+            Arc::clone(&settings_repo),
+        );
+        let event_service = EventService::new(Arc::clone(&event_repo), Arc::clone(&timeline_repo));
+        let trip_service = TripService::new(Arc::clone(&trip_repo), Arc::clone(&timeline_repo));
         let cup_service = CupService::new(Arc::clone(&cup_repo), Arc::clone(&timeline_repo));
+        let comment_service = CommentService::new(
+            Arc::clone(&comment_repo),
+            Arc::clone(&brew_repo),
+            Arc::clone(&roast_repo),
+            notification_service,
+        );
+        let quick_action_service = QuickActionService::new(
+            Arc::clone(&brew_repo),
+            Arc::clone(&cup_repo),
+            Arc::clone(&bag_repo),
+            Arc::clone(&config.clock),
+        );
+
+        let matching_service = MatchingService::new(
+            Arc::clone(&roaster_repo),
+            Arc::clone(&roast_repo),
+            Arc::clone(&alias_repo),
+        );
+
+        let import_service = ImportService::new(
+            roaster_service.clone(),
+            roast_service.clone(),
+            bag_service.clone(),
+            gear_service.clone(),
+            brew_service.clone(),
+            Arc::clone(&roaster_repo),
+            Arc::clone(&roast_repo),
+            Arc::clone(&gear_repo),
+            Arc::clone(&brew_repo),
+        );
+        let backfill_service = BackfillService::new(brew_service.clone(), cup_service.clone());
 
         Self {
             roaster_repo,
             roast_repo,
             bag_repo,
             gear_repo,
+            grinder_calibration_repo,
+            quick_note_repo,
+            process_repo,
+            green_coffee_repo,
+            roast_batch_repo,
+            event_repo,
+            trip_repo,
             brew_repo,
+            brew_share_repo,
+            comment_repo,
+            alias_repo,
             cafe_repo,
             cup_repo,
             timeline_repo,
@@ -153,8 +362,12 @@ impl AppState {
             ai_usage_repo,
             image_repo,
             stats_repo,
+            list_preference_repo,
+            saved_search_repo,
+            notification_repo,
+            settings_repo,
             webauthn: config.webauthn,
-            challenge_store: Arc::new(ChallengeStore::new()),
+            challenge_store: Arc::new(ChallengeStore::new(Arc::clone(&config.clock))),
             #[allow(clippy::expect_used)]
             http_client: reqwest::ClientBuilder::new()
                 .timeout(std::time::Duration::from_secs(30))
@@ -165,18 +378,62 @@ impl AppState {
             openrouter_url: config.openrouter_url,
             openrouter_api_key: config.openrouter_api_key,
             openrouter_model: config.openrouter_model,
+            ai_guard: Arc::new(AiGuard::default()),
             backup_service,
+            integrity_service,
+            bulk_service,
+            overview_service,
+            image_purge_service,
             roaster_service,
             roast_service,
             bag_service,
             brew_service,
             gear_service,
             cafe_service,
+            event_service,
+            trip_service,
             cup_service,
+            comment_service,
+            quick_action_service,
+            matching_service,
+            import_service,
+            backfill_service,
             insecure_cookies: config.insecure_cookies,
+            token_hasher: config.token_hasher,
             stats_invalidator: config.stats_invalidator,
             timeline_invalidator: config.timeline_invalidator,
+            image_purger: config.image_purger,
+            read_cache,
             image_semaphore: Arc::new(tokio::sync::Semaphore::new(4)),
+            image_settings: config.image_settings,
+            clock: config.clock,
+        }
+    }
+
+    /// The `OpenRouter` connection AI extractions go through.
+    pub fn openrouter(&self) -> OpenRouter<'_> {
+        OpenRouter {
+            client: &self.http_client,
+            guard: &self.ai_guard,
+            url: &self.openrouter_url,
+            api_key: &self.openrouter_api_key,
+            model: &self.openrouter_model,
+        }
+    }
+
+    /// The repositories a timeline rebuild reads from and writes to.
+    pub fn timeline_rebuilder(&self) -> TimelineRebuilder {
+        TimelineRebuilder {
+            timeline_repo: Arc::clone(&self.timeline_repo),
+            roaster_repo: Arc::clone(&self.roaster_repo),
+            roast_repo: Arc::clone(&self.roast_repo),
+            bag_repo: Arc::clone(&self.bag_repo),
+            brew_repo: Arc::clone(&self.brew_repo),
+            cup_repo: Arc::clone(&self.cup_repo),
+            gear_repo: Arc::clone(&self.gear_repo),
+            cafe_repo: Arc::clone(&self.cafe_repo),
+            event_repo: Arc::clone(&self.event_repo),
+            trip_repo: Arc::clone(&self.trip_repo),
         }
     }
 }
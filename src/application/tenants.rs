@@ -0,0 +1,523 @@
+//! Multi-tenant hosting: one server, a separate `SQLite` database per tenant.
+//!
+//! Each request is routed to its tenant by subdomain (`alice.brew.example`)
+//! or path prefix (`/t/alice/...`). A tenant runs as a complete Brewlog of
+//! its own, started the first time it is asked for, so users, sessions and
+//! tokens never cross between tenants. Requests for the bare host go to the
+//! tenant admin API, guarded by a token from the server config.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use anyhow::Context;
+use axum::extract::{FromRequestParts, Path, Request, State};
+use axum::http::request::Parts;
+use axum::http::{HeaderValue, StatusCode, Uri, header};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+use tower::ServiceExt;
+use tracing::{info, warn};
+
+use crate::application::errors::{ApiError, AppError, ErrorResponse};
+use crate::application::routes::app_router;
+use crate::application::server::{
+    Instance, ServerConfig, build_webauthn, registration_url, start_instance,
+};
+use crate::application::state::AppState;
+use crate::domain::ErrorCode;
+use crate::domain::tenants::TenantName;
+use crate::infrastructure::auth::hash_token;
+use crate::infrastructure::database::Database;
+
+/// Path-routed requests start `/t/<tenant>`.
+const PATH_PREFIX: &str = "/t/";
+
+/// Remembers a browser's tenant under path routing, since the web UI links
+/// from the site root.
+const TENANT_COOKIE: &str = "brewlog_tenant";
+
+/// How a request names its tenant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TenantRouting {
+    /// `<tenant>.<rp_id>`, each tenant with its own `WebAuthn` origin.
+    Subdomain,
+    /// `/t/<tenant>/...` on the one host.
+    Path,
+}
+
+impl TenantRouting {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Subdomain => "subdomain",
+            Self::Path => "path",
+        }
+    }
+}
+
+impl fmt::Display for TenantRouting {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for TenantRouting {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "subdomain" => Ok(Self::Subdomain),
+            "path" => Ok(Self::Path),
+            other => Err(format!(
+                "unknown tenant routing '{other}' (expected 'subdomain' or 'path')"
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TenantsConfig {
+    /// Where tenant databases live, one `<tenant>.db` each.
+    pub dir: PathBuf,
+    pub routing: TenantRouting,
+    /// Bearer token for the tenant admin API.
+    pub admin_token: String,
+}
+
+struct RunningTenant {
+    state: AppState,
+    router: Router,
+    database: Database,
+    instance_tasks: Vec<tokio::task::JoinHandle<()>>,
+}
+
+/// The tenants on disk, and those started so far.
+pub struct TenantRegistry {
+    config: ServerConfig,
+    rp_origin: url::Url,
+    tenants: TenantsConfig,
+    running: Mutex<HashMap<TenantName, RunningTenant>>,
+}
+
+impl TenantRegistry {
+    pub fn new(config: ServerConfig) -> anyhow::Result<Self> {
+        let tenants = config
+            .tenants
+            .clone()
+            .context("multi-tenant mode needs a tenants directory")?;
+        let rp_origin =
+            url::Url::parse(&config.rp_origin).context("invalid BREWLOG_RP_ORIGIN URL")?;
+        std::fs::create_dir_all(&tenants.dir).with_context(|| {
+            format!(
+                "failed to create tenants directory {}",
+                tenants.dir.display()
+            )
+        })?;
+        Ok(Self {
+            config,
+            rp_origin,
+            tenants,
+            running: Mutex::new(HashMap::new()),
+        })
+    }
+
+    fn database_path(&self, name: &TenantName) -> PathBuf {
+        self.tenants.dir.join(format!("{name}.db"))
+    }
+
+    pub fn exists(&self, name: &TenantName) -> bool {
+        self.database_path(name).is_file()
+    }
+
+    /// Every tenant with a database, by name.
+    pub fn list(&self) -> anyhow::Result<Vec<TenantName>> {
+        let entries = std::fs::read_dir(&self.tenants.dir).with_context(|| {
+            format!(
+                "failed to read tenants directory {}",
+                self.tenants.dir.display()
+            )
+        })?;
+        let mut names: Vec<TenantName> = entries
+            .filter_map(Result::ok)
+            .filter_map(|entry| {
+                let file_name = entry.file_name();
+                file_name.to_str()?.strip_suffix(".db")?.parse().ok()
+            })
+            .collect();
+        names.sort();
+        Ok(names)
+    }
+
+    /// Where the tenant is served, without a trailing slash.
+    pub fn origin(&self, name: &TenantName) -> String {
+        match self.tenants.routing {
+            TenantRouting::Subdomain => {
+                let mut origin = self.rp_origin.clone();
+                let host = format!("{name}.{}", origin.host_str().unwrap_or_default());
+                if origin.set_host(Some(&host)).is_err() {
+                    warn!(%host, "tenant host is not a valid URL host");
+                }
+                origin.origin().ascii_serialization()
+            }
+            TenantRouting::Path => format!(
+                "{}{PATH_PREFIX}{name}",
+                self.config.rp_origin.trim_end_matches('/')
+            ),
+        }
+    }
+
+    fn rp_id(&self, name: &TenantName) -> String {
+        match self.tenants.routing {
+            TenantRouting::Subdomain => format!("{name}.{}", self.config.rp_id),
+            TenantRouting::Path => self.config.rp_id.clone(),
+        }
+    }
+
+    /// Open the tenant's database, creating it if need be, and start it.
+    async fn start(&self, name: &TenantName) -> anyhow::Result<RunningTenant> {
+        let path = self.database_path(name);
+        let database = Database::connect(&format!("sqlite://{}", path.display()))
+            .await
+            .with_context(|| format!("failed to open database for tenant {name}"))?;
+        let webauthn = build_webauthn(&self.rp_id(name), &self.origin(name))?;
+        let Instance { state, tasks } = start_instance(&database, webauthn, &self.config).await;
+        info!(tenant = %name, "tenant started");
+        Ok(RunningTenant {
+            router: app_router(state.clone()),
+            state,
+            database,
+            instance_tasks: tasks,
+        })
+    }
+
+    /// The tenant from `running`, starting it first if need be. `None` if
+    /// there is no such tenant.
+    async fn started<'a>(
+        &self,
+        running: &'a mut HashMap<TenantName, RunningTenant>,
+        name: &TenantName,
+    ) -> anyhow::Result<Option<&'a RunningTenant>> {
+        if !running.contains_key(name) {
+            if !self.exists(name) {
+                return Ok(None);
+            }
+            let tenant = self.start(name).await?;
+            running.insert(name.clone(), tenant);
+        }
+        Ok(running.get(name))
+    }
+
+    async fn router(&self, name: &TenantName) -> anyhow::Result<Option<Router>> {
+        let mut running = self.running.lock().await;
+        let tenant = self.started(&mut running, name).await?;
+        Ok(tenant.map(|tenant| tenant.router.clone()))
+    }
+
+    /// Create a tenant's database and return a link for its first user to
+    /// register with.
+    pub async fn create(&self, name: &TenantName) -> Result<String, AppError> {
+        let mut running = self.running.lock().await;
+        if self.exists(name) {
+            return Err(AppError::Conflict(
+                ErrorCode::Conflict,
+                format!("tenant '{name}' already exists"),
+            ));
+        }
+        let tenant = self.start(name).await.map_err(AppError::unexpected)?;
+        let link = registration_url(&tenant.state, &self.origin(name))
+            .await
+            .map_err(AppError::unexpected)?;
+        running.insert(name.clone(), tenant);
+        info!(tenant = %name, "tenant created");
+        link.ok_or_else(|| AppError::unexpected("new tenant already has users"))
+    }
+
+    /// A fresh first-user registration link, for when the first has expired.
+    pub async fn registration(&self, name: &TenantName) -> Result<String, AppError> {
+        let mut running = self.running.lock().await;
+        let tenant = self
+            .started(&mut running, name)
+            .await
+            .map_err(AppError::unexpected)?
+            .ok_or(AppError::NotFound)?;
+        registration_url(&tenant.state, &self.origin(name))
+            .await
+            .map_err(AppError::unexpected)?
+            .ok_or_else(|| {
+                AppError::Conflict(
+                    ErrorCode::Conflict,
+                    format!("tenant '{name}' already has users; invite more from its admin page"),
+                )
+            })
+    }
+
+    /// Stop the tenant and delete its database.
+    pub async fn delete(&self, name: &TenantName) -> Result<(), AppError> {
+        let mut running = self.running.lock().await;
+        if !self.exists(name) {
+            return Err(AppError::NotFound);
+        }
+        if let Some(tenant) = running.remove(name) {
+            for task in tenant.instance_tasks {
+                task.abort();
+            }
+            tenant.database.clone_pool().close().await;
+        }
+
+        let path = self.database_path(name);
+        std::fs::remove_file(&path).map_err(|err| {
+            AppError::unexpected(format!("failed to remove {}: {err}", path.display()))
+        })?;
+        for suffix in ["-wal", "-shm"] {
+            let mut sidecar = path.clone().into_os_string();
+            sidecar.push(suffix);
+            let _ = std::fs::remove_file(sidecar);
+        }
+        info!(tenant = %name, "tenant deleted");
+        Ok(())
+    }
+
+    /// Which tenant a request is for.
+    fn resolve(&self, request: &Request) -> Target {
+        match self.tenants.routing {
+            TenantRouting::Subdomain => {
+                let Some(host) = request
+                    .headers()
+                    .get(header::HOST)
+                    .and_then(|host| host.to_str().ok())
+                    .or_else(|| request.uri().host())
+                else {
+                    return Target::Unknown;
+                };
+                let host = host.rsplit_once(':').map_or(host, |(host, _)| host);
+                if host == self.config.rp_id {
+                    return Target::Admin;
+                }
+                host.strip_suffix(&self.config.rp_id)
+                    .and_then(|label| label.strip_suffix('.'))
+                    .and_then(|label| label.parse().ok())
+                    .map_or(Target::Unknown, Target::Host)
+            }
+            TenantRouting::Path => {
+                if let Some(rest) = request.uri().path().strip_prefix(PATH_PREFIX) {
+                    let (name, path) = rest.find('/').map_or((rest, ""), |at| rest.split_at(at));
+                    return name.parse().map_or(Target::Unknown, |name| {
+                        Target::Prefix(name, path.to_string())
+                    });
+                }
+                tenant_cookie(request).map_or(Target::Admin, Target::Cookie)
+            }
+        }
+    }
+}
+
+enum Target {
+    /// The bare host, where the tenant admin API lives.
+    Admin,
+    /// Named by subdomain.
+    Host(TenantName),
+    /// Named by path prefix, with the rest of the path.
+    Prefix(TenantName, String),
+    /// Named by a cookie left by an earlier prefixed request.
+    Cookie(TenantName),
+    Unknown,
+}
+
+fn tenant_cookie(request: &Request) -> Option<TenantName> {
+    request
+        .headers()
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(';'))
+        .filter_map(|pair| pair.trim().split_once('='))
+        .find(|(key, _)| *key == TENANT_COOKIE)
+        .and_then(|(_, value)| value.parse().ok())
+}
+
+/// Route each request to its tenant's Brewlog, or to the tenant admin API.
+pub fn tenant_router(registry: Arc<TenantRegistry>) -> Router {
+    let admin = admin_router(Arc::clone(&registry));
+    Router::new().fallback(move |request: Request| {
+        let registry = Arc::clone(&registry);
+        let admin = admin.clone();
+        async move { dispatch(&registry, admin, request).await }
+    })
+}
+
+async fn dispatch(registry: &TenantRegistry, admin: Router, mut request: Request) -> Response {
+    let (name, prefixed) = match registry.resolve(&request) {
+        Target::Admin => return admin.oneshot(request).await.into_response(),
+        Target::Unknown => return no_such_tenant(),
+        Target::Host(name) | Target::Cookie(name) => (name, false),
+        Target::Prefix(name, path) => {
+            let path_and_query = match request.uri().query() {
+                Some(query) => format!("{}?{query}", if path.is_empty() { "/" } else { &path }),
+                None if path.is_empty() => "/".to_string(),
+                None => path,
+            };
+            match Uri::try_from(path_and_query) {
+                Ok(uri) => *request.uri_mut() = uri,
+                Err(_) => return StatusCode::BAD_REQUEST.into_response(),
+            }
+            (name, true)
+        }
+    };
+
+    let router = match registry.router(&name).await {
+        Ok(Some(router)) => router,
+        Ok(None) => return no_such_tenant(),
+        Err(err) => {
+            tracing::error!(error = %err, tenant = %name, "failed to start tenant");
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    let mut response = router.oneshot(request).await.into_response();
+    if prefixed {
+        let secure = if registry.config.insecure_cookies {
+            ""
+        } else {
+            "; Secure"
+        };
+        if let Ok(cookie) = HeaderValue::from_str(&format!(
+            "{TENANT_COOKIE}={name}; Path=/; HttpOnly; SameSite=Lax{secure}"
+        )) {
+            response.headers_mut().append(header::SET_COOKIE, cookie);
+        }
+    }
+    response
+}
+
+fn no_such_tenant() -> Response {
+    (
+        StatusCode::NOT_FOUND,
+        Json(ErrorResponse::new(ErrorCode::NotFound, "no such tenant")),
+    )
+        .into_response()
+}
+
+fn admin_router(registry: Arc<TenantRegistry>) -> Router {
+    Router::new()
+        .route("/api/v1/tenants", get(list_tenants).post(create_tenant))
+        .route(
+            "/api/v1/tenants/{name}",
+            axum::routing::delete(delete_tenant),
+        )
+        .route(
+            "/api/v1/tenants/{name}/registration",
+            post(renew_registration),
+        )
+        .with_state(registry)
+}
+
+/// Holder of the tenant admin token.
+struct TenantAdmin;
+
+impl FromRequestParts<Arc<TenantRegistry>> for TenantAdmin {
+    type Rejection = StatusCode;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        registry: &Arc<TenantRegistry>,
+    ) -> Result<Self, Self::Rejection> {
+        let token = parts
+            .headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .ok_or(StatusCode::UNAUTHORIZED)?;
+        // Comparing digests keeps the comparison from leaking the token.
+        if hash_token(token) == hash_token(&registry.tenants.admin_token) {
+            Ok(Self)
+        } else {
+            Err(StatusCode::UNAUTHORIZED)
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TenantInfo {
+    pub name: TenantName,
+    pub url: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TenantRegistration {
+    pub name: TenantName,
+    pub url: String,
+    /// One-time link for the tenant's first user, valid for an hour.
+    pub registration_url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct NewTenant {
+    name: String,
+}
+
+fn tenant_name(name: &str) -> Result<TenantName, ApiError> {
+    name.parse().map_err(|err| AppError::validation(err).into())
+}
+
+async fn list_tenants(
+    _admin: TenantAdmin,
+    State(registry): State<Arc<TenantRegistry>>,
+) -> Result<Json<Vec<TenantInfo>>, ApiError> {
+    let names = registry.list().map_err(AppError::unexpected)?;
+    Ok(Json(
+        names
+            .into_iter()
+            .map(|name| TenantInfo {
+                url: registry.origin(&name),
+                name,
+            })
+            .collect(),
+    ))
+}
+
+async fn create_tenant(
+    _admin: TenantAdmin,
+    State(registry): State<Arc<TenantRegistry>>,
+    Json(request): Json<NewTenant>,
+) -> Result<(StatusCode, Json<TenantRegistration>), ApiError> {
+    let name = tenant_name(&request.name)?;
+    let registration_url = registry.create(&name).await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(TenantRegistration {
+            url: registry.origin(&name),
+            name,
+            registration_url,
+        }),
+    ))
+}
+
+async fn renew_registration(
+    _admin: TenantAdmin,
+    State(registry): State<Arc<TenantRegistry>>,
+    Path(name): Path<String>,
+) -> Result<Json<TenantRegistration>, ApiError> {
+    let name = tenant_name(&name)?;
+    let registration_url = registry.registration(&name).await?;
+    Ok(Json(TenantRegistration {
+        url: registry.origin(&name),
+        name,
+        registration_url,
+    }))
+}
+
+async fn delete_tenant(
+    _admin: TenantAdmin,
+    State(registry): State<Arc<TenantRegistry>>,
+    Path(name): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    let name = tenant_name(&name)?;
+    registry.delete(&name).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -1,5 +1,5 @@
 use chrono::{DateTime, Utc};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::domain::ids::{AiUsageId, UserId};
 
@@ -36,3 +36,109 @@ pub struct AiUsageSummary {
     pub total_tokens: i64,
     pub total_cost: f64,
 }
+
+/// One model's usage in one calendar month, for the Admin page.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AiUsageMonth {
+    /// `YYYY-MM`, in UTC.
+    pub month: String,
+    pub model: String,
+    pub calls: i64,
+    pub prompt_tokens: i64,
+    pub completion_tokens: i64,
+    pub cost: f64,
+}
+
+/// Upper bound for a price per million tokens; no model comes close, so
+/// anything above it is a typo.
+pub const MAX_TOKEN_PRICE: f64 = 10_000.0;
+
+/// What a model costs, in US dollars per million tokens, as entered by hand
+/// on the Admin page.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AiModelPrice {
+    pub model: String,
+    pub prompt_price: f64,
+    pub completion_price: f64,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl AiModelPrice {
+    /// What a call with these token counts costs at this price.
+    pub fn cost(&self, prompt_tokens: i64, completion_tokens: i64) -> f64 {
+        #[allow(clippy::cast_precision_loss)] // Token counts are far below 2^52
+        let cost = (prompt_tokens as f64 * self.prompt_price
+            + completion_tokens as f64 * self.completion_price)
+            / 1_000_000.0;
+        cost
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SetAiModelPrice {
+    pub prompt_price: f64,
+    pub completion_price: f64,
+}
+
+impl SetAiModelPrice {
+    pub fn validate(&self) -> Result<(), String> {
+        let valid = |price: f64| price.is_finite() && (0.0..=MAX_TOKEN_PRICE).contains(&price);
+        if !valid(self.prompt_price) || !valid(self.completion_price) {
+            return Err(format!(
+                "prices must be between 0 and {MAX_TOKEN_PRICE} dollars per million tokens"
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Model names are `OpenRouter` IDs such as `google/gemini-3-flash-preview`.
+pub fn validate_model(model: &str) -> Result<(), String> {
+    if model.is_empty() || model.len() > 200 || model.chars().any(char::is_whitespace) {
+        return Err(format!(
+            "\"{model}\" is not a model name; use the OpenRouter ID, e.g. google/gemini-3-flash-preview"
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn price(prompt_price: f64, completion_price: f64) -> AiModelPrice {
+        AiModelPrice {
+            model: "google/gemini-3-flash-preview".to_string(),
+            prompt_price,
+            completion_price,
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn costs_tokens_per_million() {
+        let cost = price(0.5, 3.0).cost(2_000, 500);
+        assert!((cost - 0.0025).abs() < 1e-12, "{cost}");
+        assert!(price(0.0, 0.0).cost(1_000, 1_000).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn prices_must_be_sensible() {
+        let set = |prompt_price, completion_price| SetAiModelPrice {
+            prompt_price,
+            completion_price,
+        };
+        assert!(set(0.0, 0.0).validate().is_ok());
+        assert!(set(0.5, 3.0).validate().is_ok());
+        assert!(set(-1.0, 3.0).validate().is_err());
+        assert!(set(0.5, f64::NAN).validate().is_err());
+        assert!(set(MAX_TOKEN_PRICE + 1.0, 0.0).validate().is_err());
+    }
+
+    #[test]
+    fn models_are_openrouter_ids() {
+        assert!(validate_model("openrouter/free").is_ok());
+        assert!(validate_model("").is_err());
+        assert!(validate_model("gemini flash").is_err());
+    }
+}
@@ -1,4 +1,5 @@
 pub mod ai_usage;
 pub mod country_stats;
+pub mod quick_actions;
 pub mod stats;
 pub mod timeline;
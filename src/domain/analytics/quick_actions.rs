@@ -0,0 +1,350 @@
+//! Shortcuts suggested on the home page, drawn from what was logged recently
+//! and at what time of day.
+
+use std::collections::HashSet;
+use std::hash::BuildHasher;
+
+use chrono::{DateTime, Duration, Timelike, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::brews::BrewWithDetails;
+use crate::domain::cups::CupWithDetails;
+use crate::domain::ids::BagId;
+
+/// How many suggestions the home page shows.
+pub const DEFAULT_QUICK_ACTION_LIMIT: usize = 3;
+
+/// Entries logged within this many hours of the current hour, either side,
+/// count as "around now".
+const HOUR_WINDOW: u32 = 2;
+
+/// Entries logged within this many days count as recent.
+const RECENT_DAYS: i64 = 7;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuickActionKind {
+    BrewAgain,
+    CheckIn,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuickAction {
+    pub kind: QuickActionKind,
+    pub label: String,
+    /// The pre-filled form the action opens.
+    pub href: String,
+    /// Why the action was suggested, e.g. "Often brewed around now".
+    pub reason: String,
+}
+
+struct Candidate {
+    action: QuickAction,
+    score: u32,
+    last_at: DateTime<Utc>,
+}
+
+/// Tallies entries for one bag or cafe.
+struct Tally<'a, T> {
+    latest: &'a T,
+    last_at: DateTime<Utc>,
+    around_now: u32,
+}
+
+/// Suggests up to `limit` actions: brewing again from a bag that's still open,
+/// or checking in at a cafe visited before. Bags and cafes used around the
+/// current time of day rank first, then those used in the last week.
+/// Anything that is neither is left out.
+pub fn suggest_quick_actions<S: BuildHasher>(
+    now: DateTime<Utc>,
+    brews: &[BrewWithDetails],
+    cups: &[CupWithDetails],
+    open_bags: &HashSet<BagId, S>,
+    limit: usize,
+) -> Vec<QuickAction> {
+    let brew_tallies = tally(
+        now,
+        brews
+            .iter()
+            .filter(|brew| open_bags.contains(&brew.brew.bag_id)),
+        |brew| brew.brew.bag_id,
+        |brew| brew.brew.created_at,
+    );
+    // Cups drunk away from a cafe leave nowhere to check in again.
+    let cup_tallies = tally(
+        now,
+        cups.iter().filter(|cup| cup.cup.cafe_id.is_some()),
+        |cup| cup.cup.cafe_id,
+        |cup| cup.cup.created_at,
+    );
+
+    let brew_candidates = brew_tallies.into_iter().map(|t| {
+        let reason = if t.around_now > 0 {
+            "Often brewed around now"
+        } else {
+            "Brewed in the last week"
+        };
+        candidate(
+            now,
+            &t,
+            QuickAction {
+                kind: QuickActionKind::BrewAgain,
+                label: format!("Brew {} again", t.latest.roast_name),
+                href: t.latest.brew.brew_again_url(),
+                reason: reason.to_string(),
+            },
+        )
+    });
+    let cup_candidates = cup_tallies.into_iter().filter_map(|t| {
+        let cafe_id = t.latest.cup.cafe_id?;
+        let reason = if t.around_now > 0 {
+            "Often visited around now"
+        } else {
+            "Visited in the last week"
+        };
+        Some(candidate(
+            now,
+            &t,
+            QuickAction {
+                kind: QuickActionKind::CheckIn,
+                label: format!("Check in at {}", t.latest.place_name()),
+                href: format!("/check-in?cafe_id={cafe_id}"),
+                reason: reason.to_string(),
+            },
+        ))
+    });
+
+    let mut candidates: Vec<Candidate> = brew_candidates
+        .chain(cup_candidates)
+        .filter(|c| c.score > 0)
+        .collect();
+    candidates.sort_by(|a, b| b.score.cmp(&a.score).then(b.last_at.cmp(&a.last_at)));
+    candidates
+        .into_iter()
+        .take(limit)
+        .map(|c| c.action)
+        .collect()
+}
+
+/// Groups entries by `key`, keeping the latest entry of each group and how
+/// many were logged around the current time of day. Groups keep the order in
+/// which they were first seen.
+fn tally<'a, T, K: PartialEq>(
+    now: DateTime<Utc>,
+    entries: impl Iterator<Item = &'a T>,
+    key: impl Fn(&T) -> K,
+    created_at: impl Fn(&T) -> DateTime<Utc>,
+) -> Vec<Tally<'a, T>> {
+    let mut groups: Vec<(K, Tally<'a, T>)> = Vec::new();
+    for entry in entries {
+        let at = created_at(entry);
+        let around_now = u32::from(is_around(now, at));
+        let entry_key = key(entry);
+        if let Some((_, group)) = groups.iter_mut().find(|(k, _)| *k == entry_key) {
+            group.around_now += around_now;
+            if at > group.last_at {
+                group.latest = entry;
+                group.last_at = at;
+            }
+        } else {
+            groups.push((
+                entry_key,
+                Tally {
+                    latest: entry,
+                    last_at: at,
+                    around_now,
+                },
+            ));
+        }
+    }
+    groups.into_iter().map(|(_, group)| group).collect()
+}
+
+/// Time-of-day matches count double; a recent entry adds one.
+fn candidate<T>(now: DateTime<Utc>, tally: &Tally<'_, T>, action: QuickAction) -> Candidate {
+    let recent = now - tally.last_at <= Duration::days(RECENT_DAYS);
+    Candidate {
+        action,
+        score: tally.around_now * 2 + u32::from(recent),
+        last_at: tally.last_at,
+    }
+}
+
+/// Whether `at` falls within [`HOUR_WINDOW`] hours of `now`'s hour of the
+/// day, wrapping around midnight.
+fn is_around(now: DateTime<Utc>, at: DateTime<Utc>) -> bool {
+    let diff = now.hour().abs_diff(at.hour());
+    diff.min(24 - diff) <= HOUR_WINDOW
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+    use crate::domain::brews::Brew;
+    use crate::domain::cups::Cup;
+    use crate::domain::ids::{BrewId, CafeId, CupId, GearId, RoastId};
+
+    fn at(day: u32, hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 3, day, hour, 0, 0).unwrap()
+    }
+
+    fn brew(id: i64, bag_id: i64, roast_name: &str, created_at: DateTime<Utc>) -> BrewWithDetails {
+        BrewWithDetails {
+            brew: Brew {
+                id: BrewId::new(id),
+                bag_id: BagId::new(bag_id),
+                coffee_weight: 15.0,
+                grinder_id: GearId::new(1),
+                grind_setting: 6.0,
+                brewer_id: GearId::new(2),
+                filter_paper_id: None,
+                water_volume: 250,
+                water_temp: 92.0,
+                bypass_water_ml: None,
+                kettle_id: None,
+                actual_water_temp: None,
+                quick_notes: vec![],
+                brew_time: None,
+                created_at,
+                updated_at: created_at,
+                created_by: None,
+            },
+            roast_name: roast_name.to_string(),
+            roaster_name: "Roaster".to_string(),
+            roast_slug: "roast".to_string(),
+            roaster_slug: "roaster".to_string(),
+            grinder_name: "Grinder".to_string(),
+            grinder_model: "Model".to_string(),
+            brewer_name: "Brewer".to_string(),
+            filter_paper_name: None,
+            kettle_name: None,
+        }
+    }
+
+    fn cup(id: i64, cafe_id: i64, cafe_name: &str, created_at: DateTime<Utc>) -> CupWithDetails {
+        CupWithDetails {
+            cup: Cup {
+                id: CupId::new(id),
+                roast_id: RoastId::new(1),
+                cafe_id: Some(CafeId::new(cafe_id)),
+                bag_id: None,
+                rating: None,
+                created_at,
+                updated_at: created_at,
+                created_by: None,
+            },
+            roast_name: "Roast".to_string(),
+            roaster_name: "Roaster".to_string(),
+            roast_slug: "roast".to_string(),
+            roaster_slug: "roaster".to_string(),
+            cafe_name: Some(cafe_name.to_string()),
+            cafe_slug: Some("cafe".to_string()),
+            cafe_city: Some("London".to_string()),
+        }
+    }
+
+    fn open(ids: &[i64]) -> HashSet<BagId> {
+        ids.iter().copied().map(BagId::new).collect()
+    }
+
+    #[test]
+    fn suggests_bags_brewed_around_this_time_of_day_first() {
+        let now = at(20, 8);
+        let brews = vec![
+            brew(3, 2, "Konga Natural", at(19, 17)),
+            brew(2, 1, "Halo Beriti", at(18, 7)),
+            brew(1, 1, "Halo Beriti", at(17, 8)),
+        ];
+
+        let actions = suggest_quick_actions(now, &brews, &[], &open(&[1, 2]), 3);
+
+        let labels: Vec<&str> = actions.iter().map(|a| a.label.as_str()).collect();
+        assert_eq!(
+            labels,
+            ["Brew Halo Beriti again", "Brew Konga Natural again"]
+        );
+        assert_eq!(actions[0].kind, QuickActionKind::BrewAgain);
+        assert_eq!(actions[0].reason, "Often brewed around now");
+        assert_eq!(actions[1].reason, "Brewed in the last week");
+    }
+
+    #[test]
+    fn brew_again_uses_the_latest_brew_of_the_bag() {
+        let brews = vec![
+            brew(1, 1, "Halo Beriti", at(17, 8)),
+            brew(2, 1, "Halo Beriti", at(18, 8)),
+        ];
+
+        let actions = suggest_quick_actions(at(20, 8), &brews, &[], &open(&[1]), 3);
+
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0].href, brews[1].brew.brew_again_url());
+    }
+
+    #[test]
+    fn skips_bags_that_are_no_longer_open() {
+        let brews = vec![brew(1, 1, "Halo Beriti", at(19, 8))];
+
+        let actions = suggest_quick_actions(at(20, 8), &brews, &[], &open(&[]), 3);
+
+        assert!(actions.is_empty());
+    }
+
+    #[test]
+    fn suggests_checking_in_at_cafes_visited_around_now() {
+        let cups = vec![
+            cup(2, 7, "Prufrock", at(12, 14)),
+            cup(1, 7, "Prufrock", at(5, 15)),
+        ];
+
+        let actions = suggest_quick_actions(at(20, 15), &[], &cups, &open(&[]), 3);
+
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0].kind, QuickActionKind::CheckIn);
+        assert_eq!(actions[0].label, "Check in at Prufrock");
+        assert_eq!(actions[0].href, "/check-in?cafe_id=7");
+        assert_eq!(actions[0].reason, "Often visited around now");
+    }
+
+    #[test]
+    fn skips_cups_drunk_away_from_a_cafe() {
+        let mut home = cup(1, 7, "Prufrock", at(19, 15));
+        home.cup.cafe_id = None;
+        home.cafe_name = None;
+
+        let actions = suggest_quick_actions(at(20, 15), &[], &[home], &open(&[]), 3);
+
+        assert!(actions.is_empty());
+    }
+
+    #[test]
+    fn leaves_out_old_entries_from_other_times_of_day() {
+        let brews = vec![brew(1, 1, "Halo Beriti", at(1, 20))];
+        let cups = vec![cup(1, 7, "Prufrock", at(1, 20))];
+
+        let actions = suggest_quick_actions(at(20, 8), &brews, &cups, &open(&[1]), 3);
+
+        assert!(actions.is_empty());
+    }
+
+    #[test]
+    fn time_of_day_wraps_around_midnight() {
+        assert!(is_around(at(20, 23), at(19, 1)));
+        assert!(!is_around(at(20, 23), at(19, 2)));
+    }
+
+    #[test]
+    fn respects_the_limit() {
+        let brews = vec![
+            brew(1, 1, "A", at(19, 8)),
+            brew(2, 2, "B", at(19, 8)),
+            brew(3, 3, "C", at(19, 8)),
+        ];
+
+        let actions = suggest_quick_actions(at(20, 8), &brews, &[], &open(&[1, 2, 3]), 2);
+
+        assert_eq!(actions.len(), 2);
+    }
+}
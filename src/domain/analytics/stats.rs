@@ -1,6 +1,14 @@
+use std::cmp::Ordering;
+
+use chrono::{DateTime, Datelike, Days, Months, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 
+use crate::define_sort_key;
 use crate::domain::country_stats::GeoStats;
+use crate::domain::currencies::{ExchangeRate, convert};
+use crate::domain::ids::RoasterId;
+use crate::domain::listing::SortDirection;
+use crate::domain::processes::ProcessTaxonomy;
 
 /// Summary statistics for roasts: origins, flavours, and roasters.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -36,6 +44,313 @@ pub struct BrewingSummaryStats {
     pub max_brew_time_count: u64,
 }
 
+/// How many tasting notes [`TastingStats`] keeps.
+pub const TOP_TASTING_NOTES: u32 = 10;
+
+/// What the coffee brewed in one calendar year tasted like and how it was
+/// processed. Each brew counts once for every note and for its process.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TastingStats {
+    pub year: i32,
+    /// The [`TOP_TASTING_NOTES`] most brewed notes, most brewed first.
+    pub tasting_note_counts: Vec<(String, u64)>,
+    /// Brews per process family, largest first.
+    pub process_counts: Vec<(String, u64)>,
+}
+
+/// Merge per-process counts into family totals, largest first.
+pub fn process_family_counts(
+    counts: Vec<(String, u64)>,
+    taxonomy: &ProcessTaxonomy,
+) -> Vec<(String, u64)> {
+    let mut families: Vec<(String, u64)> = Vec::new();
+    for (process, count) in counts {
+        let family = taxonomy.family(&process);
+        match families.iter_mut().find(|(name, _)| *name == family) {
+            Some((_, total)) => *total += count,
+            None => families.push((family, count)),
+        }
+    }
+    families.sort_by(|(a_name, a), (b_name, b)| b.cmp(a).then_with(|| a_name.cmp(b_name)));
+    families
+}
+
+/// Day labels for [`BrewingHabits::weekday_counts`], Monday first.
+pub const WEEKDAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+/// When brews happen: how many fall on each day of the week and in each hour
+/// of the day. Brew times are stored in UTC, so the buckets are too.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BrewingHabits {
+    /// One bucket per [`WEEKDAYS`] entry, Monday first.
+    pub weekday_counts: Vec<(String, u64)>,
+    pub max_weekday_count: u64,
+    /// One bucket per hour, labelled `00` to `23`.
+    pub hour_counts: Vec<(String, u64)>,
+    pub max_hour_count: u64,
+}
+
+impl BrewingHabits {
+    /// Fill every bucket from sparse `(bucket, count)` rows. Weekdays are
+    /// numbered as `strftime('%w')` does, with Sunday as 0; rows
+    /// outside the range are ignored.
+    pub fn from_buckets(weekdays: &[(u32, u64)], hours: &[(u32, u64)]) -> Self {
+        let mut weekday_totals = [0_u64; 7];
+        for &(day, count) in weekdays.iter().filter(|(day, _)| *day < 7) {
+            weekday_totals[(day as usize + 6) % 7] += count;
+        }
+        let mut hour_totals = [0_u64; 24];
+        for &(hour, count) in hours {
+            if let Some(total) = hour_totals.get_mut(hour as usize) {
+                *total += count;
+            }
+        }
+
+        Self {
+            weekday_counts: WEEKDAYS
+                .iter()
+                .zip(weekday_totals)
+                .map(|(day, count)| ((*day).to_string(), count))
+                .collect(),
+            max_weekday_count: weekday_totals.into_iter().max().unwrap_or(0),
+            hour_counts: hour_totals
+                .iter()
+                .enumerate()
+                .map(|(hour, count)| (format!("{hour:02}"), *count))
+                .collect(),
+            max_hour_count: hour_totals.into_iter().max().unwrap_or(0),
+        }
+    }
+}
+
+/// How many roasters and origins a [`PeriodSummary`] keeps.
+pub const TOP_PERIOD_ENTRIES: u32 = 5;
+
+/// A recent window that brewing is summarised over.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatsPeriod {
+    /// The last 30 days, bucketed by day.
+    Month,
+    /// The last 12 calendar months, bucketed by month.
+    Year,
+}
+
+impl StatsPeriod {
+    /// The `strftime` format that names a bucket.
+    pub const fn bucket_format(self) -> &'static str {
+        match self {
+            Self::Month => "%Y-%m-%d",
+            Self::Year => "%Y-%m",
+        }
+    }
+
+    /// Every bucket label in the period ending `today`, oldest first.
+    pub fn buckets(self, today: NaiveDate) -> Vec<String> {
+        let format = self.bucket_format();
+        match self {
+            Self::Month => (0..30)
+                .rev()
+                .filter_map(|days| today.checked_sub_days(Days::new(days)))
+                .map(|day| day.format(format).to_string())
+                .collect(),
+            Self::Year => {
+                let this_month = today.with_day(1).unwrap_or(today);
+                (0..12)
+                    .rev()
+                    .filter_map(|months| this_month.checked_sub_months(Months::new(months)))
+                    .map(|month| month.format(format).to_string())
+                    .collect()
+            }
+        }
+    }
+}
+
+/// Brewing over a [`StatsPeriod`]: totals, a history to chart, and what was
+/// brewed most.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PeriodSummary {
+    pub grams: f64,
+    pub brews: u64,
+    pub cups: u64,
+    /// Grams brewed in every bucket of the period, oldest first.
+    pub history: Vec<(String, f64)>,
+    /// The [`TOP_PERIOD_ENTRIES`] roasters with the most grams brewed.
+    pub top_roasters: Vec<(String, f64)>,
+    /// The [`TOP_PERIOD_ENTRIES`] origins brewed most often.
+    pub top_origins: Vec<(String, u64)>,
+}
+
+impl PeriodSummary {
+    /// Grams per bucket from sparse `(bucket, grams)` rows, with empty
+    /// buckets filled in.
+    pub fn fill_history(buckets: Vec<String>, rows: &[(String, f64)]) -> Vec<(String, f64)> {
+        buckets
+            .into_iter()
+            .map(|bucket| {
+                let grams = rows
+                    .iter()
+                    .filter(|(label, _)| *label == bucket)
+                    .map(|(_, grams)| grams)
+                    .sum();
+                (bucket, grams)
+            })
+            .collect()
+    }
+}
+
+/// What was paid for bags in one currency. `currency` is unset for prices
+/// entered without one, which count as the base currency.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CurrencySpend {
+    pub currency: Option<String>,
+    pub bags: u64,
+    pub amount: f64,
+    /// `amount` in the base currency, when there's a rate for it.
+    pub converted: Option<f64>,
+}
+
+/// Money spent on bags, totalled in the base currency.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SpendStats {
+    pub base_currency: Option<String>,
+    /// Everything that could be converted, in the base currency.
+    pub total: f64,
+    pub priced_bags: u64,
+    pub by_currency: Vec<CurrencySpend>,
+    /// Currencies with no exchange rate, left out of `total`.
+    pub unconverted: Vec<String>,
+}
+
+impl SpendStats {
+    /// Convert each currency's `(currency, bags, amount)` with `rates`.
+    pub fn convert(
+        spend: Vec<(Option<String>, u64, f64)>,
+        base_currency: Option<String>,
+        rates: &[ExchangeRate],
+    ) -> Self {
+        let mut stats = Self {
+            base_currency,
+            ..Self::default()
+        };
+        for (currency, bags, amount) in spend {
+            let converted = convert(
+                amount,
+                currency.as_deref(),
+                stats.base_currency.as_deref(),
+                rates,
+            );
+            match (converted, &currency) {
+                (Some(value), _) => stats.total += value,
+                (None, Some(code)) => stats.unconverted.push(code.clone()),
+                (None, None) => {}
+            }
+            stats.priced_bags += bags;
+            stats.by_currency.push(CurrencySpend {
+                currency,
+                bags,
+                amount,
+                converted,
+            });
+        }
+        stats.by_currency.sort_by(|a, b| {
+            b.converted
+                .unwrap_or_default()
+                .total_cmp(&a.converted.unwrap_or_default())
+        });
+        stats
+    }
+}
+
+/// One roaster's standing by how much of their coffee was actually brewed,
+/// rather than how many of their roasts were logged.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoasterLeaderboardEntry {
+    pub roaster_id: RoasterId,
+    pub name: String,
+    pub slug: String,
+    pub grams_brewed: f64,
+    pub brew_count: u64,
+    /// Brews marked with the "Good" quick note.
+    pub good_brew_count: u64,
+    /// When the most recent bag of their coffee was added.
+    pub last_purchased_at: Option<DateTime<Utc>>,
+}
+
+impl RoasterLeaderboardEntry {
+    /// Share of brews marked "Good", from 0 to 1; `None` before any brews.
+    #[allow(clippy::cast_precision_loss)] // Brew counts stay far below 2^52
+    pub fn good_brew_share(&self) -> Option<f64> {
+        (self.brew_count > 0).then(|| self.good_brew_count as f64 / self.brew_count as f64)
+    }
+}
+
+define_sort_key!(pub RoasterLeaderboardSortKey {
+    #[default]
+    Grams("grams", Desc),
+    Brews("brews", Desc),
+    GoodBrews("good-brews", Desc),
+    LastPurchased("last-purchased", Desc),
+    Name("name", Asc),
+});
+
+/// Order leaderboard entries by `key`. Roasters with no value for the key
+/// go last either way; ties fall back to most grams brewed, then name.
+pub fn sort_leaderboard(
+    entries: &mut [RoasterLeaderboardEntry],
+    key: RoasterLeaderboardSortKey,
+    direction: SortDirection,
+) {
+    entries.sort_by(|a, b| {
+        let ordering = match key {
+            RoasterLeaderboardSortKey::Grams => {
+                directed(a.grams_brewed.total_cmp(&b.grams_brewed), direction)
+            }
+            RoasterLeaderboardSortKey::Brews => {
+                directed(a.brew_count.cmp(&b.brew_count), direction)
+            }
+            RoasterLeaderboardSortKey::GoodBrews => compare_optional(
+                a.good_brew_share(),
+                b.good_brew_share(),
+                direction,
+                f64::total_cmp,
+            ),
+            RoasterLeaderboardSortKey::LastPurchased => compare_optional(
+                a.last_purchased_at,
+                b.last_purchased_at,
+                direction,
+                Ord::cmp,
+            ),
+            RoasterLeaderboardSortKey::Name => {
+                directed(a.name.to_lowercase().cmp(&b.name.to_lowercase()), direction)
+            }
+        };
+        ordering
+            .then_with(|| b.grams_brewed.total_cmp(&a.grams_brewed))
+            .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+    });
+}
+
+fn directed(ordering: Ordering, direction: SortDirection) -> Ordering {
+    match direction {
+        SortDirection::Asc => ordering,
+        SortDirection::Desc => ordering.reverse(),
+    }
+}
+
+fn compare_optional<T>(
+    a: Option<T>,
+    b: Option<T>,
+    direction: SortDirection,
+    cmp: impl Fn(&T, &T) -> Ordering,
+) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => directed(cmp(&a, &b), direction),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
 /// Entity counts for the home page.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct EntityCounts {
@@ -60,4 +375,162 @@ pub struct CachedStats {
     pub computed_at: String,
     #[serde(default)]
     pub entity_counts: EntityCounts,
+    #[serde(default)]
+    pub tasting: TastingStats,
+    #[serde(default)]
+    pub habits: BrewingHabits,
+    /// Every roaster with at least one bag, most grams brewed first.
+    #[serde(default)]
+    pub roaster_leaderboard: Vec<RoasterLeaderboardEntry>,
+    #[serde(default)]
+    pub last_month: PeriodSummary,
+    #[serde(default)]
+    pub last_year: PeriodSummary,
+    #[serde(default)]
+    pub spend: SpendStats,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn period_buckets_end_today() {
+        let today = NaiveDate::from_ymd_opt(2025, 3, 5).unwrap();
+
+        let days = StatsPeriod::Month.buckets(today);
+        assert_eq!(days.len(), 30);
+        assert_eq!(days[0], "2025-02-04");
+        assert_eq!(days[29], "2025-03-05");
+
+        let months = StatsPeriod::Year.buckets(today);
+        assert_eq!(months.len(), 12);
+        assert_eq!(months[0], "2024-04");
+        assert_eq!(months[11], "2025-03");
+    }
+
+    #[test]
+    fn history_fills_empty_buckets() {
+        let history = PeriodSummary::fill_history(
+            vec!["2025-01".to_string(), "2025-02".to_string()],
+            &[("2025-02".to_string(), 30.0)],
+        );
+        assert_eq!(
+            history,
+            vec![("2025-01".to_string(), 0.0), ("2025-02".to_string(), 30.0)]
+        );
+    }
+
+    #[test]
+    fn habits_fill_every_bucket_monday_first() {
+        let habits = BrewingHabits::from_buckets(&[(0, 4), (6, 2), (1, 1), (9, 5)], &[(7, 3)]);
+
+        assert_eq!(habits.weekday_counts.len(), 7);
+        assert_eq!(habits.weekday_counts[0], ("Mon".to_string(), 1));
+        assert_eq!(habits.weekday_counts[5], ("Sat".to_string(), 2));
+        assert_eq!(habits.weekday_counts[6], ("Sun".to_string(), 4));
+        assert_eq!(habits.max_weekday_count, 4);
+
+        assert_eq!(habits.hour_counts.len(), 24);
+        assert_eq!(habits.hour_counts[7], ("07".to_string(), 3));
+        assert_eq!(habits.hour_counts[23], ("23".to_string(), 0));
+        assert_eq!(habits.max_hour_count, 3);
+    }
+
+    fn entry(name: &str, grams: f64, brews: u64, good: u64) -> RoasterLeaderboardEntry {
+        RoasterLeaderboardEntry {
+            roaster_id: RoasterId::new(1),
+            name: name.to_string(),
+            slug: name.to_lowercase(),
+            grams_brewed: grams,
+            brew_count: brews,
+            good_brew_count: good,
+            last_purchased_at: None,
+        }
+    }
+
+    fn names(entries: &[RoasterLeaderboardEntry]) -> Vec<&str> {
+        entries.iter().map(|e| e.name.as_str()).collect()
+    }
+
+    #[test]
+    fn leaderboard_sorts_by_key_and_direction() {
+        let mut entries = vec![
+            entry("Assembly", 30.0, 2, 2),
+            entry("Dak", 90.0, 6, 3),
+            entry("Unbrewed", 0.0, 0, 0),
+        ];
+
+        sort_leaderboard(
+            &mut entries,
+            RoasterLeaderboardSortKey::Grams,
+            SortDirection::Desc,
+        );
+        assert_eq!(names(&entries), ["Dak", "Assembly", "Unbrewed"]);
+
+        sort_leaderboard(
+            &mut entries,
+            RoasterLeaderboardSortKey::Name,
+            SortDirection::Asc,
+        );
+        assert_eq!(names(&entries), ["Assembly", "Dak", "Unbrewed"]);
+
+        // A roaster with no brews has no share and stays last both ways.
+        for direction in [SortDirection::Desc, SortDirection::Asc] {
+            sort_leaderboard(
+                &mut entries,
+                RoasterLeaderboardSortKey::GoodBrews,
+                direction,
+            );
+            assert_eq!(entries[2].name, "Unbrewed");
+        }
+        assert_eq!(entries[0].name, "Dak");
+    }
+
+    #[test]
+    fn family_counts_merge_and_sort() {
+        let taxonomy = ProcessTaxonomy::default();
+        let counts = process_family_counts(
+            vec![
+                ("Washed".to_string(), 2),
+                ("Natural".to_string(), 3),
+                ("Fully washed".to_string(), 4),
+                ("Honey".to_string(), 3),
+                ("Anaerobic Natural".to_string(), 1),
+            ],
+            &taxonomy,
+        );
+        assert_eq!(
+            counts,
+            [
+                ("Washed".to_string(), 6),
+                ("Natural".to_string(), 4),
+                ("Honey".to_string(), 3),
+            ]
+        );
+    }
+
+    #[test]
+    fn spend_is_totalled_in_the_base_currency() {
+        let rates = [ExchangeRate {
+            currency: "EUR".to_string(),
+            rate: 0.85,
+            updated_at: Utc::now(),
+        }];
+        let spend = SpendStats::convert(
+            vec![
+                (None, 1, 5.0),
+                (Some("EUR".to_string()), 2, 20.0),
+                (Some("GBP".to_string()), 3, 30.0),
+                (Some("JPY".to_string()), 1, 2000.0),
+            ],
+            Some("GBP".to_string()),
+            &rates,
+        );
+        assert_eq!(spend.total, 52.0);
+        assert_eq!(spend.priced_bags, 7);
+        assert_eq!(spend.unconverted, ["JPY"]);
+        assert_eq!(spend.by_currency[0].currency.as_deref(), Some("GBP"));
+        assert_eq!(spend.by_currency[1].converted, Some(17.0));
+    }
 }
@@ -1,9 +1,9 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 
 use crate::define_sort_key;
 use crate::domain::entity_type::EntityType;
-use crate::domain::ids::{BagId, GearId, TimelineEventId};
+use crate::domain::ids::{BagId, GearId, TimelineEventId, UserId};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TimelineEventDetail {
@@ -23,6 +23,8 @@ pub struct TimelineBrewData {
     pub water_volume: i32,
     pub water_temp: f64,
     pub brew_time: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bypass_water_ml: Option<i32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -38,6 +40,8 @@ pub struct TimelineEvent {
     pub slug: Option<String>,
     pub roaster_slug: Option<String>,
     pub brew_data: Option<TimelineBrewData>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_by: Option<UserId>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -52,9 +56,144 @@ pub struct NewTimelineEvent {
     pub slug: Option<String>,
     pub roaster_slug: Option<String>,
     pub brew_data: Option<TimelineBrewData>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_by: Option<UserId>,
+}
+
+/// A calendar month of the timeline, as browsed through the archive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TimelineMonth {
+    year: i32,
+    month: u32,
+}
+
+impl TimelineMonth {
+    /// `None` unless `month` is 1-12 and `year` has four digits.
+    pub fn new(year: i32, month: u32) -> Option<Self> {
+        ((1000..=9999).contains(&year) && (1..=12).contains(&month)).then_some(Self { year, month })
+    }
+
+    /// The month `at` falls in.
+    pub fn containing(at: DateTime<Utc>) -> Self {
+        Self {
+            year: at.year(),
+            month: at.month(),
+        }
+    }
+
+    pub fn year(self) -> i32 {
+        self.year
+    }
+
+    pub fn month(self) -> u32 {
+        self.month
+    }
+
+    pub fn previous(self) -> Self {
+        if self.month == 1 {
+            Self {
+                year: self.year - 1,
+                month: 12,
+            }
+        } else {
+            Self {
+                month: self.month - 1,
+                ..self
+            }
+        }
+    }
+
+    pub fn next(self) -> Self {
+        if self.month == 12 {
+            Self {
+                year: self.year + 1,
+                month: 1,
+            }
+        } else {
+            Self {
+                month: self.month + 1,
+                ..self
+            }
+        }
+    }
+
+    /// `YYYY-MM`, matching `strftime('%Y-%m', ...)` in queries.
+    pub fn key(self) -> String {
+        format!("{:04}-{:02}", self.year, self.month)
+    }
+
+    /// e.g. "March".
+    pub fn name(self) -> String {
+        NaiveDate::from_ymd_opt(self.year, self.month, 1)
+            .map_or_else(|| self.key(), |date| date.format("%B").to_string())
+    }
+
+    /// e.g. "March 2025".
+    pub fn label(self) -> String {
+        format!("{} {}", self.name(), self.year)
+    }
+}
+
+/// How many timeline events fall in one month.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimelineMonthCount {
+    pub month: TimelineMonth,
+    pub count: u64,
+}
+
+/// Narrows the timeline to a subset of events.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TimelineFilter {
+    pub created_by: Option<UserId>,
+    pub month: Option<TimelineMonth>,
+}
+
+impl TimelineFilter {
+    /// No filter - returns all events.
+    pub fn all() -> Self {
+        Self::default()
+    }
+
+    /// Filter for events logged by a specific user.
+    pub fn for_user(user_id: UserId) -> Self {
+        Self {
+            created_by: Some(user_id),
+            ..Self::default()
+        }
+    }
+
+    /// Narrow this filter to events that occurred during `month`.
+    pub fn in_month(self, month: TimelineMonth) -> Self {
+        Self {
+            month: Some(month),
+            ..self
+        }
+    }
 }
 
 define_sort_key!(pub TimelineSortKey {
     #[default]
     OccurredAt("occurred-at", Desc),
 });
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timeline_month_rejects_out_of_range_values() {
+        assert!(TimelineMonth::new(2025, 0).is_none());
+        assert!(TimelineMonth::new(2025, 13).is_none());
+        assert!(TimelineMonth::new(25, 3).is_none());
+        assert!(TimelineMonth::new(2025, 3).is_some());
+    }
+
+    #[test]
+    fn timeline_month_steps_across_years() {
+        let january = TimelineMonth::new(2025, 1).unwrap();
+        assert_eq!(january.previous(), TimelineMonth::new(2024, 12).unwrap());
+        assert_eq!(january.previous().next(), january);
+        assert_eq!(january.key(), "2025-01");
+        assert_eq!(january.label(), "January 2025");
+    }
+}
@@ -0,0 +1,194 @@
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::bags::BagSortKey;
+use crate::domain::brews::BrewSortKey;
+use crate::domain::cafes::CafeSortKey;
+use crate::domain::cups::CupSortKey;
+use crate::domain::gear::{GearCategory, GearSortKey};
+use crate::domain::ids::UserId;
+use crate::domain::listing::{MAX_PAGE_SIZE, SortDirection, SortKey};
+use crate::domain::roasters::RoasterSortKey;
+use crate::domain::roasts::RoastSortKey;
+
+/// The paginated lists that support saved defaults and searches, keyed by
+/// their `/data?type=` value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ListName {
+    Roasters,
+    Roasts,
+    Bags,
+    Gear,
+    Brews,
+    Cafes,
+    Cups,
+}
+
+impl ListName {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Roasters => "roasters",
+            Self::Roasts => "roasts",
+            Self::Bags => "bags",
+            Self::Gear => "gear",
+            Self::Brews => "brews",
+            Self::Cafes => "cafes",
+            Self::Cups => "cups",
+        }
+    }
+
+    /// Whether `value` is a valid `sort` query value for this list.
+    pub fn accepts_sort_key(self, value: &str) -> bool {
+        match self {
+            Self::Roasters => RoasterSortKey::from_query(value).is_some(),
+            Self::Roasts => RoastSortKey::from_query(value).is_some(),
+            Self::Bags => BagSortKey::from_query(value).is_some(),
+            Self::Gear => GearSortKey::from_query(value).is_some(),
+            Self::Brews => BrewSortKey::from_query(value).is_some(),
+            Self::Cafes => CafeSortKey::from_query(value).is_some(),
+            Self::Cups => CupSortKey::from_query(value).is_some(),
+        }
+    }
+
+    /// Whether `key=value` is a supported filter query parameter for this list.
+    pub fn accepts_filter(self, key: &str, value: &str) -> bool {
+        match (self, key) {
+            (Self::Bags, "status") => matches!(value, "open" | "closed"),
+            (Self::Gear, "category") => GearCategory::from_str(value).is_ok(),
+            _ => false,
+        }
+    }
+}
+
+impl std::fmt::Display for ListName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ListName {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "roasters" => Ok(Self::Roasters),
+            "roasts" => Ok(Self::Roasts),
+            "bags" => Ok(Self::Bags),
+            "gear" => Ok(Self::Gear),
+            "brews" => Ok(Self::Brews),
+            "cafes" => Ok(Self::Cafes),
+            "cups" => Ok(Self::Cups),
+            _ => Err(()),
+        }
+    }
+}
+
+/// A user's saved defaults for one list, applied when a request omits them.
+///
+/// A `page_size` of zero means "show all". Unset fields fall back to the
+/// list's built-in defaults.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ListPreference {
+    pub user_id: UserId,
+    pub list: ListName,
+    pub page_size: Option<u32>,
+    pub sort_key: Option<String>,
+    pub sort_direction: Option<SortDirection>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewListPreference {
+    pub user_id: UserId,
+    pub list: ListName,
+    pub page_size: Option<u32>,
+    pub sort_key: Option<String>,
+    pub sort_direction: Option<SortDirection>,
+}
+
+impl NewListPreference {
+    /// Check the page size is in range and the sort key belongs to the list.
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(page_size) = self.page_size
+            && page_size > MAX_PAGE_SIZE
+        {
+            return Err(format!(
+                "page size must be between 0 and {MAX_PAGE_SIZE} (0 shows all)"
+            ));
+        }
+        if let Some(key) = self.sort_key.as_deref()
+            && !self.list.accepts_sort_key(key)
+        {
+            return Err(format!("unknown sort key '{key}' for {}", self.list));
+        }
+        if self.sort_direction.is_some() && self.sort_key.is_none() {
+            return Err("sort direction requires a sort key".to_string());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn preference(list: ListName) -> NewListPreference {
+        NewListPreference {
+            user_id: UserId::new(1),
+            list,
+            page_size: None,
+            sort_key: None,
+            sort_direction: None,
+        }
+    }
+
+    #[test]
+    fn list_name_round_trips() {
+        for list in [
+            ListName::Roasters,
+            ListName::Roasts,
+            ListName::Bags,
+            ListName::Gear,
+            ListName::Brews,
+            ListName::Cafes,
+            ListName::Cups,
+        ] {
+            assert_eq!(list.as_str().parse::<ListName>(), Ok(list));
+        }
+        assert!("timeline".parse::<ListName>().is_err());
+    }
+
+    #[test]
+    fn validate_accepts_sort_key_for_list() {
+        let mut pref = preference(ListName::Roasts);
+        pref.sort_key = Some("origin".to_string());
+        pref.sort_direction = Some(SortDirection::Asc);
+        assert!(pref.validate().is_ok());
+    }
+
+    #[test]
+    fn validate_rejects_sort_key_from_another_list() {
+        let mut pref = preference(ListName::Roasters);
+        pref.sort_key = Some("origin".to_string());
+        assert!(pref.validate().is_err());
+    }
+
+    #[test]
+    fn validate_rejects_oversized_page() {
+        let mut pref = preference(ListName::Brews);
+        pref.page_size = Some(MAX_PAGE_SIZE + 1);
+        assert!(pref.validate().is_err());
+        pref.page_size = Some(0);
+        assert!(pref.validate().is_ok());
+    }
+
+    #[test]
+    fn validate_rejects_direction_without_key() {
+        let mut pref = preference(ListName::Brews);
+        pref.sort_direction = Some(SortDirection::Desc);
+        assert!(pref.validate().is_err());
+    }
+}
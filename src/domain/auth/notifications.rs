@@ -0,0 +1,107 @@
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::ids::{BagId, NotificationId, UserId};
+
+/// How many notifications the bell menu and API return at most.
+pub const NOTIFICATION_LIST_LIMIT: u32 = 50;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationKind {
+    BagRunningLow,
+    CommentReceived,
+}
+
+impl NotificationKind {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::BagRunningLow => "bag_running_low",
+            Self::CommentReceived => "comment_received",
+        }
+    }
+}
+
+impl FromStr for NotificationKind {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "bag_running_low" => Ok(Self::BagRunningLow),
+            "comment_received" => Ok(Self::CommentReceived),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Something a user should know about, shown in the bell menu until read.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Notification {
+    pub id: NotificationId,
+    pub user_id: UserId,
+    pub kind: NotificationKind,
+    pub title: String,
+    pub link: Option<String>,
+    pub read_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Notification {
+    pub fn is_read(&self) -> bool {
+        self.read_at.is_some()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewNotification {
+    pub user_id: UserId,
+    pub kind: NotificationKind,
+    pub title: String,
+    pub link: Option<String>,
+}
+
+impl NewNotification {
+    pub fn bag_running_low(user_id: UserId, bag_id: BagId, coffee: &str, remaining: f64) -> Self {
+        Self {
+            user_id,
+            kind: NotificationKind::BagRunningLow,
+            title: format!("{coffee} is running low ({remaining:.0}g left)"),
+            link: Some(format!("/bags/{bag_id}")),
+        }
+    }
+
+    pub fn comment_received(user_id: UserId, author: &str, subject: &str, link: String) -> Self {
+        Self {
+            user_id,
+            kind: NotificationKind::CommentReceived,
+            title: format!("{author} commented on {subject}"),
+            link: Some(link),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn kind_round_trips_through_its_string_form() {
+        for kind in [
+            NotificationKind::BagRunningLow,
+            NotificationKind::CommentReceived,
+        ] {
+            assert_eq!(NotificationKind::from_str(kind.as_str()), Ok(kind));
+        }
+        assert!(NotificationKind::from_str("unknown").is_err());
+    }
+
+    #[test]
+    fn bag_running_low_links_to_the_bag() {
+        let notification =
+            NewNotification::bag_running_low(UserId::new(1), BagId::new(7), "Red Brick", 42.4);
+        assert_eq!(notification.title, "Red Brick is running low (42g left)");
+        assert_eq!(notification.link.as_deref(), Some("/bags/7"));
+    }
+}
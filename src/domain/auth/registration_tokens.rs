@@ -7,28 +7,47 @@ use crate::domain::ids::{RegistrationTokenId, UserId};
 /// beyond this are clamped to `created_at + MAX_TOKEN_DURATION`.
 pub const MAX_TOKEN_DURATION: Duration = Duration::days(7);
 
+/// Most accounts a single invite can create.
+pub const MAX_TOKEN_USES: u32 = 20;
+
+/// Lifetime of an invite created without an explicit expiry.
+const DEFAULT_EXPIRY_HOURS: u32 = 24;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RegistrationToken {
     pub id: RegistrationTokenId,
-    #[serde(skip_serializing)]
+    #[serde(skip_serializing, default)]
     pub token_hash: String,
     pub created_at: DateTime<Utc>,
     pub expires_at: DateTime<Utc>,
+    pub max_uses: u32,
+    pub use_count: u32,
+    /// Most recent use.
     pub used_at: Option<DateTime<Utc>>,
+    /// User created by the most recent use.
     pub used_by_user_id: Option<UserId>,
+    pub revoked_at: Option<DateTime<Utc>>,
 }
 
 impl RegistrationToken {
-    pub fn is_expired(&self) -> bool {
-        Utc::now() > self.expires_at
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        now > self.expires_at
     }
 
     pub fn is_used(&self) -> bool {
-        self.used_at.is_some()
+        self.use_count > 0
+    }
+
+    pub fn is_exhausted(&self) -> bool {
+        self.use_count >= self.max_uses
     }
 
-    pub fn is_valid(&self) -> bool {
-        !self.is_expired() && !self.is_used()
+    pub fn is_revoked(&self) -> bool {
+        self.revoked_at.is_some()
+    }
+
+    pub fn is_valid(&self, now: DateTime<Utc>) -> bool {
+        !self.is_expired(now) && !self.is_exhausted() && !self.is_revoked()
     }
 }
 
@@ -37,16 +56,59 @@ pub struct NewRegistrationToken {
     pub token_hash: String,
     pub created_at: DateTime<Utc>,
     pub expires_at: DateTime<Utc>,
+    pub max_uses: u32,
 }
 
 impl NewRegistrationToken {
+    /// A single-use token.
     pub fn new(token_hash: String, created_at: DateTime<Utc>, expires_at: DateTime<Utc>) -> Self {
         let max_expires = created_at + MAX_TOKEN_DURATION;
         Self {
             token_hash,
             created_at,
             expires_at: expires_at.min(max_expires),
+            max_uses: 1,
+        }
+    }
+
+    /// Allow the token to be used `max_uses` times, clamped to `1..=MAX_TOKEN_USES`.
+    pub fn with_max_uses(mut self, max_uses: u32) -> Self {
+        self.max_uses = max_uses.clamp(1, MAX_TOKEN_USES);
+        self
+    }
+}
+
+/// Request body for creating an invite from the admin page or API.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CreateRegistrationToken {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_uses: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_in_hours: Option<u32>,
+}
+
+impl CreateRegistrationToken {
+    /// Validate the request and build the token to store for `token_hash`.
+    pub fn into_new_token(
+        self,
+        token_hash: String,
+        now: DateTime<Utc>,
+    ) -> Result<NewRegistrationToken, String> {
+        let max_uses = self.max_uses.unwrap_or(1);
+        if !(1..=MAX_TOKEN_USES).contains(&max_uses) {
+            return Err(format!("max_uses must be between 1 and {MAX_TOKEN_USES}"));
+        }
+
+        let max_hours = MAX_TOKEN_DURATION.num_hours();
+        let hours = self.expires_in_hours.unwrap_or(DEFAULT_EXPIRY_HOURS);
+        if hours == 0 || i64::from(hours) > max_hours {
+            return Err(format!(
+                "expires_in_hours must be between 1 and {max_hours}"
+            ));
         }
+
+        let expires_at = now + Duration::hours(i64::from(hours));
+        Ok(NewRegistrationToken::new(token_hash, now, expires_at).with_max_uses(max_uses))
     }
 }
 
@@ -54,62 +116,59 @@ impl NewRegistrationToken {
 mod tests {
     use super::*;
 
-    #[test]
-    fn token_valid() {
-        let now = Utc::now();
-        let token = RegistrationToken {
+    fn token(expires_at: DateTime<Utc>, max_uses: u32, use_count: u32) -> RegistrationToken {
+        RegistrationToken {
             id: RegistrationTokenId::new(1),
             token_hash: "hash".to_string(),
-            created_at: now,
-            expires_at: now + Duration::hours(1),
-            used_at: None,
-            used_by_user_id: None,
-        };
-        assert!(token.is_valid());
+            created_at: Utc::now() - Duration::hours(2),
+            expires_at,
+            max_uses,
+            use_count,
+            used_at: (use_count > 0).then(Utc::now),
+            used_by_user_id: (use_count > 0).then(|| UserId::new(1)),
+            revoked_at: None,
+        }
+    }
+
+    #[test]
+    fn token_valid() {
+        let token = token(Utc::now() + Duration::hours(1), 1, 0);
+        assert!(token.is_valid(Utc::now()));
     }
 
     #[test]
     fn token_expired() {
-        let now = Utc::now();
-        let token = RegistrationToken {
-            id: RegistrationTokenId::new(1),
-            token_hash: "hash".to_string(),
-            created_at: now - Duration::hours(2),
-            expires_at: now - Duration::hours(1),
-            used_at: None,
-            used_by_user_id: None,
-        };
-        assert!(!token.is_valid());
-        assert!(token.is_expired());
+        let token = token(Utc::now() - Duration::hours(1), 1, 0);
+        assert!(!token.is_valid(Utc::now()));
+        assert!(token.is_expired(Utc::now()));
     }
 
     #[test]
     fn token_used() {
-        let now = Utc::now();
-        let token = RegistrationToken {
-            id: RegistrationTokenId::new(1),
-            token_hash: "hash".to_string(),
-            created_at: now,
-            expires_at: now + Duration::hours(1),
-            used_at: Some(now),
-            used_by_user_id: Some(UserId::new(1)),
-        };
-        assert!(!token.is_valid());
+        let token = token(Utc::now() + Duration::hours(1), 1, 1);
+        assert!(!token.is_valid(Utc::now()));
         assert!(token.is_used());
     }
 
     #[test]
     fn token_expired_and_used() {
-        let now = Utc::now();
-        let token = RegistrationToken {
-            id: RegistrationTokenId::new(1),
-            token_hash: "hash".to_string(),
-            created_at: now - Duration::hours(2),
-            expires_at: now - Duration::hours(1),
-            used_at: Some(now - Duration::minutes(30)),
-            used_by_user_id: Some(UserId::new(1)),
-        };
-        assert!(!token.is_valid());
+        let token = token(Utc::now() - Duration::hours(1), 1, 1);
+        assert!(!token.is_valid(Utc::now()));
+    }
+
+    #[test]
+    fn multi_use_token_valid_until_exhausted() {
+        let expires_at = Utc::now() + Duration::hours(1);
+        assert!(token(expires_at, 3, 2).is_valid(Utc::now()));
+        assert!(token(expires_at, 3, 3).is_exhausted());
+        assert!(!token(expires_at, 3, 3).is_valid(Utc::now()));
+    }
+
+    #[test]
+    fn token_revoked() {
+        let mut token = token(Utc::now() + Duration::hours(1), 3, 0);
+        token.revoked_at = Some(Utc::now());
+        assert!(!token.is_valid(Utc::now()));
     }
 
     #[test]
@@ -119,5 +178,47 @@ mod tests {
         let token = NewRegistrationToken::new("hash".to_string(), now, excessive_expires);
         let expected_max = now + MAX_TOKEN_DURATION;
         assert_eq!(token.expires_at, expected_max);
+        assert_eq!(token.max_uses, 1);
+    }
+
+    #[test]
+    fn create_request_defaults_and_bounds() {
+        let now = Utc::now();
+        let create = |max_uses, expires_in_hours| CreateRegistrationToken {
+            max_uses,
+            expires_in_hours,
+        };
+
+        let token = create(None, None)
+            .into_new_token("hash".to_string(), now)
+            .unwrap();
+        assert_eq!(token.max_uses, 1);
+        assert_eq!(token.expires_at, now + Duration::hours(24));
+
+        let token = create(Some(4), Some(168))
+            .into_new_token("hash".to_string(), now)
+            .unwrap();
+        assert_eq!(token.max_uses, 4);
+
+        assert!(
+            create(Some(0), None)
+                .into_new_token(String::new(), now)
+                .is_err()
+        );
+        assert!(
+            create(Some(21), None)
+                .into_new_token(String::new(), now)
+                .is_err()
+        );
+        assert!(
+            create(None, Some(0))
+                .into_new_token(String::new(), now)
+                .is_err()
+        );
+        assert!(
+            create(None, Some(169))
+                .into_new_token(String::new(), now)
+                .is_err()
+        );
     }
 }
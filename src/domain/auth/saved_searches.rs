@@ -0,0 +1,143 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::ids::{SavedSearchId, UserId};
+use crate::domain::list_preferences::ListName;
+use crate::domain::listing::SortDirection;
+
+pub const MAX_SAVED_SEARCH_NAME_LENGTH: usize = 80;
+
+/// A named combination of search text, filters and sort for one list.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SavedSearch {
+    pub id: SavedSearchId,
+    pub user_id: UserId,
+    pub name: String,
+    pub list: ListName,
+    pub search: Option<String>,
+    /// Filter query parameters, e.g. `status=open` for bags.
+    pub filters: BTreeMap<String, String>,
+    pub sort_key: Option<String>,
+    pub sort_direction: Option<SortDirection>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewSavedSearch {
+    pub user_id: UserId,
+    pub name: String,
+    pub list: ListName,
+    pub search: Option<String>,
+    pub filters: BTreeMap<String, String>,
+    pub sort_key: Option<String>,
+    pub sort_direction: Option<SortDirection>,
+}
+
+impl NewSavedSearch {
+    /// Trim the name and search text, dropping empty search text and filters.
+    pub fn normalize(mut self) -> Self {
+        self.name = self.name.trim().to_string();
+        self.search = self
+            .search
+            .map(|search| search.trim().to_string())
+            .filter(|search| !search.is_empty());
+        self.filters.retain(|_, value| !value.is_empty());
+        self.sort_key = self.sort_key.filter(|key| !key.trim().is_empty());
+        self
+    }
+
+    /// Check the name is present and every filter and sort key belongs to the list.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.is_empty() {
+            return Err("name is required".to_string());
+        }
+        if self.name.chars().count() > MAX_SAVED_SEARCH_NAME_LENGTH {
+            return Err(format!(
+                "name must be at most {MAX_SAVED_SEARCH_NAME_LENGTH} characters"
+            ));
+        }
+        for (key, value) in &self.filters {
+            if !self.list.accepts_filter(key, value) {
+                return Err(format!(
+                    "unsupported filter '{key}={value}' for {}",
+                    self.list
+                ));
+            }
+        }
+        if let Some(key) = self.sort_key.as_deref()
+            && !self.list.accepts_sort_key(key)
+        {
+            return Err(format!("unknown sort key '{key}' for {}", self.list));
+        }
+        if self.sort_direction.is_some() && self.sort_key.is_none() {
+            return Err("sort direction requires a sort key".to_string());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn search(list: ListName) -> NewSavedSearch {
+        NewSavedSearch {
+            user_id: UserId::new(1),
+            name: "Light Ethiopian, unopened bags".to_string(),
+            list,
+            search: Some("Ethiopia".to_string()),
+            filters: BTreeMap::new(),
+            sort_key: None,
+            sort_direction: None,
+        }
+    }
+
+    #[test]
+    fn normalize_trims_and_drops_empty_values() {
+        let mut new = search(ListName::Bags);
+        new.name = "  Open bags ".to_string();
+        new.search = Some("   ".to_string());
+        new.filters.insert("status".to_string(), String::new());
+        new.sort_key = Some(String::new());
+
+        let new = new.normalize();
+        assert_eq!(new.name, "Open bags");
+        assert_eq!(new.search, None);
+        assert!(new.filters.is_empty());
+        assert_eq!(new.sort_key, None);
+    }
+
+    #[test]
+    fn validate_accepts_filters_for_list() {
+        let mut new = search(ListName::Bags);
+        new.filters.insert("status".to_string(), "open".to_string());
+        new.sort_key = Some("roaster".to_string());
+        new.sort_direction = Some(SortDirection::Asc);
+        assert!(new.validate().is_ok());
+    }
+
+    #[test]
+    fn validate_rejects_filters_from_another_list() {
+        let mut new = search(ListName::Roasts);
+        new.filters.insert("status".to_string(), "open".to_string());
+        assert!(new.validate().is_err());
+    }
+
+    #[test]
+    fn validate_rejects_unknown_filter_value() {
+        let mut new = search(ListName::Gear);
+        new.filters
+            .insert("category".to_string(), "scale".to_string());
+        assert!(new.validate().is_err());
+    }
+
+    #[test]
+    fn validate_requires_name() {
+        let mut new = search(ListName::Brews);
+        new.name = String::new();
+        assert!(new.validate().is_err());
+    }
+}
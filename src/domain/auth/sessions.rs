@@ -7,6 +7,19 @@ use crate::domain::ids::{SessionId, UserId};
 /// are clamped to `created_at + MAX_SESSION_DURATION`.
 pub const MAX_SESSION_DURATION: Duration = Duration::days(30);
 
+/// How stale a session's last-seen time may get before a request refreshes
+/// it, so browsing doesn't write to the database on every page.
+pub const LAST_SEEN_GRANULARITY: Duration = Duration::minutes(1);
+
+/// The device a session was signed in on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionDevice {
+    /// Hash of the device's [`user_agent_family`].
+    pub fingerprint: String,
+    /// What the device looks like, e.g. "Firefox on Linux".
+    pub label: String,
+}
+
 #[derive(Clone, Serialize, Deserialize)]
 pub struct Session {
     pub id: SessionId,
@@ -15,6 +28,12 @@ pub struct Session {
     pub session_token_hash: String,
     pub created_at: DateTime<Utc>,
     pub expires_at: DateTime<Utc>,
+    /// `None` until the session is first used from a known device.
+    #[serde(skip_serializing)]
+    pub device_fingerprint: Option<String>,
+    pub device_label: Option<String>,
+    pub last_seen_ip: Option<String>,
+    pub last_seen_at: Option<DateTime<Utc>>,
 }
 
 impl std::fmt::Debug for Session {
@@ -25,6 +44,13 @@ impl std::fmt::Debug for Session {
             .field("session_token_hash", &"<redacted>")
             .field("created_at", &self.created_at)
             .field("expires_at", &self.expires_at)
+            .field(
+                "device_fingerprint",
+                &self.device_fingerprint.as_ref().map(|_| "<redacted>"),
+            )
+            .field("device_label", &self.device_label)
+            .field("last_seen_ip", &self.last_seen_ip)
+            .field("last_seen_at", &self.last_seen_at)
             .finish()
     }
 }
@@ -43,11 +69,32 @@ impl Session {
             session_token_hash,
             created_at,
             expires_at,
+            device_fingerprint: None,
+            device_label: None,
+            last_seen_ip: None,
+            last_seen_at: None,
         }
     }
 
-    pub fn is_expired(&self) -> bool {
-        Utc::now() > self.expires_at
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        now > self.expires_at
+    }
+
+    /// Whether a request with `fingerprint` may use this session. A session
+    /// not yet bound to a device accepts any.
+    pub fn matches_device(&self, fingerprint: &str) -> bool {
+        self.device_fingerprint
+            .as_deref()
+            .is_none_or(|bound| bound == fingerprint)
+    }
+
+    /// Whether a request from `ip` at `now` should update where and when the
+    /// session was last seen.
+    pub fn needs_touch(&self, ip: Option<&str>, now: DateTime<Utc>) -> bool {
+        self.last_seen_ip.as_deref() != ip
+            || self
+                .last_seen_at
+                .is_none_or(|seen| now - seen >= LAST_SEEN_GRANULARITY)
     }
 }
 
@@ -57,6 +104,8 @@ pub struct NewSession {
     pub session_token_hash: String,
     pub created_at: DateTime<Utc>,
     pub expires_at: DateTime<Utc>,
+    pub device: Option<SessionDevice>,
+    pub ip: Option<String>,
 }
 
 impl NewSession {
@@ -72,8 +121,71 @@ impl NewSession {
             session_token_hash,
             created_at,
             expires_at: expires_at.min(max_expires),
+            device: None,
+            ip: None,
         }
     }
+
+    /// Binds the session to the device, and address, it is being signed in
+    /// from.
+    #[must_use]
+    pub fn on_device(mut self, device: SessionDevice, ip: Option<String>) -> Self {
+        self.device = Some(device);
+        self.ip = ip;
+        self
+    }
+}
+
+/// `user_agent` with its version numbers collapsed, so a browser or OS update
+/// leaves a device recognisable while a different browser does not.
+pub fn user_agent_family(user_agent: &str) -> String {
+    let mut family = String::with_capacity(user_agent.len());
+    let mut in_version = false;
+    for c in user_agent.trim().chars() {
+        if in_version && (c.is_ascii_digit() || c == '.' || c == '_') {
+            continue;
+        }
+        in_version = c.is_ascii_digit();
+        family.push(if in_version { '#' } else { c });
+    }
+    family
+}
+
+/// A readable name for the device behind `user_agent`, e.g. "Firefox on
+/// Linux".
+pub fn describe_user_agent(user_agent: &str) -> String {
+    const BROWSERS: &[(&str, &str)] = &[
+        ("Edg/", "Edge"),
+        ("OPR/", "Opera"),
+        ("Firefox/", "Firefox"),
+        ("FxiOS/", "Firefox"),
+        ("CriOS/", "Chrome"),
+        ("Chrome/", "Chrome"),
+        ("Safari/", "Safari"),
+        ("curl/", "curl"),
+    ];
+    // Checked in order: Android and iOS agents also mention Linux and Mac OS X.
+    const SYSTEMS: &[(&str, &str)] = &[
+        ("Windows", "Windows"),
+        ("Android", "Android"),
+        ("iPhone", "iOS"),
+        ("iPad", "iPadOS"),
+        ("CrOS", "ChromeOS"),
+        ("Mac OS X", "macOS"),
+        ("Linux", "Linux"),
+    ];
+
+    let find = |table: &[(&'static str, &'static str)]| {
+        table
+            .iter()
+            .find(|(marker, _)| user_agent.contains(marker))
+            .map(|(_, name)| *name)
+    };
+    match (find(BROWSERS), find(SYSTEMS)) {
+        (Some(browser), Some(system)) => format!("{browser} on {system}"),
+        (Some(name), None) | (None, Some(name)) => name.to_string(),
+        (None, None) => "Unknown device".to_string(),
+    }
 }
 
 #[cfg(test)]
@@ -90,7 +202,7 @@ mod tests {
             now,
             now + Duration::hours(1),
         );
-        assert!(!session.is_expired());
+        assert!(!session.is_expired(now));
     }
 
     #[test]
@@ -103,7 +215,7 @@ mod tests {
             now - Duration::hours(2),
             now - Duration::hours(1),
         );
-        assert!(session.is_expired());
+        assert!(session.is_expired(now));
     }
 
     #[test]
@@ -114,4 +226,68 @@ mod tests {
         let expected_max = now + MAX_SESSION_DURATION;
         assert_eq!(session.expires_at, expected_max);
     }
+
+    const FIREFOX_128: &str =
+        "Mozilla/5.0 (X11; Linux x86_64; rv:128.0) Gecko/20100101 Firefox/128.0";
+    const FIREFOX_129: &str =
+        "Mozilla/5.0 (X11; Linux x86_64; rv:129.0.1) Gecko/20100101 Firefox/129.0.1";
+    const IPHONE_SAFARI: &str = "Mozilla/5.0 (iPhone; CPU iPhone OS 17_5 like Mac OS X) \
+        AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.5 Mobile/15E148 Safari/604.1";
+
+    #[test]
+    fn updates_keep_the_family() {
+        assert_eq!(
+            user_agent_family(FIREFOX_128),
+            user_agent_family(FIREFOX_129)
+        );
+        assert_ne!(
+            user_agent_family(FIREFOX_128),
+            user_agent_family(IPHONE_SAFARI)
+        );
+        assert_eq!(user_agent_family("curl/8.5.0"), "curl/#");
+    }
+
+    #[test]
+    fn describes_devices() {
+        assert_eq!(describe_user_agent(FIREFOX_128), "Firefox on Linux");
+        assert_eq!(describe_user_agent(IPHONE_SAFARI), "Safari on iOS");
+        assert_eq!(describe_user_agent("curl/8.5.0"), "curl");
+        assert_eq!(describe_user_agent(""), "Unknown device");
+    }
+
+    #[test]
+    fn unbound_sessions_match_any_device() {
+        let now = Utc::now();
+        let mut session = Session::new(
+            SessionId::new(1),
+            UserId::new(1),
+            "hash".to_string(),
+            now,
+            now + Duration::hours(1),
+        );
+        assert!(session.matches_device("laptop"));
+
+        session.device_fingerprint = Some("laptop".to_string());
+        assert!(session.matches_device("laptop"));
+        assert!(!session.matches_device("phone"));
+    }
+
+    #[test]
+    fn touches_only_when_stale_or_moved() {
+        let now = Utc::now();
+        let mut session = Session::new(
+            SessionId::new(1),
+            UserId::new(1),
+            "hash".to_string(),
+            now,
+            now + Duration::hours(1),
+        );
+        assert!(session.needs_touch(None, now));
+
+        session.last_seen_at = Some(now);
+        session.last_seen_ip = Some("192.0.2.1".to_string());
+        assert!(!session.needs_touch(Some("192.0.2.1"), now + Duration::seconds(5)));
+        assert!(session.needs_touch(Some("192.0.2.2"), now + Duration::seconds(5)));
+        assert!(session.needs_touch(Some("192.0.2.1"), now + LAST_SEEN_GRANULARITY));
+    }
 }
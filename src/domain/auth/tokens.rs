@@ -19,6 +19,8 @@ pub struct Token {
 pub struct NewToken {
     pub user_id: UserId,
     pub token_hash: String,
+    /// Leading characters of the raw token, set when `token_hash` is salted.
+    pub lookup_prefix: Option<String>,
     pub name: String,
 }
 
@@ -27,6 +29,7 @@ impl std::fmt::Debug for NewToken {
         f.debug_struct("NewToken")
             .field("user_id", &self.user_id)
             .field("token_hash", &"<redacted>")
+            .field("lookup_prefix", &self.lookup_prefix)
             .field("name", &self.name)
             .finish()
     }
@@ -67,9 +70,15 @@ impl NewToken {
         Self {
             user_id,
             token_hash,
+            lookup_prefix: None,
             name,
         }
     }
+
+    pub fn with_lookup_prefix(mut self, lookup_prefix: Option<String>) -> Self {
+        self.lookup_prefix = lookup_prefix;
+        self
+    }
 }
 
 #[cfg(test)]
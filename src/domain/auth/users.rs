@@ -2,9 +2,11 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 use crate::domain::ids::UserId;
+use crate::domain::validation::{Validate, ValidationErrors};
 
 pub const MIN_USERNAME_LEN: usize = 3;
 pub const MAX_USERNAME_LEN: usize = 32;
+pub const MAX_DISPLAY_NAME_LEN: usize = 64;
 
 /// Returns `true` if the username contains only allowed characters
 /// (alphanumeric, underscore, hyphen) and is within length bounds.
@@ -20,6 +22,8 @@ pub struct User {
     pub id: UserId,
     pub username: String,
     pub uuid: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
     pub created_at: DateTime<Utc>,
 }
 
@@ -30,29 +34,67 @@ pub struct NewUser {
 }
 
 impl User {
-    pub fn new(id: UserId, username: String, uuid: String, created_at: DateTime<Utc>) -> Self {
+    pub fn new(
+        id: UserId,
+        username: String,
+        uuid: String,
+        display_name: Option<String>,
+        created_at: DateTime<Utc>,
+    ) -> Self {
         Self {
             id,
             username,
             uuid,
+            display_name,
             created_at,
         }
     }
+
+    /// The display name if one is set, otherwise the username.
+    pub fn name(&self) -> &str {
+        self.display_name.as_deref().unwrap_or(&self.username)
+    }
+}
+
+/// Changes to the signed-in user's profile.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UpdateProfile {
+    /// An empty value clears the display name.
+    #[serde(default)]
+    pub display_name: Option<String>,
+}
+
+impl UpdateProfile {
+    /// The display name to store: trimmed, with blanks mapped to `None`.
+    pub fn normalized_display_name(&self) -> Result<Option<String>, String> {
+        let name = crate::domain::coffee::normalize_optional_field(self.display_name.clone());
+        if let Some(name) = &name
+            && name.chars().count() > MAX_DISPLAY_NAME_LEN
+        {
+            return Err(format!(
+                "display name must be at most {MAX_DISPLAY_NAME_LEN} characters"
+            ));
+        }
+        Ok(name)
+    }
 }
 
 impl NewUser {
     pub fn new(username: String, uuid: String) -> Self {
         Self { username, uuid }
     }
+}
 
-    /// Validates that the username meets length and character constraints.
-    pub fn validate(&self) -> Result<(), &'static str> {
-        if !is_valid_username(&self.username) {
-            return Err(
-                "Username must be 3-32 characters and contain only alphanumeric, underscore, or hyphen characters",
-            );
-        }
-        Ok(())
+impl Validate for NewUser {
+    /// Checks that the username meets length and character constraints.
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+        errors.check(
+            is_valid_username(&self.username),
+            "username",
+            "Username must be 3-32 characters and contain only alphanumeric, underscore, or hyphen characters",
+        );
+        errors.into_result()
     }
 }
 
@@ -99,4 +141,33 @@ mod tests {
     fn username_max_length() {
         assert!(is_valid_username(&"a".repeat(32)));
     }
+
+    #[test]
+    fn name_falls_back_to_username() {
+        let mut user = User::new(
+            UserId::new(1),
+            "alice".to_string(),
+            "uuid".to_string(),
+            None,
+            Utc::now(),
+        );
+        assert_eq!(user.name(), "alice");
+
+        user.display_name = Some("Alice Smith".to_string());
+        assert_eq!(user.name(), "Alice Smith");
+    }
+
+    #[test]
+    fn update_profile_trims_and_bounds_display_name() {
+        let update = |name: &str| UpdateProfile {
+            display_name: Some(name.to_string()),
+        };
+
+        assert_eq!(
+            update("  Alice ").normalized_display_name(),
+            Ok(Some("Alice".to_string()))
+        );
+        assert_eq!(update("   ").normalized_display_name(), Ok(None));
+        assert!(update(&"a".repeat(65)).normalized_display_name().is_err());
+    }
 }
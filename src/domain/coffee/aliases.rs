@@ -0,0 +1,107 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::ids::{AliasId, RoastId, RoasterId};
+
+const MAX_NAME_CHARS: usize = 200;
+
+/// What an alias is another name for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AliasTarget {
+    Roaster(RoasterId),
+    Roast(RoastId),
+}
+
+/// A name a roaster or roast has also gone by, such as before a rebrand or a
+/// merge. Exactly one of `roaster_id` and `roast_id` is set.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Alias {
+    pub id: AliasId,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub roaster_id: Option<RoasterId>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub roast_id: Option<RoastId>,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewAlias {
+    pub target: AliasTarget,
+    pub name: String,
+}
+
+impl NewAlias {
+    /// Aliases are matched by slug, like the names they stand in for.
+    pub fn slug(&self) -> String {
+        slug::slugify(&self.name)
+    }
+}
+
+/// Request body for adding an alias.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AliasInput {
+    pub name: String,
+}
+
+impl AliasInput {
+    /// The trimmed name, or an error if it is empty or too long.
+    pub fn normalized_name(&self) -> Result<String, String> {
+        let name = self.name.trim();
+        if slug::slugify(name).is_empty() {
+            return Err("alias name is required".to_string());
+        }
+        if name.chars().count() > MAX_NAME_CHARS {
+            return Err(format!(
+                "alias name must be at most {MAX_NAME_CHARS} characters"
+            ));
+        }
+        Ok(name.to_string())
+    }
+}
+
+/// Request body for merging a roaster or roast into another.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct MergeInput<Id> {
+    /// The record that absorbs the merged one and keeps its name as an alias.
+    pub into: Id,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(name: &str) -> AliasInput {
+        AliasInput {
+            name: name.to_string(),
+        }
+    }
+
+    #[test]
+    fn normalized_name_trims_whitespace() {
+        assert_eq!(
+            input("  Origin Roasting \n").normalized_name(),
+            Ok("Origin Roasting".to_string())
+        );
+    }
+
+    #[test]
+    fn normalized_name_rejects_names_without_a_slug() {
+        assert!(input("   ").normalized_name().is_err());
+        assert!(input("!!!").normalized_name().is_err());
+        assert!(
+            input(&"a".repeat(MAX_NAME_CHARS + 1))
+                .normalized_name()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn aliases_match_by_slug() {
+        let alias = NewAlias {
+            target: AliasTarget::Roaster(RoasterId::new(1)),
+            name: "Origin Coffee Roasters".to_string(),
+        };
+        assert_eq!(alias.slug(), "origin-coffee-roasters");
+    }
+}
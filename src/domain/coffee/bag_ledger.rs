@@ -0,0 +1,322 @@
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::ErrorCode;
+use crate::domain::bags::Bag;
+use crate::domain::ids::{BagId, BagLedgerEntryId, BrewId, UserId};
+
+const MAX_REASON_CHARS: usize = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BagLedgerKind {
+    /// The amount the bag was opened with.
+    Initial,
+    Brew,
+    /// A manual correction, always with a reason.
+    Adjustment,
+    /// Coffee moved to or from another bag.
+    Transfer,
+}
+
+impl BagLedgerKind {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Initial => "initial",
+            Self::Brew => "brew",
+            Self::Adjustment => "adjustment",
+            Self::Transfer => "transfer",
+        }
+    }
+}
+
+impl FromStr for BagLedgerKind {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "initial" => Ok(Self::Initial),
+            "brew" => Ok(Self::Brew),
+            "adjustment" => Ok(Self::Adjustment),
+            "transfer" => Ok(Self::Transfer),
+            _ => Err(()),
+        }
+    }
+}
+
+/// One change to what is left in a bag. A bag's remaining weight is the sum
+/// of its entries, which are never edited or removed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BagLedgerEntry {
+    pub id: BagLedgerEntryId,
+    pub bag_id: BagId,
+    pub kind: BagLedgerKind,
+    /// Grams added (positive) or taken out (negative).
+    pub delta: f64,
+    /// Grams left in the bag once this entry was applied.
+    pub balance: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub brew_id: Option<BrewId>,
+    /// The other bag in a transfer.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub counterpart_bag_id: Option<BagId>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_by: Option<UserId>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A correction bringing a bag to `remaining` grams. The ledger records the
+/// difference from whatever the balance is when it is applied.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewBagAdjustment {
+    pub bag_id: BagId,
+    pub remaining: f64,
+    pub reason: String,
+    pub created_by: Option<UserId>,
+}
+
+/// Request body for correcting a bag's remaining weight.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BagCorrection {
+    pub remaining: f64,
+    pub reason: String,
+}
+
+impl BagCorrection {
+    /// Check the correction against the bag it applies to.
+    pub fn into_adjustment(
+        self,
+        bag: &Bag,
+        created_by: Option<UserId>,
+    ) -> Result<NewBagAdjustment, String> {
+        if !self.remaining.is_finite() || self.remaining < 0.0 {
+            return Err("remaining must be zero or more".to_string());
+        }
+        if self.remaining > bag.amount {
+            return Err(format!(
+                "remaining cannot exceed the bag amount of {}g",
+                bag.amount
+            ));
+        }
+        if (self.remaining - bag.remaining).abs() < f64::EPSILON {
+            return Err(format!("the bag already has {}g left", bag.remaining));
+        }
+
+        Ok(NewBagAdjustment {
+            bag_id: bag.id,
+            remaining: self.remaining,
+            reason: normalize_reason(&self.reason)?,
+            created_by,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewBagTransfer {
+    pub from_bag_id: BagId,
+    pub to_bag_id: BagId,
+    pub amount: f64,
+    pub created_by: Option<UserId>,
+}
+
+/// Request body for moving coffee from one bag into another.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BagTransferRequest {
+    pub to_bag_id: BagId,
+    pub amount: f64,
+}
+
+impl BagTransferRequest {
+    /// Check the transfer against both bags as they stand now. Failures carry
+    /// the [`ErrorCode`] a client would branch on.
+    pub fn into_transfer(
+        self,
+        from: &Bag,
+        to: &Bag,
+        created_by: Option<UserId>,
+    ) -> Result<NewBagTransfer, (ErrorCode, String)> {
+        if from.id == to.id {
+            return Err((
+                ErrorCode::BadRequest,
+                "cannot transfer coffee into the same bag".to_string(),
+            ));
+        }
+        if from.closed || to.closed {
+            return Err((ErrorCode::BagClosed, "both bags must be open".to_string()));
+        }
+        if !self.amount.is_finite() || self.amount <= 0.0 {
+            return Err((ErrorCode::BadRequest, "amount must be positive".to_string()));
+        }
+        if self.amount > from.remaining {
+            return Err((
+                ErrorCode::BagInsufficientRemaining,
+                format!("the bag only has {}g left", from.remaining),
+            ));
+        }
+        if to.remaining + self.amount > to.amount {
+            return Err((
+                ErrorCode::BagNoRoom,
+                format!(
+                    "the receiving bag only has room for {}g",
+                    to.amount - to.remaining
+                ),
+            ));
+        }
+
+        Ok(NewBagTransfer {
+            from_bag_id: from.id,
+            to_bag_id: to.id,
+            amount: self.amount,
+            created_by,
+        })
+    }
+}
+
+/// Both sides of a transfer.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BagTransfer {
+    pub from: BagLedgerEntry,
+    pub to: BagLedgerEntry,
+}
+
+fn normalize_reason(reason: &str) -> Result<String, String> {
+    let reason = reason.trim();
+    if reason.is_empty() {
+        return Err("a reason is required".to_string());
+    }
+    if reason.chars().count() > MAX_REASON_CHARS {
+        return Err(format!(
+            "reason must be at most {MAX_REASON_CHARS} characters"
+        ));
+    }
+    Ok(reason.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::ids::RoastId;
+
+    fn bag(id: i64, remaining: f64) -> Bag {
+        Bag {
+            id: BagId::new(id),
+            roast_id: RoastId::new(1),
+            roast_date: None,
+            amount: 250.0,
+            remaining,
+            closed: false,
+            finished_at: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            created_by: None,
+            low_stock_threshold: None,
+            price: None,
+            currency: None,
+            best_before: None,
+            batch_code: None,
+        }
+    }
+
+    fn correction(remaining: f64, reason: &str) -> BagCorrection {
+        BagCorrection {
+            remaining,
+            reason: reason.to_string(),
+        }
+    }
+
+    fn transfer(to: i64, amount: f64) -> BagTransferRequest {
+        BagTransferRequest {
+            to_bag_id: BagId::new(to),
+            amount,
+        }
+    }
+
+    #[test]
+    fn kind_round_trips_through_its_string_form() {
+        for kind in [
+            BagLedgerKind::Initial,
+            BagLedgerKind::Brew,
+            BagLedgerKind::Adjustment,
+            BagLedgerKind::Transfer,
+        ] {
+            assert_eq!(BagLedgerKind::from_str(kind.as_str()), Ok(kind));
+        }
+        assert!(BagLedgerKind::from_str("refund").is_err());
+    }
+
+    #[test]
+    fn corrections_are_trimmed_and_tied_to_the_bag() {
+        let adjustment = correction(100.0, "  Spilled some ")
+            .into_adjustment(&bag(3, 120.0), Some(UserId::new(1)))
+            .unwrap();
+        assert_eq!(
+            adjustment,
+            NewBagAdjustment {
+                bag_id: BagId::new(3),
+                remaining: 100.0,
+                reason: "Spilled some".to_string(),
+                created_by: Some(UserId::new(1)),
+            }
+        );
+    }
+
+    #[test]
+    fn corrections_must_change_a_plausible_weight_for_a_reason() {
+        for invalid in [
+            correction(-1.0, "Weighed it"),
+            correction(f64::NAN, "Weighed it"),
+            correction(300.0, "Weighed it"),
+            correction(120.0, "Weighed it"),
+            correction(100.0, "   "),
+            correction(100.0, &"x".repeat(MAX_REASON_CHARS + 1)),
+        ] {
+            assert!(invalid.into_adjustment(&bag(3, 120.0), None).is_err());
+        }
+    }
+
+    #[test]
+    fn transfers_fit_what_one_bag_has_and_the_other_can_take() {
+        let (from, to) = (bag(1, 40.0), bag(2, 200.0));
+        assert_eq!(
+            transfer(2, 30.0).into_transfer(&from, &to, None),
+            Ok(NewBagTransfer {
+                from_bag_id: BagId::new(1),
+                to_bag_id: BagId::new(2),
+                amount: 30.0,
+                created_by: None,
+            })
+        );
+
+        assert!(transfer(2, 0.0).into_transfer(&from, &to, None).is_err());
+        assert_eq!(
+            transfer(2, 45.0)
+                .into_transfer(&from, &to, None)
+                .unwrap_err()
+                .0,
+            ErrorCode::BagInsufficientRemaining
+        );
+        assert!(transfer(2, 40.0).into_transfer(&from, &to, None).is_ok());
+        assert!(transfer(1, 10.0).into_transfer(&from, &from, None).is_err());
+
+        let nearly_full = bag(2, 240.0);
+        assert_eq!(
+            transfer(2, 20.0)
+                .into_transfer(&from, &nearly_full, None)
+                .unwrap_err()
+                .0,
+            ErrorCode::BagNoRoom
+        );
+
+        let mut closed = bag(2, 0.0);
+        closed.closed = true;
+        assert!(
+            transfer(2, 10.0)
+                .into_transfer(&from, &closed, None)
+                .is_err()
+        );
+    }
+}
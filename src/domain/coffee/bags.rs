@@ -2,11 +2,14 @@ use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
 use serde::{Deserialize, Serialize};
 
 use crate::define_sort_key;
+use crate::domain::currencies::validate_currency;
 use crate::domain::entity_type::EntityType;
-use crate::domain::ids::{BagId, RoastId};
+use crate::domain::ids::{BagId, RoastId, UserId};
 use crate::domain::roasters::Roaster;
 use crate::domain::roasts::Roast;
+use crate::domain::settings::validate_low_stock_threshold;
 use crate::domain::timeline::{NewTimelineEvent, TimelineEventDetail};
+use crate::domain::validation::{Validate, ValidationErrors};
 
 /// 23:59:59 — used when converting a date-only `finished_at` to a datetime
 /// so that bag "finished" events sort after same-day brews.
@@ -15,6 +18,19 @@ pub const END_OF_DAY: NaiveTime = match NaiveTime::from_hms_opt(23, 59, 59) {
     None => unreachable!(),
 };
 
+/// Default low-stock threshold, used until the instance setting is changed.
+pub const LOW_STOCK_GRAMS: f64 = 50.0;
+
+/// Default dust threshold: open bags with less than this left after a brew
+/// are as good as empty, so the brewer is offered to mark them finished.
+pub const DUST_GRAMS: f64 = 8.0;
+
+/// How many days ahead of its best-before date a bag starts being flagged.
+pub const BEST_BEFORE_WARNING_DAYS: i64 = 14;
+
+/// Batch codes are short identifiers printed on the label.
+const MAX_BATCH_CODE_LEN: usize = 64;
+
 /// Deserializes a datetime that accepts both RFC 3339 (`2025-02-24T15:30:00Z`)
 /// and date-only (`2025-02-24`) formats. Date-only values become 23:59:59 UTC
 /// so bag "finished" events sort after same-day brews.
@@ -54,6 +70,88 @@ pub struct Bag {
     pub finished_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_by: Option<UserId>,
+    /// Overrides the instance-wide low-stock threshold for this bag.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub low_stock_threshold: Option<f64>,
+    /// What was paid for the bag.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub price: Option<f64>,
+    /// The currency `price` was paid in; unset means the base currency.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
+    /// The date printed on the label to drink the coffee by.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub best_before: Option<NaiveDate>,
+    /// The roaster's code for the batch the bag was packed from.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub batch_code: Option<String>,
+}
+
+impl Bag {
+    /// The threshold that applies to this bag: its own, or `default`.
+    pub fn reorder_threshold(&self, default: f64) -> f64 {
+        self.low_stock_threshold.unwrap_or(default)
+    }
+
+    /// Whether this open bag is below its threshold and due a reorder.
+    pub fn is_running_low(&self, default: f64) -> bool {
+        !self.closed && self.remaining < self.reorder_threshold(default)
+    }
+
+    /// Whether using `used` grams just took this bag below its low-stock
+    /// threshold. Only the brew that crosses the line counts, so the bag is
+    /// flagged once rather than on every later brew.
+    pub fn just_ran_low(&self, used: f64, default: f64) -> bool {
+        self.is_running_low(default) && self.remaining + used >= self.reorder_threshold(default)
+    }
+
+    /// Where this open bag stands against its best-before date on `today`.
+    /// Bags without one, and finished bags, are never flagged.
+    pub fn best_before_status(&self, today: NaiveDate) -> Option<BestBeforeStatus> {
+        let best_before = self.best_before.filter(|_| !self.closed)?;
+        let days_left = (best_before - today).num_days();
+        if days_left < 0 {
+            Some(BestBeforeStatus::Past)
+        } else if days_left <= BEST_BEFORE_WARNING_DAYS {
+            Some(BestBeforeStatus::Soon)
+        } else {
+            None
+        }
+    }
+
+    /// Suggest finishing this bag if it is open with less than `dust` grams
+    /// left. A threshold of zero never suggests anything.
+    pub fn finish_suggestion(&self, dust: f64) -> Option<BagFinishSuggestion> {
+        (!self.closed && self.remaining < dust).then_some(BagFinishSuggestion {
+            bag_id: self.id,
+            remaining: self.remaining,
+        })
+    }
+}
+
+/// An open bag nearing or past the best-before date on its label.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BestBeforeStatus {
+    Soon,
+    Past,
+}
+
+impl BestBeforeStatus {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Soon => "Best before soon",
+            Self::Past => "Past best before",
+        }
+    }
+}
+
+/// Returned alongside a new brew when its bag is down to the dust.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BagFinishSuggestion {
+    pub bag_id: BagId,
+    pub remaining: f64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -72,6 +170,14 @@ pub struct NewBag {
     pub roast_date: Option<NaiveDate>,
     pub amount: f64,
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub price: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub best_before: Option<NaiveDate>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub batch_code: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub created_at: Option<DateTime<Utc>>,
 }
 
@@ -89,6 +195,72 @@ pub struct UpdateBag {
     pub finished_at: Option<DateTime<Utc>>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub created_at: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub low_stock_threshold: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub price: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub best_before: Option<NaiveDate>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub batch_code: Option<String>,
+}
+
+impl Validate for NewBag {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+        errors.check(
+            self.roast_id.into_inner() > 0,
+            "roast_id",
+            "invalid roast id",
+        );
+        errors.check(self.amount > 0.0, "amount", "amount must be positive");
+        if let Some(price) = self.price {
+            errors.check(price >= 0.0, "price", "price cannot be negative");
+        }
+        check_batch_code(&mut errors, self.batch_code.as_deref());
+        if let Some(currency) = &self.currency {
+            errors.check_result("currency", validate_currency(currency));
+        }
+        errors.into_result()
+    }
+}
+
+impl Validate for UpdateBag {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+        if let Some(roast_id) = self.roast_id {
+            errors.check(roast_id.into_inner() > 0, "roast_id", "invalid roast id");
+        }
+        if let Some(amount) = self.amount {
+            errors.check(amount > 0.0, "amount", "amount must be positive");
+        }
+        if let Some(threshold) = self.low_stock_threshold {
+            errors.check_result(
+                "low_stock_threshold",
+                validate_low_stock_threshold(threshold),
+            );
+        }
+        if let Some(price) = self.price {
+            errors.check(price >= 0.0, "price", "price cannot be negative");
+        }
+        check_batch_code(&mut errors, self.batch_code.as_deref());
+        if let Some(currency) = &self.currency {
+            errors.check_result("currency", validate_currency(currency));
+        }
+        errors.into_result()
+    }
+}
+
+fn check_batch_code(errors: &mut ValidationErrors, batch_code: Option<&str>) {
+    if let Some(code) = batch_code {
+        errors.check(
+            code.chars().count() <= MAX_BATCH_CODE_LEN,
+            "batch_code",
+            format!("batch code must be at most {MAX_BATCH_CODE_LEN} characters"),
+        );
+    }
 }
 
 /// Filter criteria for bag queries.
@@ -171,5 +343,96 @@ pub fn bag_timeline_event(
         slug: Some(roast.slug.clone()),
         roaster_slug: Some(roaster.slug.clone()),
         brew_data: None,
+        created_by: bag.created_by,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bag(remaining: f64, closed: bool) -> Bag {
+        Bag {
+            id: BagId::new(1),
+            roast_id: RoastId::new(1),
+            roast_date: None,
+            amount: 250.0,
+            remaining,
+            closed,
+            finished_at: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            created_by: None,
+            low_stock_threshold: None,
+            price: None,
+            currency: None,
+            best_before: None,
+            batch_code: None,
+        }
+    }
+
+    #[test]
+    fn only_the_brew_that_crosses_the_threshold_runs_a_bag_low() {
+        assert!(bag(40.0, false).just_ran_low(15.0, LOW_STOCK_GRAMS));
+        assert!(bag(35.0, false).just_ran_low(15.0, LOW_STOCK_GRAMS));
+        assert!(!bag(20.0, false).just_ran_low(15.0, LOW_STOCK_GRAMS));
+        assert!(!bag(80.0, false).just_ran_low(15.0, LOW_STOCK_GRAMS));
+        assert!(!bag(0.0, true).just_ran_low(60.0, LOW_STOCK_GRAMS));
+    }
+
+    #[test]
+    fn a_bag_threshold_overrides_the_default() {
+        let mut small = bag(70.0, false);
+        assert!(!small.is_running_low(LOW_STOCK_GRAMS));
+        assert!(small.is_running_low(80.0));
+
+        small.low_stock_threshold = Some(0.0);
+        assert!(!small.is_running_low(80.0));
+        assert!(!small.just_ran_low(15.0, 80.0));
+    }
+
+    #[test]
+    fn nearly_empty_open_bags_suggest_finishing() {
+        assert_eq!(
+            bag(5.0, false).finish_suggestion(DUST_GRAMS),
+            Some(BagFinishSuggestion {
+                bag_id: BagId::new(1),
+                remaining: 5.0,
+            })
+        );
+        assert_eq!(
+            bag(0.0, false)
+                .finish_suggestion(DUST_GRAMS)
+                .map(|s| s.remaining),
+            Some(0.0)
+        );
+        assert_eq!(bag(8.0, false).finish_suggestion(DUST_GRAMS), None);
+        assert_eq!(bag(2.0, true).finish_suggestion(DUST_GRAMS), None);
+        assert_eq!(bag(0.0, false).finish_suggestion(0.0), None);
+    }
+
+    #[test]
+    fn open_bags_are_flagged_near_their_best_before_date() {
+        let today = NaiveDate::from_ymd_opt(2025, 6, 1).unwrap();
+        let labelled = |days: i64, closed: bool| Bag {
+            best_before: Some(today + chrono::TimeDelta::days(days)),
+            ..bag(100.0, closed)
+        };
+
+        assert_eq!(labelled(30, false).best_before_status(today), None);
+        assert_eq!(
+            labelled(BEST_BEFORE_WARNING_DAYS, false).best_before_status(today),
+            Some(BestBeforeStatus::Soon)
+        );
+        assert_eq!(
+            labelled(0, false).best_before_status(today),
+            Some(BestBeforeStatus::Soon)
+        );
+        assert_eq!(
+            labelled(-1, false).best_before_status(today),
+            Some(BestBeforeStatus::Past)
+        );
+        assert_eq!(labelled(-1, true).best_before_status(today), None);
+        assert_eq!(bag(100.0, false).best_before_status(today), None);
     }
 }
@@ -0,0 +1,198 @@
+//! Blends: roasts whose beans come from more than one origin.
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::validation::ValidationErrors;
+
+/// Shares may add up to a little over 100% so rounded figures still fit.
+const SHARE_TOLERANCE: f64 = 0.5;
+
+/// One origin in a blend, with its share of the beans when the roaster
+/// gives it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlendComponent {
+    pub origin: String,
+    /// Percentage of the blend by weight.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub percentage: Option<f64>,
+}
+
+impl BlendComponent {
+    pub fn new(origin: impl Into<String>, percentage: Option<f64>) -> Self {
+        Self {
+            origin: origin.into(),
+            percentage,
+        }
+    }
+}
+
+impl std::fmt::Display for BlendComponent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.percentage {
+            Some(percentage) => write!(f, "{} {percentage}%", self.origin),
+            None => f.write_str(&self.origin),
+        }
+    }
+}
+
+/// Read a blend written out as text, e.g. `Ethiopia 60%, Colombia 40%`.
+/// Components are separated by commas or newlines, and each share may come
+/// before or after its origin, with or without the `%`.
+pub fn parse_blend(text: &str) -> Result<Vec<BlendComponent>, String> {
+    text.split([',', '\n'])
+        .map(str::trim)
+        .filter(|segment| !segment.is_empty())
+        .map(parse_component)
+        .collect()
+}
+
+fn parse_component(segment: &str) -> Result<BlendComponent, String> {
+    let is_share = |word: &str| {
+        let digits = word.trim_end_matches('%');
+        !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit() || c == '.')
+    };
+    let parse_share = |word: &str| {
+        word.trim_end_matches('%')
+            .parse::<f64>()
+            .map_err(|_| format!("invalid blend percentage '{word}'"))
+    };
+
+    let words: Vec<&str> = segment.split_whitespace().collect();
+    let (origin, share) = match words.as_slice() {
+        [rest @ .., last] if !rest.is_empty() && is_share(last) => (rest, Some(*last)),
+        [first, rest @ ..] if !rest.is_empty() && is_share(first) => (rest, Some(*first)),
+        _ => (words.as_slice(), None),
+    };
+    if origin.is_empty() || origin.iter().copied().all(is_share) {
+        return Err(format!("blend component '{segment}' has no origin"));
+    }
+
+    Ok(BlendComponent {
+        origin: origin.join(" "),
+        percentage: share.map(parse_share).transpose()?,
+    })
+}
+
+/// The blend written out as text, the inverse of [`parse_blend`].
+pub fn blend_label(components: &[BlendComponent]) -> String {
+    components
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// The blend's origins as a comma-separated `origin` value.
+pub fn blend_origin(components: &[BlendComponent]) -> String {
+    components
+        .iter()
+        .map(|component| component.origin.trim())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Each origin's weight relative to the largest share, from just above zero
+/// up to one. Without shares for every component, they all weigh the same.
+pub fn origin_weights(components: &[BlendComponent]) -> Vec<(&str, f64)> {
+    let shares: Option<Vec<f64>> = components.iter().map(|c| c.percentage).collect();
+    let largest = shares
+        .as_ref()
+        .and_then(|shares| shares.iter().copied().reduce(f64::max))
+        .filter(|largest| *largest > 0.0);
+
+    components
+        .iter()
+        .map(|component| {
+            let weight = match (component.percentage, largest) {
+                (Some(share), Some(largest)) => share / largest,
+                _ => 1.0,
+            };
+            (component.origin.as_str(), weight)
+        })
+        .collect()
+}
+
+pub(crate) fn check_blend(errors: &mut ValidationErrors, components: &[BlendComponent]) {
+    errors.check(
+        components.iter().all(|c| !c.origin.trim().is_empty()),
+        "components",
+        "each blend component needs an origin",
+    );
+    errors.check(
+        components
+            .iter()
+            .filter_map(|c| c.percentage)
+            .all(|share| share > 0.0 && share <= 100.0),
+        "components",
+        "blend percentages must be between 0 and 100",
+    );
+    let total: f64 = components.iter().filter_map(|c| c.percentage).sum();
+    errors.check(
+        total <= 100.0 + SHARE_TOLERANCE,
+        "components",
+        "blend percentages add up to more than 100",
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blends_parse_from_text() {
+        let blend = parse_blend("Ethiopia 60%, 30 Colombia\nEl Salvador 10").unwrap();
+
+        assert_eq!(
+            blend,
+            vec![
+                BlendComponent::new("Ethiopia", Some(60.0)),
+                BlendComponent::new("Colombia", Some(30.0)),
+                BlendComponent::new("El Salvador", Some(10.0)),
+            ]
+        );
+        assert_eq!(
+            blend_label(&blend),
+            "Ethiopia 60%, Colombia 30%, El Salvador 10%"
+        );
+        assert_eq!(blend_origin(&blend), "Ethiopia, Colombia, El Salvador");
+    }
+
+    #[test]
+    fn shares_are_optional() {
+        assert_eq!(
+            parse_blend("Brazil, Guatemala").unwrap(),
+            vec![
+                BlendComponent::new("Brazil", None),
+                BlendComponent::new("Guatemala", None)
+            ]
+        );
+        assert!(parse_blend("").unwrap().is_empty());
+        assert!(parse_blend("60%").is_err());
+    }
+
+    #[test]
+    fn weights_are_relative_to_the_largest_share() {
+        let blend = parse_blend("Brazil 80%, Ethiopia 20%").unwrap();
+        assert_eq!(
+            origin_weights(&blend),
+            [("Brazil", 1.0), ("Ethiopia", 0.25)]
+        );
+
+        let even = parse_blend("Brazil 80%, Ethiopia").unwrap();
+        assert_eq!(origin_weights(&even), [("Brazil", 1.0), ("Ethiopia", 1.0)]);
+    }
+
+    #[test]
+    fn shares_must_fit_in_the_whole() {
+        let check = |text: &str| {
+            let mut errors = ValidationErrors::new();
+            check_blend(&mut errors, &parse_blend(text).unwrap());
+            errors.into_result().is_ok()
+        };
+
+        assert!(check("Brazil 66.7%, Ethiopia 33.3%"));
+        assert!(check("Brazil 50, Ethiopia"));
+        assert!(!check("Brazil 70%, Ethiopia 40%"));
+        assert!(!check("Brazil 0%, Ethiopia"));
+    }
+}
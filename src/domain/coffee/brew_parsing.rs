@@ -0,0 +1,669 @@
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::brews::NewBrew;
+use crate::domain::ids::{BagId, GearId};
+
+/// Words too common in names and notes to tell one bag or piece of gear from
+/// another.
+const IGNORED_WORDS: &[&str] = &[
+    "the", "and", "with", "in", "of", "at", "on", "for", "my", "co", "coffee", "coffees",
+    "roasters", "roastery", "roasting", "company", "filter", "filters", "paper", "papers", "water",
+    "grind", "grinder", "brewer",
+];
+
+const WATER_WORDS: &[&str] = &["water", "h2o", "pour", "poured", "bloom"];
+const COFFEE_WORDS: &[&str] = &["coffee", "dose", "beans", "bean", "grounds"];
+const GRIND_WORDS: &[&str] = &["grind", "grinder", "setting", "click", "clicks"];
+const TEMP_WORDS: &[&str] = &["temp", "temperature"];
+/// Skipped when looking back from a number for the word it belongs to, so
+/// "grind of 24" reads like "grind 24".
+const FILLER_WORDS: &[&str] = &["of", "at", "to", "on", "is", "was"];
+
+/// The numbers a brew description gives, each `None` until found.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BrewMeasurements {
+    pub coffee_weight: Option<f64>,
+    pub water_volume: Option<i32>,
+    pub water_temp: Option<f64>,
+    pub grind_setting: Option<f64>,
+    /// Seconds.
+    pub brew_time: Option<i32>,
+}
+
+impl BrewMeasurements {
+    /// Take any value from `other` that this doesn't have yet.
+    pub fn fill_from(&mut self, other: &Self) {
+        self.coffee_weight = self.coffee_weight.or(other.coffee_weight);
+        self.water_volume = self.water_volume.or(other.water_volume);
+        self.water_temp = self.water_temp.or(other.water_temp);
+        self.grind_setting = self.grind_setting.or(other.grind_setting);
+        self.brew_time = self.brew_time.or(other.brew_time);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    /// A `m:ss` time, in seconds.
+    Clock(i32),
+    Word(String),
+}
+
+/// A free-text brew description such as "18g konga in the v60, 285g water
+/// at 92, grind 24", read into measurements and the words left over for
+/// naming the bag and gear.
+#[derive(Debug, Clone, Default)]
+pub struct BrewText {
+    pub measurements: BrewMeasurements,
+    words: HashSet<String>,
+}
+
+impl BrewText {
+    pub fn parse(text: &str) -> Self {
+        let mut parsed = Self::default();
+        let mut timer = Timer::default();
+
+        for clause in text.to_lowercase().split([',', ';', '\n']) {
+            let tokens = tokenize(clause);
+            let mut consumed = vec![false; tokens.len()];
+
+            for (i, token) in tokens.iter().enumerate() {
+                match token {
+                    Token::Number(value) => {
+                        let units =
+                            read_number(&tokens, i, *value, &mut parsed.measurements, &mut timer);
+                        for flag in consumed.iter_mut().skip(i + 1).take(units) {
+                            *flag = true;
+                        }
+                    }
+                    Token::Clock(secs) => {
+                        parsed.measurements.brew_time.get_or_insert(*secs);
+                    }
+                    Token::Word(_) => {}
+                }
+            }
+
+            for (token, consumed) in tokens.into_iter().zip(consumed) {
+                if let Token::Word(word) = token
+                    && !consumed
+                {
+                    parsed.words.insert(word);
+                }
+            }
+        }
+
+        if let Some(total) = timer.total() {
+            parsed.measurements.brew_time.get_or_insert(total);
+        }
+        parsed
+    }
+
+    /// Count `name` as mentioned, as when a suggestion names the gear
+    /// outright.
+    pub fn mention(&mut self, name: &str) {
+        self.words.extend(name_words(name));
+    }
+
+    /// The candidate whose name best matches the words in the text: the one
+    /// with the most of its words mentioned.
+    pub fn find<Id: Copy>(&self, candidates: &[MatchCandidate<Id>]) -> EntityMatch<Id> {
+        let scored: Vec<(Id, usize)> = candidates
+            .iter()
+            .map(|candidate| {
+                let score = name_words(&candidate.label)
+                    .filter(|word| self.words.contains(word))
+                    .count();
+                (candidate.id, score)
+            })
+            .collect();
+
+        let best = scored.iter().map(|(_, score)| *score).max().unwrap_or(0);
+        if best == 0 {
+            return EntityMatch::Missing;
+        }
+        let mut ids: Vec<Id> = scored
+            .into_iter()
+            .filter(|(_, score)| *score == best)
+            .map(|(id, _)| id)
+            .collect();
+        if ids.len() == 1 {
+            EntityMatch::Found(ids.remove(0))
+        } else {
+            EntityMatch::Ambiguous(ids)
+        }
+    }
+}
+
+fn tokenize(text: &str) -> Vec<Token> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let start = i;
+        if c.is_ascii_digit() {
+            while i < chars.len() && (chars[i].is_ascii_digit() || matches!(chars[i], '.' | ':')) {
+                i += 1;
+            }
+            let raw: String = chars[start..i].iter().collect();
+            let raw = raw.trim_end_matches(['.', ':']);
+            if let Some((mins, secs)) = raw.split_once(':') {
+                if let (Ok(mins), Ok(secs)) = (mins.parse::<i32>(), secs.parse::<i32>())
+                    && secs < 60
+                {
+                    tokens.push(Token::Clock(mins * 60 + secs));
+                }
+            } else if let Ok(value) = raw.parse() {
+                tokens.push(Token::Number(value));
+            }
+        } else if c.is_alphabetic() {
+            while i < chars.len() && chars[i].is_alphanumeric() {
+                i += 1;
+            }
+            tokens.push(Token::Word(chars[start..i].iter().collect()));
+        } else {
+            if c == '°' {
+                tokens.push(Token::Word("°".to_string()));
+            }
+            i += 1;
+        }
+    }
+
+    tokens
+}
+
+/// Minutes and seconds given separately, as in "2m 30s".
+#[derive(Default)]
+struct Timer {
+    minutes: Option<f64>,
+    seconds: Option<f64>,
+}
+
+impl Timer {
+    #[allow(clippy::cast_possible_truncation)]
+    fn total(&self) -> Option<i32> {
+        if self.minutes.is_none() && self.seconds.is_none() {
+            return None;
+        }
+        let total = self.minutes.unwrap_or(0.0) * 60.0 + self.seconds.unwrap_or(0.0);
+        Some(total.round() as i32)
+    }
+}
+
+/// Work out what the number at `i` measures from its unit and the words
+/// around it. Returns how many of the tokens after it were its unit.
+fn read_number(
+    tokens: &[Token],
+    i: usize,
+    value: f64,
+    m: &mut BrewMeasurements,
+    timer: &mut Timer,
+) -> usize {
+    let unit = word_at(tokens, i + 1);
+    let after = word_at(tokens, i + 2);
+    let before = word_before(tokens, i);
+    let near = |words: &[&str]| {
+        [unit, after, before]
+            .iter()
+            .flatten()
+            .any(|word| words.contains(word))
+    };
+
+    match unit {
+        Some("g" | "gr" | "gram" | "grams") => {
+            if near(WATER_WORDS) {
+                m.water_volume.get_or_insert(round_ml(value));
+            } else if near(COFFEE_WORDS) || (m.coffee_weight.is_none() && value < 100.0) {
+                m.coffee_weight.get_or_insert(value);
+            } else {
+                m.water_volume.get_or_insert(round_ml(value));
+            }
+            1
+        }
+        Some("ml") => {
+            m.water_volume.get_or_insert(round_ml(value));
+            1
+        }
+        Some("c" | "celsius" | "degrees" | "deg") => {
+            m.water_temp.get_or_insert(value);
+            1
+        }
+        Some("°") => {
+            m.water_temp.get_or_insert(value);
+            if after == Some("c") { 2 } else { 1 }
+        }
+        Some("s" | "sec" | "secs" | "second" | "seconds") => {
+            timer.seconds = Some(value);
+            1
+        }
+        Some("m" | "min" | "mins" | "minute" | "minutes") => {
+            timer.minutes = Some(value);
+            1
+        }
+        Some("step" | "steps") => {
+            m.grind_setting.get_or_insert(value);
+            1
+        }
+        // "24 clicks", but not the "grind" of "grind 18 grind 20".
+        Some(unit) if GRIND_WORDS.contains(&unit) && before.is_none() => {
+            m.grind_setting.get_or_insert(value);
+            1
+        }
+        _ => {
+            let after_at = i.checked_sub(1).and_then(|j| word_at(tokens, j)) == Some("at");
+            match before {
+                // "water at 92"
+                _ if after_at && (70.0..=100.0).contains(&value) => {
+                    m.water_temp.get_or_insert(value);
+                }
+                Some(word) if GRIND_WORDS.contains(&word) => {
+                    m.grind_setting.get_or_insert(value);
+                }
+                Some(word) if TEMP_WORDS.contains(&word) => {
+                    m.water_temp.get_or_insert(value);
+                }
+                Some(word) if COFFEE_WORDS.contains(&word) => {
+                    m.coffee_weight.get_or_insert(value);
+                }
+                Some(word) if WATER_WORDS.contains(&word) => {
+                    m.water_volume.get_or_insert(round_ml(value));
+                }
+                _ => {}
+            }
+            0
+        }
+    }
+}
+
+fn word_at(tokens: &[Token], index: usize) -> Option<&str> {
+    match tokens.get(index) {
+        Some(Token::Word(word)) => Some(word),
+        _ => None,
+    }
+}
+
+/// The word a number follows, looking past fillers like "of" and "at".
+fn word_before(tokens: &[Token], index: usize) -> Option<&str> {
+    tokens[..index]
+        .iter()
+        .rev()
+        .map_while(|token| match token {
+            Token::Word(word) => Some(word.as_str()),
+            _ => None,
+        })
+        .find(|word| !FILLER_WORDS.contains(word))
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn round_ml(value: f64) -> i32 {
+    value.round() as i32
+}
+
+/// The words of a name that count towards matching it.
+fn name_words(name: &str) -> impl Iterator<Item = String> {
+    tokenize(&name.to_lowercase())
+        .into_iter()
+        .filter_map(|token| match token {
+            Token::Word(word) if word.chars().count() > 1 && !IGNORED_WORDS.contains(&&*word) => {
+                Some(word)
+            }
+            _ => None,
+        })
+}
+
+/// Something a brew description might name: a bag or piece of gear.
+#[derive(Debug, Clone)]
+pub struct MatchCandidate<Id> {
+    pub id: Id,
+    pub label: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EntityMatch<Id> {
+    Found(Id),
+    /// Several candidates match equally well.
+    Ambiguous(Vec<Id>),
+    Missing,
+}
+
+/// The open bags and gear a description can refer to.
+#[derive(Debug, Clone, Default)]
+pub struct BrewCandidates {
+    pub bags: Vec<MatchCandidate<BagId>>,
+    pub grinders: Vec<MatchCandidate<GearId>>,
+    pub brewers: Vec<MatchCandidate<GearId>>,
+    pub filter_papers: Vec<MatchCandidate<GearId>>,
+    /// Whether each brewer takes a filter paper, where that's known.
+    pub brewer_filters: HashMap<GearId, bool>,
+}
+
+impl BrewCandidates {
+    /// Build a draft brew from `text`, noting anything that needs
+    /// confirming before it can be logged.
+    pub fn resolve(&self, text: &BrewText) -> ParsedBrew {
+        let mut ambiguities = Vec::new();
+
+        let bag_id = pick(text, &self.bags, "bag_id", "bag", &mut ambiguities);
+        let grinder_id = pick(
+            text,
+            &self.grinders,
+            "grinder_id",
+            "grinder",
+            &mut ambiguities,
+        );
+        let brewer_id = pick(text, &self.brewers, "brewer_id", "brewer", &mut ambiguities);
+
+        let takes_filter = brewer_id.and_then(|id| self.brewer_filters.get(&id).copied());
+        let filter_paper_id = match (takes_filter, text.find(&self.filter_papers)) {
+            (Some(false), _) | (None, EntityMatch::Missing) => None,
+            (_, EntityMatch::Found(id)) => Some(id),
+            (_, EntityMatch::Ambiguous(ids)) => {
+                ambiguities.push(BrewAmbiguity::several(
+                    "filter_paper_id",
+                    "filter paper",
+                    &self.filter_papers,
+                    &ids,
+                ));
+                None
+            }
+            (Some(true), EntityMatch::Missing) => pick_only(
+                &self.filter_papers,
+                "filter_paper_id",
+                "filter paper",
+                &mut ambiguities,
+            ),
+        };
+
+        let m = &text.measurements;
+        for (field, value, what) in [
+            ("coffee_weight", m.coffee_weight.is_some(), "coffee dose"),
+            ("water_volume", m.water_volume.is_some(), "water amount"),
+            ("water_temp", m.water_temp.is_some(), "water temperature"),
+            ("grind_setting", m.grind_setting.is_some(), "grind setting"),
+        ] {
+            if !value {
+                ambiguities.push(BrewAmbiguity {
+                    field: field.to_string(),
+                    message: format!("no {what} given"),
+                    options: Vec::new(),
+                });
+            }
+        }
+
+        let draft = BrewDraft {
+            bag_id,
+            coffee_weight: m.coffee_weight,
+            grinder_id,
+            grind_setting: m.grind_setting,
+            brewer_id,
+            filter_paper_id,
+            water_volume: m.water_volume,
+            water_temp: m.water_temp,
+            brew_time: m.brew_time,
+        };
+        let brew = if ambiguities.is_empty() {
+            draft.to_new_brew()
+        } else {
+            None
+        };
+
+        ParsedBrew {
+            draft,
+            ambiguities,
+            brew,
+            used_ai: false,
+            ai_unavailable: false,
+        }
+    }
+}
+
+fn pick<Id: Copy + PartialEq + Into<i64>>(
+    text: &BrewText,
+    candidates: &[MatchCandidate<Id>],
+    field: &str,
+    noun: &str,
+    ambiguities: &mut Vec<BrewAmbiguity>,
+) -> Option<Id> {
+    match text.find(candidates) {
+        EntityMatch::Found(id) => Some(id),
+        EntityMatch::Ambiguous(ids) => {
+            ambiguities.push(BrewAmbiguity::several(field, noun, candidates, &ids));
+            None
+        }
+        EntityMatch::Missing => pick_only(candidates, field, noun, ambiguities),
+    }
+}
+
+/// With nothing named, the only candidate there is, if there's just one.
+fn pick_only<Id: Copy + Into<i64>>(
+    candidates: &[MatchCandidate<Id>],
+    field: &str,
+    noun: &str,
+    ambiguities: &mut Vec<BrewAmbiguity>,
+) -> Option<Id> {
+    if let [only] = candidates {
+        return Some(only.id);
+    }
+    let message = if candidates.is_empty() {
+        format!("no {noun} to choose from")
+    } else {
+        format!("which {noun}?")
+    };
+    ambiguities.push(BrewAmbiguity {
+        field: field.to_string(),
+        message,
+        options: candidates.iter().map(DraftOption::from).collect(),
+    });
+    None
+}
+
+/// A brew read from a description, with whatever couldn't be read left
+/// empty.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BrewDraft {
+    pub bag_id: Option<BagId>,
+    pub coffee_weight: Option<f64>,
+    pub grinder_id: Option<GearId>,
+    pub grind_setting: Option<f64>,
+    pub brewer_id: Option<GearId>,
+    pub filter_paper_id: Option<GearId>,
+    pub water_volume: Option<i32>,
+    pub water_temp: Option<f64>,
+    pub brew_time: Option<i32>,
+}
+
+impl BrewDraft {
+    /// The brew to log, once every required field is filled.
+    pub fn to_new_brew(&self) -> Option<NewBrew> {
+        Some(NewBrew {
+            bag_id: self.bag_id?,
+            coffee_weight: self.coffee_weight?,
+            grinder_id: self.grinder_id?,
+            grind_setting: self.grind_setting?,
+            brewer_id: self.brewer_id?,
+            filter_paper_id: self.filter_paper_id,
+            water_volume: self.water_volume?,
+            water_temp: self.water_temp?,
+            bypass_water_ml: None,
+            kettle_id: None,
+            actual_water_temp: None,
+            quick_notes: Vec::new(),
+            brew_time: self.brew_time,
+            created_at: None,
+        })
+    }
+}
+
+/// A field of the draft that needs confirming: nothing matched it, or
+/// several things did.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BrewAmbiguity {
+    pub field: String,
+    pub message: String,
+    /// The candidates to choose between, when there are any.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub options: Vec<DraftOption>,
+}
+
+impl BrewAmbiguity {
+    fn several<Id: Copy + PartialEq + Into<i64>>(
+        field: &str,
+        noun: &str,
+        candidates: &[MatchCandidate<Id>],
+        ids: &[Id],
+    ) -> Self {
+        Self {
+            field: field.to_string(),
+            message: format!("several {noun}s match"),
+            options: candidates
+                .iter()
+                .filter(|candidate| ids.contains(&candidate.id))
+                .map(DraftOption::from)
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DraftOption {
+    pub id: i64,
+    pub label: String,
+}
+
+impl<Id: Copy + Into<i64>> From<&MatchCandidate<Id>> for DraftOption {
+    fn from(candidate: &MatchCandidate<Id>) -> Self {
+        Self {
+            id: candidate.id.into(),
+            label: candidate.label.clone(),
+        }
+    }
+}
+
+/// The result of parsing a brew description.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParsedBrew {
+    pub draft: BrewDraft,
+    pub ambiguities: Vec<BrewAmbiguity>,
+    /// The draft as a brew ready to log, when nothing needs confirming.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub brew: Option<NewBrew>,
+    /// Whether the AI filled in what the rules couldn't.
+    #[serde(default)]
+    pub used_ai: bool,
+    /// The AI was asked but couldn't be reached, so the ambiguities stand.
+    #[serde(default)]
+    pub ai_unavailable: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate<Id>(id: Id, label: &str) -> MatchCandidate<Id> {
+        MatchCandidate {
+            id,
+            label: label.to_string(),
+        }
+    }
+
+    fn candidates() -> BrewCandidates {
+        BrewCandidates {
+            bags: vec![
+                candidate(BagId::new(1), "Konga - Square Mile"),
+                candidate(BagId::new(2), "Red Brick - Square Mile"),
+            ],
+            grinders: vec![candidate(GearId::new(3), "Comandante C40")],
+            brewers: vec![
+                candidate(GearId::new(4), "Hario V60"),
+                candidate(GearId::new(5), "AeroPress Original"),
+            ],
+            filter_papers: vec![candidate(GearId::new(6), "Hario V60 Tabbed")],
+            brewer_filters: HashMap::from([(GearId::new(4), true), (GearId::new(5), true)]),
+        }
+    }
+
+    #[test]
+    fn parse_reads_each_measurement() {
+        let text = BrewText::parse("18g konga in the v60, 285g water at 92, grind 24");
+
+        assert_eq!(
+            text.measurements,
+            BrewMeasurements {
+                coffee_weight: Some(18.0),
+                water_volume: Some(285),
+                water_temp: Some(92.0),
+                grind_setting: Some(24.0),
+                brew_time: None,
+            }
+        );
+    }
+
+    #[test]
+    fn parse_reads_units_and_times() {
+        let text = BrewText::parse("dose 15.5, 250ml, 94°C, 18 clicks, 2m 30s");
+        assert_eq!(text.measurements.coffee_weight, Some(15.5));
+        assert_eq!(text.measurements.water_volume, Some(250));
+        assert_eq!(text.measurements.water_temp, Some(94.0));
+        assert_eq!(text.measurements.grind_setting, Some(18.0));
+        assert_eq!(text.measurements.brew_time, Some(150));
+
+        let text = BrewText::parse("water 300g then coffee 20g, done by 3:15");
+        assert_eq!(text.measurements.water_volume, Some(300));
+        assert_eq!(text.measurements.coffee_weight, Some(20.0));
+        assert_eq!(text.measurements.brew_time, Some(195));
+    }
+
+    #[test]
+    fn resolve_matches_named_bag_and_gear() {
+        let text = BrewText::parse("18g konga in the v60, 285g water at 92, grind 24");
+        let parsed = candidates().resolve(&text);
+
+        assert!(parsed.ambiguities.is_empty(), "{:?}", parsed.ambiguities);
+        assert_eq!(parsed.draft.bag_id, Some(BagId::new(1)));
+        assert_eq!(
+            parsed.draft.grinder_id,
+            Some(GearId::new(3)),
+            "the only grinder"
+        );
+        assert_eq!(parsed.draft.brewer_id, Some(GearId::new(4)));
+        assert_eq!(parsed.draft.filter_paper_id, Some(GearId::new(6)));
+        assert!(parsed.brew.is_some());
+    }
+
+    #[test]
+    fn resolve_reports_what_needs_confirming() {
+        let text = BrewText::parse("18g square mile, 250g water");
+        let parsed = candidates().resolve(&text);
+
+        let fields: Vec<&str> = parsed
+            .ambiguities
+            .iter()
+            .map(|ambiguity| ambiguity.field.as_str())
+            .collect();
+        assert_eq!(
+            fields,
+            ["bag_id", "brewer_id", "water_temp", "grind_setting"]
+        );
+        assert_eq!(parsed.ambiguities[0].options.len(), 2);
+        assert_eq!(parsed.draft.filter_paper_id, None);
+        assert!(parsed.brew.is_none());
+    }
+
+    #[test]
+    fn a_mention_settles_the_match() {
+        let mut text = BrewText::parse("18g square mile");
+        assert!(matches!(
+            text.find(&candidates().bags),
+            EntityMatch::Ambiguous(_)
+        ));
+
+        text.mention("Red Brick");
+        assert_eq!(
+            text.find(&candidates().bags),
+            EntityMatch::Found(BagId::new(2))
+        );
+    }
+}
@@ -0,0 +1,114 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::ids::{BrewId, BrewShareId};
+
+const MAX_EXPIRY_DAYS: u32 = 365;
+
+/// A public, read-only link to one brew. The link carries a random token;
+/// only its hash is stored.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BrewShare {
+    pub id: BrewShareId,
+    pub brew_id: BrewId,
+    #[serde(skip_serializing, default)]
+    pub token_hash: String,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl BrewShare {
+    pub fn is_revoked(&self) -> bool {
+        self.revoked_at.is_some()
+    }
+
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= now)
+    }
+
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        !self.is_revoked() && !self.is_expired(now)
+    }
+}
+
+#[derive(Clone)]
+pub struct NewBrewShare {
+    pub brew_id: BrewId,
+    pub token_hash: String,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl std::fmt::Debug for NewBrewShare {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NewBrewShare")
+            .field("brew_id", &self.brew_id)
+            .field("token_hash", &"<redacted>")
+            .field("expires_at", &self.expires_at)
+            .finish()
+    }
+}
+
+/// Request body for creating a share link. Links never expire unless
+/// `expires_in_days` is set.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CreateBrewShare {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_in_days: Option<u32>,
+}
+
+impl CreateBrewShare {
+    /// The expiry timestamp for a link created at `now`.
+    pub fn expires_at(&self, now: DateTime<Utc>) -> Result<Option<DateTime<Utc>>, String> {
+        let Some(days) = self.expires_in_days else {
+            return Ok(None);
+        };
+        if days == 0 || days > MAX_EXPIRY_DAYS {
+            return Err(format!(
+                "expires_in_days must be between 1 and {MAX_EXPIRY_DAYS}"
+            ));
+        }
+        Ok(now.checked_add_signed(Duration::days(i64::from(days))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn share(expires_at: Option<DateTime<Utc>>, revoked_at: Option<DateTime<Utc>>) -> BrewShare {
+        BrewShare {
+            id: BrewShareId::new(1),
+            brew_id: BrewId::new(1),
+            token_hash: "hash".to_string(),
+            expires_at,
+            revoked_at,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn share_is_inactive_once_expired_or_revoked() {
+        let now = Utc::now();
+        assert!(share(None, None).is_active(now));
+        assert!(share(Some(now + Duration::days(1)), None).is_active(now));
+        assert!(!share(Some(now), None).is_active(now));
+        assert!(!share(None, Some(now)).is_active(now));
+    }
+
+    #[test]
+    fn expiry_must_be_within_range() {
+        let now = Utc::now();
+        let create = |days| CreateBrewShare {
+            expires_in_days: days,
+        };
+
+        assert_eq!(create(None).expires_at(now), Ok(None));
+        assert_eq!(
+            create(Some(7)).expires_at(now),
+            Ok(Some(now + Duration::days(7)))
+        );
+        assert!(create(Some(0)).expires_at(now).is_err());
+        assert!(create(Some(MAX_EXPIRY_DAYS + 1)).expires_at(now).is_err());
+    }
+}
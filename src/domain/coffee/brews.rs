@@ -1,3 +1,6 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::fmt::Write;
 use std::str::FromStr;
 
 use chrono::{DateTime, Utc};
@@ -5,10 +8,20 @@ use serde::{Deserialize, Serialize};
 
 use crate::define_sort_key;
 use crate::domain::entity_type::EntityType;
-use crate::domain::ids::{BagId, BrewId, GearId};
+use crate::domain::gear::{BrewMethod, BrewerProfile, Gear};
+use crate::domain::ids::{BagId, BrewId, GearId, UserId};
+use crate::domain::ratings::brew_stars;
 use crate::domain::timeline::{NewTimelineEvent, TimelineBrewData, TimelineEventDetail};
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+use crate::domain::validation::{Validate, ValidationErrors};
+
+/// A short verdict attached to a brew: one of the built-in notes, or a
+/// user-defined [`CustomQuickNote`](crate::domain::quick_notes::CustomQuickNote)
+/// referenced by its label.
+///
+/// Serialized as a plain string. Built-ins keep their variant names
+/// (`"TooFast"`) so existing API clients and backups are unaffected;
+/// custom notes serialize as their label.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum QuickNote {
     Good,
     TooFast,
@@ -16,10 +29,20 @@ pub enum QuickNote {
     TooHot,
     UnderExtracted,
     OverExtracted,
+    Custom(String),
 }
 
+const BUILT_IN_QUICK_NOTES: &[QuickNote] = &[
+    QuickNote::Good,
+    QuickNote::TooFast,
+    QuickNote::TooSlow,
+    QuickNote::TooHot,
+    QuickNote::UnderExtracted,
+    QuickNote::OverExtracted,
+];
+
 impl QuickNote {
-    pub fn label(self) -> &'static str {
+    pub fn label(&self) -> &str {
         match self {
             Self::Good => "Good",
             Self::TooFast => "Too Fast",
@@ -27,10 +50,11 @@ impl QuickNote {
             Self::TooHot => "Too Hot",
             Self::UnderExtracted => "Under Extracted",
             Self::OverExtracted => "Over Extracted",
+            Self::Custom(label) => label,
         }
     }
 
-    pub fn form_value(self) -> &'static str {
+    pub fn form_value(&self) -> &str {
         match self {
             Self::Good => "good",
             Self::TooFast => "too-fast",
@@ -38,26 +62,58 @@ impl QuickNote {
             Self::TooHot => "too-hot",
             Self::UnderExtracted => "under-extracted",
             Self::OverExtracted => "over-extracted",
+            Self::Custom(label) => label,
         }
     }
 
+    /// Parse a built-in note from its form value or label.
     pub fn from_str_value(s: &str) -> Option<Self> {
         s.parse().ok()
     }
 
-    pub fn is_positive(self) -> bool {
+    /// Parse a stored or submitted value, treating anything that isn't a
+    /// built-in as a custom note label. Empty values are ignored.
+    pub fn from_value(s: &str) -> Option<Self> {
+        let trimmed = s.trim();
+        if trimmed.is_empty() {
+            return None;
+        }
+        Some(Self::from_str_value(trimmed).unwrap_or_else(|| Self::Custom(trimmed.to_string())))
+    }
+
+    pub fn is_positive(&self) -> bool {
         matches!(self, Self::Good)
     }
 
+    pub fn is_custom(&self) -> bool {
+        matches!(self, Self::Custom(_))
+    }
+
+    /// How the note counts towards a brew's [`rating`](Brew::rating): +1 for
+    /// "Good", -1 for a built-in fault. Custom notes carry no verdict.
+    pub fn score(&self) -> Option<i32> {
+        match self {
+            Self::Good => Some(1),
+            Self::Custom(_) => None,
+            _ => Some(-1),
+        }
+    }
+
+    /// The built-in notes, in display order.
     pub fn all() -> &'static [Self] {
-        &[
-            Self::Good,
-            Self::TooFast,
-            Self::TooSlow,
-            Self::TooHot,
-            Self::UnderExtracted,
-            Self::OverExtracted,
-        ]
+        BUILT_IN_QUICK_NOTES
+    }
+
+    fn variant_name(&self) -> &str {
+        match self {
+            Self::Good => "Good",
+            Self::TooFast => "TooFast",
+            Self::TooSlow => "TooSlow",
+            Self::TooHot => "TooHot",
+            Self::UnderExtracted => "UnderExtracted",
+            Self::OverExtracted => "OverExtracted",
+            Self::Custom(label) => label,
+        }
     }
 }
 
@@ -67,16 +123,29 @@ impl FromStr for QuickNote {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             "good" | "Good" => Ok(Self::Good),
-            "too-fast" | "Too Fast" => Ok(Self::TooFast),
-            "too-slow" | "Too Slow" => Ok(Self::TooSlow),
-            "too-hot" | "Too Hot" => Ok(Self::TooHot),
-            "under-extracted" | "Under Extracted" => Ok(Self::UnderExtracted),
-            "over-extracted" | "Over Extracted" => Ok(Self::OverExtracted),
+            "too-fast" | "Too Fast" | "TooFast" => Ok(Self::TooFast),
+            "too-slow" | "Too Slow" | "TooSlow" => Ok(Self::TooSlow),
+            "too-hot" | "Too Hot" | "TooHot" => Ok(Self::TooHot),
+            "under-extracted" | "Under Extracted" | "UnderExtracted" => Ok(Self::UnderExtracted),
+            "over-extracted" | "Over Extracted" | "OverExtracted" => Ok(Self::OverExtracted),
             _ => Err(()),
         }
     }
 }
 
+impl Serialize for QuickNote {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.variant_name())
+    }
+}
+
+impl<'de> Deserialize<'de> for QuickNote {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        Self::from_value(&value).ok_or_else(|| serde::de::Error::custom("empty quick note"))
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Brew {
     pub id: BrewId,
@@ -88,10 +157,116 @@ pub struct Brew {
     pub filter_paper_id: Option<GearId>,
     pub water_volume: i32,
     pub water_temp: f64,
+    /// Water added after brewing, e.g. to dilute a concentrate.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bypass_water_ml: Option<i32>,
+    /// Kettle the water was heated in; `water_temp` is what it was set to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kettle_id: Option<GearId>,
+    /// Temperature of the water that reached the coffee, as measured or
+    /// worked out from the kettle's offset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub actual_water_temp: Option<f64>,
     pub quick_notes: Vec<QuickNote>,
     pub brew_time: Option<i32>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_by: Option<UserId>,
+}
+
+impl Brew {
+    /// Brew water plus any bypass, i.e. what ends up in the cup.
+    pub fn total_water(&self) -> i32 {
+        self.water_volume + self.bypass_water_ml.unwrap_or(0)
+    }
+
+    /// The temperature the coffee was brewed at: the actual one where it's
+    /// known, otherwise what the kettle was set to.
+    pub fn brewing_temp(&self) -> f64 {
+        self.actual_water_temp.unwrap_or(self.water_temp)
+    }
+
+    /// Water-to-coffee ratio over the total water, e.g. `16.5` for 1:16.5.
+    pub fn ratio(&self) -> Option<f64> {
+        (self.coffee_weight > 0.0).then(|| f64::from(self.total_water()) / self.coffee_weight)
+    }
+
+    /// A rough verdict from the built-in quick notes: "Good" counts +1 and
+    /// each fault -1. `None` when no built-in note was picked.
+    pub fn rating(&self) -> Option<i32> {
+        net_rating(&self.quick_notes)
+    }
+
+    /// The [`rating`](Self::rating) on the star scale cups are rated on.
+    pub fn stars(&self) -> Option<u8> {
+        self.rating().map(brew_stars)
+    }
+
+    /// The add-brew form pre-filled with this brew's parameters.
+    pub fn brew_again_url(&self) -> String {
+        let mut url = format!(
+            "/add?type=brew&bag_id={}&coffee_weight={}&grinder_id={}&grind_setting={}&brewer_id={}&water_volume={}&water_temp={}",
+            self.bag_id,
+            self.coffee_weight,
+            self.grinder_id,
+            self.grind_setting,
+            self.brewer_id,
+            self.water_volume,
+            self.water_temp,
+        );
+        if let Some(fp_id) = self.filter_paper_id {
+            let _ = write!(url, "&filter_paper_id={fp_id}");
+        }
+        if let Some(bt) = self.brew_time {
+            let _ = write!(url, "&brew_time={bt}");
+        }
+        if let Some(ml) = self.bypass_water_ml {
+            let _ = write!(url, "&bypass_water_ml={ml}");
+        }
+        if let Some(kettle_id) = self.kettle_id {
+            let _ = write!(url, "&kettle_id={kettle_id}");
+        }
+        if !self.quick_notes.is_empty() {
+            // Custom note labels may contain spaces or punctuation.
+            let values: Vec<String> = self
+                .quick_notes
+                .iter()
+                .map(|note| {
+                    url::form_urlencoded::byte_serialize(note.form_value().as_bytes()).collect()
+                })
+                .collect();
+            let _ = write!(url, "&quick_notes={}", values.join(","));
+        }
+        url
+    }
+}
+
+/// Nets "Good" against the built-in faults in `notes`; see [`Brew::rating`].
+pub fn net_rating(notes: &[QuickNote]) -> Option<i32> {
+    notes
+        .iter()
+        .filter_map(QuickNote::score)
+        .reduce(|total, score| total + score)
+}
+
+/// Format brew water with any bypass (e.g., "200ml + 100ml bypass").
+pub fn format_water(water_volume: i32, bypass_water_ml: Option<i32>) -> String {
+    match bypass_water_ml {
+        Some(ml) => format!("{water_volume}ml + {ml}ml bypass"),
+        None => format!("{water_volume}ml"),
+    }
+}
+
+/// Format a brew's water temperature, with what the kettle was set to when
+/// the water reached the coffee at something else (e.g., "93.5°C (set 96.0°C)").
+pub fn format_water_temp(water_temp: f64, actual_water_temp: Option<f64>) -> String {
+    match actual_water_temp {
+        Some(actual) if (actual - water_temp).abs() >= 0.05 => {
+            format!("{actual:.1}\u{00B0}C (set {water_temp:.1}\u{00B0}C)")
+        }
+        _ => format!("{water_temp:.1}\u{00B0}C"),
+    }
 }
 
 /// Format seconds as "M:SS" (e.g., 150 -> "2:30").
@@ -99,6 +274,28 @@ pub fn format_brew_time(seconds: i32) -> String {
     format!("{}:{:02}", seconds / 60, seconds % 60)
 }
 
+/// One brew on a bag's dialing-in curve.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BrewSeriesPoint {
+    pub brew_id: BrewId,
+    pub created_at: DateTime<Utc>,
+    pub grind_setting: f64,
+    pub ratio: Option<f64>,
+    pub rating: Option<i32>,
+}
+
+impl From<&Brew> for BrewSeriesPoint {
+    fn from(brew: &Brew) -> Self {
+        Self {
+            brew_id: brew.id,
+            created_at: brew.created_at,
+            grind_setting: brew.grind_setting,
+            ratio: brew.ratio(),
+            rating: brew.rating(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BrewWithDetails {
     #[serde(flatten)]
@@ -111,18 +308,16 @@ pub struct BrewWithDetails {
     pub grinder_model: String,
     pub brewer_name: String,
     pub filter_paper_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kettle_name: Option<String>,
 }
 
 impl BrewWithDetails {
     pub fn to_timeline_event(&self) -> NewTimelineEvent {
-        let ratio = if self.brew.coffee_weight > 0.0 {
-            format!(
-                "1:{:.1}",
-                f64::from(self.brew.water_volume) / self.brew.coffee_weight
-            )
-        } else {
-            "N/A".to_string()
-        };
+        let ratio = self
+            .brew
+            .ratio()
+            .map_or_else(|| "N/A".to_string(), |ratio| format!("1:{ratio:.1}"));
 
         let mut details = vec![
             TimelineEventDetail {
@@ -136,8 +331,9 @@ impl BrewWithDetails {
             TimelineEventDetail {
                 label: "Water".to_string(),
                 value: format!(
-                    "{}ml \u{00B7} {:.1}\u{00B0}C",
-                    self.brew.water_volume, self.brew.water_temp
+                    "{} \u{00B7} {}",
+                    format_water(self.brew.water_volume, self.brew.bypass_water_ml),
+                    format_water_temp(self.brew.water_temp, self.brew.actual_water_temp)
                 ),
             },
             TimelineEventDetail {
@@ -171,13 +367,20 @@ impl BrewWithDetails {
             });
         }
 
+        if let Some(ref kettle_name) = self.kettle_name {
+            details.push(TimelineEventDetail {
+                label: "Kettle".to_string(),
+                value: kettle_name.clone(),
+            });
+        }
+
         details.push(TimelineEventDetail {
             label: "Ratio".to_string(),
             value: ratio,
         });
 
         if !self.brew.quick_notes.is_empty() {
-            let labels: Vec<&str> = self.brew.quick_notes.iter().map(|n| n.label()).collect();
+            let labels: Vec<&str> = self.brew.quick_notes.iter().map(QuickNote::label).collect();
             details.push(TimelineEventDetail {
                 label: "Notes".to_string(),
                 value: labels.join(", "),
@@ -204,7 +407,9 @@ impl BrewWithDetails {
                 water_volume: self.brew.water_volume,
                 water_temp: self.brew.water_temp,
                 brew_time: self.brew.brew_time,
+                bypass_water_ml: self.brew.bypass_water_ml,
             }),
+            created_by: self.brew.created_by,
         }
     }
 }
@@ -219,6 +424,12 @@ pub struct NewBrew {
     pub filter_paper_id: Option<GearId>,
     pub water_volume: i32,
     pub water_temp: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bypass_water_ml: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kettle_id: Option<GearId>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub actual_water_temp: Option<f64>,
     pub quick_notes: Vec<QuickNote>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub brew_time: Option<i32>,
@@ -244,6 +455,13 @@ pub struct UpdateBrew {
     pub water_volume: Option<i32>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub water_temp: Option<f64>,
+    /// `Some(0)` removes the bypass.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bypass_water_ml: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kettle_id: Option<GearId>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub actual_water_temp: Option<f64>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub quick_notes: Option<Vec<QuickNote>>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -252,6 +470,195 @@ pub struct UpdateBrew {
     pub created_at: Option<DateTime<Utc>>,
 }
 
+/// The recipe checks shared by new brews and updates; `None` skips a field.
+fn check_recipe(
+    errors: &mut ValidationErrors,
+    coffee_weight: Option<f64>,
+    grind_setting: Option<f64>,
+    water_volume: Option<i32>,
+    water_temp: Option<f64>,
+    bypass_water_ml: Option<i32>,
+    brew_time: Option<i32>,
+) {
+    if let Some(weight) = coffee_weight {
+        errors.check(
+            weight > 0.0,
+            "coffee_weight",
+            "coffee weight must be positive",
+        );
+    }
+    if let Some(setting) = grind_setting {
+        errors.check(
+            setting >= 0.0,
+            "grind_setting",
+            "grind setting must be non-negative",
+        );
+    }
+    if let Some(volume) = water_volume {
+        errors.check(volume > 0, "water_volume", "water volume must be positive");
+    }
+    if let Some(temp) = water_temp {
+        errors.check(
+            temp > 0.0 && temp <= 100.0,
+            "water_temp",
+            "water temperature must be between 0 and 100",
+        );
+    }
+    if let Some(ml) = bypass_water_ml {
+        errors.check(
+            ml >= 0,
+            "bypass_water_ml",
+            "bypass water must not be negative",
+        );
+    }
+    if let Some(time) = brew_time {
+        errors.check(time > 0, "brew_time", "brew time must be positive");
+    }
+}
+
+fn check_actual_temp(errors: &mut ValidationErrors, actual_water_temp: Option<f64>) {
+    if let Some(temp) = actual_water_temp {
+        errors.check(
+            temp > 0.0 && temp <= 100.0,
+            "actual_water_temp",
+            "actual water temperature must be between 0 and 100",
+        );
+    }
+}
+
+impl Validate for NewBrew {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+        check_recipe(
+            &mut errors,
+            Some(self.coffee_weight),
+            Some(self.grind_setting),
+            Some(self.water_volume),
+            Some(self.water_temp),
+            self.bypass_water_ml,
+            self.brew_time,
+        );
+        check_actual_temp(&mut errors, self.actual_water_temp);
+        errors.into_result()
+    }
+}
+
+impl Validate for UpdateBrew {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+        check_recipe(
+            &mut errors,
+            self.coffee_weight,
+            self.grind_setting,
+            self.water_volume,
+            self.water_temp,
+            self.bypass_water_ml,
+            self.brew_time,
+        );
+        check_actual_temp(&mut errors, self.actual_water_temp);
+        errors.into_result()
+    }
+}
+
+/// The checks a brewer puts on a brew: immersion brewers need a steep time,
+/// and brewers set to take a filter paper (or not) need one (or none).
+///
+/// Only what's set on the brewer is enforced; a profile detected from its
+/// name just tailors the brew form.
+fn check_brewer_fit(
+    errors: &mut ValidationErrors,
+    profile: BrewerProfile,
+    has_filter_paper: bool,
+    adds_filter_paper: bool,
+    brew_time: Option<i32>,
+) {
+    match profile.uses_filter {
+        Some(true) => errors.check(
+            has_filter_paper,
+            "filter_paper_id",
+            "this brewer needs a filter paper",
+        ),
+        Some(false) => errors.check(
+            !adds_filter_paper,
+            "filter_paper_id",
+            "this brewer doesn't take a filter paper",
+        ),
+        None => {}
+    }
+    if profile.method == Some(BrewMethod::Immersion) {
+        errors.check(
+            brew_time.is_some(),
+            "brew_time",
+            "immersion brews need a steep time",
+        );
+    }
+}
+
+impl NewBrew {
+    /// Check the brew against the brewer it was made with.
+    pub fn validate_for_brewer(&self, brewer: &Gear) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+        check_brewer_fit(
+            &mut errors,
+            brewer.declared_profile(),
+            self.filter_paper_id.is_some(),
+            self.filter_paper_id.is_some(),
+            self.brew_time,
+        );
+        errors.into_result()
+    }
+
+    /// Work out the temperature reaching the coffee from the kettle's
+    /// offset, unless it was measured.
+    pub fn apply_kettle(&mut self, kettle: &Gear) {
+        if self.actual_water_temp.is_none() {
+            self.actual_water_temp = kettle.actual_temp(self.water_temp);
+        }
+    }
+}
+
+impl UpdateBrew {
+    /// Whether the update changes the kettle or what it was set to, so the
+    /// actual temperature needs working out again.
+    pub fn changes_kettle_temp(&self) -> bool {
+        self.actual_water_temp.is_none() && (self.kettle_id.is_some() || self.water_temp.is_some())
+    }
+
+    /// Work out the temperature reaching the coffee from the kettle's
+    /// offset, unless the update gives a measured one.
+    pub fn apply_kettle(&mut self, kettle: &Gear, existing: &Brew) {
+        if self.actual_water_temp.is_none() {
+            self.actual_water_temp =
+                kettle.actual_temp(self.water_temp.unwrap_or(existing.water_temp));
+        }
+    }
+
+    /// Whether the update touches anything [`Self::validate_for_brewer`] checks.
+    pub fn changes_brewer_fit(&self) -> bool {
+        self.brewer_id.is_some() || self.filter_paper_id.is_some() || self.brew_time.is_some()
+    }
+
+    /// Check the brew as it will be after this update against its brewer.
+    ///
+    /// An update can't take a filter paper off a brew, so one already on it
+    /// is only counted against brewers that need one.
+    pub fn validate_for_brewer(
+        &self,
+        brewer: &Gear,
+        existing: &Brew,
+    ) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+        check_brewer_fit(
+            &mut errors,
+            brewer.declared_profile(),
+            self.filter_paper_id.or(existing.filter_paper_id).is_some(),
+            self.filter_paper_id.is_some(),
+            self.brew_time.or(existing.brew_time),
+        );
+        errors.into_result()
+    }
+}
+
 /// Filter criteria for brew queries.
 #[derive(Debug, Default, Clone)]
 pub struct BrewFilter {
@@ -282,6 +689,57 @@ impl BrewFilter {
     }
 }
 
+/// Aggregated figures across every brew made from a roast's bags.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RoastBrewStats {
+    pub brew_count: i64,
+    /// Brews marked "Good" in their quick notes.
+    pub good_brew_count: i64,
+    pub total_coffee_grams: f64,
+    /// Mean water-to-coffee ratio, e.g. `16.5` for 1:16.5.
+    pub average_ratio: Option<f64>,
+    pub grind_min: Option<f64>,
+    pub grind_max: Option<f64>,
+    /// The brew marked "Good" with the fewest other notes, most recent first.
+    pub best_brew: Option<BrewWithDetails>,
+}
+
+/// How bags and gear have been brewed with, so pickers can offer the
+/// likeliest choice first.
+#[derive(Debug, Clone, Default)]
+pub struct BrewUsage {
+    /// When each bag was last brewed from.
+    pub bags: HashMap<BagId, DateTime<Utc>>,
+    pub gear: HashMap<GearId, GearUsage>,
+}
+
+/// How many brews used a piece of gear, and when it was last used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GearUsage {
+    pub brews: i64,
+    pub last_brewed: DateTime<Utc>,
+}
+
+impl BrewUsage {
+    /// Puts the most recently brewed bags first. Bags never brewed from keep
+    /// their order, after the rest.
+    pub fn sort_bags<T>(&self, bags: &mut [T], id: impl Fn(&T) -> BagId) {
+        bags.sort_by_key(|bag| Reverse(self.bags.get(&id(bag)).copied()));
+    }
+
+    /// Puts the most used gear first, the most recently used winning a tie.
+    /// Unused gear keeps its order, after the rest.
+    pub fn sort_gear<T>(&self, gear: &mut [T], id: impl Fn(&T) -> GearId) {
+        gear.sort_by_key(|item| {
+            Reverse(
+                self.gear
+                    .get(&id(item))
+                    .map(|usage| (usage.brews, usage.last_brewed)),
+            )
+        });
+    }
+}
+
 define_sort_key!(pub BrewSortKey {
     #[default]
     CreatedAt("created-at", Desc),
@@ -293,11 +751,99 @@ define_sort_key!(pub BrewSortKey {
 mod tests {
     use super::*;
 
+    fn brew(
+        filter_paper_id: Option<i64>,
+        brew_time: Option<i32>,
+        quick_notes: Vec<QuickNote>,
+    ) -> Brew {
+        let now = Utc::now();
+        Brew {
+            id: BrewId::new(1),
+            bag_id: BagId::new(10),
+            coffee_weight: 15.0,
+            grinder_id: GearId::new(2),
+            grind_setting: 6.0,
+            brewer_id: GearId::new(3),
+            filter_paper_id: filter_paper_id.map(GearId::new),
+            water_volume: 250,
+            water_temp: 91.0,
+            bypass_water_ml: None,
+            kettle_id: None,
+            actual_water_temp: None,
+            quick_notes,
+            brew_time,
+            created_at: now,
+            updated_at: now,
+            created_by: None,
+        }
+    }
+
+    #[test]
+    fn brew_again_url_all_params() {
+        let url = brew(
+            Some(5),
+            Some(120),
+            vec![QuickNote::Good, QuickNote::TooFast],
+        )
+        .brew_again_url();
+
+        assert!(url.starts_with("/add?type=brew&"));
+        assert!(url.contains("bag_id=10"));
+        assert!(url.contains("coffee_weight=15"));
+        assert!(url.contains("grinder_id=2"));
+        assert!(url.contains("grind_setting=6"));
+        assert!(url.contains("brewer_id=3"));
+        assert!(url.contains("water_volume=250"));
+        assert!(url.contains("water_temp=91"));
+        assert!(url.contains("filter_paper_id=5"));
+        assert!(url.contains("brew_time=120"));
+        assert!(url.contains("quick_notes=good,too-fast"));
+    }
+
+    #[test]
+    fn brew_again_url_encodes_custom_notes() {
+        let url = brew(
+            None,
+            None,
+            vec![
+                QuickNote::Good,
+                QuickNote::Custom("Tea & Honey".to_string()),
+            ],
+        )
+        .brew_again_url();
+
+        assert!(url.contains("quick_notes=good,Tea+%26+Honey"));
+    }
+
+    #[test]
+    fn brew_again_url_optional_omitted() {
+        let url = brew(None, None, vec![]).brew_again_url();
+
+        assert!(url.starts_with("/add?type=brew&"));
+        assert!(url.contains("bag_id=10"));
+        assert!(!url.contains("filter_paper_id"));
+        assert!(!url.contains("brew_time"));
+        assert!(!url.contains("bypass_water_ml"));
+        assert!(!url.contains("quick_notes"));
+    }
+
+    #[test]
+    fn bypass_water_counts_towards_the_ratio() {
+        let mut diluted = brew(None, None, vec![]);
+        diluted.water_volume = 150;
+        diluted.bypass_water_ml = Some(90);
+
+        assert_eq!(diluted.total_water(), 240);
+        assert_eq!(diluted.ratio(), Some(16.0));
+        assert!(diluted.brew_again_url().contains("bypass_water_ml=90"));
+        assert_eq!(format_water(150, Some(90)), "150ml + 90ml bypass");
+    }
+
     #[test]
     fn quick_note_roundtrip_form_value() {
-        for &variant in QuickNote::all() {
+        for variant in QuickNote::all() {
             assert_eq!(
-                QuickNote::from_str_value(variant.form_value()),
+                QuickNote::from_str_value(variant.form_value()).as_ref(),
                 Some(variant)
             );
         }
@@ -305,8 +851,11 @@ mod tests {
 
     #[test]
     fn quick_note_roundtrip_label() {
-        for &variant in QuickNote::all() {
-            assert_eq!(QuickNote::from_str_value(variant.label()), Some(variant));
+        for variant in QuickNote::all() {
+            assert_eq!(
+                QuickNote::from_str_value(variant.label()).as_ref(),
+                Some(variant)
+            );
         }
     }
 
@@ -315,6 +864,25 @@ mod tests {
         assert_eq!(QuickNote::from_str_value("invalid"), None);
     }
 
+    #[test]
+    fn quick_note_from_value_falls_back_to_custom() {
+        assert_eq!(QuickNote::from_value("Too Fast"), Some(QuickNote::TooFast));
+        assert_eq!(
+            QuickNote::from_value(" Floral "),
+            Some(QuickNote::Custom("Floral".to_string()))
+        );
+        assert_eq!(QuickNote::from_value("  "), None);
+    }
+
+    #[test]
+    fn quick_note_serde_roundtrip() {
+        let notes = vec![QuickNote::TooFast, QuickNote::Custom("Floral".to_string())];
+        let json = serde_json::to_string(&notes).unwrap();
+        assert_eq!(json, r#"["TooFast","Floral"]"#);
+        let decoded: Vec<QuickNote> = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, notes);
+    }
+
     #[test]
     fn quick_note_from_str_trait() {
         assert_eq!("good".parse::<QuickNote>(), Ok(QuickNote::Good));
@@ -334,4 +902,81 @@ mod tests {
     fn format_brew_time_under_minute() {
         assert_eq!(format_brew_time(45), "0:45");
     }
+
+    #[test]
+    fn rating_nets_good_against_faults() {
+        assert_eq!(brew(None, None, vec![QuickNote::Good]).rating(), Some(1));
+        assert_eq!(
+            brew(None, None, vec![QuickNote::Good, QuickNote::TooFast]).rating(),
+            Some(0)
+        );
+        assert_eq!(
+            brew(
+                None,
+                None,
+                vec![QuickNote::TooFast, QuickNote::UnderExtracted]
+            )
+            .rating(),
+            Some(-2)
+        );
+    }
+
+    #[test]
+    fn rating_ignores_custom_notes() {
+        assert_eq!(brew(None, None, Vec::new()).rating(), None);
+        assert_eq!(
+            brew(None, None, vec![QuickNote::Custom("Juicy".to_string())]).rating(),
+            None
+        );
+    }
+
+    #[test]
+    fn water_temp_shows_the_setting_when_it_differs() {
+        assert_eq!(format_water_temp(96.0, None), "96.0\u{00B0}C");
+        assert_eq!(format_water_temp(96.0, Some(96.0)), "96.0\u{00B0}C");
+        assert_eq!(
+            format_water_temp(96.0, Some(93.5)),
+            "93.5\u{00B0}C (set 96.0\u{00B0}C)"
+        );
+    }
+
+    #[test]
+    fn usage_puts_recent_bags_and_busy_gear_first() {
+        let now = Utc::now();
+        let earlier = now - chrono::Duration::days(3);
+        let usage = BrewUsage {
+            bags: HashMap::from([(BagId::new(2), earlier), (BagId::new(3), now)]),
+            gear: HashMap::from([
+                (
+                    GearId::new(1),
+                    GearUsage {
+                        brews: 2,
+                        last_brewed: now,
+                    },
+                ),
+                (
+                    GearId::new(2),
+                    GearUsage {
+                        brews: 5,
+                        last_brewed: earlier,
+                    },
+                ),
+                (
+                    GearId::new(3),
+                    GearUsage {
+                        brews: 2,
+                        last_brewed: earlier,
+                    },
+                ),
+            ]),
+        };
+
+        let mut bags = vec![1, 4, 2, 3];
+        usage.sort_bags(&mut bags, |id| BagId::new(*id));
+        assert_eq!(bags, vec![3, 2, 1, 4]);
+
+        let mut gear = vec![4, 3, 1, 2];
+        usage.sort_gear(&mut gear, |id| GearId::new(*id));
+        assert_eq!(gear, vec![2, 1, 3, 4]);
+    }
 }
@@ -1,12 +1,14 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Datelike, Utc};
 use serde::{Deserialize, Serialize};
 
 use super::normalize_optional_field;
 use crate::define_sort_key;
 use crate::domain::entity_type::EntityType;
-use crate::domain::ids::CafeId;
+use crate::domain::ids::{CafeId, UserId};
 use crate::domain::roasters::is_valid_url_scheme;
+use crate::domain::slugs::SlugQualifiers;
 use crate::domain::timeline::{NewTimelineEvent, TimelineEventDetail};
+use crate::domain::validation::{Validate, ValidationErrors};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Cafe {
@@ -20,6 +22,11 @@ pub struct Cafe {
     pub website: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Whether the cafe is starred as a favourite.
+    #[serde(default)]
+    pub favourite: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_by: Option<UserId>,
 }
 
 impl Cafe {
@@ -44,6 +51,7 @@ impl Cafe {
             slug: Some(self.slug.clone()),
             roaster_slug: None,
             brew_data: None,
+            created_by: self.created_by,
         }
     }
 }
@@ -73,6 +81,13 @@ impl NewCafe {
     pub fn slug(&self) -> String {
         slug::slugify(format!("{}-{}", self.name, self.city))
     }
+
+    pub fn slug_qualifiers(&self) -> SlugQualifiers {
+        SlugQualifiers {
+            year: Some(self.created_at.unwrap_or_else(Utc::now).year()),
+            roaster: None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -95,6 +110,41 @@ impl UpdateCafe {
     }
 }
 
+fn check_coordinates(errors: &mut ValidationErrors, latitude: Option<f64>, longitude: Option<f64>) {
+    if let Some(latitude) = latitude {
+        errors.check(
+            (-90.0..=90.0).contains(&latitude),
+            "latitude",
+            "latitude must be between -90 and 90",
+        );
+    }
+    if let Some(longitude) = longitude {
+        errors.check(
+            (-180.0..=180.0).contains(&longitude),
+            "longitude",
+            "longitude must be between -180 and 180",
+        );
+    }
+}
+
+impl Validate for NewCafe {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+        errors.require("name", &self.name);
+        check_coordinates(&mut errors, Some(self.latitude), Some(self.longitude));
+        errors.into_result()
+    }
+}
+
+impl Validate for UpdateCafe {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+        errors.require_if_set("name", self.name.as_deref());
+        check_coordinates(&mut errors, self.latitude, self.longitude);
+        errors.into_result()
+    }
+}
+
 define_sort_key!(pub CafeSortKey {
     #[default]
     CreatedAt("created-at", Desc),
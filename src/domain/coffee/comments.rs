@@ -0,0 +1,95 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::ids::{BrewId, CommentId, RoastId, UserId};
+
+const MAX_BODY_CHARS: usize = 2000;
+
+/// What a comment thread hangs off.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommentTarget {
+    Brew(BrewId),
+    Roast(RoastId),
+}
+
+/// A note left by a user on a brew or roast. Exactly one of `brew_id` and
+/// `roast_id` is set.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Comment {
+    pub id: CommentId,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub brew_id: Option<BrewId>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub roast_id: Option<RoastId>,
+    pub user_id: UserId,
+    /// Display name of the author, falling back to their username.
+    pub author: String,
+    pub body: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Comment {
+    pub fn is_edited(&self) -> bool {
+        self.updated_at > self.created_at
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct NewComment {
+    pub target: CommentTarget,
+    pub user_id: UserId,
+    pub body: String,
+}
+
+/// Request body for creating or editing a comment.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommentInput {
+    pub body: String,
+}
+
+impl CommentInput {
+    /// The trimmed body, or an error if it is empty or too long.
+    pub fn normalized_body(&self) -> Result<String, String> {
+        let body = self.body.trim();
+        if body.is_empty() {
+            return Err("comment body is required".to_string());
+        }
+        if body.chars().count() > MAX_BODY_CHARS {
+            return Err(format!(
+                "comment body must be at most {MAX_BODY_CHARS} characters"
+            ));
+        }
+        Ok(body.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(body: &str) -> CommentInput {
+        CommentInput {
+            body: body.to_string(),
+        }
+    }
+
+    #[test]
+    fn normalized_body_trims_whitespace() {
+        assert_eq!(
+            input("  Tastes sweeter today \n").normalized_body(),
+            Ok("Tastes sweeter today".to_string())
+        );
+    }
+
+    #[test]
+    fn normalized_body_rejects_empty_and_overlong_bodies() {
+        assert!(input("   ").normalized_body().is_err());
+        assert!(
+            input(&"a".repeat(MAX_BODY_CHARS + 1))
+                .normalized_body()
+                .is_err()
+        );
+        assert!(input(&"a".repeat(MAX_BODY_CHARS)).normalized_body().is_ok());
+    }
+}
@@ -1,18 +1,30 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 
 use crate::define_sort_key;
 use crate::domain::entity_type::EntityType;
-use crate::domain::ids::{CafeId, CupId, RoastId};
+use crate::domain::ids::{BagId, CafeId, CupId, RoastId, UserId};
+use crate::domain::ratings::MAX_STARS;
 use crate::domain::timeline::{NewTimelineEvent, TimelineEventDetail};
+use crate::domain::validation::{Validate, ValidationErrors};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Cup {
     pub id: CupId,
     pub roast_id: RoastId,
-    pub cafe_id: CafeId,
+    /// Unset for cups drunk away from a cafe.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cafe_id: Option<CafeId>,
+    /// The bag a home-brewed cup was made from.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bag_id: Option<BagId>,
+    /// Stars out of [`MAX_STARS`], when the cup was rated.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rating: Option<u8>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_by: Option<UserId>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -23,12 +35,23 @@ pub struct CupWithDetails {
     pub roaster_name: String,
     pub roast_slug: String,
     pub roaster_slug: String,
-    pub cafe_name: String,
-    pub cafe_slug: String,
-    pub cafe_city: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cafe_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cafe_slug: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cafe_city: Option<String>,
 }
 
+/// Shown in place of a cafe name for cups drunk away from one.
+pub const NO_CAFE_LABEL: &str = "Elsewhere";
+
 impl CupWithDetails {
+    /// The cafe's name, or [`NO_CAFE_LABEL`].
+    pub fn place_name(&self) -> &str {
+        self.cafe_name.as_deref().unwrap_or(NO_CAFE_LABEL)
+    }
+
     pub fn to_timeline_event(&self) -> NewTimelineEvent {
         NewTimelineEvent {
             entity_type: EntityType::Cup,
@@ -47,13 +70,14 @@ impl CupWithDetails {
                 },
                 TimelineEventDetail {
                     label: "Cafe".to_string(),
-                    value: self.cafe_name.clone(),
+                    value: self.place_name().to_string(),
                 },
             ],
             tasting_notes: vec![],
             slug: Some(self.roast_slug.clone()),
             roaster_slug: Some(self.roaster_slug.clone()),
             brew_data: None,
+            created_by: self.cup.created_by,
         }
     }
 }
@@ -61,26 +85,148 @@ impl CupWithDetails {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NewCup {
     pub roast_id: RoastId,
-    pub cafe_id: CafeId,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_optional_id",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub cafe_id: Option<CafeId>,
+    /// Must be a bag of `roast_id`.
+    #[serde(
+        default,
+        deserialize_with = "deserialize_optional_id",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub bag_id: Option<BagId>,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_optional_id",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub rating: Option<u8>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub created_at: Option<DateTime<Utc>>,
 }
 
+// The cafe, bag and rating are each left alone, cleared, or set.
+#[allow(clippy::option_option)]
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct UpdateCup {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub roast_id: Option<RoastId>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub cafe_id: Option<CafeId>,
+    /// `Some(None)` moves the cup away from its cafe.
+    #[serde(
+        default,
+        deserialize_with = "deserialize_clearable_id",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub cafe_id: Option<Option<CafeId>>,
+    /// `Some(None)` unlinks the cup from its bag.
+    #[serde(
+        default,
+        deserialize_with = "deserialize_clearable_id",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub bag_id: Option<Option<BagId>>,
+    /// `Some(None)` clears the rating.
+    #[serde(
+        default,
+        deserialize_with = "deserialize_clearable_id",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub rating: Option<Option<u8>>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub created_at: Option<DateTime<Utc>>,
 }
 
+/// Deserializes an ID, or a rating, that can be cleared: a missing field is no
+/// change, while `null` or an empty form value is `Some(None)`.
+#[allow(clippy::option_option)]
+pub(crate) fn deserialize_clearable_id<'de, D, T>(
+    deserializer: D,
+) -> Result<Option<Option<T>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: TryFrom<i64>,
+{
+    let invalid = || serde::de::Error::custom("invalid id");
+    let number = match serde_json::Value::deserialize(deserializer)? {
+        serde_json::Value::Null => return Ok(Some(None)),
+        serde_json::Value::String(s) if s.trim().is_empty() => return Ok(Some(None)),
+        serde_json::Value::String(s) => s.trim().parse::<i64>().map_err(|_| invalid())?,
+        serde_json::Value::Number(n) => n.as_i64().ok_or_else(invalid)?,
+        _ => return Err(invalid()),
+    };
+    T::try_from(number)
+        .map(|value| Some(Some(value)))
+        .map_err(|_| invalid())
+}
+
+/// Like [`deserialize_clearable_id`], for fields where clearing and leaving
+/// out mean the same thing.
+fn deserialize_optional_id<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: TryFrom<i64>,
+{
+    deserialize_clearable_id(deserializer).map(Option::flatten)
+}
+
+impl Validate for NewCup {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+        errors.check(
+            self.roast_id.into_inner() > 0,
+            "roast_id",
+            "invalid roast id",
+        );
+        if let Some(cafe_id) = self.cafe_id {
+            errors.check(cafe_id.into_inner() > 0, "cafe_id", "invalid cafe id");
+        }
+        if let Some(bag_id) = self.bag_id {
+            errors.check(bag_id.into_inner() > 0, "bag_id", "invalid bag id");
+        }
+        if let Some(rating) = self.rating {
+            check_rating(&mut errors, rating);
+        }
+        errors.into_result()
+    }
+}
+
+impl Validate for UpdateCup {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+        if let Some(roast_id) = self.roast_id {
+            errors.check(roast_id.into_inner() > 0, "roast_id", "invalid roast id");
+        }
+        if let Some(Some(cafe_id)) = self.cafe_id {
+            errors.check(cafe_id.into_inner() > 0, "cafe_id", "invalid cafe id");
+        }
+        if let Some(Some(bag_id)) = self.bag_id {
+            errors.check(bag_id.into_inner() > 0, "bag_id", "invalid bag id");
+        }
+        if let Some(Some(rating)) = self.rating {
+            check_rating(&mut errors, rating);
+        }
+        errors.into_result()
+    }
+}
+
+fn check_rating(errors: &mut ValidationErrors, rating: u8) {
+    errors.check(
+        (1..=MAX_STARS).contains(&rating),
+        "rating",
+        "rating must be between 1 and 5 stars",
+    );
+}
+
 /// Filter criteria for cup queries.
 #[derive(Debug, Default, Clone)]
 pub struct CupFilter {
     pub cafe_id: Option<CafeId>,
     pub roast_id: Option<RoastId>,
+    /// Only cups drunk between these dates, inclusive.
+    pub between: Option<(NaiveDate, NaiveDate)>,
 }
 
 impl CupFilter {
@@ -104,6 +250,14 @@ impl CupFilter {
             ..Self::default()
         }
     }
+
+    /// Filter for cups drunk between two dates, inclusive.
+    pub fn between(start: NaiveDate, end: NaiveDate) -> Self {
+        Self {
+            between: Some((start, end)),
+            ..Self::default()
+        }
+    }
 }
 
 define_sort_key!(pub CupSortKey {
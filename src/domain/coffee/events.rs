@@ -0,0 +1,228 @@
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::normalize_optional_field;
+use crate::domain::entity_type::EntityType;
+use crate::domain::ids::{CafeId, EventId, RoasterId, UserId};
+use crate::domain::timeline::{NewTimelineEvent, TimelineEventDetail};
+use crate::domain::validation::{Validate, ValidationErrors};
+
+/// A coffee event attended: a cupping, festival or tasting.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Event {
+    pub id: EventId,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub venue: Option<String>,
+    pub date: NaiveDate,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    /// Coffees tried there, as free text since most won't be in the library.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub coffees: Vec<String>,
+    /// Roasters encountered there.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub roaster_ids: Vec<RoasterId>,
+    /// Cafes encountered there.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cafe_ids: Vec<CafeId>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_by: Option<UserId>,
+}
+
+impl Event {
+    pub fn to_timeline_event(&self) -> NewTimelineEvent {
+        let mut details = Vec::new();
+        if let Some(venue) = &self.venue {
+            details.push(TimelineEventDetail {
+                label: "Venue".to_string(),
+                value: venue.clone(),
+            });
+        }
+        if !self.coffees.is_empty() {
+            details.push(TimelineEventDetail {
+                label: "Coffees".to_string(),
+                value: self.coffees.join(", "),
+            });
+        }
+
+        NewTimelineEvent {
+            entity_type: EntityType::Event,
+            entity_id: self.id.into_inner(),
+            action: "attended".to_string(),
+            occurred_at: self.date.and_time(NaiveTime::MIN).and_utc(),
+            title: self.name.clone(),
+            details,
+            tasting_notes: vec![],
+            slug: None,
+            roaster_slug: None,
+            brew_data: None,
+            created_by: self.created_by,
+        }
+    }
+}
+
+/// Narrows a list of events to those a roaster or cafe was met at.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EventFilter {
+    pub roaster_id: Option<RoasterId>,
+    pub cafe_id: Option<CafeId>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewEvent {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub venue: Option<String>,
+    pub date: NaiveDate,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub coffees: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub roaster_ids: Vec<RoasterId>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cafe_ids: Vec<CafeId>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+impl NewEvent {
+    pub fn normalize(mut self) -> Self {
+        self.name = self.name.trim().to_string();
+        self.venue = normalize_optional_field(self.venue);
+        self.notes = normalize_optional_field(self.notes);
+        self.coffees = normalize_coffees(self.coffees);
+        self.roaster_ids = dedup_ids(self.roaster_ids);
+        self.cafe_ids = dedup_ids(self.cafe_ids);
+        self
+    }
+}
+
+/// Changes to an event. The coffee and link lists replace the stored ones
+/// when set.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UpdateEvent {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub venue: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date: Option<NaiveDate>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coffees: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub roaster_ids: Option<Vec<RoasterId>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cafe_ids: Option<Vec<CafeId>>,
+}
+
+impl UpdateEvent {
+    pub fn normalize(mut self) -> Self {
+        self.name = self.name.map(|name| name.trim().to_string());
+        self.coffees = self.coffees.map(normalize_coffees);
+        self.roaster_ids = self.roaster_ids.map(dedup_ids);
+        self.cafe_ids = self.cafe_ids.map(dedup_ids);
+        self
+    }
+}
+
+/// Trims each coffee and drops blanks and repeats, keeping the order given.
+fn normalize_coffees(coffees: Vec<String>) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::with_capacity(coffees.len());
+    for coffee in coffees {
+        let coffee = coffee.trim();
+        if !coffee.is_empty() && !normalized.iter().any(|c| c.eq_ignore_ascii_case(coffee)) {
+            normalized.push(coffee.to_string());
+        }
+    }
+    normalized
+}
+
+fn dedup_ids<T: PartialEq>(ids: Vec<T>) -> Vec<T> {
+    let mut unique = Vec::with_capacity(ids.len());
+    for id in ids {
+        if !unique.contains(&id) {
+            unique.push(id);
+        }
+    }
+    unique
+}
+
+impl Validate for NewEvent {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+        errors.require("name", &self.name);
+        errors.into_result()
+    }
+}
+
+impl Validate for UpdateEvent {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+        errors.require_if_set("name", self.name.as_deref());
+        errors.into_result()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_event() -> NewEvent {
+        NewEvent {
+            name: "  London Coffee Festival  ".to_string(),
+            venue: Some("  Truman Brewery ".to_string()),
+            date: NaiveDate::from_ymd_opt(2026, 4, 18).unwrap(),
+            notes: Some("   ".to_string()),
+            coffees: vec![
+                " Kochere ".to_string(),
+                String::new(),
+                "kochere".to_string(),
+                "Finca Deborah".to_string(),
+            ],
+            roaster_ids: vec![RoasterId::new(2), RoasterId::new(1), RoasterId::new(2)],
+            cafe_ids: vec![],
+            created_at: None,
+        }
+    }
+
+    #[test]
+    fn normalize_trims_and_dedups() {
+        let event = new_event().normalize();
+        assert_eq!(event.name, "London Coffee Festival");
+        assert_eq!(event.venue.as_deref(), Some("Truman Brewery"));
+        assert_eq!(event.notes, None);
+        assert_eq!(event.coffees, ["Kochere", "Finca Deborah"]);
+        assert_eq!(event.roaster_ids, [RoasterId::new(2), RoasterId::new(1)]);
+    }
+
+    #[test]
+    fn timeline_event_falls_on_the_event_date() {
+        let new = new_event().normalize();
+        let event = Event {
+            id: EventId::new(7),
+            name: new.name,
+            venue: new.venue,
+            date: new.date,
+            notes: new.notes,
+            coffees: new.coffees,
+            roaster_ids: new.roaster_ids,
+            cafe_ids: new.cafe_ids,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            created_by: None,
+        };
+
+        let timeline = event.to_timeline_event();
+        assert_eq!(timeline.entity_type, EntityType::Event);
+        assert_eq!(timeline.action, "attended");
+        assert_eq!(timeline.occurred_at.date_naive(), event.date);
+        assert_eq!(timeline.details.len(), 2);
+        assert_eq!(timeline.details[1].value, "Kochere, Finca Deborah");
+    }
+}
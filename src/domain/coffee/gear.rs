@@ -5,16 +5,18 @@ use serde::{Deserialize, Serialize};
 
 use crate::define_sort_key;
 use crate::domain::entity_type::EntityType;
-use crate::domain::ids::GearId;
+use crate::domain::ids::{GearId, UserId};
 use crate::domain::timeline::{NewTimelineEvent, TimelineEventDetail};
+use crate::domain::validation::{Validate, ValidationErrors};
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum GearCategory {
     Grinder,
     Brewer,
     #[serde(rename = "filter_paper")]
     FilterPaper,
+    Kettle,
 }
 
 impl GearCategory {
@@ -23,6 +25,7 @@ impl GearCategory {
             GearCategory::Grinder => "grinder",
             GearCategory::Brewer => "brewer",
             GearCategory::FilterPaper => "filter_paper",
+            GearCategory::Kettle => "kettle",
         }
     }
 
@@ -31,6 +34,7 @@ impl GearCategory {
             GearCategory::Grinder => "Grinder",
             GearCategory::Brewer => "Brewer",
             GearCategory::FilterPaper => "Filter Paper",
+            GearCategory::Kettle => "Kettle",
         }
     }
 }
@@ -43,22 +47,173 @@ impl FromStr for GearCategory {
             "grinder" => Ok(GearCategory::Grinder),
             "brewer" => Ok(GearCategory::Brewer),
             "filter_paper" => Ok(GearCategory::FilterPaper),
+            "kettle" => Ok(GearCategory::Kettle),
             _ => Err(()),
         }
     }
 }
 
+/// How a brewer gets coffee out of the grounds, which decides what a brew
+/// with it records.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BrewMethod {
+    /// Grounds steep in the water, as in a French press or `AeroPress`.
+    Immersion,
+    /// Water is poured through the grounds, as in a V60 or Chemex.
+    Percolation,
+}
+
+impl BrewMethod {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BrewMethod::Immersion => "immersion",
+            BrewMethod::Percolation => "percolation",
+        }
+    }
+
+    pub fn display_label(&self) -> &'static str {
+        match self {
+            BrewMethod::Immersion => "Immersion",
+            BrewMethod::Percolation => "Percolation",
+        }
+    }
+}
+
+impl FromStr for BrewMethod {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "immersion" => Ok(BrewMethod::Immersion),
+            "percolation" => Ok(BrewMethod::Percolation),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Furthest a kettle's water can stray from its setting, in Celsius.
+const MAX_TEMP_OFFSET: f64 = 30.0;
+
+/// Hardest water a kettle can record, in ppm; tap water rarely tops 500.
+const MAX_WATER_HARDNESS: i32 = 1000;
+
+/// Default number of filter papers left below which a reorder is due.
+pub const LOW_FILTER_STOCK: i32 = 10;
+
+/// Most filter papers a stock count or restock can hold; a few big boxes.
+const MAX_FILTER_STOCK: i32 = 10_000;
+
+/// Brewers recognised by name, with how they brew and whether they take a
+/// filter paper. Checked in order against the lowercased make and model.
+const KNOWN_BREWERS: &[(&str, BrewMethod, bool)] = &[
+    ("aeropress", BrewMethod::Immersion, true),
+    ("clever", BrewMethod::Immersion, true),
+    ("french press", BrewMethod::Immersion, false),
+    ("cafetiere", BrewMethod::Immersion, false),
+    ("espro", BrewMethod::Immersion, false),
+    ("v60", BrewMethod::Percolation, true),
+    ("chemex", BrewMethod::Percolation, true),
+    ("kalita", BrewMethod::Percolation, true),
+    ("origami", BrewMethod::Percolation, true),
+    ("moka", BrewMethod::Percolation, false),
+];
+
+/// What a brewer needs from a brew: its method and whether it takes a filter
+/// paper. Either is `None` when neither set on the gear nor recognised from
+/// its name.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct BrewerProfile {
+    pub method: Option<BrewMethod>,
+    pub uses_filter: Option<bool>,
+}
+
+impl BrewerProfile {
+    /// Recognise a brewer from its make and model.
+    pub fn detect(make: &str, model: &str) -> Self {
+        let name = format!("{make} {model}").to_lowercase();
+        KNOWN_BREWERS
+            .iter()
+            .find(|(needle, ..)| name.contains(needle))
+            .map_or_else(Self::default, |&(_, method, uses_filter)| Self {
+                method: Some(method),
+                uses_filter: Some(uses_filter),
+            })
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Gear {
     pub id: GearId,
     pub category: GearCategory,
     pub make: String,
     pub model: String,
+    /// Water temperature in Celsius to start brews with, for brewers.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub water_temp: Option<f64>,
+    /// How a brewer brews, when set rather than recognised from its name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub brew_method: Option<BrewMethod>,
+    /// Whether a brewer takes a filter paper, when set rather than
+    /// recognised from its name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uses_filter: Option<bool>,
+    /// Degrees Celsius the water reaching the coffee differs from what a
+    /// kettle is set to, negative when it cools on the way.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temp_offset: Option<f64>,
+    /// Hardness of the water a kettle is filled with, in ppm as `CaCO3`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub water_hardness: Option<i32>,
+    /// Filter papers left, counted down as brews use them. Untracked when
+    /// unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stock_count: Option<i32>,
+    /// Overrides [`LOW_FILTER_STOCK`] for this filter paper.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub low_stock_threshold: Option<i32>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_by: Option<UserId>,
 }
 
 impl Gear {
+    /// What brews with this brewer record, from its settings where it has
+    /// them and its name where it doesn't. Empty for gear that isn't a
+    /// brewer.
+    pub fn brewer_profile(&self) -> BrewerProfile {
+        if self.category != GearCategory::Brewer {
+            return BrewerProfile::default();
+        }
+        let detected = BrewerProfile::detect(&self.make, &self.model);
+        BrewerProfile {
+            method: self.brew_method.or(detected.method),
+            uses_filter: self.uses_filter.or(detected.uses_filter),
+        }
+    }
+
+    /// Only what's been set on this brewer, without falling back to its name.
+    pub fn declared_profile(&self) -> BrewerProfile {
+        BrewerProfile {
+            method: self.brew_method,
+            uses_filter: self.uses_filter,
+        }
+    }
+
+    /// The temperature reaching the coffee when a kettle is set to
+    /// `set_temp`, if the kettle's been calibrated.
+    pub fn actual_temp(&self, set_temp: f64) -> Option<f64> {
+        self.temp_offset.map(|offset| set_temp + offset)
+    }
+
+    /// Whether this filter paper's tracked stock is below its threshold and
+    /// due a reorder.
+    pub fn is_running_low(&self) -> bool {
+        let threshold = self.low_stock_threshold.unwrap_or(LOW_FILTER_STOCK);
+        self.stock_count.is_some_and(|count| count < threshold)
+    }
+
     pub fn to_timeline_event(&self) -> NewTimelineEvent {
         NewTimelineEvent {
             entity_type: EntityType::Gear,
@@ -84,6 +239,7 @@ impl Gear {
             slug: None,
             roaster_slug: None,
             brew_data: None,
+            created_by: self.created_by,
         }
     }
 }
@@ -94,6 +250,20 @@ pub struct NewGear {
     pub make: String,
     pub model: String,
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub water_temp: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub brew_method: Option<BrewMethod>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uses_filter: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temp_offset: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub water_hardness: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stock_count: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub low_stock_threshold: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub created_at: Option<DateTime<Utc>>,
 }
 
@@ -102,9 +272,137 @@ pub struct UpdateGear {
     pub make: Option<String>,
     pub model: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub water_temp: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub brew_method: Option<BrewMethod>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uses_filter: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temp_offset: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub water_hardness: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stock_count: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub low_stock_threshold: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub created_at: Option<DateTime<Utc>>,
 }
 
+impl Validate for NewGear {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+        errors.check(!self.make.trim().is_empty(), "make", "make cannot be empty");
+        errors.check(
+            !self.model.trim().is_empty(),
+            "model",
+            "model cannot be empty",
+        );
+        check_water_temp(&mut errors, self.water_temp);
+        check_kettle(&mut errors, self.temp_offset, self.water_hardness);
+        check_filter_stock(&mut errors, self.stock_count, self.low_stock_threshold);
+        if self.category != GearCategory::FilterPaper {
+            errors.check(
+                self.stock_count.is_none() && self.low_stock_threshold.is_none(),
+                "stock_count",
+                "only filter papers have a stock count",
+            );
+        }
+        if self.category != GearCategory::Kettle {
+            errors.check(
+                self.temp_offset.is_none() && self.water_hardness.is_none(),
+                "temp_offset",
+                "only kettles have a temperature offset or water hardness",
+            );
+        }
+        if self.category != GearCategory::Brewer {
+            errors.check(
+                self.water_temp.is_none(),
+                "water_temp",
+                "only brewers have a water temperature",
+            );
+            errors.check(
+                self.brew_method.is_none() && self.uses_filter.is_none(),
+                "brew_method",
+                "only brewers have a brew method",
+            );
+        }
+        errors.into_result()
+    }
+}
+
+impl Validate for UpdateGear {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+        errors.require_if_set("make", self.make.as_deref());
+        errors.require_if_set("model", self.model.as_deref());
+        check_water_temp(&mut errors, self.water_temp);
+        check_kettle(&mut errors, self.temp_offset, self.water_hardness);
+        check_filter_stock(&mut errors, self.stock_count, self.low_stock_threshold);
+        errors.into_result()
+    }
+}
+
+fn check_water_temp(errors: &mut ValidationErrors, water_temp: Option<f64>) {
+    if let Some(temp) = water_temp {
+        errors.check(
+            (0.0..=100.0).contains(&temp),
+            "water_temp",
+            "water temperature must be between 0 and 100",
+        );
+    }
+}
+
+fn check_kettle(errors: &mut ValidationErrors, temp_offset: Option<f64>, hardness: Option<i32>) {
+    if let Some(offset) = temp_offset {
+        errors.check(
+            (-MAX_TEMP_OFFSET..=MAX_TEMP_OFFSET).contains(&offset),
+            "temp_offset",
+            "temperature offset must be within 30 degrees",
+        );
+    }
+    if let Some(ppm) = hardness {
+        errors.check(
+            (0..=MAX_WATER_HARDNESS).contains(&ppm),
+            "water_hardness",
+            "water hardness must be between 0 and 1000 ppm",
+        );
+    }
+}
+
+fn check_filter_stock(errors: &mut ValidationErrors, count: Option<i32>, threshold: Option<i32>) {
+    for (field, value) in [("stock_count", count), ("low_stock_threshold", threshold)] {
+        if let Some(value) = value {
+            errors.check(
+                (0..=MAX_FILTER_STOCK).contains(&value),
+                field,
+                format!(
+                    "{} must be between 0 and {MAX_FILTER_STOCK}",
+                    field.replace('_', " ")
+                ),
+            );
+        }
+    }
+}
+
+/// Filter papers bought, to add to what's left in stock.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Restock {
+    pub count: i32,
+}
+
+impl Validate for Restock {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+        errors.check(
+            (1..=MAX_FILTER_STOCK).contains(&self.count),
+            "count",
+            format!("restock count must be between 1 and {MAX_FILTER_STOCK}"),
+        );
+        errors.into_result()
+    }
+}
+
 #[derive(Debug, Default, Clone)]
 pub struct GearFilter {
     pub category: Option<GearCategory>,
@@ -148,4 +446,111 @@ mod tests {
     fn gear_category_invalid() {
         assert!("invalid".parse::<GearCategory>().is_err());
     }
+
+    fn brewer(make: &str, model: &str) -> Gear {
+        Gear {
+            id: GearId::new(1),
+            category: GearCategory::Brewer,
+            make: make.to_string(),
+            model: model.to_string(),
+            water_temp: None,
+            brew_method: None,
+            uses_filter: None,
+            temp_offset: None,
+            water_hardness: None,
+            stock_count: None,
+            low_stock_threshold: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            created_by: None,
+        }
+    }
+
+    #[test]
+    fn brewer_profile_is_recognised_from_the_name() {
+        let profile = brewer("AeroPress", "Original").brewer_profile();
+        assert_eq!(profile.method, Some(BrewMethod::Immersion));
+        assert_eq!(profile.uses_filter, Some(true));
+
+        let profile = brewer("Bodum", "Chambord French Press").brewer_profile();
+        assert_eq!(profile.uses_filter, Some(false));
+
+        assert_eq!(
+            brewer("Hario", "V60 02").brewer_profile().method,
+            Some(BrewMethod::Percolation)
+        );
+        assert_eq!(
+            brewer("Acme", "Mystery").brewer_profile(),
+            BrewerProfile::default()
+        );
+    }
+
+    #[test]
+    fn brewer_settings_override_the_recognised_profile() {
+        let mut aeropress = brewer("AeroPress", "Original");
+        aeropress.uses_filter = Some(false);
+        let profile = aeropress.brewer_profile();
+        assert_eq!(profile.method, Some(BrewMethod::Immersion));
+        assert_eq!(profile.uses_filter, Some(false));
+
+        aeropress.category = GearCategory::Grinder;
+        assert_eq!(aeropress.brewer_profile(), BrewerProfile::default());
+    }
+
+    #[test]
+    fn kettles_carry_their_own_calibration() {
+        let mut kettle = brewer("Fellow", "Stagg EKG");
+        kettle.category = GearCategory::Kettle;
+        assert_eq!(kettle.actual_temp(96.0), None);
+
+        kettle.temp_offset = Some(-2.5);
+        assert_eq!(kettle.actual_temp(96.0), Some(93.5));
+
+        let new_gear = |category, temp_offset| NewGear {
+            category,
+            make: "Fellow".to_string(),
+            model: "Stagg EKG".to_string(),
+            water_temp: None,
+            brew_method: None,
+            uses_filter: None,
+            temp_offset,
+            water_hardness: None,
+            stock_count: None,
+            low_stock_threshold: None,
+            created_at: None,
+        };
+        assert!(
+            new_gear(GearCategory::Kettle, Some(-2.5))
+                .validate()
+                .is_ok()
+        );
+        assert!(
+            new_gear(GearCategory::Kettle, Some(-45.0))
+                .validate()
+                .is_err()
+        );
+        assert!(
+            new_gear(GearCategory::Brewer, Some(-2.5))
+                .validate()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn filter_papers_run_low_below_their_threshold() {
+        let mut papers = brewer("Hario", "V60 Tabbed 02");
+        papers.category = GearCategory::FilterPaper;
+        assert!(!papers.is_running_low());
+
+        papers.stock_count = Some(LOW_FILTER_STOCK);
+        assert!(!papers.is_running_low());
+        papers.stock_count = Some(LOW_FILTER_STOCK - 1);
+        assert!(papers.is_running_low());
+
+        papers.low_stock_threshold = Some(0);
+        assert!(!papers.is_running_low());
+
+        assert!(Restock { count: 100 }.validate().is_ok());
+        assert!(Restock { count: 0 }.validate().is_err());
+    }
 }
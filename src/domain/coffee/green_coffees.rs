@@ -0,0 +1,167 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::normalize_optional_field;
+use crate::domain::ids::GreenCoffeeId;
+use crate::domain::validation::{Validate, ValidationErrors};
+
+/// A lot of unroasted coffee bought for roasting at home.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GreenCoffee {
+    pub id: GreenCoffeeId,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub origin: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub process: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub supplier: Option<String>,
+    /// Grams bought.
+    pub amount: f64,
+    /// Grams not yet roasted: `amount` less every batch's weight in.
+    #[serde(default)]
+    pub remaining: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub price: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub purchased_on: Option<NaiveDate>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewGreenCoffee {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub origin: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub process: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub supplier: Option<String>,
+    pub amount: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub price: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub purchased_on: Option<NaiveDate>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+impl NewGreenCoffee {
+    pub fn normalize(mut self) -> Self {
+        self.name = self.name.trim().to_string();
+        self.origin = normalize_optional_field(self.origin);
+        self.process = normalize_optional_field(self.process);
+        self.supplier = normalize_optional_field(self.supplier);
+        self.notes = normalize_optional_field(self.notes);
+        self
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UpdateGreenCoffee {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub origin: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub process: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub supplier: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub amount: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub price: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub purchased_on: Option<NaiveDate>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+}
+
+impl UpdateGreenCoffee {
+    pub fn normalize(mut self) -> Self {
+        self.name = self.name.map(|name| name.trim().to_string());
+        self
+    }
+}
+
+fn check_weight(errors: &mut ValidationErrors, field: &str, grams: Option<f64>) {
+    if let Some(grams) = grams {
+        errors.check(
+            grams.is_finite() && grams > 0.0,
+            field,
+            format!("{field} must be a positive number of grams"),
+        );
+    }
+}
+
+fn check_price(errors: &mut ValidationErrors, price: Option<f64>) {
+    if let Some(price) = price {
+        errors.check(
+            price.is_finite() && price >= 0.0,
+            "price",
+            "price cannot be negative",
+        );
+    }
+}
+
+impl Validate for NewGreenCoffee {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+        errors.require("name", &self.name);
+        check_weight(&mut errors, "amount", Some(self.amount));
+        check_price(&mut errors, self.price);
+        errors.into_result()
+    }
+}
+
+impl Validate for UpdateGreenCoffee {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+        errors.require_if_set("name", self.name.as_deref());
+        check_weight(&mut errors, "amount", self.amount);
+        check_price(&mut errors, self.price);
+        errors.into_result()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_green() -> NewGreenCoffee {
+        NewGreenCoffee {
+            name: "  Yirgacheffe Kochere  ".to_string(),
+            origin: Some(" Ethiopia ".to_string()),
+            process: Some("   ".to_string()),
+            supplier: None,
+            amount: 1000.0,
+            price: Some(18.5),
+            purchased_on: None,
+            notes: None,
+            created_at: None,
+        }
+    }
+
+    #[test]
+    fn normalize_trims_and_clears_blank_fields() {
+        let green = new_green().normalize();
+        assert_eq!(green.name, "Yirgacheffe Kochere");
+        assert_eq!(green.origin.as_deref(), Some("Ethiopia"));
+        assert_eq!(green.process, None);
+    }
+
+    #[test]
+    fn validate_rejects_empty_lots() {
+        let green = NewGreenCoffee {
+            amount: 0.0,
+            ..new_green()
+        };
+        assert!(green.validate().is_err());
+        assert!(new_green().validate().is_ok());
+    }
+}
@@ -0,0 +1,173 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::ids::{GearId, GrinderCalibrationId};
+
+/// A user-entered reference point: `setting_a` on one grinder produces the
+/// same grind as `setting_b` on another. Points are symmetric, so either
+/// grinder can be the source of a conversion.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GrinderCalibration {
+    pub id: GrinderCalibrationId,
+    pub grinder_a_id: GearId,
+    pub setting_a: f64,
+    pub grinder_b_id: GearId,
+    pub setting_b: f64,
+    pub created_at: DateTime<Utc>,
+}
+
+impl GrinderCalibration {
+    /// The point as `(setting on from, setting on to)`, if it relates those grinders.
+    pub fn oriented(&self, from: GearId, to: GearId) -> Option<(f64, f64)> {
+        if self.grinder_a_id == from && self.grinder_b_id == to {
+            Some((self.setting_a, self.setting_b))
+        } else if self.grinder_b_id == from && self.grinder_a_id == to {
+            Some((self.setting_b, self.setting_a))
+        } else {
+            None
+        }
+    }
+
+    /// The grinder on the other side of the point from `grinder_id`.
+    pub fn other_grinder(&self, grinder_id: GearId) -> GearId {
+        if self.grinder_a_id == grinder_id {
+            self.grinder_b_id
+        } else {
+            self.grinder_a_id
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewGrinderCalibration {
+    pub grinder_a_id: GearId,
+    pub setting_a: f64,
+    pub grinder_b_id: GearId,
+    pub setting_b: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+impl NewGrinderCalibration {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.grinder_a_id == self.grinder_b_id {
+            return Err("a calibration point needs two different grinders".to_string());
+        }
+        for setting in [self.setting_a, self.setting_b] {
+            if !setting.is_finite() || setting < 0.0 {
+                return Err(format!("invalid grind setting {setting}"));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A setting translated from one grinder to another.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GrindConversion {
+    pub from_grinder_id: GearId,
+    pub to_grinder_id: GearId,
+    pub from_setting: f64,
+    pub to_setting: f64,
+    /// Number of calibration points the estimate is based on.
+    pub reference_points: usize,
+}
+
+/// Estimate the equivalent setting from `(from, to)` reference points.
+///
+/// Settings between points are interpolated linearly and settings outside
+/// them follow the nearest segment. A single point is treated as a constant
+/// ratio between the two scales. Results are rounded to one decimal place.
+pub fn convert_setting(points: &[(f64, f64)], setting: f64) -> Option<f64> {
+    let mut points: Vec<(f64, f64)> = points.to_vec();
+    points.sort_by(|a, b| a.0.total_cmp(&b.0));
+    // Two points for the same source setting would make a zero-width
+    // segment, so only the first is kept.
+    points.dedup_by(|next, prev| (next.0 - prev.0).abs() < f64::EPSILON);
+
+    let estimate = match points.as_slice() {
+        [] => return None,
+        [(x, y)] if *x > 0.0 => setting * y / x,
+        [(x, y)] => y + (setting - x),
+        _ => {
+            let segment = points
+                .windows(2)
+                .position(|w| setting <= w[1].0)
+                .unwrap_or(points.len() - 2);
+            let (x0, y0) = points[segment];
+            let (x1, y1) = points[segment + 1];
+            y0 + (setting - x0) * (y1 - y0) / (x1 - x0)
+        }
+    };
+
+    Some((estimate.max(0.0) * 10.0).round() / 10.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_point_scales_proportionally() {
+        assert_eq!(convert_setting(&[(24.0, 38.0)], 24.0), Some(38.0));
+        assert_eq!(convert_setting(&[(24.0, 36.0)], 20.0), Some(30.0));
+    }
+
+    #[test]
+    fn interpolates_between_points() {
+        let points = [(30.0, 50.0), (20.0, 30.0)];
+        assert_eq!(convert_setting(&points, 25.0), Some(40.0));
+        assert_eq!(convert_setting(&points, 20.0), Some(30.0));
+    }
+
+    #[test]
+    fn extrapolates_from_nearest_segment() {
+        let points = [(20.0, 30.0), (30.0, 50.0), (40.0, 55.0)];
+        assert_eq!(convert_setting(&points, 10.0), Some(10.0));
+        assert_eq!(convert_setting(&points, 50.0), Some(60.0));
+    }
+
+    #[test]
+    fn never_returns_a_negative_setting() {
+        let points = [(20.0, 30.0), (30.0, 50.0)];
+        assert_eq!(convert_setting(&points, 0.0), Some(0.0));
+    }
+
+    #[test]
+    fn no_points_means_no_estimate() {
+        assert_eq!(convert_setting(&[], 24.0), None);
+    }
+
+    #[test]
+    fn oriented_flips_points_entered_the_other_way_round() {
+        let point = GrinderCalibration {
+            id: GrinderCalibrationId::new(1),
+            grinder_a_id: GearId::new(1),
+            setting_a: 24.0,
+            grinder_b_id: GearId::new(2),
+            setting_b: 38.0,
+            created_at: Utc::now(),
+        };
+        assert_eq!(
+            point.oriented(GearId::new(2), GearId::new(1)),
+            Some((38.0, 24.0))
+        );
+        assert_eq!(point.oriented(GearId::new(1), GearId::new(3)), None);
+    }
+
+    #[test]
+    fn validate_rejects_same_grinder_and_negative_settings() {
+        let mut point = NewGrinderCalibration {
+            grinder_a_id: GearId::new(1),
+            setting_a: 24.0,
+            grinder_b_id: GearId::new(1),
+            setting_b: 38.0,
+            created_at: None,
+        };
+        assert!(point.validate().is_err());
+        point.grinder_b_id = GearId::new(2);
+        assert!(point.validate().is_ok());
+        point.setting_b = -1.0;
+        assert!(point.validate().is_err());
+    }
+}
@@ -0,0 +1,254 @@
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::ids::{RoastId, RoasterId};
+
+/// Candidates at or above this confidence are matched without asking.
+pub const CONFIDENT_MATCH: f64 = 0.95;
+
+/// Candidates below this confidence are not worth showing.
+pub const MIN_CONFIDENCE: f64 = 0.3;
+
+/// How many candidates are offered for review.
+pub const MAX_CANDIDATES: usize = 3;
+
+/// Fuzzy matches never reach [`CONFIDENT_MATCH`]: a near miss is offered,
+/// not taken.
+const MAX_FUZZY_CONFIDENCE: f64 = 0.9;
+
+/// Words on a label that don't tell one roaster or roast from another.
+const FILLER_WORDS: &[&str] = &[
+    "and", "co", "coffee", "coffees", "company", "inc", "ltd", "roasters", "roastery", "roasting",
+    "the",
+];
+
+/// Lowercase words of `name` without accents, punctuation or filler, so
+/// "The Square Mile Coffee Roasters" and "Square Mile" compare equal. A name
+/// made only of filler keeps it.
+pub fn normalize_name(name: &str) -> String {
+    let slug = slug::slugify(name);
+    let words: Vec<&str> = slug
+        .split('-')
+        .filter(|word| !FILLER_WORDS.contains(word))
+        .collect();
+    if words.is_empty() {
+        slug.replace('-', " ")
+    } else {
+        words.join(" ")
+    }
+}
+
+/// Each word padded with two spaces in front and one behind, then cut into
+/// three-character windows, as `pg_trgm` does.
+fn trigrams(normalized: &str) -> HashSet<[char; 3]> {
+    normalized
+        .split(' ')
+        .filter(|word| !word.is_empty())
+        .flat_map(|word| {
+            let padded: Vec<char> = format!("  {word} ").chars().collect();
+            padded
+                .windows(3)
+                .map(|window| [window[0], window[1], window[2]])
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// How sure we are that `candidate` names the same thing as `query`: 1.0 for
+/// the same slug, [`CONFIDENT_MATCH`] when only filler words differ,
+/// otherwise the share of trigrams the two have in common.
+pub fn similarity(query: &str, candidate: &str) -> f64 {
+    if slug::slugify(query) == slug::slugify(candidate) {
+        return 1.0;
+    }
+    let (query, candidate) = (normalize_name(query), normalize_name(candidate));
+    if query.is_empty() || candidate.is_empty() {
+        return 0.0;
+    }
+    if query == candidate {
+        return CONFIDENT_MATCH;
+    }
+
+    let (query, candidate) = (trigrams(&query), trigrams(&candidate));
+    let shared = query.intersection(&candidate).count();
+    let total = query.union(&candidate).count();
+    if total == 0 {
+        return 0.0;
+    }
+    #[allow(clippy::cast_precision_loss)]
+    let jaccard = shared as f64 / total as f64;
+    jaccard.min(MAX_FUZZY_CONFIDENCE)
+}
+
+/// A roaster or roast to rank, with the names it has also gone by.
+#[derive(Debug, Clone)]
+pub struct Named<Id> {
+    pub id: Id,
+    pub name: String,
+    pub aliases: Vec<String>,
+}
+
+/// A record that might be the one a label names.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MatchCandidate<Id> {
+    pub id: Id,
+    pub name: String,
+    /// The alias that matched, when it matched better than the name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alias: Option<String>,
+    /// From 0 to 1.
+    pub confidence: f64,
+}
+
+impl<Id> MatchCandidate<Id> {
+    pub fn is_confident(&self) -> bool {
+        self.confidence >= CONFIDENT_MATCH
+    }
+}
+
+/// The best [`MAX_CANDIDATES`] of `records` for `query`, most likely first.
+pub fn rank<Id>(
+    query: &str,
+    records: impl IntoIterator<Item = Named<Id>>,
+) -> Vec<MatchCandidate<Id>> {
+    if query.trim().is_empty() {
+        return Vec::new();
+    }
+
+    let mut candidates: Vec<MatchCandidate<Id>> = records
+        .into_iter()
+        .filter_map(|record| {
+            let mut best = MatchCandidate {
+                confidence: similarity(query, &record.name),
+                id: record.id,
+                name: record.name,
+                alias: None,
+            };
+            for alias in record.aliases {
+                let confidence = similarity(query, &alias);
+                if confidence > best.confidence {
+                    best.confidence = confidence;
+                    best.alias = Some(alias);
+                }
+            }
+            (best.confidence >= MIN_CONFIDENCE).then_some(best)
+        })
+        .collect();
+
+    candidates.sort_by(|a, b| {
+        b.confidence
+            .total_cmp(&a.confidence)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    candidates.truncate(MAX_CANDIDATES);
+    candidates
+}
+
+/// A roast that might be the one a label names, with the roaster it comes
+/// from. Its confidence covers the roaster as well as the roast.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RoastMatch {
+    #[serde(flatten)]
+    pub roast: MatchCandidate<RoastId>,
+    pub roaster_id: RoasterId,
+    pub roaster_name: String,
+}
+
+/// Existing roasters and roasts that a scanned label might name, for the
+/// scan review to choose from.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ScanMatches {
+    pub roasters: Vec<MatchCandidate<RoasterId>>,
+    pub roasts: Vec<RoastMatch>,
+}
+
+impl ScanMatches {
+    /// The roaster to match without asking, if any.
+    pub fn confident_roaster(&self) -> Option<&MatchCandidate<RoasterId>> {
+        self.roasters
+            .first()
+            .filter(|candidate| candidate.is_confident())
+    }
+
+    /// The roast to match without asking, if any. Only a roast of the
+    /// confidently matched roaster qualifies.
+    pub fn confident_roast(&self) -> Option<&RoastMatch> {
+        let roaster = self.confident_roaster()?;
+        self.roasts.first().filter(|candidate| {
+            candidate.roaster_id == roaster.id && candidate.roast.is_confident()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn named(id: i64, name: &str, aliases: &[&str]) -> Named<i64> {
+        Named {
+            id,
+            name: name.to_string(),
+            aliases: aliases.iter().map(|alias| (*alias).to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn normalizing_drops_filler() {
+        assert_eq!(
+            normalize_name("The Square Mile Coffee Roasters"),
+            "square mile"
+        );
+        assert_eq!(normalize_name("Café Señor, Ltd."), "cafe senor");
+        assert_eq!(normalize_name("The Coffee Co"), "the coffee co");
+    }
+
+    #[test]
+    fn identical_slugs_are_certain() {
+        assert!((similarity("Square Mile", "square-mile") - 1.0).abs() < f64::EPSILON);
+        assert!(
+            (similarity("Square Mile Coffee Roasters", "Square Mile") - CONFIDENT_MATCH).abs()
+                < f64::EPSILON
+        );
+    }
+
+    #[test]
+    fn near_misses_are_never_confident() {
+        let confidence = similarity("Square Mlie", "Square Mile");
+        assert!(confidence >= MIN_CONFIDENCE, "{confidence}");
+        assert!(confidence < CONFIDENT_MATCH, "{confidence}");
+        assert!(similarity("Ethiopia Guji", "Colombia Huila") < MIN_CONFIDENCE);
+    }
+
+    #[test]
+    fn ranks_best_first_and_drops_the_unlikely() {
+        let records = vec![
+            named(1, "Square Mile", &[]),
+            named(2, "Square One", &[]),
+            named(3, "Origin", &[]),
+        ];
+        let ranked = rank("Square Mile Coffee", records);
+        let ids: Vec<i64> = ranked.iter().map(|candidate| candidate.id).collect();
+        assert_eq!(ids[0], 1);
+        assert!(!ids.contains(&3));
+        assert!(ranked[0].is_confident());
+    }
+
+    #[test]
+    fn aliases_count_as_names() {
+        let ranked = rank(
+            "Fleet Street Roasters",
+            vec![named(1, "Square Mile", &["Fleet Street"])],
+        );
+        assert_eq!(ranked.len(), 1);
+        assert_eq!(ranked[0].alias.as_deref(), Some("Fleet Street"));
+        assert!(ranked[0].is_confident());
+    }
+
+    #[test]
+    fn keeps_only_the_best_few() {
+        let records = (0..10).map(|id| named(id, "Square Mile", &[]));
+        assert_eq!(rank("Square Mile", records).len(), MAX_CANDIDATES);
+        assert!(rank("  ", vec![named(1, "Square Mile", &[])]).is_empty());
+    }
+}
@@ -1,11 +1,26 @@
+pub mod aliases;
+pub mod bag_ledger;
 pub mod bags;
+pub mod blends;
+pub mod brew_parsing;
+pub mod brew_shares;
 pub mod brews;
 pub mod cafes;
+pub mod comments;
 pub mod cups;
+pub mod events;
 pub mod gear;
+pub mod green_coffees;
+pub mod grinder_calibrations;
+pub mod matching;
 pub mod nearby_cafes;
+pub mod processes;
+pub mod quick_notes;
+pub mod ratings;
+pub mod roast_batches;
 pub mod roasters;
 pub mod roasts;
+pub mod trips;
 
 /// Trims an optional string field, converting empty/whitespace-only values to `None`.
 pub(crate) fn normalize_optional_field(value: Option<String>) -> Option<String> {
@@ -0,0 +1,455 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::ids::CustomProcessId;
+use crate::domain::roasts::FieldSuggestion;
+
+const MAX_NAME_CHARS: usize = 40;
+
+/// The families processes are grouped into for stats, in display order.
+pub const PROCESS_FAMILIES: [&str; 4] = ["Washed", "Natural", "Honey", "Other"];
+
+/// Fallback used by AI extraction when a process isn't in the taxonomy and
+/// doesn't belong to a family either.
+const EXPERIMENTAL: &str = "Experimental";
+
+struct BuiltinProcess {
+    name: &'static str,
+    family: &'static str,
+    /// Other spellings that mean the same process, written as [`match_key`] returns them.
+    aliases: &'static [&'static str],
+}
+
+const BUILTIN_PROCESSES: &[BuiltinProcess] = &[
+    BuiltinProcess {
+        name: "Washed",
+        family: "Washed",
+        aliases: &["fully washed", "wet"],
+    },
+    BuiltinProcess {
+        name: "Natural",
+        family: "Natural",
+        aliases: &["dry", "sun dried", "unwashed"],
+    },
+    BuiltinProcess {
+        name: "Honey",
+        family: "Honey",
+        aliases: &["pulped natural", "semi dry"],
+    },
+    BuiltinProcess {
+        name: "White Honey",
+        family: "Honey",
+        aliases: &[],
+    },
+    BuiltinProcess {
+        name: "Yellow Honey",
+        family: "Honey",
+        aliases: &[],
+    },
+    BuiltinProcess {
+        name: "Red Honey",
+        family: "Honey",
+        aliases: &[],
+    },
+    BuiltinProcess {
+        name: "Black Honey",
+        family: "Honey",
+        aliases: &[],
+    },
+    BuiltinProcess {
+        name: "Semi-Washed",
+        family: "Washed",
+        aliases: &[],
+    },
+    BuiltinProcess {
+        name: "Wet-Hulled",
+        family: "Washed",
+        aliases: &["giling basah"],
+    },
+    BuiltinProcess {
+        name: "Anaerobic",
+        family: "Other",
+        aliases: &["anaerobic fermentation"],
+    },
+    BuiltinProcess {
+        name: "Anaerobic Washed",
+        family: "Washed",
+        aliases: &[],
+    },
+    BuiltinProcess {
+        name: "Anaerobic Natural",
+        family: "Natural",
+        aliases: &[],
+    },
+    BuiltinProcess {
+        name: "Anaerobic Honey",
+        family: "Honey",
+        aliases: &[],
+    },
+    BuiltinProcess {
+        name: "Carbonic Maceration",
+        family: "Other",
+        aliases: &[],
+    },
+    BuiltinProcess {
+        name: "Double Fermentation",
+        family: "Other",
+        aliases: &["double fermented"],
+    },
+    BuiltinProcess {
+        name: "Thermal Shock",
+        family: "Other",
+        aliases: &[],
+    },
+    BuiltinProcess {
+        name: "Yeast Inoculated",
+        family: "Other",
+        aliases: &["yeast fermented"],
+    },
+    BuiltinProcess {
+        name: EXPERIMENTAL,
+        family: "Other",
+        aliases: &[],
+    },
+];
+
+/// Lowercase, with hyphens and repeated spaces folded and a trailing
+/// "process"/"processed" dropped, so "Fully-Washed Process" matches "fully washed".
+fn match_key(value: &str) -> String {
+    let words: Vec<String> = value
+        .split(|c: char| c.is_whitespace() || c == '-' || c == '_')
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+    let words = match words.split_last() {
+        Some((last, rest)) if !rest.is_empty() && (last == "process" || last == "processed") => {
+            rest
+        }
+        _ => &words[..],
+    };
+    words.join(" ")
+}
+
+/// Group free text into the family it belongs to, so "Washed", "Fully
+/// Washed" and "Wet Process" count together. Used for custom processes
+/// without a family and for roasts logged before the taxonomy. Honey is
+/// checked first because "Pulped Natural" is a honey process.
+pub fn process_family(process: &str) -> &'static str {
+    let process = process.to_lowercase();
+    if process.contains("honey") || process.contains("pulped") {
+        "Honey"
+    } else if process.contains("natural") || process.contains("dry") {
+        "Natural"
+    } else if process.contains("washed") || process.contains("wet") {
+        "Washed"
+    } else {
+        "Other"
+    }
+}
+
+/// A user-defined process, offered alongside the built-ins. Roasts store
+/// processes by name, so a name identifies its process.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomProcess {
+    pub id: CustomProcessId,
+    pub name: String,
+    pub family: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewCustomProcess {
+    pub name: String,
+    /// One of [`PROCESS_FAMILIES`]; guessed from the name when left out.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub family: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+impl NewCustomProcess {
+    /// Trim whitespace, settle the family and check the name is new.
+    pub fn normalize(self) -> Result<Self, String> {
+        let name = self.name.split_whitespace().collect::<Vec<_>>().join(" ");
+
+        if name.is_empty() {
+            return Err("process name is required".to_string());
+        }
+        if name.chars().count() > MAX_NAME_CHARS {
+            return Err(format!(
+                "process name must be at most {MAX_NAME_CHARS} characters"
+            ));
+        }
+        if let Some(builtin) = ProcessTaxonomy::default().resolve(&name) {
+            return Err(format!(
+                "\"{}\" is already a built-in process",
+                builtin.name
+            ));
+        }
+
+        let family = match self.family.as_deref().map(str::trim) {
+            None | Some("") => process_family(&name),
+            Some(family) => PROCESS_FAMILIES
+                .iter()
+                .find(|known| known.eq_ignore_ascii_case(family))
+                .copied()
+                .ok_or_else(|| {
+                    format!(
+                        "process family must be one of {}",
+                        PROCESS_FAMILIES.join(", ")
+                    )
+                })?,
+        };
+
+        Ok(Self {
+            name,
+            family: Some(family.to_string()),
+            created_at: self.created_at,
+        })
+    }
+}
+
+/// A process roasts can be logged with.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProcessOption {
+    pub name: String,
+    pub family: String,
+    /// Set for user-defined processes, which can be deleted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<CustomProcessId>,
+}
+
+/// The built-in processes plus the user's own, used to turn whatever was
+/// typed into the name roasts are stored under.
+#[derive(Debug, Clone, Default)]
+pub struct ProcessTaxonomy {
+    custom: Vec<CustomProcess>,
+}
+
+impl ProcessTaxonomy {
+    pub fn new(custom: Vec<CustomProcess>) -> Self {
+        Self { custom }
+    }
+
+    /// Every process, built-ins first.
+    pub fn options(&self) -> Vec<ProcessOption> {
+        BUILTIN_PROCESSES
+            .iter()
+            .map(|builtin| ProcessOption {
+                name: builtin.name.to_string(),
+                family: builtin.family.to_string(),
+                id: None,
+            })
+            .chain(self.custom.iter().map(|custom| ProcessOption {
+                name: custom.name.clone(),
+                family: custom.family.clone(),
+                id: Some(custom.id),
+            }))
+            .collect()
+    }
+
+    /// The process `value` names, by name or a known alias, ignoring case.
+    pub fn resolve(&self, value: &str) -> Option<ProcessOption> {
+        let key = match_key(value);
+        if key.is_empty() {
+            return None;
+        }
+        self.options().into_iter().find(|option| {
+            match_key(&option.name) == key
+                || BUILTIN_PROCESSES
+                    .iter()
+                    .any(|builtin| builtin.name == option.name && builtin.aliases.contains(&&*key))
+        })
+    }
+
+    /// The name `value` is stored under, or an error saying it needs adding first.
+    pub fn canonical(&self, value: &str) -> Result<String, String> {
+        self.resolve(value)
+            .map(|option| option.name)
+            .ok_or_else(|| {
+                format!(
+                    "unknown process \"{}\"; add it as a custom process on the Admin page first",
+                    value.trim()
+                )
+            })
+    }
+
+    /// Like [`canonical`](Self::canonical), but never fails: unknown values
+    /// fall back to their family, or "Experimental". Used for AI extraction,
+    /// whose wording can't be corrected before the roast is saved.
+    pub fn closest(&self, value: &str) -> String {
+        if let Some(option) = self.resolve(value) {
+            return option.name;
+        }
+        match process_family(value) {
+            "Other" => EXPERIMENTAL.to_string(),
+            family => family.to_string(),
+        }
+    }
+
+    /// Processes whose name contains `query`, most used first. `used` holds
+    /// the processes roasts were logged with, which may be spelled as aliases;
+    /// values outside the taxonomy aren't suggested.
+    pub fn suggest(
+        &self,
+        query: &str,
+        used: &[FieldSuggestion],
+        limit: usize,
+    ) -> Vec<FieldSuggestion> {
+        let query = match_key(query);
+        let mut suggestions: Vec<FieldSuggestion> = self
+            .options()
+            .into_iter()
+            .filter(|option| match_key(&option.name).contains(&query))
+            .map(|option| FieldSuggestion {
+                value: option.name,
+                uses: 0,
+            })
+            .collect();
+
+        for entry in used {
+            if let Some(option) = self.resolve(&entry.value)
+                && let Some(suggestion) = suggestions
+                    .iter_mut()
+                    .find(|suggestion| suggestion.value == option.name)
+            {
+                suggestion.uses += entry.uses;
+            }
+        }
+
+        suggestions.sort_by(|a, b| b.uses.cmp(&a.uses).then_with(|| a.value.cmp(&b.value)));
+        suggestions.truncate(limit);
+        suggestions
+    }
+
+    /// The family `value` belongs to, guessing from the text for processes
+    /// outside the taxonomy.
+    pub fn family(&self, value: &str) -> String {
+        self.resolve(value)
+            .map_or_else(|| process_family(value).to_string(), |option| option.family)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn custom(name: &str, family: &str) -> CustomProcess {
+        CustomProcess {
+            id: CustomProcessId::new(1),
+            name: name.to_string(),
+            family: family.to_string(),
+            created_at: Utc::now(),
+        }
+    }
+
+    fn new_process(name: &str, family: Option<&str>) -> NewCustomProcess {
+        NewCustomProcess {
+            name: name.to_string(),
+            family: family.map(String::from),
+            created_at: None,
+        }
+    }
+
+    #[test]
+    fn processes_fall_into_families() {
+        assert_eq!(process_family("Fully Washed"), "Washed");
+        assert_eq!(process_family("Wet Hulled"), "Washed");
+        assert_eq!(process_family("Anaerobic Natural"), "Natural");
+        assert_eq!(process_family("Pulped Natural"), "Honey");
+        assert_eq!(process_family("Black Honey"), "Honey");
+        assert_eq!(process_family("Carbonic Maceration"), "Other");
+    }
+
+    #[test]
+    fn names_and_aliases_resolve_to_the_stored_name() {
+        let taxonomy = ProcessTaxonomy::default();
+
+        assert_eq!(taxonomy.canonical("washed").unwrap(), "Washed");
+        assert_eq!(
+            taxonomy.canonical(" Fully-Washed Process ").unwrap(),
+            "Washed"
+        );
+        assert_eq!(taxonomy.canonical("wet hulled").unwrap(), "Wet-Hulled");
+        assert_eq!(taxonomy.canonical("Giling Basah").unwrap(), "Wet-Hulled");
+        assert_eq!(taxonomy.canonical("Pulped Natural").unwrap(), "Honey");
+        assert!(taxonomy.canonical("Koji").is_err());
+        assert!(taxonomy.canonical("").is_err());
+    }
+
+    #[test]
+    fn custom_processes_join_the_taxonomy() {
+        let taxonomy = ProcessTaxonomy::new(vec![custom("Koji", "Other")]);
+
+        assert_eq!(taxonomy.canonical("koji").unwrap(), "Koji");
+        assert_eq!(taxonomy.family("Koji"), "Other");
+        let koji = taxonomy.options().pop().unwrap();
+        assert_eq!(koji.id, Some(CustomProcessId::new(1)));
+    }
+
+    #[test]
+    fn families_come_from_the_taxonomy_before_the_text() {
+        let taxonomy = ProcessTaxonomy::new(vec![custom("Lactic Dry", "Washed")]);
+
+        assert_eq!(taxonomy.family("Anaerobic"), "Other");
+        assert_eq!(taxonomy.family("Lactic Dry"), "Washed");
+        // Roasts logged before the taxonomy still land in a family.
+        assert_eq!(taxonomy.family("Sun-kissed natural"), "Natural");
+    }
+
+    #[test]
+    fn closest_falls_back_to_the_family() {
+        let taxonomy = ProcessTaxonomy::default();
+
+        assert_eq!(taxonomy.closest("Natural Process"), "Natural");
+        assert_eq!(taxonomy.closest("Extended dry fermentation"), "Natural");
+        assert_eq!(taxonomy.closest("Koji"), "Experimental");
+    }
+
+    #[test]
+    fn suggestions_count_aliases_towards_their_process() {
+        let taxonomy = ProcessTaxonomy::default();
+        let used = [
+            FieldSuggestion {
+                value: "Fully Washed".to_string(),
+                uses: 2,
+            },
+            FieldSuggestion {
+                value: "Washed".to_string(),
+                uses: 1,
+            },
+            FieldSuggestion {
+                value: "Wash & go".to_string(),
+                uses: 9,
+            },
+        ];
+
+        let suggestions = taxonomy.suggest("wash", &used, 10);
+
+        assert_eq!(suggestions[0].value, "Washed");
+        assert_eq!(suggestions[0].uses, 3);
+        assert!(suggestions.iter().any(|s| s.value == "Semi-Washed"));
+        assert!(suggestions.iter().all(|s| s.value != "Wash & go"));
+        assert_eq!(taxonomy.suggest("", &[], 3).len(), 3);
+    }
+
+    #[test]
+    fn new_processes_are_trimmed_and_given_a_family() {
+        let process = new_process("  Lactic   Natural ", None)
+            .normalize()
+            .unwrap();
+        assert_eq!(process.name, "Lactic Natural");
+        assert_eq!(process.family.as_deref(), Some("Natural"));
+
+        let process = new_process("Koji", Some("other")).normalize().unwrap();
+        assert_eq!(process.family.as_deref(), Some("Other"));
+    }
+
+    #[test]
+    fn new_processes_are_rejected_when_invalid() {
+        assert!(new_process(" ", None).normalize().is_err());
+        assert!(new_process(&"x".repeat(41), None).normalize().is_err());
+        assert!(new_process("Fully Washed", None).normalize().is_err());
+        assert!(new_process("Koji", Some("Fermented")).normalize().is_err());
+    }
+}
@@ -0,0 +1,101 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::brews::QuickNote;
+use crate::domain::ids::CustomQuickNoteId;
+
+const MAX_LABEL_CHARS: usize = 32;
+const MAX_EMOJI_CHARS: usize = 8;
+
+/// A user-defined quick note, offered on the brew form alongside the
+/// built-ins. Brews reference it by label.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomQuickNote {
+    pub id: CustomQuickNoteId,
+    pub label: String,
+    pub emoji: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl CustomQuickNote {
+    pub fn note(&self) -> QuickNote {
+        QuickNote::Custom(self.label.clone())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewCustomQuickNote {
+    pub label: String,
+    pub emoji: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+impl NewCustomQuickNote {
+    /// Trim whitespace and check the label can be used as a brew note.
+    pub fn normalize(self) -> Result<Self, String> {
+        let label = self.label.trim().to_string();
+        let emoji = self.emoji.trim().to_string();
+
+        if label.is_empty() {
+            return Err("quick note label is required".to_string());
+        }
+        if label.chars().count() > MAX_LABEL_CHARS {
+            return Err(format!(
+                "quick note label must be at most {MAX_LABEL_CHARS} characters"
+            ));
+        }
+        // Brew forms submit notes as a comma-separated list.
+        if label.contains(',') {
+            return Err("quick note label cannot contain commas".to_string());
+        }
+        if QuickNote::from_str_value(&label).is_some() {
+            return Err(format!("\"{label}\" is already a built-in quick note"));
+        }
+        if emoji.is_empty() {
+            return Err("quick note emoji is required".to_string());
+        }
+        if emoji.chars().count() > MAX_EMOJI_CHARS {
+            return Err("quick note emoji must be a single emoji".to_string());
+        }
+
+        Ok(Self {
+            label,
+            emoji,
+            created_at: self.created_at,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_note(label: &str, emoji: &str) -> NewCustomQuickNote {
+        NewCustomQuickNote {
+            label: label.to_string(),
+            emoji: emoji.to_string(),
+            created_at: None,
+        }
+    }
+
+    #[test]
+    fn normalize_trims_label_and_emoji() {
+        let note = new_note("  Floral ", " 🌸 ").normalize().unwrap();
+        assert_eq!(note.label, "Floral");
+        assert_eq!(note.emoji, "🌸");
+    }
+
+    #[test]
+    fn normalize_rejects_built_in_labels() {
+        assert!(new_note("Too Fast", "⏩").normalize().is_err());
+        assert!(new_note("good", "👍").normalize().is_err());
+    }
+
+    #[test]
+    fn normalize_rejects_commas_and_missing_fields() {
+        assert!(new_note("Sweet, juicy", "🍑").normalize().is_err());
+        assert!(new_note("  ", "🍑").normalize().is_err());
+        assert!(new_note("Juicy", "").normalize().is_err());
+    }
+}
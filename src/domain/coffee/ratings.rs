@@ -0,0 +1,44 @@
+//! Star ratings, given to cups and brews and averaged per roast.
+
+/// The top of the star scale.
+pub const MAX_STARS: u8 = 5;
+
+/// Puts a brew's net quick-note [`rating`](crate::domain::brews::Brew::rating)
+/// on the star scale: a net "Good" is five stars, a net fault one, and a
+/// wash three.
+pub fn brew_stars(rating: i32) -> u8 {
+    let stars = rating.saturating_mul(2).saturating_add(3);
+    u8::try_from(stars.clamp(1, i32::from(MAX_STARS))).unwrap_or(MAX_STARS)
+}
+
+/// The mean of some star ratings to one decimal place, or `None` when there
+/// are none.
+pub fn average_stars(stars: impl IntoIterator<Item = u8>) -> Option<f64> {
+    let (total, count) = stars
+        .into_iter()
+        .fold((0u32, 0u32), |(total, count), stars| {
+            (total + u32::from(stars), count + 1)
+        });
+    (count > 0).then(|| (f64::from(total) / f64::from(count) * 10.0).round() / 10.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn brew_stars_spread_around_three() {
+        assert_eq!(brew_stars(1), 5);
+        assert_eq!(brew_stars(0), 3);
+        assert_eq!(brew_stars(-1), 1);
+        assert_eq!(brew_stars(-4), 1);
+        assert_eq!(brew_stars(3), 5);
+    }
+
+    #[test]
+    fn average_stars_rounds_to_one_decimal() {
+        assert_eq!(average_stars([5, 4, 4]), Some(4.3));
+        assert_eq!(average_stars([3]), Some(3.0));
+        assert_eq!(average_stars([]), None);
+    }
+}
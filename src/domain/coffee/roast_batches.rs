@@ -0,0 +1,217 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::cups::deserialize_clearable_id;
+use super::normalize_optional_field;
+use crate::domain::ids::{BagId, GreenCoffeeId, RoastBatchId};
+use crate::domain::validation::{Validate, ValidationErrors};
+
+/// One home roast of a green coffee lot.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoastBatch {
+    pub id: RoastBatchId,
+    pub green_coffee_id: GreenCoffeeId,
+    /// The bag this batch's beans went into, once there is one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bag_id: Option<BagId>,
+    pub roasted_at: DateTime<Utc>,
+    /// Green grams into the roaster.
+    pub weight_in: f64,
+    /// Roasted grams out.
+    pub weight_out: f64,
+    /// Seconds from charge to drop.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_time: Option<i64>,
+    /// Seconds from first crack to drop.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub development_time: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub curve_notes: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl RoastBatch {
+    /// Percentage of the green weight lost in the roast.
+    pub fn weight_loss(&self) -> f64 {
+        weight_loss(self.weight_in, self.weight_out)
+    }
+
+    /// Development time as a percentage of the whole roast.
+    pub fn development_ratio(&self) -> Option<f64> {
+        match (self.development_time, self.total_time) {
+            #[allow(clippy::cast_precision_loss)]
+            (Some(development), Some(total)) if total > 0 => {
+                Some(development as f64 / total as f64 * 100.0)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Percentage of `weight_in` that didn't come out of the roaster.
+pub fn weight_loss(weight_in: f64, weight_out: f64) -> f64 {
+    if weight_in > 0.0 {
+        (weight_in - weight_out) / weight_in * 100.0
+    } else {
+        0.0
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewRoastBatch {
+    pub green_coffee_id: GreenCoffeeId,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bag_id: Option<BagId>,
+    /// Defaults to now.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub roasted_at: Option<DateTime<Utc>>,
+    pub weight_in: f64,
+    pub weight_out: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_time: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub development_time: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub curve_notes: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+impl NewRoastBatch {
+    pub fn normalize(mut self) -> Self {
+        self.curve_notes = normalize_optional_field(self.curve_notes);
+        self
+    }
+}
+
+// The bag is left alone, cleared, or set.
+#[allow(clippy::option_option)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UpdateRoastBatch {
+    /// `Some(None)` unlinks the batch from its bag.
+    #[serde(
+        default,
+        deserialize_with = "deserialize_clearable_id",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub bag_id: Option<Option<BagId>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub roasted_at: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weight_in: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weight_out: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_time: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub development_time: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub curve_notes: Option<String>,
+}
+
+fn check_batch(
+    errors: &mut ValidationErrors,
+    weights: (Option<f64>, Option<f64>),
+    times: (Option<i64>, Option<i64>),
+) {
+    for (field, grams) in [("weight_in", weights.0), ("weight_out", weights.1)] {
+        if let Some(grams) = grams {
+            errors.check(
+                grams.is_finite() && grams > 0.0,
+                field,
+                format!("{field} must be a positive number of grams"),
+            );
+        }
+    }
+    if let (Some(weight_in), Some(weight_out)) = weights {
+        errors.check(
+            weight_out <= weight_in,
+            "weight_out",
+            "a batch can't weigh more out of the roaster than went in",
+        );
+    }
+    for (field, seconds) in [("total_time", times.0), ("development_time", times.1)] {
+        if let Some(seconds) = seconds {
+            errors.check(seconds >= 0, field, format!("{field} cannot be negative"));
+        }
+    }
+    if let (Some(total), Some(development)) = times {
+        errors.check(
+            development <= total,
+            "development_time",
+            "development time can't be longer than the whole roast",
+        );
+    }
+}
+
+impl Validate for NewRoastBatch {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+        check_batch(
+            &mut errors,
+            (Some(self.weight_in), Some(self.weight_out)),
+            (self.total_time, self.development_time),
+        );
+        errors.into_result()
+    }
+}
+
+impl Validate for UpdateRoastBatch {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+        check_batch(
+            &mut errors,
+            (self.weight_in, self.weight_out),
+            (self.total_time, self.development_time),
+        );
+        errors.into_result()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_batch() -> NewRoastBatch {
+        NewRoastBatch {
+            green_coffee_id: GreenCoffeeId::new(1),
+            bag_id: None,
+            roasted_at: None,
+            weight_in: 250.0,
+            weight_out: 212.5,
+            total_time: Some(600),
+            development_time: Some(120),
+            curve_notes: Some("  ".to_string()),
+            created_at: None,
+        }
+    }
+
+    #[test]
+    fn weight_loss_is_a_percentage_of_green_weight() {
+        assert!((weight_loss(250.0, 212.5) - 15.0).abs() < 1e-9);
+        assert!(weight_loss(0.0, 0.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn validate_rejects_impossible_batches() {
+        assert!(new_batch().validate().is_ok());
+
+        let heavier = NewRoastBatch {
+            weight_out: 260.0,
+            ..new_batch()
+        };
+        assert!(heavier.validate().is_err());
+
+        let overdeveloped = NewRoastBatch {
+            development_time: Some(700),
+            ..new_batch()
+        };
+        assert!(overdeveloped.validate().is_err());
+    }
+
+    #[test]
+    fn normalize_clears_blank_notes() {
+        assert_eq!(new_batch().normalize().curve_notes, None);
+    }
+}
@@ -1,11 +1,14 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Datelike, Utc};
 use serde::{Deserialize, Serialize};
 
 use super::normalize_optional_field;
 use crate::define_sort_key;
+use crate::domain::countries::canonical_country;
 use crate::domain::entity_type::EntityType;
-use crate::domain::ids::RoasterId;
+use crate::domain::ids::{RoasterId, UserId};
+use crate::domain::slugs::SlugQualifiers;
 use crate::domain::timeline::{NewTimelineEvent, TimelineEventDetail};
+use crate::domain::validation::{Validate, ValidationErrors};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Roaster {
@@ -16,6 +19,11 @@ pub struct Roaster {
     pub city: Option<String>,
     pub homepage: Option<String>,
     pub created_at: DateTime<Utc>,
+    /// Starred to pin it to the top of pickers and onto the home page.
+    #[serde(default)]
+    pub favourite: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_by: Option<UserId>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -31,7 +39,7 @@ pub struct NewRoaster {
 impl NewRoaster {
     pub fn normalize(mut self) -> Self {
         self.name = self.name.trim().to_string();
-        self.country = self.country.trim().to_string();
+        self.country = normalize_country(&self.country);
         self.city = normalize_optional_field(self.city);
         self.homepage =
             normalize_optional_field(self.homepage).filter(|url| is_valid_url_scheme(url));
@@ -45,6 +53,40 @@ impl NewRoaster {
         };
         slug::slugify(base)
     }
+
+    pub fn slug_qualifiers(&self) -> SlugQualifiers {
+        SlugQualifiers {
+            year: Some(self.created_at.unwrap_or_else(Utc::now).year()),
+            roaster: None,
+        }
+    }
+}
+
+impl Validate for NewRoaster {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+        errors.require("name", &self.name);
+        errors.check_result("country", validate_country(&self.country));
+        errors.into_result()
+    }
+}
+
+/// Replace a recognised country name, alias or ISO code with its canonical
+/// name, e.g. `UK` becomes `United Kingdom`. Unknown values are only trimmed.
+fn normalize_country(country: &str) -> String {
+    canonical_country(country).map_or_else(
+        || country.trim().to_string(),
+        |country| country.name.to_string(),
+    )
+}
+
+/// Check that a roaster country is one Brewlog recognises.
+pub fn validate_country(country: &str) -> Result<(), String> {
+    if canonical_country(country).is_some() {
+        Ok(())
+    } else {
+        Err(format!("unknown country '{}'", country.trim()))
+    }
 }
 
 /// Returns `true` if the URL starts with `http://` or `https://`.
@@ -77,6 +119,7 @@ impl Roaster {
             slug: Some(self.slug.clone()),
             roaster_slug: Some(self.slug.clone()),
             brew_data: None,
+            created_by: self.created_by,
         }
     }
 }
@@ -93,12 +136,24 @@ pub struct UpdateRoaster {
 
 impl UpdateRoaster {
     pub fn normalize(mut self) -> Self {
+        self.country = self.country.map(|country| normalize_country(&country));
         self.homepage =
             normalize_optional_field(self.homepage).filter(|url| is_valid_url_scheme(url));
         self
     }
 }
 
+impl Validate for UpdateRoaster {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+        errors.require_if_set("name", self.name.as_deref());
+        if let Some(country) = &self.country {
+            errors.check_result("country", validate_country(country));
+        }
+        errors.into_result()
+    }
+}
+
 define_sort_key!(pub RoasterSortKey {
     #[default]
     CreatedAt("created-at", Desc),
@@ -164,6 +219,61 @@ mod tests {
         assert_eq!(normalized.name, "Test");
     }
 
+    #[test]
+    fn normalize_uses_canonical_country_name() {
+        let roaster = NewRoaster {
+            name: "Test".to_string(),
+            country: " uk ".to_string(),
+            city: None,
+            homepage: None,
+            created_at: None,
+        };
+        assert_eq!(roaster.normalize().country, "United Kingdom");
+
+        let update = UpdateRoaster {
+            country: Some("SE".to_string()),
+            ..UpdateRoaster::default()
+        };
+        assert_eq!(update.normalize().country.as_deref(), Some("Sweden"));
+    }
+
+    #[test]
+    fn validate_country_rejects_unknown_names() {
+        assert!(validate_country("Japan").is_ok());
+        assert!(validate_country("Lithuania").is_ok());
+        assert!(validate_country("lu").is_ok());
+        assert_eq!(
+            validate_country(" Narnia "),
+            Err("unknown country 'Narnia'".to_string())
+        );
+    }
+
+    #[test]
+    fn validate_reports_each_bad_field() {
+        let roaster = NewRoaster {
+            name: " ".to_string(),
+            country: "Narnia".to_string(),
+            city: None,
+            homepage: None,
+            created_at: None,
+        };
+        let fields: Vec<String> = roaster
+            .validate()
+            .unwrap_err()
+            .into_errors()
+            .into_iter()
+            .map(|e| e.field)
+            .collect();
+        assert_eq!(fields, ["name", "country"]);
+
+        let update = UpdateRoaster {
+            name: Some(String::new()),
+            ..UpdateRoaster::default()
+        };
+        assert!(update.validate().is_err());
+        assert!(UpdateRoaster::default().validate().is_ok());
+    }
+
     #[test]
     fn normalize_filters_bad_scheme() {
         let roaster = NewRoaster {
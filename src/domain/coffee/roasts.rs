@@ -1,11 +1,17 @@
-use chrono::{DateTime, Utc};
+use std::str::FromStr;
+
+use chrono::{DateTime, Datelike, Utc};
 use serde::{Deserialize, Serialize};
 
 use crate::define_sort_key;
+use crate::domain::blends::{BlendComponent, blend_label, check_blend};
+use crate::domain::brews::RoastBrewStats;
 use crate::domain::entity_type::EntityType;
-use crate::domain::ids::{RoastId, RoasterId};
+use crate::domain::ids::{RoastId, RoasterId, UserId};
 use crate::domain::roasters::Roaster;
+use crate::domain::slugs::SlugQualifiers;
 use crate::domain::timeline::{NewTimelineEvent, TimelineEventDetail};
+use crate::domain::validation::{Validate, ValidationErrors};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Roast {
@@ -18,7 +24,79 @@ pub struct Roast {
     pub producer: Option<String>,
     pub tasting_notes: Vec<String>,
     pub process: Option<String>,
+    /// The origins of a blend and their shares; empty for a single origin.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub components: Vec<BlendComponent>,
+    /// Harvest this lot came from, when the roaster sells the coffee each year.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub harvest_year: Option<i32>,
     pub created_at: DateTime<Utc>,
+    /// Whether the roast is starred as a favourite.
+    #[serde(default)]
+    pub favourite: bool,
+    /// Average stars across the roast's rated brews and cups, kept up to date
+    /// with the stats.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rating: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_by: Option<UserId>,
+}
+
+impl Roast {
+    /// The countries the beans come from: a blend's components in order, or
+    /// the comma-separated `origin`.
+    pub fn origins(&self) -> Vec<&str> {
+        if self.components.is_empty() {
+            crate::domain::countries::parse_origins(self.origin.as_deref())
+        } else {
+            self.components
+                .iter()
+                .map(|component| component.origin.as_str())
+                .collect()
+        }
+    }
+
+    /// Whether `other` is another lot of this coffee: the same roaster and name.
+    pub fn is_lot_of(&self, other: &Roast) -> bool {
+        self.id != other.id
+            && self.roaster_id == other.roaster_id
+            && self.name.trim().eq_ignore_ascii_case(other.name.trim())
+    }
+
+    /// A new lot of this coffee from a later harvest, with the same details to start from.
+    pub fn new_lot(&self, harvest_year: i32) -> NewRoast {
+        NewRoast {
+            roaster_id: self.roaster_id,
+            name: self.name.clone(),
+            origin: self.origin.clone().unwrap_or_default(),
+            region: self.region.clone().unwrap_or_default(),
+            producer: self.producer.clone().unwrap_or_default(),
+            tasting_notes: self.tasting_notes.clone(),
+            process: self.process.clone().unwrap_or_default(),
+            components: self.components.clone(),
+            harvest_year: Some(harvest_year),
+            created_at: None,
+        }
+    }
+}
+
+/// Request body for starting a new lot of an existing roast.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewLotRequest {
+    pub harvest_year: i32,
+}
+
+/// Earliest and latest harvest years accepted for a lot.
+pub const HARVEST_YEARS: std::ops::RangeInclusive<i32> = 1900..=2100;
+
+/// Order lots newest harvest first, with undated lots last.
+pub fn sort_lots(lots: &mut [RoastWithRoaster]) {
+    lots.sort_by(|a, b| {
+        b.roast
+            .harvest_year
+            .cmp(&a.roast.harvest_year)
+            .then_with(|| b.roast.created_at.cmp(&a.roast.created_at))
+    });
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -29,6 +107,14 @@ pub struct RoastWithRoaster {
     pub roaster_slug: String,
 }
 
+/// A roast alongside the figures from its brews, as shown on the compare page.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoastComparison {
+    #[serde(flatten)]
+    pub roast: RoastWithRoaster,
+    pub brew_stats: RoastBrewStats,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NewRoast {
     pub roaster_id: RoasterId,
@@ -38,13 +124,32 @@ pub struct NewRoast {
     pub producer: String,
     pub tasting_notes: Vec<String>,
     pub process: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub components: Vec<BlendComponent>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub harvest_year: Option<i32>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub created_at: Option<DateTime<Utc>>,
 }
 
 impl NewRoast {
+    /// Lots of the same coffee share a name, so the harvest year keeps their slugs apart.
     pub fn slug(&self) -> String {
-        slug::slugify(&self.name)
+        match self.harvest_year {
+            Some(year) => slug::slugify(format!("{} {year}", self.name)),
+            None => slug::slugify(&self.name),
+        }
+    }
+
+    /// A harvest year is already in the slug, so it isn't offered again.
+    pub fn slug_qualifiers(&self, roaster_name: &str) -> SlugQualifiers {
+        SlugQualifiers {
+            year: self
+                .harvest_year
+                .is_none()
+                .then(|| self.created_at.unwrap_or_else(Utc::now).year()),
+            roaster: Some(roaster_name.to_string()),
+        }
     }
 }
 
@@ -57,10 +162,74 @@ pub struct UpdateRoast {
     pub producer: Option<String>,
     pub tasting_notes: Option<Vec<String>>,
     pub process: Option<String>,
+    /// Replaces the blend; an empty list makes the roast single-origin again.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub components: Option<Vec<BlendComponent>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub harvest_year: Option<i32>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub created_at: Option<DateTime<Utc>>,
 }
 
+fn check_harvest_year(errors: &mut ValidationErrors, year: Option<i32>) {
+    if let Some(year) = year {
+        errors.check(
+            HARVEST_YEARS.contains(&year),
+            "harvest_year",
+            format!(
+                "harvest year must be between {} and {}",
+                HARVEST_YEARS.start(),
+                HARVEST_YEARS.end()
+            ),
+        );
+    }
+}
+
+impl Validate for NewRoast {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+        errors.check(
+            self.roaster_id.into_inner() > 0,
+            "roaster_id",
+            "invalid roaster id",
+        );
+        errors.require("name", &self.name);
+        errors.require("origin", &self.origin);
+        check_blend(&mut errors, &self.components);
+        errors.require("region", &self.region);
+        errors.require("producer", &self.producer);
+        errors.require("process", &self.process);
+        errors.check(
+            self.tasting_notes
+                .iter()
+                .any(|note| !note.trim().is_empty()),
+            "tasting_notes",
+            "tasting notes are required",
+        );
+        check_harvest_year(&mut errors, self.harvest_year);
+        errors.into_result()
+    }
+}
+
+impl Validate for UpdateRoast {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+        if let Some(roaster_id) = self.roaster_id {
+            errors.check(
+                roaster_id.into_inner() > 0,
+                "roaster_id",
+                "invalid roaster id",
+            );
+        }
+        errors.require_if_set("name", self.name.as_deref());
+        if let Some(components) = &self.components {
+            check_blend(&mut errors, components);
+        }
+        check_harvest_year(&mut errors, self.harvest_year);
+        errors.into_result()
+    }
+}
+
 define_sort_key!(pub RoastSortKey {
     #[default]
     CreatedAt("created-at", Desc),
@@ -70,17 +239,76 @@ define_sort_key!(pub RoastSortKey {
     Producer("producer", Asc),
 });
 
+/// Roast fields that offer autocomplete suggestions from previously used values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RoastField {
+    Origin,
+    Process,
+    Producer,
+    TastingNote,
+}
+
+impl RoastField {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Origin => "origin",
+            Self::Process => "process",
+            Self::Producer => "producer",
+            Self::TastingNote => "tasting_note",
+        }
+    }
+}
+
+impl std::fmt::Display for RoastField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for RoastField {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "origin" => Ok(Self::Origin),
+            "process" => Ok(Self::Process),
+            "producer" => Ok(Self::Producer),
+            "tasting_note" => Ok(Self::TastingNote),
+            _ => Err(()),
+        }
+    }
+}
+
+/// A previously used field value and the number of roasts using it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldSuggestion {
+    pub value: String,
+    pub uses: i64,
+}
+
 pub fn roast_timeline_event(roast: &Roast, roaster: &Roaster) -> NewTimelineEvent {
     let mut details = vec![TimelineEventDetail {
         label: "Roaster".to_string(),
         value: roaster.name.clone(),
     }];
-    if let Some(ref origin) = roast.origin {
+    if !roast.components.is_empty() {
+        details.push(TimelineEventDetail {
+            label: "Blend".to_string(),
+            value: blend_label(&roast.components),
+        });
+    } else if let Some(ref origin) = roast.origin {
         details.push(TimelineEventDetail {
             label: "Origin".to_string(),
             value: origin.clone(),
         });
     }
+    if let Some(year) = roast.harvest_year {
+        details.push(TimelineEventDetail {
+            label: "Harvest".to_string(),
+            value: year.to_string(),
+        });
+    }
     if !roast.tasting_notes.is_empty() {
         details.push(TimelineEventDetail {
             label: "Tasting Notes".to_string(),
@@ -98,5 +326,119 @@ pub fn roast_timeline_event(roast: &Roast, roaster: &Roaster) -> NewTimelineEven
         slug: Some(roast.slug.clone()),
         roaster_slug: Some(roaster.slug.clone()),
         brew_data: None,
+        created_by: roast.created_by,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_roast(harvest_year: Option<i32>) -> NewRoast {
+        NewRoast {
+            roaster_id: RoasterId::new(1),
+            name: "Halo Beriti".to_string(),
+            origin: "Ethiopia".to_string(),
+            region: "Yirgacheffe".to_string(),
+            producer: "Halo Beriti".to_string(),
+            tasting_notes: vec!["Jasmine".to_string()],
+            process: "Washed".to_string(),
+            components: Vec::new(),
+            harvest_year,
+            created_at: None,
+        }
+    }
+
+    fn roast(id: i64, roaster_id: i64, name: &str, harvest_year: Option<i32>) -> Roast {
+        Roast {
+            id: RoastId::new(id),
+            roaster_id: RoasterId::new(roaster_id),
+            name: name.to_string(),
+            slug: slug::slugify(name),
+            origin: None,
+            region: None,
+            producer: None,
+            tasting_notes: Vec::new(),
+            process: None,
+            components: Vec::new(),
+            harvest_year,
+            created_at: DateTime::from_timestamp(id, 0).unwrap(),
+            favourite: false,
+            rating: None,
+            created_by: None,
+        }
+    }
+
+    #[test]
+    fn slug_includes_the_harvest_year() {
+        assert_eq!(new_roast(None).slug(), "halo-beriti");
+        assert_eq!(new_roast(Some(2025)).slug(), "halo-beriti-2025");
+    }
+
+    #[test]
+    fn the_harvest_year_is_not_offered_twice() {
+        let qualifiers = new_roast(Some(2025)).slug_qualifiers("Square Mile");
+        assert_eq!(qualifiers.year, None);
+        assert_eq!(qualifiers.roaster.as_deref(), Some("Square Mile"));
+        assert!(
+            new_roast(None)
+                .slug_qualifiers("Square Mile")
+                .year
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn harvest_year_must_be_plausible() {
+        assert!(new_roast(Some(2025)).validate().is_ok());
+        assert!(new_roast(Some(25)).validate().is_err());
+        let update = UpdateRoast {
+            harvest_year: Some(3025),
+            ..UpdateRoast::default()
+        };
+        assert!(update.validate().is_err());
+    }
+
+    #[test]
+    fn lots_share_a_roaster_and_name() {
+        let original = roast(1, 1, "Halo Beriti", Some(2024));
+        assert!(original.is_lot_of(&roast(2, 1, "halo beriti ", Some(2025))));
+        assert!(!original.is_lot_of(&original));
+        assert!(!original.is_lot_of(&roast(3, 2, "Halo Beriti", Some(2025))));
+        assert!(!original.is_lot_of(&roast(4, 1, "Kochere", Some(2025))));
+    }
+
+    #[test]
+    fn new_lot_keeps_the_details_and_sets_the_year() {
+        let mut original = roast(1, 1, "Halo Beriti", None);
+        original.origin = Some("Ethiopia".to_string());
+        original.tasting_notes = vec!["Jasmine".to_string()];
+
+        let lot = original.new_lot(2026);
+
+        assert_eq!(lot.name, "Halo Beriti");
+        assert_eq!(lot.origin, "Ethiopia");
+        assert_eq!(lot.tasting_notes, vec!["Jasmine".to_string()]);
+        assert_eq!(lot.harvest_year, Some(2026));
+        assert_eq!(lot.slug(), "halo-beriti-2026");
+    }
+
+    #[test]
+    fn lots_sort_newest_harvest_first() {
+        let with_roaster = |roast| RoastWithRoaster {
+            roast,
+            roaster_name: "Roaster".to_string(),
+            roaster_slug: "roaster".to_string(),
+        };
+        let mut lots = vec![
+            with_roaster(roast(1, 1, "Halo", None)),
+            with_roaster(roast(2, 1, "Halo", Some(2024))),
+            with_roaster(roast(3, 1, "Halo", Some(2025))),
+        ];
+
+        sort_lots(&mut lots);
+
+        let years: Vec<_> = lots.iter().map(|lot| lot.roast.harvest_year).collect();
+        assert_eq!(years, vec![Some(2025), Some(2024), None]);
     }
 }
@@ -0,0 +1,232 @@
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::normalize_optional_field;
+use crate::domain::cafes::Cafe;
+use crate::domain::cups::CupWithDetails;
+use crate::domain::entity_type::EntityType;
+use crate::domain::ids::{TripId, UserId};
+use crate::domain::timeline::{NewTimelineEvent, TimelineEventDetail};
+use crate::domain::validation::{Validate, ValidationErrors};
+
+/// A trip away: cafes visited and cups drunk between the start and end
+/// dates belong to it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Trip {
+    pub id: TripId,
+    pub name: String,
+    pub location: String,
+    pub start_date: NaiveDate,
+    /// Inclusive.
+    pub end_date: NaiveDate,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_by: Option<UserId>,
+}
+
+impl Trip {
+    pub fn includes(&self, date: NaiveDate) -> bool {
+        (self.start_date..=self.end_date).contains(&date)
+    }
+
+    /// Length of the trip in days, counting both ends.
+    pub fn days(&self) -> i64 {
+        (self.end_date - self.start_date).num_days() + 1
+    }
+
+    pub fn to_timeline_event(&self) -> NewTimelineEvent {
+        let dates = if self.start_date == self.end_date {
+            self.start_date.format("%-d %b %Y").to_string()
+        } else {
+            format!(
+                "{} – {}",
+                self.start_date.format("%-d %b"),
+                self.end_date.format("%-d %b %Y")
+            )
+        };
+        let details = vec![
+            TimelineEventDetail {
+                label: "Location".to_string(),
+                value: self.location.clone(),
+            },
+            TimelineEventDetail {
+                label: "Dates".to_string(),
+                value: dates,
+            },
+        ];
+
+        NewTimelineEvent {
+            entity_type: EntityType::Trip,
+            entity_id: self.id.into_inner(),
+            action: "travelled".to_string(),
+            occurred_at: self.start_date.and_time(NaiveTime::MIN).and_utc(),
+            title: self.name.clone(),
+            details,
+            tasting_notes: vec![],
+            slug: None,
+            roaster_slug: None,
+            brew_data: None,
+            created_by: self.created_by,
+        }
+    }
+}
+
+/// Everything checked in during a trip.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TripSummary {
+    pub trip: Trip,
+    pub cups: Vec<CupWithDetails>,
+    pub cafes: Vec<Cafe>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewTrip {
+    pub name: String,
+    pub location: String,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+impl NewTrip {
+    pub fn normalize(mut self) -> Self {
+        self.name = self.name.trim().to_string();
+        self.location = self.location.trim().to_string();
+        self.notes = normalize_optional_field(self.notes);
+        self
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UpdateTrip {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_date: Option<NaiveDate>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_date: Option<NaiveDate>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+}
+
+impl UpdateTrip {
+    pub fn normalize(mut self) -> Self {
+        self.name = self.name.map(|name| name.trim().to_string());
+        self.location = self.location.map(|location| location.trim().to_string());
+        self
+    }
+}
+
+impl Validate for NewTrip {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+        errors.require("name", &self.name);
+        errors.require("location", &self.location);
+        if self.end_date < self.start_date {
+            errors.add("end_date", "must not be before the start date");
+        }
+        errors.into_result()
+    }
+}
+
+impl Validate for UpdateTrip {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+        errors.require_if_set("name", self.name.as_deref());
+        errors.require_if_set("location", self.location.as_deref());
+        if let (Some(start), Some(end)) = (self.start_date, self.end_date)
+            && end < start
+        {
+            errors.add("end_date", "must not be before the start date");
+        }
+        errors.into_result()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, day).unwrap()
+    }
+
+    fn new_trip() -> NewTrip {
+        NewTrip {
+            name: "  Lisbon long weekend ".to_string(),
+            location: " Lisbon, Portugal ".to_string(),
+            start_date: date(6),
+            end_date: date(9),
+            notes: Some("  ".to_string()),
+            created_at: None,
+        }
+    }
+
+    fn trip() -> Trip {
+        let new = new_trip().normalize();
+        Trip {
+            id: TripId::new(3),
+            name: new.name,
+            location: new.location,
+            start_date: new.start_date,
+            end_date: new.end_date,
+            notes: new.notes,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            created_by: None,
+        }
+    }
+
+    #[test]
+    fn normalize_trims_fields() {
+        let trip = new_trip().normalize();
+        assert_eq!(trip.name, "Lisbon long weekend");
+        assert_eq!(trip.location, "Lisbon, Portugal");
+        assert_eq!(trip.notes, None);
+    }
+
+    #[test]
+    fn end_date_cannot_precede_start_date() {
+        assert!(new_trip().validate().is_ok());
+        let backwards = NewTrip {
+            end_date: date(5),
+            ..new_trip()
+        };
+        assert!(backwards.validate().is_err());
+
+        let update = UpdateTrip {
+            start_date: Some(date(9)),
+            end_date: Some(date(6)),
+            ..UpdateTrip::default()
+        };
+        assert!(update.validate().is_err());
+    }
+
+    #[test]
+    fn window_includes_both_ends() {
+        let trip = trip();
+        assert!(trip.includes(date(6)));
+        assert!(trip.includes(date(9)));
+        assert!(!trip.includes(date(10)));
+        assert_eq!(trip.days(), 4);
+    }
+
+    #[test]
+    fn timeline_event_starts_with_the_trip() {
+        let trip = trip();
+        let timeline = trip.to_timeline_event();
+        assert_eq!(timeline.entity_type, EntityType::Trip);
+        assert_eq!(timeline.action, "travelled");
+        assert_eq!(timeline.occurred_at.date_naive(), trip.start_date);
+        assert_eq!(timeline.details[0].value, "Lisbon, Portugal");
+        assert_eq!(timeline.details[1].value, "6 Mar – 9 Mar 2026");
+    }
+}
@@ -0,0 +1,350 @@
+pub mod regions;
+
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use isocountry::CountryCode;
+use serde::Serialize;
+
+/// Other names people use for countries, e.g. "UK" or "England". ISO-3166
+/// names and the short names in [`COUNTRIES`] are recognised without one.
+static COUNTRY_MAP: LazyLock<HashMap<&'static str, &'static str>> = LazyLock::new(|| {
+    HashMap::from([
+        ("drc", "CD"),
+        ("congo", "CD"),
+        ("uk", "GB"),
+        ("great britain", "GB"),
+        ("england", "GB"),
+        ("scotland", "GB"),
+        ("wales", "GB"),
+        ("northern ireland", "GB"),
+        ("czech republic", "CZ"),
+        ("holland", "NL"),
+        ("usa", "US"),
+        ("us", "US"),
+        ("korea", "KR"),
+        ("uae", "AE"),
+        ("turkiye", "TR"),
+        ("türkiye", "TR"),
+        ("ivory coast", "CI"),
+        ("east timor", "TL"),
+        ("burma", "MM"),
+    ])
+});
+
+/// Names that read better than the ISO-3166 ones, e.g. "Bolivia" for
+/// "Bolivia (Plurinational State of)".
+#[rustfmt::skip]
+const SHORT_NAMES: &[(&str, &str)] = &[
+    ("BO", "Bolivia"),
+    ("BN", "Brunei"),
+    ("CD", "Democratic Republic of the Congo"),
+    ("CG", "Republic of the Congo"),
+    ("FK", "Falkland Islands"),
+    ("GB", "United Kingdom"),
+    ("IR", "Iran"),
+    ("KP", "North Korea"),
+    ("KR", "South Korea"),
+    ("LA", "Laos"),
+    ("MD", "Moldova"),
+    ("MF", "Saint Martin"),
+    ("MK", "North Macedonia"),
+    ("FM", "Micronesia"),
+    ("PS", "Palestine"),
+    ("RU", "Russia"),
+    ("SX", "Sint Maarten"),
+    ("SY", "Syria"),
+    ("TW", "Taiwan"),
+    ("TZ", "Tanzania"),
+    ("US", "United States"),
+    ("VE", "Venezuela"),
+    ("VN", "Vietnam"),
+    ("VG", "British Virgin Islands"),
+    ("VI", "US Virgin Islands"),
+];
+
+/// Every ISO-3166-1 country, under its short name where it has one, sorted
+/// by name.
+pub static COUNTRIES: LazyLock<Vec<Country>> = LazyLock::new(|| {
+    let mut countries: Vec<Country> = CountryCode::iter()
+        .map(|country| {
+            let code = country.alpha2();
+            let name = SHORT_NAMES
+                .iter()
+                .find(|(short_code, _)| *short_code == code)
+                .map_or(country.name(), |(_, name)| *name);
+            Country { code, name }
+        })
+        .collect();
+    countries.sort_by_key(|country| country.name);
+    countries
+});
+
+/// Lowercased short and ISO names of every country, with the aliases.
+static NAME_INDEX: LazyLock<HashMap<String, &'static str>> = LazyLock::new(|| {
+    let mut index: HashMap<String, &'static str> = CountryCode::iter()
+        .map(|country| (country.name().to_lowercase(), country.alpha2()))
+        .collect();
+    for country in COUNTRIES.iter() {
+        index.insert(country.name.to_lowercase(), country.code);
+    }
+    for (alias, code) in COUNTRY_MAP.iter() {
+        index.insert((*alias).to_string(), code);
+    }
+    index
+});
+
+/// Maps a free-text country name to its ISO-3166-1 alpha-2 code.
+pub fn country_to_iso(name: &str) -> Option<&'static str> {
+    NAME_INDEX.get(&name.trim().to_lowercase()).copied()
+}
+
+/// A country with its ISO-3166-1 alpha-2 code and canonical display name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Country {
+    pub code: &'static str,
+    pub name: &'static str,
+}
+
+/// Resolve a country name, alias or ISO code to its canonical entry.
+pub fn canonical_country(input: &str) -> Option<&'static Country> {
+    let input = input.trim();
+    let code = country_to_iso(input).or_else(|| {
+        CountryCode::for_alpha2_caseless(input)
+            .ok()
+            .map(|country| country.alpha2())
+    })?;
+    COUNTRIES.iter().find(|country| country.code == code)
+}
+
+/// Countries whose name or alias starts with `query`, followed by those
+/// that merely contain it. An exact ISO code match is listed first.
+pub fn suggest_countries(query: &str) -> Vec<&'static Country> {
+    let query = query.trim().to_lowercase();
+    let mut ranked: Vec<(u8, &'static Country)> = Vec::new();
+
+    for country in COUNTRIES.iter() {
+        let aliases = COUNTRY_MAP
+            .iter()
+            .filter(|(_, code)| **code == country.code)
+            .map(|(alias, _)| *alias);
+        let names: Vec<String> = std::iter::once(country.name.to_lowercase())
+            .chain(aliases.map(str::to_string))
+            .collect();
+
+        let rank = if country.code.eq_ignore_ascii_case(&query) {
+            0
+        } else if names.iter().any(|name| name.starts_with(&query)) {
+            1
+        } else if names.iter().any(|name| name.contains(&query)) {
+            2
+        } else {
+            continue;
+        };
+        ranked.push((rank, country));
+    }
+
+    ranked.sort_by_key(|(rank, country)| (*rank, country.name));
+    ranked.into_iter().map(|(_, country)| country).collect()
+}
+
+/// Converts an ISO-3166-1 alpha-2 code to a flag emoji using regional indicator symbols.
+pub fn iso_to_flag_emoji(code: &str) -> String {
+    code.chars()
+        .filter_map(|c| {
+            let upper = c.to_ascii_uppercase();
+            if upper.is_ascii_uppercase() {
+                char::from_u32(0x1F1E6 + (upper as u32 - 'A' as u32))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// Split a comma-separated origin string into trimmed, non-empty country names.
+///
+/// Returns an empty `Vec` for `None`, empty, or whitespace-only input.
+/// Single origins like `"Ethiopia"` yield `vec!["Ethiopia"]`.
+/// Blends like `"Ethiopia, Colombia"` yield `vec!["Ethiopia", "Colombia"]`.
+pub fn parse_origins(origin: Option<&str>) -> Vec<&str> {
+    match origin {
+        Some(s) if !s.trim().is_empty() => s
+            .split(',')
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Resolve a comma-separated origin string to a space-separated flag emoji string.
+///
+/// Unknown countries are silently skipped. Returns empty string if no countries resolve.
+pub fn origins_to_flags(origin: Option<&str>) -> String {
+    flags_for(parse_origins(origin))
+}
+
+/// Flag emoji for each of the given countries that resolves, space-separated.
+pub fn flags_for<'a>(countries: impl IntoIterator<Item = &'a str>) -> String {
+    let flags: Vec<String> = countries
+        .into_iter()
+        .filter_map(country_to_iso)
+        .map(iso_to_flag_emoji)
+        .collect();
+    flags.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn country_to_iso_normalises_case() {
+        assert_eq!(country_to_iso("Ethiopia"), Some("ET"));
+        assert_eq!(country_to_iso("ETHIOPIA"), Some("ET"));
+        assert_eq!(country_to_iso("  ethiopia  "), Some("ET"));
+    }
+
+    #[test]
+    fn country_to_iso_handles_aliases() {
+        assert_eq!(country_to_iso("United Kingdom"), Some("GB"));
+        assert_eq!(country_to_iso("UK"), Some("GB"));
+        assert_eq!(country_to_iso("England"), Some("GB"));
+    }
+
+    #[test]
+    fn country_to_iso_returns_none_for_unknown() {
+        assert_eq!(country_to_iso("Blend"), None);
+        assert_eq!(country_to_iso("Multiple Origins"), None);
+        assert_eq!(country_to_iso(""), None);
+    }
+
+    #[test]
+    fn every_alias_has_a_canonical_country() {
+        for code in COUNTRY_MAP.values() {
+            assert!(
+                COUNTRIES.iter().any(|country| country.code == *code),
+                "{code}"
+            );
+        }
+    }
+
+    #[test]
+    fn canonical_country_resolves_aliases_and_codes() {
+        assert_eq!(canonical_country("UK").unwrap().name, "United Kingdom");
+        assert_eq!(canonical_country("england").unwrap().name, "United Kingdom");
+        assert_eq!(canonical_country("se").unwrap().name, "Sweden");
+        assert_eq!(canonical_country(" Czech Republic ").unwrap().code, "CZ");
+        assert!(canonical_country("Narnia").is_none());
+    }
+
+    #[test]
+    fn canonical_country_knows_every_iso_country() {
+        for (input, name) in [
+            ("Lithuania", "Lithuania"),
+            ("argentina", "Argentina"),
+            ("CL", "Chile"),
+            ("Luxembourg", "Luxembourg"),
+            ("Viet Nam", "Vietnam"),
+            ("Bolivia (Plurinational State of)", "Bolivia"),
+        ] {
+            assert!(!COUNTRY_MAP.contains_key(input.to_lowercase().as_str()));
+            assert_eq!(
+                canonical_country(input).map(|c| c.name),
+                Some(name),
+                "{input}"
+            );
+        }
+        assert_eq!(COUNTRIES.len(), CountryCode::num_country_codes());
+    }
+
+    #[test]
+    fn suggest_countries_ranks_prefix_matches_first() {
+        let names: Vec<_> = suggest_countries("uni")
+            .into_iter()
+            .map(|country| country.name)
+            .collect();
+        assert_eq!(
+            names[..4],
+            [
+                "United Arab Emirates",
+                "United Kingdom",
+                "United States",
+                "United States Minor Outlying Islands"
+            ]
+        );
+        assert!(names[4..].contains(&"Tunisia"));
+
+        let names: Vec<_> = suggest_countries("gb")
+            .into_iter()
+            .map(|country| country.name)
+            .collect();
+        assert_eq!(names, ["United Kingdom"]);
+    }
+
+    #[test]
+    fn iso_to_flag_emoji_produces_correct_flags() {
+        assert_eq!(iso_to_flag_emoji("GB"), "🇬🇧");
+        assert_eq!(iso_to_flag_emoji("US"), "🇺🇸");
+        assert_eq!(iso_to_flag_emoji("ET"), "🇪🇹");
+    }
+
+    #[test]
+    fn parse_origins_single() {
+        assert_eq!(parse_origins(Some("Ethiopia")), vec!["Ethiopia"]);
+    }
+
+    #[test]
+    fn parse_origins_multiple() {
+        assert_eq!(
+            parse_origins(Some("Ethiopia, Colombia")),
+            vec!["Ethiopia", "Colombia"]
+        );
+    }
+
+    #[test]
+    fn parse_origins_trims_whitespace() {
+        assert_eq!(
+            parse_origins(Some("  Ethiopia , Colombia  , Kenya ")),
+            vec!["Ethiopia", "Colombia", "Kenya"]
+        );
+    }
+
+    #[test]
+    fn parse_origins_empty_and_none() {
+        assert!(parse_origins(None).is_empty());
+        assert!(parse_origins(Some("")).is_empty());
+        assert!(parse_origins(Some("  ")).is_empty());
+    }
+
+    #[test]
+    fn parse_origins_trailing_comma() {
+        assert_eq!(parse_origins(Some("Ethiopia,")), vec!["Ethiopia"]);
+    }
+
+    #[test]
+    fn origins_to_flags_single() {
+        let flags = origins_to_flags(Some("Ethiopia"));
+        assert_eq!(flags, iso_to_flag_emoji("ET"));
+    }
+
+    #[test]
+    fn origins_to_flags_multiple() {
+        let flags = origins_to_flags(Some("Ethiopia, Colombia"));
+        let expected = format!("{} {}", iso_to_flag_emoji("ET"), iso_to_flag_emoji("CO"));
+        assert_eq!(flags, expected);
+    }
+
+    #[test]
+    fn origins_to_flags_skips_unknown() {
+        let flags = origins_to_flags(Some("Ethiopia, Narnia, Colombia"));
+        let expected = format!("{} {}", iso_to_flag_emoji("ET"), iso_to_flag_emoji("CO"));
+        assert_eq!(flags, expected);
+    }
+
+    #[test]
+    fn origins_to_flags_none() {
+        assert_eq!(origins_to_flags(None), "");
+    }
+}
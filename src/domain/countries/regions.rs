@@ -0,0 +1,287 @@
+use serde::Serialize;
+
+use super::{country_to_iso, parse_origins};
+
+/// A well-known coffee-producing region and its typical growing conditions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct ProducingRegion {
+    pub name: &'static str,
+    /// Country name as accepted by [`country_to_iso`].
+    pub country: &'static str,
+    #[serde(skip)]
+    pub aliases: &'static [&'static str],
+    pub elevation_min_m: u32,
+    pub elevation_max_m: u32,
+    pub varietals: &'static [&'static str],
+}
+
+impl ProducingRegion {
+    /// ISO-3166-1 alpha-2 code of the region's country.
+    pub fn country_iso(&self) -> Option<&'static str> {
+        country_to_iso(self.country)
+    }
+
+    /// Typical elevation formatted for display, e.g. `1,750–2,200 m`.
+    pub fn elevation_label(&self) -> String {
+        format!(
+            "{}\u{2013}{} m",
+            group_thousands(self.elevation_min_m),
+            group_thousands(self.elevation_max_m)
+        )
+    }
+
+    fn matches(&self, name: &str) -> bool {
+        self.name.eq_ignore_ascii_case(name)
+            || self
+                .aliases
+                .iter()
+                .any(|alias| alias.eq_ignore_ascii_case(name))
+    }
+}
+
+fn group_thousands(value: u32) -> String {
+    if value >= 1000 {
+        format!("{},{:03}", value / 1000, value % 1000)
+    } else {
+        value.to_string()
+    }
+}
+
+macro_rules! region {
+    ($name:literal, $country:literal, [$($alias:literal),*], $min:literal..$max:literal, [$($varietal:literal),+ $(,)?]) => {
+        ProducingRegion {
+            name: $name,
+            country: $country,
+            aliases: &[$($alias),*],
+            elevation_min_m: $min,
+            elevation_max_m: $max,
+            varietals: &[$($varietal),+],
+        }
+    };
+}
+
+/// Producing regions, grouped by country.
+#[rustfmt::skip]
+pub static REGIONS: &[ProducingRegion] = &[
+    // Ethiopia
+    region!("Yirgacheffe", "Ethiopia", ["Yirga Cheffe", "Yirgachefe"], 1750..2200, ["Heirloom", "74110", "74112"]),
+    region!("Guji", "Ethiopia", [], 1800..2300, ["Heirloom", "74110", "74112"]),
+    region!("Sidamo", "Ethiopia", ["Sidama"], 1500..2200, ["Heirloom", "74158"]),
+    region!("Limu", "Ethiopia", [], 1400..2000, ["Heirloom"]),
+    region!("Harrar", "Ethiopia", ["Harar"], 1500..2100, ["Heirloom", "Longberry"]),
+    region!("Jimma", "Ethiopia", ["Djimmah"], 1400..2100, ["Heirloom"]),
+    region!("Bench Maji", "Ethiopia", ["Bench Sheko"], 1500..2100, ["Gesha", "Heirloom"]),
+    // Kenya
+    region!("Nyeri", "Kenya", [], 1500..2100, ["SL28", "SL34", "Ruiru 11", "Batian"]),
+    region!("Kirinyaga", "Kenya", [], 1500..1900, ["SL28", "SL34", "Ruiru 11", "Batian"]),
+    region!("Embu", "Kenya", [], 1300..1900, ["SL28", "SL34", "Ruiru 11"]),
+    region!("Kiambu", "Kenya", [], 1500..1800, ["SL28", "SL34", "Batian"]),
+    region!("Murang'a", "Kenya", ["Muranga"], 1400..1900, ["SL28", "SL34", "Ruiru 11"]),
+    // Rwanda & Burundi
+    region!("Nyamasheke", "Rwanda", [], 1500..2000, ["Red Bourbon"]),
+    region!("Huye", "Rwanda", [], 1600..2000, ["Red Bourbon"]),
+    region!("Kayanza", "Burundi", [], 1700..2000, ["Red Bourbon"]),
+    // Colombia
+    region!("Huila", "Colombia", [], 1500..2100, ["Caturra", "Castillo", "Colombia", "Pink Bourbon"]),
+    region!("Nariño", "Colombia", ["Narino"], 1600..2300, ["Caturra", "Castillo", "Typica"]),
+    region!("Cauca", "Colombia", [], 1500..2100, ["Caturra", "Castillo", "Colombia"]),
+    region!("Tolima", "Colombia", [], 1400..2000, ["Caturra", "Castillo", "Colombia"]),
+    region!("Antioquia", "Colombia", [], 1300..2000, ["Caturra", "Castillo"]),
+    // Brazil
+    region!("Cerrado", "Brazil", ["Cerrado Mineiro"], 800..1300, ["Mundo Novo", "Catuaí", "Yellow Bourbon"]),
+    region!("Sul de Minas", "Brazil", [], 900..1400, ["Mundo Novo", "Catuaí", "Yellow Bourbon"]),
+    region!("Mogiana", "Brazil", ["Alta Mogiana"], 900..1200, ["Mundo Novo", "Catuaí"]),
+    region!("Santos", "Brazil", [], 700..1200, ["Mundo Novo", "Bourbon"]),
+    region!("Chapada Diamantina", "Brazil", [], 1000..1400, ["Catuaí", "Yellow Bourbon"]),
+    // Central America
+    region!("Antigua", "Guatemala", [], 1500..1700, ["Bourbon", "Caturra", "Typica"]),
+    region!("Huehuetenango", "Guatemala", [], 1500..2000, ["Bourbon", "Caturra", "Pache"]),
+    region!("Atitlán", "Guatemala", ["Atitlan"], 1500..1700, ["Bourbon", "Caturra", "Typica"]),
+    region!("Tarrazú", "Costa Rica", ["Tarrazu"], 1200..1900, ["Caturra", "Catuaí"]),
+    region!("West Valley", "Costa Rica", ["Valle Occidental"], 1200..1700, ["Caturra", "Villa Sarchi"]),
+    region!("Boquete", "Panama", [], 1200..2000, ["Gesha", "Caturra", "Catuaí"]),
+    region!("Santa Ana", "El Salvador", [], 1200..2000, ["Bourbon", "Pacas", "Pacamara"]),
+    region!("Marcala", "Honduras", [], 1300..1700, ["Catuaí", "Lempira", "Ihcafe 90"]),
+    region!("Santa Bárbara", "Honduras", ["Santa Barbara"], 1400..1800, ["Pacas", "Catuaí", "Parainema"]),
+    region!("Jinotega", "Nicaragua", [], 1100..1700, ["Caturra", "Catuaí", "Maracaturra"]),
+    region!("Chiapas", "Mexico", [], 1000..1800, ["Bourbon", "Typica", "Caturra"]),
+    region!("Oaxaca", "Mexico", [], 900..1800, ["Typica", "Bourbon", "Pluma Hidalgo"]),
+    // South America
+    region!("Cusco", "Peru", ["Cuzco"], 1200..2000, ["Typica", "Caturra", "Bourbon"]),
+    region!("Cajamarca", "Peru", [], 1200..2100, ["Caturra", "Typica", "Bourbon"]),
+    region!("Caranavi", "Bolivia", [], 1400..1800, ["Caturra", "Typica", "Java"]),
+    region!("Loja", "Ecuador", [], 1500..2200, ["Typica", "Sidra", "Caturra"]),
+    // Asia-Pacific
+    region!("Sumatra", "Indonesia", ["Aceh", "Gayo"], 1100..1600, ["Ateng", "Tim Tim", "Typica"]),
+    region!("Java", "Indonesia", [], 1000..1800, ["Typica", "Ateng"]),
+    region!("Yunnan", "China", [], 1000..2100, ["Catimor", "Typica"]),
+    region!("Chikmagalur", "India", [], 1000..1500, ["SLN 795", "Catimor", "Kent"]),
+    region!("Eastern Highlands", "Papua New Guinea", [], 1400..1900, ["Typica", "Bourbon", "Arusha"]),
+    // Yemen
+    region!("Haraz", "Yemen", [], 1900..2500, ["Udaini", "Dawairi", "Tufahi"]),
+];
+
+/// Look up a producing region by name or alias, ignoring case.
+pub fn find_region(name: &str) -> Option<&'static ProducingRegion> {
+    let name = name.trim();
+    if name.is_empty() {
+        return None;
+    }
+    REGIONS.iter().find(|region| region.matches(name))
+}
+
+/// Suggest regions whose name starts with or contains `query`.
+///
+/// When `origin` names one or more known countries, only regions in those
+/// countries are returned. Prefix matches sort ahead of substring matches.
+pub fn suggest_regions(query: &str, origin: Option<&str>) -> Vec<&'static ProducingRegion> {
+    let query = query.trim().to_lowercase();
+    let countries: Vec<&str> = parse_origins(origin)
+        .into_iter()
+        .filter_map(country_to_iso)
+        .collect();
+
+    let mut matches: Vec<(bool, &'static ProducingRegion)> = REGIONS
+        .iter()
+        .filter(|region| {
+            countries.is_empty()
+                || region
+                    .country_iso()
+                    .is_some_and(|iso| countries.contains(&iso))
+        })
+        .filter_map(|region| {
+            let name = region.name.to_lowercase();
+            if name.starts_with(&query) {
+                Some((true, region))
+            } else if name.contains(&query) {
+                Some((false, region))
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    matches.sort_by_key(|(is_prefix, region)| (!is_prefix, region.name));
+    matches.into_iter().map(|(_, region)| region).collect()
+}
+
+/// Resolve each comma-separated region to a known producing region, if any.
+pub fn known_regions(region: Option<&str>) -> Vec<&'static ProducingRegion> {
+    parse_origins(region)
+        .into_iter()
+        .filter_map(find_region)
+        .collect()
+}
+
+/// Check that every known region lies in one of the origin countries and
+/// return the region text with known names in their canonical spelling.
+///
+/// Regions that aren't in the dataset, or origins that don't resolve to a
+/// known country (e.g. "Blend"), are accepted unchanged.
+pub fn normalize_region(origin: &str, region: &str) -> Result<String, String> {
+    let countries: Vec<&str> = parse_origins(Some(origin))
+        .into_iter()
+        .filter_map(country_to_iso)
+        .collect();
+
+    let mut normalized = Vec::new();
+    for name in parse_origins(Some(region)) {
+        let Some(known) = find_region(name) else {
+            normalized.push(name);
+            continue;
+        };
+        if !countries.is_empty()
+            && known
+                .country_iso()
+                .is_some_and(|iso| !countries.contains(&iso))
+        {
+            return Err(format!(
+                "region '{}' is in {}, not {}",
+                known.name,
+                known.country,
+                origin.trim()
+            ));
+        }
+        normalized.push(known.name);
+    }
+
+    if normalized.is_empty() {
+        return Ok(region.trim().to_string());
+    }
+    Ok(normalized.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_region_resolves_to_a_country() {
+        for region in REGIONS {
+            assert!(region.country_iso().is_some(), "{}", region.name);
+            assert!(region.elevation_min_m < region.elevation_max_m);
+        }
+    }
+
+    #[test]
+    fn find_region_matches_names_and_aliases() {
+        assert_eq!(find_region("yirgacheffe").unwrap().country, "Ethiopia");
+        assert_eq!(find_region("Yirga Cheffe").unwrap().name, "Yirgacheffe");
+        assert!(find_region("Narnia").is_none());
+        assert!(find_region("  ").is_none());
+    }
+
+    #[test]
+    fn elevation_label_groups_thousands() {
+        let region = find_region("Yirgacheffe").unwrap();
+        assert_eq!(region.elevation_label(), "1,750\u{2013}2,200 m");
+        assert_eq!(
+            find_region("Santos").unwrap().elevation_label(),
+            "700\u{2013}1,200 m"
+        );
+    }
+
+    #[test]
+    fn suggest_regions_prefers_prefix_matches() {
+        let names: Vec<_> = suggest_regions("an", None)
+            .into_iter()
+            .map(|region| region.name)
+            .collect();
+        assert_eq!(names[..2], ["Antigua", "Antioquia"]);
+        assert!(names.contains(&"Santa Ana"));
+    }
+
+    #[test]
+    fn suggest_regions_filters_by_origin() {
+        let names: Vec<_> = suggest_regions("", Some("Kenya"))
+            .into_iter()
+            .map(|region| region.name)
+            .collect();
+        assert!(names.contains(&"Nyeri"));
+        assert!(!names.contains(&"Guji"));
+    }
+
+    #[test]
+    fn normalize_region_canonicalizes_known_names() {
+        assert_eq!(
+            normalize_region("Ethiopia", "yirga cheffe").unwrap(),
+            "Yirgacheffe"
+        );
+        assert_eq!(
+            normalize_region("Ethiopia, Colombia", "Guji, huila").unwrap(),
+            "Guji, Huila"
+        );
+        assert_eq!(normalize_region("Ethiopia", "Hambela").unwrap(), "Hambela");
+    }
+
+    #[test]
+    fn normalize_region_rejects_region_from_another_country() {
+        let err = normalize_region("Ethiopia", "Huila").unwrap_err();
+        assert_eq!(err, "region 'Huila' is in Colombia, not Ethiopia");
+    }
+
+    #[test]
+    fn normalize_region_accepts_unknown_origin() {
+        assert_eq!(normalize_region("Blend", "Huila").unwrap(), "Huila");
+    }
+}
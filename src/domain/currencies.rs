@@ -0,0 +1,104 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Upper bound for an exchange rate; no real currency pair comes close, so
+/// anything above it is a typo.
+pub const MAX_EXCHANGE_RATE: f64 = 1_000_000.0;
+
+/// Tidy a currency code as typed ("eur ") into its ISO 4217 form ("EUR").
+pub fn normalize_currency(code: &str) -> String {
+    code.trim().to_ascii_uppercase()
+}
+
+/// Currencies are ISO 4217 codes: three letters, e.g. "EUR".
+pub fn validate_currency(code: &str) -> Result<(), String> {
+    if code.len() == 3 && code.chars().all(|c| c.is_ascii_uppercase()) {
+        Ok(())
+    } else {
+        Err(format!(
+            "\"{code}\" is not a currency code; use three letters, e.g. EUR"
+        ))
+    }
+}
+
+/// How much of the base currency one unit of `currency` buys, as entered by
+/// hand on the Admin page.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExchangeRate {
+    pub currency: String,
+    pub rate: f64,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SetExchangeRate {
+    pub rate: f64,
+}
+
+impl SetExchangeRate {
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.rate.is_finite() || self.rate <= 0.0 || self.rate > MAX_EXCHANGE_RATE {
+            return Err(format!(
+                "exchange rate must be above 0 and at most {MAX_EXCHANGE_RATE}"
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Convert `amount` paid in `currency` into `base`. Prices with no currency,
+/// or in the base currency itself, need no rate. `None` when there's no rate
+/// for the currency.
+pub fn convert(
+    amount: f64,
+    currency: Option<&str>,
+    base: Option<&str>,
+    rates: &[ExchangeRate],
+) -> Option<f64> {
+    match currency {
+        None => Some(amount),
+        Some(code) if Some(code) == base => Some(amount),
+        Some(code) => rates
+            .iter()
+            .find(|rate| rate.currency == code)
+            .map(|rate| amount * rate.rate),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rate(currency: &str, rate: f64) -> ExchangeRate {
+        ExchangeRate {
+            currency: currency.to_string(),
+            rate,
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn currency_codes_are_three_letters() {
+        assert_eq!(normalize_currency(" eur "), "EUR");
+        assert!(validate_currency("EUR").is_ok());
+        assert!(validate_currency("EU").is_err());
+        assert!(validate_currency("eur").is_err());
+        assert!(validate_currency("€").is_err());
+    }
+
+    #[test]
+    fn rates_must_be_positive() {
+        assert!(SetExchangeRate { rate: 0.85 }.validate().is_ok());
+        assert!(SetExchangeRate { rate: 0.0 }.validate().is_err());
+        assert!(SetExchangeRate { rate: f64::NAN }.validate().is_err());
+    }
+
+    #[test]
+    fn prices_convert_into_the_base_currency() {
+        let rates = [rate("EUR", 0.85)];
+        assert_eq!(convert(10.0, None, Some("GBP"), &rates), Some(10.0));
+        assert_eq!(convert(10.0, Some("GBP"), Some("GBP"), &rates), Some(10.0));
+        assert_eq!(convert(10.0, Some("EUR"), Some("GBP"), &rates), Some(8.5));
+        assert_eq!(convert(10.0, Some("JPY"), Some("GBP"), &rates), None);
+    }
+}
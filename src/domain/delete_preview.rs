@@ -0,0 +1,100 @@
+use serde::{Deserialize, Serialize};
+
+/// Records affected by deleting a roaster, roast, or cafe.
+///
+/// Roasts, bags, brews, and images are removed along with the entity. Cups
+/// are protected by a `RESTRICT` foreign key, so any counted here block the
+/// delete until they are removed first.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeletePreview {
+    pub roasts: i64,
+    pub bags: i64,
+    pub brews: i64,
+    pub cups: i64,
+    pub images: i64,
+}
+
+impl DeletePreview {
+    /// Whether the delete would fail because cups still reference the entity.
+    pub fn is_blocked(&self) -> bool {
+        self.cups > 0
+    }
+
+    /// Sentence(s) describing the impact, for use in a confirmation dialog.
+    /// Empty when nothing besides the entity itself is affected.
+    pub fn warning(&self) -> String {
+        let cascaded: Vec<String> = [
+            (self.roasts, "roast", "roasts"),
+            (self.bags, "bag", "bags"),
+            (self.brews, "brew", "brews"),
+            (self.images, "image", "images"),
+        ]
+        .into_iter()
+        .filter(|(count, ..)| *count > 0)
+        .map(|(count, singular, plural)| count_label(count, singular, plural))
+        .collect();
+
+        let mut sentences = Vec::new();
+        if !cascaded.is_empty() {
+            sentences.push(format!("This will also delete {}.", join_list(&cascaded)));
+        }
+        if self.is_blocked() {
+            sentences.push(format!(
+                "{} must be deleted first.",
+                count_label(self.cups, "cup", "cups")
+            ));
+        }
+        sentences.join(" ")
+    }
+}
+
+fn count_label(count: i64, singular: &str, plural: &str) -> String {
+    if count == 1 {
+        format!("1 {singular}")
+    } else {
+        format!("{count} {plural}")
+    }
+}
+
+fn join_list(items: &[String]) -> String {
+    match items {
+        [] => String::new(),
+        [only] => only.clone(),
+        [rest @ .., last] => format!("{} and {last}", rest.join(", ")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn warning_empty_when_nothing_affected() {
+        assert_eq!(DeletePreview::default().warning(), "");
+    }
+
+    #[test]
+    fn warning_lists_cascaded_records() {
+        let preview = DeletePreview {
+            roasts: 1,
+            bags: 3,
+            brews: 12,
+            images: 2,
+            ..DeletePreview::default()
+        };
+        assert_eq!(
+            preview.warning(),
+            "This will also delete 1 roast, 3 bags, 12 brews and 2 images."
+        );
+    }
+
+    #[test]
+    fn warning_notes_blocking_cups() {
+        let preview = DeletePreview {
+            cups: 1,
+            ..DeletePreview::default()
+        };
+        assert!(preview.is_blocked());
+        assert_eq!(preview.warning(), "1 cup must be deleted first.");
+    }
+}
@@ -13,6 +13,10 @@ pub enum EntityType {
     Cup,
     Cafe,
     Gear,
+    Event,
+    Trip,
+    /// Only used for profile avatars.
+    User,
 }
 
 impl EntityType {
@@ -25,6 +29,9 @@ impl EntityType {
             Self::Cup => "cup",
             Self::Cafe => "cafe",
             Self::Gear => "gear",
+            Self::Event => "event",
+            Self::Trip => "trip",
+            Self::User => "user",
         }
     }
 }
@@ -47,6 +54,9 @@ impl FromStr for EntityType {
             "cup" => Ok(Self::Cup),
             "cafe" => Ok(Self::Cafe),
             "gear" => Ok(Self::Gear),
+            "event" => Ok(Self::Event),
+            "trip" => Ok(Self::Trip),
+            "user" => Ok(Self::User),
             _ => Err(()),
         }
     }
@@ -56,7 +66,7 @@ impl FromStr for EntityType {
 mod tests {
     use super::*;
 
-    const ALL_VARIANTS: [EntityType; 7] = [
+    const ALL_VARIANTS: [EntityType; 10] = [
         EntityType::Roaster,
         EntityType::Roast,
         EntityType::Bag,
@@ -64,6 +74,9 @@ mod tests {
         EntityType::Cup,
         EntityType::Cafe,
         EntityType::Gear,
+        EntityType::Event,
+        EntityType::Trip,
+        EntityType::User,
     ];
 
     #[test]
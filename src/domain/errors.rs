@@ -1,23 +1,131 @@
-use std::fmt::Display;
+use std::fmt::{self, Display};
 
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+/// Machine-readable reason for a failed request, sent as `code` in every API
+/// error body so clients can branch on it instead of matching messages.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    /// Malformed input, such as an unparseable date or an empty update.
+    BadRequest,
+    /// One or more fields break a rule; see `fields`.
+    InvalidFields,
+    NotFound,
+    /// The request needs a session or API token.
+    Unauthorized,
+    Forbidden,
+    /// The path names an API version this server doesn't serve.
+    UnsupportedApiVersion,
+    /// A conflict with no more specific code.
+    Conflict,
+    /// Another record already has the slug this name would produce.
+    DuplicateSlug,
+    /// The bag is closed, so nothing can be brewed from or corrected on it.
+    BagClosed,
+    BagAlreadyOpen,
+    /// The bag doesn't hold as much coffee as was asked for.
+    BagInsufficientRemaining,
+    /// The receiving bag would end up holding more than its size.
+    BagNoRoom,
+    /// Less green coffee is left than a roast batch needs, or than has
+    /// already been roasted.
+    GreenCoffeeInsufficient,
+    /// A full restore needs an empty database.
+    DatabaseNotEmpty,
+    /// The server is a read-only demo instance.
+    ReadOnly,
+    /// An uploaded image is bigger than the server accepts.
+    ImageTooLarge,
+    /// AI extraction timed out or failed; enter the details by hand.
+    AiUnavailable,
+    Unexpected,
+    /// A code this build doesn't know, from a newer server.
+    #[default]
+    #[serde(other)]
+    Unknown,
+}
+
+impl ErrorCode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::BadRequest => "bad_request",
+            Self::InvalidFields => "invalid_fields",
+            Self::NotFound => "not_found",
+            Self::Unauthorized => "unauthorized",
+            Self::Forbidden => "forbidden",
+            Self::UnsupportedApiVersion => "unsupported_api_version",
+            Self::Conflict => "conflict",
+            Self::DuplicateSlug => "duplicate_slug",
+            Self::BagClosed => "bag_closed",
+            Self::BagAlreadyOpen => "bag_already_open",
+            Self::BagInsufficientRemaining => "bag_insufficient_remaining",
+            Self::BagNoRoom => "bag_no_room",
+            Self::GreenCoffeeInsufficient => "green_coffee_insufficient",
+            Self::DatabaseNotEmpty => "database_not_empty",
+            Self::ReadOnly => "read_only",
+            Self::ImageTooLarge => "image_too_large",
+            Self::AiUnavailable => "ai_unavailable",
+            Self::Unexpected => "unexpected",
+            Self::Unknown => "unknown",
+        }
+    }
+}
+
+impl Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 #[derive(Debug, Error)]
 pub enum RepositoryError {
     #[error("entity not found")]
     NotFound,
-    #[error("conflict: {0}")]
-    Conflict(String),
+    #[error("conflict: {1}")]
+    Conflict(ErrorCode, String),
     #[error("unexpected data store error: {0}")]
     Unexpected(String),
 }
 
 impl RepositoryError {
     pub fn conflict<T: Display>(message: T) -> Self {
-        Self::Conflict(message.to_string())
+        Self::Conflict(ErrorCode::Conflict, message.to_string())
+    }
+
+    /// A conflict with a more specific [`ErrorCode`] than
+    /// [`ErrorCode::Conflict`].
+    pub fn conflict_with<T: Display>(code: ErrorCode, message: T) -> Self {
+        Self::Conflict(code, message.to_string())
     }
 
     pub fn unexpected<T: Display>(message: T) -> Self {
         Self::Unexpected(message.to_string())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes_serialize_as_their_wire_names() {
+        for code in [
+            ErrorCode::DuplicateSlug,
+            ErrorCode::BagInsufficientRemaining,
+            ErrorCode::Unknown,
+        ] {
+            assert_eq!(
+                serde_json::to_string(&code).unwrap(),
+                format!("\"{}\"", code.as_str())
+            );
+        }
+    }
+
+    #[test]
+    fn unrecognised_codes_parse_as_unknown() {
+        let code: ErrorCode = serde_json::from_str("\"from_the_future\"").unwrap();
+        assert_eq!(code, ErrorCode::Unknown);
+    }
+}
@@ -56,6 +56,19 @@ pub fn format_weight(grams: f64) -> String {
     }
 }
 
+/// Format a price for display with two decimal places ("14.50").
+pub fn format_price(price: f64) -> String {
+    format!("{price:.2}")
+}
+
+/// A price followed by its currency, when it has one ("14.50 EUR").
+pub fn format_money(amount: f64, currency: Option<&str>) -> String {
+    match currency {
+        Some(code) => format!("{amount:.2} {code}"),
+        None => format_price(amount),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -212,4 +225,12 @@ mod tests {
         assert_eq!(format_weight(2345.6), "2.3kg");
         assert_eq!(format_weight(10000.0), "10.0kg");
     }
+
+    #[test]
+    fn price_has_two_decimals() {
+        assert_eq!(format_price(14.5), "14.50");
+        assert_eq!(format_price(9.0), "9.00");
+        assert_eq!(format_money(14.5, Some("EUR")), "14.50 EUR");
+        assert_eq!(format_money(14.5, None), "14.50");
+    }
 }
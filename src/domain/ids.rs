@@ -62,3 +62,16 @@ define_id!(CupId);
 define_id!(PasskeyCredentialId);
 define_id!(RegistrationTokenId);
 define_id!(AiUsageId);
+define_id!(SavedSearchId);
+define_id!(GrinderCalibrationId);
+define_id!(CustomQuickNoteId);
+define_id!(CustomProcessId);
+define_id!(BrewShareId);
+define_id!(CommentId);
+define_id!(NotificationId);
+define_id!(BagLedgerEntryId);
+define_id!(GreenCoffeeId);
+define_id!(RoastBatchId);
+define_id!(EventId);
+define_id!(TripId);
+define_id!(AliasId);
@@ -1,6 +1,10 @@
 use std::cmp;
+use std::str::FromStr;
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum SortDirection {
     Asc,
     Desc,
@@ -22,6 +26,18 @@ impl SortDirection {
     }
 }
 
+impl FromStr for SortDirection {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "asc" => Ok(SortDirection::Asc),
+            "desc" => Ok(SortDirection::Desc),
+            _ => Err(()),
+        }
+    }
+}
+
 pub trait SortKey: Copy + Eq {
     fn default() -> Self;
     fn from_query(value: &str) -> Option<Self>;
@@ -51,7 +67,7 @@ macro_rules! define_sort_key {
             $(,)?
         }
     ) => {
-        #[derive(Debug, Clone, Copy, Eq, PartialEq)]
+        #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
         $vis enum $name {
             $($default_variant,)?
             $($variant),*
@@ -255,6 +271,28 @@ impl<T> Page<T> {
         }
     }
 
+    /// Cut a sorted, filtered listing into the requested page, clamping past
+    /// the last page the way the SQL repositories do.
+    pub fn from_sorted<K: SortKey>(items: Vec<T>, request: &ListRequest<K>) -> Self {
+        let total = items.len() as u64;
+        match request.page_size() {
+            PageSize::All => {
+                let page_size = total.min(u64::from(u32::MAX)) as u32;
+                Self::new(items, 1, page_size.max(1), total, true)
+            }
+            PageSize::Limited(page_size) => {
+                let page = (*request).ensure_page_within(total).page();
+                let offset = (page as usize - 1).saturating_mul(page_size as usize);
+                let items = items
+                    .into_iter()
+                    .skip(offset)
+                    .take(page_size as usize)
+                    .collect();
+                Self::new(items, page, page_size, total, false)
+            }
+        }
+    }
+
     pub fn total_pages(&self) -> u32 {
         if self.total == 0 || self.showing_all {
             1
@@ -0,0 +1,84 @@
+use crate::domain::roasters::is_valid_url_scheme;
+
+/// Deepest zoom level any common tile server renders.
+pub const MAX_TILE_ZOOM: u8 = 19;
+
+const PLACEHOLDERS: [&str; 3] = ["{z}", "{x}", "{y}"];
+
+/// Tile URLs are slippy-map templates such as
+/// `https://tile.openstreetmap.org/{z}/{x}/{y}.png`.
+pub fn validate_tile_url(template: &str) -> Result<(), String> {
+    if !is_valid_url_scheme(template) {
+        return Err("map tile URL must start with http:// or https://".to_string());
+    }
+    let missing: Vec<&str> = PLACEHOLDERS
+        .into_iter()
+        .filter(|placeholder| !template.contains(placeholder))
+        .collect();
+    if !missing.is_empty() {
+        return Err(format!(
+            "map tile URL is missing {}; e.g. https://tile.openstreetmap.org/{{z}}/{{x}}/{{y}}.png",
+            missing.join(", ")
+        ));
+    }
+    Ok(())
+}
+
+/// One square of the web-mercator grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MapTile {
+    pub z: u8,
+    pub x: u32,
+    pub y: u32,
+}
+
+impl MapTile {
+    /// `None` when the coordinates fall outside the grid at that zoom.
+    pub fn new(z: u8, x: u32, y: u32) -> Option<Self> {
+        if z > MAX_TILE_ZOOM {
+            return None;
+        }
+        let side = 1u32 << z;
+        (x < side && y < side).then_some(Self { z, x, y })
+    }
+
+    /// Fill the tile into a URL template accepted by [`validate_tile_url`].
+    pub fn url(&self, template: &str) -> String {
+        template
+            .replace("{z}", &self.z.to_string())
+            .replace("{x}", &self.x.to_string())
+            .replace("{y}", &self.y.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tile_urls_need_a_web_scheme_and_every_placeholder() {
+        assert!(validate_tile_url("https://tile.openstreetmap.org/{z}/{x}/{y}.png").is_ok());
+        assert!(validate_tile_url("http://tiles.local/{z}/{y}/{x}").is_ok());
+        assert!(validate_tile_url("javascript:alert(1)//{z}/{x}/{y}").is_err());
+        let err = validate_tile_url("https://tiles.local/{z}/{x}.png").unwrap_err();
+        assert!(err.contains("{y}"), "{err}");
+    }
+
+    #[test]
+    fn tiles_must_be_on_the_grid() {
+        assert!(MapTile::new(0, 0, 0).is_some());
+        assert!(MapTile::new(0, 1, 0).is_none());
+        assert!(MapTile::new(3, 7, 7).is_some());
+        assert!(MapTile::new(3, 8, 0).is_none());
+        assert!(MapTile::new(MAX_TILE_ZOOM + 1, 0, 0).is_none());
+    }
+
+    #[test]
+    fn fills_the_template() {
+        let tile = MapTile::new(12, 2047, 1362).unwrap();
+        assert_eq!(
+            tile.url("https://tiles.local/{z}/{x}/{y}.png?key=abc"),
+            "https://tiles.local/12/2047/1362.png?key=abc"
+        );
+    }
+}
@@ -1,17 +1,32 @@
 pub mod analytics;
 pub mod auth;
+pub mod clock;
 pub mod coffee;
 pub mod countries;
+pub mod currencies;
+pub mod delete_preview;
 pub mod entity_type;
 pub mod errors;
 pub mod formatting;
 pub mod ids;
 pub mod images;
 pub mod listing;
+pub mod map_tiles;
 pub mod repositories;
+pub mod settings;
+pub mod slugs;
+pub mod tenants;
+pub mod validation;
 
 // Re-exports for backward compatibility
-pub use analytics::{ai_usage, country_stats, stats, timeline};
-pub use auth::{passkey_credentials, registration_tokens, sessions, tokens, users};
-pub use coffee::{bags, brews, cafes, cups, gear, nearby_cafes, roasters, roasts};
-pub use errors::RepositoryError;
+pub use analytics::{ai_usage, country_stats, quick_actions, stats, timeline};
+pub use auth::{
+    list_preferences, notifications, passkey_credentials, registration_tokens, saved_searches,
+    sessions, tokens, users,
+};
+pub use coffee::{
+    aliases, bag_ledger, bags, blends, brew_parsing, brew_shares, brews, cafes, comments, cups,
+    events, gear, green_coffees, grinder_calibrations, matching, nearby_cafes, processes,
+    quick_notes, ratings, roast_batches, roasters, roasts, trips,
+};
+pub use errors::{ErrorCode, RepositoryError};
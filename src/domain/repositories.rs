@@ -1,33 +1,73 @@
+use std::collections::HashSet;
+
 use super::RepositoryError;
-use crate::domain::ai_usage::{AiUsage, AiUsageSummary, NewAiUsage};
+use crate::domain::ai_usage::{
+    AiModelPrice, AiUsage, AiUsageMonth, AiUsageSummary, NewAiUsage, SetAiModelPrice,
+};
+use crate::domain::delete_preview::DeletePreview;
 use crate::domain::entity_type::EntityType;
 use crate::domain::listing::{ListRequest, Page, SortDirection, SortKey};
 
+use crate::domain::aliases::{Alias, AliasTarget, NewAlias};
+use crate::domain::bag_ledger::{BagLedgerEntry, BagTransfer, NewBagAdjustment, NewBagTransfer};
 use crate::domain::bags::{Bag, BagFilter, BagSortKey, BagWithRoast, NewBag, UpdateBag};
-use crate::domain::brews::{Brew, BrewFilter, BrewSortKey, BrewWithDetails, NewBrew, UpdateBrew};
+use crate::domain::brew_shares::{BrewShare, NewBrewShare};
+use crate::domain::brews::{
+    Brew, BrewFilter, BrewSortKey, BrewUsage, BrewWithDetails, NewBrew, RoastBrewStats, UpdateBrew,
+};
 use crate::domain::cafes::{Cafe, CafeSortKey, NewCafe, UpdateCafe};
+use crate::domain::comments::{Comment, CommentTarget, NewComment};
 use crate::domain::cups::{Cup, CupFilter, CupSortKey, CupWithDetails, NewCup, UpdateCup};
+use crate::domain::currencies::ExchangeRate;
+use crate::domain::events::{Event, EventFilter, NewEvent, UpdateEvent};
 use crate::domain::gear::{Gear, GearFilter, GearSortKey, NewGear, UpdateGear};
+use crate::domain::green_coffees::{GreenCoffee, NewGreenCoffee, UpdateGreenCoffee};
+use crate::domain::grinder_calibrations::{GrinderCalibration, NewGrinderCalibration};
 use crate::domain::ids::{
-    BagId, BrewId, CafeId, CupId, GearId, PasskeyCredentialId, RegistrationTokenId, RoastId,
-    RoasterId, SessionId, TokenId, UserId,
+    AliasId, BagId, BrewId, BrewShareId, CafeId, CommentId, CupId, CustomProcessId,
+    CustomQuickNoteId, EventId, GearId, GreenCoffeeId, GrinderCalibrationId, NotificationId,
+    PasskeyCredentialId, RegistrationTokenId, RoastBatchId, RoastId, RoasterId, SavedSearchId,
+    SessionId, TimelineEventId, TokenId, TripId, UserId,
 };
 use crate::domain::images::EntityImage;
+use crate::domain::list_preferences::{ListName, ListPreference, NewListPreference};
+use crate::domain::notifications::{NewNotification, Notification};
 use crate::domain::passkey_credentials::{NewPasskeyCredential, PasskeyCredential};
+use crate::domain::processes::{CustomProcess, NewCustomProcess};
+use crate::domain::quick_notes::{CustomQuickNote, NewCustomQuickNote};
 use crate::domain::registration_tokens::{NewRegistrationToken, RegistrationToken};
+use crate::domain::roast_batches::{NewRoastBatch, RoastBatch, UpdateRoastBatch};
 use crate::domain::roasters::RoasterSortKey;
 use crate::domain::roasters::{NewRoaster, Roaster, UpdateRoaster};
 use crate::domain::roasts::RoastSortKey;
-use crate::domain::roasts::{NewRoast, Roast, RoastWithRoaster, UpdateRoast};
+use crate::domain::roasts::{
+    FieldSuggestion, NewRoast, Roast, RoastField, RoastWithRoaster, UpdateRoast,
+};
+use crate::domain::saved_searches::{NewSavedSearch, SavedSearch};
 use crate::domain::sessions::{NewSession, Session};
-use crate::domain::timeline::{NewTimelineEvent, TimelineEvent, TimelineSortKey};
+use crate::domain::settings::{Settings, UpdateSettings};
+use crate::domain::timeline::{
+    NewTimelineEvent, TimelineEvent, TimelineFilter, TimelineMonth, TimelineMonthCount,
+    TimelineSortKey,
+};
 use crate::domain::tokens::{NewToken, Token};
+use crate::domain::trips::{NewTrip, Trip, UpdateTrip};
 use crate::domain::users::{NewUser, User};
 use async_trait::async_trait;
 
 #[async_trait]
 pub trait RoasterRepository: Send + Sync {
-    async fn insert(&self, roaster: NewRoaster) -> Result<Roaster, RepositoryError>;
+    async fn insert(&self, roaster: NewRoaster) -> Result<Roaster, RepositoryError> {
+        let roaster = roaster.normalize();
+        let slug = roaster.slug();
+        self.insert_with_slug(roaster, slug).await
+    }
+    /// Insert under `slug` rather than the roaster's own, which is taken.
+    async fn insert_with_slug(
+        &self,
+        roaster: NewRoaster,
+        slug: String,
+    ) -> Result<Roaster, RepositoryError>;
     async fn get(&self, id: RoasterId) -> Result<Roaster, RepositoryError>;
     async fn get_by_slug(&self, slug: &str) -> Result<Roaster, RepositoryError>;
     async fn list(
@@ -40,7 +80,10 @@ pub trait RoasterRepository: Send + Sync {
         id: RoasterId,
         changes: UpdateRoaster,
     ) -> Result<Roaster, RepositoryError>;
+    async fn set_created_by(&self, id: RoasterId, user_id: UserId) -> Result<(), RepositoryError>;
+    async fn set_favourite(&self, id: RoasterId, favourite: bool) -> Result<(), RepositoryError>;
     async fn delete(&self, id: RoasterId) -> Result<(), RepositoryError>;
+    async fn delete_preview(&self, id: RoasterId) -> Result<DeletePreview, RepositoryError>;
 
     async fn list_all(&self) -> Result<Vec<Roaster>, RepositoryError> {
         let sort_key = <RoasterSortKey as SortKey>::default();
@@ -59,11 +102,28 @@ pub trait RoasterRepository: Send + Sync {
         let page = self.list(&request, None).await?;
         Ok(page.items)
     }
+
+    /// Starred roasters, by name.
+    async fn list_favourites(&self) -> Result<Vec<Roaster>, RepositoryError> {
+        let roasters = self
+            .list_all_sorted(RoasterSortKey::Name, SortDirection::Asc)
+            .await?;
+        Ok(roasters.into_iter().filter(|r| r.favourite).collect())
+    }
 }
 
 #[async_trait]
 pub trait RoastRepository: Send + Sync {
-    async fn insert(&self, roast: NewRoast) -> Result<Roast, RepositoryError>;
+    async fn insert(&self, roast: NewRoast) -> Result<Roast, RepositoryError> {
+        let slug = roast.slug();
+        self.insert_with_slug(roast, slug).await
+    }
+    /// Insert under `slug` rather than the roast's own, which is taken.
+    async fn insert_with_slug(
+        &self,
+        roast: NewRoast,
+        slug: String,
+    ) -> Result<Roast, RepositoryError>;
     async fn get(&self, id: RoastId) -> Result<Roast, RepositoryError>;
     async fn get_with_roaster(&self, id: RoastId) -> Result<RoastWithRoaster, RepositoryError>;
     async fn get_by_slug(
@@ -81,7 +141,17 @@ pub trait RoastRepository: Send + Sync {
         roaster_id: RoasterId,
     ) -> Result<Vec<RoastWithRoaster>, RepositoryError>;
     async fn update(&self, id: RoastId, changes: UpdateRoast) -> Result<Roast, RepositoryError>;
+    async fn set_created_by(&self, id: RoastId, user_id: UserId) -> Result<(), RepositoryError>;
+    async fn set_favourite(&self, id: RoastId, favourite: bool) -> Result<(), RepositoryError>;
     async fn delete(&self, id: RoastId) -> Result<(), RepositoryError>;
+    async fn delete_preview(&self, id: RoastId) -> Result<DeletePreview, RepositoryError>;
+    /// Previously used values of `field` containing `query`, most used first.
+    async fn suggest(
+        &self,
+        field: RoastField,
+        query: &str,
+        limit: u32,
+    ) -> Result<Vec<FieldSuggestion>, RepositoryError>;
 
     async fn list_all(&self) -> Result<Vec<RoastWithRoaster>, RepositoryError> {
         let sort_key = <RoastSortKey as SortKey>::default();
@@ -89,6 +159,17 @@ pub trait RoastRepository: Send + Sync {
         let page = self.list(&request, None).await?;
         Ok(page.items)
     }
+
+    /// Starred roasts, by name.
+    async fn list_favourites(&self) -> Result<Vec<RoastWithRoaster>, RepositoryError> {
+        let request = ListRequest::show_all(RoastSortKey::Name, SortDirection::Asc);
+        let page = self.list(&request, None).await?;
+        Ok(page
+            .items
+            .into_iter()
+            .filter(|r| r.roast.favourite)
+            .collect())
+    }
 }
 
 #[async_trait]
@@ -96,9 +177,28 @@ pub trait TimelineEventRepository: Send + Sync {
     async fn insert(&self, event: NewTimelineEvent) -> Result<TimelineEvent, RepositoryError>;
     async fn list(
         &self,
+        filter: TimelineFilter,
         request: &ListRequest<TimelineSortKey>,
     ) -> Result<Page<TimelineEvent>, RepositoryError>;
 
+    /// Events in the order they were logged: those logged after `after`, or
+    /// the latest when `after` is `None`. At most `limit` are returned.
+    async fn list_logged(
+        &self,
+        filter: TimelineFilter,
+        after: Option<TimelineEventId>,
+        limit: u32,
+    ) -> Result<Vec<TimelineEvent>, RepositoryError>;
+
+    /// Event counts for every month with at least one event, newest first.
+    /// The filter's own month, if any, is ignored.
+    async fn month_counts(
+        &self,
+        filter: TimelineFilter,
+    ) -> Result<Vec<TimelineMonthCount>, RepositoryError>;
+
+    /// Rewrite an entity's events from `event`, including when they occurred,
+    /// so editing a brew's time or an event's date moves it on the timeline.
     async fn update_by_entity(
         &self,
         entity_type: EntityType,
@@ -114,11 +214,18 @@ pub trait TimelineEventRepository: Send + Sync {
 
     async fn delete_all(&self) -> Result<(), RepositoryError>;
 
+    /// Delete the events that occurred during `month`.
+    async fn delete_in_month(&self, month: TimelineMonth) -> Result<(), RepositoryError>;
+
+    /// Delete events whose entity no longer exists, such as the roasts and
+    /// brews removed along with their roaster. Returns how many were deleted.
+    async fn delete_orphaned(&self) -> Result<u64, RepositoryError>;
+
     async fn list_all(&self) -> Result<Vec<TimelineEvent>, RepositoryError> {
         let sort_key = <TimelineSortKey as SortKey>::default();
         let request =
             ListRequest::<TimelineSortKey>::show_all(sort_key, sort_key.default_direction());
-        let page = self.list(&request).await?;
+        let page = self.list(TimelineFilter::all(), &request).await?;
         Ok(page.items)
     }
 }
@@ -130,6 +237,11 @@ pub trait UserRepository: Send + Sync {
     async fn get_by_username(&self, username: &str) -> Result<User, RepositoryError>;
     async fn get_by_uuid(&self, uuid: &str) -> Result<User, RepositoryError>;
     async fn exists(&self) -> Result<bool, RepositoryError>;
+    async fn update_display_name(
+        &self,
+        id: UserId,
+        display_name: Option<String>,
+    ) -> Result<User, RepositoryError>;
     async fn list_all(&self) -> Result<Vec<User>, RepositoryError>;
 }
 
@@ -138,9 +250,22 @@ pub trait TokenRepository: Send + Sync {
     async fn insert(&self, token: NewToken) -> Result<Token, RepositoryError>;
     async fn get(&self, id: TokenId) -> Result<Token, RepositoryError>;
     async fn get_by_token_hash(&self, token_hash: &str) -> Result<Token, RepositoryError>;
+    /// Tokens whose salted hash was stored under this lookup prefix. Callers
+    /// verify each candidate against the raw token.
+    async fn list_by_lookup_prefix(
+        &self,
+        lookup_prefix: &str,
+    ) -> Result<Vec<Token>, RepositoryError>;
     async fn list_by_user(&self, user_id: UserId) -> Result<Vec<Token>, RepositoryError>;
     async fn revoke(&self, id: TokenId) -> Result<Token, RepositoryError>;
     async fn update_last_used(&self, id: TokenId) -> Result<(), RepositoryError>;
+    /// Replace a token's stored hash, e.g. after the hashing scheme changed.
+    async fn update_hash(
+        &self,
+        id: TokenId,
+        token_hash: &str,
+        lookup_prefix: Option<&str>,
+    ) -> Result<(), RepositoryError>;
 }
 
 #[async_trait]
@@ -148,10 +273,72 @@ pub trait SessionRepository: Send + Sync {
     async fn insert(&self, session: NewSession) -> Result<Session, RepositoryError>;
     async fn get(&self, id: SessionId) -> Result<Session, RepositoryError>;
     async fn get_by_token_hash(&self, token_hash: &str) -> Result<Session, RepositoryError>;
+    /// Unexpired sessions, most recently active first.
+    async fn list_by_user(&self, user_id: UserId) -> Result<Vec<Session>, RepositoryError>;
+    /// Binds a session to a device, unless it is already bound to one.
+    async fn bind_device(
+        &self,
+        id: SessionId,
+        fingerprint: &str,
+        label: &str,
+    ) -> Result<(), RepositoryError>;
+    /// Records where and when a session was last used.
+    async fn touch(
+        &self,
+        id: SessionId,
+        ip: Option<&str>,
+        at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<(), RepositoryError>;
     async fn delete(&self, id: SessionId) -> Result<(), RepositoryError>;
     async fn delete_expired(&self) -> Result<(), RepositoryError>;
 }
 
+#[async_trait]
+pub trait ListPreferenceRepository: Send + Sync {
+    async fn get(&self, user_id: UserId, list: ListName)
+    -> Result<ListPreference, RepositoryError>;
+    async fn list_by_user(&self, user_id: UserId) -> Result<Vec<ListPreference>, RepositoryError>;
+    async fn upsert(
+        &self,
+        preference: NewListPreference,
+    ) -> Result<ListPreference, RepositoryError>;
+    async fn delete(&self, user_id: UserId, list: ListName) -> Result<(), RepositoryError>;
+}
+
+#[async_trait]
+pub trait SavedSearchRepository: Send + Sync {
+    async fn insert(&self, search: NewSavedSearch) -> Result<SavedSearch, RepositoryError>;
+    async fn get(&self, user_id: UserId, id: SavedSearchId)
+    -> Result<SavedSearch, RepositoryError>;
+    async fn list_by_user(&self, user_id: UserId) -> Result<Vec<SavedSearch>, RepositoryError>;
+    /// Replace the definition of an existing saved search owned by `search.user_id`.
+    async fn update(
+        &self,
+        id: SavedSearchId,
+        search: NewSavedSearch,
+    ) -> Result<SavedSearch, RepositoryError>;
+    async fn delete(&self, user_id: UserId, id: SavedSearchId) -> Result<(), RepositoryError>;
+}
+
+#[async_trait]
+pub trait NotificationRepository: Send + Sync {
+    async fn insert(&self, notification: NewNotification) -> Result<Notification, RepositoryError>;
+    /// The user's most recent notifications, newest first.
+    async fn list_by_user(
+        &self,
+        user_id: UserId,
+        limit: u32,
+    ) -> Result<Vec<Notification>, RepositoryError>;
+    async fn count_unread(&self, user_id: UserId) -> Result<u64, RepositoryError>;
+    async fn mark_read(
+        &self,
+        user_id: UserId,
+        id: NotificationId,
+    ) -> Result<Notification, RepositoryError>;
+    /// Mark every unread notification read, returning how many changed.
+    async fn mark_all_read(&self, user_id: UserId) -> Result<u64, RepositoryError>;
+}
+
 #[async_trait]
 pub trait BagRepository: Send + Sync {
     async fn insert(&self, bag: NewBag) -> Result<Bag, RepositoryError>;
@@ -164,7 +351,19 @@ pub trait BagRepository: Send + Sync {
         search: Option<&str>,
     ) -> Result<Page<BagWithRoast>, RepositoryError>;
     async fn update(&self, id: BagId, changes: UpdateBag) -> Result<Bag, RepositoryError>;
+    async fn set_created_by(&self, id: BagId, user_id: UserId) -> Result<(), RepositoryError>;
     async fn delete(&self, id: BagId) -> Result<(), RepositoryError>;
+    /// Mark a closed bag open again, clearing `finished_at`.
+    async fn reopen(&self, id: BagId) -> Result<Bag, RepositoryError>;
+    /// Append a ledger entry bringing the bag to the corrected weight.
+    async fn adjust(&self, adjustment: NewBagAdjustment)
+    -> Result<BagLedgerEntry, RepositoryError>;
+    /// Move coffee between bags as a matching pair of ledger entries.
+    async fn transfer(&self, transfer: NewBagTransfer) -> Result<BagTransfer, RepositoryError>;
+    /// The bag's ledger, newest entry first.
+    async fn list_ledger(&self, id: BagId) -> Result<Vec<BagLedgerEntry>, RepositoryError>;
+    /// Every bag bought of a roast, oldest purchase first.
+    async fn list_purchases(&self, roast_id: RoastId) -> Result<Vec<Bag>, RepositoryError>;
 
     async fn list_all(&self) -> Result<Vec<BagWithRoast>, RepositoryError> {
         let sort_key = <BagSortKey as SortKey>::default();
@@ -185,6 +384,10 @@ pub trait GearRepository: Send + Sync {
         search: Option<&str>,
     ) -> Result<Page<Gear>, RepositoryError>;
     async fn update(&self, id: GearId, changes: UpdateGear) -> Result<Gear, RepositoryError>;
+    /// Add `count` filter papers to the gear's stock, starting the count if
+    /// it wasn't tracked.
+    async fn restock(&self, id: GearId, count: i32) -> Result<Gear, RepositoryError>;
+    async fn set_created_by(&self, id: GearId, user_id: UserId) -> Result<(), RepositoryError>;
     async fn delete(&self, id: GearId) -> Result<(), RepositoryError>;
 
     async fn list_all(&self) -> Result<Vec<Gear>, RepositoryError> {
@@ -195,10 +398,177 @@ pub trait GearRepository: Send + Sync {
     }
 }
 
+#[async_trait]
+pub trait GrinderCalibrationRepository: Send + Sync {
+    async fn insert(
+        &self,
+        calibration: NewGrinderCalibration,
+    ) -> Result<GrinderCalibration, RepositoryError>;
+    /// Points involving `grinder_id` on either side, or every point when `None`.
+    async fn list(
+        &self,
+        grinder_id: Option<GearId>,
+    ) -> Result<Vec<GrinderCalibration>, RepositoryError>;
+    /// Points relating the two grinders, in whichever order they were entered.
+    async fn list_for_pair(
+        &self,
+        grinder: GearId,
+        other: GearId,
+    ) -> Result<Vec<GrinderCalibration>, RepositoryError>;
+    async fn delete(&self, id: GrinderCalibrationId) -> Result<(), RepositoryError>;
+}
+
+#[async_trait]
+pub trait CustomQuickNoteRepository: Send + Sync {
+    async fn insert(&self, note: NewCustomQuickNote) -> Result<CustomQuickNote, RepositoryError>;
+    /// Every custom note, oldest first.
+    async fn list(&self) -> Result<Vec<CustomQuickNote>, RepositoryError>;
+    async fn delete(&self, id: CustomQuickNoteId) -> Result<(), RepositoryError>;
+}
+
+#[async_trait]
+pub trait CustomProcessRepository: Send + Sync {
+    async fn insert(&self, process: NewCustomProcess) -> Result<CustomProcess, RepositoryError>;
+    /// Every custom process, oldest first.
+    async fn list(&self) -> Result<Vec<CustomProcess>, RepositoryError>;
+    async fn delete(&self, id: CustomProcessId) -> Result<(), RepositoryError>;
+}
+
+#[async_trait]
+pub trait GreenCoffeeRepository: Send + Sync {
+    async fn insert(&self, green: NewGreenCoffee) -> Result<GreenCoffee, RepositoryError>;
+    async fn get(&self, id: GreenCoffeeId) -> Result<GreenCoffee, RepositoryError>;
+    /// Every green coffee, most recently bought first.
+    async fn list(&self) -> Result<Vec<GreenCoffee>, RepositoryError>;
+    async fn update(
+        &self,
+        id: GreenCoffeeId,
+        changes: UpdateGreenCoffee,
+    ) -> Result<GreenCoffee, RepositoryError>;
+    /// Deletes the green coffee and every batch roasted from it.
+    async fn delete(&self, id: GreenCoffeeId) -> Result<(), RepositoryError>;
+}
+
+#[async_trait]
+pub trait RoastBatchRepository: Send + Sync {
+    async fn insert(&self, batch: NewRoastBatch) -> Result<RoastBatch, RepositoryError>;
+    async fn get(&self, id: RoastBatchId) -> Result<RoastBatch, RepositoryError>;
+    /// Batches of `green_coffee_id`, or every batch when `None`, newest first.
+    async fn list(
+        &self,
+        green_coffee_id: Option<GreenCoffeeId>,
+    ) -> Result<Vec<RoastBatch>, RepositoryError>;
+    async fn update(
+        &self,
+        id: RoastBatchId,
+        changes: UpdateRoastBatch,
+    ) -> Result<RoastBatch, RepositoryError>;
+    async fn delete(&self, id: RoastBatchId) -> Result<(), RepositoryError>;
+}
+
+#[async_trait]
+pub trait EventRepository: Send + Sync {
+    async fn insert(&self, event: NewEvent) -> Result<Event, RepositoryError>;
+    async fn get(&self, id: EventId) -> Result<Event, RepositoryError>;
+    /// Events matching `filter`, most recent first.
+    async fn list(&self, filter: EventFilter) -> Result<Vec<Event>, RepositoryError>;
+    async fn update(&self, id: EventId, changes: UpdateEvent) -> Result<Event, RepositoryError>;
+    async fn set_created_by(&self, id: EventId, user_id: UserId) -> Result<(), RepositoryError>;
+    async fn delete(&self, id: EventId) -> Result<(), RepositoryError>;
+}
+
+#[async_trait]
+pub trait TripRepository: Send + Sync {
+    async fn insert(&self, trip: NewTrip) -> Result<Trip, RepositoryError>;
+    async fn get(&self, id: TripId) -> Result<Trip, RepositoryError>;
+    /// Every trip, most recent first.
+    async fn list(&self) -> Result<Vec<Trip>, RepositoryError>;
+    async fn update(&self, id: TripId, changes: UpdateTrip) -> Result<Trip, RepositoryError>;
+    async fn set_created_by(&self, id: TripId, user_id: UserId) -> Result<(), RepositoryError>;
+    async fn delete(&self, id: TripId) -> Result<(), RepositoryError>;
+}
+
+#[async_trait]
+pub trait SettingsRepository: Send + Sync {
+    /// The current settings, with defaults for anything never saved.
+    async fn get(&self) -> Result<Settings, RepositoryError>;
+    /// Changing the base currency clears the exchange rates, which were
+    /// quoted against the old one.
+    async fn update(&self, changes: UpdateSettings) -> Result<Settings, RepositoryError>;
+    /// Exchange rates into the base currency, by currency code.
+    async fn list_exchange_rates(&self) -> Result<Vec<ExchangeRate>, RepositoryError>;
+    async fn set_exchange_rate(
+        &self,
+        currency: &str,
+        rate: f64,
+    ) -> Result<ExchangeRate, RepositoryError>;
+    async fn delete_exchange_rate(&self, currency: &str) -> Result<(), RepositoryError>;
+}
+
+#[async_trait]
+pub trait BrewShareRepository: Send + Sync {
+    async fn insert(&self, share: NewBrewShare) -> Result<BrewShare, RepositoryError>;
+    async fn get_by_token_hash(&self, token_hash: &str) -> Result<BrewShare, RepositoryError>;
+    /// Every link for the brew, newest first, including revoked and expired ones.
+    async fn list_for_brew(&self, brew_id: BrewId) -> Result<Vec<BrewShare>, RepositoryError>;
+    async fn revoke(&self, id: BrewShareId) -> Result<BrewShare, RepositoryError>;
+}
+
+#[async_trait]
+pub trait CommentRepository: Send + Sync {
+    async fn insert(&self, comment: NewComment) -> Result<Comment, RepositoryError>;
+    /// The thread on a brew or roast, oldest first.
+    async fn list_for(&self, target: CommentTarget) -> Result<Vec<Comment>, RepositoryError>;
+    /// Replace the body of a comment. Only its author may edit it; other
+    /// users get `NotFound`.
+    async fn update(
+        &self,
+        id: CommentId,
+        user_id: UserId,
+        body: String,
+    ) -> Result<Comment, RepositoryError>;
+    /// Delete a comment. Only its author may delete it; other users get
+    /// `NotFound`.
+    async fn delete(&self, id: CommentId, user_id: UserId) -> Result<(), RepositoryError>;
+}
+
+#[async_trait]
+pub trait AliasRepository: Send + Sync {
+    /// Rejects a name the roaster or roast already has as an alias.
+    async fn insert(&self, alias: NewAlias) -> Result<Alias, RepositoryError>;
+    /// A roaster's or roast's aliases, oldest first.
+    async fn list_for(&self, target: AliasTarget) -> Result<Vec<Alias>, RepositoryError>;
+    /// Every roaster's and roast's aliases, oldest first.
+    async fn list_all(&self) -> Result<Vec<Alias>, RepositoryError>;
+    async fn delete(&self, id: AliasId) -> Result<(), RepositoryError>;
+    /// The roaster that once went by a name with this slug, if any.
+    async fn find_roaster(&self, slug: &str) -> Result<Option<RoasterId>, RepositoryError>;
+    /// The roast of `roaster_id` that once went by a name with this slug.
+    async fn find_roast(
+        &self,
+        roaster_id: RoasterId,
+        slug: &str,
+    ) -> Result<Option<RoastId>, RepositoryError>;
+    /// Fold `source` into `into`: its roasts and event appearances move
+    /// across, its aliases and name become aliases of `into`, and it is
+    /// deleted. Rejects with `DuplicateSlug` if both have a roast of the same
+    /// name, which need merging first. This is a transactional operation.
+    async fn merge_roasters(
+        &self,
+        source: RoasterId,
+        into: RoasterId,
+    ) -> Result<(), RepositoryError>;
+    /// Fold `source` into `into`: its bags, cups and comments move across,
+    /// its aliases and name become aliases of `into`, and it is deleted. This
+    /// is a transactional operation.
+    async fn merge_roasts(&self, source: RoastId, into: RoastId) -> Result<(), RepositoryError>;
+}
+
 #[async_trait]
 pub trait BrewRepository: Send + Sync {
-    /// Insert a new brew and deduct `coffee_weight` from the bag's remaining amount,
-    /// clamping to zero. Rejects if the bag is closed. This is a transactional operation.
+    /// Insert a new brew and record its `coffee_weight` in the bag's ledger, taking
+    /// no more than the bag has left. Rejects if the bag is closed. This is a
+    /// transactional operation.
     async fn insert(&self, brew: NewBrew) -> Result<Brew, RepositoryError>;
     async fn get(&self, id: BrewId) -> Result<Brew, RepositoryError>;
     async fn get_with_details(&self, id: BrewId) -> Result<BrewWithDetails, RepositoryError>;
@@ -209,7 +579,13 @@ pub trait BrewRepository: Send + Sync {
         search: Option<&str>,
     ) -> Result<Page<BrewWithDetails>, RepositoryError>;
     async fn update(&self, id: BrewId, changes: UpdateBrew) -> Result<Brew, RepositoryError>;
+    async fn set_created_by(&self, id: BrewId, user_id: UserId) -> Result<(), RepositoryError>;
     async fn delete(&self, id: BrewId) -> Result<(), RepositoryError>;
+    /// Aggregate the brews made from any bag of the given roast.
+    async fn roast_stats(&self, roast_id: RoastId) -> Result<RoastBrewStats, RepositoryError>;
+    /// When each bag was last brewed from, and how much each piece of gear
+    /// has been used.
+    async fn usage(&self) -> Result<BrewUsage, RepositoryError>;
 
     async fn list_all(&self) -> Result<Vec<BrewWithDetails>, RepositoryError> {
         let sort_key = <BrewSortKey as SortKey>::default();
@@ -221,7 +597,13 @@ pub trait BrewRepository: Send + Sync {
 
 #[async_trait]
 pub trait CafeRepository: Send + Sync {
-    async fn insert(&self, cafe: NewCafe) -> Result<Cafe, RepositoryError>;
+    async fn insert(&self, cafe: NewCafe) -> Result<Cafe, RepositoryError> {
+        let cafe = cafe.normalize();
+        let slug = cafe.slug();
+        self.insert_with_slug(cafe, slug).await
+    }
+    /// Insert under `slug` rather than the cafe's own, which is taken.
+    async fn insert_with_slug(&self, cafe: NewCafe, slug: String) -> Result<Cafe, RepositoryError>;
     async fn get(&self, id: CafeId) -> Result<Cafe, RepositoryError>;
     async fn get_by_slug(&self, slug: &str) -> Result<Cafe, RepositoryError>;
     async fn list(
@@ -230,7 +612,10 @@ pub trait CafeRepository: Send + Sync {
         search: Option<&str>,
     ) -> Result<Page<Cafe>, RepositoryError>;
     async fn update(&self, id: CafeId, changes: UpdateCafe) -> Result<Cafe, RepositoryError>;
+    async fn set_created_by(&self, id: CafeId, user_id: UserId) -> Result<(), RepositoryError>;
+    async fn set_favourite(&self, id: CafeId, favourite: bool) -> Result<(), RepositoryError>;
     async fn delete(&self, id: CafeId) -> Result<(), RepositoryError>;
+    async fn delete_preview(&self, id: CafeId) -> Result<DeletePreview, RepositoryError>;
 
     async fn list_all(&self) -> Result<Vec<Cafe>, RepositoryError> {
         let sort_key = <CafeSortKey as SortKey>::default();
@@ -248,6 +633,14 @@ pub trait CafeRepository: Send + Sync {
         let page = self.list(&request, None).await?;
         Ok(page.items)
     }
+
+    /// Starred cafes, by name.
+    async fn list_favourites(&self) -> Result<Vec<Cafe>, RepositoryError> {
+        let cafes = self
+            .list_all_sorted(CafeSortKey::Name, SortDirection::Asc)
+            .await?;
+        Ok(cafes.into_iter().filter(|c| c.favourite).collect())
+    }
 }
 
 #[async_trait]
@@ -262,6 +655,7 @@ pub trait CupRepository: Send + Sync {
         search: Option<&str>,
     ) -> Result<Page<CupWithDetails>, RepositoryError>;
     async fn update(&self, id: CupId, changes: UpdateCup) -> Result<Cup, RepositoryError>;
+    async fn set_created_by(&self, id: CupId, user_id: UserId) -> Result<(), RepositoryError>;
     async fn delete(&self, id: CupId) -> Result<(), RepositoryError>;
 
     async fn list_all(&self) -> Result<Vec<CupWithDetails>, RepositoryError> {
@@ -303,17 +697,34 @@ pub trait RegistrationTokenRepository: Send + Sync {
         &self,
         token_hash: &str,
     ) -> Result<RegistrationToken, RepositoryError>;
+    /// Every token, newest first, including used, expired and revoked ones.
+    async fn list(&self) -> Result<Vec<RegistrationToken>, RepositoryError>;
+    /// Count one use against the token. Fails with a conflict if it is
+    /// revoked or has no uses left.
+    async fn claim_use(&self, id: RegistrationTokenId) -> Result<(), RepositoryError>;
+    /// Record the user created by the most recent use.
     async fn mark_used(
         &self,
         id: RegistrationTokenId,
         user_id: UserId,
     ) -> Result<(), RepositoryError>;
+    async fn revoke(&self, id: RegistrationTokenId) -> Result<RegistrationToken, RepositoryError>;
 }
 
 #[async_trait]
 pub trait AiUsageRepository: Send + Sync {
     async fn insert(&self, usage: NewAiUsage) -> Result<AiUsage, RepositoryError>;
     async fn summary_for_user(&self, user_id: UserId) -> Result<AiUsageSummary, RepositoryError>;
+    /// Usage per month and model, newest month first.
+    async fn monthly_for_user(&self, user_id: UserId)
+    -> Result<Vec<AiUsageMonth>, RepositoryError>;
+    async fn list_prices(&self) -> Result<Vec<AiModelPrice>, RepositoryError>;
+    async fn set_price(
+        &self,
+        model: &str,
+        price: &SetAiModelPrice,
+    ) -> Result<AiModelPrice, RepositoryError>;
+    async fn delete_price(&self, model: &str) -> Result<(), RepositoryError>;
 }
 
 #[async_trait]
@@ -335,6 +746,13 @@ pub trait ImageRepository: Send + Sync {
         entity_type: EntityType,
         entity_id: i64,
     ) -> Result<bool, RepositoryError>;
+    /// Which of `entity_ids` have an image, in one query, so lists can show
+    /// thumbnails without a lookup per row.
+    async fn ids_with_images(
+        &self,
+        entity_type: EntityType,
+        entity_ids: &[i64],
+    ) -> Result<HashSet<i64>, RepositoryError>;
 }
 
 #[async_trait]
@@ -353,6 +771,17 @@ pub trait StatsRepository: Send + Sync {
         &self,
     ) -> Result<crate::domain::stats::BrewingSummaryStats, RepositoryError>;
     async fn entity_counts(&self) -> Result<crate::domain::stats::EntityCounts, RepositoryError>;
+    /// Tasting notes and processes of the coffee brewed during `year`.
+    async fn tasting_summary(
+        &self,
+        year: i32,
+    ) -> Result<crate::domain::stats::TastingStats, RepositoryError>;
+    /// Brews per weekday and per hour of the day, across all time.
+    async fn brewing_habits(&self) -> Result<crate::domain::stats::BrewingHabits, RepositoryError>;
+    /// Roasters with at least one bag, most grams brewed first.
+    async fn roaster_leaderboard(
+        &self,
+    ) -> Result<Vec<crate::domain::stats::RoasterLeaderboardEntry>, RepositoryError>;
     async fn get_cached(
         &self,
     ) -> Result<Option<crate::domain::stats::CachedStats>, RepositoryError>;
@@ -360,4 +789,14 @@ pub trait StatsRepository: Send + Sync {
         &self,
         stats: &crate::domain::stats::CachedStats,
     ) -> Result<(), RepositoryError>;
+    /// Brewing over `period`, ending on `today`.
+    async fn period_summary(
+        &self,
+        period: crate::domain::stats::StatsPeriod,
+        today: chrono::NaiveDate,
+    ) -> Result<crate::domain::stats::PeriodSummary, RepositoryError>;
+    /// Re-average every roast's stored rating from its rated brews and cups.
+    async fn refresh_roast_ratings(&self) -> Result<(), RepositoryError>;
+    /// What priced bags cost, converted into the base currency.
+    async fn spend_summary(&self) -> Result<crate::domain::stats::SpendStats, RepositoryError>;
 }
@@ -0,0 +1,171 @@
+use serde::{Deserialize, Serialize};
+
+use crate::domain::bags::{DUST_GRAMS, LOW_STOCK_GRAMS};
+use crate::domain::currencies::validate_currency;
+use crate::domain::map_tiles::validate_tile_url;
+use crate::domain::slugs::SlugStrategy;
+
+/// Upper bound for low-stock thresholds, in grams; well above any retail bag.
+pub const MAX_LOW_STOCK_THRESHOLD: f64 = 5000.0;
+
+/// Upper bound for the dust threshold, in grams; more than a dose or two is
+/// not dust.
+pub const MAX_DUST_THRESHOLD: f64 = 50.0;
+
+/// Instance-wide preferences shared by everyone using this Brewlog.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Settings {
+    /// Open bags with less than this many grams left are due a reorder,
+    /// unless the bag sets its own threshold.
+    pub low_stock_threshold: f64,
+    /// A brew that leaves less than this many grams in its bag prompts the
+    /// brewer to mark the bag finished.
+    pub dust_threshold: f64,
+    /// The currency spend is totalled in. Prices paid in any other currency
+    /// are converted with the exchange rates; unset means prices are summed
+    /// as entered.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_currency: Option<String>,
+    /// A slippy-map tile URL template. When set, cafe maps and location
+    /// pickers draw street maps from it instead of the world outline.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub map_tile_url: Option<String>,
+    /// Credit shown on tile maps, as most tile servers require.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub map_attribution: Option<String>,
+    /// How a new roaster, roast or cafe whose slug is taken is told apart.
+    #[serde(default)]
+    pub slug_strategy: SlugStrategy,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            low_stock_threshold: LOW_STOCK_GRAMS,
+            dust_threshold: DUST_GRAMS,
+            base_currency: None,
+            map_tile_url: None,
+            map_attribution: None,
+            slug_strategy: SlugStrategy::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UpdateSettings {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub low_stock_threshold: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dust_threshold: Option<f64>,
+    /// An empty string clears the base currency.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_currency: Option<String>,
+    /// An empty string clears the tile URL, falling back to the world map.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub map_tile_url: Option<String>,
+    /// An empty string clears the attribution.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub map_attribution: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slug_strategy: Option<SlugStrategy>,
+}
+
+impl UpdateSettings {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.low_stock_threshold.is_none()
+            && self.dust_threshold.is_none()
+            && self.base_currency.is_none()
+            && self.map_tile_url.is_none()
+            && self.map_attribution.is_none()
+            && self.slug_strategy.is_none()
+        {
+            return Err("no changes provided".to_string());
+        }
+        self.low_stock_threshold
+            .map_or(Ok(()), validate_low_stock_threshold)?;
+        self.dust_threshold
+            .map_or(Ok(()), validate_dust_threshold)?;
+        match self.base_currency.as_deref() {
+            Some(code) if !code.is_empty() => validate_currency(code)?,
+            _ => {}
+        }
+        match self.map_tile_url.as_deref() {
+            Some(url) if !url.is_empty() => validate_tile_url(url),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Shared by the instance setting and per-bag overrides. Zero is allowed and
+/// means "never flag".
+pub fn validate_low_stock_threshold(grams: f64) -> Result<(), String> {
+    if !grams.is_finite() || !(0.0..=MAX_LOW_STOCK_THRESHOLD).contains(&grams) {
+        return Err(format!(
+            "low-stock threshold must be between 0 and {MAX_LOW_STOCK_THRESHOLD}g"
+        ));
+    }
+    Ok(())
+}
+
+/// Zero turns the finish prompt off.
+pub fn validate_dust_threshold(grams: f64) -> Result<(), String> {
+    if !grams.is_finite() || !(0.0..=MAX_DUST_THRESHOLD).contains(&grams) {
+        return Err(format!(
+            "dust threshold must be between 0 and {MAX_DUST_THRESHOLD}g"
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults_to_the_built_in_thresholds() {
+        let settings = Settings::default();
+        assert_eq!(settings.low_stock_threshold, LOW_STOCK_GRAMS);
+        assert_eq!(settings.dust_threshold, DUST_GRAMS);
+    }
+
+    #[test]
+    fn thresholds_must_be_in_range() {
+        assert!(validate_low_stock_threshold(0.0).is_ok());
+        assert!(validate_low_stock_threshold(60.0).is_ok());
+        assert!(validate_low_stock_threshold(-1.0).is_err());
+        assert!(validate_low_stock_threshold(f64::NAN).is_err());
+        assert!(validate_low_stock_threshold(MAX_LOW_STOCK_THRESHOLD + 1.0).is_err());
+        assert!(validate_dust_threshold(0.0).is_ok());
+        assert!(validate_dust_threshold(MAX_DUST_THRESHOLD + 1.0).is_err());
+    }
+
+    #[test]
+    fn empty_updates_are_rejected() {
+        assert!(UpdateSettings::default().validate().is_err());
+        let update = UpdateSettings {
+            low_stock_threshold: Some(75.0),
+            ..Default::default()
+        };
+        assert!(update.validate().is_ok());
+        let update = UpdateSettings {
+            dust_threshold: Some(-1.0),
+            ..Default::default()
+        };
+        assert!(update.validate().is_err());
+        let update = UpdateSettings {
+            base_currency: Some("euros".to_string()),
+            ..Default::default()
+        };
+        assert!(update.validate().is_err());
+        let update = UpdateSettings {
+            map_tile_url: Some("https://tiles.local/{z}/{x}.png".to_string()),
+            ..Default::default()
+        };
+        assert!(update.validate().is_err());
+        let update = UpdateSettings {
+            map_tile_url: Some(String::new()),
+            ..Default::default()
+        };
+        assert!(update.validate().is_ok());
+    }
+}
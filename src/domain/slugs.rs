@@ -0,0 +1,196 @@
+use serde::{Deserialize, Serialize};
+
+/// Numeric suffixes tried before giving up on a taken slug.
+pub const MAX_SLUG_SUFFIX: u32 = 50;
+
+/// How a new roaster, roast or cafe is told apart from one that already has
+/// its slug, e.g. a second "Ethiopia Natural" from the same roaster.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SlugStrategy {
+    /// Turn the newcomer away as a duplicate.
+    #[default]
+    Refuse,
+    /// `ethiopia-natural-2025`: the harvest year for roasts, otherwise the
+    /// year it was added.
+    AppendYear,
+    /// `ethiopia-natural-square-mile`. Only roasts have a roaster; roasters
+    /// and cafes get a numeric suffix.
+    AppendRoaster,
+    /// `ethiopia-natural-2`, `ethiopia-natural-3`, …
+    NumericSuffix,
+}
+
+impl SlugStrategy {
+    pub const ALL: [Self; 4] = [
+        Self::Refuse,
+        Self::AppendYear,
+        Self::AppendRoaster,
+        Self::NumericSuffix,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Refuse => "refuse",
+            Self::AppendYear => "append_year",
+            Self::AppendRoaster => "append_roaster",
+            Self::NumericSuffix => "numeric_suffix",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Refuse => "Refuse duplicates",
+            Self::AppendYear => "Append the year",
+            Self::AppendRoaster => "Append the roaster",
+            Self::NumericSuffix => "Append a number",
+        }
+    }
+
+    /// Slugs to try, in order, once `base` turns out to be taken. A qualified
+    /// slug can be taken too, so every strategy but [`Self::Refuse`] ends in
+    /// numbered variants of it.
+    pub fn candidates(self, base: &str, qualifiers: &SlugQualifiers) -> Vec<String> {
+        let stem = match self {
+            Self::Refuse => return Vec::new(),
+            Self::AppendYear => qualifiers.year.map(|year| format!("{base}-{year}")),
+            Self::AppendRoaster => qualifiers
+                .roaster
+                .as_deref()
+                .map(|roaster| slug::slugify(format!("{base} {roaster}")))
+                .filter(|stem| stem != base),
+            Self::NumericSuffix => None,
+        };
+        let numbered = stem.as_deref().unwrap_or(base).to_string();
+        stem.into_iter()
+            .chain((2..=MAX_SLUG_SUFFIX).map(|n| format!("{numbered}-{n}")))
+            .collect()
+    }
+}
+
+impl std::str::FromStr for SlugStrategy {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|strategy| strategy.as_str() == value)
+            .ok_or_else(|| format!("unknown slug strategy: {value}"))
+    }
+}
+
+/// What the strategies can add to a slug. Missing qualifiers fall back to a
+/// numeric suffix.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SlugQualifiers {
+    /// `None` when the slug already carries the year.
+    pub year: Option<i32>,
+    pub roaster: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn qualifiers(year: Option<i32>, roaster: Option<&str>) -> SlugQualifiers {
+        SlugQualifiers {
+            year,
+            roaster: roaster.map(str::to_string),
+        }
+    }
+
+    /// The first candidate that isn't already taken.
+    fn resolve(
+        strategy: SlugStrategy,
+        base: &str,
+        q: &SlugQualifiers,
+        taken: &[&str],
+    ) -> Option<String> {
+        strategy
+            .candidates(base, q)
+            .into_iter()
+            .find(|slug| !taken.contains(&slug.as_str()))
+    }
+
+    #[test]
+    fn refusing_offers_nothing() {
+        let q = qualifiers(Some(2025), Some("Square Mile"));
+        assert!(SlugStrategy::Refuse.candidates("konga", &q).is_empty());
+    }
+
+    #[test]
+    fn appends_the_year() {
+        let q = qualifiers(Some(2025), None);
+        assert_eq!(
+            resolve(
+                SlugStrategy::AppendYear,
+                "ethiopia-natural",
+                &q,
+                &["ethiopia-natural"]
+            ),
+            Some("ethiopia-natural-2025".to_string())
+        );
+        assert_eq!(
+            resolve(
+                SlugStrategy::AppendYear,
+                "ethiopia-natural",
+                &q,
+                &["ethiopia-natural", "ethiopia-natural-2025"]
+            ),
+            Some("ethiopia-natural-2025-2".to_string())
+        );
+    }
+
+    #[test]
+    fn appends_the_roaster() {
+        let q = qualifiers(None, Some("Square Mile"));
+        assert_eq!(
+            resolve(SlugStrategy::AppendRoaster, "red-brick", &q, &["red-brick"]),
+            Some("red-brick-square-mile".to_string())
+        );
+    }
+
+    #[test]
+    fn numbers_from_two() {
+        let q = qualifiers(Some(2025), Some("Square Mile"));
+        assert_eq!(
+            resolve(
+                SlugStrategy::NumericSuffix,
+                "konga",
+                &q,
+                &["konga", "konga-2"]
+            ),
+            Some("konga-3".to_string())
+        );
+    }
+
+    #[test]
+    fn missing_qualifiers_fall_back_to_numbers() {
+        let q = qualifiers(None, None);
+        for strategy in [SlugStrategy::AppendYear, SlugStrategy::AppendRoaster] {
+            assert_eq!(
+                strategy
+                    .candidates("prufrock-london", &q)
+                    .first()
+                    .map(String::as_str),
+                Some("prufrock-london-2")
+            );
+        }
+    }
+
+    #[test]
+    fn gives_up_eventually() {
+        let candidates =
+            SlugStrategy::NumericSuffix.candidates("konga", &SlugQualifiers::default());
+        assert_eq!(candidates.len(), MAX_SLUG_SUFFIX as usize - 1);
+        assert_eq!(candidates.last().map(String::as_str), Some("konga-50"));
+    }
+
+    #[test]
+    fn round_trips_through_strings() {
+        for strategy in SlugStrategy::ALL {
+            assert_eq!(strategy.as_str().parse::<SlugStrategy>(), Ok(strategy));
+        }
+        assert!("append_month".parse::<SlugStrategy>().is_err());
+    }
+}
@@ -0,0 +1,92 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+/// Longest tenant name; a tenant's name is also a DNS label.
+pub const MAX_TENANT_NAME_LEN: usize = 63;
+
+/// The name of a tenant on a multi-tenant server. Used as its subdomain, its
+/// path prefix and its database file name, so it is restricted to lowercase
+/// letters, digits and inner hyphens.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct TenantName(String);
+
+impl TenantName {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl FromStr for TenantName {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        if name.is_empty() || name.len() > MAX_TENANT_NAME_LEN {
+            return Err(format!(
+                "tenant names must be 1 to {MAX_TENANT_NAME_LEN} characters"
+            ));
+        }
+        if !name
+            .bytes()
+            .all(|byte| byte.is_ascii_lowercase() || byte.is_ascii_digit() || byte == b'-')
+        {
+            return Err(
+                "tenant names may only contain lowercase letters, digits and hyphens".to_string(),
+            );
+        }
+        if name.starts_with('-') || name.ends_with('-') {
+            return Err("tenant names can't start or end with a hyphen".to_string());
+        }
+        Ok(Self(name.to_string()))
+    }
+}
+
+impl TryFrom<String> for TenantName {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<TenantName> for String {
+    fn from(value: TenantName) -> Self {
+        value.0
+    }
+}
+
+impl fmt::Display for TenantName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_dns_labels() {
+        for name in ["alice", "team-2", "a", &"x".repeat(MAX_TENANT_NAME_LEN)] {
+            assert!(name.parse::<TenantName>().is_ok(), "{name}");
+        }
+    }
+
+    #[test]
+    fn rejects_names_unfit_for_hosts_and_files() {
+        for name in [
+            "",
+            "Alice",
+            "-alice",
+            "alice-",
+            "al.ice",
+            "../alice",
+            "al ice",
+            &"x".repeat(MAX_TENANT_NAME_LEN + 1),
+        ] {
+            assert!(name.parse::<TenantName>().is_err(), "{name}");
+        }
+    }
+}
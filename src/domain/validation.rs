@@ -0,0 +1,130 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// A rule that one field of a submission breaks.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+}
+
+/// Every rule a submission breaks, so a client can point at each bad field
+/// at once rather than fixing them one request at a time.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValidationErrors {
+    errors: Vec<FieldError>,
+}
+
+impl ValidationErrors {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, field: &str, message: impl Into<String>) {
+        self.errors.push(FieldError {
+            field: field.to_string(),
+            message: message.into(),
+        });
+    }
+
+    /// Record `message` against `field` unless `valid` holds.
+    pub fn check(&mut self, valid: bool, field: &str, message: impl Into<String>) {
+        if !valid {
+            self.add(field, message);
+        }
+    }
+
+    /// Record the error from a `Result`-returning check, such as
+    /// `validate_country`.
+    pub fn check_result(&mut self, field: &str, result: Result<(), String>) {
+        if let Err(message) = result {
+            self.add(field, message);
+        }
+    }
+
+    /// Record `field` as required when `value` is blank.
+    pub fn require(&mut self, field: &str, value: &str) {
+        self.check(
+            !value.trim().is_empty(),
+            field,
+            format!("{field} is required"),
+        );
+    }
+
+    /// Like [`require`](Self::require), but only when an update sets the
+    /// field.
+    pub fn require_if_set(&mut self, field: &str, value: Option<&str>) {
+        if let Some(value) = value {
+            self.check(
+                !value.trim().is_empty(),
+                field,
+                format!("{field} cannot be empty"),
+            );
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    pub fn errors(&self) -> &[FieldError] {
+        &self.errors
+    }
+
+    pub fn into_errors(self) -> Vec<FieldError> {
+        self.errors
+    }
+
+    pub fn into_result(self) -> Result<(), Self> {
+        if self.is_empty() { Ok(()) } else { Err(self) }
+    }
+}
+
+impl fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let messages: Vec<&str> = self.errors.iter().map(|e| e.message.as_str()).collect();
+        f.write_str(&messages.join("; "))
+    }
+}
+
+impl std::error::Error for ValidationErrors {}
+
+/// Field-level checks for the `New*`/`Update*` types, run by the API before
+/// anything is stored and by the CLI before anything is sent. Checks that
+/// need the database, such as whether a referenced bag exists, stay in the
+/// services and repositories.
+pub trait Validate {
+    fn validate(&self) -> Result<(), ValidationErrors>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collects_every_failed_check() {
+        let mut errors = ValidationErrors::new();
+        errors.require("name", "  ");
+        errors.check(true, "amount", "amount must be positive");
+        errors.check(false, "amount", "amount must be positive");
+
+        let err = errors.into_result().unwrap_err();
+        assert_eq!(
+            err.errors()
+                .iter()
+                .map(|e| e.field.as_str())
+                .collect::<Vec<_>>(),
+            ["name", "amount"]
+        );
+        assert_eq!(err.to_string(), "name is required; amount must be positive");
+    }
+
+    #[test]
+    fn no_errors_is_ok() {
+        let mut errors = ValidationErrors::new();
+        errors.require_if_set("name", None);
+        errors.require_if_set("city", Some("Leeds"));
+        assert!(errors.into_result().is_ok());
+    }
+}
@@ -1,12 +1,22 @@
-use std::time::Duration;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
 
+use chrono::NaiveDate;
+use reqwest::StatusCode;
 use serde::{Deserialize, Serialize};
+use tracing::warn;
 
 use crate::application::errors::AppError;
+use crate::domain::ErrorCode;
+use crate::domain::blends::BlendComponent;
 
 pub const OPENROUTER_URL: &str = "https://openrouter.ai/api/v1/chat/completions";
 const USER_AGENT: &str = "Brewlog/1.0";
-const REQUEST_TIMEOUT: Duration = Duration::from_secs(90);
+
+/// What clients see when extraction can't reach the model; forms fall back
+/// to manual entry.
+pub const AI_UNAVAILABLE_MESSAGE: &str = "AI is unavailable right now. Enter the details manually.";
 
 const ROASTER_PROMPT: &str = r#"Extract coffee roaster information from this input. Use web search to look up any details you cannot determine from the input alone (e.g. the roaster's website, location, or background). Return a JSON object with these fields (only include fields you can identify with confidence):
 - "name": the roaster's name
@@ -24,6 +34,7 @@ const ROAST_PROMPT: &str = r#"Extract coffee roast information from this input.
 - "producer": the farm, estate, or cooperative that produced the beans
 - "process": the processing method (e.g. Washed, Natural, Honey, Anaerobic)
 - "tasting_notes": an array of flavour/tasting notes in Title Case (e.g. ["Blueberry", "Jasmine", "Dark Chocolate"])
+- "components": only for a blend of several origins, an array with one object per origin country giving its "origin" and, if stated, its "percentage" of the blend as a number (e.g. [{"origin": "Brazil", "percentage": 60}, {"origin": "Ethiopia", "percentage": 40}])
 
 Return ONLY the JSON object, no other text."#;
 
@@ -42,14 +53,231 @@ const SCAN_PROMPT: &str = r#"Extract both the coffee roaster and the roast infor
     "region": "the region within the origin country",
     "producer": "the farm, estate, or cooperative",
     "process": "processing method (e.g. Washed, Natural, Honey, Anaerobic)",
-    "tasting_notes": ["Array", "Of", "Flavour Notes In Title Case"]
+    "tasting_notes": ["Array", "Of", "Flavour Notes In Title Case"],
+    "components": [{"origin": "for a blend only, one entry per origin country", "percentage": 60}]
+  },
+  "bag": {
+    "best_before": "the best-before date printed on this bag, as YYYY-MM-DD",
+    "batch_code": "the roaster's batch or lot code printed on this bag"
   }
 }
 
-Only include fields you can identify with confidence. Each tasting note must be in Title Case. Return ONLY the JSON object, no other text."#;
+Only include fields you can identify with confidence. Leave out "components" for a single-origin coffee, and leave out a component's "percentage" unless it is stated. Only fill in "bag" from what is printed on the bag itself; never look it up. Each tasting note must be in Title Case. Return ONLY the JSON object, no other text."#;
+
+const BREW_PROMPT: &str = r#"Read this description of a coffee brew. It may be followed by lists of the coffees and gear the user has; when the description refers to one of them, give its name exactly as listed. Return a JSON object with these fields (only include fields the description gives):
+- "coffee_weight": grams of ground coffee
+- "water_volume": grams or millilitres of water
+- "water_temp": water temperature in Celsius
+- "grind_setting": the grinder setting, as a number
+- "brew_time": total brew time in seconds
+- "bag": the coffee used
+- "grinder": the grinder used
+- "brewer": the brewer used
+- "filter_paper": the filter paper used
+
+Return ONLY the JSON object, no other text."#;
+
+// --- Resilience ---
+
+/// How hard to try `OpenRouter` before giving up, and when to stop trying for
+/// a while.
+#[derive(Debug, Clone, Copy)]
+pub struct AiPolicy {
+    /// Tries per extraction, including the first.
+    pub attempts: u32,
+    /// Limit on each try. Web search makes slow answers normal.
+    pub attempt_timeout: Duration,
+    /// Wait before the first retry, doubling after each one.
+    pub backoff: Duration,
+    /// Consecutive failed extractions that open the circuit.
+    pub failure_threshold: u32,
+    /// How long an open circuit refuses calls before letting one through.
+    pub cool_down: Duration,
+}
+
+impl Default for AiPolicy {
+    fn default() -> Self {
+        Self {
+            attempts: 3,
+            attempt_timeout: Duration::from_mins(1),
+            backoff: Duration::from_secs(1),
+            failure_threshold: 5,
+            cool_down: Duration::from_mins(1),
+        }
+    }
+}
+
+/// Counts of `OpenRouter` extractions since the server started.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AiMetrics {
+    pub calls: u64,
+    /// Extractions that failed after every retry.
+    pub failures: u64,
+    pub retries: u64,
+    /// Extractions refused without a call because the circuit was open.
+    pub rejected: u64,
+    pub circuit_open: bool,
+}
+
+impl AiMetrics {
+    /// Share of extractions that ended unavailable, refused ones included.
+    pub fn failure_rate(&self) -> f64 {
+        if self.calls == 0 {
+            return 0.0;
+        }
+        #[allow(clippy::cast_precision_loss)]
+        let rate = (self.failures + self.rejected) as f64 / self.calls as f64;
+        rate
+    }
+}
+
+#[derive(Debug, Default)]
+struct Breaker {
+    consecutive_failures: u32,
+    open_until: Option<Instant>,
+}
+
+/// Retries transient `OpenRouter` failures and opens a circuit breaker after
+/// repeated ones, so an outage fails fast instead of hanging every scan.
+#[derive(Debug)]
+pub struct AiGuard {
+    policy: AiPolicy,
+    breaker: Mutex<Breaker>,
+    calls: AtomicU64,
+    failures: AtomicU64,
+    retries: AtomicU64,
+    rejected: AtomicU64,
+}
+
+impl Default for AiGuard {
+    fn default() -> Self {
+        Self::new(AiPolicy::default())
+    }
+}
+
+impl AiGuard {
+    pub fn new(policy: AiPolicy) -> Self {
+        Self {
+            policy,
+            breaker: Mutex::new(Breaker::default()),
+            calls: AtomicU64::new(0),
+            failures: AtomicU64::new(0),
+            retries: AtomicU64::new(0),
+            rejected: AtomicU64::new(0),
+        }
+    }
+
+    pub fn metrics(&self) -> AiMetrics {
+        AiMetrics {
+            calls: self.calls.load(Ordering::Relaxed),
+            failures: self.failures.load(Ordering::Relaxed),
+            retries: self.retries.load(Ordering::Relaxed),
+            rejected: self.rejected.load(Ordering::Relaxed),
+            circuit_open: self.is_open(Instant::now()),
+        }
+    }
+
+    fn breaker(&self) -> std::sync::MutexGuard<'_, Breaker> {
+        self.breaker
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    fn is_open(&self, now: Instant) -> bool {
+        self.breaker().open_until.is_some_and(|until| now < until)
+    }
+
+    fn succeeded(&self) {
+        *self.breaker() = Breaker::default();
+    }
+
+    fn failed(&self) {
+        self.failures.fetch_add(1, Ordering::Relaxed);
+        let mut breaker = self.breaker();
+        breaker.consecutive_failures += 1;
+        // Once open, a single failed trial after the cool-down reopens it.
+        if breaker.consecutive_failures >= self.policy.failure_threshold {
+            breaker.open_until = Some(Instant::now() + self.policy.cool_down);
+            warn!(
+                failures = breaker.consecutive_failures,
+                cool_down_secs = self.policy.cool_down.as_secs(),
+                "AI circuit breaker opened"
+            );
+        }
+    }
+
+    /// Run `attempt` under the policy: refuse while the circuit is open,
+    /// retry transient failures with backoff, and report anything else that
+    /// stops `OpenRouter` answering as [`ErrorCode::AiUnavailable`].
+    async fn run<F, Fut>(&self, mut attempt: F) -> Result<reqwest::Response, AppError>
+    where
+        F: FnMut(Duration) -> Fut,
+        Fut: Future<Output = Result<reqwest::Response, reqwest::Error>>,
+    {
+        self.calls.fetch_add(1, Ordering::Relaxed);
+        if self.is_open(Instant::now()) {
+            self.rejected.fetch_add(1, Ordering::Relaxed);
+            warn!("AI extraction refused while the circuit is open");
+            return Err(unavailable());
+        }
+
+        let mut delay = self.policy.backoff;
+        for tries in 1..=self.policy.attempts.max(1) {
+            let failure = match attempt(self.policy.attempt_timeout).await {
+                Ok(response) if response.status().is_success() => {
+                    self.succeeded();
+                    return Ok(response);
+                }
+                Ok(response) => {
+                    let status = response.status();
+                    let body = response
+                        .text()
+                        .await
+                        .unwrap_or_else(|_| "(unreadable body)".to_string());
+                    warn!(%status, body, tries, "OpenRouter returned an error");
+                    is_transient_status(status)
+                }
+                Err(err) => {
+                    warn!(error = %err, tries, "OpenRouter request failed");
+                    err.is_timeout() || err.is_connect() || err.is_request()
+                }
+            };
+            if !failure || tries == self.policy.attempts {
+                break;
+            }
+            self.retries.fetch_add(1, Ordering::Relaxed);
+            tokio::time::sleep(delay).await;
+            delay *= 2;
+        }
+
+        self.failed();
+        Err(unavailable())
+    }
+}
+
+/// Rate limits and server errors are worth another try; other statuses
+/// (a bad key, an unknown model) won't change by retrying.
+fn is_transient_status(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS
+        || status == StatusCode::REQUEST_TIMEOUT
+        || status.is_server_error()
+}
+
+fn unavailable() -> AppError {
+    AppError::Unavailable(ErrorCode::AiUnavailable, AI_UNAVAILABLE_MESSAGE.to_string())
+}
 
 // --- Public types ---
 
+/// Where and how to reach `OpenRouter` for an extraction.
+pub struct OpenRouter<'a> {
+    pub client: &'a reqwest::Client,
+    pub guard: &'a AiGuard,
+    pub url: &'a str,
+    pub api_key: &'a str,
+    pub model: &'a str,
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct Usage {
     pub prompt_tokens: i64,
@@ -81,25 +309,47 @@ pub struct ExtractedRoast {
     pub producer: Option<String>,
     pub process: Option<String>,
     pub tasting_notes: Option<Vec<String>>,
+    /// The origins of a blend, with their shares when the roaster gives them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub components: Option<Vec<BlendComponent>>,
+}
+
+/// Details read off the label of the bag in hand.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExtractedBag {
+    #[serde(default, deserialize_with = "lenient_date")]
+    pub best_before: Option<NaiveDate>,
+    pub batch_code: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExtractedBagScan {
     pub roaster: ExtractedRoaster,
     pub roast: ExtractedRoast,
+    #[serde(default)]
+    pub bag: ExtractedBag,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExtractedBrew {
+    pub coffee_weight: Option<f64>,
+    pub water_volume: Option<f64>,
+    pub water_temp: Option<f64>,
+    pub grind_setting: Option<f64>,
+    pub brew_time: Option<f64>,
+    pub bag: Option<String>,
+    pub grinder: Option<String>,
+    pub brewer: Option<String>,
+    pub filter_paper: Option<String>,
 }
 
 // --- Public functions ---
 
 pub async fn extract_roaster(
-    client: &reqwest::Client,
-    url: &str,
-    api_key: &str,
-    model: &str,
+    openrouter: &OpenRouter<'_>,
     input: &ExtractionInput,
 ) -> Result<(ExtractedRoaster, Option<Usage>), AppError> {
-    let (content, usage) =
-        call_openrouter(client, url, api_key, model, ROASTER_PROMPT, input).await?;
+    let (content, usage) = call_openrouter(openrouter, ROASTER_PROMPT, input).await?;
     let json = extract_json(&content);
 
     let extracted = serde_json::from_str(json).map_err(|e| {
@@ -109,14 +359,10 @@ pub async fn extract_roaster(
 }
 
 pub async fn extract_roast(
-    client: &reqwest::Client,
-    url: &str,
-    api_key: &str,
-    model: &str,
+    openrouter: &OpenRouter<'_>,
     input: &ExtractionInput,
 ) -> Result<(ExtractedRoast, Option<Usage>), AppError> {
-    let (content, usage) =
-        call_openrouter(client, url, api_key, model, ROAST_PROMPT, input).await?;
+    let (content, usage) = call_openrouter(openrouter, ROAST_PROMPT, input).await?;
     let json = extract_json(&content);
 
     let extracted = serde_json::from_str(json).map_err(|e| {
@@ -126,13 +372,10 @@ pub async fn extract_roast(
 }
 
 pub async fn extract_bag_scan(
-    client: &reqwest::Client,
-    url: &str,
-    api_key: &str,
-    model: &str,
+    openrouter: &OpenRouter<'_>,
     input: &ExtractionInput,
 ) -> Result<(ExtractedBagScan, Option<Usage>), AppError> {
-    let (content, usage) = call_openrouter(client, url, api_key, model, SCAN_PROMPT, input).await?;
+    let (content, usage) = call_openrouter(openrouter, SCAN_PROMPT, input).await?;
     let json = extract_json(&content);
 
     let extracted = serde_json::from_str(json).map_err(|e| {
@@ -141,13 +384,35 @@ pub async fn extract_bag_scan(
     Ok((extracted, usage))
 }
 
+pub async fn extract_brew(
+    openrouter: &OpenRouter<'_>,
+    input: &ExtractionInput,
+) -> Result<(ExtractedBrew, Option<Usage>), AppError> {
+    let (content, usage) = call_openrouter(openrouter, BREW_PROMPT, input).await?;
+    let json = extract_json(&content);
+
+    let extracted = serde_json::from_str(json).map_err(|e| {
+        AppError::unexpected(format!("Failed to parse AI response as brew data: {e}"))
+    })?;
+    Ok((extracted, usage))
+}
+
 // --- Internal helpers ---
 
+/// A misread date is dropped rather than failing the whole extraction.
+fn lenient_date<'de, D>(deserializer: D) -> Result<Option<NaiveDate>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let value: Option<serde_json::Value> = Option::deserialize(deserializer)?;
+    Ok(value
+        .as_ref()
+        .and_then(serde_json::Value::as_str)
+        .and_then(|s| NaiveDate::parse_from_str(s.trim(), "%Y-%m-%d").ok()))
+}
+
 async fn call_openrouter(
-    client: &reqwest::Client,
-    url: &str,
-    api_key: &str,
-    model: &str,
+    openrouter: &OpenRouter<'_>,
     system_prompt: &str,
     input: &ExtractionInput,
 ) -> Result<(String, Option<Usage>), AppError> {
@@ -181,33 +446,26 @@ async fn call_openrouter(
     }
 
     let request_body = ChatRequest {
-        model: model.to_string(),
+        model: openrouter.model.to_string(),
         messages: vec![Message {
             role: "user".to_string(),
             content: content_parts,
         }],
     };
 
-    let response = client
-        .post(url)
-        .header("User-Agent", USER_AGENT)
-        .header("Authorization", format!("Bearer {api_key}"))
-        .timeout(REQUEST_TIMEOUT)
-        .json(&request_body)
-        .send()
-        .await
-        .map_err(|e| AppError::unexpected(format!("OpenRouter request failed: {e}")))?;
-
-    if !response.status().is_success() {
-        let status = response.status();
-        let body = response
-            .text()
-            .await
-            .unwrap_or_else(|_| "(unreadable body)".to_string());
-        return Err(AppError::unexpected(format!(
-            "OpenRouter returned status {status}: {body}"
-        )));
-    }
+    let response = openrouter
+        .guard
+        .run(|timeout| {
+            openrouter
+                .client
+                .post(openrouter.url)
+                .header("User-Agent", USER_AGENT)
+                .header("Authorization", format!("Bearer {}", openrouter.api_key))
+                .timeout(timeout)
+                .json(&request_body)
+                .send()
+        })
+        .await?;
 
     let body = response.text().await.map_err(|e| {
         AppError::unexpected(format!("Failed to read OpenRouter response body: {e}"))
@@ -400,6 +658,49 @@ mod tests {
         assert!(roast.roaster_name.is_none());
         assert!(roast.region.is_none());
         assert!(roast.tasting_notes.is_none());
+        assert!(roast.components.is_none());
+    }
+
+    #[test]
+    fn parse_blend_extraction() {
+        let json = r#"{
+            "name": "Red Brick",
+            "origin": "Brazil, Ethiopia",
+            "components": [
+                {"origin": "Brazil", "percentage": 70},
+                {"origin": "Ethiopia"}
+            ]
+        }"#;
+
+        let roast: ExtractedRoast = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            roast.components.unwrap(),
+            [
+                BlendComponent::new("Brazil", Some(70.0)),
+                BlendComponent::new("Ethiopia", None)
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_bag_label_extraction() {
+        let json = r#"{
+            "roaster": {"name": "Square Mile"},
+            "roast": {"name": "Red Brick"},
+            "bag": {"best_before": "2025-09-30", "batch_code": "RB-2207"}
+        }"#;
+
+        let scan: ExtractedBagScan = serde_json::from_str(json).unwrap();
+        assert_eq!(scan.bag.best_before, NaiveDate::from_ymd_opt(2025, 9, 30));
+        assert_eq!(scan.bag.batch_code.as_deref(), Some("RB-2207"));
+
+        let json = r#"{
+            "roaster": {"name": "Square Mile"},
+            "roast": {"name": "Red Brick"},
+            "bag": {"best_before": "end of September"}
+        }"#;
+        let scan: ExtractedBagScan = serde_json::from_str(json).unwrap();
+        assert!(scan.bag.best_before.is_none());
     }
 
     #[test]
@@ -444,4 +745,41 @@ mod tests {
         let raw = "Here is the data:\n{\"name\": \"Square Mile\"}\nHope that helps!";
         assert_eq!(extract_json(raw), r#"{"name": "Square Mile"}"#);
     }
+
+    #[test]
+    fn failure_rate_counts_refused_extractions() {
+        let metrics = AiMetrics {
+            calls: 8,
+            failures: 1,
+            retries: 3,
+            rejected: 1,
+            circuit_open: false,
+        };
+        assert!((metrics.failure_rate() - 0.25).abs() < f64::EPSILON);
+        assert!(AiMetrics::default().failure_rate().abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn breaker_opens_at_the_threshold_and_success_closes_it() {
+        let guard = AiGuard::new(AiPolicy {
+            failure_threshold: 2,
+            ..AiPolicy::default()
+        });
+        guard.failed();
+        assert!(!guard.metrics().circuit_open);
+        guard.failed();
+        assert!(guard.metrics().circuit_open);
+
+        guard.succeeded();
+        assert!(!guard.metrics().circuit_open);
+        assert_eq!(guard.metrics().failures, 2);
+    }
+
+    #[test]
+    fn only_transient_statuses_are_retried() {
+        assert!(is_transient_status(StatusCode::TOO_MANY_REQUESTS));
+        assert!(is_transient_status(StatusCode::BAD_GATEWAY));
+        assert!(!is_transient_status(StatusCode::UNAUTHORIZED));
+        assert!(!is_transient_status(StatusCode::BAD_REQUEST));
+    }
 }
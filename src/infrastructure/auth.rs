@@ -1,7 +1,29 @@
-use anyhow::Result;
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::{Algorithm, Argon2, Params, Version};
 use base64::{Engine as _, engine::general_purpose};
 use rand::Rng;
+use serde::Deserialize;
 use sha2::{Digest, Sha256};
+use tracing::warn;
+
+/// Number of leading characters of an API token kept in the clear next to a
+/// salted hash, so the row can be found without hashing against every token.
+pub const TOKEN_LOOKUP_PREFIX_LEN: usize = 12;
+
+/// How long a successful Argon2 verification is remembered, so a client
+/// making a burst of API calls pays for the slow hash once.
+pub const VERIFIED_TOKEN_TTL: Duration = Duration::from_mins(5);
+
+/// Upper bound on remembered verifications; expired entries are dropped
+/// first, and the whole map is cleared if that isn't enough.
+const MAX_VERIFIED_TOKENS: usize = 1024;
 
 /// Generates a cryptographically secure random token
 /// Returns a base64-encoded token string
@@ -26,6 +48,267 @@ pub fn generate_session_token() -> String {
     general_purpose::URL_SAFE_NO_PAD.encode(token_bytes)
 }
 
+/// The first characters of a token, used to find its row when the stored
+/// hash is salted.
+pub fn token_lookup_prefix(token: &str) -> &str {
+    token
+        .char_indices()
+        .nth(TOKEN_LOOKUP_PREFIX_LEN)
+        .map_or(token, |(idx, _)| &token[..idx])
+}
+
+/// How long-lived API tokens are hashed at rest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TokenHashScheme {
+    /// Unsalted SHA-256, the original scheme. Looked up by exact hash.
+    Sha256,
+    /// Salted Argon2id, looked up by token prefix and then verified.
+    Argon2,
+}
+
+impl TokenHashScheme {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Sha256 => "sha256",
+            Self::Argon2 => "argon2",
+        }
+    }
+
+    /// Which scheme produced a stored hash. Argon2 hashes are PHC strings;
+    /// anything else is a base64 SHA-256 digest.
+    fn of(stored: &str) -> Self {
+        if stored.starts_with("$argon2") {
+            Self::Argon2
+        } else {
+            Self::Sha256
+        }
+    }
+}
+
+impl fmt::Display for TokenHashScheme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for TokenHashScheme {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "sha256" => Ok(Self::Sha256),
+            "argon2" => Ok(Self::Argon2),
+            other => Err(format!(
+                "unknown token hash scheme '{other}' (expected 'argon2' or 'sha256')"
+            )),
+        }
+    }
+}
+
+/// A token hash ready to be written to the `tokens` table.
+#[derive(Clone)]
+pub struct StoredTokenHash {
+    pub hash: String,
+    /// Set for salted schemes, `None` for SHA-256.
+    pub lookup_prefix: Option<String>,
+}
+
+/// Recently verified tokens, keyed by the token's SHA-256 and holding the
+/// stored hash they were checked against. A token whose stored hash changes
+/// (rehash, rotation) misses and is verified again.
+#[derive(Default)]
+struct VerifiedTokens {
+    entries: Mutex<HashMap<String, (Instant, String)>>,
+}
+
+impl VerifiedTokens {
+    fn contains(&self, key: &str, stored: &str) -> bool {
+        let entries = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+        entries.get(key).is_some_and(|(verified_at, hash)| {
+            hash == stored && verified_at.elapsed() < VERIFIED_TOKEN_TTL
+        })
+    }
+
+    fn insert(&self, key: String, stored: &str) {
+        let mut entries = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+        if entries.len() >= MAX_VERIFIED_TOKENS {
+            entries.retain(|_, (verified_at, _)| verified_at.elapsed() < VERIFIED_TOKEN_TTL);
+            if entries.len() >= MAX_VERIFIED_TOKENS {
+                entries.clear();
+            }
+        }
+        entries.insert(key, (Instant::now(), stored.to_string()));
+    }
+}
+
+impl fmt::Debug for VerifiedTokens {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VerifiedTokens").finish_non_exhaustive()
+    }
+}
+
+/// Hashes and verifies API tokens with the configured scheme.
+///
+/// Verification accepts hashes from any supported scheme so existing tokens
+/// keep working after the scheme or its parameters change. `needs_rehash`
+/// reports stored hashes that should be replaced the next time the token is
+/// presented. Successful Argon2 verifications are remembered for
+/// [`VERIFIED_TOKEN_TTL`], shared between clones.
+#[derive(Debug, Clone)]
+pub struct TokenHasher {
+    scheme: TokenHashScheme,
+    params: Params,
+    verified: Arc<VerifiedTokens>,
+}
+
+impl Default for TokenHasher {
+    fn default() -> Self {
+        Self {
+            scheme: TokenHashScheme::Argon2,
+            params: Params::DEFAULT,
+            verified: Arc::default(),
+        }
+    }
+}
+
+impl TokenHasher {
+    pub fn sha256() -> Self {
+        Self {
+            scheme: TokenHashScheme::Sha256,
+            params: Params::DEFAULT,
+            verified: Arc::default(),
+        }
+    }
+
+    /// Argon2id with the given memory cost (KiB), iteration count and
+    /// parallelism.
+    pub fn argon2(memory_kib: u32, iterations: u32, parallelism: u32) -> Result<Self> {
+        let params = Params::new(memory_kib, iterations, parallelism, None)
+            .map_err(|err| anyhow::anyhow!("invalid Argon2 parameters: {err}"))?;
+        Ok(Self {
+            scheme: TokenHashScheme::Argon2,
+            params,
+            verified: Arc::default(),
+        })
+    }
+
+    pub fn scheme(&self) -> TokenHashScheme {
+        self.scheme
+    }
+
+    fn argon2_context(&self) -> Argon2<'static> {
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, self.params.clone())
+    }
+
+    /// Hash a raw token with the configured scheme.
+    pub fn hash_blocking(&self, token: &str) -> Result<StoredTokenHash> {
+        match self.scheme {
+            TokenHashScheme::Sha256 => Ok(StoredTokenHash {
+                hash: hash_token(token),
+                lookup_prefix: None,
+            }),
+            TokenHashScheme::Argon2 => {
+                let mut salt_bytes = [0u8; 16];
+                rand::rng().fill_bytes(&mut salt_bytes);
+                let salt = SaltString::encode_b64(&salt_bytes)
+                    .map_err(|err| anyhow::anyhow!("failed to encode salt: {err}"))?;
+                let hash = self
+                    .argon2_context()
+                    .hash_password(token.as_bytes(), &salt)
+                    .map_err(|err| anyhow::anyhow!("failed to hash token: {err}"))?;
+                Ok(StoredTokenHash {
+                    hash: hash.to_string(),
+                    lookup_prefix: Some(token_lookup_prefix(token).to_string()),
+                })
+            }
+        }
+    }
+
+    /// Check a raw token against a stored hash of any supported scheme.
+    pub fn verify_blocking(&self, token: &str, stored: &str) -> bool {
+        match TokenHashScheme::of(stored) {
+            TokenHashScheme::Sha256 => hash_token(token) == stored,
+            TokenHashScheme::Argon2 => {
+                let parsed = match PasswordHash::new(stored) {
+                    Ok(parsed) => parsed,
+                    Err(err) => {
+                        warn!(error = %err, "stored Argon2 token hash is malformed");
+                        return false;
+                    }
+                };
+                // Parameters come from the stored hash, not the configured ones
+                self.argon2_context()
+                    .verify_password(token.as_bytes(), &parsed)
+                    .is_ok()
+            }
+        }
+    }
+
+    /// Whether a stored hash was made with a different scheme or different
+    /// Argon2 parameters than the ones configured.
+    pub fn needs_rehash(&self, stored: &str) -> bool {
+        let stored_scheme = TokenHashScheme::of(stored);
+        if stored_scheme != self.scheme {
+            return true;
+        }
+        if stored_scheme == TokenHashScheme::Sha256 {
+            return false;
+        }
+        let Ok(parsed) = PasswordHash::new(stored) else {
+            return true;
+        };
+        match Params::try_from(&parsed) {
+            Ok(params) => {
+                parsed.algorithm != Algorithm::Argon2id.ident()
+                    || params.m_cost() != self.params.m_cost()
+                    || params.t_cost() != self.params.t_cost()
+                    || params.p_cost() != self.params.p_cost()
+            }
+            Err(_) => true,
+        }
+    }
+
+    /// Hash a token on the blocking thread pool; Argon2 is deliberately slow.
+    pub async fn hash(&self, token: &str) -> Result<StoredTokenHash> {
+        let hasher = self.clone();
+        let token = token.to_string();
+        tokio::task::spawn_blocking(move || hasher.hash_blocking(&token))
+            .await
+            .context("token hashing task failed")?
+    }
+
+    /// Verify a token on the blocking thread pool, or from the record of
+    /// recent successful verifications.
+    pub async fn verify(&self, token: &str, stored: &str) -> bool {
+        if TokenHashScheme::of(stored) == TokenHashScheme::Sha256 {
+            return self.verify_blocking(token, stored);
+        }
+        let key = hash_token(token);
+        if self.verified.contains(&key, stored) {
+            return true;
+        }
+        let hasher = self.clone();
+        let token = token.to_string();
+        let owned_stored = stored.to_string();
+        let valid = match tokio::task::spawn_blocking(move || {
+            hasher.verify_blocking(&token, &owned_stored)
+        })
+        .await
+        {
+            Ok(valid) => valid,
+            Err(err) => {
+                warn!(error = %err, "token verification task failed");
+                false
+            }
+        };
+        if valid {
+            self.verified.insert(key, stored);
+        }
+        valid
+    }
+}
+
 #[cfg(test)]
 #[allow(clippy::unwrap_used)]
 mod tests {
@@ -51,4 +334,86 @@ mod tests {
         let hash3 = hash_token("different_token");
         assert_ne!(hash1, hash3);
     }
+
+    fn fast_hasher() -> TokenHasher {
+        TokenHasher::argon2(8, 1, 1).unwrap()
+    }
+
+    #[test]
+    fn argon2_hash_verifies_and_is_salted() {
+        let hasher = fast_hasher();
+        let first = hasher.hash_blocking("test_token_12345").unwrap();
+        let second = hasher.hash_blocking("test_token_12345").unwrap();
+
+        assert!(first.hash.starts_with("$argon2id$"));
+        assert_ne!(first.hash, second.hash);
+        assert_eq!(first.lookup_prefix.as_deref(), Some("test_token_1"));
+        assert!(hasher.verify_blocking("test_token_12345", &first.hash));
+        assert!(!hasher.verify_blocking("different_token", &first.hash));
+    }
+
+    #[tokio::test]
+    async fn successful_verifications_are_remembered_per_stored_hash() {
+        let hasher = fast_hasher();
+        let first = hasher.hash_blocking("test_token_12345").unwrap().hash;
+        let second = hasher.hash_blocking("test_token_12345").unwrap().hash;
+
+        assert!(!hasher.verify("different_token", &first).await);
+        assert!(hasher.verify("test_token_12345", &first).await);
+        // Clones share what has been verified
+        let clone = hasher.clone();
+        assert!(
+            clone
+                .verified
+                .contains(&hash_token("test_token_12345"), &first)
+        );
+        assert!(
+            !hasher
+                .verified
+                .contains(&hash_token("test_token_12345"), &second)
+        );
+        assert!(
+            !hasher
+                .verified
+                .contains(&hash_token("different_token"), &first)
+        );
+    }
+
+    #[test]
+    fn legacy_sha256_hashes_still_verify() {
+        let hasher = fast_hasher();
+        let legacy = hash_token("test_token_12345");
+
+        assert!(hasher.verify_blocking("test_token_12345", &legacy));
+        assert!(!hasher.verify_blocking("different_token", &legacy));
+    }
+
+    #[test]
+    fn rehash_needed_when_scheme_or_params_change() {
+        let hasher = fast_hasher();
+        let current = hasher.hash_blocking("token").unwrap().hash;
+
+        assert!(!hasher.needs_rehash(&current));
+        assert!(hasher.needs_rehash(&hash_token("token")));
+        assert!(
+            TokenHasher::argon2(16, 1, 1)
+                .unwrap()
+                .needs_rehash(&current)
+        );
+        assert!(TokenHasher::sha256().needs_rehash(&current));
+        assert!(!TokenHasher::sha256().needs_rehash(&hash_token("token")));
+    }
+
+    #[test]
+    fn scheme_parses_case_insensitively() {
+        assert_eq!(
+            "Argon2".parse::<TokenHashScheme>(),
+            Ok(TokenHashScheme::Argon2)
+        );
+        assert_eq!(
+            "sha256".parse::<TokenHashScheme>(),
+            Ok(TokenHashScheme::Sha256)
+        );
+        assert!("md5".parse::<TokenHashScheme>().is_err());
+    }
 }
@@ -5,20 +5,39 @@ use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::{from_str, to_string};
 use sqlx::AssertSqlSafe;
+use tracing::info;
 
+use crate::domain::aliases::Alias;
 use crate::domain::bags::Bag;
 use crate::domain::brews::{Brew, QuickNote};
 use crate::domain::cafes::Cafe;
 use crate::domain::cups::Cup;
 use crate::domain::entity_type::EntityType;
-use crate::domain::gear::{Gear, GearCategory};
+use crate::domain::events::Event;
+use crate::domain::gear::{BrewMethod, Gear, GearCategory};
+use crate::domain::green_coffees::GreenCoffee;
 use crate::domain::ids::{
-    BagId, BrewId, CafeId, CupId, GearId, RoastId, RoasterId, TimelineEventId,
+    AliasId, BagId, BrewId, CafeId, CupId, CustomProcessId, CustomQuickNoteId, EventId, GearId,
+    GreenCoffeeId, RoastBatchId, RoastId, RoasterId, TimelineEventId, TripId,
 };
+use crate::domain::processes::CustomProcess;
+use crate::domain::quick_notes::CustomQuickNote;
+use crate::domain::roast_batches::RoastBatch;
 use crate::domain::roasters::Roaster;
 use crate::domain::roasts::Roast;
 use crate::domain::timeline::TimelineEvent;
-use crate::infrastructure::database::{DatabasePool, DatabaseTransaction};
+use crate::domain::trips::Trip;
+use crate::infrastructure::database::{
+    DatabaseConnection, DatabasePool, DatabaseTransaction, parse_id_list,
+};
+use crate::infrastructure::overview::TableCount;
+use crate::infrastructure::repositories::brews::decode_quick_notes;
+
+/// Current backup format version written by [`BackupService::export`].
+pub const BACKUP_VERSION: u32 = 2;
+
+/// Number of records written per transaction during a streaming restore.
+const RESTORE_BATCH_SIZE: usize = 500;
 
 fn decode_json_vec<T: serde::de::DeserializeOwned>(
     raw: Option<String>,
@@ -75,18 +94,249 @@ pub struct BackupData {
     pub created_at: DateTime<Utc>,
     pub roasters: Vec<Roaster>,
     pub gear: Vec<Gear>,
+    #[serde(default)]
+    pub quick_notes: Vec<CustomQuickNote>,
+    #[serde(default)]
+    pub processes: Vec<CustomProcess>,
     pub roasts: Vec<Roast>,
+    #[serde(default)]
+    pub aliases: Vec<Alias>,
     pub bags: Vec<Bag>,
     pub brews: Vec<Brew>,
     #[serde(default)]
     pub cafes: Vec<Cafe>,
     #[serde(default)]
     pub cups: Vec<Cup>,
+    #[serde(default)]
+    pub green_coffees: Vec<GreenCoffee>,
+    #[serde(default)]
+    pub roast_batches: Vec<RoastBatch>,
+    #[serde(default)]
+    pub events: Vec<Event>,
+    #[serde(default)]
+    pub trips: Vec<Trip>,
     pub timeline_events: Vec<TimelineEvent>,
     #[serde(default)]
     pub images: Vec<BackupImage>,
 }
 
+impl BackupData {
+    /// Flatten the backup into a header followed by one record per row, in
+    /// the foreign-key-safe order used by restore.
+    pub fn into_records(self) -> Vec<BackupRecord> {
+        let mut records = vec![BackupRecord::Header {
+            version: self.version,
+            created_at: self.created_at,
+        }];
+        records.extend(self.roasters.into_iter().map(BackupRecord::Roaster));
+        records.extend(self.gear.into_iter().map(BackupRecord::Gear));
+        records.extend(self.quick_notes.into_iter().map(BackupRecord::QuickNote));
+        records.extend(self.processes.into_iter().map(BackupRecord::Process));
+        records.extend(self.roasts.into_iter().map(BackupRecord::Roast));
+        records.extend(self.aliases.into_iter().map(BackupRecord::Alias));
+        records.extend(self.bags.into_iter().map(BackupRecord::Bag));
+        records.extend(self.brews.into_iter().map(BackupRecord::Brew));
+        records.extend(self.cafes.into_iter().map(BackupRecord::Cafe));
+        records.extend(self.cups.into_iter().map(BackupRecord::Cup));
+        records.extend(
+            self.green_coffees
+                .into_iter()
+                .map(BackupRecord::GreenCoffee),
+        );
+        records.extend(self.roast_batches.into_iter().map(BackupRecord::RoastBatch));
+        records.extend(self.events.into_iter().map(BackupRecord::Event));
+        records.extend(self.trips.into_iter().map(BackupRecord::Trip));
+        records.extend(
+            self.timeline_events
+                .into_iter()
+                .map(BackupRecord::TimelineEvent),
+        );
+        records.extend(self.images.into_iter().map(BackupRecord::Image));
+        records
+    }
+
+    /// Render the backup as newline-delimited JSON, one [`BackupRecord`] per line.
+    pub fn to_ndjson(self) -> anyhow::Result<String> {
+        let mut out = String::new();
+        for record in self.into_records() {
+            out.push_str(&to_string(&record).context("failed to encode backup record")?);
+            out.push('\n');
+        }
+        Ok(out)
+    }
+
+    /// Read a backup written by [`BackupData::to_ndjson`]. Blank lines are
+    /// skipped, and the header must come before any other record.
+    pub fn from_ndjson(input: &str) -> anyhow::Result<Self> {
+        let mut lines = input
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty());
+        let Some((index, first)) = lines.next() else {
+            bail!("the backup is empty");
+        };
+        let BackupRecord::Header {
+            version,
+            created_at,
+        } = from_str(first).with_context(|| format!("failed to decode line {}", index + 1))?
+        else {
+            bail!("the backup does not start with a header record");
+        };
+
+        let mut data = Self {
+            version,
+            created_at,
+            roasters: Vec::new(),
+            gear: Vec::new(),
+            quick_notes: Vec::new(),
+            processes: Vec::new(),
+            roasts: Vec::new(),
+            aliases: Vec::new(),
+            bags: Vec::new(),
+            brews: Vec::new(),
+            cafes: Vec::new(),
+            cups: Vec::new(),
+            green_coffees: Vec::new(),
+            roast_batches: Vec::new(),
+            events: Vec::new(),
+            trips: Vec::new(),
+            timeline_events: Vec::new(),
+            images: Vec::new(),
+        };
+        for (index, line) in lines {
+            let record: BackupRecord =
+                from_str(line).with_context(|| format!("failed to decode line {}", index + 1))?;
+            match record {
+                BackupRecord::Header { .. } => {
+                    bail!("line {} is a second header record", index + 1);
+                }
+                BackupRecord::Roaster(roaster) => data.roasters.push(roaster),
+                BackupRecord::Gear(gear) => data.gear.push(gear),
+                BackupRecord::QuickNote(note) => data.quick_notes.push(note),
+                BackupRecord::Process(process) => data.processes.push(process),
+                BackupRecord::Roast(roast) => data.roasts.push(roast),
+                BackupRecord::Alias(alias) => data.aliases.push(alias),
+                BackupRecord::Bag(bag) => data.bags.push(bag),
+                BackupRecord::Brew(brew) => data.brews.push(brew),
+                BackupRecord::Cafe(cafe) => data.cafes.push(cafe),
+                BackupRecord::Cup(cup) => data.cups.push(cup),
+                BackupRecord::GreenCoffee(green) => data.green_coffees.push(green),
+                BackupRecord::RoastBatch(batch) => data.roast_batches.push(batch),
+                BackupRecord::Event(event) => data.events.push(event),
+                BackupRecord::Trip(trip) => data.trips.push(trip),
+                BackupRecord::TimelineEvent(event) => data.timeline_events.push(event),
+                BackupRecord::Image(image) => data.images.push(image),
+            }
+        }
+        Ok(data)
+    }
+}
+
+/// A single line of an NDJSON backup stream.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data", rename_all = "snake_case")]
+pub enum BackupRecord {
+    Header {
+        version: u32,
+        created_at: DateTime<Utc>,
+    },
+    Roaster(Roaster),
+    Gear(Gear),
+    QuickNote(CustomQuickNote),
+    Process(CustomProcess),
+    Roast(Roast),
+    Alias(Alias),
+    Bag(Bag),
+    Brew(Brew),
+    Cafe(Cafe),
+    Cup(Cup),
+    GreenCoffee(GreenCoffee),
+    RoastBatch(RoastBatch),
+    Event(Event),
+    Trip(Trip),
+    TimelineEvent(TimelineEvent),
+    Image(BackupImage),
+}
+
+impl BackupRecord {
+    /// The table the record is restored into; `None` for the header.
+    pub fn table(&self) -> Option<&'static str> {
+        Some(match self {
+            Self::Header { .. } => return None,
+            Self::Roaster(_) => "roasters",
+            Self::Gear(_) => "gear",
+            Self::QuickNote(_) => "custom_quick_notes",
+            Self::Process(_) => "custom_processes",
+            Self::Roast(_) => "roasts",
+            Self::Alias(_) => "aliases",
+            Self::Bag(_) => "bags",
+            Self::Brew(_) => "brews",
+            Self::Cafe(_) => "cafes",
+            Self::Cup(_) => "cups",
+            Self::GreenCoffee(_) => "green_coffees",
+            Self::RoastBatch(_) => "roast_batches",
+            Self::Event(_) => "events",
+            Self::Trip(_) => "trips",
+            Self::TimelineEvent(_) => "timeline_events",
+            Self::Image(_) => "entity_images",
+        })
+    }
+}
+
+/// How rows are written during a restore.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RestoreMode {
+    /// Require an empty database and fail on any conflicting row.
+    Fresh,
+    /// Skip rows that already exist, so an interrupted restore can be
+    /// replayed from the start of the same backup.
+    Resume,
+}
+
+impl RestoreMode {
+    fn insert_verb(self) -> &'static str {
+        match self {
+            Self::Fresh => "INSERT",
+            Self::Resume => "INSERT OR IGNORE",
+        }
+    }
+}
+
+/// Totals reported at the end of a streaming restore.
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+pub struct RestoreSummary {
+    pub restored: u64,
+    pub skipped: u64,
+}
+
+/// What a reset, restore or timeline rebuild would change, reported by its
+/// dry run.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct DryRunReport {
+    /// Rows that would be deleted, per table.
+    pub deleted: Vec<TableCount>,
+    /// Rows that would be written, per table.
+    pub written: Vec<TableCount>,
+}
+
+impl DryRunReport {
+    fn add_written(&mut self, table: &str, rows: u64) {
+        add_rows(&mut self.written, table, rows);
+    }
+}
+
+/// Add `rows` to `table`'s entry in `counts`, keeping first-seen order.
+fn add_rows(counts: &mut Vec<TableCount>, table: &str, rows: u64) {
+    let rows = i64::try_from(rows).unwrap_or(i64::MAX);
+    match counts.iter_mut().find(|count| count.table == table) {
+        Some(count) => count.rows += rows,
+        None => counts.push(TableCount {
+            table: table.to_string(),
+            rows,
+        }),
+    }
+}
+
 pub struct BackupService {
     pool: DatabasePool,
 }
@@ -99,24 +349,38 @@ impl BackupService {
     pub async fn export(&self) -> anyhow::Result<BackupData> {
         let roasters = self.export_roasters().await?;
         let gear = self.export_gear().await?;
+        let quick_notes = self.export_quick_notes().await?;
+        let processes = self.export_processes().await?;
         let roasts = self.export_roasts().await?;
+        let aliases = self.export_aliases().await?;
         let bags = self.export_bags().await?;
         let brews = self.export_brews().await?;
         let cafes = self.export_cafes().await?;
         let cups = self.export_cups().await?;
+        let green_coffees = self.export_green_coffees().await?;
+        let roast_batches = self.export_roast_batches().await?;
+        let events = self.export_events().await?;
+        let trips = self.export_trips().await?;
         let timeline_events = self.export_timeline_events().await?;
         let images = self.export_images().await?;
 
         Ok(BackupData {
-            version: 2,
+            version: BACKUP_VERSION,
             created_at: Utc::now(),
             roasters,
             gear,
+            quick_notes,
+            processes,
             roasts,
+            aliases,
             bags,
             brews,
             cafes,
             cups,
+            green_coffees,
+            roast_batches,
+            events,
+            trips,
             timeline_events,
             images,
         })
@@ -131,22 +395,62 @@ impl BackupService {
             .await
             .context("failed to begin transaction")?;
 
-        self.restore_roasters(&mut tx, &data.roasters).await?;
-        self.restore_gear(&mut tx, &data.gear).await?;
-        self.restore_roasts(&mut tx, &data.roasts).await?;
-        self.restore_bags(&mut tx, &data.bags).await?;
-        self.restore_brews(&mut tx, &data.brews).await?;
-        self.restore_cafes(&mut tx, &data.cafes).await?;
-        self.restore_cups(&mut tx, &data.cups).await?;
-        self.restore_timeline_events(&mut tx, &data.timeline_events)
-            .await?;
-        self.restore_images(&mut tx, &data.images).await?;
+        for record in data.into_records() {
+            insert_record(&mut tx, RestoreMode::Fresh, &record).await?;
+        }
 
         tx.commit().await.context("failed to commit transaction")?;
 
         Ok(())
     }
 
+    /// The rows [`BackupService::restore`] would write, found by restoring
+    /// into a transaction that is then rolled back.
+    pub async fn preview_restore(&self, data: BackupData) -> anyhow::Result<DryRunReport> {
+        self.verify_empty_database().await?;
+
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("failed to begin transaction")?;
+
+        let mut report = DryRunReport::default();
+        for record in data.into_records() {
+            let rows = insert_record(&mut tx, RestoreMode::Fresh, &record).await?;
+            if let Some(table) = record.table() {
+                report.add_written(table, rows);
+            }
+        }
+
+        tx.rollback()
+            .await
+            .context("failed to roll back transaction")?;
+
+        Ok(report)
+    }
+
+    /// Start a record-by-record restore. In [`RestoreMode::Fresh`] the
+    /// database must be empty, exactly as for [`BackupService::restore`].
+    pub async fn begin_streaming_restore(
+        &self,
+        mode: RestoreMode,
+    ) -> anyhow::Result<StreamingRestore> {
+        if mode == RestoreMode::Fresh {
+            self.verify_empty_database().await?;
+        }
+
+        Ok(StreamingRestore {
+            pool: self.pool.clone(),
+            mode,
+            dry_run: false,
+            tx: None,
+            pending: 0,
+            summary: RestoreSummary::default(),
+            written: DryRunReport::default(),
+        })
+    }
+
     /// Delete all coffee data, leaving auth tables intact.
     ///
     /// After a successful reset the database is in the same state that
@@ -159,39 +463,39 @@ impl BackupService {
             .await
             .context("failed to begin transaction")?;
 
-        // Delete in FK-safe order: children before parents.
-        // brews has RESTRICT FK → gear; cups has RESTRICT FK → roasts, cafes.
-        let tables = [
-            "entity_images",
-            "brews",
-            "cups",
-            "bags",
-            "roasts",
-            "timeline_events",
-            "gear",
-            "cafes",
-            "roasters",
-            "stats_cache",
-        ];
-
-        for table in tables {
-            let query = format!("DELETE FROM {table}");
-            sqlx::query(AssertSqlSafe(query))
-                .execute(&mut *tx)
-                .await
-                .with_context(|| format!("failed to delete from {table}"))?;
-        }
+        delete_coffee_data(&mut tx).await?;
 
         tx.commit().await.context("failed to commit transaction")?;
 
         Ok(())
     }
 
+    /// The rows [`BackupService::reset`] would delete, found by resetting
+    /// inside a transaction that is then rolled back.
+    pub async fn preview_reset(&self) -> anyhow::Result<DryRunReport> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("failed to begin transaction")?;
+
+        let deleted = delete_coffee_data(&mut tx).await?;
+
+        tx.rollback()
+            .await
+            .context("failed to roll back transaction")?;
+
+        Ok(DryRunReport {
+            deleted,
+            written: Vec::new(),
+        })
+    }
+
     // --- Export methods ---
 
     async fn export_roasters(&self) -> anyhow::Result<Vec<Roaster>> {
         let records = sqlx::query_as::<_, RoasterRecord>(
-            "SELECT id, name, slug, country, city, homepage, created_at FROM roasters ORDER BY id",
+            "SELECT id, name, slug, country, city, homepage, created_at, favourite FROM roasters ORDER BY id",
         )
         .fetch_all(&self.pool)
         .await
@@ -205,7 +509,7 @@ impl BackupService {
 
     async fn export_gear(&self) -> anyhow::Result<Vec<Gear>> {
         let records = sqlx::query_as::<_, GearRecord>(
-            "SELECT id, category, make, model, created_at, updated_at FROM gear ORDER BY id",
+            "SELECT id, category, make, model, water_temp, brew_method, uses_filter, temp_offset, water_hardness, stock_count, low_stock_threshold, created_at, updated_at FROM gear ORDER BY id",
         )
         .fetch_all(&self.pool)
         .await
@@ -217,9 +521,37 @@ impl BackupService {
             .collect::<anyhow::Result<Vec<_>>>()
     }
 
+    async fn export_quick_notes(&self) -> anyhow::Result<Vec<CustomQuickNote>> {
+        let records = sqlx::query_as::<_, CustomQuickNoteRecord>(
+            "SELECT id, label, emoji, created_at FROM custom_quick_notes ORDER BY id",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("failed to export quick notes")?;
+
+        Ok(records
+            .into_iter()
+            .map(CustomQuickNoteRecord::into_domain)
+            .collect())
+    }
+
+    async fn export_processes(&self) -> anyhow::Result<Vec<CustomProcess>> {
+        let records = sqlx::query_as::<_, CustomProcessRecord>(
+            "SELECT id, name, family, created_at FROM custom_processes ORDER BY id",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("failed to export processes")?;
+
+        Ok(records
+            .into_iter()
+            .map(CustomProcessRecord::into_domain)
+            .collect())
+    }
+
     async fn export_roasts(&self) -> anyhow::Result<Vec<Roast>> {
         let records = sqlx::query_as::<_, RoastRecord>(
-            "SELECT id, roaster_id, name, slug, origin, region, producer, process, tasting_notes, created_at FROM roasts ORDER BY id",
+            "SELECT id, roaster_id, name, slug, origin, region, producer, process, tasting_notes, components, harvest_year, created_at, favourite FROM roasts ORDER BY id",
         )
         .fetch_all(&self.pool)
         .await
@@ -233,7 +565,7 @@ impl BackupService {
 
     async fn export_bags(&self) -> anyhow::Result<Vec<Bag>> {
         let records = sqlx::query_as::<_, BagRecord>(
-            "SELECT id, roast_id, roast_date, amount, remaining, closed, finished_at, created_at, updated_at FROM bags ORDER BY id",
+            "SELECT id, roast_id, roast_date, amount, remaining, closed, finished_at, created_at, updated_at, low_stock_threshold, price, currency, best_before, batch_code FROM bags ORDER BY id",
         )
         .fetch_all(&self.pool)
         .await
@@ -244,7 +576,7 @@ impl BackupService {
 
     async fn export_brews(&self) -> anyhow::Result<Vec<Brew>> {
         let records = sqlx::query_as::<_, BrewRecord>(
-            "SELECT id, bag_id, coffee_weight, grinder_id, grind_setting, brewer_id, filter_paper_id, water_volume, water_temp, quick_notes, brew_time, created_at, updated_at FROM brews ORDER BY id",
+            "SELECT id, bag_id, coffee_weight, grinder_id, grind_setting, brewer_id, filter_paper_id, water_volume, water_temp, bypass_water_ml, kettle_id, actual_water_temp, quick_notes, brew_time, created_at, updated_at FROM brews ORDER BY id",
         )
         .fetch_all(&self.pool)
         .await
@@ -255,7 +587,7 @@ impl BackupService {
 
     async fn export_cafes(&self) -> anyhow::Result<Vec<Cafe>> {
         let records = sqlx::query_as::<_, CafeRecord>(
-            "SELECT id, name, slug, city, country, latitude, longitude, website, created_at, updated_at FROM cafes ORDER BY id",
+            "SELECT id, name, slug, city, country, latitude, longitude, website, created_at, updated_at, favourite FROM cafes ORDER BY id",
         )
         .fetch_all(&self.pool)
         .await
@@ -266,7 +598,7 @@ impl BackupService {
 
     async fn export_cups(&self) -> anyhow::Result<Vec<Cup>> {
         let records = sqlx::query_as::<_, CupRecord>(
-            "SELECT id, roast_id, cafe_id, created_at, updated_at FROM cups ORDER BY id",
+            "SELECT id, roast_id, cafe_id, bag_id, rating, created_at, updated_at FROM cups ORDER BY id",
         )
         .fetch_all(&self.pool)
         .await
@@ -275,6 +607,67 @@ impl BackupService {
         Ok(records.into_iter().map(CupRecord::into_domain).collect())
     }
 
+    async fn export_green_coffees(&self) -> anyhow::Result<Vec<GreenCoffee>> {
+        let records = sqlx::query_as::<_, GreenCoffeeRecord>(
+            "SELECT id, name, origin, process, supplier, amount, amount - COALESCE((SELECT SUM(weight_in) FROM roast_batches WHERE green_coffee_id = green_coffees.id), 0) AS remaining, price, purchased_on, notes, created_at, updated_at FROM green_coffees ORDER BY id",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("failed to export green coffees")?;
+
+        Ok(records
+            .into_iter()
+            .map(GreenCoffeeRecord::into_domain)
+            .collect())
+    }
+
+    async fn export_roast_batches(&self) -> anyhow::Result<Vec<RoastBatch>> {
+        let records = sqlx::query_as::<_, RoastBatchRecord>(
+            "SELECT id, green_coffee_id, bag_id, roasted_at, weight_in, weight_out, total_time, development_time, curve_notes, created_at, updated_at FROM roast_batches ORDER BY id",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("failed to export roast batches")?;
+
+        Ok(records
+            .into_iter()
+            .map(RoastBatchRecord::into_domain)
+            .collect())
+    }
+
+    async fn export_events(&self) -> anyhow::Result<Vec<Event>> {
+        let records = sqlx::query_as::<_, EventRecord>(
+            "SELECT id, name, venue, event_date, notes, coffees_json, (SELECT GROUP_CONCAT(roaster_id) FROM event_roasters WHERE event_id = events.id) AS roaster_ids, (SELECT GROUP_CONCAT(cafe_id) FROM event_cafes WHERE event_id = events.id) AS cafe_ids, created_at, updated_at FROM events ORDER BY id",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("failed to export events")?;
+
+        records.into_iter().map(EventRecord::into_domain).collect()
+    }
+
+    async fn export_aliases(&self) -> anyhow::Result<Vec<Alias>> {
+        let records = sqlx::query_as::<_, AliasRecord>(
+            "SELECT id, roaster_id, roast_id, name, created_at FROM aliases ORDER BY id",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("failed to export aliases")?;
+
+        Ok(records.into_iter().map(AliasRecord::into_domain).collect())
+    }
+
+    async fn export_trips(&self) -> anyhow::Result<Vec<Trip>> {
+        let records = sqlx::query_as::<_, TripRecord>(
+            "SELECT id, name, location, start_date, end_date, notes, created_at, updated_at FROM trips ORDER BY id",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("failed to export trips")?;
+
+        Ok(records.into_iter().map(TripRecord::into_domain).collect())
+    }
+
     async fn export_timeline_events(&self) -> anyhow::Result<Vec<TimelineEvent>> {
         let records = sqlx::query_as::<_, TimelineEventRecord>(
             "SELECT id, entity_type, entity_id, action, occurred_at, title, details_json, tasting_notes_json, slug, roaster_slug, brew_data_json FROM timeline_events ORDER BY id",
@@ -291,7 +684,9 @@ impl BackupService {
 
     async fn export_images(&self) -> anyhow::Result<Vec<BackupImage>> {
         let records = sqlx::query_as::<_, ImageRecord>(
-            "SELECT entity_type, entity_id, content_type, image_data, thumbnail_data FROM entity_images ORDER BY entity_type, entity_id",
+            // Avatars belong to users, which are not part of a backup, and
+            // deleted images are only waiting to be purged.
+            "SELECT entity_type, entity_id, content_type, image_data, thumbnail_data FROM entity_images WHERE entity_type != 'user' AND deleted_at IS NULL ORDER BY entity_type, entity_id",
         )
         .fetch_all(&self.pool)
         .await
@@ -306,17 +701,25 @@ impl BackupService {
         let tables = [
             "roasters",
             "roasts",
+            "aliases",
             "bags",
             "gear",
             "brews",
             "cafes",
             "cups",
+            "green_coffees",
+            "roast_batches",
+            "events",
+            "trips",
             "timeline_events",
             "entity_images",
+            "custom_quick_notes",
+            "custom_processes",
         ];
 
         for table in tables {
-            let query = format!("SELECT COUNT(*) as count FROM {table}");
+            let filter = coffee_rows_filter(table);
+            let query = format!("SELECT COUNT(*) as count FROM {table}{filter}");
             let row: (i64,) = sqlx::query_as(AssertSqlSafe(query))
                 .fetch_one(&self.pool)
                 .await
@@ -332,264 +735,666 @@ impl BackupService {
 
         Ok(())
     }
+}
 
-    async fn restore_roasters(
-        &self,
-        tx: &mut DatabaseTransaction<'_>,
-        roasters: &[Roaster],
-    ) -> anyhow::Result<()> {
-        for roaster in roasters {
-            sqlx::query(
-                "INSERT INTO roasters (id, name, slug, country, city, homepage, created_at) VALUES (?, ?, ?, ?, ?, ?, ?)",
-            )
-            .bind(i64::from(roaster.id))
-            .bind(&roaster.name)
-            .bind(&roaster.slug)
-            .bind(&roaster.country)
-            .bind(roaster.city.as_deref())
-            .bind(roaster.homepage.as_deref())
-            .bind(roaster.created_at)
-            .execute(&mut **tx)
+/// Delete every row a backup covers, returning how many went from each
+/// table.
+async fn delete_coffee_data(conn: &mut DatabaseConnection) -> anyhow::Result<Vec<TableCount>> {
+    // Delete in FK-safe order: children before parents.
+    // brews has RESTRICT FK → gear; cups has RESTRICT FK → roasts, cafes.
+    let tables = [
+        "entity_images",
+        "events",
+        "trips",
+        "roast_batches",
+        "green_coffees",
+        "brews",
+        "cups",
+        "bags",
+        "aliases",
+        "roasts",
+        "timeline_events",
+        "gear",
+        "cafes",
+        "roasters",
+        "custom_quick_notes",
+        "custom_processes",
+        "stats_cache",
+    ];
+
+    let mut deleted = Vec::new();
+    for table in tables {
+        let filter = coffee_rows_filter(table);
+        let query = format!("DELETE FROM {table}{filter}");
+        let result = sqlx::query(AssertSqlSafe(query))
+            .execute(&mut *conn)
             .await
-            .context("failed to restore roaster")?;
-        }
+            .with_context(|| format!("failed to delete from {table}"))?;
+        add_rows(&mut deleted, table, result.rows_affected());
+    }
+    Ok(deleted)
+}
 
-        Ok(())
+/// `WHERE` clause limiting `table` to rows covered by a backup. Profile
+/// avatars share `entity_images` but belong to users, which are not backed up.
+fn coffee_rows_filter(table: &str) -> &'static str {
+    if table == "entity_images" {
+        " WHERE entity_type != 'user'"
+    } else {
+        ""
     }
+}
 
-    async fn restore_gear(
-        &self,
-        tx: &mut DatabaseTransaction<'_>,
-        gear: &[Gear],
-    ) -> anyhow::Result<()> {
-        for item in gear {
-            sqlx::query(
-                "INSERT INTO gear (id, category, make, model, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?)",
-            )
-            .bind(i64::from(item.id))
-            .bind(item.category.as_str())
-            .bind(&item.make)
-            .bind(&item.model)
-            .bind(item.created_at)
-            .bind(item.updated_at)
-            .execute(&mut **tx)
-            .await
-            .context("failed to restore gear")?;
-        }
+/// Applies backup records one at a time, committing every
+/// [`RESTORE_BATCH_SIZE`] records. Memory use stays flat regardless of backup
+/// size, and batches committed before a failure are kept so the restore can
+/// be re-run with [`RestoreMode::Resume`].
+pub struct StreamingRestore {
+    pool: DatabasePool,
+    mode: RestoreMode,
+    /// Hold every record in one transaction, to be rolled back by
+    /// [`StreamingRestore::discard`].
+    dry_run: bool,
+    tx: Option<DatabaseTransaction<'static>>,
+    pending: usize,
+    summary: RestoreSummary,
+    written: DryRunReport,
+}
 
-        Ok(())
+impl StreamingRestore {
+    /// Keep everything in a single transaction that is never committed, so
+    /// [`StreamingRestore::discard`] can report what the restore would write.
+    #[must_use]
+    pub fn dry_run(mut self) -> Self {
+        self.dry_run = true;
+        self
     }
 
-    async fn restore_roasts(
-        &self,
-        tx: &mut DatabaseTransaction<'_>,
-        roasts: &[Roast],
-    ) -> anyhow::Result<()> {
-        for roast in roasts {
-            let tasting_notes_json = if roast.tasting_notes.is_empty() {
-                None
-            } else {
-                Some(
-                    to_string(&roast.tasting_notes)
-                        .context("failed to encode tasting notes for restore")?,
-                )
-            };
+    pub async fn apply(&mut self, record: &BackupRecord) -> anyhow::Result<()> {
+        if let BackupRecord::Header { version, .. } = record {
+            if *version > BACKUP_VERSION {
+                bail!(
+                    "unsupported backup version {version} (newest supported is {BACKUP_VERSION})"
+                );
+            }
+            return Ok(());
+        }
 
-            sqlx::query(
-                "INSERT INTO roasts (id, roaster_id, name, slug, origin, region, producer, process, tasting_notes, created_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
-            )
-            .bind(i64::from(roast.id))
-            .bind(i64::from(roast.roaster_id))
-            .bind(&roast.name)
-            .bind(&roast.slug)
-            .bind(roast.origin.as_deref())
-            .bind(roast.region.as_deref())
-            .bind(roast.producer.as_deref())
-            .bind(roast.process.as_deref())
-            .bind(tasting_notes_json.as_deref())
-            .bind(roast.created_at)
-            .execute(&mut **tx)
-            .await
-            .context("failed to restore roast")?;
+        if self.tx.is_none() {
+            let tx = self
+                .pool
+                .begin()
+                .await
+                .context("failed to begin transaction")?;
+            self.tx = Some(tx);
         }
+        let tx = self.tx.as_mut().context("restore transaction missing")?;
 
-        Ok(())
-    }
+        let rows = insert_record(tx, self.mode, record).await?;
+        if rows > 0 {
+            self.summary.restored += 1;
+        } else {
+            self.summary.skipped += 1;
+        }
+        if let Some(table) = record.table() {
+            self.written.add_written(table, rows);
+        }
 
-    async fn restore_bags(
-        &self,
-        tx: &mut DatabaseTransaction<'_>,
-        bags: &[Bag],
-    ) -> anyhow::Result<()> {
-        for bag in bags {
-            sqlx::query(
-                "INSERT INTO bags (id, roast_id, roast_date, amount, remaining, closed, finished_at, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
-            )
-            .bind(i64::from(bag.id))
-            .bind(i64::from(bag.roast_id))
-            .bind(bag.roast_date)
-            .bind(bag.amount)
-            .bind(bag.remaining)
-            .bind(bag.closed)
-            .bind(bag.finished_at)
-            .bind(bag.created_at)
-            .bind(bag.updated_at)
-            .execute(&mut **tx)
-            .await
-            .context("failed to restore bag")?;
+        self.pending += 1;
+        if !self.dry_run && self.pending >= RESTORE_BATCH_SIZE {
+            self.commit().await?;
         }
 
         Ok(())
     }
 
-    async fn restore_brews(
-        &self,
-        tx: &mut DatabaseTransaction<'_>,
-        brews: &[Brew],
-    ) -> anyhow::Result<()> {
-        for brew in brews {
-            let quick_notes_json = if brew.quick_notes.is_empty() {
-                None
-            } else {
-                let values: Vec<&str> = brew.quick_notes.iter().map(|n| n.form_value()).collect();
-                Some(to_string(&values).context("failed to encode quick notes for restore")?)
-            };
+    /// Commit any outstanding records and return the totals.
+    pub async fn finish(mut self) -> anyhow::Result<RestoreSummary> {
+        self.commit().await?;
+        Ok(self.summary)
+    }
 
-            sqlx::query(
-                "INSERT INTO brews (id, bag_id, coffee_weight, grinder_id, grind_setting, brewer_id, filter_paper_id, water_volume, water_temp, quick_notes, brew_time, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
-            )
-            .bind(i64::from(brew.id))
-            .bind(i64::from(brew.bag_id))
-            .bind(brew.coffee_weight)
-            .bind(i64::from(brew.grinder_id))
-            .bind(brew.grind_setting)
-            .bind(i64::from(brew.brewer_id))
-            .bind(brew.filter_paper_id.map(i64::from))
-            .bind(brew.water_volume)
-            .bind(brew.water_temp)
-            .bind(quick_notes_json.as_deref())
-            .bind(brew.brew_time)
-            .bind(brew.created_at)
-            .bind(brew.updated_at)
-            .execute(&mut **tx)
-            .await
-            .context("failed to restore brew")?;
+    /// Roll back everything applied and report what would have been written.
+    pub async fn discard(mut self) -> anyhow::Result<DryRunReport> {
+        if let Some(tx) = self.tx.take() {
+            tx.rollback().await.context("failed to roll back restore")?;
         }
+        Ok(self.written)
+    }
 
+    async fn commit(&mut self) -> anyhow::Result<()> {
+        if let Some(tx) = self.tx.take() {
+            tx.commit()
+                .await
+                .context("failed to commit restore batch")?;
+            info!(
+                restored = self.summary.restored,
+                skipped = self.summary.skipped,
+                "backup restore progress"
+            );
+        }
+        self.pending = 0;
         Ok(())
     }
+}
 
-    async fn restore_cafes(
-        &self,
-        tx: &mut DatabaseTransaction<'_>,
-        cafes: &[Cafe],
-    ) -> anyhow::Result<()> {
-        for cafe in cafes {
-            sqlx::query(
-                "INSERT INTO cafes (id, name, slug, city, country, latitude, longitude, website, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
-            )
-            .bind(i64::from(cafe.id))
-            .bind(&cafe.name)
-            .bind(&cafe.slug)
-            .bind(&cafe.city)
-            .bind(&cafe.country)
-            .bind(cafe.latitude)
-            .bind(cafe.longitude)
-            .bind(cafe.website.as_deref())
-            .bind(cafe.created_at)
-            .bind(cafe.updated_at)
-            .execute(&mut **tx)
-            .await
-            .context("failed to restore cafe")?;
-        }
+// --- Per-record inserts ---
+
+/// Insert a single backup record, returning the number of rows written.
+/// In [`RestoreMode::Resume`] a row that already exists is skipped and `0`
+/// is returned.
+async fn insert_record(
+    conn: &mut DatabaseConnection,
+    mode: RestoreMode,
+    record: &BackupRecord,
+) -> anyhow::Result<u64> {
+    let verb = mode.insert_verb();
+    match record {
+        // The header carries metadata only.
+        BackupRecord::Header { .. } => Ok(0),
+        BackupRecord::Roaster(roaster) => insert_roaster(conn, verb, roaster).await,
+        BackupRecord::Gear(gear) => insert_gear(conn, verb, gear).await,
+        BackupRecord::QuickNote(note) => insert_quick_note(conn, verb, note).await,
+        BackupRecord::Process(process) => insert_process(conn, verb, process).await,
+        BackupRecord::Roast(roast) => insert_roast(conn, verb, roast).await,
+        BackupRecord::Alias(alias) => insert_alias(conn, verb, alias).await,
+        BackupRecord::Bag(bag) => insert_bag(conn, verb, bag).await,
+        BackupRecord::Brew(brew) => insert_brew(conn, verb, brew).await,
+        BackupRecord::Cafe(cafe) => insert_cafe(conn, verb, cafe).await,
+        BackupRecord::Cup(cup) => insert_cup(conn, verb, cup).await,
+        BackupRecord::GreenCoffee(green) => insert_green_coffee(conn, verb, green).await,
+        BackupRecord::RoastBatch(batch) => insert_roast_batch(conn, verb, batch).await,
+        BackupRecord::Event(event) => insert_event(conn, verb, event).await,
+        BackupRecord::Trip(trip) => insert_trip(conn, verb, trip).await,
+        BackupRecord::TimelineEvent(event) => insert_timeline_event(conn, verb, event).await,
+        BackupRecord::Image(image) => insert_image(conn, verb, image).await,
+    }
+}
 
-        Ok(())
+async fn insert_roaster(
+    conn: &mut DatabaseConnection,
+    verb: &str,
+    roaster: &Roaster,
+) -> anyhow::Result<u64> {
+    let query = format!(
+        "{verb} INTO roasters (id, name, slug, country, city, homepage, created_at, favourite) VALUES (?, ?, ?, ?, ?, ?, ?, ?)"
+    );
+    let result = sqlx::query(AssertSqlSafe(query))
+        .bind(i64::from(roaster.id))
+        .bind(&roaster.name)
+        .bind(&roaster.slug)
+        .bind(&roaster.country)
+        .bind(roaster.city.as_deref())
+        .bind(roaster.homepage.as_deref())
+        .bind(roaster.created_at)
+        .bind(roaster.favourite)
+        .execute(&mut *conn)
+        .await
+        .context("failed to restore roaster")?;
+
+    Ok(result.rows_affected())
+}
+
+async fn insert_gear(
+    conn: &mut DatabaseConnection,
+    verb: &str,
+    item: &Gear,
+) -> anyhow::Result<u64> {
+    let query = format!(
+        "{verb} INTO gear (id, category, make, model, water_temp, brew_method, uses_filter, temp_offset, water_hardness, stock_count, low_stock_threshold, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+    );
+    let result = sqlx::query(AssertSqlSafe(query))
+        .bind(i64::from(item.id))
+        .bind(item.category.as_str())
+        .bind(&item.make)
+        .bind(&item.model)
+        .bind(item.water_temp)
+        .bind(item.brew_method.map(|method| method.as_str()))
+        .bind(item.uses_filter)
+        .bind(item.temp_offset)
+        .bind(item.water_hardness)
+        .bind(item.stock_count)
+        .bind(item.low_stock_threshold)
+        .bind(item.created_at)
+        .bind(item.updated_at)
+        .execute(&mut *conn)
+        .await
+        .context("failed to restore gear")?;
+
+    Ok(result.rows_affected())
+}
+
+async fn insert_quick_note(
+    conn: &mut DatabaseConnection,
+    verb: &str,
+    note: &CustomQuickNote,
+) -> anyhow::Result<u64> {
+    let query = format!(
+        "{verb} INTO custom_quick_notes (id, label, emoji, created_at) VALUES (?, ?, ?, ?)"
+    );
+    let result = sqlx::query(AssertSqlSafe(query))
+        .bind(i64::from(note.id))
+        .bind(&note.label)
+        .bind(&note.emoji)
+        .bind(note.created_at)
+        .execute(&mut *conn)
+        .await
+        .context("failed to restore quick note")?;
+
+    Ok(result.rows_affected())
+}
+
+async fn insert_process(
+    conn: &mut DatabaseConnection,
+    verb: &str,
+    process: &CustomProcess,
+) -> anyhow::Result<u64> {
+    let query =
+        format!("{verb} INTO custom_processes (id, name, family, created_at) VALUES (?, ?, ?, ?)");
+    let result = sqlx::query(AssertSqlSafe(query))
+        .bind(i64::from(process.id))
+        .bind(&process.name)
+        .bind(&process.family)
+        .bind(process.created_at)
+        .execute(&mut *conn)
+        .await
+        .context("failed to restore process")?;
+
+    Ok(result.rows_affected())
+}
+
+async fn insert_roast(
+    conn: &mut DatabaseConnection,
+    verb: &str,
+    roast: &Roast,
+) -> anyhow::Result<u64> {
+    let tasting_notes_json = if roast.tasting_notes.is_empty() {
+        None
+    } else {
+        Some(
+            to_string(&roast.tasting_notes)
+                .context("failed to encode tasting notes for restore")?,
+        )
+    };
+    let components_json = if roast.components.is_empty() {
+        None
+    } else {
+        Some(
+            to_string(&roast.components)
+                .context("failed to encode blend components for restore")?,
+        )
+    };
+
+    let query = format!(
+        "{verb} INTO roasts (id, roaster_id, name, slug, origin, region, producer, process, tasting_notes, components, harvest_year, created_at, favourite) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+    );
+    let result = sqlx::query(AssertSqlSafe(query))
+        .bind(i64::from(roast.id))
+        .bind(i64::from(roast.roaster_id))
+        .bind(&roast.name)
+        .bind(&roast.slug)
+        .bind(roast.origin.as_deref())
+        .bind(roast.region.as_deref())
+        .bind(roast.producer.as_deref())
+        .bind(roast.process.as_deref())
+        .bind(tasting_notes_json.as_deref())
+        .bind(components_json.as_deref())
+        .bind(roast.harvest_year)
+        .bind(roast.created_at)
+        .bind(roast.favourite)
+        .execute(&mut *conn)
+        .await
+        .context("failed to restore roast")?;
+
+    Ok(result.rows_affected())
+}
+
+async fn insert_bag(conn: &mut DatabaseConnection, verb: &str, bag: &Bag) -> anyhow::Result<u64> {
+    let query = format!(
+        "{verb} INTO bags (id, roast_id, roast_date, amount, remaining, closed, finished_at, created_at, updated_at, low_stock_threshold, price, currency, best_before, batch_code) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+    );
+    let result = sqlx::query(AssertSqlSafe(query))
+        .bind(i64::from(bag.id))
+        .bind(i64::from(bag.roast_id))
+        .bind(bag.roast_date)
+        .bind(bag.amount)
+        .bind(bag.remaining)
+        .bind(bag.closed)
+        .bind(bag.finished_at)
+        .bind(bag.created_at)
+        .bind(bag.updated_at)
+        .bind(bag.low_stock_threshold)
+        .bind(bag.price)
+        .bind(&bag.currency)
+        .bind(bag.best_before)
+        .bind(&bag.batch_code)
+        .execute(&mut *conn)
+        .await
+        .context("failed to restore bag")?;
+    if result.rows_affected() == 0 {
+        return Ok(0);
     }
 
-    async fn restore_cups(
-        &self,
-        tx: &mut DatabaseTransaction<'_>,
-        cups: &[Cup],
-    ) -> anyhow::Result<()> {
-        for cup in cups {
-            sqlx::query(
-                "INSERT INTO cups (id, roast_id, cafe_id, created_at, updated_at) VALUES (?, ?, ?, ?, ?)",
-            )
-            .bind(i64::from(cup.id))
-            .bind(i64::from(cup.roast_id))
-            .bind(i64::from(cup.cafe_id))
-            .bind(cup.created_at)
-            .bind(cup.updated_at)
-            .execute(&mut **tx)
-            .await
-            .context("failed to restore cup")?;
-        }
+    // The ledger is not part of the backup: the new bag opens with its full
+    // amount, so bring it to the backed-up balance in a single correction.
+    sqlx::query(
+        "INSERT INTO bag_ledger (bag_id, kind, delta, reason, created_at) \
+         SELECT id, 'adjustment', ? - remaining, 'Restored from backup', ? \
+         FROM bags WHERE id = ? AND remaining IS NOT ?",
+    )
+    .bind(bag.remaining)
+    .bind(bag.updated_at)
+    .bind(i64::from(bag.id))
+    .bind(bag.remaining)
+    .execute(&mut *conn)
+    .await
+    .context("failed to restore bag balance")?;
+    sqlx::query("UPDATE bags SET updated_at = ? WHERE id = ?")
+        .bind(bag.updated_at)
+        .bind(i64::from(bag.id))
+        .execute(&mut *conn)
+        .await
+        .context("failed to restore bag")?;
 
-        Ok(())
+    Ok(result.rows_affected())
+}
+
+async fn insert_brew(
+    conn: &mut DatabaseConnection,
+    verb: &str,
+    brew: &Brew,
+) -> anyhow::Result<u64> {
+    let quick_notes_json = if brew.quick_notes.is_empty() {
+        None
+    } else {
+        let values: Vec<&str> = brew.quick_notes.iter().map(QuickNote::form_value).collect();
+        Some(to_string(&values).context("failed to encode quick notes for restore")?)
+    };
+
+    let query = format!(
+        "{verb} INTO brews (id, bag_id, coffee_weight, grinder_id, grind_setting, brewer_id, filter_paper_id, water_volume, water_temp, bypass_water_ml, kettle_id, actual_water_temp, quick_notes, brew_time, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+    );
+    let result = sqlx::query(AssertSqlSafe(query))
+        .bind(i64::from(brew.id))
+        .bind(i64::from(brew.bag_id))
+        .bind(brew.coffee_weight)
+        .bind(i64::from(brew.grinder_id))
+        .bind(brew.grind_setting)
+        .bind(i64::from(brew.brewer_id))
+        .bind(brew.filter_paper_id.map(i64::from))
+        .bind(brew.water_volume)
+        .bind(brew.water_temp)
+        .bind(brew.bypass_water_ml)
+        .bind(brew.kettle_id.map(i64::from))
+        .bind(brew.actual_water_temp)
+        .bind(quick_notes_json.as_deref())
+        .bind(brew.brew_time)
+        .bind(brew.created_at)
+        .bind(brew.updated_at)
+        .execute(&mut *conn)
+        .await
+        .context("failed to restore brew")?;
+
+    Ok(result.rows_affected())
+}
+
+async fn insert_cafe(
+    conn: &mut DatabaseConnection,
+    verb: &str,
+    cafe: &Cafe,
+) -> anyhow::Result<u64> {
+    let query = format!(
+        "{verb} INTO cafes (id, name, slug, city, country, latitude, longitude, website, created_at, updated_at, favourite) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+    );
+    let result = sqlx::query(AssertSqlSafe(query))
+        .bind(i64::from(cafe.id))
+        .bind(&cafe.name)
+        .bind(&cafe.slug)
+        .bind(&cafe.city)
+        .bind(&cafe.country)
+        .bind(cafe.latitude)
+        .bind(cafe.longitude)
+        .bind(cafe.website.as_deref())
+        .bind(cafe.created_at)
+        .bind(cafe.updated_at)
+        .bind(cafe.favourite)
+        .execute(&mut *conn)
+        .await
+        .context("failed to restore cafe")?;
+
+    Ok(result.rows_affected())
+}
+
+async fn insert_cup(conn: &mut DatabaseConnection, verb: &str, cup: &Cup) -> anyhow::Result<u64> {
+    let query = format!(
+        "{verb} INTO cups (id, roast_id, cafe_id, bag_id, rating, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?)"
+    );
+    let result = sqlx::query(AssertSqlSafe(query))
+        .bind(i64::from(cup.id))
+        .bind(i64::from(cup.roast_id))
+        .bind(cup.cafe_id.map(i64::from))
+        .bind(cup.bag_id.map(i64::from))
+        .bind(cup.rating)
+        .bind(cup.created_at)
+        .bind(cup.updated_at)
+        .execute(&mut *conn)
+        .await
+        .context("failed to restore cup")?;
+
+    Ok(result.rows_affected())
+}
+
+async fn insert_green_coffee(
+    conn: &mut DatabaseConnection,
+    verb: &str,
+    green: &GreenCoffee,
+) -> anyhow::Result<u64> {
+    let query = format!(
+        "{verb} INTO green_coffees (id, name, origin, process, supplier, amount, price, purchased_on, notes, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+    );
+    let result = sqlx::query(AssertSqlSafe(query))
+        .bind(i64::from(green.id))
+        .bind(&green.name)
+        .bind(green.origin.as_deref())
+        .bind(green.process.as_deref())
+        .bind(green.supplier.as_deref())
+        .bind(green.amount)
+        .bind(green.price)
+        .bind(green.purchased_on)
+        .bind(green.notes.as_deref())
+        .bind(green.created_at)
+        .bind(green.updated_at)
+        .execute(&mut *conn)
+        .await
+        .context("failed to restore green coffee")?;
+
+    Ok(result.rows_affected())
+}
+
+async fn insert_roast_batch(
+    conn: &mut DatabaseConnection,
+    verb: &str,
+    batch: &RoastBatch,
+) -> anyhow::Result<u64> {
+    let query = format!(
+        "{verb} INTO roast_batches (id, green_coffee_id, bag_id, roasted_at, weight_in, weight_out, total_time, development_time, curve_notes, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+    );
+    let result = sqlx::query(AssertSqlSafe(query))
+        .bind(i64::from(batch.id))
+        .bind(i64::from(batch.green_coffee_id))
+        .bind(batch.bag_id.map(i64::from))
+        .bind(batch.roasted_at)
+        .bind(batch.weight_in)
+        .bind(batch.weight_out)
+        .bind(batch.total_time)
+        .bind(batch.development_time)
+        .bind(batch.curve_notes.as_deref())
+        .bind(batch.created_at)
+        .bind(batch.updated_at)
+        .execute(&mut *conn)
+        .await
+        .context("failed to restore roast batch")?;
+
+    Ok(result.rows_affected())
+}
+
+async fn insert_event(
+    conn: &mut DatabaseConnection,
+    verb: &str,
+    event: &Event,
+) -> anyhow::Result<u64> {
+    let coffees_json = if event.coffees.is_empty() {
+        None
+    } else {
+        Some(to_string(&event.coffees).context("failed to encode event coffees for restore")?)
+    };
+
+    let query = format!(
+        "{verb} INTO events (id, name, venue, event_date, notes, coffees_json, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?)"
+    );
+    let result = sqlx::query(AssertSqlSafe(query))
+        .bind(i64::from(event.id))
+        .bind(&event.name)
+        .bind(event.venue.as_deref())
+        .bind(event.date)
+        .bind(event.notes.as_deref())
+        .bind(coffees_json.as_deref())
+        .bind(event.created_at)
+        .bind(event.updated_at)
+        .execute(&mut *conn)
+        .await
+        .context("failed to restore event")?;
+
+    // A skipped event keeps the links it already has.
+    if result.rows_affected() == 0 {
+        return Ok(0);
     }
 
-    async fn restore_timeline_events(
-        &self,
-        tx: &mut DatabaseTransaction<'_>,
-        events: &[TimelineEvent],
-    ) -> anyhow::Result<()> {
-        for event in events {
-            let details_json = to_string(&event.details)
-                .context("failed to encode timeline event details for restore")?;
-
-            let tasting_notes_json = to_string(&event.tasting_notes)
-                .context("failed to encode timeline event tasting notes for restore")?;
-
-            let brew_data_json = event
-                .brew_data
-                .as_ref()
-                .map(to_string)
-                .transpose()
-                .context("failed to encode timeline brew data for restore")?;
-
-            sqlx::query(
-                "INSERT INTO timeline_events (id, entity_type, entity_id, action, occurred_at, title, details_json, tasting_notes_json, slug, roaster_slug, brew_data_json) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
-            )
+    for roaster_id in &event.roaster_ids {
+        sqlx::query("INSERT INTO event_roasters (event_id, roaster_id) VALUES (?, ?)")
             .bind(i64::from(event.id))
-            .bind(event.entity_type.as_str())
-            .bind(event.entity_id)
-            .bind(&event.action)
-            .bind(event.occurred_at)
-            .bind(&event.title)
-            .bind(&details_json)
-            .bind(&tasting_notes_json)
-            .bind(event.slug.as_deref())
-            .bind(event.roaster_slug.as_deref())
-            .bind(brew_data_json.as_deref())
-            .execute(&mut **tx)
+            .bind(i64::from(*roaster_id))
+            .execute(&mut *conn)
             .await
-            .context("failed to restore timeline event")?;
-        }
-
-        Ok(())
+            .context("failed to restore event roaster")?;
     }
-
-    async fn restore_images(
-        &self,
-        tx: &mut DatabaseTransaction<'_>,
-        images: &[BackupImage],
-    ) -> anyhow::Result<()> {
-        for image in images {
-            sqlx::query(
-                "INSERT INTO entity_images (entity_type, entity_id, content_type, image_data, thumbnail_data) VALUES (?, ?, ?, ?, ?)",
-            )
-            .bind(&image.entity_type)
-            .bind(image.entity_id)
-            .bind(&image.content_type)
-            .bind(&image.image_data)
-            .bind(&image.thumbnail_data)
-            .execute(&mut **tx)
+    for cafe_id in &event.cafe_ids {
+        sqlx::query("INSERT INTO event_cafes (event_id, cafe_id) VALUES (?, ?)")
+            .bind(i64::from(event.id))
+            .bind(i64::from(*cafe_id))
+            .execute(&mut *conn)
             .await
-            .context("failed to restore image")?;
-        }
-
-        Ok(())
+            .context("failed to restore event cafe")?;
     }
+
+    Ok(result.rows_affected())
+}
+
+async fn insert_alias(
+    conn: &mut DatabaseConnection,
+    verb: &str,
+    alias: &Alias,
+) -> anyhow::Result<u64> {
+    let query = format!(
+        "{verb} INTO aliases (id, roaster_id, roast_id, name, slug, created_at) VALUES (?, ?, ?, ?, ?, ?)"
+    );
+    let result = sqlx::query(AssertSqlSafe(query))
+        .bind(i64::from(alias.id))
+        .bind(alias.roaster_id.map(i64::from))
+        .bind(alias.roast_id.map(i64::from))
+        .bind(&alias.name)
+        .bind(slug::slugify(&alias.name))
+        .bind(alias.created_at)
+        .execute(&mut *conn)
+        .await
+        .context("failed to restore alias")?;
+
+    Ok(result.rows_affected())
+}
+
+async fn insert_trip(
+    conn: &mut DatabaseConnection,
+    verb: &str,
+    trip: &Trip,
+) -> anyhow::Result<u64> {
+    let query = format!(
+        "{verb} INTO trips (id, name, location, start_date, end_date, notes, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?)"
+    );
+    let result = sqlx::query(AssertSqlSafe(query))
+        .bind(i64::from(trip.id))
+        .bind(&trip.name)
+        .bind(&trip.location)
+        .bind(trip.start_date)
+        .bind(trip.end_date)
+        .bind(trip.notes.as_deref())
+        .bind(trip.created_at)
+        .bind(trip.updated_at)
+        .execute(&mut *conn)
+        .await
+        .context("failed to restore trip")?;
+
+    Ok(result.rows_affected())
+}
+
+async fn insert_timeline_event(
+    conn: &mut DatabaseConnection,
+    verb: &str,
+    event: &TimelineEvent,
+) -> anyhow::Result<u64> {
+    let details_json =
+        to_string(&event.details).context("failed to encode timeline event details for restore")?;
+
+    let tasting_notes_json = to_string(&event.tasting_notes)
+        .context("failed to encode timeline event tasting notes for restore")?;
+
+    let brew_data_json = event
+        .brew_data
+        .as_ref()
+        .map(to_string)
+        .transpose()
+        .context("failed to encode timeline brew data for restore")?;
+
+    let query = format!(
+        "{verb} INTO timeline_events (id, entity_type, entity_id, action, occurred_at, title, details_json, tasting_notes_json, slug, roaster_slug, brew_data_json) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+    );
+    let result = sqlx::query(AssertSqlSafe(query))
+        .bind(i64::from(event.id))
+        .bind(event.entity_type.as_str())
+        .bind(event.entity_id)
+        .bind(&event.action)
+        .bind(event.occurred_at)
+        .bind(&event.title)
+        .bind(&details_json)
+        .bind(&tasting_notes_json)
+        .bind(event.slug.as_deref())
+        .bind(event.roaster_slug.as_deref())
+        .bind(brew_data_json.as_deref())
+        .execute(&mut *conn)
+        .await
+        .context("failed to restore timeline event")?;
+
+    Ok(result.rows_affected())
+}
+
+async fn insert_image(
+    conn: &mut DatabaseConnection,
+    verb: &str,
+    image: &BackupImage,
+) -> anyhow::Result<u64> {
+    let query = format!(
+        "{verb} INTO entity_images (entity_type, entity_id, content_type, image_data, thumbnail_data) VALUES (?, ?, ?, ?, ?)"
+    );
+    let result = sqlx::query(AssertSqlSafe(query))
+        .bind(&image.entity_type)
+        .bind(image.entity_id)
+        .bind(&image.content_type)
+        .bind(&image.image_data)
+        .bind(&image.thumbnail_data)
+        .execute(&mut *conn)
+        .await
+        .context("failed to restore image")?;
+
+    Ok(result.rows_affected())
 }
 
 // --- Record types for export queries ---
@@ -603,6 +1408,7 @@ struct RoasterRecord {
     city: Option<String>,
     homepage: Option<String>,
     created_at: DateTime<Utc>,
+    favourite: bool,
 }
 
 impl RoasterRecord {
@@ -615,6 +1421,8 @@ impl RoasterRecord {
             city: self.city,
             homepage: self.homepage,
             created_at: self.created_at,
+            favourite: self.favourite,
+            created_by: None,
         }
     }
 }
@@ -625,6 +1433,13 @@ struct GearRecord {
     category: String,
     make: String,
     model: String,
+    water_temp: Option<f64>,
+    brew_method: Option<String>,
+    uses_filter: Option<bool>,
+    temp_offset: Option<f64>,
+    water_hardness: Option<i32>,
+    stock_count: Option<i32>,
+    low_stock_threshold: Option<i32>,
     created_at: DateTime<Utc>,
     updated_at: DateTime<Utc>,
 }
@@ -633,18 +1448,71 @@ impl GearRecord {
     fn into_domain(self) -> anyhow::Result<Gear> {
         let category = GearCategory::from_str(&self.category)
             .map_err(|()| anyhow::anyhow!("invalid gear category: {}", self.category))?;
+        let brew_method = self
+            .brew_method
+            .map(|method| {
+                BrewMethod::from_str(&method)
+                    .map_err(|()| anyhow::anyhow!("invalid brew method: {method}"))
+            })
+            .transpose()?;
 
         Ok(Gear {
             id: GearId::new(self.id),
             category,
             make: self.make,
             model: self.model,
+            water_temp: self.water_temp,
+            brew_method,
+            uses_filter: self.uses_filter,
+            temp_offset: self.temp_offset,
+            water_hardness: self.water_hardness,
+            stock_count: self.stock_count,
+            low_stock_threshold: self.low_stock_threshold,
             created_at: self.created_at,
             updated_at: self.updated_at,
+            created_by: None,
         })
     }
 }
 
+#[derive(sqlx::FromRow)]
+struct CustomQuickNoteRecord {
+    id: i64,
+    label: String,
+    emoji: String,
+    created_at: DateTime<Utc>,
+}
+
+impl CustomQuickNoteRecord {
+    fn into_domain(self) -> CustomQuickNote {
+        CustomQuickNote {
+            id: CustomQuickNoteId::new(self.id),
+            label: self.label,
+            emoji: self.emoji,
+            created_at: self.created_at,
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct CustomProcessRecord {
+    id: i64,
+    name: String,
+    family: String,
+    created_at: DateTime<Utc>,
+}
+
+impl CustomProcessRecord {
+    fn into_domain(self) -> CustomProcess {
+        CustomProcess {
+            id: CustomProcessId::new(self.id),
+            name: self.name,
+            family: self.family,
+            created_at: self.created_at,
+        }
+    }
+}
+
 #[derive(sqlx::FromRow)]
 struct RoastRecord {
     id: i64,
@@ -656,12 +1524,16 @@ struct RoastRecord {
     producer: Option<String>,
     process: Option<String>,
     tasting_notes: Option<String>,
+    components: Option<String>,
+    harvest_year: Option<i32>,
     created_at: DateTime<Utc>,
+    favourite: bool,
 }
 
 impl RoastRecord {
     fn into_domain(self) -> anyhow::Result<Roast> {
         let tasting_notes = decode_json_vec(self.tasting_notes, "tasting notes")?;
+        let components = decode_json_vec(self.components, "blend components")?;
 
         Ok(Roast {
             id: RoastId::from(self.id),
@@ -673,7 +1545,13 @@ impl RoastRecord {
             producer: self.producer,
             process: self.process,
             tasting_notes,
+            components,
+            harvest_year: self.harvest_year,
             created_at: self.created_at,
+            favourite: self.favourite,
+            // Derived from brews and cups; refreshed with the stats after a restore.
+            rating: None,
+            created_by: None,
         })
     }
 }
@@ -689,6 +1567,11 @@ struct BagRecord {
     finished_at: Option<DateTime<Utc>>,
     created_at: DateTime<Utc>,
     updated_at: DateTime<Utc>,
+    low_stock_threshold: Option<f64>,
+    price: Option<f64>,
+    currency: Option<String>,
+    best_before: Option<NaiveDate>,
+    batch_code: Option<String>,
 }
 
 impl BagRecord {
@@ -703,6 +1586,12 @@ impl BagRecord {
             finished_at: self.finished_at,
             created_at: self.created_at,
             updated_at: self.updated_at,
+            created_by: None,
+            low_stock_threshold: self.low_stock_threshold,
+            price: self.price,
+            currency: self.currency,
+            best_before: self.best_before,
+            batch_code: self.batch_code,
         }
     }
 }
@@ -718,6 +1607,9 @@ struct BrewRecord {
     filter_paper_id: Option<i64>,
     water_volume: i32,
     water_temp: f64,
+    bypass_water_ml: Option<i32>,
+    kettle_id: Option<i64>,
+    actual_water_temp: Option<f64>,
     quick_notes: Option<String>,
     brew_time: Option<i32>,
     created_at: DateTime<Utc>,
@@ -726,14 +1618,7 @@ struct BrewRecord {
 
 impl BrewRecord {
     fn into_domain(self) -> Brew {
-        let quick_notes = match self.quick_notes {
-            Some(s) if !s.is_empty() => serde_json::from_str::<Vec<String>>(&s)
-                .unwrap_or_default()
-                .iter()
-                .filter_map(|v| QuickNote::from_str_value(v))
-                .collect(),
-            _ => Vec::new(),
-        };
+        let quick_notes = decode_quick_notes(self.quick_notes);
         Brew {
             id: BrewId::new(self.id),
             bag_id: BagId::new(self.bag_id),
@@ -744,10 +1629,14 @@ impl BrewRecord {
             filter_paper_id: self.filter_paper_id.map(GearId::new),
             water_volume: self.water_volume,
             water_temp: self.water_temp,
+            bypass_water_ml: self.bypass_water_ml,
+            kettle_id: self.kettle_id.map(GearId::new),
+            actual_water_temp: self.actual_water_temp,
             quick_notes,
             brew_time: self.brew_time,
             created_at: self.created_at,
             updated_at: self.updated_at,
+            created_by: None,
         }
     }
 }
@@ -764,6 +1653,7 @@ struct CafeRecord {
     website: Option<String>,
     created_at: DateTime<Utc>,
     updated_at: DateTime<Utc>,
+    favourite: bool,
 }
 
 impl CafeRecord {
@@ -779,6 +1669,8 @@ impl CafeRecord {
             website: self.website,
             created_at: self.created_at,
             updated_at: self.updated_at,
+            favourite: self.favourite,
+            created_by: None,
         }
     }
 }
@@ -787,7 +1679,9 @@ impl CafeRecord {
 struct CupRecord {
     id: i64,
     roast_id: i64,
-    cafe_id: i64,
+    cafe_id: Option<i64>,
+    bag_id: Option<i64>,
+    rating: Option<u8>,
     created_at: DateTime<Utc>,
     updated_at: DateTime<Utc>,
 }
@@ -797,13 +1691,171 @@ impl CupRecord {
         Cup {
             id: CupId::from(self.id),
             roast_id: RoastId::from(self.roast_id),
-            cafe_id: CafeId::from(self.cafe_id),
+            cafe_id: self.cafe_id.map(CafeId::from),
+            bag_id: self.bag_id.map(BagId::from),
+            rating: self.rating,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+            created_by: None,
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct GreenCoffeeRecord {
+    id: i64,
+    name: String,
+    origin: Option<String>,
+    process: Option<String>,
+    supplier: Option<String>,
+    amount: f64,
+    remaining: f64,
+    price: Option<f64>,
+    purchased_on: Option<NaiveDate>,
+    notes: Option<String>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl GreenCoffeeRecord {
+    fn into_domain(self) -> GreenCoffee {
+        GreenCoffee {
+            id: GreenCoffeeId::from(self.id),
+            name: self.name,
+            origin: self.origin,
+            process: self.process,
+            supplier: self.supplier,
+            amount: self.amount,
+            remaining: self.remaining,
+            price: self.price,
+            purchased_on: self.purchased_on,
+            notes: self.notes,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct RoastBatchRecord {
+    id: i64,
+    green_coffee_id: i64,
+    bag_id: Option<i64>,
+    roasted_at: DateTime<Utc>,
+    weight_in: f64,
+    weight_out: f64,
+    total_time: Option<i64>,
+    development_time: Option<i64>,
+    curve_notes: Option<String>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl RoastBatchRecord {
+    fn into_domain(self) -> RoastBatch {
+        RoastBatch {
+            id: RoastBatchId::from(self.id),
+            green_coffee_id: GreenCoffeeId::from(self.green_coffee_id),
+            bag_id: self.bag_id.map(BagId::from),
+            roasted_at: self.roasted_at,
+            weight_in: self.weight_in,
+            weight_out: self.weight_out,
+            total_time: self.total_time,
+            development_time: self.development_time,
+            curve_notes: self.curve_notes,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct EventRecord {
+    id: i64,
+    name: String,
+    venue: Option<String>,
+    event_date: NaiveDate,
+    notes: Option<String>,
+    coffees_json: Option<String>,
+    roaster_ids: Option<String>,
+    cafe_ids: Option<String>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl EventRecord {
+    fn into_domain(self) -> anyhow::Result<Event> {
+        let coffees = decode_json_vec(self.coffees_json, "event coffees")?;
+
+        Ok(Event {
+            id: EventId::from(self.id),
+            name: self.name,
+            venue: self.venue,
+            date: self.event_date,
+            notes: self.notes,
+            coffees,
+            roaster_ids: decode_id_list(self.roaster_ids.as_deref(), "event roaster")?,
+            cafe_ids: decode_id_list(self.cafe_ids.as_deref(), "event cafe")?,
             created_at: self.created_at,
             updated_at: self.updated_at,
+            created_by: None,
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct AliasRecord {
+    id: i64,
+    roaster_id: Option<i64>,
+    roast_id: Option<i64>,
+    name: String,
+    created_at: DateTime<Utc>,
+}
+
+impl AliasRecord {
+    fn into_domain(self) -> Alias {
+        Alias {
+            id: AliasId::from(self.id),
+            roaster_id: self.roaster_id.map(RoasterId::from),
+            roast_id: self.roast_id.map(RoastId::from),
+            name: self.name,
+            created_at: self.created_at,
         }
     }
 }
 
+#[derive(sqlx::FromRow)]
+struct TripRecord {
+    id: i64,
+    name: String,
+    location: String,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+    notes: Option<String>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl TripRecord {
+    fn into_domain(self) -> Trip {
+        Trip {
+            id: TripId::from(self.id),
+            name: self.name,
+            location: self.location,
+            start_date: self.start_date,
+            end_date: self.end_date,
+            notes: self.notes,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+            created_by: None,
+        }
+    }
+}
+
+fn decode_id_list<T: From<i64>>(raw: Option<&str>, what: &str) -> anyhow::Result<Vec<T>> {
+    parse_id_list(raw).map_err(|err| anyhow::anyhow!("invalid {what} id {err}"))
+}
+
 #[derive(sqlx::FromRow)]
 struct TimelineEventRecord {
     id: i64,
@@ -842,6 +1894,7 @@ impl TimelineEventRecord {
             slug: self.slug,
             roaster_slug: self.roaster_slug,
             brew_data,
+            created_by: None,
         })
     }
 }
@@ -866,3 +1919,79 @@ impl ImageRecord {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn decoding_arbitrary_lines_never_panics(line in any::<String>()) {
+            let _ = from_str::<BackupRecord>(&line);
+        }
+
+        #[test]
+        fn decoding_arbitrary_records_never_panics(
+            kind in "(header|roaster|gear|quick_note|process|roast|alias|bag|brew|cafe|cup|green_coffee|roast_batch|event|trip|timeline_event|image)",
+            data in "\\{[ -~]{0,64}\\}",
+        ) {
+            let _ = from_str::<BackupRecord>(&format!(r#"{{"type":"{kind}","data":{data}}}"#));
+        }
+
+        #[test]
+        fn header_records_round_trip(version in any::<u32>(), secs in 0i64..4_102_444_800) {
+            let created_at = DateTime::from_timestamp(secs, 0).unwrap();
+            let line = to_string(&BackupRecord::Header { version, created_at }).unwrap();
+            match from_str::<BackupRecord>(&line).unwrap() {
+                BackupRecord::Header { version: v, created_at: c } => {
+                    prop_assert_eq!(v, version);
+                    prop_assert_eq!(c, created_at);
+                }
+                other => prop_assert!(false, "unexpected record {other:?}"),
+            }
+        }
+
+        #[test]
+        fn image_payloads_round_trip(
+            image_data in prop::collection::vec(any::<u8>(), 0..256),
+            thumbnail_data in prop::collection::vec(any::<u8>(), 0..64),
+        ) {
+            let record = BackupRecord::Image(BackupImage {
+                entity_type: "roast".to_string(),
+                entity_id: 1,
+                content_type: "image/jpeg".to_string(),
+                image_data: image_data.clone(),
+                thumbnail_data: thumbnail_data.clone(),
+            });
+            let line = to_string(&record).unwrap();
+            let BackupRecord::Image(decoded) = from_str::<BackupRecord>(&line).unwrap() else {
+                panic!("expected an image record");
+            };
+            prop_assert_eq!(decoded.image_data, image_data);
+            prop_assert_eq!(decoded.thumbnail_data, thumbnail_data);
+        }
+    }
+
+    #[test]
+    fn ndjson_backups_read_back_unchanged() {
+        let original =
+            serde_json::to_value(crate::infrastructure::demo::sample_backup().unwrap()).unwrap();
+        let data: BackupData = serde_json::from_value(original.clone()).unwrap();
+
+        let read_back = BackupData::from_ndjson(&data.to_ndjson().unwrap()).unwrap();
+
+        assert_eq!(serde_json::to_value(read_back).unwrap(), original);
+    }
+
+    #[test]
+    fn ndjson_backups_must_start_with_a_header() {
+        let err = BackupData::from_ndjson(r#"{"type":"quick_note","data":{}}"#).unwrap_err();
+        assert!(
+            err.to_string().contains("failed to decode line 1"),
+            "{err:#}"
+        );
+        assert!(BackupData::from_ndjson("\n").is_err());
+    }
+}
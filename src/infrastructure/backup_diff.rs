@@ -0,0 +1,203 @@
+use std::collections::BTreeMap;
+
+use anyhow::Context;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::infrastructure::backup::BackupData;
+
+/// What changed between two backups, section by section in restore order.
+#[derive(Debug, Serialize)]
+pub struct BackupDiff {
+    pub sections: Vec<SectionDiff>,
+}
+
+impl BackupDiff {
+    pub fn is_empty(&self) -> bool {
+        self.sections.iter().all(SectionDiff::is_empty)
+    }
+}
+
+/// The rows of one backup section that were added, removed or changed.
+#[derive(Debug, Serialize)]
+pub struct SectionDiff {
+    /// The section's key in a JSON backup, e.g. `roasters`.
+    pub section: &'static str,
+    pub unchanged: usize,
+    pub added: Vec<DiffEntry>,
+    pub removed: Vec<DiffEntry>,
+    pub changed: Vec<DiffEntry>,
+}
+
+impl SectionDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DiffEntry {
+    /// The row's ID, or `<entity_type>/<entity_id>` for images.
+    pub key: String,
+    /// A name to recognise the row by, when it has one.
+    pub label: Option<String>,
+}
+
+/// Compare two backups row by row. Rows are matched by ID, so a row that was
+/// deleted and recreated under a new ID shows up as removed and added.
+pub fn diff_backups(old: &BackupData, new: &BackupData) -> anyhow::Result<BackupDiff> {
+    let old = sections(old)?;
+    let new = sections(new)?;
+    let sections = old
+        .into_iter()
+        .zip(new)
+        .map(|((section, old_rows), (_, new_rows))| diff_section(section, old_rows, new_rows))
+        .collect();
+    Ok(BackupDiff { sections })
+}
+
+fn sections(data: &BackupData) -> anyhow::Result<Vec<(&'static str, Vec<Value>)>> {
+    fn rows<T: Serialize>(
+        section: &'static str,
+        rows: &[T],
+    ) -> anyhow::Result<(&'static str, Vec<Value>)> {
+        let rows = rows
+            .iter()
+            .map(serde_json::to_value)
+            .collect::<Result<_, _>>()
+            .with_context(|| format!("failed to encode {section}"))?;
+        Ok((section, rows))
+    }
+
+    Ok(vec![
+        rows("roasters", &data.roasters)?,
+        rows("gear", &data.gear)?,
+        rows("quick_notes", &data.quick_notes)?,
+        rows("processes", &data.processes)?,
+        rows("roasts", &data.roasts)?,
+        rows("bags", &data.bags)?,
+        rows("brews", &data.brews)?,
+        rows("cafes", &data.cafes)?,
+        rows("cups", &data.cups)?,
+        rows("green_coffees", &data.green_coffees)?,
+        rows("roast_batches", &data.roast_batches)?,
+        rows("events", &data.events)?,
+        rows("timeline_events", &data.timeline_events)?,
+        rows("images", &data.images)?,
+    ])
+}
+
+fn diff_section(section: &'static str, old: Vec<Value>, new: Vec<Value>) -> SectionDiff {
+    let mut old = keyed(old);
+    let mut unchanged = 0;
+    let mut added = Vec::new();
+    let mut changed = Vec::new();
+    for (key, row) in keyed(new) {
+        match old.remove(&key) {
+            None => added.push(entry(key, &row)),
+            Some(previous) if previous != row => changed.push(entry(key, &row)),
+            Some(_) => unchanged += 1,
+        }
+    }
+    let removed = old.into_iter().map(|(key, row)| entry(key, &row)).collect();
+
+    SectionDiff {
+        section,
+        unchanged,
+        added,
+        removed,
+        changed,
+    }
+}
+
+/// Rows by key, sorted so numeric IDs come out in numeric order.
+fn keyed(rows: Vec<Value>) -> BTreeMap<RowKey, Value> {
+    rows.into_iter().map(|row| (row_key(&row), row)).collect()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum RowKey {
+    Id(i64),
+    Other(String),
+}
+
+fn row_key(row: &Value) -> RowKey {
+    if let Some(id) = row.get("id").and_then(Value::as_i64) {
+        return RowKey::Id(id);
+    }
+    match (row.get("entity_type"), row.get("entity_id")) {
+        (Some(Value::String(entity_type)), Some(entity_id)) => {
+            RowKey::Other(format!("{entity_type}/{entity_id}"))
+        }
+        _ => RowKey::Other(row.to_string()),
+    }
+}
+
+fn entry(key: RowKey, row: &Value) -> DiffEntry {
+    let key = match key {
+        RowKey::Id(id) => id.to_string(),
+        RowKey::Other(key) => key,
+    };
+    DiffEntry {
+        key,
+        label: row_label(row),
+    }
+}
+
+fn row_label(row: &Value) -> Option<String> {
+    let text = |field: &str| row.get(field).and_then(Value::as_str);
+    if let Some(label) = text("name")
+        .or_else(|| text("title"))
+        .or_else(|| text("label"))
+    {
+        return Some(label.to_string());
+    }
+    match (text("make"), text("model")) {
+        (Some(make), Some(model)) => Some(format!("{make} {model}")),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::demo::sample_backup;
+
+    #[test]
+    fn identical_backups_have_no_differences() {
+        let diff = diff_backups(&sample_backup().unwrap(), &sample_backup().unwrap()).unwrap();
+
+        assert!(diff.is_empty());
+        let roasters = &diff.sections[0];
+        assert_eq!(roasters.section, "roasters");
+        assert_eq!(roasters.unchanged, sample_backup().unwrap().roasters.len());
+    }
+
+    #[test]
+    fn added_removed_and_changed_rows_are_reported_per_section() {
+        let old = sample_backup().unwrap();
+        let mut new = sample_backup().unwrap();
+        let dropped = new.roasters.pop().unwrap();
+        new.roasters[0].name = "Renamed".to_string();
+        let mut extra = new.gear[0].clone();
+        extra.id = 9_999.into();
+        new.gear.push(extra);
+
+        let diff = diff_backups(&old, &new).unwrap();
+
+        let section = |name: &str| diff.sections.iter().find(|s| s.section == name).unwrap();
+        let roasters = section("roasters");
+        assert_eq!(
+            roasters.removed,
+            vec![DiffEntry {
+                key: i64::from(dropped.id).to_string(),
+                label: Some(dropped.name),
+            }]
+        );
+        assert_eq!(roasters.changed.len(), 1);
+        assert_eq!(roasters.changed[0].label.as_deref(), Some("Renamed"));
+        assert!(roasters.added.is_empty());
+        assert_eq!(section("gear").added[0].key, "9999");
+        assert!(section("brews").is_empty());
+    }
+}
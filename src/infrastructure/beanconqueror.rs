@@ -0,0 +1,759 @@
+//! Reads and writes exports for Beanconqueror, the mobile coffee-tracking
+//! app, so a history can move between it and Brewlog in either direction.
+//!
+//! The app's "export" is a zip holding `Beanconqueror.json`; large exports
+//! spill brews and beans into numbered `Beanconqueror_Brews_1.json`-style
+//! files alongside it. A bare `Beanconqueror.json` is accepted too.
+
+use std::collections::{BTreeMap, HashMap};
+use std::io::{Cursor, Read};
+use std::path::Path;
+
+use anyhow::{Context, bail};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde_json::{Map, Value, json};
+
+use crate::domain::bags::NewBag;
+use crate::domain::brews::{NewBrew, QuickNote};
+use crate::domain::gear::GearCategory;
+use crate::domain::ids::{BagId, GearId, RoastId, RoasterId};
+use crate::domain::roasts::NewRoast;
+use crate::infrastructure::backup::BackupData;
+
+/// Used for details Brewlog requires but Beanconqueror left blank.
+pub const PLACEHOLDER: &str = "Unknown";
+
+/// Top-level sections that are either imported or hold app preferences.
+const KNOWN_SECTIONS: [&str; 6] = [
+    "BEANS",
+    "BREWS",
+    "MILL",
+    "PREPARATION",
+    "SETTINGS",
+    "VERSION",
+];
+
+const BEAN_FIELDS: [&str; 10] = [
+    "config",
+    "name",
+    "roaster",
+    "roastingDate",
+    "buyDate",
+    "weight",
+    "cost",
+    "aromatics",
+    "finished",
+    "bean_information",
+];
+
+const BEAN_INFORMATION_FIELDS: [&str; 5] = ["country", "region", "farm", "farmer", "processing"];
+
+const BREW_FIELDS: [&str; 12] = [
+    "config",
+    "bean",
+    "mill",
+    "method_of_preparation",
+    "grind_size",
+    "grind_weight",
+    "brew_temperature",
+    "brew_time",
+    "brew_quantity",
+    "brew_quantity_type",
+    "brew_beverage_quantity",
+    "brew_beverage_quantity_type",
+];
+
+/// A parsed Beanconqueror export.
+#[derive(Debug, Clone, Default)]
+pub struct BeanconquerorExport {
+    pub beans: Vec<Bean>,
+    pub brews: Vec<LoggedBrew>,
+    pub mills: Vec<Equipment>,
+    pub preparations: Vec<Equipment>,
+    /// Fields and sections Brewlog has nowhere to put, with how many records
+    /// carried them, e.g. `beans.url` or `water`.
+    pub unmapped: BTreeMap<String, usize>,
+}
+
+/// A bag of beans as Beanconqueror records it: the coffee and the purchase
+/// in one.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Bean {
+    pub uuid: String,
+    pub name: String,
+    pub roaster: Option<String>,
+    pub roast_date: Option<NaiveDate>,
+    pub created_at: Option<DateTime<Utc>>,
+    /// Grams.
+    pub weight: Option<f64>,
+    pub cost: Option<f64>,
+    pub tasting_notes: Vec<String>,
+    pub finished: bool,
+    pub origin: Option<String>,
+    pub region: Option<String>,
+    pub producer: Option<String>,
+    pub process: Option<String>,
+}
+
+/// A grinder ("mill") or brew method ("preparation").
+#[derive(Debug, Clone, PartialEq)]
+pub struct Equipment {
+    pub uuid: String,
+    pub name: String,
+}
+
+/// One brew from the export, with its bean and equipment as Beanconqueror
+/// UUIDs.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LoggedBrew {
+    pub bean: String,
+    pub mill: Option<String>,
+    pub preparation: Option<String>,
+    pub dose: Option<f64>,
+    pub grind_setting: Option<f64>,
+    /// Millilitres, falling back to the beverage weight for espresso.
+    pub water: Option<f64>,
+    pub water_temp: Option<f64>,
+    pub brew_time: Option<i32>,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+/// Read an export from the bytes of a zip or a bare JSON document.
+pub fn read_export(bytes: &[u8]) -> anyhow::Result<BeanconquerorExport> {
+    let root = if bytes.starts_with(b"PK") {
+        read_archive(bytes)?
+    } else {
+        serde_json::from_slice(bytes).context("not a Beanconqueror export: invalid JSON")?
+    };
+    parse_export(&root)
+}
+
+/// Merge the main document and any spill-over files in an export zip.
+fn read_archive(bytes: &[u8]) -> anyhow::Result<Value> {
+    let mut archive =
+        zip::ZipArchive::new(Cursor::new(bytes)).context("not a Beanconqueror export: bad zip")?;
+
+    let mut root: Option<Map<String, Value>> = None;
+    let mut spilled: Vec<(&str, Vec<Value>)> = Vec::new();
+    for index in 0..archive.len() {
+        let mut file = archive.by_index(index)?;
+        let name = file
+            .name()
+            .rsplit('/')
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+        if !Path::new(&name)
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+        {
+            continue;
+        }
+        let mut contents = Vec::new();
+        file.read_to_end(&mut contents)?;
+        let document: Value = serde_json::from_slice(&contents)
+            .with_context(|| format!("{} is not valid JSON", file.name()))?;
+
+        if name == "beanconqueror.json" {
+            let Value::Object(map) = document else {
+                bail!("Beanconqueror.json is not a JSON object");
+            };
+            root = Some(map);
+        } else if let Value::Array(records) = document {
+            let section = if name.contains("_brews_") {
+                "BREWS"
+            } else if name.contains("_beans_") {
+                "BEANS"
+            } else if name.contains("_mill") {
+                "MILL"
+            } else if name.contains("_preparation") {
+                "PREPARATION"
+            } else {
+                continue;
+            };
+            spilled.push((section, records));
+        }
+    }
+
+    let mut root = root.context("not a Beanconqueror export: no Beanconqueror.json in the zip")?;
+    for (section, records) in spilled {
+        if let Value::Array(existing) = root
+            .entry(section)
+            .or_insert_with(|| Value::Array(Vec::new()))
+        {
+            existing.extend(records);
+        }
+    }
+    Ok(Value::Object(root))
+}
+
+fn parse_export(root: &Value) -> anyhow::Result<BeanconquerorExport> {
+    let Value::Object(sections) = root else {
+        bail!("not a Beanconqueror export: expected a JSON object");
+    };
+    if !KNOWN_SECTIONS[..4]
+        .iter()
+        .any(|section| sections.contains_key(*section))
+    {
+        bail!("not a Beanconqueror export: no beans, brews, mills or preparations");
+    }
+
+    let mut export = BeanconquerorExport::default();
+    for (section, value) in sections {
+        if KNOWN_SECTIONS.contains(&section.as_str()) {
+            continue;
+        }
+        let entries = match value {
+            Value::Array(items) => items.iter().filter(|item| is_meaningful(item)).count(),
+            other => usize::from(is_meaningful(other)),
+        };
+        if entries > 0 {
+            *export.unmapped.entry(section.to_lowercase()).or_default() += entries;
+        }
+    }
+
+    for record in records(sections, "BEANS") {
+        if let Some(bean) = parse_bean(record, &mut export.unmapped) {
+            export.beans.push(bean);
+        }
+    }
+    for record in records(sections, "BREWS") {
+        if let Some(brew) = parse_brew(record, &mut export.unmapped) {
+            export.brews.push(brew);
+        }
+    }
+    export.mills = records(sections, "MILL")
+        .filter_map(parse_equipment)
+        .collect();
+    export.preparations = records(sections, "PREPARATION")
+        .filter_map(parse_equipment)
+        .collect();
+    Ok(export)
+}
+
+fn records<'a>(
+    sections: &'a Map<String, Value>,
+    section: &str,
+) -> impl Iterator<Item = &'a Map<String, Value>> {
+    sections
+        .get(section)
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_object)
+}
+
+fn parse_bean(record: &Map<String, Value>, unmapped: &mut BTreeMap<String, usize>) -> Option<Bean> {
+    let name = text(record, "name")?;
+    note_unmapped(unmapped, "beans", record, &BEAN_FIELDS);
+
+    // Blends list several origins; the first describes the coffee well enough.
+    let information = record
+        .get("bean_information")
+        .and_then(Value::as_array)
+        .and_then(|entries| entries.iter().find_map(Value::as_object));
+    if let Some(information) = information {
+        note_unmapped(
+            unmapped,
+            "beans.bean_information",
+            information,
+            &BEAN_INFORMATION_FIELDS,
+        );
+    }
+    let detail = |key: &str| information.and_then(|info| text(info, key));
+
+    let tasting_notes = text(record, "aromatics")
+        .map(|notes| {
+            notes
+                .split(',')
+                .map(str::trim)
+                .filter(|note| !note.is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Some(Bean {
+        uuid: uuid(record)?,
+        name,
+        roaster: text(record, "roaster"),
+        roast_date: text(record, "roastingDate").and_then(|date| parse_date(&date)),
+        created_at: text(record, "buyDate")
+            .and_then(|date| parse_date(&date))
+            .and_then(|date| date.and_hms_opt(0, 0, 0))
+            .map(|date| date.and_utc())
+            .or_else(|| created_at(record)),
+        weight: number(record, "weight"),
+        cost: number(record, "cost"),
+        tasting_notes,
+        finished: record
+            .get("finished")
+            .and_then(Value::as_bool)
+            .unwrap_or(false),
+        origin: detail("country"),
+        region: detail("region"),
+        producer: detail("farmer").or_else(|| detail("farm")),
+        process: detail("processing"),
+    })
+}
+
+fn parse_brew(
+    record: &Map<String, Value>,
+    unmapped: &mut BTreeMap<String, usize>,
+) -> Option<LoggedBrew> {
+    note_unmapped(unmapped, "brews", record, &BREW_FIELDS);
+
+    let grind_setting = text(record, "grind_size").and_then(|size| {
+        let parsed = size.replace(',', ".").parse::<f64>().ok();
+        if parsed.is_none() {
+            *unmapped.entry("brews.grind_size".to_string()).or_default() += 1;
+        }
+        parsed
+    });
+
+    Some(LoggedBrew {
+        bean: text(record, "bean")?,
+        mill: text(record, "mill"),
+        preparation: text(record, "method_of_preparation"),
+        dose: number(record, "grind_weight"),
+        grind_setting,
+        water: number(record, "brew_quantity")
+            .or_else(|| number(record, "brew_beverage_quantity")),
+        water_temp: number(record, "brew_temperature"),
+        #[allow(clippy::cast_possible_truncation)] // Seconds
+        brew_time: number(record, "brew_time").map(|seconds| seconds.round() as i32),
+        created_at: created_at(record),
+    })
+}
+
+fn parse_equipment(record: &Map<String, Value>) -> Option<Equipment> {
+    Some(Equipment {
+        uuid: uuid(record)?,
+        name: text(record, "name")?,
+    })
+}
+
+fn uuid(record: &Map<String, Value>) -> Option<String> {
+    record
+        .get("config")
+        .and_then(Value::as_object)
+        .and_then(|config| text(config, "uuid"))
+}
+
+fn created_at(record: &Map<String, Value>) -> Option<DateTime<Utc>> {
+    record
+        .get("config")
+        .and_then(|config| config.get("unix_timestamp"))
+        .and_then(Value::as_i64)
+        .filter(|seconds| *seconds > 0)
+        .and_then(|seconds| DateTime::from_timestamp(seconds, 0))
+}
+
+fn text(record: &Map<String, Value>, key: &str) -> Option<String> {
+    record
+        .get(key)
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+/// A positive number, however it was written.
+fn number(record: &Map<String, Value>, key: &str) -> Option<f64> {
+    match record.get(key)? {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.trim().replace(',', ".").parse().ok(),
+        _ => None,
+    }
+    .filter(|n: &f64| n.is_finite() && *n > 0.0)
+}
+
+/// Dates are ISO timestamps in the phone's time zone; the calendar day is
+/// what matters.
+fn parse_date(value: &str) -> Option<NaiveDate> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|date| date.date_naive())
+        .ok()
+        .or_else(|| NaiveDate::parse_from_str(value.get(..10)?, "%Y-%m-%d").ok())
+}
+
+/// Whether a value holds anything beyond the app's empty defaults.
+fn is_meaningful(value: &Value) -> bool {
+    match value {
+        Value::Null => false,
+        Value::Bool(b) => *b,
+        Value::Number(n) => n.as_f64().is_some_and(|n| n != 0.0),
+        Value::String(s) => {
+            let s = s.trim();
+            !s.is_empty() && !s.eq_ignore_ascii_case("unknown")
+        }
+        Value::Array(items) => items.iter().any(is_meaningful),
+        Value::Object(fields) => fields.values().any(is_meaningful),
+    }
+}
+
+fn note_unmapped(
+    unmapped: &mut BTreeMap<String, usize>,
+    prefix: &str,
+    record: &Map<String, Value>,
+    mapped: &[&str],
+) {
+    for (key, value) in record {
+        if !mapped.contains(&key.as_str()) && is_meaningful(value) {
+            *unmapped.entry(format!("{prefix}.{key}")).or_default() += 1;
+        }
+    }
+}
+
+/// Split an equipment name like "Comandante C40" into make and model. A
+/// single word is the make, with the category standing in for the model.
+pub fn make_and_model(name: &str, category: GearCategory) -> (String, String) {
+    if let Some((make, model)) = name.trim().split_once(char::is_whitespace) {
+        return (make.to_string(), model.trim().to_string());
+    }
+    let model = match category {
+        GearCategory::Grinder => "Grinder",
+        GearCategory::Brewer => "Brewer",
+        GearCategory::FilterPaper => "Filter",
+        GearCategory::Kettle => "Kettle",
+    };
+    (name.trim().to_string(), model.to_string())
+}
+
+impl Bean {
+    /// The roast this bean is a bag of. Details Brewlog requires but the
+    /// export lacks are filled with [`PLACEHOLDER`] and tallied in `filled_in`.
+    pub fn new_roast(
+        &self,
+        roaster_id: RoasterId,
+        filled_in: &mut BTreeMap<String, usize>,
+    ) -> NewRoast {
+        let mut fill = |field: &str, value: &Option<String>| {
+            value.clone().unwrap_or_else(|| {
+                *filled_in.entry(format!("roasts.{field}")).or_default() += 1;
+                PLACEHOLDER.to_string()
+            })
+        };
+        let origin = fill("origin", &self.origin);
+        let region = fill("region", &self.region);
+        let producer = fill("producer", &self.producer);
+        let process = fill("process", &self.process);
+        let tasting_notes = if self.tasting_notes.is_empty() {
+            *filled_in
+                .entry("roasts.tasting_notes".to_string())
+                .or_default() += 1;
+            vec![PLACEHOLDER.to_string()]
+        } else {
+            self.tasting_notes.clone()
+        };
+
+        NewRoast {
+            roaster_id,
+            name: self.name.clone(),
+            origin,
+            region,
+            producer,
+            tasting_notes,
+            process,
+            components: Vec::new(),
+            harvest_year: None,
+            created_at: self.created_at,
+        }
+    }
+
+    /// The bag itself. Beanconqueror lets the weight go unrecorded, in which
+    /// case `fallback_amount` (what its brews used) stands in.
+    pub fn new_bag(&self, roast_id: RoastId, fallback_amount: f64) -> Option<NewBag> {
+        let amount = self
+            .weight
+            .or_else(|| (fallback_amount > 0.0).then_some(fallback_amount))?;
+        Some(NewBag {
+            roast_id,
+            roast_date: self.roast_date,
+            amount,
+            price: self.cost,
+            currency: None,
+            best_before: None,
+            batch_code: None,
+            created_at: self.created_at,
+        })
+    }
+}
+
+impl LoggedBrew {
+    /// Dose, water and temperature, which Brewlog requires of every brew.
+    fn recipe(&self) -> Result<(f64, f64, f64), &'static str> {
+        let coffee_weight = self.dose.ok_or("no coffee dose")?;
+        let water = self.water.ok_or("no water amount")?;
+        let water_temp = self
+            .water_temp
+            .filter(|temp| *temp <= 100.0)
+            .ok_or("no water temperature")?;
+        Ok((coffee_weight, water, water_temp))
+    }
+
+    /// Why this brew can't be logged in Brewlog, if it can't.
+    pub fn missing(&self) -> Option<&'static str> {
+        self.recipe().err()
+    }
+
+    /// The brew in Brewlog's terms, or why it can't be one.
+    pub fn new_brew(
+        &self,
+        bag_id: BagId,
+        grinder_id: GearId,
+        brewer_id: GearId,
+    ) -> Result<NewBrew, &'static str> {
+        let (coffee_weight, water, water_temp) = self.recipe()?;
+
+        Ok(NewBrew {
+            bag_id,
+            coffee_weight,
+            grinder_id,
+            grind_setting: self.grind_setting.unwrap_or(0.0),
+            brewer_id,
+            filter_paper_id: None,
+            #[allow(clippy::cast_possible_truncation)] // Millilitres
+            water_volume: water.round() as i32,
+            water_temp,
+            bypass_water_ml: None,
+            kettle_id: None,
+            actual_water_temp: None,
+            quick_notes: Vec::new(),
+            brew_time: self.brew_time.filter(|seconds| *seconds > 0),
+            created_at: self.created_at,
+        })
+    }
+}
+
+/// Write Brewlog data as a `Beanconqueror.json` the app can import. Each
+/// bag becomes a bean, grinders become mills, brewers become preparations,
+/// and quick notes go into the brew's note. Cafes and cups have no
+/// Beanconqueror equivalent and are left out.
+pub fn write_export(data: &BackupData) -> Value {
+    let roasters: HashMap<_, _> = data.roasters.iter().map(|r| (r.id, r)).collect();
+    let roasts: HashMap<_, _> = data.roasts.iter().map(|r| (r.id, r)).collect();
+
+    let beans: Vec<Value> = data
+        .bags
+        .iter()
+        .filter_map(|bag| {
+            let roast = roasts.get(&bag.roast_id)?;
+            let roaster = roasters.get(&roast.roaster_id).map(|r| r.name.as_str());
+            Some(json!({
+                "name": roast.name,
+                "roaster": roaster.unwrap_or_default(),
+                "roastingDate": bag.roast_date.map(|date| date.to_string()).unwrap_or_default(),
+                "buyDate": bag.created_at.to_rfc3339(),
+                "weight": bag.amount,
+                "cost": bag.price.unwrap_or_default(),
+                "aromatics": roast.tasting_notes.join(", "),
+                "finished": bag.closed,
+                "bean_information": [{
+                    "country": roast.origin.as_deref().unwrap_or_default(),
+                    "region": roast.region.as_deref().unwrap_or_default(),
+                    "farmer": roast.producer.as_deref().unwrap_or_default(),
+                    "processing": roast.process.as_deref().unwrap_or_default(),
+                }],
+                "config": config(&format!("bag-{}", bag.id), bag.created_at),
+            }))
+        })
+        .collect();
+
+    let equipment = |category: GearCategory| -> Vec<Value> {
+        data.gear
+            .iter()
+            .filter(|gear| gear.category == category)
+            .map(|gear| {
+                let mut item = json!({
+                    "name": format!("{} {}", gear.make, gear.model),
+                    "config": config(&format!("gear-{}", gear.id), gear.created_at),
+                });
+                if category == GearCategory::Brewer {
+                    item["type"] = json!("CUSTOM_PREPARATION");
+                }
+                item
+            })
+            .collect()
+    };
+
+    let brews: Vec<Value> = data
+        .brews
+        .iter()
+        .map(|brew| {
+            let notes: Vec<&str> = brew.quick_notes.iter().map(QuickNote::label).collect();
+            json!({
+                "bean": format!("bag-{}", brew.bag_id),
+                "mill": format!("gear-{}", brew.grinder_id),
+                "method_of_preparation": format!("gear-{}", brew.brewer_id),
+                "grind_size": brew.grind_setting.to_string(),
+                "grind_weight": brew.coffee_weight,
+                "brew_temperature": brew.water_temp,
+                "brew_time": brew.brew_time.unwrap_or_default(),
+                "brew_quantity": brew.total_water(),
+                "brew_quantity_type": "GR",
+                "note": notes.join(", "),
+                "config": config(&format!("brew-{}", brew.id), brew.created_at),
+            })
+        })
+        .collect();
+
+    json!({
+        "BEANS": beans,
+        "BREWS": brews,
+        "MILL": equipment(GearCategory::Grinder),
+        "PREPARATION": equipment(GearCategory::Brewer),
+    })
+}
+
+fn config(uuid: &str, created_at: DateTime<Utc>) -> Value {
+    json!({ "uuid": uuid, "unix_timestamp": created_at.timestamp() })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use serde_json::json;
+
+    use super::*;
+
+    fn sample() -> Value {
+        json!({
+            "VERSION": [],
+            "SETTINGS": [{ "brew_view": 1 }],
+            "WATER": [{ "name": "Third Wave Water" }],
+            "BEANS": [{
+                "name": "Halo Beriti",
+                "roaster": "Square Mile",
+                "roastingDate": "2024-03-01T00:00:00.000+01:00",
+                "buyDate": "",
+                "weight": 250,
+                "cost": 14.5,
+                "aromatics": "Jasmine, Peach",
+                "finished": true,
+                "url": "https://example.com/halo",
+                "rating": 0,
+                "bean_information": [{
+                    "country": "Ethiopia",
+                    "region": "Yirgacheffe",
+                    "farm": "",
+                    "farmer": "",
+                    "processing": "Washed",
+                    "variety": "Heirloom"
+                }],
+                "config": { "uuid": "bean-1", "unix_timestamp": 1_709_300_000 }
+            }],
+            "MILL": [{ "name": "Comandante C40", "config": { "uuid": "mill-1" } }],
+            "PREPARATION": [{ "name": "V60", "type": "V60", "config": { "uuid": "prep-1" } }],
+            "BREWS": [{
+                "bean": "bean-1",
+                "mill": "mill-1",
+                "method_of_preparation": "prep-1",
+                "grind_size": "24",
+                "grind_weight": 15,
+                "brew_temperature": 94,
+                "brew_time": 180,
+                "brew_quantity": 250,
+                "brew_quantity_type": "GR",
+                "note": "Bright",
+                "config": { "uuid": "brew-1", "unix_timestamp": 1_709_400_000 }
+            }]
+        })
+    }
+
+    #[test]
+    fn reads_beans_brews_and_equipment() {
+        let export = parse_export(&sample()).unwrap();
+
+        let bean = &export.beans[0];
+        assert_eq!(bean.name, "Halo Beriti");
+        assert_eq!(bean.roaster.as_deref(), Some("Square Mile"));
+        assert_eq!(bean.roast_date, NaiveDate::from_ymd_opt(2024, 3, 1));
+        assert_eq!(bean.tasting_notes, ["Jasmine", "Peach"]);
+        assert_eq!(bean.producer, None);
+        assert!(bean.finished);
+
+        let brew = &export.brews[0];
+        assert_eq!(brew.bean, "bean-1");
+        assert_eq!(brew.grind_setting, Some(24.0));
+        assert_eq!(brew.water, Some(250.0));
+        assert_eq!(brew.brew_time, Some(180));
+
+        assert_eq!(export.mills[0].name, "Comandante C40");
+        assert_eq!(export.preparations[0].uuid, "prep-1");
+    }
+
+    #[test]
+    fn reports_what_has_nowhere_to_go() {
+        let export = parse_export(&sample()).unwrap();
+
+        let unmapped: Vec<_> = export.unmapped.keys().map(String::as_str).collect();
+        assert_eq!(
+            unmapped,
+            [
+                "beans.bean_information.variety",
+                "beans.url",
+                "brews.note",
+                "water"
+            ]
+        );
+    }
+
+    #[test]
+    fn reads_zipped_exports_with_spilled_brews() {
+        let root = sample();
+        let spilled = json!([{
+            "bean": "bean-1",
+            "grind_weight": 16,
+            "config": { "uuid": "brew-2", "unix_timestamp": 1_709_500_000 }
+        }]);
+
+        let mut archive = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        let options = zip::write::SimpleFileOptions::default();
+        archive.start_file("Beanconqueror.json", options).unwrap();
+        archive
+            .write_all(serde_json::to_vec(&root).unwrap().as_slice())
+            .unwrap();
+        archive
+            .start_file("Beanconqueror_Brews_1.json", options)
+            .unwrap();
+        archive
+            .write_all(serde_json::to_vec(&spilled).unwrap().as_slice())
+            .unwrap();
+        let bytes = archive.finish().unwrap().into_inner();
+
+        let export = read_export(&bytes).unwrap();
+        assert_eq!(export.brews.len(), 2);
+        assert_eq!(export.brews[1].dose, Some(16.0));
+        assert!(read_export(b"{\"SETTINGS\": []}").is_err());
+    }
+
+    #[test]
+    fn brews_without_a_recipe_are_refused() {
+        let brew = LoggedBrew {
+            dose: Some(15.0),
+            water: Some(250.0),
+            ..LoggedBrew::default()
+        };
+        let ids = (BagId::new(1), GearId::new(1), GearId::new(2));
+
+        assert_eq!(brew.missing(), Some("no water temperature"));
+        assert_eq!(
+            brew.new_brew(ids.0, ids.1, ids.2).unwrap_err(),
+            "no water temperature"
+        );
+    }
+
+    #[test]
+    fn single_word_equipment_uses_the_category_as_model() {
+        assert_eq!(
+            make_and_model("Comandante C40 MK4", GearCategory::Grinder),
+            ("Comandante".to_string(), "C40 MK4".to_string())
+        );
+        assert_eq!(
+            make_and_model("AeroPress", GearCategory::Brewer),
+            ("AeroPress".to_string(), "Brewer".to_string())
+        );
+    }
+}
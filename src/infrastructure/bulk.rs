@@ -0,0 +1,294 @@
+use anyhow::{Context, bail};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{AssertSqlSafe, Connection};
+
+use crate::domain::brews::QuickNote;
+use crate::domain::entity_type::EntityType;
+use crate::domain::ids::RoasterId;
+use crate::infrastructure::database::{DatabaseConnection, DatabasePool};
+
+/// The most rows a single bulk request may touch.
+pub const MAX_BULK_ITEMS: usize = 500;
+
+/// A data list that bulk actions can run against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BulkEntity {
+    Roasters,
+    Roasts,
+    Bags,
+    Gear,
+    Brews,
+    Cafes,
+    Cups,
+}
+
+impl BulkEntity {
+    pub const fn entity_type(self) -> EntityType {
+        match self {
+            Self::Roasters => EntityType::Roaster,
+            Self::Roasts => EntityType::Roast,
+            Self::Bags => EntityType::Bag,
+            Self::Gear => EntityType::Gear,
+            Self::Brews => EntityType::Brew,
+            Self::Cafes => EntityType::Cafe,
+            Self::Cups => EntityType::Cup,
+        }
+    }
+
+    const fn table(self) -> &'static str {
+        match self {
+            Self::Roasters => "roasters",
+            Self::Roasts => "roasts",
+            Self::Bags => "bags",
+            Self::Gear => "gear",
+            Self::Brews => "brews",
+            Self::Cafes => "cafes",
+            Self::Cups => "cups",
+        }
+    }
+}
+
+/// What to do to every selected row.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum BulkAction {
+    Delete,
+    /// Finish open bags.
+    Archive,
+    /// Add a quick note to brews.
+    Tag {
+        note: QuickNote,
+    },
+    /// Move roasts to another roaster.
+    AssignRoaster {
+        roaster_id: RoasterId,
+    },
+}
+
+impl BulkAction {
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Delete => "delete",
+            Self::Archive => "archive",
+            Self::Tag { .. } => "tag",
+            Self::AssignRoaster { .. } => "assign_roaster",
+        }
+    }
+
+    pub const fn applies_to(&self, entity: BulkEntity) -> bool {
+        match self {
+            Self::Delete => true,
+            Self::Archive => matches!(entity, BulkEntity::Bags),
+            Self::Tag { .. } => matches!(entity, BulkEntity::Brews),
+            Self::AssignRoaster { .. } => matches!(entity, BulkEntity::Roasts),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkItemResult {
+    pub id: i64,
+    pub ok: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BulkReport {
+    pub succeeded: usize,
+    pub failed: usize,
+    /// One result per requested id, in request order.
+    pub results: Vec<BulkItemResult>,
+}
+
+/// Applies one action to many rows of a list at once.
+pub struct BulkService {
+    pool: DatabasePool,
+}
+
+impl BulkService {
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+
+    /// Apply `action` to each id in a single transaction. Each row gets its
+    /// own savepoint, so a row that fails is rolled back and reported while
+    /// the rest still go through.
+    pub async fn apply(
+        &self,
+        entity: BulkEntity,
+        action: &BulkAction,
+        ids: &[i64],
+        now: DateTime<Utc>,
+    ) -> anyhow::Result<BulkReport> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("failed to begin transaction")?;
+
+        let mut report = BulkReport::default();
+        for &id in ids {
+            let mut savepoint = tx.begin().await.context("failed to begin savepoint")?;
+            let outcome = apply_one(&mut savepoint, entity, action, id, now).await;
+            let error = match outcome {
+                Ok(()) => {
+                    savepoint
+                        .commit()
+                        .await
+                        .context("failed to release savepoint")?;
+                    report.succeeded += 1;
+                    None
+                }
+                Err(err) => {
+                    savepoint
+                        .rollback()
+                        .await
+                        .context("failed to roll back savepoint")?;
+                    report.failed += 1;
+                    Some(err.to_string())
+                }
+            };
+            report.results.push(BulkItemResult {
+                id,
+                ok: error.is_none(),
+                error,
+            });
+        }
+
+        tx.commit().await.context("failed to commit transaction")?;
+        Ok(report)
+    }
+}
+
+async fn apply_one(
+    conn: &mut DatabaseConnection,
+    entity: BulkEntity,
+    action: &BulkAction,
+    id: i64,
+    now: DateTime<Utc>,
+) -> anyhow::Result<()> {
+    match action {
+        BulkAction::Delete => delete_row(conn, entity, id).await,
+        BulkAction::Archive => finish_bag(conn, id, now).await,
+        BulkAction::Tag { note } => tag_brew(conn, id, note).await,
+        BulkAction::AssignRoaster { roaster_id } => assign_roaster(conn, id, *roaster_id).await,
+    }
+}
+
+async fn delete_row(
+    conn: &mut DatabaseConnection,
+    entity: BulkEntity,
+    id: i64,
+) -> anyhow::Result<()> {
+    let result = sqlx::query(AssertSqlSafe(format!(
+        "DELETE FROM {} WHERE id = ?",
+        entity.table()
+    )))
+    .bind(id)
+    .execute(&mut *conn)
+    .await;
+
+    let result = match result {
+        Ok(result) => result,
+        Err(err)
+            if err
+                .as_database_error()
+                .is_some_and(sqlx::error::DatabaseError::is_foreign_key_violation) =>
+        {
+            bail!("still in use");
+        }
+        Err(err) => return Err(err).context("failed to delete"),
+    };
+    if result.rows_affected() == 0 {
+        bail!("not found");
+    }
+
+    // The image purge task removes the row itself.
+    sqlx::query(
+        "UPDATE entity_images SET deleted_at = strftime('%Y-%m-%dT%H:%M:%SZ', 'now') \
+         WHERE entity_type = ? AND entity_id = ? AND deleted_at IS NULL",
+    )
+    .bind(entity.entity_type().as_str())
+    .bind(id)
+    .execute(&mut *conn)
+    .await
+    .context("failed to delete image")?;
+    Ok(())
+}
+
+async fn finish_bag(
+    conn: &mut DatabaseConnection,
+    id: i64,
+    now: DateTime<Utc>,
+) -> anyhow::Result<()> {
+    let closed: Option<bool> = sqlx::query_scalar("SELECT closed FROM bags WHERE id = ?")
+        .bind(id)
+        .fetch_optional(&mut *conn)
+        .await
+        .context("failed to load bag")?;
+    match closed {
+        None => bail!("not found"),
+        Some(true) => bail!("already finished"),
+        Some(false) => {}
+    }
+
+    sqlx::query(
+        "UPDATE bags SET closed = TRUE, finished_at = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?",
+    )
+    .bind(now)
+    .bind(id)
+    .execute(&mut *conn)
+    .await
+    .context("failed to finish bag")?;
+    Ok(())
+}
+
+async fn tag_brew(conn: &mut DatabaseConnection, id: i64, note: &QuickNote) -> anyhow::Result<()> {
+    let raw: Option<Option<String>> =
+        sqlx::query_scalar("SELECT quick_notes FROM brews WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&mut *conn)
+            .await
+            .context("failed to load brew")?;
+    let Some(raw) = raw else {
+        bail!("not found");
+    };
+
+    let mut labels: Vec<String> = raw
+        .filter(|s| !s.is_empty())
+        .map(|s| serde_json::from_str(&s))
+        .transpose()
+        .context("failed to read quick notes")?
+        .unwrap_or_default();
+    if labels.iter().any(|label| label == note.label()) {
+        return Ok(());
+    }
+    labels.push(note.label().to_string());
+
+    sqlx::query("UPDATE brews SET quick_notes = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?")
+        .bind(serde_json::to_string(&labels)?)
+        .bind(id)
+        .execute(&mut *conn)
+        .await
+        .context("failed to tag brew")?;
+    Ok(())
+}
+
+async fn assign_roaster(
+    conn: &mut DatabaseConnection,
+    id: i64,
+    roaster_id: RoasterId,
+) -> anyhow::Result<()> {
+    let result = sqlx::query("UPDATE roasts SET roaster_id = ? WHERE id = ?")
+        .bind(roaster_id.into_inner())
+        .bind(id)
+        .execute(&mut *conn)
+        .await
+        .context("failed to assign roaster")?;
+    if result.rows_affected() == 0 {
+        bail!("not found");
+    }
+    Ok(())
+}
@@ -0,0 +1,39 @@
+use anyhow::{Context, Result};
+
+use crate::infrastructure::integrity::IntegrityReport;
+
+use super::BrewlogClient;
+
+pub struct AdminClient<'a> {
+    inner: &'a BrewlogClient,
+}
+
+impl<'a> AdminClient<'a> {
+    pub(crate) fn new(inner: &'a BrewlogClient) -> Self {
+        Self { inner }
+    }
+
+    pub async fn check_integrity(&self) -> Result<IntegrityReport> {
+        let url = self.inner.endpoint("api/v1/admin/integrity")?;
+        let response = self
+            .inner
+            .request(reqwest::Method::GET, url)
+            .send()
+            .await
+            .context("failed to issue integrity check request")?;
+
+        self.inner.handle_response(response).await
+    }
+
+    pub async fn repair_integrity(&self) -> Result<IntegrityReport> {
+        let url = self.inner.endpoint("api/v1/admin/integrity/repair")?;
+        let response = self
+            .inner
+            .request(reqwest::Method::POST, url)
+            .send()
+            .await
+            .context("failed to issue integrity repair request")?;
+
+        self.inner.handle_response(response).await
+    }
+}
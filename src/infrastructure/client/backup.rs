@@ -1,10 +1,16 @@
+use std::path::Path;
+
 use anyhow::{Context, Result};
 use reqwest::StatusCode;
+use tokio::io::AsyncReadExt;
 
-use crate::infrastructure::backup::BackupData;
+use crate::infrastructure::backup::{BackupData, RestoreSummary};
 
 use super::BrewlogClient;
 
+/// Size of each chunk read from disk when streaming a restore.
+const UPLOAD_CHUNK_SIZE: usize = 256 * 1024;
+
 pub struct BackupClient<'a> {
     inner: &'a BrewlogClient,
 }
@@ -26,6 +32,26 @@ impl<'a> BackupClient<'a> {
         self.inner.handle_response(response).await
     }
 
+    pub async fn export_ndjson(&self) -> Result<String> {
+        let mut url = self.inner.endpoint("api/v1/backup")?;
+        url.query_pairs_mut().append_pair("format", "ndjson");
+        let response = self
+            .inner
+            .request(reqwest::Method::GET, url)
+            .send()
+            .await
+            .context("failed to issue backup export request")?;
+
+        if response.status().is_success() {
+            response
+                .text()
+                .await
+                .context("failed to read backup export body")
+        } else {
+            Err(self.inner.response_error(response).await)
+        }
+    }
+
     pub async fn restore(&self, data: &BackupData) -> Result<()> {
         let url = self.inner.endpoint("api/v1/backup/restore")?;
         let response = self
@@ -41,4 +67,54 @@ impl<'a> BackupClient<'a> {
             _ => Err(self.inner.response_error(response).await),
         }
     }
+
+    /// Stream an NDJSON backup file to the server without reading it into
+    /// memory. `on_progress` receives the number of bytes sent so far.
+    pub async fn restore_stream<F>(
+        &self,
+        path: &Path,
+        resume: bool,
+        on_progress: F,
+    ) -> Result<RestoreSummary>
+    where
+        F: Fn(u64) + Send + Sync + 'static,
+    {
+        let file = tokio::fs::File::open(path)
+            .await
+            .with_context(|| format!("failed to open {}", path.display()))?;
+
+        let chunks = futures_util::stream::unfold(
+            (Some(file), 0_u64, on_progress),
+            |(file, sent, on_progress)| async move {
+                let mut file = file?;
+                let mut buf = vec![0_u8; UPLOAD_CHUNK_SIZE];
+                match file.read(&mut buf).await {
+                    Ok(0) => None,
+                    Ok(n) => {
+                        buf.truncate(n);
+                        let sent = sent + n as u64;
+                        on_progress(sent);
+                        Some((Ok(buf), (Some(file), sent, on_progress)))
+                    }
+                    Err(err) => Some((Err(err), (None, sent, on_progress))),
+                }
+            },
+        );
+
+        let mut url = self.inner.endpoint("api/v1/backup/restore/stream")?;
+        if resume {
+            url.query_pairs_mut().append_pair("resume", "true");
+        }
+
+        let response = self
+            .inner
+            .request(reqwest::Method::POST, url)
+            .header(reqwest::header::CONTENT_TYPE, "application/x-ndjson")
+            .body(reqwest::Body::wrap_stream(chunks))
+            .send()
+            .await
+            .context("failed to issue streaming restore request")?;
+
+        self.inner.handle_response(response).await
+    }
 }
@@ -1,6 +1,7 @@
 use anyhow::{Context, Result};
 use chrono::{DateTime, NaiveDate, Utc};
 
+use crate::domain::bag_ledger::{BagCorrection, BagLedgerEntry, BagTransfer, BagTransferRequest};
 use crate::domain::bags::{BagWithRoast, UpdateBag};
 use crate::domain::ids::{BagId, RoastId};
 
@@ -15,11 +16,16 @@ impl<'a> BagsClient<'a> {
         Self { inner }
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub async fn create(
         &self,
         roast_id: RoastId,
         roast_date: Option<NaiveDate>,
         amount: f64,
+        price: Option<f64>,
+        currency: Option<&str>,
+        best_before: Option<NaiveDate>,
+        batch_code: Option<&str>,
         created_at: Option<DateTime<Utc>>,
     ) -> Result<BagWithRoast> {
         let url = self.inner.endpoint("api/v1/bags")?;
@@ -28,6 +34,18 @@ impl<'a> BagsClient<'a> {
             "roast_date": roast_date.map(|d| d.to_string()),
             "amount": amount,
         });
+        if let Some(price) = price {
+            payload["price"] = serde_json::json!(price);
+        }
+        if let Some(currency) = currency {
+            payload["currency"] = serde_json::json!(currency);
+        }
+        if let Some(best_before) = best_before {
+            payload["best_before"] = serde_json::json!(best_before.to_string());
+        }
+        if let Some(batch_code) = batch_code {
+            payload["batch_code"] = serde_json::json!(batch_code);
+        }
         if let Some(ts) = created_at {
             payload["created_at"] = serde_json::json!(ts);
         }
@@ -72,6 +90,7 @@ impl<'a> BagsClient<'a> {
         self.inner.handle_response(response).await
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub async fn update(
         &self,
         id: BagId,
@@ -79,6 +98,11 @@ impl<'a> BagsClient<'a> {
         closed: Option<bool>,
         finished_at: Option<DateTime<Utc>>,
         created_at: Option<DateTime<Utc>>,
+        low_stock_threshold: Option<f64>,
+        price: Option<f64>,
+        currency: Option<String>,
+        best_before: Option<NaiveDate>,
+        batch_code: Option<String>,
     ) -> Result<BagWithRoast> {
         let url = self.inner.endpoint(&format!("api/v1/bags/{id}"))?;
         let payload = UpdateBag {
@@ -86,6 +110,11 @@ impl<'a> BagsClient<'a> {
             closed,
             finished_at,
             created_at,
+            low_stock_threshold,
+            price,
+            currency,
+            best_before,
+            batch_code,
             ..Default::default()
         };
 
@@ -100,6 +129,65 @@ impl<'a> BagsClient<'a> {
         self.inner.handle_response(response).await
     }
 
+    pub async fn reopen(&self, id: BagId) -> Result<BagWithRoast> {
+        let url = self.inner.endpoint(&format!("api/v1/bags/{id}/reopen"))?;
+        let response = self
+            .inner
+            .request(reqwest::Method::POST, url)
+            .send()
+            .await
+            .context("failed to issue reopen bag request")?;
+
+        self.inner.handle_response(response).await
+    }
+
+    pub async fn adjust(
+        &self,
+        id: BagId,
+        remaining: f64,
+        reason: String,
+    ) -> Result<BagLedgerEntry> {
+        let url = self
+            .inner
+            .endpoint(&format!("api/v1/bags/{id}/adjustments"))?;
+        let response = self
+            .inner
+            .request(reqwest::Method::POST, url)
+            .json(&BagCorrection { remaining, reason })
+            .send()
+            .await
+            .context("failed to issue bag adjustment request")?;
+
+        self.inner.handle_response(response).await
+    }
+
+    pub async fn transfer(&self, id: BagId, to_bag_id: BagId, amount: f64) -> Result<BagTransfer> {
+        let url = self
+            .inner
+            .endpoint(&format!("api/v1/bags/{id}/transfers"))?;
+        let response = self
+            .inner
+            .request(reqwest::Method::POST, url)
+            .json(&BagTransferRequest { to_bag_id, amount })
+            .send()
+            .await
+            .context("failed to issue bag transfer request")?;
+
+        self.inner.handle_response(response).await
+    }
+
+    pub async fn ledger(&self, id: BagId) -> Result<Vec<BagLedgerEntry>> {
+        let url = self.inner.endpoint(&format!("api/v1/bags/{id}/ledger"))?;
+        let response = self
+            .inner
+            .request(reqwest::Method::GET, url)
+            .send()
+            .await
+            .context("failed to issue bag ledger request")?;
+
+        self.inner.handle_response(response).await
+    }
+
     pub async fn delete(&self, id: BagId) -> Result<()> {
         let url = self.inner.endpoint(&format!("api/v1/bags/{id}"))?;
         let response = self
@@ -26,6 +26,9 @@ impl<'a> BrewsClient<'a> {
         filter_paper_id: Option<GearId>,
         water_volume: i32,
         water_temp: f64,
+        bypass_water_ml: Option<i32>,
+        kettle_id: Option<GearId>,
+        actual_water_temp: Option<f64>,
         quick_notes: Vec<QuickNote>,
         brew_time: Option<i32>,
         created_at: Option<DateTime<Utc>>,
@@ -43,8 +46,17 @@ impl<'a> BrewsClient<'a> {
         if let Some(fp_id) = filter_paper_id {
             payload["filter_paper_id"] = serde_json::json!(fp_id);
         }
+        if let Some(ml) = bypass_water_ml {
+            payload["bypass_water_ml"] = serde_json::json!(ml);
+        }
+        if let Some(kettle_id) = kettle_id {
+            payload["kettle_id"] = serde_json::json!(kettle_id);
+        }
+        if let Some(temp) = actual_water_temp {
+            payload["actual_water_temp"] = serde_json::json!(temp);
+        }
         if !quick_notes.is_empty() {
-            let labels: Vec<&str> = quick_notes.iter().map(|n| n.label()).collect();
+            let labels: Vec<&str> = quick_notes.iter().map(QuickNote::label).collect();
             payload["quick_notes"] = serde_json::json!(labels);
         }
         if let Some(bt) = brew_time {
@@ -0,0 +1,91 @@
+use anyhow::{Context, Result};
+use reqwest::StatusCode;
+
+use crate::domain::events::{Event, EventFilter, NewEvent, UpdateEvent};
+use crate::domain::ids::EventId;
+
+use super::BrewlogClient;
+
+pub struct EventsClient<'a> {
+    inner: &'a BrewlogClient,
+}
+
+impl<'a> EventsClient<'a> {
+    pub(crate) fn new(inner: &'a BrewlogClient) -> Self {
+        Self { inner }
+    }
+
+    pub async fn create(&self, payload: &NewEvent) -> Result<Event> {
+        let url = self.inner.endpoint("api/v1/events")?;
+        let response = self
+            .inner
+            .request(reqwest::Method::POST, url)
+            .json(payload)
+            .send()
+            .await
+            .context("failed to issue create event request")?;
+
+        self.inner.handle_response(response).await
+    }
+
+    pub async fn list(&self, filter: EventFilter) -> Result<Vec<Event>> {
+        let mut url = self.inner.endpoint("api/v1/events")?;
+        if let Some(roaster_id) = filter.roaster_id {
+            url.query_pairs_mut()
+                .append_pair("roaster", &roaster_id.to_string());
+        }
+        if let Some(cafe_id) = filter.cafe_id {
+            url.query_pairs_mut()
+                .append_pair("cafe", &cafe_id.to_string());
+        }
+
+        let response = self
+            .inner
+            .request(reqwest::Method::GET, url)
+            .send()
+            .await
+            .context("failed to issue list events request")?;
+
+        self.inner.handle_response(response).await
+    }
+
+    pub async fn get(&self, id: EventId) -> Result<Event> {
+        let url = self.inner.endpoint(&format!("api/v1/events/{id}"))?;
+        let response = self
+            .inner
+            .request(reqwest::Method::GET, url)
+            .send()
+            .await
+            .context("failed to issue get event request")?;
+
+        self.inner.handle_response(response).await
+    }
+
+    pub async fn update(&self, id: EventId, payload: &UpdateEvent) -> Result<Event> {
+        let url = self.inner.endpoint(&format!("api/v1/events/{id}"))?;
+        let response = self
+            .inner
+            .request(reqwest::Method::PUT, url)
+            .json(payload)
+            .send()
+            .await
+            .context("failed to issue update event request")?;
+
+        self.inner.handle_response(response).await
+    }
+
+    pub async fn delete(&self, id: EventId) -> Result<()> {
+        let url = self.inner.endpoint(&format!("api/v1/events/{id}"))?;
+        let response = self
+            .inner
+            .request(reqwest::Method::DELETE, url)
+            .send()
+            .await
+            .context("failed to issue delete event request")?;
+
+        match response.status() {
+            StatusCode::NO_CONTENT => Ok(()),
+            _ => Err(self.inner.response_error(response).await),
+        }
+    }
+}
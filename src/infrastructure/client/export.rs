@@ -0,0 +1,27 @@
+use anyhow::{Context, Result};
+use serde_json::Value;
+
+use super::BrewlogClient;
+
+pub struct ExportClient<'a> {
+    inner: &'a BrewlogClient,
+}
+
+impl<'a> ExportClient<'a> {
+    pub(crate) fn new(inner: &'a BrewlogClient) -> Self {
+        Self { inner }
+    }
+
+    /// Everything as a `Beanconqueror.json` document.
+    pub async fn beanconqueror(&self) -> Result<Value> {
+        let url = self.inner.endpoint("api/v1/export/beanconqueror")?;
+        let response = self
+            .inner
+            .request(reqwest::Method::GET, url)
+            .send()
+            .await
+            .context("failed to issue export request")?;
+
+        self.inner.handle_response(response).await
+    }
+}
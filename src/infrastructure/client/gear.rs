@@ -1,7 +1,6 @@
 use anyhow::{Context, Result};
-use chrono::{DateTime, Utc};
 
-use crate::domain::gear::{Gear, UpdateGear};
+use crate::domain::gear::{Gear, NewGear, Restock, UpdateGear};
 use crate::domain::ids::GearId;
 
 use super::BrewlogClient;
@@ -15,27 +14,12 @@ impl<'a> GearClient<'a> {
         Self { inner }
     }
 
-    pub async fn create(
-        &self,
-        category: &str,
-        make: String,
-        model: String,
-        created_at: Option<DateTime<Utc>>,
-    ) -> Result<Gear> {
+    pub async fn create(&self, gear: &NewGear) -> Result<Gear> {
         let url = self.inner.endpoint("api/v1/gear")?;
-        let mut payload = serde_json::json!({
-            "category": category,
-            "make": make,
-            "model": model,
-        });
-        if let Some(ts) = created_at {
-            payload["created_at"] = serde_json::json!(ts);
-        }
-
         let response = self
             .inner
             .request(reqwest::Method::POST, url)
-            .json(&payload)
+            .json(gear)
             .send()
             .await
             .context("failed to issue create gear request")?;
@@ -71,24 +55,12 @@ impl<'a> GearClient<'a> {
         self.inner.handle_response(response).await
     }
 
-    pub async fn update(
-        &self,
-        id: GearId,
-        make: Option<String>,
-        model: Option<String>,
-        created_at: Option<DateTime<Utc>>,
-    ) -> Result<Gear> {
+    pub async fn update(&self, id: GearId, changes: &UpdateGear) -> Result<Gear> {
         let url = self.inner.endpoint(&format!("api/v1/gear/{id}"))?;
-        let payload = UpdateGear {
-            make,
-            model,
-            created_at,
-        };
-
         let response = self
             .inner
             .request(reqwest::Method::PUT, url)
-            .json(&payload)
+            .json(changes)
             .send()
             .await
             .context("failed to issue update gear request")?;
@@ -96,6 +68,19 @@ impl<'a> GearClient<'a> {
         self.inner.handle_response(response).await
     }
 
+    pub async fn restock(&self, id: GearId, restock: &Restock) -> Result<Gear> {
+        let url = self.inner.endpoint(&format!("api/v1/gear/{id}/restock"))?;
+        let response = self
+            .inner
+            .request(reqwest::Method::POST, url)
+            .json(restock)
+            .send()
+            .await
+            .context("failed to issue restock gear request")?;
+
+        self.inner.handle_response(response).await
+    }
+
     pub async fn delete(&self, id: GearId) -> Result<()> {
         let url = self.inner.endpoint(&format!("api/v1/gear/{id}"))?;
         let response = self
@@ -0,0 +1,86 @@
+use anyhow::{Context, Result};
+use reqwest::StatusCode;
+
+use crate::domain::green_coffees::{GreenCoffee, NewGreenCoffee, UpdateGreenCoffee};
+use crate::domain::ids::GreenCoffeeId;
+
+use super::BrewlogClient;
+
+pub struct GreenCoffeesClient<'a> {
+    inner: &'a BrewlogClient,
+}
+
+impl<'a> GreenCoffeesClient<'a> {
+    pub(crate) fn new(inner: &'a BrewlogClient) -> Self {
+        Self { inner }
+    }
+
+    pub async fn create(&self, payload: &NewGreenCoffee) -> Result<GreenCoffee> {
+        let url = self.inner.endpoint("api/v1/green-coffees")?;
+        let response = self
+            .inner
+            .request(reqwest::Method::POST, url)
+            .json(payload)
+            .send()
+            .await
+            .context("failed to issue create green coffee request")?;
+
+        self.inner.handle_response(response).await
+    }
+
+    pub async fn list(&self) -> Result<Vec<GreenCoffee>> {
+        let url = self.inner.endpoint("api/v1/green-coffees")?;
+        let response = self
+            .inner
+            .request(reqwest::Method::GET, url)
+            .send()
+            .await
+            .context("failed to issue list green coffees request")?;
+
+        self.inner.handle_response(response).await
+    }
+
+    pub async fn get(&self, id: GreenCoffeeId) -> Result<GreenCoffee> {
+        let url = self.inner.endpoint(&format!("api/v1/green-coffees/{id}"))?;
+        let response = self
+            .inner
+            .request(reqwest::Method::GET, url)
+            .send()
+            .await
+            .context("failed to issue get green coffee request")?;
+
+        self.inner.handle_response(response).await
+    }
+
+    pub async fn update(
+        &self,
+        id: GreenCoffeeId,
+        payload: &UpdateGreenCoffee,
+    ) -> Result<GreenCoffee> {
+        let url = self.inner.endpoint(&format!("api/v1/green-coffees/{id}"))?;
+        let response = self
+            .inner
+            .request(reqwest::Method::PUT, url)
+            .json(payload)
+            .send()
+            .await
+            .context("failed to issue update green coffee request")?;
+
+        self.inner.handle_response(response).await
+    }
+
+    pub async fn delete(&self, id: GreenCoffeeId) -> Result<()> {
+        let url = self.inner.endpoint(&format!("api/v1/green-coffees/{id}"))?;
+        let response = self
+            .inner
+            .request(reqwest::Method::DELETE, url)
+            .send()
+            .await
+            .context("failed to issue delete green coffee request")?;
+
+        match response.status() {
+            StatusCode::NO_CONTENT => Ok(()),
+            _ => Err(self.inner.response_error(response).await),
+        }
+    }
+}
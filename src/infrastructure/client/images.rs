@@ -0,0 +1,98 @@
+use anyhow::{Context, Result, bail};
+use reqwest::StatusCode;
+
+use crate::domain::entity_type::EntityType;
+use crate::infrastructure::image_processing::encode_data_url;
+
+use super::BrewlogClient;
+
+/// An entity's full-size image as the server stores it.
+#[derive(Debug)]
+pub struct DownloadedImage {
+    pub content_type: String,
+    pub data: Vec<u8>,
+}
+
+pub struct ImagesClient<'a> {
+    inner: &'a BrewlogClient,
+}
+
+impl<'a> ImagesClient<'a> {
+    pub(crate) fn new(inner: &'a BrewlogClient) -> Self {
+        Self { inner }
+    }
+
+    /// Upload an image, given as a `data:` URL, for an entity.
+    pub async fn upload(&self, entity_type: EntityType, id: i64, data_url: &str) -> Result<()> {
+        let url = self
+            .inner
+            .endpoint(&format!("api/v1/{entity_type}/{id}/image"))?;
+        let response = self
+            .inner
+            .request(reqwest::Method::PUT, url)
+            .json(&serde_json::json!({ "image": data_url }))
+            .send()
+            .await
+            .context("failed to issue image upload request")?;
+
+        match response.status() {
+            StatusCode::NO_CONTENT => Ok(()),
+            _ => Err(self.inner.response_error(response).await),
+        }
+    }
+
+    /// Upload raw image bytes for an entity. The type is worked out from
+    /// the bytes, so JPEG, PNG, WebP and HEIC files all work as they are.
+    pub async fn upload_bytes(&self, entity_type: EntityType, id: i64, bytes: &[u8]) -> Result<()> {
+        let data_url = encode_data_url(bytes, None);
+        if data_url.starts_with("data:application/octet-stream") {
+            bail!("not a recognised image file");
+        }
+        self.upload(entity_type, id, &data_url).await
+    }
+
+    pub async fn download(&self, entity_type: EntityType, id: i64) -> Result<DownloadedImage> {
+        let url = self
+            .inner
+            .endpoint(&format!("api/v1/{entity_type}/{id}/image"))?;
+        let response = self
+            .inner
+            .request(reqwest::Method::GET, url)
+            .send()
+            .await
+            .context("failed to issue image download request")?;
+
+        if !response.status().is_success() {
+            return Err(self.inner.response_error(response).await);
+        }
+        let content_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or("application/octet-stream")
+            .to_string();
+        let data = response
+            .bytes()
+            .await
+            .context("failed to read image body")?
+            .to_vec();
+        Ok(DownloadedImage { content_type, data })
+    }
+
+    pub async fn delete(&self, entity_type: EntityType, id: i64) -> Result<()> {
+        let url = self
+            .inner
+            .endpoint(&format!("api/v1/{entity_type}/{id}/image"))?;
+        let response = self
+            .inner
+            .request(reqwest::Method::DELETE, url)
+            .send()
+            .await
+            .context("failed to issue image delete request")?;
+
+        match response.status() {
+            StatusCode::NO_CONTENT => Ok(()),
+            _ => Err(self.inner.response_error(response).await),
+        }
+    }
+}
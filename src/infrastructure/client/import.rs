@@ -0,0 +1,50 @@
+use anyhow::{Context, Result};
+
+use crate::application::services::{Backfill, BackfillReport, ImportReport};
+
+use super::BrewlogClient;
+
+pub struct ImportClient<'a> {
+    inner: &'a BrewlogClient,
+}
+
+impl<'a> ImportClient<'a> {
+    pub(crate) fn new(inner: &'a BrewlogClient) -> Self {
+        Self { inner }
+    }
+
+    /// Upload a Beanconqueror export (the zip, or its `Beanconqueror.json`).
+    pub async fn beanconqueror(
+        &self,
+        export: Vec<u8>,
+        roaster_country: &str,
+    ) -> Result<ImportReport> {
+        let mut url = self.inner.endpoint("api/v1/import/beanconqueror")?;
+        url.query_pairs_mut()
+            .append_pair("roaster_country", roaster_country);
+        let response = self
+            .inner
+            .request(reqwest::Method::POST, url)
+            .header(reqwest::header::CONTENT_TYPE, "application/octet-stream")
+            .body(export)
+            .send()
+            .await
+            .context("failed to issue import request")?;
+
+        self.inner.handle_response(response).await
+    }
+
+    /// Log historical brews and cups, each at its own `created_at`.
+    pub async fn backfill(&self, backfill: &Backfill) -> Result<BackfillReport> {
+        let url = self.inner.endpoint("api/v1/backfill")?;
+        let response = self
+            .inner
+            .request(reqwest::Method::POST, url)
+            .json(backfill)
+            .send()
+            .await
+            .context("failed to issue backfill request")?;
+
+        self.inner.handle_response(response).await
+    }
+}
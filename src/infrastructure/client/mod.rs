@@ -1,18 +1,48 @@
+pub mod admin;
 pub mod backup;
 pub mod bags;
 pub mod brews;
 pub mod cafes;
 pub mod cups;
+pub mod events;
+pub mod export;
 pub mod gear;
+pub mod green_coffees;
+pub mod images;
+pub mod import;
+pub mod roast_batches;
 pub mod roasters;
 pub mod roasts;
+pub mod stats;
 pub mod timeline;
 pub mod tokens;
+pub mod trips;
 
-use anyhow::{Context, Result, anyhow};
-use reqwest::{Client, Url};
+use anyhow::{Context, Result};
+use reqwest::{Client, StatusCode, Url};
+use thiserror::Error;
 
 use crate::application::errors::ErrorResponse;
+use crate::domain::ErrorCode;
+use crate::domain::validation::FieldError;
+
+/// A request the server refused. Callers that need to react to a specific
+/// failure can downcast the `anyhow::Error` to this and match on `code`.
+#[derive(Debug, Error)]
+#[error("request failed ({status}): {message}")]
+pub struct ApiRequestError {
+    pub status: StatusCode,
+    pub code: ErrorCode,
+    pub message: String,
+    pub fields: Vec<FieldError>,
+}
+
+impl ApiRequestError {
+    /// The server's error code, if `err` came from a refused request.
+    pub fn code_of(err: &anyhow::Error) -> Option<ErrorCode> {
+        err.downcast_ref::<Self>().map(|err| err.code)
+    }
+}
 
 pub struct BrewlogClient {
     base_url: Url,
@@ -46,10 +76,33 @@ impl BrewlogClient {
         Self::new(url)
     }
 
+    /// Authenticate with `token` instead of `BREWLOG_TOKEN`.
+    #[must_use]
+    pub fn with_token(mut self, token: String) -> Self {
+        self.token = Some(token);
+        self
+    }
+
+    pub fn admin(&self) -> admin::AdminClient<'_> {
+        admin::AdminClient::new(self)
+    }
+
     pub fn backup(&self) -> backup::BackupClient<'_> {
         backup::BackupClient::new(self)
     }
 
+    pub fn export(&self) -> export::ExportClient<'_> {
+        export::ExportClient::new(self)
+    }
+
+    pub fn images(&self) -> images::ImagesClient<'_> {
+        images::ImagesClient::new(self)
+    }
+
+    pub fn import(&self) -> import::ImportClient<'_> {
+        import::ImportClient::new(self)
+    }
+
     pub fn roasters(&self) -> roasters::RoastersClient<'_> {
         roasters::RoastersClient::new(self)
     }
@@ -82,6 +135,26 @@ impl BrewlogClient {
         cups::CupsClient::new(self)
     }
 
+    pub fn events(&self) -> events::EventsClient<'_> {
+        events::EventsClient::new(self)
+    }
+
+    pub fn trips(&self) -> trips::TripsClient<'_> {
+        trips::TripsClient::new(self)
+    }
+
+    pub fn green_coffees(&self) -> green_coffees::GreenCoffeesClient<'_> {
+        green_coffees::GreenCoffeesClient::new(self)
+    }
+
+    pub fn roast_batches(&self) -> roast_batches::RoastBatchesClient<'_> {
+        roast_batches::RoastBatchesClient::new(self)
+    }
+
+    pub fn stats(&self) -> stats::StatsClient<'_> {
+        stats::StatsClient::new(self)
+    }
+
     pub fn timeline(&self) -> timeline::TimelineClient<'_> {
         timeline::TimelineClient::new(self)
     }
@@ -119,11 +192,20 @@ impl BrewlogClient {
         let status = response.status();
         let bytes = response.bytes().await.unwrap_or_default();
 
-        if let Ok(err) = serde_json::from_slice::<ErrorResponse>(&bytes) {
-            return anyhow!("request failed ({status}): {}", err.message);
-        }
-
-        let message = String::from_utf8_lossy(&bytes);
-        anyhow!("request failed ({status}): {message}")
+        let error = match serde_json::from_slice::<ErrorResponse>(&bytes) {
+            Ok(err) => ApiRequestError {
+                status,
+                code: err.code,
+                message: err.message,
+                fields: err.fields,
+            },
+            Err(_) => ApiRequestError {
+                status,
+                code: ErrorCode::Unknown,
+                message: String::from_utf8_lossy(&bytes).into_owned(),
+                fields: Vec::new(),
+            },
+        };
+        error.into()
     }
 }
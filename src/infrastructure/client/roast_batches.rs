@@ -0,0 +1,87 @@
+use anyhow::{Context, Result};
+use reqwest::StatusCode;
+
+use crate::domain::ids::{GreenCoffeeId, RoastBatchId};
+use crate::domain::roast_batches::{NewRoastBatch, RoastBatch, UpdateRoastBatch};
+
+use super::BrewlogClient;
+
+pub struct RoastBatchesClient<'a> {
+    inner: &'a BrewlogClient,
+}
+
+impl<'a> RoastBatchesClient<'a> {
+    pub(crate) fn new(inner: &'a BrewlogClient) -> Self {
+        Self { inner }
+    }
+
+    pub async fn create(&self, payload: &NewRoastBatch) -> Result<RoastBatch> {
+        let url = self.inner.endpoint("api/v1/roast-batches")?;
+        let response = self
+            .inner
+            .request(reqwest::Method::POST, url)
+            .json(payload)
+            .send()
+            .await
+            .context("failed to issue create roast batch request")?;
+
+        self.inner.handle_response(response).await
+    }
+
+    pub async fn list(&self, green_coffee_id: Option<GreenCoffeeId>) -> Result<Vec<RoastBatch>> {
+        let mut url = self.inner.endpoint("api/v1/roast-batches")?;
+        if let Some(green_coffee_id) = green_coffee_id {
+            url.query_pairs_mut()
+                .append_pair("green_coffee", &green_coffee_id.to_string());
+        }
+
+        let response = self
+            .inner
+            .request(reqwest::Method::GET, url)
+            .send()
+            .await
+            .context("failed to issue list roast batches request")?;
+
+        self.inner.handle_response(response).await
+    }
+
+    pub async fn get(&self, id: RoastBatchId) -> Result<RoastBatch> {
+        let url = self.inner.endpoint(&format!("api/v1/roast-batches/{id}"))?;
+        let response = self
+            .inner
+            .request(reqwest::Method::GET, url)
+            .send()
+            .await
+            .context("failed to issue get roast batch request")?;
+
+        self.inner.handle_response(response).await
+    }
+
+    pub async fn update(&self, id: RoastBatchId, payload: &UpdateRoastBatch) -> Result<RoastBatch> {
+        let url = self.inner.endpoint(&format!("api/v1/roast-batches/{id}"))?;
+        let response = self
+            .inner
+            .request(reqwest::Method::PUT, url)
+            .json(payload)
+            .send()
+            .await
+            .context("failed to issue update roast batch request")?;
+
+        self.inner.handle_response(response).await
+    }
+
+    pub async fn delete(&self, id: RoastBatchId) -> Result<()> {
+        let url = self.inner.endpoint(&format!("api/v1/roast-batches/{id}"))?;
+        let response = self
+            .inner
+            .request(reqwest::Method::DELETE, url)
+            .send()
+            .await
+            .context("failed to issue delete roast batch request")?;
+
+        match response.status() {
+            StatusCode::NO_CONTENT => Ok(()),
+            _ => Err(self.inner.response_error(response).await),
+        }
+    }
+}
@@ -2,7 +2,7 @@ use anyhow::{Context, Result};
 use reqwest::StatusCode;
 
 use crate::domain::ids::{RoastId, RoasterId};
-use crate::domain::roasts::{NewRoast, RoastWithRoaster, UpdateRoast};
+use crate::domain::roasts::{NewLotRequest, NewRoast, RoastWithRoaster, UpdateRoast};
 
 use super::BrewlogClient;
 
@@ -70,6 +70,19 @@ impl<'a> RoastsClient<'a> {
         self.inner.handle_response(response).await
     }
 
+    pub async fn new_lot(&self, id: RoastId, harvest_year: i32) -> Result<RoastWithRoaster> {
+        let url = self.inner.endpoint(&format!("api/v1/roasts/{id}/lots"))?;
+        let response = self
+            .inner
+            .request(reqwest::Method::POST, url)
+            .json(&NewLotRequest { harvest_year })
+            .send()
+            .await
+            .context("failed to issue new lot request")?;
+
+        self.inner.handle_response(response).await
+    }
+
     pub async fn delete(&self, id: RoastId) -> Result<()> {
         let url = self.inner.endpoint(&format!("api/v1/roasts/{id}"))?;
         let response = self
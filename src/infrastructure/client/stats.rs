@@ -0,0 +1,27 @@
+use anyhow::{Context, Result};
+
+use crate::domain::stats::CachedStats;
+
+use super::BrewlogClient;
+
+pub struct StatsClient<'a> {
+    inner: &'a BrewlogClient,
+}
+
+impl<'a> StatsClient<'a> {
+    pub(crate) fn new(inner: &'a BrewlogClient) -> Self {
+        Self { inner }
+    }
+
+    pub async fn get(&self) -> Result<CachedStats> {
+        let url = self.inner.endpoint("api/v1/stats")?;
+        let response = self
+            .inner
+            .request(reqwest::Method::GET, url)
+            .send()
+            .await
+            .context("failed to issue get stats request")?;
+
+        self.inner.handle_response(response).await
+    }
+}
@@ -1,6 +1,9 @@
 use anyhow::{Context, Result};
 use reqwest::StatusCode;
 
+use crate::domain::ids::{TimelineEventId, UserId};
+use crate::domain::timeline::TimelineEvent;
+
 use super::BrewlogClient;
 
 pub struct TimelineClient<'a> {
@@ -12,6 +15,36 @@ impl<'a> TimelineClient<'a> {
         Self { inner }
     }
 
+    /// Events in the order they were logged, optionally only those after
+    /// `after`.
+    pub async fn logged(
+        &self,
+        after: Option<TimelineEventId>,
+        limit: u32,
+        user: Option<UserId>,
+    ) -> Result<Vec<TimelineEvent>> {
+        let mut url = self.inner.endpoint("api/v1/timeline")?;
+        {
+            let mut pairs = url.query_pairs_mut();
+            pairs.append_pair("limit", &limit.to_string());
+            if let Some(after) = after {
+                pairs.append_pair("after", &after.to_string());
+            }
+            if let Some(user) = user {
+                pairs.append_pair("user", &user.to_string());
+            }
+        }
+
+        let response = self
+            .inner
+            .request(reqwest::Method::GET, url)
+            .send()
+            .await
+            .context("failed to issue list timeline request")?;
+
+        self.inner.handle_response(response).await
+    }
+
     pub async fn rebuild(&self) -> Result<()> {
         let url = self.inner.endpoint("api/v1/timeline/rebuild")?;
         let response = self
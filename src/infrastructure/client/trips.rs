@@ -0,0 +1,94 @@
+use anyhow::{Context, Result};
+use reqwest::StatusCode;
+
+use crate::domain::ids::TripId;
+use crate::domain::trips::{NewTrip, Trip, TripSummary, UpdateTrip};
+
+use super::BrewlogClient;
+
+pub struct TripsClient<'a> {
+    inner: &'a BrewlogClient,
+}
+
+impl<'a> TripsClient<'a> {
+    pub(crate) fn new(inner: &'a BrewlogClient) -> Self {
+        Self { inner }
+    }
+
+    pub async fn create(&self, payload: &NewTrip) -> Result<Trip> {
+        let url = self.inner.endpoint("api/v1/trips")?;
+        let response = self
+            .inner
+            .request(reqwest::Method::POST, url)
+            .json(payload)
+            .send()
+            .await
+            .context("failed to issue create trip request")?;
+
+        self.inner.handle_response(response).await
+    }
+
+    pub async fn list(&self) -> Result<Vec<Trip>> {
+        let url = self.inner.endpoint("api/v1/trips")?;
+        let response = self
+            .inner
+            .request(reqwest::Method::GET, url)
+            .send()
+            .await
+            .context("failed to issue list trips request")?;
+
+        self.inner.handle_response(response).await
+    }
+
+    pub async fn get(&self, id: TripId) -> Result<Trip> {
+        let url = self.inner.endpoint(&format!("api/v1/trips/{id}"))?;
+        let response = self
+            .inner
+            .request(reqwest::Method::GET, url)
+            .send()
+            .await
+            .context("failed to issue get trip request")?;
+
+        self.inner.handle_response(response).await
+    }
+
+    pub async fn summary(&self, id: TripId) -> Result<TripSummary> {
+        let url = self.inner.endpoint(&format!("api/v1/trips/{id}/summary"))?;
+        let response = self
+            .inner
+            .request(reqwest::Method::GET, url)
+            .send()
+            .await
+            .context("failed to issue trip summary request")?;
+
+        self.inner.handle_response(response).await
+    }
+
+    pub async fn update(&self, id: TripId, payload: &UpdateTrip) -> Result<Trip> {
+        let url = self.inner.endpoint(&format!("api/v1/trips/{id}"))?;
+        let response = self
+            .inner
+            .request(reqwest::Method::PUT, url)
+            .json(payload)
+            .send()
+            .await
+            .context("failed to issue update trip request")?;
+
+        self.inner.handle_response(response).await
+    }
+
+    pub async fn delete(&self, id: TripId) -> Result<()> {
+        let url = self.inner.endpoint(&format!("api/v1/trips/{id}"))?;
+        let response = self
+            .inner
+            .request(reqwest::Method::DELETE, url)
+            .send()
+            .await
+            .context("failed to issue delete trip request")?;
+
+        match response.status() {
+            StatusCode::NO_CONTENT => Ok(()),
+            _ => Err(self.inner.response_error(response).await),
+        }
+    }
+}
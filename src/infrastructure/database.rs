@@ -5,6 +5,7 @@ use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqliteSynchronous};
 pub type DatabasePool = sqlx::SqlitePool;
 type PoolOptions = sqlx::sqlite::SqlitePoolOptions;
 pub type DatabaseTransaction<'a> = sqlx::Transaction<'a, sqlx::Sqlite>;
+pub type DatabaseConnection = sqlx::SqliteConnection;
 pub type DatabaseRow = sqlx::sqlite::SqliteRow;
 pub type DatabaseDriver = sqlx::Sqlite;
 
@@ -14,6 +15,21 @@ pub struct Database {
 
 impl Database {
     pub async fn connect(database_url: &str) -> anyhow::Result<Self> {
+        Self::open(database_url, PoolOptions::new()).await
+    }
+
+    /// A private in-memory database that lives as long as the pool. The
+    /// pool's only connection is never recycled, since closing it would
+    /// drop every row.
+    pub async fn in_memory() -> anyhow::Result<Self> {
+        let pool_options = PoolOptions::new()
+            .min_connections(1)
+            .idle_timeout(None)
+            .max_lifetime(None);
+        Self::open("sqlite::memory:", pool_options).await
+    }
+
+    async fn open(database_url: &str, pool_options: PoolOptions) -> anyhow::Result<Self> {
         let pool = {
             use std::str::FromStr;
 
@@ -27,7 +43,7 @@ impl Database {
                 .pragma("temp_store", "MEMORY")
                 .busy_timeout(std::time::Duration::from_secs(5));
 
-            PoolOptions::new()
+            pool_options
                 .max_connections(1)
                 .connect_with(options)
                 .await
@@ -55,3 +71,16 @@ impl Database {
             .context("database migration failed")
     }
 }
+
+/// Parse a `GROUP_CONCAT` of ids, in ascending order. The error names the
+/// entry that isn't an id, for callers to wrap in their own error type.
+pub(crate) fn parse_id_list<T: From<i64>>(raw: Option<&str>) -> Result<Vec<T>, String> {
+    let mut ids = raw
+        .unwrap_or_default()
+        .split(',')
+        .filter(|id| !id.is_empty())
+        .map(|id| id.parse::<i64>().map_err(|err| format!("'{id}': {err}")))
+        .collect::<Result<Vec<_>, _>>()?;
+    ids.sort_unstable();
+    Ok(ids.into_iter().map(T::from).collect())
+}
@@ -0,0 +1,35 @@
+//! Sample data bundled for `serve --demo`: a small backup covering every
+//! kind of entity, restored into a fresh in-memory database at startup.
+
+use anyhow::Context;
+
+use crate::infrastructure::backup::{BackupData, BackupService};
+
+const SAMPLE_BACKUP: &str = include_str!("../../demo/sample-backup.json");
+
+pub fn sample_backup() -> anyhow::Result<BackupData> {
+    serde_json::from_str(SAMPLE_BACKUP).context("failed to parse bundled demo data")
+}
+
+/// Restore the sample data. The backup carries no timeline events, so the
+/// caller should trigger a timeline rebuild afterwards.
+pub async fn load_sample(backup_service: &BackupService) -> anyhow::Result<()> {
+    backup_service
+        .restore(sample_backup()?)
+        .await
+        .context("failed to load demo data")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sample_backup_parses() {
+        let data = sample_backup().unwrap();
+        assert!(!data.roasters.is_empty());
+        assert!(!data.brews.is_empty());
+        assert!(!data.cups.is_empty());
+        assert!(data.timeline_events.is_empty());
+    }
+}
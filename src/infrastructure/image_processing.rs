@@ -2,28 +2,135 @@ use anyhow::{Context, bail};
 use base64::Engine;
 use image::{DynamicImage, ImageReader};
 use std::io::Cursor;
-
-/// Maximum dimension (width or height) for the full-size image.
-const MAX_FULL_SIZE: u32 = 1200;
-
-/// Maximum dimension (width or height) for the thumbnail.
-const MAX_THUMBNAIL_SIZE: u32 = 200;
-
-/// Maximum allowed input dimension (width or height) to prevent decompression bombs.
-const MAX_INPUT_DIMENSION: u32 = 10_000;
+use thiserror::Error;
 
 /// Maximum memory the decoder may allocate (256 MB).
 const MAX_DECODER_ALLOC: u64 = 256 * 1024 * 1024;
 
-/// Allowed MIME types in data URLs.
-const ALLOWED_MIMES: &[&str] = &["image/jpeg", "image/png", "image/webp"];
-
-/// JPEG quality for the full-size image (0-100).
-const JPEG_QUALITY_FULL: u8 = 85;
+/// Smallest request body limit for routes that carry an image (1 MB).
+const MIN_IMAGE_BODY_LIMIT: usize = 1024 * 1024;
+
+/// Allowed MIME types in data URLs. HEIC is always accepted here so that a
+/// server built without the `heic` feature can explain why it can't decode it.
+const ALLOWED_MIMES: &[&str] = &[
+    "image/jpeg",
+    "image/png",
+    "image/webp",
+    "image/heic",
+    "image/heif",
+];
+
+/// ISO base media file brands used by HEIC/HEIF stills, as found in the
+/// `ftyp` box at the start of the file.
+const HEIF_BRANDS: &[&[u8; 4]] = &[
+    b"heic", b"heix", b"heim", b"heis", b"hevc", b"hevx", b"mif1", b"msf1",
+];
 
 /// JPEG quality for the thumbnail (0-100).
 const JPEG_QUALITY_THUMBNAIL: u8 = 80;
 
+/// Limits and output sizes for uploaded images, set at server startup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ImageSettings {
+    /// Longest side of the stored image; larger uploads are downscaled.
+    pub max_dimension: u32,
+    /// Longest side of the thumbnail.
+    pub thumbnail_size: u32,
+    /// JPEG quality for the stored image (1-100).
+    pub jpeg_quality: u8,
+    /// Uploads wider or taller than this are rejected before decoding, which
+    /// also guards against decompression bombs.
+    pub max_upload_dimension: u32,
+    /// Largest accepted image file, in bytes.
+    pub max_file_size: usize,
+}
+
+impl Default for ImageSettings {
+    fn default() -> Self {
+        Self {
+            max_dimension: 1200,
+            thumbnail_size: 200,
+            jpeg_quality: 85,
+            max_upload_dimension: 10_000,
+            max_file_size: 10 * 1024 * 1024,
+        }
+    }
+}
+
+impl ImageSettings {
+    pub fn new(
+        max_dimension: u32,
+        thumbnail_size: u32,
+        jpeg_quality: u8,
+        max_upload_dimension: u32,
+        max_file_size: usize,
+    ) -> anyhow::Result<Self> {
+        if !(1..=100).contains(&jpeg_quality) {
+            bail!("image JPEG quality must be between 1 and 100, got {jpeg_quality}");
+        }
+        if thumbnail_size == 0 || thumbnail_size > max_dimension {
+            bail!(
+                "image thumbnail size must be between 1 and the maximum dimension \
+                 ({max_dimension}), got {thumbnail_size}"
+            );
+        }
+        if max_dimension > max_upload_dimension {
+            bail!(
+                "image maximum dimension ({max_dimension}) must not exceed the maximum \
+                 upload dimension ({max_upload_dimension})"
+            );
+        }
+        if max_file_size == 0 {
+            bail!("image maximum file size must be greater than zero");
+        }
+        Ok(Self {
+            max_dimension,
+            thumbnail_size,
+            jpeg_quality,
+            max_upload_dimension,
+            max_file_size,
+        })
+    }
+
+    /// Request body limit for routes that carry an image. Images arrive
+    /// base64-encoded, often inside a URL-encoded form, so the body can be
+    /// roughly twice the size of the file. A small file limit still leaves
+    /// room for the rest of the form, and for the size check to explain
+    /// itself rather than the request being cut off.
+    pub fn body_limit(&self) -> usize {
+        self.max_file_size
+            .saturating_mul(2)
+            .max(MIN_IMAGE_BODY_LIMIT)
+    }
+}
+
+/// Why an upload couldn't be turned into a stored image.
+#[derive(Debug, Error)]
+pub enum ImageError {
+    #[error("image is {} but the limit is {}", format_mib(*.size), format_mib(*.limit))]
+    FileTooLarge { size: usize, limit: usize },
+    #[error("image is {width}x{height} pixels but neither side may exceed {limit}")]
+    DimensionsTooLarge { width: u32, height: u32, limit: u32 },
+    #[error(transparent)]
+    Invalid(#[from] anyhow::Error),
+}
+
+impl ImageError {
+    /// Whether the upload broke one of the configured limits, rather than
+    /// being unreadable.
+    pub fn is_too_large(&self) -> bool {
+        matches!(
+            self,
+            Self::FileTooLarge { .. } | Self::DimensionsTooLarge { .. }
+        )
+    }
+}
+
+#[allow(clippy::cast_precision_loss)] // file sizes are far below 2^52 bytes
+fn format_mib(bytes: usize) -> String {
+    format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
+}
+
 /// Processed image data ready for storage.
 pub struct ProcessedImage {
     pub image_data: Vec<u8>,
@@ -35,48 +142,180 @@ pub struct ProcessedImage {
 /// produce both a full-size and thumbnail JPEG.
 ///
 /// Returns `(image_data, thumbnail_data, content_type)`.
-pub fn process_data_url(data_url: &str) -> anyhow::Result<ProcessedImage> {
+pub fn process_data_url(
+    data_url: &str,
+    settings: &ImageSettings,
+) -> Result<ProcessedImage, ImageError> {
     let raw_bytes = decode_data_url(data_url)?;
-    process_image_bytes(&raw_bytes)
+    process_image_bytes(&raw_bytes, settings)
+}
+
+/// Encode an uploaded file as a data URL, so it can take the same path as
+/// images sent by the browser. The declared MIME type is used when it's one
+/// we accept; otherwise the type is guessed from the bytes.
+pub fn encode_data_url(raw_bytes: &[u8], declared_mime: Option<&str>) -> String {
+    let mime = declared_mime
+        .filter(|mime| ALLOWED_MIMES.contains(mime))
+        .or_else(|| {
+            if is_heif(raw_bytes) {
+                Some("image/heic")
+            } else {
+                image::guess_format(raw_bytes)
+                    .ok()
+                    .map(|format| format.to_mime_type())
+            }
+        })
+        .or(declared_mime)
+        .unwrap_or("application/octet-stream");
+    let encoded = base64::engine::general_purpose::STANDARD.encode(raw_bytes);
+    format!("data:{mime};base64,{encoded}")
 }
 
-/// Process raw image bytes (JPEG/PNG/WebP) into resized full + thumbnail JPEGs.
-pub fn process_image_bytes(raw_bytes: &[u8]) -> anyhow::Result<ProcessedImage> {
+/// Re-encode a HEIC data URL as a JPEG one, for consumers such as the AI
+/// extraction models that only read common formats. Returns `None` when the
+/// data URL isn't HEIC and can be passed on as it is.
+pub fn heic_data_url_to_jpeg(
+    data_url: &str,
+    settings: &ImageSettings,
+) -> Result<Option<String>, ImageError> {
+    let raw_bytes = decode_data_url(data_url)?;
+    if !is_heif(&raw_bytes) {
+        return Ok(None);
+    }
+    let processed = process_image_bytes(&raw_bytes, settings)?;
+    let encoded = base64::engine::general_purpose::STANDARD.encode(processed.image_data);
+    Ok(Some(format!(
+        "data:{};base64,{encoded}",
+        processed.content_type
+    )))
+}
+
+/// Process raw image bytes (JPEG/PNG/WebP, or HEIC with the `heic` feature) into resized full + thumbnail JPEGs.
+/// Images already within `max_dimension` keep their size.
+pub fn process_image_bytes(
+    raw_bytes: &[u8],
+    settings: &ImageSettings,
+) -> Result<ProcessedImage, ImageError> {
+    if raw_bytes.len() > settings.max_file_size {
+        return Err(ImageError::FileTooLarge {
+            size: raw_bytes.len(),
+            limit: settings.max_file_size,
+        });
+    }
+
+    let img = if is_heif(raw_bytes) {
+        decode_heif(raw_bytes, settings)?
+    } else {
+        decode_standard(raw_bytes, settings)?
+    };
+
+    let full = fit_within(&img, settings.max_dimension);
+    let thumb = fit_within(&img, settings.thumbnail_size);
+
+    let image_data = encode_jpeg(&full, settings.jpeg_quality)?;
+    let thumbnail_data = encode_jpeg(&thumb, JPEG_QUALITY_THUMBNAIL)?;
+
+    Ok(ProcessedImage {
+        image_data,
+        thumbnail_data,
+        content_type: "image/jpeg".to_string(),
+    })
+}
+
+/// Decode a JPEG, PNG or WebP image, applying its EXIF orientation.
+fn decode_standard(raw_bytes: &[u8], settings: &ImageSettings) -> Result<DynamicImage, ImageError> {
     let orientation = read_exif_orientation(raw_bytes);
 
+    let (width, height) = ImageReader::new(Cursor::new(raw_bytes))
+        .with_guessed_format()
+        .context("failed to guess image format")?
+        .into_dimensions()
+        .context("failed to read image dimensions")?;
+    if width.max(height) > settings.max_upload_dimension {
+        return Err(ImageError::DimensionsTooLarge {
+            width,
+            height,
+            limit: settings.max_upload_dimension,
+        });
+    }
+
     let mut reader = ImageReader::new(Cursor::new(raw_bytes))
         .with_guessed_format()
         .context("failed to guess image format")?;
 
     let mut limits = image::Limits::default();
-    limits.max_image_width = Some(MAX_INPUT_DIMENSION);
-    limits.max_image_height = Some(MAX_INPUT_DIMENSION);
+    limits.max_image_width = Some(settings.max_upload_dimension);
+    limits.max_image_height = Some(settings.max_upload_dimension);
     limits.max_alloc = Some(MAX_DECODER_ALLOC);
     reader.limits(limits);
 
     let img = reader.decode().context("failed to decode image")?;
-    let img = apply_exif_orientation(img, orientation);
+    Ok(apply_exif_orientation(img, orientation))
+}
 
-    let full = img.resize(
-        MAX_FULL_SIZE,
-        MAX_FULL_SIZE,
-        image::imageops::FilterType::Lanczos3,
-    );
+/// Whether `raw_bytes` look like a HEIC/HEIF file, judging by the major brand
+/// of its `ftyp` box.
+fn is_heif(raw_bytes: &[u8]) -> bool {
+    raw_bytes.get(4..8) == Some(b"ftyp".as_slice())
+        && raw_bytes
+            .get(8..12)
+            .is_some_and(|brand| HEIF_BRANDS.iter().any(|b| b.as_slice() == brand))
+}
 
-    let thumb = img.resize(
-        MAX_THUMBNAIL_SIZE,
-        MAX_THUMBNAIL_SIZE,
-        image::imageops::FilterType::Lanczos3,
-    );
+/// Decode the primary image of a HEIC/HEIF file with libheif. libheif applies
+/// the container's rotation and mirroring itself, so EXIF orientation is not
+/// applied on top.
+#[cfg(feature = "heic")]
+fn decode_heif(raw_bytes: &[u8], settings: &ImageSettings) -> Result<DynamicImage, ImageError> {
+    use libheif_rs::{ColorSpace, HeifContext, LibHeif, RgbChroma};
+
+    let context = HeifContext::read_from_bytes(raw_bytes).context("failed to read HEIC image")?;
+    let handle = context
+        .primary_image_handle()
+        .context("HEIC image has no primary image")?;
+    let (width, height) = (handle.width(), handle.height());
+    if width.max(height) > settings.max_upload_dimension {
+        return Err(ImageError::DimensionsTooLarge {
+            width,
+            height,
+            limit: settings.max_upload_dimension,
+        });
+    }
 
-    let image_data = encode_jpeg(&full, JPEG_QUALITY_FULL)?;
-    let thumbnail_data = encode_jpeg(&thumb, JPEG_QUALITY_THUMBNAIL)?;
+    let decoded = LibHeif::new()
+        .decode(&handle, ColorSpace::Rgb(RgbChroma::Rgb), None)
+        .context("failed to decode HEIC image")?;
+    let planes = decoded.planes();
+    let plane = planes
+        .interleaved
+        .context("decoded HEIC image has no interleaved RGB plane")?;
+
+    // Rows may be padded beyond `width * 3` bytes, so copy them one at a time.
+    let row_len = plane.width as usize * 3;
+    let mut pixels = Vec::with_capacity(row_len * plane.height as usize);
+    for row in plane.data.chunks(plane.stride).take(plane.height as usize) {
+        pixels.extend_from_slice(&row[..row_len]);
+    }
+    let buffer = image::RgbImage::from_raw(plane.width, plane.height, pixels)
+        .context("decoded HEIC image has an unexpected size")?;
+    Ok(DynamicImage::ImageRgb8(buffer))
+}
 
-    Ok(ProcessedImage {
-        image_data,
-        thumbnail_data,
-        content_type: "image/jpeg".to_string(),
-    })
+#[cfg(not(feature = "heic"))]
+fn decode_heif(_raw_bytes: &[u8], _settings: &ImageSettings) -> Result<DynamicImage, ImageError> {
+    Err(anyhow::anyhow!(
+        "HEIC images are not supported by this server; it must be built with the `heic` feature"
+    )
+    .into())
+}
+
+/// Downscale `img` so neither side exceeds `max`. Smaller images are left
+/// alone rather than upscaled.
+fn fit_within(img: &DynamicImage, max: u32) -> DynamicImage {
+    if img.width() <= max && img.height() <= max {
+        return img.clone();
+    }
+    img.resize(max, max, image::imageops::FilterType::Lanczos3)
 }
 
 /// Read the EXIF orientation tag from raw image bytes.
@@ -210,6 +449,134 @@ mod tests {
         assert_eq!((result.width(), result.height()), (4, 2));
     }
 
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        let img = DynamicImage::new_rgb8(width, height);
+        let mut buf = Vec::new();
+        img.write_to(&mut Cursor::new(&mut buf), image::ImageFormat::Png)
+            .expect("encode png");
+        buf
+    }
+
+    fn dimensions(jpeg: &[u8]) -> (u32, u32) {
+        image::load_from_memory(jpeg)
+            .expect("decode jpeg")
+            .to_rgb8()
+            .dimensions()
+    }
+
+    #[test]
+    fn process_image_downscales_to_configured_sizes() {
+        let settings = ImageSettings {
+            max_dimension: 40,
+            thumbnail_size: 10,
+            ..ImageSettings::default()
+        };
+        let processed = process_image_bytes(&png(80, 20), &settings).unwrap();
+        assert_eq!(dimensions(&processed.image_data), (40, 10));
+        assert_eq!(dimensions(&processed.thumbnail_data), (10, 3));
+    }
+
+    #[test]
+    fn process_image_keeps_small_images_at_their_size() {
+        let processed = process_image_bytes(&png(30, 20), &ImageSettings::default()).unwrap();
+        assert_eq!(dimensions(&processed.image_data), (30, 20));
+    }
+
+    #[test]
+    fn process_image_rejects_oversized_files() {
+        let settings = ImageSettings {
+            max_file_size: 10,
+            ..ImageSettings::default()
+        };
+        let err = process_image_bytes(&png(4, 4), &settings)
+            .err()
+            .expect("oversized file should be rejected");
+        assert!(matches!(err, ImageError::FileTooLarge { limit: 10, .. }));
+        assert!(err.is_too_large());
+    }
+
+    #[test]
+    fn process_image_rejects_oversized_dimensions() {
+        let settings = ImageSettings {
+            max_dimension: 16,
+            thumbnail_size: 8,
+            max_upload_dimension: 32,
+            ..ImageSettings::default()
+        };
+        let err = process_image_bytes(&png(64, 8), &settings)
+            .err()
+            .expect("oversized image should be rejected");
+        assert_eq!(
+            err.to_string(),
+            "image is 64x8 pixels but neither side may exceed 32"
+        );
+    }
+
+    /// The start of an ISO base media file with the given major brand.
+    fn ftyp(brand: &[u8; 4]) -> Vec<u8> {
+        let mut bytes = vec![0, 0, 0, 24];
+        bytes.extend_from_slice(b"ftyp");
+        bytes.extend_from_slice(brand);
+        bytes.extend_from_slice(&[0; 12]);
+        bytes
+    }
+
+    #[test]
+    fn is_heif_recognises_heic_brands() {
+        assert!(is_heif(&ftyp(b"heic")));
+        assert!(is_heif(&ftyp(b"mif1")));
+        assert!(!is_heif(&ftyp(b"isom")));
+        assert!(!is_heif(&png(4, 4)));
+        assert!(!is_heif(b"ftyp"));
+    }
+
+    #[test]
+    fn decode_data_url_accepts_heic_mime() {
+        let data = base64::engine::general_purpose::STANDARD.encode(ftyp(b"heic"));
+        assert!(decode_data_url(&format!("data:image/heic;base64,{data}")).is_ok());
+    }
+
+    #[cfg(not(feature = "heic"))]
+    #[test]
+    fn process_image_explains_missing_heic_support() {
+        let err = process_image_bytes(&ftyp(b"heic"), &ImageSettings::default())
+            .err()
+            .expect("HEIC should be rejected without the heic feature");
+        assert!(!err.is_too_large());
+        assert!(err.to_string().contains("`heic` feature"));
+    }
+
+    #[test]
+    fn encode_data_url_guesses_missing_or_generic_types() {
+        let bytes = png(2, 2);
+        let url = encode_data_url(&bytes, Some("application/octet-stream"));
+        assert!(url.starts_with("data:image/png;base64,"));
+        assert_eq!(decode_data_url(&url).unwrap(), bytes);
+
+        assert!(encode_data_url(&bytes, Some("image/webp")).starts_with("data:image/webp;"));
+        assert!(encode_data_url(b"hello", None).starts_with("data:application/octet-stream;"));
+    }
+
+    #[test]
+    fn heic_data_url_to_jpeg_leaves_other_formats_alone() {
+        let data = base64::engine::general_purpose::STANDARD.encode(png(4, 4));
+        let url = format!("data:image/png;base64,{data}");
+        assert!(
+            heic_data_url_to_jpeg(&url, &ImageSettings::default())
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn image_settings_reject_inconsistent_values() {
+        assert!(ImageSettings::new(1200, 200, 85, 10_000, 1024).is_ok());
+        assert!(ImageSettings::new(1200, 200, 0, 10_000, 1024).is_err());
+        assert!(ImageSettings::new(1200, 2000, 85, 10_000, 1024).is_err());
+        assert!(ImageSettings::new(20_000, 200, 85, 10_000, 1024).is_err());
+        assert!(ImageSettings::new(1200, 200, 85, 10_000, 0).is_err());
+    }
+
     #[test]
     fn read_exif_orientation_returns_default_for_png() {
         // PNG doesn't have EXIF, should return 1
@@ -0,0 +1,197 @@
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Instant;
+
+use anyhow::Context;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::AssertSqlSafe;
+
+use crate::domain::clock::Clock;
+use crate::infrastructure::database::DatabasePool;
+use crate::infrastructure::integrity::ENTITY_TABLES;
+
+/// Images removed per statement. Each row carries the full image and its
+/// thumbnail, so small batches keep the write lock short.
+pub const PURGE_BATCH_SIZE: i64 = 25;
+
+/// The outcome of one purge pass.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImagePurgeRun {
+    pub finished_at: DateTime<Utc>,
+    pub duration_ms: i64,
+    /// Images whose entity was deleted without them, e.g. a roast's images
+    /// when its roaster was deleted.
+    pub orphans_marked: u64,
+    pub purged: u64,
+    /// Why the pass stopped early, if it did.
+    pub error: Option<String>,
+}
+
+/// Where the purge task has got to, for the admin page.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImagePurgeProgress {
+    /// Images removed so far by a pass that is still running.
+    pub running: Option<u64>,
+    pub last_run: Option<ImagePurgeRun>,
+}
+
+/// Removes deleted images in the background. Deleting an entity only marks
+/// its image with `deleted_at`; this finds images left behind by cascading
+/// deletes, marks those too, and then removes marked rows in batches.
+pub struct ImagePurgeService {
+    pool: DatabasePool,
+    clock: Arc<dyn Clock>,
+    progress: Mutex<ImagePurgeProgress>,
+}
+
+impl ImagePurgeService {
+    pub fn new(pool: DatabasePool, clock: Arc<dyn Clock>) -> Self {
+        Self {
+            pool,
+            clock,
+            progress: Mutex::new(ImagePurgeProgress::default()),
+        }
+    }
+
+    pub fn progress(&self) -> ImagePurgeProgress {
+        self.progress
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Mark orphaned images and purge everything marked, yielding between
+    /// batches. A failing step is recorded in the run's `error` rather than
+    /// returned.
+    pub async fn run(&self) -> ImagePurgeRun {
+        let started = Instant::now();
+        self.update(|progress| progress.running = Some(0));
+
+        let mut orphans_marked = 0;
+        let mut purged = 0;
+        let outcome = async {
+            orphans_marked = self.mark_orphans().await?;
+            loop {
+                let removed = self.purge_batch(PURGE_BATCH_SIZE).await?;
+                if removed == 0 {
+                    break;
+                }
+                purged += removed;
+                self.update(|progress| progress.running = Some(purged));
+                tokio::task::yield_now().await;
+            }
+            anyhow::Ok(())
+        }
+        .await;
+
+        let run = ImagePurgeRun {
+            finished_at: self.clock.now(),
+            duration_ms: i64::try_from(started.elapsed().as_millis()).unwrap_or(i64::MAX),
+            orphans_marked,
+            purged,
+            error: outcome.err().map(|err| format!("{err:#}")),
+        };
+        self.update(|progress| {
+            progress.running = None;
+            progress.last_run = Some(run.clone());
+        });
+        run
+    }
+
+    async fn mark_orphans(&self) -> anyhow::Result<u64> {
+        let mut marked = 0;
+        for (entity_type, table) in ENTITY_TABLES {
+            let result = sqlx::query(AssertSqlSafe(format!(
+                "UPDATE entity_images SET deleted_at = strftime('%Y-%m-%dT%H:%M:%SZ', 'now') \
+                 WHERE entity_type = ? AND deleted_at IS NULL \
+                 AND entity_id NOT IN (SELECT id FROM {table})"
+            )))
+            .bind(entity_type.as_str())
+            .execute(&self.pool)
+            .await
+            .with_context(|| format!("failed to mark orphaned {entity_type} images"))?;
+            marked += result.rows_affected();
+        }
+        Ok(marked)
+    }
+
+    async fn purge_batch(&self, limit: i64) -> anyhow::Result<u64> {
+        let result = sqlx::query(
+            "DELETE FROM entity_images WHERE id IN \
+             (SELECT id FROM entity_images WHERE deleted_at IS NOT NULL LIMIT ?)",
+        )
+        .bind(limit)
+        .execute(&self.pool)
+        .await
+        .context("failed to purge deleted images")?;
+        Ok(result.rows_affected())
+    }
+
+    fn update(&self, change: impl FnOnce(&mut ImagePurgeProgress)) {
+        change(&mut self.progress.lock().unwrap_or_else(PoisonError::into_inner));
+    }
+}
+
+/// Images marked deleted and not yet purged.
+pub(crate) async fn awaiting_purge(pool: &DatabasePool) -> anyhow::Result<i64> {
+    let (count,): (i64,) =
+        sqlx::query_as("SELECT COUNT(*) FROM entity_images WHERE deleted_at IS NOT NULL")
+            .fetch_one(pool)
+            .await
+            .context("failed to count images awaiting purge")?;
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::clock::SystemClock;
+    use crate::infrastructure::database::Database;
+
+    async fn insert_image(pool: &DatabasePool, entity_type: &str, entity_id: i64) {
+        sqlx::query(
+            "INSERT INTO entity_images (entity_type, entity_id, content_type, image_data, thumbnail_data) \
+             VALUES (?, ?, 'image/jpeg', x'00', x'00')",
+        )
+        .bind(entity_type)
+        .bind(entity_id)
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn orphaned_and_marked_images_are_purged() {
+        let database = Database::in_memory().await.unwrap();
+        let pool = database.clone_pool();
+        sqlx::query(
+            "INSERT INTO roasters (id, name, slug, country) VALUES (1, 'Kept', 'kept', 'UK')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        insert_image(&pool, "roaster", 1).await;
+        insert_image(&pool, "roaster", 2).await;
+        insert_image(&pool, "cafe", 3).await;
+        insert_image(&pool, "roast", 4).await;
+        sqlx::query("UPDATE entity_images SET deleted_at = '2025-01-01T00:00:00Z' WHERE entity_type = 'roast'")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let service = ImagePurgeService::new(pool.clone(), Arc::new(SystemClock));
+        let run = service.run().await;
+
+        assert_eq!(run.error, None);
+        assert_eq!(run.orphans_marked, 2);
+        assert_eq!(run.purged, 3);
+        assert_eq!(awaiting_purge(&pool).await.unwrap(), 0);
+        let (left,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM entity_images")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(left, 1);
+        assert_eq!(service.progress().last_run, Some(run));
+        assert_eq!(service.progress().running, None);
+    }
+}
@@ -0,0 +1,256 @@
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use sqlx::AssertSqlSafe;
+
+use crate::domain::entity_type::EntityType;
+use crate::infrastructure::database::{DatabaseConnection, DatabasePool};
+
+/// Entity types that can own images or timeline events, with their backing table.
+pub(crate) const ENTITY_TABLES: [(EntityType, &str); 10] = [
+    (EntityType::Roaster, "roasters"),
+    (EntityType::Roast, "roasts"),
+    (EntityType::Bag, "bags"),
+    (EntityType::Gear, "gear"),
+    (EntityType::Brew, "brews"),
+    (EntityType::Cafe, "cafes"),
+    (EntityType::Cup, "cups"),
+    (EntityType::Event, "events"),
+    (EntityType::Trip, "trips"),
+    (EntityType::User, "users"),
+];
+
+/// Gear columns on `brews` and the category each must reference.
+const BREW_GEAR_COLUMNS: [(&str, &str); 3] = [
+    ("grinder_id", "grinder"),
+    ("brewer_id", "brewer"),
+    ("filter_paper_id", "filter_paper"),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IntegrityIssueKind {
+    /// An image row whose owning entity no longer exists.
+    OrphanedImage,
+    /// A timeline event whose entity no longer exists.
+    OrphanedTimelineEvent,
+    /// A bag with more coffee remaining than it started with.
+    BagRemainingExceedsAmount,
+    /// A brew whose grinder, brewer, or filter paper points at gear of another category.
+    BrewGearCategoryMismatch,
+}
+
+impl IntegrityIssueKind {
+    /// Whether [`IntegrityService::repair`] can fix this kind of issue automatically.
+    pub const fn is_repairable(self) -> bool {
+        !matches!(self, Self::BrewGearCategoryMismatch)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IntegrityIssue {
+    pub kind: IntegrityIssueKind,
+    pub entity_type: String,
+    pub entity_id: i64,
+    pub description: String,
+    pub repairable: bool,
+}
+
+impl IntegrityIssue {
+    fn new(
+        kind: IntegrityIssueKind,
+        entity_type: &str,
+        entity_id: i64,
+        description: String,
+    ) -> Self {
+        Self {
+            kind,
+            entity_type: entity_type.to_string(),
+            entity_id,
+            description,
+            repairable: kind.is_repairable(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IntegrityReport {
+    pub issues: Vec<IntegrityIssue>,
+    /// Number of issues fixed; always zero for a check-only run.
+    pub repaired: u64,
+}
+
+impl IntegrityReport {
+    pub fn is_clean(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+/// Finds inconsistencies that `SQLite` foreign keys cannot express, such as
+/// polymorphic references from images and timeline events.
+pub struct IntegrityService {
+    pool: DatabasePool,
+}
+
+impl IntegrityService {
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn check(&self) -> anyhow::Result<IntegrityReport> {
+        let mut conn = self
+            .pool
+            .acquire()
+            .await
+            .context("failed to acquire connection")?;
+        let issues = find_issues(&mut conn).await?;
+        Ok(IntegrityReport {
+            issues,
+            repaired: 0,
+        })
+    }
+
+    /// Fix every repairable issue in a single transaction. The returned report
+    /// lists all issues found before the repair, including unrepairable ones.
+    pub async fn repair(&self) -> anyhow::Result<IntegrityReport> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("failed to begin transaction")?;
+
+        let issues = find_issues(&mut tx).await?;
+        let mut repaired = 0;
+        for issue in issues.iter().filter(|issue| issue.repairable) {
+            repaired += repair_issue(&mut tx, issue).await?;
+        }
+
+        tx.commit().await.context("failed to commit transaction")?;
+
+        Ok(IntegrityReport { issues, repaired })
+    }
+}
+
+async fn find_issues(conn: &mut DatabaseConnection) -> anyhow::Result<Vec<IntegrityIssue>> {
+    let mut issues = Vec::new();
+
+    for (entity_type, table) in ENTITY_TABLES {
+        let entity_type = entity_type.as_str();
+
+        let images: Vec<(i64,)> = sqlx::query_as(AssertSqlSafe(format!(
+            "SELECT entity_id FROM entity_images \
+             WHERE entity_type = ? AND entity_id NOT IN (SELECT id FROM {table}) \
+             AND deleted_at IS NULL \
+             ORDER BY entity_id"
+        )))
+        .bind(entity_type)
+        .fetch_all(&mut *conn)
+        .await
+        .with_context(|| format!("failed to check {entity_type} images"))?;
+
+        issues.extend(images.into_iter().map(|(entity_id,)| {
+            IntegrityIssue::new(
+                IntegrityIssueKind::OrphanedImage,
+                entity_type,
+                entity_id,
+                format!("image attached to missing {entity_type} {entity_id}"),
+            )
+        }));
+
+        let events: Vec<(i64, i64)> = sqlx::query_as(AssertSqlSafe(format!(
+            "SELECT id, entity_id FROM timeline_events \
+             WHERE entity_type = ? AND entity_id NOT IN (SELECT id FROM {table}) \
+             ORDER BY id"
+        )))
+        .bind(entity_type)
+        .fetch_all(&mut *conn)
+        .await
+        .with_context(|| format!("failed to check {entity_type} timeline events"))?;
+
+        issues.extend(events.into_iter().map(|(event_id, entity_id)| {
+            IntegrityIssue::new(
+                IntegrityIssueKind::OrphanedTimelineEvent,
+                entity_type,
+                entity_id,
+                format!("timeline event {event_id} refers to missing {entity_type} {entity_id}"),
+            )
+        }));
+    }
+
+    let bags: Vec<(i64, f64, f64)> = sqlx::query_as(
+        "SELECT id, amount, remaining FROM bags WHERE remaining > amount ORDER BY id",
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .context("failed to check bag amounts")?;
+
+    issues.extend(bags.into_iter().map(|(id, amount, remaining)| {
+        IntegrityIssue::new(
+            IntegrityIssueKind::BagRemainingExceedsAmount,
+            EntityType::Bag.as_str(),
+            id,
+            format!("bag {id} has {remaining}g remaining of {amount}g"),
+        )
+    }));
+
+    for (column, category) in BREW_GEAR_COLUMNS {
+        let brews: Vec<(i64, i64, String)> = sqlx::query_as(AssertSqlSafe(format!(
+            "SELECT b.id, g.id, g.category FROM brews b \
+             JOIN gear g ON g.id = b.{column} \
+             WHERE g.category != ? ORDER BY b.id"
+        )))
+        .bind(category)
+        .fetch_all(&mut *conn)
+        .await
+        .with_context(|| format!("failed to check brew {column}"))?;
+
+        issues.extend(brews.into_iter().map(|(brew_id, gear_id, actual)| {
+            IntegrityIssue::new(
+                IntegrityIssueKind::BrewGearCategoryMismatch,
+                EntityType::Brew.as_str(),
+                brew_id,
+                format!("brew {brew_id} uses {actual} gear {gear_id} as its {category}"),
+            )
+        }));
+    }
+
+    Ok(issues)
+}
+
+async fn repair_issue(
+    conn: &mut DatabaseConnection,
+    issue: &IntegrityIssue,
+) -> anyhow::Result<u64> {
+    let result = match issue.kind {
+        IntegrityIssueKind::OrphanedImage => {
+            sqlx::query("DELETE FROM entity_images WHERE entity_type = ? AND entity_id = ?")
+                .bind(&issue.entity_type)
+                .bind(issue.entity_id)
+                .execute(&mut *conn)
+                .await
+        }
+        IntegrityIssueKind::OrphanedTimelineEvent => {
+            sqlx::query("DELETE FROM timeline_events WHERE entity_type = ? AND entity_id = ?")
+                .bind(&issue.entity_type)
+                .bind(issue.entity_id)
+                .execute(&mut *conn)
+                .await
+        }
+        IntegrityIssueKind::BagRemainingExceedsAmount => {
+            sqlx::query(
+                "INSERT INTO bag_ledger (bag_id, kind, delta, reason) \
+                 SELECT id, 'adjustment', amount - remaining, 'Capped at the bag amount' \
+                 FROM bags WHERE id = ? AND remaining > amount",
+            )
+            .bind(issue.entity_id)
+            .execute(&mut *conn)
+            .await
+        }
+        IntegrityIssueKind::BrewGearCategoryMismatch => return Ok(0),
+    };
+
+    // Orphaned timeline events are removed per entity, so the first issue for
+    // an entity accounts for all of its events and later ones affect no rows.
+    Ok(result
+        .with_context(|| format!("failed to repair: {}", issue.description))?
+        .rows_affected())
+}
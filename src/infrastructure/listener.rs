@@ -0,0 +1,138 @@
+//! Where the server listens: a TCP address, or a Unix domain socket for a
+//! reverse proxy on the same host.
+
+use std::fmt;
+use std::net::SocketAddr;
+use std::os::unix::fs::{FileTypeExt, PermissionsExt};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use anyhow::Context;
+use serde::Deserialize;
+use tokio::net::{TcpListener, UnixListener};
+
+/// Owner and group may connect to a Unix socket; put the proxy in the
+/// service's group to let it in.
+const SOCKET_MODE: u32 = 0o660;
+
+/// A bind address as given on the command line: `host:port`, or
+/// `unix:/path/to.sock`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub enum BindAddress {
+    Tcp(SocketAddr),
+    Unix(PathBuf),
+}
+
+impl FromStr for BindAddress {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if let Some(path) = value.strip_prefix("unix:") {
+            if path.is_empty() {
+                return Err("a unix: bind address needs a socket path".to_string());
+            }
+            return Ok(Self::Unix(PathBuf::from(path)));
+        }
+        value.parse().map(Self::Tcp).map_err(|_| {
+            format!("invalid bind address '{value}': expected host:port or unix:/path/to.sock")
+        })
+    }
+}
+
+impl TryFrom<String> for BindAddress {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl fmt::Display for BindAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Tcp(address) => write!(f, "{address}"),
+            Self::Unix(path) => write!(f, "unix:{}", path.display()),
+        }
+    }
+}
+
+/// A socket ready to accept connections.
+pub enum BoundListener {
+    Tcp(TcpListener),
+    /// A Unix socket, with the path to remove on shutdown when it was
+    /// created here rather than handed over by systemd.
+    Unix(UnixListener, Option<PathBuf>),
+}
+
+impl BoundListener {
+    pub async fn bind(address: &BindAddress) -> anyhow::Result<Self> {
+        match address {
+            BindAddress::Tcp(address) => TcpListener::bind(address)
+                .await
+                .map(Self::Tcp)
+                .with_context(|| format!("failed to bind to {address}")),
+            BindAddress::Unix(path) => bind_unix(path),
+        }
+    }
+
+    /// The address actually listened on, for logging.
+    pub fn address(&self) -> String {
+        match self {
+            Self::Tcp(listener) => listener
+                .local_addr()
+                .map_or_else(|_| "unknown".to_string(), |address| address.to_string()),
+            Self::Unix(listener, _) => listener
+                .local_addr()
+                .ok()
+                .and_then(|address| address.as_pathname().map(Path::to_path_buf))
+                .map_or_else(
+                    || "unix socket".to_string(),
+                    |path| format!("unix:{}", path.display()),
+                ),
+        }
+    }
+}
+
+fn bind_unix(path: &Path) -> anyhow::Result<BoundListener> {
+    // A socket left behind by an unclean exit would block the bind. Anything
+    // else at the path is left alone.
+    if let Ok(metadata) = std::fs::symlink_metadata(path)
+        && metadata.file_type().is_socket()
+    {
+        std::fs::remove_file(path)
+            .with_context(|| format!("failed to remove stale socket {}", path.display()))?;
+    }
+
+    let listener = UnixListener::bind(path)
+        .with_context(|| format!("failed to bind to unix:{}", path.display()))?;
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(SOCKET_MODE))
+        .with_context(|| format!("failed to set permissions on {}", path.display()))?;
+    Ok(BoundListener::Unix(listener, Some(path.to_path_buf())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_tcp_and_unix_addresses() {
+        assert_eq!(
+            "127.0.0.1:3000".parse::<BindAddress>().unwrap(),
+            BindAddress::Tcp("127.0.0.1:3000".parse().unwrap())
+        );
+        assert_eq!(
+            "unix:/run/brewlog.sock".parse::<BindAddress>().unwrap(),
+            BindAddress::Unix(PathBuf::from("/run/brewlog.sock"))
+        );
+        assert!("unix:".parse::<BindAddress>().is_err());
+        assert!("localhost".parse::<BindAddress>().is_err());
+    }
+
+    #[test]
+    fn display_round_trips() {
+        for address in ["0.0.0.0:8080", "unix:/run/brewlog.sock"] {
+            assert_eq!(address.parse::<BindAddress>().unwrap().to_string(), address);
+        }
+    }
+}
@@ -0,0 +1,193 @@
+use std::sync::Arc;
+use std::time::Instant;
+
+use anyhow::Context;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::clock::Clock;
+use crate::infrastructure::database::DatabasePool;
+
+/// How many past runs to keep.
+const KEPT_RUNS: i64 = 30;
+
+/// The outcome of one maintenance pass over the database.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, sqlx::FromRow)]
+pub struct MaintenanceRun {
+    pub ran_at: DateTime<Utc>,
+    pub duration_ms: i64,
+    /// Frames in the WAL when the checkpoint ran.
+    pub wal_frames: i64,
+    pub checkpointed_frames: i64,
+    /// A reader held the WAL, so it couldn't be fully checkpointed.
+    pub checkpoint_busy: bool,
+    pub vacuumed: bool,
+    pub bytes_before: i64,
+    pub bytes_after: i64,
+    /// Why the run stopped early, if it did.
+    pub error: Option<String>,
+}
+
+struct Checkpoint {
+    busy: bool,
+    wal_frames: i64,
+    checkpointed_frames: i64,
+}
+
+/// Keeps a long-running database tidy: checkpoints and truncates the
+/// WAL so it can't grow without bound, refreshes the query planner's
+/// statistics and, when asked, rebuilds the file with VACUUM.
+pub struct MaintenanceService {
+    pool: DatabasePool,
+    clock: Arc<dyn Clock>,
+}
+
+impl MaintenanceService {
+    pub fn new(pool: DatabasePool, clock: Arc<dyn Clock>) -> Self {
+        Self { pool, clock }
+    }
+
+    /// Run maintenance now and record the result. A failing step is recorded
+    /// in the run's `error` rather than returned.
+    pub async fn run(&self, vacuum: bool) -> anyhow::Result<MaintenanceRun> {
+        let ran_at = self.clock.now();
+        let started = Instant::now();
+        let bytes_before = database_bytes(&self.pool).await?;
+
+        let mut vacuumed = false;
+        let outcome = async {
+            let checkpoint = self.checkpoint().await?;
+            sqlx::query("PRAGMA optimize")
+                .execute(&self.pool)
+                .await
+                .context("PRAGMA optimize failed")?;
+            if vacuum {
+                sqlx::query("VACUUM")
+                    .execute(&self.pool)
+                    .await
+                    .context("VACUUM failed")?;
+                vacuumed = true;
+            }
+            anyhow::Ok(checkpoint)
+        }
+        .await;
+
+        let (checkpoint, error) = match outcome {
+            Ok(checkpoint) => (checkpoint, None),
+            Err(err) => (
+                Checkpoint {
+                    busy: false,
+                    wal_frames: 0,
+                    checkpointed_frames: 0,
+                },
+                Some(format!("{err:#}")),
+            ),
+        };
+
+        let run = MaintenanceRun {
+            ran_at,
+            duration_ms: i64::try_from(started.elapsed().as_millis()).unwrap_or(i64::MAX),
+            wal_frames: checkpoint.wal_frames,
+            checkpointed_frames: checkpoint.checkpointed_frames,
+            checkpoint_busy: checkpoint.busy,
+            vacuumed,
+            bytes_before,
+            bytes_after: database_bytes(&self.pool).await.unwrap_or(bytes_before),
+            error,
+        };
+        self.record(&run).await?;
+        Ok(run)
+    }
+
+    async fn checkpoint(&self) -> anyhow::Result<Checkpoint> {
+        let (busy, wal_frames, checkpointed_frames): (i64, i64, i64) =
+            sqlx::query_as("PRAGMA wal_checkpoint(TRUNCATE)")
+                .fetch_one(&self.pool)
+                .await
+                .context("WAL checkpoint failed")?;
+        // Both counts are -1 when the database isn't in WAL mode.
+        Ok(Checkpoint {
+            busy: busy != 0,
+            wal_frames: wal_frames.max(0),
+            checkpointed_frames: checkpointed_frames.max(0),
+        })
+    }
+
+    async fn record(&self, run: &MaintenanceRun) -> anyhow::Result<()> {
+        sqlx::query(
+            "INSERT INTO maintenance_runs (ran_at, duration_ms, wal_frames, checkpointed_frames, \
+             checkpoint_busy, vacuumed, bytes_before, bytes_after, error) \
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(run.ran_at)
+        .bind(run.duration_ms)
+        .bind(run.wal_frames)
+        .bind(run.checkpointed_frames)
+        .bind(run.checkpoint_busy)
+        .bind(run.vacuumed)
+        .bind(run.bytes_before)
+        .bind(run.bytes_after)
+        .bind(run.error.as_deref())
+        .execute(&self.pool)
+        .await
+        .context("failed to record maintenance run")?;
+
+        sqlx::query(
+            "DELETE FROM maintenance_runs WHERE id NOT IN \
+             (SELECT id FROM maintenance_runs ORDER BY id DESC LIMIT ?)",
+        )
+        .bind(KEPT_RUNS)
+        .execute(&self.pool)
+        .await
+        .context("failed to prune maintenance runs")?;
+        Ok(())
+    }
+}
+
+/// The most recent maintenance run, if there has been one.
+pub(crate) async fn latest_run(pool: &DatabasePool) -> anyhow::Result<Option<MaintenanceRun>> {
+    sqlx::query_as(
+        "SELECT ran_at, duration_ms, wal_frames, checkpointed_frames, checkpoint_busy, \
+         vacuumed, bytes_before, bytes_after, error \
+         FROM maintenance_runs ORDER BY id DESC LIMIT 1",
+    )
+    .fetch_optional(pool)
+    .await
+    .context("failed to load the last maintenance run")
+}
+
+/// Size of the main database file, excluding the WAL.
+pub(crate) async fn database_bytes(pool: &DatabasePool) -> anyhow::Result<i64> {
+    let (page_count,): (i64,) = sqlx::query_as("PRAGMA page_count")
+        .fetch_one(pool)
+        .await
+        .context("failed to read page count")?;
+    let (page_size,): (i64,) = sqlx::query_as("PRAGMA page_size")
+        .fetch_one(pool)
+        .await
+        .context("failed to read page size")?;
+    Ok(page_count * page_size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::clock::SystemClock;
+    use crate::infrastructure::database::Database;
+
+    #[tokio::test]
+    async fn runs_are_recorded_and_the_latest_is_reported() {
+        let database = Database::in_memory().await.unwrap();
+        let service = MaintenanceService::new(database.clone_pool(), Arc::new(SystemClock));
+
+        assert_eq!(latest_run(database.pool()).await.unwrap(), None);
+
+        service.run(false).await.unwrap();
+        let run = service.run(true).await.unwrap();
+
+        assert_eq!(run.error, None);
+        assert!(run.vacuumed);
+        assert!(run.bytes_after > 0);
+        assert_eq!(latest_run(database.pool()).await.unwrap(), Some(run));
+    }
+}
@@ -1,9 +1,21 @@
 pub mod ai;
 pub mod auth;
 pub mod backup;
+pub mod backup_diff;
+pub mod beanconqueror;
+pub mod bulk;
 pub mod client;
 pub mod database;
+pub mod demo;
 pub mod foursquare;
 pub mod image_processing;
+pub mod image_purge;
+pub mod integrity;
+pub mod listener;
+pub mod maintenance;
+pub mod overview;
+pub mod qr;
 pub mod repositories;
+pub mod systemd;
+pub mod theme;
 pub mod webauthn;
@@ -0,0 +1,147 @@
+use std::sync::Arc;
+
+use anyhow::Context;
+use chrono::Datelike;
+use serde::{Deserialize, Serialize};
+use sqlx::AssertSqlSafe;
+
+use crate::domain::clock::Clock;
+use crate::infrastructure::database::DatabasePool;
+use crate::infrastructure::image_purge::awaiting_purge;
+use crate::infrastructure::maintenance::{MaintenanceRun, database_bytes, latest_run};
+use crate::infrastructure::repositories::query_timing::slow_query_count;
+
+/// Tables included in the per-table row counts, in display order.
+const COUNTED_TABLES: [&str; 12] = [
+    "roasters",
+    "roasts",
+    "bags",
+    "gear",
+    "brews",
+    "cafes",
+    "cups",
+    "timeline_events",
+    "entity_images",
+    "users",
+    "passkey_credentials",
+    "ai_usage",
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TableCount {
+    pub table: String,
+    pub rows: i64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MonthlyAiUsage {
+    pub calls: i64,
+    pub tokens: i64,
+    pub cost: f64,
+}
+
+/// Instance-wide statistics for the admin dashboard.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstanceOverview {
+    /// Size of the main database file, excluding the WAL.
+    pub database_bytes: i64,
+    pub image_count: i64,
+    /// Combined size of stored images and their thumbnails.
+    pub image_bytes: i64,
+    /// Images deleted but still stored until the purge task removes them.
+    #[serde(default)]
+    pub images_awaiting_purge: i64,
+    pub table_counts: Vec<TableCount>,
+    /// AI usage across all users since the start of the current UTC month.
+    pub ai_usage_this_month: MonthlyAiUsage,
+    pub active_sessions: i64,
+    pub active_tokens: i64,
+    /// The latest scheduled maintenance, if any has run.
+    #[serde(default)]
+    pub last_maintenance: Option<MaintenanceRun>,
+    /// Queries over the slow query threshold since the server started.
+    #[serde(default)]
+    pub slow_queries: u64,
+}
+
+pub struct OverviewService {
+    pool: DatabasePool,
+    clock: Arc<dyn Clock>,
+}
+
+impl OverviewService {
+    pub fn new(pool: DatabasePool, clock: Arc<dyn Clock>) -> Self {
+        Self { pool, clock }
+    }
+
+    pub async fn overview(&self) -> anyhow::Result<InstanceOverview> {
+        let database_bytes = database_bytes(&self.pool).await?;
+
+        let (image_count, image_bytes): (i64, i64) = sqlx::query_as(
+            "SELECT COUNT(*), COALESCE(SUM(LENGTH(image_data) + LENGTH(thumbnail_data)), 0) \
+             FROM entity_images",
+        )
+        .fetch_one(&self.pool)
+        .await
+        .context("failed to measure image storage")?;
+
+        let images_awaiting_purge = awaiting_purge(&self.pool).await?;
+
+        let mut table_counts = Vec::with_capacity(COUNTED_TABLES.len());
+        for table in COUNTED_TABLES {
+            let (rows,): (i64,) =
+                sqlx::query_as(AssertSqlSafe(format!("SELECT COUNT(*) FROM {table}")))
+                    .fetch_one(&self.pool)
+                    .await
+                    .with_context(|| format!("failed to count {table}"))?;
+            table_counts.push(TableCount {
+                table: table.to_string(),
+                rows,
+            });
+        }
+
+        let now = self.clock.now();
+        // Matches the `strftime('%Y-%m-%dT%H:%M:%fZ')` format used for `created_at`.
+        let month_start = format!("{:04}-{:02}-01T00:00:00.000Z", now.year(), now.month());
+        let (calls, tokens, cost): (i64, i64, f64) = sqlx::query_as(
+            "SELECT COUNT(*), COALESCE(SUM(total_tokens), 0), COALESCE(SUM(cost), 0.0) \
+             FROM ai_usage WHERE created_at >= ?",
+        )
+        .bind(month_start)
+        .fetch_one(&self.pool)
+        .await
+        .context("failed to summarise AI usage")?;
+
+        let (active_sessions,): (i64,) =
+            sqlx::query_as("SELECT COUNT(*) FROM sessions WHERE expires_at > ?")
+                .bind(now)
+                .fetch_one(&self.pool)
+                .await
+                .context("failed to count active sessions")?;
+
+        let (active_tokens,): (i64,) =
+            sqlx::query_as("SELECT COUNT(*) FROM tokens WHERE revoked_at IS NULL")
+                .fetch_one(&self.pool)
+                .await
+                .context("failed to count active tokens")?;
+
+        let last_maintenance = latest_run(&self.pool).await?;
+
+        Ok(InstanceOverview {
+            database_bytes,
+            image_count,
+            image_bytes,
+            images_awaiting_purge,
+            table_counts,
+            ai_usage_this_month: MonthlyAiUsage {
+                calls,
+                tokens,
+                cost,
+            },
+            active_sessions,
+            active_tokens,
+            last_maintenance,
+            slow_queries: slow_query_count(),
+        })
+    }
+}
@@ -0,0 +1,60 @@
+use anyhow::{Context, bail};
+use qrcode::QrCode;
+use qrcode::render::{svg, unicode};
+
+/// Longest payload accepted for encoding. Comfortably fits any link the app
+/// generates while keeping the code scannable from a phone.
+pub const MAX_QR_DATA_LEN: usize = 1024;
+
+/// Minimum rendered size of the SVG in pixels.
+const SVG_MIN_SIZE: u32 = 200;
+
+fn encode(data: &str) -> anyhow::Result<QrCode> {
+    if data.is_empty() {
+        bail!("QR code data is required");
+    }
+    if data.len() > MAX_QR_DATA_LEN {
+        bail!("QR code data must be at most {MAX_QR_DATA_LEN} bytes");
+    }
+    QrCode::new(data.as_bytes()).context("failed to encode QR code")
+}
+
+/// Render `data` as a standalone SVG document.
+pub fn render_svg(data: &str) -> anyhow::Result<String> {
+    let code = encode(data)?;
+    Ok(code
+        .render::<svg::Color<'_>>()
+        .min_dimensions(SVG_MIN_SIZE, SVG_MIN_SIZE)
+        .dark_color(svg::Color("#000000"))
+        .light_color(svg::Color("#ffffff"))
+        .build())
+}
+
+/// Render `data` with half-block characters for printing to a terminal.
+pub fn render_terminal(data: &str) -> anyhow::Result<String> {
+    let code = encode(data)?;
+    Ok(code
+        .render::<unicode::Dense1x2>()
+        .dark_color(unicode::Dense1x2::Light)
+        .light_color(unicode::Dense1x2::Dark)
+        .build())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_svg_document() {
+        let svg = render_svg("https://example.com/share/abc").unwrap();
+        assert!(svg.contains("<svg"));
+        assert!(svg.contains("#000000"));
+    }
+
+    #[test]
+    fn rejects_empty_and_oversized_data() {
+        assert!(render_svg("").is_err());
+        assert!(render_svg(&"a".repeat(MAX_QR_DATA_LEN + 1)).is_err());
+        assert!(render_terminal("").is_err());
+    }
+}
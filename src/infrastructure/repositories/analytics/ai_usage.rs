@@ -3,7 +3,9 @@ use chrono::{DateTime, Utc};
 use sqlx::query_as;
 
 use crate::domain::RepositoryError;
-use crate::domain::ai_usage::{AiUsage, AiUsageSummary, NewAiUsage};
+use crate::domain::ai_usage::{
+    AiModelPrice, AiUsage, AiUsageMonth, AiUsageSummary, NewAiUsage, SetAiModelPrice,
+};
 use crate::domain::ids::{AiUsageId, UserId};
 use crate::domain::repositories::AiUsageRepository;
 use crate::infrastructure::database::DatabasePool;
@@ -17,11 +19,32 @@ impl SqlAiUsageRepository {
     pub fn new(pool: DatabasePool) -> Self {
         Self { pool }
     }
+
+    async fn price_for(&self, model: &str) -> Result<Option<AiModelPrice>, RepositoryError> {
+        let record = query_as::<_, AiModelPriceRecord>(
+            "SELECT model, prompt_price, completion_price, updated_at \
+             FROM ai_model_prices WHERE model = ?",
+        )
+        .bind(model)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|err| RepositoryError::unexpected(err.to_string()))?;
+
+        Ok(record.map(AiModelPrice::from))
+    }
 }
 
 #[async_trait]
 impl AiUsageRepository for SqlAiUsageRepository {
     async fn insert(&self, usage: NewAiUsage) -> Result<AiUsage, RepositoryError> {
+        // A price set by hand wins over the cost OpenRouter reports.
+        let cost = self
+            .price_for(&usage.model)
+            .await?
+            .map_or(usage.cost, |price| {
+                price.cost(usage.prompt_tokens, usage.completion_tokens)
+            });
+
         let query = r"
             INSERT INTO ai_usage (user_id, model, endpoint, prompt_tokens, completion_tokens, total_tokens, cost)
             VALUES (?, ?, ?, ?, ?, ?, ?)
@@ -35,7 +58,7 @@ impl AiUsageRepository for SqlAiUsageRepository {
             .bind(usage.prompt_tokens)
             .bind(usage.completion_tokens)
             .bind(usage.total_tokens)
-            .bind(usage.cost)
+            .bind(cost)
             .fetch_one(&self.pool)
             .await
             .map_err(|err| RepositoryError::unexpected(err.to_string()))?;
@@ -69,6 +92,93 @@ impl AiUsageRepository for SqlAiUsageRepository {
             total_cost: record.total_cost,
         })
     }
+
+    async fn monthly_for_user(
+        &self,
+        user_id: UserId,
+    ) -> Result<Vec<AiUsageMonth>, RepositoryError> {
+        let query = r"
+            SELECT
+                substr(created_at, 1, 7) as month,
+                model,
+                COUNT(*) as calls,
+                SUM(prompt_tokens) as prompt_tokens,
+                SUM(completion_tokens) as completion_tokens,
+                SUM(cost) as cost
+            FROM ai_usage
+            WHERE user_id = ?
+            GROUP BY month, model
+            ORDER BY month DESC, cost DESC, model
+        ";
+
+        let records = query_as::<_, AiUsageMonthRecord>(query)
+            .bind(i64::from(user_id))
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|err| RepositoryError::unexpected(err.to_string()))?;
+
+        Ok(records
+            .into_iter()
+            .map(|record| AiUsageMonth {
+                month: record.month,
+                model: record.model,
+                calls: record.calls,
+                prompt_tokens: record.prompt_tokens,
+                completion_tokens: record.completion_tokens,
+                cost: record.cost,
+            })
+            .collect())
+    }
+
+    async fn list_prices(&self) -> Result<Vec<AiModelPrice>, RepositoryError> {
+        let records = query_as::<_, AiModelPriceRecord>(
+            "SELECT model, prompt_price, completion_price, updated_at \
+             FROM ai_model_prices ORDER BY model",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|err| RepositoryError::unexpected(format!("failed to list AI prices: {err}")))?;
+
+        Ok(records.into_iter().map(AiModelPrice::from).collect())
+    }
+
+    async fn set_price(
+        &self,
+        model: &str,
+        price: &SetAiModelPrice,
+    ) -> Result<AiModelPrice, RepositoryError> {
+        let record = query_as::<_, AiModelPriceRecord>(
+            "INSERT INTO ai_model_prices (model, prompt_price, completion_price) VALUES (?, ?, ?) \
+             ON CONFLICT(model) DO UPDATE SET prompt_price = excluded.prompt_price, \
+             completion_price = excluded.completion_price, \
+             updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') \
+             RETURNING model, prompt_price, completion_price, updated_at",
+        )
+        .bind(model)
+        .bind(price.prompt_price)
+        .bind(price.completion_price)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|err| RepositoryError::unexpected(format!("failed to save AI price: {err}")))?;
+
+        Ok(record.into())
+    }
+
+    async fn delete_price(&self, model: &str) -> Result<(), RepositoryError> {
+        let result = sqlx::query("DELETE FROM ai_model_prices WHERE model = ?")
+            .bind(model)
+            .execute(&self.pool)
+            .await
+            .map_err(|err| {
+                RepositoryError::unexpected(format!("failed to delete AI price: {err}"))
+            })?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+
+        Ok(())
+    }
 }
 
 #[derive(sqlx::FromRow)]
@@ -109,3 +219,32 @@ struct AiUsageSummaryRecord {
     total_tokens: i64,
     total_cost: f64,
 }
+
+#[derive(sqlx::FromRow)]
+struct AiUsageMonthRecord {
+    month: String,
+    model: String,
+    calls: i64,
+    prompt_tokens: i64,
+    completion_tokens: i64,
+    cost: f64,
+}
+
+#[derive(sqlx::FromRow)]
+struct AiModelPriceRecord {
+    model: String,
+    prompt_price: f64,
+    completion_price: f64,
+    updated_at: DateTime<Utc>,
+}
+
+impl From<AiModelPriceRecord> for AiModelPrice {
+    fn from(record: AiModelPriceRecord) -> Self {
+        Self {
+            model: record.model,
+            prompt_price: record.prompt_price,
+            completion_price: record.completion_price,
+            updated_at: record.updated_at,
+        }
+    }
+}
@@ -1,13 +1,25 @@
+use std::collections::BTreeMap;
+
 use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
 use sqlx::{Row, query_as, query_scalar};
 use tracing::info;
 
 use crate::domain::RepositoryError;
-use crate::domain::repositories::StatsRepository;
+use crate::domain::brews::{QuickNote, net_rating};
+use crate::domain::ids::RoasterId;
+use crate::domain::processes::ProcessTaxonomy;
+use crate::domain::ratings::{average_stars, brew_stars};
+use crate::domain::repositories::{CustomProcessRepository, SettingsRepository, StatsRepository};
 use crate::domain::stats::{
-    BrewingSummaryStats, CachedStats, ConsumptionStats, EntityCounts, RoastSummaryStats,
+    BrewingHabits, BrewingSummaryStats, CachedStats, ConsumptionStats, EntityCounts, PeriodSummary,
+    RoastSummaryStats, RoasterLeaderboardEntry, SpendStats, StatsPeriod, TOP_PERIOD_ENTRIES,
+    TOP_TASTING_NOTES, TastingStats, process_family_counts,
 };
 use crate::infrastructure::database::DatabasePool;
+use crate::infrastructure::repositories::brews::decode_quick_notes;
+use crate::infrastructure::repositories::processes::SqlCustomProcessRepository;
+use crate::infrastructure::repositories::settings::SqlSettingsRepository;
 
 #[derive(Clone)]
 pub struct SqlStatsRepository {
@@ -46,6 +58,43 @@ struct NameWeight {
     total_grams: f64,
 }
 
+#[derive(sqlx::FromRow)]
+struct BucketCount {
+    bucket: i64,
+    count: i64,
+}
+
+impl BucketCount {
+    fn into_tuple(self) -> (u32, u64) {
+        (self.bucket as u32, self.count as u64)
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct LeaderboardRecord {
+    roaster_id: i64,
+    name: String,
+    slug: String,
+    grams_brewed: f64,
+    brew_count: i64,
+    good_brew_count: i64,
+    last_purchased_at: Option<DateTime<Utc>>,
+}
+
+impl LeaderboardRecord {
+    fn into_domain(self) -> RoasterLeaderboardEntry {
+        RoasterLeaderboardEntry {
+            roaster_id: RoasterId::new(self.roaster_id),
+            name: self.name,
+            slug: self.slug,
+            grams_brewed: self.grams_brewed,
+            brew_count: self.brew_count as u64,
+            good_brew_count: self.good_brew_count as u64,
+            last_purchased_at: self.last_purchased_at,
+        }
+    }
+}
+
 #[async_trait]
 impl StatsRepository for SqlStatsRepository {
     async fn roaster_country_counts(&self) -> Result<Vec<(String, u64)>, RepositoryError> {
@@ -87,6 +136,8 @@ impl StatsRepository for SqlStatsRepository {
     }
 
     async fn cup_country_counts(&self) -> Result<Vec<(String, u64)>, RepositoryError> {
+        // Cups drunk away from a cafe have no location, so the inner join
+        // leaves them off the map.
         let rows = query_as::<_, CountryCount>(
             r"SELECT ca.country as country, COUNT(*) as count
                FROM cups c
@@ -376,6 +427,126 @@ impl StatsRepository for SqlStatsRepository {
         })
     }
 
+    async fn tasting_summary(&self, year: i32) -> Result<TastingStats, RepositoryError> {
+        let year_text = year.to_string();
+
+        let tasting_note_counts: Vec<(String, u64)> = query_as::<_, NameCount>(
+            r"WITH RECURSIVE raw(val) AS (
+                SELECT TRIM(j.value)
+                FROM brews b
+                JOIN bags ba ON b.bag_id = ba.id
+                JOIN roasts r ON ba.roast_id = r.id,
+                     json_each(r.tasting_notes) j
+                WHERE strftime('%Y', b.created_at) = ?
+                  AND r.tasting_notes IS NOT NULL AND r.tasting_notes != '[]'
+              ),
+              split(note, rest) AS (
+                SELECT TRIM(SUBSTR(val, 1, INSTR(val || ',', ',') - 1)),
+                       TRIM(SUBSTR(val, INSTR(val || ',', ',') + 1))
+                FROM raw
+                UNION ALL
+                SELECT TRIM(SUBSTR(rest, 1, INSTR(rest || ',', ',') - 1)),
+                       TRIM(SUBSTR(rest, INSTR(rest || ',', ',') + 1))
+                FROM split WHERE rest != ''
+              )
+              SELECT note as name, COUNT(*) as count
+              FROM split WHERE note != ''
+              GROUP BY LOWER(note) ORDER BY count DESC, LOWER(note)
+              LIMIT ?",
+        )
+        .bind(&year_text)
+        .bind(i64::from(TOP_TASTING_NOTES))
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|err| RepositoryError::unexpected(err.to_string()))?
+        .into_iter()
+        .map(|r| (r.name, r.count as u64))
+        .collect();
+
+        let process_counts: Vec<(String, u64)> = query_as::<_, NameCount>(
+            r"SELECT TRIM(r.process) as name, COUNT(*) as count
+               FROM brews b
+               JOIN bags ba ON b.bag_id = ba.id
+               JOIN roasts r ON ba.roast_id = r.id
+               WHERE strftime('%Y', b.created_at) = ?
+                 AND r.process IS NOT NULL AND TRIM(r.process) != ''
+               GROUP BY LOWER(TRIM(r.process))",
+        )
+        .bind(&year_text)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|err| RepositoryError::unexpected(err.to_string()))?
+        .into_iter()
+        .map(|r| (r.name, r.count as u64))
+        .collect();
+
+        // Custom processes carry their own family.
+        let taxonomy = ProcessTaxonomy::new(
+            SqlCustomProcessRepository::new(self.pool.clone())
+                .list()
+                .await?,
+        );
+
+        Ok(TastingStats {
+            year,
+            tasting_note_counts,
+            process_counts: process_family_counts(process_counts, &taxonomy),
+        })
+    }
+
+    async fn brewing_habits(&self) -> Result<BrewingHabits, RepositoryError> {
+        let weekdays: Vec<(u32, u64)> = query_as::<_, BucketCount>(
+            r"SELECT CAST(strftime('%w', created_at) AS INTEGER) as bucket, COUNT(*) as count
+               FROM brews
+               GROUP BY bucket",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|err| RepositoryError::unexpected(err.to_string()))?
+        .into_iter()
+        .map(BucketCount::into_tuple)
+        .collect();
+
+        let hours: Vec<(u32, u64)> = query_as::<_, BucketCount>(
+            r"SELECT CAST(strftime('%H', created_at) AS INTEGER) as bucket, COUNT(*) as count
+               FROM brews
+               GROUP BY bucket",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|err| RepositoryError::unexpected(err.to_string()))?
+        .into_iter()
+        .map(BucketCount::into_tuple)
+        .collect();
+
+        Ok(BrewingHabits::from_buckets(&weekdays, &hours))
+    }
+
+    async fn roaster_leaderboard(&self) -> Result<Vec<RoasterLeaderboardEntry>, RepositoryError> {
+        let rows = query_as::<_, LeaderboardRecord>(
+            r"SELECT ro.id as roaster_id, ro.name as name, ro.slug as slug,
+                     COALESCE(SUM(br.coffee_weight), 0.0) as grams_brewed,
+                     COUNT(br.id) as brew_count,
+                     COALESCE(SUM(EXISTS (SELECT 1 FROM json_each(br.quick_notes) WHERE value = ?)), 0) as good_brew_count,
+                     MAX(ba.created_at) as last_purchased_at
+               FROM roasters ro
+               JOIN roasts r ON r.roaster_id = ro.id
+               JOIN bags ba ON ba.roast_id = r.id
+               LEFT JOIN brews br ON br.bag_id = ba.id
+               GROUP BY ro.id
+               ORDER BY grams_brewed DESC, LOWER(ro.name)",
+        )
+        .bind(QuickNote::Good.label())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|err| RepositoryError::unexpected(err.to_string()))?;
+
+        Ok(rows
+            .into_iter()
+            .map(LeaderboardRecord::into_domain)
+            .collect())
+    }
+
     async fn get_cached(&self) -> Result<Option<CachedStats>, RepositoryError> {
         let row = sqlx::query(r"SELECT data FROM stats_cache WHERE id = 1")
             .fetch_optional(&self.pool)
@@ -411,4 +582,174 @@ impl StatsRepository for SqlStatsRepository {
         info!("stats cache updated");
         Ok(())
     }
+
+    async fn period_summary(
+        &self,
+        period: StatsPeriod,
+        today: NaiveDate,
+    ) -> Result<PeriodSummary, RepositoryError> {
+        let buckets = period.buckets(today);
+        let format = period.bucket_format();
+        let first = buckets.first().cloned().unwrap_or_default();
+        let last = buckets.last().cloned().unwrap_or_default();
+
+        let rows: Vec<(String, f64, i64)> = query_as(
+            r"SELECT strftime(?1, created_at) AS bucket,
+                     COALESCE(SUM(coffee_weight), 0.0) AS grams,
+                     COUNT(*) AS brews
+               FROM brews
+               WHERE strftime(?1, created_at) BETWEEN ?2 AND ?3
+               GROUP BY bucket",
+        )
+        .bind(format)
+        .bind(&first)
+        .bind(&last)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|err| RepositoryError::unexpected(err.to_string()))?;
+
+        let cups: i64 = query_scalar(
+            r"SELECT COUNT(*) FROM cups
+               WHERE strftime(?1, created_at) BETWEEN ?2 AND ?3",
+        )
+        .bind(format)
+        .bind(&first)
+        .bind(&last)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|err| RepositoryError::unexpected(err.to_string()))?;
+
+        let top_roasters: Vec<(String, f64)> = query_as(
+            r"SELECT ro.name, SUM(br.coffee_weight) AS grams
+               FROM brews br
+               JOIN bags ba ON br.bag_id = ba.id
+               JOIN roasts r ON ba.roast_id = r.id
+               JOIN roasters ro ON r.roaster_id = ro.id
+               WHERE strftime(?1, br.created_at) BETWEEN ?2 AND ?3
+               GROUP BY ro.id
+               ORDER BY grams DESC, LOWER(ro.name)
+               LIMIT ?4",
+        )
+        .bind(format)
+        .bind(&first)
+        .bind(&last)
+        .bind(i64::from(TOP_PERIOD_ENTRIES))
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|err| RepositoryError::unexpected(err.to_string()))?;
+
+        let top_origins: Vec<(String, u64)> = query_as::<_, NameCount>(
+            r"SELECT TRIM(r.origin) AS name, COUNT(*) AS count
+               FROM brews br
+               JOIN bags ba ON br.bag_id = ba.id
+               JOIN roasts r ON ba.roast_id = r.id
+               WHERE strftime(?1, br.created_at) BETWEEN ?2 AND ?3
+                 AND r.origin IS NOT NULL AND TRIM(r.origin) != ''
+               GROUP BY LOWER(TRIM(r.origin))
+               ORDER BY count DESC, LOWER(name)
+               LIMIT ?4",
+        )
+        .bind(format)
+        .bind(&first)
+        .bind(&last)
+        .bind(i64::from(TOP_PERIOD_ENTRIES))
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|err| RepositoryError::unexpected(err.to_string()))?
+        .into_iter()
+        .map(|r| (r.name, r.count as u64))
+        .collect();
+
+        let history: Vec<(String, f64)> = rows
+            .iter()
+            .map(|(bucket, grams, _)| (bucket.clone(), *grams))
+            .collect();
+
+        Ok(PeriodSummary {
+            grams: history.iter().map(|(_, grams)| grams).sum(),
+            brews: rows.iter().map(|(_, _, brews)| *brews as u64).sum(),
+            cups: cups as u64,
+            history: PeriodSummary::fill_history(buckets, &history),
+            top_roasters,
+            top_origins,
+        })
+    }
+
+    async fn refresh_roast_ratings(&self) -> Result<(), RepositoryError> {
+        let mut stars: BTreeMap<i64, Vec<u8>> = BTreeMap::new();
+
+        let brews: Vec<(i64, Option<String>)> = query_as(
+            r"SELECT ba.roast_id, br.quick_notes
+               FROM brews br
+               JOIN bags ba ON br.bag_id = ba.id",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|err| RepositoryError::unexpected(err.to_string()))?;
+        for (roast_id, raw) in brews {
+            let notes = decode_quick_notes(raw);
+            if let Some(rating) = net_rating(&notes) {
+                stars.entry(roast_id).or_default().push(brew_stars(rating));
+            }
+        }
+
+        let cups: Vec<(i64, i64)> =
+            query_as(r"SELECT roast_id, rating FROM cups WHERE rating IS NOT NULL")
+                .fetch_all(&self.pool)
+                .await
+                .map_err(|err| RepositoryError::unexpected(err.to_string()))?;
+        for (roast_id, rating) in cups {
+            if let Ok(rating) = u8::try_from(rating) {
+                stars.entry(roast_id).or_default().push(rating);
+            }
+        }
+
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|err| RepositoryError::unexpected(err.to_string()))?;
+        sqlx::query(r"UPDATE roasts SET rating = NULL WHERE rating IS NOT NULL")
+            .execute(&mut *tx)
+            .await
+            .map_err(|err| RepositoryError::unexpected(err.to_string()))?;
+        for (roast_id, stars) in stars {
+            sqlx::query(r"UPDATE roasts SET rating = ? WHERE id = ?")
+                .bind(average_stars(stars))
+                .bind(roast_id)
+                .execute(&mut *tx)
+                .await
+                .map_err(|err| RepositoryError::unexpected(err.to_string()))?;
+        }
+        tx.commit()
+            .await
+            .map_err(|err| RepositoryError::unexpected(err.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn spend_summary(&self) -> Result<SpendStats, RepositoryError> {
+        let spend: Vec<(Option<String>, i64, f64)> = query_as(
+            r"SELECT currency, COUNT(*), SUM(price)
+               FROM bags
+               WHERE price IS NOT NULL
+               GROUP BY currency",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|err| RepositoryError::unexpected(err.to_string()))?;
+
+        let settings = SqlSettingsRepository::new(self.pool.clone());
+        let base_currency = settings.get().await?.base_currency;
+        let rates = settings.list_exchange_rates().await?;
+
+        Ok(SpendStats::convert(
+            spend
+                .into_iter()
+                .map(|(currency, bags, amount)| (currency, bags as u64, amount))
+                .collect(),
+            base_currency,
+            &rates,
+        ))
+    }
 }
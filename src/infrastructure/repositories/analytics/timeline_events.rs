@@ -1,15 +1,18 @@
 use crate::domain::RepositoryError;
 use crate::domain::entity_type::EntityType;
-use crate::domain::ids::TimelineEventId;
+use crate::domain::ids::{TimelineEventId, UserId};
 use crate::domain::listing::{ListRequest, Page, SortDirection};
 use crate::domain::repositories::TimelineEventRepository;
 use crate::domain::timeline::{
-    NewTimelineEvent, TimelineBrewData, TimelineEvent, TimelineEventDetail, TimelineSortKey,
+    NewTimelineEvent, TimelineBrewData, TimelineEvent, TimelineEventDetail, TimelineFilter,
+    TimelineMonth, TimelineMonthCount, TimelineSortKey,
 };
 use crate::infrastructure::database::DatabasePool;
+use crate::infrastructure::repositories::query_timing::timed;
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use serde_json::from_str;
+use sqlx::AssertSqlSafe;
 
 #[derive(Clone)]
 pub struct SqlTimelineEventRepository {
@@ -22,13 +25,35 @@ impl SqlTimelineEventRepository {
     }
 }
 
+/// SAFETY: Direct interpolation is safe here because IDs are i64 from typed
+/// wrappers and month keys are formatted from integers.
+fn where_clause(filter: TimelineFilter) -> String {
+    let conditions: Vec<String> = [
+        filter
+            .created_by
+            .map(|user_id| format!("created_by = {}", user_id.into_inner())),
+        filter
+            .month
+            .map(|month| format!("strftime('%Y-%m', occurred_at) = '{}'", month.key())),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+
+    if conditions.is_empty() {
+        String::new()
+    } else {
+        format!(" WHERE {}", conditions.join(" AND "))
+    }
+}
+
 #[async_trait]
 impl TimelineEventRepository for SqlTimelineEventRepository {
     async fn insert(&self, event: NewTimelineEvent) -> Result<TimelineEvent, RepositoryError> {
         let query = r"
-            INSERT INTO timeline_events (entity_type, entity_id, action, occurred_at, title, details_json, tasting_notes_json, slug, roaster_slug, brew_data_json)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
-            RETURNING id, entity_type, entity_id, action, occurred_at, title, details_json, tasting_notes_json, slug, roaster_slug, brew_data_json
+            INSERT INTO timeline_events (entity_type, entity_id, action, occurred_at, title, details_json, tasting_notes_json, slug, roaster_slug, brew_data_json, created_by)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            RETURNING id, entity_type, entity_id, action, occurred_at, title, details_json, tasting_notes_json, slug, roaster_slug, brew_data_json, created_by
         ";
 
         let details_json = serde_json::to_string(&event.details).map_err(|err| {
@@ -61,6 +86,7 @@ impl TimelineEventRepository for SqlTimelineEventRepository {
             .bind(event.slug)
             .bind(event.roaster_slug)
             .bind(brew_data_json)
+            .bind(event.created_by.map(i64::from))
             .fetch_one(&self.pool)
             .await
             .map_err(|err| RepositoryError::unexpected(err.to_string()))?;
@@ -95,10 +121,11 @@ impl TimelineEventRepository for SqlTimelineEventRepository {
 
         sqlx::query(
             r"UPDATE timeline_events
-              SET title = ?, details_json = ?, tasting_notes_json = ?,
+              SET occurred_at = ?, title = ?, details_json = ?, tasting_notes_json = ?,
                   slug = ?, roaster_slug = ?, brew_data_json = ?
               WHERE entity_type = ? AND entity_id = ?",
         )
+        .bind(event.occurred_at)
         .bind(event.title)
         .bind(details_json)
         .bind(tasting_notes_json)
@@ -136,8 +163,39 @@ impl TimelineEventRepository for SqlTimelineEventRepository {
         Ok(())
     }
 
+    async fn delete_in_month(&self, month: TimelineMonth) -> Result<(), RepositoryError> {
+        let where_clause = where_clause(TimelineFilter::all().in_month(month));
+        sqlx::query(AssertSqlSafe(format!(
+            "DELETE FROM timeline_events{where_clause}"
+        )))
+        .execute(&self.pool)
+        .await
+        .map_err(|err| RepositoryError::unexpected(err.to_string()))?;
+        Ok(())
+    }
+
+    async fn delete_orphaned(&self) -> Result<u64, RepositoryError> {
+        let result = sqlx::query(
+            r"DELETE FROM timeline_events
+              WHERE (entity_type = 'roaster' AND NOT EXISTS (SELECT 1 FROM roasters WHERE id = entity_id))
+                 OR (entity_type = 'roast' AND NOT EXISTS (SELECT 1 FROM roasts WHERE id = entity_id))
+                 OR (entity_type = 'bag' AND NOT EXISTS (SELECT 1 FROM bags WHERE id = entity_id))
+                 OR (entity_type = 'gear' AND NOT EXISTS (SELECT 1 FROM gear WHERE id = entity_id))
+                 OR (entity_type = 'brew' AND NOT EXISTS (SELECT 1 FROM brews WHERE id = entity_id))
+                 OR (entity_type = 'cafe' AND NOT EXISTS (SELECT 1 FROM cafes WHERE id = entity_id))
+                 OR (entity_type = 'cup' AND NOT EXISTS (SELECT 1 FROM cups WHERE id = entity_id))
+                 OR (entity_type = 'event' AND NOT EXISTS (SELECT 1 FROM events WHERE id = entity_id))
+                 OR (entity_type = 'trip' AND NOT EXISTS (SELECT 1 FROM trips WHERE id = entity_id))",
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|err| RepositoryError::unexpected(err.to_string()))?;
+        Ok(result.rows_affected())
+    }
+
     async fn list(
         &self,
+        filter: TimelineFilter,
         request: &ListRequest<TimelineSortKey>,
     ) -> Result<Page<TimelineEvent>, RepositoryError> {
         let direction_sql = match request.sort_direction() {
@@ -147,24 +205,113 @@ impl TimelineEventRepository for SqlTimelineEventRepository {
 
         let order_clause = format!("occurred_at {direction_sql}, id DESC");
 
+        let where_clause = where_clause(filter);
+
         // All data is now denormalized in the timeline_events table - no JOINs needed
-        let base_query = r"SELECT
+        let base_query = format!(
+            r"SELECT
+            id, entity_type, entity_id, action, occurred_at, title,
+            details_json, tasting_notes_json, slug, roaster_slug, brew_data_json, created_by
+        FROM timeline_events{where_clause}"
+        );
+
+        let count_query = format!("SELECT COUNT(*) FROM timeline_events{where_clause}");
+
+        timed(
+            "timeline_events.list",
+            crate::infrastructure::repositories::pagination::paginate(
+                &self.pool,
+                request,
+                &base_query,
+                &count_query,
+                &order_clause,
+                None,
+                |record: TimelineEventRecord| record.into_domain(),
+            ),
+        )
+        .await
+    }
+
+    async fn list_logged(
+        &self,
+        filter: TimelineFilter,
+        after: Option<TimelineEventId>,
+        limit: u32,
+    ) -> Result<Vec<TimelineEvent>, RepositoryError> {
+        let mut where_clause = where_clause(filter);
+        if let Some(after) = after {
+            let joiner = if where_clause.is_empty() {
+                " WHERE"
+            } else {
+                " AND"
+            };
+            where_clause = format!("{where_clause}{joiner} id > {}", after.into_inner());
+        }
+        // Oldest first when following on from `after`, otherwise the newest.
+        let direction = if after.is_some() { "ASC" } else { "DESC" };
+        let query = format!(
+            r"SELECT
             id, entity_type, entity_id, action, occurred_at, title,
-            details_json, tasting_notes_json, slug, roaster_slug, brew_data_json
-        FROM timeline_events";
-
-        let count_query = "SELECT COUNT(*) FROM timeline_events";
-
-        crate::infrastructure::repositories::pagination::paginate(
-            &self.pool,
-            request,
-            base_query,
-            count_query,
-            &order_clause,
-            None,
-            |record: TimelineEventRecord| record.into_domain(),
+            details_json, tasting_notes_json, slug, roaster_slug, brew_data_json, created_by
+        FROM timeline_events{where_clause}
+        ORDER BY id {direction}
+        LIMIT ?"
+        );
+
+        let records: Vec<TimelineEventRecord> = timed(
+            "timeline_events.list_logged",
+            sqlx::query_as(AssertSqlSafe(query))
+                .bind(i64::from(limit))
+                .fetch_all(&self.pool),
+        )
+        .await
+        .map_err(|err| RepositoryError::unexpected(err.to_string()))?;
+
+        let mut events = records
+            .into_iter()
+            .map(TimelineEventRecord::into_domain)
+            .collect::<Result<Vec<_>, _>>()?;
+        if after.is_none() {
+            events.reverse();
+        }
+        Ok(events)
+    }
+
+    async fn month_counts(
+        &self,
+        filter: TimelineFilter,
+    ) -> Result<Vec<TimelineMonthCount>, RepositoryError> {
+        let where_clause = where_clause(TimelineFilter {
+            month: None,
+            ..filter
+        });
+        let query = format!(
+            r"SELECT CAST(strftime('%Y', occurred_at) AS INTEGER) AS year,
+                     CAST(strftime('%m', occurred_at) AS INTEGER) AS month,
+                     COUNT(*) AS count
+              FROM timeline_events{where_clause}
+              GROUP BY year, month
+              ORDER BY year DESC, month DESC"
+        );
+
+        let rows: Vec<(i64, i64, i64)> = timed(
+            "timeline_events.month_counts",
+            sqlx::query_as(AssertSqlSafe(query)).fetch_all(&self.pool),
         )
         .await
+        .map_err(|err| RepositoryError::unexpected(err.to_string()))?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|(year, month, count)| {
+                let month =
+                    TimelineMonth::new(i32::try_from(year).ok()?, u32::try_from(month).ok()?)?;
+                Some(TimelineMonthCount {
+                    month,
+                    count: u64::try_from(count).unwrap_or_default(),
+                })
+            })
+            .collect())
     }
 }
 
@@ -181,6 +328,7 @@ struct TimelineEventRecord {
     slug: Option<String>,
     roaster_slug: Option<String>,
     brew_data_json: Option<String>,
+    created_by: Option<i64>,
 }
 
 impl TimelineEventRecord {
@@ -230,6 +378,7 @@ impl TimelineEventRecord {
             slug: self.slug,
             roaster_slug: self.roaster_slug,
             brew_data,
+            created_by: self.created_by.map(UserId::from),
         })
     }
 }
@@ -0,0 +1,153 @@
+use std::str::FromStr;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{query, query_as};
+
+use crate::domain::RepositoryError;
+use crate::domain::ids::UserId;
+use crate::domain::list_preferences::{ListName, ListPreference, NewListPreference};
+use crate::domain::listing::SortDirection;
+use crate::domain::repositories::ListPreferenceRepository;
+use crate::infrastructure::database::DatabasePool;
+
+#[derive(Clone)]
+pub struct SqlListPreferenceRepository {
+    pool: DatabasePool,
+}
+
+impl SqlListPreferenceRepository {
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl ListPreferenceRepository for SqlListPreferenceRepository {
+    async fn get(
+        &self,
+        user_id: UserId,
+        list: ListName,
+    ) -> Result<ListPreference, RepositoryError> {
+        let query = "SELECT user_id, list, page_size, sort_key, sort_direction, updated_at FROM list_preferences WHERE user_id = ? AND list = ?";
+
+        let record = query_as::<_, ListPreferenceRecord>(query)
+            .bind(i64::from(user_id))
+            .bind(list.as_str())
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|err| {
+                RepositoryError::unexpected(format!("failed to get list preference: {err}"))
+            })?
+            .ok_or(RepositoryError::NotFound)?;
+
+        record.try_into()
+    }
+
+    async fn list_by_user(&self, user_id: UserId) -> Result<Vec<ListPreference>, RepositoryError> {
+        let query = "SELECT user_id, list, page_size, sort_key, sort_direction, updated_at FROM list_preferences WHERE user_id = ? ORDER BY list";
+
+        let records = query_as::<_, ListPreferenceRecord>(query)
+            .bind(i64::from(user_id))
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|err| {
+                RepositoryError::unexpected(format!("failed to list list preferences: {err}"))
+            })?;
+
+        records.into_iter().map(TryInto::try_into).collect()
+    }
+
+    async fn upsert(
+        &self,
+        preference: NewListPreference,
+    ) -> Result<ListPreference, RepositoryError> {
+        let query = "INSERT INTO list_preferences (user_id, list, page_size, sort_key, sort_direction) VALUES (?, ?, ?, ?, ?) \
+             ON CONFLICT (user_id, list) DO UPDATE SET page_size = excluded.page_size, sort_key = excluded.sort_key, \
+             sort_direction = excluded.sort_direction, updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') \
+             RETURNING user_id, list, page_size, sort_key, sort_direction, updated_at";
+
+        let NewListPreference {
+            user_id,
+            list,
+            page_size,
+            sort_key,
+            sort_direction,
+        } = preference;
+
+        let record = query_as::<_, ListPreferenceRecord>(query)
+            .bind(i64::from(user_id))
+            .bind(list.as_str())
+            .bind(page_size.map(i64::from))
+            .bind(sort_key)
+            .bind(sort_direction.map(SortDirection::as_str))
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|err| {
+                RepositoryError::unexpected(format!("failed to save list preference: {err}"))
+            })?;
+
+        record.try_into()
+    }
+
+    async fn delete(&self, user_id: UserId, list: ListName) -> Result<(), RepositoryError> {
+        let result = query("DELETE FROM list_preferences WHERE user_id = ? AND list = ?")
+            .bind(i64::from(user_id))
+            .bind(list.as_str())
+            .execute(&self.pool)
+            .await
+            .map_err(|err| {
+                RepositoryError::unexpected(format!("failed to delete list preference: {err}"))
+            })?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct ListPreferenceRecord {
+    user_id: i64,
+    list: String,
+    page_size: Option<i64>,
+    sort_key: Option<String>,
+    sort_direction: Option<String>,
+    updated_at: DateTime<Utc>,
+}
+
+impl TryFrom<ListPreferenceRecord> for ListPreference {
+    type Error = RepositoryError;
+
+    fn try_from(record: ListPreferenceRecord) -> Result<Self, Self::Error> {
+        let list = ListName::from_str(&record.list)
+            .map_err(|()| RepositoryError::unexpected(format!("invalid list: {}", record.list)))?;
+
+        let page_size = record
+            .page_size
+            .map(u32::try_from)
+            .transpose()
+            .map_err(|err| RepositoryError::unexpected(format!("invalid page size: {err}")))?;
+
+        let sort_direction = record
+            .sort_direction
+            .as_deref()
+            .map(|value| {
+                SortDirection::from_str(value).map_err(|()| {
+                    RepositoryError::unexpected(format!("invalid sort direction: {value}"))
+                })
+            })
+            .transpose()?;
+
+        Ok(ListPreference {
+            user_id: UserId::from(record.user_id),
+            list,
+            page_size,
+            sort_key: record.sort_key,
+            sort_direction,
+            updated_at: record.updated_at,
+        })
+    }
+}
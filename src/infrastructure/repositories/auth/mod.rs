@@ -1,5 +1,8 @@
+pub mod list_preferences;
+pub mod notifications;
 pub mod passkey_credentials;
 pub mod registration_tokens;
+pub mod saved_searches;
 pub mod sessions;
 pub mod tokens;
 pub mod users;
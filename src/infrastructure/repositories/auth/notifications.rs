@@ -0,0 +1,148 @@
+use std::str::FromStr;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{AssertSqlSafe, query, query_as, query_scalar};
+
+use crate::domain::RepositoryError;
+use crate::domain::ids::{NotificationId, UserId};
+use crate::domain::notifications::{NewNotification, Notification, NotificationKind};
+use crate::domain::repositories::NotificationRepository;
+use crate::infrastructure::database::DatabasePool;
+
+const NOTIFICATION_COLUMNS: &str = "id, user_id, kind, title, link, read_at, created_at";
+
+#[derive(Clone)]
+pub struct SqlNotificationRepository {
+    pool: DatabasePool,
+}
+
+impl SqlNotificationRepository {
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl NotificationRepository for SqlNotificationRepository {
+    async fn insert(&self, notification: NewNotification) -> Result<Notification, RepositoryError> {
+        let sql = format!(
+            "INSERT INTO notifications (user_id, kind, title, link, created_at) \
+             VALUES (?, ?, ?, ?, ?) RETURNING {NOTIFICATION_COLUMNS}"
+        );
+
+        let record = query_as::<_, NotificationRecord>(AssertSqlSafe(sql))
+            .bind(notification.user_id.into_inner())
+            .bind(notification.kind.as_str())
+            .bind(&notification.title)
+            .bind(&notification.link)
+            .bind(Utc::now())
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|err| {
+                RepositoryError::unexpected(format!("failed to insert notification: {err}"))
+            })?;
+
+        record.try_into()
+    }
+
+    async fn list_by_user(
+        &self,
+        user_id: UserId,
+        limit: u32,
+    ) -> Result<Vec<Notification>, RepositoryError> {
+        let sql = format!(
+            "SELECT {NOTIFICATION_COLUMNS} FROM notifications WHERE user_id = ? \
+             ORDER BY created_at DESC, id DESC LIMIT ?"
+        );
+
+        let records = query_as::<_, NotificationRecord>(AssertSqlSafe(sql))
+            .bind(user_id.into_inner())
+            .bind(i64::from(limit))
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|err| {
+                RepositoryError::unexpected(format!("failed to list notifications: {err}"))
+            })?;
+
+        records.into_iter().map(TryInto::try_into).collect()
+    }
+
+    async fn count_unread(&self, user_id: UserId) -> Result<u64, RepositoryError> {
+        let count = query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM notifications WHERE user_id = ? AND read_at IS NULL",
+        )
+        .bind(user_id.into_inner())
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|err| RepositoryError::unexpected(err.to_string()))?;
+
+        Ok(u64::try_from(count).unwrap_or_default())
+    }
+
+    async fn mark_read(
+        &self,
+        user_id: UserId,
+        id: NotificationId,
+    ) -> Result<Notification, RepositoryError> {
+        // Keep the original timestamp if it was already read.
+        let sql = format!(
+            "UPDATE notifications SET read_at = COALESCE(read_at, ?) \
+             WHERE id = ? AND user_id = ? RETURNING {NOTIFICATION_COLUMNS}"
+        );
+
+        let record = query_as::<_, NotificationRecord>(AssertSqlSafe(sql))
+            .bind(Utc::now())
+            .bind(id.into_inner())
+            .bind(user_id.into_inner())
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|err| RepositoryError::unexpected(err.to_string()))?
+            .ok_or(RepositoryError::NotFound)?;
+
+        record.try_into()
+    }
+
+    async fn mark_all_read(&self, user_id: UserId) -> Result<u64, RepositoryError> {
+        let result =
+            query("UPDATE notifications SET read_at = ? WHERE user_id = ? AND read_at IS NULL")
+                .bind(Utc::now())
+                .bind(user_id.into_inner())
+                .execute(&self.pool)
+                .await
+                .map_err(|err| RepositoryError::unexpected(err.to_string()))?;
+
+        Ok(result.rows_affected())
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct NotificationRecord {
+    id: i64,
+    user_id: i64,
+    kind: String,
+    title: String,
+    link: Option<String>,
+    read_at: Option<DateTime<Utc>>,
+    created_at: DateTime<Utc>,
+}
+
+impl TryFrom<NotificationRecord> for Notification {
+    type Error = RepositoryError;
+
+    fn try_from(record: NotificationRecord) -> Result<Self, Self::Error> {
+        let kind = NotificationKind::from_str(&record.kind).map_err(|()| {
+            RepositoryError::unexpected(format!("invalid notification kind: {}", record.kind))
+        })?;
+
+        Ok(Notification {
+            id: NotificationId::new(record.id),
+            user_id: UserId::new(record.user_id),
+            kind,
+            title: record.title,
+            link: record.link,
+            read_at: record.read_at,
+            created_at: record.created_at,
+        })
+    }
+}
@@ -26,15 +26,17 @@ impl RegistrationTokenRepository for SqlRegistrationTokenRepository {
         token: NewRegistrationToken,
     ) -> Result<RegistrationToken, RepositoryError> {
         let sql = r"
-            INSERT INTO registration_tokens (token_hash, created_at, expires_at)
-            VALUES (?, ?, ?)
-            RETURNING id, token_hash, created_at, expires_at, used_at, used_by_user_id
+            INSERT INTO registration_tokens (token_hash, created_at, expires_at, max_uses)
+            VALUES (?, ?, ?, ?)
+            RETURNING id, token_hash, created_at, expires_at, max_uses, use_count, used_at,
+                      used_by_user_id, revoked_at
         ";
 
         let record = query_as::<_, RegistrationTokenRecord>(sql)
             .bind(&token.token_hash)
             .bind(token.created_at)
             .bind(token.expires_at)
+            .bind(token.max_uses)
             .fetch_one(&self.pool)
             .await
             .map_err(|err| {
@@ -49,7 +51,8 @@ impl RegistrationTokenRepository for SqlRegistrationTokenRepository {
         token_hash: &str,
     ) -> Result<RegistrationToken, RepositoryError> {
         let sql = r"
-            SELECT id, token_hash, created_at, expires_at, used_at, used_by_user_id
+            SELECT id, token_hash, created_at, expires_at, max_uses, use_count, used_at,
+                   used_by_user_id, revoked_at
             FROM registration_tokens
             WHERE token_hash = ?
         ";
@@ -66,6 +69,48 @@ impl RegistrationTokenRepository for SqlRegistrationTokenRepository {
         Ok(record.into())
     }
 
+    async fn list(&self) -> Result<Vec<RegistrationToken>, RepositoryError> {
+        let sql = r"
+            SELECT id, token_hash, created_at, expires_at, max_uses, use_count, used_at,
+                   used_by_user_id, revoked_at
+            FROM registration_tokens
+            ORDER BY created_at DESC, id DESC
+        ";
+
+        let records = query_as::<_, RegistrationTokenRecord>(sql)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|err| {
+                RepositoryError::unexpected(format!("failed to list registration tokens: {err}"))
+            })?;
+
+        Ok(records.into_iter().map(Into::into).collect())
+    }
+
+    async fn claim_use(&self, id: RegistrationTokenId) -> Result<(), RepositoryError> {
+        // Guarded in SQL so concurrent registrations cannot exceed max_uses.
+        let sql = r"
+            UPDATE registration_tokens SET use_count = use_count + 1
+            WHERE id = ? AND revoked_at IS NULL AND use_count < max_uses
+        ";
+
+        let result = sqlx::query(sql)
+            .bind(i64::from(id))
+            .execute(&self.pool)
+            .await
+            .map_err(|err| {
+                RepositoryError::unexpected(format!("failed to claim registration token: {err}"))
+            })?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::conflict(
+                "registration token has no uses remaining",
+            ));
+        }
+
+        Ok(())
+    }
+
     async fn mark_used(
         &self,
         id: RegistrationTokenId,
@@ -88,6 +133,27 @@ impl RegistrationTokenRepository for SqlRegistrationTokenRepository {
 
         Ok(())
     }
+
+    async fn revoke(&self, id: RegistrationTokenId) -> Result<RegistrationToken, RepositoryError> {
+        let sql = r"
+            UPDATE registration_tokens SET revoked_at = COALESCE(revoked_at, ?)
+            WHERE id = ?
+            RETURNING id, token_hash, created_at, expires_at, max_uses, use_count, used_at,
+                      used_by_user_id, revoked_at
+        ";
+
+        let record = query_as::<_, RegistrationTokenRecord>(sql)
+            .bind(Utc::now())
+            .bind(i64::from(id))
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|err| {
+                RepositoryError::unexpected(format!("failed to revoke registration token: {err}"))
+            })?
+            .ok_or(RepositoryError::NotFound)?;
+
+        Ok(record.into())
+    }
 }
 
 #[derive(sqlx::FromRow)]
@@ -96,8 +162,11 @@ struct RegistrationTokenRecord {
     token_hash: String,
     created_at: DateTime<Utc>,
     expires_at: DateTime<Utc>,
+    max_uses: u32,
+    use_count: u32,
     used_at: Option<DateTime<Utc>>,
     used_by_user_id: Option<i64>,
+    revoked_at: Option<DateTime<Utc>>,
 }
 
 impl From<RegistrationTokenRecord> for RegistrationToken {
@@ -107,8 +176,11 @@ impl From<RegistrationTokenRecord> for RegistrationToken {
             token_hash: record.token_hash,
             created_at: record.created_at,
             expires_at: record.expires_at,
+            max_uses: record.max_uses,
+            use_count: record.use_count,
             used_at: record.used_at,
             used_by_user_id: record.used_by_user_id.map(UserId::from),
+            revoked_at: record.revoked_at,
         }
     }
 }
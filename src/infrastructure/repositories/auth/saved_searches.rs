@@ -0,0 +1,200 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{query, query_as};
+
+use crate::domain::RepositoryError;
+use crate::domain::ids::{SavedSearchId, UserId};
+use crate::domain::list_preferences::ListName;
+use crate::domain::listing::SortDirection;
+use crate::domain::repositories::SavedSearchRepository;
+use crate::domain::saved_searches::{NewSavedSearch, SavedSearch};
+use crate::infrastructure::database::DatabasePool;
+
+const SAVED_SEARCH_COLUMNS: &str =
+    "id, user_id, name, list, search, filters, sort_key, sort_direction, created_at, updated_at";
+
+#[derive(Clone)]
+pub struct SqlSavedSearchRepository {
+    pool: DatabasePool,
+}
+
+impl SqlSavedSearchRepository {
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+}
+
+fn map_write_error(err: sqlx::Error, action: &str) -> RepositoryError {
+    if let sqlx::Error::Database(db_err) = &err
+        && db_err.is_unique_violation()
+    {
+        return RepositoryError::conflict("a saved search with this name already exists");
+    }
+    RepositoryError::unexpected(format!("failed to {action} saved search: {err}"))
+}
+
+fn encode_filters(filters: &BTreeMap<String, String>) -> Result<String, RepositoryError> {
+    serde_json::to_string(filters)
+        .map_err(|err| RepositoryError::unexpected(format!("failed to encode filters: {err}")))
+}
+
+#[async_trait]
+impl SavedSearchRepository for SqlSavedSearchRepository {
+    async fn insert(&self, search: NewSavedSearch) -> Result<SavedSearch, RepositoryError> {
+        let query = format!(
+            "INSERT INTO saved_searches (user_id, name, list, search, filters, sort_key, sort_direction) \
+             VALUES (?, ?, ?, ?, ?, ?, ?) RETURNING {SAVED_SEARCH_COLUMNS}"
+        );
+
+        let filters = encode_filters(&search.filters)?;
+        let record = query_as::<_, SavedSearchRecord>(sqlx::AssertSqlSafe(query))
+            .bind(i64::from(search.user_id))
+            .bind(&search.name)
+            .bind(search.list.as_str())
+            .bind(&search.search)
+            .bind(filters)
+            .bind(&search.sort_key)
+            .bind(search.sort_direction.map(SortDirection::as_str))
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|err| map_write_error(err, "insert"))?;
+
+        record.try_into()
+    }
+
+    async fn get(
+        &self,
+        user_id: UserId,
+        id: SavedSearchId,
+    ) -> Result<SavedSearch, RepositoryError> {
+        let query = format!(
+            "SELECT {SAVED_SEARCH_COLUMNS} FROM saved_searches WHERE id = ? AND user_id = ?"
+        );
+
+        let record = query_as::<_, SavedSearchRecord>(sqlx::AssertSqlSafe(query))
+            .bind(i64::from(id))
+            .bind(i64::from(user_id))
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|err| {
+                RepositoryError::unexpected(format!("failed to get saved search: {err}"))
+            })?
+            .ok_or(RepositoryError::NotFound)?;
+
+        record.try_into()
+    }
+
+    async fn list_by_user(&self, user_id: UserId) -> Result<Vec<SavedSearch>, RepositoryError> {
+        let query = format!(
+            "SELECT {SAVED_SEARCH_COLUMNS} FROM saved_searches WHERE user_id = ? ORDER BY list, name COLLATE NOCASE"
+        );
+
+        let records = query_as::<_, SavedSearchRecord>(sqlx::AssertSqlSafe(query))
+            .bind(i64::from(user_id))
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|err| {
+                RepositoryError::unexpected(format!("failed to list saved searches: {err}"))
+            })?;
+
+        records.into_iter().map(TryInto::try_into).collect()
+    }
+
+    async fn update(
+        &self,
+        id: SavedSearchId,
+        search: NewSavedSearch,
+    ) -> Result<SavedSearch, RepositoryError> {
+        let query = format!(
+            "UPDATE saved_searches SET name = ?, list = ?, search = ?, filters = ?, sort_key = ?, sort_direction = ?, \
+             updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') \
+             WHERE id = ? AND user_id = ? RETURNING {SAVED_SEARCH_COLUMNS}"
+        );
+
+        let filters = encode_filters(&search.filters)?;
+        let record = query_as::<_, SavedSearchRecord>(sqlx::AssertSqlSafe(query))
+            .bind(&search.name)
+            .bind(search.list.as_str())
+            .bind(&search.search)
+            .bind(filters)
+            .bind(&search.sort_key)
+            .bind(search.sort_direction.map(SortDirection::as_str))
+            .bind(i64::from(id))
+            .bind(i64::from(search.user_id))
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|err| map_write_error(err, "update"))?
+            .ok_or(RepositoryError::NotFound)?;
+
+        record.try_into()
+    }
+
+    async fn delete(&self, user_id: UserId, id: SavedSearchId) -> Result<(), RepositoryError> {
+        let result = query("DELETE FROM saved_searches WHERE id = ? AND user_id = ?")
+            .bind(i64::from(id))
+            .bind(i64::from(user_id))
+            .execute(&self.pool)
+            .await
+            .map_err(|err| {
+                RepositoryError::unexpected(format!("failed to delete saved search: {err}"))
+            })?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct SavedSearchRecord {
+    id: i64,
+    user_id: i64,
+    name: String,
+    list: String,
+    search: Option<String>,
+    filters: String,
+    sort_key: Option<String>,
+    sort_direction: Option<String>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl TryFrom<SavedSearchRecord> for SavedSearch {
+    type Error = RepositoryError;
+
+    fn try_from(record: SavedSearchRecord) -> Result<Self, Self::Error> {
+        let list = ListName::from_str(&record.list)
+            .map_err(|()| RepositoryError::unexpected(format!("invalid list: {}", record.list)))?;
+
+        let filters = serde_json::from_str(&record.filters)
+            .map_err(|err| RepositoryError::unexpected(format!("invalid filters: {err}")))?;
+
+        let sort_direction = record
+            .sort_direction
+            .as_deref()
+            .map(|value| {
+                SortDirection::from_str(value).map_err(|()| {
+                    RepositoryError::unexpected(format!("invalid sort direction: {value}"))
+                })
+            })
+            .transpose()?;
+
+        Ok(SavedSearch {
+            id: SavedSearchId::from(record.id),
+            user_id: UserId::from(record.user_id),
+            name: record.name,
+            list,
+            search: record.search,
+            filters,
+            sort_key: record.sort_key,
+            sort_direction,
+            created_at: record.created_at,
+            updated_at: record.updated_at,
+        })
+    }
+}
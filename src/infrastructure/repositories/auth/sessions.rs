@@ -1,5 +1,5 @@
 use async_trait::async_trait;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use sqlx::{query, query_as};
 
 use crate::domain::ids::{SessionId, UserId};
@@ -21,20 +21,30 @@ impl SqlSessionRepository {
 #[async_trait]
 impl SessionRepository for SqlSessionRepository {
     async fn insert(&self, session: NewSession) -> Result<Session, RepositoryError> {
-        let query = "INSERT INTO sessions (user_id, session_token_hash, created_at, expires_at) VALUES (?, ?, ?, ?) RETURNING id, user_id, session_token_hash, created_at, expires_at";
+        let query = "INSERT INTO sessions (user_id, session_token_hash, created_at, expires_at, device_fingerprint, device_label, last_seen_ip, last_seen_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?) RETURNING id, user_id, session_token_hash, created_at, expires_at, device_fingerprint, device_label, last_seen_ip, last_seen_at";
 
         let NewSession {
             user_id,
             session_token_hash,
             created_at,
             expires_at,
+            device,
+            ip,
         } = session;
+        let last_seen_at = device.as_ref().map(|_| created_at);
+        let (device_fingerprint, device_label) = device
+            .map(|device| (device.fingerprint, device.label))
+            .unzip();
 
         let record = query_as::<_, SessionRecord>(query)
             .bind(i64::from(user_id))
             .bind(&session_token_hash)
             .bind(created_at)
             .bind(expires_at)
+            .bind(device_fingerprint)
+            .bind(device_label)
+            .bind(ip)
+            .bind(last_seen_at)
             .fetch_one(&self.pool)
             .await
             .map_err(|err| {
@@ -45,7 +55,7 @@ impl SessionRepository for SqlSessionRepository {
     }
 
     async fn get(&self, id: SessionId) -> Result<Session, RepositoryError> {
-        let query = "SELECT id, user_id, session_token_hash, created_at, expires_at FROM sessions WHERE id = ?";
+        let query = "SELECT id, user_id, session_token_hash, created_at, expires_at, device_fingerprint, device_label, last_seen_ip, last_seen_at FROM sessions WHERE id = ?";
 
         let record = query_as::<_, SessionRecord>(query)
             .bind(i64::from(id))
@@ -58,7 +68,7 @@ impl SessionRepository for SqlSessionRepository {
     }
 
     async fn get_by_token_hash(&self, token_hash: &str) -> Result<Session, RepositoryError> {
-        let query = "SELECT id, user_id, session_token_hash, created_at, expires_at FROM sessions WHERE session_token_hash = ?";
+        let query = "SELECT id, user_id, session_token_hash, created_at, expires_at, device_fingerprint, device_label, last_seen_ip, last_seen_at FROM sessions WHERE session_token_hash = ?";
 
         let record = query_as::<_, SessionRecord>(query)
             .bind(token_hash)
@@ -72,6 +82,59 @@ impl SessionRepository for SqlSessionRepository {
         Ok(record.into())
     }
 
+    async fn list_by_user(&self, user_id: UserId) -> Result<Vec<Session>, RepositoryError> {
+        let query = "SELECT id, user_id, session_token_hash, created_at, expires_at, device_fingerprint, device_label, last_seen_ip, last_seen_at FROM sessions WHERE user_id = ? AND expires_at >= ? ORDER BY COALESCE(last_seen_at, created_at) DESC, id DESC";
+
+        let records = query_as::<_, SessionRecord>(query)
+            .bind(i64::from(user_id))
+            .bind(Utc::now())
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|err| {
+                RepositoryError::unexpected(format!("failed to list sessions: {err}"))
+            })?;
+
+        Ok(records.into_iter().map(Session::from).collect())
+    }
+
+    async fn bind_device(
+        &self,
+        id: SessionId,
+        fingerprint: &str,
+        label: &str,
+    ) -> Result<(), RepositoryError> {
+        query("UPDATE sessions SET device_fingerprint = ?, device_label = ? WHERE id = ? AND device_fingerprint IS NULL")
+            .bind(fingerprint)
+            .bind(label)
+            .bind(i64::from(id))
+            .execute(&self.pool)
+            .await
+            .map_err(|err| {
+                RepositoryError::unexpected(format!("failed to bind session device: {err}"))
+            })?;
+
+        Ok(())
+    }
+
+    async fn touch(
+        &self,
+        id: SessionId,
+        ip: Option<&str>,
+        at: DateTime<Utc>,
+    ) -> Result<(), RepositoryError> {
+        query("UPDATE sessions SET last_seen_ip = ?, last_seen_at = ? WHERE id = ?")
+            .bind(ip)
+            .bind(at)
+            .bind(i64::from(id))
+            .execute(&self.pool)
+            .await
+            .map_err(|err| {
+                RepositoryError::unexpected(format!("failed to touch session: {err}"))
+            })?;
+
+        Ok(())
+    }
+
     async fn delete(&self, id: SessionId) -> Result<(), RepositoryError> {
         query("DELETE FROM sessions WHERE id = ?")
             .bind(i64::from(id))
@@ -103,18 +166,28 @@ struct SessionRecord {
     id: i64,
     user_id: i64,
     session_token_hash: String,
-    created_at: chrono::DateTime<Utc>,
-    expires_at: chrono::DateTime<Utc>,
+    created_at: DateTime<Utc>,
+    expires_at: DateTime<Utc>,
+    device_fingerprint: Option<String>,
+    device_label: Option<String>,
+    last_seen_ip: Option<String>,
+    last_seen_at: Option<DateTime<Utc>>,
 }
 
 impl From<SessionRecord> for Session {
     fn from(record: SessionRecord) -> Self {
-        Session::new(
-            SessionId::from(record.id),
-            UserId::from(record.user_id),
-            record.session_token_hash,
-            record.created_at,
-            record.expires_at,
-        )
+        Session {
+            device_fingerprint: record.device_fingerprint,
+            device_label: record.device_label,
+            last_seen_ip: record.last_seen_ip,
+            last_seen_at: record.last_seen_at,
+            ..Session::new(
+                SessionId::from(record.id),
+                UserId::from(record.user_id),
+                record.session_token_hash,
+                record.created_at,
+                record.expires_at,
+            )
+        }
     }
 }
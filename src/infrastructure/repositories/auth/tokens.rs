@@ -22,17 +22,19 @@ impl SqlTokenRepository {
 #[async_trait]
 impl TokenRepository for SqlTokenRepository {
     async fn insert(&self, token: NewToken) -> Result<Token, RepositoryError> {
-        let query = "INSERT INTO tokens (user_id, token_hash, name) VALUES (?, ?, ?) RETURNING id, user_id, token_hash, name, created_at, last_used_at, revoked_at";
+        let query = "INSERT INTO tokens (user_id, token_hash, lookup_prefix, name) VALUES (?, ?, ?, ?) RETURNING id, user_id, token_hash, name, created_at, last_used_at, revoked_at";
 
         let NewToken {
             user_id,
             token_hash,
+            lookup_prefix,
             name,
         } = token;
 
         let record = query_as::<_, TokenRecord>(query)
             .bind(i64::from(user_id))
             .bind(&token_hash)
+            .bind(&lookup_prefix)
             .bind(&name)
             .fetch_one(&self.pool)
             .await
@@ -74,6 +76,21 @@ impl TokenRepository for SqlTokenRepository {
         Ok(record.into())
     }
 
+    async fn list_by_lookup_prefix(
+        &self,
+        lookup_prefix: &str,
+    ) -> Result<Vec<Token>, RepositoryError> {
+        let query = "SELECT id, user_id, token_hash, name, created_at, last_used_at, revoked_at FROM tokens WHERE lookup_prefix = ?";
+
+        let records = query_as::<_, TokenRecord>(query)
+            .bind(lookup_prefix)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|err| RepositoryError::unexpected(err.to_string()))?;
+
+        Ok(records.into_iter().map(Into::into).collect())
+    }
+
     async fn list_by_user(&self, user_id: UserId) -> Result<Vec<Token>, RepositoryError> {
         let query = "SELECT id, user_id, token_hash, name, created_at, last_used_at, revoked_at FROM tokens WHERE user_id = ? ORDER BY created_at DESC";
 
@@ -115,6 +132,28 @@ impl TokenRepository for SqlTokenRepository {
 
         Ok(())
     }
+
+    async fn update_hash(
+        &self,
+        id: TokenId,
+        token_hash: &str,
+        lookup_prefix: Option<&str>,
+    ) -> Result<(), RepositoryError> {
+        let result =
+            sqlx::query("UPDATE tokens SET token_hash = ?, lookup_prefix = ? WHERE id = ?")
+                .bind(token_hash)
+                .bind(lookup_prefix)
+                .bind(i64::from(id))
+                .execute(&self.pool)
+                .await
+                .map_err(|err| RepositoryError::unexpected(err.to_string()))?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+
+        Ok(())
+    }
 }
 
 #[derive(sqlx::FromRow)]
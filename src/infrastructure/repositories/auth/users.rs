@@ -22,7 +22,7 @@ impl SqlUserRepository {
 #[async_trait]
 impl UserRepository for SqlUserRepository {
     async fn insert(&self, user: NewUser) -> Result<User, RepositoryError> {
-        let query = "INSERT INTO users (username, uuid) VALUES (?, ?) RETURNING id, username, uuid, created_at";
+        let query = "INSERT INTO users (username, uuid) VALUES (?, ?) RETURNING id, username, uuid, display_name, created_at";
 
         let record = sqlx::query_as::<_, UserRecord>(query)
             .bind(&user.username)
@@ -42,7 +42,7 @@ impl UserRepository for SqlUserRepository {
     }
 
     async fn get(&self, id: UserId) -> Result<User, RepositoryError> {
-        let query = "SELECT id, username, uuid, created_at FROM users WHERE id = ?";
+        let query = "SELECT id, username, uuid, display_name, created_at FROM users WHERE id = ?";
 
         let record = query_as::<_, UserRecord>(query)
             .bind(i64::from(id))
@@ -55,7 +55,8 @@ impl UserRepository for SqlUserRepository {
     }
 
     async fn get_by_username(&self, username: &str) -> Result<User, RepositoryError> {
-        let query = "SELECT id, username, uuid, created_at FROM users WHERE username = ?";
+        let query =
+            "SELECT id, username, uuid, display_name, created_at FROM users WHERE username = ?";
 
         let record = query_as::<_, UserRecord>(query)
             .bind(username)
@@ -68,7 +69,7 @@ impl UserRepository for SqlUserRepository {
     }
 
     async fn get_by_uuid(&self, uuid: &str) -> Result<User, RepositoryError> {
-        let query = "SELECT id, username, uuid, created_at FROM users WHERE uuid = ?";
+        let query = "SELECT id, username, uuid, display_name, created_at FROM users WHERE uuid = ?";
 
         let record = query_as::<_, UserRecord>(query)
             .bind(uuid)
@@ -91,8 +92,26 @@ impl UserRepository for SqlUserRepository {
         Ok(count > 0)
     }
 
+    async fn update_display_name(
+        &self,
+        id: UserId,
+        display_name: Option<String>,
+    ) -> Result<User, RepositoryError> {
+        let query = "UPDATE users SET display_name = ? WHERE id = ? RETURNING id, username, uuid, display_name, created_at";
+
+        let record = query_as::<_, UserRecord>(query)
+            .bind(display_name)
+            .bind(i64::from(id))
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|err| RepositoryError::unexpected(err.to_string()))?
+            .ok_or(RepositoryError::NotFound)?;
+
+        Ok(record.into())
+    }
+
     async fn list_all(&self) -> Result<Vec<User>, RepositoryError> {
-        let query = "SELECT id, username, uuid, created_at FROM users ORDER BY created_at ASC";
+        let query = "SELECT id, username, uuid, display_name, created_at FROM users ORDER BY created_at ASC";
 
         let records = query_as::<_, UserRecord>(query)
             .fetch_all(&self.pool)
@@ -108,6 +127,7 @@ struct UserRecord {
     id: i64,
     username: String,
     uuid: String,
+    display_name: Option<String>,
     created_at: DateTime<Utc>,
 }
 
@@ -117,6 +137,7 @@ impl From<UserRecord> for User {
             UserId::from(record.id),
             record.username,
             record.uuid,
+            record.display_name,
             record.created_at,
         )
     }
@@ -0,0 +1,436 @@
+//! Read-through caching for reference lists that nearly every page render
+//! fetches: roasters and gear populate the option pickers on the add, edit
+//! and check-in forms. Only unsearched "show all" listings are cached; the
+//! paginated data pages always go to the database.
+//!
+//! Whether an entity has an image is cached too, since detail pages and
+//! list thumbnails ask on every render but images rarely change.
+
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+
+use crate::domain::RepositoryError;
+use crate::domain::delete_preview::DeletePreview;
+use crate::domain::entity_type::EntityType;
+use crate::domain::gear::{Gear, GearCategory, GearFilter, GearSortKey, NewGear, UpdateGear};
+use crate::domain::ids::{GearId, RoasterId, UserId};
+use crate::domain::images::EntityImage;
+use crate::domain::listing::{ListRequest, Page, SortDirection, SortKey};
+use crate::domain::repositories::{GearRepository, ImageRepository, RoasterRepository};
+use crate::domain::roasters::{NewRoaster, Roaster, RoasterSortKey, UpdateRoaster};
+
+/// How long a cached listing is served before it is reloaded.
+pub const DEFAULT_READ_CACHE_TTL: Duration = Duration::from_secs(30);
+
+/// A map whose entries expire `ttl` after they were inserted.
+struct TtlCache<K, V> {
+    ttl: Duration,
+    entries: Mutex<HashMap<K, (Instant, V)>>,
+}
+
+impl<K: Eq + Hash, V: Clone> TtlCache<K, V> {
+    fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn get(&self, key: &K) -> Option<V> {
+        let mut entries = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+        match entries.get(key) {
+            Some((stored_at, value)) if stored_at.elapsed() < self.ttl => Some(value.clone()),
+            Some(_) => {
+                entries.remove(key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    fn insert(&self, key: K, value: V) {
+        self.entries
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(key, (Instant::now(), value));
+    }
+
+    fn remove(&self, key: &K) {
+        self.entries
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(key);
+    }
+
+    fn clear(&self) {
+        self.entries
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clear();
+    }
+}
+
+type RoasterListKey = (RoasterSortKey, SortDirection);
+type GearListKey = (Option<GearCategory>, GearSortKey, SortDirection);
+type ImageKey = (EntityType, i64);
+
+/// Shared handle to the cached roaster and gear listings and image presence.
+/// Clones share the same entries, so invalidating through any handle clears
+/// them for all.
+#[derive(Clone)]
+pub struct ReadCache {
+    roasters: Arc<TtlCache<RoasterListKey, Page<Roaster>>>,
+    gear: Arc<TtlCache<GearListKey, Page<Gear>>>,
+    images: Arc<TtlCache<ImageKey, bool>>,
+}
+
+impl ReadCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            roasters: Arc::new(TtlCache::new(ttl)),
+            gear: Arc::new(TtlCache::new(ttl)),
+            images: Arc::new(TtlCache::new(ttl)),
+        }
+    }
+
+    pub fn invalidate_roasters(&self) {
+        self.roasters.clear();
+    }
+
+    pub fn invalidate_gear(&self) {
+        self.gear.clear();
+    }
+
+    /// Forget which entities have images, after they were deleted without
+    /// going through the image repository.
+    pub fn invalidate_images(&self) {
+        self.images.clear();
+    }
+
+    /// Drop everything, e.g. after a backup restore writes rows directly.
+    pub fn invalidate_all(&self) {
+        self.invalidate_roasters();
+        self.invalidate_gear();
+        self.invalidate_images();
+    }
+}
+
+impl Default for ReadCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_READ_CACHE_TTL)
+    }
+}
+
+/// Only full, unsearched listings are worth caching — they are what the
+/// option loaders ask for, and they have a small, bounded key space.
+fn cacheable<K: SortKey>(request: &ListRequest<K>, search: Option<&str>) -> bool {
+    search.is_none() && request.page_size().is_all()
+}
+
+/// Wraps a roaster repository, serving full listings from a [`ReadCache`]
+/// and clearing them whenever a roaster is written.
+pub struct CachedRoasterRepository {
+    inner: Arc<dyn RoasterRepository>,
+    cache: ReadCache,
+}
+
+impl CachedRoasterRepository {
+    pub fn new(inner: Arc<dyn RoasterRepository>, cache: ReadCache) -> Self {
+        Self { inner, cache }
+    }
+}
+
+#[async_trait]
+impl RoasterRepository for CachedRoasterRepository {
+    async fn insert_with_slug(
+        &self,
+        roaster: NewRoaster,
+        slug: String,
+    ) -> Result<Roaster, RepositoryError> {
+        let result = self.inner.insert_with_slug(roaster, slug).await;
+        self.cache.invalidate_roasters();
+        result
+    }
+
+    async fn get(&self, id: RoasterId) -> Result<Roaster, RepositoryError> {
+        self.inner.get(id).await
+    }
+
+    async fn get_by_slug(&self, slug: &str) -> Result<Roaster, RepositoryError> {
+        self.inner.get_by_slug(slug).await
+    }
+
+    async fn list(
+        &self,
+        request: &ListRequest<RoasterSortKey>,
+        search: Option<&str>,
+    ) -> Result<Page<Roaster>, RepositoryError> {
+        if !cacheable(request, search) {
+            return self.inner.list(request, search).await;
+        }
+
+        let key = (request.sort_key(), request.sort_direction());
+        if let Some(page) = self.cache.roasters.get(&key) {
+            return Ok(page);
+        }
+        let page = self.inner.list(request, search).await?;
+        self.cache.roasters.insert(key, page.clone());
+        Ok(page)
+    }
+
+    async fn update(
+        &self,
+        id: RoasterId,
+        changes: UpdateRoaster,
+    ) -> Result<Roaster, RepositoryError> {
+        let result = self.inner.update(id, changes).await;
+        self.cache.invalidate_roasters();
+        result
+    }
+
+    async fn set_created_by(&self, id: RoasterId, user_id: UserId) -> Result<(), RepositoryError> {
+        let result = self.inner.set_created_by(id, user_id).await;
+        self.cache.invalidate_roasters();
+        result
+    }
+
+    async fn set_favourite(&self, id: RoasterId, favourite: bool) -> Result<(), RepositoryError> {
+        let result = self.inner.set_favourite(id, favourite).await;
+        self.cache.invalidate_roasters();
+        result
+    }
+
+    async fn delete(&self, id: RoasterId) -> Result<(), RepositoryError> {
+        let result = self.inner.delete(id).await;
+        self.cache.invalidate_roasters();
+        result
+    }
+
+    async fn delete_preview(&self, id: RoasterId) -> Result<DeletePreview, RepositoryError> {
+        self.inner.delete_preview(id).await
+    }
+}
+
+/// Wraps a gear repository, serving full listings from a [`ReadCache`] and
+/// clearing them whenever gear is written.
+pub struct CachedGearRepository {
+    inner: Arc<dyn GearRepository>,
+    cache: ReadCache,
+}
+
+impl CachedGearRepository {
+    pub fn new(inner: Arc<dyn GearRepository>, cache: ReadCache) -> Self {
+        Self { inner, cache }
+    }
+}
+
+#[async_trait]
+impl GearRepository for CachedGearRepository {
+    async fn insert(&self, gear: NewGear) -> Result<Gear, RepositoryError> {
+        let result = self.inner.insert(gear).await;
+        self.cache.invalidate_gear();
+        result
+    }
+
+    async fn get(&self, id: GearId) -> Result<Gear, RepositoryError> {
+        self.inner.get(id).await
+    }
+
+    async fn list(
+        &self,
+        filter: GearFilter,
+        request: &ListRequest<GearSortKey>,
+        search: Option<&str>,
+    ) -> Result<Page<Gear>, RepositoryError> {
+        if !cacheable(request, search) {
+            return self.inner.list(filter, request, search).await;
+        }
+
+        let key = (
+            filter.category,
+            request.sort_key(),
+            request.sort_direction(),
+        );
+        if let Some(page) = self.cache.gear.get(&key) {
+            return Ok(page);
+        }
+        let page = self.inner.list(filter, request, search).await?;
+        self.cache.gear.insert(key, page.clone());
+        Ok(page)
+    }
+
+    async fn update(&self, id: GearId, changes: UpdateGear) -> Result<Gear, RepositoryError> {
+        let result = self.inner.update(id, changes).await;
+        self.cache.invalidate_gear();
+        result
+    }
+
+    async fn restock(&self, id: GearId, count: i32) -> Result<Gear, RepositoryError> {
+        let result = self.inner.restock(id, count).await;
+        self.cache.invalidate_gear();
+        result
+    }
+
+    async fn set_created_by(&self, id: GearId, user_id: UserId) -> Result<(), RepositoryError> {
+        let result = self.inner.set_created_by(id, user_id).await;
+        self.cache.invalidate_gear();
+        result
+    }
+
+    async fn delete(&self, id: GearId) -> Result<(), RepositoryError> {
+        let result = self.inner.delete(id).await;
+        self.cache.invalidate_gear();
+        result
+    }
+}
+
+/// Wraps an image repository, remembering which entities have an image.
+/// Uploads and deletes update the entry in place rather than clearing the
+/// whole cache, so a list page stays cached while photos are being added.
+pub struct CachedImageRepository {
+    inner: Arc<dyn ImageRepository>,
+    cache: ReadCache,
+}
+
+impl CachedImageRepository {
+    pub fn new(inner: Arc<dyn ImageRepository>, cache: ReadCache) -> Self {
+        Self { inner, cache }
+    }
+}
+
+#[async_trait]
+impl ImageRepository for CachedImageRepository {
+    async fn upsert(&self, image: EntityImage) -> Result<(), RepositoryError> {
+        let key = (image.entity_type, image.entity_id);
+        let result = self.inner.upsert(image).await;
+        if result.is_ok() {
+            self.cache.images.insert(key, true);
+        } else {
+            self.cache.images.remove(&key);
+        }
+        result
+    }
+
+    async fn get(
+        &self,
+        entity_type: EntityType,
+        entity_id: i64,
+    ) -> Result<EntityImage, RepositoryError> {
+        self.inner.get(entity_type, entity_id).await
+    }
+
+    async fn get_thumbnail(
+        &self,
+        entity_type: EntityType,
+        entity_id: i64,
+    ) -> Result<EntityImage, RepositoryError> {
+        self.inner.get_thumbnail(entity_type, entity_id).await
+    }
+
+    async fn delete(&self, entity_type: EntityType, entity_id: i64) -> Result<(), RepositoryError> {
+        let key = (entity_type, entity_id);
+        let result = self.inner.delete(entity_type, entity_id).await;
+        if result.is_ok() {
+            self.cache.images.insert(key, false);
+        } else {
+            self.cache.images.remove(&key);
+        }
+        result
+    }
+
+    async fn has_image(
+        &self,
+        entity_type: EntityType,
+        entity_id: i64,
+    ) -> Result<bool, RepositoryError> {
+        let key = (entity_type, entity_id);
+        if let Some(present) = self.cache.images.get(&key) {
+            return Ok(present);
+        }
+        let present = self.inner.has_image(entity_type, entity_id).await?;
+        self.cache.images.insert(key, present);
+        Ok(present)
+    }
+
+    async fn ids_with_images(
+        &self,
+        entity_type: EntityType,
+        entity_ids: &[i64],
+    ) -> Result<HashSet<i64>, RepositoryError> {
+        let mut present = HashSet::new();
+        let mut unknown = Vec::new();
+        for &id in entity_ids {
+            match self.cache.images.get(&(entity_type, id)) {
+                Some(true) => {
+                    present.insert(id);
+                }
+                Some(false) => {}
+                None => unknown.push(id),
+            }
+        }
+        if unknown.is_empty() {
+            return Ok(present);
+        }
+
+        let found = self.inner.ids_with_images(entity_type, &unknown).await?;
+        for id in unknown {
+            self.cache
+                .images
+                .insert((entity_type, id), found.contains(&id));
+        }
+        present.extend(found);
+        Ok(present)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ttl_cache_returns_fresh_entries() {
+        let cache = TtlCache::new(Duration::from_secs(60));
+        cache.insert("roasters", 3);
+        assert_eq!(cache.get(&"roasters"), Some(3));
+        assert_eq!(cache.get(&"gear"), None);
+    }
+
+    #[test]
+    fn ttl_cache_drops_expired_entries() {
+        let cache = TtlCache::new(Duration::ZERO);
+        cache.insert("roasters", 3);
+        assert_eq!(cache.get(&"roasters"), None);
+    }
+
+    #[test]
+    fn invalidating_one_list_leaves_the_other() {
+        let cache = ReadCache::default();
+        let roaster_key = (RoasterSortKey::Name, SortDirection::Asc);
+        let gear_key = (None, GearSortKey::Make, SortDirection::Asc);
+        cache
+            .roasters
+            .insert(roaster_key, Page::new(Vec::new(), 1, 0, 0, true));
+        cache
+            .gear
+            .insert(gear_key, Page::new(Vec::new(), 1, 0, 0, true));
+
+        cache.invalidate_roasters();
+
+        assert!(cache.roasters.get(&roaster_key).is_none());
+        assert!(cache.gear.get(&gear_key).is_some());
+    }
+
+    #[test]
+    fn invalidating_all_forgets_image_presence() {
+        let cache = ReadCache::default();
+        cache.images.insert((EntityType::Roast, 1), true);
+
+        cache.invalidate_all();
+
+        assert!(cache.images.get(&(EntityType::Roast, 1)).is_none());
+    }
+}
@@ -0,0 +1,307 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{AssertSqlSafe, query, query_as, query_scalar};
+
+use crate::domain::aliases::{Alias, AliasTarget, NewAlias};
+use crate::domain::ids::{AliasId, RoastId, RoasterId};
+use crate::domain::repositories::AliasRepository;
+use crate::domain::{ErrorCode, RepositoryError};
+use crate::infrastructure::database::{DatabasePool, DatabaseTransaction};
+
+const ALIAS_COLUMNS: &str = "id, roaster_id, roast_id, name, created_at";
+
+#[derive(Clone)]
+pub struct SqlAliasRepository {
+    pool: DatabasePool,
+}
+
+impl SqlAliasRepository {
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+}
+
+fn unexpected(err: sqlx::Error) -> RepositoryError {
+    RepositoryError::unexpected(err.to_string())
+}
+
+/// The name of the row `id` in `table`.
+async fn name_of(
+    tx: &mut DatabaseTransaction<'_>,
+    table: &str,
+    id: i64,
+) -> Result<String, RepositoryError> {
+    let query = format!("SELECT name FROM {table} WHERE id = ?");
+    query_scalar::<_, String>(AssertSqlSafe(query))
+        .bind(id)
+        .fetch_optional(&mut **tx)
+        .await
+        .map_err(unexpected)?
+        .ok_or(RepositoryError::NotFound)
+}
+
+/// Move `source`'s aliases to `into` and add `source`'s own name, unless it
+/// is `into`'s name already. `column` is `roaster_id` or `roast_id`.
+async fn carry_aliases(
+    tx: &mut DatabaseTransaction<'_>,
+    column: &str,
+    source: (i64, &str),
+    into: (i64, &str),
+) -> Result<(), RepositoryError> {
+    let carried = format!(
+        "INSERT OR IGNORE INTO aliases ({column}, name, slug, created_at) \
+         SELECT ?, name, slug, created_at FROM aliases WHERE {column} = ?"
+    );
+    query(AssertSqlSafe(carried))
+        .bind(into.0)
+        .bind(source.0)
+        .execute(&mut **tx)
+        .await
+        .map_err(unexpected)?;
+
+    let slug = slug::slugify(source.1);
+    if slug == slug::slugify(into.1) {
+        return Ok(());
+    }
+    let merged = format!(
+        "INSERT OR IGNORE INTO aliases ({column}, name, slug, created_at) VALUES (?, ?, ?, ?)"
+    );
+    query(AssertSqlSafe(merged))
+        .bind(into.0)
+        .bind(source.1)
+        .bind(slug)
+        .bind(Utc::now())
+        .execute(&mut **tx)
+        .await
+        .map_err(unexpected)?;
+    Ok(())
+}
+
+fn refuse_self_merge(source: i64, into: i64) -> Result<(), RepositoryError> {
+    if source == into {
+        return Err(RepositoryError::conflict(
+            "cannot merge a record into itself",
+        ));
+    }
+    Ok(())
+}
+
+#[async_trait]
+impl AliasRepository for SqlAliasRepository {
+    async fn insert(&self, alias: NewAlias) -> Result<Alias, RepositoryError> {
+        let slug = alias.slug();
+        let (roaster_id, roast_id) = match alias.target {
+            AliasTarget::Roaster(id) => (Some(id.into_inner()), None),
+            AliasTarget::Roast(id) => (None, Some(id.into_inner())),
+        };
+
+        let query = format!(
+            "INSERT INTO aliases (roaster_id, roast_id, name, slug, created_at) \
+             VALUES (?, ?, ?, ?, ?) RETURNING {ALIAS_COLUMNS}"
+        );
+        let record = query_as::<_, AliasRecord>(AssertSqlSafe(query))
+            .bind(roaster_id)
+            .bind(roast_id)
+            .bind(&alias.name)
+            .bind(slug)
+            .bind(Utc::now())
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|err| match &err {
+                sqlx::Error::Database(db_err) if db_err.is_unique_violation() => {
+                    RepositoryError::conflict("this name is already an alias")
+                }
+                sqlx::Error::Database(db_err) if db_err.is_foreign_key_violation() => {
+                    RepositoryError::NotFound
+                }
+                _ => RepositoryError::unexpected(format!("failed to insert alias: {err}")),
+            })?;
+
+        Ok(record.into())
+    }
+
+    async fn list_for(&self, target: AliasTarget) -> Result<Vec<Alias>, RepositoryError> {
+        let (column, id) = match target {
+            AliasTarget::Roaster(id) => ("roaster_id", id.into_inner()),
+            AliasTarget::Roast(id) => ("roast_id", id.into_inner()),
+        };
+        let query = format!(
+            "SELECT {ALIAS_COLUMNS} FROM aliases WHERE {column} = ? ORDER BY created_at, id"
+        );
+
+        let records = query_as::<_, AliasRecord>(AssertSqlSafe(query))
+            .bind(id)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|err| RepositoryError::unexpected(format!("failed to list aliases: {err}")))?;
+
+        Ok(records.into_iter().map(Into::into).collect())
+    }
+
+    async fn list_all(&self) -> Result<Vec<Alias>, RepositoryError> {
+        let query = format!("SELECT {ALIAS_COLUMNS} FROM aliases ORDER BY created_at, id");
+
+        let records = query_as::<_, AliasRecord>(AssertSqlSafe(query))
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|err| RepositoryError::unexpected(format!("failed to list aliases: {err}")))?;
+
+        Ok(records.into_iter().map(Into::into).collect())
+    }
+
+    async fn delete(&self, id: AliasId) -> Result<(), RepositoryError> {
+        let result = query("DELETE FROM aliases WHERE id = ?")
+            .bind(id.into_inner())
+            .execute(&self.pool)
+            .await
+            .map_err(unexpected)?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+
+        Ok(())
+    }
+
+    async fn find_roaster(&self, slug: &str) -> Result<Option<RoasterId>, RepositoryError> {
+        query_scalar::<_, i64>(
+            "SELECT roaster_id FROM aliases WHERE slug = ? AND roaster_id IS NOT NULL \
+             ORDER BY id LIMIT 1",
+        )
+        .bind(slug)
+        .fetch_optional(&self.pool)
+        .await
+        .map(|id| id.map(RoasterId::new))
+        .map_err(unexpected)
+    }
+
+    async fn find_roast(
+        &self,
+        roaster_id: RoasterId,
+        slug: &str,
+    ) -> Result<Option<RoastId>, RepositoryError> {
+        query_scalar::<_, i64>(
+            "SELECT a.roast_id FROM aliases a JOIN roasts r ON r.id = a.roast_id \
+             WHERE a.slug = ? AND r.roaster_id = ? ORDER BY a.id LIMIT 1",
+        )
+        .bind(slug)
+        .bind(roaster_id.into_inner())
+        .fetch_optional(&self.pool)
+        .await
+        .map(|id| id.map(RoastId::new))
+        .map_err(unexpected)
+    }
+
+    async fn merge_roasters(
+        &self,
+        source: RoasterId,
+        into: RoasterId,
+    ) -> Result<(), RepositoryError> {
+        let (source, into) = (source.into_inner(), into.into_inner());
+        refuse_self_merge(source, into)?;
+        let mut tx = self.pool.begin().await.map_err(unexpected)?;
+
+        let source_name = name_of(&mut tx, "roasters", source).await?;
+        let into_name = name_of(&mut tx, "roasters", into).await?;
+
+        let clash = query_scalar::<_, String>(
+            "SELECT s.name FROM roasts s JOIN roasts t ON t.slug = s.slug \
+             WHERE s.roaster_id = ? AND t.roaster_id = ? LIMIT 1",
+        )
+        .bind(source)
+        .bind(into)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(unexpected)?;
+        if let Some(roast) = clash {
+            return Err(RepositoryError::conflict_with(
+                ErrorCode::DuplicateSlug,
+                format!("both roasters have a roast called {roast}; merge those first"),
+            ));
+        }
+
+        query("UPDATE roasts SET roaster_id = ? WHERE roaster_id = ?")
+            .bind(into)
+            .bind(source)
+            .execute(&mut *tx)
+            .await
+            .map_err(unexpected)?;
+        query(
+            "INSERT OR IGNORE INTO event_roasters (event_id, roaster_id) \
+             SELECT event_id, ? FROM event_roasters WHERE roaster_id = ?",
+        )
+        .bind(into)
+        .bind(source)
+        .execute(&mut *tx)
+        .await
+        .map_err(unexpected)?;
+        carry_aliases(
+            &mut tx,
+            "roaster_id",
+            (source, &source_name),
+            (into, &into_name),
+        )
+        .await?;
+        query("DELETE FROM roasters WHERE id = ?")
+            .bind(source)
+            .execute(&mut *tx)
+            .await
+            .map_err(unexpected)?;
+
+        tx.commit().await.map_err(unexpected)
+    }
+
+    async fn merge_roasts(&self, source: RoastId, into: RoastId) -> Result<(), RepositoryError> {
+        let (source, into) = (source.into_inner(), into.into_inner());
+        refuse_self_merge(source, into)?;
+        let mut tx = self.pool.begin().await.map_err(unexpected)?;
+
+        let source_name = name_of(&mut tx, "roasts", source).await?;
+        let into_name = name_of(&mut tx, "roasts", into).await?;
+
+        for table in ["bags", "cups", "comments"] {
+            let moved = format!("UPDATE {table} SET roast_id = ? WHERE roast_id = ?");
+            query(AssertSqlSafe(moved))
+                .bind(into)
+                .bind(source)
+                .execute(&mut *tx)
+                .await
+                .map_err(unexpected)?;
+        }
+        carry_aliases(
+            &mut tx,
+            "roast_id",
+            (source, &source_name),
+            (into, &into_name),
+        )
+        .await?;
+        query("DELETE FROM roasts WHERE id = ?")
+            .bind(source)
+            .execute(&mut *tx)
+            .await
+            .map_err(unexpected)?;
+
+        tx.commit().await.map_err(unexpected)
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct AliasRecord {
+    id: i64,
+    roaster_id: Option<i64>,
+    roast_id: Option<i64>,
+    name: String,
+    created_at: DateTime<Utc>,
+}
+
+impl From<AliasRecord> for Alias {
+    fn from(record: AliasRecord) -> Self {
+        Alias {
+            id: AliasId::new(record.id),
+            roaster_id: record.roaster_id.map(RoasterId::new),
+            roast_id: record.roast_id.map(RoastId::new),
+            name: record.name,
+            created_at: record.created_at,
+        }
+    }
+}
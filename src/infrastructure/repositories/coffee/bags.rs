@@ -1,18 +1,23 @@
 use async_trait::async_trait;
 use chrono::{DateTime, NaiveDate, Utc};
-use sqlx::{AssertSqlSafe, QueryBuilder, query_as};
+use sqlx::{AssertSqlSafe, QueryBuilder, query_as, query_scalar};
 
-use crate::domain::RepositoryError;
+use crate::domain::bag_ledger::{
+    BagLedgerEntry, BagLedgerKind, BagTransfer, NewBagAdjustment, NewBagTransfer,
+};
 use crate::domain::bags::{Bag, BagFilter, BagSortKey, BagWithRoast, NewBag, UpdateBag};
-use crate::domain::ids::{BagId, RoastId};
+use crate::domain::ids::{BagId, BagLedgerEntryId, BrewId, RoastId, UserId};
 use crate::domain::listing::{ListRequest, Page, SortDirection};
 use crate::domain::repositories::BagRepository;
+use crate::domain::{ErrorCode, RepositoryError};
 use crate::infrastructure::database::DatabasePool;
+use crate::infrastructure::repositories::coffee::set_created_by;
 use crate::infrastructure::repositories::macros::push_update_field;
+use crate::infrastructure::repositories::query_timing::timed;
 
 const BASE_SELECT: &str = r"
     SELECT
-        b.id, b.roast_id, b.roast_date, b.amount, b.remaining, b.closed, b.finished_at, b.created_at, b.updated_at,
+        b.id, b.roast_id, b.roast_date, b.amount, b.remaining, b.closed, b.finished_at, b.created_at, b.updated_at, b.created_by, b.low_stock_threshold, b.price, b.currency, b.best_before, b.batch_code,
         r.name as roast_name, r.slug as roast_slug,
         rr.name as roaster_name, rr.slug as roaster_slug
     FROM bags b
@@ -20,6 +25,15 @@ const BASE_SELECT: &str = r"
     JOIN roasters rr ON r.roaster_id = rr.id
 ";
 
+/// A bag's ledger entries with the balance after each one.
+const LEDGER_SELECT: &str = r"
+    SELECT id, bag_id, kind, delta,
+           SUM(delta) OVER (ORDER BY id) AS balance,
+           brew_id, counterpart_bag_id, reason, created_by, created_at
+    FROM bag_ledger
+    WHERE bag_id = ?
+";
+
 #[derive(Clone)]
 pub struct SqlBagRepository {
     pool: DatabasePool,
@@ -49,6 +63,26 @@ impl SqlBagRepository {
         }
     }
 
+    async fn get_ledger_entry(
+        &self,
+        bag_id: BagId,
+        id: i64,
+    ) -> Result<BagLedgerEntry, RepositoryError> {
+        // The running balance needs the whole bag's ledger, so filter after
+        // the window is computed.
+        let query = format!("SELECT * FROM ({LEDGER_SELECT}) WHERE id = ?");
+
+        let record = query_as::<_, BagLedgerRecord>(AssertSqlSafe(query))
+            .bind(bag_id.into_inner())
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|err| RepositoryError::unexpected(err.to_string()))?
+            .ok_or(RepositoryError::NotFound)?;
+
+        record.try_into()
+    }
+
     fn build_where_clause(filter: &BagFilter) -> Option<String> {
         let mut conditions = Vec::new();
 
@@ -80,9 +114,9 @@ impl BagRepository for SqlBagRepository {
     async fn insert(&self, bag: NewBag) -> Result<Bag, RepositoryError> {
         let created_at = bag.created_at.unwrap_or_else(Utc::now);
         let query = r"
-            INSERT INTO bags (roast_id, roast_date, amount, remaining, created_at, updated_at)
-            VALUES (?, ?, ?, ?, ?, ?)
-            RETURNING id, roast_id, roast_date, amount, remaining, closed, finished_at, created_at, updated_at
+            INSERT INTO bags (roast_id, roast_date, amount, remaining, price, currency, best_before, batch_code, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            RETURNING id, roast_id, roast_date, amount, remaining, closed, finished_at, created_at, updated_at, created_by, low_stock_threshold, price, currency, best_before, batch_code
         ";
 
         let record = query_as::<_, BagRecord>(query)
@@ -90,6 +124,10 @@ impl BagRepository for SqlBagRepository {
             .bind(bag.roast_date)
             .bind(bag.amount)
             .bind(bag.amount) // remaining starts as amount
+            .bind(bag.price)
+            .bind(bag.currency)
+            .bind(bag.best_before)
+            .bind(bag.batch_code)
             .bind(created_at)
             .bind(created_at)
             .fetch_one(&self.pool)
@@ -101,7 +139,7 @@ impl BagRepository for SqlBagRepository {
 
     async fn get(&self, id: BagId) -> Result<Bag, RepositoryError> {
         let query = r"
-            SELECT id, roast_id, roast_date, amount, remaining, closed, finished_at, created_at, updated_at
+            SELECT id, roast_id, roast_date, amount, remaining, closed, finished_at, created_at, updated_at, created_by, low_stock_threshold, price, currency, best_before, batch_code
             FROM bags
             WHERE id = ?
         ";
@@ -156,14 +194,17 @@ impl BagRepository for SqlBagRepository {
 
         let sf = search.and_then(|t| SearchFilter::new(t, vec!["rr.name", "r.name"]));
 
-        crate::infrastructure::repositories::pagination::paginate(
-            &self.pool,
-            request,
-            &base_query,
-            &count_query,
-            &order_clause,
-            sf.as_ref(),
-            |record: BagWithRoastRecord| Ok(record.into()),
+        timed(
+            "bags.list",
+            crate::infrastructure::repositories::pagination::paginate(
+                &self.pool,
+                request,
+                &base_query,
+                &count_query,
+                &order_clause,
+                sf.as_ref(),
+                |record: BagWithRoastRecord| Ok(record.into()),
+            ),
         )
         .await
     }
@@ -182,15 +223,24 @@ impl BagRepository for SqlBagRepository {
         );
         push_update_field!(builder, sep, "roast_date", changes.roast_date);
         push_update_field!(builder, sep, "amount", changes.amount);
-        push_update_field!(builder, sep, "remaining", changes.remaining);
         push_update_field!(builder, sep, "closed", changes.closed);
         push_update_field!(builder, sep, "finished_at", changes.finished_at);
         push_update_field!(builder, sep, "created_at", changes.created_at);
+        push_update_field!(
+            builder,
+            sep,
+            "low_stock_threshold",
+            changes.low_stock_threshold
+        );
+        push_update_field!(builder, sep, "price", changes.price);
+        push_update_field!(builder, sep, "currency", changes.currency);
+        push_update_field!(builder, sep, "best_before", changes.best_before);
+        push_update_field!(builder, sep, "batch_code", changes.batch_code);
         let _ = sep; // Suppress unused_assignments warning from macro
 
         builder.push(" WHERE id = ");
         builder.push_bind(id.into_inner());
-        builder.push(" RETURNING id, roast_id, roast_date, amount, remaining, closed, finished_at, created_at, updated_at");
+        builder.push(" RETURNING id, roast_id, roast_date, amount, remaining, closed, finished_at, created_at, updated_at, created_by, low_stock_threshold, price, currency, best_before, batch_code");
 
         let record = builder
             .build_query_as::<BagRecord>()
@@ -202,6 +252,10 @@ impl BagRepository for SqlBagRepository {
         Ok(record.into())
     }
 
+    async fn set_created_by(&self, id: BagId, user_id: UserId) -> Result<(), RepositoryError> {
+        set_created_by(&self.pool, "bags", i64::from(id), user_id).await
+    }
+
     async fn delete(&self, id: BagId) -> Result<(), RepositoryError> {
         let query = "DELETE FROM bags WHERE id = ?";
 
@@ -217,6 +271,191 @@ impl BagRepository for SqlBagRepository {
 
         Ok(())
     }
+
+    async fn reopen(&self, id: BagId) -> Result<Bag, RepositoryError> {
+        let query = r"
+            UPDATE bags
+            SET closed = FALSE, finished_at = NULL, updated_at = CURRENT_TIMESTAMP
+            WHERE id = ?
+            RETURNING id, roast_id, roast_date, amount, remaining, closed, finished_at, created_at, updated_at, created_by, low_stock_threshold, price, currency, best_before, batch_code
+        ";
+
+        let record = query_as::<_, BagRecord>(query)
+            .bind(id.into_inner())
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|err| RepositoryError::unexpected(err.to_string()))?
+            .ok_or(RepositoryError::NotFound)?;
+
+        Ok(record.into())
+    }
+
+    async fn adjust(
+        &self,
+        adjustment: NewBagAdjustment,
+    ) -> Result<BagLedgerEntry, RepositoryError> {
+        // The delta is worked out against the balance in the same statement,
+        // so a brew landing in between can't skew the correction.
+        let id = query_scalar::<_, i64>(
+            r"
+            INSERT INTO bag_ledger (bag_id, kind, delta, reason, created_by, created_at)
+            SELECT id, 'adjustment', ? - remaining, ?, ?, ?
+            FROM bags
+            WHERE id = ?
+            RETURNING id
+            ",
+        )
+        .bind(adjustment.remaining)
+        .bind(&adjustment.reason)
+        .bind(adjustment.created_by.map(UserId::into_inner))
+        .bind(Utc::now())
+        .bind(adjustment.bag_id.into_inner())
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|err| RepositoryError::unexpected(err.to_string()))?
+        .ok_or(RepositoryError::NotFound)?;
+
+        self.get_ledger_entry(adjustment.bag_id, id).await
+    }
+
+    async fn transfer(&self, transfer: NewBagTransfer) -> Result<BagTransfer, RepositoryError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|err| RepositoryError::unexpected(err.to_string()))?;
+        let now = Utc::now();
+        let created_by = transfer.created_by.map(UserId::into_inner);
+
+        // Both sides re-check the balances they depend on, so a transfer
+        // racing a brew fails rather than overdrawing or overfilling a bag.
+        let from_id = query_scalar::<_, i64>(
+            r"
+            INSERT INTO bag_ledger (bag_id, kind, delta, counterpart_bag_id, created_by, created_at)
+            SELECT id, 'transfer', -?, ?, ?, ?
+            FROM bags
+            WHERE id = ? AND closed = FALSE AND remaining >= ?
+            RETURNING id
+            ",
+        )
+        .bind(transfer.amount)
+        .bind(transfer.to_bag_id.into_inner())
+        .bind(created_by)
+        .bind(now)
+        .bind(transfer.from_bag_id.into_inner())
+        .bind(transfer.amount)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|err| RepositoryError::unexpected(err.to_string()))?
+        .ok_or_else(|| {
+            RepositoryError::conflict_with(
+                ErrorCode::BagInsufficientRemaining,
+                "not enough coffee left to transfer",
+            )
+        })?;
+
+        let to_id = query_scalar::<_, i64>(
+            r"
+            INSERT INTO bag_ledger (bag_id, kind, delta, counterpart_bag_id, created_by, created_at)
+            SELECT id, 'transfer', ?, ?, ?, ?
+            FROM bags
+            WHERE id = ? AND closed = FALSE AND remaining + ? <= amount
+            RETURNING id
+            ",
+        )
+        .bind(transfer.amount)
+        .bind(transfer.from_bag_id.into_inner())
+        .bind(created_by)
+        .bind(now)
+        .bind(transfer.to_bag_id.into_inner())
+        .bind(transfer.amount)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|err| RepositoryError::unexpected(err.to_string()))?
+        .ok_or_else(|| {
+            RepositoryError::conflict_with(
+                ErrorCode::BagNoRoom,
+                "the receiving bag has no room for the coffee",
+            )
+        })?;
+
+        tx.commit()
+            .await
+            .map_err(|err| RepositoryError::unexpected(err.to_string()))?;
+
+        Ok(BagTransfer {
+            from: self.get_ledger_entry(transfer.from_bag_id, from_id).await?,
+            to: self.get_ledger_entry(transfer.to_bag_id, to_id).await?,
+        })
+    }
+
+    async fn list_ledger(&self, id: BagId) -> Result<Vec<BagLedgerEntry>, RepositoryError> {
+        let query = format!("{LEDGER_SELECT} ORDER BY id DESC");
+
+        let records = query_as::<_, BagLedgerRecord>(AssertSqlSafe(query))
+            .bind(id.into_inner())
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|err| RepositoryError::unexpected(format!("failed to list ledger: {err}")))?;
+
+        records.into_iter().map(TryInto::try_into).collect()
+    }
+
+    async fn list_purchases(&self, roast_id: RoastId) -> Result<Vec<Bag>, RepositoryError> {
+        let query = r"
+            SELECT id, roast_id, roast_date, amount, remaining, closed, finished_at, created_at, updated_at, created_by, low_stock_threshold, price, currency, best_before, batch_code
+            FROM bags
+            WHERE roast_id = ?
+            ORDER BY created_at ASC, id ASC
+        ";
+
+        let records = query_as::<_, BagRecord>(query)
+            .bind(roast_id.into_inner())
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|err| {
+                RepositoryError::unexpected(format!("failed to list purchases: {err}"))
+            })?;
+
+        Ok(records.into_iter().map(Bag::from).collect())
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct BagLedgerRecord {
+    id: i64,
+    bag_id: i64,
+    kind: String,
+    delta: f64,
+    balance: f64,
+    brew_id: Option<i64>,
+    counterpart_bag_id: Option<i64>,
+    reason: Option<String>,
+    created_by: Option<i64>,
+    created_at: DateTime<Utc>,
+}
+
+impl TryFrom<BagLedgerRecord> for BagLedgerEntry {
+    type Error = RepositoryError;
+
+    fn try_from(record: BagLedgerRecord) -> Result<Self, Self::Error> {
+        let kind = record.kind.parse::<BagLedgerKind>().map_err(|()| {
+            RepositoryError::unexpected(format!("unknown ledger entry kind: {}", record.kind))
+        })?;
+
+        Ok(BagLedgerEntry {
+            id: BagLedgerEntryId::new(record.id),
+            bag_id: BagId::new(record.bag_id),
+            kind,
+            delta: record.delta,
+            balance: record.balance,
+            brew_id: record.brew_id.map(BrewId::new),
+            counterpart_bag_id: record.counterpart_bag_id.map(BagId::new),
+            reason: record.reason,
+            created_by: record.created_by.map(UserId::from),
+            created_at: record.created_at,
+        })
+    }
 }
 
 #[derive(sqlx::FromRow)]
@@ -230,6 +469,12 @@ struct BagRecord {
     finished_at: Option<DateTime<Utc>>,
     created_at: DateTime<Utc>,
     updated_at: DateTime<Utc>,
+    created_by: Option<i64>,
+    low_stock_threshold: Option<f64>,
+    price: Option<f64>,
+    currency: Option<String>,
+    best_before: Option<NaiveDate>,
+    batch_code: Option<String>,
 }
 
 impl From<BagRecord> for Bag {
@@ -244,6 +489,12 @@ impl From<BagRecord> for Bag {
             finished_at: record.finished_at,
             created_at: record.created_at,
             updated_at: record.updated_at,
+            created_by: record.created_by.map(UserId::from),
+            low_stock_threshold: record.low_stock_threshold,
+            price: record.price,
+            currency: record.currency,
+            best_before: record.best_before,
+            batch_code: record.batch_code,
         }
     }
 }
@@ -259,6 +510,12 @@ struct BagWithRoastRecord {
     finished_at: Option<DateTime<Utc>>,
     created_at: DateTime<Utc>,
     updated_at: DateTime<Utc>,
+    created_by: Option<i64>,
+    low_stock_threshold: Option<f64>,
+    price: Option<f64>,
+    currency: Option<String>,
+    best_before: Option<NaiveDate>,
+    batch_code: Option<String>,
     roast_name: String,
     roast_slug: String,
     roaster_name: String,
@@ -278,6 +535,12 @@ impl From<BagWithRoastRecord> for BagWithRoast {
                 finished_at: record.finished_at,
                 created_at: record.created_at,
                 updated_at: record.updated_at,
+                created_by: record.created_by.map(UserId::from),
+                low_stock_threshold: record.low_stock_threshold,
+                price: record.price,
+                currency: record.currency,
+                best_before: record.best_before,
+                batch_code: record.batch_code,
             },
             roast_name: record.roast_name,
             roaster_name: record.roaster_name,
@@ -0,0 +1,122 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{AssertSqlSafe, query_as};
+
+use crate::domain::RepositoryError;
+use crate::domain::brew_shares::{BrewShare, NewBrewShare};
+use crate::domain::ids::{BrewId, BrewShareId};
+use crate::domain::repositories::BrewShareRepository;
+use crate::infrastructure::database::DatabasePool;
+
+const BREW_SHARE_COLUMNS: &str = "id, brew_id, token_hash, expires_at, revoked_at, created_at";
+
+#[derive(Clone)]
+pub struct SqlBrewShareRepository {
+    pool: DatabasePool,
+}
+
+impl SqlBrewShareRepository {
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl BrewShareRepository for SqlBrewShareRepository {
+    async fn insert(&self, share: NewBrewShare) -> Result<BrewShare, RepositoryError> {
+        let query = format!(
+            "INSERT INTO brew_shares (brew_id, token_hash, expires_at, created_at) \
+             VALUES (?, ?, ?, ?) RETURNING {BREW_SHARE_COLUMNS}"
+        );
+
+        let record = query_as::<_, BrewShareRecord>(AssertSqlSafe(query))
+            .bind(share.brew_id.into_inner())
+            .bind(&share.token_hash)
+            .bind(share.expires_at)
+            .bind(Utc::now())
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|err| match &err {
+                sqlx::Error::Database(db_err) if db_err.is_foreign_key_violation() => {
+                    RepositoryError::NotFound
+                }
+                sqlx::Error::Database(db_err) if db_err.is_unique_violation() => {
+                    RepositoryError::conflict("share link already exists")
+                }
+                _ => RepositoryError::unexpected(format!("failed to insert brew share: {err}")),
+            })?;
+
+        Ok(record.into())
+    }
+
+    async fn get_by_token_hash(&self, token_hash: &str) -> Result<BrewShare, RepositoryError> {
+        let query = format!("SELECT {BREW_SHARE_COLUMNS} FROM brew_shares WHERE token_hash = ?");
+
+        let record = query_as::<_, BrewShareRecord>(AssertSqlSafe(query))
+            .bind(token_hash)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|err| RepositoryError::unexpected(err.to_string()))?
+            .ok_or(RepositoryError::NotFound)?;
+
+        Ok(record.into())
+    }
+
+    async fn list_for_brew(&self, brew_id: BrewId) -> Result<Vec<BrewShare>, RepositoryError> {
+        let query = format!(
+            "SELECT {BREW_SHARE_COLUMNS} FROM brew_shares WHERE brew_id = ? \
+             ORDER BY created_at DESC, id DESC"
+        );
+
+        let records = query_as::<_, BrewShareRecord>(AssertSqlSafe(query))
+            .bind(brew_id.into_inner())
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|err| {
+                RepositoryError::unexpected(format!("failed to list brew shares: {err}"))
+            })?;
+
+        Ok(records.into_iter().map(Into::into).collect())
+    }
+
+    async fn revoke(&self, id: BrewShareId) -> Result<BrewShare, RepositoryError> {
+        // Keep the original timestamp if the link was already revoked.
+        let query = format!(
+            "UPDATE brew_shares SET revoked_at = COALESCE(revoked_at, ?) WHERE id = ? \
+             RETURNING {BREW_SHARE_COLUMNS}"
+        );
+
+        let record = query_as::<_, BrewShareRecord>(AssertSqlSafe(query))
+            .bind(Utc::now())
+            .bind(id.into_inner())
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|err| RepositoryError::unexpected(err.to_string()))?
+            .ok_or(RepositoryError::NotFound)?;
+
+        Ok(record.into())
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct BrewShareRecord {
+    id: i64,
+    brew_id: i64,
+    token_hash: String,
+    expires_at: Option<DateTime<Utc>>,
+    revoked_at: Option<DateTime<Utc>>,
+    created_at: DateTime<Utc>,
+}
+
+impl From<BrewShareRecord> for BrewShare {
+    fn from(record: BrewShareRecord) -> Self {
+        BrewShare {
+            id: BrewShareId::new(record.id),
+            brew_id: BrewId::new(record.brew_id),
+            token_hash: record.token_hash,
+            expires_at: record.expires_at,
+            revoked_at: record.revoked_at,
+            created_at: record.created_at,
+        }
+    }
+}
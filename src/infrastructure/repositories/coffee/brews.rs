@@ -1,29 +1,34 @@
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use sqlx::{AssertSqlSafe, QueryBuilder, query_as};
+use tracing::warn;
 
-use crate::domain::RepositoryError;
 use crate::domain::brews::{
-    Brew, BrewFilter, BrewSortKey, BrewWithDetails, NewBrew, QuickNote, UpdateBrew,
+    Brew, BrewFilter, BrewSortKey, BrewUsage, BrewWithDetails, GearUsage, NewBrew, QuickNote,
+    RoastBrewStats, UpdateBrew,
 };
-use crate::domain::ids::{BagId, BrewId, GearId};
+use crate::domain::ids::{BagId, BrewId, GearId, RoastId, UserId};
 use crate::domain::listing::{ListRequest, Page, SortDirection};
 use crate::domain::repositories::BrewRepository;
+use crate::domain::{ErrorCode, RepositoryError};
 use crate::infrastructure::database::DatabasePool;
+use crate::infrastructure::repositories::coffee::set_created_by;
 use crate::infrastructure::repositories::macros::push_update_field;
+use crate::infrastructure::repositories::query_timing::timed;
 
 const BASE_SELECT: &str = r"
     SELECT
         br.id, br.bag_id, br.coffee_weight, br.grinder_id, br.grind_setting,
         br.brewer_id, br.filter_paper_id, br.water_volume, br.water_temp,
-        br.quick_notes, br.brew_time,
-        br.created_at, br.updated_at,
+        br.bypass_water_ml, br.kettle_id, br.actual_water_temp,
+        br.quick_notes, br.brew_time, br.created_at, br.updated_at, br.created_by,
         r.name as roast_name, r.slug as roast_slug,
         rr.name as roaster_name, rr.slug as roaster_slug,
         (g_grinder.make || ' ' || g_grinder.model) as grinder_name,
         g_grinder.model as grinder_model,
         (g_brewer.make || ' ' || g_brewer.model) as brewer_name,
-        (g_fp.make || ' ' || g_fp.model) as filter_paper_name
+        (g_fp.make || ' ' || g_fp.model) as filter_paper_name,
+        (g_kettle.make || ' ' || g_kettle.model) as kettle_name
     FROM brews br
     JOIN bags b ON br.bag_id = b.id
     JOIN roasts r ON b.roast_id = r.id
@@ -31,15 +36,23 @@ const BASE_SELECT: &str = r"
     JOIN gear g_grinder ON br.grinder_id = g_grinder.id
     JOIN gear g_brewer ON br.brewer_id = g_brewer.id
     LEFT JOIN gear g_fp ON br.filter_paper_id = g_fp.id
+    LEFT JOIN gear g_kettle ON br.kettle_id = g_kettle.id
 ";
 
-fn decode_quick_notes(raw: Option<String>) -> Vec<QuickNote> {
+/// Decode a brew's `quick_notes` JSON column. Unknown notes are skipped, and
+/// a column that isn't a JSON list of strings reads as no notes.
+pub(crate) fn decode_quick_notes(raw: Option<String>) -> Vec<QuickNote> {
     match raw {
-        Some(s) if !s.is_empty() => serde_json::from_str::<Vec<String>>(&s)
-            .unwrap_or_default()
-            .iter()
-            .filter_map(|v| QuickNote::from_str_value(v))
-            .collect(),
+        Some(s) if !s.is_empty() => match serde_json::from_str::<Vec<String>>(&s) {
+            Ok(values) => values
+                .iter()
+                .filter_map(|v| QuickNote::from_value(v))
+                .collect(),
+            Err(err) => {
+                warn!(error = %err, "failed to decode brew quick notes");
+                Vec::new()
+            }
+        },
         _ => Vec::new(),
     }
 }
@@ -71,11 +84,11 @@ impl SqlBrewRepository {
         if notes.is_empty() {
             None
         } else {
-            let labels: Vec<&str> = notes.iter().map(|n| n.label()).collect();
+            let labels: Vec<&str> = notes.iter().map(QuickNote::label).collect();
             match serde_json::to_string(&labels) {
                 Ok(json) => Some(json),
                 Err(err) => {
-                    tracing::warn!(error = %err, "failed to encode quick notes as JSON");
+                    warn!(error = %err, "failed to encode quick notes as JSON");
                     None
                 }
             }
@@ -92,7 +105,7 @@ impl SqlBrewRepository {
         if let Some(gear_id) = filter.gear_id {
             let id = gear_id.into_inner();
             conditions.push(format!(
-                "(br.grinder_id = {id} OR br.brewer_id = {id} OR br.filter_paper_id = {id})"
+                "(br.grinder_id = {id} OR br.brewer_id = {id} OR br.filter_paper_id = {id} OR br.kettle_id = {id})"
             ));
         }
 
@@ -108,38 +121,19 @@ impl SqlBrewRepository {
 impl BrewRepository for SqlBrewRepository {
     async fn insert(&self, brew: NewBrew) -> Result<Brew, RepositoryError> {
         // Use a transaction to atomically:
-        // 1. Deduct coffee_weight from bag's remaining
-        // 2. Insert the brew
+        // 1. Insert the brew
+        // 2. Record the coffee it used in the bag's ledger
         let mut tx = self
             .pool
             .begin()
             .await
             .map_err(|err| RepositoryError::unexpected(err.to_string()))?;
 
-        // Deduct coffee weight from bag's remaining amount, clamping to zero
-        let update_bag_query = r"
-            UPDATE bags
-            SET remaining = MAX(remaining - ?, 0), updated_at = CURRENT_TIMESTAMP
-            WHERE id = ? AND closed = FALSE
-        ";
-
-        let result = sqlx::query(update_bag_query)
-            .bind(brew.coffee_weight)
-            .bind(brew.bag_id.into_inner())
-            .execute(&mut *tx)
-            .await
-            .map_err(|err| RepositoryError::unexpected(err.to_string()))?;
-
-        if result.rows_affected() == 0 {
-            return Err(RepositoryError::conflict("Bag is closed or not found"));
-        }
-
-        // Insert the brew
         let created_at = brew.created_at.unwrap_or_else(Utc::now);
         let insert_query = r"
-            INSERT INTO brews (bag_id, coffee_weight, grinder_id, grind_setting, brewer_id, filter_paper_id, water_volume, water_temp, quick_notes, brew_time, created_at, updated_at)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
-            RETURNING id, bag_id, coffee_weight, grinder_id, grind_setting, brewer_id, filter_paper_id, water_volume, water_temp, quick_notes, brew_time, created_at, updated_at
+            INSERT INTO brews (bag_id, coffee_weight, grinder_id, grind_setting, brewer_id, filter_paper_id, water_volume, water_temp, bypass_water_ml, kettle_id, actual_water_temp, quick_notes, brew_time, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            RETURNING id, bag_id, coffee_weight, grinder_id, grind_setting, brewer_id, filter_paper_id, water_volume, water_temp, bypass_water_ml, kettle_id, actual_water_temp, quick_notes, brew_time, created_at, updated_at, created_by
         ";
 
         let record = query_as::<_, BrewRecord>(insert_query)
@@ -154,13 +148,62 @@ impl BrewRepository for SqlBrewRepository {
             )
             .bind(brew.water_volume)
             .bind(brew.water_temp)
+            .bind(brew.bypass_water_ml.filter(|ml| *ml > 0))
+            .bind(brew.kettle_id.map(crate::domain::ids::GearId::into_inner))
+            .bind(brew.actual_water_temp)
             .bind(Self::encode_quick_notes(&brew.quick_notes))
             .bind(brew.brew_time)
             .bind(created_at)
             .bind(created_at)
             .fetch_one(&mut *tx)
             .await
+            .map_err(|err| match &err {
+                sqlx::Error::Database(db_err) if db_err.is_foreign_key_violation() => {
+                    RepositoryError::conflict_with(
+                        ErrorCode::BagClosed,
+                        "Bag is closed or not found",
+                    )
+                }
+                _ => RepositoryError::unexpected(err.to_string()),
+            })?;
+
+        // The balance is read in the same statement that records the brew, so
+        // brews landing together can't take more than the bag holds.
+        let ledger_query = r"
+            INSERT INTO bag_ledger (bag_id, kind, delta, brew_id, created_at)
+            SELECT id, 'brew', -MIN(?, remaining), ?, ?
+            FROM bags
+            WHERE id = ? AND closed = FALSE
+        ";
+
+        let result = sqlx::query(ledger_query)
+            .bind(brew.coffee_weight)
+            .bind(record.id)
+            .bind(created_at)
+            .bind(brew.bag_id.into_inner())
+            .execute(&mut *tx)
+            .await
+            .map_err(|err| RepositoryError::unexpected(err.to_string()))?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::conflict_with(
+                ErrorCode::BagClosed,
+                "Bag is closed or not found",
+            ));
+        }
+
+        // Each brew takes one paper from the box, if the box is being counted.
+        // This skips the cached gear repository, so `BrewService::create`
+        // invalidates the read cache afterwards.
+        if let Some(filter_paper_id) = brew.filter_paper_id {
+            sqlx::query(
+                "UPDATE gear SET stock_count = MAX(stock_count - 1, 0) WHERE id = ? AND stock_count IS NOT NULL",
+            )
+            .bind(filter_paper_id.into_inner())
+            .execute(&mut *tx)
+            .await
             .map_err(|err| RepositoryError::unexpected(err.to_string()))?;
+        }
 
         tx.commit()
             .await
@@ -171,7 +214,7 @@ impl BrewRepository for SqlBrewRepository {
 
     async fn get(&self, id: BrewId) -> Result<Brew, RepositoryError> {
         let query = r"
-            SELECT id, bag_id, coffee_weight, grinder_id, grind_setting, brewer_id, filter_paper_id, water_volume, water_temp, quick_notes, brew_time, created_at, updated_at
+            SELECT id, bag_id, coffee_weight, grinder_id, grind_setting, brewer_id, filter_paper_id, water_volume, water_temp, bypass_water_ml, kettle_id, actual_water_temp, quick_notes, brew_time, created_at, updated_at, created_by
             FROM brews
             WHERE id = ?
         ";
@@ -229,14 +272,17 @@ impl BrewRepository for SqlBrewRepository {
 
         let sf = search.and_then(|t| SearchFilter::new(t, vec!["r.name", "rr.name"]));
 
-        crate::infrastructure::repositories::pagination::paginate(
-            &self.pool,
-            request,
-            &base_query,
-            &count_query,
-            &order_clause,
-            sf.as_ref(),
-            |record: BrewWithDetailsRecord| Ok(record.into()),
+        timed(
+            "brews.list",
+            crate::infrastructure::repositories::pagination::paginate(
+                &self.pool,
+                request,
+                &base_query,
+                &count_query,
+                &order_clause,
+                sf.as_ref(),
+                |record: BrewWithDetailsRecord| Ok(record.into()),
+            ),
         )
         .await
     }
@@ -279,6 +325,22 @@ impl BrewRepository for SqlBrewRepository {
         );
         push_update_field!(builder, sep, "water_volume", changes.water_volume);
         push_update_field!(builder, sep, "water_temp", changes.water_temp);
+        // Zero clears the bypass rather than recording 0ml.
+        push_update_field!(
+            builder,
+            sep,
+            "bypass_water_ml",
+            changes.bypass_water_ml.map(|ml| (ml > 0).then_some(ml))
+        );
+        push_update_field!(
+            builder,
+            sep,
+            "kettle_id",
+            changes
+                .kettle_id
+                .map(crate::domain::ids::GearId::into_inner)
+        );
+        push_update_field!(builder, sep, "actual_water_temp", changes.actual_water_temp);
         if let Some(ref notes) = changes.quick_notes {
             if sep {
                 builder.push(", ");
@@ -294,7 +356,7 @@ impl BrewRepository for SqlBrewRepository {
         builder.push(" WHERE id = ");
         builder.push_bind(id.into_inner());
         builder.push(
-            " RETURNING id, bag_id, coffee_weight, grinder_id, grind_setting, brewer_id, filter_paper_id, water_volume, water_temp, quick_notes, brew_time, created_at, updated_at",
+            " RETURNING id, bag_id, coffee_weight, grinder_id, grind_setting, brewer_id, filter_paper_id, water_volume, water_temp, bypass_water_ml, kettle_id, actual_water_temp, quick_notes, brew_time, created_at, updated_at, created_by",
         );
 
         let record = builder
@@ -307,6 +369,10 @@ impl BrewRepository for SqlBrewRepository {
         Ok(record.into())
     }
 
+    async fn set_created_by(&self, id: BrewId, user_id: UserId) -> Result<(), RepositoryError> {
+        set_created_by(&self.pool, "brews", i64::from(id), user_id).await
+    }
+
     async fn delete(&self, id: BrewId) -> Result<(), RepositoryError> {
         let query = "DELETE FROM brews WHERE id = ?";
 
@@ -322,6 +388,109 @@ impl BrewRepository for SqlBrewRepository {
 
         Ok(())
     }
+
+    async fn roast_stats(&self, roast_id: RoastId) -> Result<RoastBrewStats, RepositoryError> {
+        let totals = query_as::<_, RoastBrewTotalsRecord>(
+            r"
+            SELECT
+                COUNT(*) AS brew_count,
+                COALESCE(SUM(EXISTS (SELECT 1 FROM json_each(br.quick_notes) WHERE value = ?)), 0) AS good_brew_count,
+                COALESCE(SUM(br.coffee_weight), 0.0) AS total_coffee_grams,
+                AVG(CASE WHEN br.coffee_weight > 0 THEN (br.water_volume + COALESCE(br.bypass_water_ml, 0)) / br.coffee_weight END) AS average_ratio,
+                MIN(br.grind_setting) AS grind_min,
+                MAX(br.grind_setting) AS grind_max
+            FROM brews br
+            JOIN bags b ON br.bag_id = b.id
+            WHERE b.roast_id = ?
+            ",
+        )
+        .bind(QuickNote::Good.label())
+        .bind(roast_id.into_inner())
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|err| RepositoryError::unexpected(err.to_string()))?;
+
+        // Quick notes are stored as a JSON array of labels; a brew only counts
+        // as a candidate when it was marked "Good".
+        let best_query = format!(
+            "{BASE_SELECT} WHERE b.roast_id = ? \
+             AND EXISTS (SELECT 1 FROM json_each(br.quick_notes) WHERE value = ?) \
+             ORDER BY json_array_length(br.quick_notes) ASC, br.created_at DESC, br.id DESC \
+             LIMIT 1"
+        );
+        let best_brew = query_as::<_, BrewWithDetailsRecord>(AssertSqlSafe(best_query))
+            .bind(roast_id.into_inner())
+            .bind(QuickNote::Good.label())
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|err| RepositoryError::unexpected(err.to_string()))?
+            .map(BrewWithDetails::from);
+
+        Ok(RoastBrewStats {
+            brew_count: totals.brew_count,
+            good_brew_count: totals.good_brew_count,
+            total_coffee_grams: totals.total_coffee_grams,
+            average_ratio: totals.average_ratio,
+            grind_min: totals.grind_min,
+            grind_max: totals.grind_max,
+            best_brew,
+        })
+    }
+
+    async fn usage(&self) -> Result<BrewUsage, RepositoryError> {
+        let bags = timed(
+            "brews.bag_usage",
+            query_as::<_, (i64, DateTime<Utc>)>(
+                "SELECT bag_id, MAX(created_at) FROM brews GROUP BY bag_id",
+            )
+            .fetch_all(&self.pool),
+        )
+        .await
+        .map_err(|err| RepositoryError::unexpected(err.to_string()))?;
+
+        // Every brew uses a grinder and brewer, and optionally a filter paper
+        // and kettle; each use counts towards that gear.
+        let gear = timed(
+            "brews.gear_usage",
+            query_as::<_, (i64, i64, DateTime<Utc>)>(
+                r"
+            SELECT gear_id, COUNT(*), MAX(created_at) FROM (
+                SELECT grinder_id AS gear_id, created_at FROM brews
+                UNION ALL SELECT brewer_id, created_at FROM brews
+                UNION ALL SELECT filter_paper_id, created_at FROM brews
+                    WHERE filter_paper_id IS NOT NULL
+                UNION ALL SELECT kettle_id, created_at FROM brews
+                    WHERE kettle_id IS NOT NULL
+            )
+            GROUP BY gear_id
+            ",
+            )
+            .fetch_all(&self.pool),
+        )
+        .await
+        .map_err(|err| RepositoryError::unexpected(err.to_string()))?;
+
+        Ok(BrewUsage {
+            bags: bags
+                .into_iter()
+                .map(|(id, last_brewed)| (BagId::new(id), last_brewed))
+                .collect(),
+            gear: gear
+                .into_iter()
+                .map(|(id, brews, last_brewed)| (GearId::new(id), GearUsage { brews, last_brewed }))
+                .collect(),
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct RoastBrewTotalsRecord {
+    brew_count: i64,
+    good_brew_count: i64,
+    total_coffee_grams: f64,
+    average_ratio: Option<f64>,
+    grind_min: Option<f64>,
+    grind_max: Option<f64>,
 }
 
 #[derive(sqlx::FromRow)]
@@ -335,10 +504,14 @@ struct BrewRecord {
     filter_paper_id: Option<i64>,
     water_volume: i32,
     water_temp: f64,
+    bypass_water_ml: Option<i32>,
+    kettle_id: Option<i64>,
+    actual_water_temp: Option<f64>,
     quick_notes: Option<String>,
     brew_time: Option<i32>,
     created_at: DateTime<Utc>,
     updated_at: DateTime<Utc>,
+    created_by: Option<i64>,
 }
 
 impl From<BrewRecord> for Brew {
@@ -353,10 +526,14 @@ impl From<BrewRecord> for Brew {
             filter_paper_id: record.filter_paper_id.map(GearId::new),
             water_volume: record.water_volume,
             water_temp: record.water_temp,
+            bypass_water_ml: record.bypass_water_ml,
+            kettle_id: record.kettle_id.map(GearId::new),
+            actual_water_temp: record.actual_water_temp,
             quick_notes: decode_quick_notes(record.quick_notes),
             brew_time: record.brew_time,
             created_at: record.created_at,
             updated_at: record.updated_at,
+            created_by: record.created_by.map(UserId::from),
         }
     }
 }
@@ -372,10 +549,14 @@ struct BrewWithDetailsRecord {
     filter_paper_id: Option<i64>,
     water_volume: i32,
     water_temp: f64,
+    bypass_water_ml: Option<i32>,
+    kettle_id: Option<i64>,
+    actual_water_temp: Option<f64>,
     quick_notes: Option<String>,
     brew_time: Option<i32>,
     created_at: DateTime<Utc>,
     updated_at: DateTime<Utc>,
+    created_by: Option<i64>,
     roast_name: String,
     roast_slug: String,
     roaster_name: String,
@@ -384,6 +565,7 @@ struct BrewWithDetailsRecord {
     grinder_model: String,
     brewer_name: String,
     filter_paper_name: Option<String>,
+    kettle_name: Option<String>,
 }
 
 impl From<BrewWithDetailsRecord> for BrewWithDetails {
@@ -399,10 +581,14 @@ impl From<BrewWithDetailsRecord> for BrewWithDetails {
                 filter_paper_id: record.filter_paper_id.map(GearId::new),
                 water_volume: record.water_volume,
                 water_temp: record.water_temp,
+                bypass_water_ml: record.bypass_water_ml,
+                kettle_id: record.kettle_id.map(GearId::new),
+                actual_water_temp: record.actual_water_temp,
                 quick_notes: decode_quick_notes(record.quick_notes),
                 brew_time: record.brew_time,
                 created_at: record.created_at,
                 updated_at: record.updated_at,
+                created_by: record.created_by.map(UserId::from),
             },
             roast_name: record.roast_name,
             roaster_name: record.roaster_name,
@@ -412,6 +598,7 @@ impl From<BrewWithDetailsRecord> for BrewWithDetails {
             grinder_model: record.grinder_model,
             brewer_name: record.brewer_name,
             filter_paper_name: record.filter_paper_name,
+            kettle_name: record.kettle_name,
         }
     }
 }
@@ -2,13 +2,18 @@ use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use sqlx::{QueryBuilder, query, query_as};
 
-use crate::domain::RepositoryError;
 use crate::domain::cafes::{Cafe, CafeSortKey, NewCafe, UpdateCafe};
-use crate::domain::ids::CafeId;
+use crate::domain::delete_preview::DeletePreview;
+use crate::domain::ids::{CafeId, UserId};
 use crate::domain::listing::{ListRequest, Page, SortDirection};
 use crate::domain::repositories::CafeRepository;
+use crate::domain::{ErrorCode, RepositoryError};
 use crate::infrastructure::database::DatabasePool;
+use crate::infrastructure::repositories::coffee::{
+    DeletePreviewRecord, set_created_by, set_favourite,
+};
 use crate::infrastructure::repositories::macros::push_update_field;
+use crate::infrastructure::repositories::query_timing::timed;
 
 #[derive(Clone)]
 pub struct SqlCafeRepository {
@@ -37,14 +42,17 @@ impl SqlCafeRepository {
 
 #[async_trait]
 impl CafeRepository for SqlCafeRepository {
-    async fn insert(&self, new_cafe: NewCafe) -> Result<Cafe, RepositoryError> {
+    async fn insert_with_slug(
+        &self,
+        new_cafe: NewCafe,
+        slug: String,
+    ) -> Result<Cafe, RepositoryError> {
         let new_cafe = new_cafe.normalize();
-        let slug = new_cafe.slug();
         let now = new_cafe.created_at.unwrap_or_else(Utc::now);
 
         let record = query_as::<_, CafeRecord>(
                 "INSERT INTO cafes (name, slug, city, country, latitude, longitude, website, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)\
-                 RETURNING id, name, slug, city, country, latitude, longitude, website, created_at, updated_at",
+                 RETURNING id, name, slug, city, country, latitude, longitude, website, created_at, updated_at, favourite, created_by",
             )
             .bind(&new_cafe.name)
             .bind(&slug)
@@ -61,9 +69,7 @@ impl CafeRepository for SqlCafeRepository {
                 if let sqlx::Error::Database(db_err) = &err
                     && db_err.is_unique_violation()
                 {
-                    return RepositoryError::conflict(
-                        "A cafe with this name and city already exists",
-                    );
+                    return RepositoryError::conflict_with(ErrorCode::DuplicateSlug, "A cafe with this name and city already exists");
                 }
                 RepositoryError::unexpected(err.to_string())
             })?;
@@ -73,7 +79,7 @@ impl CafeRepository for SqlCafeRepository {
 
     async fn get(&self, id: CafeId) -> Result<Cafe, RepositoryError> {
         let record = query_as::<_, CafeRecord>(
-                "SELECT id, name, slug, city, country, latitude, longitude, website, created_at, updated_at FROM cafes WHERE id = ?",
+                "SELECT id, name, slug, city, country, latitude, longitude, website, created_at, updated_at, favourite, created_by FROM cafes WHERE id = ?",
             )
             .bind(i64::from(id))
             .fetch_optional(&self.pool)
@@ -88,7 +94,7 @@ impl CafeRepository for SqlCafeRepository {
 
     async fn get_by_slug(&self, slug: &str) -> Result<Cafe, RepositoryError> {
         let record = query_as::<_, CafeRecord>(
-                "SELECT id, name, slug, city, country, latitude, longitude, website, created_at, updated_at FROM cafes WHERE slug = ?",
+                "SELECT id, name, slug, city, country, latitude, longitude, website, created_at, updated_at, favourite, created_by FROM cafes WHERE slug = ?",
             )
             .bind(slug)
             .fetch_optional(&self.pool)
@@ -109,18 +115,21 @@ impl CafeRepository for SqlCafeRepository {
         use crate::infrastructure::repositories::pagination::SearchFilter;
 
         let order_clause = Self::order_clause(request);
-        let base_query = "SELECT id, name, slug, city, country, latitude, longitude, website, created_at, updated_at FROM cafes";
+        let base_query = "SELECT id, name, slug, city, country, latitude, longitude, website, created_at, updated_at, favourite, created_by FROM cafes";
         let count_query = "SELECT COUNT(*) FROM cafes";
         let sf = search.and_then(|t| SearchFilter::new(t, vec!["name", "city", "country"]));
 
-        crate::infrastructure::repositories::pagination::paginate(
-            &self.pool,
-            request,
-            base_query,
-            count_query,
-            &order_clause,
-            sf.as_ref(),
-            |record: CafeRecord| Ok(record.into()),
+        timed(
+            "cafes.list",
+            crate::infrastructure::repositories::pagination::paginate(
+                &self.pool,
+                request,
+                base_query,
+                count_query,
+                &order_clause,
+                sf.as_ref(),
+                |record: CafeRecord| Ok(record.into()),
+            ),
         )
         .await
     }
@@ -154,6 +163,14 @@ impl CafeRepository for SqlCafeRepository {
         self.get(id).await
     }
 
+    async fn set_created_by(&self, id: CafeId, user_id: UserId) -> Result<(), RepositoryError> {
+        set_created_by(&self.pool, "cafes", i64::from(id), user_id).await
+    }
+
+    async fn set_favourite(&self, id: CafeId, favourite: bool) -> Result<(), RepositoryError> {
+        set_favourite(&self.pool, "cafes", i64::from(id), favourite).await
+    }
+
     async fn delete(&self, id: CafeId) -> Result<(), RepositoryError> {
         let result = query("DELETE FROM cafes WHERE id = ?")
             .bind(i64::from(id))
@@ -167,6 +184,28 @@ impl CafeRepository for SqlCafeRepository {
 
         Ok(())
     }
+
+    async fn delete_preview(&self, id: CafeId) -> Result<DeletePreview, RepositoryError> {
+        let sql = r"
+            SELECT
+                0 AS roasts,
+                0 AS bags,
+                0 AS brews,
+                (SELECT COUNT(*) FROM cups WHERE cafe_id = ?1) AS cups,
+                (SELECT COUNT(*) FROM entity_images
+                    WHERE entity_type = 'cafe' AND entity_id = ?1
+                      AND deleted_at IS NULL) AS images
+            FROM cafes WHERE id = ?1
+        ";
+
+        query_as::<_, DeletePreviewRecord>(sql)
+            .bind(i64::from(id))
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|err| RepositoryError::unexpected(err.to_string()))?
+            .map(DeletePreview::from)
+            .ok_or(RepositoryError::NotFound)
+    }
 }
 
 #[derive(Debug, sqlx::FromRow)]
@@ -181,6 +220,8 @@ struct CafeRecord {
     website: Option<String>,
     created_at: DateTime<Utc>,
     updated_at: DateTime<Utc>,
+    favourite: bool,
+    created_by: Option<i64>,
 }
 
 impl From<CafeRecord> for Cafe {
@@ -196,6 +237,8 @@ impl From<CafeRecord> for Cafe {
             website: record.website,
             created_at: record.created_at,
             updated_at: record.updated_at,
+            favourite: record.favourite,
+            created_by: record.created_by.map(UserId::from),
         }
     }
 }
@@ -0,0 +1,157 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{AssertSqlSafe, query_as, query_scalar};
+
+use crate::domain::RepositoryError;
+use crate::domain::comments::{Comment, CommentTarget, NewComment};
+use crate::domain::ids::{BrewId, CommentId, RoastId, UserId};
+use crate::domain::repositories::CommentRepository;
+use crate::infrastructure::database::DatabasePool;
+
+const BASE_SELECT: &str = r"
+    SELECT c.id, c.brew_id, c.roast_id, c.user_id,
+           COALESCE(u.display_name, u.username) AS author,
+           c.body, c.created_at, c.updated_at
+    FROM comments c
+    JOIN users u ON u.id = c.user_id
+";
+
+#[derive(Clone)]
+pub struct SqlCommentRepository {
+    pool: DatabasePool,
+}
+
+impl SqlCommentRepository {
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+
+    async fn get(&self, id: i64) -> Result<Comment, RepositoryError> {
+        let query = format!("{BASE_SELECT} WHERE c.id = ?");
+
+        let record = query_as::<_, CommentRecord>(AssertSqlSafe(query))
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|err| RepositoryError::unexpected(err.to_string()))?
+            .ok_or(RepositoryError::NotFound)?;
+
+        Ok(record.into())
+    }
+}
+
+#[async_trait]
+impl CommentRepository for SqlCommentRepository {
+    async fn insert(&self, comment: NewComment) -> Result<Comment, RepositoryError> {
+        let (brew_id, roast_id) = match comment.target {
+            CommentTarget::Brew(id) => (Some(id.into_inner()), None),
+            CommentTarget::Roast(id) => (None, Some(id.into_inner())),
+        };
+        let now = Utc::now();
+
+        let id = query_scalar::<_, i64>(
+            "INSERT INTO comments (brew_id, roast_id, user_id, body, created_at, updated_at) \
+             VALUES (?, ?, ?, ?, ?, ?) RETURNING id",
+        )
+        .bind(brew_id)
+        .bind(roast_id)
+        .bind(comment.user_id.into_inner())
+        .bind(&comment.body)
+        .bind(now)
+        .bind(now)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|err| match &err {
+            sqlx::Error::Database(db_err) if db_err.is_foreign_key_violation() => {
+                RepositoryError::NotFound
+            }
+            _ => RepositoryError::unexpected(format!("failed to insert comment: {err}")),
+        })?;
+
+        self.get(id).await
+    }
+
+    async fn list_for(&self, target: CommentTarget) -> Result<Vec<Comment>, RepositoryError> {
+        let (column, id) = match target {
+            CommentTarget::Brew(id) => ("brew_id", id.into_inner()),
+            CommentTarget::Roast(id) => ("roast_id", id.into_inner()),
+        };
+        let query = format!("{BASE_SELECT} WHERE c.{column} = ? ORDER BY c.created_at, c.id");
+
+        let records = query_as::<_, CommentRecord>(AssertSqlSafe(query))
+            .bind(id)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|err| {
+                RepositoryError::unexpected(format!("failed to list comments: {err}"))
+            })?;
+
+        Ok(records.into_iter().map(Into::into).collect())
+    }
+
+    async fn update(
+        &self,
+        id: CommentId,
+        user_id: UserId,
+        body: String,
+    ) -> Result<Comment, RepositoryError> {
+        let result = sqlx::query(
+            "UPDATE comments SET body = ?, updated_at = ? WHERE id = ? AND user_id = ?",
+        )
+        .bind(&body)
+        .bind(Utc::now())
+        .bind(id.into_inner())
+        .bind(user_id.into_inner())
+        .execute(&self.pool)
+        .await
+        .map_err(|err| RepositoryError::unexpected(err.to_string()))?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+
+        self.get(id.into_inner()).await
+    }
+
+    async fn delete(&self, id: CommentId, user_id: UserId) -> Result<(), RepositoryError> {
+        let result = sqlx::query("DELETE FROM comments WHERE id = ? AND user_id = ?")
+            .bind(id.into_inner())
+            .bind(user_id.into_inner())
+            .execute(&self.pool)
+            .await
+            .map_err(|err| RepositoryError::unexpected(err.to_string()))?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct CommentRecord {
+    id: i64,
+    brew_id: Option<i64>,
+    roast_id: Option<i64>,
+    user_id: i64,
+    author: String,
+    body: String,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl From<CommentRecord> for Comment {
+    fn from(record: CommentRecord) -> Self {
+        Comment {
+            id: CommentId::new(record.id),
+            brew_id: record.brew_id.map(BrewId::new),
+            roast_id: record.roast_id.map(RoastId::new),
+            user_id: UserId::new(record.user_id),
+            author: record.author,
+            body: record.body,
+            created_at: record.created_at,
+            updated_at: record.updated_at,
+        }
+    }
+}
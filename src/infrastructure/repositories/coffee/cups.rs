@@ -4,16 +4,18 @@ use sqlx::{AssertSqlSafe, QueryBuilder, query, query_as};
 
 use crate::domain::RepositoryError;
 use crate::domain::cups::{Cup, CupFilter, CupSortKey, CupWithDetails, NewCup, UpdateCup};
-use crate::domain::ids::{CafeId, CupId, RoastId};
+use crate::domain::ids::{BagId, CafeId, CupId, RoastId, UserId};
 use crate::domain::listing::{ListRequest, Page, SortDirection};
 use crate::domain::repositories::CupRepository;
 use crate::infrastructure::database::DatabasePool;
+use crate::infrastructure::repositories::coffee::set_created_by;
 use crate::infrastructure::repositories::macros::push_update_field;
+use crate::infrastructure::repositories::query_timing::timed;
 
 const BASE_SELECT: &str = r"
     SELECT
-        c.id, c.roast_id, c.cafe_id,
-        c.created_at, c.updated_at,
+        c.id, c.roast_id, c.cafe_id, c.bag_id, c.rating,
+        c.created_at, c.updated_at, c.created_by,
         r.name as roast_name, r.slug as roast_slug,
         rr.name as roaster_name, rr.slug as roaster_slug,
         ca.name as cafe_name, ca.slug as cafe_slug,
@@ -21,7 +23,7 @@ const BASE_SELECT: &str = r"
     FROM cups c
     JOIN roasts r ON c.roast_id = r.id
     JOIN roasters rr ON r.roaster_id = rr.id
-    JOIN cafes ca ON c.cafe_id = ca.id
+    LEFT JOIN cafes ca ON c.cafe_id = ca.id
 ";
 
 #[derive(Clone)]
@@ -67,6 +69,10 @@ impl SqlCupRepository {
         if let Some(roast_id) = filter.roast_id {
             conditions.push(format!("c.roast_id = {}", roast_id.into_inner()));
         }
+        // SAFETY: `NaiveDate` formats as a plain `YYYY-MM-DD`.
+        if let Some((start, end)) = filter.between {
+            conditions.push(format!("date(c.created_at) BETWEEN '{start}' AND '{end}'"));
+        }
 
         if conditions.is_empty() {
             None
@@ -81,11 +87,13 @@ impl CupRepository for SqlCupRepository {
     async fn insert(&self, new_cup: NewCup) -> Result<Cup, RepositoryError> {
         let created_at = new_cup.created_at.unwrap_or_else(Utc::now);
         let record = query_as::<_, CupRecord>(
-            "INSERT INTO cups (roast_id, cafe_id, created_at, updated_at) VALUES (?, ?, ?, ?) \
-             RETURNING id, roast_id, cafe_id, created_at, updated_at",
+            "INSERT INTO cups (roast_id, cafe_id, bag_id, rating, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?) \
+             RETURNING id, roast_id, cafe_id, bag_id, rating, created_at, updated_at, created_by",
         )
         .bind(new_cup.roast_id.into_inner())
-        .bind(new_cup.cafe_id.into_inner())
+        .bind(new_cup.cafe_id.map(CafeId::into_inner))
+        .bind(new_cup.bag_id.map(BagId::into_inner))
+        .bind(new_cup.rating)
         .bind(created_at)
         .bind(created_at)
         .fetch_one(&self.pool)
@@ -97,7 +105,7 @@ impl CupRepository for SqlCupRepository {
 
     async fn get(&self, id: CupId) -> Result<Cup, RepositoryError> {
         let record = query_as::<_, CupRecord>(
-            "SELECT id, roast_id, cafe_id, created_at, updated_at FROM cups WHERE id = ?",
+            "SELECT id, roast_id, cafe_id, bag_id, rating, created_at, updated_at, created_by FROM cups WHERE id = ?",
         )
         .bind(i64::from(id))
         .fetch_optional(&self.pool)
@@ -143,7 +151,7 @@ impl CupRepository for SqlCupRepository {
             SELECT COUNT(*) FROM cups c
             JOIN roasts r ON c.roast_id = r.id
             JOIN roasters rr ON r.roaster_id = rr.id
-            JOIN cafes ca ON c.cafe_id = ca.id
+            LEFT JOIN cafes ca ON c.cafe_id = ca.id
         ";
 
         let count_query = match &where_clause {
@@ -153,14 +161,17 @@ impl CupRepository for SqlCupRepository {
 
         let sf = search.and_then(|t| SearchFilter::new(t, vec!["r.name", "rr.name", "ca.name"]));
 
-        crate::infrastructure::repositories::pagination::paginate(
-            &self.pool,
-            request,
-            &base_query,
-            &count_query,
-            &order_clause,
-            sf.as_ref(),
-            |record: CupWithDetailsRecord| Ok(record.into()),
+        timed(
+            "cups.list",
+            crate::infrastructure::repositories::pagination::paginate(
+                &self.pool,
+                request,
+                &base_query,
+                &count_query,
+                &order_clause,
+                sf.as_ref(),
+                |record: CupWithDetailsRecord| Ok(record.into()),
+            ),
         )
         .await
     }
@@ -181,14 +192,23 @@ impl CupRepository for SqlCupRepository {
             builder,
             sep,
             "cafe_id",
-            changes.cafe_id.map(crate::domain::ids::CafeId::into_inner)
+            changes.cafe_id.map(|id| id.map(CafeId::into_inner))
+        );
+        push_update_field!(
+            builder,
+            sep,
+            "bag_id",
+            changes.bag_id.map(|id| id.map(BagId::into_inner))
         );
+        push_update_field!(builder, sep, "rating", changes.rating);
         push_update_field!(builder, sep, "created_at", changes.created_at);
         let _ = sep;
 
         builder.push(" WHERE id = ");
         builder.push_bind(i64::from(id));
-        builder.push(" RETURNING id, roast_id, cafe_id, created_at, updated_at");
+        builder.push(
+            " RETURNING id, roast_id, cafe_id, bag_id, rating, created_at, updated_at, created_by",
+        );
 
         let record = builder
             .build_query_as::<CupRecord>()
@@ -200,6 +220,10 @@ impl CupRepository for SqlCupRepository {
         Ok(record.into())
     }
 
+    async fn set_created_by(&self, id: CupId, user_id: UserId) -> Result<(), RepositoryError> {
+        set_created_by(&self.pool, "cups", i64::from(id), user_id).await
+    }
+
     async fn delete(&self, id: CupId) -> Result<(), RepositoryError> {
         let result = query("DELETE FROM cups WHERE id = ?")
             .bind(i64::from(id))
@@ -219,9 +243,12 @@ impl CupRepository for SqlCupRepository {
 struct CupRecord {
     id: i64,
     roast_id: i64,
-    cafe_id: i64,
+    cafe_id: Option<i64>,
+    bag_id: Option<i64>,
+    rating: Option<u8>,
     created_at: DateTime<Utc>,
     updated_at: DateTime<Utc>,
+    created_by: Option<i64>,
 }
 
 impl From<CupRecord> for Cup {
@@ -229,9 +256,12 @@ impl From<CupRecord> for Cup {
         Cup {
             id: CupId::new(record.id),
             roast_id: RoastId::new(record.roast_id),
-            cafe_id: CafeId::new(record.cafe_id),
+            cafe_id: record.cafe_id.map(CafeId::new),
+            bag_id: record.bag_id.map(BagId::new),
+            rating: record.rating,
             created_at: record.created_at,
             updated_at: record.updated_at,
+            created_by: record.created_by.map(UserId::from),
         }
     }
 }
@@ -240,16 +270,19 @@ impl From<CupRecord> for Cup {
 struct CupWithDetailsRecord {
     id: i64,
     roast_id: i64,
-    cafe_id: i64,
+    cafe_id: Option<i64>,
+    bag_id: Option<i64>,
+    rating: Option<u8>,
     created_at: DateTime<Utc>,
     updated_at: DateTime<Utc>,
+    created_by: Option<i64>,
     roast_name: String,
     roast_slug: String,
     roaster_name: String,
     roaster_slug: String,
-    cafe_name: String,
-    cafe_slug: String,
-    cafe_city: String,
+    cafe_name: Option<String>,
+    cafe_slug: Option<String>,
+    cafe_city: Option<String>,
 }
 
 impl From<CupWithDetailsRecord> for CupWithDetails {
@@ -258,9 +291,12 @@ impl From<CupWithDetailsRecord> for CupWithDetails {
             cup: Cup {
                 id: CupId::new(record.id),
                 roast_id: RoastId::new(record.roast_id),
-                cafe_id: CafeId::new(record.cafe_id),
+                cafe_id: record.cafe_id.map(CafeId::new),
+                bag_id: record.bag_id.map(BagId::new),
+                rating: record.rating,
                 created_at: record.created_at,
                 updated_at: record.updated_at,
+                created_by: record.created_by.map(UserId::from),
             },
             roast_name: record.roast_name,
             roaster_name: record.roaster_name,
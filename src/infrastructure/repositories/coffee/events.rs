@@ -0,0 +1,281 @@
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde_json::{from_str, to_string};
+use sqlx::{AssertSqlSafe, QueryBuilder, query, query_as};
+
+use crate::domain::RepositoryError;
+use crate::domain::events::{Event, EventFilter, NewEvent, UpdateEvent};
+use crate::domain::ids::{CafeId, EventId, RoasterId, UserId};
+use crate::domain::repositories::EventRepository;
+use crate::infrastructure::database::{DatabaseConnection, DatabasePool, parse_id_list};
+use crate::infrastructure::repositories::coffee::set_created_by;
+use crate::infrastructure::repositories::macros::push_update_field;
+
+/// Columns of an event, with its linked roasters and cafes gathered into
+/// comma-separated id lists.
+const EVENT_COLUMNS: &str = "e.id, e.name, e.venue, e.event_date, e.notes, e.coffees_json, \
+     (SELECT GROUP_CONCAT(er.roaster_id) FROM event_roasters er WHERE er.event_id = e.id) AS roaster_ids, \
+     (SELECT GROUP_CONCAT(ec.cafe_id) FROM event_cafes ec WHERE ec.event_id = e.id) AS cafe_ids, \
+     e.created_at, e.updated_at, e.created_by";
+
+#[derive(Clone)]
+pub struct SqlEventRepository {
+    pool: DatabasePool,
+}
+
+impl SqlEventRepository {
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+
+    fn encode_coffees(coffees: &[String]) -> Result<Option<String>, RepositoryError> {
+        if coffees.is_empty() {
+            Ok(None)
+        } else {
+            to_string(coffees).map(Some).map_err(|err| {
+                RepositoryError::unexpected(format!("failed to encode event coffees: {err}"))
+            })
+        }
+    }
+}
+
+/// Replace the roasters linked to an event.
+async fn replace_roaster_links(
+    conn: &mut DatabaseConnection,
+    event_id: i64,
+    roaster_ids: &[RoasterId],
+) -> Result<(), RepositoryError> {
+    query("DELETE FROM event_roasters WHERE event_id = ?")
+        .bind(event_id)
+        .execute(&mut *conn)
+        .await
+        .map_err(|err| RepositoryError::unexpected(format!("failed to unlink roasters: {err}")))?;
+
+    for roaster_id in roaster_ids {
+        query("INSERT INTO event_roasters (event_id, roaster_id) VALUES (?, ?)")
+            .bind(event_id)
+            .bind(i64::from(*roaster_id))
+            .execute(&mut *conn)
+            .await
+            .map_err(|err| RepositoryError::unexpected(format!("failed to link roaster: {err}")))?;
+    }
+    Ok(())
+}
+
+/// Replace the cafes linked to an event.
+async fn replace_cafe_links(
+    conn: &mut DatabaseConnection,
+    event_id: i64,
+    cafe_ids: &[CafeId],
+) -> Result<(), RepositoryError> {
+    query("DELETE FROM event_cafes WHERE event_id = ?")
+        .bind(event_id)
+        .execute(&mut *conn)
+        .await
+        .map_err(|err| RepositoryError::unexpected(format!("failed to unlink cafes: {err}")))?;
+
+    for cafe_id in cafe_ids {
+        query("INSERT INTO event_cafes (event_id, cafe_id) VALUES (?, ?)")
+            .bind(event_id)
+            .bind(i64::from(*cafe_id))
+            .execute(&mut *conn)
+            .await
+            .map_err(|err| RepositoryError::unexpected(format!("failed to link cafe: {err}")))?;
+    }
+    Ok(())
+}
+
+#[async_trait]
+impl EventRepository for SqlEventRepository {
+    async fn insert(&self, event: NewEvent) -> Result<Event, RepositoryError> {
+        let event = event.normalize();
+        let now = event.created_at.unwrap_or_else(Utc::now);
+        let coffees_json = Self::encode_coffees(&event.coffees)?;
+
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|err| RepositoryError::unexpected(err.to_string()))?;
+
+        let id: i64 = query_as::<_, (i64,)>(
+            "INSERT INTO events (name, venue, event_date, notes, coffees_json, created_at, updated_at) \
+             VALUES (?, ?, ?, ?, ?, ?, ?) RETURNING id",
+        )
+        .bind(&event.name)
+        .bind(event.venue.as_deref())
+        .bind(event.date)
+        .bind(event.notes.as_deref())
+        .bind(coffees_json.as_deref())
+        .bind(now)
+        .bind(now)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|err| RepositoryError::unexpected(format!("failed to insert event: {err}")))?
+        .0;
+
+        replace_roaster_links(&mut tx, id, &event.roaster_ids).await?;
+        replace_cafe_links(&mut tx, id, &event.cafe_ids).await?;
+
+        tx.commit()
+            .await
+            .map_err(|err| RepositoryError::unexpected(err.to_string()))?;
+
+        self.get(EventId::new(id)).await
+    }
+
+    async fn get(&self, id: EventId) -> Result<Event, RepositoryError> {
+        let query = format!("SELECT {EVENT_COLUMNS} FROM events e WHERE e.id = ?");
+
+        query_as::<_, EventRecord>(AssertSqlSafe(query))
+            .bind(id.into_inner())
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|err| RepositoryError::unexpected(format!("failed to load event: {err}")))?
+            .ok_or(RepositoryError::NotFound)?
+            .try_into()
+    }
+
+    async fn list(&self, filter: EventFilter) -> Result<Vec<Event>, RepositoryError> {
+        let mut builder = QueryBuilder::new(format!("SELECT {EVENT_COLUMNS} FROM events e"));
+        let mut clause = " WHERE ";
+        if let Some(roaster_id) = filter.roaster_id {
+            builder.push(clause);
+            builder.push(
+                "EXISTS (SELECT 1 FROM event_roasters er WHERE er.event_id = e.id AND er.roaster_id = ",
+            );
+            builder.push_bind(i64::from(roaster_id));
+            builder.push(")");
+            clause = " AND ";
+        }
+        if let Some(cafe_id) = filter.cafe_id {
+            builder.push(clause);
+            builder.push(
+                "EXISTS (SELECT 1 FROM event_cafes ec WHERE ec.event_id = e.id AND ec.cafe_id = ",
+            );
+            builder.push_bind(i64::from(cafe_id));
+            builder.push(")");
+        }
+        builder.push(" ORDER BY e.event_date DESC, e.id DESC");
+
+        let records = builder
+            .build_query_as::<EventRecord>()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|err| RepositoryError::unexpected(format!("failed to list events: {err}")))?;
+
+        records.into_iter().map(Event::try_from).collect()
+    }
+
+    async fn update(&self, id: EventId, changes: UpdateEvent) -> Result<Event, RepositoryError> {
+        let changes = changes.normalize();
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|err| RepositoryError::unexpected(err.to_string()))?;
+
+        let mut builder = QueryBuilder::new("UPDATE events SET updated_at = CURRENT_TIMESTAMP");
+        let mut sep = true;
+
+        push_update_field!(builder, sep, "name", changes.name);
+        push_update_field!(builder, sep, "venue", changes.venue);
+        push_update_field!(builder, sep, "event_date", changes.date);
+        push_update_field!(builder, sep, "notes", changes.notes);
+        if let Some(coffees) = &changes.coffees {
+            builder.push(", coffees_json = ");
+            builder.push_bind(Self::encode_coffees(coffees)?);
+        }
+        let _ = sep;
+
+        builder.push(" WHERE id = ");
+        builder.push_bind(id.into_inner());
+
+        let result =
+            builder.build().execute(&mut *tx).await.map_err(|err| {
+                RepositoryError::unexpected(format!("failed to update event: {err}"))
+            })?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+
+        if let Some(roaster_ids) = &changes.roaster_ids {
+            replace_roaster_links(&mut tx, id.into_inner(), roaster_ids).await?;
+        }
+        if let Some(cafe_ids) = &changes.cafe_ids {
+            replace_cafe_links(&mut tx, id.into_inner(), cafe_ids).await?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|err| RepositoryError::unexpected(err.to_string()))?;
+
+        self.get(id).await
+    }
+
+    async fn set_created_by(&self, id: EventId, user_id: UserId) -> Result<(), RepositoryError> {
+        set_created_by(&self.pool, "events", i64::from(id), user_id).await
+    }
+
+    async fn delete(&self, id: EventId) -> Result<(), RepositoryError> {
+        let result = query("DELETE FROM events WHERE id = ?")
+            .bind(id.into_inner())
+            .execute(&self.pool)
+            .await
+            .map_err(|err| RepositoryError::unexpected(format!("failed to delete event: {err}")))?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct EventRecord {
+    id: i64,
+    name: String,
+    venue: Option<String>,
+    event_date: NaiveDate,
+    notes: Option<String>,
+    coffees_json: Option<String>,
+    roaster_ids: Option<String>,
+    cafe_ids: Option<String>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    created_by: Option<i64>,
+}
+
+fn parse_ids<T: From<i64>>(raw: Option<&str>) -> Result<Vec<T>, RepositoryError> {
+    parse_id_list(raw)
+        .map_err(|err| RepositoryError::unexpected(format!("invalid linked id {err}")))
+}
+
+impl TryFrom<EventRecord> for Event {
+    type Error = RepositoryError;
+
+    fn try_from(record: EventRecord) -> Result<Self, Self::Error> {
+        let coffees = match record.coffees_json {
+            Some(raw) => from_str::<Vec<String>>(&raw).map_err(|err| {
+                RepositoryError::unexpected(format!("failed to decode event coffees: {err}"))
+            })?,
+            None => Vec::new(),
+        };
+
+        Ok(Event {
+            id: EventId::new(record.id),
+            name: record.name,
+            venue: record.venue,
+            date: record.event_date,
+            notes: record.notes,
+            coffees,
+            roaster_ids: parse_ids(record.roaster_ids.as_deref())?,
+            cafe_ids: parse_ids(record.cafe_ids.as_deref())?,
+            created_at: record.created_at,
+            updated_at: record.updated_at,
+            created_by: record.created_by.map(UserId::new),
+        })
+    }
+}
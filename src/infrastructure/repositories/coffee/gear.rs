@@ -5,12 +5,16 @@ use chrono::{DateTime, Utc};
 use sqlx::{QueryBuilder, query_as};
 
 use crate::domain::RepositoryError;
-use crate::domain::gear::{Gear, GearCategory, GearFilter, GearSortKey, NewGear, UpdateGear};
-use crate::domain::ids::GearId;
+use crate::domain::gear::{
+    BrewMethod, Gear, GearCategory, GearFilter, GearSortKey, NewGear, UpdateGear,
+};
+use crate::domain::ids::{GearId, UserId};
 use crate::domain::listing::{ListRequest, Page, SortDirection};
 use crate::domain::repositories::GearRepository;
 use crate::infrastructure::database::DatabasePool;
+use crate::infrastructure::repositories::coffee::set_created_by;
 use crate::infrastructure::repositories::macros::push_update_field;
+use crate::infrastructure::repositories::query_timing::timed;
 
 #[derive(Clone)]
 pub struct SqlGearRepository {
@@ -41,6 +45,7 @@ impl SqlGearRepository {
             GearCategory::Grinder => "category = 'grinder'",
             GearCategory::Brewer => "category = 'brewer'",
             GearCategory::FilterPaper => "category = 'filter_paper'",
+            GearCategory::Kettle => "category = 'kettle'",
         })
     }
 }
@@ -50,15 +55,22 @@ impl GearRepository for SqlGearRepository {
     async fn insert(&self, gear: NewGear) -> Result<Gear, RepositoryError> {
         let created_at = gear.created_at.unwrap_or_else(Utc::now);
         let query = r"
-            INSERT INTO gear (category, make, model, created_at, updated_at)
-            VALUES (?, ?, ?, ?, ?)
-            RETURNING id, category, make, model, created_at, updated_at
+            INSERT INTO gear (category, make, model, water_temp, brew_method, uses_filter, temp_offset, water_hardness, stock_count, low_stock_threshold, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            RETURNING id, category, make, model, water_temp, brew_method, uses_filter, temp_offset, water_hardness, stock_count, low_stock_threshold, created_at, updated_at, created_by
         ";
 
         let record = query_as::<_, GearRecord>(query)
             .bind(gear.category.as_str())
             .bind(&gear.make)
             .bind(&gear.model)
+            .bind(gear.water_temp)
+            .bind(gear.brew_method.map(|method| method.as_str()))
+            .bind(gear.uses_filter)
+            .bind(gear.temp_offset)
+            .bind(gear.water_hardness)
+            .bind(gear.stock_count)
+            .bind(gear.low_stock_threshold)
             .bind(created_at)
             .bind(created_at)
             .fetch_one(&self.pool)
@@ -70,7 +82,7 @@ impl GearRepository for SqlGearRepository {
 
     async fn get(&self, id: GearId) -> Result<Gear, RepositoryError> {
         let query = r"
-            SELECT id, category, make, model, created_at, updated_at
+            SELECT id, category, make, model, water_temp, brew_method, uses_filter, temp_offset, water_hardness, stock_count, low_stock_threshold, created_at, updated_at, created_by
             FROM gear
             WHERE id = ?
         ";
@@ -98,10 +110,11 @@ impl GearRepository for SqlGearRepository {
 
         let base_query = match &where_clause {
             Some(w) => format!(
-                "SELECT id, category, make, model, created_at, updated_at FROM gear WHERE {w}"
+                "SELECT id, category, make, model, water_temp, brew_method, uses_filter, temp_offset, water_hardness, stock_count, low_stock_threshold, created_at, updated_at, created_by FROM gear WHERE {w}"
             ),
             None => {
-                "SELECT id, category, make, model, created_at, updated_at FROM gear".to_string()
+                "SELECT id, category, make, model, water_temp, brew_method, uses_filter, temp_offset, water_hardness, stock_count, low_stock_threshold, created_at, updated_at, created_by FROM gear"
+                    .to_string()
             }
         };
 
@@ -112,14 +125,17 @@ impl GearRepository for SqlGearRepository {
 
         let sf = search.and_then(|t| SearchFilter::new(t, vec!["make", "model"]));
 
-        crate::infrastructure::repositories::pagination::paginate(
-            &self.pool,
-            request,
-            &base_query,
-            &count_query,
-            &order_clause,
-            sf.as_ref(),
-            |record: GearRecord| record.try_into(),
+        timed(
+            "gear.list",
+            crate::infrastructure::repositories::pagination::paginate(
+                &self.pool,
+                request,
+                &base_query,
+                &count_query,
+                &order_clause,
+                sf.as_ref(),
+                |record: GearRecord| record.try_into(),
+            ),
         )
         .await
     }
@@ -130,12 +146,31 @@ impl GearRepository for SqlGearRepository {
 
         push_update_field!(builder, sep, "make", changes.make);
         push_update_field!(builder, sep, "model", changes.model);
+        push_update_field!(builder, sep, "water_temp", changes.water_temp);
+        push_update_field!(
+            builder,
+            sep,
+            "brew_method",
+            changes.brew_method.map(|method| method.as_str())
+        );
+        push_update_field!(builder, sep, "uses_filter", changes.uses_filter);
+        push_update_field!(builder, sep, "temp_offset", changes.temp_offset);
+        push_update_field!(builder, sep, "water_hardness", changes.water_hardness);
+        push_update_field!(builder, sep, "stock_count", changes.stock_count);
+        push_update_field!(
+            builder,
+            sep,
+            "low_stock_threshold",
+            changes.low_stock_threshold
+        );
         push_update_field!(builder, sep, "created_at", changes.created_at);
         let _ = sep; // Suppress unused_assignments warning
 
         builder.push(" WHERE id = ");
         builder.push_bind(id.into_inner());
-        builder.push(" RETURNING id, category, make, model, created_at, updated_at");
+        builder.push(
+            " RETURNING id, category, make, model, water_temp, brew_method, uses_filter, temp_offset, water_hardness, stock_count, low_stock_threshold, created_at, updated_at, created_by",
+        );
 
         let record = builder
             .build_query_as::<GearRecord>()
@@ -147,6 +182,29 @@ impl GearRepository for SqlGearRepository {
         record.try_into()
     }
 
+    async fn restock(&self, id: GearId, count: i32) -> Result<Gear, RepositoryError> {
+        let query = r"
+            UPDATE gear
+            SET stock_count = COALESCE(stock_count, 0) + ?, updated_at = CURRENT_TIMESTAMP
+            WHERE id = ?
+            RETURNING id, category, make, model, water_temp, brew_method, uses_filter, temp_offset, water_hardness, stock_count, low_stock_threshold, created_at, updated_at, created_by
+        ";
+
+        let record = query_as::<_, GearRecord>(query)
+            .bind(count)
+            .bind(id.into_inner())
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|err| RepositoryError::unexpected(err.to_string()))?
+            .ok_or(RepositoryError::NotFound)?;
+
+        record.try_into()
+    }
+
+    async fn set_created_by(&self, id: GearId, user_id: UserId) -> Result<(), RepositoryError> {
+        set_created_by(&self.pool, "gear", i64::from(id), user_id).await
+    }
+
     async fn delete(&self, id: GearId) -> Result<(), RepositoryError> {
         let query = "DELETE FROM gear WHERE id = ?";
 
@@ -170,8 +228,16 @@ struct GearRecord {
     category: String,
     make: String,
     model: String,
+    water_temp: Option<f64>,
+    brew_method: Option<String>,
+    uses_filter: Option<bool>,
+    temp_offset: Option<f64>,
+    water_hardness: Option<i32>,
+    stock_count: Option<i32>,
+    low_stock_threshold: Option<i32>,
     created_at: DateTime<Utc>,
     updated_at: DateTime<Utc>,
+    created_by: Option<i64>,
 }
 
 impl TryFrom<GearRecord> for Gear {
@@ -181,14 +247,30 @@ impl TryFrom<GearRecord> for Gear {
         let category = GearCategory::from_str(&record.category).map_err(|()| {
             RepositoryError::unexpected(format!("invalid category: {}", record.category))
         })?;
+        let brew_method = record
+            .brew_method
+            .map(|method| {
+                BrewMethod::from_str(&method).map_err(|()| {
+                    RepositoryError::unexpected(format!("invalid brew method: {method}"))
+                })
+            })
+            .transpose()?;
 
         Ok(Gear {
             id: GearId::new(record.id),
             category,
             make: record.make,
             model: record.model,
+            water_temp: record.water_temp,
+            brew_method,
+            uses_filter: record.uses_filter,
+            temp_offset: record.temp_offset,
+            water_hardness: record.water_hardness,
+            stock_count: record.stock_count,
+            low_stock_threshold: record.low_stock_threshold,
             created_at: record.created_at,
             updated_at: record.updated_at,
+            created_by: record.created_by.map(UserId::from),
         })
     }
 }
@@ -0,0 +1,187 @@
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use sqlx::{AssertSqlSafe, QueryBuilder, query, query_as};
+
+use crate::domain::green_coffees::{GreenCoffee, NewGreenCoffee, UpdateGreenCoffee};
+use crate::domain::ids::GreenCoffeeId;
+use crate::domain::repositories::GreenCoffeeRepository;
+use crate::domain::{ErrorCode, RepositoryError};
+use crate::infrastructure::database::DatabasePool;
+use crate::infrastructure::repositories::macros::push_update_field;
+
+/// Columns of a green coffee, with its remaining stock worked out from its
+/// batches.
+const GREEN_COFFEE_COLUMNS: &str = "g.id, g.name, g.origin, g.process, g.supplier, g.amount, \
+     g.amount - COALESCE((SELECT SUM(b.weight_in) FROM roast_batches b WHERE b.green_coffee_id = g.id), 0) AS remaining, \
+     g.price, g.purchased_on, g.notes, g.created_at, g.updated_at";
+
+#[derive(Clone)]
+pub struct SqlGreenCoffeeRepository {
+    pool: DatabasePool,
+}
+
+impl SqlGreenCoffeeRepository {
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl GreenCoffeeRepository for SqlGreenCoffeeRepository {
+    async fn insert(&self, green: NewGreenCoffee) -> Result<GreenCoffee, RepositoryError> {
+        let green = green.normalize();
+        let now = green.created_at.unwrap_or_else(Utc::now);
+
+        let id: i64 = query_as::<_, (i64,)>(
+            "INSERT INTO green_coffees (name, origin, process, supplier, amount, price, purchased_on, notes, created_at, updated_at) \
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?) RETURNING id",
+        )
+        .bind(&green.name)
+        .bind(green.origin.as_deref())
+        .bind(green.process.as_deref())
+        .bind(green.supplier.as_deref())
+        .bind(green.amount)
+        .bind(green.price)
+        .bind(green.purchased_on)
+        .bind(green.notes.as_deref())
+        .bind(now)
+        .bind(now)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|err| RepositoryError::unexpected(format!("failed to insert green coffee: {err}")))?
+        .0;
+
+        self.get(GreenCoffeeId::new(id)).await
+    }
+
+    async fn get(&self, id: GreenCoffeeId) -> Result<GreenCoffee, RepositoryError> {
+        let query = format!("SELECT {GREEN_COFFEE_COLUMNS} FROM green_coffees g WHERE g.id = ?");
+
+        query_as::<_, GreenCoffeeRecord>(AssertSqlSafe(query))
+            .bind(id.into_inner())
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|err| {
+                RepositoryError::unexpected(format!("failed to load green coffee: {err}"))
+            })?
+            .map(Into::into)
+            .ok_or(RepositoryError::NotFound)
+    }
+
+    async fn list(&self) -> Result<Vec<GreenCoffee>, RepositoryError> {
+        let query = format!(
+            "SELECT {GREEN_COFFEE_COLUMNS} FROM green_coffees g \
+             ORDER BY COALESCE(g.purchased_on, g.created_at) DESC, g.id DESC"
+        );
+
+        let records = query_as::<_, GreenCoffeeRecord>(AssertSqlSafe(query))
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|err| {
+                RepositoryError::unexpected(format!("failed to list green coffees: {err}"))
+            })?;
+
+        Ok(records.into_iter().map(Into::into).collect())
+    }
+
+    async fn update(
+        &self,
+        id: GreenCoffeeId,
+        changes: UpdateGreenCoffee,
+    ) -> Result<GreenCoffee, RepositoryError> {
+        let changes = changes.normalize();
+        let mut builder =
+            QueryBuilder::new("UPDATE green_coffees SET updated_at = CURRENT_TIMESTAMP");
+        let mut sep = true;
+
+        push_update_field!(builder, sep, "name", changes.name);
+        push_update_field!(builder, sep, "origin", changes.origin);
+        push_update_field!(builder, sep, "process", changes.process);
+        push_update_field!(builder, sep, "supplier", changes.supplier);
+        push_update_field!(builder, sep, "amount", changes.amount);
+        push_update_field!(builder, sep, "price", changes.price);
+        push_update_field!(builder, sep, "purchased_on", changes.purchased_on);
+        push_update_field!(builder, sep, "notes", changes.notes);
+        let _ = sep;
+
+        builder.push(" WHERE id = ");
+        builder.push_bind(id.into_inner());
+        // Guarded in SQL so the amount can't drop below what its batches
+        // have already roasted, even with a batch being added meanwhile.
+        if let Some(amount) = changes.amount {
+            builder.push(" AND ");
+            builder.push_bind(amount);
+            builder.push(
+                " >= (SELECT COALESCE(SUM(weight_in), 0) FROM roast_batches \
+                 WHERE green_coffee_id = green_coffees.id)",
+            );
+        }
+
+        let result = builder.build().execute(&self.pool).await.map_err(|err| {
+            RepositoryError::unexpected(format!("failed to update green coffee: {err}"))
+        })?;
+
+        if result.rows_affected() == 0 {
+            if changes.amount.is_some() && self.get(id).await.is_ok() {
+                return Err(RepositoryError::conflict_with(
+                    ErrorCode::GreenCoffeeInsufficient,
+                    "more green coffee has already been roasted than that",
+                ));
+            }
+            return Err(RepositoryError::NotFound);
+        }
+
+        self.get(id).await
+    }
+
+    async fn delete(&self, id: GreenCoffeeId) -> Result<(), RepositoryError> {
+        let result = query("DELETE FROM green_coffees WHERE id = ?")
+            .bind(id.into_inner())
+            .execute(&self.pool)
+            .await
+            .map_err(|err| {
+                RepositoryError::unexpected(format!("failed to delete green coffee: {err}"))
+            })?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct GreenCoffeeRecord {
+    id: i64,
+    name: String,
+    origin: Option<String>,
+    process: Option<String>,
+    supplier: Option<String>,
+    amount: f64,
+    remaining: f64,
+    price: Option<f64>,
+    purchased_on: Option<NaiveDate>,
+    notes: Option<String>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl From<GreenCoffeeRecord> for GreenCoffee {
+    fn from(record: GreenCoffeeRecord) -> Self {
+        GreenCoffee {
+            id: GreenCoffeeId::new(record.id),
+            name: record.name,
+            origin: record.origin,
+            process: record.process,
+            supplier: record.supplier,
+            amount: record.amount,
+            remaining: record.remaining,
+            price: record.price,
+            purchased_on: record.purchased_on,
+            notes: record.notes,
+            created_at: record.created_at,
+            updated_at: record.updated_at,
+        }
+    }
+}
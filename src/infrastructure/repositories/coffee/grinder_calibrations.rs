@@ -0,0 +1,135 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{AssertSqlSafe, query, query_as};
+
+use crate::domain::RepositoryError;
+use crate::domain::grinder_calibrations::{GrinderCalibration, NewGrinderCalibration};
+use crate::domain::ids::{GearId, GrinderCalibrationId};
+use crate::domain::repositories::GrinderCalibrationRepository;
+use crate::infrastructure::database::DatabasePool;
+
+const CALIBRATION_COLUMNS: &str =
+    "id, grinder_a_id, setting_a, grinder_b_id, setting_b, created_at";
+
+#[derive(Clone)]
+pub struct SqlGrinderCalibrationRepository {
+    pool: DatabasePool,
+}
+
+impl SqlGrinderCalibrationRepository {
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl GrinderCalibrationRepository for SqlGrinderCalibrationRepository {
+    async fn insert(
+        &self,
+        calibration: NewGrinderCalibration,
+    ) -> Result<GrinderCalibration, RepositoryError> {
+        let created_at = calibration.created_at.unwrap_or_else(Utc::now);
+        let query = format!(
+            "INSERT INTO grinder_calibrations (grinder_a_id, setting_a, grinder_b_id, setting_b, created_at) \
+             VALUES (?, ?, ?, ?, ?) RETURNING {CALIBRATION_COLUMNS}"
+        );
+
+        let record = query_as::<_, GrinderCalibrationRecord>(AssertSqlSafe(query))
+            .bind(calibration.grinder_a_id.into_inner())
+            .bind(calibration.setting_a)
+            .bind(calibration.grinder_b_id.into_inner())
+            .bind(calibration.setting_b)
+            .bind(created_at)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|err| {
+                RepositoryError::unexpected(format!("failed to insert grinder calibration: {err}"))
+            })?;
+
+        Ok(record.into())
+    }
+
+    async fn list(
+        &self,
+        grinder_id: Option<GearId>,
+    ) -> Result<Vec<GrinderCalibration>, RepositoryError> {
+        let query = format!(
+            "SELECT {CALIBRATION_COLUMNS} FROM grinder_calibrations \
+             WHERE ?1 IS NULL OR grinder_a_id = ?1 OR grinder_b_id = ?1 \
+             ORDER BY created_at, id"
+        );
+
+        let records = query_as::<_, GrinderCalibrationRecord>(AssertSqlSafe(query))
+            .bind(grinder_id.map(GearId::into_inner))
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|err| {
+                RepositoryError::unexpected(format!("failed to list grinder calibrations: {err}"))
+            })?;
+
+        Ok(records.into_iter().map(Into::into).collect())
+    }
+
+    async fn list_for_pair(
+        &self,
+        grinder: GearId,
+        other: GearId,
+    ) -> Result<Vec<GrinderCalibration>, RepositoryError> {
+        let query = format!(
+            "SELECT {CALIBRATION_COLUMNS} FROM grinder_calibrations \
+             WHERE (grinder_a_id = ?1 AND grinder_b_id = ?2) \
+                OR (grinder_a_id = ?2 AND grinder_b_id = ?1) \
+             ORDER BY created_at, id"
+        );
+
+        let records = query_as::<_, GrinderCalibrationRecord>(AssertSqlSafe(query))
+            .bind(grinder.into_inner())
+            .bind(other.into_inner())
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|err| {
+                RepositoryError::unexpected(format!("failed to list grinder calibrations: {err}"))
+            })?;
+
+        Ok(records.into_iter().map(Into::into).collect())
+    }
+
+    async fn delete(&self, id: GrinderCalibrationId) -> Result<(), RepositoryError> {
+        let result = query("DELETE FROM grinder_calibrations WHERE id = ?")
+            .bind(id.into_inner())
+            .execute(&self.pool)
+            .await
+            .map_err(|err| {
+                RepositoryError::unexpected(format!("failed to delete grinder calibration: {err}"))
+            })?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct GrinderCalibrationRecord {
+    id: i64,
+    grinder_a_id: i64,
+    setting_a: f64,
+    grinder_b_id: i64,
+    setting_b: f64,
+    created_at: DateTime<Utc>,
+}
+
+impl From<GrinderCalibrationRecord> for GrinderCalibration {
+    fn from(record: GrinderCalibrationRecord) -> Self {
+        GrinderCalibration {
+            id: GrinderCalibrationId::new(record.id),
+            grinder_a_id: GearId::new(record.grinder_a_id),
+            setting_a: record.setting_a,
+            grinder_b_id: GearId::new(record.grinder_b_id),
+            setting_b: record.setting_b,
+            created_at: record.created_at,
+        }
+    }
+}
@@ -1,7 +1,92 @@
+pub mod aliases;
 pub mod bags;
+pub mod brew_shares;
 pub mod brews;
 pub mod cafes;
+pub mod comments;
 pub mod cups;
+pub mod events;
 pub mod gear;
+pub mod green_coffees;
+pub mod grinder_calibrations;
+pub mod processes;
+pub mod quick_notes;
+pub mod roast_batches;
 pub mod roasters;
 pub mod roasts;
+pub mod trips;
+
+use sqlx::AssertSqlSafe;
+
+use crate::domain::RepositoryError;
+use crate::domain::delete_preview::DeletePreview;
+use crate::domain::ids::UserId;
+use crate::infrastructure::database::DatabasePool;
+
+/// Row shape shared by the `delete_preview` queries of the roaster, roast, and cafe repositories.
+#[derive(sqlx::FromRow)]
+pub(crate) struct DeletePreviewRecord {
+    roasts: i64,
+    bags: i64,
+    brews: i64,
+    cups: i64,
+    images: i64,
+}
+
+impl From<DeletePreviewRecord> for DeletePreview {
+    fn from(record: DeletePreviewRecord) -> Self {
+        DeletePreview {
+            roasts: record.roasts,
+            bags: record.bags,
+            brews: record.brews,
+            cups: record.cups,
+            images: record.images,
+        }
+    }
+}
+
+/// Attribute a coffee entity to the user who created it. Shared by the
+/// `set_created_by` implementations of the coffee repositories.
+pub(crate) async fn set_created_by(
+    pool: &DatabasePool,
+    table: &'static str,
+    id: i64,
+    user_id: UserId,
+) -> Result<(), RepositoryError> {
+    let result = sqlx::query(AssertSqlSafe(format!(
+        "UPDATE {table} SET created_by = ? WHERE id = ?"
+    )))
+    .bind(i64::from(user_id))
+    .bind(id)
+    .execute(pool)
+    .await
+    .map_err(|err| RepositoryError::unexpected(err.to_string()))?;
+
+    if result.rows_affected() == 0 {
+        return Err(RepositoryError::NotFound);
+    }
+    Ok(())
+}
+
+/// Star or unstar a roaster, roast or cafe. Shared by the `set_favourite`
+/// implementations of those repositories.
+pub(crate) async fn set_favourite(
+    pool: &DatabasePool,
+    table: &'static str,
+    id: i64,
+    favourite: bool,
+) -> Result<(), RepositoryError> {
+    let result = sqlx::query(AssertSqlSafe(format!(
+        "UPDATE {table} SET favourite = ? WHERE id = ?"
+    )))
+    .bind(favourite)
+    .bind(id)
+    .execute(pool)
+    .await
+    .map_err(|err| RepositoryError::unexpected(err.to_string()))?;
+
+    if result.rows_affected() == 0 {
+        return Err(RepositoryError::NotFound);
+    }
+    Ok(())
+}
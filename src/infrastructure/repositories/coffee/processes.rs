@@ -0,0 +1,102 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{AssertSqlSafe, query, query_as};
+
+use crate::domain::RepositoryError;
+use crate::domain::ids::CustomProcessId;
+use crate::domain::processes::{CustomProcess, NewCustomProcess, process_family};
+use crate::domain::repositories::CustomProcessRepository;
+use crate::infrastructure::database::DatabasePool;
+
+const PROCESS_COLUMNS: &str = "id, name, family, created_at";
+
+#[derive(Clone)]
+pub struct SqlCustomProcessRepository {
+    pool: DatabasePool,
+}
+
+impl SqlCustomProcessRepository {
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl CustomProcessRepository for SqlCustomProcessRepository {
+    async fn insert(&self, process: NewCustomProcess) -> Result<CustomProcess, RepositoryError> {
+        let created_at = process.created_at.unwrap_or_else(Utc::now);
+        let family = process
+            .family
+            .unwrap_or_else(|| process_family(&process.name).to_string());
+        let query = format!(
+            "INSERT INTO custom_processes (name, family, created_at) \
+             VALUES (?, ?, ?) RETURNING {PROCESS_COLUMNS}"
+        );
+
+        let record = query_as::<_, CustomProcessRecord>(AssertSqlSafe(query))
+            .bind(&process.name)
+            .bind(&family)
+            .bind(created_at)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|err| {
+                if let sqlx::Error::Database(db_err) = &err
+                    && db_err.is_unique_violation()
+                {
+                    return RepositoryError::conflict("a process with this name already exists");
+                }
+                RepositoryError::unexpected(format!("failed to insert process: {err}"))
+            })?;
+
+        Ok(record.into())
+    }
+
+    async fn list(&self) -> Result<Vec<CustomProcess>, RepositoryError> {
+        let query =
+            format!("SELECT {PROCESS_COLUMNS} FROM custom_processes ORDER BY created_at, id");
+
+        let records = query_as::<_, CustomProcessRecord>(AssertSqlSafe(query))
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|err| {
+                RepositoryError::unexpected(format!("failed to list processes: {err}"))
+            })?;
+
+        Ok(records.into_iter().map(Into::into).collect())
+    }
+
+    async fn delete(&self, id: CustomProcessId) -> Result<(), RepositoryError> {
+        let result = query("DELETE FROM custom_processes WHERE id = ?")
+            .bind(id.into_inner())
+            .execute(&self.pool)
+            .await
+            .map_err(|err| {
+                RepositoryError::unexpected(format!("failed to delete process: {err}"))
+            })?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct CustomProcessRecord {
+    id: i64,
+    name: String,
+    family: String,
+    created_at: DateTime<Utc>,
+}
+
+impl From<CustomProcessRecord> for CustomProcess {
+    fn from(record: CustomProcessRecord) -> Self {
+        CustomProcess {
+            id: CustomProcessId::new(record.id),
+            name: record.name,
+            family: record.family,
+            created_at: record.created_at,
+        }
+    }
+}
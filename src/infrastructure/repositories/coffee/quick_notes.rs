@@ -0,0 +1,101 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{AssertSqlSafe, query, query_as};
+
+use crate::domain::RepositoryError;
+use crate::domain::ids::CustomQuickNoteId;
+use crate::domain::quick_notes::{CustomQuickNote, NewCustomQuickNote};
+use crate::domain::repositories::CustomQuickNoteRepository;
+use crate::infrastructure::database::DatabasePool;
+
+const QUICK_NOTE_COLUMNS: &str = "id, label, emoji, created_at";
+
+#[derive(Clone)]
+pub struct SqlCustomQuickNoteRepository {
+    pool: DatabasePool,
+}
+
+impl SqlCustomQuickNoteRepository {
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl CustomQuickNoteRepository for SqlCustomQuickNoteRepository {
+    async fn insert(&self, note: NewCustomQuickNote) -> Result<CustomQuickNote, RepositoryError> {
+        let created_at = note.created_at.unwrap_or_else(Utc::now);
+        let query = format!(
+            "INSERT INTO custom_quick_notes (label, emoji, created_at) \
+             VALUES (?, ?, ?) RETURNING {QUICK_NOTE_COLUMNS}"
+        );
+
+        let record = query_as::<_, CustomQuickNoteRecord>(AssertSqlSafe(query))
+            .bind(&note.label)
+            .bind(&note.emoji)
+            .bind(created_at)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|err| {
+                if let sqlx::Error::Database(db_err) = &err
+                    && db_err.is_unique_violation()
+                {
+                    return RepositoryError::conflict(
+                        "a quick note with this label already exists",
+                    );
+                }
+                RepositoryError::unexpected(format!("failed to insert quick note: {err}"))
+            })?;
+
+        Ok(record.into())
+    }
+
+    async fn list(&self) -> Result<Vec<CustomQuickNote>, RepositoryError> {
+        let query =
+            format!("SELECT {QUICK_NOTE_COLUMNS} FROM custom_quick_notes ORDER BY created_at, id");
+
+        let records = query_as::<_, CustomQuickNoteRecord>(AssertSqlSafe(query))
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|err| {
+                RepositoryError::unexpected(format!("failed to list quick notes: {err}"))
+            })?;
+
+        Ok(records.into_iter().map(Into::into).collect())
+    }
+
+    async fn delete(&self, id: CustomQuickNoteId) -> Result<(), RepositoryError> {
+        let result = query("DELETE FROM custom_quick_notes WHERE id = ?")
+            .bind(id.into_inner())
+            .execute(&self.pool)
+            .await
+            .map_err(|err| {
+                RepositoryError::unexpected(format!("failed to delete quick note: {err}"))
+            })?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct CustomQuickNoteRecord {
+    id: i64,
+    label: String,
+    emoji: String,
+    created_at: DateTime<Utc>,
+}
+
+impl From<CustomQuickNoteRecord> for CustomQuickNote {
+    fn from(record: CustomQuickNoteRecord) -> Self {
+        CustomQuickNote {
+            id: CustomQuickNoteId::new(record.id),
+            label: record.label,
+            emoji: record.emoji,
+            created_at: record.created_at,
+        }
+    }
+}
@@ -0,0 +1,210 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{AssertSqlSafe, QueryBuilder, query, query_as};
+
+use crate::domain::ids::{BagId, GreenCoffeeId, RoastBatchId};
+use crate::domain::repositories::RoastBatchRepository;
+use crate::domain::roast_batches::{NewRoastBatch, RoastBatch, UpdateRoastBatch};
+use crate::domain::{ErrorCode, RepositoryError};
+use crate::infrastructure::database::DatabasePool;
+use crate::infrastructure::repositories::macros::push_update_field;
+
+const BATCH_COLUMNS: &str = "id, green_coffee_id, bag_id, roasted_at, weight_in, weight_out, \
+     total_time, development_time, curve_notes, created_at, updated_at";
+
+/// Green coffee left to roast for the batch's green coffee, as of the
+/// statement. Correlated on `roast_batches.green_coffee_id` in an update.
+const GREEN_REMAINING: &str = "(SELECT g.amount - COALESCE((SELECT SUM(b.weight_in) \
+     FROM roast_batches b WHERE b.green_coffee_id = g.id), 0) FROM green_coffees g WHERE g.id = ";
+
+fn not_enough_green() -> RepositoryError {
+    RepositoryError::conflict_with(
+        ErrorCode::GreenCoffeeInsufficient,
+        "not enough green coffee is left to roast",
+    )
+}
+
+#[derive(Clone)]
+pub struct SqlRoastBatchRepository {
+    pool: DatabasePool,
+}
+
+impl SqlRoastBatchRepository {
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl RoastBatchRepository for SqlRoastBatchRepository {
+    async fn insert(&self, batch: NewRoastBatch) -> Result<RoastBatch, RepositoryError> {
+        let batch = batch.normalize();
+        let now = batch.created_at.unwrap_or_else(Utc::now);
+        let roasted_at = batch.roasted_at.unwrap_or(now);
+        // Guarded in SQL so concurrent batches cannot roast more green
+        // coffee than was bought.
+        let query = format!(
+            "INSERT INTO roast_batches (green_coffee_id, bag_id, roasted_at, weight_in, weight_out, \
+             total_time, development_time, curve_notes, created_at, updated_at) \
+             SELECT ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10 \
+             WHERE ?4 <= {GREEN_REMAINING}?1) RETURNING {BATCH_COLUMNS}"
+        );
+
+        let record = query_as::<_, RoastBatchRecord>(AssertSqlSafe(query))
+            .bind(batch.green_coffee_id.into_inner())
+            .bind(batch.bag_id.map(BagId::into_inner))
+            .bind(roasted_at)
+            .bind(batch.weight_in)
+            .bind(batch.weight_out)
+            .bind(batch.total_time)
+            .bind(batch.development_time)
+            .bind(batch.curve_notes.as_deref())
+            .bind(now)
+            .bind(now)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|err| {
+                RepositoryError::unexpected(format!("failed to insert roast batch: {err}"))
+            })?
+            .ok_or_else(not_enough_green)?;
+
+        Ok(record.into())
+    }
+
+    async fn get(&self, id: RoastBatchId) -> Result<RoastBatch, RepositoryError> {
+        let query = format!("SELECT {BATCH_COLUMNS} FROM roast_batches WHERE id = ?");
+
+        query_as::<_, RoastBatchRecord>(AssertSqlSafe(query))
+            .bind(id.into_inner())
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|err| {
+                RepositoryError::unexpected(format!("failed to load roast batch: {err}"))
+            })?
+            .map(Into::into)
+            .ok_or(RepositoryError::NotFound)
+    }
+
+    async fn list(
+        &self,
+        green_coffee_id: Option<GreenCoffeeId>,
+    ) -> Result<Vec<RoastBatch>, RepositoryError> {
+        let query = format!(
+            "SELECT {BATCH_COLUMNS} FROM roast_batches \
+             WHERE ?1 IS NULL OR green_coffee_id = ?1 \
+             ORDER BY roasted_at DESC, id DESC"
+        );
+
+        let records = query_as::<_, RoastBatchRecord>(AssertSqlSafe(query))
+            .bind(green_coffee_id.map(GreenCoffeeId::into_inner))
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|err| {
+                RepositoryError::unexpected(format!("failed to list roast batches: {err}"))
+            })?;
+
+        Ok(records.into_iter().map(Into::into).collect())
+    }
+
+    async fn update(
+        &self,
+        id: RoastBatchId,
+        changes: UpdateRoastBatch,
+    ) -> Result<RoastBatch, RepositoryError> {
+        let mut builder =
+            QueryBuilder::new("UPDATE roast_batches SET updated_at = CURRENT_TIMESTAMP");
+        let mut sep = true;
+
+        push_update_field!(
+            builder,
+            sep,
+            "bag_id",
+            changes.bag_id.map(|id| id.map(BagId::into_inner))
+        );
+        push_update_field!(builder, sep, "roasted_at", changes.roasted_at);
+        push_update_field!(builder, sep, "weight_in", changes.weight_in);
+        push_update_field!(builder, sep, "weight_out", changes.weight_out);
+        push_update_field!(builder, sep, "total_time", changes.total_time);
+        push_update_field!(builder, sep, "development_time", changes.development_time);
+        push_update_field!(builder, sep, "curve_notes", changes.curve_notes);
+        let _ = sep;
+
+        builder.push(" WHERE id = ");
+        builder.push_bind(id.into_inner());
+        // Only the extra green coffee a heavier batch takes has to be left.
+        if let Some(weight_in) = changes.weight_in {
+            builder.push(" AND ");
+            builder.push_bind(weight_in);
+            builder.push(format!(
+                " - weight_in <= {GREEN_REMAINING}roast_batches.green_coffee_id)"
+            ));
+        }
+        builder.push(format!(" RETURNING {BATCH_COLUMNS}"));
+
+        let record = builder
+            .build_query_as::<RoastBatchRecord>()
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|err| {
+                RepositoryError::unexpected(format!("failed to update roast batch: {err}"))
+            })?;
+
+        match record {
+            Some(record) => Ok(record.into()),
+            None if changes.weight_in.is_some() => match self.get(id).await {
+                Ok(_) => Err(not_enough_green()),
+                Err(err) => Err(err),
+            },
+            None => Err(RepositoryError::NotFound),
+        }
+    }
+
+    async fn delete(&self, id: RoastBatchId) -> Result<(), RepositoryError> {
+        let result = query("DELETE FROM roast_batches WHERE id = ?")
+            .bind(id.into_inner())
+            .execute(&self.pool)
+            .await
+            .map_err(|err| {
+                RepositoryError::unexpected(format!("failed to delete roast batch: {err}"))
+            })?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct RoastBatchRecord {
+    id: i64,
+    green_coffee_id: i64,
+    bag_id: Option<i64>,
+    roasted_at: DateTime<Utc>,
+    weight_in: f64,
+    weight_out: f64,
+    total_time: Option<i64>,
+    development_time: Option<i64>,
+    curve_notes: Option<String>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl From<RoastBatchRecord> for RoastBatch {
+    fn from(record: RoastBatchRecord) -> Self {
+        RoastBatch {
+            id: RoastBatchId::new(record.id),
+            green_coffee_id: GreenCoffeeId::new(record.green_coffee_id),
+            bag_id: record.bag_id.map(BagId::new),
+            roasted_at: record.roasted_at,
+            weight_in: record.weight_in,
+            weight_out: record.weight_out,
+            total_time: record.total_time,
+            development_time: record.development_time,
+            curve_notes: record.curve_notes,
+            created_at: record.created_at,
+            updated_at: record.updated_at,
+        }
+    }
+}
@@ -2,13 +2,22 @@ use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use sqlx::{QueryBuilder, query, query_as};
 
-use crate::domain::RepositoryError;
-use crate::domain::ids::RoasterId;
+use crate::domain::delete_preview::DeletePreview;
+use crate::domain::ids::{RoasterId, UserId};
 use crate::domain::listing::{ListRequest, Page, SortDirection};
 use crate::domain::repositories::RoasterRepository;
 use crate::domain::roasters::{NewRoaster, Roaster, RoasterSortKey, UpdateRoaster};
+use crate::domain::{ErrorCode, RepositoryError};
 use crate::infrastructure::database::DatabasePool;
+use crate::infrastructure::repositories::coffee::{
+    DeletePreviewRecord, set_created_by, set_favourite,
+};
 use crate::infrastructure::repositories::macros::push_update_field;
+use crate::infrastructure::repositories::query_timing::timed;
+
+/// A roaster's aliases, so searching a previous name still finds it.
+const ROASTER_ALIASES: &str =
+    "COALESCE((SELECT GROUP_CONCAT(name, ' ') FROM aliases WHERE roaster_id = roasters.id),'')";
 
 #[derive(Clone)]
 pub struct SqlRoasterRepository {
@@ -37,14 +46,17 @@ impl SqlRoasterRepository {
 
 #[async_trait]
 impl RoasterRepository for SqlRoasterRepository {
-    async fn insert(&self, new_roaster: NewRoaster) -> Result<Roaster, RepositoryError> {
+    async fn insert_with_slug(
+        &self,
+        new_roaster: NewRoaster,
+        slug: String,
+    ) -> Result<Roaster, RepositoryError> {
         let new_roaster = new_roaster.normalize();
-        let slug = new_roaster.slug();
         let created_at = new_roaster.created_at.unwrap_or_else(Utc::now);
 
         let record = query_as::<_, RoasterRecord>(
                 "INSERT INTO roasters (name, slug, country, city, homepage, created_at) VALUES (?, ?, ?, ?, ?, ?)\
-                 RETURNING id, name, slug, country, city, homepage, created_at",
+                 RETURNING id, name, slug, country, city, homepage, created_at, favourite, created_by",
             )
             .bind(&new_roaster.name)
             .bind(&slug)
@@ -58,9 +70,7 @@ impl RoasterRepository for SqlRoasterRepository {
                 if let sqlx::Error::Database(db_err) = &err
                     && db_err.is_unique_violation()
                 {
-                    return RepositoryError::conflict(
-                        "A roaster with this name and city already exists",
-                    );
+                    return RepositoryError::conflict_with(ErrorCode::DuplicateSlug, "A roaster with this name and city already exists");
                 }
                 RepositoryError::unexpected(err.to_string())
             })?;
@@ -70,7 +80,7 @@ impl RoasterRepository for SqlRoasterRepository {
 
     async fn get(&self, id: RoasterId) -> Result<Roaster, RepositoryError> {
         let record = query_as::<_, RoasterRecord>(
-            "SELECT id, name, slug, country, city, homepage, created_at FROM roasters WHERE id = ?",
+            "SELECT id, name, slug, country, city, homepage, created_at, favourite, created_by FROM roasters WHERE id = ?",
         )
         .bind(i64::from(id))
         .fetch_optional(&self.pool)
@@ -85,7 +95,7 @@ impl RoasterRepository for SqlRoasterRepository {
 
     async fn get_by_slug(&self, slug: &str) -> Result<Roaster, RepositoryError> {
         let record = query_as::<_, RoasterRecord>(
-                "SELECT id, name, slug, country, city, homepage, created_at FROM roasters WHERE slug = ?",
+                "SELECT id, name, slug, country, city, homepage, created_at, favourite, created_by FROM roasters WHERE slug = ?",
             )
             .bind(slug)
             .fetch_optional(&self.pool)
@@ -106,19 +116,26 @@ impl RoasterRepository for SqlRoasterRepository {
         use crate::infrastructure::repositories::pagination::SearchFilter;
 
         let order_clause = Self::order_clause(request);
-        let base_query = "SELECT id, name, slug, country, city, homepage, created_at FROM roasters";
+        let base_query = "SELECT id, name, slug, country, city, homepage, created_at, favourite, created_by FROM roasters";
         let count_query = "SELECT COUNT(*) FROM roasters";
-        let sf =
-            search.and_then(|t| SearchFilter::new(t, vec!["name", "country", "COALESCE(city,'')"]));
-
-        crate::infrastructure::repositories::pagination::paginate(
-            &self.pool,
-            request,
-            base_query,
-            count_query,
-            &order_clause,
-            sf.as_ref(),
-            |record: RoasterRecord| Ok(record.into()),
+        let sf = search.and_then(|t| {
+            SearchFilter::new(
+                t,
+                vec!["name", "country", "COALESCE(city,'')", ROASTER_ALIASES],
+            )
+        });
+
+        timed(
+            "roasters.list",
+            crate::infrastructure::repositories::pagination::paginate(
+                &self.pool,
+                request,
+                base_query,
+                count_query,
+                &order_clause,
+                sf.as_ref(),
+                |record: RoasterRecord| Ok(record.into()),
+            ),
         )
         .await
     }
@@ -159,6 +176,14 @@ impl RoasterRepository for SqlRoasterRepository {
         self.get(id).await
     }
 
+    async fn set_created_by(&self, id: RoasterId, user_id: UserId) -> Result<(), RepositoryError> {
+        set_created_by(&self.pool, "roasters", i64::from(id), user_id).await
+    }
+
+    async fn set_favourite(&self, id: RoasterId, favourite: bool) -> Result<(), RepositoryError> {
+        set_favourite(&self.pool, "roasters", i64::from(id), favourite).await
+    }
+
     async fn delete(&self, id: RoasterId) -> Result<(), RepositoryError> {
         let result = query("DELETE FROM roasters WHERE id = ?")
             .bind(i64::from(id))
@@ -172,6 +197,34 @@ impl RoasterRepository for SqlRoasterRepository {
 
         Ok(())
     }
+
+    async fn delete_preview(&self, id: RoasterId) -> Result<DeletePreview, RepositoryError> {
+        let sql = r"
+            WITH r AS (SELECT id FROM roasts WHERE roaster_id = ?1),
+                 b AS (SELECT id FROM bags WHERE roast_id IN (SELECT id FROM r)),
+                 br AS (SELECT id FROM brews WHERE bag_id IN (SELECT id FROM b)),
+                 c AS (SELECT id FROM cups WHERE roast_id IN (SELECT id FROM r))
+            SELECT
+                (SELECT COUNT(*) FROM r) AS roasts,
+                (SELECT COUNT(*) FROM b) AS bags,
+                (SELECT COUNT(*) FROM br) AS brews,
+                (SELECT COUNT(*) FROM c) AS cups,
+                (SELECT COUNT(*) FROM entity_images
+                    WHERE deleted_at IS NULL
+                      AND ((entity_type = 'roaster' AND entity_id = ?1)
+                       OR (entity_type = 'roast' AND entity_id IN (SELECT id FROM r))
+                       OR (entity_type = 'brew' AND entity_id IN (SELECT id FROM br)))) AS images
+            FROM roasters WHERE id = ?1
+        ";
+
+        query_as::<_, DeletePreviewRecord>(sql)
+            .bind(i64::from(id))
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|err| RepositoryError::unexpected(err.to_string()))?
+            .map(DeletePreview::from)
+            .ok_or(RepositoryError::NotFound)
+    }
 }
 
 #[derive(Debug, sqlx::FromRow)]
@@ -183,6 +236,8 @@ struct RoasterRecord {
     city: Option<String>,
     homepage: Option<String>,
     created_at: DateTime<Utc>,
+    favourite: bool,
+    created_by: Option<i64>,
 }
 
 impl From<RoasterRecord> for Roaster {
@@ -195,6 +250,8 @@ impl From<RoasterRecord> for Roaster {
             city: record.city,
             homepage: record.homepage,
             created_at: record.created_at,
+            favourite: record.favourite,
+            created_by: record.created_by.map(UserId::from),
         }
     }
 }
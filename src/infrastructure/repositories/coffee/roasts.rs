@@ -3,13 +3,26 @@ use chrono::{DateTime, Utc};
 use serde_json::{from_str, to_string};
 use sqlx::{Error as SqlxError, QueryBuilder, query, query_as};
 
-use crate::domain::RepositoryError;
-use crate::domain::ids::{RoastId, RoasterId};
+use crate::domain::blends::BlendComponent;
+use crate::domain::delete_preview::DeletePreview;
+use crate::domain::ids::{RoastId, RoasterId, UserId};
 use crate::domain::listing::{ListRequest, Page, SortDirection};
 use crate::domain::repositories::RoastRepository;
-use crate::domain::roasts::{NewRoast, Roast, RoastSortKey, RoastWithRoaster, UpdateRoast};
+use crate::domain::roasts::{
+    FieldSuggestion, NewRoast, Roast, RoastField, RoastSortKey, RoastWithRoaster, UpdateRoast,
+};
+use crate::domain::{ErrorCode, RepositoryError};
 use crate::infrastructure::database::DatabasePool;
+use crate::infrastructure::repositories::coffee::{
+    DeletePreviewRecord, set_created_by, set_favourite,
+};
 use crate::infrastructure::repositories::macros::push_update_field;
+use crate::infrastructure::repositories::query_timing::timed;
+
+/// Aliases of a roast and of its roaster, so searching a previous name of
+/// either still finds it.
+const ROAST_ALIASES: &str = "COALESCE((SELECT GROUP_CONCAT(name, ' ') FROM aliases \
+     WHERE roast_id = r.id OR roaster_id = r.roaster_id),'')";
 
 #[derive(Clone)]
 pub struct SqlRoastRepository {
@@ -40,6 +53,16 @@ impl SqlRoastRepository {
         }
     }
 
+    fn encode_components(components: &[BlendComponent]) -> Result<Option<String>, RepositoryError> {
+        if components.is_empty() {
+            Ok(None)
+        } else {
+            to_string(components).map(Some).map_err(|err| {
+                RepositoryError::unexpected(format!("failed to encode blend components: {err}"))
+            })
+        }
+    }
+
     fn encode_notes(notes: &[String]) -> Result<Option<String>, RepositoryError> {
         if notes.is_empty() {
             Ok(None)
@@ -57,8 +80,11 @@ fn empty_to_none(s: String) -> Option<String> {
 
 #[async_trait]
 impl RoastRepository for SqlRoastRepository {
-    async fn insert(&self, new_roast: NewRoast) -> Result<Roast, RepositoryError> {
-        let slug = new_roast.slug();
+    async fn insert_with_slug(
+        &self,
+        new_roast: NewRoast,
+        slug: String,
+    ) -> Result<Roast, RepositoryError> {
         let NewRoast {
             roaster_id,
             name,
@@ -67,6 +93,8 @@ impl RoastRepository for SqlRoastRepository {
             producer,
             tasting_notes,
             process,
+            components,
+            harvest_year,
             created_at,
         } = new_roast;
 
@@ -77,10 +105,11 @@ impl RoastRepository for SqlRoastRepository {
 
         let created_at = created_at.unwrap_or_else(Utc::now);
         let notes_json = Self::encode_notes(&tasting_notes)?;
+        let components_json = Self::encode_components(&components)?;
 
         let record = query_as::<_, RoastRecord>(
-                "INSERT INTO roasts (roaster_id, name, slug, origin, region, producer, process, tasting_notes, created_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)\
-                 RETURNING id, roaster_id, name, slug, origin, region, producer, process, tasting_notes, created_at",
+                "INSERT INTO roasts (roaster_id, name, slug, origin, region, producer, process, tasting_notes, components, harvest_year, created_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)\
+                 RETURNING id, roaster_id, name, slug, origin, region, producer, process, tasting_notes, components, harvest_year, created_at, favourite, rating, created_by",
             )
             .bind(i64::from(roaster_id))
             .bind(&name)
@@ -90,6 +119,8 @@ impl RoastRepository for SqlRoastRepository {
             .bind(producer_value.as_deref())
             .bind(process_value.as_deref())
             .bind(notes_json.as_deref())
+            .bind(components_json.as_deref())
+            .bind(harvest_year)
             .bind(created_at)
             .fetch_one(&self.pool)
             .await
@@ -97,9 +128,7 @@ impl RoastRepository for SqlRoastRepository {
                 if let sqlx::Error::Database(db_err) = &err
                     && db_err.is_unique_violation()
                 {
-                    return RepositoryError::conflict(
-                        "A roast with this name already exists for this roaster",
-                    );
+                    return RepositoryError::conflict_with(ErrorCode::DuplicateSlug, "A roast with this name already exists for this roaster");
                 }
                 map_insert_error(err, "unknown roaster reference")
             })?;
@@ -109,7 +138,7 @@ impl RoastRepository for SqlRoastRepository {
 
     async fn get(&self, id: RoastId) -> Result<Roast, RepositoryError> {
         query_as::<_, RoastRecord>(
-                "SELECT id, roaster_id, name, slug, origin, region, producer, process, tasting_notes, created_at FROM roasts WHERE id = ?",
+                "SELECT id, roaster_id, name, slug, origin, region, producer, process, tasting_notes, components, harvest_year, created_at, favourite, rating, created_by FROM roasts WHERE id = ?",
             )
             .bind(i64::from(id))
             .fetch_optional(&self.pool)
@@ -122,7 +151,7 @@ impl RoastRepository for SqlRoastRepository {
 
     async fn get_with_roaster(&self, id: RoastId) -> Result<RoastWithRoaster, RepositoryError> {
         query_as::<_, RoastWithRoasterRecord>(
-            "SELECT r.id, r.roaster_id, r.name, r.slug, r.origin, r.region, r.producer, r.process, r.tasting_notes, r.created_at, ro.name AS roaster_name, ro.slug AS roaster_slug \
+            "SELECT r.id, r.roaster_id, r.name, r.slug, r.origin, r.region, r.producer, r.process, r.tasting_notes, r.components, r.harvest_year, r.created_at, r.favourite, r.rating, r.created_by, ro.name AS roaster_name, ro.slug AS roaster_slug \
              FROM roasts r \
              JOIN roasters ro ON ro.id = r.roaster_id \
              WHERE r.id = ?",
@@ -142,7 +171,7 @@ impl RoastRepository for SqlRoastRepository {
         slug: &str,
     ) -> Result<Roast, RepositoryError> {
         query_as::<_, RoastRecord>(
-                "SELECT id, roaster_id, name, slug, origin, region, producer, process, tasting_notes, created_at FROM roasts WHERE roaster_id = ? AND slug = ?",
+                "SELECT id, roaster_id, name, slug, origin, region, producer, process, tasting_notes, components, harvest_year, created_at, favourite, rating, created_by FROM roasts WHERE roaster_id = ? AND slug = ?",
             )
             .bind(i64::from(roaster_id))
             .bind(slug)
@@ -162,7 +191,7 @@ impl RoastRepository for SqlRoastRepository {
         use crate::infrastructure::repositories::pagination::SearchFilter;
 
         let order_clause = Self::order_clause(request);
-        let base_query = "SELECT r.id, r.roaster_id, r.name, r.slug, r.origin, r.region, r.producer, r.process, r.tasting_notes, r.created_at, ro.name AS roaster_name, ro.slug AS roaster_slug \n                     FROM roasts r \n                     JOIN roasters ro ON ro.id = r.roaster_id";
+        let base_query = "SELECT r.id, r.roaster_id, r.name, r.slug, r.origin, r.region, r.producer, r.process, r.tasting_notes, r.components, r.harvest_year, r.created_at, r.favourite, r.rating, r.created_by, ro.name AS roaster_name, ro.slug AS roaster_slug \n                     FROM roasts r \n                     JOIN roasters ro ON ro.id = r.roaster_id";
         let count_query = "SELECT COUNT(*) FROM roasts r JOIN roasters ro ON ro.id = r.roaster_id";
         let sf = search.and_then(|t| {
             SearchFilter::new(
@@ -173,18 +202,22 @@ impl RoastRepository for SqlRoastRepository {
                     "COALESCE(r.origin,'')",
                     "COALESCE(r.producer,'')",
                     "COALESCE(r.tasting_notes,'')",
+                    ROAST_ALIASES,
                 ],
             )
         });
 
-        crate::infrastructure::repositories::pagination::paginate(
-            &self.pool,
-            request,
-            base_query,
-            count_query,
-            &order_clause,
-            sf.as_ref(),
-            |record: RoastWithRoasterRecord| record.try_into(),
+        timed(
+            "roasts.list",
+            crate::infrastructure::repositories::pagination::paginate(
+                &self.pool,
+                request,
+                base_query,
+                count_query,
+                &order_clause,
+                sf.as_ref(),
+                |record: RoastWithRoasterRecord| record.try_into(),
+            ),
         )
         .await
     }
@@ -194,7 +227,7 @@ impl RoastRepository for SqlRoastRepository {
         roaster_id: RoasterId,
     ) -> Result<Vec<RoastWithRoaster>, RepositoryError> {
         let records = query_as::<_, RoastWithRoasterRecord>(
-                "SELECT r.id, r.roaster_id, r.name, r.slug, r.origin, r.region, r.producer, r.process, r.tasting_notes, r.created_at, ro.name AS roaster_name, ro.slug AS roaster_slug \n             FROM roasts r \n             JOIN roasters ro ON ro.id = r.roaster_id \n             WHERE r.roaster_id = ? \n             ORDER BY r.created_at DESC",
+                "SELECT r.id, r.roaster_id, r.name, r.slug, r.origin, r.region, r.producer, r.process, r.tasting_notes, r.components, r.harvest_year, r.created_at, r.favourite, r.rating, r.created_by, ro.name AS roaster_name, ro.slug AS roaster_slug \n             FROM roasts r \n             JOIN roasters ro ON ro.id = r.roaster_id \n             WHERE r.roaster_id = ? \n             ORDER BY r.created_at DESC",
             )
             .bind(i64::from(roaster_id))
             .fetch_all(&self.pool)
@@ -229,6 +262,7 @@ impl RoastRepository for SqlRoastRepository {
         push_update_field!(builder, sep, "region", changes.region);
         push_update_field!(builder, sep, "producer", changes.producer);
         push_update_field!(builder, sep, "process", changes.process);
+        push_update_field!(builder, sep, "harvest_year", changes.harvest_year);
         push_update_field!(builder, sep, "created_at", changes.created_at);
 
         // Handle tasting_notes specially due to JSON encoding
@@ -242,6 +276,16 @@ impl RoastRepository for SqlRoastRepository {
             builder.push_bind(notes_json);
         }
 
+        if let Some(components) = changes.components {
+            let components_json = Self::encode_components(&components)?;
+            if sep {
+                builder.push(", ");
+            }
+            sep = true;
+            builder.push("components = ");
+            builder.push_bind(components_json);
+        }
+
         if !sep {
             return Err(RepositoryError::unexpected(
                 "No fields provided for update".to_string(),
@@ -268,6 +312,14 @@ impl RoastRepository for SqlRoastRepository {
         self.get(id).await
     }
 
+    async fn set_created_by(&self, id: RoastId, user_id: UserId) -> Result<(), RepositoryError> {
+        set_created_by(&self.pool, "roasts", i64::from(id), user_id).await
+    }
+
+    async fn set_favourite(&self, id: RoastId, favourite: bool) -> Result<(), RepositoryError> {
+        set_favourite(&self.pool, "roasts", i64::from(id), favourite).await
+    }
+
     async fn delete(&self, id: RoastId) -> Result<(), RepositoryError> {
         let result = query("DELETE FROM roasts WHERE id = ?")
             .bind(i64::from(id))
@@ -281,6 +333,66 @@ impl RoastRepository for SqlRoastRepository {
 
         Ok(())
     }
+
+    async fn delete_preview(&self, id: RoastId) -> Result<DeletePreview, RepositoryError> {
+        let sql = r"
+            WITH b AS (SELECT id FROM bags WHERE roast_id = ?1),
+                 br AS (SELECT id FROM brews WHERE bag_id IN (SELECT id FROM b))
+            SELECT
+                0 AS roasts,
+                (SELECT COUNT(*) FROM b) AS bags,
+                (SELECT COUNT(*) FROM br) AS brews,
+                (SELECT COUNT(*) FROM cups WHERE roast_id = ?1) AS cups,
+                (SELECT COUNT(*) FROM entity_images
+                    WHERE deleted_at IS NULL
+                      AND ((entity_type = 'roast' AND entity_id = ?1)
+                       OR (entity_type = 'brew' AND entity_id IN (SELECT id FROM br)))) AS images
+            FROM roasts WHERE id = ?1
+        ";
+
+        query_as::<_, DeletePreviewRecord>(sql)
+            .bind(i64::from(id))
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|err| RepositoryError::unexpected(err.to_string()))?
+            .map(DeletePreview::from)
+            .ok_or(RepositoryError::NotFound)
+    }
+
+    async fn suggest(
+        &self,
+        field: RoastField,
+        query: &str,
+        limit: u32,
+    ) -> Result<Vec<FieldSuggestion>, RepositoryError> {
+        // Tasting notes are stored as a JSON array, so expand them into rows first.
+        let source = match field {
+            RoastField::Origin => "SELECT TRIM(origin) AS value FROM roasts",
+            RoastField::Process => "SELECT TRIM(process) AS value FROM roasts",
+            RoastField::Producer => "SELECT TRIM(producer) AS value FROM roasts",
+            RoastField::TastingNote => {
+                "SELECT TRIM(n.value) AS value FROM roasts, json_each(roasts.tasting_notes) n \
+                 WHERE json_valid(roasts.tasting_notes)"
+            }
+        };
+        let sql = format!(
+            "SELECT MIN(value) AS value, COUNT(*) AS uses FROM ({source}) \
+             WHERE value IS NOT NULL AND value != '' AND LOWER(value) LIKE ? \
+             GROUP BY LOWER(value) ORDER BY uses DESC, LOWER(value) LIMIT ?"
+        );
+
+        query_as::<_, (String, i64)>(sqlx::AssertSqlSafe(sql))
+            .bind(format!("%{}%", query.trim().to_lowercase()))
+            .bind(i64::from(limit))
+            .fetch_all(&self.pool)
+            .await
+            .map(|rows| {
+                rows.into_iter()
+                    .map(|(value, uses)| FieldSuggestion { value, uses })
+                    .collect()
+            })
+            .map_err(|err| RepositoryError::unexpected(err.to_string()))
+    }
 }
 
 fn map_insert_error(err: SqlxError, message: &'static str) -> RepositoryError {
@@ -293,6 +405,17 @@ fn map_insert_error(err: SqlxError, message: &'static str) -> RepositoryError {
     RepositoryError::unexpected(err.to_string())
 }
 
+fn decode_components(raw: Option<String>) -> Result<Vec<BlendComponent>, RepositoryError> {
+    raw.map_or_else(
+        || Ok(Vec::new()),
+        |raw| {
+            from_str(&raw).map_err(|err| {
+                RepositoryError::unexpected(format!("failed to decode blend components: {err}"))
+            })
+        },
+    )
+}
+
 #[derive(sqlx::FromRow)]
 struct RoastRecord {
     id: i64,
@@ -304,7 +427,12 @@ struct RoastRecord {
     producer: Option<String>,
     process: Option<String>,
     tasting_notes: Option<String>,
+    components: Option<String>,
+    harvest_year: Option<i32>,
     created_at: DateTime<Utc>,
+    favourite: bool,
+    rating: Option<f64>,
+    created_by: Option<i64>,
 }
 
 impl TryFrom<RoastRecord> for Roast {
@@ -317,6 +445,7 @@ impl TryFrom<RoastRecord> for Roast {
             })?,
             None => Vec::new(),
         };
+        let components = decode_components(record.components)?;
 
         Ok(Roast {
             id: RoastId::from(record.id),
@@ -328,7 +457,12 @@ impl TryFrom<RoastRecord> for Roast {
             producer: record.producer,
             process: record.process,
             tasting_notes,
+            components,
+            harvest_year: record.harvest_year,
             created_at: record.created_at,
+            favourite: record.favourite,
+            rating: record.rating,
+            created_by: record.created_by.map(UserId::from),
         })
     }
 }
@@ -344,7 +478,12 @@ struct RoastWithRoasterRecord {
     producer: Option<String>,
     process: Option<String>,
     tasting_notes: Option<String>,
+    components: Option<String>,
+    harvest_year: Option<i32>,
     created_at: DateTime<Utc>,
+    favourite: bool,
+    rating: Option<f64>,
+    created_by: Option<i64>,
     roaster_name: String,
     roaster_slug: String,
 }
@@ -359,6 +498,7 @@ impl TryFrom<RoastWithRoasterRecord> for RoastWithRoaster {
             })?,
             None => Vec::new(),
         };
+        let components = decode_components(record.components)?;
 
         Ok(RoastWithRoaster {
             roast: Roast {
@@ -371,7 +511,12 @@ impl TryFrom<RoastWithRoasterRecord> for RoastWithRoaster {
                 producer: record.producer,
                 process: record.process,
                 tasting_notes,
+                components,
+                harvest_year: record.harvest_year,
                 created_at: record.created_at,
+                favourite: record.favourite,
+                rating: record.rating,
+                created_by: record.created_by.map(UserId::from),
             },
             roaster_name: record.roaster_name,
             roaster_slug: record.roaster_slug,
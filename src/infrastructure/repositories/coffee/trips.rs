@@ -0,0 +1,148 @@
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use sqlx::{AssertSqlSafe, QueryBuilder, query, query_as};
+
+use crate::domain::RepositoryError;
+use crate::domain::ids::{TripId, UserId};
+use crate::domain::repositories::TripRepository;
+use crate::domain::trips::{NewTrip, Trip, UpdateTrip};
+use crate::infrastructure::database::DatabasePool;
+use crate::infrastructure::repositories::coffee::set_created_by;
+use crate::infrastructure::repositories::macros::push_update_field;
+
+const TRIP_COLUMNS: &str =
+    "id, name, location, start_date, end_date, notes, created_at, updated_at, created_by";
+
+#[derive(Clone)]
+pub struct SqlTripRepository {
+    pool: DatabasePool,
+}
+
+impl SqlTripRepository {
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl TripRepository for SqlTripRepository {
+    async fn insert(&self, trip: NewTrip) -> Result<Trip, RepositoryError> {
+        let trip = trip.normalize();
+        let now = trip.created_at.unwrap_or_else(Utc::now);
+
+        let query = format!(
+            "INSERT INTO trips (name, location, start_date, end_date, notes, created_at, updated_at) \
+             VALUES (?, ?, ?, ?, ?, ?, ?) RETURNING {TRIP_COLUMNS}"
+        );
+
+        query_as::<_, TripRecord>(AssertSqlSafe(query))
+            .bind(&trip.name)
+            .bind(&trip.location)
+            .bind(trip.start_date)
+            .bind(trip.end_date)
+            .bind(trip.notes.as_deref())
+            .bind(now)
+            .bind(now)
+            .fetch_one(&self.pool)
+            .await
+            .map(Trip::from)
+            .map_err(|err| RepositoryError::unexpected(format!("failed to insert trip: {err}")))
+    }
+
+    async fn get(&self, id: TripId) -> Result<Trip, RepositoryError> {
+        let query = format!("SELECT {TRIP_COLUMNS} FROM trips WHERE id = ?");
+
+        query_as::<_, TripRecord>(AssertSqlSafe(query))
+            .bind(id.into_inner())
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|err| RepositoryError::unexpected(format!("failed to load trip: {err}")))?
+            .map(Trip::from)
+            .ok_or(RepositoryError::NotFound)
+    }
+
+    async fn list(&self) -> Result<Vec<Trip>, RepositoryError> {
+        let query = format!("SELECT {TRIP_COLUMNS} FROM trips ORDER BY start_date DESC, id DESC");
+
+        let records = query_as::<_, TripRecord>(AssertSqlSafe(query))
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|err| RepositoryError::unexpected(format!("failed to list trips: {err}")))?;
+
+        Ok(records.into_iter().map(Trip::from).collect())
+    }
+
+    async fn update(&self, id: TripId, changes: UpdateTrip) -> Result<Trip, RepositoryError> {
+        let changes = changes.normalize();
+        let mut builder = QueryBuilder::new("UPDATE trips SET updated_at = CURRENT_TIMESTAMP");
+        let mut sep = true;
+
+        push_update_field!(builder, sep, "name", changes.name);
+        push_update_field!(builder, sep, "location", changes.location);
+        push_update_field!(builder, sep, "start_date", changes.start_date);
+        push_update_field!(builder, sep, "end_date", changes.end_date);
+        push_update_field!(builder, sep, "notes", changes.notes);
+        let _ = sep;
+
+        builder.push(" WHERE id = ");
+        builder.push_bind(id.into_inner());
+
+        let result =
+            builder.build().execute(&self.pool).await.map_err(|err| {
+                RepositoryError::unexpected(format!("failed to update trip: {err}"))
+            })?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+
+        self.get(id).await
+    }
+
+    async fn set_created_by(&self, id: TripId, user_id: UserId) -> Result<(), RepositoryError> {
+        set_created_by(&self.pool, "trips", i64::from(id), user_id).await
+    }
+
+    async fn delete(&self, id: TripId) -> Result<(), RepositoryError> {
+        let result = query("DELETE FROM trips WHERE id = ?")
+            .bind(id.into_inner())
+            .execute(&self.pool)
+            .await
+            .map_err(|err| RepositoryError::unexpected(format!("failed to delete trip: {err}")))?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct TripRecord {
+    id: i64,
+    name: String,
+    location: String,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+    notes: Option<String>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    created_by: Option<i64>,
+}
+
+impl From<TripRecord> for Trip {
+    fn from(record: TripRecord) -> Self {
+        Trip {
+            id: TripId::new(record.id),
+            name: record.name,
+            location: record.location,
+            start_date: record.start_date,
+            end_date: record.end_date,
+            notes: record.notes,
+            created_at: record.created_at,
+            updated_at: record.updated_at,
+            created_by: record.created_by.map(UserId::new),
+        }
+    }
+}
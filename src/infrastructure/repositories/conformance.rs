@@ -0,0 +1,809 @@
+//! Behaviour every repository implementation must share, independent of the
+//! storage behind it. Each suite takes fresh, empty repositories and works
+//! through CRUD, listing, sorting, pagination and the error variants callers
+//! rely on. A new backend proves itself by running the suites from its own
+//! tests, as the SQLite repositories do at the bottom of this file.
+
+use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+
+use crate::domain::RepositoryError;
+use crate::domain::bag_ledger::{BagLedgerKind, NewBagAdjustment, NewBagTransfer};
+use crate::domain::bags::{BagFilter, BagSortKey, NewBag, UpdateBag};
+use crate::domain::blends::BlendComponent;
+use crate::domain::gear::{GearCategory, GearFilter, GearSortKey, NewGear, UpdateGear};
+use crate::domain::ids::{BagId, GearId, RoastId, RoasterId};
+use crate::domain::listing::{ListRequest, PageSize, SortDirection};
+use crate::domain::repositories::{
+    BagRepository, GearRepository, RoastRepository, RoasterRepository,
+};
+use crate::domain::roasters::{NewRoaster, Roaster, RoasterSortKey, UpdateRoaster};
+use crate::domain::roasts::{NewRoast, Roast, RoastSortKey, UpdateRoast};
+
+/// Noon on the given day of January 2025. Whole seconds keep timestamps
+/// comparable across backends that store them at different precisions.
+fn at(day: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2025, 1, day, 12, 0, 0).unwrap()
+}
+
+#[track_caller]
+fn assert_not_found<T: std::fmt::Debug>(result: Result<T, RepositoryError>) {
+    match result {
+        Err(RepositoryError::NotFound) => {}
+        other => panic!("expected NotFound, got {other:?}"),
+    }
+}
+
+#[track_caller]
+fn assert_conflict<T: std::fmt::Debug>(result: Result<T, RepositoryError>) {
+    match result {
+        Err(RepositoryError::Conflict(..)) => {}
+        other => panic!("expected Conflict, got {other:?}"),
+    }
+}
+
+fn new_roaster(name: &str, country: &str, city: &str, day: u32) -> NewRoaster {
+    NewRoaster {
+        name: name.to_string(),
+        country: country.to_string(),
+        city: Some(city.to_string()),
+        homepage: None,
+        created_at: Some(at(day)),
+    }
+}
+
+fn new_roast(roaster_id: RoasterId, name: &str, origin: &str, day: u32) -> NewRoast {
+    NewRoast {
+        roaster_id,
+        name: name.to_string(),
+        origin: origin.to_string(),
+        region: String::new(),
+        producer: String::new(),
+        tasting_notes: Vec::new(),
+        process: String::new(),
+        components: Vec::new(),
+        harvest_year: None,
+        created_at: Some(at(day)),
+    }
+}
+
+fn new_bag(roast_id: RoastId, amount: f64, day: u32) -> NewBag {
+    NewBag {
+        roast_id,
+        roast_date: NaiveDate::from_ymd_opt(2024, 12, 20),
+        amount,
+        price: None,
+        currency: None,
+        best_before: None,
+        batch_code: None,
+        created_at: Some(at(day)),
+    }
+}
+
+fn names<'a>(items: impl IntoIterator<Item = &'a str>) -> Vec<&'a str> {
+    items.into_iter().collect()
+}
+
+pub(crate) async fn roaster_repository(repo: &dyn RoasterRepository) {
+    let empty = repo
+        .list(&ListRequest::default_query(), None)
+        .await
+        .unwrap();
+    assert_eq!(empty.total, 0);
+    assert!(empty.items.is_empty());
+
+    // Insert normalises input and derives the slug from name and city.
+    let tim = repo
+        .insert(new_roaster(" Tim Wendelboe ", "Norway", "Oslo", 1))
+        .await
+        .unwrap();
+    assert_eq!(tim.name, "Tim Wendelboe");
+    assert_eq!(tim.slug, "tim-wendelboe-oslo");
+    assert_eq!(tim.country, "Norway");
+    assert_eq!(tim.city.as_deref(), Some("Oslo"));
+    assert_eq!(tim.created_at, at(1));
+    assert_conflict(
+        repo.insert(new_roaster("Tim Wendelboe", "Norway", "Oslo", 2))
+            .await,
+    );
+
+    assert_eq!(repo.get(tim.id).await.unwrap().name, "Tim Wendelboe");
+    assert_eq!(
+        repo.get_by_slug("tim-wendelboe-oslo").await.unwrap().id,
+        tim.id
+    );
+    assert_not_found(repo.get(RoasterId::new(9999)).await);
+    assert_not_found(repo.get_by_slug("nobody").await);
+
+    repo.insert(new_roaster("april", "Denmark", "Copenhagen", 2))
+        .await
+        .unwrap();
+    repo.insert(new_roaster("Square Mile", "United Kingdom", "London", 3))
+        .await
+        .unwrap();
+    repo.insert(new_roaster("Kurasu", "Japan", "Kyoto", 4))
+        .await
+        .unwrap();
+    let collective = repo
+        .insert(new_roaster("Coffee Collective", "Denmark", "Copenhagen", 5))
+        .await
+        .unwrap();
+
+    // Sorting: names compare case-insensitively, newest first by default.
+    let by_name = repo
+        .list_all_sorted(RoasterSortKey::Name, SortDirection::Asc)
+        .await
+        .unwrap();
+    assert_eq!(
+        names(by_name.iter().map(|r| r.name.as_str())),
+        [
+            "april",
+            "Coffee Collective",
+            "Kurasu",
+            "Square Mile",
+            "Tim Wendelboe"
+        ]
+    );
+    let by_name_desc = repo
+        .list_all_sorted(RoasterSortKey::Name, SortDirection::Desc)
+        .await
+        .unwrap();
+    assert_eq!(by_name_desc.first().unwrap().name, "Tim Wendelboe");
+    assert_eq!(by_name_desc.last().unwrap().name, "april");
+    let newest = repo.list_all().await.unwrap();
+    assert_eq!(
+        newest.iter().map(|r| r.created_at).collect::<Vec<_>>(),
+        [at(5), at(4), at(3), at(2), at(1)]
+    );
+
+    // Pagination, including clamping past the last page.
+    let request = |page| {
+        ListRequest::new(
+            page,
+            PageSize::limited(2),
+            RoasterSortKey::Name,
+            SortDirection::Asc,
+        )
+    };
+    let first = repo.list(&request(1), None).await.unwrap();
+    assert_eq!(first.items.len(), 2);
+    assert_eq!(first.total, 5);
+    assert!(!first.showing_all);
+    let last = repo.list(&request(3), None).await.unwrap();
+    assert_eq!(
+        names(last.items.iter().map(|r| r.name.as_str())),
+        ["Tim Wendelboe"]
+    );
+    let clamped = repo.list(&request(9), None).await.unwrap();
+    assert_eq!(clamped.page, 3);
+    assert_eq!(clamped.items.len(), 1);
+    let all = repo
+        .list(
+            &ListRequest::show_all(RoasterSortKey::Name, SortDirection::Asc),
+            None,
+        )
+        .await
+        .unwrap();
+    assert!(all.showing_all);
+    assert_eq!(all.items.len(), 5);
+
+    // Search is case-insensitive across name, country and city.
+    let first_page = request(1);
+    let search = |term| repo.list(&first_page, Some(term));
+    assert_eq!(search("copenhagen").await.unwrap().total, 2);
+    assert_eq!(search("MILE").await.unwrap().total, 1);
+    assert_eq!(search("japan").await.unwrap().total, 1);
+    assert_eq!(search("   ").await.unwrap().total, 5);
+    assert_eq!(search("nothing like it").await.unwrap().total, 0);
+
+    let moved = repo
+        .update(
+            collective.id,
+            UpdateRoaster {
+                city: Some("Aarhus".to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(moved.city.as_deref(), Some("Aarhus"));
+    assert_eq!(moved.name, "Coffee Collective");
+    assert_eq!(
+        repo.get(collective.id).await.unwrap().city.as_deref(),
+        Some("Aarhus")
+    );
+    assert_not_found(
+        repo.update(
+            RoasterId::new(9999),
+            UpdateRoaster {
+                city: Some("Nowhere".to_string()),
+                ..Default::default()
+            },
+        )
+        .await,
+    );
+
+    let preview = repo.delete_preview(tim.id).await.unwrap();
+    assert_eq!(preview.roasts, 0);
+    assert_not_found(repo.delete_preview(RoasterId::new(9999)).await);
+
+    repo.delete(tim.id).await.unwrap();
+    assert_not_found(repo.get(tim.id).await);
+    assert_not_found(repo.delete(tim.id).await);
+    assert_eq!(repo.list_all().await.unwrap().len(), 4);
+}
+
+pub(crate) async fn gear_repository(repo: &dyn GearRepository) {
+    let insert = |category, make: &str, model: &str, day| {
+        repo.insert(NewGear {
+            category,
+            make: make.to_string(),
+            model: model.to_string(),
+            water_temp: None,
+            brew_method: None,
+            uses_filter: None,
+            temp_offset: None,
+            water_hardness: None,
+            stock_count: None,
+            low_stock_threshold: None,
+            created_at: Some(at(day)),
+        })
+    };
+    let comandante = insert(GearCategory::Grinder, "Comandante", "C40", 1)
+        .await
+        .unwrap();
+    assert_eq!(comandante.category, GearCategory::Grinder);
+    assert_eq!(comandante.make, "Comandante");
+    assert_eq!(comandante.model, "C40");
+    assert_eq!(comandante.created_at, at(1));
+    let v60 = insert(GearCategory::Brewer, "Hario", "V60", 2)
+        .await
+        .unwrap();
+    insert(GearCategory::Grinder, "baratza", "Encore", 3)
+        .await
+        .unwrap();
+    let abaca = insert(GearCategory::FilterPaper, "Cafec", "Abaca", 4)
+        .await
+        .unwrap();
+    assert_eq!(abaca.stock_count, None);
+    assert_eq!(
+        repo.restock(abaca.id, 100).await.unwrap().stock_count,
+        Some(100)
+    );
+    assert_eq!(
+        repo.restock(abaca.id, 50).await.unwrap().stock_count,
+        Some(150)
+    );
+    assert_not_found(repo.restock(GearId::new(9999), 10).await);
+
+    assert_eq!(repo.get(v60.id).await.unwrap().model, "V60");
+    assert_not_found(repo.get(GearId::new(9999)).await);
+
+    let show_all = |key, direction| ListRequest::show_all(key, direction);
+    let grinders = repo
+        .list(
+            GearFilter::for_category(GearCategory::Grinder),
+            &show_all(GearSortKey::Make, SortDirection::Asc),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        names(grinders.items.iter().map(|g| g.make.as_str())),
+        ["baratza", "Comandante"]
+    );
+
+    let by_make = repo
+        .list(
+            GearFilter::all(),
+            &show_all(GearSortKey::Make, SortDirection::Asc),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        names(by_make.items.iter().map(|g| g.make.as_str())),
+        ["baratza", "Cafec", "Comandante", "Hario"]
+    );
+    let newest = repo.list_all().await.unwrap();
+    assert_eq!(
+        newest.iter().map(|g| g.created_at).collect::<Vec<_>>(),
+        [at(4), at(3), at(2), at(1)]
+    );
+
+    let paged = ListRequest::new(
+        2,
+        PageSize::limited(3),
+        GearSortKey::Make,
+        SortDirection::Asc,
+    );
+    let second = repo.list(GearFilter::all(), &paged, None).await.unwrap();
+    assert_eq!(second.total, 4);
+    assert_eq!(
+        names(second.items.iter().map(|g| g.make.as_str())),
+        ["Hario"]
+    );
+    let found = repo
+        .list(GearFilter::all(), &paged.with_page(1), Some("v60"))
+        .await
+        .unwrap();
+    assert_eq!(found.total, 1);
+    assert_eq!(found.items[0].id, v60.id);
+
+    let renamed = repo
+        .update(
+            v60.id,
+            UpdateGear {
+                make: None,
+                model: Some("V60 Switch".to_string()),
+                water_temp: Some(93.0),
+                brew_method: None,
+                uses_filter: None,
+                temp_offset: None,
+                water_hardness: None,
+                stock_count: None,
+                low_stock_threshold: None,
+                created_at: None,
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(renamed.make, "Hario");
+    assert_eq!(renamed.model, "V60 Switch");
+    assert_eq!(renamed.water_temp, Some(93.0));
+    assert_eq!(repo.get(v60.id).await.unwrap().water_temp, Some(93.0));
+    assert_not_found(
+        repo.update(
+            GearId::new(9999),
+            UpdateGear {
+                make: Some("Nobody".to_string()),
+                model: None,
+                water_temp: None,
+                brew_method: None,
+                uses_filter: None,
+                temp_offset: None,
+                water_hardness: None,
+                stock_count: None,
+                low_stock_threshold: None,
+                created_at: None,
+            },
+        )
+        .await,
+    );
+
+    repo.delete(comandante.id).await.unwrap();
+    assert_not_found(repo.get(comandante.id).await);
+    assert_not_found(repo.delete(comandante.id).await);
+    assert_eq!(repo.list_all().await.unwrap().len(), 3);
+}
+
+pub(crate) async fn roast_repository(
+    roasters: &dyn RoasterRepository,
+    roasts: &dyn RoastRepository,
+) {
+    let tim = roasters
+        .insert(new_roaster("Tim Wendelboe", "Norway", "Oslo", 1))
+        .await
+        .unwrap();
+    let april = roasters
+        .insert(new_roaster("April", "Denmark", "Copenhagen", 1))
+        .await
+        .unwrap();
+
+    let ruiru = roasts
+        .insert(NewRoast {
+            roaster_id: tim.id,
+            name: "Ruiru".to_string(),
+            origin: "Kenya".to_string(),
+            region: "Kiambu".to_string(),
+            producer: "Ruiru Estate".to_string(),
+            tasting_notes: vec!["Blackcurrant".to_string(), "Grapefruit".to_string()],
+            process: "Washed".to_string(),
+            components: Vec::new(),
+            harvest_year: None,
+            created_at: Some(at(2)),
+        })
+        .await
+        .unwrap();
+    assert_eq!(ruiru.roaster_id, tim.id);
+    assert_eq!(ruiru.slug, "ruiru");
+    assert_eq!(ruiru.origin.as_deref(), Some("Kenya"));
+    assert_eq!(ruiru.region.as_deref(), Some("Kiambu"));
+    assert_eq!(ruiru.producer.as_deref(), Some("Ruiru Estate"));
+    assert_eq!(ruiru.tasting_notes, ["Blackcurrant", "Grapefruit"]);
+    assert_eq!(ruiru.process.as_deref(), Some("Washed"));
+    assert_eq!(ruiru.created_at, at(2));
+
+    // Blank optional fields are stored as absent.
+    let bare = roasts
+        .insert(new_roast(april.id, "Bare Bones", "", 3))
+        .await
+        .unwrap();
+    assert_eq!(bare.origin, None);
+    assert_eq!(bare.region, None);
+    assert!(bare.tasting_notes.is_empty());
+
+    // Names are unique per roaster, not globally.
+    assert_conflict(roasts.insert(new_roast(tim.id, "Ruiru", "Kenya", 4)).await);
+    let april_ruiru = roasts
+        .insert(new_roast(april.id, "Ruiru", "Kenya", 4))
+        .await
+        .unwrap();
+    assert!(
+        roasts
+            .insert(new_roast(RoasterId::new(9999), "Orphan", "", 5))
+            .await
+            .is_err()
+    );
+
+    assert_eq!(
+        roasts.get_by_slug(tim.id, "ruiru").await.unwrap().id,
+        ruiru.id
+    );
+    assert_eq!(
+        roasts.get_by_slug(april.id, "ruiru").await.unwrap().id,
+        april_ruiru.id
+    );
+    assert_not_found(roasts.get_by_slug(tim.id, "bare-bones").await);
+    assert_not_found(roasts.get(RoastId::new(9999)).await);
+    let with_roaster = roasts.get_with_roaster(ruiru.id).await.unwrap();
+    assert_eq!(with_roaster.roast.id, ruiru.id);
+    assert_eq!(with_roaster.roaster_name, "Tim Wendelboe");
+    assert_eq!(with_roaster.roaster_slug, tim.slug);
+    assert_not_found(roasts.get_with_roaster(RoastId::new(9999)).await);
+
+    let newest = roasts.list_all().await.unwrap();
+    assert_eq!(
+        newest
+            .iter()
+            .map(|r| r.roast.created_at)
+            .collect::<Vec<_>>(),
+        [at(4), at(3), at(2)]
+    );
+    let by_roaster = roasts
+        .list(
+            &ListRequest::show_all(RoastSortKey::Roaster, SortDirection::Asc),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        names(by_roaster.items.iter().map(|r| r.roaster_name.as_str())),
+        ["April", "April", "Tim Wendelboe"]
+    );
+
+    let request = ListRequest::<RoastSortKey>::new(
+        1,
+        PageSize::limited(2),
+        RoastSortKey::Name,
+        SortDirection::Asc,
+    );
+    let page = roasts.list(&request, None).await.unwrap();
+    assert_eq!(page.total, 3);
+    assert_eq!(page.items.len(), 2);
+    assert_eq!(page.items[0].roast.name, "Bare Bones");
+    let kenyan = roasts.list(&request, Some("kenya")).await.unwrap();
+    assert_eq!(kenyan.total, 2);
+    let by_roaster_name = roasts.list(&request, Some("wendelboe")).await.unwrap();
+    assert_eq!(by_roaster_name.total, 1);
+    let by_note = roasts.list(&request, Some("blackcurrant")).await.unwrap();
+    assert_eq!(by_note.total, 1);
+
+    let april_roasts = roasts.list_by_roaster(april.id).await.unwrap();
+    assert_eq!(
+        names(april_roasts.iter().map(|r| r.roast.name.as_str())),
+        ["Ruiru", "Bare Bones"]
+    );
+
+    let updated = roasts
+        .update(
+            bare.id,
+            UpdateRoast {
+                origin: Some("Ethiopia".to_string()),
+                tasting_notes: Some(vec!["Jasmine".to_string()]),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(updated.name, "Bare Bones");
+    assert_eq!(updated.origin.as_deref(), Some("Ethiopia"));
+    assert_eq!(updated.tasting_notes, ["Jasmine"]);
+
+    // A blend's components round-trip, and an empty list clears them.
+    let blend = vec![
+        BlendComponent::new("Brazil", Some(70.0)),
+        BlendComponent::new("Ethiopia", None),
+    ];
+    let blended = roasts
+        .update(
+            bare.id,
+            UpdateRoast {
+                components: Some(blend.clone()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(blended.components, blend);
+    assert_eq!(roasts.get(bare.id).await.unwrap().components, blend);
+    let single = roasts
+        .update(
+            bare.id,
+            UpdateRoast {
+                components: Some(Vec::new()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    assert!(single.components.is_empty());
+    assert_not_found(
+        roasts
+            .update(
+                RoastId::new(9999),
+                UpdateRoast {
+                    origin: Some("Ethiopia".to_string()),
+                    ..Default::default()
+                },
+            )
+            .await,
+    );
+
+    let preview = roasters.delete_preview(april.id).await.unwrap();
+    assert_eq!(preview.roasts, 2);
+
+    // Deleting a roaster takes its roasts with it.
+    roasters.delete(april.id).await.unwrap();
+    assert_not_found(roasts.get(bare.id).await);
+    assert_not_found(roasts.get(april_ruiru.id).await);
+    assert_eq!(roasts.list_all().await.unwrap().len(), 1);
+
+    roasts.delete(ruiru.id).await.unwrap();
+    assert_not_found(roasts.get(ruiru.id).await);
+    assert_not_found(roasts.delete(ruiru.id).await);
+}
+
+pub(crate) async fn bag_repository(
+    roasters: &dyn RoasterRepository,
+    roasts: &dyn RoastRepository,
+    bags: &dyn BagRepository,
+) {
+    let roaster: Roaster = roasters
+        .insert(new_roaster("Tim Wendelboe", "Norway", "Oslo", 1))
+        .await
+        .unwrap();
+    let ruiru: Roast = roasts
+        .insert(new_roast(roaster.id, "Ruiru", "Kenya", 1))
+        .await
+        .unwrap();
+    let nano: Roast = roasts
+        .insert(new_roast(roaster.id, "Nano Challa", "Ethiopia", 1))
+        .await
+        .unwrap();
+
+    let first = bags.insert(new_bag(ruiru.id, 250.0, 2)).await.unwrap();
+    assert_eq!(first.roast_id, ruiru.id);
+    assert!((first.amount - 250.0).abs() < f64::EPSILON);
+    assert!((first.remaining - 250.0).abs() < f64::EPSILON);
+    assert!(!first.closed);
+    assert_eq!(first.finished_at, None);
+    assert_eq!(first.created_at, at(2));
+    assert_eq!(first.roast_date, NaiveDate::from_ymd_opt(2024, 12, 20));
+    assert!(
+        bags.insert(new_bag(RoastId::new(9999), 250.0, 2))
+            .await
+            .is_err()
+    );
+
+    let with_roast = bags.get_with_roast(first.id).await.unwrap();
+    assert_eq!(with_roast.roast_name, "Ruiru");
+    assert_eq!(with_roast.roaster_name, "Tim Wendelboe");
+    assert_eq!(with_roast.roast_slug, ruiru.slug);
+    assert_eq!(with_roast.roaster_slug, roaster.slug);
+    assert_not_found(bags.get(BagId::new(9999)).await);
+    assert_not_found(bags.get_with_roast(BagId::new(9999)).await);
+
+    let second = bags.insert(new_bag(nano.id, 250.0, 3)).await.unwrap();
+    let third = bags.insert(new_bag(nano.id, 1000.0, 4)).await.unwrap();
+
+    let closed = bags
+        .update(
+            third.id,
+            UpdateBag {
+                closed: Some(true),
+                finished_at: Some(at(10)),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    assert!(closed.closed);
+    assert_eq!(closed.finished_at, Some(at(10)));
+    assert_not_found(
+        bags.update(
+            BagId::new(9999),
+            UpdateBag {
+                closed: Some(true),
+                ..Default::default()
+            },
+        )
+        .await,
+    );
+
+    let show_all = ListRequest::show_all(BagSortKey::CreatedAt, SortDirection::Desc);
+    let open = bags.list(BagFilter::open(), &show_all, None).await.unwrap();
+    assert_eq!(
+        open.items.iter().map(|b| b.bag.id).collect::<Vec<_>>(),
+        [second.id, first.id]
+    );
+    let finished = bags
+        .list(BagFilter::closed(), &show_all, None)
+        .await
+        .unwrap();
+    assert_eq!(
+        finished.items.iter().map(|b| b.bag.id).collect::<Vec<_>>(),
+        [third.id]
+    );
+    let of_nano = bags
+        .list(BagFilter::for_roast(nano.id), &show_all, None)
+        .await
+        .unwrap();
+    assert_eq!(of_nano.total, 2);
+    let priced = bags
+        .update(
+            second.id,
+            UpdateBag {
+                price: Some(14.5),
+                currency: Some("EUR".to_string()),
+                best_before: NaiveDate::from_ymd_opt(2025, 3, 20),
+                batch_code: Some("B-0412".to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(priced.price, Some(14.5));
+    assert_eq!(priced.currency.as_deref(), Some("EUR"));
+    assert_eq!(priced.best_before, NaiveDate::from_ymd_opt(2025, 3, 20));
+    assert_eq!(priced.batch_code.as_deref(), Some("B-0412"));
+    let purchases = bags.list_purchases(nano.id).await.unwrap();
+    assert_eq!(
+        purchases.iter().map(|b| b.id).collect::<Vec<_>>(),
+        [second.id, third.id]
+    );
+    assert_eq!(purchases[0].price, Some(14.5));
+    assert!(
+        bags.list_purchases(RoastId::new(9999))
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    let searched = bags
+        .list(BagFilter::all(), &show_all, Some("ruiru"))
+        .await
+        .unwrap();
+    assert_eq!(searched.total, 1);
+
+    let paged = ListRequest::new(
+        2,
+        PageSize::limited(2),
+        BagSortKey::CreatedAt,
+        SortDirection::Asc,
+    );
+    let page = bags.list(BagFilter::all(), &paged, None).await.unwrap();
+    assert_eq!(page.total, 3);
+    assert_eq!(
+        page.items.iter().map(|b| b.bag.id).collect::<Vec<_>>(),
+        [third.id]
+    );
+
+    let reopened = bags.reopen(third.id).await.unwrap();
+    assert!(!reopened.closed);
+    assert_eq!(reopened.finished_at, None);
+    assert_not_found(bags.reopen(BagId::new(9999)).await);
+
+    // The ledger keeps `remaining` in step with every correction.
+    let adjustment = bags
+        .adjust(NewBagAdjustment {
+            bag_id: first.id,
+            remaining: 200.0,
+            reason: "Spilled some".to_string(),
+            created_by: None,
+        })
+        .await
+        .unwrap();
+    assert_eq!(adjustment.kind, BagLedgerKind::Adjustment);
+    assert!((adjustment.delta + 50.0).abs() < f64::EPSILON);
+    assert!((adjustment.balance - 200.0).abs() < f64::EPSILON);
+    assert_eq!(adjustment.reason.as_deref(), Some("Spilled some"));
+    assert!((bags.get(first.id).await.unwrap().remaining - 200.0).abs() < f64::EPSILON);
+    assert_not_found(
+        bags.adjust(NewBagAdjustment {
+            bag_id: BagId::new(9999),
+            remaining: 10.0,
+            reason: "Nothing".to_string(),
+            created_by: None,
+        })
+        .await,
+    );
+
+    let transfer = |from: BagId, to: BagId, amount| {
+        bags.transfer(NewBagTransfer {
+            from_bag_id: from,
+            to_bag_id: to,
+            amount,
+            created_by: None,
+        })
+    };
+    // `second` is still full, and `first` now has 50g of room.
+    assert_conflict(transfer(first.id, second.id, 10.0).await);
+    assert_conflict(transfer(second.id, first.id, 300.0).await);
+    assert_conflict(transfer(second.id, first.id, 60.0).await);
+    let moved = transfer(second.id, first.id, 30.0).await.unwrap();
+    assert_eq!(moved.from.kind, BagLedgerKind::Transfer);
+    assert!((moved.from.delta + 30.0).abs() < f64::EPSILON);
+    assert!((moved.from.balance - 220.0).abs() < f64::EPSILON);
+    assert_eq!(moved.from.counterpart_bag_id, Some(first.id));
+    assert!((moved.to.delta - 30.0).abs() < f64::EPSILON);
+    assert!((moved.to.balance - 230.0).abs() < f64::EPSILON);
+    assert_eq!(moved.to.counterpart_bag_id, Some(second.id));
+    assert!((bags.get(first.id).await.unwrap().remaining - 230.0).abs() < f64::EPSILON);
+    assert!((bags.get(second.id).await.unwrap().remaining - 220.0).abs() < f64::EPSILON);
+
+    let ledger = bags.list_ledger(first.id).await.unwrap();
+    assert_eq!(
+        ledger.iter().map(|e| e.kind).collect::<Vec<_>>(),
+        [
+            BagLedgerKind::Transfer,
+            BagLedgerKind::Adjustment,
+            BagLedgerKind::Initial
+        ]
+    );
+    assert!((ledger.iter().map(|e| e.delta).sum::<f64>() - 230.0).abs() < f64::EPSILON);
+
+    bags.delete(third.id).await.unwrap();
+    assert_not_found(bags.get(third.id).await);
+    assert_not_found(bags.delete(third.id).await);
+    assert_eq!(bags.list_all().await.unwrap().len(), 2);
+}
+
+mod sqlite {
+    use crate::infrastructure::database::Database;
+    use crate::infrastructure::repositories::bags::SqlBagRepository;
+    use crate::infrastructure::repositories::gear::SqlGearRepository;
+    use crate::infrastructure::repositories::roasters::SqlRoasterRepository;
+    use crate::infrastructure::repositories::roasts::SqlRoastRepository;
+
+    #[tokio::test]
+    async fn roasters_conform() {
+        let db = Database::in_memory().await.unwrap();
+        super::roaster_repository(&SqlRoasterRepository::new(db.clone_pool())).await;
+    }
+
+    #[tokio::test]
+    async fn gear_conforms() {
+        let db = Database::in_memory().await.unwrap();
+        super::gear_repository(&SqlGearRepository::new(db.clone_pool())).await;
+    }
+
+    #[tokio::test]
+    async fn roasts_conform() {
+        let db = Database::in_memory().await.unwrap();
+        super::roast_repository(
+            &SqlRoasterRepository::new(db.clone_pool()),
+            &SqlRoastRepository::new(db.clone_pool()),
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn bags_conform() {
+        let db = Database::in_memory().await.unwrap();
+        super::bag_repository(
+            &SqlRoasterRepository::new(db.clone_pool()),
+            &SqlRoastRepository::new(db.clone_pool()),
+            &SqlBagRepository::new(db.clone_pool()),
+        )
+        .await;
+    }
+}
@@ -1,3 +1,5 @@
+use std::collections::HashSet;
+
 use async_trait::async_trait;
 use sqlx::{query, query_as};
 
@@ -71,7 +73,8 @@ impl ImageRepository for SqlImageRepository {
                DO UPDATE SET content_type = excluded.content_type,
                              image_data = excluded.image_data,
                              thumbnail_data = excluded.thumbnail_data,
-                             created_at = strftime('%Y-%m-%dT%H:%M:%SZ', 'now')",
+                             created_at = strftime('%Y-%m-%dT%H:%M:%SZ', 'now'),
+                             deleted_at = NULL",
         )
         .bind(image.entity_type.as_str())
         .bind(image.entity_id)
@@ -93,7 +96,7 @@ impl ImageRepository for SqlImageRepository {
         let record = query_as::<_, ImageRecord>(
             r"SELECT entity_type, entity_id, content_type, image_data, thumbnail_data
                FROM entity_images
-               WHERE entity_type = ? AND entity_id = ?",
+               WHERE entity_type = ? AND entity_id = ? AND deleted_at IS NULL",
         )
         .bind(entity_type.as_str())
         .bind(entity_id)
@@ -113,7 +116,7 @@ impl ImageRepository for SqlImageRepository {
         let record = query_as::<_, ThumbnailRecord>(
             r"SELECT entity_type, entity_id, content_type, thumbnail_data
                FROM entity_images
-               WHERE entity_type = ? AND entity_id = ?",
+               WHERE entity_type = ? AND entity_id = ? AND deleted_at IS NULL",
         )
         .bind(entity_type.as_str())
         .bind(entity_id)
@@ -125,13 +128,18 @@ impl ImageRepository for SqlImageRepository {
         Self::thumbnail_to_domain(record)
     }
 
+    /// Marks the image deleted. The row itself is removed later by the
+    /// image purge task.
     async fn delete(&self, entity_type: EntityType, entity_id: i64) -> Result<(), RepositoryError> {
-        query(r"DELETE FROM entity_images WHERE entity_type = ? AND entity_id = ?")
-            .bind(entity_type.as_str())
-            .bind(entity_id)
-            .execute(&self.pool)
-            .await
-            .map_err(|e| RepositoryError::unexpected(e.to_string()))?;
+        query(
+            r"UPDATE entity_images SET deleted_at = strftime('%Y-%m-%dT%H:%M:%SZ', 'now')
+               WHERE entity_type = ? AND entity_id = ? AND deleted_at IS NULL",
+        )
+        .bind(entity_type.as_str())
+        .bind(entity_id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::unexpected(e.to_string()))?;
 
         Ok(())
     }
@@ -142,7 +150,7 @@ impl ImageRepository for SqlImageRepository {
         entity_id: i64,
     ) -> Result<bool, RepositoryError> {
         let row: (i64,) =
-            query_as(r"SELECT COUNT(*) FROM entity_images WHERE entity_type = ? AND entity_id = ?")
+            query_as(r"SELECT COUNT(*) FROM entity_images WHERE entity_type = ? AND entity_id = ? AND deleted_at IS NULL")
                 .bind(entity_type.as_str())
                 .bind(entity_id)
                 .fetch_one(&self.pool)
@@ -151,4 +159,28 @@ impl ImageRepository for SqlImageRepository {
 
         Ok(row.0 > 0)
     }
+
+    async fn ids_with_images(
+        &self,
+        entity_type: EntityType,
+        entity_ids: &[i64],
+    ) -> Result<HashSet<i64>, RepositoryError> {
+        if entity_ids.is_empty() {
+            return Ok(HashSet::new());
+        }
+        let ids = serde_json::to_string(entity_ids)
+            .map_err(|e| RepositoryError::unexpected(e.to_string()))?;
+        let rows: Vec<(i64,)> = query_as(
+            r"SELECT entity_id FROM entity_images
+               WHERE entity_type = ? AND entity_id IN (SELECT value FROM json_each(?))
+                 AND deleted_at IS NULL",
+        )
+        .bind(entity_type.as_str())
+        .bind(ids)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::unexpected(e.to_string()))?;
+
+        Ok(rows.into_iter().map(|(id,)| id).collect())
+    }
 }
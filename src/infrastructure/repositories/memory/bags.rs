@@ -0,0 +1,377 @@
+use std::cmp::Ordering;
+use std::collections::HashSet;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use super::{MemoryStore, Tables, cmp_lower, directed, matches, next_id, search_term};
+use crate::domain::bag_ledger::{
+    BagLedgerEntry, BagLedgerKind, BagTransfer, NewBagAdjustment, NewBagTransfer,
+};
+use crate::domain::bags::{Bag, BagFilter, BagSortKey, BagWithRoast, NewBag, UpdateBag};
+use crate::domain::ids::{BagId, BagLedgerEntryId, RoastId, UserId};
+use crate::domain::listing::{ListRequest, Page};
+use crate::domain::repositories::BagRepository;
+use crate::domain::{ErrorCode, RepositoryError};
+
+#[derive(Clone)]
+pub struct MemoryBagRepository {
+    store: MemoryStore,
+}
+
+impl MemoryBagRepository {
+    pub fn new(store: MemoryStore) -> Self {
+        Self { store }
+    }
+}
+
+/// A ledger row to append; [`Tables::append_ledger`] fills in the id and
+/// running balance.
+struct LedgerRow {
+    bag_id: BagId,
+    kind: BagLedgerKind,
+    delta: f64,
+    counterpart_bag_id: Option<BagId>,
+    reason: Option<String>,
+    created_by: Option<UserId>,
+    created_at: DateTime<Utc>,
+}
+
+impl Tables {
+    /// Append to a bag's ledger and carry the new balance onto the bag, as the
+    /// `bag_ledger_balance` trigger does.
+    fn append_ledger(&mut self, row: LedgerRow) -> BagLedgerEntry {
+        let id = next_id(&mut self.last_ledger_id);
+        let mut balance = row.delta;
+        if let Some(bag) = self.bags.get_mut(&row.bag_id.into_inner()) {
+            balance += bag.remaining;
+            bag.remaining = balance;
+            if row.kind != BagLedgerKind::Initial {
+                bag.updated_at = Utc::now();
+            }
+        }
+
+        let entry = BagLedgerEntry {
+            id: BagLedgerEntryId::new(id),
+            bag_id: row.bag_id,
+            kind: row.kind,
+            delta: row.delta,
+            balance,
+            brew_id: None,
+            counterpart_bag_id: row.counterpart_bag_id,
+            reason: row.reason,
+            created_by: row.created_by,
+            created_at: row.created_at,
+        };
+        self.ledger.push(entry.clone());
+        entry
+    }
+}
+
+fn with_roast(tables: &Tables, bag: &Bag) -> Option<BagWithRoast> {
+    let roast = tables.roasts.get(&bag.roast_id.into_inner())?;
+    let roaster = tables.roasters.get(&roast.roaster_id.into_inner())?;
+    Some(BagWithRoast {
+        bag: bag.clone(),
+        roast_name: roast.name.clone(),
+        roaster_name: roaster.name.clone(),
+        roast_slug: roast.slug.clone(),
+        roaster_slug: roaster.slug.clone(),
+    })
+}
+
+/// Closed bags sort after every open one when ordering by status.
+fn status_rank(bag: &Bag) -> f64 {
+    if bag.closed { 10_000.0 } else { bag.remaining }
+}
+
+fn compare(a: &BagWithRoast, b: &BagWithRoast, request: &ListRequest<BagSortKey>) -> Ordering {
+    let direction = request.sort_direction();
+    let newest_first = || b.bag.created_at.cmp(&a.bag.created_at);
+    let highest_id_first = || b.bag.id.into_inner().cmp(&a.bag.id.into_inner());
+    match request.sort_key() {
+        BagSortKey::RoastDate => {
+            directed(a.bag.roast_date.cmp(&b.bag.roast_date), direction).then_with(newest_first)
+        }
+        BagSortKey::CreatedAt => {
+            directed(a.bag.created_at.cmp(&b.bag.created_at), direction).then_with(highest_id_first)
+        }
+        BagSortKey::UpdatedAt => {
+            directed(a.bag.updated_at.cmp(&b.bag.updated_at), direction).then_with(highest_id_first)
+        }
+        BagSortKey::Roaster => {
+            directed(cmp_lower(&a.roaster_name, &b.roaster_name), direction).then_with(newest_first)
+        }
+        BagSortKey::Roast => {
+            directed(cmp_lower(&a.roast_name, &b.roast_name), direction).then_with(newest_first)
+        }
+        BagSortKey::Status => directed(
+            status_rank(&a.bag).total_cmp(&status_rank(&b.bag)),
+            direction,
+        )
+        .then_with(newest_first),
+        BagSortKey::FinishedAt => {
+            directed(a.bag.finished_at.cmp(&b.bag.finished_at), direction).then_with(newest_first)
+        }
+    }
+}
+
+#[async_trait]
+impl BagRepository for MemoryBagRepository {
+    async fn insert(&self, bag: NewBag) -> Result<Bag, RepositoryError> {
+        let mut tables = self.store.lock();
+        if !tables.roasts.contains_key(&bag.roast_id.into_inner()) {
+            return Err(RepositoryError::unexpected("unknown roast reference"));
+        }
+
+        let id = next_id(&mut tables.last_bag_id);
+        let created_at = bag.created_at.unwrap_or_else(Utc::now);
+        tables.bags.insert(
+            id,
+            Bag {
+                id: BagId::new(id),
+                roast_id: bag.roast_id,
+                roast_date: bag.roast_date,
+                amount: bag.amount,
+                remaining: 0.0,
+                closed: false,
+                finished_at: None,
+                created_at,
+                updated_at: created_at,
+                created_by: None,
+                low_stock_threshold: None,
+                price: bag.price,
+                currency: bag.currency,
+                best_before: bag.best_before,
+                batch_code: bag.batch_code,
+            },
+        );
+        tables.append_ledger(LedgerRow {
+            bag_id: BagId::new(id),
+            kind: BagLedgerKind::Initial,
+            delta: bag.amount,
+            counterpart_bag_id: None,
+            reason: None,
+            created_by: None,
+            created_at,
+        });
+
+        Ok(tables.bags[&id].clone())
+    }
+
+    async fn get(&self, id: BagId) -> Result<Bag, RepositoryError> {
+        self.store
+            .lock()
+            .bags
+            .get(&id.into_inner())
+            .cloned()
+            .ok_or(RepositoryError::NotFound)
+    }
+
+    async fn get_with_roast(&self, id: BagId) -> Result<BagWithRoast, RepositoryError> {
+        let tables = self.store.lock();
+        tables
+            .bags
+            .get(&id.into_inner())
+            .and_then(|bag| with_roast(&tables, bag))
+            .ok_or(RepositoryError::NotFound)
+    }
+
+    async fn list(
+        &self,
+        filter: BagFilter,
+        request: &ListRequest<BagSortKey>,
+        search: Option<&str>,
+    ) -> Result<Page<BagWithRoast>, RepositoryError> {
+        let term = search_term(search);
+        let tables = self.store.lock();
+        let mut items: Vec<BagWithRoast> = tables
+            .bags
+            .values()
+            .filter(|bag| filter.closed.is_none_or(|closed| bag.closed == closed))
+            .filter(|bag| {
+                filter
+                    .roast_id
+                    .is_none_or(|roast_id| bag.roast_id == roast_id)
+            })
+            .filter_map(|bag| with_roast(&tables, bag))
+            .filter(|b| matches(term.as_deref(), &[&b.roaster_name, &b.roast_name]))
+            .collect();
+        items.sort_by(|a, b| compare(a, b, request));
+        Ok(Page::from_sorted(items, request))
+    }
+
+    /// `remaining` is ignored: it only changes through the ledger.
+    async fn update(&self, id: BagId, changes: UpdateBag) -> Result<Bag, RepositoryError> {
+        let mut tables = self.store.lock();
+        if let Some(roast_id) = changes.roast_id
+            && !tables.roasts.contains_key(&roast_id.into_inner())
+        {
+            return Err(RepositoryError::unexpected("unknown roast reference"));
+        }
+        let bag = tables
+            .bags
+            .get_mut(&id.into_inner())
+            .ok_or(RepositoryError::NotFound)?;
+        if let Some(roast_id) = changes.roast_id {
+            bag.roast_id = roast_id;
+        }
+        if let Some(roast_date) = changes.roast_date {
+            bag.roast_date = Some(roast_date);
+        }
+        if let Some(amount) = changes.amount {
+            bag.amount = amount;
+        }
+        if let Some(closed) = changes.closed {
+            bag.closed = closed;
+        }
+        if let Some(finished_at) = changes.finished_at {
+            bag.finished_at = Some(finished_at);
+        }
+        if let Some(created_at) = changes.created_at {
+            bag.created_at = created_at;
+        }
+        if let Some(threshold) = changes.low_stock_threshold {
+            bag.low_stock_threshold = Some(threshold);
+        }
+        if let Some(price) = changes.price {
+            bag.price = Some(price);
+        }
+        if let Some(currency) = changes.currency {
+            bag.currency = Some(currency);
+        }
+        if let Some(best_before) = changes.best_before {
+            bag.best_before = Some(best_before);
+        }
+        if let Some(batch_code) = changes.batch_code {
+            bag.batch_code = Some(batch_code);
+        }
+        bag.updated_at = Utc::now();
+        Ok(bag.clone())
+    }
+
+    async fn set_created_by(&self, id: BagId, user_id: UserId) -> Result<(), RepositoryError> {
+        let mut tables = self.store.lock();
+        let bag = tables
+            .bags
+            .get_mut(&id.into_inner())
+            .ok_or(RepositoryError::NotFound)?;
+        bag.created_by = Some(user_id);
+        Ok(())
+    }
+
+    async fn delete(&self, id: BagId) -> Result<(), RepositoryError> {
+        let mut tables = self.store.lock();
+        if !tables.bags.contains_key(&id.into_inner()) {
+            return Err(RepositoryError::NotFound);
+        }
+        tables.delete_bags(&HashSet::from([id.into_inner()]));
+        Ok(())
+    }
+
+    async fn reopen(&self, id: BagId) -> Result<Bag, RepositoryError> {
+        let mut tables = self.store.lock();
+        let bag = tables
+            .bags
+            .get_mut(&id.into_inner())
+            .ok_or(RepositoryError::NotFound)?;
+        bag.closed = false;
+        bag.finished_at = None;
+        bag.updated_at = Utc::now();
+        Ok(bag.clone())
+    }
+
+    async fn adjust(
+        &self,
+        adjustment: NewBagAdjustment,
+    ) -> Result<BagLedgerEntry, RepositoryError> {
+        let mut tables = self.store.lock();
+        let remaining = tables
+            .bags
+            .get(&adjustment.bag_id.into_inner())
+            .ok_or(RepositoryError::NotFound)?
+            .remaining;
+
+        Ok(tables.append_ledger(LedgerRow {
+            bag_id: adjustment.bag_id,
+            kind: BagLedgerKind::Adjustment,
+            delta: adjustment.remaining - remaining,
+            counterpart_bag_id: None,
+            reason: Some(adjustment.reason),
+            created_by: adjustment.created_by,
+            created_at: Utc::now(),
+        }))
+    }
+
+    async fn transfer(&self, transfer: NewBagTransfer) -> Result<BagTransfer, RepositoryError> {
+        // Both checks and both entries happen under one lock, so nothing can
+        // land in between.
+        let mut tables = self.store.lock();
+        let from_ok = tables
+            .bags
+            .get(&transfer.from_bag_id.into_inner())
+            .is_some_and(|bag| !bag.closed && bag.remaining >= transfer.amount);
+        if !from_ok {
+            return Err(RepositoryError::conflict_with(
+                ErrorCode::BagInsufficientRemaining,
+                "not enough coffee left to transfer",
+            ));
+        }
+        let to_ok = tables
+            .bags
+            .get(&transfer.to_bag_id.into_inner())
+            .is_some_and(|bag| !bag.closed && bag.remaining + transfer.amount <= bag.amount);
+        if !to_ok {
+            return Err(RepositoryError::conflict_with(
+                ErrorCode::BagNoRoom,
+                "the receiving bag has no room for the coffee",
+            ));
+        }
+
+        let now = Utc::now();
+        let from = tables.append_ledger(LedgerRow {
+            bag_id: transfer.from_bag_id,
+            kind: BagLedgerKind::Transfer,
+            delta: -transfer.amount,
+            counterpart_bag_id: Some(transfer.to_bag_id),
+            reason: None,
+            created_by: transfer.created_by,
+            created_at: now,
+        });
+        let to = tables.append_ledger(LedgerRow {
+            bag_id: transfer.to_bag_id,
+            kind: BagLedgerKind::Transfer,
+            delta: transfer.amount,
+            counterpart_bag_id: Some(transfer.from_bag_id),
+            reason: None,
+            created_by: transfer.created_by,
+            created_at: now,
+        });
+        Ok(BagTransfer { from, to })
+    }
+
+    async fn list_ledger(&self, id: BagId) -> Result<Vec<BagLedgerEntry>, RepositoryError> {
+        Ok(self
+            .store
+            .lock()
+            .ledger
+            .iter()
+            .rev()
+            .filter(|entry| entry.bag_id == id)
+            .cloned()
+            .collect())
+    }
+
+    async fn list_purchases(&self, roast_id: RoastId) -> Result<Vec<Bag>, RepositoryError> {
+        let mut bags: Vec<Bag> = self
+            .store
+            .lock()
+            .bags
+            .values()
+            .filter(|bag| bag.roast_id == roast_id)
+            .cloned()
+            .collect();
+        bags.sort_by_key(|bag| (bag.created_at, bag.id.into_inner()));
+        Ok(bags)
+    }
+}
@@ -0,0 +1,169 @@
+use std::cmp::Ordering;
+
+use async_trait::async_trait;
+use chrono::Utc;
+
+use super::{MemoryStore, cmp_lower, directed, matches, next_id, search_term};
+use crate::domain::RepositoryError;
+use crate::domain::gear::{Gear, GearFilter, GearSortKey, NewGear, UpdateGear};
+use crate::domain::ids::{GearId, UserId};
+use crate::domain::listing::{ListRequest, Page};
+use crate::domain::repositories::GearRepository;
+
+#[derive(Clone)]
+pub struct MemoryGearRepository {
+    store: MemoryStore,
+}
+
+impl MemoryGearRepository {
+    pub fn new(store: MemoryStore) -> Self {
+        Self { store }
+    }
+}
+
+fn compare(a: &Gear, b: &Gear, request: &ListRequest<GearSortKey>) -> Ordering {
+    let direction = request.sort_direction();
+    let newest_first = || b.created_at.cmp(&a.created_at);
+    match request.sort_key() {
+        GearSortKey::Make => {
+            directed(cmp_lower(&a.make, &b.make), direction).then_with(newest_first)
+        }
+        GearSortKey::Model => {
+            directed(cmp_lower(&a.model, &b.model), direction).then_with(newest_first)
+        }
+        GearSortKey::Category => directed(a.category.as_str().cmp(b.category.as_str()), direction)
+            .then_with(|| cmp_lower(&a.make, &b.make)),
+        GearSortKey::CreatedAt => directed(a.created_at.cmp(&b.created_at), direction)
+            .then_with(|| b.id.into_inner().cmp(&a.id.into_inner())),
+    }
+}
+
+#[async_trait]
+impl GearRepository for MemoryGearRepository {
+    async fn insert(&self, gear: NewGear) -> Result<Gear, RepositoryError> {
+        let mut tables = self.store.lock();
+        let id = next_id(&mut tables.last_gear_id);
+        let created_at = gear.created_at.unwrap_or_else(Utc::now);
+        let gear = Gear {
+            id: GearId::new(id),
+            category: gear.category,
+            make: gear.make,
+            model: gear.model,
+            water_temp: gear.water_temp,
+            brew_method: gear.brew_method,
+            uses_filter: gear.uses_filter,
+            temp_offset: gear.temp_offset,
+            water_hardness: gear.water_hardness,
+            stock_count: gear.stock_count,
+            low_stock_threshold: gear.low_stock_threshold,
+            created_at,
+            updated_at: created_at,
+            created_by: None,
+        };
+        tables.gear.insert(id, gear.clone());
+        Ok(gear)
+    }
+
+    async fn get(&self, id: GearId) -> Result<Gear, RepositoryError> {
+        self.store
+            .lock()
+            .gear
+            .get(&id.into_inner())
+            .cloned()
+            .ok_or(RepositoryError::NotFound)
+    }
+
+    async fn list(
+        &self,
+        filter: GearFilter,
+        request: &ListRequest<GearSortKey>,
+        search: Option<&str>,
+    ) -> Result<Page<Gear>, RepositoryError> {
+        let term = search_term(search);
+        let mut items: Vec<Gear> = self
+            .store
+            .lock()
+            .gear
+            .values()
+            .filter(|g| {
+                filter
+                    .category
+                    .is_none_or(|category| g.category == category)
+            })
+            .filter(|g| matches(term.as_deref(), &[&g.make, &g.model]))
+            .cloned()
+            .collect();
+        items.sort_by(|a, b| compare(a, b, request));
+        Ok(Page::from_sorted(items, request))
+    }
+
+    async fn update(&self, id: GearId, changes: UpdateGear) -> Result<Gear, RepositoryError> {
+        let mut tables = self.store.lock();
+        let gear = tables
+            .gear
+            .get_mut(&id.into_inner())
+            .ok_or(RepositoryError::NotFound)?;
+        if let Some(make) = changes.make {
+            gear.make = make;
+        }
+        if let Some(model) = changes.model {
+            gear.model = model;
+        }
+        if let Some(water_temp) = changes.water_temp {
+            gear.water_temp = Some(water_temp);
+        }
+        if let Some(brew_method) = changes.brew_method {
+            gear.brew_method = Some(brew_method);
+        }
+        if let Some(uses_filter) = changes.uses_filter {
+            gear.uses_filter = Some(uses_filter);
+        }
+        if let Some(temp_offset) = changes.temp_offset {
+            gear.temp_offset = Some(temp_offset);
+        }
+        if let Some(water_hardness) = changes.water_hardness {
+            gear.water_hardness = Some(water_hardness);
+        }
+        if let Some(stock_count) = changes.stock_count {
+            gear.stock_count = Some(stock_count);
+        }
+        if let Some(threshold) = changes.low_stock_threshold {
+            gear.low_stock_threshold = Some(threshold);
+        }
+        if let Some(created_at) = changes.created_at {
+            gear.created_at = created_at;
+        }
+        gear.updated_at = Utc::now();
+        Ok(gear.clone())
+    }
+
+    async fn restock(&self, id: GearId, count: i32) -> Result<Gear, RepositoryError> {
+        let mut tables = self.store.lock();
+        let gear = tables
+            .gear
+            .get_mut(&id.into_inner())
+            .ok_or(RepositoryError::NotFound)?;
+        gear.stock_count = Some(gear.stock_count.unwrap_or(0) + count);
+        gear.updated_at = Utc::now();
+        Ok(gear.clone())
+    }
+
+    async fn set_created_by(&self, id: GearId, user_id: UserId) -> Result<(), RepositoryError> {
+        let mut tables = self.store.lock();
+        let gear = tables
+            .gear
+            .get_mut(&id.into_inner())
+            .ok_or(RepositoryError::NotFound)?;
+        gear.created_by = Some(user_id);
+        Ok(())
+    }
+
+    async fn delete(&self, id: GearId) -> Result<(), RepositoryError> {
+        self.store
+            .lock()
+            .gear
+            .remove(&id.into_inner())
+            .map(|_| ())
+            .ok_or(RepositoryError::NotFound)
+    }
+}
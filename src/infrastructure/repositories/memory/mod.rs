@@ -0,0 +1,171 @@
+//! In-memory implementations of the catalogue repositories: roasters, roasts,
+//! bags and gear. All four share one [`MemoryStore`], so joins, cascading
+//! deletes and the bag ledger behave as they do in `SQLite`. Selected with
+//! [`RepositoryBackend::Memory`](crate::application::state::RepositoryBackend);
+//! the remaining repositories and the pool-backed services (backup,
+//! integrity, overview, stats) still read the database and don't see these
+//! rows.
+//!
+//! Every implementation runs the suites in
+//! [`conformance`](super::conformance) to stay interchangeable with SQL.
+
+pub mod bags;
+pub mod gear;
+pub mod roasters;
+pub mod roasts;
+
+pub use bags::MemoryBagRepository;
+pub use gear::MemoryGearRepository;
+pub use roasters::MemoryRoasterRepository;
+pub use roasts::MemoryRoastRepository;
+
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashSet};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use crate::domain::bag_ledger::BagLedgerEntry;
+use crate::domain::bags::Bag;
+use crate::domain::gear::Gear;
+use crate::domain::listing::SortDirection;
+use crate::domain::roasters::Roaster;
+use crate::domain::roasts::Roast;
+
+/// Shared rows behind the in-memory repositories. Clones share the same
+/// tables, so build every repository from one store.
+#[derive(Clone, Default)]
+pub struct MemoryStore {
+    tables: Arc<Mutex<Tables>>,
+}
+
+impl MemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Tables> {
+        self.tables.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Rows keyed by id. Ids count up per table and are never reused, like
+/// `SQLite`'s `AUTOINCREMENT`.
+#[derive(Default)]
+struct Tables {
+    roasters: BTreeMap<i64, Roaster>,
+    roasts: BTreeMap<i64, Roast>,
+    bags: BTreeMap<i64, Bag>,
+    ledger: Vec<BagLedgerEntry>,
+    gear: BTreeMap<i64, Gear>,
+    last_roaster_id: i64,
+    last_roast_id: i64,
+    last_bag_id: i64,
+    last_ledger_id: i64,
+    last_gear_id: i64,
+}
+
+impl Tables {
+    /// Remove roasts and everything that cascades from them.
+    fn delete_roasts(&mut self, ids: &HashSet<i64>) {
+        self.roasts.retain(|id, _| !ids.contains(id));
+        let bag_ids = self
+            .bags
+            .values()
+            .filter(|bag| ids.contains(&bag.roast_id.into_inner()))
+            .map(|bag| bag.id.into_inner())
+            .collect();
+        self.delete_bags(&bag_ids);
+    }
+
+    /// Remove bags with their ledgers, detaching transfers that named them.
+    fn delete_bags(&mut self, ids: &HashSet<i64>) {
+        self.bags.retain(|id, _| !ids.contains(id));
+        self.ledger
+            .retain(|entry| !ids.contains(&entry.bag_id.into_inner()));
+        for entry in &mut self.ledger {
+            if entry
+                .counterpart_bag_id
+                .is_some_and(|id| ids.contains(&id.into_inner()))
+            {
+                entry.counterpart_bag_id = None;
+            }
+        }
+    }
+}
+
+/// A row count as the `i64` that `DeletePreview` carries.
+fn count(rows: usize) -> i64 {
+    i64::try_from(rows).unwrap_or(i64::MAX)
+}
+
+fn next_id(last: &mut i64) -> i64 {
+    *last += 1;
+    *last
+}
+
+/// The lowercased search term, or `None` when it's blank — the same rules as
+/// the SQL `SearchFilter`.
+fn search_term(search: Option<&str>) -> Option<String> {
+    search
+        .map(|term| term.trim().to_lowercase())
+        .filter(|term| !term.is_empty())
+}
+
+/// Whether any field contains the term, ignoring case.
+fn matches(term: Option<&str>, fields: &[&str]) -> bool {
+    term.is_none_or(|term| {
+        fields
+            .iter()
+            .any(|field| field.to_lowercase().contains(term))
+    })
+}
+
+/// Case-insensitive comparison, like ordering by `LOWER(column)`.
+fn cmp_lower(a: &str, b: &str) -> Ordering {
+    a.to_lowercase().cmp(&b.to_lowercase())
+}
+
+fn directed(ordering: Ordering, direction: SortDirection) -> Ordering {
+    match direction {
+        SortDirection::Asc => ordering,
+        SortDirection::Desc => ordering.reverse(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::repositories::conformance;
+
+    #[tokio::test]
+    async fn roasters_conform() {
+        let store = MemoryStore::new();
+        conformance::roaster_repository(&MemoryRoasterRepository::new(store)).await;
+    }
+
+    #[tokio::test]
+    async fn gear_conforms() {
+        let store = MemoryStore::new();
+        conformance::gear_repository(&MemoryGearRepository::new(store)).await;
+    }
+
+    #[tokio::test]
+    async fn roasts_conform() {
+        let store = MemoryStore::new();
+        conformance::roast_repository(
+            &MemoryRoasterRepository::new(store.clone()),
+            &MemoryRoastRepository::new(store),
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn bags_conform() {
+        let store = MemoryStore::new();
+        conformance::bag_repository(
+            &MemoryRoasterRepository::new(store.clone()),
+            &MemoryRoastRepository::new(store.clone()),
+            &MemoryBagRepository::new(store),
+        )
+        .await;
+    }
+}
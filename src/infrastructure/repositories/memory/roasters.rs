@@ -0,0 +1,221 @@
+use std::collections::HashSet;
+
+use async_trait::async_trait;
+use chrono::Utc;
+
+use super::{MemoryStore, cmp_lower, count, directed, matches, next_id, search_term};
+use crate::domain::delete_preview::DeletePreview;
+use crate::domain::ids::{RoasterId, UserId};
+use crate::domain::listing::{ListRequest, Page};
+use crate::domain::repositories::RoasterRepository;
+use crate::domain::roasters::{NewRoaster, Roaster, RoasterSortKey, UpdateRoaster};
+use crate::domain::{ErrorCode, RepositoryError};
+
+#[derive(Clone)]
+pub struct MemoryRoasterRepository {
+    store: MemoryStore,
+}
+
+impl MemoryRoasterRepository {
+    pub fn new(store: MemoryStore) -> Self {
+        Self { store }
+    }
+}
+
+fn compare(a: &Roaster, b: &Roaster, request: &ListRequest<RoasterSortKey>) -> std::cmp::Ordering {
+    let direction = request.sort_direction();
+    match request.sort_key() {
+        RoasterSortKey::CreatedAt => {
+            directed(a.created_at.cmp(&b.created_at), direction).then_with(|| a.name.cmp(&b.name))
+        }
+        RoasterSortKey::Name => directed(cmp_lower(&a.name, &b.name), direction)
+            .then_with(|| b.created_at.cmp(&a.created_at)),
+        RoasterSortKey::Country => directed(cmp_lower(&a.country, &b.country), direction)
+            .then_with(|| cmp_lower(&a.name, &b.name)),
+        RoasterSortKey::City => directed(
+            cmp_lower(
+                a.city.as_deref().unwrap_or_default(),
+                b.city.as_deref().unwrap_or_default(),
+            ),
+            direction,
+        )
+        .then_with(|| cmp_lower(&a.name, &b.name)),
+    }
+}
+
+#[async_trait]
+impl RoasterRepository for MemoryRoasterRepository {
+    async fn insert_with_slug(
+        &self,
+        new_roaster: NewRoaster,
+        slug: String,
+    ) -> Result<Roaster, RepositoryError> {
+        let new_roaster = new_roaster.normalize();
+        let mut tables = self.store.lock();
+
+        if tables.roasters.values().any(|r| r.slug == slug) {
+            return Err(RepositoryError::conflict_with(
+                ErrorCode::DuplicateSlug,
+                "A roaster with this name and city already exists",
+            ));
+        }
+
+        let id = next_id(&mut tables.last_roaster_id);
+        let roaster = Roaster {
+            id: RoasterId::new(id),
+            name: new_roaster.name,
+            slug,
+            country: new_roaster.country,
+            city: new_roaster.city,
+            homepage: new_roaster.homepage,
+            created_at: new_roaster.created_at.unwrap_or_else(Utc::now),
+            favourite: false,
+            created_by: None,
+        };
+        tables.roasters.insert(id, roaster.clone());
+        Ok(roaster)
+    }
+
+    async fn get(&self, id: RoasterId) -> Result<Roaster, RepositoryError> {
+        self.store
+            .lock()
+            .roasters
+            .get(&id.into_inner())
+            .cloned()
+            .ok_or(RepositoryError::NotFound)
+    }
+
+    async fn get_by_slug(&self, slug: &str) -> Result<Roaster, RepositoryError> {
+        self.store
+            .lock()
+            .roasters
+            .values()
+            .find(|r| r.slug == slug)
+            .cloned()
+            .ok_or(RepositoryError::NotFound)
+    }
+
+    async fn list(
+        &self,
+        request: &ListRequest<RoasterSortKey>,
+        search: Option<&str>,
+    ) -> Result<Page<Roaster>, RepositoryError> {
+        let term = search_term(search);
+        let mut items: Vec<Roaster> = self
+            .store
+            .lock()
+            .roasters
+            .values()
+            .filter(|r| {
+                matches(
+                    term.as_deref(),
+                    &[&r.name, &r.country, r.city.as_deref().unwrap_or_default()],
+                )
+            })
+            .cloned()
+            .collect();
+        items.sort_by(|a, b| compare(a, b, request));
+        Ok(Page::from_sorted(items, request))
+    }
+
+    async fn update(
+        &self,
+        id: RoasterId,
+        changes: UpdateRoaster,
+    ) -> Result<Roaster, RepositoryError> {
+        if changes.name.is_none()
+            && changes.country.is_none()
+            && changes.city.is_none()
+            && changes.homepage.is_none()
+            && changes.created_at.is_none()
+        {
+            return Err(RepositoryError::unexpected(
+                "No fields provided for update".to_string(),
+            ));
+        }
+
+        let mut tables = self.store.lock();
+        let roaster = tables
+            .roasters
+            .get_mut(&id.into_inner())
+            .ok_or(RepositoryError::NotFound)?;
+        if let Some(name) = changes.name {
+            roaster.name = name;
+        }
+        if let Some(country) = changes.country {
+            roaster.country = country;
+        }
+        if let Some(city) = changes.city {
+            roaster.city = Some(city);
+        }
+        if let Some(homepage) = changes.homepage {
+            roaster.homepage = Some(homepage);
+        }
+        if let Some(created_at) = changes.created_at {
+            roaster.created_at = created_at;
+        }
+        Ok(roaster.clone())
+    }
+
+    async fn set_created_by(&self, id: RoasterId, user_id: UserId) -> Result<(), RepositoryError> {
+        let mut tables = self.store.lock();
+        let roaster = tables
+            .roasters
+            .get_mut(&id.into_inner())
+            .ok_or(RepositoryError::NotFound)?;
+        roaster.created_by = Some(user_id);
+        Ok(())
+    }
+
+    async fn set_favourite(&self, id: RoasterId, favourite: bool) -> Result<(), RepositoryError> {
+        let mut tables = self.store.lock();
+        let roaster = tables
+            .roasters
+            .get_mut(&id.into_inner())
+            .ok_or(RepositoryError::NotFound)?;
+        roaster.favourite = favourite;
+        Ok(())
+    }
+
+    async fn delete(&self, id: RoasterId) -> Result<(), RepositoryError> {
+        let mut tables = self.store.lock();
+        if tables.roasters.remove(&id.into_inner()).is_none() {
+            return Err(RepositoryError::NotFound);
+        }
+        let roast_ids = tables
+            .roasts
+            .values()
+            .filter(|roast| roast.roaster_id == id)
+            .map(|roast| roast.id.into_inner())
+            .collect();
+        tables.delete_roasts(&roast_ids);
+        Ok(())
+    }
+
+    /// Brews, cups and images live in the database, so only roasts and bags
+    /// are counted.
+    async fn delete_preview(&self, id: RoasterId) -> Result<DeletePreview, RepositoryError> {
+        let tables = self.store.lock();
+        if !tables.roasters.contains_key(&id.into_inner()) {
+            return Err(RepositoryError::NotFound);
+        }
+        let roast_ids: HashSet<_> = tables
+            .roasts
+            .values()
+            .filter(|roast| roast.roaster_id == id)
+            .map(|roast| roast.id)
+            .collect();
+        let bags = tables
+            .bags
+            .values()
+            .filter(|bag| roast_ids.contains(&bag.roast_id))
+            .count();
+        Ok(DeletePreview {
+            roasts: count(roast_ids.len()),
+            bags: count(bags),
+            brews: 0,
+            cups: 0,
+            images: 0,
+        })
+    }
+}
@@ -0,0 +1,350 @@
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashSet};
+
+use async_trait::async_trait;
+use chrono::Utc;
+
+use super::{MemoryStore, Tables, cmp_lower, count, directed, matches, next_id, search_term};
+use crate::domain::delete_preview::DeletePreview;
+use crate::domain::ids::{RoastId, RoasterId, UserId};
+use crate::domain::listing::{ListRequest, Page};
+use crate::domain::repositories::RoastRepository;
+use crate::domain::roasts::{
+    FieldSuggestion, NewRoast, Roast, RoastField, RoastSortKey, RoastWithRoaster, UpdateRoast,
+};
+use crate::domain::{ErrorCode, RepositoryError};
+
+#[derive(Clone)]
+pub struct MemoryRoastRepository {
+    store: MemoryStore,
+}
+
+impl MemoryRoastRepository {
+    pub fn new(store: MemoryStore) -> Self {
+        Self { store }
+    }
+}
+
+fn empty_to_none(s: String) -> Option<String> {
+    if s.trim().is_empty() { None } else { Some(s) }
+}
+
+fn with_roaster(tables: &Tables, roast: &Roast) -> Option<RoastWithRoaster> {
+    let roaster = tables.roasters.get(&roast.roaster_id.into_inner())?;
+    Some(RoastWithRoaster {
+        roast: roast.clone(),
+        roaster_name: roaster.name.clone(),
+        roaster_slug: roaster.slug.clone(),
+    })
+}
+
+fn compare(
+    a: &RoastWithRoaster,
+    b: &RoastWithRoaster,
+    request: &ListRequest<RoastSortKey>,
+) -> Ordering {
+    let direction = request.sort_direction();
+    let newest_first = || b.roast.created_at.cmp(&a.roast.created_at);
+    match request.sort_key() {
+        RoastSortKey::CreatedAt => directed(a.roast.created_at.cmp(&b.roast.created_at), direction)
+            .then_with(|| cmp_lower(&a.roast.name, &b.roast.name)),
+        RoastSortKey::Name => {
+            directed(cmp_lower(&a.roast.name, &b.roast.name), direction).then_with(newest_first)
+        }
+        RoastSortKey::Roaster => {
+            directed(cmp_lower(&a.roaster_name, &b.roaster_name), direction).then_with(newest_first)
+        }
+        RoastSortKey::Origin => directed(
+            cmp_lower(
+                a.roast.origin.as_deref().unwrap_or_default(),
+                b.roast.origin.as_deref().unwrap_or_default(),
+            ),
+            direction,
+        )
+        .then_with(newest_first),
+        RoastSortKey::Producer => directed(
+            cmp_lower(
+                a.roast.producer.as_deref().unwrap_or_default(),
+                b.roast.producer.as_deref().unwrap_or_default(),
+            ),
+            direction,
+        )
+        .then_with(newest_first),
+    }
+}
+
+#[async_trait]
+impl RoastRepository for MemoryRoastRepository {
+    async fn insert_with_slug(
+        &self,
+        new_roast: NewRoast,
+        slug: String,
+    ) -> Result<Roast, RepositoryError> {
+        let mut tables = self.store.lock();
+
+        if !tables
+            .roasters
+            .contains_key(&new_roast.roaster_id.into_inner())
+        {
+            return Err(RepositoryError::conflict("unknown roaster reference"));
+        }
+        if tables
+            .roasts
+            .values()
+            .any(|r| r.roaster_id == new_roast.roaster_id && r.slug == slug)
+        {
+            return Err(RepositoryError::conflict_with(
+                ErrorCode::DuplicateSlug,
+                "A roast with this name already exists for this roaster",
+            ));
+        }
+
+        let id = next_id(&mut tables.last_roast_id);
+        let roast = Roast {
+            id: RoastId::new(id),
+            roaster_id: new_roast.roaster_id,
+            name: new_roast.name,
+            slug,
+            origin: empty_to_none(new_roast.origin),
+            region: empty_to_none(new_roast.region),
+            producer: empty_to_none(new_roast.producer),
+            tasting_notes: new_roast.tasting_notes,
+            process: empty_to_none(new_roast.process),
+            components: new_roast.components,
+            harvest_year: new_roast.harvest_year,
+            created_at: new_roast.created_at.unwrap_or_else(Utc::now),
+            favourite: false,
+            rating: None,
+            created_by: None,
+        };
+        tables.roasts.insert(id, roast.clone());
+        Ok(roast)
+    }
+
+    async fn get(&self, id: RoastId) -> Result<Roast, RepositoryError> {
+        self.store
+            .lock()
+            .roasts
+            .get(&id.into_inner())
+            .cloned()
+            .ok_or(RepositoryError::NotFound)
+    }
+
+    async fn get_with_roaster(&self, id: RoastId) -> Result<RoastWithRoaster, RepositoryError> {
+        let tables = self.store.lock();
+        tables
+            .roasts
+            .get(&id.into_inner())
+            .and_then(|roast| with_roaster(&tables, roast))
+            .ok_or(RepositoryError::NotFound)
+    }
+
+    async fn get_by_slug(
+        &self,
+        roaster_id: RoasterId,
+        slug: &str,
+    ) -> Result<Roast, RepositoryError> {
+        self.store
+            .lock()
+            .roasts
+            .values()
+            .find(|r| r.roaster_id == roaster_id && r.slug == slug)
+            .cloned()
+            .ok_or(RepositoryError::NotFound)
+    }
+
+    async fn list(
+        &self,
+        request: &ListRequest<RoastSortKey>,
+        search: Option<&str>,
+    ) -> Result<Page<RoastWithRoaster>, RepositoryError> {
+        let term = search_term(search);
+        let tables = self.store.lock();
+        let mut items: Vec<RoastWithRoaster> = tables
+            .roasts
+            .values()
+            .filter_map(|roast| with_roaster(&tables, roast))
+            .filter(|r| {
+                matches(
+                    term.as_deref(),
+                    &[
+                        &r.roast.name,
+                        &r.roaster_name,
+                        r.roast.origin.as_deref().unwrap_or_default(),
+                        r.roast.producer.as_deref().unwrap_or_default(),
+                        &r.roast.tasting_notes.join(", "),
+                    ],
+                )
+            })
+            .collect();
+        items.sort_by(|a, b| compare(a, b, request));
+        Ok(Page::from_sorted(items, request))
+    }
+
+    async fn list_by_roaster(
+        &self,
+        roaster_id: RoasterId,
+    ) -> Result<Vec<RoastWithRoaster>, RepositoryError> {
+        let tables = self.store.lock();
+        let mut items: Vec<RoastWithRoaster> = tables
+            .roasts
+            .values()
+            .filter(|roast| roast.roaster_id == roaster_id)
+            .filter_map(|roast| with_roaster(&tables, roast))
+            .collect();
+        items.sort_by_key(|item| std::cmp::Reverse(item.roast.created_at));
+        Ok(items)
+    }
+
+    async fn update(&self, id: RoastId, changes: UpdateRoast) -> Result<Roast, RepositoryError> {
+        if changes.roaster_id.is_none()
+            && changes.name.is_none()
+            && changes.origin.is_none()
+            && changes.region.is_none()
+            && changes.producer.is_none()
+            && changes.tasting_notes.is_none()
+            && changes.process.is_none()
+            && changes.components.is_none()
+            && changes.harvest_year.is_none()
+            && changes.created_at.is_none()
+        {
+            return Err(RepositoryError::unexpected(
+                "No fields provided for update".to_string(),
+            ));
+        }
+
+        let mut tables = self.store.lock();
+        if let Some(roaster_id) = changes.roaster_id
+            && !tables.roasters.contains_key(&roaster_id.into_inner())
+        {
+            return Err(RepositoryError::unexpected("unknown roaster reference"));
+        }
+        let roast = tables
+            .roasts
+            .get_mut(&id.into_inner())
+            .ok_or(RepositoryError::NotFound)?;
+        if let Some(roaster_id) = changes.roaster_id {
+            roast.roaster_id = roaster_id;
+        }
+        if let Some(name) = changes.name {
+            roast.name = name;
+        }
+        if let Some(origin) = changes.origin {
+            roast.origin = Some(origin);
+        }
+        if let Some(region) = changes.region {
+            roast.region = Some(region);
+        }
+        if let Some(producer) = changes.producer {
+            roast.producer = Some(producer);
+        }
+        if let Some(tasting_notes) = changes.tasting_notes {
+            roast.tasting_notes = tasting_notes;
+        }
+        if let Some(process) = changes.process {
+            roast.process = Some(process);
+        }
+        if let Some(components) = changes.components {
+            roast.components = components;
+        }
+        if let Some(harvest_year) = changes.harvest_year {
+            roast.harvest_year = Some(harvest_year);
+        }
+        if let Some(created_at) = changes.created_at {
+            roast.created_at = created_at;
+        }
+        Ok(roast.clone())
+    }
+
+    async fn set_created_by(&self, id: RoastId, user_id: UserId) -> Result<(), RepositoryError> {
+        let mut tables = self.store.lock();
+        let roast = tables
+            .roasts
+            .get_mut(&id.into_inner())
+            .ok_or(RepositoryError::NotFound)?;
+        roast.created_by = Some(user_id);
+        Ok(())
+    }
+
+    async fn set_favourite(&self, id: RoastId, favourite: bool) -> Result<(), RepositoryError> {
+        let mut tables = self.store.lock();
+        let roast = tables
+            .roasts
+            .get_mut(&id.into_inner())
+            .ok_or(RepositoryError::NotFound)?;
+        roast.favourite = favourite;
+        Ok(())
+    }
+
+    async fn delete(&self, id: RoastId) -> Result<(), RepositoryError> {
+        let mut tables = self.store.lock();
+        if !tables.roasts.contains_key(&id.into_inner()) {
+            return Err(RepositoryError::NotFound);
+        }
+        tables.delete_roasts(&HashSet::from([id.into_inner()]));
+        Ok(())
+    }
+
+    /// Brews, cups and images live in the database, so only bags are counted.
+    async fn delete_preview(&self, id: RoastId) -> Result<DeletePreview, RepositoryError> {
+        let tables = self.store.lock();
+        if !tables.roasts.contains_key(&id.into_inner()) {
+            return Err(RepositoryError::NotFound);
+        }
+        let bags = tables
+            .bags
+            .values()
+            .filter(|bag| bag.roast_id == id)
+            .count();
+        Ok(DeletePreview {
+            roasts: 0,
+            bags: count(bags),
+            brews: 0,
+            cups: 0,
+            images: 0,
+        })
+    }
+
+    async fn suggest(
+        &self,
+        field: RoastField,
+        query: &str,
+        limit: u32,
+    ) -> Result<Vec<FieldSuggestion>, RepositoryError> {
+        let query = query.trim().to_lowercase();
+        let tables = self.store.lock();
+        let values = tables.roasts.values().flat_map(|roast| match field {
+            RoastField::Origin => roast.origin.iter().cloned().collect::<Vec<_>>(),
+            RoastField::Process => roast.process.iter().cloned().collect(),
+            RoastField::Producer => roast.producer.iter().cloned().collect(),
+            RoastField::TastingNote => roast.tasting_notes.clone(),
+        });
+
+        // Group case-insensitively, keeping the smallest spelling as SQL's MIN does.
+        let mut groups: BTreeMap<String, FieldSuggestion> = BTreeMap::new();
+        for value in values {
+            let value = value.trim().to_string();
+            let key = value.to_lowercase();
+            if value.is_empty() || !key.contains(&query) {
+                continue;
+            }
+            let group = groups.entry(key).or_insert_with(|| FieldSuggestion {
+                value: value.clone(),
+                uses: 0,
+            });
+            group.uses += 1;
+            if value < group.value {
+                group.value = value;
+            }
+        }
+
+        let mut suggestions: Vec<(String, FieldSuggestion)> = groups.into_iter().collect();
+        suggestions
+            .sort_by(|(a_key, a), (b_key, b)| b.uses.cmp(&a.uses).then_with(|| a_key.cmp(b_key)));
+        Ok(suggestions
+            .into_iter()
+            .take(limit as usize)
+            .map(|(_, suggestion)| suggestion)
+            .collect())
+    }
+}
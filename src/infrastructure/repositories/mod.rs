@@ -1,11 +1,24 @@
 pub mod analytics;
 pub mod auth;
+pub mod cached;
 pub mod coffee;
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+pub(crate) mod conformance;
 pub mod images;
 pub(crate) mod macros;
+pub mod memory;
 pub mod pagination;
+pub mod query_timing;
+pub mod settings;
 
 // Re-exports for backward compatibility
 pub use analytics::{ai_usage, stats, timeline_events};
-pub use auth::{passkey_credentials, registration_tokens, sessions, tokens, users};
-pub use coffee::{bags, brews, cafes, cups, gear, roasters, roasts};
+pub use auth::{
+    list_preferences, notifications, passkey_credentials, registration_tokens, saved_searches,
+    sessions, tokens, users,
+};
+pub use coffee::{
+    aliases, bags, brew_shares, brews, cafes, comments, cups, events, gear, green_coffees,
+    grinder_calibrations, processes, quick_notes, roast_batches, roasters, roasts, trips,
+};
@@ -0,0 +1,70 @@
+//! Timing for the repository queries behind list and timeline pages, so a
+//! slow one is logged by name rather than just making a page drag.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// The threshold used unless `--slow-query-threshold-ms` says otherwise.
+pub const DEFAULT_SLOW_QUERY_THRESHOLD_MS: u64 = 250;
+
+/// Queries taking at least this long are logged; 0 turns logging off.
+static THRESHOLD_MS: AtomicU64 = AtomicU64::new(DEFAULT_SLOW_QUERY_THRESHOLD_MS);
+
+static SLOW_QUERIES: AtomicU64 = AtomicU64::new(0);
+
+/// Log queries taking at least `threshold`, or none when it's `None`.
+pub fn set_slow_query_threshold(threshold: Option<Duration>) {
+    let millis = threshold.map_or(0, |t| {
+        u64::try_from(t.as_millis()).unwrap_or(u64::MAX).max(1)
+    });
+    THRESHOLD_MS.store(millis, Ordering::Relaxed);
+}
+
+pub fn slow_query_threshold() -> Option<Duration> {
+    match THRESHOLD_MS.load(Ordering::Relaxed) {
+        0 => None,
+        millis => Some(Duration::from_millis(millis)),
+    }
+}
+
+/// How many queries have crossed the threshold since the server started.
+pub fn slow_query_count() -> u64 {
+    SLOW_QUERIES.load(Ordering::Relaxed)
+}
+
+/// Run `query`, logging it as `statement` if it takes longer than the
+/// threshold.
+pub(crate) async fn timed<F: Future>(statement: &'static str, query: F) -> F::Output {
+    let started = Instant::now();
+    let output = query.await;
+    record(statement, started.elapsed());
+    output
+}
+
+fn record(statement: &'static str, elapsed: Duration) {
+    let Some(threshold) = slow_query_threshold() else {
+        return;
+    };
+    if elapsed < threshold {
+        return;
+    }
+    SLOW_QUERIES.fetch_add(1, Ordering::Relaxed);
+    tracing::warn!(
+        statement,
+        elapsed_ms = u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX),
+        threshold_ms = u64::try_from(threshold.as_millis()).unwrap_or(u64::MAX),
+        "slow query"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn queries_over_the_threshold_are_counted() {
+        let before = slow_query_count();
+        record("test.slow", Duration::from_secs(60));
+        assert!(slow_query_count() > before);
+    }
+}
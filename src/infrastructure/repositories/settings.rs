@@ -0,0 +1,202 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::query_as;
+
+use crate::domain::RepositoryError;
+use crate::domain::currencies::ExchangeRate;
+use crate::domain::repositories::SettingsRepository;
+use crate::domain::settings::{Settings, UpdateSettings};
+use crate::infrastructure::database::DatabasePool;
+
+const LOW_STOCK_THRESHOLD_KEY: &str = "low_stock_threshold";
+const DUST_THRESHOLD_KEY: &str = "dust_threshold";
+const BASE_CURRENCY_KEY: &str = "base_currency";
+const MAP_TILE_URL_KEY: &str = "map_tile_url";
+const MAP_ATTRIBUTION_KEY: &str = "map_attribution";
+const SLUG_STRATEGY_KEY: &str = "slug_strategy";
+
+#[derive(Clone)]
+pub struct SqlSettingsRepository {
+    pool: DatabasePool,
+}
+
+impl SqlSettingsRepository {
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+
+    async fn upsert(&self, key: &str, value: String) -> Result<(), RepositoryError> {
+        sqlx::query(
+            "INSERT INTO settings (key, value) VALUES (?, ?) \
+             ON CONFLICT(key) DO UPDATE SET value = excluded.value, \
+             updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')",
+        )
+        .bind(key)
+        .bind(value)
+        .execute(&self.pool)
+        .await
+        .map_err(|err| {
+            RepositoryError::unexpected(format!("failed to save setting {key}: {err}"))
+        })?;
+
+        Ok(())
+    }
+}
+
+fn parse_grams(record: &SettingRecord) -> Result<f64, RepositoryError> {
+    record.value.parse().map_err(|_| {
+        RepositoryError::unexpected(format!("invalid {} setting: {}", record.key, record.value))
+    })
+}
+
+#[async_trait]
+impl SettingsRepository for SqlSettingsRepository {
+    async fn get(&self) -> Result<Settings, RepositoryError> {
+        let records = query_as::<_, SettingRecord>("SELECT key, value FROM settings")
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|err| {
+                RepositoryError::unexpected(format!("failed to load settings: {err}"))
+            })?;
+
+        let mut settings = Settings::default();
+        // Unknown keys are ignored so older binaries can read newer databases.
+        for record in records {
+            match record.key.as_str() {
+                LOW_STOCK_THRESHOLD_KEY => settings.low_stock_threshold = parse_grams(&record)?,
+                DUST_THRESHOLD_KEY => settings.dust_threshold = parse_grams(&record)?,
+                BASE_CURRENCY_KEY if !record.value.is_empty() => {
+                    settings.base_currency = Some(record.value);
+                }
+                MAP_TILE_URL_KEY if !record.value.is_empty() => {
+                    settings.map_tile_url = Some(record.value);
+                }
+                MAP_ATTRIBUTION_KEY if !record.value.is_empty() => {
+                    settings.map_attribution = Some(record.value);
+                }
+                SLUG_STRATEGY_KEY => {
+                    settings.slug_strategy = record.value.parse().map_err(|_| {
+                        RepositoryError::unexpected(format!(
+                            "invalid {} setting: {}",
+                            record.key, record.value
+                        ))
+                    })?;
+                }
+                _ => {}
+            }
+        }
+
+        Ok(settings)
+    }
+
+    async fn update(&self, changes: UpdateSettings) -> Result<Settings, RepositoryError> {
+        if let Some(threshold) = changes.low_stock_threshold {
+            self.upsert(LOW_STOCK_THRESHOLD_KEY, threshold.to_string())
+                .await?;
+        }
+        if let Some(threshold) = changes.dust_threshold {
+            self.upsert(DUST_THRESHOLD_KEY, threshold.to_string())
+                .await?;
+        }
+        if let Some(currency) = changes.base_currency {
+            let current = self.get().await?.base_currency.unwrap_or_default();
+            if currency != current {
+                sqlx::query("DELETE FROM exchange_rates")
+                    .execute(&self.pool)
+                    .await
+                    .map_err(|err| {
+                        RepositoryError::unexpected(format!(
+                            "failed to clear exchange rates: {err}"
+                        ))
+                    })?;
+            }
+            self.upsert(BASE_CURRENCY_KEY, currency).await?;
+        }
+        if let Some(url) = changes.map_tile_url {
+            self.upsert(MAP_TILE_URL_KEY, url).await?;
+        }
+        if let Some(attribution) = changes.map_attribution {
+            self.upsert(MAP_ATTRIBUTION_KEY, attribution).await?;
+        }
+        if let Some(strategy) = changes.slug_strategy {
+            self.upsert(SLUG_STRATEGY_KEY, strategy.as_str().to_string())
+                .await?;
+        }
+
+        self.get().await
+    }
+
+    async fn list_exchange_rates(&self) -> Result<Vec<ExchangeRate>, RepositoryError> {
+        let records = query_as::<_, ExchangeRateRecord>(
+            "SELECT currency, rate, updated_at FROM exchange_rates ORDER BY currency",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|err| {
+            RepositoryError::unexpected(format!("failed to load exchange rates: {err}"))
+        })?;
+
+        Ok(records.into_iter().map(ExchangeRate::from).collect())
+    }
+
+    async fn set_exchange_rate(
+        &self,
+        currency: &str,
+        rate: f64,
+    ) -> Result<ExchangeRate, RepositoryError> {
+        let record = query_as::<_, ExchangeRateRecord>(
+            "INSERT INTO exchange_rates (currency, rate) VALUES (?, ?) \
+             ON CONFLICT(currency) DO UPDATE SET rate = excluded.rate, \
+             updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') \
+             RETURNING currency, rate, updated_at",
+        )
+        .bind(currency)
+        .bind(rate)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|err| {
+            RepositoryError::unexpected(format!("failed to save exchange rate: {err}"))
+        })?;
+
+        Ok(record.into())
+    }
+
+    async fn delete_exchange_rate(&self, currency: &str) -> Result<(), RepositoryError> {
+        let result = sqlx::query("DELETE FROM exchange_rates WHERE currency = ?")
+            .bind(currency)
+            .execute(&self.pool)
+            .await
+            .map_err(|err| {
+                RepositoryError::unexpected(format!("failed to delete exchange rate: {err}"))
+            })?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct SettingRecord {
+    key: String,
+    value: String,
+}
+
+#[derive(sqlx::FromRow)]
+struct ExchangeRateRecord {
+    currency: String,
+    rate: f64,
+    updated_at: DateTime<Utc>,
+}
+
+impl From<ExchangeRateRecord> for ExchangeRate {
+    fn from(record: ExchangeRateRecord) -> Self {
+        Self {
+            currency: record.currency,
+            rate: record.rate,
+            updated_at: record.updated_at,
+        }
+    }
+}
@@ -0,0 +1,75 @@
+//! Running under systemd: taking a socket-activated listener and reporting
+//! readiness, liveness and shutdown over `sd_notify`. Outside systemd none of
+//! the environment these read is set, so everything here is a no-op.
+
+use std::time::Duration;
+
+use anyhow::Context;
+use listenfd::ListenFd;
+use sd_notify::NotifyState;
+use tokio::net::{TcpListener, UnixListener};
+use tracing::{debug, info, warn};
+
+use super::listener::{BindAddress, BoundListener};
+
+/// The socket systemd passed in (`LISTEN_FDS`) when started from a `.socket`
+/// unit, otherwise a fresh one bound to `address`.
+pub async fn listener(address: &BindAddress) -> anyhow::Result<BoundListener> {
+    let mut fds = ListenFd::from_env();
+
+    if let Ok(Some(listener)) = fds.take_tcp_listener(0) {
+        listener
+            .set_nonblocking(true)
+            .context("failed to configure inherited socket")?;
+        info!("using the TCP socket passed in by systemd");
+        return TcpListener::from_std(listener)
+            .map(BoundListener::Tcp)
+            .context("failed to adopt inherited socket");
+    }
+
+    if let Some(listener) = fds
+        .take_unix_listener(0)
+        .context("inherited socket is neither a TCP nor a Unix stream listener")?
+    {
+        listener
+            .set_nonblocking(true)
+            .context("failed to configure inherited socket")?;
+        info!("using the Unix socket passed in by systemd");
+        // systemd owns the socket file, so it stays put on shutdown.
+        return UnixListener::from_std(listener)
+            .map(|listener| BoundListener::Unix(listener, None))
+            .context("failed to adopt inherited socket");
+    }
+
+    BoundListener::bind(address).await
+}
+
+/// Tell systemd the server is accepting connections, and keep its watchdog
+/// fed if the unit sets `WatchdogSec=`.
+pub fn notify_ready() {
+    notify(&[NotifyState::Ready]);
+
+    let mut usec = 0;
+    if sd_notify::watchdog_enabled(false, &mut usec) {
+        let interval = Duration::from_micros(usec) / 2;
+        debug!(?interval, "systemd watchdog enabled");
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                notify(&[NotifyState::Watchdog]);
+            }
+        });
+    }
+}
+
+/// Tell systemd the server has begun shutting down.
+pub fn notify_stopping() {
+    notify(&[NotifyState::Stopping]);
+}
+
+fn notify(state: &[NotifyState]) {
+    if let Err(err) = sd_notify::notify(false, state) {
+        warn!(error = %err, "failed to notify systemd");
+    }
+}
@@ -0,0 +1,177 @@
+//! Static asset overrides for self-hosted branding. A theme directory mirrors
+//! the layout of `static/`: a file at the same relative path replaces the
+//! embedded copy, and any other file is served alongside it. If the theme
+//! provides `css/custom.css` it is linked after the main stylesheet.
+//!
+//! Templates are compiled into the binary, so only static assets can be
+//! themed. Files are read once at startup.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use anyhow::{Context, bail};
+
+/// Optional stylesheet linked on every page after `css/styles.css`.
+pub const CUSTOM_STYLESHEET: &str = "css/custom.css";
+
+/// A file loaded from the theme directory.
+pub struct ThemeFile {
+    pub body: Vec<u8>,
+    pub hash: String,
+}
+
+#[derive(Default)]
+pub struct Theme {
+    files: HashMap<String, ThemeFile>,
+}
+
+impl Theme {
+    /// Load every file under `dir`, keyed by its `/`-separated path relative
+    /// to `dir`. Hidden files and paths that can't be routed are skipped.
+    pub fn load(dir: &Path) -> anyhow::Result<Self> {
+        if !dir.is_dir() {
+            bail!("theme directory {} does not exist", dir.display());
+        }
+        let mut files = HashMap::new();
+        collect_files(dir, dir, &mut files)?;
+        Ok(Self { files })
+    }
+
+    pub fn get(&self, path: &str) -> Option<&ThemeFile> {
+        self.files.get(path)
+    }
+
+    pub fn paths(&self) -> impl Iterator<Item = &str> {
+        self.files.keys().map(String::as_str)
+    }
+
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+}
+
+fn collect_files(
+    root: &Path,
+    dir: &Path,
+    files: &mut HashMap<String, ThemeFile>,
+) -> anyhow::Result<()> {
+    let entries = std::fs::read_dir(dir)
+        .with_context(|| format!("failed to read theme directory {}", dir.display()))?;
+    for entry in entries {
+        let entry = entry?;
+        if entry.file_name().to_string_lossy().starts_with('.') {
+            continue;
+        }
+        let path = entry.path();
+        if path.is_dir() {
+            collect_files(root, &path, files)?;
+            continue;
+        }
+
+        let relative = path
+            .strip_prefix(root)
+            .unwrap_or(&path)
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy().into_owned())
+            .collect::<Vec<_>>()
+            .join("/");
+        if !routable(&relative) {
+            tracing::warn!(path = %relative, "skipping theme file with unsupported name");
+            continue;
+        }
+
+        let body = std::fs::read(&path)
+            .with_context(|| format!("failed to read theme file {}", path.display()))?;
+        let hash = format!("{:016x}", fnv1a(&body));
+        files.insert(relative, ThemeFile { body, hash });
+    }
+    Ok(())
+}
+
+/// Theme paths become route paths, so keep them to characters the router
+/// treats literally.
+fn routable(path: &str) -> bool {
+    !path.is_empty()
+        && path
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '/'))
+}
+
+/// 64-bit FNV-1a, matching the hashes `build.rs` computes for `static/`.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in bytes {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    hash
+}
+
+/// Content type for a theme file that has no embedded counterpart.
+pub fn content_type(path: &str) -> &'static str {
+    let extension = path.rsplit_once('.').map_or("", |(_, ext)| ext);
+    match extension.to_ascii_lowercase().as_str() {
+        "css" => "text/css; charset=utf-8",
+        "js" => "application/javascript; charset=utf-8",
+        "svg" => "image/svg+xml",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "webp" => "image/webp",
+        "ico" => "image/x-icon",
+        "woff2" => "font/woff2",
+        "woff" => "font/woff",
+        "webmanifest" => "application/manifest+json; charset=utf-8",
+        _ => "application/octet-stream",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn loads_nested_files_with_relative_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("css")).unwrap();
+        std::fs::write(dir.path().join("css/custom.css"), "body{}").unwrap();
+        std::fs::write(dir.path().join("favicon-light.svg"), "<svg/>").unwrap();
+        std::fs::write(dir.path().join(".DS_Store"), "").unwrap();
+
+        let theme = Theme::load(dir.path()).unwrap();
+
+        assert_eq!(theme.len(), 2);
+        assert_eq!(theme.get(CUSTOM_STYLESHEET).unwrap().body, b"body{}");
+        assert!(theme.get("favicon-light.svg").is_some());
+        assert!(theme.get(".DS_Store").is_none());
+    }
+
+    #[test]
+    fn missing_directory_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(Theme::load(&dir.path().join("nope")).is_err());
+    }
+
+    #[test]
+    fn unroutable_names_are_skipped() {
+        assert!(routable("css/custom.css"));
+        assert!(routable("img/logo_v2-dark.png"));
+        assert!(!routable("img/{id}.png"));
+        assert!(!routable("img/my logo.png"));
+    }
+
+    #[test]
+    fn hashes_match_build_script() {
+        assert_eq!(format!("{:016x}", fnv1a(b"")), "cbf29ce484222325");
+    }
+
+    #[test]
+    fn content_type_falls_back_to_octet_stream() {
+        assert_eq!(content_type("img/logo.PNG"), "image/png");
+        assert_eq!(content_type("fonts/brand.woff2"), "font/woff2");
+        assert_eq!(content_type("README"), "application/octet-stream");
+    }
+}
@@ -7,6 +7,7 @@ use webauthn_rs::prelude::{
     DiscoverableAuthentication, PasskeyAuthentication, PasskeyRegistration,
 };
 
+use crate::domain::clock::{Clock, SystemClock};
 use crate::domain::ids::UserId;
 
 /// Stores in-flight `WebAuthn` ceremony state between start/finish calls.
@@ -16,6 +17,7 @@ pub struct ChallengeStore {
     registrations: Arc<RwLock<HashMap<String, RegistrationEntry>>>,
     authentications: Arc<RwLock<HashMap<String, AuthenticationEntry>>>,
     discoverable_authentications: Arc<RwLock<HashMap<String, DiscoverableAuthEntry>>>,
+    clock: Arc<dyn Clock>,
 }
 
 struct RegistrationEntry {
@@ -46,16 +48,17 @@ const CHALLENGE_TTL_MINUTES: i64 = 5;
 
 impl Default for ChallengeStore {
     fn default() -> Self {
-        Self::new()
+        Self::new(Arc::new(SystemClock))
     }
 }
 
 impl ChallengeStore {
-    pub fn new() -> Self {
+    pub fn new(clock: Arc<dyn Clock>) -> Self {
         Self {
             registrations: Arc::new(RwLock::new(HashMap::new())),
             authentications: Arc::new(RwLock::new(HashMap::new())),
             discoverable_authentications: Arc::new(RwLock::new(HashMap::new())),
+            clock,
         }
     }
 
@@ -65,13 +68,14 @@ impl ChallengeStore {
         user_id: UserId,
         state: PasskeyRegistration,
     ) {
+        let now = self.clock.now();
         let entry = RegistrationEntry {
             user_id,
             state,
-            expires_at: Utc::now() + Duration::minutes(CHALLENGE_TTL_MINUTES),
+            expires_at: now + Duration::minutes(CHALLENGE_TTL_MINUTES),
         };
         let mut map = self.registrations.write().await;
-        Self::cleanup_expired_registrations(&mut map);
+        Self::cleanup_expired_registrations(&mut map, now);
         map.insert(challenge_id, entry);
     }
 
@@ -81,7 +85,7 @@ impl ChallengeStore {
     ) -> Option<(UserId, PasskeyRegistration)> {
         let mut map = self.registrations.write().await;
         let entry = map.remove(challenge_id)?;
-        if Utc::now() > entry.expires_at {
+        if self.clock.now() > entry.expires_at {
             return None;
         }
         Some((entry.user_id, entry.state))
@@ -93,13 +97,14 @@ impl ChallengeStore {
         state: PasskeyAuthentication,
         cli_callback: Option<CliCallbackInfo>,
     ) {
+        let now = self.clock.now();
         let entry = AuthenticationEntry {
             state,
-            expires_at: Utc::now() + Duration::minutes(CHALLENGE_TTL_MINUTES),
+            expires_at: now + Duration::minutes(CHALLENGE_TTL_MINUTES),
             cli_callback,
         };
         let mut map = self.authentications.write().await;
-        Self::cleanup_expired_authentications(&mut map);
+        Self::cleanup_expired_authentications(&mut map, now);
         map.insert(challenge_id, entry);
     }
 
@@ -109,7 +114,7 @@ impl ChallengeStore {
     ) -> Option<(PasskeyAuthentication, Option<CliCallbackInfo>)> {
         let mut map = self.authentications.write().await;
         let entry = map.remove(challenge_id)?;
-        if Utc::now() > entry.expires_at {
+        if self.clock.now() > entry.expires_at {
             return None;
         }
         Some((entry.state, entry.cli_callback))
@@ -120,12 +125,13 @@ impl ChallengeStore {
         challenge_id: String,
         state: DiscoverableAuthentication,
     ) {
+        let now = self.clock.now();
         let entry = DiscoverableAuthEntry {
             state,
-            expires_at: Utc::now() + Duration::minutes(CHALLENGE_TTL_MINUTES),
+            expires_at: now + Duration::minutes(CHALLENGE_TTL_MINUTES),
         };
         let mut map = self.discoverable_authentications.write().await;
-        Self::cleanup_expired_discoverable(&mut map);
+        Self::cleanup_expired_discoverable(&mut map, now);
         map.insert(challenge_id, entry);
     }
 
@@ -135,24 +141,30 @@ impl ChallengeStore {
     ) -> Option<DiscoverableAuthentication> {
         let mut map = self.discoverable_authentications.write().await;
         let entry = map.remove(challenge_id)?;
-        if Utc::now() > entry.expires_at {
+        if self.clock.now() > entry.expires_at {
             return None;
         }
         Some(entry.state)
     }
 
-    fn cleanup_expired_registrations(map: &mut HashMap<String, RegistrationEntry>) {
-        let now = Utc::now();
+    fn cleanup_expired_registrations(
+        map: &mut HashMap<String, RegistrationEntry>,
+        now: DateTime<Utc>,
+    ) {
         map.retain(|_, entry| entry.expires_at > now);
     }
 
-    fn cleanup_expired_authentications(map: &mut HashMap<String, AuthenticationEntry>) {
-        let now = Utc::now();
+    fn cleanup_expired_authentications(
+        map: &mut HashMap<String, AuthenticationEntry>,
+        now: DateTime<Utc>,
+    ) {
         map.retain(|_, entry| entry.expires_at > now);
     }
 
-    fn cleanup_expired_discoverable(map: &mut HashMap<String, DiscoverableAuthEntry>) {
-        let now = Utc::now();
+    fn cleanup_expired_discoverable(
+        map: &mut HashMap<String, DiscoverableAuthEntry>,
+        now: DateTime<Utc>,
+    ) {
         map.retain(|_, entry| entry.expires_at > now);
     }
 }
@@ -1,9 +1,14 @@
 use std::sync::OnceLock;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use infrastructure::theme::{CUSTOM_STYLESHEET, Theme};
 
 pub mod application;
 pub mod domain;
 pub mod infrastructure;
 pub mod presentation;
+#[cfg(feature = "test-support")]
+pub mod test_support;
 
 pub struct VersionInfo {
     pub version: &'static str,
@@ -24,3 +29,69 @@ pub fn set_base_url(url: String) {
 pub fn base_url() -> &'static str {
     BASE_URL.get().map_or("", std::string::String::as_str)
 }
+
+static DEV_MODE: AtomicBool = AtomicBool::new(false);
+
+/// Enable development mode: static assets are read from the checkout on
+/// every request and linked without content hashes.
+pub fn set_dev_mode(enabled: bool) {
+    DEV_MODE.store(enabled, Ordering::Relaxed);
+}
+
+pub fn dev_mode() -> bool {
+    DEV_MODE.load(Ordering::Relaxed)
+}
+
+static DEMO_MODE: AtomicBool = AtomicBool::new(false);
+
+/// Enable demo mode: every page shows a banner explaining that the instance
+/// is a read-only sample.
+pub fn set_demo_mode(enabled: bool) {
+    DEMO_MODE.store(enabled, Ordering::Relaxed);
+}
+
+pub fn demo_mode() -> bool {
+    DEMO_MODE.load(Ordering::Relaxed)
+}
+
+static THEME: OnceLock<Theme> = OnceLock::new();
+
+/// Install static asset overrides loaded from `--theme-dir`.
+pub fn set_theme(theme: Theme) {
+    let _ = THEME.set(theme);
+}
+
+pub fn theme() -> Option<&'static Theme> {
+    THEME.get()
+}
+
+/// `(path, hash)` for every file under `static/`, computed by `build.rs`.
+const ASSET_HASHES: &[(&str, &str)] = include!(concat!(env!("OUT_DIR"), "/asset_hashes.rs"));
+
+/// Content-hashed URL for a file under `static/`, e.g. `css/styles.css`
+/// becomes `/static/css/styles.<hash>.css`. A theme override is hashed by
+/// its own content. Files without a hash keep their plain URL.
+pub fn asset_url(file: &str) -> String {
+    if dev_mode() {
+        return format!("/static/{file}");
+    }
+    let themed = theme().and_then(|theme| theme.get(file));
+    let hash = match themed {
+        Some(themed) => themed.hash.as_str(),
+        None => match ASSET_HASHES.iter().find(|(path, _)| *path == file) {
+            Some((_, hash)) => *hash,
+            None => return format!("/static/{file}"),
+        },
+    };
+    match file.rsplit_once('.') {
+        Some((stem, extension)) => format!("/static/{stem}.{hash}.{extension}"),
+        None => format!("/static/{file}.{hash}"),
+    }
+}
+
+/// URL of the theme's extra stylesheet, if the theme provides one.
+pub fn custom_stylesheet_url() -> Option<String> {
+    theme()
+        .and_then(|theme| theme.get(CUSTOM_STYLESHEET))
+        .map(|_| asset_url(CUSTOM_STYLESHEET))
+}
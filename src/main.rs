@@ -1,141 +1,205 @@
+use std::time::Duration;
+
 use anyhow::Result;
-use brewlog::application::{ServerConfig, serve};
-use brewlog::infrastructure::backup::BackupData;
+use brewlog::application::serve;
+use brewlog::application::server::spawn_dry_run;
 use brewlog::infrastructure::client::BrewlogClient;
+use brewlog::infrastructure::repositories::query_timing::set_slow_query_threshold;
+use brewlog::infrastructure::theme::Theme;
+use brewlog::presentation::cli::backup::{BackupCommand, BackupSubcommand};
+use brewlog::presentation::cli::config::ServeConfigFile;
 use brewlog::presentation::cli::{
-    Cli, Commands, ServeCommand, bags, brews, cafes, cups, gear, roasters, roasts, timeline, tokens,
+    Cli, Commands, LogFormat, LoggingArgs, ServeCommand, admin, backup, bags, brews, cafes, config,
+    cups, dev, events, export, gear, green_coffees, import, roast_batches, roasters, roasts, stats,
+    timeline, tokens, trips,
 };
-use clap::Parser;
+use clap::{ArgMatches, CommandFactory, FromArgMatches};
 use tracing_subscriber::{EnvFilter, layer::SubscriberExt, util::SubscriberInitExt};
 
 #[tokio::main]
+#[allow(clippy::too_many_lines)]
 async fn main() -> Result<()> {
     // Load .env file if present (before clap parses env vars)
     let _ = dotenvy::dotenv();
 
-    init_tracing();
+    let matches = Cli::command().get_matches();
+    let mut cli = Cli::from_arg_matches(&matches).unwrap_or_else(|err| err.exit());
+    if let (Commands::Serve(cmd), Some(("serve", serve_matches))) =
+        (&mut cli.command, matches.subcommand())
+    {
+        layer_config_file(cmd, serve_matches)?;
+    }
 
-    let cli = Cli::parse();
+    let logging = match &cli.command {
+        Commands::Serve(cmd) => cmd.logging.clone(),
+        _ => LoggingArgs::default(),
+    };
+    init_tracing(&logging)?;
 
     match cli.command {
-        Commands::Serve(cmd) => run_server(cmd).await,
+        Commands::Serve(cmd) => run_server(*cmd).await,
         Commands::Roaster { command } => {
-            let client = BrewlogClient::from_base_url(&cli.api_url)?;
+            let client = connect(&cli.api_url, cli.dry_run).await?;
             roasters::run(&client, command).await
         }
         Commands::Roast { command } => {
-            let client = BrewlogClient::from_base_url(&cli.api_url)?;
+            let client = connect(&cli.api_url, cli.dry_run).await?;
             roasts::run(&client, command).await
         }
         Commands::Bag { command } => {
-            let client = BrewlogClient::from_base_url(&cli.api_url)?;
+            let client = connect(&cli.api_url, cli.dry_run).await?;
             bags::run(&client, command).await
         }
         Commands::Gear { command } => {
-            let client = BrewlogClient::from_base_url(&cli.api_url)?;
+            let client = connect(&cli.api_url, cli.dry_run).await?;
             gear::run(&client, command).await
         }
         Commands::Brew { command } => {
-            let client = BrewlogClient::from_base_url(&cli.api_url)?;
+            let client = connect(&cli.api_url, cli.dry_run).await?;
             brews::run(&client, command).await
         }
         Commands::Cafe { command } => {
-            let client = BrewlogClient::from_base_url(&cli.api_url)?;
+            let client = connect(&cli.api_url, cli.dry_run).await?;
             cafes::run(&client, command).await
         }
         Commands::Cup { command } => {
-            let client = BrewlogClient::from_base_url(&cli.api_url)?;
+            let client = connect(&cli.api_url, cli.dry_run).await?;
             cups::run(&client, command).await
         }
+        Commands::GreenCoffee { command } => {
+            let client = connect(&cli.api_url, cli.dry_run).await?;
+            green_coffees::run(&client, command).await
+        }
+        Commands::RoastBatch { command } => {
+            let client = connect(&cli.api_url, cli.dry_run).await?;
+            roast_batches::run(&client, command).await
+        }
+        Commands::Event { command } => {
+            let client = connect(&cli.api_url, cli.dry_run).await?;
+            events::run(&client, command).await
+        }
+        Commands::Trip { command } => {
+            let client = connect(&cli.api_url, cli.dry_run).await?;
+            trips::run(&client, command).await
+        }
         Commands::Token { command } => {
-            let client = BrewlogClient::from_base_url(&cli.api_url)?;
+            let client = connect(&cli.api_url, cli.dry_run).await?;
             tokens::run(&client, command).await
         }
+        Commands::Stats(cmd) => {
+            let client = connect(&cli.api_url, cli.dry_run).await?;
+            stats::stats(&client, cmd).await
+        }
         Commands::Timeline { command } => {
-            let client = BrewlogClient::from_base_url(&cli.api_url)?;
-            match command {
-                timeline::TimelineCommands::Rebuild => {
-                    client.timeline().rebuild().await?;
-                    eprintln!("Timeline rebuild initiated.");
-                    Ok(())
-                }
-            }
-        }
-        Commands::Backup(_cmd) => {
-            let client = BrewlogClient::from_base_url(&cli.api_url)?;
-            let data = client.backup().export().await?;
-            let json = serde_json::to_string_pretty(&data)?;
-            println!("{json}");
-            Ok(())
+            let client = connect(&cli.api_url, cli.dry_run).await?;
+            timeline::run(&client, command).await
+        }
+        Commands::Admin { command } => {
+            let client = connect(&cli.api_url, cli.dry_run).await?;
+            admin::run(&client, command).await
+        }
+        Commands::Dev { command } => {
+            let client = connect(&cli.api_url, cli.dry_run).await?;
+            dev::run(&client, command).await
+        }
+        Commands::Backup(BackupCommand {
+            command: Some(BackupSubcommand::Diff(cmd)),
+            ..
+        }) => backup::diff(&cmd),
+        Commands::Backup(cmd) => {
+            let client = connect(&cli.api_url, cli.dry_run).await?;
+            backup::backup(&client, cmd).await
         }
         Commands::Restore(cmd) => {
-            let contents = std::fs::read_to_string(&cmd.file)?;
-            let data: BackupData = serde_json::from_str(&contents)?;
-            let client = BrewlogClient::from_base_url(&cli.api_url)?;
-            client.backup().restore(&data).await?;
-            eprintln!("Restore complete.");
-            Ok(())
+            let client = connect(&cli.api_url, cli.dry_run).await?;
+            backup::restore(&client, cmd).await
+        }
+        Commands::Import { command } => {
+            let client = connect(&cli.api_url, cli.dry_run).await?;
+            import::run(&client, command).await
+        }
+        Commands::Export { command } => {
+            let client = connect(&cli.api_url, cli.dry_run).await?;
+            export::run(&client, command).await
         }
+        Commands::Config { command } => config::run(command),
     }
 }
 
-async fn run_server(command: ServeCommand) -> Result<()> {
-    let rp_id = command.rp_id;
-    let rp_origin = command.rp_origin;
+/// The client for every command but `serve`. A dry run talks to a private
+/// in-memory server instead, so nothing it does reaches `api_url`.
+async fn connect(api_url: &str, dry_run: bool) -> Result<BrewlogClient> {
+    if !dry_run {
+        return BrewlogClient::from_base_url(api_url);
+    }
+    let (address, token) = spawn_dry_run().await?;
+    eprintln!("Dry run: using a throwaway in-memory instance; nothing will be saved.");
+    Ok(BrewlogClient::from_base_url(&address)?.with_token(token))
+}
 
-    let insecure_cookies = command.insecure_cookies
-        || (rp_id == "localhost" && rp_origin.starts_with("http://localhost"));
-    if insecure_cookies {
+async fn run_server(command: ServeCommand) -> Result<()> {
+    let config = command.server_config()?;
+    if config.insecure_cookies {
         tracing::warn!(
             "insecure cookies enabled for development/demo setup - do not use in production"
         );
     }
 
-    let openrouter_api_key = command.openrouter_api_key.ok_or_else(|| {
-        anyhow::anyhow!(
-            "BREWLOG_OPENROUTER_API_KEY is required. Set this environment variable \
-             to an OpenRouter API key for AI-powered extraction features."
-        )
-    })?;
-
-    let foursquare_api_key = command.foursquare_api_key.ok_or_else(|| {
-        anyhow::anyhow!(
-            "BREWLOG_FOURSQUARE_API_KEY is required. Set this environment variable \
-             to a Foursquare API key for nearby cafe search."
-        )
-    })?;
-
-    brewlog::set_base_url(rp_origin.clone());
-
-    let config = ServerConfig {
-        bind_address: command.bind_address,
-        database_url: command.database_url,
-        rp_id,
-        rp_origin,
-        insecure_cookies,
-        openrouter_api_key,
-        openrouter_model: command.openrouter_model,
-        foursquare_api_key,
-    };
+    brewlog::set_base_url(config.rp_origin.clone());
+    brewlog::set_dev_mode(command.dev);
+    brewlog::set_demo_mode(command.demo);
+    set_slow_query_threshold(
+        (command.slow_query_threshold_ms > 0)
+            .then(|| Duration::from_millis(command.slow_query_threshold_ms)),
+    );
+    if let Some(dir) = &command.theme_dir {
+        let theme = Theme::load(dir)?;
+        tracing::info!(dir = %dir.display(), files = theme.len(), "loaded theme overrides");
+        brewlog::set_theme(theme);
+    }
 
     serve(config).await
 }
 
-#[allow(clippy::expect_used)] // Startup: panicking is appropriate if logging cannot be initialized
-fn init_tracing() {
-    let env_filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+/// Fill in whatever `serve` wasn't given as a flag or environment variable
+/// from its config file, if it has one.
+fn layer_config_file(command: &mut ServeCommand, matches: &ArgMatches) -> Result<()> {
+    if let Some(path) = command.config.clone() {
+        command.layer_file(ServeConfigFile::load(&path)?, matches);
+    }
+    Ok(())
+}
 
-    let use_json = std::env::var("RUST_LOG_FORMAT").is_ok_and(|v| v.eq_ignore_ascii_case("json"));
+fn init_tracing(logging: &LoggingArgs) -> Result<()> {
+    let directives = logging.filter_directives();
+    let env_filter = EnvFilter::try_new(&directives)
+        .map_err(|err| anyhow::anyhow!("invalid log filter '{directives}': {err}"))?;
 
     let registry = tracing_subscriber::registry().with(env_filter);
 
-    if use_json {
-        registry
-            .with(tracing_subscriber::fmt::layer().json())
-            .init();
-    } else {
-        registry
-            .with(tracing_subscriber::fmt::layer().compact())
-            .init();
+    match logging.format() {
+        LogFormat::Pretty => registry
+            .with(
+                tracing_subscriber::fmt::layer()
+                    .pretty()
+                    .with_writer(std::io::stderr),
+            )
+            .init(),
+        LogFormat::Json => registry
+            .with(
+                tracing_subscriber::fmt::layer()
+                    .json()
+                    .with_writer(std::io::stderr),
+            )
+            .init(),
+        LogFormat::Compact => registry
+            .with(
+                tracing_subscriber::fmt::layer()
+                    .compact()
+                    .with_writer(std::io::stderr),
+            )
+            .init(),
     }
+
+    Ok(())
 }
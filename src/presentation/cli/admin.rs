@@ -0,0 +1,64 @@
+use anyhow::{Result, bail};
+use clap::{Args, Subcommand};
+
+use crate::infrastructure::client::BrewlogClient;
+
+#[derive(Debug, Subcommand)]
+pub enum AdminCommands {
+    /// Check the database for orphaned rows and inconsistent data
+    Check(CheckCommand),
+}
+
+#[derive(Debug, Args)]
+pub struct CheckCommand {
+    /// Fix repairable issues instead of only reporting them
+    #[arg(long)]
+    pub repair: bool,
+}
+
+pub async fn run(client: &BrewlogClient, cmd: AdminCommands) -> Result<()> {
+    match cmd {
+        AdminCommands::Check(c) => check(client, c).await,
+    }
+}
+
+async fn check(client: &BrewlogClient, cmd: CheckCommand) -> Result<()> {
+    let report = if cmd.repair {
+        client.admin().repair_integrity().await?
+    } else {
+        client.admin().check_integrity().await?
+    };
+
+    if report.is_clean() {
+        eprintln!("No integrity issues found.");
+        return Ok(());
+    }
+
+    for issue in &report.issues {
+        let note = if issue.repairable {
+            ""
+        } else {
+            " (manual fix required)"
+        };
+        println!("{}{note}", issue.description);
+    }
+
+    let unrepairable = report.issues.iter().filter(|i| !i.repairable).count();
+    if cmd.repair {
+        eprintln!(
+            "Found {} issues, repaired {}.",
+            report.issues.len(),
+            report.repaired
+        );
+        if unrepairable > 0 {
+            bail!("{unrepairable} issues need manual attention");
+        }
+        Ok(())
+    } else {
+        bail!(
+            "{} integrity issues found (run with --repair to fix {} of them)",
+            report.issues.len(),
+            report.issues.len() - unrepairable
+        )
+    }
+}
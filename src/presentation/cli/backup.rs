@@ -1,11 +1,183 @@
-use clap::Args;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use clap::{Args, Subcommand, ValueEnum};
+
+use crate::domain::ErrorCode;
+use crate::infrastructure::backup::BackupData;
+use crate::infrastructure::backup_diff::{BackupDiff, DiffEntry, diff_backups};
+use crate::infrastructure::client::{ApiRequestError, BrewlogClient};
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum BackupFormat {
+    /// A single JSON document
+    Json,
+    /// One record per line, for streaming restore of large backups
+    Ndjson,
+}
+
+#[derive(Debug, Args)]
+#[command(args_conflicts_with_subcommands = true)]
+pub struct BackupCommand {
+    #[command(subcommand)]
+    pub command: Option<BackupSubcommand>,
+    /// Output format
+    #[arg(long, value_enum, default_value_t = BackupFormat::Json)]
+    pub format: BackupFormat,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum BackupSubcommand {
+    /// Compare two backup files and list what was added, removed or changed
+    Diff(DiffCommand),
+}
 
 #[derive(Debug, Args)]
-pub struct BackupCommand;
+pub struct DiffCommand {
+    /// The earlier backup (`.json`, or `.ndjson`)
+    pub old: PathBuf,
+    /// The later backup
+    pub new: PathBuf,
+    /// Print the differences as JSON instead
+    #[arg(long)]
+    pub json: bool,
+}
 
 #[derive(Debug, Args)]
 pub struct RestoreCommand {
-    /// Path to the backup JSON file
+    /// Path to the backup file (`.json`, or `.ndjson` to stream the restore)
     #[arg(long)]
     pub file: String,
+    /// Skip records that already exist, continuing an interrupted NDJSON restore
+    #[arg(long)]
+    pub resume: bool,
+}
+
+pub async fn backup(client: &BrewlogClient, command: BackupCommand) -> Result<()> {
+    match command.format {
+        BackupFormat::Json => {
+            let data = client.backup().export().await?;
+            super::print_json(&data)
+        }
+        BackupFormat::Ndjson => {
+            let body = client.backup().export_ndjson().await?;
+            print!("{body}");
+            Ok(())
+        }
+    }
+}
+
+pub async fn restore(client: &BrewlogClient, command: RestoreCommand) -> Result<()> {
+    let path = Path::new(&command.file);
+
+    if !is_ndjson(path) {
+        if command.resume {
+            bail!(
+                "--resume requires an NDJSON backup (create one with `brewlog backup --format ndjson`)"
+            );
+        }
+        let contents = std::fs::read_to_string(path)?;
+        let data: BackupData = serde_json::from_str(&contents)?;
+        client
+            .backup()
+            .restore(&data)
+            .await
+            .map_err(|err| explain_restore_error(err, false))?;
+        eprintln!("Restore complete.");
+        return Ok(());
+    }
+
+    let total = std::fs::metadata(path)?.len().max(1);
+    let summary = client
+        .backup()
+        .restore_stream(path, command.resume, move |sent| {
+            let percent = sent.saturating_mul(100) / total;
+            eprint!("\rUploading backup: {percent}%");
+        })
+        .await;
+    eprintln!();
+
+    let summary = summary.map_err(|err| explain_restore_error(err, command.resume))?;
+    eprintln!(
+        "Restore complete: {} records restored, {} already present.",
+        summary.restored, summary.skipped
+    );
+    Ok(())
+}
+
+/// Compare two backup files. Runs locally, without contacting the server.
+pub fn diff(command: &DiffCommand) -> Result<()> {
+    let old = read_backup(&command.old)?;
+    let new = read_backup(&command.new)?;
+    let diff = diff_backups(&old, &new)?;
+    if command.json {
+        return super::print_json(&diff);
+    }
+    print!("{}", render_diff(&diff));
+    Ok(())
+}
+
+fn read_backup(path: &Path) -> Result<BackupData> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    let data = if is_ndjson(path) {
+        BackupData::from_ndjson(&contents)
+    } else {
+        serde_json::from_str(&contents).map_err(anyhow::Error::from)
+    };
+    data.with_context(|| format!("{} is not a brewlog backup", path.display()))
+}
+
+fn render_diff(diff: &BackupDiff) -> String {
+    if diff.is_empty() {
+        return "No differences.\n".to_string();
+    }
+
+    let mut out = String::new();
+    for section in diff.sections.iter().filter(|s| !s.is_empty()) {
+        let _ = writeln!(
+            out,
+            "{}: {} added, {} removed, {} changed, {} unchanged",
+            section.section,
+            section.added.len(),
+            section.removed.len(),
+            section.changed.len(),
+            section.unchanged
+        );
+        for (marker, entries) in [
+            ('+', &section.added),
+            ('-', &section.removed),
+            ('~', &section.changed),
+        ] {
+            for entry in entries {
+                let _ = writeln!(out, "  {marker} {}", describe(entry));
+            }
+        }
+    }
+    out
+}
+
+fn describe(entry: &DiffEntry) -> String {
+    match &entry.label {
+        Some(label) => format!("{} {label}", entry.key),
+        None => entry.key.clone(),
+    }
+}
+
+/// A fresh restore into a server that already holds data is the usual
+/// mistake, so say how to recover from it.
+fn explain_restore_error(err: anyhow::Error, resume: bool) -> anyhow::Error {
+    if resume || ApiRequestError::code_of(&err) != Some(ErrorCode::DatabaseNotEmpty) {
+        return err;
+    }
+    err.context(
+        "the server already holds data: restore into an empty instance, or pass --resume with an NDJSON backup to continue an interrupted restore",
+    )
+}
+
+fn is_ndjson(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("ndjson") || ext.eq_ignore_ascii_case("jsonl"))
 }
@@ -1,10 +1,15 @@
 use anyhow::Result;
 use clap::{Args, Subcommand};
 
+use super::images::{self, ImageCommands};
 use super::macros::{define_delete_command, define_get_command};
 use super::print_json;
 use super::{parse_created_at, parse_finished_at};
+use crate::domain::bags::{NewBag, UpdateBag};
+use crate::domain::currencies::normalize_currency;
+use crate::domain::entity_type::EntityType;
 use crate::domain::ids::{BagId, RoastId};
+use crate::domain::validation::Validate;
 use crate::infrastructure::client::BrewlogClient;
 
 #[derive(Debug, Subcommand)]
@@ -17,8 +22,21 @@ pub enum BagCommands {
     Get(GetBagCommand),
     /// Update a bag
     Update(UpdateBagCommand),
+    /// Reopen a finished bag
+    Reopen(ReopenBagCommand),
+    /// Correct a bag's remaining weight, recording why
+    Adjust(AdjustBagCommand),
+    /// Move coffee from one bag into another
+    Transfer(TransferBagCommand),
+    /// Show every change to a bag's weight
+    Ledger(BagLedgerCommand),
     /// Delete a bag
     Delete(DeleteBagCommand),
+    /// Upload, download or remove the photo
+    Image {
+        #[command(subcommand)]
+        command: ImageCommands,
+    },
 }
 
 pub async fn run(client: &BrewlogClient, cmd: BagCommands) -> Result<()> {
@@ -27,7 +45,12 @@ pub async fn run(client: &BrewlogClient, cmd: BagCommands) -> Result<()> {
         BagCommands::List(c) => list_bags(client, c).await,
         BagCommands::Get(c) => get_bag(client, c).await,
         BagCommands::Update(c) => update_bag(client, c).await,
+        BagCommands::Reopen(c) => reopen_bag(client, c).await,
+        BagCommands::Adjust(c) => adjust_bag(client, c).await,
+        BagCommands::Transfer(c) => transfer_bag(client, c).await,
+        BagCommands::Ledger(c) => bag_ledger(client, c).await,
         BagCommands::Delete(c) => delete_bag(client, c).await,
+        BagCommands::Image { command } => images::run(client, EntityType::Bag, command).await,
     }
 }
 
@@ -39,6 +62,18 @@ pub struct AddBagCommand {
     pub roast_date: Option<String>,
     #[arg(long)]
     pub amount: f64,
+    /// What the bag cost
+    #[arg(long)]
+    pub price: Option<f64>,
+    /// Currency the price was paid in (e.g. EUR), if not the base currency
+    #[arg(long)]
+    pub currency: Option<String>,
+    /// Best-before date from the label (YYYY-MM-DD)
+    #[arg(long)]
+    pub best_before: Option<String>,
+    /// The roaster's batch code from the label
+    #[arg(long)]
+    pub batch_code: Option<String>,
     /// Override creation timestamp (e.g. 2025-08-05T10:00:00Z or 2025-08-05)
     #[arg(long)]
     pub created_at: Option<String>,
@@ -49,17 +84,37 @@ pub async fn add_bag(client: &BrewlogClient, command: AddBagCommand) -> Result<(
         .roast_date
         .map(|d| chrono::NaiveDate::parse_from_str(&d, "%Y-%m-%d"))
         .transpose()?;
+    let best_before = command
+        .best_before
+        .map(|d| chrono::NaiveDate::parse_from_str(&d, "%Y-%m-%d"))
+        .transpose()?;
     let created_at = command
         .created_at
         .map(|s| parse_created_at(&s))
         .transpose()?;
+    let payload = NewBag {
+        roast_id: RoastId::new(command.roast_id),
+        roast_date,
+        amount: command.amount,
+        price: command.price,
+        currency: command.currency.as_deref().map(normalize_currency),
+        best_before,
+        batch_code: command.batch_code,
+        created_at,
+    };
+    payload.validate()?;
+
     let bag = client
         .bags()
         .create(
-            RoastId::new(command.roast_id),
-            roast_date,
-            command.amount,
-            created_at,
+            payload.roast_id,
+            payload.roast_date,
+            payload.amount,
+            payload.price,
+            payload.currency.as_deref(),
+            payload.best_before,
+            payload.batch_code.as_deref(),
+            payload.created_at,
         )
         .await?;
     print_json(&bag)
@@ -94,6 +149,21 @@ pub struct UpdateBagCommand {
     /// Override creation timestamp (e.g. 2025-08-05T10:00:00Z or 2025-08-05)
     #[arg(long)]
     pub created_at: Option<String>,
+    /// Grams left below which this bag needs reordering (overrides the default)
+    #[arg(long)]
+    pub low_stock_threshold: Option<f64>,
+    /// What the bag cost
+    #[arg(long)]
+    pub price: Option<f64>,
+    /// Currency the price was paid in (e.g. EUR), if not the base currency
+    #[arg(long)]
+    pub currency: Option<String>,
+    /// Best-before date from the label (YYYY-MM-DD)
+    #[arg(long)]
+    pub best_before: Option<String>,
+    /// The roaster's batch code from the label
+    #[arg(long)]
+    pub batch_code: Option<String>,
 }
 
 pub async fn update_bag(client: &BrewlogClient, command: UpdateBagCommand) -> Result<()> {
@@ -105,6 +175,20 @@ pub async fn update_bag(client: &BrewlogClient, command: UpdateBagCommand) -> Re
         .created_at
         .map(|s| parse_created_at(&s))
         .transpose()?;
+    let best_before = command
+        .best_before
+        .map(|d| chrono::NaiveDate::parse_from_str(&d, "%Y-%m-%d"))
+        .transpose()?;
+    let currency = command.currency.as_deref().map(normalize_currency);
+    UpdateBag {
+        remaining: command.remaining,
+        low_stock_threshold: command.low_stock_threshold,
+        price: command.price,
+        currency: currency.clone(),
+        batch_code: command.batch_code.clone(),
+        ..UpdateBag::default()
+    }
+    .validate()?;
 
     let bag = client
         .bags()
@@ -114,9 +198,81 @@ pub async fn update_bag(client: &BrewlogClient, command: UpdateBagCommand) -> Re
             command.closed,
             finished_at,
             created_at,
+            command.low_stock_threshold,
+            command.price,
+            currency,
+            best_before,
+            command.batch_code,
         )
         .await?;
     print_json(&bag)
 }
 
+#[derive(Debug, Args)]
+pub struct ReopenBagCommand {
+    #[arg(long)]
+    pub id: i64,
+}
+
+pub async fn reopen_bag(client: &BrewlogClient, command: ReopenBagCommand) -> Result<()> {
+    let bag = client.bags().reopen(BagId::new(command.id)).await?;
+    print_json(&bag)
+}
+
+#[derive(Debug, Args)]
+pub struct AdjustBagCommand {
+    #[arg(long)]
+    pub id: i64,
+    /// Grams actually left in the bag
+    #[arg(long)]
+    pub remaining: f64,
+    /// Why the weight changed, e.g. "weighed the bag"
+    #[arg(long)]
+    pub reason: String,
+}
+
+pub async fn adjust_bag(client: &BrewlogClient, command: AdjustBagCommand) -> Result<()> {
+    let adjustment = client
+        .bags()
+        .adjust(BagId::new(command.id), command.remaining, command.reason)
+        .await?;
+    print_json(&adjustment)
+}
+
+#[derive(Debug, Args)]
+pub struct TransferBagCommand {
+    /// Bag to take the coffee from
+    #[arg(long)]
+    pub id: i64,
+    /// Bag to put the coffee in
+    #[arg(long)]
+    pub to: i64,
+    /// Grams to move
+    #[arg(long)]
+    pub amount: f64,
+}
+
+pub async fn transfer_bag(client: &BrewlogClient, command: TransferBagCommand) -> Result<()> {
+    let transfer = client
+        .bags()
+        .transfer(
+            BagId::new(command.id),
+            BagId::new(command.to),
+            command.amount,
+        )
+        .await?;
+    print_json(&transfer)
+}
+
+#[derive(Debug, Args)]
+pub struct BagLedgerCommand {
+    #[arg(long)]
+    pub id: i64,
+}
+
+pub async fn bag_ledger(client: &BrewlogClient, command: BagLedgerCommand) -> Result<()> {
+    let entries = client.bags().ledger(BagId::new(command.id)).await?;
+    print_json(&entries)
+}
+
 define_delete_command!(DeleteBagCommand, delete_bag, BagId, bags, "bag");
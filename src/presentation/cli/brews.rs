@@ -1,11 +1,14 @@
 use anyhow::Result;
 use clap::{Args, Subcommand};
 
+use super::images::{self, ImageCommands};
 use super::macros::{define_delete_command, define_get_command};
 use super::parse_created_at;
 use super::print_json;
-use crate::domain::brews::{QuickNote, UpdateBrew};
+use crate::domain::brews::{NewBrew, QuickNote, UpdateBrew};
+use crate::domain::entity_type::EntityType;
 use crate::domain::ids::{BagId, BrewId, GearId};
+use crate::domain::validation::Validate;
 use crate::infrastructure::client::BrewlogClient;
 
 #[derive(Debug, Subcommand)]
@@ -20,6 +23,11 @@ pub enum BrewCommands {
     Update(UpdateBrewCommand),
     /// Delete a brew
     Delete(DeleteBrewCommand),
+    /// Upload, download or remove the photo
+    Image {
+        #[command(subcommand)]
+        command: ImageCommands,
+    },
 }
 
 pub async fn run(client: &BrewlogClient, cmd: BrewCommands) -> Result<()> {
@@ -29,6 +37,7 @@ pub async fn run(client: &BrewlogClient, cmd: BrewCommands) -> Result<()> {
         BrewCommands::Get(c) => get_brew(client, c).await,
         BrewCommands::Update(c) => update_brew(client, c).await,
         BrewCommands::Delete(c) => delete_brew(client, c).await,
+        BrewCommands::Image { command } => images::run(client, EntityType::Brew, command).await,
     }
 }
 
@@ -66,7 +75,21 @@ pub struct AddBrewCommand {
     #[arg(long, default_value = "91.0")]
     pub water_temp: f64,
 
-    /// Quick notes (comma-separated: good,too-fast,too-slow,too-hot,under-extracted,over-extracted)
+    /// Water in ml added after brewing, e.g. to dilute a concentrate
+    #[arg(long)]
+    pub bypass_water_ml: Option<i32>,
+
+    /// ID of the kettle the water was heated in
+    #[arg(long)]
+    pub kettle_id: Option<i64>,
+
+    /// Measured temperature of the water reaching the coffee, when it differs
+    /// from what the kettle was set to (worked out from the kettle's offset
+    /// if left out)
+    #[arg(long)]
+    pub actual_water_temp: Option<f64>,
+
+    /// Quick notes (comma-separated: good,too-fast,too-slow,too-hot,under-extracted,over-extracted, or custom note labels)
     #[arg(long, value_delimiter = ',')]
     pub quick_notes: Vec<String>,
 
@@ -83,27 +106,47 @@ pub async fn add_brew(client: &BrewlogClient, command: AddBrewCommand) -> Result
     let quick_notes: Vec<QuickNote> = command
         .quick_notes
         .iter()
-        .filter_map(|s| QuickNote::from_str_value(s))
+        .filter_map(|s| QuickNote::from_value(s))
         .collect();
     let created_at = command
         .created_at
         .map(|s| parse_created_at(&s))
         .transpose()?;
+    let payload = NewBrew {
+        bag_id: BagId::new(command.bag_id),
+        coffee_weight: command.coffee_weight,
+        grinder_id: GearId::new(command.grinder_id),
+        grind_setting: command.grind_setting,
+        brewer_id: GearId::new(command.brewer_id),
+        filter_paper_id: command.filter_paper_id.map(GearId::new),
+        water_volume: command.water_volume,
+        water_temp: command.water_temp,
+        bypass_water_ml: command.bypass_water_ml,
+        kettle_id: command.kettle_id.map(GearId::new),
+        actual_water_temp: command.actual_water_temp,
+        quick_notes,
+        brew_time: command.brew_time,
+        created_at,
+    };
+    payload.validate()?;
 
     let brew = client
         .brews()
         .create(
-            BagId::new(command.bag_id),
-            command.coffee_weight,
-            GearId::new(command.grinder_id),
-            command.grind_setting,
-            GearId::new(command.brewer_id),
-            command.filter_paper_id.map(GearId::new),
-            command.water_volume,
-            command.water_temp,
-            quick_notes,
-            command.brew_time,
-            created_at,
+            payload.bag_id,
+            payload.coffee_weight,
+            payload.grinder_id,
+            payload.grind_setting,
+            payload.brewer_id,
+            payload.filter_paper_id,
+            payload.water_volume,
+            payload.water_temp,
+            payload.bypass_water_ml,
+            payload.kettle_id,
+            payload.actual_water_temp,
+            payload.quick_notes,
+            payload.brew_time,
+            payload.created_at,
         )
         .await?;
     print_json(&brew)
@@ -158,7 +201,19 @@ pub struct UpdateBrewCommand {
     #[arg(long)]
     pub water_temp: Option<f64>,
 
-    /// Quick notes (comma-separated: good,too-fast,too-slow,too-hot,under-extracted,over-extracted)
+    /// Water in ml added after brewing; 0 removes it
+    #[arg(long)]
+    pub bypass_water_ml: Option<i32>,
+
+    /// ID of the kettle the water was heated in
+    #[arg(long)]
+    pub kettle_id: Option<i64>,
+
+    /// Measured temperature of the water reaching the coffee
+    #[arg(long)]
+    pub actual_water_temp: Option<f64>,
+
+    /// Quick notes (comma-separated: good,too-fast,too-slow,too-hot,under-extracted,over-extracted, or custom note labels)
     #[arg(long, value_delimiter = ',')]
     pub quick_notes: Option<Vec<String>>,
 
@@ -179,7 +234,7 @@ pub async fn update_brew(client: &BrewlogClient, command: UpdateBrewCommand) ->
     let quick_notes = command.quick_notes.map(|notes| {
         notes
             .iter()
-            .filter_map(|s| QuickNote::from_str_value(s))
+            .filter_map(|s| QuickNote::from_value(s))
             .collect()
     });
     let payload = UpdateBrew {
@@ -191,10 +246,14 @@ pub async fn update_brew(client: &BrewlogClient, command: UpdateBrewCommand) ->
         filter_paper_id: command.filter_paper_id.map(GearId::new),
         water_volume: command.water_volume,
         water_temp: command.water_temp,
+        bypass_water_ml: command.bypass_water_ml,
+        kettle_id: command.kettle_id.map(GearId::new),
+        actual_water_temp: command.actual_water_temp,
         quick_notes,
         brew_time: command.brew_time,
         created_at,
     };
+    payload.validate()?;
 
     let brew = client
         .brews()
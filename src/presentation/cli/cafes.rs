@@ -1,11 +1,14 @@
 use anyhow::Result;
 use clap::{Args, Subcommand};
 
+use super::images::{self, ImageCommands};
 use super::macros::{define_delete_command, define_get_command};
 use super::parse_created_at;
 use super::print_json;
 use crate::domain::cafes::{NewCafe, UpdateCafe};
+use crate::domain::entity_type::EntityType;
 use crate::domain::ids::CafeId;
+use crate::domain::validation::Validate;
 use crate::infrastructure::client::BrewlogClient;
 
 #[derive(Debug, Subcommand)]
@@ -20,6 +23,11 @@ pub enum CafeCommands {
     Update(UpdateCafeCommand),
     /// Delete a cafe
     Delete(DeleteCafeCommand),
+    /// Upload, download or remove the photo
+    Image {
+        #[command(subcommand)]
+        command: ImageCommands,
+    },
 }
 
 pub async fn run(client: &BrewlogClient, cmd: CafeCommands) -> Result<()> {
@@ -29,6 +37,7 @@ pub async fn run(client: &BrewlogClient, cmd: CafeCommands) -> Result<()> {
         CafeCommands::Get(c) => get_cafe(client, c).await,
         CafeCommands::Update(c) => update_cafe(client, c).await,
         CafeCommands::Delete(c) => delete_cafe(client, c).await,
+        CafeCommands::Image { command } => images::run(client, EntityType::Cafe, command).await,
     }
 }
 
@@ -65,6 +74,7 @@ pub async fn add_cafe(client: &BrewlogClient, command: AddCafeCommand) -> Result
         website: command.website,
         created_at,
     };
+    payload.validate()?;
 
     let cafe = client.cafes().create(&payload).await?;
     print_json(&cafe)
@@ -112,6 +122,7 @@ pub async fn update_cafe(client: &BrewlogClient, command: UpdateCafeCommand) ->
         website: command.website,
         created_at,
     };
+    payload.validate()?;
 
     let cafe = client
         .cafes()
@@ -0,0 +1,333 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use clap::parser::ValueSource;
+use clap::{ArgMatches, CommandFactory, FromArgMatches, Parser, Subcommand};
+use serde::Deserialize;
+
+use super::{LogFormat, ServeCommand};
+use crate::application::services::maintenance::MaintenanceSchedule;
+use crate::application::tenants::{TenantRouting, TenantsConfig};
+use crate::application::{HttpConfig, ServerConfig};
+use crate::infrastructure::auth::{TokenHashScheme, TokenHasher};
+use crate::infrastructure::image_processing::ImageSettings;
+use crate::infrastructure::listener::BindAddress;
+
+#[derive(Debug, Subcommand)]
+pub enum ConfigCommands {
+    /// Check a server config file, with any `BREWLOG_*` environment
+    /// variables applied over it, without starting the server
+    Validate {
+        #[arg(long, env = "BREWLOG_CONFIG")]
+        config: PathBuf,
+    },
+}
+
+/// Settings for `brewlog serve` read from a TOML file. Keys are the flag
+/// names with underscores (`bind_address`, `image_jpeg_quality`, ...), and
+/// anything left out keeps its default.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ServeConfigFile {
+    database_url: Option<String>,
+    bind_address: Option<BindAddress>,
+    rp_id: Option<String>,
+    rp_origin: Option<String>,
+    insecure_cookies: Option<bool>,
+    dev: Option<bool>,
+    demo: Option<bool>,
+    theme_dir: Option<PathBuf>,
+    openrouter_api_key: Option<String>,
+    openrouter_model: Option<String>,
+    foursquare_api_key: Option<String>,
+    token_hash: Option<TokenHashScheme>,
+    token_hash_memory_kib: Option<u32>,
+    token_hash_iterations: Option<u32>,
+    token_hash_parallelism: Option<u32>,
+    http2: Option<bool>,
+    keep_alive: Option<bool>,
+    header_read_timeout_secs: Option<u64>,
+    http2_keep_alive_interval_secs: Option<u64>,
+    http2_max_concurrent_streams: Option<u32>,
+    image_max_dimension: Option<u32>,
+    image_thumbnail_size: Option<u32>,
+    image_jpeg_quality: Option<u8>,
+    image_max_upload_dimension: Option<u32>,
+    image_max_file_size_mib: Option<usize>,
+    maintenance_interval_hours: Option<u64>,
+    maintenance_vacuum: Option<bool>,
+    slow_query_threshold_ms: Option<u64>,
+    tenants_dir: Option<PathBuf>,
+    tenant_routing: Option<TenantRouting>,
+    tenant_admin_token: Option<String>,
+    log_format: Option<LogFormat>,
+    log_level: Option<String>,
+    log_filters: Option<Vec<String>>,
+}
+
+impl ServeConfigFile {
+    pub fn load(path: &Path) -> Result<Self> {
+        let raw = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read config file {}", path.display()))?;
+        toml::from_str(&raw).with_context(|| format!("invalid config file {}", path.display()))
+    }
+}
+
+/// Whether `id` was given as a flag or environment variable, which both
+/// outrank the config file.
+fn given(matches: &ArgMatches, id: &str) -> bool {
+    matches!(
+        matches.value_source(id),
+        Some(ValueSource::CommandLine | ValueSource::EnvVariable)
+    )
+}
+
+impl ServeCommand {
+    /// Take each setting from `file` unless it was given as a flag or
+    /// environment variable. `matches` are those `self` was parsed from.
+    pub fn layer_file(&mut self, file: ServeConfigFile, matches: &ArgMatches) {
+        macro_rules! layer {
+            ($($field:ident),* $(,)?) => {
+                $(
+                    if let Some(value) = file.$field
+                        && !given(matches, stringify!($field))
+                    {
+                        self.$field = value;
+                    }
+                )*
+            };
+        }
+        macro_rules! layer_optional {
+            ($($target:ident . $field:ident),* $(,)?) => {
+                $(
+                    if let Some(value) = file.$field
+                        && !given(matches, stringify!($field))
+                    {
+                        $target.$field = Some(value);
+                    }
+                )*
+            };
+        }
+
+        layer!(
+            database_url,
+            bind_address,
+            rp_id,
+            rp_origin,
+            insecure_cookies,
+            dev,
+            demo,
+            openrouter_model,
+            token_hash,
+            token_hash_memory_kib,
+            token_hash_iterations,
+            token_hash_parallelism,
+            http2,
+            keep_alive,
+            header_read_timeout_secs,
+            http2_max_concurrent_streams,
+            image_max_dimension,
+            image_thumbnail_size,
+            image_jpeg_quality,
+            image_max_upload_dimension,
+            image_max_file_size_mib,
+            maintenance_interval_hours,
+            maintenance_vacuum,
+            slow_query_threshold_ms,
+            tenant_routing,
+        );
+        let logging = &mut self.logging;
+        layer_optional!(logging.log_format, logging.log_level);
+        if let Some(filters) = file.log_filters
+            && !given(matches, "log_filters")
+        {
+            logging.log_filters = filters;
+        }
+        layer_optional!(
+            self.theme_dir,
+            self.openrouter_api_key,
+            self.foursquare_api_key,
+            self.http2_keep_alive_interval_secs,
+            self.tenants_dir,
+            self.tenant_admin_token,
+        );
+    }
+
+    /// Check the settings and gather them into what the server runs with.
+    pub fn server_config(&self) -> Result<ServerConfig> {
+        let insecure_cookies = self.insecure_cookies
+            || (self.rp_id == "localhost" && self.rp_origin.starts_with("http://localhost"));
+
+        // A demo instance can't reach either integration usefully, so it runs
+        // without keys.
+        let openrouter_api_key = self
+            .openrouter_api_key
+            .clone()
+            .or_else(|| self.demo.then(String::new))
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "BREWLOG_OPENROUTER_API_KEY is required. Set this environment variable \
+                     (or `openrouter_api_key` in the config file) to an OpenRouter API key \
+                     for AI-powered extraction features."
+                )
+            })?;
+
+        let foursquare_api_key = self
+            .foursquare_api_key
+            .clone()
+            .or_else(|| self.demo.then(String::new))
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "BREWLOG_FOURSQUARE_API_KEY is required. Set this environment variable \
+                     (or `foursquare_api_key` in the config file) to a Foursquare API key \
+                     for nearby cafe search."
+                )
+            })?;
+
+        let token_hasher = match self.token_hash {
+            TokenHashScheme::Argon2 => TokenHasher::argon2(
+                self.token_hash_memory_kib,
+                self.token_hash_iterations,
+                self.token_hash_parallelism,
+            )?,
+            TokenHashScheme::Sha256 => TokenHasher::sha256(),
+        };
+
+        let images = ImageSettings::new(
+            self.image_max_dimension,
+            self.image_thumbnail_size,
+            self.image_jpeg_quality,
+            self.image_max_upload_dimension,
+            self.image_max_file_size_mib.saturating_mul(1024 * 1024),
+        )?;
+
+        let http = HttpConfig {
+            http2: self.http2,
+            keep_alive: self.keep_alive,
+            header_read_timeout: Duration::from_secs(self.header_read_timeout_secs),
+            http2_keep_alive_interval: self.http2_keep_alive_interval_secs.map(Duration::from_secs),
+            http2_max_concurrent_streams: self.http2_max_concurrent_streams,
+        };
+
+        let maintenance = (self.maintenance_interval_hours > 0).then(|| MaintenanceSchedule {
+            interval: Duration::from_secs(self.maintenance_interval_hours * 60 * 60),
+            vacuum: self.maintenance_vacuum,
+        });
+
+        let tenants = match (&self.tenants_dir, self.demo) {
+            (None, _) => None,
+            (Some(_), true) => anyhow::bail!("a demo server can't host tenants"),
+            (Some(dir), false) => Some(TenantsConfig {
+                dir: dir.clone(),
+                routing: self.tenant_routing,
+                admin_token: self
+                    .tenant_admin_token
+                    .clone()
+                    .filter(|token| !token.is_empty())
+                    .ok_or_else(|| {
+                        anyhow::anyhow!(
+                            "BREWLOG_TENANT_ADMIN_TOKEN is required to host tenants, to guard \
+                             the tenant admin API."
+                        )
+                    })?,
+            }),
+        };
+
+        Ok(ServerConfig {
+            bind_address: self.bind_address.clone(),
+            http,
+            database_url: self.database_url.clone(),
+            rp_id: self.rp_id.clone(),
+            rp_origin: self.rp_origin.clone(),
+            insecure_cookies,
+            demo: self.demo,
+            token_hasher,
+            openrouter_api_key,
+            openrouter_model: self.openrouter_model.clone(),
+            foursquare_api_key,
+            images,
+            maintenance,
+            tenants,
+        })
+    }
+}
+
+/// `serve`'s arguments on their own, so they can be read from the
+/// environment without the rest of the command line.
+#[derive(Debug, Parser)]
+struct ServeArgs {
+    #[command(flatten)]
+    serve: ServeCommand,
+}
+
+pub fn run(command: ConfigCommands) -> Result<()> {
+    match command {
+        ConfigCommands::Validate { config } => {
+            let matches = ServeArgs::command().try_get_matches_from(["brewlog"])?;
+            let mut serve = ServeCommand::from_arg_matches(&matches)?;
+            serve.layer_file(ServeConfigFile::load(&config)?, &matches);
+            let directives = serve.logging.filter_directives();
+            tracing_subscriber::EnvFilter::try_new(&directives)
+                .map_err(|err| anyhow::anyhow!("invalid log filter '{directives}': {err}"))?;
+            serve.server_config()?;
+            println!("{} is valid", config.display());
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layered(args: &[&str], file: &str) -> ServeCommand {
+        let matches = ServeArgs::command()
+            .try_get_matches_from(std::iter::once("brewlog").chain(args.iter().copied()))
+            .unwrap();
+        let mut serve = ServeCommand::from_arg_matches(&matches).unwrap();
+        serve.layer_file(toml::from_str(file).unwrap(), &matches);
+        serve
+    }
+
+    #[test]
+    fn file_settings_replace_defaults() {
+        let serve = layered(
+            &[],
+            r#"
+            bind_address = "0.0.0.0:8080"
+            token_hash = "sha256"
+            image_jpeg_quality = 70
+            theme_dir = "/srv/theme"
+            log_format = "json"
+            log_filters = ["sqlx=warn"]
+            "#,
+        );
+
+        assert_eq!(serve.bind_address.to_string(), "0.0.0.0:8080");
+        assert_eq!(serve.token_hash, TokenHashScheme::Sha256);
+        assert_eq!(serve.image_jpeg_quality, 70);
+        assert_eq!(serve.theme_dir, Some(PathBuf::from("/srv/theme")));
+        assert_eq!(serve.logging.log_format, Some(LogFormat::Json));
+        assert_eq!(serve.logging.log_filters, ["sqlx=warn"]);
+        assert_eq!(serve.image_max_dimension, 1200, "unset keys keep defaults");
+    }
+
+    #[test]
+    fn flags_outrank_the_file() {
+        let serve = layered(
+            &["--image-jpeg-quality", "90", "--http2", "false"],
+            "image_jpeg_quality = 70\nhttp2 = true\nkeep_alive = false",
+        );
+
+        assert_eq!(serve.image_jpeg_quality, 90);
+        assert!(!serve.http2);
+        assert!(!serve.keep_alive);
+    }
+
+    #[test]
+    fn unknown_keys_are_rejected() {
+        let err = toml::from_str::<ServeConfigFile>("bind_adress = \"0.0.0.0:80\"").unwrap_err();
+        assert!(err.to_string().contains("bind_adress"));
+    }
+}
@@ -1,11 +1,14 @@
 use anyhow::Result;
 use clap::{Args, Subcommand};
 
+use super::images::{self, ImageCommands};
 use super::macros::{define_delete_command, define_get_command};
 use super::parse_created_at;
 use super::print_json;
 use crate::domain::cups::{NewCup, UpdateCup};
-use crate::domain::ids::{CafeId, CupId, RoastId};
+use crate::domain::entity_type::EntityType;
+use crate::domain::ids::{BagId, CafeId, CupId, RoastId};
+use crate::domain::validation::Validate;
 use crate::infrastructure::client::BrewlogClient;
 
 #[derive(Debug, Subcommand)]
@@ -20,6 +23,11 @@ pub enum CupCommands {
     Update(UpdateCupCommand),
     /// Delete a cup
     Delete(DeleteCupCommand),
+    /// Upload, download or remove the photo
+    Image {
+        #[command(subcommand)]
+        command: ImageCommands,
+    },
 }
 
 pub async fn run(client: &BrewlogClient, cmd: CupCommands) -> Result<()> {
@@ -29,6 +37,7 @@ pub async fn run(client: &BrewlogClient, cmd: CupCommands) -> Result<()> {
         CupCommands::Get(c) => get_cup(client, c).await,
         CupCommands::Update(c) => update_cup(client, c).await,
         CupCommands::Delete(c) => delete_cup(client, c).await,
+        CupCommands::Image { command } => images::run(client, EntityType::Cup, command).await,
     }
 }
 
@@ -36,8 +45,15 @@ pub async fn run(client: &BrewlogClient, cmd: CupCommands) -> Result<()> {
 pub struct AddCupCommand {
     #[arg(long)]
     pub roast_id: i64,
+    /// ID of the cafe; leave out for a cup drunk elsewhere
     #[arg(long)]
-    pub cafe_id: i64,
+    pub cafe_id: Option<i64>,
+    /// ID of the bag the cup was brewed from
+    #[arg(long)]
+    pub bag_id: Option<i64>,
+    /// Stars out of five
+    #[arg(long)]
+    pub rating: Option<u8>,
     /// Override creation timestamp (e.g. 2025-08-05T10:00:00Z or 2025-08-05)
     #[arg(long)]
     pub created_at: Option<String>,
@@ -50,9 +66,12 @@ pub async fn add_cup(client: &BrewlogClient, command: AddCupCommand) -> Result<(
         .transpose()?;
     let payload = NewCup {
         roast_id: RoastId::new(command.roast_id),
-        cafe_id: CafeId::new(command.cafe_id),
+        cafe_id: command.cafe_id.map(CafeId::new),
+        bag_id: command.bag_id.map(BagId::new),
+        rating: command.rating,
         created_at,
     };
+    payload.validate()?;
 
     let cup = client.cups().create(&payload).await?;
     print_json(&cup)
@@ -73,9 +92,29 @@ pub struct UpdateCupCommand {
     pub roast_id: Option<i64>,
 
     /// ID of the cafe
-    #[arg(long)]
+    #[arg(long, conflicts_with = "no_cafe")]
     pub cafe_id: Option<i64>,
 
+    /// Detach the cup from its cafe
+    #[arg(long)]
+    pub no_cafe: bool,
+
+    /// ID of the bag the cup was brewed from
+    #[arg(long, conflicts_with = "no_bag")]
+    pub bag_id: Option<i64>,
+
+    /// Unlink the cup from its bag
+    #[arg(long)]
+    pub no_bag: bool,
+
+    /// Stars out of five
+    #[arg(long, conflicts_with = "no_rating")]
+    pub rating: Option<u8>,
+
+    /// Clear the cup's rating
+    #[arg(long)]
+    pub no_rating: bool,
+
     /// Override creation timestamp (e.g. 2025-08-05T10:00:00Z or 2025-08-05)
     #[arg(long)]
     pub created_at: Option<String>,
@@ -88,9 +127,12 @@ pub async fn update_cup(client: &BrewlogClient, command: UpdateCupCommand) -> Re
         .transpose()?;
     let payload = UpdateCup {
         roast_id: command.roast_id.map(RoastId::new),
-        cafe_id: command.cafe_id.map(CafeId::new),
+        cafe_id: clearable(command.cafe_id.map(CafeId::new), command.no_cafe),
+        bag_id: clearable(command.bag_id.map(BagId::new), command.no_bag),
+        rating: clearable(command.rating, command.no_rating),
         created_at,
     };
+    payload.validate()?;
 
     let cup = client
         .cups()
@@ -99,5 +141,10 @@ pub async fn update_cup(client: &BrewlogClient, command: UpdateCupCommand) -> Re
     print_json(&cup)
 }
 
+#[allow(clippy::option_option)]
+fn clearable<T>(value: Option<T>, clear: bool) -> Option<Option<T>> {
+    if clear { Some(None) } else { value.map(Some) }
+}
+
 define_get_command!(GetCupCommand, get_cup, CupId, cups);
 define_delete_command!(DeleteCupCommand, delete_cup, CupId, cups, "cup");
@@ -0,0 +1,665 @@
+use std::collections::HashSet;
+use std::io::Cursor;
+
+use anyhow::{Context, Result, bail};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use chrono::{DateTime, Duration, Utc};
+use clap::{Args, Subcommand};
+use rand::rngs::StdRng;
+use rand::seq::IndexedRandom;
+use rand::{RngExt, SeedableRng};
+
+use crate::domain::brews::QuickNote;
+use crate::domain::cafes::NewCafe;
+use crate::domain::cups::NewCup;
+use crate::domain::entity_type::EntityType;
+use crate::domain::gear::{GearCategory, NewGear};
+use crate::domain::ids::{BagId, CafeId, GearId, RoastId, RoasterId};
+use crate::domain::roasters::NewRoaster;
+use crate::domain::roasts::NewRoast;
+use crate::infrastructure::client::BrewlogClient;
+
+#[derive(Debug, Subcommand)]
+pub enum DevCommands {
+    /// Fill a server with realistic fake data for testing and screenshots
+    Seed(SeedCommand),
+}
+
+#[derive(Debug, Args)]
+pub struct SeedCommand {
+    #[arg(long, default_value_t = 5)]
+    pub roasters: usize,
+    /// Number of roasts, spread across the roasters (default: two each)
+    #[arg(long)]
+    pub roasts: Option<usize>,
+    #[arg(long, default_value_t = 100)]
+    pub brews: usize,
+    #[arg(long, default_value_t = 3)]
+    pub cafes: usize,
+    #[arg(long, default_value_t = 10)]
+    pub cups: usize,
+    /// Spread brews and cups over this many days up to now
+    #[arg(long, default_value_t = 180)]
+    pub days: i64,
+    /// Upload a placeholder image for each roaster and roast
+    #[arg(long)]
+    pub images: bool,
+    /// Random seed, for repeatable output
+    #[arg(long)]
+    pub seed: Option<u64>,
+}
+
+impl SeedCommand {
+    fn roast_count(&self) -> usize {
+        self.roasts.unwrap_or(self.roasters * 2)
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.days < 1 {
+            bail!("--days must be at least 1");
+        }
+        if self.roast_count() > 0 && self.roasters == 0 {
+            bail!("roasts need at least one roaster");
+        }
+        if self.brews > 0 && self.roast_count() == 0 {
+            bail!("brews need at least one roast");
+        }
+        if self.cups > 0 && (self.cafes == 0 || self.roast_count() == 0) {
+            bail!("cups need at least one cafe and one roast");
+        }
+        Ok(())
+    }
+}
+
+pub async fn run(client: &BrewlogClient, cmd: DevCommands) -> Result<()> {
+    match cmd {
+        DevCommands::Seed(c) => seed(client, c).await,
+    }
+}
+
+const ROASTER_PREFIXES: &[&str] = &[
+    "Copper", "Northern", "Little", "Wild", "Golden", "Hidden", "Common", "Foundry", "Harbour",
+    "Lantern", "Stone", "Kiln", "Field", "Ember", "Atlas", "Marlow",
+];
+
+const ROASTER_SUFFIXES: &[&str] = &[
+    "Coffee Roasters",
+    "Roastery",
+    "Coffee Co.",
+    "Coffee",
+    "Roasting Works",
+];
+
+const ROASTER_LOCATIONS: &[(&str, &str)] = &[
+    ("London", "United Kingdom"),
+    ("Bristol", "United Kingdom"),
+    ("Edinburgh", "United Kingdom"),
+    ("Berlin", "Germany"),
+    ("Copenhagen", "Denmark"),
+    ("Oslo", "Norway"),
+    ("Amsterdam", "Netherlands"),
+    ("Melbourne", "Australia"),
+    ("Portland", "United States"),
+    ("Tokyo", "Japan"),
+];
+
+const ORIGINS: &[(&str, &[&str])] = &[
+    ("Ethiopia", &["Yirgacheffe", "Guji", "Sidamo"]),
+    ("Kenya", &["Nyeri", "Kiambu", "Kirinyaga"]),
+    ("Colombia", &["Huila", "Nariño", "Cauca"]),
+    ("Brazil", &["Cerrado", "Sul de Minas", "Mogiana"]),
+    ("Guatemala", &["Huehuetenango", "Antigua"]),
+    ("Rwanda", &["Nyamasheke", "Huye"]),
+    ("Costa Rica", &["Tarrazú", "West Valley"]),
+    ("Peru", &["Cajamarca"]),
+    ("Panama", &["Boquete"]),
+    ("Burundi", &["Kayanza"]),
+];
+
+const PROCESSES: &[&str] = &["Washed", "Washed", "Natural", "Honey", "Anaerobic"];
+
+const PRODUCERS: &[&str] = &[
+    "Smallholder Farmers",
+    "Finca La Esperanza",
+    "Cooperative Members",
+    "Hacienda Santa Rosa",
+    "Kamwangi Factory",
+    "Familia Aguilar",
+    "Buncho Washing Station",
+    "Sitio Boa Vista",
+];
+
+const TASTING_NOTES: &[&str] = &[
+    "Blueberry",
+    "Jasmine",
+    "Bergamot",
+    "Milk Chocolate",
+    "Dark Chocolate",
+    "Caramel",
+    "Hazelnut",
+    "Red Apple",
+    "Peach",
+    "Apricot",
+    "Blackcurrant",
+    "Grapefruit",
+    "Lemon",
+    "Brown Sugar",
+    "Toffee",
+    "Cherry",
+    "Plum",
+    "Raspberry",
+    "Black Tea",
+    "Honey",
+    "Orange",
+    "Vanilla",
+];
+
+/// Make, model, and whether it is an immersion brewer.
+const BREWERS: &[(&str, &str, bool)] =
+    &[("Hario", "V60 02", false), ("AeroPress", "Original", true)];
+
+const GRINDERS: &[(&str, &str)] = &[("Comandante", "C40 MK4"), ("1Zpresso", "ZP6 Special")];
+
+const FILTER_PAPERS: &[(&str, &str)] = &[
+    ("Hario", "V60 Tabbed 02"),
+    ("AeroPress", "Paper Micro-Filters"),
+];
+
+/// Name, city, country, latitude, longitude.
+const CAFES: &[(&str, &str, &str, f64, f64)] = &[
+    (
+        "Prufrock Coffee",
+        "London",
+        "United Kingdom",
+        51.5198,
+        -0.1097,
+    ),
+    (
+        "Coffee Collective",
+        "Copenhagen",
+        "Denmark",
+        55.6903,
+        12.5522,
+    ),
+    ("Bonanza Coffee", "Berlin", "Germany", 52.5381, 13.4123),
+    ("Fuglen", "Oslo", "Norway", 59.9152, 10.7380),
+    ("Lot Sixty One", "Amsterdam", "Netherlands", 52.3664, 4.8789),
+    ("Market Lane", "Melbourne", "Australia", -37.8076, 144.9568),
+];
+
+const BAG_SIZES: &[f64] = &[250.0, 250.0, 250.0, 340.0, 500.0];
+
+struct SeededBag {
+    id: BagId,
+    remaining: f64,
+}
+
+/// A brewer, the filter paper used with it, and whether it is immersion.
+type BrewerSetup = (GearId, GearId, bool);
+
+/// Shared state for one seeding run.
+struct Seeder<'a> {
+    client: &'a BrewlogClient,
+    rng: StdRng,
+    images: bool,
+    /// When roasters, gear and cafes appear, ahead of the first brew.
+    setup: DateTime<Utc>,
+    /// Brews and cups fall between `start` and `end`.
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+}
+
+async fn seed(client: &BrewlogClient, cmd: SeedCommand) -> Result<()> {
+    cmd.validate()?;
+
+    let end = Utc::now();
+    let start = end - Duration::days(cmd.days);
+    let mut seeder = Seeder {
+        client,
+        rng: match cmd.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => rand::make_rng(),
+        },
+        images: cmd.images,
+        setup: start - Duration::days(30),
+        start,
+        end,
+    };
+
+    let (grinders, brewers) = seeder.gear().await?;
+    eprintln!("Creating {} roasters...", cmd.roasters);
+    let roasters = seeder.roasters(cmd.roasters).await?;
+    let roast_count = cmd.roast_count();
+    eprintln!("Creating {roast_count} roasts...");
+    let roasts = seeder.roasts(&roasters, roast_count).await?;
+    eprintln!("Creating {} brews...", cmd.brews);
+    let bags = seeder
+        .brews(&roasts, &grinders, &brewers, cmd.brews)
+        .await?;
+    let cafes = seeder.cafes(cmd.cafes).await?;
+    seeder.cups(&roasts, &cafes, cmd.cups).await?;
+
+    eprintln!(
+        "Seeded {} roasters, {roast_count} roasts, {bags} bags, {} brews, {} gear, {} cafes and {} cups.",
+        cmd.roasters,
+        cmd.brews,
+        grinders.len() + brewers.len() * 2,
+        cmd.cafes,
+        cmd.cups,
+    );
+    Ok(())
+}
+
+impl Seeder<'_> {
+    async fn gear(&mut self) -> Result<(Vec<GearId>, Vec<BrewerSetup>)> {
+        let gear = self.client.gear();
+        let new_gear = |category, make: &str, model: &str, water_temp| NewGear {
+            category,
+            make: make.to_string(),
+            model: model.to_string(),
+            water_temp,
+            brew_method: None,
+            uses_filter: None,
+            temp_offset: None,
+            water_hardness: None,
+            stock_count: None,
+            low_stock_threshold: None,
+            created_at: Some(self.setup),
+        };
+
+        let mut grinders = Vec::new();
+        for (make, model) in GRINDERS {
+            let grinder = gear
+                .create(&new_gear(GearCategory::Grinder, make, model, None))
+                .await?;
+            grinders.push(grinder.id);
+        }
+
+        let mut brewers = Vec::new();
+        for ((make, model, immersion), (paper_make, paper_model)) in
+            BREWERS.iter().zip(FILTER_PAPERS)
+        {
+            let water_temp = if *immersion { 85.0 } else { 93.0 };
+            let brewer = gear
+                .create(&new_gear(
+                    GearCategory::Brewer,
+                    make,
+                    model,
+                    Some(water_temp),
+                ))
+                .await?;
+            let paper = gear
+                .create(&new_gear(
+                    GearCategory::FilterPaper,
+                    paper_make,
+                    paper_model,
+                    None,
+                ))
+                .await?;
+            brewers.push((brewer.id, paper.id, *immersion));
+        }
+        Ok((grinders, brewers))
+    }
+
+    async fn roasters(&mut self, count: usize) -> Result<Vec<RoasterId>> {
+        let mut used_names = HashSet::new();
+        let mut roasters = Vec::with_capacity(count);
+        for _ in 0..count {
+            let rng = &mut self.rng;
+            let base = format!(
+                "{} {}",
+                ROASTER_PREFIXES.choose(rng).unwrap_or(&"Brewlog"),
+                ROASTER_SUFFIXES.choose(rng).unwrap_or(&"Coffee"),
+            );
+            let (city, country) = ROASTER_LOCATIONS
+                .choose(rng)
+                .copied()
+                .unwrap_or(("London", "United Kingdom"));
+            let payload = NewRoaster {
+                name: unique_name(&base, &mut used_names),
+                country: country.to_string(),
+                city: Some(city.to_string()),
+                homepage: None,
+                created_at: Some(self.setup + Duration::days(rng.random_range(0..14))),
+            };
+            let roaster = self.client.roasters().create(&payload).await?;
+            self.placeholder(EntityType::Roaster, roaster.id.into_inner())
+                .await?;
+            roasters.push(roaster.id);
+        }
+        Ok(roasters)
+    }
+
+    async fn roasts(&mut self, roasters: &[RoasterId], count: usize) -> Result<Vec<RoastId>> {
+        let mut used_names = HashSet::new();
+        let mut roasts = Vec::with_capacity(count);
+        for i in 0..count {
+            let rng = &mut self.rng;
+            let (origin, regions) = ORIGINS.choose(rng).copied().unwrap_or(ORIGINS[0]);
+            let region = regions.choose(rng).copied().unwrap_or(origin);
+            let process = PROCESSES.choose(rng).copied().unwrap_or("Washed");
+            let payload = NewRoast {
+                roaster_id: roasters[i % roasters.len()],
+                name: unique_name(&format!("{region} {process}"), &mut used_names),
+                origin: origin.to_string(),
+                region: region.to_string(),
+                producer: PRODUCERS
+                    .choose(rng)
+                    .copied()
+                    .unwrap_or("Smallholder Farmers")
+                    .to_string(),
+                tasting_notes: TASTING_NOTES
+                    .sample(rng, 3)
+                    .map(|note| (*note).to_string())
+                    .collect(),
+                process: process.to_string(),
+                components: Vec::new(),
+                harvest_year: None,
+                created_at: Some(self.start - Duration::days(rng.random_range(0..14))),
+            };
+            let roast = self.client.roasts().create(&payload).await?;
+            self.placeholder(EntityType::Roast, roast.roast.id.into_inner())
+                .await?;
+            roasts.push(roast.roast.id);
+        }
+        Ok(roasts)
+    }
+
+    /// Log `count` brews, working through one bag at a time and opening the
+    /// next when the current one can't cover a dose. Returns the number of
+    /// bags opened.
+    async fn brews(
+        &mut self,
+        roasts: &[RoastId],
+        grinders: &[GearId],
+        brewers: &[BrewerSetup],
+        count: usize,
+    ) -> Result<usize> {
+        let mut bag: Option<SeededBag> = None;
+        let mut bags_opened = 0;
+        let mut last_brew = self.start;
+        for brewed_at in spread_times(&mut self.rng, count, self.start, self.end) {
+            let rng = &mut self.rng;
+            let (brewer_id, filter_paper_id, immersion) =
+                *brewers.choose(rng).context("no brewers")?;
+            let dose: f64 = if immersion {
+                *[12.0, 15.0].choose(rng).unwrap_or(&15.0)
+            } else {
+                *[15.0, 18.0, 20.0].choose(rng).unwrap_or(&15.0)
+            };
+
+            if bag.as_ref().is_none_or(|b| b.remaining < dose) {
+                if let Some(finished) = bag.take() {
+                    self.client
+                        .bags()
+                        .update(
+                            finished.id,
+                            None,
+                            Some(true),
+                            Some(last_brew),
+                            None,
+                            None,
+                            None,
+                            None,
+                            None,
+                            None,
+                        )
+                        .await?;
+                }
+                bag = Some(self.open_bag(roasts, brewed_at).await?);
+                bags_opened += 1;
+            }
+            let Some(current) = bag.as_mut() else {
+                continue;
+            };
+
+            let rng = &mut self.rng;
+            let grinder_id = *grinders.choose(rng).context("no grinders")?;
+            let ratio = if immersion { 15.0 } else { 16.5 };
+            #[allow(clippy::cast_possible_truncation)]
+            let water_volume = (dose * ratio).round() as i32;
+            let (water_temp, brew_time) = if immersion {
+                (rng.random_range(80..=90_u8), rng.random_range(90..150))
+            } else {
+                (rng.random_range(92..=96_u8), rng.random_range(150..210))
+            };
+            self.client
+                .brews()
+                .create(
+                    current.id,
+                    dose,
+                    grinder_id,
+                    f64::from(rng.random_range(14..30_u8)),
+                    brewer_id,
+                    Some(filter_paper_id),
+                    water_volume,
+                    f64::from(water_temp),
+                    None,
+                    None,
+                    None,
+                    random_quick_notes(rng),
+                    Some(brew_time),
+                    Some(brewed_at),
+                )
+                .await?;
+            current.remaining -= dose;
+            last_brew = brewed_at;
+        }
+        Ok(bags_opened)
+    }
+
+    async fn open_bag(
+        &mut self,
+        roasts: &[RoastId],
+        first_brew: DateTime<Utc>,
+    ) -> Result<SeededBag> {
+        let rng = &mut self.rng;
+        let roast_id = *roasts.choose(rng).context("no roasts")?;
+        let amount = *BAG_SIZES.choose(rng).unwrap_or(&250.0);
+        let opened_at = first_brew - Duration::hours(1);
+        let roast_date = (opened_at - Duration::days(rng.random_range(3..14))).date_naive();
+        let price = (amount / 1000.0 * f64::from(rng.random_range(30..70))).round();
+        let created = self
+            .client
+            .bags()
+            .create(
+                roast_id,
+                Some(roast_date),
+                amount,
+                Some(price),
+                None,
+                Some(roast_date + Duration::days(90)),
+                None,
+                Some(opened_at),
+            )
+            .await?;
+        Ok(SeededBag {
+            id: created.bag.id,
+            remaining: amount,
+        })
+    }
+
+    async fn cafes(&mut self, count: usize) -> Result<Vec<CafeId>> {
+        let mut used_names = HashSet::new();
+        let mut cafes = Vec::with_capacity(count);
+        for i in 0..count {
+            let (name, city, country, latitude, longitude) = CAFES[i % CAFES.len()];
+            let payload = NewCafe {
+                name: unique_name(name, &mut used_names),
+                city: city.to_string(),
+                country: country.to_string(),
+                latitude,
+                longitude,
+                website: None,
+                created_at: Some(self.setup + Duration::days(self.rng.random_range(0..30))),
+            };
+            cafes.push(self.client.cafes().create(&payload).await?.id);
+        }
+        Ok(cafes)
+    }
+
+    async fn cups(&mut self, roasts: &[RoastId], cafes: &[CafeId], count: usize) -> Result<()> {
+        for visited_at in spread_times(&mut self.rng, count, self.start, self.end) {
+            let payload = NewCup {
+                roast_id: *roasts.choose(&mut self.rng).context("no roasts")?,
+                cafe_id: Some(*cafes.choose(&mut self.rng).context("no cafes")?),
+                bag_id: None,
+                rating: Some(self.rng.random_range(2..=5)),
+                created_at: Some(visited_at),
+            };
+            self.client.cups().create(&payload).await?;
+        }
+        Ok(())
+    }
+
+    /// Upload a placeholder image for an entity, if `--images` was given.
+    async fn placeholder(&mut self, entity_type: EntityType, id: i64) -> Result<()> {
+        if !self.images {
+            return Ok(());
+        }
+        let data_url = placeholder_image(&mut self.rng)?;
+        self.client
+            .images()
+            .upload(entity_type, id, &data_url)
+            .await
+    }
+}
+
+/// `base`, or `base 2`, `base 3`... if it has been used already.
+fn unique_name(base: &str, used: &mut HashSet<String>) -> String {
+    let mut name = base.to_string();
+    let mut n = 2;
+    while !used.insert(name.clone()) {
+        name = format!("{base} {n}");
+        n += 1;
+    }
+    name
+}
+
+/// `count` random instants between `start` and `end`, in order.
+fn spread_times(
+    rng: &mut StdRng,
+    count: usize,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Vec<DateTime<Utc>> {
+    let span = (end - start).num_seconds().max(1);
+    let mut times: Vec<_> = (0..count)
+        .map(|_| start + Duration::seconds(rng.random_range(0..span)))
+        .collect();
+    times.sort();
+    times
+}
+
+/// Mostly "Good", sometimes a complaint, sometimes nothing.
+fn random_quick_notes(rng: &mut StdRng) -> Vec<QuickNote> {
+    let roll: f64 = rng.random();
+    if roll < 0.6 {
+        vec![QuickNote::Good]
+    } else if roll < 0.85 {
+        QuickNote::all()[1..]
+            .choose(rng)
+            .cloned()
+            .into_iter()
+            .collect()
+    } else {
+        Vec::new()
+    }
+}
+
+/// A small PNG with a diagonal gradient between two random colours, as a
+/// `data:` URL.
+fn placeholder_image(rng: &mut StdRng) -> Result<String> {
+    const SIZE: u32 = 256;
+    let from: [u8; 3] = rng.random();
+    let to: [u8; 3] = rng.random();
+    let image = image::RgbImage::from_fn(SIZE, SIZE, |x, y| {
+        let t = f64::from(x + y) / f64::from(2 * (SIZE - 1));
+        image::Rgb(std::array::from_fn(|i| {
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let channel = (f64::from(from[i]) * (1.0 - t) + f64::from(to[i]) * t).round() as u8;
+            channel
+        }))
+    });
+
+    let mut png = Vec::new();
+    image
+        .write_to(&mut Cursor::new(&mut png), image::ImageFormat::Png)
+        .context("failed to encode placeholder image")?;
+    Ok(format!("data:image/png;base64,{}", STANDARD.encode(png)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seed_command() -> SeedCommand {
+        SeedCommand {
+            roasters: 2,
+            roasts: None,
+            brews: 10,
+            cafes: 1,
+            cups: 1,
+            days: 30,
+            images: false,
+            seed: Some(1),
+        }
+    }
+
+    #[test]
+    fn unique_name_numbers_repeats() {
+        let mut used = HashSet::new();
+        assert_eq!(unique_name("Kiln Coffee", &mut used), "Kiln Coffee");
+        assert_eq!(unique_name("Kiln Coffee", &mut used), "Kiln Coffee 2");
+        assert_eq!(unique_name("Kiln Coffee", &mut used), "Kiln Coffee 3");
+    }
+
+    #[test]
+    fn spread_times_are_ordered_and_in_range() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let end = Utc::now();
+        let start = end - Duration::days(90);
+        let times = spread_times(&mut rng, 200, start, end);
+        assert_eq!(times.len(), 200);
+        assert!(times.is_sorted());
+        assert!(times.iter().all(|t| *t >= start && *t <= end));
+    }
+
+    #[test]
+    fn roasts_default_to_two_per_roaster() {
+        assert_eq!(seed_command().roast_count(), 4);
+    }
+
+    #[test]
+    fn validate_rejects_brews_without_roasts() {
+        let cmd = SeedCommand {
+            roasts: Some(0),
+            ..seed_command()
+        };
+        assert!(cmd.validate().is_err());
+        assert!(seed_command().validate().is_ok());
+    }
+
+    #[test]
+    fn validate_rejects_cups_without_cafes() {
+        let cmd = SeedCommand {
+            cafes: 0,
+            ..seed_command()
+        };
+        assert!(cmd.validate().is_err());
+    }
+
+    #[test]
+    fn placeholder_image_is_accepted_by_the_image_pipeline() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let data_url = placeholder_image(&mut rng).unwrap();
+        assert!(
+            crate::infrastructure::image_processing::process_data_url(
+                &data_url,
+                &Default::default()
+            )
+            .is_ok()
+        );
+    }
+}
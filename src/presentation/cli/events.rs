@@ -0,0 +1,147 @@
+use anyhow::Result;
+use clap::{Args, Subcommand};
+
+use super::macros::{define_delete_command, define_get_command};
+use super::{parse_date, print_json};
+use crate::domain::events::{EventFilter, NewEvent, UpdateEvent};
+use crate::domain::ids::{CafeId, EventId, RoasterId};
+use crate::domain::validation::Validate;
+use crate::infrastructure::client::BrewlogClient;
+
+#[derive(Debug, Subcommand)]
+pub enum EventCommands {
+    /// Log a coffee event attended
+    Add(AddEventCommand),
+    /// List events
+    List(ListEventsCommand),
+    /// Get an event by ID
+    Get(GetEventCommand),
+    /// Update an event
+    Update(UpdateEventCommand),
+    /// Delete an event
+    Delete(DeleteEventCommand),
+}
+
+pub async fn run(client: &BrewlogClient, cmd: EventCommands) -> Result<()> {
+    match cmd {
+        EventCommands::Add(c) => add_event(client, c).await,
+        EventCommands::List(c) => list_events(client, c).await,
+        EventCommands::Get(c) => get_event(client, c).await,
+        EventCommands::Update(c) => update_event(client, c).await,
+        EventCommands::Delete(c) => delete_event(client, c).await,
+    }
+}
+
+#[derive(Debug, Args)]
+pub struct AddEventCommand {
+    #[arg(long)]
+    pub name: String,
+    /// Day of the event (e.g. 2025-04-18)
+    #[arg(long)]
+    pub date: String,
+    #[arg(long)]
+    pub venue: Option<String>,
+    #[arg(long)]
+    pub notes: Option<String>,
+    /// A coffee tried there (repeatable)
+    #[arg(long = "coffee")]
+    pub coffees: Vec<String>,
+    /// A roaster met there (repeatable)
+    #[arg(long = "roaster-id")]
+    pub roaster_ids: Vec<i64>,
+    /// A cafe met there (repeatable)
+    #[arg(long = "cafe-id")]
+    pub cafe_ids: Vec<i64>,
+}
+
+pub async fn add_event(client: &BrewlogClient, command: AddEventCommand) -> Result<()> {
+    let payload = NewEvent {
+        name: command.name,
+        venue: command.venue,
+        date: parse_date(&command.date)?,
+        notes: command.notes,
+        coffees: command.coffees,
+        roaster_ids: command
+            .roaster_ids
+            .into_iter()
+            .map(RoasterId::new)
+            .collect(),
+        cafe_ids: command.cafe_ids.into_iter().map(CafeId::new).collect(),
+        created_at: None,
+    }
+    .normalize();
+    payload.validate()?;
+
+    let event = client.events().create(&payload).await?;
+    print_json(&event)
+}
+
+#[derive(Debug, Args)]
+pub struct ListEventsCommand {
+    /// Only list events this roaster was met at
+    #[arg(long)]
+    pub roaster_id: Option<i64>,
+    /// Only list events this cafe was met at
+    #[arg(long)]
+    pub cafe_id: Option<i64>,
+}
+
+pub async fn list_events(client: &BrewlogClient, command: ListEventsCommand) -> Result<()> {
+    let filter = EventFilter {
+        roaster_id: command.roaster_id.map(RoasterId::new),
+        cafe_id: command.cafe_id.map(CafeId::new),
+    };
+    let events = client.events().list(filter).await?;
+    print_json(&events)
+}
+
+define_get_command!(GetEventCommand, get_event, EventId, events);
+
+#[derive(Debug, Args)]
+pub struct UpdateEventCommand {
+    #[arg(long)]
+    pub id: i64,
+    #[arg(long)]
+    pub name: Option<String>,
+    #[arg(long)]
+    pub date: Option<String>,
+    #[arg(long)]
+    pub venue: Option<String>,
+    #[arg(long)]
+    pub notes: Option<String>,
+    /// Replace the coffees tried (repeatable)
+    #[arg(long = "coffee")]
+    pub coffees: Option<Vec<String>>,
+    /// Replace the roasters met (repeatable)
+    #[arg(long = "roaster-id")]
+    pub roaster_ids: Option<Vec<i64>>,
+    /// Replace the cafes met (repeatable)
+    #[arg(long = "cafe-id")]
+    pub cafe_ids: Option<Vec<i64>>,
+}
+
+pub async fn update_event(client: &BrewlogClient, command: UpdateEventCommand) -> Result<()> {
+    let payload = UpdateEvent {
+        name: command.name,
+        venue: command.venue,
+        date: command.date.as_deref().map(parse_date).transpose()?,
+        notes: command.notes,
+        coffees: command.coffees,
+        roaster_ids: command
+            .roaster_ids
+            .map(|ids| ids.into_iter().map(RoasterId::new).collect()),
+        cafe_ids: command
+            .cafe_ids
+            .map(|ids| ids.into_iter().map(CafeId::new).collect()),
+    }
+    .normalize();
+    payload.validate()?;
+
+    let event = client
+        .events()
+        .update(EventId::new(command.id), &payload)
+        .await?;
+    print_json(&event)
+}
+
+define_delete_command!(DeleteEventCommand, delete_event, EventId, events, "event");
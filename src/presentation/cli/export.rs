@@ -0,0 +1,19 @@
+use anyhow::Result;
+use clap::Subcommand;
+
+use crate::infrastructure::client::BrewlogClient;
+
+#[derive(Debug, Subcommand)]
+pub enum ExportCommands {
+    /// Export as a Beanconqueror.json (stdout) for the app's import
+    Beanconqueror,
+}
+
+pub async fn run(client: &BrewlogClient, cmd: ExportCommands) -> Result<()> {
+    match cmd {
+        ExportCommands::Beanconqueror => {
+            let export = client.export().beanconqueror().await?;
+            super::print_json(&export)
+        }
+    }
+}
@@ -1,10 +1,16 @@
-use anyhow::Result;
+use std::str::FromStr;
+
+use anyhow::{Result, anyhow};
 use clap::{Args, Subcommand};
 
+use super::images::{self, ImageCommands};
 use super::macros::{define_delete_command, define_get_command};
 use super::parse_created_at;
 use super::print_json;
+use crate::domain::entity_type::EntityType;
+use crate::domain::gear::{BrewMethod, GearCategory, NewGear, Restock, UpdateGear};
 use crate::domain::ids::GearId;
+use crate::domain::validation::Validate;
 use crate::infrastructure::client::BrewlogClient;
 
 #[derive(Debug, Subcommand)]
@@ -19,6 +25,13 @@ pub enum GearCommands {
     Update(UpdateGearCommand),
     /// Delete gear
     Delete(DeleteGearCommand),
+    /// Add newly bought filter papers to the stock count
+    Restock(RestockGearCommand),
+    /// Upload, download or remove the photo
+    Image {
+        #[command(subcommand)]
+        command: ImageCommands,
+    },
 }
 
 pub async fn run(client: &BrewlogClient, cmd: GearCommands) -> Result<()> {
@@ -28,9 +41,19 @@ pub async fn run(client: &BrewlogClient, cmd: GearCommands) -> Result<()> {
         GearCommands::Get(c) => get_gear(client, c).await,
         GearCommands::Update(c) => update_gear(client, c).await,
         GearCommands::Delete(c) => delete_gear(client, c).await,
+        GearCommands::Restock(c) => restock_gear(client, c).await,
+        GearCommands::Image { command } => images::run(client, EntityType::Gear, command).await,
     }
 }
 
+fn parse_brew_method(value: Option<String>) -> Result<Option<BrewMethod>> {
+    value
+        .map(|method| {
+            BrewMethod::from_str(&method).map_err(|()| anyhow!("invalid brew method '{method}'"))
+        })
+        .transpose()
+}
+
 #[derive(Debug, Args)]
 pub struct AddGearCommand {
     #[arg(long)]
@@ -39,6 +62,28 @@ pub struct AddGearCommand {
     pub make: String,
     #[arg(long)]
     pub model: String,
+    /// Water temperature in Celsius that brews with this brewer start at
+    #[arg(long)]
+    pub water_temp: Option<f64>,
+    /// How this brewer brews: immersion or percolation
+    #[arg(long)]
+    pub brew_method: Option<String>,
+    /// Whether this brewer takes a filter paper (true or false)
+    #[arg(long)]
+    pub uses_filter: Option<bool>,
+    /// Degrees Celsius the water reaching the coffee differs from this
+    /// kettle's setting (e.g. -2.5 when it cools on the way)
+    #[arg(long, allow_hyphen_values = true)]
+    pub temp_offset: Option<f64>,
+    /// Hardness of the water this kettle is filled with, in ppm
+    #[arg(long)]
+    pub water_hardness: Option<i32>,
+    /// Filter papers left in stock, counted down by each brew that uses one
+    #[arg(long)]
+    pub stock_count: Option<i32>,
+    /// Warn to reorder once fewer filter papers than this are left
+    #[arg(long)]
+    pub low_stock_threshold: Option<i32>,
     /// Override creation timestamp (e.g. 2025-08-05T10:00:00Z or 2025-08-05)
     #[arg(long)]
     pub created_at: Option<String>,
@@ -49,10 +94,24 @@ pub async fn add_gear(client: &BrewlogClient, command: AddGearCommand) -> Result
         .created_at
         .map(|s| parse_created_at(&s))
         .transpose()?;
-    let gear = client
-        .gear()
-        .create(&command.category, command.make, command.model, created_at)
-        .await?;
+    let category = GearCategory::from_str(&command.category)
+        .map_err(|()| anyhow!("invalid category '{}'", command.category))?;
+    let payload = NewGear {
+        category,
+        make: command.make,
+        model: command.model,
+        water_temp: command.water_temp,
+        brew_method: parse_brew_method(command.brew_method)?,
+        uses_filter: command.uses_filter,
+        temp_offset: command.temp_offset,
+        water_hardness: command.water_hardness,
+        stock_count: command.stock_count,
+        low_stock_threshold: command.low_stock_threshold,
+        created_at,
+    };
+    payload.validate()?;
+
+    let gear = client.gear().create(&payload).await?;
     print_json(&gear)
 }
 
@@ -77,6 +136,28 @@ pub struct UpdateGearCommand {
     pub make: Option<String>,
     #[arg(long)]
     pub model: Option<String>,
+    /// Water temperature in Celsius that brews with this brewer start at
+    #[arg(long)]
+    pub water_temp: Option<f64>,
+    /// How this brewer brews: immersion or percolation
+    #[arg(long)]
+    pub brew_method: Option<String>,
+    /// Whether this brewer takes a filter paper (true or false)
+    #[arg(long)]
+    pub uses_filter: Option<bool>,
+    /// Degrees Celsius the water reaching the coffee differs from this
+    /// kettle's setting (e.g. -2.5 when it cools on the way)
+    #[arg(long, allow_hyphen_values = true)]
+    pub temp_offset: Option<f64>,
+    /// Hardness of the water this kettle is filled with, in ppm
+    #[arg(long)]
+    pub water_hardness: Option<i32>,
+    /// Filter papers left in stock, counted down by each brew that uses one
+    #[arg(long)]
+    pub stock_count: Option<i32>,
+    /// Warn to reorder once fewer filter papers than this are left
+    #[arg(long)]
+    pub low_stock_threshold: Option<i32>,
     /// Override creation timestamp (e.g. 2025-08-05T10:00:00Z or 2025-08-05)
     #[arg(long)]
     pub created_at: Option<String>,
@@ -87,16 +168,47 @@ pub async fn update_gear(client: &BrewlogClient, command: UpdateGearCommand) ->
         .created_at
         .map(|s| parse_created_at(&s))
         .transpose()?;
+    let payload = UpdateGear {
+        make: command.make,
+        model: command.model,
+        water_temp: command.water_temp,
+        brew_method: parse_brew_method(command.brew_method)?,
+        uses_filter: command.uses_filter,
+        temp_offset: command.temp_offset,
+        water_hardness: command.water_hardness,
+        stock_count: command.stock_count,
+        low_stock_threshold: command.low_stock_threshold,
+        created_at,
+    };
+    payload.validate()?;
+
     let gear = client
         .gear()
-        .update(
-            GearId::new(command.id),
-            command.make,
-            command.model,
-            created_at,
-        )
+        .update(GearId::new(command.id), &payload)
         .await?;
     print_json(&gear)
 }
 
 define_delete_command!(DeleteGearCommand, delete_gear, GearId, gear, "gear");
+
+#[derive(Debug, Args)]
+pub struct RestockGearCommand {
+    #[arg(long)]
+    pub id: i64,
+    /// Number of filter papers bought
+    #[arg(long)]
+    pub count: i32,
+}
+
+pub async fn restock_gear(client: &BrewlogClient, command: RestockGearCommand) -> Result<()> {
+    let payload = Restock {
+        count: command.count,
+    };
+    payload.validate()?;
+
+    let gear = client
+        .gear()
+        .restock(GearId::new(command.id), &payload)
+        .await?;
+    print_json(&gear)
+}
@@ -0,0 +1,159 @@
+use anyhow::Result;
+use chrono::NaiveDate;
+use clap::{Args, Subcommand};
+
+use super::macros::{define_delete_command, define_get_command};
+use super::parse_created_at;
+use super::print_json;
+use crate::domain::green_coffees::{NewGreenCoffee, UpdateGreenCoffee};
+use crate::domain::ids::GreenCoffeeId;
+use crate::domain::validation::Validate;
+use crate::infrastructure::client::BrewlogClient;
+
+#[derive(Debug, Subcommand)]
+pub enum GreenCoffeeCommands {
+    /// Add green coffee bought for roasting
+    Add(AddGreenCoffeeCommand),
+    /// List green coffee with what's left of each
+    List,
+    /// Get green coffee by ID
+    Get(GetGreenCoffeeCommand),
+    /// Update green coffee
+    Update(UpdateGreenCoffeeCommand),
+    /// Delete green coffee and its roast batches
+    Delete(DeleteGreenCoffeeCommand),
+}
+
+pub async fn run(client: &BrewlogClient, cmd: GreenCoffeeCommands) -> Result<()> {
+    match cmd {
+        GreenCoffeeCommands::Add(c) => add_green_coffee(client, c).await,
+        GreenCoffeeCommands::List => list_green_coffees(client).await,
+        GreenCoffeeCommands::Get(c) => get_green_coffee(client, c).await,
+        GreenCoffeeCommands::Update(c) => update_green_coffee(client, c).await,
+        GreenCoffeeCommands::Delete(c) => delete_green_coffee(client, c).await,
+    }
+}
+
+fn parse_purchased_on(value: Option<String>) -> Result<Option<NaiveDate>> {
+    Ok(value
+        .map(|d| NaiveDate::parse_from_str(&d, "%Y-%m-%d"))
+        .transpose()?)
+}
+
+#[derive(Debug, Args)]
+pub struct AddGreenCoffeeCommand {
+    #[arg(long)]
+    pub name: String,
+    #[arg(long)]
+    pub origin: Option<String>,
+    #[arg(long)]
+    pub process: Option<String>,
+    /// Where the green coffee was bought
+    #[arg(long)]
+    pub supplier: Option<String>,
+    /// Grams bought
+    #[arg(long)]
+    pub amount: f64,
+    /// What the green coffee cost
+    #[arg(long)]
+    pub price: Option<f64>,
+    /// Purchase date (e.g. 2025-08-05)
+    #[arg(long)]
+    pub purchased_on: Option<String>,
+    #[arg(long)]
+    pub notes: Option<String>,
+    /// Override creation timestamp (e.g. 2025-08-05T10:00:00Z or 2025-08-05)
+    #[arg(long)]
+    pub created_at: Option<String>,
+}
+
+pub async fn add_green_coffee(
+    client: &BrewlogClient,
+    command: AddGreenCoffeeCommand,
+) -> Result<()> {
+    let created_at = command
+        .created_at
+        .map(|s| parse_created_at(&s))
+        .transpose()?;
+    let payload = NewGreenCoffee {
+        name: command.name,
+        origin: command.origin,
+        process: command.process,
+        supplier: command.supplier,
+        amount: command.amount,
+        price: command.price,
+        purchased_on: parse_purchased_on(command.purchased_on)?,
+        notes: command.notes,
+        created_at,
+    }
+    .normalize();
+    payload.validate()?;
+
+    let green = client.green_coffees().create(&payload).await?;
+    print_json(&green)
+}
+
+pub async fn list_green_coffees(client: &BrewlogClient) -> Result<()> {
+    let green_coffees = client.green_coffees().list().await?;
+    print_json(&green_coffees)
+}
+
+define_get_command!(
+    GetGreenCoffeeCommand,
+    get_green_coffee,
+    GreenCoffeeId,
+    green_coffees
+);
+
+#[derive(Debug, Args)]
+pub struct UpdateGreenCoffeeCommand {
+    #[arg(long)]
+    pub id: i64,
+    #[arg(long)]
+    pub name: Option<String>,
+    #[arg(long)]
+    pub origin: Option<String>,
+    #[arg(long)]
+    pub process: Option<String>,
+    #[arg(long)]
+    pub supplier: Option<String>,
+    #[arg(long)]
+    pub amount: Option<f64>,
+    #[arg(long)]
+    pub price: Option<f64>,
+    #[arg(long)]
+    pub purchased_on: Option<String>,
+    #[arg(long)]
+    pub notes: Option<String>,
+}
+
+pub async fn update_green_coffee(
+    client: &BrewlogClient,
+    command: UpdateGreenCoffeeCommand,
+) -> Result<()> {
+    let payload = UpdateGreenCoffee {
+        name: command.name,
+        origin: command.origin,
+        process: command.process,
+        supplier: command.supplier,
+        amount: command.amount,
+        price: command.price,
+        purchased_on: parse_purchased_on(command.purchased_on)?,
+        notes: command.notes,
+    };
+    payload.validate()?;
+
+    let green = client
+        .green_coffees()
+        .update(GreenCoffeeId::new(command.id), &payload)
+        .await?;
+    print_json(&green)
+}
+
+define_delete_command!(
+    DeleteGreenCoffeeCommand,
+    delete_green_coffee,
+    GreenCoffeeId,
+    green_coffees,
+    "green_coffee"
+);
@@ -0,0 +1,122 @@
+use std::io::Write;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use clap::{Args, Subcommand};
+
+use super::print_json;
+use crate::domain::entity_type::EntityType;
+use crate::infrastructure::client::BrewlogClient;
+
+/// Manage the photo attached to an entity. Shared by every entity command
+/// that can carry an image, e.g. `brewlog roast image set 3 photo.jpg`.
+#[derive(Debug, Subcommand)]
+pub enum ImageCommands {
+    /// Upload a photo, replacing any existing one
+    Set(SetImageCommand),
+    /// Download the photo
+    Get(GetImageCommand),
+    /// Remove the photo
+    Delete(DeleteImageCommand),
+}
+
+pub async fn run(
+    client: &BrewlogClient,
+    entity_type: EntityType,
+    cmd: ImageCommands,
+) -> Result<()> {
+    match cmd {
+        ImageCommands::Set(c) => set_image(client, entity_type, c).await,
+        ImageCommands::Get(c) => get_image(client, entity_type, c).await,
+        ImageCommands::Delete(c) => delete_image(client, entity_type, c).await,
+    }
+}
+
+#[derive(Debug, Args)]
+pub struct SetImageCommand {
+    pub id: i64,
+    /// JPEG, PNG, WebP or HEIC file to upload
+    pub path: PathBuf,
+}
+
+pub async fn set_image(
+    client: &BrewlogClient,
+    entity_type: EntityType,
+    command: SetImageCommand,
+) -> Result<()> {
+    let bytes = std::fs::read(&command.path)
+        .with_context(|| format!("failed to read {}", command.path.display()))?;
+    client
+        .images()
+        .upload_bytes(entity_type, command.id, &bytes)
+        .await
+        .with_context(|| format!("failed to upload {}", command.path.display()))?;
+    print_json(&serde_json::json!({
+        "status": "uploaded",
+        "resource": entity_type.as_str(),
+        "id": command.id,
+    }))
+}
+
+#[derive(Debug, Args)]
+pub struct GetImageCommand {
+    pub id: i64,
+    /// Where to save the photo; `-` writes it to stdout. Defaults to
+    /// `<type>-<id>.<ext>` in the current directory.
+    #[arg(long, short)]
+    pub output: Option<PathBuf>,
+}
+
+pub async fn get_image(
+    client: &BrewlogClient,
+    entity_type: EntityType,
+    command: GetImageCommand,
+) -> Result<()> {
+    let image = client.images().download(entity_type, command.id).await?;
+
+    let path = command.output.unwrap_or_else(|| {
+        PathBuf::from(format!(
+            "{entity_type}-{}.{}",
+            command.id,
+            extension_for(&image.content_type)
+        ))
+    });
+    if path.as_os_str() == "-" {
+        let mut stdout = std::io::stdout().lock();
+        stdout.write_all(&image.data)?;
+        return stdout.flush().context("failed to write image to stdout");
+    }
+
+    std::fs::write(&path, &image.data)
+        .with_context(|| format!("failed to write {}", path.display()))?;
+    eprintln!("Saved {} ({} bytes)", path.display(), image.data.len());
+    Ok(())
+}
+
+#[derive(Debug, Args)]
+pub struct DeleteImageCommand {
+    pub id: i64,
+}
+
+pub async fn delete_image(
+    client: &BrewlogClient,
+    entity_type: EntityType,
+    command: DeleteImageCommand,
+) -> Result<()> {
+    client.images().delete(entity_type, command.id).await?;
+    print_json(&serde_json::json!({
+        "status": "deleted",
+        "resource": format!("{entity_type} image"),
+        "id": command.id,
+    }))
+}
+
+/// File extension for a downloaded image's content type.
+fn extension_for(content_type: &str) -> &'static str {
+    match content_type {
+        "image/png" => "png",
+        "image/webp" => "webp",
+        "image/heic" => "heic",
+        _ => "jpg",
+    }
+}
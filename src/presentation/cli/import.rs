@@ -0,0 +1,80 @@
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use clap::{Args, Subcommand};
+
+use crate::application::services::Backfill;
+use crate::infrastructure::client::BrewlogClient;
+
+#[derive(Debug, Subcommand)]
+pub enum ImportCommands {
+    /// Import a Beanconqueror export: beans, brews, mills and preparations
+    Beanconqueror(BeanconquerorCommand),
+    /// Log historical brews and cups from a JSON file, each at its own date
+    Backfill(BackfillCommand),
+}
+
+#[derive(Debug, Args)]
+pub struct BeanconquerorCommand {
+    /// The export zip, or the `Beanconqueror.json` inside it
+    pub file: PathBuf,
+    /// Country for roasters this instance doesn't know yet
+    #[arg(long)]
+    pub roaster_country: String,
+}
+
+#[derive(Debug, Args)]
+pub struct BackfillCommand {
+    /// JSON with `brews` and `cups` arrays, shaped like the create payloads;
+    /// every record needs a `created_at`
+    pub file: PathBuf,
+}
+
+pub async fn run(client: &BrewlogClient, cmd: ImportCommands) -> Result<()> {
+    match cmd {
+        ImportCommands::Beanconqueror(c) => beanconqueror(client, c).await,
+        ImportCommands::Backfill(c) => backfill(client, c).await,
+    }
+}
+
+async fn beanconqueror(client: &BrewlogClient, cmd: BeanconquerorCommand) -> Result<()> {
+    let export = std::fs::read(&cmd.file)
+        .with_context(|| format!("failed to read {}", cmd.file.display()))?;
+    let report = client
+        .import()
+        .beanconqueror(export, &cmd.roaster_country)
+        .await?;
+
+    eprintln!(
+        "Imported {} roasters, {} roasts, {} bags, {} gear and {} brews.",
+        report.roasters, report.roasts, report.bags, report.gear, report.brews
+    );
+    for (reason, count) in &report.skipped {
+        eprintln!("Skipped {count}: {reason}");
+    }
+    if !report.unmapped.is_empty() {
+        let fields: Vec<_> = report
+            .unmapped
+            .iter()
+            .map(|(field, count)| format!("{field} ({count})"))
+            .collect();
+        eprintln!("Not imported: {}", fields.join(", "));
+    }
+    super::print_json(&report)
+}
+
+async fn backfill(client: &BrewlogClient, cmd: BackfillCommand) -> Result<()> {
+    let contents = std::fs::read_to_string(&cmd.file)
+        .with_context(|| format!("failed to read {}", cmd.file.display()))?;
+    let backfill: Backfill = serde_json::from_str(&contents)
+        .with_context(|| format!("failed to parse {}", cmd.file.display()))?;
+    let report = client.import().backfill(&backfill).await?;
+
+    eprintln!(
+        "Backfilled {} brews and {} cups across {} months.",
+        report.brews,
+        report.cups,
+        report.months.len()
+    );
+    super::print_json(&report)
+}
@@ -1,30 +1,57 @@
+pub mod admin;
 pub mod backup;
 pub mod bags;
 pub mod brews;
 pub mod cafes;
+pub mod config;
 pub mod cups;
+pub mod dev;
+pub mod events;
+pub mod export;
 pub mod gear;
+pub mod green_coffees;
+pub mod images;
+pub mod import;
 mod macros;
+pub mod roast_batches;
 pub mod roasters;
 pub mod roasts;
+pub mod stats;
 pub mod timeline;
 pub mod tokens;
+pub mod trips;
 
-use std::net::SocketAddr;
+use std::path::PathBuf;
 
 use chrono::{DateTime, NaiveDate, Utc};
+use serde::Deserialize;
 
+use admin::AdminCommands;
 use backup::{BackupCommand, RestoreCommand};
 use bags::BagCommands;
 use brews::BrewCommands;
 use cafes::CafeCommands;
-use clap::{Args, Parser, Subcommand};
+use clap::{ArgAction, Args, Parser, Subcommand, ValueEnum};
+use config::ConfigCommands;
 use cups::CupCommands;
+use dev::DevCommands;
+use events::EventCommands;
+use export::ExportCommands;
 use gear::GearCommands;
+use green_coffees::GreenCoffeeCommands;
+use import::ImportCommands;
+use roast_batches::RoastBatchCommands;
 use roasters::RoasterCommands;
 use roasts::RoastCommands;
+use stats::StatsCommand;
 use timeline::TimelineCommands;
 use tokens::TokenCommands;
+use trips::TripCommands;
+
+use crate::application::tenants::TenantRouting;
+use crate::infrastructure::auth::TokenHashScheme;
+use crate::infrastructure::listener::BindAddress;
+use crate::infrastructure::repositories::query_timing::DEFAULT_SLOW_QUERY_THRESHOLD_MS;
 
 #[derive(Debug, Parser)]
 #[command(author, version, about = "Track coffee roasts, brews, and cups", long_about = None)]
@@ -37,6 +64,11 @@ pub struct Cli {
     )]
     pub api_url: String,
 
+    /// Run against a throwaway in-memory server instead of `--api-url`.
+    /// It starts empty and nothing is saved.
+    #[arg(long, global = true)]
+    pub dry_run: bool,
+
     #[command(subcommand)]
     pub command: Commands,
 }
@@ -44,7 +76,7 @@ pub struct Cli {
 #[derive(Debug, Subcommand)]
 pub enum Commands {
     /// Run the HTTP server
-    Serve(ServeCommand),
+    Serve(Box<ServeCommand>),
 
     /// Manage roasters
     Roaster {
@@ -88,27 +120,89 @@ pub enum Commands {
         command: CupCommands,
     },
 
+    /// Manage green coffee for home roasting
+    GreenCoffee {
+        #[command(subcommand)]
+        command: GreenCoffeeCommands,
+    },
+
+    /// Manage home roast batches
+    RoastBatch {
+        #[command(subcommand)]
+        command: RoastBatchCommands,
+    },
+
+    /// Manage coffee events attended
+    Event {
+        #[command(subcommand)]
+        command: EventCommands,
+    },
+
+    /// Manage trips away
+    Trip {
+        #[command(subcommand)]
+        command: TripCommands,
+    },
+
     /// Manage API tokens
     Token {
         #[command(subcommand)]
         command: TokenCommands,
     },
 
+    /// Summarise recent brewing in the terminal
+    Stats(StatsCommand),
+
     /// Manage timeline events
     Timeline {
         #[command(subcommand)]
         command: TimelineCommands,
     },
 
-    /// Back up all coffee data to JSON (stdout)
+    /// Database maintenance
+    Admin {
+        #[command(subcommand)]
+        command: AdminCommands,
+    },
+
+    /// Development helpers
+    Dev {
+        #[command(subcommand)]
+        command: DevCommands,
+    },
+
+    /// Back up all coffee data to JSON (stdout), or compare two backups
     Backup(BackupCommand),
 
     /// Restore coffee data from a JSON backup file
     Restore(RestoreCommand),
+
+    /// Import history from other coffee apps
+    Import {
+        #[command(subcommand)]
+        command: ImportCommands,
+    },
+
+    /// Export data for other coffee apps
+    Export {
+        #[command(subcommand)]
+        command: ExportCommands,
+    },
+
+    /// Check server configuration
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommands,
+    },
 }
 
 #[derive(Debug, Args)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct ServeCommand {
+    /// TOML file of settings; flags and environment variables override it
+    #[arg(long, env = "BREWLOG_CONFIG")]
+    pub config: Option<PathBuf>,
+
     #[arg(
         long,
         env = "BREWLOG_DATABASE_URL",
@@ -116,8 +210,14 @@ pub struct ServeCommand {
     )]
     pub database_url: String,
 
-    #[arg(long, env = "BREWLOG_BIND_ADDRESS", default_value = "127.0.0.1:3000")]
-    pub bind_address: SocketAddr,
+    /// `host:port`, or `unix:/path/to.sock` for a Unix domain socket
+    #[arg(
+        long,
+        alias = "bind",
+        env = "BREWLOG_BIND_ADDRESS",
+        default_value = "127.0.0.1:3000"
+    )]
+    pub bind_address: BindAddress,
 
     #[arg(long, env = "BREWLOG_RP_ID", default_value = "localhost")]
     pub rp_id: String,
@@ -132,6 +232,18 @@ pub struct ServeCommand {
     #[arg(long, env = "BREWLOG_INSECURE_COOKIES")]
     pub insecure_cookies: bool,
 
+    /// Development mode: serve static assets from disk, uncached
+    #[arg(long, env = "BREWLOG_DEV")]
+    pub dev: bool,
+
+    /// Serve bundled sample data from memory, read-only, with a demo banner
+    #[arg(long, env = "BREWLOG_DEMO")]
+    pub demo: bool,
+
+    /// Directory of static asset overrides, laid out like `static/`
+    #[arg(long, env = "BREWLOG_THEME_DIR")]
+    pub theme_dir: Option<PathBuf>,
+
     #[arg(long, env = "BREWLOG_OPENROUTER_API_KEY")]
     pub openrouter_api_key: Option<String>,
 
@@ -144,6 +256,168 @@ pub struct ServeCommand {
 
     #[arg(long, env = "BREWLOG_FOURSQUARE_API_KEY")]
     pub foursquare_api_key: Option<String>,
+
+    /// Hashing scheme for API tokens (`argon2` or `sha256`). Existing tokens
+    /// are rehashed to this scheme the next time they are used.
+    #[arg(long, env = "BREWLOG_TOKEN_HASH", default_value = "argon2")]
+    pub token_hash: TokenHashScheme,
+
+    /// Argon2 memory cost in KiB
+    #[arg(long, env = "BREWLOG_TOKEN_HASH_MEMORY_KIB", default_value_t = 19_456)]
+    pub token_hash_memory_kib: u32,
+
+    /// Argon2 iteration count
+    #[arg(long, env = "BREWLOG_TOKEN_HASH_ITERATIONS", default_value_t = 2)]
+    pub token_hash_iterations: u32,
+
+    /// Argon2 degree of parallelism
+    #[arg(long, env = "BREWLOG_TOKEN_HASH_PARALLELISM", default_value_t = 1)]
+    pub token_hash_parallelism: u32,
+
+    /// Accept HTTP/2 (h2c) connections alongside HTTP/1.1
+    #[arg(long, env = "BREWLOG_HTTP2", default_value_t = true, action = ArgAction::Set)]
+    pub http2: bool,
+
+    /// Keep HTTP/1.1 connections open between requests
+    #[arg(long, env = "BREWLOG_HTTP_KEEP_ALIVE", default_value_t = true, action = ArgAction::Set)]
+    pub keep_alive: bool,
+
+    /// Seconds a client may take to send request headers
+    #[arg(long, env = "BREWLOG_HTTP_HEADER_READ_TIMEOUT", default_value_t = 30)]
+    pub header_read_timeout_secs: u64,
+
+    /// Seconds between HTTP/2 keep-alive pings (disabled when unset)
+    #[arg(long, env = "BREWLOG_HTTP2_KEEP_ALIVE_INTERVAL")]
+    pub http2_keep_alive_interval_secs: Option<u64>,
+
+    /// Maximum concurrent streams per HTTP/2 connection
+    #[arg(
+        long,
+        env = "BREWLOG_HTTP2_MAX_CONCURRENT_STREAMS",
+        default_value_t = 200
+    )]
+    pub http2_max_concurrent_streams: u32,
+
+    /// Longest side, in pixels, of stored images; larger uploads are downscaled
+    #[arg(long, env = "BREWLOG_IMAGE_MAX_DIMENSION", default_value_t = 1200)]
+    pub image_max_dimension: u32,
+
+    /// Longest side, in pixels, of image thumbnails
+    #[arg(long, env = "BREWLOG_IMAGE_THUMBNAIL_SIZE", default_value_t = 200)]
+    pub image_thumbnail_size: u32,
+
+    /// JPEG quality (1-100) of stored images
+    #[arg(long, env = "BREWLOG_IMAGE_JPEG_QUALITY", default_value_t = 85)]
+    pub image_jpeg_quality: u8,
+
+    /// Uploads wider or taller than this many pixels are rejected
+    #[arg(
+        long,
+        env = "BREWLOG_IMAGE_MAX_UPLOAD_DIMENSION",
+        default_value_t = 10_000
+    )]
+    pub image_max_upload_dimension: u32,
+
+    /// Largest accepted image file, in MiB
+    #[arg(long, env = "BREWLOG_IMAGE_MAX_FILE_SIZE_MIB", default_value_t = 10)]
+    pub image_max_file_size_mib: usize,
+
+    /// Hours between database maintenance runs (WAL checkpoint and
+    /// `PRAGMA optimize`); 0 disables them
+    #[arg(long, env = "BREWLOG_MAINTENANCE_INTERVAL_HOURS", default_value_t = 24)]
+    pub maintenance_interval_hours: u64,
+
+    /// Also VACUUM the database during maintenance
+    #[arg(long, env = "BREWLOG_MAINTENANCE_VACUUM", default_value_t = false, action = ArgAction::Set)]
+    pub maintenance_vacuum: bool,
+
+    /// Log list and timeline queries slower than this many milliseconds,
+    /// and count them on the Admin page; 0 turns this off
+    #[arg(
+        long,
+        env = "BREWLOG_SLOW_QUERY_THRESHOLD_MS",
+        default_value_t = DEFAULT_SLOW_QUERY_THRESHOLD_MS
+    )]
+    pub slow_query_threshold_ms: u64,
+
+    /// Host a database per tenant in this directory instead of
+    /// `--database-url`
+    #[arg(long, env = "BREWLOG_TENANTS_DIR")]
+    pub tenants_dir: Option<PathBuf>,
+
+    /// How requests name their tenant (`subdomain` or `path`)
+    #[arg(long, env = "BREWLOG_TENANT_ROUTING", default_value = "subdomain")]
+    pub tenant_routing: TenantRouting,
+
+    /// Bearer token for the tenant admin API
+    #[arg(long, env = "BREWLOG_TENANT_ADMIN_TOKEN")]
+    pub tenant_admin_token: Option<String>,
+
+    #[command(flatten)]
+    pub logging: LoggingArgs,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    /// Multi-line, human-readable output for local development
+    Pretty,
+    /// One JSON object per line, for log aggregation
+    Json,
+    /// Single-line, human-readable output
+    Compact,
+}
+
+/// Log output settings. Only `serve` exposes these as flags; other commands
+/// use the defaults, which still honour `RUST_LOG` and `RUST_LOG_FORMAT`.
+#[derive(Debug, Clone, Default, Args)]
+pub struct LoggingArgs {
+    /// Log output format [default: `RUST_LOG_FORMAT`, then compact]
+    #[arg(long, env = "BREWLOG_LOG_FORMAT", value_enum)]
+    pub log_format: Option<LogFormat>,
+
+    /// Default log level, e.g. `debug` [default: `RUST_LOG`, then info]
+    #[arg(long, env = "BREWLOG_LOG_LEVEL")]
+    pub log_level: Option<String>,
+
+    /// Per-module override as `module=level`, e.g. `sqlx=warn`. Repeatable.
+    #[arg(long = "log-filter", env = "BREWLOG_LOG_FILTER", value_delimiter = ',')]
+    pub log_filters: Vec<String>,
+}
+
+impl LoggingArgs {
+    pub fn format(&self) -> LogFormat {
+        self.log_format.unwrap_or_else(|| {
+            let use_json =
+                std::env::var("RUST_LOG_FORMAT").is_ok_and(|v| v.eq_ignore_ascii_case("json"));
+            if use_json {
+                LogFormat::Json
+            } else {
+                LogFormat::Compact
+            }
+        })
+    }
+
+    /// `EnvFilter` directives: the default level, then any module overrides
+    /// so they take precedence.
+    pub fn filter_directives(&self) -> String {
+        let level = self
+            .log_level
+            .clone()
+            .or_else(|| std::env::var("RUST_LOG").ok())
+            .filter(|level| !level.trim().is_empty())
+            .unwrap_or_else(|| "info".to_string());
+
+        std::iter::once(level)
+            .chain(
+                self.log_filters
+                    .iter()
+                    .map(|filter| filter.trim().to_string())
+                    .filter(|filter| !filter.is_empty()),
+            )
+            .collect::<Vec<_>>()
+            .join(",")
+    }
 }
 
 pub fn parse_created_at(value: &str) -> anyhow::Result<DateTime<Utc>> {
@@ -172,6 +446,11 @@ pub fn parse_finished_at(value: &str) -> anyhow::Result<DateTime<Utc>> {
     )
 }
 
+/// Parse a calendar day given as YYYY-MM-DD.
+pub(crate) fn parse_date(value: &str) -> anyhow::Result<NaiveDate> {
+    Ok(NaiveDate::parse_from_str(value, "%Y-%m-%d")?)
+}
+
 pub(crate) fn print_json<T>(value: &T) -> anyhow::Result<()>
 where
     T: serde::Serialize,
@@ -185,6 +464,8 @@ where
 mod tests {
     use super::*;
 
+    use proptest::prelude::*;
+
     #[test]
     fn parse_created_at_rfc3339() {
         let result = parse_created_at("2025-08-05T10:30:00Z").unwrap();
@@ -221,4 +502,50 @@ mod tests {
             .and_utc();
         assert_eq!(result, expected);
     }
+
+    #[test]
+    fn log_filters_follow_the_default_level() {
+        let logging = LoggingArgs {
+            log_format: None,
+            log_level: Some("warn".to_string()),
+            log_filters: vec!["brewlog=debug".to_string(), " sqlx=error ".to_string()],
+        };
+        assert_eq!(logging.filter_directives(), "warn,brewlog=debug,sqlx=error");
+    }
+
+    #[test]
+    fn explicit_log_format_wins() {
+        let logging = LoggingArgs {
+            log_format: Some(LogFormat::Pretty),
+            ..LoggingArgs::default()
+        };
+        assert_eq!(logging.format(), LogFormat::Pretty);
+    }
+
+    proptest! {
+        #[test]
+        fn parse_created_at_round_trips_rfc3339(secs in 0i64..4_102_444_800) {
+            let instant = DateTime::from_timestamp(secs, 0).unwrap();
+            prop_assert_eq!(parse_created_at(&instant.to_rfc3339()).unwrap(), instant);
+        }
+
+        #[test]
+        fn parse_created_at_dates_are_midnight(days in 0u64..73_000) {
+            let date = NaiveDate::from_ymd_opt(1970, 1, 1).unwrap() + chrono::Days::new(days);
+            let parsed = parse_created_at(&date.format("%Y-%m-%d").to_string()).unwrap();
+            prop_assert_eq!(parsed, date.and_time(chrono::NaiveTime::MIN).and_utc());
+        }
+
+        #[test]
+        fn parse_finished_at_dates_are_end_of_day(days in 0u64..73_000) {
+            let date = NaiveDate::from_ymd_opt(1970, 1, 1).unwrap() + chrono::Days::new(days);
+            let parsed = parse_finished_at(&date.format("%Y-%m-%d").to_string()).unwrap();
+            prop_assert_eq!(parsed, date.and_time(crate::domain::bags::END_OF_DAY).and_utc());
+        }
+
+        #[test]
+        fn parse_created_at_never_panics(input in any::<String>()) {
+            let _ = parse_created_at(&input);
+        }
+    }
 }
@@ -0,0 +1,172 @@
+use anyhow::Result;
+use clap::{Args, Subcommand};
+
+use super::macros::{define_delete_command, define_get_command};
+use super::parse_created_at;
+use super::print_json;
+use crate::domain::ids::{BagId, GreenCoffeeId, RoastBatchId};
+use crate::domain::roast_batches::{NewRoastBatch, UpdateRoastBatch};
+use crate::domain::validation::Validate;
+use crate::infrastructure::client::BrewlogClient;
+
+#[derive(Debug, Subcommand)]
+pub enum RoastBatchCommands {
+    /// Log a batch roasted from green coffee
+    Add(AddRoastBatchCommand),
+    /// List roast batches
+    List(ListRoastBatchesCommand),
+    /// Get a roast batch by ID
+    Get(GetRoastBatchCommand),
+    /// Update a roast batch
+    Update(UpdateRoastBatchCommand),
+    /// Delete a roast batch
+    Delete(DeleteRoastBatchCommand),
+}
+
+pub async fn run(client: &BrewlogClient, cmd: RoastBatchCommands) -> Result<()> {
+    match cmd {
+        RoastBatchCommands::Add(c) => add_roast_batch(client, c).await,
+        RoastBatchCommands::List(c) => list_roast_batches(client, c).await,
+        RoastBatchCommands::Get(c) => get_roast_batch(client, c).await,
+        RoastBatchCommands::Update(c) => update_roast_batch(client, c).await,
+        RoastBatchCommands::Delete(c) => delete_roast_batch(client, c).await,
+    }
+}
+
+#[derive(Debug, Args)]
+pub struct AddRoastBatchCommand {
+    #[arg(long)]
+    pub green_coffee_id: i64,
+    /// Bag the roasted beans went into
+    #[arg(long)]
+    pub bag_id: Option<i64>,
+    /// When the batch was roasted (e.g. 2025-08-05T10:00:00Z or 2025-08-05)
+    #[arg(long)]
+    pub roasted_at: Option<String>,
+    /// Green grams into the roaster
+    #[arg(long)]
+    pub weight_in: f64,
+    /// Roasted grams out
+    #[arg(long)]
+    pub weight_out: f64,
+    /// Seconds from charge to drop
+    #[arg(long)]
+    pub total_time: Option<i64>,
+    /// Seconds from first crack to drop
+    #[arg(long)]
+    pub development_time: Option<i64>,
+    /// Notes on the roast curve
+    #[arg(long)]
+    pub curve_notes: Option<String>,
+}
+
+pub async fn add_roast_batch(client: &BrewlogClient, command: AddRoastBatchCommand) -> Result<()> {
+    let roasted_at = command
+        .roasted_at
+        .map(|s| parse_created_at(&s))
+        .transpose()?;
+    let payload = NewRoastBatch {
+        green_coffee_id: GreenCoffeeId::new(command.green_coffee_id),
+        bag_id: command.bag_id.map(BagId::new),
+        roasted_at,
+        weight_in: command.weight_in,
+        weight_out: command.weight_out,
+        total_time: command.total_time,
+        development_time: command.development_time,
+        curve_notes: command.curve_notes,
+        created_at: None,
+    }
+    .normalize();
+    payload.validate()?;
+
+    let batch = client.roast_batches().create(&payload).await?;
+    print_json(&batch)
+}
+
+#[derive(Debug, Args)]
+pub struct ListRoastBatchesCommand {
+    /// Only list batches of this green coffee
+    #[arg(long)]
+    pub green_coffee_id: Option<i64>,
+}
+
+pub async fn list_roast_batches(
+    client: &BrewlogClient,
+    command: ListRoastBatchesCommand,
+) -> Result<()> {
+    let batches = client
+        .roast_batches()
+        .list(command.green_coffee_id.map(GreenCoffeeId::new))
+        .await?;
+    print_json(&batches)
+}
+
+define_get_command!(
+    GetRoastBatchCommand,
+    get_roast_batch,
+    RoastBatchId,
+    roast_batches
+);
+
+#[derive(Debug, Args)]
+pub struct UpdateRoastBatchCommand {
+    #[arg(long)]
+    pub id: i64,
+    /// Bag the roasted beans went into
+    #[arg(long, conflicts_with = "clear_bag")]
+    pub bag_id: Option<i64>,
+    /// Unlink the batch from its bag
+    #[arg(long)]
+    pub clear_bag: bool,
+    #[arg(long)]
+    pub roasted_at: Option<String>,
+    #[arg(long)]
+    pub weight_in: Option<f64>,
+    #[arg(long)]
+    pub weight_out: Option<f64>,
+    #[arg(long)]
+    pub total_time: Option<i64>,
+    #[arg(long)]
+    pub development_time: Option<i64>,
+    #[arg(long)]
+    pub curve_notes: Option<String>,
+}
+
+pub async fn update_roast_batch(
+    client: &BrewlogClient,
+    command: UpdateRoastBatchCommand,
+) -> Result<()> {
+    let roasted_at = command
+        .roasted_at
+        .map(|s| parse_created_at(&s))
+        .transpose()?;
+    let bag_id = if command.clear_bag {
+        Some(None)
+    } else {
+        command.bag_id.map(|id| Some(BagId::new(id)))
+    };
+    let payload = UpdateRoastBatch {
+        bag_id,
+        roasted_at,
+        weight_in: command.weight_in,
+        weight_out: command.weight_out,
+        total_time: command.total_time,
+        development_time: command.development_time,
+        curve_notes: command.curve_notes,
+    };
+    payload.validate()?;
+
+    let batch = client
+        .roast_batches()
+        .update(RoastBatchId::new(command.id), &payload)
+        .await?;
+    print_json(&batch)
+}
+
+define_delete_command!(
+    DeleteRoastBatchCommand,
+    delete_roast_batch,
+    RoastBatchId,
+    roast_batches,
+    "roast_batch"
+);
@@ -1,11 +1,14 @@
 use anyhow::Result;
 use clap::{Args, Subcommand};
 
+use super::images::{self, ImageCommands};
 use super::macros::{define_delete_command, define_get_command};
 use super::parse_created_at;
 use super::print_json;
+use crate::domain::entity_type::EntityType;
 use crate::domain::ids::RoasterId;
 use crate::domain::roasters::{NewRoaster, UpdateRoaster};
+use crate::domain::validation::Validate;
 use crate::infrastructure::client::BrewlogClient;
 
 #[derive(Debug, Subcommand)]
@@ -20,6 +23,11 @@ pub enum RoasterCommands {
     Update(UpdateRoasterCommand),
     /// Delete a roaster
     Delete(DeleteRoasterCommand),
+    /// Upload, download or remove the photo
+    Image {
+        #[command(subcommand)]
+        command: ImageCommands,
+    },
 }
 
 pub async fn run(client: &BrewlogClient, cmd: RoasterCommands) -> Result<()> {
@@ -29,6 +37,9 @@ pub async fn run(client: &BrewlogClient, cmd: RoasterCommands) -> Result<()> {
         RoasterCommands::Get(c) => get_roaster(client, c).await,
         RoasterCommands::Update(c) => update_roaster(client, c).await,
         RoasterCommands::Delete(c) => delete_roaster(client, c).await,
+        RoasterCommands::Image { command } => {
+            images::run(client, EntityType::Roaster, command).await
+        }
     }
 }
 
@@ -59,6 +70,7 @@ pub async fn add_roaster(client: &BrewlogClient, command: AddRoasterCommand) ->
         homepage: command.homepage,
         created_at,
     };
+    payload.validate()?;
 
     let roaster = client.roasters().create(&payload).await?;
     print_json(&roaster)
@@ -100,6 +112,7 @@ pub async fn update_roaster(client: &BrewlogClient, command: UpdateRoasterComman
         homepage: command.homepage,
         created_at,
     };
+    payload.validate()?;
 
     let roaster = client
         .roasters()
@@ -1,11 +1,15 @@
 use anyhow::Result;
 use clap::{Args, Subcommand};
 
+use super::images::{self, ImageCommands};
 use super::macros::{define_delete_command, define_get_command};
 use super::parse_created_at;
 use super::print_json;
+use crate::domain::blends::{BlendComponent, blend_origin, parse_blend};
+use crate::domain::entity_type::EntityType;
 use crate::domain::ids::{RoastId, RoasterId};
 use crate::domain::roasts::{NewRoast, UpdateRoast};
+use crate::domain::validation::Validate;
 use crate::infrastructure::client::BrewlogClient;
 
 #[derive(Debug, Subcommand)]
@@ -18,8 +22,15 @@ pub enum RoastCommands {
     Get(GetRoastCommand),
     /// Update a roast
     Update(UpdateRoastCommand),
+    /// Start a new lot of a roast for another harvest
+    NewLot(NewLotCommand),
     /// Delete a roast
     Delete(DeleteRoastCommand),
+    /// Upload, download or remove the photo
+    Image {
+        #[command(subcommand)]
+        command: ImageCommands,
+    },
 }
 
 pub async fn run(client: &BrewlogClient, cmd: RoastCommands) -> Result<()> {
@@ -28,7 +39,9 @@ pub async fn run(client: &BrewlogClient, cmd: RoastCommands) -> Result<()> {
         RoastCommands::List(c) => list_roasts(client, c).await,
         RoastCommands::Get(c) => get_roast(client, c).await,
         RoastCommands::Update(c) => update_roast(client, c).await,
+        RoastCommands::NewLot(c) => new_lot(client, c).await,
         RoastCommands::Delete(c) => delete_roast(client, c).await,
+        RoastCommands::Image { command } => images::run(client, EntityType::Roast, command).await,
     }
 }
 
@@ -38,8 +51,8 @@ pub struct AddRoastCommand {
     pub roaster_id: i64,
     #[arg(long)]
     pub name: String,
-    #[arg(long)]
-    pub origin: String,
+    #[arg(long, required_unless_present = "blend")]
+    pub origin: Option<String>,
     #[arg(long)]
     pub region: String,
     #[arg(long)]
@@ -48,6 +61,13 @@ pub struct AddRoastCommand {
     pub process: String,
     #[arg(long = "tasting-notes", required = true)]
     pub tasting_notes: Vec<String>,
+    /// Origins of a blend with their shares (e.g. "Brazil 60%, Ethiopia 40%");
+    /// sets the origin to match
+    #[arg(long)]
+    pub blend: Option<String>,
+    /// Harvest the coffee came from, when the roaster sells it each year
+    #[arg(long)]
+    pub harvest_year: Option<i32>,
     /// Override creation timestamp (e.g. 2025-08-05T10:00:00Z or 2025-08-05)
     #[arg(long)]
     pub created_at: Option<String>,
@@ -58,16 +78,25 @@ pub async fn add_roast(client: &BrewlogClient, command: AddRoastCommand) -> Resu
         .created_at
         .map(|s| parse_created_at(&s))
         .transpose()?;
+    let components = parse_blend_arg(command.blend.as_deref())?.unwrap_or_default();
+    let origin = if components.is_empty() {
+        command.origin.unwrap_or_default()
+    } else {
+        blend_origin(&components)
+    };
     let payload = NewRoast {
         roaster_id: RoasterId::new(command.roaster_id),
         name: command.name,
-        origin: command.origin,
+        origin,
         region: command.region,
         producer: command.producer,
         tasting_notes: command.tasting_notes,
         process: command.process,
+        components,
+        harvest_year: command.harvest_year,
         created_at,
     };
+    payload.validate()?;
 
     let roast = client.roasts().create(&payload).await?;
     print_json(&roast)
@@ -107,6 +136,11 @@ pub struct UpdateRoastCommand {
     pub process: Option<String>,
     #[arg(long = "tasting-notes")]
     pub tasting_notes: Option<Vec<String>>,
+    /// Replace the blend (e.g. "Brazil 60%, Ethiopia 40%"); pass "" to clear it
+    #[arg(long)]
+    pub blend: Option<String>,
+    #[arg(long)]
+    pub harvest_year: Option<i32>,
     /// Override creation timestamp (e.g. 2025-08-05T10:00:00Z or 2025-08-05)
     #[arg(long)]
     pub created_at: Option<String>,
@@ -117,16 +151,24 @@ pub async fn update_roast(client: &BrewlogClient, command: UpdateRoastCommand) -
         .created_at
         .map(|s| parse_created_at(&s))
         .transpose()?;
+    let components = parse_blend_arg(command.blend.as_deref())?;
+    let origin = match &components {
+        Some(components) if !components.is_empty() => Some(blend_origin(components)),
+        _ => command.origin,
+    };
     let payload = UpdateRoast {
         roaster_id: command.roaster_id.map(RoasterId::new),
         name: command.name,
-        origin: command.origin,
+        origin,
         region: command.region,
         producer: command.producer,
         tasting_notes: command.tasting_notes,
         process: command.process,
+        components,
+        harvest_year: command.harvest_year,
         created_at,
     };
+    payload.validate()?;
 
     let roast = client
         .roasts()
@@ -135,4 +177,27 @@ pub async fn update_roast(client: &BrewlogClient, command: UpdateRoastCommand) -
     print_json(&roast)
 }
 
+fn parse_blend_arg(blend: Option<&str>) -> Result<Option<Vec<BlendComponent>>> {
+    blend
+        .map(|text| parse_blend(text).map_err(anyhow::Error::msg))
+        .transpose()
+}
+
+#[derive(Debug, Args)]
+pub struct NewLotCommand {
+    /// Roast to copy the details from
+    #[arg(long)]
+    pub id: i64,
+    #[arg(long)]
+    pub harvest_year: i32,
+}
+
+pub async fn new_lot(client: &BrewlogClient, command: NewLotCommand) -> Result<()> {
+    let roast = client
+        .roasts()
+        .new_lot(RoastId::new(command.id), command.harvest_year)
+        .await?;
+    print_json(&roast)
+}
+
 define_delete_command!(DeleteRoastCommand, delete_roast, RoastId, roasts, "roast");
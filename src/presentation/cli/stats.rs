@@ -0,0 +1,131 @@
+use std::fmt::Write;
+
+use anyhow::Result;
+use clap::{Args, ValueEnum};
+
+use super::print_json;
+use crate::domain::formatting::format_weight;
+use crate::domain::stats::{CachedStats, PeriodSummary};
+use crate::infrastructure::client::BrewlogClient;
+
+/// Bars from lightest to heaviest, for the consumption sparkline.
+const SPARKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum StatsPeriodArg {
+    /// The last 30 days, day by day
+    Month,
+    /// The last 12 months, month by month
+    Year,
+}
+
+#[derive(Debug, Args)]
+pub struct StatsCommand {
+    /// How far back the summary looks
+    #[arg(long, value_enum, default_value_t = StatsPeriodArg::Month)]
+    pub period: StatsPeriodArg,
+    /// Print the full stats as JSON instead
+    #[arg(long)]
+    pub json: bool,
+}
+
+pub async fn stats(client: &BrewlogClient, command: StatsCommand) -> Result<()> {
+    let stats = client.stats().get().await?;
+    if command.json {
+        return print_json(&stats);
+    }
+    print!("{}", render_summary(&stats, command.period));
+    Ok(())
+}
+
+fn render_summary(stats: &CachedStats, period: StatsPeriodArg) -> String {
+    let (title, summary): (&str, &PeriodSummary) = match period {
+        StatsPeriodArg::Month => ("Last 30 days", &stats.last_month),
+        StatsPeriodArg::Year => ("Last 12 months", &stats.last_year),
+    };
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "{title}: {} brewed across {} brews, {} cups out",
+        format_weight(summary.grams),
+        summary.brews,
+        summary.cups
+    );
+    let _ = writeln!(
+        out,
+        "All time: {} brewed across {} brews",
+        format_weight(stats.consumption.all_time_grams),
+        stats.consumption.brews_all_time
+    );
+
+    let grams: Vec<f64> = summary.history.iter().map(|(_, grams)| *grams).collect();
+    if let (Some((first, _)), Some((last, _))) = (summary.history.first(), summary.history.last()) {
+        let _ = writeln!(out);
+        let _ = writeln!(out, "{}", sparkline(&grams));
+        let gap = grams.len().saturating_sub(first.len() + last.len()).max(1);
+        let _ = writeln!(out, "{first}{}{last}", " ".repeat(gap));
+    }
+
+    let _ = writeln!(out);
+    let _ = writeln!(out, "Top roasters");
+    write_entries(
+        &mut out,
+        summary
+            .top_roasters
+            .iter()
+            .map(|(name, grams)| (name.as_str(), format_weight(*grams))),
+    );
+
+    let _ = writeln!(out);
+    let _ = writeln!(out, "Top origins");
+    write_entries(
+        &mut out,
+        summary.top_origins.iter().map(|(name, count)| {
+            let unit = if *count == 1 { "brew" } else { "brews" };
+            (name.as_str(), format!("{count} {unit}"))
+        }),
+    );
+
+    out
+}
+
+fn write_entries<'a>(out: &mut String, entries: impl Iterator<Item = (&'a str, String)>) {
+    let mut any = false;
+    for (name, value) in entries {
+        any = true;
+        let _ = writeln!(out, "  {name:<28} {value}");
+    }
+    if !any {
+        let _ = writeln!(out, "  Nothing brewed yet");
+    }
+}
+
+/// One bar per value, scaled to the largest. Empty buckets get the lowest
+/// bar so the timeline stays readable.
+fn sparkline(values: &[f64]) -> String {
+    let max = values.iter().copied().fold(0.0_f64, f64::max);
+    values
+        .iter()
+        .map(|value| {
+            if max <= 0.0 || *value <= 0.0 {
+                return SPARKS[0];
+            }
+            #[allow(clippy::cast_precision_loss)]
+            let level = ((value / max) * (SPARKS.len() - 1) as f64).round() as usize;
+            SPARKS[level.clamp(1, SPARKS.len() - 1)]
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sparkline_scales_to_the_largest_value() {
+        assert_eq!(sparkline(&[0.0, 15.0, 30.0]), "▁▅█");
+        assert_eq!(sparkline(&[0.0, 0.0]), "▁▁");
+        assert_eq!(sparkline(&[1.0, 100.0]), "▂█");
+    }
+}
@@ -1,7 +1,168 @@
-use clap::Subcommand;
+use std::io::IsTerminal;
+use std::time::Duration;
+
+use anyhow::Result;
+use chrono::Local;
+use clap::{Args, Subcommand};
+
+use crate::domain::entity_type::EntityType;
+use crate::domain::ids::{TimelineEventId, UserId};
+use crate::domain::timeline::TimelineEvent;
+use crate::infrastructure::client::BrewlogClient;
+
+/// Most events fetched per poll while following.
+const FOLLOW_BATCH: u32 = 100;
 
 #[derive(Debug, Subcommand)]
 pub enum TimelineCommands {
     /// Rebuild all timeline events from current entity data
     Rebuild,
+    /// Print the latest timeline events, optionally following new ones
+    Tail(TailTimelineCommand),
+}
+
+pub async fn run(client: &BrewlogClient, cmd: TimelineCommands) -> Result<()> {
+    match cmd {
+        TimelineCommands::Rebuild => {
+            client.timeline().rebuild().await?;
+            eprintln!("Timeline rebuild initiated.");
+            Ok(())
+        }
+        TimelineCommands::Tail(c) => tail_timeline(client, c).await,
+    }
+}
+
+#[derive(Debug, Args)]
+pub struct TailTimelineCommand {
+    /// Keep running and print events as they are logged
+    #[arg(long, short)]
+    pub follow: bool,
+    /// How many of the latest events to print first
+    #[arg(long, short = 'n', default_value_t = 10)]
+    pub lines: u32,
+    /// Seconds between checks for new events when following
+    #[arg(long, default_value_t = 5)]
+    pub interval: u64,
+    /// Only events logged by this user
+    #[arg(long)]
+    pub user_id: Option<i64>,
+}
+
+pub async fn tail_timeline(client: &BrewlogClient, command: TailTimelineCommand) -> Result<()> {
+    let color = std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none();
+    let user = command.user_id.map(UserId::from);
+
+    let mut last_seen = None;
+    if command.lines > 0 {
+        let events = client.timeline().logged(None, command.lines, user).await?;
+        last_seen = print_events(&events, color);
+    }
+    if !command.follow {
+        return Ok(());
+    }
+    if last_seen.is_none() {
+        // Start from the newest event without printing it.
+        let newest = client.timeline().logged(None, 1, user).await?;
+        last_seen = newest.last().map(|event| event.id);
+    }
+
+    let interval = Duration::from_secs(command.interval.max(1));
+    loop {
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => return Ok(()),
+            () = tokio::time::sleep(interval) => {}
+        }
+        match client
+            .timeline()
+            .logged(last_seen, FOLLOW_BATCH, user)
+            .await
+        {
+            Ok(events) => last_seen = print_events(&events, color).or(last_seen),
+            // Keep following through a server restart or a dropped connection.
+            Err(err) => eprintln!("Failed to fetch new events: {err:#}"),
+        }
+    }
+}
+
+/// Prints `events` one per line, returning the ID of the last one.
+fn print_events(events: &[TimelineEvent], color: bool) -> Option<TimelineEventId> {
+    for event in events {
+        println!("{}", format_event(event, color));
+    }
+    events.last().map(|event| event.id)
+}
+
+/// One compact line per event: when it happened, what kind of entry it is,
+/// and its title.
+fn format_event(event: &TimelineEvent, color: bool) -> String {
+    let when = event
+        .occurred_at
+        .with_timezone(&Local)
+        .format("%Y-%m-%d %H:%M");
+    let kind = format!("{:<7}", event.entity_type.as_str());
+    let author = event
+        .created_by
+        .map(|user_id| format!(" (user {user_id})"))
+        .unwrap_or_default();
+
+    if color {
+        format!(
+            "\x1b[2m{when}\x1b[0m  \x1b[{}m{kind}\x1b[0m  \x1b[1m{}\x1b[0m \x1b[2m{}{author}\x1b[0m",
+            kind_color(event.entity_type),
+            event.title,
+            event.action,
+        )
+    } else {
+        format!("{when}  {kind}  {} {}{author}", event.title, event.action)
+    }
+}
+
+/// ANSI foreground colour for each kind of timeline entry.
+fn kind_color(entity_type: EntityType) -> &'static str {
+    match entity_type {
+        EntityType::Brew => "33",
+        EntityType::Cup => "35",
+        EntityType::Roast => "32",
+        EntityType::Roaster => "36",
+        EntityType::Bag => "34",
+        EntityType::Cafe => "31",
+        EntityType::Gear | EntityType::Event | EntityType::Trip | EntityType::User => "37",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::*;
+
+    fn event() -> TimelineEvent {
+        TimelineEvent {
+            id: TimelineEventId::from(7),
+            entity_type: EntityType::Brew,
+            entity_id: 3,
+            action: "brewed".to_string(),
+            occurred_at: Utc::now(),
+            title: "Kochere".to_string(),
+            details: Vec::new(),
+            tasting_notes: Vec::new(),
+            slug: None,
+            roaster_slug: None,
+            brew_data: None,
+            created_by: Some(UserId::from(2)),
+        }
+    }
+
+    #[test]
+    fn plain_lines_carry_kind_title_and_author() {
+        let line = format_event(&event(), false);
+        assert!(line.contains("  brew     Kochere brewed (user 2)"));
+        assert!(!line.contains('\x1b'));
+    }
+
+    #[test]
+    fn coloured_lines_wrap_the_kind_in_its_colour() {
+        let line = format_event(&event(), true);
+        assert!(line.contains("\x1b[33mbrew   \x1b[0m"));
+    }
 }
@@ -83,7 +83,7 @@ pub async fn create_token(client: &BrewlogClient, cmd: CreateTokenCommand) -> Re
         result = rx => {
             result.context("callback server closed without receiving a token")?
         }
-        () = tokio::time::sleep(std::time::Duration::from_secs(120)) => {
+        () = tokio::time::sleep(std::time::Duration::from_mins(2)) => {
             return Err(anyhow!("timed out waiting for browser authentication (2 minutes)"));
         }
     };
@@ -0,0 +1,123 @@
+use anyhow::Result;
+use clap::{Args, Subcommand};
+
+use super::macros::{define_delete_command, define_get_command};
+use super::{parse_date, print_json};
+use crate::domain::ids::TripId;
+use crate::domain::trips::{NewTrip, UpdateTrip};
+use crate::domain::validation::Validate;
+use crate::infrastructure::client::BrewlogClient;
+
+#[derive(Debug, Subcommand)]
+pub enum TripCommands {
+    /// Log a trip away
+    Add(AddTripCommand),
+    /// List trips
+    List,
+    /// Get a trip by ID
+    Get(GetTripCommand),
+    /// Show the cafes visited and cups drunk on a trip
+    Summary(TripSummaryCommand),
+    /// Update a trip
+    Update(UpdateTripCommand),
+    /// Delete a trip
+    Delete(DeleteTripCommand),
+}
+
+pub async fn run(client: &BrewlogClient, cmd: TripCommands) -> Result<()> {
+    match cmd {
+        TripCommands::Add(c) => add_trip(client, c).await,
+        TripCommands::List => print_json(&client.trips().list().await?),
+        TripCommands::Get(c) => get_trip(client, c).await,
+        TripCommands::Summary(c) => trip_summary(client, c).await,
+        TripCommands::Update(c) => update_trip(client, c).await,
+        TripCommands::Delete(c) => delete_trip(client, c).await,
+    }
+}
+
+#[derive(Debug, Args)]
+pub struct AddTripCommand {
+    #[arg(long)]
+    pub name: String,
+    /// Where the trip went (e.g. "Lisbon, Portugal")
+    #[arg(long)]
+    pub location: String,
+    /// First day away (e.g. 2025-04-18)
+    #[arg(long)]
+    pub from: String,
+    /// Last day away; defaults to the first, for a day trip
+    #[arg(long)]
+    pub to: Option<String>,
+    #[arg(long)]
+    pub notes: Option<String>,
+}
+
+pub async fn add_trip(client: &BrewlogClient, command: AddTripCommand) -> Result<()> {
+    let start_date = parse_date(&command.from)?;
+    let end_date = match command.to.as_deref() {
+        Some(to) => parse_date(to)?,
+        None => start_date,
+    };
+    let payload = NewTrip {
+        name: command.name,
+        location: command.location,
+        start_date,
+        end_date,
+        notes: command.notes,
+        created_at: None,
+    }
+    .normalize();
+    payload.validate()?;
+
+    let trip = client.trips().create(&payload).await?;
+    print_json(&trip)
+}
+
+define_get_command!(GetTripCommand, get_trip, TripId, trips);
+
+#[derive(Debug, Args)]
+pub struct TripSummaryCommand {
+    #[arg(long)]
+    pub id: i64,
+}
+
+pub async fn trip_summary(client: &BrewlogClient, command: TripSummaryCommand) -> Result<()> {
+    let summary = client.trips().summary(TripId::new(command.id)).await?;
+    print_json(&summary)
+}
+
+#[derive(Debug, Args)]
+pub struct UpdateTripCommand {
+    #[arg(long)]
+    pub id: i64,
+    #[arg(long)]
+    pub name: Option<String>,
+    #[arg(long)]
+    pub location: Option<String>,
+    #[arg(long)]
+    pub from: Option<String>,
+    #[arg(long)]
+    pub to: Option<String>,
+    #[arg(long)]
+    pub notes: Option<String>,
+}
+
+pub async fn update_trip(client: &BrewlogClient, command: UpdateTripCommand) -> Result<()> {
+    let payload = UpdateTrip {
+        name: command.name,
+        location: command.location,
+        start_date: command.from.as_deref().map(parse_date).transpose()?,
+        end_date: command.to.as_deref().map(parse_date).transpose()?,
+        notes: command.notes,
+    }
+    .normalize();
+    payload.validate()?;
+
+    let trip = client
+        .trips()
+        .update(TripId::new(command.id), &payload)
+        .await?;
+    print_json(&trip)
+}
+
+define_delete_command!(DeleteTripCommand, delete_trip, TripId, trips, "trip");
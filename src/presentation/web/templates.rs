@@ -1,11 +1,16 @@
 use askama::Template;
 
 use super::views::{
-    BagDetailView, BagOptionView, BagView, BrewDefaultsView, BrewDetailView, BrewView,
-    CafeDetailView, CafeOptionView, CafeView, CupDetailView, CupView, GearDetailView,
-    GearOptionView, GearView, ListNavigator, NearbyCafeView, Paginated, QuickNoteView,
-    RoastDetailView, RoastOptionView, RoastView, RoasterDetailView, RoasterOptionView, RoasterView,
-    StatCard, StatsView, TimelineEventView, TimelineMonthView,
+    AliasView, AuthorOptionView, BagDetailView, BagFinishPromptView, BagLedgerEntryView,
+    BagOptionView, BagView, BrewDefaultsView, BrewDetailView, BrewShareView, BrewView,
+    CafeDetailView, CafeOptionView, CafeView, CommentView, CupDetailView, CupView, EventView,
+    GearDetailView, GearOptionView, GearView, GreenCoffeeView, GrinderCalibrationView,
+    ListNavigator, MapTilesView, MergeTargetView, NavSearchResultView, NearbyCafeView,
+    NotificationView, Paginated, QuickActionView, QuickNoteView, RoastBatchView,
+    RoastComparisonView, RoastDetailView, RoastLotView, RoastOptionView, RoastView,
+    RoasterDetailView, RoasterLeaderboardView, RoasterOptionView, RoasterView, SavedSearchView,
+    SpendView, StatCard, StatsView, TimelineArchiveLinkView, TimelineArchiveYearView,
+    TimelineEventView, TimelineMonthNavView, TimelineMonthView, TripDetailView, TripView,
 };
 use crate::domain::bags::BagSortKey;
 use crate::domain::brews::BrewSortKey;
@@ -14,7 +19,10 @@ use crate::domain::cups::CupSortKey;
 use crate::domain::gear::GearSortKey;
 use crate::domain::roasters::RoasterSortKey;
 use crate::domain::roasts::{RoastSortKey, RoastWithRoaster};
-use crate::domain::stats::{BrewingSummaryStats, ConsumptionStats, RoastSummaryStats};
+use crate::domain::stats::{
+    BrewingHabits, BrewingSummaryStats, ConsumptionStats, RoastSummaryStats,
+    RoasterLeaderboardSortKey, TastingStats,
+};
 use crate::domain::timeline::TimelineSortKey;
 
 #[derive(Template)]
@@ -25,6 +33,25 @@ pub struct RoasterListTemplate {
     pub navigator: ListNavigator<RoasterSortKey>,
 }
 
+/// A single roaster row, patched into an already-rendered list.
+#[derive(Template)]
+#[template(path = "partials/lists/roaster_row.html")]
+pub struct RoasterRowTemplate {
+    pub is_authenticated: bool,
+    pub roaster: RoasterView,
+}
+
+/// The favourite star for a roaster, roast or cafe, re-rendered after it
+/// is toggled.
+#[derive(Template)]
+#[template(path = "partials/favourite_toggle.html")]
+pub struct FavouriteToggleTemplate {
+    /// Collection segment of the entity's API path, e.g. `roasters`.
+    pub api_path: &'static str,
+    pub id: String,
+    pub favourite: bool,
+}
+
 #[derive(Template)]
 #[template(path = "partials/lists/roast_list.html")]
 pub struct RoastListTemplate {
@@ -33,6 +60,13 @@ pub struct RoastListTemplate {
     pub navigator: ListNavigator<RoastSortKey>,
 }
 
+#[derive(Template)]
+#[template(path = "partials/lists/roast_row.html")]
+pub struct RoastRowTemplate {
+    pub is_authenticated: bool,
+    pub roast: RoastView,
+}
+
 #[derive(Template)]
 #[template(path = "pages/timeline.html")]
 pub struct TimelineTemplate {
@@ -43,6 +77,52 @@ pub struct TimelineTemplate {
     pub events: Paginated<TimelineEventView>,
     pub navigator: ListNavigator<TimelineSortKey>,
     pub months: Vec<TimelineMonthView>,
+    pub authors: Vec<AuthorOptionView>,
+    /// `/timeline`, or the archive page for a single month.
+    pub path: String,
+    pub archive: Option<TimelineMonthNavView>,
+}
+
+impl TimelineTemplate {
+    /// True when the timeline is not narrowed to a single user.
+    pub fn showing_everyone(&self) -> bool {
+        !self.authors.iter().any(|author| author.selected)
+    }
+}
+
+#[derive(Template)]
+#[template(path = "pages/timeline_archive.html")]
+pub struct TimelineArchiveTemplate {
+    pub nav_active: &'static str,
+    pub is_authenticated: bool,
+    pub version_info: &'static crate::VersionInfo,
+
+    pub heading: String,
+    pub years: Vec<TimelineArchiveYearView>,
+    /// Neighbouring years, on the page for a single year.
+    pub previous_year: Option<TimelineArchiveLinkView>,
+    pub next_year: Option<TimelineArchiveLinkView>,
+}
+
+#[derive(Template)]
+#[template(path = "partials/notifications_menu.html")]
+pub struct NotificationsMenuFragment {
+    pub unread: u64,
+    pub notifications: Vec<NotificationView>,
+}
+
+#[derive(Template)]
+#[template(path = "partials/quick_actions.html")]
+pub struct QuickActionsFragment {
+    pub actions: Vec<QuickActionView>,
+}
+
+/// Matches for the header's quick-nav search box.
+#[derive(Template)]
+#[template(path = "partials/nav_search_results.html")]
+pub struct NavSearchResultsTemplate {
+    pub query: String,
+    pub results: Vec<NavSearchResultView>,
 }
 
 #[derive(Template)]
@@ -70,6 +150,20 @@ pub struct GearListTemplate {
     pub navigator: ListNavigator<GearSortKey>,
 }
 
+#[derive(Template)]
+#[template(path = "partials/lists/gear_row.html")]
+pub struct GearRowTemplate {
+    pub item: GearView,
+}
+
+#[derive(Template)]
+#[template(path = "partials/roaster_select.html")]
+pub struct RoasterSelectTemplate {
+    pub roaster_options: Vec<RoasterOptionView>,
+    /// The roaster picked when the picker loads, empty for none.
+    pub selected_roaster_id: String,
+}
+
 #[derive(Template)]
 #[template(path = "partials/roast_options.html")]
 pub struct RoastOptionsTemplate {
@@ -92,6 +186,13 @@ pub struct CafeListTemplate {
     pub navigator: ListNavigator<CafeSortKey>,
 }
 
+#[derive(Template)]
+#[template(path = "partials/lists/cafe_row.html")]
+pub struct CafeRowTemplate {
+    pub is_authenticated: bool,
+    pub cafe: CafeView,
+}
+
 #[derive(Template)]
 #[template(path = "partials/lists/cup_list.html")]
 pub struct CupListTemplate {
@@ -111,6 +212,9 @@ pub struct HomeTemplate {
     pub recent_brews: Vec<BrewView>,
     pub open_bags: Vec<BagView>,
     pub recent_events: Vec<TimelineEventView>,
+    pub favourite_roasters: Vec<RoasterView>,
+    pub favourite_roasts: Vec<RoastView>,
+    pub favourite_cafes: Vec<CafeView>,
     pub stats: StatsView,
     pub stat_cards: Vec<StatCard>,
 }
@@ -124,6 +228,12 @@ pub struct CheckInTemplate {
 
     pub roast_options: Vec<RoastOptionView>,
     pub cafe_options: Vec<CafeOptionView>,
+    /// Open bags, for cups brewed from your own coffee away from a cafe.
+    pub bag_options: Vec<BagOptionView>,
+    /// Pre-selected from a "Check in at …" quick action.
+    pub selected_cafe: Option<CafeOptionView>,
+    /// Set when a tile server is configured, for the location picker.
+    pub map_tiles: Option<MapTilesView>,
 }
 
 #[derive(Template)]
@@ -147,6 +257,7 @@ pub struct DataTemplate {
     pub tab_fetch_mode: &'static str,
     pub content: String,
     pub search_value: String,
+    pub saved_searches: Vec<SavedSearchView>,
 }
 
 pub struct Tab {
@@ -173,10 +284,13 @@ pub struct AddTemplate {
     pub grinder_options: Vec<GearOptionView>,
     pub brewer_options: Vec<GearOptionView>,
     pub filter_paper_options: Vec<GearOptionView>,
+    pub kettle_options: Vec<GearOptionView>,
     pub cafe_options: Vec<CafeOptionView>,
     pub defaults: BrewDefaultsView,
     pub quick_note_options: Vec<QuickNoteView>,
     pub pre_select_bag_id: Option<String>,
+    /// Set when a tile server is configured, for the cafe location picker.
+    pub map_tiles: Option<MapTilesView>,
 }
 
 #[derive(Template)]
@@ -197,10 +311,14 @@ pub struct StatsPageTemplate {
     pub roast_summary: RoastSummaryStats,
     pub consumption: ConsumptionStats,
     pub brewing_summary: BrewingSummaryStats,
+    pub tasting: TastingStats,
+    pub habits: BrewingHabits,
     pub grinder_weights: Vec<(String, f64, String)>,
     pub max_grinder_weight: f64,
     pub consumption_30d_weight: String,
     pub consumption_all_time_weight: String,
+    pub spend: SpendView,
+    pub leaderboard: String,
     pub cache_age: String,
     pub has_data: bool,
 }
@@ -211,6 +329,13 @@ pub struct StatsMapFragment<'a> {
     pub geo_stats: &'a crate::domain::country_stats::GeoStats,
 }
 
+#[derive(Template)]
+#[template(path = "partials/roaster_leaderboard.html")]
+pub struct RoasterLeaderboardFragment {
+    pub entries: Vec<RoasterLeaderboardView>,
+    pub navigator: ListNavigator<RoasterLeaderboardSortKey>,
+}
+
 #[derive(Template)]
 #[template(path = "pages/bag.html")]
 pub struct BagDetailTemplate {
@@ -221,8 +346,20 @@ pub struct BagDetailTemplate {
     pub bag: BagDetailView,
     pub roaster_slug: String,
     pub roast_slug: String,
+    /// The bag's own photo, e.g. of its label.
     pub image_url: Option<String>,
+    /// The roast's artwork, shown when the bag has no photo of its own.
+    pub roast_image_url: Option<String>,
+    /// Who logged this entry, shown on shared instances.
+    pub author: Option<String>,
     pub edit_url: String,
+    /// Grams left and bought, as plain numbers for the correction form.
+    pub remaining_grams: f64,
+    pub amount_grams: f64,
+    /// Brews made from the bag; the dialing-in chart needs at least two.
+    pub brew_count: usize,
+    /// Every change to the remaining weight, newest first.
+    pub ledger: Vec<BagLedgerEntryView>,
 }
 
 #[derive(Template)]
@@ -236,7 +373,24 @@ pub struct BrewDetailTemplate {
     pub roaster_slug: String,
     pub roast_slug: String,
     pub image_url: Option<String>,
+    /// Who logged this entry, shown on shared instances.
+    pub author: Option<String>,
     pub edit_url: String,
+    pub shares: Vec<BrewShareView>,
+    pub comments: Vec<CommentView>,
+    /// Set right after a brew leaves its bag nearly empty.
+    pub finish_prompt: Option<BagFinishPromptView>,
+}
+
+#[derive(Template)]
+#[template(path = "pages/shared_brew.html")]
+pub struct SharedBrewTemplate {
+    pub nav_active: &'static str,
+    pub is_authenticated: bool,
+    pub version_info: &'static crate::VersionInfo,
+    pub base_url: &'static str,
+    pub brew: BrewDetailView,
+    pub image_url: Option<String>,
 }
 
 #[derive(Template)]
@@ -249,11 +403,26 @@ pub struct CupDetailTemplate {
     pub cup: CupDetailView,
     pub roaster_slug: String,
     pub roast_slug: String,
-    pub cafe_slug: String,
     pub image_url: Option<String>,
+    /// Who logged this entry, shown on shared instances.
+    pub author: Option<String>,
     pub edit_url: String,
 }
 
+#[derive(Template)]
+#[template(path = "pages/compare.html")]
+pub struct CompareTemplate {
+    pub nav_active: &'static str,
+    pub is_authenticated: bool,
+    pub version_info: &'static crate::VersionInfo,
+    pub base_url: &'static str,
+    pub roast_options: Vec<RoastOptionView>,
+    /// Selected roast ID for each picker, empty for an unfilled slot.
+    pub slots: Vec<String>,
+    pub columns: Vec<RoastComparisonView>,
+    pub error: Option<String>,
+}
+
 #[derive(Template)]
 #[template(path = "pages/roast.html")]
 pub struct RoastDetailTemplate {
@@ -264,7 +433,16 @@ pub struct RoastDetailTemplate {
     pub roast: RoastDetailView,
     pub roaster_slug: String,
     pub image_url: Option<String>,
+    /// Who logged this entry, shown on shared instances.
+    pub author: Option<String>,
     pub edit_url: String,
+    pub delete_warning: String,
+    pub comments: Vec<CommentView>,
+    /// Every harvest of this coffee, this one included.
+    pub lots: Vec<RoastLotView>,
+    pub aliases: Vec<AliasView>,
+    /// Other roasts this one can be merged into; empty unless signed in.
+    pub merge_targets: Vec<MergeTargetView>,
 }
 
 #[derive(Template)]
@@ -276,7 +454,13 @@ pub struct RoasterDetailTemplate {
     pub base_url: &'static str,
     pub roaster: RoasterDetailView,
     pub image_url: Option<String>,
+    /// Who logged this entry, shown on shared instances.
+    pub author: Option<String>,
     pub edit_url: String,
+    pub delete_warning: String,
+    pub aliases: Vec<AliasView>,
+    /// Other roasters this one can be merged into; empty unless signed in.
+    pub merge_targets: Vec<MergeTargetView>,
 }
 
 #[derive(Template)]
@@ -288,7 +472,58 @@ pub struct CafeDetailTemplate {
     pub base_url: &'static str,
     pub cafe: CafeDetailView,
     pub image_url: Option<String>,
+    /// Who logged this entry, shown on shared instances.
+    pub author: Option<String>,
     pub edit_url: String,
+    pub delete_warning: String,
+    pub map_tiles: Option<MapTilesView>,
+}
+
+#[derive(Template)]
+#[template(path = "pages/roasting.html")]
+pub struct RoastingTemplate {
+    pub nav_active: &'static str,
+    pub is_authenticated: bool,
+    pub version_info: &'static crate::VersionInfo,
+    pub base_url: &'static str,
+    pub green_coffees: Vec<GreenCoffeeView>,
+    pub batches: Vec<RoastBatchView>,
+    /// Open bags a new batch can be linked to.
+    pub bag_options: Vec<BagOptionView>,
+}
+
+#[derive(Template)]
+#[template(path = "pages/events.html")]
+pub struct EventsTemplate {
+    pub nav_active: &'static str,
+    pub is_authenticated: bool,
+    pub version_info: &'static crate::VersionInfo,
+    pub base_url: &'static str,
+    pub events: Vec<EventView>,
+    /// Roasters and cafes a new event can be linked to.
+    pub roaster_options: Vec<RoasterOptionView>,
+    pub cafe_options: Vec<CafeOptionView>,
+}
+
+#[derive(Template)]
+#[template(path = "pages/trips.html")]
+pub struct TripsTemplate {
+    pub nav_active: &'static str,
+    pub is_authenticated: bool,
+    pub version_info: &'static crate::VersionInfo,
+    pub base_url: &'static str,
+    pub trips: Vec<TripView>,
+}
+
+#[derive(Template)]
+#[template(path = "pages/trip.html")]
+pub struct TripDetailTemplate {
+    pub nav_active: &'static str,
+    pub is_authenticated: bool,
+    pub version_info: &'static crate::VersionInfo,
+    pub base_url: &'static str,
+    pub trip: TripDetailView,
+    pub map_tiles: Option<MapTilesView>,
 }
 
 #[derive(Template)]
@@ -300,7 +535,13 @@ pub struct GearDetailTemplate {
     pub base_url: &'static str,
     pub gear: GearDetailView,
     pub image_url: Option<String>,
+    /// Who logged this entry, shown on shared instances.
+    pub author: Option<String>,
     pub edit_url: String,
+    pub is_grinder: bool,
+    pub calibrations: Vec<GrinderCalibrationView>,
+    /// Other grinders a new calibration point can be entered against.
+    pub other_grinders: Vec<GearOptionView>,
 }
 
 // ── Edit page templates ──
@@ -335,6 +576,9 @@ pub struct RoastEditTemplate {
     pub producer: String,
     pub process: String,
     pub tasting_notes: String,
+    /// The blend as text, e.g. `Brazil 60%, Ethiopia 40%`.
+    pub components: String,
+    pub harvest_year: String,
     pub roaster_options: Vec<RoasterOptionView>,
     pub image_url: Option<String>,
     pub signals_json: String,
@@ -352,7 +596,14 @@ pub struct BagEditTemplate {
     pub roast_date: String,
     pub amount: f64,
     pub remaining: f64,
+    pub low_stock_threshold: String,
+    pub default_low_stock_threshold: String,
+    pub price: String,
+    pub currency: String,
+    pub best_before: String,
+    pub batch_code: String,
     pub roast_options: Vec<RoastOptionView>,
+    pub image_url: Option<String>,
     pub signals_json: String,
 }
 
@@ -372,12 +623,18 @@ pub struct BrewEditTemplate {
     pub filter_paper_id: String,
     pub water_volume: i32,
     pub water_temp: f64,
+    pub kettle_id: String,
+    /// The measured water temperature, empty when it wasn't taken.
+    pub actual_water_temp: String,
+    /// Zero when the brew has no bypass; saving zero removes it.
+    pub bypass_water_ml: i32,
     pub brew_time: i32,
     pub quick_notes: String,
     pub bag_options: Vec<BagOptionView>,
     pub grinder_options: Vec<GearOptionView>,
     pub brewer_options: Vec<GearOptionView>,
     pub filter_paper_options: Vec<GearOptionView>,
+    pub kettle_options: Vec<GearOptionView>,
     pub quick_note_options: Vec<QuickNoteView>,
     pub image_url: Option<String>,
 }
@@ -397,6 +654,7 @@ pub struct CafeEditTemplate {
     pub website: String,
     pub image_url: Option<String>,
     pub signals_json: String,
+    pub map_tiles: Option<MapTilesView>,
 }
 
 #[derive(Template)]
@@ -408,8 +666,10 @@ pub struct CupEditTemplate {
     pub id: String,
     pub roast_id: String,
     pub roast_label: String,
+    /// Empty for cups drunk away from a cafe.
     pub cafe_id: String,
-    pub cafe_label: String,
+    /// Stars out of five, or 0 when unrated.
+    pub rating: u8,
     pub roast_options: Vec<RoastOptionView>,
     pub cafe_options: Vec<CafeOptionView>,
     pub image_url: Option<String>,
@@ -417,6 +677,7 @@ pub struct CupEditTemplate {
 
 #[derive(Template)]
 #[template(path = "pages/edit_gear.html")]
+#[allow(clippy::struct_excessive_bools)]
 pub struct GearEditTemplate {
     pub nav_active: &'static str,
     pub is_authenticated: bool,
@@ -425,6 +686,23 @@ pub struct GearEditTemplate {
     pub category: String,
     pub make: String,
     pub model: String,
+    pub is_brewer: bool,
+    /// The brewer's water temperature preset, empty if it has none.
+    pub water_temp: String,
+    /// The brewer's method as set or detected, empty if unknown.
+    pub brew_method: &'static str,
+    /// "true" or "false" as set or detected, empty if unknown.
+    pub uses_filter: &'static str,
+    pub is_kettle: bool,
+    /// The kettle's temperature offset, empty if it hasn't been calibrated.
+    pub temp_offset: String,
+    /// The kettle's water hardness in ppm, empty if unknown.
+    pub water_hardness: String,
+    pub is_filter_paper: bool,
+    /// Filter papers left, empty if stock isn't tracked.
+    pub stock_count: String,
+    /// The reorder threshold, empty to use the default.
+    pub low_stock_threshold: String,
     pub image_url: Option<String>,
     pub signals_json: String,
 }
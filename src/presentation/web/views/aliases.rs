@@ -0,0 +1,21 @@
+use crate::domain::aliases::Alias;
+
+pub struct AliasView {
+    pub id: String,
+    pub name: String,
+}
+
+impl From<Alias> for AliasView {
+    fn from(alias: Alias) -> Self {
+        Self {
+            id: alias.id.to_string(),
+            name: alias.name,
+        }
+    }
+}
+
+/// Another roaster or roast that a detail page's record can be merged into.
+pub struct MergeTargetView {
+    pub id: String,
+    pub label: String,
+}
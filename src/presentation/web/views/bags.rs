@@ -1,11 +1,14 @@
-use crate::domain::bags::BagWithRoast;
-use crate::domain::formatting::format_weight;
+use crate::domain::bag_ledger::{BagLedgerEntry, BagLedgerKind};
+use crate::domain::bags::{BagFinishSuggestion, BagWithRoast, BestBeforeStatus};
+use crate::domain::formatting::{format_money, format_weight};
 use crate::domain::roasters::Roaster;
 use crate::domain::roasts::Roast;
+use crate::domain::stats::SpendStats;
 
 use super::tasting_notes::TastingNoteView;
 use super::{
     LegendEntry, build_coffee_info, build_origin_roaster_map, build_roaster_info, format_datetime,
+    relative_date,
 };
 
 fn used_percent(amount: f64, remaining: f64) -> u8 {
@@ -32,10 +35,22 @@ pub struct BagView {
     pub roast_slug: String,
     pub roaster_slug: String,
     pub used_percent: u8,
+    /// Open and below its low-stock threshold.
+    pub reorder_soon: bool,
+    /// Set when an open bag is nearing or past its best-before date.
+    pub best_before_flag: Option<&'static str>,
+    /// The bag's own photo, or else its roast's artwork, on list rows.
+    pub thumbnail_url: Option<String>,
 }
 
-impl From<BagWithRoast> for BagView {
-    fn from(bag: BagWithRoast) -> Self {
+impl BagView {
+    /// `low_stock_threshold` is the instance default; bags may override it.
+    pub fn new(bag: BagWithRoast, low_stock_threshold: f64) -> Self {
+        let reorder_soon = bag.bag.is_running_low(low_stock_threshold);
+        let best_before_flag = bag
+            .bag
+            .best_before_status(super::now().date_naive())
+            .map(BestBeforeStatus::label);
         let used_percent = used_percent(bag.bag.amount, bag.bag.remaining);
         let (created_date, created_time) = format_datetime(bag.bag.created_at);
         Self {
@@ -56,6 +71,78 @@ impl From<BagWithRoast> for BagView {
             roast_slug: bag.roast_slug,
             roaster_slug: bag.roaster_slug,
             used_percent,
+            reorder_soon,
+            best_before_flag,
+            thumbnail_url: None,
+        }
+    }
+
+    #[must_use]
+    pub fn with_thumbnail(mut self, thumbnail_url: Option<String>) -> Self {
+        self.thumbnail_url = thumbnail_url;
+        self
+    }
+}
+
+pub struct BagFinishPromptView {
+    pub bag_id: String,
+    pub remaining: String,
+    pub has_remaining: bool,
+}
+
+impl From<BagFinishSuggestion> for BagFinishPromptView {
+    fn from(suggestion: BagFinishSuggestion) -> Self {
+        Self {
+            bag_id: suggestion.bag_id.to_string(),
+            remaining: format_weight(suggestion.remaining),
+            has_remaining: suggestion.remaining > 0.0,
+        }
+    }
+}
+
+pub struct BagLedgerEntryView {
+    /// What happened, e.g. "Brew" or "Moved to bag 4".
+    pub label: String,
+    /// Page about the brew or bag on the other side of the entry.
+    pub link: Option<String>,
+    /// Signed change, e.g. "+5g" or "-12g".
+    pub change: String,
+    pub balance: String,
+    pub reason: Option<String>,
+    pub author: Option<String>,
+    pub relative_date: String,
+    pub date_label: String,
+}
+
+impl BagLedgerEntryView {
+    pub fn new(entry: BagLedgerEntry, author: Option<String>) -> Self {
+        let (label, link) = match (entry.kind, entry.brew_id, entry.counterpart_bag_id) {
+            (BagLedgerKind::Initial, ..) => ("Opened".to_string(), None),
+            (BagLedgerKind::Brew, Some(brew_id), _) => {
+                ("Brew".to_string(), Some(format!("/brews/{brew_id}")))
+            }
+            (BagLedgerKind::Brew, None, _) => ("Brew".to_string(), None),
+            (BagLedgerKind::Adjustment, ..) => ("Correction".to_string(), None),
+            (BagLedgerKind::Transfer, _, Some(bag_id)) => {
+                let direction = if entry.delta < 0.0 { "to" } else { "from" };
+                (
+                    format!("Moved {direction} bag {bag_id}"),
+                    Some(format!("/bags/{bag_id}")),
+                )
+            }
+            (BagLedgerKind::Transfer, _, None) => ("Transfer".to_string(), None),
+        };
+        let sign = if entry.delta < 0.0 { "-" } else { "+" };
+        let (date, time) = format_datetime(entry.created_at);
+        Self {
+            label,
+            link,
+            change: format!("{sign}{}", format_weight(entry.delta.abs())),
+            balance: format_weight(entry.balance),
+            reason: entry.reason,
+            author,
+            relative_date: relative_date(entry.created_at),
+            date_label: format!("{date} {time}"),
         }
     }
 }
@@ -67,6 +154,8 @@ pub struct BagOptionView {
     pub roast_name: String,
     pub roaster_name: String,
     pub remaining: String,
+    /// Finished bags only appear when a brew already uses one.
+    pub open: bool,
 }
 
 pub struct BagDetailView {
@@ -92,6 +181,10 @@ pub struct BagDetailView {
     pub closed: bool,
     pub roast_date: Option<String>,
     pub finished_date: Option<String>,
+    pub price: Option<String>,
+    pub best_before: Option<String>,
+    pub best_before_flag: Option<&'static str>,
+    pub batch_code: Option<String>,
     // Map
     pub map_countries: String,
     pub map_max: u32,
@@ -110,7 +203,7 @@ impl BagDetailView {
         let roaster_info = build_roaster_info(roaster);
 
         let (map_countries, map_max, legend_entries) =
-            build_origin_roaster_map(roast.origin.as_deref(), &roaster.country);
+            build_origin_roaster_map(roast, &roaster.country);
         let (created_date, created_time) = format_datetime(bag.bag.created_at);
 
         Self {
@@ -138,6 +231,16 @@ impl BagDetailView {
                 .bag
                 .finished_at
                 .map(|d| d.format("%Y-%m-%d").to_string()),
+            price: bag
+                .bag
+                .price
+                .map(|price| format_money(price, bag.bag.currency.as_deref())),
+            best_before: bag.bag.best_before.map(|d| d.to_string()),
+            best_before_flag: bag
+                .bag
+                .best_before_status(super::now().date_naive())
+                .map(BestBeforeStatus::label),
+            batch_code: bag.bag.batch_code,
             map_countries,
             map_max,
             legend_entries,
@@ -159,6 +262,7 @@ impl From<BagWithRoast> for BagOptionView {
             roast_name: bag.roast_name,
             roaster_name: bag.roaster_name,
             remaining,
+            open: !bag.bag.closed,
         }
     }
 }
@@ -187,3 +291,50 @@ mod tests {
         assert_eq!(used_percent(100.0, 150.0), 0);
     }
 }
+
+/// What was spent on bags, for the stats page.
+pub struct SpendView {
+    pub total: String,
+    pub priced_bags: u64,
+    pub by_currency: Vec<CurrencySpendView>,
+    /// Currencies left out of the total for want of a rate, e.g. "JPY, USD".
+    pub unconverted: String,
+}
+
+pub struct CurrencySpendView {
+    pub currency: String,
+    pub bags: u64,
+    pub amount: String,
+    /// The amount in the base currency, when it was paid in another one.
+    pub converted: Option<String>,
+}
+
+impl From<SpendStats> for SpendView {
+    fn from(spend: SpendStats) -> Self {
+        let base = spend.base_currency.as_deref();
+        let by_currency = spend
+            .by_currency
+            .into_iter()
+            .map(|row| {
+                let foreign = row.currency.is_some() && row.currency.as_deref() != base;
+                CurrencySpendView {
+                    amount: format_money(row.amount, row.currency.as_deref()),
+                    converted: row
+                        .converted
+                        .filter(|_| foreign)
+                        .map(|value| format_money(value, base)),
+                    currency: row
+                        .currency
+                        .unwrap_or_else(|| base.unwrap_or("Unspecified").to_string()),
+                    bags: row.bags,
+                }
+            })
+            .collect();
+        Self {
+            total: format_money(spend.total, base),
+            priced_bags: spend.priced_bags,
+            by_currency,
+            unconverted: spend.unconverted.join(", "),
+        }
+    }
+}
@@ -1,7 +1,9 @@
-use std::fmt::Write;
-
-use crate::domain::brews::{BrewWithDetails, QuickNote, format_brew_time};
+use crate::domain::brew_shares::BrewShare;
+use crate::domain::brews::{
+    BrewWithDetails, QuickNote, format_brew_time, format_water, format_water_temp,
+};
 use crate::domain::formatting::format_weight;
+use crate::domain::quick_notes::CustomQuickNote;
 use crate::domain::roasters::Roaster;
 use crate::domain::roasts::Roast;
 
@@ -14,16 +16,28 @@ use super::{
 #[derive(Clone)]
 pub struct QuickNoteView {
     pub label: String,
+    /// Only custom notes carry an emoji.
+    pub emoji: String,
     pub pill_class: &'static str,
     pub form_value: String,
 }
 
-impl From<QuickNote> for QuickNoteView {
-    fn from(note: QuickNote) -> Self {
+impl QuickNoteView {
+    /// The form value as a JS string literal, for use in Datastar expressions.
+    pub fn js_value(&self) -> String {
+        serde_json::to_string(&self.form_value).unwrap_or_default()
+    }
+}
+
+impl From<&QuickNote> for QuickNoteView {
+    fn from(note: &QuickNote) -> Self {
         Self {
             label: note.label().to_string(),
+            emoji: String::new(),
             pill_class: if note.is_positive() {
                 "pill pill-success"
+            } else if note.is_custom() {
+                "pill pill-accent"
             } else {
                 "pill pill-warning"
             },
@@ -32,6 +46,63 @@ impl From<QuickNote> for QuickNoteView {
     }
 }
 
+impl From<&CustomQuickNote> for QuickNoteView {
+    fn from(note: &CustomQuickNote) -> Self {
+        Self {
+            emoji: note.emoji.clone(),
+            ..Self::from(&note.note())
+        }
+    }
+}
+
+/// Initial value for the brew form's `_quick-notes` signal: a JS array of
+/// the form values in a comma-separated list.
+pub fn quick_notes_signal(raw: &str) -> String {
+    let values: Vec<&str> = raw
+        .split(',')
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .collect();
+    serde_json::to_string(&values).unwrap_or_else(|_| "[]".to_string())
+}
+
+/// A share link as listed on the brew page. The link itself is only shown
+/// when it is created.
+#[derive(Clone)]
+pub struct BrewShareView {
+    pub id: String,
+    pub created_date: String,
+    pub expires_label: String,
+    pub status: &'static str,
+    pub is_active: bool,
+}
+
+impl From<&BrewShare> for BrewShareView {
+    fn from(share: &BrewShare) -> Self {
+        let now = super::now();
+        let (created_date, _) = format_datetime(share.created_at);
+        let expires_label = share.expires_at.map_or_else(
+            || "Never expires".to_string(),
+            |expires_at| format!("Expires {}", format_datetime(expires_at).0),
+        );
+        let status = if share.is_revoked() {
+            "Revoked"
+        } else if share.is_expired(now) {
+            "Expired"
+        } else {
+            "Active"
+        };
+
+        Self {
+            id: share.id.to_string(),
+            created_date,
+            expires_label,
+            status,
+            is_active: share.is_active(now),
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct BrewView {
     pub id: String,
@@ -55,34 +126,24 @@ pub struct BrewView {
     pub brew_time: Option<String>,
     pub quick_notes: Vec<QuickNoteView>,
     pub quick_notes_label: String,
-    pub quick_notes_raw: String,
     pub created_date: String,
     pub created_time: String,
     pub relative_date_label: String,
-    // Raw values for "brew again" feature
-    pub coffee_weight_raw: f64,
-    pub grind_setting_raw: f64,
-    pub water_volume_raw: i32,
-    pub water_temp_raw: f64,
-    pub brew_time_raw: Option<i32>,
+    /// The add-brew form pre-filled with this brew's parameters.
+    pub brew_again_url: String,
 }
 
 impl From<BrewWithDetails> for BrewView {
     fn from(brew: BrewWithDetails) -> Self {
-        let ratio = if brew.brew.coffee_weight > 0.0 {
-            format!(
-                "1:{:.1}",
-                f64::from(brew.brew.water_volume) / brew.brew.coffee_weight
-            )
-        } else {
-            "\u{2014}".to_string()
-        };
+        let ratio = brew
+            .brew
+            .ratio()
+            .map_or_else(|| "\u{2014}".to_string(), |ratio| format!("1:{ratio:.1}"));
 
         let quick_notes: Vec<QuickNoteView> = brew
             .brew
             .quick_notes
             .iter()
-            .copied()
             .map(QuickNoteView::from)
             .collect();
         let quick_notes_label = quick_notes
@@ -90,12 +151,8 @@ impl From<BrewWithDetails> for BrewView {
             .map(|n| n.label.as_str())
             .collect::<Vec<_>>()
             .join(", ");
-        let quick_notes_raw = quick_notes
-            .iter()
-            .map(|n| n.form_value.as_str())
-            .collect::<Vec<_>>()
-            .join(",");
         let (created_date, created_time) = format_datetime(brew.brew.created_at);
+        let brew_again_url = brew.brew.brew_again_url();
 
         Self {
             id: brew.brew.id.to_string(),
@@ -116,48 +173,17 @@ impl From<BrewWithDetails> for BrewView {
                 .filter_paper_id
                 .map(crate::domain::ids::GearId::into_inner),
             filter_paper_name: brew.filter_paper_name,
-            water_volume: format!("{}ml", brew.brew.water_volume),
-            water_temp: format!("{:.1}\u{00B0}C", brew.brew.water_temp),
+            water_volume: format_water(brew.brew.water_volume, brew.brew.bypass_water_ml),
+            water_temp: format!("{:.1}\u{00B0}C", brew.brew.brewing_temp()),
             ratio,
             brew_time: brew.brew.brew_time.map(format_brew_time),
             quick_notes,
             quick_notes_label,
-            quick_notes_raw,
             created_date,
             created_time,
             relative_date_label: relative_date(brew.brew.created_at),
-            coffee_weight_raw: brew.brew.coffee_weight,
-            grind_setting_raw: brew.brew.grind_setting,
-            water_volume_raw: brew.brew.water_volume,
-            water_temp_raw: brew.brew.water_temp,
-            brew_time_raw: brew.brew.brew_time,
-        }
-    }
-}
-
-impl BrewView {
-    /// Build a URL to the add-brew form pre-filled with this brew's parameters.
-    pub fn brew_again_url(&self) -> String {
-        let mut url = format!(
-            "/add?type=brew&bag_id={}&coffee_weight={}&grinder_id={}&grind_setting={}&brewer_id={}&water_volume={}&water_temp={}",
-            self.bag_id,
-            self.coffee_weight_raw,
-            self.grinder_id,
-            self.grind_setting_raw,
-            self.brewer_id,
-            self.water_volume_raw,
-            self.water_temp_raw,
-        );
-        if let Some(fp_id) = self.filter_paper_id {
-            let _ = write!(url, "&filter_paper_id={fp_id}");
-        }
-        if let Some(bt) = self.brew_time_raw {
-            let _ = write!(url, "&brew_time={bt}");
-        }
-        if !self.quick_notes_raw.is_empty() {
-            let _ = write!(url, "&quick_notes={}", self.quick_notes_raw);
+            brew_again_url,
         }
-        url
     }
 }
 
@@ -173,6 +199,8 @@ pub struct BrewDefaultsView {
     pub grind_setting: f64,
     pub water_volume: i32,
     pub water_temp: f64,
+    pub bypass_water_ml: Option<i32>,
+    pub kettle_id: String,
     pub brew_time: Option<i32>,
     /// Comma-separated quick note form values (e.g. "good,too-fast") for pre-filling toggles.
     pub quick_notes_raw: String,
@@ -192,6 +220,8 @@ impl Default for BrewDefaultsView {
             grind_setting: 6.0,
             water_volume: 250,
             water_temp: 91.0,
+            bypass_water_ml: None,
+            kettle_id: String::new(),
             brew_time: Some(120),
             quick_notes_raw: String::new(),
         }
@@ -217,7 +247,10 @@ pub struct BrewDetailView {
     // Recipe
     pub coffee_weight: String,
     pub water_volume: String,
+    /// Water added after brewing, if any.
+    pub bypass_water: Option<String>,
     pub water_temp: String,
+    pub ratio: String,
     pub grind_setting: String,
     pub brew_time: Option<String>,
     pub quick_notes_label: String,
@@ -225,6 +258,7 @@ pub struct BrewDetailView {
     pub grinder_name: String,
     pub brewer_name: String,
     pub filter_paper_name: Option<String>,
+    pub kettle_name: Option<String>,
     // Map
     pub map_countries: String,
     pub map_max: u32,
@@ -243,14 +277,14 @@ impl BrewDetailView {
         let roaster_info = build_roaster_info(roaster);
 
         let (map_countries, map_max, legend_entries) =
-            build_origin_roaster_map(roast.origin.as_deref(), &roaster.country);
+            build_origin_roaster_map(roast, &roaster.country);
         let (created_date, created_time) = format_datetime(brew.brew.created_at);
 
         let quick_notes_label = brew
             .brew
             .quick_notes
             .iter()
-            .map(|n| n.label())
+            .map(QuickNote::label)
             .collect::<Vec<_>>()
             .join(", ");
 
@@ -272,13 +306,19 @@ impl BrewDetailView {
             roaster_homepage: roaster_info.homepage,
             coffee_weight: format_weight(brew.brew.coffee_weight),
             water_volume: format!("{}ml", brew.brew.water_volume),
-            water_temp: format!("{:.1}\u{00B0}C", brew.brew.water_temp),
+            bypass_water: brew.brew.bypass_water_ml.map(|ml| format!("{ml}ml")),
+            water_temp: format_water_temp(brew.brew.water_temp, brew.brew.actual_water_temp),
+            ratio: brew
+                .brew
+                .ratio()
+                .map_or_else(|| "\u{2014}".to_string(), |ratio| format!("1:{ratio:.1}")),
             grind_setting: format!("{:.1}", brew.brew.grind_setting),
             brew_time: brew.brew.brew_time.map(format_brew_time),
             quick_notes_label,
             grinder_name: brew.grinder_name,
             brewer_name: brew.brewer_name,
             filter_paper_name: brew.filter_paper_name,
+            kettle_name: brew.kettle_name,
             map_countries,
             map_max,
             legend_entries,
@@ -306,6 +346,12 @@ impl From<BrewWithDetails> for BrewDefaultsView {
             grind_setting: brew.brew.grind_setting,
             water_volume: brew.brew.water_volume,
             water_temp: brew.brew.water_temp,
+            bypass_water_ml: brew.brew.bypass_water_ml,
+            kettle_id: brew
+                .brew
+                .kettle_id
+                .map(|id| id.to_string())
+                .unwrap_or_default(),
             brew_time: brew.brew.brew_time,
             quick_notes_raw: String::new(),
         }
@@ -316,72 +362,9 @@ impl From<BrewWithDetails> for BrewDefaultsView {
 mod tests {
     use super::*;
 
-    fn dummy_brew_view(
-        filter_paper_id: Option<i64>,
-        brew_time_raw: Option<i32>,
-        quick_notes_raw: &str,
-    ) -> BrewView {
-        BrewView {
-            id: "1".to_string(),
-            bag_id: 10,
-            roast_name: "Test Roast".to_string(),
-            roaster_name: "Test Roaster".to_string(),
-            roast_slug: "test-roast".to_string(),
-            roaster_slug: "test-roaster".to_string(),
-            coffee_weight: "15.0g".to_string(),
-            grinder_id: 2,
-            grinder_name: "Grinder".to_string(),
-            grinder_model: "Model".to_string(),
-            grind_setting: "6.0".to_string(),
-            brewer_id: 3,
-            brewer_name: "Brewer".to_string(),
-            filter_paper_id,
-            filter_paper_name: filter_paper_id.map(|_| "Filter".to_string()),
-            water_volume: "250ml".to_string(),
-            water_temp: "91.0\u{00B0}C".to_string(),
-            ratio: "1:16.7".to_string(),
-            brew_time: brew_time_raw.map(|_| "2:00".to_string()),
-            quick_notes: vec![],
-            quick_notes_label: String::new(),
-            quick_notes_raw: quick_notes_raw.to_string(),
-            created_date: "2025-01-01".to_string(),
-            created_time: "12:00".to_string(),
-            relative_date_label: "today".to_string(),
-            coffee_weight_raw: 15.0,
-            grind_setting_raw: 6.0,
-            water_volume_raw: 250,
-            water_temp_raw: 91.0,
-            brew_time_raw,
-        }
-    }
-
-    #[test]
-    fn brew_again_url_all_params() {
-        let view = dummy_brew_view(Some(5), Some(120), "good,too-fast");
-        let url = view.brew_again_url();
-
-        assert!(url.starts_with("/add?type=brew&"));
-        assert!(url.contains("bag_id=10"));
-        assert!(url.contains("coffee_weight=15"));
-        assert!(url.contains("grinder_id=2"));
-        assert!(url.contains("grind_setting=6"));
-        assert!(url.contains("brewer_id=3"));
-        assert!(url.contains("water_volume=250"));
-        assert!(url.contains("water_temp=91"));
-        assert!(url.contains("filter_paper_id=5"));
-        assert!(url.contains("brew_time=120"));
-        assert!(url.contains("quick_notes=good,too-fast"));
-    }
-
     #[test]
-    fn brew_again_url_optional_omitted() {
-        let view = dummy_brew_view(None, None, "");
-        let url = view.brew_again_url();
-
-        assert!(url.starts_with("/add?type=brew&"));
-        assert!(url.contains("bag_id=10"));
-        assert!(!url.contains("filter_paper_id"));
-        assert!(!url.contains("brew_time"));
-        assert!(!url.contains("quick_notes"));
+    fn quick_notes_signal_is_a_js_array() {
+        assert_eq!(quick_notes_signal(""), "[]");
+        assert_eq!(quick_notes_signal("good, Juicy"), r#"["good","Juicy"]"#);
     }
 }
@@ -2,7 +2,7 @@ use crate::domain::cafes::Cafe;
 use crate::domain::countries::{country_to_iso, iso_to_flag_emoji};
 use crate::domain::nearby_cafes::NearbyCafeResult;
 
-use super::{LegendEntry, build_map_data, format_datetime};
+use super::{LegendEntry, build_map_data, build_map_points, format_datetime};
 
 pub struct CafeDetailView {
     pub id: String,
@@ -11,9 +11,11 @@ pub struct CafeDetailView {
     pub country: String,
     pub country_flag: String,
     pub website: Option<String>,
+    pub favourite: bool,
     pub map_url: String,
     pub map_countries: String,
     pub map_max: u32,
+    pub map_points: String,
     pub legend_entries: Vec<LegendEntry>,
     pub created_date: String,
     pub created_time: String,
@@ -26,6 +28,7 @@ impl From<Cafe> for CafeDetailView {
             .map(iso_to_flag_emoji)
             .unwrap_or_default();
         let (map_countries, map_max) = build_map_data(&[(&cafe.country, 1)]);
+        let map_points = build_map_points(&[(cafe.latitude, cafe.longitude, &cafe.name)]);
         let map_url = format!(
             "https://www.google.com/maps?q={},{}",
             cafe.latitude, cafe.longitude
@@ -38,9 +41,11 @@ impl From<Cafe> for CafeDetailView {
             country_flag,
             country: cafe.country,
             website: cafe.website,
+            favourite: cafe.favourite,
             map_url,
             map_countries,
             map_max,
+            map_points,
             legend_entries: vec![LegendEntry {
                 label: "Cafe",
                 opacity: "",
@@ -67,6 +72,17 @@ pub struct CafeView {
     pub created_date: String,
     pub created_time: String,
     pub created_at_sort_key: i64,
+    pub favourite: bool,
+    /// Set on list rows when the cafe has a photo.
+    pub thumbnail_url: Option<String>,
+}
+
+impl CafeView {
+    #[must_use]
+    pub fn with_thumbnail(mut self, thumbnail_url: Option<String>) -> Self {
+        self.thumbnail_url = thumbnail_url;
+        self
+    }
 }
 
 impl From<Cafe> for CafeView {
@@ -82,6 +98,8 @@ impl From<Cafe> for CafeView {
             website,
             created_at,
             updated_at: _,
+            favourite,
+            created_by: _,
         } = cafe;
 
         let website = website.unwrap_or_default();
@@ -111,10 +129,13 @@ impl From<Cafe> for CafeView {
             created_date,
             created_time,
             created_at_sort_key,
+            favourite,
+            thumbnail_url: None,
         }
     }
 }
 
+#[derive(Clone)]
 pub struct CafeOptionView {
     pub id: String,
     pub label: String,
@@ -122,6 +143,18 @@ pub struct CafeOptionView {
     pub city: String,
 }
 
+impl CafeOptionView {
+    /// The name as a JS string literal, for use in Datastar expressions.
+    pub fn js_name(&self) -> String {
+        serde_json::to_string(&self.name).unwrap_or_default()
+    }
+
+    /// The city as a JS string literal, for use in Datastar expressions.
+    pub fn js_city(&self) -> String {
+        serde_json::to_string(&self.city).unwrap_or_default()
+    }
+}
+
 impl From<Cafe> for CafeOptionView {
     fn from(cafe: Cafe) -> Self {
         Self {
@@ -0,0 +1,30 @@
+use crate::domain::comments::Comment;
+use crate::domain::ids::UserId;
+
+use super::{format_datetime, relative_date};
+
+pub struct CommentView {
+    pub id: String,
+    pub author: String,
+    pub body: String,
+    pub relative_date: String,
+    pub date_label: String,
+    pub is_edited: bool,
+    /// Whether the signed-in viewer wrote this comment and may edit it.
+    pub is_own: bool,
+}
+
+impl CommentView {
+    pub fn new(comment: Comment, viewer: Option<UserId>) -> Self {
+        let (date, time) = format_datetime(comment.created_at);
+        Self {
+            id: comment.id.to_string(),
+            relative_date: relative_date(comment.created_at),
+            date_label: format!("{date} {time}"),
+            is_edited: comment.is_edited(),
+            is_own: viewer == Some(comment.user_id),
+            author: comment.author,
+            body: comment.body,
+        }
+    }
+}
@@ -1,11 +1,14 @@
 use crate::domain::cafes::Cafe;
 use crate::domain::countries::{country_to_iso, iso_to_flag_emoji};
-use crate::domain::cups::CupWithDetails;
+use crate::domain::cups::{CupWithDetails, NO_CAFE_LABEL};
 use crate::domain::roasters::Roaster;
 use crate::domain::roasts::Roast;
 
 use super::tasting_notes::TastingNoteView;
-use super::{LegendEntry, build_coffee_info, build_map_data, build_roaster_info, format_datetime};
+use super::{
+    LegendEntry, MAP_WEIGHT_STEP, StarRatingView, build_coffee_info, build_map_data,
+    build_roaster_info, format_datetime, origin_map_entries,
+};
 
 #[derive(Clone)]
 pub struct CupView {
@@ -24,15 +27,16 @@ pub struct CupView {
 impl From<CupWithDetails> for CupView {
     fn from(cup: CupWithDetails) -> Self {
         let (created_date, created_time) = format_datetime(cup.cup.created_at);
+        let cafe_name = cup.place_name().to_string();
         Self {
             id: cup.cup.id.to_string(),
             roast_name: cup.roast_name,
             roaster_name: cup.roaster_name,
             roast_slug: cup.roast_slug,
             roaster_slug: cup.roaster_slug,
-            cafe_name: cup.cafe_name,
-            cafe_slug: cup.cafe_slug,
-            cafe_city: cup.cafe_city,
+            cafe_name,
+            cafe_slug: cup.cafe_slug.unwrap_or_default(),
+            cafe_city: cup.cafe_city.unwrap_or_default(),
             created_date,
             created_time,
         }
@@ -55,13 +59,12 @@ pub struct CupDetailView {
     pub roaster_country_flag: String,
     pub roaster_city: Option<String>,
     pub roaster_homepage: Option<String>,
-    // Cafe info
-    pub cafe_name: String,
-    pub cafe_city: String,
-    pub cafe_country: String,
-    pub cafe_country_flag: String,
-    pub cafe_website: Option<String>,
-    pub cafe_map_url: String,
+    /// Unset for cups drunk away from a cafe.
+    pub cafe: Option<CupCafeView>,
+    pub place_name: String,
+    /// Link to the bag a home-brewed cup was made from.
+    pub bag_path: Option<String>,
+    pub rating: Option<StarRatingView>,
     // Map
     pub map_countries: String,
     pub map_max: u32,
@@ -69,27 +72,56 @@ pub struct CupDetailView {
     // Slugs (for breadcrumbs)
     pub roaster_slug: String,
     pub roast_slug: String,
-    pub cafe_slug: String,
     // Dates
     pub created_date: String,
     pub created_time: String,
 }
 
+pub struct CupCafeView {
+    pub name: String,
+    pub slug: String,
+    pub city: String,
+    pub country: String,
+    pub country_flag: String,
+    pub website: Option<String>,
+    pub map_url: String,
+}
+
+impl From<&Cafe> for CupCafeView {
+    fn from(cafe: &Cafe) -> Self {
+        Self {
+            name: cafe.name.clone(),
+            slug: cafe.slug.clone(),
+            city: cafe.city.clone(),
+            country: cafe.country.clone(),
+            country_flag: country_to_iso(&cafe.country)
+                .map(iso_to_flag_emoji)
+                .unwrap_or_default(),
+            website: cafe.website.clone(),
+            map_url: format!(
+                "https://www.google.com/maps?q={},{}",
+                cafe.latitude, cafe.longitude
+            ),
+        }
+    }
+}
+
 impl CupDetailView {
-    pub fn from_parts(cup: CupWithDetails, roast: &Roast, roaster: &Roaster, cafe: &Cafe) -> Self {
+    pub fn from_parts(
+        cup: CupWithDetails,
+        roast: &Roast,
+        roaster: &Roaster,
+        cafe: Option<&Cafe>,
+    ) -> Self {
         let coffee = build_coffee_info(roast);
         let roaster_info = build_roaster_info(roaster);
 
-        let cafe_country_flag = country_to_iso(&cafe.country)
-            .map(iso_to_flag_emoji)
-            .unwrap_or_default();
-
         let mut map_entries: Vec<(&str, u32)> = Vec::new();
-        map_entries.push((cafe.country.as_str(), 3));
-        for o in crate::domain::countries::parse_origins(roast.origin.as_deref()) {
-            map_entries.push((o, 2));
+        if let Some(cafe) = cafe {
+            map_entries.push((cafe.country.as_str(), 3 * MAP_WEIGHT_STEP));
         }
-        map_entries.push((roaster.country.as_str(), 1));
+        map_entries.extend(origin_map_entries(roast, 2));
+        map_entries.push((roaster.country.as_str(), MAP_WEIGHT_STEP));
         let (map_countries, map_max) = build_map_data(&map_entries);
         let (created_date, created_time) = format_datetime(cup.cup.created_at);
 
@@ -107,21 +139,18 @@ impl CupDetailView {
             roaster_country_flag: roaster_info.country_flag,
             roaster_city: roaster_info.city,
             roaster_homepage: roaster_info.homepage,
-            cafe_name: cafe.name.clone(),
-            cafe_city: cafe.city.clone(),
-            cafe_country: cafe.country.clone(),
-            cafe_country_flag,
-            cafe_website: cafe.website.clone(),
-            cafe_map_url: format!(
-                "https://www.google.com/maps?q={},{}",
-                cafe.latitude, cafe.longitude
-            ),
+            cafe: cafe.map(CupCafeView::from),
+            place_name: cafe.map_or_else(|| NO_CAFE_LABEL.to_string(), |cafe| cafe.name.clone()),
+            bag_path: cup.cup.bag_id.map(|bag_id| format!("/bags/{bag_id}")),
+            rating: cup
+                .cup
+                .rating
+                .map(|stars| StarRatingView::new(f64::from(stars))),
             roaster_slug: roaster.slug.clone(),
             roast_slug: roast.slug.clone(),
-            cafe_slug: cafe.slug.clone(),
             map_countries,
             map_max,
-            legend_entries: vec![
+            legend_entries: [
                 LegendEntry {
                     label: "Cafe",
                     opacity: "",
@@ -134,7 +163,10 @@ impl CupDetailView {
                     label: "Roaster",
                     opacity: "opacity-35",
                 },
-            ],
+            ]
+            .into_iter()
+            .skip(usize::from(cafe.is_none()))
+            .collect(),
             created_date,
             created_time,
         }
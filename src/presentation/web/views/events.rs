@@ -0,0 +1,50 @@
+use crate::domain::cafes::Cafe;
+use crate::domain::events::Event;
+use crate::domain::roasters::Roaster;
+
+/// A roaster or cafe met at an event.
+pub struct EventLinkView {
+    pub name: String,
+    pub url: String,
+}
+
+/// A coffee event, with the roasters and cafes met there resolved to links.
+pub struct EventView {
+    pub id: String,
+    pub name: String,
+    pub venue: Option<String>,
+    pub date: String,
+    pub notes: Option<String>,
+    pub coffees: Vec<String>,
+    pub roasters: Vec<EventLinkView>,
+    pub cafes: Vec<EventLinkView>,
+}
+
+impl EventView {
+    pub fn new(event: &Event, roasters: &[Roaster], cafes: &[Cafe]) -> Self {
+        Self {
+            id: event.id.to_string(),
+            name: event.name.clone(),
+            venue: event.venue.clone(),
+            date: event.date.format("%Y-%m-%d").to_string(),
+            notes: event.notes.clone(),
+            coffees: event.coffees.clone(),
+            roasters: roasters
+                .iter()
+                .filter(|r| event.roaster_ids.contains(&r.id))
+                .map(|r| EventLinkView {
+                    name: r.name.clone(),
+                    url: format!("/roasters/{}", r.slug),
+                })
+                .collect(),
+            cafes: cafes
+                .iter()
+                .filter(|c| event.cafe_ids.contains(&c.id))
+                .map(|c| EventLinkView {
+                    name: c.name.clone(),
+                    url: format!("/cafes/{}", c.slug),
+                })
+                .collect(),
+        }
+    }
+}
@@ -1,4 +1,6 @@
-use crate::domain::gear::Gear;
+use crate::domain::gear::{BrewMethod, Gear, GearCategory};
+use crate::domain::grinder_calibrations::GrinderCalibration;
+use crate::domain::ids::GearId;
 
 use super::format_datetime;
 
@@ -7,6 +9,18 @@ pub struct GearDetailView {
     pub category_label: String,
     pub make: String,
     pub model: String,
+    /// A brewer's water temperature preset, e.g. "93.0°C".
+    pub water_temp: Option<String>,
+    /// A brewer's method as set or detected, e.g. "Immersion".
+    pub brew_method: Option<&'static str>,
+    /// A kettle's calibration, e.g. "-2.0°C".
+    pub temp_offset: Option<String>,
+    /// A kettle's water hardness, e.g. "80 ppm".
+    pub water_hardness: Option<String>,
+    /// Filter papers left, e.g. "42 left".
+    pub stock: Option<String>,
+    pub reorder_soon: bool,
+    pub is_filter_paper: bool,
     pub created_date: String,
     pub created_time: String,
 }
@@ -14,11 +28,22 @@ pub struct GearDetailView {
 impl From<Gear> for GearDetailView {
     fn from(gear: Gear) -> Self {
         let (created_date, created_time) = format_datetime(gear.created_at);
+        let brew_method = gear.brewer_profile().method;
+        let reorder_soon = gear.is_running_low();
         Self {
             id: gear.id.to_string(),
             category_label: gear.category.display_label().to_string(),
             make: gear.make,
             model: gear.model,
+            water_temp: gear.water_temp.map(|temp| format!("{temp:.1}\u{00B0}C")),
+            brew_method: brew_method.as_ref().map(BrewMethod::display_label),
+            temp_offset: gear
+                .temp_offset
+                .map(|offset| format!("{offset:+.1}\u{00B0}C")),
+            water_hardness: gear.water_hardness.map(|ppm| format!("{ppm} ppm")),
+            stock: gear.stock_count.map(|count| format!("{count} left")),
+            reorder_soon,
+            is_filter_paper: gear.category == GearCategory::FilterPaper,
             created_date,
             created_time,
         }
@@ -33,6 +58,9 @@ pub struct GearView {
     pub make: String,
     pub model: String,
     pub full_name: String,
+    /// Filter papers left, e.g. "42 left".
+    pub stock: Option<String>,
+    pub reorder_soon: bool,
     pub created_date: String,
     pub created_time: String,
 }
@@ -47,6 +75,8 @@ impl From<Gear> for GearView {
             make: gear.make.clone(),
             model: gear.model.clone(),
             full_name: format!("{} {}", gear.make, gear.model),
+            stock: gear.stock_count.map(|count| format!("{count} left")),
+            reorder_soon: gear.is_running_low(),
             created_date,
             created_time,
         }
@@ -57,13 +87,56 @@ impl From<Gear> for GearView {
 pub struct GearOptionView {
     pub id: String,
     pub label: String,
+    /// "immersion" or "percolation" for brewers whose method is known.
+    pub method: &'static str,
+    /// "required", "none" or "optional": whether brews need a filter paper.
+    pub filter: &'static str,
 }
 
 impl From<Gear> for GearOptionView {
     fn from(gear: Gear) -> Self {
+        let profile = gear.brewer_profile();
         Self {
             id: gear.id.to_string(),
             label: format!("{} {}", gear.make, gear.model),
+            method: profile.method.as_ref().map_or("", BrewMethod::as_str),
+            filter: match profile.uses_filter {
+                Some(true) => "required",
+                Some(false) => "none",
+                None => "optional",
+            },
+        }
+    }
+}
+
+/// A calibration point as seen from one grinder's detail page.
+pub struct GrinderCalibrationView {
+    pub id: String,
+    pub setting: String,
+    pub other_grinder_label: String,
+    pub other_setting: String,
+}
+
+impl GrinderCalibrationView {
+    pub fn for_grinder(
+        point: &GrinderCalibration,
+        grinder_id: GearId,
+        grinders: &[GearOptionView],
+    ) -> Self {
+        let other_id = point.other_grinder(grinder_id);
+        let (setting, other_setting) = point
+            .oriented(grinder_id, other_id)
+            .unwrap_or((point.setting_a, point.setting_b));
+        let other_grinder_label = grinders
+            .iter()
+            .find(|g| g.id == other_id.to_string())
+            .map_or_else(|| "Unknown grinder".to_string(), |g| g.label.clone());
+
+        Self {
+            id: point.id.to_string(),
+            setting: format!("{setting:.1}"),
+            other_grinder_label,
+            other_setting: format!("{other_setting:.1}"),
         }
     }
 }
@@ -1,24 +1,46 @@
+mod aliases;
 mod bags;
 mod brews;
 mod cafes;
+mod comments;
 mod cups;
+mod events;
 mod gear;
+mod notifications;
 mod roasters;
+mod roasting;
 mod roasts;
+mod saved_searches;
 pub mod tasting_notes;
 mod timeline;
+mod trips;
 
-pub use bags::{BagDetailView, BagOptionView, BagView};
-pub use brews::{BrewDefaultsView, BrewDetailView, BrewView, QuickNoteView};
+pub use aliases::{AliasView, MergeTargetView};
+pub use bags::{
+    BagDetailView, BagFinishPromptView, BagLedgerEntryView, BagOptionView, BagView, SpendView,
+};
+pub use brews::{
+    BrewDefaultsView, BrewDetailView, BrewShareView, BrewView, QuickNoteView, quick_notes_signal,
+};
 pub use cafes::{CafeDetailView, CafeOptionView, CafeView, NearbyCafeView};
-pub use cups::{CupDetailView, CupView};
-pub use gear::{GearDetailView, GearOptionView, GearView};
-pub use roasters::{RoasterDetailView, RoasterOptionView, RoasterView};
-pub use roasts::{RoastDetailView, RoastOptionView, RoastView};
+pub use comments::CommentView;
+pub use cups::{CupCafeView, CupDetailView, CupView};
+pub use events::{EventLinkView, EventView};
+pub use gear::{GearDetailView, GearOptionView, GearView, GrinderCalibrationView};
+pub use notifications::NotificationView;
+pub use roasters::{RoasterDetailView, RoasterLeaderboardView, RoasterOptionView, RoasterView};
+pub use roasting::{GreenCoffeeView, RoastBatchView};
+pub use roasts::{
+    RegionInfoView, RoastBrewStatsView, RoastComparisonView, RoastDetailView, RoastLotView,
+    RoastOptionView, RoastPurchaseView, RoastView,
+};
+pub use saved_searches::SavedSearchView;
 pub use tasting_notes::TastingNoteView;
 pub use timeline::{
-    TimelineBrewDataView, TimelineEventDetailView, TimelineEventView, TimelineMonthView,
+    AuthorOptionView, TimelineArchiveLinkView, TimelineArchiveYearView, TimelineBrewDataView,
+    TimelineEventDetailView, TimelineEventView, TimelineMonthNavView, TimelineMonthView,
 };
+pub use trips::{TripDetailView, TripView};
 
 #[derive(Default)]
 pub struct StatsView {
@@ -41,18 +63,137 @@ impl StatsView {
     }
 }
 
+/// A star rating, drawn as a row of stars with the number beside it.
+pub struct StarRatingView {
+    /// Stars to fill, rounding to the nearest whole star.
+    pub filled: usize,
+    /// The rating as text, e.g. "4.3".
+    pub label: String,
+}
+
+impl StarRatingView {
+    pub fn new(stars: f64) -> Self {
+        let filled = (1..=crate::domain::ratings::MAX_STARS)
+            .take_while(|star| f64::from(*star) - 0.5 <= stars)
+            .count();
+        Self {
+            filled,
+            label: format!("{stars:.1}"),
+        }
+    }
+
+    /// Every star position, for templates to draw filled or empty.
+    pub fn positions(&self) -> std::ops::Range<usize> {
+        0..usize::from(crate::domain::ratings::MAX_STARS)
+    }
+}
+
 pub struct StatCard {
     pub icon: &'static str,
     pub value: String,
     pub label: &'static str,
 }
 
+/// A shortcut suggested on the home page.
+pub struct QuickActionView {
+    pub icon: &'static str,
+    pub label: String,
+    pub href: String,
+    pub reason: String,
+}
+
+impl From<QuickAction> for QuickActionView {
+    fn from(action: QuickAction) -> Self {
+        Self {
+            icon: match action.kind {
+                QuickActionKind::BrewAgain => "beaker",
+                QuickActionKind::CheckIn => "location",
+            },
+            label: action.label,
+            href: action.href,
+            reason: action.reason,
+        }
+    }
+}
+
+/// One entry in the header's quick-nav results.
+pub struct NavSearchResultView {
+    pub icon: &'static str,
+    pub label: String,
+    pub detail: String,
+    pub href: String,
+}
+
+impl From<Roaster> for NavSearchResultView {
+    fn from(roaster: Roaster) -> Self {
+        Self {
+            icon: "roaster",
+            detail: roaster.city.map_or_else(
+                || roaster.country.clone(),
+                |city| format!("{city}, {}", roaster.country),
+            ),
+            href: format!("/roasters/{}", roaster.slug),
+            label: roaster.name,
+        }
+    }
+}
+
+impl From<RoastWithRoaster> for NavSearchResultView {
+    fn from(roast: RoastWithRoaster) -> Self {
+        Self {
+            icon: "roast",
+            href: format!(
+                "/roasters/{}/roasts/{}",
+                roast.roaster_slug, roast.roast.slug
+            ),
+            label: roast.roast.name,
+            detail: roast.roaster_name,
+        }
+    }
+}
+
+impl From<Cafe> for NavSearchResultView {
+    fn from(cafe: Cafe) -> Self {
+        Self {
+            icon: "cafe",
+            detail: format!("{}, {}", cafe.city, cafe.country),
+            href: format!("/cafes/{}", cafe.slug),
+            label: cafe.name,
+        }
+    }
+}
+
+use std::sync::Arc;
+
 use chrono::{DateTime, Utc};
 
+use crate::domain::cafes::Cafe;
+use crate::domain::clock::{Clock, SystemClock};
 use crate::domain::listing::{DEFAULT_PAGE_SIZE, ListRequest, Page, PageSize, SortKey};
+use crate::domain::quick_actions::{QuickAction, QuickActionKind};
+use crate::domain::roasters::Roaster;
+use crate::domain::roasts::RoastWithRoaster;
+
+tokio::task_local! {
+    static RENDER_CLOCK: Arc<dyn Clock>;
+}
+
+/// Build views inside `render` against `clock`. The router wraps every
+/// request in this, so `From` conversions that need "now" see the app's
+/// clock without it being threaded through each one.
+pub async fn with_clock<F: Future>(clock: Arc<dyn Clock>, render: F) -> F::Output {
+    RENDER_CLOCK.scope(clock, render).await
+}
+
+/// The current request's clock, or the system clock outside a request.
+pub(crate) fn now() -> DateTime<Utc> {
+    RENDER_CLOCK
+        .try_with(|clock| clock.now())
+        .unwrap_or_else(|_| SystemClock.now())
+}
 
 fn relative_date(dt: DateTime<Utc>) -> String {
-    crate::domain::formatting::format_relative_time(dt, Utc::now())
+    crate::domain::formatting::format_relative_time(dt, now())
 }
 
 pub(crate) fn format_datetime(dt: DateTime<Utc>) -> (String, String) {
@@ -170,6 +311,7 @@ pub struct ListNavigator<K: SortKey> {
     fragment_path: String,
     request: ListRequest<K>,
     search: Option<String>,
+    filters: Vec<(&'static str, String)>,
 }
 
 impl<K: SortKey> ListNavigator<K> {
@@ -184,9 +326,17 @@ impl<K: SortKey> ListNavigator<K> {
             fragment_path: fragment_path.into(),
             request,
             search,
+            filters: Vec::new(),
         }
     }
 
+    /// Carry a filter query parameter through every link the navigator builds.
+    #[must_use]
+    pub fn with_filter(mut self, key: &'static str, value: impl Into<String>) -> Self {
+        self.filters.push((key, value.into()));
+        self
+    }
+
     pub const fn request(&self) -> ListRequest<K> {
         self.request
     }
@@ -279,6 +429,29 @@ impl<K: SortKey> ListNavigator<K> {
         self.search.is_some()
     }
 
+    /// Returns the active value of a filter, or an empty string when unset.
+    pub fn filter_value(&self, key: &str) -> &str {
+        self.filters
+            .iter()
+            .find(|(name, _)| *name == key)
+            .map_or("", |(_, value)| value.as_str())
+    }
+
+    pub fn has_filters(&self) -> bool {
+        !self.filters.is_empty()
+    }
+
+    /// Link to page 1 with `key` set to `value`, or removed when `value` is empty.
+    pub fn filter_href(&self, key: &'static str, value: &str) -> String {
+        self.with_filter_changed(key, value)
+            .build_href(&self.base_path, self.request.with_page(1))
+    }
+
+    pub fn fragment_filter_href(&self, key: &'static str, value: &str) -> String {
+        self.with_filter_changed(key, value)
+            .build_href(&self.fragment_path, self.request.with_page(1))
+    }
+
     /// Returns the base path (e.g., "/roasters") without query or fragment.
     pub fn path(&self) -> &str {
         &self.base_path
@@ -287,12 +460,14 @@ impl<K: SortKey> ListNavigator<K> {
     /// Returns query params for search actions (page reset to 1, preserves `sort/page_size`).
     /// Does NOT include the `q` parameter — the template appends it dynamically from JS.
     pub fn search_query_base(&self) -> String {
-        format!(
+        let mut qs = format!(
             "page=1&page_size={}&sort={}&dir={}",
             self.request.page_size().to_query_value(),
             self.request.sort_key().query_value(),
             self.request.sort_direction().as_str()
-        )
+        );
+        self.push_filters(&mut qs);
+        qs
     }
 
     /// Returns the full URL prefix for search: `{path}?{query_base}&q=` or `{path}&{query_base}&q=`
@@ -317,6 +492,24 @@ impl<K: SortKey> ListNavigator<K> {
         }
     }
 
+    fn with_filter_changed(&self, key: &'static str, value: &str) -> Self {
+        let mut navigator = self.clone();
+        navigator.filters.retain(|(name, _)| *name != key);
+        if !value.is_empty() {
+            navigator.filters.push((key, value.to_string()));
+        }
+        navigator
+    }
+
+    fn push_filters(&self, qs: &mut String) {
+        for (key, value) in &self.filters {
+            qs.push('&');
+            qs.push_str(key);
+            qs.push('=');
+            qs.push_str(&encode_uri_component(value));
+        }
+    }
+
     fn request_for_rows(request: ListRequest<K>, value: &str) -> ListRequest<K> {
         let page_size = page_size_from_text(value);
         request.with_page(1).with_page_size(page_size)
@@ -335,6 +528,7 @@ impl<K: SortKey> ListNavigator<K> {
             request.sort_key().query_value(),
             request.sort_direction().as_str()
         );
+        self.push_filters(&mut qs);
         if let Some(ref q) = self.search {
             qs.push_str("&q=");
             qs.push_str(&encode_uri_component(q));
@@ -387,11 +581,16 @@ pub(crate) struct CoffeeInfo {
 
 /// Build coffee info fields from a roast, using em dash for empty/missing values.
 pub(crate) fn build_coffee_info(roast: &crate::domain::roasts::Roast) -> CoffeeInfo {
-    use crate::domain::countries::origins_to_flags;
+    use crate::domain::blends::blend_label;
+    use crate::domain::countries::flags_for;
 
     let em_dash = "\u{2014}".to_string();
-    let origin = roast.origin.clone().unwrap_or_default();
-    let origin_flag = origins_to_flags(roast.origin.as_deref());
+    let origin = if roast.components.is_empty() {
+        roast.origin.clone().unwrap_or_default()
+    } else {
+        blend_label(&roast.components)
+    };
+    let origin_flag = flags_for(roast.origins());
 
     let notes = tasting_notes::parse_and_categorize(&roast.tasting_notes);
 
@@ -445,22 +644,43 @@ pub(crate) fn build_roaster_info(roaster: &crate::domain::roasters::Roaster) ->
     }
 }
 
+/// Map weights step in hundredths so a blend's smaller components can be
+/// shaded lighter than its main one.
+pub(crate) const MAP_WEIGHT_STEP: u32 = 100;
+
+/// Map entries for a roast's origins at `weight` steps. A blend's components
+/// are scaled by their share, relative to the largest.
+pub(crate) fn origin_map_entries(
+    roast: &crate::domain::roasts::Roast,
+    weight: u32,
+) -> Vec<(&str, u32)> {
+    use crate::domain::blends::origin_weights;
+
+    let full = weight * MAP_WEIGHT_STEP;
+    if roast.components.is_empty() {
+        return roast.origins().into_iter().map(|o| (o, full)).collect();
+    }
+    origin_weights(&roast.components)
+        .into_iter()
+        .map(|(origin, share)| {
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let scaled = (f64::from(full) * share).round() as u32;
+            (origin, scaled.max(1))
+        })
+        .collect()
+}
+
 /// Build origin + roaster country map data with standard legend entries.
 ///
-/// Origin gets weight 2, roaster country gets weight 1. Comma-separated
-/// origins (blends) are split so each country is highlighted on the map.
+/// Origins get weight 2, roaster country gets weight 1; a blend's origins
+/// are shaded by their share.
 /// Returns the `(data-countries, data-max, legend_entries)` tuple used by detail pages.
 pub(crate) fn build_origin_roaster_map(
-    origin: Option<&str>,
+    roast: &crate::domain::roasts::Roast,
     roaster_country: &str,
 ) -> (String, u32, Vec<LegendEntry>) {
-    use crate::domain::countries::parse_origins;
-
-    let mut entries: Vec<(&str, u32)> = Vec::new();
-    for o in parse_origins(origin) {
-        entries.push((o, 2));
-    }
-    entries.push((roaster_country, 1));
+    let mut entries = origin_map_entries(roast, 2);
+    entries.push((roaster_country, MAP_WEIGHT_STEP));
     let (map_countries, map_max) = build_map_data(&entries);
     (
         map_countries,
@@ -512,6 +732,31 @@ pub(crate) fn build_map_data(entries: &[(&str, u32)]) -> (String, u32) {
     (parts.join(","), max)
 }
 
+/// Present when a map tile server is configured; pages then draw the
+/// `<tile-map>` component instead of `<world-map>`.
+pub struct MapTilesView {
+    pub attribution: String,
+}
+
+impl MapTilesView {
+    pub fn from_settings(settings: &crate::domain::settings::Settings) -> Option<Self> {
+        settings.map_tile_url.as_ref().map(|_| Self {
+            attribution: settings.map_attribution.clone().unwrap_or_default(),
+        })
+    }
+}
+
+/// Build the `data-points` value for the tile-map component: a JSON array of
+/// `{lat, lng, label}` markers. Points with non-finite coordinates are skipped.
+pub(crate) fn build_map_points(points: &[(f64, f64, &str)]) -> String {
+    let markers: Vec<serde_json::Value> = points
+        .iter()
+        .filter(|(lat, lng, _)| lat.is_finite() && lng.is_finite())
+        .map(|&(lat, lng, label)| serde_json::json!({ "lat": lat, "lng": lng, "label": label }))
+        .collect();
+    serde_json::Value::Array(markers).to_string()
+}
+
 /// Build a JSON string for Datastar `data-signals` attribute initialization.
 ///
 /// Signal names may use kebab-case (`_roaster-name`); they are automatically
@@ -547,9 +792,11 @@ mod tests {
     use super::*;
 
     use chrono::TimeZone;
+    use proptest::prelude::*;
 
+    use crate::domain::bags::BagSortKey;
     use crate::domain::ids::{RoastId, RoasterId};
-    use crate::domain::listing::{DEFAULT_PAGE_SIZE, PageSize};
+    use crate::domain::listing::{DEFAULT_PAGE_SIZE, ListRequest, PageSize, SortDirection};
     use crate::domain::roasts::Roast;
 
     // ── StatsView::is_empty ─────────────────────────────────────────
@@ -712,6 +959,46 @@ mod tests {
         assert_eq!(encode_uri_component("-_.~"), "-_.~");
     }
 
+    // ── ListNavigator filters ───────────────────────────────────────
+
+    fn bag_navigator() -> ListNavigator<BagSortKey> {
+        ListNavigator::new(
+            "/data?type=bags",
+            "/data?type=bags#bag-list",
+            ListRequest::new(
+                2,
+                PageSize::limited(10),
+                BagSortKey::default(),
+                SortDirection::Desc,
+            ),
+            None,
+        )
+        .with_filter("status", "open")
+    }
+
+    #[test]
+    fn navigator_links_carry_filters() {
+        let navigator = bag_navigator();
+        assert_eq!(navigator.filter_value("status"), "open");
+        assert!(navigator.page_href(3).contains("&status=open"));
+        assert!(navigator.search_href_prefix().contains("&status=open&q="));
+    }
+
+    #[test]
+    fn navigator_filter_href_replaces_and_clears() {
+        let navigator = bag_navigator();
+        let closed = navigator.filter_href("status", "closed");
+        assert!(closed.contains("page=1&"));
+        assert!(closed.contains("status=closed"));
+        assert!(!closed.contains("status=open"));
+        assert!(!navigator.filter_href("status", "").contains("status="));
+        assert!(
+            navigator
+                .fragment_filter_href("status", "closed")
+                .ends_with("&status=closed#bag-list")
+        );
+    }
+
     // ── page_size_from_text ─────────────────────────────────────────
 
     #[test]
@@ -767,6 +1054,18 @@ mod tests {
         assert_eq!(max, 0);
     }
 
+    // ── build_map_points ────────────────────────────────────────────
+
+    #[test]
+    fn build_map_points_skips_non_finite_coordinates() {
+        let points = vec![(51.5, -0.12, "Prufrock"), (f64::NAN, 0.0, "Nowhere")];
+        let data: serde_json::Value = serde_json::from_str(&build_map_points(&points)).unwrap();
+        assert_eq!(
+            data,
+            serde_json::json!([{ "lat": 51.5, "lng": -0.12, "label": "Prufrock" }])
+        );
+    }
+
     // ── build_coffee_info ───────────────────────────────────────────
 
     fn make_roast(
@@ -786,7 +1085,12 @@ mod tests {
             producer: producer.map(String::from),
             tasting_notes: tasting_notes.into_iter().map(String::from).collect(),
             process: process.map(String::from),
+            components: Vec::new(),
+            harvest_year: None,
             created_at: Utc::now(),
+            favourite: false,
+            rating: None,
+            created_by: None,
         }
     }
 
@@ -837,4 +1141,71 @@ mod tests {
         assert_eq!(info.tasting_notes[1].label, "Jasmine");
         assert_eq!(info.tasting_notes[2].label, "Caramel");
     }
+
+    #[test]
+    fn blends_show_each_origin_and_share() {
+        let mut roast = make_roast(Some("Brazil, Ethiopia"), None, None, None, vec![]);
+        roast.components = crate::domain::blends::parse_blend("Brazil 80%, Ethiopia 20%").unwrap();
+
+        let info = build_coffee_info(&roast);
+        assert_eq!(info.origin, "Brazil 80%, Ethiopia 20%");
+        assert_eq!(info.origin_flag.split(' ').count(), 2);
+
+        let (countries, max, _) = build_origin_roaster_map(&roast, "Ethiopia");
+        let mut countries: Vec<&str> = countries.split(',').collect();
+        countries.sort_unstable();
+        assert_eq!(countries, ["BR:200", "ET:100"]);
+        assert_eq!(max, 200);
+    }
+
+    // ── Properties ──────────────────────────────────────────────────
+
+    fn percent_decode(encoded: &str) -> Vec<u8> {
+        let bytes = encoded.as_bytes();
+        let mut decoded = Vec::with_capacity(bytes.len());
+        let mut i = 0;
+        while i < bytes.len() {
+            if bytes[i] == b'%' {
+                let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).unwrap();
+                decoded.push(u8::from_str_radix(hex, 16).unwrap());
+                i += 3;
+            } else {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+        decoded
+    }
+
+    proptest! {
+        #[test]
+        fn encode_uri_component_round_trips(input in any::<String>()) {
+            let encoded = encode_uri_component(&input);
+            prop_assert!(
+                encoded
+                    .bytes()
+                    .all(|b| b.is_ascii_alphanumeric() || b"-_.~%".contains(&b))
+            );
+            prop_assert_eq!(percent_decode(&encoded), input.as_bytes());
+        }
+
+        #[test]
+        fn page_size_from_text_parses_any_u32(n in any::<u32>()) {
+            prop_assert_eq!(page_size_from_text(&n.to_string()), PageSize::limited(n));
+        }
+
+        #[test]
+        fn page_size_from_text_accepts_all_in_any_case(input in "[aA][lL][lL]") {
+            prop_assert_eq!(page_size_from_text(&input), PageSize::All);
+        }
+
+        #[test]
+        fn page_size_from_text_falls_back_on_garbage(input in "[^0-9]*") {
+            prop_assume!(!input.eq_ignore_ascii_case("all"));
+            prop_assert_eq!(
+                page_size_from_text(&input),
+                PageSize::limited(DEFAULT_PAGE_SIZE)
+            );
+        }
+    }
 }
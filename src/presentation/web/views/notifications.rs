@@ -0,0 +1,23 @@
+use crate::domain::notifications::Notification;
+
+use super::relative_date;
+
+pub struct NotificationView {
+    pub id: String,
+    pub title: String,
+    pub link: Option<String>,
+    pub relative_date: String,
+    pub is_read: bool,
+}
+
+impl From<Notification> for NotificationView {
+    fn from(notification: Notification) -> Self {
+        Self {
+            id: notification.id.to_string(),
+            relative_date: relative_date(notification.created_at),
+            is_read: notification.is_read(),
+            title: notification.title,
+            link: notification.link,
+        }
+    }
+}
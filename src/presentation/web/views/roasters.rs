@@ -1,5 +1,7 @@
 use crate::domain::countries::{country_to_iso, iso_to_flag_emoji};
+use crate::domain::formatting::format_weight;
 use crate::domain::roasters::Roaster;
+use crate::domain::stats::RoasterLeaderboardEntry;
 
 use super::{LegendEntry, build_map_data, format_datetime};
 
@@ -10,6 +12,7 @@ pub struct RoasterDetailView {
     pub country_flag: String,
     pub city: Option<String>,
     pub homepage: Option<String>,
+    pub favourite: bool,
     pub map_countries: String,
     pub map_max: u32,
     pub legend_entries: Vec<LegendEntry>,
@@ -32,6 +35,7 @@ impl From<Roaster> for RoasterDetailView {
             country: roaster.country,
             city: roaster.city,
             homepage: roaster.homepage,
+            favourite: roaster.favourite,
             map_countries,
             map_max,
             legend_entries: vec![LegendEntry {
@@ -80,6 +84,7 @@ pub struct RoasterView {
     pub created_date: String,
     pub created_time: String,
     pub created_at_sort_key: i64,
+    pub favourite: bool,
 }
 
 impl From<Roaster> for RoasterView {
@@ -92,6 +97,8 @@ impl From<Roaster> for RoasterView {
             city,
             homepage,
             created_at,
+            favourite,
+            created_by: _,
         } = roaster;
 
         let homepage = homepage.unwrap_or_default();
@@ -117,6 +124,36 @@ impl From<Roaster> for RoasterView {
             created_date,
             created_time,
             created_at_sort_key,
+            favourite,
+        }
+    }
+}
+
+/// A row in the stats page's roaster leaderboard.
+pub struct RoasterLeaderboardView {
+    pub rank: usize,
+    pub name: String,
+    pub detail_path: String,
+    pub grams_brewed: String,
+    pub brew_count: u64,
+    pub good_brews: String,
+    pub last_purchased: String,
+}
+
+impl RoasterLeaderboardView {
+    pub fn from_entry(rank: usize, entry: RoasterLeaderboardEntry) -> Self {
+        Self {
+            rank,
+            detail_path: format!("/roasters/{}", entry.slug),
+            grams_brewed: format_weight(entry.grams_brewed),
+            brew_count: entry.brew_count,
+            good_brews: entry
+                .good_brew_share()
+                .map_or_else(|| "—".to_string(), |share| format!("{:.0}%", share * 100.0)),
+            last_purchased: entry
+                .last_purchased_at
+                .map_or_else(|| "—".to_string(), |d| d.format("%Y-%m-%d").to_string()),
+            name: entry.name,
         }
     }
 }
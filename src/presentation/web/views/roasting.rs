@@ -0,0 +1,81 @@
+use crate::domain::formatting::{format_price, format_weight};
+use crate::domain::green_coffees::GreenCoffee;
+use crate::domain::roast_batches::RoastBatch;
+
+/// A lot of green coffee, with what's left of it to roast.
+pub struct GreenCoffeeView {
+    pub id: String,
+    pub name: String,
+    /// Origin, process and supplier, whichever are known, e.g.
+    /// "Ethiopia · Washed · Sweet Maria's".
+    pub details: String,
+    pub amount: String,
+    pub remaining: String,
+    pub is_used_up: bool,
+    pub price: Option<String>,
+    pub purchased_on: Option<String>,
+}
+
+impl From<&GreenCoffee> for GreenCoffeeView {
+    fn from(green: &GreenCoffee) -> Self {
+        let details = [&green.origin, &green.process, &green.supplier]
+            .into_iter()
+            .flatten()
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join(" · ");
+        Self {
+            id: green.id.to_string(),
+            name: green.name.clone(),
+            details,
+            amount: format_weight(green.amount),
+            remaining: format_weight(green.remaining.max(0.0)),
+            is_used_up: green.remaining <= 0.0,
+            price: green.price.map(format_price),
+            purchased_on: green.purchased_on.map(|d| d.format("%Y-%m-%d").to_string()),
+        }
+    }
+}
+
+/// One roast batch, with its weight loss and development ratio worked out.
+pub struct RoastBatchView {
+    pub id: String,
+    pub green_name: String,
+    pub roasted_on: String,
+    pub weight_in: String,
+    pub weight_out: String,
+    pub weight_loss: String,
+    /// Charge to drop as "m:ss".
+    pub total_time: Option<String>,
+    /// First crack to drop as "m:ss".
+    pub development_time: Option<String>,
+    pub development_ratio: Option<String>,
+    pub curve_notes: Option<String>,
+    pub bag_url: Option<String>,
+}
+
+impl RoastBatchView {
+    pub fn new(batch: &RoastBatch, greens: &[GreenCoffee]) -> Self {
+        let green_name = greens
+            .iter()
+            .find(|g| g.id == batch.green_coffee_id)
+            .map_or_else(|| "Unknown green coffee".to_string(), |g| g.name.clone());
+        Self {
+            id: batch.id.to_string(),
+            green_name,
+            roasted_on: batch.roasted_at.format("%Y-%m-%d").to_string(),
+            weight_in: format_weight(batch.weight_in),
+            weight_out: format_weight(batch.weight_out),
+            weight_loss: format!("{:.1}%", batch.weight_loss()),
+            total_time: batch.total_time.map(format_roast_time),
+            development_time: batch.development_time.map(format_roast_time),
+            development_ratio: batch.development_ratio().map(|r| format!("{r:.1}%")),
+            curve_notes: batch.curve_notes.clone(),
+            bag_url: batch.bag_id.map(|id| format!("/bags/{id}")),
+        }
+    }
+}
+
+fn format_roast_time(seconds: i64) -> String {
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
@@ -1,10 +1,16 @@
-use crate::domain::countries::origins_to_flags;
+use crate::domain::bags::Bag;
+use crate::domain::brews::RoastBrewStats;
+use crate::domain::countries::flags_for;
+use crate::domain::countries::iso_to_flag_emoji;
+use crate::domain::countries::regions::{ProducingRegion, known_regions};
+use crate::domain::formatting::{format_money, format_weight};
 use crate::domain::roasters::Roaster;
-use crate::domain::roasts::{Roast, RoastWithRoaster};
+use crate::domain::roasts::{Roast, RoastComparison, RoastWithRoaster};
 
 use super::tasting_notes::{self, TastingNoteView};
 use super::{
-    LegendEntry, build_coffee_info, build_origin_roaster_map, build_roaster_info, format_datetime,
+    BrewView, LegendEntry, StarRatingView, build_coffee_info, build_origin_roaster_map,
+    build_roaster_info, format_datetime,
 };
 
 pub struct RoastView {
@@ -22,6 +28,10 @@ pub struct RoastView {
     pub created_time: String,
     pub created_at_sort_key: i64,
     pub tasting_notes: Vec<TastingNoteView>,
+    pub favourite: bool,
+    pub rating: Option<StarRatingView>,
+    /// Set on list rows when the roast has artwork.
+    pub thumbnail_url: Option<String>,
 }
 
 impl RoastView {
@@ -39,6 +49,7 @@ impl RoastView {
     }
 
     fn from_parts(roast: Roast, roaster_name: &str, roaster_slug: &str) -> Self {
+        let origin_flag = flags_for(roast.origins());
         let Roast {
             id: roast_id,
             roaster_id: _,
@@ -49,7 +60,12 @@ impl RoastView {
             producer,
             tasting_notes,
             process,
+            components: _,
+            harvest_year: _,
             created_at,
+            favourite,
+            rating,
+            created_by: _,
         } = roast;
 
         let full_id = roast_id.to_string();
@@ -59,7 +75,6 @@ impl RoastView {
         } else {
             roaster_name.to_string()
         };
-        let origin_flag = origins_to_flags(origin.as_deref());
         let origin = origin.unwrap_or_else(|| "—".to_string());
         let region = region.unwrap_or_else(|| "—".to_string());
         let producer = producer.unwrap_or_else(|| "—".to_string());
@@ -84,13 +99,25 @@ impl RoastView {
             created_time,
             created_at_sort_key,
             tasting_notes,
+            favourite,
+            rating: rating.map(StarRatingView::new),
+            thumbnail_url: None,
         }
     }
+
+    #[must_use]
+    pub fn with_thumbnail(mut self, thumbnail_url: Option<String>) -> Self {
+        self.thumbnail_url = thumbnail_url;
+        self
+    }
 }
 
 pub struct RoastDetailView {
     pub id: String,
     pub name: String,
+    pub harvest_year: Option<i32>,
+    pub favourite: bool,
+    pub rating: Option<StarRatingView>,
     pub roaster_name: String,
     pub roaster_slug: String,
     // Coffee info
@@ -100,6 +127,8 @@ pub struct RoastDetailView {
     pub producer: String,
     pub process: String,
     pub tasting_notes: Vec<TastingNoteView>,
+    pub regions: Vec<RegionInfoView>,
+    pub brew_stats: Option<RoastBrewStatsView>,
     // Roaster info
     pub roaster_country: String,
     pub roaster_country_flag: String,
@@ -112,20 +141,33 @@ pub struct RoastDetailView {
     // Dates
     pub created_date: String,
     pub created_time: String,
+    // Purchases
+    pub purchases: Vec<RoastPurchaseView>,
+    /// Everything bought of this roast, in kilograms, e.g. "1.25kg".
+    pub total_bought: String,
 }
 
 impl RoastDetailView {
-    pub fn from_parts(roast: Roast, roaster: &Roaster) -> Self {
+    /// `purchases` are the roast's bags, oldest first.
+    pub fn from_parts(
+        roast: Roast,
+        roaster: &Roaster,
+        brew_stats: RoastBrewStats,
+        purchases: &[Bag],
+    ) -> Self {
         let coffee = build_coffee_info(&roast);
         let roaster_info = build_roaster_info(roaster);
 
         let (map_countries, map_max, legend_entries) =
-            build_origin_roaster_map(roast.origin.as_deref(), &roaster.country);
+            build_origin_roaster_map(&roast, &roaster.country);
         let (created_date, created_time) = format_datetime(roast.created_at);
 
         Self {
             id: roast.id.to_string(),
             name: roast.name,
+            harvest_year: roast.harvest_year,
+            favourite: roast.favourite,
+            rating: roast.rating.map(StarRatingView::new),
             roaster_name: roaster.name.clone(),
             roaster_slug: roaster.slug.clone(),
             origin: coffee.origin,
@@ -134,6 +176,11 @@ impl RoastDetailView {
             producer: coffee.producer,
             process: coffee.process,
             tasting_notes: coffee.tasting_notes,
+            regions: known_regions(roast.region.as_deref())
+                .into_iter()
+                .map(RegionInfoView::from)
+                .collect(),
+            brew_stats: RoastBrewStatsView::from_stats(brew_stats),
             roaster_country: roaster_info.country,
             roaster_country_flag: roaster_info.country_flag,
             roaster_city: roaster_info.city,
@@ -143,6 +190,130 @@ impl RoastDetailView {
             legend_entries,
             created_date,
             created_time,
+            purchases: purchases.iter().map(RoastPurchaseView::from).collect(),
+            total_bought: format!(
+                "{:.2}kg",
+                purchases.iter().map(|bag| bag.amount).sum::<f64>() / 1000.0
+            ),
+        }
+    }
+}
+
+/// One bag in a roast's purchase history.
+pub struct RoastPurchaseView {
+    pub bag_id: String,
+    pub date: String,
+    pub size: String,
+    pub price: Option<String>,
+}
+
+impl From<&Bag> for RoastPurchaseView {
+    fn from(bag: &Bag) -> Self {
+        Self {
+            bag_id: bag.id.to_string(),
+            date: bag.created_at.format("%Y-%m-%d").to_string(),
+            size: format_weight(bag.amount),
+            price: bag
+                .price
+                .map(|price| format_money(price, bag.currency.as_deref())),
+        }
+    }
+}
+
+/// One harvest of a coffee, linked from the other lots' detail pages.
+pub struct RoastLotView {
+    pub label: String,
+    pub detail_path: String,
+    pub brew_count: i64,
+    pub is_current: bool,
+}
+
+impl RoastLotView {
+    pub fn new(lot: &RoastWithRoaster, brew_count: i64, current: &Roast) -> Self {
+        let label = match lot.roast.harvest_year {
+            Some(year) => format!("{year} harvest"),
+            None => "No harvest year".to_string(),
+        };
+        Self {
+            label,
+            detail_path: format!("/roasters/{}/roasts/{}", lot.roaster_slug, lot.roast.slug),
+            brew_count,
+            is_current: lot.roast.id == current.id,
+        }
+    }
+}
+
+/// Brewing summary for a roast; absent until it has been brewed at least once.
+pub struct RoastBrewStatsView {
+    pub brew_count: i64,
+    pub good_brews: String,
+    pub total_coffee: String,
+    pub average_ratio: String,
+    pub grind_range: String,
+    pub best_brew: Option<BrewView>,
+}
+
+impl RoastBrewStatsView {
+    fn from_stats(stats: RoastBrewStats) -> Option<Self> {
+        if stats.brew_count == 0 {
+            return None;
+        }
+
+        let average_ratio = stats
+            .average_ratio
+            .map_or_else(|| "\u{2014}".to_string(), |ratio| format!("1:{ratio:.1}"));
+        let grind_range = match (stats.grind_min, stats.grind_max) {
+            (Some(min), Some(max)) if (max - min).abs() < f64::EPSILON => format!("{min:.1}"),
+            (Some(min), Some(max)) => format!("{min:.1}\u{2013}{max:.1}"),
+            _ => "\u{2014}".to_string(),
+        };
+
+        Some(Self {
+            brew_count: stats.brew_count,
+            good_brews: format!("{} of {}", stats.good_brew_count, stats.brew_count),
+            total_coffee: format_weight(stats.total_coffee_grams),
+            average_ratio,
+            grind_range,
+            best_brew: stats.best_brew.map(BrewView::from),
+        })
+    }
+}
+
+/// One column of the roast comparison table.
+pub struct RoastComparisonView {
+    pub roast: RoastView,
+    pub brew_stats: Option<RoastBrewStatsView>,
+}
+
+impl From<RoastComparison> for RoastComparisonView {
+    fn from(comparison: RoastComparison) -> Self {
+        Self {
+            roast: RoastView::from_list_item(comparison.roast),
+            brew_stats: RoastBrewStatsView::from_stats(comparison.brew_stats),
+        }
+    }
+}
+
+/// Typical growing conditions for a known producing region.
+pub struct RegionInfoView {
+    pub name: &'static str,
+    pub country: &'static str,
+    pub country_flag: String,
+    pub elevation: String,
+    pub varietals: String,
+}
+
+impl From<&'static ProducingRegion> for RegionInfoView {
+    fn from(region: &'static ProducingRegion) -> Self {
+        Self {
+            name: region.name,
+            country: region.country,
+            country_flag: region
+                .country_iso()
+                .map(iso_to_flag_emoji)
+                .unwrap_or_default(),
+            elevation: region.elevation_label(),
+            varietals: region.varietals.join(", "),
         }
     }
 }
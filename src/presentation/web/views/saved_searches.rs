@@ -0,0 +1,76 @@
+use crate::domain::saved_searches::SavedSearch;
+
+use super::encode_uri_component;
+
+pub struct SavedSearchView {
+    pub id: String,
+    pub name: String,
+    pub list: &'static str,
+    pub href: String,
+}
+
+impl From<SavedSearch> for SavedSearchView {
+    fn from(search: SavedSearch) -> Self {
+        let mut href = format!("/data?type={}&page=1", search.list.as_str());
+        if let Some(key) = &search.sort_key {
+            href.push_str("&sort=");
+            href.push_str(&encode_uri_component(key));
+        }
+        if let Some(direction) = search.sort_direction {
+            href.push_str("&dir=");
+            href.push_str(direction.as_str());
+        }
+        for (key, value) in &search.filters {
+            href.push('&');
+            href.push_str(&encode_uri_component(key));
+            href.push('=');
+            href.push_str(&encode_uri_component(value));
+        }
+        if let Some(q) = &search.search {
+            href.push_str("&q=");
+            href.push_str(&encode_uri_component(q));
+        }
+
+        Self {
+            id: search.id.to_string(),
+            name: search.name,
+            list: search.list.as_str(),
+            href,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use chrono::Utc;
+
+    use super::*;
+    use crate::domain::ids::{SavedSearchId, UserId};
+    use crate::domain::list_preferences::ListName;
+    use crate::domain::listing::SortDirection;
+
+    #[test]
+    fn href_serializes_search_filters_and_sort() {
+        let search = SavedSearch {
+            id: SavedSearchId::new(3),
+            user_id: UserId::new(1),
+            name: "Light Ethiopian, unopened bags".to_string(),
+            list: ListName::Bags,
+            search: Some("Ethiopia Guji".to_string()),
+            filters: BTreeMap::from([("status".to_string(), "open".to_string())]),
+            sort_key: Some("roaster".to_string()),
+            sort_direction: Some(SortDirection::Asc),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+
+        let view = SavedSearchView::from(search);
+        assert_eq!(
+            view.href,
+            "/data?type=bags&page=1&sort=roaster&dir=asc&status=open&q=Ethiopia%20Guji"
+        );
+        assert_eq!(view.list, "bags");
+    }
+}
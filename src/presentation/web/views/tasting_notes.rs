@@ -451,6 +451,8 @@ fn best_fuzzy_hit(input: &str) -> Option<NoteCategory> {
 mod tests {
     use super::*;
 
+    use proptest::prelude::*;
+
     #[test]
     fn exact_match_known_notes() {
         assert_eq!(categorize("Jasmine").pill_class, "pill pill-floral");
@@ -582,4 +584,38 @@ mod tests {
         let result = parse_and_categorize(&notes);
         assert!(result.is_empty());
     }
+
+    proptest! {
+        #[test]
+        fn categorize_keeps_the_label(note in any::<String>()) {
+            prop_assert_eq!(categorize(&note).label, note);
+        }
+
+        #[test]
+        fn categorize_ignores_ascii_case(note in "[ -~]{0,24}") {
+            prop_assert_eq!(
+                categorize(&note.to_ascii_uppercase()).pill_class,
+                categorize(&note.to_ascii_lowercase()).pill_class
+            );
+        }
+
+        #[test]
+        fn parse_and_categorize_yields_trimmed_segments(
+            notes in prop::collection::vec(any::<String>(), 0..4)
+        ) {
+            for view in parse_and_categorize(&notes) {
+                prop_assert!(!view.label.is_empty());
+                prop_assert_eq!(view.label.trim(), view.label.as_str());
+                prop_assert!(!view.label.contains([',', '\n']));
+            }
+        }
+
+        #[test]
+        fn levenshtein_is_a_metric(a in "\\PC{0,12}", b in "\\PC{0,12}") {
+            prop_assert_eq!(levenshtein(&a, &a), 0);
+            prop_assert_eq!(levenshtein(&a, &b), levenshtein(&b, &a));
+            let longest = a.chars().count().max(b.chars().count());
+            prop_assert!(levenshtein(&a, &b) <= longest);
+        }
+    }
 }
@@ -1,6 +1,8 @@
 use crate::domain::countries::{country_to_iso, iso_to_flag_emoji};
 use crate::domain::entity_type::EntityType;
-use crate::domain::timeline::{TimelineEvent, TimelineEventDetail};
+use crate::domain::timeline::{
+    TimelineEvent, TimelineEventDetail, TimelineMonth, TimelineMonthCount,
+};
 
 use super::relative_date;
 use super::tasting_notes::{self, TastingNoteView};
@@ -43,6 +45,7 @@ pub struct TimelineBrewDataView {
     pub water_volume: i32,
     pub water_temp: f64,
     pub brew_time: Option<i32>,
+    pub bypass_water_ml: Option<i32>,
 }
 
 #[derive(Clone)]
@@ -61,6 +64,16 @@ pub struct TimelineEventView {
     pub subtitle: Option<String>,
     pub tasting_notes: Option<Vec<TastingNoteView>>,
     pub brew_data: Option<TimelineBrewDataView>,
+    /// Name of the user who logged the event, shown on shared instances.
+    pub author: Option<String>,
+}
+
+/// A user the timeline can be filtered to.
+#[derive(Clone)]
+pub struct AuthorOptionView {
+    pub id: i64,
+    pub name: String,
+    pub selected: bool,
 }
 
 pub struct TimelineMonthView {
@@ -69,6 +82,101 @@ pub struct TimelineMonthView {
     pub events: Vec<TimelineEventView>,
 }
 
+/// A year or month of the timeline archive, with how many events it holds.
+#[derive(Clone)]
+pub struct TimelineArchiveLinkView {
+    pub label: String,
+    pub href: String,
+    pub count: u64,
+}
+
+impl TimelineArchiveLinkView {
+    /// `query` is appended to the link, e.g. `?user=2`.
+    pub fn month(count: TimelineMonthCount, query: &str) -> Self {
+        Self {
+            label: count.month.label(),
+            href: format!(
+                "/timeline/{}/{:02}{query}",
+                count.month.year(),
+                count.month.month()
+            ),
+            count: count.count,
+        }
+    }
+
+    fn year(year: i32, count: u64, query: &str) -> Self {
+        Self {
+            label: year.to_string(),
+            href: format!("/timeline/{year}{query}"),
+            count,
+        }
+    }
+}
+
+/// One year of the archive index and its months, newest first.
+pub struct TimelineArchiveYearView {
+    pub number: i32,
+    pub year: TimelineArchiveLinkView,
+    pub months: Vec<TimelineArchiveLinkView>,
+}
+
+impl TimelineArchiveYearView {
+    /// Groups newest-first month counts into years.
+    pub fn group(counts: &[TimelineMonthCount], query: &str) -> Vec<Self> {
+        let mut years: Vec<Self> = Vec::new();
+        for count in counts {
+            let number = count.month.year();
+            let month = TimelineArchiveLinkView {
+                label: count.month.name(),
+                ..TimelineArchiveLinkView::month(*count, query)
+            };
+            match years.last_mut() {
+                Some(last) if last.number == number => {
+                    last.year.count += count.count;
+                    last.months.push(month);
+                }
+                _ => years.push(Self {
+                    number,
+                    year: TimelineArchiveLinkView::year(number, count.count, query),
+                    months: vec![month],
+                }),
+            }
+        }
+        years
+    }
+}
+
+/// Heading and neighbouring months for a timeline narrowed to one month.
+pub struct TimelineMonthNavView {
+    pub heading: String,
+    pub year: TimelineArchiveLinkView,
+    pub previous: Option<TimelineArchiveLinkView>,
+    pub next: Option<TimelineArchiveLinkView>,
+}
+
+impl TimelineMonthNavView {
+    /// `counts` are newest first; the neighbours skip months with no events.
+    pub fn new(month: TimelineMonth, counts: &[TimelineMonthCount], query: &str) -> Self {
+        let year_count = counts
+            .iter()
+            .filter(|count| count.month.year() == month.year())
+            .map(|count| count.count)
+            .sum();
+        Self {
+            heading: month.label(),
+            year: TimelineArchiveLinkView::year(month.year(), year_count, query),
+            previous: counts
+                .iter()
+                .find(|count| count.month < month)
+                .map(|count| TimelineArchiveLinkView::month(*count, query)),
+            next: counts
+                .iter()
+                .rfind(|count| count.month > month)
+                .map(|count| TimelineArchiveLinkView::month(*count, query)),
+        }
+    }
+}
+
 impl From<TimelineEvent> for TimelineEventView {
     fn from(event: TimelineEvent) -> Self {
         let TimelineEvent {
@@ -83,6 +191,7 @@ impl From<TimelineEvent> for TimelineEventView {
             slug,
             roaster_slug,
             brew_data,
+            created_by: _,
         } = event;
 
         let entity_type_str = entity_type.as_str();
@@ -96,6 +205,8 @@ impl From<TimelineEvent> for TimelineEventView {
             (EntityType::Brew, "brewed") => "Brew Added",
             (EntityType::Cafe, "added") => "Cafe Added",
             (EntityType::Cup, "added") => "Cup Added",
+            (EntityType::Event, "attended") => "Event Attended",
+            (EntityType::Trip, "travelled") => "Trip",
             _ => "Event",
         };
 
@@ -104,6 +215,9 @@ impl From<TimelineEvent> for TimelineEventView {
             EntityType::Cup => format!("/cups/{entity_id}"),
             EntityType::Bag => format!("/bags/{entity_id}"),
             EntityType::Gear => format!("/gear/{entity_id}"),
+            EntityType::Event => format!("/events#event-{entity_id}"),
+            EntityType::Trip => format!("/trips/{entity_id}"),
+            EntityType::User => "/profile".to_string(),
             EntityType::Roaster => slug.as_deref().map_or_else(
                 || "/data?type=roasters".to_string(),
                 |s| format!("/roasters/{s}"),
@@ -142,6 +256,7 @@ impl From<TimelineEvent> for TimelineEventView {
             water_volume: bd.water_volume,
             water_temp: bd.water_temp,
             brew_time: bd.brew_time,
+            bypass_water_ml: bd.bypass_water_ml,
         });
 
         Self {
@@ -150,7 +265,9 @@ impl From<TimelineEvent> for TimelineEventView {
             kind_label,
             date_label: occurred_at.format("%b %d, %y").to_string(),
             relative_date_label: relative_date(occurred_at),
-            time_label: Some(occurred_at.format("%H:%M UTC").to_string()),
+            // Events and trips are logged by day, so their midnight timestamp means nothing.
+            time_label: (!matches!(entity_type, EntityType::Event | EntityType::Trip))
+                .then(|| occurred_at.format("%H:%M UTC").to_string()),
             iso_timestamp: occurred_at.to_rfc3339(),
             title,
             link,
@@ -159,11 +276,18 @@ impl From<TimelineEvent> for TimelineEventView {
             subtitle,
             tasting_notes,
             brew_data: brew_data_view,
+            author: None,
         }
     }
 }
 
 impl TimelineEventView {
+    #[must_use]
+    pub fn with_author(mut self, author: Option<String>) -> Self {
+        self.author = author;
+        self
+    }
+
     fn build_subtitle(entity_type: &str, details: &[TimelineEventDetailView]) -> Option<String> {
         let find_value = |label: &str| {
             details
@@ -177,6 +301,8 @@ impl TimelineEventView {
             "brew" => &["Roaster", "Brewer"],
             "roast" => &["Roaster", "Origin"],
             "roaster" | "cafe" => &["City", "Country"],
+            "event" => &["Venue"],
+            "trip" => &["Location", "Dates"],
             "cup" => &["Roaster", "Cafe"],
             _ => &[],
         };
@@ -0,0 +1,82 @@
+use std::collections::HashSet;
+
+use crate::domain::trips::{Trip, TripSummary};
+
+use super::{CafeView, CupView, LegendEntry, build_map_data, build_map_points};
+
+/// A row on the trips page.
+pub struct TripView {
+    pub id: String,
+    pub name: String,
+    pub location: String,
+    pub start_date: String,
+    pub end_date: String,
+    pub days: i64,
+    pub notes: Option<String>,
+}
+
+impl From<&Trip> for TripView {
+    fn from(trip: &Trip) -> Self {
+        Self {
+            id: trip.id.to_string(),
+            name: trip.name.clone(),
+            location: trip.location.clone(),
+            start_date: trip.start_date.format("%Y-%m-%d").to_string(),
+            end_date: trip.end_date.format("%Y-%m-%d").to_string(),
+            days: trip.days(),
+            notes: trip.notes.clone(),
+        }
+    }
+}
+
+/// A trip's summary page: where it went, the cafes visited and the coffees
+/// drunk along the way.
+pub struct TripDetailView {
+    pub trip: TripView,
+    pub map_countries: String,
+    pub map_max: u32,
+    /// Markers for each cafe visited, for the tile map.
+    pub map_points: String,
+    pub legend_entries: Vec<LegendEntry>,
+    pub cafes: Vec<CafeView>,
+    pub cups: Vec<CupView>,
+    /// Distinct roasts among the cups.
+    pub coffee_count: usize,
+}
+
+impl From<TripSummary> for TripDetailView {
+    fn from(summary: TripSummary) -> Self {
+        let TripSummary { trip, cups, cafes } = summary;
+
+        let map_entries: Vec<(&str, u32)> = cafes
+            .iter()
+            .map(|cafe| (cafe.country.as_str(), 1))
+            .collect();
+        let (map_countries, map_max) = build_map_data(&map_entries);
+        let map_points = build_map_points(
+            &cafes
+                .iter()
+                .map(|cafe| (cafe.latitude, cafe.longitude, cafe.name.as_str()))
+                .collect::<Vec<_>>(),
+        );
+        let coffee_count = cups
+            .iter()
+            .map(|cwd| cwd.cup.roast_id)
+            .collect::<HashSet<_>>()
+            .len();
+
+        Self {
+            trip: TripView::from(&trip),
+            map_countries,
+            map_max,
+            map_points,
+            legend_entries: vec![LegendEntry {
+                label: "Cafe visited",
+                opacity: "",
+            }],
+            cafes: cafes.into_iter().map(CafeView::from).collect(),
+            cups: cups.into_iter().map(CupView::from).collect(),
+            coffee_count,
+        }
+    }
+}
@@ -0,0 +1,465 @@
+//! Spawn a real Brewlog server on a random local port, backed by an
+//! in-memory database, for integration tests. Enabled by the `test-support`
+//! feature.
+//!
+//! ```ignore
+//! let app = crate::test_support::spawn_app_with_auth().await;
+//! let response = reqwest::Client::new()
+//!     .get(app.api_url("/roasters"))
+//!     .bearer_auth(app.auth_token.as_ref().unwrap())
+//!     .send()
+//!     .await?;
+//! ```
+
+// Setup failures can only be reported by failing the test.
+#![allow(clippy::expect_used, clippy::unwrap_used)]
+
+use std::sync::Arc;
+
+use tokio::net::TcpListener;
+use tokio::task::AbortHandle;
+use webauthn_rs::prelude::*;
+
+use crate::application::routes::{app_router, demo_app_router};
+use crate::application::services::{ImagePurger, StatsInvalidator, TimelineInvalidator};
+use crate::application::state::{AppState, AppStateConfig, RepositoryBackend};
+use crate::domain::clock::{Clock, SystemClock};
+use crate::domain::repositories::{
+    CafeRepository, RoastRepository, RoasterRepository, SessionRepository, TimelineEventRepository,
+    TokenRepository, UserRepository,
+};
+use crate::domain::sessions::NewSession;
+use crate::domain::tokens::NewToken;
+use crate::domain::users::NewUser;
+use crate::infrastructure::ai::{AiGuard, AiPolicy};
+use crate::infrastructure::auth::{
+    TokenHasher, generate_session_token, generate_token, hash_token,
+};
+use crate::infrastructure::backup::BackupService;
+use crate::infrastructure::database::Database;
+use crate::infrastructure::image_processing::ImageSettings;
+
+/// A running server. It stops when dropped.
+pub struct TestApp {
+    pub address: String,
+    pub roaster_repo: Arc<dyn RoasterRepository>,
+    pub roast_repo: Arc<dyn RoastRepository>,
+    pub cafe_repo: Arc<dyn CafeRepository>,
+    pub timeline_repo: Arc<dyn TimelineEventRepository>,
+    pub user_repo: Option<Arc<dyn UserRepository>>,
+    pub token_repo: Option<Arc<dyn TokenRepository>>,
+    pub session_repo: Option<Arc<dyn SessionRepository>>,
+    /// An API token for the `admin` user, once authenticated.
+    pub auth_token: Option<String>,
+    /// Stands in for Foursquare and `OpenRouter` in the `*_mock` spawners.
+    pub mock_server: Option<wiremock::MockServer>,
+    server_handle: AbortHandle,
+}
+
+impl TestApp {
+    /// `path` under `/api/v1`, e.g. `app.api_url("/roasters")`.
+    pub fn api_url(&self, path: &str) -> String {
+        format!("{}/api/v1{}", self.address, path)
+    }
+
+    pub fn webauthn_url(&self, path: &str) -> String {
+        format!("{}/api/v1/webauthn{}", self.address, path)
+    }
+
+    pub fn page_url(&self, path: &str) -> String {
+        format!("{}{}", self.address, path)
+    }
+}
+
+impl Drop for TestApp {
+    fn drop(&mut self) {
+        self.server_handle.abort();
+    }
+}
+
+fn test_webauthn() -> Arc<Webauthn> {
+    let rp_origin = url::Url::parse("http://localhost:0").expect("valid URL");
+    Arc::new(
+        WebauthnBuilder::new("localhost", &rp_origin)
+            .expect("valid RP config")
+            .rp_name("Brewlog Test")
+            .build()
+            .expect("valid WebAuthn"),
+    )
+}
+
+/// Spawn an app with no users, so every write is refused.
+pub async fn spawn_app() -> TestApp {
+    let database = Database::connect("sqlite::memory:")
+        .await
+        .expect("Failed to connect to in-memory database");
+
+    spawn_app_inner(database, test_state_config(), None).await
+}
+
+/// The configuration every spawner starts from: insecure cookies, no
+/// third-party API keys and background tasks that never run. Override fields
+/// with struct update syntax and pass the state to [`spawn_app_with_state`].
+pub fn test_state_config() -> AppStateConfig {
+    let (stats_tx, _stats_rx) = tokio::sync::mpsc::channel(1);
+    let (timeline_tx, _timeline_rx) = tokio::sync::mpsc::channel(1);
+    let (image_purge_tx, _image_purge_rx) = tokio::sync::mpsc::channel(1);
+    AppStateConfig {
+        webauthn: test_webauthn(),
+        insecure_cookies: true,
+        token_hasher: TokenHasher::sha256(),
+        foursquare_url: crate::infrastructure::foursquare::FOURSQUARE_SEARCH_URL.to_string(),
+        foursquare_api_key: String::new(),
+        openrouter_url: crate::infrastructure::ai::OPENROUTER_URL.to_string(),
+        openrouter_api_key: String::new(),
+        openrouter_model: "openrouter/free".to_string(),
+        stats_invalidator: StatsInvalidator::new(stats_tx),
+        timeline_invalidator: TimelineInvalidator::new(timeline_tx),
+        image_purger: ImagePurger::new(image_purge_tx),
+        clock: Arc::new(SystemClock),
+        repositories: RepositoryBackend::Sql,
+        image_settings: ImageSettings::default(),
+    }
+}
+
+async fn spawn_app_inner(
+    database: Database,
+    config: AppStateConfig,
+    mock_server: Option<wiremock::MockServer>,
+) -> TestApp {
+    let state = AppState::from_database(&database, config);
+    spawn_app_inner_from_state(state, mock_server).await
+}
+
+async fn spawn_app_inner_from_state(
+    state: AppState,
+    mock_server: Option<wiremock::MockServer>,
+) -> TestApp {
+    spawn_app_with_router(state, mock_server, app_router).await
+}
+
+/// Spawn an authenticated app serving `state`, e.g. one built from
+/// [`test_state_config`] over a database seeded beforehand.
+pub async fn spawn_app_with_state(state: AppState) -> TestApp {
+    let app = spawn_app_inner_from_state(state, None).await;
+    add_auth_to_app(app).await
+}
+
+/// Spawn an authenticated app the way `serve --demo` runs: the bundled
+/// sample data, with every write refused.
+pub async fn spawn_demo_app() -> TestApp {
+    let database = Database::connect("sqlite::memory:")
+        .await
+        .expect("Failed to connect to in-memory database");
+    crate::infrastructure::demo::load_sample(&BackupService::new(database.clone_pool()))
+        .await
+        .expect("Failed to load demo data");
+
+    let state = AppState::from_database(&database, test_state_config());
+    let app = spawn_app_with_router(state, None, demo_app_router).await;
+    add_auth_to_app(app).await
+}
+
+async fn spawn_app_with_router(
+    state: AppState,
+    mock_server: Option<wiremock::MockServer>,
+    router: fn(AppState) -> axum::Router,
+) -> TestApp {
+    // Clone repos we need for TestApp before consuming state in the router
+    let roaster_repo = state.roaster_repo.clone();
+    let roast_repo = state.roast_repo.clone();
+    let cafe_repo = state.cafe_repo.clone();
+    let timeline_repo = state.timeline_repo.clone();
+    let user_repo = state.user_repo.clone();
+    let token_repo = state.token_repo.clone();
+    let session_repo = state.session_repo.clone();
+
+    let app = router(state);
+
+    let listener = TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("Failed to bind to random port");
+
+    let local_addr = listener.local_addr().expect("Failed to get local address");
+    let address = format!("http://{local_addr}");
+
+    let server_handle = tokio::spawn(async move {
+        axum::serve(
+            listener,
+            app.into_make_service_with_connect_info::<std::net::SocketAddr>(),
+        )
+        .await
+        .expect("Server failed to start");
+    })
+    .abort_handle();
+
+    TestApp {
+        address,
+        roaster_repo,
+        roast_repo,
+        cafe_repo,
+        timeline_repo,
+        user_repo: Some(user_repo),
+        token_repo: Some(token_repo),
+        session_repo: Some(session_repo),
+        auth_token: None,
+        mock_server,
+        server_handle,
+    }
+}
+
+/// Spawn an app with an `admin` user whose API token is in
+/// [`TestApp::auth_token`].
+pub async fn spawn_app_with_auth() -> TestApp {
+    let app = spawn_app().await;
+    add_auth_to_app(app).await
+}
+
+/// Spawn an authenticated test app whose notion of "now" comes from `clock`.
+pub async fn spawn_app_with_clock(clock: Arc<dyn Clock>) -> TestApp {
+    let database = Database::connect("sqlite::memory:")
+        .await
+        .expect("Failed to connect to in-memory database");
+
+    let app = spawn_app_inner(
+        database,
+        AppStateConfig {
+            clock,
+            ..test_state_config()
+        },
+        None,
+    )
+    .await;
+
+    add_auth_to_app(app).await
+}
+
+/// Spawn an authenticated test app with custom image upload limits.
+pub async fn spawn_app_with_image_settings(image_settings: ImageSettings) -> TestApp {
+    let database = Database::connect("sqlite::memory:")
+        .await
+        .expect("Failed to connect to in-memory database");
+
+    let app = spawn_app_inner(
+        database,
+        AppStateConfig {
+            image_settings,
+            ..test_state_config()
+        },
+        None,
+    )
+    .await;
+
+    add_auth_to_app(app).await
+}
+
+/// Spawn an authenticated test app whose roasters, roasts, bags and gear are
+/// kept in memory instead of `SQLite`.
+pub async fn spawn_app_with_memory_repositories() -> TestApp {
+    let database = Database::connect("sqlite::memory:")
+        .await
+        .expect("Failed to connect to in-memory database");
+
+    let app = spawn_app_inner(
+        database,
+        AppStateConfig {
+            repositories: RepositoryBackend::Memory,
+            ..test_state_config()
+        },
+        None,
+    )
+    .await;
+
+    add_auth_to_app(app).await
+}
+
+/// Spawn an authenticated test app that hashes API tokens with `token_hasher`.
+/// The seeded auth token is still stored as a legacy SHA-256 hash.
+pub async fn spawn_app_with_token_hasher(token_hasher: TokenHasher) -> TestApp {
+    let database = Database::connect("sqlite::memory:")
+        .await
+        .expect("Failed to connect to in-memory database");
+
+    let app = spawn_app_inner(
+        database,
+        AppStateConfig {
+            token_hasher,
+            ..test_state_config()
+        },
+        None,
+    )
+    .await;
+
+    add_auth_to_app(app).await
+}
+
+/// Spawn a test app with the timeline background rebuild task running.
+/// Uses a short debounce (50ms) so tests don't have to wait long.
+pub async fn spawn_app_with_timeline_sync() -> TestApp {
+    use crate::application::services::timeline_refresh::timeline_rebuild_task;
+
+    let database = Database::connect("sqlite::memory:")
+        .await
+        .expect("Failed to connect to in-memory database");
+
+    let (timeline_tx, timeline_rx) = tokio::sync::mpsc::channel(32);
+    let config = AppStateConfig {
+        timeline_invalidator: TimelineInvalidator::new(timeline_tx),
+        ..test_state_config()
+    };
+
+    let state = AppState::from_database(&database, config);
+
+    // Clone repos for the rebuilder before consuming state
+    let rebuilder = state.timeline_rebuilder();
+
+    tokio::spawn(timeline_rebuild_task(
+        timeline_rx,
+        rebuilder,
+        std::time::Duration::from_millis(50),
+    ));
+
+    let app = spawn_app_inner_from_state(state, None).await;
+    add_auth_to_app(app).await
+}
+
+/// Spawn an authenticated app whose Foursquare searches go to
+/// [`TestApp::mock_server`].
+pub async fn spawn_app_with_foursquare_mock() -> TestApp {
+    let mock_server = wiremock::MockServer::start().await;
+    let foursquare_url = format!("{}/places/search", mock_server.uri());
+
+    let database = Database::connect("sqlite::memory:")
+        .await
+        .expect("Failed to connect to in-memory database");
+
+    let app = spawn_app_inner(
+        database,
+        AppStateConfig {
+            foursquare_url,
+            foursquare_api_key: "test-api-key".to_string(),
+            ..test_state_config()
+        },
+        Some(mock_server),
+    )
+    .await;
+
+    add_auth_to_app(app).await
+}
+
+async fn add_auth_to_app(mut app: TestApp) -> TestApp {
+    // Create user with UUID (no password)
+    let user_uuid = uuid::Uuid::new_v4().to_string();
+    let admin_user = NewUser::new("admin".to_string(), user_uuid);
+
+    let admin_user = app
+        .user_repo
+        .as_ref()
+        .unwrap()
+        .insert(admin_user)
+        .await
+        .expect("Failed to create admin user");
+
+    // Create a token for testing via direct DB insert
+    let token_value = generate_token().expect("Failed to generate token");
+    let token_hash = hash_token(&token_value);
+    let token = NewToken::new(admin_user.id, token_hash, "test-token".to_string());
+
+    app.token_repo
+        .as_ref()
+        .unwrap()
+        .insert(token)
+        .await
+        .expect("Failed to insert token");
+
+    app.auth_token = Some(token_value);
+    app
+}
+
+/// Creates a session for the authenticated user and returns the raw session token
+/// to use as a `brewlog_session` cookie value.
+pub async fn create_session(app: &TestApp) -> String {
+    let session_token = generate_session_token();
+    let session_hash = hash_token(&session_token);
+
+    // Get the user ID from the auth token
+    let token_hash = hash_token(app.auth_token.as_ref().expect("auth token required"));
+    let token = app
+        .token_repo
+        .as_ref()
+        .expect("token_repo required")
+        .get_by_token_hash(&token_hash)
+        .await
+        .expect("failed to find token");
+
+    let now = chrono::Utc::now();
+    let expires_at = now
+        .checked_add_signed(chrono::Duration::hours(24))
+        .expect("timestamp overflow");
+
+    let new_session = NewSession::new(token.user_id, session_hash, now, expires_at);
+
+    app.session_repo
+        .as_ref()
+        .expect("session_repo required")
+        .insert(new_session)
+        .await
+        .expect("failed to create session");
+
+    session_token
+}
+
+/// Spawn an authenticated app whose AI extractions go to
+/// [`TestApp::mock_server`].
+pub async fn spawn_app_with_openrouter_mock() -> TestApp {
+    spawn_app_with_ai_policy(AiPolicy::default()).await
+}
+
+/// Like [`spawn_app_with_openrouter_mock`], with `OpenRouter` retried and
+/// circuit-broken under `policy`.
+pub async fn spawn_app_with_ai_policy(policy: AiPolicy) -> TestApp {
+    let mock_server = wiremock::MockServer::start().await;
+    let openrouter_url = format!("{}/api/v1/chat/completions", mock_server.uri());
+
+    let database = Database::connect("sqlite::memory:")
+        .await
+        .expect("Failed to connect to in-memory database");
+
+    let mut state = AppState::from_database(
+        &database,
+        AppStateConfig {
+            openrouter_url,
+            ..test_state_config()
+        },
+    );
+    state.ai_guard = Arc::new(AiGuard::new(policy));
+    let app = spawn_app_inner_from_state(state, Some(mock_server)).await;
+
+    add_auth_to_app(app).await
+}
+
+/// Spawn an authenticated app whose Foursquare searches and AI extractions
+/// both go to [`TestApp::mock_server`].
+pub async fn spawn_app_with_all_mocks() -> TestApp {
+    let mock_server = wiremock::MockServer::start().await;
+    let foursquare_url = format!("{}/places/search", mock_server.uri());
+    let openrouter_url = format!("{}/api/v1/chat/completions", mock_server.uri());
+
+    let database = Database::connect("sqlite::memory:")
+        .await
+        .expect("Failed to connect to in-memory database");
+
+    let app = spawn_app_inner(
+        database,
+        AppStateConfig {
+            foursquare_url,
+            foursquare_api_key: "test-api-key".to_string(),
+            openrouter_url,
+            ..test_state_config()
+        },
+        Some(mock_server),
+    )
+    .await;
+
+    add_auth_to_app(app).await
+}
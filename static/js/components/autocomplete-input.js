@@ -0,0 +1,112 @@
+customElements.define(
+  "autocomplete-input",
+  class extends HTMLElement {
+    connectedCallback() {
+      this._setup();
+    }
+
+    disconnectedCallback() {
+      this._ac?.abort();
+      this._fetch?.abort();
+      clearTimeout(this._timer);
+      this._initialized = false;
+    }
+
+    _setup() {
+      if (this._initialized) return;
+      const control = this.querySelector("input, textarea");
+      const field = this.getAttribute("field");
+      if (!control || !field) return;
+      this._initialized = true;
+      this._ac = new AbortController();
+      const { signal } = this._ac;
+
+      // Comma-separated values (tasting notes) complete the last entry only.
+      const multiple = this.hasAttribute("multiple");
+      const split = (value) => {
+        if (!multiple) return ["", value];
+        const idx = Math.max(value.lastIndexOf(","), value.lastIndexOf("\n"));
+        if (idx === -1) return ["", value];
+        return [`${value.slice(0, idx + 1).trimEnd()} `, value.slice(idx + 1)];
+      };
+
+      // Inputs use a native datalist; textareas don't support one, so render
+      // suggestion chips underneath instead.
+      let render;
+      if (control.tagName === "TEXTAREA") {
+        const chips = document.createElement("div");
+        chips.className = "flex flex-wrap gap-1.5";
+        this.appendChild(chips);
+        render = (prefix, term, values) => {
+          const used = new Set(
+            prefix
+              .split(/[,\n]/)
+              .map((v) => v.trim().toLowerCase())
+              .filter(Boolean),
+          );
+          const shown = term.trim()
+            ? values.filter((v) => !used.has(v.toLowerCase()))
+            : [];
+          chips.replaceChildren(
+            ...shown.map((value) => {
+              const chip = document.createElement("button");
+              chip.type = "button";
+              chip.className = "pill pill-muted";
+              chip.textContent = value;
+              chip.addEventListener("click", () => {
+                control.value = `${prefix}${value}, `;
+                control.dispatchEvent(new Event("input", { bubbles: true }));
+                control.focus();
+              });
+              return chip;
+            }),
+          );
+        };
+      } else {
+        const list = document.createElement("datalist");
+        list.id = `ac-${field}-${Math.random().toString(36).slice(2, 8)}`;
+        this.appendChild(list);
+        control.setAttribute("list", list.id);
+        control.setAttribute("autocomplete", "off");
+        render = (prefix, _term, values) => {
+          list.replaceChildren(
+            ...values.map((value) => {
+              const option = document.createElement("option");
+              option.value = prefix + value;
+              return option;
+            }),
+          );
+        };
+      }
+
+      const load = async () => {
+        const [prefix, term] = split(control.value);
+        this._fetch?.abort();
+        this._fetch = new AbortController();
+        const params = new URLSearchParams({ field, q: term.trim() });
+        try {
+          const res = await fetch(`/api/v1/autocomplete?${params}`, {
+            signal: this._fetch.signal,
+          });
+          if (!res.ok) return;
+          const suggestions = await res.json();
+          render(
+            prefix,
+            term,
+            suggestions.map((s) => s.value),
+          );
+        } catch (err) {
+          if (err.name !== "AbortError") console.warn(err);
+        }
+      };
+
+      const schedule = () => {
+        clearTimeout(this._timer);
+        this._timer = setTimeout(load, 150);
+      };
+
+      control.addEventListener("input", schedule, { signal });
+      control.addEventListener("focus", schedule, { signal });
+    }
+  },
+);
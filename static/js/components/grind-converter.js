@@ -0,0 +1,83 @@
+customElements.define(
+  "grind-converter",
+  class extends HTMLElement {
+    connectedCallback() {
+      this._setup();
+    }
+
+    disconnectedCallback() {
+      this._ac?.abort();
+      this._fetch?.abort();
+      this._initialized = false;
+    }
+
+    _setup() {
+      if (this._initialized) return;
+      const select = this.querySelector('select[name="grinder_id"]');
+      const input = this.querySelector('input[name="grind_setting"]');
+      if (!select || !input) return;
+      this._initialized = true;
+      this._ac = new AbortController();
+      const { signal } = this._ac;
+
+      const hint = document.createElement("div");
+      hint.className =
+        "mt-2 flex items-center justify-between gap-3 rounded-md border bg-surface-alt px-3 py-2 text-sm text-text-secondary";
+      hint.hidden = true;
+      const text = document.createElement("span");
+      const apply = document.createElement("button");
+      apply.type = "button";
+      apply.className =
+        "text-sm font-medium text-accent transition hover:text-accent-hover";
+      apply.textContent = "Use";
+      hint.append(text, apply);
+      this.appendChild(hint);
+
+      const current = () => ({
+        id: select.value,
+        label: select.options[select.selectedIndex]?.text.trim() ?? "",
+      });
+      let previous = current();
+
+      const convert = async () => {
+        const next = current();
+        const from = previous;
+        const setting = Number.parseFloat(input.value);
+        previous = next;
+        hint.hidden = true;
+        this._fetch?.abort();
+        if (!from.id || from.id === next.id || !Number.isFinite(setting)) {
+          return;
+        }
+
+        this._fetch = new AbortController();
+        const params = new URLSearchParams({
+          from: from.id,
+          to: next.id,
+          setting: String(setting),
+        });
+        try {
+          const res = await fetch(
+            `/api/v1/grinder-calibrations/convert?${params}`,
+            { signal: this._fetch.signal },
+          );
+          // No calibration between these grinders; nothing to suggest.
+          if (!res.ok) return;
+          const conversion = await res.json();
+          const target = conversion.to_setting.toFixed(1);
+          text.textContent = `${from.label} ${setting.toFixed(1)} ≈ ${next.label} ${target}`;
+          apply.onclick = () => {
+            input.value = target;
+            input.dispatchEvent(new Event("input", { bubbles: true }));
+            hint.hidden = true;
+          };
+          hint.hidden = false;
+        } catch (err) {
+          if (err.name !== "AbortError") console.warn(err);
+        }
+      };
+
+      select.addEventListener("change", convert, { signal });
+    }
+  },
+);
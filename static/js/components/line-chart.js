@@ -0,0 +1,138 @@
+const esc = (s) =>
+  s
+    .replace(/&/g, "&amp;")
+    .replace(/</g, "&lt;")
+    .replace(/>/g, "&gt;")
+    .replace(/"/g, "&quot;");
+
+const fmt = (value) =>
+  Number.isInteger(value) ? String(value) : value.toFixed(1);
+
+// Plots one small line per series from a JSON array of points fetched from
+// `data-src`. `data-series` lists the fields to plot as "field:Label|...";
+// points missing a field leave a gap in that line.
+class LineChart extends HTMLElement {
+  static get observedAttributes() {
+    return ["data-src", "data-series"];
+  }
+
+  connectedCallback() {
+    this.load();
+    this._themeObserver = new MutationObserver(() =>
+      requestAnimationFrame(() => this.render()),
+    );
+    this._themeObserver.observe(document.documentElement, {
+      attributes: true,
+      attributeFilter: ["data-theme"],
+    });
+  }
+
+  disconnectedCallback() {
+    this._themeObserver?.disconnect();
+    this._themeObserver = null;
+  }
+
+  attributeChangedCallback(name) {
+    if (!this.isConnected) return;
+    if (name === "data-src") this.load();
+    else requestAnimationFrame(() => this.render());
+  }
+
+  async load() {
+    const src = this.dataset.src;
+    if (!src) return;
+    try {
+      const response = await fetch(src, {
+        headers: { Accept: "application/json" },
+      });
+      this._points = response.ok ? await response.json() : [];
+    } catch {
+      this._points = [];
+    }
+    requestAnimationFrame(() => this.render());
+  }
+
+  series() {
+    return (this.dataset.series || "")
+      .split("|")
+      .map((s) => {
+        const [field, label] = s.split(":").map((part) => part?.trim());
+        return field ? { field, label: label || field } : null;
+      })
+      .filter(Boolean);
+  }
+
+  render() {
+    const points = this._points || [];
+    if (points.length < 2) {
+      this.innerHTML = `<p class="text-sm text-text-muted">${esc(
+        this.dataset.empty || "Not enough data to chart yet.",
+      )}</p>`;
+      return;
+    }
+
+    const rgb =
+      getComputedStyle(document.documentElement)
+        .getPropertyValue("--highlight-rgb")
+        .trim() || "194, 65, 12";
+    const width = 300;
+    const height = 60;
+    const pad = 6;
+    const step = (width - pad * 2) / (points.length - 1);
+
+    const charts = this.series().map(({ field, label }) => {
+      const values = points.map((p) =>
+        typeof p[field] === "number" ? p[field] : null,
+      );
+      const known = values.filter((v) => v !== null);
+      if (known.length === 0) return "";
+
+      const min = Math.min(...known);
+      const max = Math.max(...known);
+      const span = max - min || 1;
+      const y = (v) => height - pad - ((v - min) / span) * (height - pad * 2);
+
+      // Break the line wherever a point has no value.
+      const segments = [];
+      let current = [];
+      values.forEach((v, i) => {
+        if (v === null) {
+          if (current.length) segments.push(current);
+          current = [];
+        } else {
+          current.push(`${(pad + i * step).toFixed(1)},${y(v).toFixed(1)}`);
+        }
+      });
+      if (current.length) segments.push(current);
+
+      const lines = segments
+        .map(
+          (coords) =>
+            `<polyline points="${coords.join(" ")}" fill="none" stroke="rgb(${rgb})" stroke-width="2" stroke-linejoin="round" stroke-linecap="round" />`,
+        )
+        .join("");
+      const dots = values
+        .map((v, i) =>
+          v === null
+            ? ""
+            : `<circle cx="${(pad + i * step).toFixed(1)}" cy="${y(v).toFixed(1)}" r="2.5" fill="rgb(${rgb})" />`,
+        )
+        .join("");
+      const latest = known[known.length - 1];
+
+      return `<div style="display:flex;flex-direction:column;gap:0.25rem">
+        <div style="display:flex;align-items:baseline;justify-content:space-between;gap:0.5rem">
+          <span class="text-xs font-semibold text-text-muted uppercase tracking-wide">${esc(label)}</span>
+          <span class="text-xs text-text-secondary">${fmt(min)}&ndash;${fmt(max)} &middot; latest ${fmt(latest)}</span>
+        </div>
+        <svg viewBox="0 0 ${width} ${height}" preserveAspectRatio="none" style="display:block;width:100%;height:${height}px" role="img" aria-label="${esc(label)} over ${points.length} entries, from ${fmt(known[0])} to ${fmt(latest)}">
+          ${lines}${dots}
+        </svg>
+      </div>`;
+    });
+
+    this.innerHTML = `<div style="display:flex;flex-direction:column;gap:1rem">${charts.join("")}</div>`;
+  }
+}
+
+customElements.define("line-chart", LineChart);
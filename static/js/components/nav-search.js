@@ -0,0 +1,99 @@
+customElements.define(
+  "nav-search",
+  class extends HTMLElement {
+    connectedCallback() {
+      this._setup();
+    }
+
+    disconnectedCallback() {
+      this._ac?.abort();
+      this._initialized = false;
+    }
+
+    _setup() {
+      if (this._initialized) return;
+      const input = this.querySelector("input");
+      if (!input) return;
+      this._initialized = true;
+      this._ac = new AbortController();
+      const { signal } = this._ac;
+
+      // Datastar swaps the results element on every keystroke, so look the
+      // options up fresh rather than holding on to them.
+      const results = () => [
+        ...this.querySelectorAll('[data-role="nav-result"]'),
+      ];
+      let active = -1;
+
+      const highlight = (index) => {
+        const options = results();
+        if (options.length === 0) return;
+        active = (index + options.length) % options.length;
+        options.forEach((option, i) => {
+          const selected = i === active;
+          option.classList.toggle("bg-accent/10", selected);
+          option.setAttribute("aria-selected", String(selected));
+          if (selected) option.scrollIntoView({ block: "nearest" });
+        });
+      };
+
+      const close = () => {
+        active = -1;
+        this.querySelector("#nav-search-results")?.replaceChildren();
+      };
+
+      input.addEventListener("input", () => (active = -1), { signal });
+
+      input.addEventListener(
+        "keydown",
+        (e) => {
+          if (e.key === "ArrowDown") {
+            e.preventDefault();
+            highlight(active + 1);
+          } else if (e.key === "ArrowUp") {
+            e.preventDefault();
+            highlight(active - 1);
+          } else if (e.key === "Enter") {
+            const target = results()[Math.max(active, 0)];
+            if (!target) return;
+            e.preventDefault();
+            window.location.href = target.href;
+          } else if (e.key === "Escape") {
+            input.value = "";
+            close();
+            input.blur();
+          }
+        },
+        { signal },
+      );
+
+      // "/" jumps to the box from anywhere that isn't already taking text.
+      document.addEventListener(
+        "keydown",
+        (e) => {
+          if (e.key !== "/" || e.metaKey || e.ctrlKey || e.altKey) return;
+          const el = document.activeElement;
+          if (
+            el &&
+            (el.isContentEditable ||
+              ["INPUT", "TEXTAREA", "SELECT"].includes(el.tagName))
+          ) {
+            return;
+          }
+          e.preventDefault();
+          input.focus();
+          input.select();
+        },
+        { signal },
+      );
+
+      document.addEventListener(
+        "click",
+        (e) => {
+          if (!this.contains(e.target)) close();
+        },
+        { signal },
+      );
+    }
+  },
+);
@@ -41,10 +41,15 @@ customElements.define(
         "hidden mt-2 max-h-48 overflow-y-auto rounded-lg border bg-surface";
       options.id = listId;
       options.setAttribute("role", "listbox");
-      buttons.forEach((btn) => {
-        btn.setAttribute("role", "option");
-        options.appendChild(btn);
-      });
+      [...this.querySelectorAll("button, [data-option-group]")].forEach(
+        (el) => {
+          el.setAttribute(
+            "role",
+            el.matches("button") ? "option" : "presentation",
+          );
+          options.appendChild(el);
+        },
+      );
 
       const searchWrap = document.createElement("div");
       searchWrap.appendChild(search);
@@ -111,6 +116,16 @@ customElements.define(
               ? ""
               : "none";
           });
+          // A group heading shows only while one of its options matches.
+          let heading = null;
+          for (const el of options.children) {
+            if (el.hasAttribute("data-option-group")) {
+              heading = el;
+              heading.style.display = "none";
+            } else if (heading && el.style.display !== "none") {
+              heading.style.display = "";
+            }
+          }
           updateExpanded();
         },
         { signal },
@@ -0,0 +1,253 @@
+// Street map web component drawn from the tile server configured in
+// settings. Tiles are fetched through /map-tiles so the CSP stays 'self'.
+//
+// Attributes:
+//   data-points       JSON array of {lat, lng, label} markers
+//   data-lat/data-lng a single marker, for location pickers
+//   data-pickable     clicking the map emits a `pick` event with {lat, lng}
+//   data-attribution  credit line for the tile server
+const TILE_SIZE = 256;
+const MIN_ZOOM = 1;
+const MAX_ZOOM = 19;
+const DEFAULT_ZOOM = 15;
+const FIT_PADDING = 40;
+
+const lngToX = (lng, zoom) => ((lng + 180) / 360) * TILE_SIZE * 2 ** zoom;
+
+const latToY = (lat, zoom) => {
+  const rad = (Math.max(-85, Math.min(85, lat)) * Math.PI) / 180;
+  return (
+    ((1 - Math.log(Math.tan(rad) + 1 / Math.cos(rad)) / Math.PI) / 2) *
+    TILE_SIZE *
+    2 ** zoom
+  );
+};
+
+const xToLng = (x, zoom) => (x / (TILE_SIZE * 2 ** zoom)) * 360 - 180;
+
+const yToLat = (y, zoom) => {
+  const n = Math.PI - (2 * Math.PI * y) / (TILE_SIZE * 2 ** zoom);
+  return (180 / Math.PI) * Math.atan(Math.sinh(n));
+};
+
+customElements.define(
+  "tile-map",
+  class extends HTMLElement {
+    static get observedAttributes() {
+      return ["data-points", "data-lat", "data-lng"];
+    }
+
+    connectedCallback() {
+      this.style.position = "relative";
+      this.style.overflow = "hidden";
+      this.style.touchAction = "none";
+      this._layer = document.createElement("div");
+      this._layer.className = "absolute inset-0";
+      this.appendChild(this._layer);
+      this._buildControls();
+      this.addEventListener("pointerdown", this._onPointerDown);
+      this._resizeObserver = new ResizeObserver(() => this._scheduleRender());
+      this._resizeObserver.observe(this);
+      this._fit();
+    }
+
+    disconnectedCallback() {
+      this._resizeObserver?.disconnect();
+      this._resizeObserver = null;
+    }
+
+    attributeChangedCallback() {
+      if (!this.isConnected) return;
+      // A picked point stays in view without losing the chosen zoom.
+      if (this._zoom !== undefined && this.hasAttribute("data-pickable")) {
+        const [marker] = this._markers();
+        if (marker) this._center = { lat: marker.lat, lng: marker.lng };
+        this._scheduleRender();
+      } else {
+        this._fit();
+      }
+    }
+
+    _markers() {
+      const lat = parseFloat(this.dataset.lat);
+      const lng = parseFloat(this.dataset.lng);
+      if (this.hasAttribute("data-lat")) {
+        return Number.isFinite(lat) && Number.isFinite(lng) && (lat || lng)
+          ? [{ lat, lng, label: "" }]
+          : [];
+      }
+      try {
+        return JSON.parse(this.dataset.points || "[]");
+      } catch {
+        return [];
+      }
+    }
+
+    // Centre on the markers at the deepest zoom that shows all of them.
+    _fit() {
+      const markers = this._markers();
+      if (markers.length === 0) {
+        this._center = { lat: 20, lng: 0 };
+        this._zoom = MIN_ZOOM;
+        this._scheduleRender();
+        return;
+      }
+      const lats = markers.map((m) => m.lat);
+      const lngs = markers.map((m) => m.lng);
+      const bounds = {
+        north: Math.max(...lats),
+        south: Math.min(...lats),
+        east: Math.max(...lngs),
+        west: Math.min(...lngs),
+      };
+      this._center = {
+        lat: (bounds.north + bounds.south) / 2,
+        lng: (bounds.east + bounds.west) / 2,
+      };
+      const width = Math.max(this.clientWidth - FIT_PADDING * 2, TILE_SIZE);
+      const height = Math.max(this.clientHeight - FIT_PADDING * 2, TILE_SIZE);
+      let zoom = DEFAULT_ZOOM;
+      while (
+        zoom > MIN_ZOOM &&
+        (lngToX(bounds.east, zoom) - lngToX(bounds.west, zoom) > width ||
+          latToY(bounds.south, zoom) - latToY(bounds.north, zoom) > height)
+      ) {
+        zoom -= 1;
+      }
+      this._zoom = zoom;
+      this._scheduleRender();
+    }
+
+    _buildControls() {
+      const controls = document.createElement("div");
+      controls.className =
+        "absolute top-2 right-2 z-10 flex flex-col overflow-hidden rounded-md border bg-surface text-text";
+      [
+        ["+", 1, "Zoom in"],
+        ["−", -1, "Zoom out"],
+      ].forEach(([text, step, label]) => {
+        const button = document.createElement("button");
+        button.type = "button";
+        button.textContent = text;
+        button.setAttribute("aria-label", label);
+        button.className = "h-8 w-8 text-lg leading-none hover:bg-surface-alt";
+        button.addEventListener("pointerdown", (evt) => evt.stopPropagation());
+        button.addEventListener("click", () => this._zoomBy(step));
+        controls.appendChild(button);
+      });
+      this.appendChild(controls);
+
+      const credit = this.dataset.attribution;
+      if (credit) {
+        const attribution = document.createElement("div");
+        attribution.className =
+          "absolute bottom-0 right-0 z-10 bg-surface/80 px-1 text-2xs text-text-muted";
+        attribution.textContent = credit;
+        this.appendChild(attribution);
+      }
+    }
+
+    _zoomBy(step) {
+      this._zoom = Math.max(MIN_ZOOM, Math.min(MAX_ZOOM, this._zoom + step));
+      this._scheduleRender();
+    }
+
+    _onPointerDown = (evt) => {
+      const start = { x: evt.clientX, y: evt.clientY };
+      const origin = {
+        x: lngToX(this._center.lng, this._zoom),
+        y: latToY(this._center.lat, this._zoom),
+      };
+      let dragged = false;
+
+      const move = (e) => {
+        const dx = e.clientX - start.x;
+        const dy = e.clientY - start.y;
+        if (Math.abs(dx) + Math.abs(dy) > 4) dragged = true;
+        if (!dragged) return;
+        this._center = {
+          lat: yToLat(origin.y - dy, this._zoom),
+          lng: xToLng(origin.x - dx, this._zoom),
+        };
+        this._scheduleRender();
+      };
+      const up = (e) => {
+        window.removeEventListener("pointermove", move);
+        window.removeEventListener("pointerup", up);
+        if (!dragged && this.hasAttribute("data-pickable")) this._pick(e);
+      };
+      window.addEventListener("pointermove", move);
+      window.addEventListener("pointerup", up);
+    };
+
+    _pick(evt) {
+      const rect = this.getBoundingClientRect();
+      const left = lngToX(this._center.lng, this._zoom) - rect.width / 2;
+      const top = latToY(this._center.lat, this._zoom) - rect.height / 2;
+      const round = (n) => Math.round(n * 1e6) / 1e6;
+      this.dispatchEvent(
+        new CustomEvent("pick", {
+          bubbles: true,
+          detail: {
+            lat: round(yToLat(top + evt.clientY - rect.top, this._zoom)),
+            lng: round(xToLng(left + evt.clientX - rect.left, this._zoom)),
+          },
+        }),
+      );
+    }
+
+    _scheduleRender() {
+      if (this._pendingRender) return;
+      this._pendingRender = true;
+      requestAnimationFrame(() => {
+        this._pendingRender = false;
+        this._render();
+      });
+    }
+
+    _render() {
+      const width = this.clientWidth;
+      const height = this.clientHeight;
+      const zoom = this._zoom;
+      const count = 2 ** zoom;
+      const left = lngToX(this._center.lng, zoom) - width / 2;
+      const top = latToY(this._center.lat, zoom) - height / 2;
+
+      const fragment = document.createDocumentFragment();
+      const firstX = Math.floor(left / TILE_SIZE);
+      const firstY = Math.max(0, Math.floor(top / TILE_SIZE));
+      const lastX = Math.floor((left + width) / TILE_SIZE);
+      const lastY = Math.min(count - 1, Math.floor((top + height) / TILE_SIZE));
+      for (let ty = firstY; ty <= lastY; ty += 1) {
+        for (let tx = firstX; tx <= lastX; tx += 1) {
+          const wrapped = ((tx % count) + count) % count;
+          const img = document.createElement("img");
+          img.src = `/map-tiles/${zoom}/${wrapped}/${ty}`;
+          img.alt = "";
+          img.draggable = false;
+          img.className = "absolute max-w-none select-none";
+          img.style.width = `${TILE_SIZE}px`;
+          img.style.height = `${TILE_SIZE}px`;
+          img.style.left = `${tx * TILE_SIZE - left}px`;
+          img.style.top = `${ty * TILE_SIZE - top}px`;
+          fragment.appendChild(img);
+        }
+      }
+
+      this._markers().forEach((marker) => {
+        const dot = document.createElement("span");
+        dot.className =
+          "absolute h-3.5 w-3.5 -translate-x-1/2 -translate-y-1/2 rounded-full border-2 border-white bg-accent";
+        dot.style.left = `${lngToX(marker.lng, zoom) - left}px`;
+        dot.style.top = `${latToY(marker.lat, zoom) - top}px`;
+        if (marker.label) {
+          dot.title = marker.label;
+          dot.setAttribute("aria-label", marker.label);
+        }
+        fragment.appendChild(dot);
+      });
+
+      this._layer.replaceChildren(fragment);
+    }
+  },
+);
@@ -1,8 +1,29 @@
+/** Read a file as a data URL without touching its contents. */
+const fileToDataUrl = (file) =>
+  new Promise((resolve, reject) => {
+    const reader = new FileReader();
+    reader.onload = () => resolve(reader.result);
+    reader.onerror = () => reject(reader.error);
+    reader.readAsDataURL(file);
+  });
+
+const isHeic = (file) =>
+  /^image\/hei[cf]$/.test(file.type) || /\.hei[cf]$/i.test(file.name);
+
 /** Convert any image file (HEIC, AVIF, WebP, PNG, etc.) to a JPEG data URL via Canvas.
  *  Uses createImageBitmap which correctly applies EXIF orientation (e.g. iPhone photos).
- *  Caps the longest side to 1920px to avoid exceeding the request body limit. */
+ *  Caps the longest side to 1920px to avoid exceeding the request body limit.
+ *  Browsers that can't decode HEIC send it as-is for the server to convert. */
 const imageToJpegDataUrl = async (file) => {
-  const bitmap = await createImageBitmap(file);
+  let bitmap;
+  try {
+    bitmap = await createImageBitmap(file);
+  } catch (err) {
+    if (!isHeic(file)) throw err;
+    const dataUrl = await fileToDataUrl(file);
+    // Some browsers report HEIC files with an empty or generic type.
+    return dataUrl.replace(/^data:[^;,]*/, "data:image/heic");
+  }
   const maxDim = 1920;
   let { width, height } = bitmap;
   if (width > maxDim || height > maxDim) {
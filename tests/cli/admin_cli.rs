@@ -0,0 +1,7 @@
+use crate::test_macros::define_cli_auth_test;
+
+define_cli_auth_test!(admin_check_requires_auth, &["admin", "check"]);
+define_cli_auth_test!(
+    admin_check_repair_requires_auth,
+    &["admin", "check", "--repair"]
+);
@@ -1,4 +1,4 @@
-use super::helpers::{create_token, run_brewlog};
+use super::helpers::{create_roaster, create_token, run_brewlog};
 use crate::test_macros::define_cli_auth_test;
 
 define_cli_auth_test!(backup_requires_auth, &["backup"]);
@@ -29,3 +29,88 @@ fn backup_produces_valid_json() {
     assert!(data["cups"].is_array());
     assert!(data["timeline_events"].is_array());
 }
+
+#[test]
+fn backup_ndjson_starts_with_header() {
+    let token = create_token("backup-ndjson-test");
+
+    let output = run_brewlog(
+        &["backup", "--format", "ndjson"],
+        &[("BREWLOG_TOKEN", &token)],
+    );
+
+    assert!(
+        output.status.success(),
+        "backup command failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let header: serde_json::Value =
+        serde_json::from_str(stdout.lines().next().expect("backup output is empty"))
+            .expect("first line is not valid JSON");
+
+    assert_eq!(header["type"], "header");
+    assert_eq!(header["data"]["version"], 2);
+}
+
+fn backup_to(dir: &std::path::Path, name: &str, args: &[&str], token: &str) -> String {
+    let output = run_brewlog(args, &[("BREWLOG_TOKEN", token)]);
+    assert!(
+        output.status.success(),
+        "backup command failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let path = dir.join(name);
+    std::fs::write(&path, &output.stdout).expect("failed to write backup");
+    path.to_string_lossy().into_owned()
+}
+
+#[test]
+fn backup_diff_lists_added_roasters() {
+    let token = create_token("backup-diff-test");
+    let dir = tempfile::TempDir::new().unwrap();
+
+    let old = backup_to(dir.path(), "old.json", &["backup"], &token);
+    let roaster_id = create_roaster("Diffed Roasters", &token);
+    let new = backup_to(
+        dir.path(),
+        "new.ndjson",
+        &["backup", "--format", "ndjson"],
+        &token,
+    );
+
+    let output = run_brewlog(&["backup", "diff", &old, &new, "--json"], &[]);
+    assert!(
+        output.status.success(),
+        "backup diff failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let diff: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let roasters = &diff["sections"][0];
+    assert_eq!(roasters["section"], "roasters");
+    assert!(
+        roasters["added"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|e| e["key"] == roaster_id.as_str() && e["label"] == "Diffed Roasters"),
+        "roaster missing from {roasters}"
+    );
+
+    let output = run_brewlog(&["backup", "diff", &old, &old], &[]);
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "No differences.\n");
+}
+
+#[test]
+fn backup_diff_rejects_files_that_are_not_backups() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let path = dir.path().join("notes.json");
+    std::fs::write(&path, "{}").unwrap();
+    let path = path.to_string_lossy();
+
+    let output = run_brewlog(&["backup", "diff", &path, &path], &[]);
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("is not a brewlog backup"));
+}
@@ -14,6 +14,29 @@ define_cli_auth_test!(
     test_delete_bag_requires_authentication,
     &["bag", "delete", "--id", "123"]
 );
+define_cli_auth_test!(
+    test_reopen_bag_requires_authentication,
+    &["bag", "reopen", "--id", "123"]
+);
+define_cli_auth_test!(
+    test_adjust_bag_requires_authentication,
+    &[
+        "bag",
+        "adjust",
+        "--id",
+        "123",
+        "--remaining",
+        "100.0",
+        "--reason",
+        "weighed"
+    ]
+);
+define_cli_auth_test!(
+    test_transfer_bag_requires_authentication,
+    &[
+        "bag", "transfer", "--id", "123", "--to", "124", "--amount", "10.0"
+    ]
+);
 define_cli_list_test!(
     test_list_bags_works_without_authentication,
     &["bag", "list"]
@@ -50,6 +73,62 @@ fn test_add_bag_with_authentication() {
     assert!(bag["id"].is_i64());
 }
 
+#[test]
+fn test_add_bag_with_label_details() {
+    let token = create_token("test-add-bag-label");
+    let roaster_id = create_roaster("Bag Label Roaster", &token);
+    let roast_id = create_roast(&roaster_id, "Bag Label Roast", &token);
+
+    let output = run_brewlog(
+        &[
+            "bag",
+            "add",
+            "--roast-id",
+            &roast_id,
+            "--amount",
+            "250.0",
+            "--best-before",
+            "2025-09-30",
+            "--batch-code",
+            "BL-7",
+        ],
+        &[("BREWLOG_TOKEN", &token)],
+    );
+
+    assert!(output.status.success());
+    let bag: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(bag["best_before"], "2025-09-30");
+    assert_eq!(bag["batch_code"], "BL-7");
+}
+
+#[test]
+fn test_add_bag_bought_abroad() {
+    let token = create_token("test-add-bag-currency");
+    let roaster_id = create_roaster("Bag Currency Roaster", &token);
+    let roast_id = create_roast(&roaster_id, "Bag Currency Roast", &token);
+
+    let output = run_brewlog(
+        &[
+            "bag",
+            "add",
+            "--roast-id",
+            &roast_id,
+            "--amount",
+            "250.0",
+            "--price",
+            "18",
+            "--currency",
+            "eur",
+        ],
+        &[("BREWLOG_TOKEN", &token)],
+    );
+
+    assert!(output.status.success());
+    let bag: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(bag["price"], 18.0);
+    assert_eq!(bag["currency"], "EUR");
+}
+
 #[test]
 fn test_update_bag_with_authentication() {
     let token = create_token("test-update-bag");
@@ -87,6 +166,73 @@ fn test_update_bag_with_authentication() {
     assert_eq!(updated_bag["closed"].as_bool(), Some(true));
 }
 
+#[test]
+fn test_reopen_and_adjust_bag() {
+    let token = create_token("test-reopen-bag");
+
+    let roaster_id = create_roaster("Bag Reopen Roaster", &token);
+    let roast_id = create_roast(&roaster_id, "Bag Reopen Roast", &token);
+
+    let bag_output = run_brewlog(
+        &["bag", "add", "--roast-id", &roast_id, "--amount", "250.0"],
+        &[("BREWLOG_TOKEN", &token)],
+    );
+    let bag: Value = serde_json::from_slice(&bag_output.stdout).unwrap();
+    let bag_id = bag["id"].as_i64().unwrap().to_string();
+
+    run_brewlog(
+        &[
+            "bag",
+            "update",
+            "--id",
+            &bag_id,
+            "--remaining",
+            "0",
+            "--closed",
+            "true",
+        ],
+        &[("BREWLOG_TOKEN", &token)],
+    );
+
+    let output = run_brewlog(
+        &["bag", "reopen", "--id", &bag_id],
+        &[("BREWLOG_TOKEN", &token)],
+    );
+    assert!(output.status.success());
+    let reopened: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(reopened["closed"].as_bool(), Some(false));
+    assert!(reopened["finished_at"].is_null());
+
+    let output = run_brewlog(
+        &[
+            "bag",
+            "adjust",
+            "--id",
+            &bag_id,
+            "--remaining",
+            "40",
+            "--reason",
+            "Found the rest in the cupboard",
+        ],
+        &[("BREWLOG_TOKEN", &token)],
+    );
+    assert!(output.status.success());
+    let adjustment: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(adjustment["delta"].as_f64(), Some(40.0));
+    assert_eq!(adjustment["reason"], "Found the rest in the cupboard");
+
+    let output = run_brewlog(&["bag", "ledger", "--id", &bag_id], &[]);
+    assert!(output.status.success());
+    let ledger: Value = serde_json::from_slice(&output.stdout).unwrap();
+    let kinds: Vec<_> = ledger
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|entry| entry["kind"].as_str().unwrap())
+        .collect();
+    assert_eq!(kinds, ["adjustment", "adjustment", "initial"]);
+}
+
 #[test]
 fn test_list_bags_shows_added_bag() {
     let token = create_token("test-list-bags");
@@ -0,0 +1,60 @@
+use std::io::Write;
+
+use super::helpers::run_brewlog;
+
+fn config_file(contents: &str) -> tempfile::NamedTempFile {
+    let mut file = tempfile::Builder::new()
+        .suffix(".toml")
+        .tempfile()
+        .expect("failed to create config file");
+    file.write_all(contents.as_bytes())
+        .expect("failed to write config file");
+    file
+}
+
+fn validate(file: &tempfile::NamedTempFile) -> std::process::Output {
+    let path = file.path().to_str().unwrap();
+    run_brewlog(&["config", "validate", "--config", path], &[])
+}
+
+#[test]
+fn test_config_validate_accepts_a_complete_file() {
+    let file = config_file(
+        r#"
+        bind_address = "0.0.0.0:3000"
+        openrouter_api_key = "sk-or-test"
+        foursquare_api_key = "fsq-test"
+        token_hash = "sha256"
+        log_filters = ["sqlx=warn"]
+        "#,
+    );
+
+    let output = validate(&file);
+
+    assert!(
+        output.status.success(),
+        "validate failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(String::from_utf8_lossy(&output.stdout).contains("is valid"));
+}
+
+#[test]
+fn test_config_validate_rejects_unknown_keys() {
+    let file = config_file("demo = true\nbind_adress = \"0.0.0.0:3000\"\n");
+
+    let output = validate(&file);
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("bind_adress"));
+}
+
+#[test]
+fn test_config_validate_checks_settings() {
+    let file = config_file("demo = true\nimage_jpeg_quality = 0\n");
+
+    let output = validate(&file);
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("quality"));
+}
@@ -0,0 +1,55 @@
+use crate::helpers::{create_token, run_brewlog};
+use crate::test_macros::define_cli_auth_test;
+
+define_cli_auth_test!(
+    test_dev_seed_requires_authentication,
+    &["dev", "seed", "--roasters", "1", "--brews", "1"]
+);
+
+#[test]
+fn test_dev_seed_creates_data_through_the_api() {
+    let token = create_token("test-dev-seed");
+
+    let output = run_brewlog(
+        &[
+            "dev",
+            "seed",
+            "--roasters",
+            "1",
+            "--roasts",
+            "2",
+            "--brews",
+            "20",
+            "--cafes",
+            "1",
+            "--cups",
+            "2",
+            "--days",
+            "30",
+            "--seed",
+            "42",
+            "--images",
+        ],
+        &[("BREWLOG_TOKEN", &token)],
+    );
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "seed should succeed: {stderr}");
+    assert!(
+        stderr.contains("Seeded 1 roasters, 2 roasts,"),
+        "unexpected summary: {stderr}"
+    );
+    assert!(stderr.contains("20 brews"), "unexpected summary: {stderr}");
+}
+
+#[test]
+fn test_dev_seed_rejects_brews_without_roasts() {
+    let token = create_token("test-dev-seed-invalid");
+
+    let output = run_brewlog(
+        &["dev", "seed", "--roasters", "0", "--brews", "5"],
+        &[("BREWLOG_TOKEN", &token)],
+    );
+
+    assert!(!output.status.success());
+}
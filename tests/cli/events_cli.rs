@@ -0,0 +1,23 @@
+use crate::test_macros::{define_cli_auth_test, define_cli_list_test};
+
+define_cli_auth_test!(
+    test_add_event_requires_authentication,
+    &[
+        "event",
+        "add",
+        "--name",
+        "London Coffee Festival",
+        "--date",
+        "2026-04-18",
+        "--coffee",
+        "Kochere"
+    ]
+);
+define_cli_auth_test!(
+    test_delete_event_requires_authentication,
+    &["event", "delete", "--id", "123"]
+);
+define_cli_list_test!(
+    test_list_events_works_without_authentication,
+    &["event", "list"]
+);
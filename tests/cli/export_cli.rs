@@ -0,0 +1,22 @@
+use serde_json::Value;
+
+use super::helpers::{create_token, run_brewlog};
+use crate::test_macros::define_cli_auth_test;
+
+define_cli_auth_test!(export_requires_auth, &["export", "beanconqueror"]);
+
+#[test]
+fn export_beanconqueror_prints_an_importable_document() {
+    let token = create_token("export-test");
+
+    let output = run_brewlog(&["export", "beanconqueror"], &[("BREWLOG_TOKEN", &token)]);
+
+    assert!(
+        output.status.success(),
+        "export failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let export: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(export["BEANS"].is_array());
+    assert!(export["BREWS"].is_array());
+}
@@ -23,6 +23,10 @@ define_cli_auth_test!(
     test_delete_gear_requires_authentication,
     &["gear", "delete", "--id", "123"]
 );
+define_cli_auth_test!(
+    test_restock_gear_requires_authentication,
+    &["gear", "restock", "--id", "123", "--count", "100"]
+);
 define_cli_list_test!(
     test_list_gear_works_without_authentication,
     &["gear", "list"]
@@ -55,6 +59,128 @@ fn test_add_gear_with_authentication() {
     assert!(gear["id"].is_i64());
 }
 
+#[test]
+fn test_add_brewer_with_water_temp() {
+    let token = create_token("test-add-brewer-water-temp");
+
+    let output = run_brewlog(
+        &[
+            "gear",
+            "add",
+            "--category",
+            "brewer",
+            "--make",
+            "AeroPress",
+            "--model",
+            "Original",
+            "--water-temp",
+            "85",
+        ],
+        &[("BREWLOG_TOKEN", &token)],
+    );
+
+    assert!(output.status.success());
+    let gear: Value = serde_json::from_slice(&output.stdout).unwrap();
+
+    assert_eq!(gear["category"], "brewer");
+    assert_eq!(gear["water_temp"], 85.0);
+}
+
+#[test]
+fn test_add_kettle_with_a_temperature_offset() {
+    let token = create_token("test-add-kettle-temp-offset");
+
+    let output = run_brewlog(
+        &[
+            "gear",
+            "add",
+            "--category",
+            "kettle",
+            "--make",
+            "Fellow",
+            "--model",
+            "Stagg EKG",
+            "--temp-offset",
+            "-2.5",
+            "--water-hardness",
+            "80",
+        ],
+        &[("BREWLOG_TOKEN", &token)],
+    );
+
+    assert!(output.status.success());
+    let gear: Value = serde_json::from_slice(&output.stdout).unwrap();
+
+    assert_eq!(gear["category"], "kettle");
+    assert_eq!(gear["temp_offset"], -2.5);
+    assert_eq!(gear["water_hardness"], 80);
+}
+
+#[test]
+fn test_restock_filter_papers() {
+    let token = create_token("test-restock-filter-papers");
+
+    let output = run_brewlog(
+        &[
+            "gear",
+            "add",
+            "--category",
+            "filter_paper",
+            "--make",
+            "Hario",
+            "--model",
+            "V60 02",
+            "--stock-count",
+            "4",
+            "--low-stock-threshold",
+            "5",
+        ],
+        &[("BREWLOG_TOKEN", &token)],
+    );
+    assert!(output.status.success());
+    let gear: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(gear["stock_count"], 4);
+    assert_eq!(gear["low_stock_threshold"], 5);
+
+    let id = gear["id"].as_i64().unwrap().to_string();
+    let output = run_brewlog(
+        &["gear", "restock", "--id", &id, "--count", "100"],
+        &[("BREWLOG_TOKEN", &token)],
+    );
+    assert!(output.status.success());
+    let gear: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(gear["stock_count"], 104);
+}
+
+#[test]
+fn test_add_brewer_with_brew_method() {
+    let token = create_token("test-add-brewer-brew-method");
+
+    let output = run_brewlog(
+        &[
+            "gear",
+            "add",
+            "--category",
+            "brewer",
+            "--make",
+            "Bodum",
+            "--model",
+            "Chambord",
+            "--brew-method",
+            "immersion",
+            "--uses-filter",
+            "false",
+        ],
+        &[("BREWLOG_TOKEN", &token)],
+    );
+
+    assert!(output.status.success());
+    let gear: Value = serde_json::from_slice(&output.stdout).unwrap();
+
+    assert_eq!(gear["brew_method"], "immersion");
+    assert_eq!(gear["uses_filter"], false);
+}
+
 #[test]
 fn test_list_gear_shows_added_gear() {
     let token = create_token("test-list-gear");
@@ -8,7 +8,7 @@ use tokio::net::TcpListener;
 use webauthn_rs::prelude::*;
 
 use brewlog::application::routes::app_router;
-use brewlog::application::state::{AppState, AppStateConfig};
+use brewlog::application::state::{AppState, AppStateConfig, RepositoryBackend};
 use brewlog::infrastructure::database::Database;
 
 /// Shared test server state.
@@ -87,11 +87,13 @@ fn ensure_server_started() -> Result<(String, String), String> {
 
                 let (stats_tx, _stats_rx) = tokio::sync::mpsc::channel(1);
                 let (timeline_tx, timeline_rx) = tokio::sync::mpsc::channel(32);
+                let (image_purge_tx, _image_purge_rx) = tokio::sync::mpsc::channel(1);
                 let state = AppState::from_database(
                     &database,
                     AppStateConfig {
                         webauthn: test_webauthn(),
                         insecure_cookies: true,
+                        token_hasher: brewlog::infrastructure::auth::TokenHasher::sha256(),
                         foursquare_url: brewlog::infrastructure::foursquare::FOURSQUARE_SEARCH_URL
                             .to_string(),
                         foursquare_api_key: String::new(),
@@ -103,6 +105,13 @@ fn ensure_server_started() -> Result<(String, String), String> {
                         ),
                         timeline_invalidator:
                             brewlog::application::services::TimelineInvalidator::new(timeline_tx),
+                        image_purger: brewlog::application::services::ImagePurger::new(
+                            image_purge_tx,
+                        ),
+                        clock: std::sync::Arc::new(brewlog::domain::clock::SystemClock),
+                        repositories: RepositoryBackend::Sql,
+                        image_settings:
+                            brewlog::infrastructure::image_processing::ImageSettings::default(),
                     },
                 );
 
@@ -119,6 +128,8 @@ fn ensure_server_started() -> Result<(String, String), String> {
                     cup_repo: state.cup_repo.clone(),
                     gear_repo: state.gear_repo.clone(),
                     cafe_repo: state.cafe_repo.clone(),
+                    event_repo: state.event_repo.clone(),
+                    trip_repo: state.trip_repo.clone(),
                 };
                 tokio::spawn(timeline_rebuild_task(
                     timeline_rx,
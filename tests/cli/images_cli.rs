@@ -0,0 +1,110 @@
+use crate::helpers::{create_roast, create_roaster, create_token, run_brewlog};
+
+/// Generate a solid red PNG of the given size.
+fn png_bytes(width: u32, height: u32) -> Vec<u8> {
+    use image::{ImageBuffer, Rgba};
+
+    let img = ImageBuffer::from_pixel(width, height, Rgba([255u8, 0, 0, 255]));
+    let mut buf = Vec::new();
+    let encoder = image::codecs::png::PngEncoder::new(&mut buf);
+    image::ImageEncoder::write_image(
+        encoder,
+        img.as_raw(),
+        width,
+        height,
+        image::ColorType::Rgba8.into(),
+    )
+    .expect("failed to encode test PNG");
+    buf
+}
+
+#[test]
+fn test_roast_image_set_then_get_round_trips() {
+    let token = create_token("test-roast-image");
+    let roaster_id = create_roaster("Image CLI Roasters", &token);
+    let roast_id = create_roast(&roaster_id, "Image CLI Roast", &token);
+
+    let dir = tempfile::tempdir().expect("failed to create temp dir");
+    let photo = dir.path().join("photo.png");
+    std::fs::write(&photo, png_bytes(4, 4)).expect("failed to write photo");
+
+    let output = run_brewlog(
+        &["roast", "image", "set", &roast_id, photo.to_str().unwrap()],
+        &[("BREWLOG_TOKEN", &token)],
+    );
+    assert!(
+        output.status.success(),
+        "image set should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let saved = dir.path().join("out.jpg");
+    let output = run_brewlog(
+        &[
+            "roast",
+            "image",
+            "get",
+            &roast_id,
+            "-o",
+            saved.to_str().unwrap(),
+        ],
+        &[],
+    );
+    assert!(
+        output.status.success(),
+        "image get should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let bytes = std::fs::read(&saved).expect("image should be saved");
+    assert_eq!(
+        image::guess_format(&bytes).expect("saved file should be an image"),
+        image::ImageFormat::Jpeg
+    );
+}
+
+#[test]
+fn test_image_set_rejects_files_that_are_not_images() {
+    let token = create_token("test-roaster-image-invalid");
+    let roaster_id = create_roaster("Image CLI Invalid Roasters", &token);
+
+    let dir = tempfile::tempdir().expect("failed to create temp dir");
+    let notes = dir.path().join("notes.txt");
+    std::fs::write(&notes, "not a photo").expect("failed to write file");
+
+    let output = run_brewlog(
+        &[
+            "roaster",
+            "image",
+            "set",
+            &roaster_id,
+            notes.to_str().unwrap(),
+        ],
+        &[("BREWLOG_TOKEN", &token)],
+    );
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("not a recognised image"));
+}
+
+#[test]
+fn test_image_get_fails_when_there_is_no_image() {
+    let token = create_token("test-roaster-image-missing");
+    let roaster_id = create_roaster("Image CLI Missing Roasters", &token);
+
+    let dir = tempfile::tempdir().expect("failed to create temp dir");
+    let saved = dir.path().join("out.jpg");
+    let output = run_brewlog(
+        &[
+            "roaster",
+            "image",
+            "get",
+            &roaster_id,
+            "-o",
+            saved.to_str().unwrap(),
+        ],
+        &[],
+    );
+
+    assert!(!output.status.success());
+    assert!(!saved.exists());
+}
@@ -0,0 +1,86 @@
+use serde_json::{Value, json};
+
+use super::helpers::{create_roast, create_roaster, create_token, run_brewlog};
+use crate::test_macros::define_cli_auth_test;
+
+define_cli_auth_test!(
+    backfill_requires_auth,
+    &["import", "backfill", "Cargo.toml"]
+);
+
+define_cli_auth_test!(
+    import_requires_auth,
+    &[
+        "import",
+        "beanconqueror",
+        "Cargo.toml",
+        "--roaster-country",
+        "UK"
+    ]
+);
+
+#[test]
+fn import_beanconqueror_reports_what_was_created() {
+    let token = create_token("import-test");
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("Beanconqueror.json");
+    let export = json!({
+        "BEANS": [{
+            "name": "CLI Import Bean",
+            "roaster": "CLI Import Roasters",
+            "weight": 250,
+            "config": { "uuid": "cli-bean" }
+        }]
+    });
+    std::fs::write(&path, export.to_string()).unwrap();
+
+    let output = run_brewlog(
+        &[
+            "import",
+            "beanconqueror",
+            path.to_str().unwrap(),
+            "--roaster-country",
+            "UK",
+        ],
+        &[("BREWLOG_TOKEN", &token)],
+    );
+
+    assert!(
+        output.status.success(),
+        "import failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let report: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["bags"], 1);
+    assert_eq!(report["roasts"], 1);
+}
+
+#[test]
+fn import_backfill_logs_cups_at_their_dates() {
+    let token = create_token("backfill-test");
+    let roaster_id = create_roaster("CLI Backfill Roasters", &token);
+    let roast_id = create_roast(&roaster_id, "CLI Backfill Roast", &token);
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("history.json");
+    let history = json!({
+        "cups": [
+            { "roast_id": roast_id.parse::<i64>().unwrap(), "created_at": "2023-03-04T10:00:00Z" },
+            { "roast_id": roast_id.parse::<i64>().unwrap(), "created_at": "2023-04-01T10:00:00Z" }
+        ]
+    });
+    std::fs::write(&path, history.to_string()).unwrap();
+
+    let output = run_brewlog(
+        &["import", "backfill", path.to_str().unwrap()],
+        &[("BREWLOG_TOKEN", &token)],
+    );
+
+    assert!(
+        output.status.success(),
+        "backfill failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let report: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["cups"], 2);
+    assert_eq!(report["months"], json!(["2023-03", "2023-04"]));
+}
@@ -1,12 +1,22 @@
+pub mod admin_cli;
 pub mod backup_cli;
 pub mod bags_cli;
 pub mod brews_cli;
 pub mod cafes_cli;
+pub mod config_cli;
 pub mod cups_cli;
+pub mod dev_cli;
+pub mod events_cli;
+pub mod export_cli;
 pub mod gear_cli;
 pub mod helpers;
+pub mod images_cli;
+pub mod import_cli;
 pub mod roasters_cli;
+pub mod roasting_cli;
 pub mod roasts_cli;
+pub mod stats_cli;
 pub mod test_macros;
 pub mod timeline_cli;
 pub mod tokens_cli;
+pub mod trips_cli;
@@ -60,7 +60,7 @@ fn test_add_roaster_with_authentication() {
         .unwrap_or_else(|_| panic!("Should output valid JSON, got: {}", stdout));
 
     assert_eq!(roaster["name"], "Test Roasters");
-    assert_eq!(roaster["country"], "UK");
+    assert_eq!(roaster["country"], "United Kingdom");
     assert!(roaster["id"].is_i64(), "Should have an ID");
 }
 
@@ -89,3 +89,55 @@ fn test_list_roasters_shows_added_roaster() {
         .any(|r| r["id"].as_i64().unwrap().to_string() == roaster_id);
     assert!(found, "Should find the added roaster in the list");
 }
+
+#[test]
+fn test_dry_run_adds_roaster_without_touching_the_server() {
+    let output = run_brewlog(
+        &[
+            "--dry-run",
+            "roaster",
+            "add",
+            "--name",
+            "Dry Run Roasters",
+            "--country",
+            "UK",
+        ],
+        &[],
+    );
+
+    assert!(
+        output.status.success(),
+        "dry-run roaster add should succeed without a token: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let roaster: Value = serde_json::from_slice(&output.stdout).expect("Should output valid JSON");
+    assert_eq!(roaster["name"], "Dry Run Roasters");
+
+    let list = run_brewlog(&["roaster", "list"], &[]);
+    assert!(
+        !String::from_utf8_lossy(&list.stdout).contains("Dry Run Roasters"),
+        "dry-run roaster should not reach the real server"
+    );
+}
+
+#[test]
+fn test_add_roaster_checks_arguments_before_sending() {
+    // Nothing listens on this port, so the error must come from the CLI.
+    let output = run_brewlog(
+        &[
+            "--api-url",
+            "http://127.0.0.1:9",
+            "roaster",
+            "add",
+            "--name",
+            "Nowhere Roasters",
+            "--country",
+            "Atlantis",
+        ],
+        &[],
+    );
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("unknown country 'Atlantis'"), "{stderr}");
+}
@@ -0,0 +1,35 @@
+use crate::test_macros::{define_cli_auth_test, define_cli_list_test};
+
+define_cli_auth_test!(
+    test_add_green_coffee_requires_authentication,
+    &["green-coffee", "add", "--name", "Guji", "--amount", "1000"]
+);
+define_cli_auth_test!(
+    test_delete_green_coffee_requires_authentication,
+    &["green-coffee", "delete", "--id", "123"]
+);
+define_cli_auth_test!(
+    test_add_roast_batch_requires_authentication,
+    &[
+        "roast-batch",
+        "add",
+        "--green-coffee-id",
+        "1",
+        "--weight-in",
+        "250",
+        "--weight-out",
+        "212"
+    ]
+);
+define_cli_auth_test!(
+    test_update_roast_batch_requires_authentication,
+    &["roast-batch", "update", "--id", "123", "--clear-bag"]
+);
+define_cli_list_test!(
+    test_list_green_coffee_works_without_authentication,
+    &["green-coffee", "list"]
+);
+define_cli_list_test!(
+    test_list_roast_batches_works_without_authentication,
+    &["roast-batch", "list"]
+);
@@ -81,6 +81,44 @@ fn test_add_roast_with_authentication() {
     assert!(roast["id"].is_i64(), "Should have an ID");
 }
 
+#[test]
+fn test_add_roast_with_a_blend() {
+    let token = create_token("test-add-roast-blend");
+    let roaster_id = create_roaster("Test Roasters Blend", &token);
+
+    let output = run_brewlog(
+        &[
+            "roast",
+            "add",
+            "--roaster-id",
+            &roaster_id,
+            "--name",
+            "House Blend",
+            "--blend",
+            "Brazil 60%, Colombia 40%",
+            "--region",
+            "Various",
+            "--producer",
+            "Various",
+            "--process",
+            "Washed",
+            "--tasting-notes",
+            "Chocolate",
+        ],
+        &[("BREWLOG_TOKEN", &token)],
+    );
+
+    assert!(
+        output.status.success(),
+        "roast add with a blend should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let roast: Value = serde_json::from_slice(&output.stdout).expect("Should output valid JSON");
+    assert_eq!(roast["origin"], "Brazil, Colombia");
+    assert_eq!(roast["components"][0]["origin"], "Brazil");
+    assert_eq!(roast["components"][1]["percentage"], 40.0);
+}
+
 #[test]
 fn test_list_roasts_shows_added_roast() {
     let token = create_token("test-list-roasts");
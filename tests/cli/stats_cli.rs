@@ -0,0 +1,46 @@
+use crate::helpers::run_brewlog;
+
+#[test]
+fn test_stats_prints_a_monthly_summary_by_default() {
+    let output = run_brewlog(&["stats"], &[]);
+    assert!(
+        output.status.success(),
+        "stats should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Last 30 days:"), "got: {stdout}");
+    assert!(stdout.contains("Top roasters"), "got: {stdout}");
+    assert!(stdout.contains("Top origins"), "got: {stdout}");
+}
+
+#[test]
+fn test_stats_year_period_covers_twelve_months() {
+    let output = run_brewlog(&["stats", "--period", "year"], &[]);
+    assert!(
+        output.status.success(),
+        "stats --period year should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Last 12 months:"), "got: {stdout}");
+}
+
+#[test]
+fn test_stats_json_includes_period_history() {
+    let output = run_brewlog(&["stats", "--json"], &[]);
+    assert!(output.status.success());
+
+    let stats: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("stats --json should print JSON");
+    let history = stats["last_month"]["history"]
+        .as_array()
+        .expect("last_month history should be an array");
+    assert_eq!(history.len(), 30);
+    let history = stats["last_year"]["history"]
+        .as_array()
+        .expect("last_year history should be an array");
+    assert_eq!(history.len(), 12);
+}
@@ -1,4 +1,6 @@
-use crate::helpers::{create_roast, create_roaster, create_token, run_brewlog, server_info};
+use crate::helpers::{
+    brewlog_bin, create_roast, create_roaster, create_token, run_brewlog, server_info,
+};
 
 #[test]
 fn test_editing_roaster_updates_timeline_events_after_rebuild() {
@@ -73,3 +75,56 @@ fn test_editing_roaster_updates_timeline_events_after_rebuild() {
         "Expected original roaster name '{original_name}' to no longer appear in timeline, got: {body}"
     );
 }
+
+#[test]
+fn test_timeline_tail_prints_latest_events() {
+    let token = create_token("test-timeline-tail");
+    create_roaster("Tail CLI Roasters", &token);
+    std::thread::sleep(std::time::Duration::from_millis(50));
+
+    let output = run_brewlog(&["timeline", "tail", "-n", "100"], &[]);
+
+    assert!(
+        output.status.success(),
+        "timeline tail should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let line = stdout
+        .lines()
+        .find(|line| line.contains("Tail CLI Roasters"))
+        .expect("roaster event should be printed");
+    assert!(
+        line.contains("roaster"),
+        "line should name the kind: {line}"
+    );
+    assert!(
+        !line.contains('\x1b'),
+        "piped output should not be coloured"
+    );
+}
+
+#[test]
+fn test_timeline_tail_follow_prints_new_events() {
+    let token = create_token("test-timeline-follow");
+    let (address, _) = server_info();
+
+    let mut child = std::process::Command::new(brewlog_bin())
+        .args(["timeline", "tail", "--follow", "-n", "0", "--interval", "1"])
+        .env("BREWLOG_URL", &address)
+        .stdout(std::process::Stdio::piped())
+        .spawn()
+        .expect("failed to start timeline tail");
+
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    create_roaster("Followed CLI Roasters", &token);
+    std::thread::sleep(std::time::Duration::from_millis(2500));
+
+    child.kill().expect("failed to stop timeline tail");
+    let output = child.wait_with_output().expect("failed to collect output");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("Followed CLI Roasters"),
+        "new roaster should be streamed, got: {stdout}"
+    );
+}
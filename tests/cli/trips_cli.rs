@@ -0,0 +1,62 @@
+use serde_json::Value;
+
+use crate::helpers::{create_token, run_brewlog};
+use crate::test_macros::{define_cli_auth_test, define_cli_list_test};
+
+define_cli_auth_test!(
+    test_add_trip_requires_authentication,
+    &[
+        "trip",
+        "add",
+        "--name",
+        "Lisbon long weekend",
+        "--location",
+        "Lisbon, Portugal",
+        "--from",
+        "2026-03-06"
+    ]
+);
+define_cli_auth_test!(
+    test_delete_trip_requires_authentication,
+    &["trip", "delete", "--id", "123"]
+);
+define_cli_list_test!(
+    test_list_trips_works_without_authentication,
+    &["trip", "list"]
+);
+
+#[test]
+fn test_add_day_trip_and_read_its_summary() {
+    let token = create_token("test-add-day-trip");
+
+    let output = run_brewlog(
+        &[
+            "trip",
+            "add",
+            "--name",
+            "Day in Bristol",
+            "--location",
+            "Bristol, UK",
+            "--from",
+            "2026-02-14",
+        ],
+        &[("BREWLOG_TOKEN", &token)],
+    );
+    assert!(
+        output.status.success(),
+        "trip add should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let trip: Value = serde_json::from_slice(&output.stdout).expect("Should output valid JSON");
+    assert_eq!(
+        trip["end_date"], "2026-02-14",
+        "a day trip ends when it starts"
+    );
+
+    let id = trip["id"].to_string();
+    let output = run_brewlog(&["trip", "summary", "--id", &id], &[]);
+    assert!(output.status.success());
+    let summary: Value = serde_json::from_slice(&output.stdout).expect("Should output valid JSON");
+    assert_eq!(summary["trip"]["name"], "Day in Bristol");
+    assert!(summary["cups"].is_array());
+}
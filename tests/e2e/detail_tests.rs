@@ -37,7 +37,7 @@ async fn roaster_detail_shows_all_fields() {
     let body = session.driver.find(By::Css("body")).await.unwrap();
     let text = body.text().await.unwrap();
     assert!(text.contains("Detail Roasters"), "Should show roaster name");
-    assert!(text.contains("JP"), "Should show country code");
+    assert!(text.contains("Japan"), "Should show country");
     assert!(text.contains("Tokyo"), "Should show city");
     assert!(text.contains("Visit Website"), "Should show homepage link");
 
@@ -0,0 +1,121 @@
+use brewlog::infrastructure::overview::InstanceOverview;
+use serde_json::json;
+
+use super::helpers::{create_default_roaster, create_session, spawn_app, spawn_app_with_auth};
+
+#[tokio::test]
+async fn overview_requires_auth() {
+    let app = spawn_app().await;
+
+    let response = reqwest::Client::new()
+        .get(app.api_url("/admin/overview"))
+        .send()
+        .await
+        .expect("failed to send request");
+
+    assert_eq!(response.status(), reqwest::StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn overview_reports_instance_statistics() {
+    let app = spawn_app_with_auth().await;
+    create_default_roaster(&app).await;
+    create_session(&app).await;
+
+    let response = reqwest::Client::new()
+        .get(app.api_url("/admin/overview"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .send()
+        .await
+        .expect("failed to send request");
+
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    let overview: InstanceOverview = response.json().await.expect("failed to parse overview");
+
+    assert!(overview.database_bytes > 0);
+    assert_eq!(overview.image_count, 0);
+    assert_eq!(overview.image_bytes, 0);
+    assert_eq!(overview.images_awaiting_purge, 0);
+    assert_eq!(overview.active_tokens, 1);
+    assert_eq!(overview.active_sessions, 1);
+    assert_eq!(overview.ai_usage_this_month.calls, 0);
+    assert_eq!(overview.last_maintenance, None);
+
+    let rows = |table: &str| {
+        overview
+            .table_counts
+            .iter()
+            .find(|t| t.table == table)
+            .map(|t| t.rows)
+    };
+    assert_eq!(rows("roasters"), Some(1));
+    assert_eq!(rows("users"), Some(1));
+    assert_eq!(rows("brews"), Some(0));
+}
+
+#[tokio::test]
+async fn normalizing_countries_rewrites_aliases_and_reports_unknowns() {
+    let app = spawn_app_with_auth().await;
+    let client = reqwest::Client::new();
+    // Restores write rows verbatim, which is how un-normalized countries
+    // from older instances end up in the database.
+    let roasters: Vec<_> = [
+        (1, "Alias Roasters", "USA"),
+        (2, "Canonical Roasters", "Japan"),
+        (3, "Mystery Roasters", "Atlantis"),
+    ]
+    .into_iter()
+    .map(|(id, name, country)| {
+        json!({
+            "id": id,
+            "name": name,
+            "slug": name.to_lowercase().replace(' ', "-"),
+            "country": country,
+            "city": null,
+            "homepage": null,
+            "created_at": "2024-01-01T00:00:00Z"
+        })
+    })
+    .collect();
+    let backup = json!({
+        "version": 2,
+        "created_at": "2024-01-01T00:00:00Z",
+        "roasters": roasters,
+        "gear": [],
+        "roasts": [],
+        "bags": [],
+        "brews": [],
+        "timeline_events": []
+    });
+    let response = client
+        .post(app.api_url("/backup/restore"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&backup)
+        .send()
+        .await
+        .expect("failed to send request");
+    assert_eq!(response.status(), reqwest::StatusCode::NO_CONTENT);
+
+    let response = client
+        .post(app.api_url("/admin/normalize-countries"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .send()
+        .await
+        .expect("failed to send request");
+
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    let report: serde_json::Value = response.json().await.expect("failed to parse report");
+    assert_eq!(report["updated"], 1);
+    assert_eq!(
+        report["unrecognized"][0]["roaster_name"],
+        "Mystery Roasters"
+    );
+    assert_eq!(report["unrecognized"][0]["country"], "Atlantis");
+
+    let roasters = app.roaster_repo.list_all().await.expect("failed to list");
+    let alias = roasters
+        .iter()
+        .find(|r| r.name == "Alias Roasters")
+        .expect("roaster missing");
+    assert_eq!(alias.country, "United States");
+}
@@ -0,0 +1,151 @@
+use std::time::Duration;
+
+use brewlog::domain::ai_usage::AiModelPrice;
+use chrono::Utc;
+use reqwest::Client;
+use serde_json::{Value, json};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, ResponseTemplate};
+
+use crate::helpers::{
+    TestApp, create_session, spawn_app_with_auth, spawn_app_with_openrouter_mock,
+};
+
+async fn set_price(app: &TestApp, model: &str, price: Value) -> reqwest::Response {
+    Client::new()
+        .put(app.api_url(&format!("/ai-prices/{model}")))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&price)
+        .send()
+        .await
+        .expect("failed to execute request")
+}
+
+async fn list_prices(app: &TestApp) -> Vec<AiModelPrice> {
+    Client::new()
+        .get(app.api_url("/ai-prices"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .send()
+        .await
+        .expect("failed to execute request")
+        .json()
+        .await
+        .unwrap()
+}
+
+async fn admin_page(app: &TestApp, session: &str) -> String {
+    Client::new()
+        .get(app.page_url("/admin"))
+        .header("Cookie", format!("brewlog_session={session}"))
+        .send()
+        .await
+        .expect("failed to fetch admin page")
+        .text()
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn prices_are_set_per_model() {
+    let app = spawn_app_with_auth().await;
+
+    let response = set_price(
+        &app,
+        "google/gemini-3-flash-preview",
+        json!({ "prompt_price": 0.5, "completion_price": 3.0 }),
+    )
+    .await;
+    assert_eq!(response.status(), 200);
+
+    set_price(
+        &app,
+        "google/gemini-3-flash-preview",
+        json!({ "prompt_price": 0.3, "completion_price": 2.5 }),
+    )
+    .await;
+    let prices = list_prices(&app).await;
+    assert_eq!(prices.len(), 1);
+    assert_eq!(prices[0].model, "google/gemini-3-flash-preview");
+    assert!((prices[0].prompt_price - 0.3).abs() < f64::EPSILON);
+
+    let response = Client::new()
+        .delete(app.api_url("/ai-prices/google/gemini-3-flash-preview"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 204);
+    assert!(list_prices(&app).await.is_empty());
+}
+
+#[tokio::test]
+async fn prices_must_be_sensible() {
+    let app = spawn_app_with_auth().await;
+
+    let response = set_price(
+        &app,
+        "openrouter/free",
+        json!({ "prompt_price": -1.0, "completion_price": 3.0 }),
+    )
+    .await;
+    assert_eq!(response.status(), 400);
+
+    let response = set_price(
+        &app,
+        "gemini%20flash",
+        json!({ "prompt_price": 0.5, "completion_price": 3.0 }),
+    )
+    .await;
+    assert_eq!(response.status(), 400);
+}
+
+#[tokio::test]
+async fn usage_is_costed_from_the_price_table_by_month() {
+    let app = spawn_app_with_openrouter_mock().await;
+    let mock_server = app.mock_server.as_ref().unwrap();
+    Mock::given(method("POST"))
+        .and(path("/api/v1/chat/completions"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "choices": [{ "message": { "role": "assistant", "content": "{}" } }],
+            "usage": {
+                "prompt_tokens": 100,
+                "completion_tokens": 50,
+                "total_tokens": 150,
+                "cost": 0.001
+            }
+        })))
+        .mount(mock_server)
+        .await;
+
+    // The test server talks to `openrouter/free`: 100 prompt tokens at $1/M
+    // and 50 completion tokens at $2/M come to $0.0002.
+    set_price(
+        &app,
+        "openrouter/free",
+        json!({ "prompt_price": 1.0, "completion_price": 2.0 }),
+    )
+    .await;
+    let response = Client::new()
+        .post(app.api_url("/extract-roaster"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&json!({ "prompt": "Square Mile" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+
+    // Usage is recorded in the background.
+    let session = create_session(&app).await;
+    let month = Utc::now().format("%B %Y").to_string();
+    let mut page = String::new();
+    for _ in 0..50 {
+        page = admin_page(&app, &session).await;
+        if page.contains(&month) {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    assert!(page.contains(&month), "no usage for {month}");
+    assert!(page.contains("$0.0002"));
+    assert!(!page.contains("$0.0010"));
+}
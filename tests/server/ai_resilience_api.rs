@@ -0,0 +1,157 @@
+use std::time::Duration;
+
+use brewlog::application::errors::ErrorResponse;
+use brewlog::domain::ErrorCode;
+use brewlog::infrastructure::ai::{AI_UNAVAILABLE_MESSAGE, AiPolicy, ExtractedRoaster};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, ResponseTemplate};
+
+use crate::helpers::{TestApp, create_session, spawn_app_with_ai_policy};
+
+fn fast_policy() -> AiPolicy {
+    AiPolicy {
+        attempts: 2,
+        attempt_timeout: Duration::from_millis(500),
+        backoff: Duration::from_millis(10),
+        failure_threshold: 2,
+        cool_down: Duration::from_secs(60),
+    }
+}
+
+fn roaster_response() -> ResponseTemplate {
+    ResponseTemplate::new(200).set_body_json(serde_json::json!({
+        "choices": [{
+            "message": {
+                "role": "assistant",
+                "content": r#"{"name": "Square Mile", "country": "United Kingdom"}"#
+            }
+        }]
+    }))
+}
+
+async fn extract_roaster(app: &TestApp, datastar: bool) -> reqwest::Response {
+    let mut request = reqwest::Client::new()
+        .post(app.api_url("/extract-roaster"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&serde_json::json!({ "prompt": "Square Mile Coffee" }));
+    if datastar {
+        request = request.header("datastar-request", "true");
+    }
+    request.send().await.expect("failed to execute request")
+}
+
+#[tokio::test]
+async fn transient_failures_are_retried() {
+    let app = spawn_app_with_ai_policy(fast_policy()).await;
+    let mock_server = app.mock_server.as_ref().unwrap();
+
+    Mock::given(method("POST"))
+        .and(path("/api/v1/chat/completions"))
+        .respond_with(ResponseTemplate::new(502))
+        .up_to_n_times(1)
+        .expect(1)
+        .mount(mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/api/v1/chat/completions"))
+        .respond_with(roaster_response())
+        .expect(1)
+        .mount(mock_server)
+        .await;
+
+    let response = extract_roaster(&app, false).await;
+
+    assert_eq!(response.status(), 200);
+    let roaster: ExtractedRoaster = response.json().await.unwrap();
+    assert_eq!(roaster.name.as_deref(), Some("Square Mile"));
+}
+
+#[tokio::test]
+async fn timeouts_fall_back_to_manual_entry() {
+    let app = spawn_app_with_ai_policy(fast_policy()).await;
+    let mock_server = app.mock_server.as_ref().unwrap();
+
+    Mock::given(method("POST"))
+        .and(path("/api/v1/chat/completions"))
+        .respond_with(roaster_response().set_delay(Duration::from_secs(2)))
+        .expect(2)
+        .mount(mock_server)
+        .await;
+
+    let response = extract_roaster(&app, false).await;
+
+    assert_eq!(response.status(), 503);
+    let body: ErrorResponse = response.json().await.unwrap();
+    assert_eq!(body.code, ErrorCode::AiUnavailable);
+    assert_eq!(body.message, AI_UNAVAILABLE_MESSAGE);
+}
+
+#[tokio::test]
+async fn datastar_forms_get_the_fallback_signal() {
+    let app = spawn_app_with_ai_policy(fast_policy()).await;
+    let mock_server = app.mock_server.as_ref().unwrap();
+
+    Mock::given(method("POST"))
+        .and(path("/api/v1/chat/completions"))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(mock_server)
+        .await;
+
+    let response = extract_roaster(&app, true).await;
+
+    assert_eq!(response.status(), 200);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["_aiUnavailable"], true);
+}
+
+#[tokio::test]
+async fn requests_that_cannot_succeed_are_not_retried() {
+    let app = spawn_app_with_ai_policy(fast_policy()).await;
+    let mock_server = app.mock_server.as_ref().unwrap();
+
+    Mock::given(method("POST"))
+        .and(path("/api/v1/chat/completions"))
+        .respond_with(ResponseTemplate::new(401))
+        .expect(1)
+        .mount(mock_server)
+        .await;
+
+    assert_eq!(extract_roaster(&app, false).await.status(), 503);
+}
+
+#[tokio::test]
+async fn repeated_failures_open_the_circuit() {
+    let app = spawn_app_with_ai_policy(AiPolicy {
+        attempts: 1,
+        ..fast_policy()
+    })
+    .await;
+    let mock_server = app.mock_server.as_ref().unwrap();
+
+    // Two failures open the circuit; the third extraction never calls out.
+    Mock::given(method("POST"))
+        .and(path("/api/v1/chat/completions"))
+        .respond_with(ResponseTemplate::new(503))
+        .expect(2)
+        .mount(mock_server)
+        .await;
+
+    for _ in 0..3 {
+        let response = extract_roaster(&app, false).await;
+        assert_eq!(response.status(), 503);
+    }
+
+    let session = create_session(&app).await;
+    let admin = reqwest::Client::new()
+        .get(app.page_url("/admin"))
+        .header("Cookie", format!("brewlog_session={session}"))
+        .send()
+        .await
+        .unwrap()
+        .text()
+        .await
+        .unwrap();
+    assert!(admin.contains("AI Health"));
+    assert!(admin.contains("100.0%"));
+    assert!(admin.contains("Paused after repeated failures"));
+}
@@ -0,0 +1,239 @@
+use brewlog::application::errors::ErrorResponse;
+use brewlog::domain::ErrorCode;
+use brewlog::domain::aliases::Alias;
+use brewlog::domain::roasters::Roaster;
+use brewlog::domain::roasts::{NewRoast, Roast, RoastWithRoaster};
+use reqwest::Client;
+use serde::Serialize;
+use serde_json::json;
+
+use crate::helpers::{
+    TestApp, create_default_bag, create_default_roast, create_roast_with_payload,
+    create_roaster_with_name, create_session, spawn_app_with_auth,
+};
+
+async fn post<P: Serialize>(app: &TestApp, path: &str, payload: &P) -> reqwest::Response {
+    Client::new()
+        .post(app.api_url(path))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(payload)
+        .send()
+        .await
+        .expect("failed to execute request")
+}
+
+async fn get(app: &TestApp, path: &str) -> reqwest::Response {
+    Client::new()
+        .get(app.api_url(path))
+        .send()
+        .await
+        .expect("failed to execute request")
+}
+
+async fn aliases(app: &TestApp, path: &str) -> Vec<String> {
+    let aliases: Vec<Alias> = get(app, path).await.json().await.unwrap();
+    aliases.into_iter().map(|alias| alias.name).collect()
+}
+
+#[tokio::test]
+async fn aliases_are_added_listed_and_removed() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_roaster_with_name(&app, "Square Mile").await;
+    let path = format!("/roasters/{}/aliases", roaster.id);
+
+    let response = post(&app, &path, &json!({ "name": "  Square Mile Coffee  " })).await;
+    assert_eq!(response.status(), 201);
+    let alias: Alias = response.json().await.unwrap();
+    assert_eq!(alias.name, "Square Mile Coffee");
+    assert_eq!(alias.roaster_id, Some(roaster.id));
+    assert_eq!(aliases(&app, &path).await, ["Square Mile Coffee"]);
+
+    let response = post(&app, &path, &json!({ "name": "square mile coffee" })).await;
+    assert_eq!(response.status(), 409);
+
+    let response = post(&app, &path, &json!({ "name": " - " })).await;
+    assert_eq!(response.status(), 400);
+
+    let response = Client::new()
+        .delete(app.api_url(&format!("/aliases/{}", alias.id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 204);
+    assert!(aliases(&app, &path).await.is_empty());
+}
+
+#[tokio::test]
+async fn searches_find_previous_names() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_roaster_with_name(&app, "Square Mile").await;
+    post(
+        &app,
+        &format!("/roasters/{}/aliases", roaster.id),
+        &json!({ "name": "Fleet Street Roasters" }),
+    )
+    .await;
+
+    let body = get(&app, "/nav-search?q=fleet").await.text().await.unwrap();
+    assert!(body.contains(&format!("href=\"/roasters/{}\"", roaster.slug)));
+}
+
+#[tokio::test]
+async fn quick_add_matches_previous_names() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_roaster_with_name(&app, "Square Mile").await;
+    post(
+        &app,
+        &format!("/roasters/{}/aliases", roaster.id),
+        &json!({ "name": "Fleet Street Roasters" }),
+    )
+    .await;
+
+    let response = post(
+        &app,
+        "/roasters/quick-add",
+        &json!({ "name": "Fleet Street Roasters", "country": "UK" }),
+    )
+    .await;
+    assert_eq!(response.status(), 200);
+    let matched: Roaster = response.json().await.unwrap();
+    assert_eq!(matched.id, roaster.id);
+}
+
+#[tokio::test]
+async fn merging_roasters_keeps_the_old_name() {
+    let app = spawn_app_with_auth().await;
+    let source = create_roaster_with_name(&app, "Fleet Street Roasters").await;
+    let into = create_roaster_with_name(&app, "Square Mile").await;
+    let roast = create_default_roast(&app, source.id).await;
+    // Warm the roaster listing cache so a stale row would show.
+    get(&app, "/roasters").await;
+
+    let response = post(
+        &app,
+        &format!("/roasters/{}/merge", source.id),
+        &json!({ "into": into.id }),
+    )
+    .await;
+    assert_eq!(response.status(), 200);
+    let merged: Roaster = response.json().await.unwrap();
+    assert_eq!(merged.id, into.id);
+
+    assert_eq!(
+        get(&app, &format!("/roasters/{}", source.id))
+            .await
+            .status(),
+        404
+    );
+    let listed: Vec<Roaster> = get(&app, "/roasters").await.json().await.unwrap();
+    assert!(listed.iter().all(|roaster| roaster.id != source.id));
+    let moved: Roast = get(&app, &format!("/roasts/{}", roast.id))
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(moved.roaster_id, into.id);
+    assert_eq!(
+        aliases(&app, &format!("/roasters/{}/aliases", into.id)).await,
+        ["Fleet Street Roasters"]
+    );
+
+    let page = Client::new()
+        .get(app.page_url(&format!("/roasters/{}", source.slug)))
+        .send()
+        .await
+        .unwrap()
+        .text()
+        .await
+        .unwrap();
+    assert!(
+        page.contains("Also Known As"),
+        "old roaster links still resolve"
+    );
+    assert!(page.contains("Fleet Street Roasters"));
+}
+
+#[tokio::test]
+async fn merging_roasters_with_the_same_roast_is_refused() {
+    let app = spawn_app_with_auth().await;
+    let source = create_roaster_with_name(&app, "Fleet Street Roasters").await;
+    let into = create_roaster_with_name(&app, "Square Mile").await;
+    create_default_roast(&app, source.id).await;
+    create_default_roast(&app, into.id).await;
+
+    let response = post(
+        &app,
+        &format!("/roasters/{}/merge", source.id),
+        &json!({ "into": into.id }),
+    )
+    .await;
+    assert_eq!(response.status(), 409);
+    let body: ErrorResponse = response.json().await.unwrap();
+    assert_eq!(body.code, ErrorCode::DuplicateSlug);
+
+    let response = post(
+        &app,
+        &format!("/roasters/{}/merge", into.id),
+        &json!({ "into": into.id }),
+    )
+    .await;
+    assert_eq!(response.status(), 409);
+}
+
+#[tokio::test]
+async fn merging_roasts_moves_their_bags() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_roaster_with_name(&app, "Square Mile").await;
+    let into = create_default_roast(&app, roaster.id).await;
+    let source = create_roast_with_payload(
+        &app,
+        NewRoast {
+            roaster_id: roaster.id,
+            name: "Red Brick Blend".to_string(),
+            origin: "Brazil".to_string(),
+            region: "Cerrado".to_string(),
+            producer: "Various".to_string(),
+            tasting_notes: vec!["Chocolate".to_string()],
+            process: "Natural".to_string(),
+            components: Vec::new(),
+            harvest_year: None,
+            created_at: None,
+        },
+    )
+    .await;
+    let bag = create_default_bag(&app, source.id).await;
+
+    let response = post(
+        &app,
+        &format!("/roasts/{}/merge", source.id),
+        &json!({ "into": into.id }),
+    )
+    .await;
+    assert_eq!(response.status(), 200);
+    let merged: RoastWithRoaster = response.json().await.unwrap();
+    assert_eq!(merged.roast.id, into.id);
+
+    let bag: serde_json::Value = get(&app, &format!("/bags/{}", bag.id))
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(bag["roast_id"], json!(into.id));
+    assert_eq!(
+        aliases(&app, &format!("/roasts/{}/aliases", into.id)).await,
+        ["Red Brick Blend"]
+    );
+
+    let session = create_session(&app).await;
+    let page = Client::new()
+        .get(app.page_url(&format!(
+            "/roasters/{}/roasts/red-brick-blend",
+            roaster.slug
+        )))
+        .header("Cookie", format!("brewlog_session={session}"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(page.status(), 200, "old roast links still resolve");
+}
@@ -0,0 +1,195 @@
+use brewlog::application::errors::{ErrorEnvelope, ErrorResponse};
+use brewlog::application::routes::api::version::PageEnvelope;
+use brewlog::domain::ErrorCode;
+use brewlog::domain::roasters::Roaster;
+use reqwest::Client;
+use reqwest::header::LINK;
+
+use super::helpers::{TestApp, create_roaster_with_name, spawn_app, spawn_app_with_auth};
+
+fn v2_url(app: &TestApp, path: &str) -> String {
+    format!("{}/api/v2{path}", app.address)
+}
+
+#[tokio::test]
+async fn v1_responses_point_to_v2() {
+    let app = spawn_app().await;
+
+    let response = Client::new()
+        .get(app.api_url("/roasters"))
+        .send()
+        .await
+        .expect("failed to send request");
+
+    assert_eq!(response.status(), 200);
+    let headers = response.headers();
+    assert_eq!(headers["api-version"], "v1");
+    assert!(headers["deprecation"].to_str().unwrap().starts_with('@'));
+    assert_eq!(headers[LINK], "</api/v2>; rel=\"successor-version\"");
+}
+
+#[tokio::test]
+async fn v2_responses_are_not_deprecated() {
+    let app = spawn_app().await;
+
+    let response = Client::new()
+        .get(v2_url(&app, "/roasters"))
+        .send()
+        .await
+        .expect("failed to send request");
+
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers()["api-version"], "v2");
+    assert!(response.headers().get("deprecation").is_none());
+}
+
+#[tokio::test]
+async fn both_versions_list_the_same_roasters() {
+    let app = spawn_app_with_auth().await;
+    for name in ["Alpha", "Bravo", "Charlie"] {
+        create_roaster_with_name(&app, name).await;
+    }
+    let client = Client::new();
+
+    let v1: Vec<Roaster> = client
+        .get(app.api_url("/roasters"))
+        .send()
+        .await
+        .expect("failed to send request")
+        .json()
+        .await
+        .expect("v1 should return an array");
+    let v2: PageEnvelope<Roaster> = client
+        .get(v2_url(&app, "/roasters?page=2&page_size=2"))
+        .send()
+        .await
+        .expect("failed to send request")
+        .json()
+        .await
+        .expect("v2 should return a page");
+
+    assert_eq!(v1.len(), 3);
+    assert_eq!(v2.total, 3);
+    assert_eq!(v2.total_pages, 2);
+    assert_eq!(v2.page, 2);
+    assert_eq!(v2.page_size, 2);
+    assert_eq!(v2.items.len(), 1);
+    assert_eq!(v2.items[0].name, "Charlie");
+}
+
+#[tokio::test]
+async fn v2_lists_default_to_the_first_page() {
+    let app = spawn_app_with_auth().await;
+    create_roaster_with_name(&app, "Alpha").await;
+
+    let page: PageEnvelope<serde_json::Value> = Client::new()
+        .get(v2_url(&app, "/bags"))
+        .send()
+        .await
+        .expect("failed to send request")
+        .json()
+        .await
+        .expect("v2 should return a page");
+
+    assert_eq!(page.page, 1);
+    assert_eq!(page.total, 0);
+    assert!(page.items.is_empty());
+}
+
+#[tokio::test]
+async fn v2_errors_are_wrapped_in_an_error_object() {
+    let app = spawn_app().await;
+    let client = Client::new();
+
+    let response = client
+        .get(app.api_url("/roasters/9999"))
+        .send()
+        .await
+        .expect("failed to send request");
+    assert_eq!(response.status(), 404);
+    let v1: ErrorResponse = response.json().await.expect("v1 error should be flat");
+    assert_eq!(v1.code, ErrorCode::NotFound);
+
+    let response = client
+        .get(v2_url(&app, "/roasters/9999"))
+        .send()
+        .await
+        .expect("failed to send request");
+    assert_eq!(response.status(), 404);
+    let v2: ErrorEnvelope = response.json().await.expect("v2 error should be wrapped");
+    assert_eq!(v2.error.code, ErrorCode::NotFound);
+    assert_eq!(v2.error.message, v1.message);
+}
+
+#[tokio::test]
+async fn v2_gives_bodiless_errors_a_body() {
+    let app = spawn_app_with_auth().await;
+
+    let response = Client::new()
+        .post(v2_url(&app, "/roasters"))
+        .json(&serde_json::json!({"name": "Nope", "country": "UK"}))
+        .send()
+        .await
+        .expect("failed to send request");
+
+    assert_eq!(response.status(), 401);
+    let body: ErrorEnvelope = response.json().await.expect("v2 error should be wrapped");
+    assert_eq!(body.error.code, ErrorCode::Unauthorized);
+}
+
+#[tokio::test]
+async fn v2_writes_reach_the_shared_handlers() {
+    let app = spawn_app_with_auth().await;
+    let client = Client::new();
+
+    let response = client
+        .post(v2_url(&app, "/roasters"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&serde_json::json!({"name": "Versioned", "country": "UK"}))
+        .send()
+        .await
+        .expect("failed to send request");
+    assert_eq!(response.status(), 201);
+    let created: Roaster = response.json().await.unwrap();
+
+    let fetched: Roaster = client
+        .get(app.api_url(&format!("/roasters/{}", created.id)))
+        .send()
+        .await
+        .expect("failed to send request")
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(fetched.name, "Versioned");
+}
+
+#[tokio::test]
+async fn unknown_versions_are_refused() {
+    let app = spawn_app().await;
+
+    let response = Client::new()
+        .get(format!("{}/api/v3/roasters", app.address))
+        .send()
+        .await
+        .expect("failed to send request");
+
+    assert_eq!(response.status(), 404);
+    let body: ErrorResponse = response.json().await.expect("should explain the refusal");
+    assert_eq!(body.code, ErrorCode::UnsupportedApiVersion);
+    assert!(body.message.contains("v1, v2"), "{}", body.message);
+}
+
+#[tokio::test]
+async fn unknown_paths_in_a_known_version_are_plain_not_found() {
+    let app = spawn_app().await;
+
+    let response = Client::new()
+        .get(v2_url(&app, "/no-such-thing"))
+        .send()
+        .await
+        .expect("failed to send request");
+    assert_eq!(response.status(), 404);
+    assert_eq!(response.headers()["api-version"], "v2");
+    let body: ErrorEnvelope = response.json().await.expect("v2 error should be wrapped");
+    assert_eq!(body.error.code, ErrorCode::NotFound);
+}
@@ -0,0 +1,94 @@
+use brewlog::domain::ids::UserId;
+use reqwest::Client;
+
+use super::helpers::{TestApp, create_default_roaster, create_second_user, spawn_app_with_auth};
+
+async fn admin_id(app: &TestApp) -> UserId {
+    app.user_repo
+        .as_ref()
+        .unwrap()
+        .get_by_username("admin")
+        .await
+        .expect("admin user should exist")
+        .id
+}
+
+async fn get_page(app: &TestApp, path: &str) -> String {
+    let response = Client::new()
+        .get(app.page_url(path))
+        .send()
+        .await
+        .expect("failed to fetch page");
+    assert_eq!(response.status(), 200);
+    response.text().await.expect("failed to read body")
+}
+
+#[tokio::test]
+async fn created_entities_record_their_creator() {
+    let app = spawn_app_with_auth().await;
+
+    let roaster = create_default_roaster(&app).await;
+
+    assert_eq!(roaster.created_by, Some(admin_id(&app).await));
+}
+
+#[tokio::test]
+async fn timeline_events_record_their_creator() {
+    let app = spawn_app_with_auth().await;
+
+    create_default_roaster(&app).await;
+
+    let events = app
+        .timeline_repo
+        .list_all()
+        .await
+        .expect("failed to list timeline events");
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].created_by, Some(admin_id(&app).await));
+}
+
+#[tokio::test]
+async fn single_user_instances_hide_attribution() {
+    let app = spawn_app_with_auth().await;
+
+    let roaster = create_default_roaster(&app).await;
+
+    let timeline = get_page(&app, "/timeline").await;
+    assert!(!timeline.contains("timeline-user-filter"));
+    assert!(!timeline.contains("Logged by"));
+
+    let detail = get_page(&app, &format!("/roasters/{}", roaster.slug)).await;
+    assert!(!detail.contains("Logged by"));
+}
+
+#[tokio::test]
+async fn shared_instances_show_who_logged_each_entry() {
+    let app = spawn_app_with_auth().await;
+    create_second_user(&app, "barista").await;
+
+    let roaster = create_default_roaster(&app).await;
+
+    let timeline = get_page(&app, "/timeline").await;
+    assert!(timeline.contains("timeline-user-filter"));
+    assert!(timeline.contains("barista"));
+    assert!(timeline.contains("Logged by admin"));
+
+    let detail = get_page(&app, &format!("/roasters/{}", roaster.slug)).await;
+    assert!(detail.contains("Logged by admin"));
+}
+
+#[tokio::test]
+async fn timeline_can_be_filtered_by_user() {
+    let app = spawn_app_with_auth().await;
+    let barista = create_second_user(&app, "barista").await.id;
+    let admin = admin_id(&app).await;
+
+    let roaster = create_default_roaster(&app).await;
+
+    let mine = get_page(&app, &format!("/timeline?user={admin}")).await;
+    assert!(mine.contains(&roaster.name));
+
+    let theirs = get_page(&app, &format!("/timeline?user={barista}")).await;
+    assert!(!theirs.contains(&roaster.name));
+    assert!(theirs.contains("No events yet"));
+}
@@ -1,7 +1,8 @@
+use brewlog::infrastructure::auth::{TokenHasher, hash_token};
 use reqwest::{Client, StatusCode};
 use serde_json::json;
 
-use crate::helpers::{spawn_app, spawn_app_with_auth};
+use crate::helpers::{spawn_app, spawn_app_with_auth, spawn_app_with_token_hasher};
 
 #[tokio::test]
 async fn test_create_token_requires_authentication() {
@@ -166,6 +167,95 @@ async fn test_revoked_token_cannot_be_used() {
     assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
 }
 
+fn fast_argon2() -> TokenHasher {
+    TokenHasher::argon2(8, 1, 1).unwrap()
+}
+
+#[tokio::test]
+async fn test_argon2_hashed_token_can_be_used() {
+    let app = spawn_app_with_token_hasher(fast_argon2()).await;
+    let client = Client::new();
+    let auth_token = app.auth_token.as_ref().unwrap();
+
+    let create_body: serde_json::Value = client
+        .post(&app.api_url("/tokens"))
+        .bearer_auth(auth_token)
+        .json(&json!({ "name": "argon2-token" }))
+        .send()
+        .await
+        .expect("Failed to send request")
+        .json()
+        .await
+        .expect("Failed to parse response");
+    let new_token = create_body.get("token").unwrap().as_str().unwrap();
+
+    // Salted hashes can't be found by exact match
+    let lookup = app
+        .token_repo
+        .as_ref()
+        .unwrap()
+        .get_by_token_hash(&hash_token(new_token))
+        .await;
+    assert!(lookup.is_err());
+
+    let response = client
+        .get(&app.api_url("/tokens"))
+        .bearer_auth(new_token)
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = client
+        .get(&app.api_url("/tokens"))
+        .bearer_auth(format!("{new_token}x"))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_legacy_token_is_rehashed_on_use() {
+    let app = spawn_app_with_token_hasher(fast_argon2()).await;
+    let client = Client::new();
+    let auth_token = app.auth_token.as_ref().unwrap();
+    let token_repo = app.token_repo.as_ref().unwrap();
+
+    let legacy = token_repo
+        .get_by_token_hash(&hash_token(auth_token))
+        .await
+        .expect("seeded token should use the legacy hash");
+
+    let response = client
+        .get(&app.api_url("/tokens"))
+        .bearer_auth(auth_token)
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // The upgrade happens in the background after the request
+    let mut upgraded = None;
+    for _ in 0..50 {
+        let token = token_repo.get(legacy.id).await.unwrap();
+        if token.token_hash.starts_with("$argon2id$") {
+            upgraded = Some(token);
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+    }
+    assert!(upgraded.is_some(), "token hash was not upgraded");
+
+    let response = client
+        .get(&app.api_url("/tokens"))
+        .bearer_auth(auth_token)
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
 #[tokio::test]
 async fn test_protected_endpoints_require_authentication() {
     let app = spawn_app_with_auth().await;
@@ -0,0 +1,118 @@
+use brewlog::domain::ids::RoasterId;
+use brewlog::domain::roasts::{FieldSuggestion, NewRoast, Roast};
+
+use crate::helpers::{TestApp, create_default_roaster, create_entity, spawn_app_with_auth};
+
+async fn create_roast(
+    app: &TestApp,
+    roaster_id: RoasterId,
+    name: &str,
+    origin: &str,
+    notes: &[&str],
+) {
+    let _: Roast = create_entity(
+        app,
+        "/roasts",
+        &NewRoast {
+            roaster_id,
+            name: name.to_string(),
+            origin: origin.to_string(),
+            region: "Unknown".to_string(),
+            producer: "Smallholders".to_string(),
+            tasting_notes: notes.iter().map(ToString::to_string).collect(),
+            process: "Washed".to_string(),
+            components: Vec::new(),
+            harvest_year: None,
+            created_at: None,
+        },
+    )
+    .await;
+}
+
+async fn autocomplete(app: &TestApp, query: &str) -> reqwest::Response {
+    reqwest::Client::new()
+        .get(app.api_url(&format!("/autocomplete?{query}")))
+        .send()
+        .await
+        .expect("Failed to execute request")
+}
+
+#[tokio::test]
+async fn autocomplete_ranks_values_by_frequency() {
+    let app = spawn_app_with_auth().await;
+    let roaster_id = create_default_roaster(&app).await.id;
+    create_roast(&app, roaster_id, "One", "Kenya", &["Blackcurrant"]).await;
+    create_roast(&app, roaster_id, "Two", "Ethiopia", &["Blueberry"]).await;
+    create_roast(&app, roaster_id, "Three", "ethiopia", &["Bergamot"]).await;
+
+    let response = autocomplete(&app, "field=origin&q=").await;
+    assert_eq!(response.status(), 200);
+    let suggestions: Vec<FieldSuggestion> = response.json().await.expect("Failed to parse");
+
+    assert_eq!(suggestions.len(), 2);
+    assert_eq!(suggestions[0].value.to_lowercase(), "ethiopia");
+    assert_eq!(suggestions[0].uses, 2);
+    assert_eq!(suggestions[1].value, "Kenya");
+}
+
+#[tokio::test]
+async fn autocomplete_matches_individual_tasting_notes() {
+    let app = spawn_app_with_auth().await;
+    let roaster_id = create_default_roaster(&app).await.id;
+    create_roast(
+        &app,
+        roaster_id,
+        "One",
+        "Kenya",
+        &["Blackcurrant", "Tomato"],
+    )
+    .await;
+    create_roast(
+        &app,
+        roaster_id,
+        "Two",
+        "Kenya",
+        &["Blackcurrant", "Blueberry"],
+    )
+    .await;
+
+    let suggestions: Vec<FieldSuggestion> = autocomplete(&app, "field=tasting_note&q=bl")
+        .await
+        .json()
+        .await
+        .expect("Failed to parse");
+
+    let values: Vec<_> = suggestions.iter().map(|s| s.value.as_str()).collect();
+    assert_eq!(values, ["Blackcurrant", "Blueberry"]);
+}
+
+#[tokio::test]
+async fn autocomplete_respects_limit() {
+    let app = spawn_app_with_auth().await;
+    let roaster_id = create_default_roaster(&app).await.id;
+    create_roast(
+        &app,
+        roaster_id,
+        "One",
+        "Kenya",
+        &["Cherry", "Cocoa", "Caramel"],
+    )
+    .await;
+
+    let suggestions: Vec<FieldSuggestion> = autocomplete(&app, "field=tasting_note&q=c&limit=2")
+        .await
+        .json()
+        .await
+        .expect("Failed to parse");
+
+    assert_eq!(suggestions.len(), 2);
+}
+
+#[tokio::test]
+async fn autocomplete_rejects_unknown_field() {
+    let app = spawn_app_with_auth().await;
+
+    let response = autocomplete(&app, "field=roaster&q=a").await;
+
+    assert_eq!(response.status(), 400);
+}
@@ -0,0 +1,200 @@
+use brewlog::application::services::BackfillReport;
+use brewlog::domain::entity_type::EntityType;
+use brewlog::domain::roasters::NewRoaster;
+use reqwest::Client;
+use serde_json::{Value, json};
+use tokio::time::{Duration, sleep};
+
+use crate::helpers::{
+    TestApp, create_default_bag, create_default_gear, create_default_roast, create_default_roaster,
+    create_roaster_with_payload, spawn_app_with_auth, spawn_app_with_timeline_sync,
+};
+
+/// A bag, grinder and brewer to log brews against, as the JSON for one brew
+/// minus its date.
+async fn brew_template(app: &TestApp) -> Value {
+    let roaster = create_default_roaster(app).await;
+    let roast = create_default_roast(app, roaster.id).await;
+    let bag = create_default_bag(app, roast.id).await;
+    let grinder = create_default_gear(app, "grinder", "Comandante", "C40 MK4").await;
+    let brewer = create_default_gear(app, "brewer", "Hario", "V60 02").await;
+    json!({
+        "bag_id": bag.id,
+        "roast_id": roast.id,
+        "coffee_weight": 15.0,
+        "grinder_id": grinder.id,
+        "grind_setting": 24.0,
+        "brewer_id": brewer.id,
+        "water_volume": 250,
+        "water_temp": 92.0,
+        "quick_notes": []
+    })
+}
+
+fn dated(template: &Value, created_at: &str) -> Value {
+    let mut record = template.clone();
+    record["created_at"] = json!(created_at);
+    record
+}
+
+async fn backfill(app: &TestApp, body: &Value) -> reqwest::Response {
+    Client::new()
+        .post(app.api_url("/backfill"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(body)
+        .send()
+        .await
+        .expect("failed to send backfill")
+}
+
+#[tokio::test]
+async fn backfill_requires_auth() {
+    let app = spawn_app_with_auth().await;
+
+    let response = Client::new()
+        .post(app.api_url("/backfill"))
+        .json(&json!({ "brews": [] }))
+        .send()
+        .await
+        .expect("failed to send backfill");
+
+    assert_eq!(response.status(), 401);
+}
+
+#[tokio::test]
+async fn backfill_logs_records_at_their_own_dates() {
+    let app = spawn_app_with_auth().await;
+    let brew = brew_template(&app).await;
+
+    let response = backfill(
+        &app,
+        &json!({
+            "brews": [
+                dated(&brew, "2024-02-10T08:00:00Z"),
+                dated(&brew, "2024-01-05T08:00:00Z"),
+            ],
+            "cups": [{
+                "roast_id": brew["roast_id"],
+                "created_at": "2024-02-11T15:00:00Z"
+            }]
+        }),
+    )
+    .await;
+    assert_eq!(response.status(), 200);
+
+    let report: BackfillReport = response.json().await.expect("failed to parse report");
+    assert_eq!(report.brews, 2);
+    assert_eq!(report.cups, 1);
+    assert_eq!(report.months, ["2024-01", "2024-02"]);
+
+    let events = app.timeline_repo.list_all().await.unwrap();
+    let mut backfilled: Vec<String> = events
+        .iter()
+        .filter(|event| matches!(event.entity_type, EntityType::Brew | EntityType::Cup))
+        .map(|event| event.occurred_at.format("%Y-%m-%d").to_string())
+        .collect();
+    backfilled.sort();
+    assert_eq!(backfilled, ["2024-01-05", "2024-02-10", "2024-02-11"]);
+}
+
+#[tokio::test]
+async fn backfill_rejects_the_batch_when_a_record_is_undated() {
+    let app = spawn_app_with_auth().await;
+    let brew = brew_template(&app).await;
+
+    let response = backfill(
+        &app,
+        &json!({ "brews": [dated(&brew, "2024-02-10T08:00:00Z"), brew] }),
+    )
+    .await;
+    assert_eq!(response.status(), 422);
+
+    let body: Value = response.json().await.expect("failed to parse error");
+    assert_eq!(body["fields"][0]["field"], "brews[1].created_at");
+    let brews: Value = Client::new()
+        .get(app.api_url("/brews"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(brews.as_array().map(Vec::len), Some(0), "nothing is logged");
+}
+
+#[tokio::test]
+async fn backfill_rejects_records_dated_in_the_future() {
+    let app = spawn_app_with_auth().await;
+    let brew = brew_template(&app).await;
+
+    let response = backfill(
+        &app,
+        &json!({ "brews": [dated(&brew, "2999-01-01T08:00:00Z")] }),
+    )
+    .await;
+
+    assert_eq!(response.status(), 422);
+}
+
+#[tokio::test]
+async fn backfill_names_the_record_whose_gear_is_missing() {
+    let app = spawn_app_with_auth().await;
+    let mut brew = dated(&brew_template(&app).await, "2024-02-10T08:00:00Z");
+    brew["brewer_id"] = json!(9999);
+
+    let response = backfill(&app, &json!({ "brews": [brew] })).await;
+
+    assert_eq!(response.status(), 400);
+    let body = response.text().await.unwrap();
+    assert!(
+        body.contains("brew 1"),
+        "error should name the record: {body}"
+    );
+}
+
+#[tokio::test]
+async fn backfill_rebuilds_only_the_months_it_touched() {
+    let app = spawn_app_with_timeline_sync().await;
+    let brew = brew_template(&app).await;
+    let roaster_on = |name: &str, created_at: &str| NewRoaster {
+        name: name.to_string(),
+        country: "UK".to_string(),
+        city: None,
+        homepage: None,
+        created_at: Some(created_at.parse().unwrap()),
+    };
+    let touched = create_roaster_with_payload(
+        &app,
+        roaster_on("February Roasters", "2024-02-01T09:00:00Z"),
+    )
+    .await;
+    let untouched =
+        create_roaster_with_payload(&app, roaster_on("June Roasters", "2023-06-01T09:00:00Z"))
+            .await;
+    for roaster in [&touched, &untouched] {
+        app.timeline_repo
+            .delete_by_entity(EntityType::Roaster, roaster.id.into_inner())
+            .await
+            .unwrap();
+    }
+
+    let response = backfill(
+        &app,
+        &json!({ "brews": [dated(&brew, "2024-02-10T08:00:00Z")] }),
+    )
+    .await;
+    assert_eq!(response.status(), 200);
+    sleep(Duration::from_millis(300)).await;
+
+    let roasters: Vec<i64> = app
+        .timeline_repo
+        .list_all()
+        .await
+        .unwrap()
+        .into_iter()
+        .filter(|event| event.entity_type == EntityType::Roaster)
+        .map(|event| event.entity_id)
+        .collect();
+    assert!(roasters.contains(&touched.id.into_inner()));
+    assert!(!roasters.contains(&untouched.id.into_inner()));
+}
@@ -8,22 +8,26 @@ use brewlog::domain::gear::{Gear, GearCategory, GearFilter, GearSortKey, NewGear
 use brewlog::domain::listing::{ListRequest, PageSize};
 use brewlog::domain::repositories::{
     BagRepository, BrewRepository, CafeRepository, GearRepository, RoastRepository,
-    RoasterRepository, TimelineEventRepository,
+    RoasterRepository, SettingsRepository, TimelineEventRepository,
 };
 use brewlog::domain::roasters::{NewRoaster, Roaster, RoasterSortKey};
 use brewlog::domain::roasts::{NewRoast, Roast, RoastSortKey};
 use brewlog::domain::timeline::TimelineEvent;
-use brewlog::infrastructure::backup::{BackupData, BackupService};
+use brewlog::infrastructure::backup::{
+    BackupData, BackupService, DryRunReport, RestoreMode, RestoreSummary,
+};
 use brewlog::infrastructure::database::{Database, DatabasePool};
+use brewlog::infrastructure::overview::TableCount;
 use brewlog::infrastructure::repositories::bags::SqlBagRepository;
 use brewlog::infrastructure::repositories::brews::SqlBrewRepository;
 use brewlog::infrastructure::repositories::cafes::SqlCafeRepository;
 use brewlog::infrastructure::repositories::gear::SqlGearRepository;
 use brewlog::infrastructure::repositories::roasters::SqlRoasterRepository;
 use brewlog::infrastructure::repositories::roasts::SqlRoastRepository;
+use brewlog::infrastructure::repositories::settings::SqlSettingsRepository;
 use brewlog::infrastructure::repositories::timeline_events::SqlTimelineEventRepository;
 
-use super::helpers::{create_default_roaster, spawn_app, spawn_app_with_auth};
+use super::helpers::{TestApp, create_default_roaster, spawn_app, spawn_app_with_auth};
 
 struct TestDb {
     pool: DatabasePool,
@@ -58,15 +62,26 @@ async fn create_test_db() -> TestDb {
     let timeline_repo: Arc<dyn TimelineEventRepository> =
         Arc::new(SqlTimelineEventRepository::new(pool.clone()));
 
-    let roaster_service =
-        RoasterService::new(Arc::clone(&roaster_repo), Arc::clone(&timeline_repo));
+    let settings_repo: Arc<dyn SettingsRepository> =
+        Arc::new(SqlSettingsRepository::new(pool.clone()));
+
+    let roaster_service = RoasterService::new(
+        Arc::clone(&roaster_repo),
+        Arc::clone(&timeline_repo),
+        Arc::clone(&settings_repo),
+    );
     let roast_service = RoastService::new(
         Arc::clone(&roast_repo),
         Arc::clone(&roaster_repo),
         Arc::clone(&timeline_repo),
+        Arc::clone(&settings_repo),
     );
     let gear_service = GearService::new(Arc::clone(&gear_repo), Arc::clone(&timeline_repo));
-    let cafe_service = CafeService::new(Arc::clone(&cafe_repo), Arc::clone(&timeline_repo));
+    let cafe_service = CafeService::new(
+        Arc::clone(&cafe_repo),
+        Arc::clone(&timeline_repo),
+        settings_repo,
+    );
 
     TestDb {
         pool: pool.clone(),
@@ -172,33 +187,41 @@ async fn populate_test_data(db: &TestDb) -> (Roaster, Roast, Bag, Gear, Gear, Ge
     // Create roaster (via service to generate timeline event)
     let roaster = db
         .roaster_service
-        .create(NewRoaster {
-            name: "Square Mile".to_string(),
-            country: "UK".to_string(),
-            city: Some("London".to_string()),
-            homepage: Some("https://shop.squaremilecoffee.com".to_string()),
-            created_at: None,
-        })
+        .create(
+            NewRoaster {
+                name: "Square Mile".to_string(),
+                country: "UK".to_string(),
+                city: Some("London".to_string()),
+                homepage: Some("https://shop.squaremilecoffee.com".to_string()),
+                created_at: None,
+            },
+            None,
+        )
         .await
         .expect("failed to create roaster");
 
     // Create roast (via service to generate timeline event)
     let roast = db
         .roast_service
-        .create(NewRoast {
-            roaster_id: roaster.id,
-            name: "Red Brick".to_string(),
-            origin: "Brazil".to_string(),
-            region: "Cerrado".to_string(),
-            producer: "Fazenda Passeio".to_string(),
-            tasting_notes: vec![
-                "Milk Chocolate".to_string(),
-                "Hazelnut".to_string(),
-                "Caramel".to_string(),
-            ],
-            process: "Natural".to_string(),
-            created_at: None,
-        })
+        .create(
+            NewRoast {
+                roaster_id: roaster.id,
+                name: "Red Brick".to_string(),
+                origin: "Brazil".to_string(),
+                region: "Cerrado".to_string(),
+                producer: "Fazenda Passeio".to_string(),
+                tasting_notes: vec![
+                    "Milk Chocolate".to_string(),
+                    "Hazelnut".to_string(),
+                    "Caramel".to_string(),
+                ],
+                process: "Natural".to_string(),
+                components: Vec::new(),
+                harvest_year: None,
+                created_at: None,
+            },
+            None,
+        )
         .await
         .expect("failed to create roast");
 
@@ -209,6 +232,10 @@ async fn populate_test_data(db: &TestDb) -> (Roaster, Roast, Bag, Gear, Gear, Ge
             roast_id: roast.id,
             roast_date: Some(chrono::NaiveDate::from_ymd_opt(2025, 1, 15).unwrap()),
             amount: 250.0,
+            price: None,
+            currency: None,
+            best_before: None,
+            batch_code: None,
             created_at: None,
         })
         .await
@@ -217,34 +244,64 @@ async fn populate_test_data(db: &TestDb) -> (Roaster, Roast, Bag, Gear, Gear, Ge
     // Create gear (via service to generate timeline events)
     let grinder = db
         .gear_service
-        .create(NewGear {
-            category: GearCategory::Grinder,
-            make: "Comandante".to_string(),
-            model: "C40 MK4".to_string(),
-            created_at: None,
-        })
+        .create(
+            NewGear {
+                category: GearCategory::Grinder,
+                make: "Comandante".to_string(),
+                model: "C40 MK4".to_string(),
+                water_temp: None,
+                brew_method: None,
+                uses_filter: None,
+                temp_offset: None,
+                water_hardness: None,
+                stock_count: None,
+                low_stock_threshold: None,
+                created_at: None,
+            },
+            None,
+        )
         .await
         .expect("failed to create grinder");
 
     let brewer = db
         .gear_service
-        .create(NewGear {
-            category: GearCategory::Brewer,
-            make: "Hario".to_string(),
-            model: "V60 02".to_string(),
-            created_at: None,
-        })
+        .create(
+            NewGear {
+                category: GearCategory::Brewer,
+                make: "Hario".to_string(),
+                model: "V60 02".to_string(),
+                water_temp: None,
+                brew_method: None,
+                uses_filter: None,
+                temp_offset: None,
+                water_hardness: None,
+                stock_count: None,
+                low_stock_threshold: None,
+                created_at: None,
+            },
+            None,
+        )
         .await
         .expect("failed to create brewer");
 
     let filter_paper = db
         .gear_service
-        .create(NewGear {
-            category: GearCategory::FilterPaper,
-            make: "Hario".to_string(),
-            model: "V60 Tabbed 02".to_string(),
-            created_at: None,
-        })
+        .create(
+            NewGear {
+                category: GearCategory::FilterPaper,
+                make: "Hario".to_string(),
+                model: "V60 Tabbed 02".to_string(),
+                water_temp: None,
+                brew_method: None,
+                uses_filter: None,
+                temp_offset: None,
+                water_hardness: None,
+                stock_count: None,
+                low_stock_threshold: None,
+                created_at: None,
+            },
+            None,
+        )
         .await
         .expect("failed to create filter paper");
 
@@ -260,6 +317,9 @@ async fn populate_test_data(db: &TestDb) -> (Roaster, Roast, Bag, Gear, Gear, Ge
             filter_paper_id: Some(filter_paper.id),
             water_volume: 250,
             water_temp: 93.5,
+            bypass_water_ml: None,
+            kettle_id: None,
+            actual_water_temp: None,
             quick_notes: Vec::new(),
             brew_time: None,
             created_at: None,
@@ -282,15 +342,18 @@ async fn populate_test_data(db: &TestDb) -> (Roaster, Roast, Bag, Gear, Gear, Ge
     // Create cafe (via service to generate timeline event)
     let cafe = db
         .cafe_service
-        .create(NewCafe {
-            name: "Prufrock".to_string(),
-            city: "London".to_string(),
-            country: "UK".to_string(),
-            latitude: 51.5246,
-            longitude: -0.1098,
-            website: Some("https://prufrockcoffee.com".to_string()),
-            created_at: None,
-        })
+        .create(
+            NewCafe {
+                name: "Prufrock".to_string(),
+                city: "London".to_string(),
+                country: "UK".to_string(),
+                latitude: 51.5246,
+                longitude: -0.1098,
+                website: Some("https://prufrockcoffee.com".to_string()),
+                created_at: None,
+            },
+            None,
+        )
         .await
         .expect("failed to create cafe");
 
@@ -498,11 +561,18 @@ async fn restore_to_non_empty_database_fails() {
         created_at: chrono::Utc::now(),
         roasters: vec![],
         gear: vec![],
+        quick_notes: vec![],
+        processes: vec![],
         roasts: vec![],
+        aliases: vec![],
         bags: vec![],
         brews: vec![],
         cafes: vec![],
         cups: vec![],
+        green_coffees: vec![],
+        roast_batches: vec![],
+        events: vec![],
+        trips: vec![],
         timeline_events: vec![],
         images: vec![],
     };
@@ -592,11 +662,18 @@ async fn backup_restore_requires_auth() {
         created_at: chrono::Utc::now(),
         roasters: vec![],
         gear: vec![],
+        quick_notes: vec![],
+        processes: vec![],
         roasts: vec![],
+        aliases: vec![],
         bags: vec![],
         brews: vec![],
         cafes: vec![],
         cups: vec![],
+        green_coffees: vec![],
+        roast_batches: vec![],
+        events: vec![],
+        trips: vec![],
         timeline_events: vec![],
         images: vec![],
     };
@@ -624,11 +701,18 @@ async fn backup_restore_non_empty_db_returns_conflict() {
         created_at: chrono::Utc::now(),
         roasters: vec![],
         gear: vec![],
+        quick_notes: vec![],
+        processes: vec![],
         roasts: vec![],
+        aliases: vec![],
         bags: vec![],
         brews: vec![],
         cafes: vec![],
         cups: vec![],
+        green_coffees: vec![],
+        roast_batches: vec![],
+        events: vec![],
+        trips: vec![],
         timeline_events: vec![],
         images: vec![],
     };
@@ -689,6 +773,168 @@ async fn backup_round_trip_via_api() {
     assert_eq!(roasters[0].name, "Test Roasters");
 }
 
+// --- Streaming restore tests ---
+
+async fn export_ndjson(app: &TestApp) -> String {
+    let response = reqwest::Client::new()
+        .get(app.api_url("/backup?format=ndjson"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .send()
+        .await
+        .expect("failed to export backup");
+
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    assert_eq!(response.headers()["content-type"], "application/x-ndjson");
+    response.text().await.expect("failed to read backup body")
+}
+
+async fn post_stream_restore(app: &TestApp, body: String, resume: bool) -> reqwest::Response {
+    let path = if resume {
+        "/backup/restore/stream?resume=true"
+    } else {
+        "/backup/restore/stream"
+    };
+
+    reqwest::Client::new()
+        .post(app.api_url(path))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .header("content-type", "application/x-ndjson")
+        .body(body)
+        .send()
+        .await
+        .expect("failed to send stream restore")
+}
+
+#[tokio::test]
+async fn streaming_restore_matches_full_backup() {
+    let source = create_test_db().await;
+    let (roaster, ..) = populate_test_data(&source).await;
+    insert_test_image(&source.pool, "roaster", i64::from(roaster.id)).await;
+
+    let backup = source
+        .backup_service
+        .export()
+        .await
+        .expect("failed to export");
+    let expected_records = backup.roasters.len()
+        + backup.gear.len()
+        + backup.roasts.len()
+        + backup.bags.len()
+        + backup.brews.len()
+        + backup.cafes.len()
+        + backup.cups.len()
+        + backup.timeline_events.len()
+        + backup.images.len();
+
+    let target = create_test_db().await;
+    let mut restore = target
+        .backup_service
+        .begin_streaming_restore(RestoreMode::Fresh)
+        .await
+        .expect("failed to begin streaming restore");
+    for record in backup.into_records() {
+        restore
+            .apply(&record)
+            .await
+            .expect("failed to apply record");
+    }
+    let summary = restore.finish().await.expect("failed to finish restore");
+
+    assert_eq!(summary.restored, expected_records as u64);
+    assert_eq!(summary.skipped, 0);
+    assert_eq!(list_all_gear(target.gear_repo.as_ref()).await.len(), 3);
+    assert_eq!(list_all_brews(target.brew_repo.as_ref()).await.len(), 1);
+    assert_eq!(count_images(&target.pool).await, 1);
+}
+
+#[tokio::test]
+async fn stream_restore_requires_auth() {
+    let app = spawn_app().await;
+
+    let response = reqwest::Client::new()
+        .post(app.api_url("/backup/restore/stream"))
+        .header("content-type", "application/x-ndjson")
+        .body("")
+        .send()
+        .await
+        .expect("failed to send request");
+
+    assert_eq!(response.status(), reqwest::StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn stream_restore_round_trip_via_api() {
+    let source = spawn_app_with_auth().await;
+    create_default_roaster(&source).await;
+    let body = export_ndjson(&source).await;
+    assert!(body.starts_with(r#"{"type":"header""#));
+
+    let target = spawn_app_with_auth().await;
+    let response = post_stream_restore(&target, body, false).await;
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+
+    let summary: RestoreSummary = response.json().await.expect("failed to parse summary");
+    assert!(summary.restored >= 1);
+    assert_eq!(summary.skipped, 0);
+
+    let roasters: Vec<Roaster> = reqwest::Client::new()
+        .get(target.api_url("/roasters"))
+        .send()
+        .await
+        .expect("failed to list roasters")
+        .json()
+        .await
+        .expect("failed to parse roasters");
+    assert_eq!(roasters.len(), 1);
+    assert_eq!(roasters[0].name, "Test Roasters");
+}
+
+#[tokio::test]
+async fn stream_restore_non_empty_db_returns_conflict() {
+    let app = spawn_app_with_auth().await;
+    create_default_roaster(&app).await;
+    let body = export_ndjson(&app).await;
+
+    let response = post_stream_restore(&app, body, false).await;
+
+    assert_eq!(response.status(), reqwest::StatusCode::CONFLICT);
+    let body: brewlog::application::errors::ErrorResponse =
+        response.json().await.expect("failed to parse error");
+    assert_eq!(body.code, brewlog::domain::ErrorCode::DatabaseNotEmpty);
+}
+
+#[tokio::test]
+async fn stream_restore_resume_skips_existing_records() {
+    let source = spawn_app_with_auth().await;
+    create_default_roaster(&source).await;
+    let body = export_ndjson(&source).await;
+
+    let target = spawn_app_with_auth().await;
+    let first: RestoreSummary = post_stream_restore(&target, body.clone(), false)
+        .await
+        .json()
+        .await
+        .expect("failed to parse summary");
+
+    let response = post_stream_restore(&target, body, true).await;
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+
+    let second: RestoreSummary = response.json().await.expect("failed to parse summary");
+    assert_eq!(second.restored, 0);
+    assert_eq!(second.skipped, first.restored);
+}
+
+#[tokio::test]
+async fn stream_restore_rejects_malformed_line() {
+    let app = spawn_app_with_auth().await;
+
+    let response = post_stream_restore(&app, "{\"type\":\"unknown\"}\n".to_string(), false).await;
+
+    assert_eq!(response.status(), reqwest::StatusCode::BAD_REQUEST);
+    let body = response.text().await.expect("failed to read body");
+    assert!(body.contains("line 1"));
+}
+
 // --- Reset tests ---
 
 #[tokio::test]
@@ -748,6 +994,37 @@ async fn reset_clears_all_data() {
     assert_eq!(count_images(&db.pool).await, 0);
 }
 
+#[tokio::test]
+async fn reset_clears_cached_listings() {
+    let app = spawn_app_with_auth().await;
+    create_default_roaster(&app).await;
+    let client = reqwest::Client::new();
+    // Warm the roaster listing cache so a stale row would show.
+    client
+        .get(app.api_url("/roasters"))
+        .send()
+        .await
+        .expect("failed to send request");
+
+    let response = client
+        .post(app.api_url("/backup/reset"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .send()
+        .await
+        .expect("failed to send request");
+    assert_eq!(response.status(), reqwest::StatusCode::NO_CONTENT);
+
+    let roasters: Vec<Roaster> = client
+        .get(app.api_url("/roasters"))
+        .send()
+        .await
+        .expect("failed to send request")
+        .json()
+        .await
+        .expect("failed to parse response");
+    assert!(roasters.is_empty());
+}
+
 #[tokio::test]
 async fn reset_then_restore_succeeds() {
     let db = create_test_db().await;
@@ -779,3 +1056,105 @@ async fn reset_then_restore_succeeds() {
     assert_eq!(list_all_brews(db.brew_repo.as_ref()).await.len(), 1);
     assert_eq!(list_all_cafes(db.cafe_repo.as_ref()).await.len(), 1);
 }
+
+// --- Dry run tests ---
+
+fn rows(counts: &[TableCount], table: &str) -> i64 {
+    counts
+        .iter()
+        .find(|count| count.table == table)
+        .map_or(0, |count| count.rows)
+}
+
+#[tokio::test]
+async fn reset_preview_counts_rows_without_deleting_them() {
+    let db = create_test_db().await;
+    populate_test_data(&db).await;
+
+    let report = db
+        .backup_service
+        .preview_reset()
+        .await
+        .expect("failed to preview reset");
+
+    assert_eq!(rows(&report.deleted, "roasters"), 1);
+    assert_eq!(rows(&report.deleted, "gear"), 3);
+    assert_eq!(rows(&report.deleted, "brews"), 1);
+    assert!(report.written.is_empty());
+    assert_eq!(list_all_roasters(db.roaster_repo.as_ref()).await.len(), 1);
+    assert_eq!(list_all_brews(db.brew_repo.as_ref()).await.len(), 1);
+}
+
+#[tokio::test]
+async fn restore_preview_counts_rows_without_writing_them() {
+    let db = create_test_db().await;
+    populate_test_data(&db).await;
+    let backup = db.backup_service.export().await.expect("failed to export");
+    db.backup_service
+        .reset()
+        .await
+        .expect("failed to reset database");
+
+    let report = db
+        .backup_service
+        .preview_restore(backup)
+        .await
+        .expect("failed to preview restore");
+
+    assert_eq!(rows(&report.written, "roasters"), 1);
+    assert_eq!(rows(&report.written, "gear"), 3);
+    assert_eq!(rows(&report.written, "brews"), 1);
+    assert!(list_all_roasters(db.roaster_repo.as_ref()).await.is_empty());
+    assert!(list_all_gear(db.gear_repo.as_ref()).await.is_empty());
+}
+
+#[tokio::test]
+async fn reset_dry_run_via_api_leaves_data_in_place() {
+    let app = spawn_app_with_auth().await;
+    create_default_roaster(&app).await;
+
+    let response = reqwest::Client::new()
+        .post(app.api_url("/backup/reset?dry_run=true"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .send()
+        .await
+        .expect("failed to send request");
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    let report: DryRunReport = response.json().await.expect("failed to parse report");
+
+    assert_eq!(rows(&report.deleted, "roasters"), 1);
+    assert_eq!(list_all_roasters(app.roaster_repo.as_ref()).await.len(), 1);
+}
+
+#[tokio::test]
+async fn stream_restore_dry_run_writes_nothing() {
+    let source = spawn_app_with_auth().await;
+    create_default_roaster(&source).await;
+    let body = export_ndjson(&source).await;
+
+    let target = spawn_app_with_auth().await;
+    let response = reqwest::Client::new()
+        .post(target.api_url("/backup/restore/stream?dry_run=true"))
+        .bearer_auth(target.auth_token.as_ref().unwrap())
+        .header("content-type", "application/x-ndjson")
+        .body(body.clone())
+        .send()
+        .await
+        .expect("failed to send stream restore");
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    let report: DryRunReport = response.json().await.expect("failed to parse report");
+    assert_eq!(rows(&report.written, "roasters"), 1);
+    assert!(
+        list_all_roasters(target.roaster_repo.as_ref())
+            .await
+            .is_empty()
+    );
+
+    // The dry run left the database empty, so the real restore still works.
+    let response = post_stream_restore(&target, body, false).await;
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    assert_eq!(
+        list_all_roasters(target.roaster_repo.as_ref()).await.len(),
+        1
+    );
+}
@@ -0,0 +1,151 @@
+use brewlog::domain::bags::{BagWithRoast, UpdateBag};
+use brewlog::domain::brews::{Brew, NewBrew};
+use reqwest::Client;
+use serde_json::{Value, json};
+
+use super::helpers::{
+    TestApp, create_default_brew, create_session, post_form, spawn_app_with_auth,
+};
+
+/// Brew again from the same bag and kit as `brew`, returning the raw JSON.
+async fn brew_again(app: &TestApp, brew: &Brew, coffee_weight: f64) -> Value {
+    let response = Client::new()
+        .post(app.api_url("/brews"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&NewBrew {
+            bag_id: brew.bag_id,
+            coffee_weight,
+            grinder_id: brew.grinder_id,
+            grind_setting: 24.0,
+            brewer_id: brew.brewer_id,
+            filter_paper_id: None,
+            water_volume: 250,
+            water_temp: 92.0,
+            bypass_water_ml: None,
+            kettle_id: None,
+            actual_water_temp: None,
+            quick_notes: Vec::new(),
+            brew_time: None,
+            created_at: None,
+        })
+        .send()
+        .await
+        .expect("failed to execute request");
+    assert_eq!(response.status(), 201);
+    response.json().await.unwrap()
+}
+
+async fn brew_page(app: &TestApp, path: &str, session: Option<&str>) -> String {
+    let mut request = Client::new().get(app.page_url(path));
+    if let Some(session) = session {
+        request = request.header("Cookie", format!("brewlog_session={session}"));
+    }
+    request
+        .send()
+        .await
+        .expect("failed to fetch page")
+        .text()
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn brews_that_leave_only_dust_suggest_finishing_the_bag() {
+    let app = spawn_app_with_auth().await;
+    // The default bag holds 250g and the default brew uses 15g.
+    let brew = create_default_brew(&app).await;
+
+    let body = brew_again(&app, &brew, 100.0).await;
+    assert!(body.get("bag_finish_suggestion").is_none());
+    assert_eq!(body["bag_id"], json!(brew.bag_id));
+
+    let body = brew_again(&app, &brew, 130.0).await;
+    assert_eq!(
+        body["bag_finish_suggestion"],
+        json!({ "bag_id": brew.bag_id, "remaining": 5.0 })
+    );
+}
+
+#[tokio::test]
+async fn a_zero_dust_threshold_turns_the_suggestion_off() {
+    let app = spawn_app_with_auth().await;
+    Client::new()
+        .put(app.api_url("/settings"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&json!({ "dust_threshold": 0 }))
+        .send()
+        .await
+        .expect("failed to execute request");
+    let brew = create_default_brew(&app).await;
+
+    let body = brew_again(&app, &brew, 235.0).await;
+    assert!(body.get("bag_finish_suggestion").is_none());
+}
+
+#[tokio::test]
+async fn the_brew_form_redirects_to_a_finish_prompt() {
+    let app = spawn_app_with_auth().await;
+    let brew = create_default_brew(&app).await;
+
+    let form_fields = vec![
+        ("bag_id", brew.bag_id.into_inner().to_string()),
+        ("coffee_weight", "230.0".into()),
+        ("grinder_id", brew.grinder_id.into_inner().to_string()),
+        ("grind_setting", "24.0".into()),
+        ("brewer_id", brew.brewer_id.into_inner().to_string()),
+        ("water_volume", "250".into()),
+        ("water_temp", "96.0".into()),
+    ];
+    let response = post_form(&app, "/brews", &form_fields).await;
+    assert_eq!(response.status(), 303);
+    let location = response
+        .headers()
+        .get("location")
+        .and_then(|v| v.to_str().ok())
+        .expect("missing Location header")
+        .to_string();
+    assert!(location.ends_with("?finish_bag=true"), "got {location}");
+
+    let session = create_session(&app).await;
+    let page = brew_page(&app, &location, Some(&session)).await;
+    assert!(page.contains("id=\"finish-bag-dialog\""));
+    assert!(page.contains("Dump the remaining 5g"));
+
+    // Visitors never see the prompt.
+    let page = brew_page(&app, &location, None).await;
+    assert!(!page.contains("finish-bag-dialog"));
+}
+
+#[tokio::test]
+async fn the_prompt_disappears_once_the_bag_is_finished() {
+    let app = spawn_app_with_auth().await;
+    let brew = create_default_brew(&app).await;
+    let body = brew_again(&app, &brew, 230.0).await;
+    let path = format!("/brews/{}?finish_bag=true", body["id"]);
+    let session = create_session(&app).await;
+    assert!(
+        brew_page(&app, &path, Some(&session))
+            .await
+            .contains("finish-bag-dialog")
+    );
+
+    let response = Client::new()
+        .put(app.api_url(&format!("/bags/{}", brew.bag_id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&UpdateBag {
+            closed: Some(true),
+            remaining: Some(0.0),
+            ..Default::default()
+        })
+        .send()
+        .await
+        .expect("failed to execute request");
+    let bag: BagWithRoast = response.json().await.unwrap();
+    assert!(bag.bag.closed);
+
+    assert!(
+        !brew_page(&app, &path, Some(&session))
+            .await
+            .contains("finish-bag-dialog")
+    );
+}
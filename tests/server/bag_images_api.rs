@@ -0,0 +1,246 @@
+use brewlog::domain::bags::BagWithRoast;
+use brewlog::domain::ids::RoastId;
+use reqwest::Client;
+
+use crate::helpers::{
+    TestApp, create_default_bag, create_default_roast, create_default_roaster, create_session,
+    spawn_app_with_auth,
+};
+
+/// A solid PNG of the given colour as a base64 data URL.
+fn png_data_url(rgb: [u8; 3]) -> String {
+    use base64::Engine;
+    use image::{ImageBuffer, Rgb};
+
+    let img = ImageBuffer::from_pixel(2, 2, Rgb(rgb));
+    let mut buf = Vec::new();
+    let encoder = image::codecs::png::PngEncoder::new(&mut buf);
+    image::ImageEncoder::write_image(encoder, img.as_raw(), 2, 2, image::ColorType::Rgb8.into())
+        .expect("failed to encode test PNG");
+    format!(
+        "data:image/png;base64,{}",
+        base64::engine::general_purpose::STANDARD.encode(&buf)
+    )
+}
+
+async fn upload_image(app: &TestApp, entity_type: &str, id: i64, rgb: [u8; 3]) {
+    let response = Client::new()
+        .put(app.api_url(&format!("/{entity_type}/{id}/image")))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&serde_json::json!({ "image": png_data_url(rgb) }))
+        .send()
+        .await
+        .expect("failed to upload image");
+    assert_eq!(response.status(), 204);
+}
+
+async fn image(app: &TestApp, entity_type: &str, id: i64) -> Option<Vec<u8>> {
+    let response = Client::new()
+        .get(app.api_url(&format!("/{entity_type}/{id}/image")))
+        .send()
+        .await
+        .expect("failed to fetch image");
+    if response.status() == 404 {
+        return None;
+    }
+    assert_eq!(response.status(), 200);
+    Some(response.bytes().await.unwrap().to_vec())
+}
+
+async fn bags_of(app: &TestApp, roast_id: RoastId) -> Vec<BagWithRoast> {
+    Client::new()
+        .get(app.api_url(&format!("/bags?roast_id={roast_id}")))
+        .send()
+        .await
+        .expect("failed to list bags")
+        .json()
+        .await
+        .expect("failed to parse bags")
+}
+
+async fn bag_page(app: &TestApp, bag_id: i64, session: Option<&str>) -> String {
+    let mut request = Client::new().get(app.page_url(&format!("/bags/{bag_id}")));
+    if let Some(session) = session {
+        request = request.header("Cookie", format!("brewlog_session={session}"));
+    }
+    request
+        .send()
+        .await
+        .expect("failed to fetch bag page")
+        .text()
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn bag_page_shows_the_bag_photo_rather_than_the_roast_artwork() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+    let roast = create_default_roast(&app, roaster.id).await;
+    let bag = create_default_bag(&app, roast.id).await;
+    let bag_id = bag.id.into_inner();
+    upload_image(&app, "roast", roast.id.into_inner(), [200, 0, 0]).await;
+    upload_image(&app, "bag", bag_id, [0, 0, 200]).await;
+
+    let body = bag_page(&app, bag_id, None).await;
+
+    assert!(body.contains(&format!("/api/v1/bag/{bag_id}/image")));
+    assert!(!body.contains(&format!("/api/v1/roast/{}/image", roast.id)));
+}
+
+#[tokio::test]
+async fn bag_page_falls_back_to_the_roast_artwork() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+    let roast = create_default_roast(&app, roaster.id).await;
+    let bag = create_default_bag(&app, roast.id).await;
+    upload_image(&app, "roast", roast.id.into_inner(), [200, 0, 0]).await;
+
+    let body = bag_page(&app, bag.id.into_inner(), None).await;
+
+    assert!(body.contains(&format!("/api/v1/roast/{}/image", roast.id)));
+}
+
+#[tokio::test]
+async fn bag_page_offers_a_photo_upload_when_signed_in() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+    let roast = create_default_roast(&app, roaster.id).await;
+    let bag = create_default_bag(&app, roast.id).await;
+    let session = create_session(&app).await;
+
+    let body = bag_page(&app, bag.id.into_inner(), Some(&session)).await;
+
+    assert!(body.contains("entity-type=\"bag\""));
+}
+
+#[tokio::test]
+async fn bag_edit_page_offers_a_bag_photo() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+    let roast = create_default_roast(&app, roaster.id).await;
+    let bag = create_default_bag(&app, roast.id).await;
+    let session = create_session(&app).await;
+
+    let body = Client::new()
+        .get(app.page_url(&format!("/bags/{}/edit", bag.id)))
+        .header("Cookie", format!("brewlog_session={session}"))
+        .send()
+        .await
+        .expect("failed to fetch edit page")
+        .text()
+        .await
+        .unwrap();
+
+    assert!(body.contains("id=\"edit-bag-image\""));
+}
+
+#[tokio::test]
+async fn creating_a_bag_with_an_image_saves_it() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+    let roast = create_default_roast(&app, roaster.id).await;
+
+    let response = Client::new()
+        .post(app.api_url("/bags"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&serde_json::json!({
+            "roast_id": roast.id,
+            "amount": 250.0,
+            "image": png_data_url([0, 0, 200]),
+        }))
+        .send()
+        .await
+        .expect("failed to create bag");
+    assert_eq!(response.status(), 201);
+
+    let bag: BagWithRoast = response.json().await.unwrap();
+    assert!(image(&app, "bag", bag.bag.id.into_inner()).await.is_some());
+    assert!(image(&app, "roast", roast.id.into_inner()).await.is_none());
+}
+
+#[tokio::test]
+async fn deleting_a_bag_deletes_its_photo() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+    let roast = create_default_roast(&app, roaster.id).await;
+    let bag = create_default_bag(&app, roast.id).await;
+    let bag_id = bag.id.into_inner();
+    upload_image(&app, "bag", bag_id, [0, 0, 200]).await;
+
+    let response = Client::new()
+        .delete(app.api_url(&format!("/bags/{bag_id}")))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .send()
+        .await
+        .expect("failed to delete bag");
+    assert_eq!(response.status(), 204);
+
+    assert!(image(&app, "bag", bag_id).await.is_none());
+}
+
+#[tokio::test]
+async fn scan_saves_the_photo_to_the_bag_it_opens() {
+    let app = spawn_app_with_auth().await;
+
+    let response = Client::new()
+        .post(app.api_url("/scan"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&serde_json::json!({
+            "roaster_name": "Photo Roasters",
+            "roaster_country": "UK",
+            "roast_name": "Photo Roast",
+            "origin": "Ethiopia",
+            "region": "Guji",
+            "producer": "Test Farm",
+            "process": "Natural",
+            "tasting_notes": "Strawberry",
+            "open_bag": "true",
+            "scan_image": png_data_url([0, 0, 200]),
+        }))
+        .send()
+        .await
+        .expect("failed to submit scan");
+    assert_eq!(response.status(), 201);
+    let result: serde_json::Value = response.json().await.unwrap();
+    let roast_id = RoastId::new(result["roast_id"].as_i64().unwrap());
+
+    let bags = bags_of(&app, roast_id).await;
+    assert_eq!(bags.len(), 1);
+    assert!(
+        image(&app, "bag", bags[0].bag.id.into_inner())
+            .await
+            .is_some()
+    );
+    // A new roast has no artwork yet, so it takes the photo too.
+    assert!(image(&app, "roast", roast_id.into_inner()).await.is_some());
+}
+
+#[tokio::test]
+async fn scan_of_an_existing_roast_keeps_its_artwork() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+    let roast = create_default_roast(&app, roaster.id).await;
+    upload_image(&app, "roast", roast.id.into_inner(), [200, 0, 0]).await;
+    let artwork = image(&app, "roast", roast.id.into_inner()).await;
+
+    let response = Client::new()
+        .post(app.api_url("/scan"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&serde_json::json!({
+            "matched_roast_id": roast.id.into_inner().to_string(),
+            "open_bag": "true",
+            "scan_image": png_data_url([0, 0, 200]),
+        }))
+        .send()
+        .await
+        .expect("failed to submit scan");
+    assert_eq!(response.status(), 201);
+
+    let bags = bags_of(&app, roast.id).await;
+    assert_eq!(bags.len(), 1);
+    let bag_photo = image(&app, "bag", bags[0].bag.id.into_inner()).await;
+    assert!(bag_photo.is_some());
+    assert_ne!(bag_photo, artwork);
+    assert_eq!(image(&app, "roast", roast.id.into_inner()).await, artwork);
+}
@@ -0,0 +1,378 @@
+use brewlog::application::errors::ErrorResponse;
+use brewlog::domain::ErrorCode;
+use brewlog::domain::bag_ledger::{BagLedgerEntry, BagLedgerKind, BagTransfer};
+use brewlog::domain::bags::{Bag, BagWithRoast};
+use brewlog::domain::brews::{Brew, NewBrew};
+use futures_util::future::join_all;
+use reqwest::Client;
+use serde_json::json;
+
+use super::helpers::{
+    TestApp, create_default_bag, create_default_gear, create_default_roast, create_default_roaster,
+    create_entity, create_session, spawn_app_with_auth,
+};
+
+async fn default_bag(app: &TestApp) -> Bag {
+    let roaster = create_default_roaster(app).await;
+    let roast = create_default_roast(app, roaster.id).await;
+    create_default_bag(app, roast.id).await
+}
+
+async fn error_code(response: reqwest::Response) -> ErrorCode {
+    response
+        .json::<ErrorResponse>()
+        .await
+        .expect("failed to parse error")
+        .code
+}
+
+async fn finish_bag(app: &TestApp, bag: &Bag) {
+    let response = Client::new()
+        .put(app.api_url(&format!("/bags/{}", bag.id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&json!({ "closed": true, "remaining": 0.0 }))
+        .send()
+        .await
+        .expect("failed to execute request");
+    assert!(response.status().is_success());
+}
+
+async fn reopen(app: &TestApp, bag: &Bag) -> reqwest::Response {
+    Client::new()
+        .post(app.api_url(&format!("/bags/{}/reopen", bag.id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .send()
+        .await
+        .expect("failed to execute request")
+}
+
+async fn correct(app: &TestApp, bag: &Bag, payload: serde_json::Value) -> reqwest::Response {
+    Client::new()
+        .post(app.api_url(&format!("/bags/{}/adjustments", bag.id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&payload)
+        .send()
+        .await
+        .expect("failed to execute request")
+}
+
+async fn ledger(app: &TestApp, bag: &Bag) -> Vec<BagLedgerEntry> {
+    Client::new()
+        .get(app.api_url(&format!("/bags/{}/ledger", bag.id)))
+        .send()
+        .await
+        .expect("failed to execute request")
+        .json()
+        .await
+        .unwrap()
+}
+
+async fn remaining(app: &TestApp, bag: &Bag) -> f64 {
+    let bag: BagWithRoast = Client::new()
+        .get(app.api_url(&format!("/bags/{}", bag.id)))
+        .send()
+        .await
+        .expect("failed to execute request")
+        .json()
+        .await
+        .unwrap();
+    bag.bag.remaining
+}
+
+async fn transfer(app: &TestApp, from: &Bag, to: &Bag, amount: f64) -> reqwest::Response {
+    Client::new()
+        .post(app.api_url(&format!("/bags/{}/transfers", from.id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&json!({ "to_bag_id": to.id, "amount": amount }))
+        .send()
+        .await
+        .expect("failed to execute request")
+}
+
+async fn bag_page(app: &TestApp, bag: &Bag, session: Option<&str>) -> String {
+    let mut request = Client::new().get(app.page_url(&format!("/bags/{}", bag.id)));
+    if let Some(session) = session {
+        request = request.header("Cookie", format!("brewlog_session={session}"));
+    }
+    request
+        .send()
+        .await
+        .expect("failed to fetch page")
+        .text()
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn finished_bags_can_be_reopened() {
+    let app = spawn_app_with_auth().await;
+    let bag = default_bag(&app).await;
+    finish_bag(&app, &bag).await;
+
+    let response = Client::new()
+        .post(app.api_url(&format!("/bags/{}/reopen", bag.id)))
+        .send()
+        .await
+        .expect("failed to execute request");
+    assert_eq!(response.status(), 401);
+
+    let response = reopen(&app, &bag).await;
+    assert_eq!(response.status(), 200);
+    let reopened: BagWithRoast = response.json().await.unwrap();
+    assert!(!reopened.bag.closed);
+    assert_eq!(reopened.bag.finished_at, None);
+
+    let response = reopen(&app, &bag).await;
+    assert_eq!(response.status(), 409);
+}
+
+#[tokio::test]
+async fn corrections_are_appended_to_the_ledger() {
+    let app = spawn_app_with_auth().await;
+    let bag = default_bag(&app).await;
+
+    let response = correct(
+        &app,
+        &bag,
+        json!({ "remaining": 220.0, "reason": "Weighed the bag" }),
+    )
+    .await;
+    assert_eq!(response.status(), 201);
+    let entry: BagLedgerEntry = response.json().await.unwrap();
+    assert_eq!(entry.kind, BagLedgerKind::Adjustment);
+    assert_eq!(entry.delta, -30.0);
+    assert_eq!(entry.balance, 220.0);
+    assert_eq!(entry.reason.as_deref(), Some("Weighed the bag"));
+
+    correct(
+        &app,
+        &bag,
+        json!({ "remaining": 225.0, "reason": "Scale was off" }),
+    )
+    .await;
+    assert_eq!(remaining(&app, &bag).await, 225.0);
+
+    let entries = ledger(&app, &bag).await;
+    let rows: Vec<_> = entries
+        .iter()
+        .map(|e| (e.kind, e.delta, e.balance))
+        .collect();
+    assert_eq!(
+        rows,
+        [
+            (BagLedgerKind::Adjustment, 5.0, 225.0),
+            (BagLedgerKind::Adjustment, -30.0, 220.0),
+            (BagLedgerKind::Initial, 250.0, 250.0),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn editing_the_remaining_weight_records_a_correction() {
+    let app = spawn_app_with_auth().await;
+    let bag = default_bag(&app).await;
+
+    let response = Client::new()
+        .put(app.api_url(&format!("/bags/{}", bag.id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&json!({ "remaining": 180.0 }))
+        .send()
+        .await
+        .expect("failed to execute request");
+    assert!(response.status().is_success());
+
+    let entries = ledger(&app, &bag).await;
+    assert_eq!(entries[0].kind, BagLedgerKind::Adjustment);
+    assert_eq!(entries[0].delta, -70.0);
+    assert_eq!(
+        entries[0].reason.as_deref(),
+        Some("Remaining weight edited")
+    );
+    assert_eq!(remaining(&app, &bag).await, 180.0);
+}
+
+#[tokio::test]
+async fn transfers_move_coffee_between_bags() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+    let roast = create_default_roast(&app, roaster.id).await;
+    let from = create_default_bag(&app, roast.id).await;
+    let to = create_default_bag(&app, roast.id).await;
+    correct(
+        &app,
+        &to,
+        json!({ "remaining": 100.0, "reason": "Used some" }),
+    )
+    .await;
+
+    let response = Client::new()
+        .post(app.api_url(&format!("/bags/{}/transfers", from.id)))
+        .json(&json!({ "to_bag_id": to.id, "amount": 40.0 }))
+        .send()
+        .await
+        .expect("failed to execute request");
+    assert_eq!(response.status(), 401);
+
+    let response = transfer(&app, &from, &to, 40.0).await;
+    assert_eq!(response.status(), 201);
+    let moved: BagTransfer = response.json().await.unwrap();
+    assert_eq!(moved.from.delta, -40.0);
+    assert_eq!(moved.from.counterpart_bag_id, Some(to.id));
+    assert_eq!(moved.to.delta, 40.0);
+    assert_eq!(moved.to.counterpart_bag_id, Some(from.id));
+
+    assert_eq!(remaining(&app, &from).await, 210.0);
+    assert_eq!(remaining(&app, &to).await, 140.0);
+    assert_eq!(ledger(&app, &to).await[0].kind, BagLedgerKind::Transfer);
+}
+
+#[tokio::test]
+async fn transfers_cannot_overdraw_or_overfill_a_bag() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+    let roast = create_default_roast(&app, roaster.id).await;
+    let from = create_default_bag(&app, roast.id).await;
+    let to = create_default_bag(&app, roast.id).await;
+    correct(
+        &app,
+        &from,
+        json!({ "remaining": 30.0, "reason": "Used some" }),
+    )
+    .await;
+    correct(
+        &app,
+        &to,
+        json!({ "remaining": 230.0, "reason": "Used some" }),
+    )
+    .await;
+
+    // More than the bag has left.
+    let response = transfer(&app, &from, &to, 35.0).await;
+    assert_eq!(response.status(), 400);
+    assert_eq!(
+        error_code(response).await,
+        ErrorCode::BagInsufficientRemaining
+    );
+    // More than the other bag can hold.
+    let response = transfer(&app, &from, &to, 25.0).await;
+    assert_eq!(response.status(), 400);
+    assert_eq!(error_code(response).await, ErrorCode::BagNoRoom);
+    assert_eq!(transfer(&app, &from, &from, 10.0).await.status(), 400);
+
+    let response = Client::new()
+        .post(app.api_url(&format!("/bags/{}/transfers", from.id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&json!({ "to_bag_id": 999_999, "amount": 10.0 }))
+        .send()
+        .await
+        .expect("failed to execute request");
+    assert_eq!(response.status(), 400);
+
+    assert_eq!(remaining(&app, &from).await, 30.0);
+    assert_eq!(remaining(&app, &to).await, 230.0);
+}
+
+#[tokio::test]
+async fn concurrent_brews_all_land_in_the_ledger() {
+    let app = spawn_app_with_auth().await;
+    let bag = default_bag(&app).await;
+    let grinder = create_default_gear(&app, "grinder", "Comandante", "C40 MK4").await;
+    let brewer = create_default_gear(&app, "brewer", "Hario", "V60 02").await;
+
+    let brew = NewBrew {
+        bag_id: bag.id,
+        coffee_weight: 30.0,
+        grinder_id: grinder.id,
+        grind_setting: 24.0,
+        brewer_id: brewer.id,
+        filter_paper_id: None,
+        water_volume: 500,
+        water_temp: 92.0,
+        bypass_water_ml: None,
+        kettle_id: None,
+        actual_water_temp: None,
+        quick_notes: Vec::new(),
+        brew_time: None,
+        created_at: None,
+    };
+    let brews = (0..6).map(|_| create_entity::<_, Brew>(&app, "/brews", &brew));
+    join_all(brews).await;
+
+    let entries = ledger(&app, &bag).await;
+    let brew_entries = entries
+        .iter()
+        .filter(|e| e.kind == BagLedgerKind::Brew)
+        .count();
+    assert_eq!(brew_entries, 6);
+    assert_eq!(entries.iter().map(|e| e.delta).sum::<f64>(), 70.0);
+    assert_eq!(entries[0].balance, 70.0);
+    assert_eq!(remaining(&app, &bag).await, 70.0);
+}
+
+#[tokio::test]
+async fn corrections_need_a_reason_a_valid_weight_and_an_open_bag() {
+    let app = spawn_app_with_auth().await;
+    let bag = default_bag(&app).await;
+
+    let response = Client::new()
+        .post(app.api_url(&format!("/bags/{}/adjustments", bag.id)))
+        .json(&json!({ "remaining": 100.0, "reason": "Weighed" }))
+        .send()
+        .await
+        .expect("failed to execute request");
+    assert_eq!(response.status(), 401);
+
+    for payload in [
+        json!({ "remaining": 100.0, "reason": "  " }),
+        json!({ "remaining": 300.0, "reason": "Weighed" }),
+        json!({ "remaining": 250.0, "reason": "Weighed" }),
+    ] {
+        assert_eq!(correct(&app, &bag, payload).await.status(), 400);
+    }
+
+    finish_bag(&app, &bag).await;
+    let response = correct(
+        &app,
+        &bag,
+        json!({ "remaining": 100.0, "reason": "Weighed" }),
+    )
+    .await;
+    assert_eq!(response.status(), 409);
+
+    let response = Client::new()
+        .get(app.api_url("/bags/999999/ledger"))
+        .send()
+        .await
+        .expect("failed to execute request");
+    assert_eq!(response.status(), 404);
+}
+
+#[tokio::test]
+async fn bag_page_shows_the_ledger_and_offers_reopening() {
+    let app = spawn_app_with_auth().await;
+    let bag = default_bag(&app).await;
+    correct(
+        &app,
+        &bag,
+        json!({ "remaining": 200.0, "reason": "Spilled <b>some</b>" }),
+    )
+    .await;
+    let session = create_session(&app).await;
+
+    let body = bag_page(&app, &bag, Some(&session)).await;
+    assert_eq!(body.matches("data-role=\"ledger-entry\"").count(), 2);
+    assert!(body.contains("-50g"));
+    assert!(body.contains("200g left"));
+    assert!(!body.contains("<b>some</b>"), "reasons must be escaped");
+    assert!(body.contains("correctRemaining(event)"));
+    assert!(!body.contains("Reopen Bag"));
+
+    let public = bag_page(&app, &bag, None).await;
+    assert!(public.contains("data-role=\"ledger-entry\""));
+    assert!(!public.contains("correctRemaining(event)"));
+
+    finish_bag(&app, &bag).await;
+    let body = bag_page(&app, &bag, Some(&session)).await;
+    assert!(body.contains("Reopen Bag"));
+    assert!(!body.contains("correctRemaining(event)"));
+}
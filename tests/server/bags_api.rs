@@ -1,9 +1,10 @@
 use crate::helpers::{
     create_default_bag, create_default_gear, create_default_roast, create_default_roaster,
-    spawn_app, spawn_app_with_auth,
+    create_entity, create_session, spawn_app, spawn_app_with_auth,
 };
 use crate::test_macros::define_crud_tests;
 use brewlog::domain::bags::{Bag, BagWithRoast, NewBag, UpdateBag};
+use brewlog::domain::brews::{Brew, BrewSeriesPoint, NewBrew, QuickNote};
 use chrono::{NaiveDate, TimeZone, Utc};
 
 define_crud_tests!(
@@ -24,6 +25,10 @@ async fn creating_a_bag_returns_a_201_for_valid_data() {
         roast_id: roast.id,
         roast_date: Some(NaiveDate::from_ymd_opt(2023, 1, 1).unwrap()),
         amount: 250.0,
+        price: None,
+        currency: None,
+        best_before: None,
+        batch_code: None,
         created_at: None,
     };
 
@@ -87,6 +92,10 @@ async fn listing_bags_returns_200_and_correct_data() {
         roast_id: roast.id,
         roast_date: None,
         amount: 500.0,
+        price: None,
+        currency: None,
+        best_before: None,
+        batch_code: None,
         created_at: None,
     };
 
@@ -126,6 +135,10 @@ async fn getting_a_bag_returns_200_for_valid_id() {
         roast_id: roast.id,
         roast_date: None,
         amount: 250.0,
+        price: None,
+        currency: None,
+        best_before: None,
+        batch_code: None,
         created_at: None,
     };
 
@@ -167,6 +180,10 @@ async fn updating_a_bag_returns_200_and_updates_data() {
         roast_id: roast.id,
         roast_date: None,
         amount: 250.0,
+        price: None,
+        currency: None,
+        best_before: None,
+        batch_code: None,
         created_at: None,
     };
 
@@ -222,6 +239,10 @@ async fn deleting_a_bag_returns_204() {
         roast_id: roast.id,
         roast_date: None,
         amount: 250.0,
+        price: None,
+        currency: None,
+        best_before: None,
+        batch_code: None,
         created_at: None,
     };
 
@@ -271,6 +292,10 @@ async fn closing_a_bag_automatically_sets_finished_at() {
         roast_id: roast.id,
         roast_date: None,
         amount: 250.0,
+        price: None,
+        currency: None,
+        best_before: None,
+        batch_code: None,
         created_at: None,
     };
 
@@ -336,6 +361,9 @@ async fn updating_bag_amount_recomputes_remaining() {
         filter_paper_id: None,
         water_volume: 250,
         water_temp: 92.0,
+        bypass_water_ml: None,
+        kettle_id: None,
+        actual_water_temp: None,
         quick_notes: Vec::new(),
         brew_time: None,
         created_at: None,
@@ -371,7 +399,7 @@ async fn updating_bag_amount_recomputes_remaining() {
 }
 
 #[tokio::test]
-async fn creating_bag_with_zero_amount_returns_400() {
+async fn creating_bag_with_zero_amount_returns_422() {
     let app = spawn_app_with_auth().await;
     let roaster = create_default_roaster(&app).await;
     let roast = create_default_roast(&app, roaster.id).await;
@@ -388,11 +416,11 @@ async fn creating_bag_with_zero_amount_returns_400() {
         .await
         .expect("Failed to execute request");
 
-    assert_eq!(response.status(), 400);
+    assert_eq!(response.status(), 422);
 }
 
 #[tokio::test]
-async fn creating_bag_with_negative_amount_returns_400() {
+async fn creating_bag_with_negative_amount_returns_422() {
     let app = spawn_app_with_auth().await;
     let roaster = create_default_roaster(&app).await;
     let roast = create_default_roast(&app, roaster.id).await;
@@ -409,7 +437,7 @@ async fn creating_bag_with_negative_amount_returns_400() {
         .await
         .expect("Failed to execute request");
 
-    assert_eq!(response.status(), 400);
+    assert_eq!(response.status(), 422);
 }
 
 #[tokio::test]
@@ -433,3 +461,269 @@ async fn creating_bag_with_invalid_date_returns_400() {
 
     assert_eq!(response.status(), 400);
 }
+
+#[tokio::test]
+async fn bag_price_is_stored_and_a_blank_form_price_is_ignored() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+    let roast = create_default_roast(&app, roaster.id).await;
+    let client = reqwest::Client::new();
+
+    let priced: BagWithRoast = client
+        .post(app.api_url("/bags"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&serde_json::json!({
+            "roast_id": roast.id,
+            "amount": 250.0,
+            "price": 14.5
+        }))
+        .send()
+        .await
+        .expect("Failed to execute request")
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(priced.bag.price, Some(14.5));
+
+    let response = client
+        .post(app.api_url("/bags"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .form(&[
+            ("roast_id", roast.id.to_string().as_str()),
+            ("amount", "250"),
+            ("price", ""),
+        ])
+        .send()
+        .await
+        .expect("Failed to execute request");
+    assert!(response.status().is_success() || response.status().is_redirection());
+
+    let bags: Vec<BagWithRoast> = client
+        .get(app.api_url(&format!("/bags?roast_id={}", roast.id)))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(bags.len(), 2);
+    assert_eq!(bags.iter().filter(|b| b.bag.price.is_none()).count(), 1);
+}
+
+#[tokio::test]
+async fn creating_bag_with_negative_price_returns_422() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+    let roast = create_default_roast(&app, roaster.id).await;
+
+    let response = reqwest::Client::new()
+        .post(app.api_url("/bags"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&serde_json::json!({
+            "roast_id": roast.id,
+            "amount": 250.0,
+            "price": -3.0
+        }))
+        .send()
+        .await
+        .expect("Failed to execute request");
+
+    assert_eq!(response.status(), 422);
+}
+
+#[tokio::test]
+async fn bag_labels_are_stored_and_blank_form_fields_are_ignored() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+    let roast = create_default_roast(&app, roaster.id).await;
+    let client = reqwest::Client::new();
+
+    let labelled: BagWithRoast = client
+        .post(app.api_url("/bags"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&serde_json::json!({
+            "roast_id": roast.id,
+            "amount": 250.0,
+            "best_before": "2025-09-30",
+            "batch_code": " RB-2207 "
+        }))
+        .send()
+        .await
+        .expect("Failed to execute request")
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(
+        labelled.bag.best_before,
+        NaiveDate::from_ymd_opt(2025, 9, 30)
+    );
+    assert_eq!(labelled.bag.batch_code.as_deref(), Some("RB-2207"));
+
+    let response = client
+        .put(app.api_url(&format!("/bags/{}", labelled.bag.id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .form(&[("amount", "250"), ("best_before", ""), ("batch_code", "")])
+        .send()
+        .await
+        .expect("Failed to execute request");
+    assert!(response.status().is_success());
+
+    let bag: BagWithRoast = client
+        .get(app.api_url(&format!("/bags/{}", labelled.bag.id)))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(bag.bag.best_before, NaiveDate::from_ymd_opt(2025, 9, 30));
+    assert_eq!(bag.bag.batch_code.as_deref(), Some("RB-2207"));
+}
+
+#[tokio::test]
+async fn open_bags_near_their_best_before_date_are_flagged() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+    let roast = create_default_roast(&app, roaster.id).await;
+    let bag = create_default_bag(&app, roast.id).await;
+    let session = create_session(&app).await;
+    let client = reqwest::Client::new();
+    let home_page = || async {
+        client
+            .get(app.page_url("/"))
+            .header("Cookie", format!("brewlog_session={session}"))
+            .send()
+            .await
+            .expect("Failed to fetch page")
+            .text()
+            .await
+            .unwrap()
+    };
+    let set_best_before = |date: NaiveDate| {
+        client
+            .put(app.api_url(&format!("/bags/{}", bag.id)))
+            .bearer_auth(app.auth_token.as_ref().unwrap())
+            .json(&serde_json::json!({ "best_before": date }))
+            .send()
+    };
+    let today = Utc::now().date_naive();
+
+    set_best_before(today + chrono::Days::new(60))
+        .await
+        .unwrap();
+    assert!(!home_page().await.contains("data-role=\"best-before\""));
+
+    set_best_before(today + chrono::Days::new(3)).await.unwrap();
+    assert!(home_page().await.contains("Best before soon"));
+
+    set_best_before(today - chrono::Days::new(1)).await.unwrap();
+    assert!(home_page().await.contains("Past best before"));
+}
+
+#[tokio::test]
+async fn creating_bag_with_an_overlong_batch_code_returns_422() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+    let roast = create_default_roast(&app, roaster.id).await;
+
+    let response = reqwest::Client::new()
+        .post(app.api_url("/bags"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&serde_json::json!({
+            "roast_id": roast.id,
+            "amount": 250.0,
+            "batch_code": "X".repeat(65)
+        }))
+        .send()
+        .await
+        .expect("Failed to execute request");
+
+    assert_eq!(response.status(), 422);
+}
+
+async fn brew_from_bag(
+    app: &crate::helpers::TestApp,
+    bag: &Bag,
+    grind_setting: f64,
+    quick_notes: Vec<QuickNote>,
+) -> Brew {
+    let grinder = create_default_gear(app, "grinder", "Comandante", "C40 MK4").await;
+    let brewer = create_default_gear(app, "brewer", "Hario", "V60 02").await;
+    create_entity(
+        app,
+        "/brews",
+        &NewBrew {
+            bag_id: bag.id,
+            coffee_weight: 15.0,
+            grinder_id: grinder.id,
+            grind_setting,
+            brewer_id: brewer.id,
+            filter_paper_id: None,
+            water_volume: 240,
+            water_temp: 93.0,
+            bypass_water_ml: None,
+            kettle_id: None,
+            actual_water_temp: None,
+            quick_notes,
+            brew_time: None,
+            created_at: None,
+        },
+    )
+    .await
+}
+
+#[tokio::test]
+async fn brew_series_follows_the_bag_dialing_in() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+    let roast = create_default_roast(&app, roaster.id).await;
+    let bag = create_default_bag(&app, roast.id).await;
+    let first = brew_from_bag(&app, &bag, 24.0, vec![QuickNote::TooFast]).await;
+    let second = brew_from_bag(&app, &bag, 20.0, vec![QuickNote::Good]).await;
+
+    let series: Vec<BrewSeriesPoint> = reqwest::Client::new()
+        .get(app.api_url(&format!("/bags/{}/brew-series", bag.id)))
+        .send()
+        .await
+        .expect("Failed to execute request")
+        .json()
+        .await
+        .unwrap();
+
+    assert_eq!(
+        series.iter().map(|p| p.brew_id).collect::<Vec<_>>(),
+        [first.id, second.id]
+    );
+    assert_eq!(
+        series.iter().map(|p| p.grind_setting).collect::<Vec<_>>(),
+        [24.0, 20.0]
+    );
+    assert_eq!(series[0].ratio, Some(16.0));
+    assert_eq!(
+        series.iter().map(|p| p.rating).collect::<Vec<_>>(),
+        [Some(-1), Some(1)]
+    );
+
+    let body = reqwest::Client::new()
+        .get(app.page_url(&format!("/bags/{}", bag.id)))
+        .send()
+        .await
+        .unwrap()
+        .text()
+        .await
+        .unwrap();
+    assert!(body.contains(&format!("/api/v1/bags/{}/brew-series", bag.id)));
+}
+
+#[tokio::test]
+async fn brew_series_for_a_missing_bag_returns_404() {
+    let app = spawn_app().await;
+
+    let response = reqwest::Client::new()
+        .get(app.api_url("/bags/9999/brew-series"))
+        .send()
+        .await
+        .expect("Failed to execute request");
+
+    assert_eq!(response.status(), 404);
+}
@@ -0,0 +1,240 @@
+use brewlog::domain::bags::Bag;
+use brewlog::domain::brew_parsing::ParsedBrew;
+use brewlog::domain::gear::Gear;
+use brewlog::domain::roasts::NewRoast;
+use brewlog::infrastructure::ai::AiPolicy;
+use wiremock::matchers::{body_string_contains, method, path};
+use wiremock::{Mock, ResponseTemplate};
+
+use crate::helpers::{
+    TestApp, create_default_bag, create_default_gear, create_roast_with_payload,
+    create_roaster_with_name, spawn_app, spawn_app_with_ai_policy, spawn_app_with_auth,
+    spawn_app_with_openrouter_mock,
+};
+
+struct Setup {
+    konga: Bag,
+    red_brick: Bag,
+    grinder: Gear,
+    v60: Gear,
+    papers: Gear,
+}
+
+async fn setup(app: &TestApp) -> Setup {
+    let roaster = create_roaster_with_name(app, "Square Mile").await;
+    let mut bags = Vec::new();
+    for name in ["Konga", "Red Brick"] {
+        let roast = create_roast_with_payload(
+            app,
+            NewRoast {
+                roaster_id: roaster.id,
+                name: name.to_string(),
+                origin: "Ethiopia".to_string(),
+                region: "Yirgacheffe".to_string(),
+                producer: "Coop".to_string(),
+                tasting_notes: vec!["Blueberry".to_string()],
+                process: "Washed".to_string(),
+                components: Vec::new(),
+                harvest_year: None,
+                created_at: None,
+            },
+        )
+        .await;
+        bags.push(create_default_bag(app, roast.id).await);
+    }
+    let red_brick = bags.pop().unwrap();
+    let konga = bags.pop().unwrap();
+
+    let grinder = create_default_gear(app, "grinder", "Comandante", "C40 MK4").await;
+    let v60 = create_default_gear(app, "brewer", "Hario", "V60 02").await;
+    let _ = create_default_gear(app, "brewer", "AeroPress", "Original").await;
+    let papers = create_default_gear(app, "filter_paper", "Hario", "V60 Tabbed").await;
+
+    Setup {
+        konga,
+        red_brick,
+        grinder,
+        v60,
+        papers,
+    }
+}
+
+async fn post_parse(app: &TestApp, body: serde_json::Value) -> reqwest::Response {
+    reqwest::Client::new()
+        .post(app.api_url("/parse-brew"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&body)
+        .send()
+        .await
+        .expect("failed to execute request")
+}
+
+#[tokio::test]
+async fn parsing_a_brew_requires_authentication() {
+    let app = spawn_app().await;
+
+    let response = reqwest::Client::new()
+        .post(app.api_url("/parse-brew"))
+        .json(&serde_json::json!({ "text": "18g in the v60" }))
+        .send()
+        .await
+        .expect("failed to execute request");
+
+    assert_eq!(response.status(), 401);
+}
+
+#[tokio::test]
+async fn a_full_description_parses_into_a_brew_ready_to_log() {
+    let app = spawn_app_with_auth().await;
+    let setup = setup(&app).await;
+
+    let response = post_parse(
+        &app,
+        serde_json::json!({ "text": "18g konga in the v60, 285g water at 92, grind 24, 3:10" }),
+    )
+    .await;
+    assert_eq!(response.status(), 200);
+    let parsed: ParsedBrew = response.json().await.expect("failed to parse");
+
+    assert!(parsed.ambiguities.is_empty(), "{:?}", parsed.ambiguities);
+    assert!(!parsed.used_ai);
+    let draft = &parsed.draft;
+    assert_eq!(draft.bag_id, Some(setup.konga.id));
+    assert_eq!(draft.coffee_weight, Some(18.0));
+    assert_eq!(draft.grinder_id, Some(setup.grinder.id));
+    assert_eq!(draft.grind_setting, Some(24.0));
+    assert_eq!(draft.brewer_id, Some(setup.v60.id));
+    assert_eq!(draft.filter_paper_id, Some(setup.papers.id));
+    assert_eq!(draft.water_volume, Some(285));
+    assert_eq!(draft.water_temp, Some(92.0));
+    assert_eq!(draft.brew_time, Some(190));
+
+    let response = reqwest::Client::new()
+        .post(app.api_url("/brews"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&parsed.brew.expect("a brew ready to log"))
+        .send()
+        .await
+        .expect("failed to execute request");
+    assert_eq!(response.status(), 201);
+}
+
+#[tokio::test]
+async fn ambiguities_are_returned_for_confirmation() {
+    let app = spawn_app_with_auth().await;
+    let setup = setup(&app).await;
+
+    let parsed: ParsedBrew = post_parse(
+        &app,
+        serde_json::json!({ "text": "18g square mile, 250g water, grind 20" }),
+    )
+    .await
+    .json()
+    .await
+    .expect("failed to parse");
+
+    assert!(parsed.brew.is_none());
+    let bag = parsed
+        .ambiguities
+        .iter()
+        .find(|ambiguity| ambiguity.field == "bag_id")
+        .expect("the bag needs confirming");
+    let mut options: Vec<i64> = bag.options.iter().map(|option| option.id).collect();
+    options.sort_unstable();
+    assert_eq!(
+        options,
+        [setup.konga.id.into_inner(), setup.red_brick.id.into_inner()]
+    );
+    assert!(
+        parsed
+            .ambiguities
+            .iter()
+            .any(|ambiguity| ambiguity.field == "brewer_id")
+    );
+    assert!(
+        parsed
+            .ambiguities
+            .iter()
+            .any(|ambiguity| ambiguity.field == "water_temp")
+    );
+}
+
+#[tokio::test]
+async fn an_empty_description_is_rejected() {
+    let app = spawn_app_with_auth().await;
+
+    let response = post_parse(&app, serde_json::json!({ "text": "  " })).await;
+
+    assert_eq!(response.status(), 400);
+}
+
+#[tokio::test]
+async fn the_ai_fills_in_what_the_rules_miss() {
+    let app = spawn_app_with_openrouter_mock().await;
+    let setup = setup(&app).await;
+    let mock_server = app.mock_server.as_ref().unwrap();
+
+    let content = r#"{"brewer": "Hario V60 02", "water_temp": 93, "grind_setting": 22}"#;
+    Mock::given(method("POST"))
+        .and(path("/api/v1/chat/completions"))
+        .and(body_string_contains("Hario V60 02"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "choices": [{ "message": { "role": "assistant", "content": content } }],
+            "usage": {
+                "prompt_tokens": 100,
+                "completion_tokens": 50,
+                "total_tokens": 150,
+                "cost": 0.001
+            }
+        })))
+        .expect(1)
+        .mount(mock_server)
+        .await;
+
+    let parsed: ParsedBrew = post_parse(
+        &app,
+        serde_json::json!({ "text": "18g konga with 250g water, pour over", "ai": true }),
+    )
+    .await
+    .json()
+    .await
+    .expect("failed to parse");
+
+    assert!(parsed.used_ai);
+    assert!(parsed.ambiguities.is_empty(), "{:?}", parsed.ambiguities);
+    assert_eq!(parsed.draft.bag_id, Some(setup.konga.id));
+    assert_eq!(parsed.draft.brewer_id, Some(setup.v60.id));
+    assert_eq!(parsed.draft.water_temp, Some(93.0));
+    assert_eq!(parsed.draft.grind_setting, Some(22.0));
+}
+
+#[tokio::test]
+async fn the_rules_draft_stands_when_the_ai_is_unavailable() {
+    let app = spawn_app_with_ai_policy(AiPolicy {
+        attempts: 1,
+        ..AiPolicy::default()
+    })
+    .await;
+    setup(&app).await;
+    let mock_server = app.mock_server.as_ref().unwrap();
+
+    Mock::given(method("POST"))
+        .and(path("/api/v1/chat/completions"))
+        .respond_with(ResponseTemplate::new(503))
+        .expect(1)
+        .mount(mock_server)
+        .await;
+
+    let response = post_parse(
+        &app,
+        serde_json::json!({ "text": "18g square mile, 250g water", "ai": true }),
+    )
+    .await;
+
+    assert_eq!(response.status(), 200);
+    let parsed: ParsedBrew = response.json().await.expect("failed to parse");
+    assert!(!parsed.used_ai);
+    assert!(parsed.ai_unavailable);
+    assert!(!parsed.ambiguities.is_empty());
+    assert_eq!(parsed.draft.coffee_weight, Some(18.0));
+}
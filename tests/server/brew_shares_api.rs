@@ -0,0 +1,186 @@
+use std::sync::Arc;
+
+use brewlog::domain::brew_shares::BrewShare;
+use brewlog::domain::clock::FixedClock;
+use chrono::{Duration, TimeZone, Utc};
+use serde_json::Value;
+
+use super::helpers::{
+    TestApp, create_default_brew, create_session, spawn_app_with_auth, spawn_app_with_clock,
+};
+
+async fn create_share(app: &TestApp, brew_id: i64, body: Option<Value>) -> reqwest::Response {
+    let mut request = reqwest::Client::new()
+        .post(app.api_url(&format!("/brews/{brew_id}/share")))
+        .bearer_auth(app.auth_token.as_ref().unwrap());
+    if let Some(body) = body {
+        request = request.json(&body);
+    }
+    request.send().await.expect("failed to execute request")
+}
+
+/// Create a share link and return it along with the page path from its URL.
+async fn share_brew(app: &TestApp, brew_id: i64) -> (BrewShare, String) {
+    let response = create_share(app, brew_id, None).await;
+    assert_eq!(response.status(), 201);
+    let body: Value = response.json().await.expect("failed to parse response");
+
+    let url = body["url"].as_str().expect("missing url");
+    let path = &url[url.find("/share/").expect("unexpected share url")..];
+    let share = serde_json::from_value(body["share"].clone()).expect("failed to parse share");
+    (share, path.to_string())
+}
+
+async fn get_page(app: &TestApp, path: &str) -> reqwest::Response {
+    reqwest::Client::new()
+        .get(app.page_url(path))
+        .send()
+        .await
+        .expect("failed to execute request")
+}
+
+#[tokio::test]
+async fn creating_a_share_link_requires_authentication() {
+    let app = spawn_app_with_auth().await;
+    let brew = create_default_brew(&app).await;
+
+    let response = reqwest::Client::new()
+        .post(app.api_url(&format!("/brews/{}/share", brew.id)))
+        .send()
+        .await
+        .expect("failed to execute request");
+
+    assert_eq!(response.status(), 401);
+}
+
+#[tokio::test]
+async fn share_link_renders_the_brew_without_authentication() {
+    let app = spawn_app_with_auth().await;
+    let brew = create_default_brew(&app).await;
+    let (share, path) = share_brew(&app, brew.id.into_inner()).await;
+    assert_eq!(share.brew_id, brew.id);
+    assert!(share.expires_at.is_none());
+
+    let response = get_page(&app, &path).await;
+    assert_eq!(response.status(), 200);
+    let body = response.text().await.expect("failed to read body");
+    assert!(body.contains("shared-brew"));
+    assert!(body.contains("Comandante"));
+    assert!(body.contains("noindex"));
+}
+
+#[tokio::test]
+async fn share_links_can_expire() {
+    let app = spawn_app_with_auth().await;
+    let brew = create_default_brew(&app).await;
+
+    let response = create_share(
+        &app,
+        brew.id.into_inner(),
+        Some(serde_json::json!({ "expires_in_days": 7 })),
+    )
+    .await;
+    assert_eq!(response.status(), 201);
+    let body: Value = response.json().await.expect("failed to parse response");
+    assert!(body["share"]["expires_at"].is_string());
+
+    let response = create_share(
+        &app,
+        brew.id.into_inner(),
+        Some(serde_json::json!({ "expires_in_days": 0 })),
+    )
+    .await;
+    assert_eq!(response.status(), 400);
+}
+
+#[tokio::test]
+async fn share_links_stop_working_once_they_expire() {
+    let created_at = Utc.with_ymd_and_hms(2025, 6, 1, 9, 0, 0).unwrap();
+    let clock = Arc::new(FixedClock::new(created_at));
+    let app = spawn_app_with_clock(clock.clone()).await;
+    let brew = create_default_brew(&app).await;
+
+    let response = create_share(
+        &app,
+        brew.id.into_inner(),
+        Some(serde_json::json!({ "expires_in_days": 7 })),
+    )
+    .await;
+    assert_eq!(response.status(), 201);
+    let body: Value = response.json().await.expect("failed to parse response");
+    let share: BrewShare =
+        serde_json::from_value(body["share"].clone()).expect("failed to parse share");
+    assert_eq!(share.expires_at, Some(created_at + Duration::days(7)));
+    let url = body["url"].as_str().expect("missing url");
+    let path = &url[url.find("/share/").expect("unexpected share url")..];
+
+    clock.advance(Duration::days(7) - Duration::minutes(1));
+    assert_eq!(get_page(&app, path).await.status(), 200);
+
+    clock.advance(Duration::minutes(1));
+    assert_eq!(get_page(&app, path).await.status(), 404);
+}
+
+#[tokio::test]
+async fn revoked_share_links_are_not_found() {
+    let app = spawn_app_with_auth().await;
+    let brew = create_default_brew(&app).await;
+    let (share, path) = share_brew(&app, brew.id.into_inner()).await;
+
+    let response = reqwest::Client::new()
+        .post(app.api_url(&format!("/shares/{}/revoke", share.id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .send()
+        .await
+        .expect("failed to execute request");
+    assert_eq!(response.status(), 200);
+    let revoked: BrewShare = response.json().await.expect("failed to parse share");
+    assert!(revoked.revoked_at.is_some());
+
+    assert_eq!(get_page(&app, &path).await.status(), 404);
+}
+
+#[tokio::test]
+async fn unknown_share_tokens_are_not_found() {
+    let app = spawn_app_with_auth().await;
+
+    assert_eq!(get_page(&app, "/share/not-a-token").await.status(), 404);
+}
+
+#[tokio::test]
+async fn sharing_a_missing_brew_returns_not_found() {
+    let app = spawn_app_with_auth().await;
+
+    assert_eq!(create_share(&app, 9999, None).await.status(), 404);
+}
+
+#[tokio::test]
+async fn share_links_are_listed_on_the_brew_page() {
+    let app = spawn_app_with_auth().await;
+    let brew = create_default_brew(&app).await;
+    share_brew(&app, brew.id.into_inner()).await;
+
+    let shares: Vec<BrewShare> = reqwest::Client::new()
+        .get(app.api_url(&format!("/brews/{}/shares", brew.id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .send()
+        .await
+        .expect("failed to execute request")
+        .json()
+        .await
+        .expect("failed to parse shares");
+    assert_eq!(shares.len(), 1);
+
+    let session_token = create_session(&app).await;
+    let body = reqwest::Client::new()
+        .get(app.page_url(&format!("/brews/{}", brew.id)))
+        .header("Cookie", format!("brewlog_session={session_token}"))
+        .send()
+        .await
+        .expect("failed to execute request")
+        .text()
+        .await
+        .expect("failed to read body");
+    assert!(body.contains("brew-shares"));
+    assert!(body.contains("Never expires"));
+}
@@ -1,9 +1,12 @@
 use crate::helpers::{
-    create_default_bag, create_default_gear, create_default_roast, create_default_roaster,
-    spawn_app, spawn_app_with_auth,
+    create_default_bag, create_default_brew, create_default_gear, create_default_roast,
+    create_default_roaster, create_entity, create_roast_with_payload, create_roaster_with_name,
+    create_session, spawn_app, spawn_app_with_auth,
 };
 use brewlog::domain::bags::Bag;
 use brewlog::domain::brews::{Brew, BrewWithDetails, NewBrew};
+use brewlog::domain::gear::Gear;
+use brewlog::domain::roasts::NewRoast;
 
 #[tokio::test]
 async fn creating_a_brew_returns_201_for_valid_data() {
@@ -25,6 +28,9 @@ async fn creating_a_brew_returns_201_for_valid_data() {
         filter_paper_id: None,
         water_volume: 250,
         water_temp: 92.0,
+        bypass_water_ml: None,
+        kettle_id: None,
+        actual_water_temp: None,
         quick_notes: Vec::new(),
         brew_time: None,
         created_at: None,
@@ -74,6 +80,9 @@ async fn creating_a_brew_with_filter_paper_returns_201() {
         filter_paper_id: Some(filter_paper.id),
         water_volume: 250,
         water_temp: 92.0,
+        bypass_water_ml: None,
+        kettle_id: None,
+        actual_water_temp: None,
         quick_notes: Vec::new(),
         brew_time: None,
         created_at: None,
@@ -115,6 +124,9 @@ async fn creating_a_brew_deducts_from_bag_remaining() {
         filter_paper_id: None,
         water_volume: 250,
         water_temp: 92.0,
+        bypass_water_ml: None,
+        kettle_id: None,
+        actual_water_temp: None,
         quick_notes: Vec::new(),
         brew_time: None,
         created_at: None,
@@ -160,6 +172,9 @@ async fn creating_a_brew_with_excess_coffee_clamps_remaining_to_zero() {
         filter_paper_id: None,
         water_volume: 250,
         water_temp: 92.0,
+        bypass_water_ml: None,
+        kettle_id: None,
+        actual_water_temp: None,
         quick_notes: Vec::new(),
         brew_time: None,
         created_at: None,
@@ -219,6 +234,9 @@ async fn creating_a_brew_against_empty_open_bag_succeeds() {
         filter_paper_id: None,
         water_volume: 250,
         water_temp: 92.0,
+        bypass_water_ml: None,
+        kettle_id: None,
+        actual_water_temp: None,
         quick_notes: Vec::new(),
         brew_time: None,
         created_at: None,
@@ -297,6 +315,9 @@ async fn listing_brews_returns_200_and_enriched_data() {
         filter_paper_id: Some(filter_paper.id),
         water_volume: 250,
         water_temp: 92.0,
+        bypass_water_ml: None,
+        kettle_id: None,
+        actual_water_temp: None,
         quick_notes: Vec::new(),
         brew_time: None,
         created_at: None,
@@ -353,6 +374,9 @@ async fn listing_brews_without_filter_paper_returns_none() {
         filter_paper_id: None,
         water_volume: 255,
         water_temp: 88.0,
+        bypass_water_ml: None,
+        kettle_id: None,
+        actual_water_temp: None,
         quick_notes: Vec::new(),
         brew_time: None,
         created_at: None,
@@ -404,6 +428,9 @@ async fn listing_brews_with_bag_filter_returns_filtered_results() {
         filter_paper_id: None,
         water_volume: 250,
         water_temp: 92.0,
+        bypass_water_ml: None,
+        kettle_id: None,
+        actual_water_temp: None,
         quick_notes: Vec::new(),
         brew_time: None,
         created_at: None,
@@ -427,6 +454,9 @@ async fn listing_brews_with_bag_filter_returns_filtered_results() {
         filter_paper_id: None,
         water_volume: 255,
         water_temp: 88.0,
+        bypass_water_ml: None,
+        kettle_id: None,
+        actual_water_temp: None,
         quick_notes: Vec::new(),
         brew_time: None,
         created_at: None,
@@ -475,6 +505,9 @@ async fn getting_a_brew_returns_200_for_valid_id() {
         filter_paper_id: None,
         water_volume: 250,
         water_temp: 92.0,
+        bypass_water_ml: None,
+        kettle_id: None,
+        actual_water_temp: None,
         quick_notes: Vec::new(),
         brew_time: None,
         created_at: None,
@@ -527,6 +560,9 @@ async fn deleting_a_brew_returns_204() {
         filter_paper_id: None,
         water_volume: 250,
         water_temp: 92.0,
+        bypass_water_ml: None,
+        kettle_id: None,
+        actual_water_temp: None,
         quick_notes: Vec::new(),
         brew_time: None,
         created_at: None,
@@ -585,6 +621,9 @@ async fn updating_a_brew_returns_200_and_updates_data() {
         filter_paper_id: None,
         water_volume: 250,
         water_temp: 92.0,
+        bypass_water_ml: None,
+        kettle_id: None,
+        actual_water_temp: None,
         quick_notes: Vec::new(),
         brew_time: None,
         created_at: None,
@@ -661,6 +700,9 @@ async fn updating_a_brew_with_no_changes_returns_400() {
         filter_paper_id: None,
         water_volume: 250,
         water_temp: 92.0,
+        bypass_water_ml: None,
+        kettle_id: None,
+        actual_water_temp: None,
         quick_notes: Vec::new(),
         brew_time: None,
         created_at: None,
@@ -713,7 +755,7 @@ async fn updating_a_nonexistent_brew_returns_404() {
 }
 
 #[tokio::test]
-async fn creating_brew_with_zero_coffee_weight_returns_400() {
+async fn creating_brew_with_zero_coffee_weight_returns_422() {
     let app = spawn_app_with_auth().await;
     let roaster = create_default_roaster(&app).await;
     let roast = create_default_roast(&app, roaster.id).await;
@@ -734,6 +776,9 @@ async fn creating_brew_with_zero_coffee_weight_returns_400() {
             filter_paper_id: None,
             water_volume: 250,
             water_temp: 92.0,
+            bypass_water_ml: None,
+            kettle_id: None,
+            actual_water_temp: None,
             quick_notes: Vec::new(),
             brew_time: None,
             created_at: None,
@@ -742,11 +787,11 @@ async fn creating_brew_with_zero_coffee_weight_returns_400() {
         .await
         .expect("Failed to execute request");
 
-    assert_eq!(response.status(), 400);
+    assert_eq!(response.status(), 422);
 }
 
 #[tokio::test]
-async fn creating_brew_with_negative_grind_setting_returns_400() {
+async fn creating_brew_with_negative_grind_setting_returns_422() {
     let app = spawn_app_with_auth().await;
     let roaster = create_default_roaster(&app).await;
     let roast = create_default_roast(&app, roaster.id).await;
@@ -767,6 +812,9 @@ async fn creating_brew_with_negative_grind_setting_returns_400() {
             filter_paper_id: None,
             water_volume: 250,
             water_temp: 92.0,
+            bypass_water_ml: None,
+            kettle_id: None,
+            actual_water_temp: None,
             quick_notes: Vec::new(),
             brew_time: None,
             created_at: None,
@@ -775,11 +823,11 @@ async fn creating_brew_with_negative_grind_setting_returns_400() {
         .await
         .expect("Failed to execute request");
 
-    assert_eq!(response.status(), 400);
+    assert_eq!(response.status(), 422);
 }
 
 #[tokio::test]
-async fn creating_brew_with_zero_water_volume_returns_400() {
+async fn creating_brew_with_zero_water_volume_returns_422() {
     let app = spawn_app_with_auth().await;
     let roaster = create_default_roaster(&app).await;
     let roast = create_default_roast(&app, roaster.id).await;
@@ -800,6 +848,9 @@ async fn creating_brew_with_zero_water_volume_returns_400() {
             filter_paper_id: None,
             water_volume: 0,
             water_temp: 92.0,
+            bypass_water_ml: None,
+            kettle_id: None,
+            actual_water_temp: None,
             quick_notes: Vec::new(),
             brew_time: None,
             created_at: None,
@@ -808,11 +859,11 @@ async fn creating_brew_with_zero_water_volume_returns_400() {
         .await
         .expect("Failed to execute request");
 
-    assert_eq!(response.status(), 400);
+    assert_eq!(response.status(), 422);
 }
 
 #[tokio::test]
-async fn creating_brew_with_water_temp_over_100_returns_400() {
+async fn creating_brew_with_water_temp_over_100_returns_422() {
     let app = spawn_app_with_auth().await;
     let roaster = create_default_roaster(&app).await;
     let roast = create_default_roast(&app, roaster.id).await;
@@ -833,6 +884,9 @@ async fn creating_brew_with_water_temp_over_100_returns_400() {
             filter_paper_id: None,
             water_volume: 250,
             water_temp: 101.0,
+            bypass_water_ml: None,
+            kettle_id: None,
+            actual_water_temp: None,
             quick_notes: Vec::new(),
             brew_time: None,
             created_at: None,
@@ -841,5 +895,339 @@ async fn creating_brew_with_water_temp_over_100_returns_400() {
         .await
         .expect("Failed to execute request");
 
-    assert_eq!(response.status(), 400);
+    assert_eq!(response.status(), 422);
+}
+
+#[tokio::test]
+async fn bypass_water_counts_towards_the_ratio_and_the_timeline() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+    let roast = create_default_roast(&app, roaster.id).await;
+    let bag = create_default_bag(&app, roast.id).await;
+    let grinder = create_default_gear(&app, "grinder", "Comandante", "C40 MK4").await;
+    let brewer = create_default_gear(&app, "brewer", "Hario", "V60 02").await;
+    let client = reqwest::Client::new();
+
+    let response = client
+        .post(app.api_url("/brews"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&NewBrew {
+            bag_id: bag.id,
+            coffee_weight: 15.0,
+            grinder_id: grinder.id,
+            grind_setting: 12.0,
+            brewer_id: brewer.id,
+            filter_paper_id: None,
+            water_volume: 150,
+            water_temp: 94.0,
+            bypass_water_ml: Some(90),
+            kettle_id: None,
+            actual_water_temp: None,
+            quick_notes: Vec::new(),
+            brew_time: None,
+            created_at: None,
+        })
+        .send()
+        .await
+        .expect("Failed to execute request");
+    assert_eq!(response.status(), 201);
+    let brew: Brew = response.json().await.unwrap();
+    assert_eq!(brew.bypass_water_ml, Some(90));
+    assert_eq!(brew.ratio(), Some(16.0));
+
+    let body = client
+        .get(app.page_url(&format!("/brews/{}", brew.id)))
+        .send()
+        .await
+        .unwrap()
+        .text()
+        .await
+        .unwrap();
+    assert!(body.contains("90ml"));
+    assert!(body.contains("1:16.0"));
+
+    let events = app.timeline_repo.list_all().await.unwrap();
+    let brew_data = events
+        .iter()
+        .find_map(|event| event.brew_data.as_ref())
+        .expect("brew event should carry brew data");
+    assert_eq!(brew_data.bypass_water_ml, Some(90));
+}
+
+#[tokio::test]
+async fn a_blank_bypass_is_accepted_from_the_form_and_zero_clears_it() {
+    let app = spawn_app_with_auth().await;
+    let brew = create_default_brew(&app).await;
+    let client = reqwest::Client::new();
+
+    let response = client
+        .put(app.api_url(&format!("/brews/{}", brew.id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .form(&[("bypass_water_ml", "60")])
+        .send()
+        .await
+        .expect("Failed to update brew");
+    assert!(response.status().is_success() || response.status().is_redirection());
+
+    let fetched: BrewWithDetails = client
+        .get(app.api_url(&format!("/brews/{}", brew.id)))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(fetched.brew.bypass_water_ml, Some(60));
+
+    let response = client
+        .put(app.api_url(&format!("/brews/{}", brew.id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .form(&[("bypass_water_ml", ""), ("water_volume", "300")])
+        .send()
+        .await
+        .expect("Failed to update brew");
+    assert!(response.status().is_success() || response.status().is_redirection());
+
+    let fetched: BrewWithDetails = client
+        .get(app.api_url(&format!("/brews/{}", brew.id)))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(fetched.brew.water_volume, 300);
+    assert_eq!(fetched.brew.bypass_water_ml, Some(60));
+
+    let response = client
+        .put(app.api_url(&format!("/brews/{}", brew.id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .form(&[("bypass_water_ml", "0")])
+        .send()
+        .await
+        .expect("Failed to update brew");
+    assert!(response.status().is_success() || response.status().is_redirection());
+
+    let fetched: BrewWithDetails = client
+        .get(app.api_url(&format!("/brews/{}", brew.id)))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(fetched.brew.bypass_water_ml, None);
+}
+
+/// A bag, a grinder and a brewer with the given method settings, ready for a
+/// brew to be made from.
+async fn brew_setup(app: &crate::helpers::TestApp, brewer: serde_json::Value) -> NewBrew {
+    let roaster = create_default_roaster(app).await;
+    let roast = create_default_roast(app, roaster.id).await;
+    let bag = create_default_bag(app, roast.id).await;
+    let grinder = create_default_gear(app, "grinder", "Comandante", "C40 MK4").await;
+    let brewer: Gear = create_entity(app, "/gear", &brewer).await;
+    NewBrew {
+        bag_id: bag.id,
+        coffee_weight: 15.0,
+        grinder_id: grinder.id,
+        grind_setting: 24.0,
+        brewer_id: brewer.id,
+        filter_paper_id: None,
+        water_volume: 250,
+        water_temp: 92.0,
+        bypass_water_ml: None,
+        kettle_id: None,
+        actual_water_temp: None,
+        quick_notes: Vec::new(),
+        brew_time: None,
+        created_at: None,
+    }
+}
+
+async fn post_brew(app: &crate::helpers::TestApp, brew: &NewBrew) -> reqwest::Response {
+    reqwest::Client::new()
+        .post(app.api_url("/brews"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(brew)
+        .send()
+        .await
+        .expect("Failed to execute request")
+}
+
+#[tokio::test]
+async fn a_brewer_set_to_take_a_filter_needs_one() {
+    let app = spawn_app_with_auth().await;
+    let mut brew = brew_setup(
+        &app,
+        serde_json::json!({
+            "category": "brewer",
+            "make": "Hario",
+            "model": "V60 02",
+            "brew_method": "percolation",
+            "uses_filter": true
+        }),
+    )
+    .await;
+
+    let response = post_brew(&app, &brew).await;
+    assert_eq!(response.status(), 422);
+    let body: serde_json::Value = response.json().await.expect("Failed to parse response");
+    assert!(body.to_string().contains("filter paper"));
+
+    let paper = create_default_gear(&app, "filter_paper", "Hario", "Tabbed 02").await;
+    brew.filter_paper_id = Some(paper.id);
+    assert_eq!(post_brew(&app, &brew).await.status(), 201);
+}
+
+#[tokio::test]
+async fn a_brewer_set_to_take_no_filter_rejects_one() {
+    let app = spawn_app_with_auth().await;
+    let mut brew = brew_setup(
+        &app,
+        serde_json::json!({
+            "category": "brewer",
+            "make": "Bodum",
+            "model": "Chambord",
+            "brew_method": "immersion",
+            "uses_filter": false
+        }),
+    )
+    .await;
+    brew.brew_time = Some(240);
+    let paper = create_default_gear(&app, "filter_paper", "Hario", "Tabbed 02").await;
+    brew.filter_paper_id = Some(paper.id);
+
+    assert_eq!(post_brew(&app, &brew).await.status(), 422);
+
+    brew.filter_paper_id = None;
+    assert_eq!(post_brew(&app, &brew).await.status(), 201);
+}
+
+#[tokio::test]
+async fn immersion_brews_need_a_steep_time() {
+    let app = spawn_app_with_auth().await;
+    let mut brew = brew_setup(
+        &app,
+        serde_json::json!({
+            "category": "brewer",
+            "make": "AeroPress",
+            "model": "Original",
+            "brew_method": "immersion"
+        }),
+    )
+    .await;
+
+    let response = post_brew(&app, &brew).await;
+    assert_eq!(response.status(), 422);
+    let body: serde_json::Value = response.json().await.expect("Failed to parse response");
+    assert!(body.to_string().contains("steep time"));
+
+    brew.brew_time = Some(120);
+    assert_eq!(post_brew(&app, &brew).await.status(), 201);
+}
+
+#[tokio::test]
+async fn brewers_only_detected_from_their_name_are_not_enforced() {
+    let app = spawn_app_with_auth().await;
+    let brew = brew_setup(
+        &app,
+        serde_json::json!({ "category": "brewer", "make": "AeroPress", "model": "Go" }),
+    )
+    .await;
+
+    assert_eq!(post_brew(&app, &brew).await.status(), 201);
+}
+
+#[tokio::test]
+async fn the_brew_form_carries_each_brewers_method() {
+    let app = spawn_app_with_auth().await;
+    let _ = brew_setup(
+        &app,
+        serde_json::json!({ "category": "brewer", "make": "AeroPress", "model": "Go" }),
+    )
+    .await;
+    let session_token = create_session(&app).await;
+
+    let body = reqwest::Client::new()
+        .get(app.page_url("/add"))
+        .header("Cookie", format!("brewlog_session={session_token}"))
+        .send()
+        .await
+        .expect("Failed to execute request")
+        .text()
+        .await
+        .expect("Failed to read body");
+
+    assert!(body.contains(r#"data-method="immersion""#));
+    assert!(body.contains(r#"data-filter="required""#));
+}
+
+#[tokio::test]
+async fn the_brew_form_offers_recently_used_bags_and_gear_first() {
+    let app = spawn_app_with_auth().await;
+    // Brewed from the Test Roast bag with the Comandante.
+    let brew = create_default_brew(&app).await;
+    let roaster = create_roaster_with_name(&app, "Other Roasters").await;
+    let newer = create_roast_with_payload(
+        &app,
+        NewRoast {
+            roaster_id: roaster.id,
+            name: "Newer Roast".to_string(),
+            origin: "Kenya".to_string(),
+            region: "Nyeri".to_string(),
+            producer: "Coop".to_string(),
+            tasting_notes: vec!["Blackcurrant".to_string()],
+            process: "Washed".to_string(),
+            components: Vec::new(),
+            harvest_year: None,
+            created_at: None,
+        },
+    )
+    .await;
+    let _: Bag = create_entity(
+        &app,
+        "/bags",
+        &serde_json::json!({ "roast_id": newer.id, "roast_date": "2024-01-01", "amount": 250.0 }),
+    )
+    .await;
+    create_default_gear(&app, "grinder", "Baratza", "Encore").await;
+    let session_token = create_session(&app).await;
+
+    let body = reqwest::Client::new()
+        .get(app.page_url("/add?type=brew"))
+        .header("Cookie", format!("brewlog_session={session_token}"))
+        .send()
+        .await
+        .expect("Failed to execute request")
+        .text()
+        .await
+        .expect("Failed to read body");
+
+    let bags = &body[body.find(r#"name="bag_id""#).unwrap()..];
+    assert!(bags.contains("Open bags"));
+    assert!(bags.find("Test Roast").unwrap() < bags.find("Newer Roast").unwrap());
+    let grinders = &body[body.find(r#"name="grinder_id""#).unwrap()..];
+    assert!(grinders.find("Comandante").unwrap() < grinders.find("Baratza").unwrap());
+
+    // A brew whose bag has been finished can still be edited against it.
+    reqwest::Client::new()
+        .put(app.api_url(&format!("/bags/{}", brew.bag_id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&serde_json::json!({ "closed": true }))
+        .send()
+        .await
+        .expect("Failed to execute request");
+    let body = reqwest::Client::new()
+        .get(app.page_url(&format!("/brews/{}/edit", brew.id)))
+        .header("Cookie", format!("brewlog_session={session_token}"))
+        .send()
+        .await
+        .expect("Failed to execute request")
+        .text()
+        .await
+        .expect("Failed to read body");
+    let bags = &body[body.find(r#"name="bag_id""#).unwrap()..];
+    assert!(bags.find("Newer Roast").unwrap() < bags.find("Finished").unwrap());
+    assert!(bags.find("Finished").unwrap() < bags.find("Test Roast").unwrap());
 }
@@ -0,0 +1,251 @@
+use brewlog::domain::bags::BagWithRoast;
+use brewlog::domain::brews::{BrewWithDetails, QuickNote};
+use brewlog::domain::roasters::Roaster;
+use brewlog::domain::roasts::RoastWithRoaster;
+use brewlog::infrastructure::bulk::BulkReport;
+
+use crate::helpers::{
+    TestApp, create_default_bag, create_default_brew, create_default_roast, create_default_roaster,
+    create_roaster_with_name, create_session, spawn_app, spawn_app_with_auth,
+};
+
+async fn post_bulk(app: &TestApp, list: &str, body: serde_json::Value) -> reqwest::Response {
+    reqwest::Client::new()
+        .post(app.api_url(&format!("/{list}/bulk")))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&body)
+        .send()
+        .await
+        .expect("failed to execute request")
+}
+
+#[tokio::test]
+async fn bulk_actions_require_authentication() {
+    let app = spawn_app().await;
+
+    let response = reqwest::Client::new()
+        .post(app.api_url("/roasters/bulk"))
+        .json(&serde_json::json!({ "action": "delete", "ids": [1] }))
+        .send()
+        .await
+        .expect("failed to execute request");
+
+    assert_eq!(response.status(), 401);
+}
+
+#[tokio::test]
+async fn bulk_delete_reports_each_row() {
+    let app = spawn_app_with_auth().await;
+    let first = create_roaster_with_name(&app, "First Roasters").await;
+    let second = create_roaster_with_name(&app, "Second Roasters").await;
+
+    let response = post_bulk(
+        &app,
+        "roasters",
+        serde_json::json!({
+            "action": "delete",
+            "ids": [first.id, 999_999, second.id]
+        }),
+    )
+    .await;
+
+    assert_eq!(response.status(), 200);
+    let report: BulkReport = response.json().await.expect("failed to parse");
+    assert_eq!(report.succeeded, 2);
+    assert_eq!(report.failed, 1);
+    assert_eq!(report.results.len(), 3);
+    assert!(!report.results[1].ok);
+    assert_eq!(report.results[1].error.as_deref(), Some("not found"));
+
+    let response = reqwest::Client::new()
+        .get(app.api_url(&format!("/roasters/{}", second.id)))
+        .send()
+        .await
+        .expect("failed to execute request");
+    assert_eq!(response.status(), 404);
+}
+
+#[tokio::test]
+async fn bulk_delete_clears_cached_listings() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_roaster_with_name(&app, "Cached Roasters").await;
+    // Warm the roaster listing cache so a stale row would show.
+    reqwest::Client::new()
+        .get(app.api_url("/roasters"))
+        .send()
+        .await
+        .expect("failed to execute request");
+
+    let response = post_bulk(
+        &app,
+        "roasters",
+        serde_json::json!({ "action": "delete", "ids": [roaster.id] }),
+    )
+    .await;
+    assert_eq!(response.status(), 200);
+
+    let listed: Vec<Roaster> = reqwest::Client::new()
+        .get(app.api_url("/roasters"))
+        .send()
+        .await
+        .expect("failed to execute request")
+        .json()
+        .await
+        .expect("failed to parse");
+    assert!(listed.iter().all(|listed| listed.id != roaster.id));
+}
+
+#[tokio::test]
+async fn bulk_archive_finishes_open_bags() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+    let roast = create_default_roast(&app, roaster.id).await;
+    let bag = create_default_bag(&app, roast.id).await;
+
+    let body = serde_json::json!({ "action": "archive", "ids": [bag.id] });
+    let report: BulkReport = post_bulk(&app, "bags", body.clone())
+        .await
+        .json()
+        .await
+        .expect("failed to parse");
+    assert_eq!(report.succeeded, 1);
+
+    let fetched: BagWithRoast = reqwest::Client::new()
+        .get(app.api_url(&format!("/bags/{}", bag.id)))
+        .send()
+        .await
+        .expect("failed to execute request")
+        .json()
+        .await
+        .expect("failed to parse");
+    assert!(fetched.bag.closed);
+    assert!(fetched.bag.finished_at.is_some());
+
+    let report: BulkReport = post_bulk(&app, "bags", body)
+        .await
+        .json()
+        .await
+        .expect("failed to parse");
+    assert_eq!(report.failed, 1);
+    assert_eq!(report.results[0].error.as_deref(), Some("already finished"));
+}
+
+#[tokio::test]
+async fn bulk_actions_only_run_on_lists_they_fit() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+
+    let response = post_bulk(
+        &app,
+        "roasters",
+        serde_json::json!({ "action": "archive", "ids": [roaster.id] }),
+    )
+    .await;
+    assert_eq!(response.status(), 400);
+
+    let response = post_bulk(
+        &app,
+        "roasters",
+        serde_json::json!({ "action": "delete", "ids": [] }),
+    )
+    .await;
+    assert_eq!(response.status(), 400);
+}
+
+#[tokio::test]
+async fn bulk_assign_roaster_moves_roasts() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+    let roast = create_default_roast(&app, roaster.id).await;
+    let other = create_roaster_with_name(&app, "Other Roasters").await;
+
+    let response = post_bulk(
+        &app,
+        "roasts",
+        serde_json::json!({ "action": "assign_roaster", "roaster_id": 999_999, "ids": [roast.id] }),
+    )
+    .await;
+    assert_eq!(response.status(), 400);
+
+    let report: BulkReport = post_bulk(
+        &app,
+        "roasts",
+        serde_json::json!({ "action": "assign_roaster", "roaster_id": other.id, "ids": [roast.id] }),
+    )
+    .await
+    .json()
+    .await
+    .expect("failed to parse");
+    assert_eq!(report.succeeded, 1);
+
+    let fetched: RoastWithRoaster = reqwest::Client::new()
+        .get(app.api_url(&format!("/roasts/{}", roast.id)))
+        .send()
+        .await
+        .expect("failed to execute request")
+        .json()
+        .await
+        .expect("failed to parse");
+    assert_eq!(fetched.roast.roaster_id, other.id);
+}
+
+#[tokio::test]
+async fn bulk_tag_adds_a_quick_note_to_brews() {
+    let app = spawn_app_with_auth().await;
+    let brew = create_default_brew(&app).await;
+
+    let response = post_bulk(
+        &app,
+        "brews",
+        serde_json::json!({ "action": "tag", "note": "Imported", "ids": [brew.id] }),
+    )
+    .await;
+    assert_eq!(
+        response.status(),
+        400,
+        "undefined custom notes are rejected"
+    );
+
+    let body = serde_json::json!({ "action": "tag", "note": "TooFast", "ids": [brew.id] });
+    let report: BulkReport = post_bulk(&app, "brews", body.clone())
+        .await
+        .json()
+        .await
+        .expect("failed to parse");
+    assert_eq!(report.succeeded, 1);
+    let _: BulkReport = post_bulk(&app, "brews", body)
+        .await
+        .json()
+        .await
+        .expect("failed to parse");
+
+    let fetched: BrewWithDetails = reqwest::Client::new()
+        .get(app.api_url(&format!("/brews/{}", brew.id)))
+        .send()
+        .await
+        .expect("failed to execute request")
+        .json()
+        .await
+        .expect("failed to parse");
+    assert_eq!(fetched.brew.quick_notes, vec![QuickNote::TooFast]);
+}
+
+#[tokio::test]
+async fn data_lists_mark_rows_for_bulk_selection() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+    let session_token = create_session(&app).await;
+
+    let body = reqwest::Client::new()
+        .get(app.page_url("/data?type=roasters"))
+        .header("Cookie", format!("brewlog_session={session_token}"))
+        .send()
+        .await
+        .expect("failed to execute request")
+        .text()
+        .await
+        .expect("failed to read body");
+
+    assert!(body.contains(&format!(r#"data-bulk-id="{}""#, roaster.id)));
+    assert!(body.contains(r#"id="bulk-controls""#));
+}
@@ -1,6 +1,7 @@
-use crate::helpers::{create_default_cafe, spawn_app_with_auth};
+use crate::helpers::{create_default_cafe, create_default_cup, spawn_app_with_auth};
 use crate::test_macros::define_crud_tests;
 use brewlog::domain::cafes::{Cafe, NewCafe, UpdateCafe};
+use brewlog::domain::delete_preview::DeletePreview;
 
 define_crud_tests!(
     entity: cafe,
@@ -289,3 +290,26 @@ async fn deleting_a_cafe_returns_a_204_for_valid_id() {
 
     assert_eq!(get_response.status(), 404);
 }
+
+#[tokio::test]
+async fn delete_preview_counts_cups_at_the_cafe() {
+    let app = spawn_app_with_auth().await;
+    let cup = create_default_cup(&app).await;
+
+    let response = reqwest::Client::new()
+        .get(app.api_url(&format!("/cafes/{}/delete-preview", cup.cafe_id.unwrap())))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .send()
+        .await
+        .expect("Failed to execute request");
+
+    assert_eq!(response.status(), 200);
+    let preview: DeletePreview = response.json().await.expect("Failed to parse preview");
+    assert_eq!(
+        preview,
+        DeletePreview {
+            cups: 1,
+            ..DeletePreview::default()
+        }
+    );
+}
@@ -1,7 +1,8 @@
 use brewlog::domain::cups::Cup;
 
 use crate::helpers::{
-    create_default_cafe, create_default_roast, create_default_roaster, spawn_app_with_auth,
+    create_default_bag, create_default_cafe, create_default_roast, create_default_roaster,
+    spawn_app_with_auth,
 };
 
 #[tokio::test]
@@ -30,7 +31,7 @@ async fn checkin_with_existing_cafe_creates_cup() {
 
     let cup: Cup = response.json().await.expect("Failed to parse response");
     assert_eq!(cup.roast_id, roast.id);
-    assert_eq!(cup.cafe_id, cafe.id);
+    assert_eq!(cup.cafe_id, Some(cafe.id));
 }
 
 #[tokio::test]
@@ -140,3 +141,45 @@ async fn checkin_requires_cafe_name_when_no_cafe_id() {
 
     assert_eq!(response.status(), 400);
 }
+
+#[tokio::test]
+async fn checkin_away_from_a_cafe_takes_the_roast_from_the_bag() {
+    let app = spawn_app_with_auth().await;
+    let client = reqwest::Client::new();
+
+    let roaster = create_default_roaster(&app).await;
+    let roast = create_default_roast(&app, roaster.id).await;
+    let bag = create_default_bag(&app, roast.id).await;
+
+    let payload = serde_json::json!({
+        "no_cafe": "true",
+        "cafe_name": "Elsewhere",
+        "roast_id": "",
+        "bag_id": bag.id.to_string(),
+    });
+
+    let response = client
+        .post(app.api_url("/check-in"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&payload)
+        .send()
+        .await
+        .expect("Failed to execute request");
+
+    assert_eq!(response.status(), 201);
+
+    let cup: Cup = response.json().await.expect("Failed to parse response");
+    assert_eq!(cup.roast_id, roast.id);
+    assert_eq!(cup.cafe_id, None);
+    assert_eq!(cup.bag_id, Some(bag.id));
+
+    let cafes: Vec<serde_json::Value> = client
+        .get(app.api_url("/cafes"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert!(cafes.is_empty());
+}
@@ -0,0 +1,251 @@
+use brewlog::domain::comments::Comment;
+use reqwest::Client;
+use serde_json::json;
+
+use super::helpers::{
+    TestApp, create_default_brew, create_default_roast, create_default_roaster, create_second_user,
+    create_session, spawn_app_with_auth,
+};
+
+async fn post_comment(app: &TestApp, token: &str, path: &str, body: &str) -> reqwest::Response {
+    Client::new()
+        .post(app.api_url(path))
+        .bearer_auth(token)
+        .json(&json!({ "body": body }))
+        .send()
+        .await
+        .expect("failed to execute request")
+}
+
+#[tokio::test]
+async fn commenting_requires_authentication() {
+    let app = spawn_app_with_auth().await;
+    let brew = create_default_brew(&app).await;
+
+    let response = Client::new()
+        .post(app.api_url(&format!("/brews/{}/comments", brew.id)))
+        .json(&json!({ "body": "Lovely" }))
+        .send()
+        .await
+        .expect("failed to execute request");
+
+    assert_eq!(response.status(), 401);
+}
+
+#[tokio::test]
+async fn comments_on_a_brew_are_listed_oldest_first() {
+    let app = spawn_app_with_auth().await;
+    let token = app.auth_token.clone().unwrap();
+    let brew = create_default_brew(&app).await;
+    let path = format!("/brews/{}/comments", brew.id);
+
+    let response = post_comment(&app, &token, &path, "  Bit sour today ").await;
+    assert_eq!(response.status(), 201);
+    let created: Comment = response.json().await.unwrap();
+    assert_eq!(created.body, "Bit sour today");
+    assert_eq!(created.brew_id, Some(brew.id));
+    assert_eq!(created.author, "admin");
+
+    post_comment(&app, &token, &path, "Finer next time").await;
+
+    let comments: Vec<Comment> = Client::new()
+        .get(app.api_url(&path))
+        .send()
+        .await
+        .expect("failed to execute request")
+        .json()
+        .await
+        .unwrap();
+    let bodies: Vec<_> = comments.iter().map(|c| c.body.as_str()).collect();
+    assert_eq!(bodies, ["Bit sour today", "Finer next time"]);
+}
+
+#[tokio::test]
+async fn roasts_have_their_own_threads() {
+    let app = spawn_app_with_auth().await;
+    let token = app.auth_token.clone().unwrap();
+    let roaster = create_default_roaster(&app).await;
+    let roast = create_default_roast(&app, roaster.id).await;
+    let path = format!("/roasts/{}/comments", roast.id);
+
+    let response = post_comment(&app, &token, &path, "Great as espresso").await;
+    assert_eq!(response.status(), 201);
+    let created: Comment = response.json().await.unwrap();
+    assert_eq!(created.roast_id, Some(roast.id));
+    assert_eq!(created.brew_id, None);
+}
+
+#[tokio::test]
+async fn empty_comments_and_unknown_targets_are_rejected() {
+    let app = spawn_app_with_auth().await;
+    let token = app.auth_token.clone().unwrap();
+    let brew = create_default_brew(&app).await;
+
+    let response = post_comment(&app, &token, &format!("/brews/{}/comments", brew.id), "  ").await;
+    assert_eq!(response.status(), 400);
+
+    let response = post_comment(&app, &token, "/brews/999999/comments", "Hello").await;
+    assert_eq!(response.status(), 404);
+
+    let response = Client::new()
+        .get(app.api_url("/roasts/999999/comments"))
+        .send()
+        .await
+        .expect("failed to execute request");
+    assert_eq!(response.status(), 404);
+}
+
+#[tokio::test]
+async fn authors_can_edit_and_delete_their_comments() {
+    let app = spawn_app_with_auth().await;
+    let token = app.auth_token.clone().unwrap();
+    let brew = create_default_brew(&app).await;
+    let path = format!("/brews/{}/comments", brew.id);
+
+    let created: Comment = post_comment(&app, &token, &path, "Too hot")
+        .await
+        .json()
+        .await
+        .unwrap();
+
+    let response = Client::new()
+        .put(app.api_url(&format!("/comments/{}", created.id)))
+        .bearer_auth(&token)
+        .json(&json!({ "body": "Too hot, try 92°C" }))
+        .send()
+        .await
+        .expect("failed to execute request");
+    assert_eq!(response.status(), 200);
+    let updated: Comment = response.json().await.unwrap();
+    assert_eq!(updated.body, "Too hot, try 92°C");
+    assert!(updated.updated_at >= created.updated_at);
+
+    let response = Client::new()
+        .delete(app.api_url(&format!("/comments/{}", created.id)))
+        .bearer_auth(&token)
+        .send()
+        .await
+        .expect("failed to execute request");
+    assert_eq!(response.status(), 204);
+
+    let comments: Vec<Comment> = Client::new()
+        .get(app.api_url(&path))
+        .send()
+        .await
+        .expect("failed to execute request")
+        .json()
+        .await
+        .unwrap();
+    assert!(comments.is_empty());
+}
+
+#[tokio::test]
+async fn other_users_cannot_edit_or_delete_a_comment() {
+    let app = spawn_app_with_auth().await;
+    let brew = create_default_brew(&app).await;
+    let created: Comment = post_comment(
+        &app,
+        app.auth_token.as_ref().unwrap(),
+        &format!("/brews/{}/comments", brew.id),
+        "Mine",
+    )
+    .await
+    .json()
+    .await
+    .unwrap();
+    let housemate = create_second_user(&app, "housemate").await.token;
+
+    let response = Client::new()
+        .put(app.api_url(&format!("/comments/{}", created.id)))
+        .bearer_auth(&housemate)
+        .json(&json!({ "body": "Not yours" }))
+        .send()
+        .await
+        .expect("failed to execute request");
+    assert_eq!(response.status(), 404);
+
+    let response = Client::new()
+        .delete(app.api_url(&format!("/comments/{}", created.id)))
+        .bearer_auth(&housemate)
+        .send()
+        .await
+        .expect("failed to execute request");
+    assert_eq!(response.status(), 404);
+
+    // Housemates can still join the thread.
+    let response = post_comment(
+        &app,
+        &housemate,
+        &format!("/brews/{}/comments", brew.id),
+        "Agreed",
+    )
+    .await;
+    assert_eq!(response.status(), 201);
+}
+
+#[tokio::test]
+async fn deleting_a_brew_removes_its_comments() {
+    let app = spawn_app_with_auth().await;
+    let token = app.auth_token.clone().unwrap();
+    let brew = create_default_brew(&app).await;
+    post_comment(&app, &token, &format!("/brews/{}/comments", brew.id), "Bye").await;
+
+    let response = Client::new()
+        .delete(app.api_url(&format!("/brews/{}", brew.id)))
+        .bearer_auth(&token)
+        .send()
+        .await
+        .expect("failed to execute request");
+    assert!(response.status().is_success());
+
+    let response = Client::new()
+        .get(app.api_url(&format!("/brews/{}/comments", brew.id)))
+        .send()
+        .await
+        .expect("failed to execute request");
+    assert_eq!(response.status(), 404);
+}
+
+#[tokio::test]
+async fn brew_page_shows_the_thread_with_controls_for_own_comments() {
+    let app = spawn_app_with_auth().await;
+    let token = app.auth_token.clone().unwrap();
+    let brew = create_default_brew(&app).await;
+    post_comment(
+        &app,
+        &token,
+        &format!("/brews/{}/comments", brew.id),
+        "Nice <b>body</b>",
+    )
+    .await;
+    let session = create_session(&app).await;
+
+    let body = Client::new()
+        .get(app.page_url(&format!("/brews/{}", brew.id)))
+        .header("Cookie", format!("brewlog_session={session}"))
+        .send()
+        .await
+        .expect("failed to fetch page")
+        .text()
+        .await
+        .unwrap();
+    assert!(body.contains("Nice"));
+    assert!(
+        !body.contains("<b>body</b>"),
+        "comment bodies must be escaped"
+    );
+    assert!(body.contains("aria-label=\"Edit comment\""));
+    assert!(body.contains("Add a comment"));
+
+    let public = Client::new()
+        .get(app.page_url(&format!("/brews/{}", brew.id)))
+        .send()
+        .await
+        .expect("failed to fetch page")
+        .text()
+        .await
+        .unwrap();
+    assert!(public.contains("Nice"));
+    assert!(!public.contains("aria-label=\"Edit comment\""));
+    assert!(!public.contains("Add a comment"));
+}
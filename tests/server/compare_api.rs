@@ -0,0 +1,144 @@
+use brewlog::domain::roasts::{NewRoast, Roast, RoastComparison};
+
+use super::helpers::{
+    create_default_brew, create_default_roaster, create_entity, spawn_app, spawn_app_with_auth,
+};
+
+fn kenyan_roast(roaster_id: brewlog::domain::ids::RoasterId, name: &str) -> NewRoast {
+    NewRoast {
+        roaster_id,
+        name: name.to_string(),
+        origin: "Kenya".to_string(),
+        region: "Nyeri".to_string(),
+        producer: "Gatomboya Cooperative".to_string(),
+        tasting_notes: vec!["Blackcurrant".to_string(), "Grapefruit".to_string()],
+        process: "Washed".to_string(),
+        components: Vec::new(),
+        harvest_year: None,
+        created_at: None,
+    }
+}
+
+#[tokio::test]
+async fn comparing_roasts_returns_each_with_brew_stats() {
+    let app = spawn_app_with_auth().await;
+    let brew = create_default_brew(&app).await;
+    let brewed = app
+        .roast_repo
+        .list_all()
+        .await
+        .expect("failed to list roasts")
+        .remove(0);
+    let unbrewed: Roast = create_entity(
+        &app,
+        "/roasts",
+        &kenyan_roast(brewed.roast.roaster_id, "Karogoto AA"),
+    )
+    .await;
+
+    let response = reqwest::Client::new()
+        .get(app.api_url(&format!(
+            "/compare?roast={}&roast={}",
+            brewed.roast.id, unbrewed.id
+        )))
+        .send()
+        .await
+        .expect("failed to send request");
+
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    let comparison: Vec<RoastComparison> = response.json().await.expect("failed to parse");
+    assert_eq!(comparison.len(), 2);
+    assert_eq!(comparison[0].roast.roast.id, brewed.roast.id);
+    assert_eq!(comparison[0].brew_stats.brew_count, 1);
+    assert_eq!(
+        comparison[0].brew_stats.total_coffee_grams,
+        brew.coffee_weight
+    );
+    assert_eq!(comparison[1].roast.roast.name, "Karogoto AA");
+    assert_eq!(comparison[1].brew_stats.brew_count, 0);
+}
+
+#[tokio::test]
+async fn comparing_roasts_validates_the_selection() {
+    let app = spawn_app().await;
+    let client = reqwest::Client::new();
+
+    for query in [
+        "",
+        "?roast=1",
+        "?roast=1&roast=1",
+        "?roast=abc&roast=2",
+        "?roast=1&roast=2&roast=3&roast=4&roast=5",
+    ] {
+        let response = client
+            .get(app.api_url(&format!("/compare{query}")))
+            .send()
+            .await
+            .expect("failed to send request");
+
+        assert_eq!(
+            response.status(),
+            reqwest::StatusCode::BAD_REQUEST,
+            "{query}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn comparing_a_missing_roast_returns_404() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+    let roast: Roast = create_entity(&app, "/roasts", &kenyan_roast(roaster.id, "Kiriga AB")).await;
+
+    let response = reqwest::Client::new()
+        .get(app.api_url(&format!("/compare?roast={}&roast=999999", roast.id)))
+        .send()
+        .await
+        .expect("failed to send request");
+
+    assert_eq!(response.status(), reqwest::StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn compare_page_renders_a_side_by_side_table() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+    let first: Roast =
+        create_entity(&app, "/roasts", &kenyan_roast(roaster.id, "Karogoto AA")).await;
+    let second: Roast =
+        create_entity(&app, "/roasts", &kenyan_roast(roaster.id, "Kiriga AB")).await;
+
+    let body = reqwest::Client::new()
+        .get(app.page_url(&format!("/compare?roast={}&roast={}", first.id, second.id)))
+        .send()
+        .await
+        .expect("failed to send request")
+        .text()
+        .await
+        .expect("failed to read body");
+
+    assert!(body.contains("Compare Roasts"));
+    assert!(body.contains("Karogoto AA"));
+    assert!(body.contains("Kiriga AB"));
+    assert!(body.contains("Blackcurrant"));
+    assert!(body.contains("Average Ratio"));
+}
+
+#[tokio::test]
+async fn compare_page_with_one_roast_only_prefills_the_picker() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+    let roast: Roast =
+        create_entity(&app, "/roasts", &kenyan_roast(roaster.id, "Karogoto AA")).await;
+
+    let response = reqwest::Client::new()
+        .get(app.page_url(&format!("/compare?roast={}", roast.id)))
+        .send()
+        .await
+        .expect("failed to send request");
+
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    let body = response.text().await.expect("failed to read body");
+    assert!(body.contains("Choose a roast"));
+    assert!(!body.contains("Average Ratio"));
+}
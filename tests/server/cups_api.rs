@@ -1,10 +1,11 @@
 use crate::helpers::{
-    create_cafe_with_payload, create_default_cafe, create_default_roast, create_default_roaster,
-    spawn_app_with_auth,
+    create_cafe_with_payload, create_default_bag, create_default_cafe, create_default_cup,
+    create_default_roast, create_default_roaster, create_roast_with_payload, spawn_app_with_auth,
 };
 use crate::test_macros::define_crud_tests;
 use brewlog::domain::cups::{Cup, CupWithDetails, NewCup};
-use brewlog::domain::ids::{CafeId, RoastId};
+use brewlog::domain::ids::{CafeId, RoastId, RoasterId};
+use brewlog::domain::roasts::{NewRoast, Roast};
 
 define_crud_tests!(
     entity: cup,
@@ -23,7 +24,9 @@ async fn creating_a_cup_returns_a_201_for_valid_data() {
 
     let new_cup = NewCup {
         roast_id: roast.id,
-        cafe_id: cafe.id,
+        cafe_id: Some(cafe.id),
+        bag_id: None,
+        rating: None,
         created_at: None,
     };
 
@@ -39,7 +42,7 @@ async fn creating_a_cup_returns_a_201_for_valid_data() {
 
     let cup: Cup = response.json().await.expect("Failed to parse response");
     assert_eq!(cup.roast_id, roast.id);
-    assert_eq!(cup.cafe_id, cafe.id);
+    assert_eq!(cup.cafe_id, Some(cafe.id));
 }
 
 #[tokio::test]
@@ -49,7 +52,9 @@ async fn creating_a_cup_requires_authentication() {
 
     let new_cup = NewCup {
         roast_id: RoastId::new(1),
-        cafe_id: CafeId::new(1),
+        cafe_id: Some(CafeId::new(1)),
+        bag_id: None,
+        rating: None,
         created_at: None,
     };
 
@@ -74,7 +79,9 @@ async fn listing_cups_returns_a_200_with_enriched_data() {
 
     let new_cup = NewCup {
         roast_id: roast.id,
-        cafe_id: cafe.id,
+        cafe_id: Some(cafe.id),
+        bag_id: None,
+        rating: None,
         created_at: None,
     };
 
@@ -98,7 +105,7 @@ async fn listing_cups_returns_a_200_with_enriched_data() {
     assert_eq!(cups.len(), 1);
     assert_eq!(cups[0].roast_name, "Test Roast");
     assert_eq!(cups[0].roaster_name, "Test Roasters");
-    assert_eq!(cups[0].cafe_name, "Blue Bottle");
+    assert_eq!(cups[0].place_name(), "Blue Bottle");
 }
 
 #[tokio::test]
@@ -112,7 +119,9 @@ async fn getting_a_cup_returns_a_200_with_details() {
 
     let new_cup = NewCup {
         roast_id: roast.id,
-        cafe_id: cafe.id,
+        cafe_id: Some(cafe.id),
+        bag_id: None,
+        rating: None,
         created_at: None,
     };
 
@@ -140,7 +149,7 @@ async fn getting_a_cup_returns_a_200_with_details() {
     let fetched: CupWithDetails = response.json().await.expect("Failed to parse response");
     assert_eq!(fetched.cup.id, cup.id);
     assert_eq!(fetched.roast_name, "Test Roast");
-    assert_eq!(fetched.cafe_name, "Blue Bottle");
+    assert_eq!(fetched.place_name(), "Blue Bottle");
 }
 
 #[tokio::test]
@@ -154,7 +163,9 @@ async fn deleting_a_cup_returns_a_204_for_valid_id() {
 
     let new_cup = NewCup {
         roast_id: roast.id,
-        cafe_id: cafe.id,
+        cafe_id: Some(cafe.id),
+        bag_id: None,
+        rating: None,
         created_at: None,
     };
 
@@ -201,7 +212,9 @@ async fn updating_a_cup_returns_a_200_for_valid_data() {
 
     let new_cup = NewCup {
         roast_id: roast.id,
-        cafe_id: cafe1.id,
+        cafe_id: Some(cafe1.id),
+        bag_id: None,
+        rating: None,
         created_at: None,
     };
 
@@ -247,7 +260,7 @@ async fn updating_a_cup_returns_a_200_for_valid_data() {
 
     assert_eq!(response.status(), 200);
     let updated: Cup = response.json().await.expect("Failed to parse response");
-    assert_eq!(updated.cafe_id, cafe2.id);
+    assert_eq!(updated.cafe_id, Some(cafe2.id));
     assert_eq!(updated.roast_id, roast.id); // unchanged
 }
 
@@ -281,7 +294,9 @@ async fn updating_a_cup_with_no_changes_returns_400() {
 
     let new_cup = NewCup {
         roast_id: roast.id,
-        cafe_id: cafe.id,
+        cafe_id: Some(cafe.id),
+        bag_id: None,
+        rating: None,
         created_at: None,
     };
 
@@ -330,3 +345,159 @@ async fn updating_a_nonexistent_cup_returns_404() {
 
     assert_eq!(response.status(), 404);
 }
+
+async fn create_other_roast(app: &crate::helpers::TestApp, roaster_id: RoasterId) -> Roast {
+    create_roast_with_payload(
+        app,
+        NewRoast {
+            roaster_id,
+            name: "Other Roast".to_string(),
+            origin: "Kenya".to_string(),
+            region: "Nyeri".to_string(),
+            producer: "Gatomboya".to_string(),
+            tasting_notes: vec!["Blackcurrant".to_string()],
+            process: "Washed".to_string(),
+            components: Vec::new(),
+            harvest_year: None,
+            created_at: None,
+        },
+    )
+    .await
+}
+
+async fn post_cup(app: &crate::helpers::TestApp, payload: serde_json::Value) -> reqwest::Response {
+    reqwest::Client::new()
+        .post(app.api_url("/cups"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&payload)
+        .send()
+        .await
+        .expect("Failed to create cup")
+}
+
+#[tokio::test]
+async fn a_cup_can_be_logged_without_a_cafe() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+    let roast = create_default_roast(&app, roaster.id).await;
+
+    let response = post_cup(&app, serde_json::json!({ "roast_id": roast.id })).await;
+    assert_eq!(response.status(), 201);
+    let cup: Cup = response.json().await.unwrap();
+    assert_eq!(cup.cafe_id, None);
+
+    let fetched: CupWithDetails = reqwest::Client::new()
+        .get(app.api_url(&format!("/cups/{}", cup.id)))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(fetched.cafe_name, None);
+    assert_eq!(fetched.place_name(), "Elsewhere");
+
+    let body = reqwest::Client::new()
+        .get(app.page_url(&format!("/cups/{}", cup.id)))
+        .send()
+        .await
+        .unwrap()
+        .text()
+        .await
+        .unwrap();
+    assert!(body.contains("Elsewhere"));
+}
+
+#[tokio::test]
+async fn a_cup_can_be_linked_to_a_bag_of_its_roast() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+    let roast = create_default_roast(&app, roaster.id).await;
+    let bag = create_default_bag(&app, roast.id).await;
+
+    let response = post_cup(
+        &app,
+        serde_json::json!({ "roast_id": roast.id, "bag_id": bag.id }),
+    )
+    .await;
+    assert_eq!(response.status(), 201);
+    let cup: Cup = response.json().await.unwrap();
+    assert_eq!(cup.bag_id, Some(bag.id));
+
+    let body = reqwest::Client::new()
+        .get(app.page_url(&format!("/cups/{}", cup.id)))
+        .send()
+        .await
+        .unwrap()
+        .text()
+        .await
+        .unwrap();
+    assert!(body.contains(&format!("href=\"/bags/{}\"", bag.id)));
+}
+
+#[tokio::test]
+async fn a_bag_of_a_different_roast_is_rejected() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+    let roast = create_default_roast(&app, roaster.id).await;
+    let other = create_other_roast(&app, roaster.id).await;
+    let bag = create_default_bag(&app, other.id).await;
+
+    let response = post_cup(
+        &app,
+        serde_json::json!({ "roast_id": roast.id, "bag_id": bag.id }),
+    )
+    .await;
+
+    assert_eq!(response.status(), 400);
+}
+
+#[tokio::test]
+async fn clearing_the_cafe_moves_a_cup_elsewhere() {
+    let app = spawn_app_with_auth().await;
+    let cup = create_default_cup(&app).await;
+    assert!(cup.cafe_id.is_some());
+
+    let response = reqwest::Client::new()
+        .put(app.api_url(&format!("/cups/{}", cup.id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&serde_json::json!({ "cafe_id": null }))
+        .send()
+        .await
+        .expect("Failed to update cup");
+
+    assert_eq!(response.status(), 200);
+    let updated: CupWithDetails = response.json().await.unwrap();
+    assert_eq!(updated.cup.cafe_id, None);
+    assert_eq!(updated.place_name(), "Elsewhere");
+}
+
+#[tokio::test]
+async fn changing_the_roast_unlinks_the_old_bag() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+    let roast = create_default_roast(&app, roaster.id).await;
+    let other = create_other_roast(&app, roaster.id).await;
+    let bag = create_default_bag(&app, roast.id).await;
+    let cup: Cup = post_cup(
+        &app,
+        serde_json::json!({ "roast_id": roast.id, "bag_id": bag.id }),
+    )
+    .await
+    .json()
+    .await
+    .unwrap();
+
+    let response = reqwest::Client::new()
+        .put(app.api_url(&format!("/cups/{}", cup.id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&serde_json::json!({ "roast_id": other.id }))
+        .send()
+        .await
+        .expect("Failed to update cup");
+
+    assert_eq!(response.status(), 200);
+    let updated: CupWithDetails = response.json().await.unwrap();
+    assert_eq!(updated.cup.roast_id, other.id);
+    assert_eq!(updated.cup.bag_id, None);
+}
@@ -0,0 +1,179 @@
+use brewlog::domain::bags::BagWithRoast;
+use brewlog::domain::currencies::ExchangeRate;
+use brewlog::domain::ids::RoastId;
+use brewlog::domain::stats::CachedStats;
+use reqwest::Client;
+use serde_json::{Value, json};
+
+use crate::helpers::{
+    TestApp, create_default_roast, create_default_roaster, create_session, spawn_app_with_auth,
+};
+
+async fn set_base_currency(app: &TestApp, currency: &str) -> reqwest::Response {
+    Client::new()
+        .put(app.api_url("/settings"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&json!({ "base_currency": currency }))
+        .send()
+        .await
+        .expect("failed to execute request")
+}
+
+async fn set_rate(app: &TestApp, currency: &str, rate: f64) -> reqwest::Response {
+    Client::new()
+        .put(app.api_url(&format!("/exchange-rates/{currency}")))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&json!({ "rate": rate }))
+        .send()
+        .await
+        .expect("failed to execute request")
+}
+
+async fn list_rates(app: &TestApp) -> Vec<ExchangeRate> {
+    Client::new()
+        .get(app.api_url("/exchange-rates"))
+        .send()
+        .await
+        .expect("failed to execute request")
+        .json()
+        .await
+        .unwrap()
+}
+
+async fn buy_bag(app: &TestApp, roast_id: RoastId, price: f64, currency: Option<&str>) {
+    let response = Client::new()
+        .post(app.api_url("/bags"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&json!({
+            "roast_id": roast_id,
+            "amount": 250.0,
+            "price": price,
+            "currency": currency,
+        }))
+        .send()
+        .await
+        .expect("failed to execute request");
+    assert_eq!(response.status(), 201);
+}
+
+async fn recompute(app: &TestApp) -> CachedStats {
+    Client::new()
+        .post(app.api_url("/stats/recompute"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .send()
+        .await
+        .expect("failed to execute request")
+        .json()
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn bag_currencies_are_upper_cased_and_checked() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+    let roast = create_default_roast(&app, roaster.id).await;
+
+    let response = Client::new()
+        .post(app.api_url("/bags"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&json!({ "roast_id": roast.id, "amount": 250.0, "price": 18, "currency": " eur " }))
+        .send()
+        .await
+        .expect("failed to execute request");
+    assert_eq!(response.status(), 201);
+    let bag: BagWithRoast = response.json().await.unwrap();
+    assert_eq!(bag.bag.currency.as_deref(), Some("EUR"));
+
+    let response = Client::new()
+        .post(app.api_url("/bags"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&json!({ "roast_id": roast.id, "amount": 250.0, "currency": "euros" }))
+        .send()
+        .await
+        .expect("failed to execute request");
+    assert_eq!(response.status(), 422);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["fields"][0]["field"], "currency");
+}
+
+#[tokio::test]
+async fn exchange_rates_need_auth_and_a_base_currency() {
+    let app = spawn_app_with_auth().await;
+
+    let response = Client::new()
+        .put(app.api_url("/exchange-rates/EUR"))
+        .json(&json!({ "rate": 0.85 }))
+        .send()
+        .await
+        .expect("failed to execute request");
+    assert_eq!(response.status(), 401);
+
+    assert_eq!(set_rate(&app, "EUR", 0.85).await.status(), 400);
+
+    assert_eq!(set_base_currency(&app, "gbp").await.status(), 200);
+    assert_eq!(set_rate(&app, "GBP", 1.0).await.status(), 400);
+    assert_eq!(set_rate(&app, "EUR", 0.0).await.status(), 400);
+    assert_eq!(set_rate(&app, "EURO", 0.85).await.status(), 400);
+
+    let response = set_rate(&app, "eur", 0.85).await;
+    assert_eq!(response.status(), 200);
+    let rate: ExchangeRate = response.json().await.unwrap();
+    assert_eq!(rate.currency, "EUR");
+    assert_eq!(list_rates(&app).await, [rate]);
+
+    let response = Client::new()
+        .delete(app.api_url("/exchange-rates/EUR"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .send()
+        .await
+        .expect("failed to execute request");
+    assert_eq!(response.status(), 204);
+    assert!(list_rates(&app).await.is_empty());
+}
+
+#[tokio::test]
+async fn changing_the_base_currency_clears_the_rates() {
+    let app = spawn_app_with_auth().await;
+    set_base_currency(&app, "GBP").await;
+    set_rate(&app, "EUR", 0.85).await;
+
+    set_base_currency(&app, "GBP").await;
+    assert_eq!(list_rates(&app).await.len(), 1, "same currency keeps rates");
+
+    set_base_currency(&app, "USD").await;
+    assert!(list_rates(&app).await.is_empty());
+}
+
+#[tokio::test]
+async fn spend_is_totalled_in_the_base_currency() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+    let roast = create_default_roast(&app, roaster.id).await;
+    set_base_currency(&app, "GBP").await;
+    set_rate(&app, "EUR", 0.85).await;
+    buy_bag(&app, roast.id, 12.0, None).await;
+    buy_bag(&app, roast.id, 10.0, Some("GBP")).await;
+    buy_bag(&app, roast.id, 20.0, Some("EUR")).await;
+    buy_bag(&app, roast.id, 2000.0, Some("JPY")).await;
+
+    let stats = recompute(&app).await;
+
+    assert!((stats.spend.total - 39.0).abs() < 1e-9);
+    assert_eq!(stats.spend.priced_bags, 4);
+    assert_eq!(stats.spend.base_currency.as_deref(), Some("GBP"));
+    assert_eq!(stats.spend.unconverted, ["JPY"]);
+
+    let session = create_session(&app).await;
+    let page = Client::new()
+        .get(app.page_url("/stats"))
+        .header("Cookie", format!("brewlog_session={session}"))
+        .send()
+        .await
+        .expect("failed to fetch page")
+        .text()
+        .await
+        .unwrap();
+    assert!(page.contains("39.00 GBP"));
+    assert!(page.contains("17.00 GBP"));
+}
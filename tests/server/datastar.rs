@@ -197,6 +197,8 @@ async fn roasts_create_with_datastar_header_returns_fragment() {
         producer: "Test Farm".to_string(),
         tasting_notes: vec!["Blueberry".to_string()],
         process: "Washed".to_string(),
+        components: Vec::new(),
+        harvest_year: None,
         created_at: None,
     };
 
@@ -242,6 +244,82 @@ async fn roast_options_with_datastar_header_returns_fragment() {
     assert_html_fragment(&body);
 }
 
+#[tokio::test]
+async fn quick_add_roaster_returns_the_picker_with_it_selected() {
+    let app = spawn_app_with_auth().await;
+    create_default_roaster(&app).await;
+    let client = Client::new();
+
+    let response = client
+        .post(app.api_url("/roasters/quick-add"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .header("datastar-request", "true")
+        .form(&[("name", "Quick Roasters"), ("country", "Norway")])
+        .send()
+        .await
+        .expect("failed to quick-add roaster");
+
+    assert_eq!(response.status(), 200);
+    assert_datastar_headers(&response, "#roast-roaster-select");
+
+    let body = response.text().await.expect("failed to read body");
+    assert_html_fragment(&body);
+    assert!(body.contains("Quick Roasters"));
+    assert!(
+        body.contains("Test Roasters"),
+        "Other roasters stay pickable"
+    );
+
+    let roasters: Vec<brewlog::domain::roasters::Roaster> = client
+        .get(app.api_url("/roasters"))
+        .send()
+        .await
+        .expect("failed to list roasters")
+        .json()
+        .await
+        .expect("failed to parse JSON");
+    let added = roasters
+        .iter()
+        .find(|roaster| roaster.name == "Quick Roasters")
+        .expect("roaster should have been created");
+    assert!(body.contains(&format!(r#"initial-value="{}""#, added.id)));
+}
+
+#[tokio::test]
+async fn quick_add_roaster_picks_an_existing_roaster() {
+    let app = spawn_app_with_auth().await;
+    let existing = create_default_roaster(&app).await;
+
+    let response = Client::new()
+        .post(app.api_url("/roasters/quick-add"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&serde_json::json!({ "name": "Test Roasters", "country": "UK" }))
+        .send()
+        .await
+        .expect("failed to quick-add roaster");
+
+    assert_eq!(response.status(), 200);
+    let roaster: brewlog::domain::roasters::Roaster =
+        response.json().await.expect("failed to parse JSON");
+    assert_eq!(roaster.id, existing.id);
+}
+
+#[tokio::test]
+async fn quick_add_roaster_needs_a_country() {
+    let app = spawn_app_with_auth().await;
+
+    let response = Client::new()
+        .post(app.api_url("/roasters/quick-add"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .header("datastar-request", "true")
+        .form(&[("name", "Quick Roasters"), ("country", "")])
+        .send()
+        .await
+        .expect("failed to quick-add roaster");
+
+    assert!(response.status().is_client_error());
+}
+
 #[tokio::test]
 async fn roast_options_without_datastar_header_returns_json() {
     let app = spawn_app_with_auth().await;
@@ -527,6 +605,13 @@ async fn gear_update_without_datastar_header_returns_json() {
     let update = brewlog::domain::gear::UpdateGear {
         make: Some("JSON Updated".to_string()),
         model: None,
+        water_temp: None,
+        brew_method: None,
+        uses_filter: None,
+        temp_offset: None,
+        water_hardness: None,
+        stock_count: None,
+        low_stock_threshold: None,
         created_at: None,
     };
 
@@ -792,3 +877,127 @@ async fn cups_update_with_datastar_header_returns_redirect_script() {
         "Expected redirect script in body"
     );
 }
+
+// ============================================================================
+// Row-level fragments (?fragment=row)
+// ============================================================================
+
+#[tokio::test]
+async fn roasters_create_with_row_fragment_prepends_only_the_new_row() {
+    let app = spawn_app_with_auth().await;
+    create_default_roaster(&app).await;
+    let client = Client::new();
+
+    let new_roaster = NewRoaster {
+        name: "Row Fragment Roasters".to_string(),
+        country: "UK".to_string(),
+        city: None,
+        homepage: None,
+        created_at: None,
+    };
+
+    let response = client
+        .post(app.api_url("/roasters?fragment=row"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .header("datastar-request", "true")
+        .header("referer", format!("{}/data?type=roasters", app.address))
+        .json(&new_roaster)
+        .send()
+        .await
+        .expect("failed to create roaster");
+
+    assert_eq!(response.status(), 200);
+    assert_datastar_headers_with_mode(&response, "#roaster-list tbody", "prepend");
+
+    let body = response.text().await.expect("failed to read body");
+    assert!(
+        body.trim_start().starts_with("<tr"),
+        "Expected a single row"
+    );
+    assert!(body.contains("Row Fragment Roasters"));
+    assert!(
+        !body.contains("Test Roasters"),
+        "Row fragment should not include other roasters"
+    );
+}
+
+#[tokio::test]
+async fn roasters_create_with_row_fragment_falls_back_to_list_when_sorted_by_name() {
+    let app = spawn_app_with_auth().await;
+    let client = Client::new();
+
+    let new_roaster = NewRoaster {
+        name: "Sorted Roasters".to_string(),
+        country: "UK".to_string(),
+        city: None,
+        homepage: None,
+        created_at: None,
+    };
+
+    let response = client
+        .post(app.api_url("/roasters?fragment=row&sort=name&dir=asc"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .header("datastar-request", "true")
+        .header("referer", format!("{}/data?type=roasters", app.address))
+        .json(&new_roaster)
+        .send()
+        .await
+        .expect("failed to create roaster");
+
+    assert_eq!(response.status(), 200);
+    assert_datastar_headers(&response, "#roaster-list");
+}
+
+#[tokio::test]
+async fn gear_update_with_row_fragment_replaces_the_row() {
+    let app = spawn_app_with_auth().await;
+    let gear = create_default_gear(&app, "grinder", "Baratza", "Encore").await;
+    let client = Client::new();
+
+    let response = client
+        .put(app.api_url(&format!("/gear/{}?fragment=row", gear.id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .header("datastar-request", "true")
+        .header("referer", format!("{}/data?type=gear", app.address))
+        .json(&serde_json::json!({ "model": "Encore ESP" }))
+        .send()
+        .await
+        .expect("failed to update gear");
+
+    assert_eq!(response.status(), 200);
+    let selector = format!("#gear-row-{}", gear.id);
+    assert_datastar_headers_with_mode(&response, &selector, "replace");
+
+    let body = response.text().await.expect("failed to read body");
+    assert!(body.contains(&format!(r#"id="gear-row-{}""#, gear.id)));
+    assert!(body.contains("Encore ESP"));
+}
+
+#[tokio::test]
+async fn roasts_update_with_row_fragment_replaces_the_row() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+    let roast = create_default_roast(&app, roaster.id).await;
+    let client = Client::new();
+
+    let response = client
+        .put(app.api_url(&format!("/roasts/{}?fragment=row", roast.id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .header("datastar-request", "true")
+        .header("referer", format!("{}/data?type=roasts", app.address))
+        .json(&serde_json::json!({ "name": "Renamed Roast" }))
+        .send()
+        .await
+        .expect("failed to update roast");
+
+    assert_eq!(response.status(), 200);
+    let selector = format!("#roast-row-{}", roast.id);
+    assert_datastar_headers_with_mode(&response, &selector, "replace");
+
+    let body = response.text().await.expect("failed to read body");
+    assert!(body.contains("Renamed Roast"));
+    assert!(
+        body.contains("Test Roasters"),
+        "Row keeps the roaster label"
+    );
+}
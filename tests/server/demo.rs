@@ -0,0 +1,55 @@
+use brewlog::domain::roasters::Roaster;
+use brewlog::infrastructure::demo::sample_backup;
+
+use crate::helpers::spawn_demo_app;
+
+#[tokio::test]
+async fn demo_serves_the_bundled_sample_data() {
+    let app = spawn_demo_app().await;
+    let client = reqwest::Client::new();
+    let sample = sample_backup().expect("sample data should parse");
+
+    let response = client
+        .get(app.api_url("/roasters"))
+        .send()
+        .await
+        .expect("Failed to execute request");
+
+    assert_eq!(response.status(), 200);
+    let roasters: Vec<Roaster> = response.json().await.expect("Failed to parse response");
+    assert_eq!(roasters.len(), sample.roasters.len());
+}
+
+#[tokio::test]
+async fn demo_rejects_writes_even_when_authenticated() {
+    let app = spawn_demo_app().await;
+    let client = reqwest::Client::new();
+
+    let response = client
+        .post(app.api_url("/roasters"))
+        .bearer_auth(app.auth_token.as_ref().expect("auth token"))
+        .json(&serde_json::json!({ "name": "Blocked Roasters", "country": "UK" }))
+        .send()
+        .await
+        .expect("Failed to execute request");
+
+    assert_eq!(response.status(), 403);
+    let body: serde_json::Value = response.json().await.expect("Failed to parse JSON");
+    assert_eq!(body["message"], "this is a read-only demo instance");
+}
+
+#[tokio::test]
+async fn demo_pages_still_render() {
+    let app = spawn_demo_app().await;
+    let client = reqwest::Client::new();
+
+    let response = client
+        .get(app.page_url("/data?type=roasts"))
+        .send()
+        .await
+        .expect("Failed to execute request");
+
+    assert_eq!(response.status(), 200);
+    let body = response.text().await.expect("Failed to read body");
+    assert!(body.contains("Red Brick"));
+}
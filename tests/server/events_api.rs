@@ -0,0 +1,266 @@
+use brewlog::domain::entity_type::EntityType;
+use brewlog::domain::events::{Event, NewEvent};
+use brewlog::domain::ids::{CafeId, RoasterId};
+use brewlog::infrastructure::backup::BackupData;
+use chrono::NaiveDate;
+
+use super::helpers::{
+    create_default_cafe, create_default_roaster, create_entity, create_roaster_with_name,
+    create_session, spawn_app, spawn_app_with_auth,
+};
+
+fn event(name: &str, roaster_ids: Vec<RoasterId>, cafe_ids: Vec<CafeId>) -> NewEvent {
+    NewEvent {
+        name: name.to_string(),
+        venue: Some("Truman Brewery".to_string()),
+        date: NaiveDate::from_ymd_opt(2026, 4, 18).unwrap(),
+        notes: None,
+        coffees: vec!["Kochere".to_string(), "Finca Deborah".to_string()],
+        roaster_ids,
+        cafe_ids,
+        created_at: None,
+    }
+}
+
+async fn list_events(app: &crate::helpers::TestApp, query: &str) -> Vec<Event> {
+    reqwest::Client::new()
+        .get(app.api_url(&format!("/events{query}")))
+        .send()
+        .await
+        .expect("failed to execute request")
+        .json()
+        .await
+        .expect("failed to parse")
+}
+
+#[tokio::test]
+async fn logging_an_event_requires_authentication() {
+    let app = spawn_app().await;
+
+    let response = reqwest::Client::new()
+        .post(app.api_url("/events"))
+        .json(&event("London Coffee Festival", vec![], vec![]))
+        .send()
+        .await
+        .expect("failed to execute request");
+
+    assert_eq!(response.status(), 401);
+}
+
+#[tokio::test]
+async fn an_event_links_roasters_and_cafes_and_lands_on_the_timeline() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+    let cafe = create_default_cafe(&app).await;
+
+    let created: Event = create_entity(
+        &app,
+        "/events",
+        &event("London Coffee Festival", vec![roaster.id], vec![cafe.id]),
+    )
+    .await;
+    assert_eq!(created.roaster_ids, [roaster.id]);
+    assert_eq!(created.cafe_ids, [cafe.id]);
+    assert_eq!(created.coffees, ["Kochere", "Finca Deborah"]);
+
+    let timeline = app
+        .timeline_repo
+        .list_all()
+        .await
+        .expect("failed to list timeline events");
+    let entry = timeline
+        .iter()
+        .find(|entry| entry.entity_type == EntityType::Event)
+        .expect("event should be on the timeline");
+    assert_eq!(entry.entity_id, i64::from(created.id));
+    assert_eq!(entry.action, "attended");
+    assert_eq!(entry.occurred_at.date_naive(), created.date);
+}
+
+#[tokio::test]
+async fn an_event_cannot_link_a_missing_roaster() {
+    let app = spawn_app_with_auth().await;
+
+    let response = reqwest::Client::new()
+        .post(app.api_url("/events"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&event("Cupping", vec![RoasterId::new(9999)], vec![]))
+        .send()
+        .await
+        .expect("failed to execute request");
+
+    assert_eq!(response.status(), 400);
+}
+
+#[tokio::test]
+async fn events_can_be_filtered_by_roaster_and_cafe() {
+    let app = spawn_app_with_auth().await;
+    let first = create_roaster_with_name(&app, "Square Mile").await;
+    let second = create_roaster_with_name(&app, "Origin").await;
+    let cafe = create_default_cafe(&app).await;
+
+    let festival: Event = create_entity(
+        &app,
+        "/events",
+        &event("Festival", vec![first.id, second.id], vec![cafe.id]),
+    )
+    .await;
+    let cupping: Event =
+        create_entity(&app, "/events", &event("Cupping", vec![second.id], vec![])).await;
+
+    let by_first = list_events(&app, &format!("?roaster={}", first.id)).await;
+    assert_eq!(by_first.len(), 1);
+    assert_eq!(by_first[0].id, festival.id);
+
+    let by_second = list_events(&app, &format!("?roaster={}", second.id)).await;
+    assert_eq!(by_second.len(), 2);
+
+    let by_cafe = list_events(&app, &format!("?cafe={}", cafe.id)).await;
+    assert_eq!(by_cafe.len(), 1);
+    assert_eq!(by_cafe[0].id, festival.id);
+
+    assert!(
+        list_events(&app, "")
+            .await
+            .iter()
+            .any(|e| e.id == cupping.id)
+    );
+}
+
+#[tokio::test]
+async fn updating_an_event_replaces_its_links() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+    let cafe = create_default_cafe(&app).await;
+    let created: Event = create_entity(
+        &app,
+        "/events",
+        &event("Festival", vec![roaster.id], vec![]),
+    )
+    .await;
+
+    let response = reqwest::Client::new()
+        .put(app.api_url(&format!("/events/{}", created.id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&serde_json::json!({
+            "name": "Coffee Festival",
+            "roaster_ids": [],
+            "cafe_ids": [cafe.id],
+        }))
+        .send()
+        .await
+        .expect("failed to execute request");
+    assert_eq!(response.status(), 200);
+
+    let updated: Event = response.json().await.expect("failed to parse");
+    assert_eq!(updated.name, "Coffee Festival");
+    assert!(updated.roaster_ids.is_empty());
+    assert_eq!(updated.cafe_ids, [cafe.id]);
+    assert_eq!(updated.coffees, created.coffees);
+}
+
+#[tokio::test]
+async fn deleting_an_event_removes_it_from_the_timeline() {
+    let app = spawn_app_with_auth().await;
+    let created: Event = create_entity(&app, "/events", &event("Festival", vec![], vec![])).await;
+    let client = reqwest::Client::new();
+
+    let response = client
+        .delete(app.api_url(&format!("/events/{}", created.id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .send()
+        .await
+        .expect("failed to execute request");
+    assert_eq!(response.status(), 204);
+
+    let response = client
+        .get(app.api_url(&format!("/events/{}", created.id)))
+        .send()
+        .await
+        .expect("failed to execute request");
+    assert_eq!(response.status(), 404);
+
+    let timeline = app
+        .timeline_repo
+        .list_all()
+        .await
+        .expect("failed to list timeline events");
+    assert!(
+        timeline
+            .iter()
+            .all(|entry| entry.entity_type != EntityType::Event)
+    );
+}
+
+#[tokio::test]
+async fn events_page_lists_events_with_their_roasters() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+    let _: Event = create_entity(
+        &app,
+        "/events",
+        &event("London Coffee Festival", vec![roaster.id], vec![]),
+    )
+    .await;
+    let session_token = create_session(&app).await;
+
+    let body = reqwest::Client::new()
+        .get(app.page_url("/events"))
+        .header("Cookie", format!("brewlog_session={session_token}"))
+        .send()
+        .await
+        .expect("failed to execute request")
+        .text()
+        .await
+        .expect("failed to read body");
+
+    assert!(body.contains("London Coffee Festival"));
+    assert!(body.contains("Truman Brewery"));
+    assert!(body.contains(&format!("/roasters/{}", roaster.slug)));
+    assert!(body.contains("event-form"));
+}
+
+#[tokio::test]
+async fn backups_carry_events_and_their_links() {
+    let source = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&source).await;
+    let created: Event = create_entity(
+        &source,
+        "/events",
+        &event("Festival", vec![roaster.id], vec![]),
+    )
+    .await;
+    let client = reqwest::Client::new();
+
+    let backup: BackupData = client
+        .get(source.api_url("/backup"))
+        .bearer_auth(source.auth_token.as_ref().unwrap())
+        .send()
+        .await
+        .expect("failed to export backup")
+        .json()
+        .await
+        .expect("failed to parse backup");
+    assert_eq!(backup.events.len(), 1);
+
+    let target = spawn_app_with_auth().await;
+    let response = client
+        .post(target.api_url("/backup/restore"))
+        .bearer_auth(target.auth_token.as_ref().unwrap())
+        .json(&backup)
+        .send()
+        .await
+        .expect("failed to restore backup");
+    assert_eq!(response.status(), 204);
+
+    let restored: Event = client
+        .get(target.api_url(&format!("/events/{}", created.id)))
+        .send()
+        .await
+        .expect("failed to execute request")
+        .json()
+        .await
+        .expect("failed to parse");
+    assert_eq!(restored.roaster_ids, [roaster.id]);
+    assert_eq!(restored.coffees, created.coffees);
+}
@@ -0,0 +1,52 @@
+use brewlog::infrastructure::beanconqueror;
+use reqwest::Client;
+
+use crate::helpers::{create_default_brew, spawn_app, spawn_app_with_auth};
+
+#[tokio::test]
+async fn beanconqueror_export_requires_authentication() {
+    let app = spawn_app().await;
+
+    let response = Client::new()
+        .get(app.api_url("/export/beanconqueror"))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 401);
+}
+
+#[tokio::test]
+async fn beanconqueror_export_reads_back_as_the_same_history() {
+    let app = spawn_app_with_auth().await;
+    let brew = create_default_brew(&app).await;
+
+    let response = Client::new()
+        .get(app.api_url("/export/beanconqueror"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    assert!(
+        response.headers()["content-disposition"]
+            .to_str()
+            .unwrap()
+            .contains("Beanconqueror.json")
+    );
+    let body = response.bytes().await.unwrap();
+
+    let export = beanconqueror::read_export(&body).unwrap();
+    assert_eq!(export.beans.len(), 1);
+    assert_eq!(export.beans[0].name, "Test Roast");
+    assert_eq!(export.beans[0].roaster.as_deref(), Some("Test Roasters"));
+    assert_eq!(export.mills[0].name, "Comandante C40 MK4");
+    assert_eq!(export.mills.len(), 1);
+    assert_eq!(export.preparations.len(), 1);
+    assert_eq!(export.brews.len(), 1);
+    let exported = &export.brews[0];
+    assert_eq!(exported.bean, export.beans[0].uuid);
+    assert_eq!(exported.dose, Some(brew.coffee_weight));
+    assert_eq!(exported.water_temp, Some(brew.water_temp));
+    assert_eq!(exported.grind_setting, Some(brew.grind_setting));
+}
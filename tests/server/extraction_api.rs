@@ -1,8 +1,11 @@
+use brewlog::domain::matching::ScanMatches;
 use brewlog::infrastructure::ai::{ExtractedBagScan, ExtractedRoast, ExtractedRoaster};
-use wiremock::matchers::{method, path};
+use wiremock::matchers::{body_string_contains, method, path};
 use wiremock::{Mock, ResponseTemplate};
 
-use crate::helpers::spawn_app_with_openrouter_mock;
+use crate::helpers::{
+    create_default_roast, create_roaster_with_name, spawn_app_with_openrouter_mock,
+};
 
 fn mock_openrouter_response(json_content: &str) -> ResponseTemplate {
     let body = serde_json::json!({
@@ -197,6 +200,35 @@ async fn extract_roast_returns_datastar_signals() {
     assert_eq!(body["_process"], "Natural");
 }
 
+#[tokio::test]
+async fn extract_roast_fills_in_a_blend() {
+    let app = spawn_app_with_openrouter_mock().await;
+    let mock_server = app.mock_server.as_ref().unwrap();
+
+    Mock::given(method("POST"))
+        .and(path("/api/v1/chat/completions"))
+        .and(body_string_contains("components"))
+        .respond_with(mock_openrouter_response(
+            r#"{"name": "Red Brick", "origin": "Brazil, Ethiopia", "components": [{"origin": "Brazil", "percentage": 70}, {"origin": "Ethiopia", "percentage": 30}]}"#,
+        ))
+        .mount(mock_server)
+        .await;
+
+    let response = reqwest::Client::new()
+        .post(app.api_url("/extract-roast"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .header("datastar-request", "true")
+        .json(&serde_json::json!({ "prompt": "Red Brick espresso blend" }))
+        .send()
+        .await
+        .expect("Failed to execute request");
+
+    assert_eq!(response.status(), 200);
+    let body: serde_json::Value = response.json().await.expect("Failed to parse response");
+    assert_eq!(body["_origin"], "Brazil, Ethiopia");
+    assert_eq!(body["_components"], "Brazil 70%, Ethiopia 30%");
+}
+
 // --- extract-bag-scan ---
 
 #[tokio::test]
@@ -230,6 +262,37 @@ async fn extract_bag_scan_returns_json() {
     assert_eq!(result.roast.name.as_deref(), Some("Blend One"));
 }
 
+#[tokio::test]
+async fn extract_bag_scan_accepts_a_multipart_image() {
+    let app = spawn_app_with_openrouter_mock().await;
+    let mock_server = app.mock_server.as_ref().unwrap();
+
+    Mock::given(method("POST"))
+        .and(path("/api/v1/chat/completions"))
+        .and(body_string_contains("data:image/jpeg;base64,aGVsbG8="))
+        .respond_with(mock_openrouter_response(
+            r#"{"roaster": {"name": "Origin", "country": "UK"}, "roast": {"name": "Blend One"}}"#,
+        ))
+        .mount(mock_server)
+        .await;
+
+    let part = reqwest::multipart::Part::bytes(b"hello".to_vec())
+        .file_name("bag.jpg")
+        .mime_str("image/jpeg")
+        .unwrap();
+    let response = reqwest::Client::new()
+        .post(app.api_url("/extract-bag-scan"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .multipart(reqwest::multipart::Form::new().part("image", part))
+        .send()
+        .await
+        .expect("Failed to execute request");
+
+    assert_eq!(response.status(), 200);
+    let result: ExtractedBagScan = response.json().await.expect("Failed to parse response");
+    assert_eq!(result.roast.name.as_deref(), Some("Blend One"));
+}
+
 #[tokio::test]
 async fn extract_bag_scan_returns_datastar_signals() {
     let app = spawn_app_with_openrouter_mock().await;
@@ -264,6 +327,104 @@ async fn extract_bag_scan_returns_datastar_signals() {
     assert_eq!(body["_scanExtracted"], true);
 }
 
+#[tokio::test]
+async fn extract_bag_scan_reads_the_bag_label() {
+    let app = spawn_app_with_openrouter_mock().await;
+    let mock_server = app.mock_server.as_ref().unwrap();
+
+    Mock::given(method("POST"))
+        .and(path("/api/v1/chat/completions"))
+        .respond_with(mock_openrouter_response(
+            r#"{"roaster": {"name": "Allpress"}, "roast": {"name": "Redchurch"}, "bag": {"best_before": "2025-09-30", "batch_code": "RC-118"}}"#,
+        ))
+        .mount(mock_server)
+        .await;
+
+    let response = reqwest::Client::new()
+        .post(app.api_url("/extract-bag-scan"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .header("datastar-request", "true")
+        .json(&serde_json::json!({ "prompt": "Allpress Redchurch" }))
+        .send()
+        .await
+        .expect("Failed to execute request");
+
+    assert_eq!(response.status(), 200);
+    let body: serde_json::Value = response.json().await.expect("Failed to parse response");
+    assert_eq!(body["_bestBefore"], "2025-09-30");
+    assert_eq!(body["_batchCode"], "RC-118");
+}
+
+#[tokio::test]
+async fn extract_bag_scan_ranks_existing_roasters_and_roasts() {
+    let app = spawn_app_with_openrouter_mock().await;
+    let mock_server = app.mock_server.as_ref().unwrap();
+    let roaster = create_roaster_with_name(&app, "Test Roasters").await;
+    let roast = create_default_roast(&app, roaster.id).await;
+    create_roaster_with_name(&app, "Origin").await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v1/chat/completions"))
+        .respond_with(mock_openrouter_response(
+            r#"{"roaster": {"name": "Test Roastrs Ltd"}, "roast": {"name": "Test Roast"}}"#,
+        ))
+        .mount(mock_server)
+        .await;
+
+    let response = reqwest::Client::new()
+        .post(app.api_url("/extract-bag-scan"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&serde_json::json!({ "prompt": "Test Roastrs Ltd, Test Roast" }))
+        .send()
+        .await
+        .expect("Failed to execute request");
+
+    assert_eq!(response.status(), 200);
+    let body: serde_json::Value = response.json().await.expect("Failed to parse response");
+    assert_eq!(body["roaster"]["name"], "Test Roastrs Ltd");
+    let matches: ScanMatches =
+        serde_json::from_value(body["matches"].clone()).expect("Failed to parse matches");
+    assert_eq!(matches.roasters.len(), 1, "Origin is no match");
+    assert_eq!(matches.roasters[0].id, roaster.id);
+    assert!(
+        !matches.roasters[0].is_confident(),
+        "a misspelling is offered, not taken"
+    );
+    assert_eq!(matches.roasts[0].roast.id, roast.id);
+    assert_eq!(matches.roasts[0].roaster_id, roaster.id);
+}
+
+#[tokio::test]
+async fn extract_bag_scan_matches_confidently_without_filler_words() {
+    let app = spawn_app_with_openrouter_mock().await;
+    let mock_server = app.mock_server.as_ref().unwrap();
+    let roaster = create_roaster_with_name(&app, "Allpress").await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v1/chat/completions"))
+        .respond_with(mock_openrouter_response(
+            r#"{"roaster": {"name": "Allpress Coffee Roasters"}, "roast": {"name": "Redchurch"}}"#,
+        ))
+        .mount(mock_server)
+        .await;
+
+    let response = reqwest::Client::new()
+        .post(app.api_url("/extract-bag-scan"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .header("datastar-request", "true")
+        .json(&serde_json::json!({ "prompt": "Allpress Redchurch" }))
+        .send()
+        .await
+        .expect("Failed to execute request");
+
+    let body: serde_json::Value = response.json().await.expect("Failed to parse response");
+    assert_eq!(body["_matchedRoasterId"], roaster.id.to_string());
+    assert_eq!(body["_roasterName"], "Allpress", "shows the existing name");
+    assert_eq!(body["_matchedRoastId"], "");
+    assert_eq!(body["_roasterCandidates"][0]["confidence"], 0.95);
+    assert_eq!(body["_roastCandidates"], serde_json::json!([]));
+}
+
 #[tokio::test]
 async fn extract_bag_scan_requires_auth() {
     let app = spawn_app_with_openrouter_mock().await;
@@ -0,0 +1,152 @@
+use brewlog::domain::cafes::Cafe;
+use brewlog::domain::roasters::Roaster;
+use brewlog::domain::roasts::RoastWithRoaster;
+
+use super::helpers::{
+    assert_datastar_headers, assert_html_fragment, create_default_cafe, create_default_roast,
+    create_default_roaster, create_roaster_with_name, create_session, spawn_app_with_auth,
+};
+
+async fn set_favourite(
+    app: &crate::helpers::TestApp,
+    path: &str,
+    favourite: bool,
+) -> reqwest::Response {
+    let client = reqwest::Client::new();
+    let url = app.api_url(&format!("{path}/favourite"));
+    let request = if favourite {
+        client.put(url)
+    } else {
+        client.delete(url)
+    };
+    request
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .send()
+        .await
+        .expect("failed to execute request")
+}
+
+async fn get_json<T: serde::de::DeserializeOwned>(app: &crate::helpers::TestApp, path: &str) -> T {
+    reqwest::Client::new()
+        .get(app.api_url(path))
+        .send()
+        .await
+        .expect("failed to execute request")
+        .json()
+        .await
+        .expect("failed to parse")
+}
+
+#[tokio::test]
+async fn starring_requires_authentication() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+
+    let response = reqwest::Client::new()
+        .put(app.api_url(&format!("/roasters/{}/favourite", roaster.id)))
+        .send()
+        .await
+        .expect("failed to execute request");
+
+    assert_eq!(response.status(), 401);
+}
+
+#[tokio::test]
+async fn put_stars_and_delete_unstars_a_roaster() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+    assert!(!roaster.favourite);
+
+    let path = format!("/roasters/{}", roaster.id);
+    let response = set_favourite(&app, &path, true).await;
+    assert_eq!(response.status(), 204);
+    let starred: Roaster = get_json(&app, &path).await;
+    assert!(starred.favourite);
+
+    let response = set_favourite(&app, &path, false).await;
+    assert_eq!(response.status(), 204);
+    let unstarred: Roaster = get_json(&app, &path).await;
+    assert!(!unstarred.favourite);
+}
+
+#[tokio::test]
+async fn roasts_and_cafes_can_be_starred() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+    let roast = create_default_roast(&app, roaster.id).await;
+    let cafe = create_default_cafe(&app).await;
+
+    let roast_path = format!("/roasts/{}", roast.id);
+    let cafe_path = format!("/cafes/{}", cafe.id);
+    assert_eq!(set_favourite(&app, &roast_path, true).await.status(), 204);
+    assert_eq!(set_favourite(&app, &cafe_path, true).await.status(), 204);
+
+    let roast: RoastWithRoaster = get_json(&app, &roast_path).await;
+    assert!(roast.roast.favourite);
+    let cafe: Cafe = get_json(&app, &cafe_path).await;
+    assert!(cafe.favourite);
+}
+
+#[tokio::test]
+async fn starring_a_missing_entity_returns_not_found() {
+    let app = spawn_app_with_auth().await;
+
+    let response = set_favourite(&app, "/roasters/999999", true).await;
+
+    assert_eq!(response.status(), 404);
+}
+
+#[tokio::test]
+async fn datastar_toggle_returns_the_star_fragment() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+
+    let response = reqwest::Client::new()
+        .put(app.api_url(&format!("/roasters/{}/favourite", roaster.id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .header("datastar-request", "true")
+        .send()
+        .await
+        .expect("failed to execute request");
+
+    assert_eq!(response.status(), 200);
+    assert_datastar_headers(&response, &format!("#favourite-roasters-{}", roaster.id));
+    let body = response.text().await.expect("failed to read body");
+    assert_html_fragment(&body);
+    assert!(body.contains(&format!("id=\"favourite-roasters-{}\"", roaster.id)));
+    assert!(body.contains("aria-pressed=\"true\""));
+    assert!(body.contains("@delete"));
+}
+
+#[tokio::test]
+async fn favourites_lead_the_roaster_picker_and_show_on_the_home_page() {
+    let app = spawn_app_with_auth().await;
+    create_roaster_with_name(&app, "Alpha Roasters").await;
+    let starred = create_roaster_with_name(&app, "Zeta Roasters").await;
+    set_favourite(&app, &format!("/roasters/{}", starred.id), true).await;
+
+    let session = create_session(&app).await;
+    let page = reqwest::Client::new()
+        .get(app.page_url("/add?type=roast"))
+        .header("Cookie", format!("brewlog_session={session}"))
+        .send()
+        .await
+        .expect("failed to execute request")
+        .text()
+        .await
+        .expect("failed to read body");
+    let zeta = page.find("Zeta Roasters").expect("starred roaster listed");
+    let alpha = page.find("Alpha Roasters").expect("other roaster listed");
+    assert!(zeta < alpha, "favourite should come first in the picker");
+
+    let home = reqwest::Client::new()
+        .get(app.page_url("/"))
+        .send()
+        .await
+        .expect("failed to execute request")
+        .text()
+        .await
+        .expect("failed to read body");
+    assert!(home.contains("favourites-section"));
+    assert!(home.contains("Zeta Roasters"));
+}
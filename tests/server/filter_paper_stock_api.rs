@@ -0,0 +1,199 @@
+use brewlog::domain::brews::NewBrew;
+use brewlog::domain::gear::Gear;
+use reqwest::Client;
+
+use crate::helpers::{
+    TestApp, create_default_bag, create_default_gear, create_default_roast, create_default_roaster,
+    create_entity, create_session, spawn_app_with_auth,
+};
+
+async fn create_filter_papers(app: &TestApp, stock_count: i32) -> Gear {
+    create_entity(
+        app,
+        "/gear",
+        &serde_json::json!({
+            "category": "filter_paper",
+            "make": "Hario",
+            "model": "V60 02 Tabbed",
+            "stock_count": stock_count
+        }),
+    )
+    .await
+}
+
+async fn get_gear(app: &TestApp, id: i64) -> Gear {
+    Client::new()
+        .get(app.api_url(&format!("/gear/{id}")))
+        .send()
+        .await
+        .expect("Failed to execute request")
+        .json()
+        .await
+        .expect("Failed to parse response")
+}
+
+async fn restock(app: &TestApp, id: i64, count: i32) -> reqwest::Response {
+    Client::new()
+        .post(app.api_url(&format!("/gear/{id}/restock")))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&serde_json::json!({ "count": count }))
+        .send()
+        .await
+        .expect("Failed to execute request")
+}
+
+/// Logs a V60 brew through the given filter paper.
+async fn brew_with(app: &TestApp, filter_paper: &Gear) {
+    let roaster = create_default_roaster(app).await;
+    let roast = create_default_roast(app, roaster.id).await;
+    let bag = create_default_bag(app, roast.id).await;
+    let grinder = create_default_gear(app, "grinder", "Comandante", "C40 MK4").await;
+    let brewer = create_default_gear(app, "brewer", "Hario", "V60").await;
+    let brew = NewBrew {
+        bag_id: bag.id,
+        coffee_weight: 15.0,
+        grinder_id: grinder.id,
+        grind_setting: 24.0,
+        brewer_id: brewer.id,
+        filter_paper_id: Some(filter_paper.id),
+        water_volume: 250,
+        water_temp: 96.0,
+        bypass_water_ml: None,
+        kettle_id: None,
+        actual_water_temp: None,
+        quick_notes: Vec::new(),
+        brew_time: None,
+        created_at: None,
+    };
+
+    let response = Client::new()
+        .post(app.api_url("/brews"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&brew)
+        .send()
+        .await
+        .expect("Failed to execute request");
+    assert_eq!(response.status(), 201);
+}
+
+#[tokio::test]
+async fn brewing_uses_up_a_filter_paper() {
+    let app = spawn_app_with_auth().await;
+    let papers = create_filter_papers(&app, 20).await;
+    // Warm the gear listing cache so a stale count would show.
+    Client::new()
+        .get(app.api_url("/gear"))
+        .send()
+        .await
+        .expect("Failed to execute request");
+
+    brew_with(&app, &papers).await;
+
+    assert_eq!(
+        get_gear(&app, i64::from(papers.id)).await.stock_count,
+        Some(19)
+    );
+    let listed: Vec<Gear> = Client::new()
+        .get(app.api_url("/gear"))
+        .send()
+        .await
+        .expect("Failed to execute request")
+        .json()
+        .await
+        .expect("Failed to parse response");
+    let listed = listed.iter().find(|gear| gear.id == papers.id).unwrap();
+    assert_eq!(listed.stock_count, Some(19));
+}
+
+#[tokio::test]
+async fn untracked_filter_papers_stay_untracked() {
+    let app = spawn_app_with_auth().await;
+    let papers = create_default_gear(&app, "filter_paper", "Hario", "V60 02").await;
+
+    brew_with(&app, &papers).await;
+
+    assert_eq!(get_gear(&app, i64::from(papers.id)).await.stock_count, None);
+}
+
+#[tokio::test]
+async fn restocking_adds_to_what_is_left() {
+    let app = spawn_app_with_auth().await;
+    let papers = create_filter_papers(&app, 5).await;
+
+    let response = restock(&app, i64::from(papers.id), 100).await;
+    assert_eq!(response.status(), 200);
+    let restocked: Gear = response.json().await.expect("Failed to parse response");
+    assert_eq!(restocked.stock_count, Some(105));
+    assert!(!restocked.is_running_low());
+
+    let response = restock(&app, i64::from(papers.id), 0).await;
+    assert_eq!(response.status(), 422);
+}
+
+#[tokio::test]
+async fn only_filter_papers_have_a_stock_count() {
+    let app = spawn_app_with_auth().await;
+    let client = Client::new();
+
+    let response = client
+        .post(app.api_url("/gear"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&serde_json::json!({
+            "category": "brewer",
+            "make": "Hario",
+            "model": "V60",
+            "stock_count": 10
+        }))
+        .send()
+        .await
+        .expect("Failed to execute request");
+    assert_eq!(response.status(), 422);
+
+    let brewer = create_default_gear(&app, "brewer", "Hario", "V60").await;
+    let response = client
+        .put(app.api_url(&format!("/gear/{}", brewer.id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&serde_json::json!({ "low_stock_threshold": 5 }))
+        .send()
+        .await
+        .expect("Failed to execute request");
+    assert_eq!(response.status(), 400);
+
+    let response = restock(&app, i64::from(brewer.id), 10).await;
+    assert_eq!(response.status(), 400);
+}
+
+#[tokio::test]
+async fn restocking_requires_authentication() {
+    let app = spawn_app_with_auth().await;
+    let papers = create_filter_papers(&app, 5).await;
+
+    let response = Client::new()
+        .post(app.api_url(&format!("/gear/{}/restock", papers.id)))
+        .json(&serde_json::json!({ "count": 10 }))
+        .send()
+        .await
+        .expect("Failed to execute request");
+    assert_eq!(response.status(), 401);
+}
+
+#[tokio::test]
+async fn the_gear_page_warns_when_filter_papers_run_low() {
+    let app = spawn_app_with_auth().await;
+    let papers = create_filter_papers(&app, 3).await;
+    let session_token = create_session(&app).await;
+
+    let body = Client::new()
+        .get(app.page_url(&format!("/gear/{}", papers.id)))
+        .header("Cookie", format!("brewlog_session={session_token}"))
+        .send()
+        .await
+        .expect("Failed to execute request")
+        .text()
+        .await
+        .expect("Failed to read body");
+
+    assert!(body.contains("3 left"));
+    assert!(body.contains(r#"data-role="reorder-soon""#));
+    assert!(body.contains(r#"id="restock-form""#));
+}
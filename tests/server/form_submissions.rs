@@ -142,6 +142,9 @@ async fn brew_update_form(app: &TestApp) -> (String, Vec<(String, String)>) {
             filter_paper_id: None,
             water_volume: 250,
             water_temp: 92.0,
+            bypass_water_ml: None,
+            kettle_id: None,
+            actual_water_temp: None,
             quick_notes: Vec::new(),
             brew_time: None,
             created_at: None,
@@ -163,7 +166,9 @@ async fn cup_update_form(app: &TestApp) -> (String, Vec<(String, String)>) {
         "/cups",
         &brewlog::domain::cups::NewCup {
             roast_id: roast.id,
-            cafe_id: cafe.id,
+            cafe_id: Some(cafe.id),
+            bag_id: None,
+            rating: None,
             created_at: None,
         },
     )
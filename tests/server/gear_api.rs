@@ -1,5 +1,7 @@
-use crate::helpers::{spawn_app, spawn_app_with_auth};
-use brewlog::domain::gear::{Gear, UpdateGear};
+use crate::helpers::{
+    create_default_gear, create_entity, create_session, spawn_app, spawn_app_with_auth,
+};
+use brewlog::domain::gear::{BrewMethod, Gear, UpdateGear};
 
 #[tokio::test]
 async fn creating_gear_returns_201_for_valid_data() {
@@ -80,7 +82,7 @@ async fn creating_gear_with_invalid_category_returns_400() {
 }
 
 #[tokio::test]
-async fn creating_gear_with_empty_make_returns_400() {
+async fn creating_gear_with_empty_make_returns_422() {
     // Arrange
     let app = spawn_app_with_auth().await;
     let client = reqwest::Client::new();
@@ -101,11 +103,11 @@ async fn creating_gear_with_empty_make_returns_400() {
         .expect("Failed to execute request");
 
     // Assert
-    assert_eq!(response.status(), 400);
+    assert_eq!(response.status(), 422);
 }
 
 #[tokio::test]
-async fn creating_gear_with_empty_model_returns_400() {
+async fn creating_gear_with_empty_model_returns_422() {
     // Arrange
     let app = spawn_app_with_auth().await;
     let client = reqwest::Client::new();
@@ -126,7 +128,7 @@ async fn creating_gear_with_empty_model_returns_400() {
         .expect("Failed to execute request");
 
     // Assert
-    assert_eq!(response.status(), 400);
+    assert_eq!(response.status(), 422);
 }
 
 #[tokio::test]
@@ -282,6 +284,13 @@ async fn updating_gear_returns_updated_data() {
     let update = UpdateGear {
         make: Some("Comandante".to_string()),
         model: Some("C40".to_string()),
+        water_temp: None,
+        brew_method: None,
+        uses_filter: None,
+        temp_offset: None,
+        water_hardness: None,
+        stock_count: None,
+        low_stock_threshold: None,
         created_at: None,
     };
 
@@ -311,6 +320,13 @@ async fn updating_gear_without_auth_returns_401() {
     let update = UpdateGear {
         make: Some("Updated".to_string()),
         model: None,
+        water_temp: None,
+        brew_method: None,
+        uses_filter: None,
+        temp_offset: None,
+        water_hardness: None,
+        stock_count: None,
+        low_stock_threshold: None,
         created_at: None,
     };
 
@@ -386,3 +402,261 @@ async fn deleting_gear_without_auth_returns_401() {
     // Assert
     assert_eq!(response.status(), 401);
 }
+
+#[tokio::test]
+async fn listing_gear_reflects_new_and_updated_gear_immediately() {
+    // Arrange
+    let app = spawn_app_with_auth().await;
+    let client = reqwest::Client::new();
+
+    let list = |client: reqwest::Client| {
+        let url = app.api_url("/gear?category=grinder");
+        async move {
+            client
+                .get(url)
+                .send()
+                .await
+                .expect("Failed to list gear")
+                .json::<Vec<Gear>>()
+                .await
+                .expect("Failed to parse gear")
+        }
+    };
+    assert!(list(client.clone()).await.is_empty());
+
+    // Act
+    let created: Gear = client
+        .post(app.api_url("/gear"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&serde_json::json!({
+            "category": "grinder",
+            "make": "Baratza",
+            "model": "Encore"
+        }))
+        .send()
+        .await
+        .expect("Failed to create gear")
+        .json()
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(list(client.clone()).await.len(), 1);
+
+    client
+        .put(app.api_url(&format!("/gear/{}", created.id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&UpdateGear {
+            make: None,
+            model: Some("Encore ESP".to_string()),
+            water_temp: None,
+            brew_method: None,
+            uses_filter: None,
+            temp_offset: None,
+            water_hardness: None,
+            stock_count: None,
+            low_stock_threshold: None,
+            created_at: None,
+        })
+        .send()
+        .await
+        .expect("Failed to update gear");
+
+    assert_eq!(list(client).await[0].model, "Encore ESP");
+}
+
+#[tokio::test]
+async fn brewers_keep_a_water_temperature_preset() {
+    let app = spawn_app_with_auth().await;
+    let client = reqwest::Client::new();
+
+    let response = client
+        .post(app.api_url("/gear"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&serde_json::json!({
+            "category": "brewer",
+            "make": "AeroPress",
+            "model": "Original",
+            "water_temp": 85.0
+        }))
+        .send()
+        .await
+        .expect("Failed to execute request");
+    assert_eq!(response.status(), 201);
+    let brewer: Gear = response.json().await.expect("Failed to parse response");
+    assert_eq!(brewer.water_temp, Some(85.0));
+
+    let response = client
+        .put(app.api_url(&format!("/gear/{}", brewer.id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&serde_json::json!({ "water_temp": 82.5 }))
+        .send()
+        .await
+        .expect("Failed to execute request");
+    assert_eq!(response.status(), 200);
+    let updated: Gear = response.json().await.expect("Failed to parse response");
+    assert_eq!(updated.water_temp, Some(82.5));
+    assert_eq!(updated.make, "AeroPress");
+}
+
+#[tokio::test]
+async fn only_brewers_take_a_water_temperature() {
+    let app = spawn_app_with_auth().await;
+    let client = reqwest::Client::new();
+
+    let response = client
+        .post(app.api_url("/gear"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&serde_json::json!({
+            "category": "grinder",
+            "make": "Comandante",
+            "model": "C40",
+            "water_temp": 93.0
+        }))
+        .send()
+        .await
+        .expect("Failed to execute request");
+    assert_eq!(response.status(), 422);
+
+    let grinder = create_default_gear(&app, "grinder", "Comandante", "C40").await;
+    let response = client
+        .put(app.api_url(&format!("/gear/{}", grinder.id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&serde_json::json!({ "water_temp": 93.0 }))
+        .send()
+        .await
+        .expect("Failed to execute request");
+    assert_eq!(response.status(), 400);
+}
+
+#[tokio::test]
+async fn choosing_a_brewer_patches_its_water_temperature_into_the_brew_form() {
+    let app = spawn_app_with_auth().await;
+    let client = reqwest::Client::new();
+    let v60: Gear = create_entity(
+        &app,
+        "/gear",
+        &serde_json::json!({
+            "category": "brewer",
+            "make": "Hario",
+            "model": "V60",
+            "water_temp": 93.0
+        }),
+    )
+    .await;
+    let switch = create_default_gear(&app, "brewer", "Hario", "Switch").await;
+    let session_token = create_session(&app).await;
+
+    let water_temp = |brewer_id| {
+        client
+            .get(app.page_url(&format!("/add/water-temp?brewer_id={brewer_id}")))
+            .header("Cookie", format!("brewlog_session={session_token}"))
+            .send()
+    };
+
+    let response = water_temp(v60.id).await.expect("Failed to execute request");
+    assert_eq!(response.status(), 200);
+    let signals: serde_json::Value = response.json().await.expect("Failed to parse response");
+    assert_eq!(signals, serde_json::json!({ "_brewTemp": 93.0 }));
+
+    let signals: serde_json::Value = water_temp(switch.id)
+        .await
+        .expect("Failed to execute request")
+        .json()
+        .await
+        .expect("Failed to parse response");
+    assert_eq!(signals, serde_json::json!({}));
+}
+
+#[tokio::test]
+async fn brewer_edit_page_shows_the_water_temperature() {
+    let app = spawn_app_with_auth().await;
+    let v60: Gear = create_entity(
+        &app,
+        "/gear",
+        &serde_json::json!({
+            "category": "brewer",
+            "make": "Hario",
+            "model": "V60",
+            "water_temp": 93.0
+        }),
+    )
+    .await;
+    let session_token = create_session(&app).await;
+
+    let body = reqwest::Client::new()
+        .get(app.page_url(&format!("/gear/{}/edit", v60.id)))
+        .header("Cookie", format!("brewlog_session={session_token}"))
+        .send()
+        .await
+        .expect("Failed to execute request")
+        .text()
+        .await
+        .expect("Failed to read body");
+
+    assert!(body.contains(r#"name="water_temp""#));
+    assert!(body.contains(r#"value="93.0""#));
+}
+
+#[tokio::test]
+async fn brewers_keep_a_brew_method() {
+    let app = spawn_app_with_auth().await;
+    let client = reqwest::Client::new();
+    let brewer = create_default_gear(&app, "brewer", "Hario", "Switch").await;
+    assert_eq!(brewer.brew_method, None);
+
+    let response = client
+        .put(app.api_url(&format!("/gear/{}", brewer.id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .form(&[("brew_method", "immersion"), ("uses_filter", "true")])
+        .send()
+        .await
+        .expect("Failed to execute request");
+    assert!(response.status().is_success() || response.status().is_redirection());
+
+    let fetched: Gear = client
+        .get(app.api_url(&format!("/gear/{}", brewer.id)))
+        .send()
+        .await
+        .expect("Failed to execute request")
+        .json()
+        .await
+        .expect("Failed to parse response");
+    assert_eq!(fetched.brew_method, Some(BrewMethod::Immersion));
+    assert_eq!(fetched.uses_filter, Some(true));
+
+    let grinder = create_default_gear(&app, "grinder", "Comandante", "C40").await;
+    let response = client
+        .put(app.api_url(&format!("/gear/{}", grinder.id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&serde_json::json!({ "brew_method": "percolation" }))
+        .send()
+        .await
+        .expect("Failed to execute request");
+    assert_eq!(response.status(), 400);
+}
+
+#[tokio::test]
+async fn brewer_edit_page_offers_the_detected_method() {
+    let app = spawn_app_with_auth().await;
+    let press = create_default_gear(&app, "brewer", "Bodum", "French Press").await;
+    let session_token = create_session(&app).await;
+
+    let body = reqwest::Client::new()
+        .get(app.page_url(&format!("/gear/{}/edit", press.id)))
+        .header("Cookie", format!("brewlog_session={session_token}"))
+        .send()
+        .await
+        .expect("Failed to execute request")
+        .text()
+        .await
+        .expect("Failed to read body");
+
+    let selected = |value: &str| {
+        body.split("<option").any(|option| {
+            option.contains(&format!(r#"value="{value}""#)) && option.contains("selected")
+        })
+    };
+    assert!(selected("immersion"));
+    assert!(selected("false"));
+}
@@ -0,0 +1,207 @@
+use brewlog::domain::gear::Gear;
+use brewlog::domain::grinder_calibrations::{
+    GrindConversion, GrinderCalibration, NewGrinderCalibration,
+};
+
+use super::helpers::{
+    create_default_gear, create_entity, create_session, spawn_app, spawn_app_with_auth,
+};
+
+fn point(a: &Gear, setting_a: f64, b: &Gear, setting_b: f64) -> NewGrinderCalibration {
+    NewGrinderCalibration {
+        grinder_a_id: a.id,
+        setting_a,
+        grinder_b_id: b.id,
+        setting_b,
+        created_at: None,
+    }
+}
+
+async fn convert(
+    app: &crate::helpers::TestApp,
+    from: &Gear,
+    to: &Gear,
+    setting: f64,
+) -> reqwest::Response {
+    reqwest::Client::new()
+        .get(app.api_url(&format!(
+            "/grinder-calibrations/convert?from={}&to={}&setting={setting}",
+            from.id, to.id
+        )))
+        .send()
+        .await
+        .expect("failed to execute request")
+}
+
+#[tokio::test]
+async fn creating_a_calibration_requires_authentication() {
+    let app = spawn_app().await;
+
+    let response = reqwest::Client::new()
+        .post(app.api_url("/grinder-calibrations"))
+        .json(&serde_json::json!({
+            "grinder_a_id": 1,
+            "setting_a": 24.0,
+            "grinder_b_id": 2,
+            "setting_b": 38.0
+        }))
+        .send()
+        .await
+        .expect("failed to execute request");
+
+    assert_eq!(response.status(), 401);
+}
+
+#[tokio::test]
+async fn created_calibrations_are_listed_for_either_grinder() {
+    let app = spawn_app_with_auth().await;
+    let comandante = create_default_gear(&app, "grinder", "Comandante", "C40 MK4").await;
+    let df64 = create_default_gear(&app, "grinder", "Turin", "DF64").await;
+    let encore = create_default_gear(&app, "grinder", "Baratza", "Encore").await;
+
+    let created: GrinderCalibration = create_entity(
+        &app,
+        "/grinder-calibrations",
+        &point(&comandante, 24.0, &df64, 38.0),
+    )
+    .await;
+    let _: GrinderCalibration = create_entity(
+        &app,
+        "/grinder-calibrations",
+        &point(&comandante, 20.0, &encore, 14.0),
+    )
+    .await;
+    assert_eq!(created.grinder_a_id, comandante.id);
+    assert_eq!(created.setting_b, 38.0);
+
+    let response = reqwest::Client::new()
+        .get(app.api_url(&format!("/grinder-calibrations?grinder={}", df64.id)))
+        .send()
+        .await
+        .expect("failed to execute request");
+    assert_eq!(response.status(), 200);
+    let points: Vec<GrinderCalibration> = response.json().await.expect("failed to parse");
+    assert_eq!(points.len(), 1);
+    assert_eq!(points[0].id, created.id);
+}
+
+#[tokio::test]
+async fn calibrations_must_relate_two_different_grinders() {
+    let app = spawn_app_with_auth().await;
+    let comandante = create_default_gear(&app, "grinder", "Comandante", "C40 MK4").await;
+    let v60 = create_default_gear(&app, "brewer", "Hario", "V60").await;
+    let client = reqwest::Client::new();
+
+    for payload in [
+        point(&comandante, 24.0, &comandante, 30.0),
+        point(&comandante, 24.0, &v60, 3.0),
+    ] {
+        let response = client
+            .post(app.api_url("/grinder-calibrations"))
+            .bearer_auth(app.auth_token.as_ref().unwrap())
+            .json(&payload)
+            .send()
+            .await
+            .expect("failed to execute request");
+        assert_eq!(response.status(), 400);
+    }
+}
+
+#[tokio::test]
+async fn converting_interpolates_between_points_in_either_direction() {
+    let app = spawn_app_with_auth().await;
+    let comandante = create_default_gear(&app, "grinder", "Comandante", "C40 MK4").await;
+    let df64 = create_default_gear(&app, "grinder", "Turin", "DF64").await;
+    for (a, b) in [(20.0, 30.0), (30.0, 50.0)] {
+        let _: GrinderCalibration = create_entity(
+            &app,
+            "/grinder-calibrations",
+            &point(&comandante, a, &df64, b),
+        )
+        .await;
+    }
+
+    let response = convert(&app, &comandante, &df64, 25.0).await;
+    assert_eq!(response.status(), 200);
+    let forward: GrindConversion = response.json().await.expect("failed to parse");
+    assert_eq!(forward.to_setting, 40.0);
+    assert_eq!(forward.reference_points, 2);
+
+    let reverse: GrindConversion = convert(&app, &df64, &comandante, 40.0)
+        .await
+        .json()
+        .await
+        .expect("failed to parse");
+    assert_eq!(reverse.to_setting, 25.0);
+}
+
+#[tokio::test]
+async fn converting_without_calibration_returns_400() {
+    let app = spawn_app_with_auth().await;
+    let comandante = create_default_gear(&app, "grinder", "Comandante", "C40 MK4").await;
+    let df64 = create_default_gear(&app, "grinder", "Turin", "DF64").await;
+
+    let response = convert(&app, &comandante, &df64, 24.0).await;
+
+    assert_eq!(response.status(), 400);
+}
+
+#[tokio::test]
+async fn deleting_a_calibration_removes_it() {
+    let app = spawn_app_with_auth().await;
+    let comandante = create_default_gear(&app, "grinder", "Comandante", "C40 MK4").await;
+    let df64 = create_default_gear(&app, "grinder", "Turin", "DF64").await;
+    let created: GrinderCalibration = create_entity(
+        &app,
+        "/grinder-calibrations",
+        &point(&comandante, 24.0, &df64, 38.0),
+    )
+    .await;
+    let client = reqwest::Client::new();
+
+    let response = client
+        .delete(app.api_url(&format!("/grinder-calibrations/{}", created.id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .send()
+        .await
+        .expect("failed to execute request");
+    assert_eq!(response.status(), 204);
+
+    let response = client
+        .delete(app.api_url(&format!("/grinder-calibrations/{}", created.id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .send()
+        .await
+        .expect("failed to execute request");
+    assert_eq!(response.status(), 404);
+}
+
+#[tokio::test]
+async fn grinder_page_shows_calibration_points() {
+    let app = spawn_app_with_auth().await;
+    let comandante = create_default_gear(&app, "grinder", "Comandante", "C40 MK4").await;
+    let df64 = create_default_gear(&app, "grinder", "Turin", "DF64").await;
+    let _: GrinderCalibration = create_entity(
+        &app,
+        "/grinder-calibrations",
+        &point(&df64, 38.0, &comandante, 24.0),
+    )
+    .await;
+    let session_token = create_session(&app).await;
+
+    let body = reqwest::Client::new()
+        .get(app.page_url(&format!("/gear/{}", comandante.id)))
+        .header("Cookie", format!("brewlog_session={session_token}"))
+        .send()
+        .await
+        .expect("failed to execute request")
+        .text()
+        .await
+        .expect("failed to read body");
+
+    assert!(body.contains("grinder-calibration"));
+    assert!(body.contains("24.0"));
+    assert!(body.contains("Turin DF64"));
+    assert!(body.contains("38.0"));
+    assert!(body.contains("calibration-form"));
+}
@@ -1,258 +1,13 @@
-use std::sync::Arc;
-
-use brewlog::application::routes::app_router;
-use brewlog::application::state::{AppState, AppStateConfig};
 use brewlog::domain::cafes::{Cafe, NewCafe};
-use brewlog::domain::repositories::{
-    CafeRepository, RoastRepository, RoasterRepository, SessionRepository, TimelineEventRepository,
-    TokenRepository, UserRepository,
-};
+use brewlog::domain::ids::UserId;
 use brewlog::domain::roasters::{NewRoaster, Roaster};
+use brewlog::domain::tokens::NewToken;
 use brewlog::domain::users::NewUser;
-use brewlog::infrastructure::database::Database;
+use brewlog::infrastructure::auth::{generate_token, hash_token};
 use reqwest::Client;
 use serde::{Serialize, de::DeserializeOwned};
-use tokio::net::TcpListener;
-use tokio::task::AbortHandle;
-use webauthn_rs::prelude::*;
-
-pub struct TestApp {
-    pub address: String,
-    pub roaster_repo: Arc<dyn RoasterRepository>,
-    pub roast_repo: Arc<dyn RoastRepository>,
-    #[allow(dead_code)]
-    pub cafe_repo: Arc<dyn CafeRepository>,
-    #[allow(dead_code)]
-    pub timeline_repo: Arc<dyn TimelineEventRepository>,
-    #[allow(dead_code)]
-    pub user_repo: Option<Arc<dyn UserRepository>>,
-    #[allow(dead_code)]
-    pub token_repo: Option<Arc<dyn TokenRepository>>,
-    pub session_repo: Option<Arc<dyn SessionRepository>>,
-    pub auth_token: Option<String>,
-    #[allow(dead_code)]
-    pub mock_server: Option<wiremock::MockServer>,
-    server_handle: AbortHandle,
-}
-
-impl TestApp {
-    pub fn api_url(&self, path: &str) -> String {
-        format!("{}/api/v1{}", self.address, path)
-    }
-
-    pub fn webauthn_url(&self, path: &str) -> String {
-        format!("{}/api/v1/webauthn{}", self.address, path)
-    }
-
-    pub fn page_url(&self, path: &str) -> String {
-        format!("{}{}", self.address, path)
-    }
-}
-
-impl Drop for TestApp {
-    fn drop(&mut self) {
-        self.server_handle.abort();
-    }
-}
-
-fn test_webauthn() -> Arc<Webauthn> {
-    #[allow(clippy::expect_used)]
-    let rp_origin = url::Url::parse("http://localhost:0").expect("valid URL");
-    #[allow(clippy::expect_used)]
-    Arc::new(
-        WebauthnBuilder::new("localhost", &rp_origin)
-            .expect("valid RP config")
-            .rp_name("Brewlog Test")
-            .build()
-            .expect("valid WebAuthn"),
-    )
-}
-
-pub async fn spawn_app() -> TestApp {
-    let database = Database::connect("sqlite::memory:")
-        .await
-        .expect("Failed to connect to in-memory database");
-
-    spawn_app_inner(database, test_state_config(), None).await
-}
-
-fn test_state_config() -> AppStateConfig {
-    let (stats_tx, _stats_rx) = tokio::sync::mpsc::channel(1);
-    let (timeline_tx, _timeline_rx) = tokio::sync::mpsc::channel(1);
-    AppStateConfig {
-        webauthn: test_webauthn(),
-        insecure_cookies: true,
-        foursquare_url: brewlog::infrastructure::foursquare::FOURSQUARE_SEARCH_URL.to_string(),
-        foursquare_api_key: String::new(),
-        openrouter_url: brewlog::infrastructure::ai::OPENROUTER_URL.to_string(),
-        openrouter_api_key: String::new(),
-        openrouter_model: "openrouter/free".to_string(),
-        stats_invalidator: brewlog::application::services::StatsInvalidator::new(stats_tx),
-        timeline_invalidator: brewlog::application::services::TimelineInvalidator::new(timeline_tx),
-    }
-}
-
-async fn spawn_app_inner(
-    database: Database,
-    config: AppStateConfig,
-    mock_server: Option<wiremock::MockServer>,
-) -> TestApp {
-    let state = AppState::from_database(&database, config);
-    spawn_app_inner_from_state(state, mock_server).await
-}
-
-async fn spawn_app_inner_from_state(
-    state: AppState,
-    mock_server: Option<wiremock::MockServer>,
-) -> TestApp {
-    // Clone repos we need for TestApp before consuming state in the router
-    let roaster_repo = state.roaster_repo.clone();
-    let roast_repo = state.roast_repo.clone();
-    let cafe_repo = state.cafe_repo.clone();
-    let timeline_repo = state.timeline_repo.clone();
-    let user_repo = state.user_repo.clone();
-    let token_repo = state.token_repo.clone();
-    let session_repo = state.session_repo.clone();
-
-    let app = app_router(state);
-
-    let listener = TcpListener::bind("127.0.0.1:0")
-        .await
-        .expect("Failed to bind to random port");
-
-    let local_addr = listener.local_addr().expect("Failed to get local address");
-    let address = format!("http://{}", local_addr);
-
-    let server_handle = tokio::spawn(async move {
-        axum::serve(listener, app)
-            .await
-            .expect("Server failed to start");
-    })
-    .abort_handle();
-
-    TestApp {
-        address,
-        roaster_repo,
-        roast_repo,
-        cafe_repo,
-        timeline_repo,
-        user_repo: Some(user_repo),
-        token_repo: Some(token_repo),
-        session_repo: Some(session_repo),
-        auth_token: None,
-        mock_server,
-        server_handle,
-    }
-}
-
-pub async fn spawn_app_with_auth() -> TestApp {
-    let app = spawn_app().await;
-    add_auth_to_app(app).await
-}
-
-/// Spawn a test app with the timeline background rebuild task running.
-/// Uses a short debounce (50ms) so tests don't have to wait long.
-pub async fn spawn_app_with_timeline_sync() -> TestApp {
-    use brewlog::application::services::timeline_refresh::{
-        TimelineRebuilder, timeline_rebuild_task,
-    };
-
-    let database = Database::connect("sqlite::memory:")
-        .await
-        .expect("Failed to connect to in-memory database");
-
-    let (stats_tx, _stats_rx) = tokio::sync::mpsc::channel(1);
-    let (timeline_tx, timeline_rx) = tokio::sync::mpsc::channel(32);
-
-    let config = AppStateConfig {
-        webauthn: test_webauthn(),
-        insecure_cookies: true,
-        foursquare_url: brewlog::infrastructure::foursquare::FOURSQUARE_SEARCH_URL.to_string(),
-        foursquare_api_key: String::new(),
-        openrouter_url: brewlog::infrastructure::ai::OPENROUTER_URL.to_string(),
-        openrouter_api_key: String::new(),
-        openrouter_model: "openrouter/free".to_string(),
-        stats_invalidator: brewlog::application::services::StatsInvalidator::new(stats_tx),
-        timeline_invalidator: brewlog::application::services::TimelineInvalidator::new(timeline_tx),
-    };
-
-    let state = AppState::from_database(&database, config);
-
-    // Clone repos for the rebuilder before consuming state
-    let rebuilder = TimelineRebuilder {
-        timeline_repo: state.timeline_repo.clone(),
-        roaster_repo: state.roaster_repo.clone(),
-        roast_repo: state.roast_repo.clone(),
-        bag_repo: state.bag_repo.clone(),
-        brew_repo: state.brew_repo.clone(),
-        cup_repo: state.cup_repo.clone(),
-        gear_repo: state.gear_repo.clone(),
-        cafe_repo: state.cafe_repo.clone(),
-    };
-
-    tokio::spawn(timeline_rebuild_task(
-        timeline_rx,
-        rebuilder,
-        std::time::Duration::from_millis(50),
-    ));
-
-    let app = spawn_app_inner_from_state(state, None).await;
-    add_auth_to_app(app).await
-}
-
-pub async fn spawn_app_with_foursquare_mock() -> TestApp {
-    let mock_server = wiremock::MockServer::start().await;
-    let foursquare_url = format!("{}/places/search", mock_server.uri());
-
-    let database = Database::connect("sqlite::memory:")
-        .await
-        .expect("Failed to connect to in-memory database");
-
-    let app = spawn_app_inner(
-        database,
-        AppStateConfig {
-            foursquare_url,
-            foursquare_api_key: "test-api-key".to_string(),
-            ..test_state_config()
-        },
-        Some(mock_server),
-    )
-    .await;
-
-    add_auth_to_app(app).await
-}
-
-async fn add_auth_to_app(mut app: TestApp) -> TestApp {
-    // Create user with UUID (no password)
-    let user_uuid = uuid::Uuid::new_v4().to_string();
-    let admin_user = NewUser::new("admin".to_string(), user_uuid);
-
-    let admin_user = app
-        .user_repo
-        .as_ref()
-        .unwrap()
-        .insert(admin_user)
-        .await
-        .expect("Failed to create admin user");
 
-    // Create a token for testing via direct DB insert
-    use brewlog::domain::tokens::NewToken;
-    use brewlog::infrastructure::auth::{generate_token, hash_token};
-
-    let token_value = generate_token().expect("Failed to generate token");
-    let token_hash = hash_token(&token_value);
-    let token = NewToken::new(admin_user.id, token_hash, "test-token".to_string());
-
-    app.token_repo
-        .as_ref()
-        .unwrap()
-        .insert(token)
-        .await
-        .expect("Failed to insert token");
-
-    app.auth_token = Some(token_value);
-    app
-}
+pub use brewlog::test_support::*;
 
 /// Generic helper: POST a JSON payload and deserialize the response.
 /// Automatically attaches the auth token if the test app has one.
@@ -308,6 +63,8 @@ pub async fn create_default_roast(
             producer: "Coop".to_string(),
             tasting_notes: vec!["Blueberry".to_string()],
             process: "Washed".to_string(),
+            components: Vec::new(),
+            harvest_year: None,
             created_at: None,
         },
     )
@@ -339,12 +96,50 @@ pub async fn create_default_bag(
             roast_id,
             roast_date: Some(chrono::NaiveDate::from_ymd_opt(2023, 1, 1).unwrap()),
             amount: 250.0,
+            price: None,
+            currency: None,
+            best_before: None,
+            batch_code: None,
             created_at: None,
         },
     )
     .await
 }
 
+/// Another user on the instance, with an API token of their own.
+pub struct SecondUser {
+    pub id: UserId,
+    pub token: String,
+}
+
+/// Create a user called `username` alongside `admin`.
+pub async fn create_second_user(app: &TestApp, username: &str) -> SecondUser {
+    let user = app
+        .user_repo
+        .as_ref()
+        .unwrap()
+        .insert(NewUser::new(
+            username.to_string(),
+            uuid::Uuid::new_v4().to_string(),
+        ))
+        .await
+        .expect("failed to create second user");
+
+    let token = generate_token().expect("failed to generate token");
+    app.token_repo
+        .as_ref()
+        .unwrap()
+        .insert(NewToken::new(
+            user.id,
+            hash_token(&token),
+            format!("{username}-token"),
+        ))
+        .await
+        .expect("failed to insert token");
+
+    SecondUser { id: user.id, token }
+}
+
 /// Asserts that the response has valid Datastar fragment headers
 pub fn assert_datastar_headers(response: &reqwest::Response, expected_selector: &str) {
     assert_datastar_headers_with_mode(response, expected_selector, "replace");
@@ -410,6 +205,7 @@ pub async fn create_default_gear(
         "grinder" => brewlog::domain::gear::GearCategory::Grinder,
         "brewer" => brewlog::domain::gear::GearCategory::Brewer,
         "filter_paper" => brewlog::domain::gear::GearCategory::FilterPaper,
+        "kettle" => brewlog::domain::gear::GearCategory::Kettle,
         _ => panic!("Unknown gear category: {}", category),
     };
     create_entity(
@@ -419,6 +215,13 @@ pub async fn create_default_gear(
             category: gear_category,
             make: make.to_string(),
             model: model.to_string(),
+            water_temp: None,
+            brew_method: None,
+            uses_filter: None,
+            temp_offset: None,
+            water_hardness: None,
+            stock_count: None,
+            low_stock_threshold: None,
             created_at: None,
         },
     )
@@ -463,6 +266,9 @@ pub async fn create_default_brew(app: &TestApp) -> brewlog::domain::brews::Brew
             filter_paper_id: None,
             water_volume: 250,
             water_temp: 92.0,
+            bypass_water_ml: None,
+            kettle_id: None,
+            actual_water_temp: None,
             quick_notes: Vec::new(),
             brew_time: None,
             created_at: None,
@@ -480,50 +286,15 @@ pub async fn create_default_cup(app: &TestApp) -> brewlog::domain::cups::Cup {
         "/cups",
         &brewlog::domain::cups::NewCup {
             roast_id: roast.id,
-            cafe_id: cafe.id,
+            cafe_id: Some(cafe.id),
+            bag_id: None,
+            rating: None,
             created_at: None,
         },
     )
     .await
 }
 
-/// Creates a session for the authenticated user and returns the raw session token
-/// to use as a `brewlog_session` cookie value.
-pub async fn create_session(app: &TestApp) -> String {
-    use brewlog::domain::sessions::NewSession;
-    use brewlog::infrastructure::auth::{generate_session_token, hash_token};
-
-    let session_token = generate_session_token();
-    let session_hash = hash_token(&session_token);
-
-    // Get the user ID from the auth token
-    let token_hash = hash_token(app.auth_token.as_ref().expect("auth token required"));
-    let token = app
-        .token_repo
-        .as_ref()
-        .expect("token_repo required")
-        .get_by_token_hash(&token_hash)
-        .await
-        .expect("failed to find token");
-
-    let now = chrono::Utc::now();
-    #[allow(clippy::expect_used)]
-    let expires_at = now
-        .checked_add_signed(chrono::Duration::hours(24))
-        .expect("timestamp overflow");
-
-    let new_session = NewSession::new(token.user_id, session_hash, now, expires_at);
-
-    app.session_repo
-        .as_ref()
-        .expect("session_repo required")
-        .insert(new_session)
-        .await
-        .expect("failed to create session");
-
-    session_token
-}
-
 /// POST a form-encoded payload with session cookie auth.
 /// Uses `redirect::Policy::none()` so tests can assert the 303 redirect itself.
 pub async fn post_form(
@@ -611,48 +382,3 @@ pub async fn put_form_datastar(
         .await
         .expect("failed to PUT form with datastar")
 }
-
-pub async fn spawn_app_with_openrouter_mock() -> TestApp {
-    let mock_server = wiremock::MockServer::start().await;
-    let openrouter_url = format!("{}/api/v1/chat/completions", mock_server.uri());
-
-    let database = Database::connect("sqlite::memory:")
-        .await
-        .expect("Failed to connect to in-memory database");
-
-    let app = spawn_app_inner(
-        database,
-        AppStateConfig {
-            openrouter_url,
-            ..test_state_config()
-        },
-        Some(mock_server),
-    )
-    .await;
-
-    add_auth_to_app(app).await
-}
-
-pub async fn spawn_app_with_all_mocks() -> TestApp {
-    let mock_server = wiremock::MockServer::start().await;
-    let foursquare_url = format!("{}/places/search", mock_server.uri());
-    let openrouter_url = format!("{}/api/v1/chat/completions", mock_server.uri());
-
-    let database = Database::connect("sqlite::memory:")
-        .await
-        .expect("Failed to connect to in-memory database");
-
-    let app = spawn_app_inner(
-        database,
-        AppStateConfig {
-            foursquare_url,
-            foursquare_api_key: "test-api-key".to_string(),
-            openrouter_url,
-            ..test_state_config()
-        },
-        Some(mock_server),
-    )
-    .await;
-
-    add_auth_to_app(app).await
-}
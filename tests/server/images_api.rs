@@ -1,26 +1,53 @@
+use brewlog::application::errors::ErrorResponse;
+use brewlog::domain::ErrorCode;
 use brewlog::domain::cups::Cup;
 use brewlog::domain::roasters::Roaster;
+use brewlog::infrastructure::image_processing::ImageSettings;
+use brewlog::infrastructure::overview::InstanceOverview;
 
 use crate::helpers::{
-    assert_datastar_headers, assert_html_fragment, create_default_cafe, create_default_roast,
-    create_default_roaster, spawn_app_with_auth,
+    assert_datastar_headers, assert_html_fragment, create_default_bag, create_default_cafe,
+    create_default_roast, create_default_roaster, spawn_app_with_auth,
+    spawn_app_with_image_settings,
 };
 
 /// Generate a minimal valid 1x1 red PNG as a base64 data URL.
 fn tiny_png_data_url() -> String {
-    use base64::Engine;
+    png_data_url(1, 1)
+}
+
+/// Generate a solid red PNG of the given size.
+fn png_bytes(width: u32, height: u32) -> Vec<u8> {
     use image::{ImageBuffer, Rgba};
 
-    let img = ImageBuffer::from_pixel(1, 1, Rgba([255u8, 0, 0, 255]));
+    let img = ImageBuffer::from_pixel(width, height, Rgba([255u8, 0, 0, 255]));
     let mut buf = Vec::new();
     let encoder = image::codecs::png::PngEncoder::new(&mut buf);
-    image::ImageEncoder::write_image(encoder, img.as_raw(), 1, 1, image::ColorType::Rgba8.into())
-        .expect("failed to encode test PNG");
+    image::ImageEncoder::write_image(
+        encoder,
+        img.as_raw(),
+        width,
+        height,
+        image::ColorType::Rgba8.into(),
+    )
+    .expect("failed to encode test PNG");
+    buf
+}
 
-    let b64 = base64::engine::general_purpose::STANDARD.encode(&buf);
+/// Generate a solid red PNG of the given size as a base64 data URL.
+fn png_data_url(width: u32, height: u32) -> String {
+    use base64::Engine;
+
+    let b64 = base64::engine::general_purpose::STANDARD.encode(png_bytes(width, height));
     format!("data:image/png;base64,{b64}")
 }
 
+/// A multipart form carrying `bytes` as an `image` file part.
+fn image_form(bytes: Vec<u8>) -> reqwest::multipart::Form {
+    let part = reqwest::multipart::Part::bytes(bytes).file_name("photo.png");
+    reqwest::multipart::Form::new().part("image", part)
+}
+
 fn image_url(entity_type: &str, id: impl std::fmt::Display) -> String {
     format!("/{entity_type}/{id}/image")
 }
@@ -208,6 +235,59 @@ async fn deleting_entity_deletes_image() {
     assert_eq!(response.status(), 404);
 }
 
+#[tokio::test]
+async fn deleted_images_wait_for_the_purge_task() {
+    let app = spawn_app_with_auth().await;
+    let client = reqwest::Client::new();
+    let roaster = create_default_roaster(&app).await;
+    let cafe = create_default_cafe(&app).await;
+    upload_image(&client, &app, "roaster", roaster.id).await;
+    upload_image(&client, &app, "cafe", cafe.id).await;
+
+    client
+        .delete(app.api_url(&format!("/roasters/{}", roaster.id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .send()
+        .await
+        .expect("failed to delete roaster");
+
+    // The test app doesn't run the purge task, so the row is still stored
+    let overview: InstanceOverview = client
+        .get(app.api_url("/admin/overview"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .send()
+        .await
+        .expect("failed to fetch overview")
+        .json()
+        .await
+        .expect("failed to parse overview");
+    assert_eq!(overview.image_count, 2);
+    assert_eq!(overview.images_awaiting_purge, 1);
+}
+
+#[tokio::test]
+async fn reuploading_a_deleted_image_restores_it() {
+    let app = spawn_app_with_auth().await;
+    let client = reqwest::Client::new();
+    let roaster = create_default_roaster(&app).await;
+    upload_image(&client, &app, "roaster", roaster.id).await;
+
+    client
+        .delete(app.api_url(&image_url("roaster", roaster.id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .send()
+        .await
+        .expect("failed to delete image");
+    upload_image(&client, &app, "roaster", roaster.id).await;
+
+    let response = client
+        .get(app.api_url(&image_url("roaster", roaster.id)))
+        .send()
+        .await
+        .expect("failed to get image");
+    assert_eq!(response.status(), 200);
+}
+
 // ===========================================================================
 // Auth
 // ===========================================================================
@@ -300,6 +380,184 @@ async fn upload_image_invalid_entity_type_returns_400() {
     assert_eq!(response.status(), 400);
 }
 
+#[tokio::test]
+async fn upload_image_larger_than_the_file_size_limit_returns_400() {
+    let app = spawn_app_with_image_settings(ImageSettings {
+        max_file_size: 64,
+        ..ImageSettings::default()
+    })
+    .await;
+    let roaster = create_default_roaster(&app).await;
+
+    let response = reqwest::Client::new()
+        .put(app.api_url(&image_url("roaster", roaster.id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&serde_json::json!({ "image": png_data_url(64, 64) }))
+        .send()
+        .await
+        .expect("failed to send request");
+
+    assert_eq!(response.status(), 400);
+    let body: ErrorResponse = response.json().await.unwrap();
+    assert_eq!(body.code, ErrorCode::ImageTooLarge);
+    assert!(body.message.contains("the limit is"), "{}", body.message);
+}
+
+#[tokio::test]
+async fn upload_image_wider_than_the_upload_dimension_limit_returns_400() {
+    let app = spawn_app_with_image_settings(ImageSettings {
+        max_dimension: 16,
+        thumbnail_size: 8,
+        max_upload_dimension: 32,
+        ..ImageSettings::default()
+    })
+    .await;
+    let roaster = create_default_roaster(&app).await;
+
+    let response = reqwest::Client::new()
+        .put(app.api_url(&image_url("roaster", roaster.id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&serde_json::json!({ "image": png_data_url(64, 8) }))
+        .send()
+        .await
+        .expect("failed to send request");
+
+    assert_eq!(response.status(), 400);
+    let body: ErrorResponse = response.json().await.unwrap();
+    assert_eq!(body.code, ErrorCode::ImageTooLarge);
+    assert_eq!(
+        body.message,
+        "image is 64x8 pixels but neither side may exceed 32"
+    );
+}
+
+#[cfg(not(feature = "heic"))]
+#[tokio::test]
+async fn upload_heic_image_without_heic_support_returns_400() {
+    use base64::Engine;
+
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+    let mut heic = vec![0, 0, 0, 24];
+    heic.extend_from_slice(b"ftypheic");
+    heic.extend_from_slice(&[0; 12]);
+    let data_url = format!(
+        "data:image/heic;base64,{}",
+        base64::engine::general_purpose::STANDARD.encode(heic)
+    );
+
+    let response = reqwest::Client::new()
+        .put(app.api_url(&image_url("roaster", roaster.id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&serde_json::json!({ "image": data_url }))
+        .send()
+        .await
+        .expect("failed to send request");
+
+    assert_eq!(response.status(), 400);
+    let body: ErrorResponse = response.json().await.unwrap();
+    assert!(body.message.contains("`heic` feature"), "{}", body.message);
+}
+
+#[tokio::test]
+async fn upload_image_accepts_a_multipart_file() {
+    let app = spawn_app_with_auth().await;
+    let client = reqwest::Client::new();
+    let roaster = create_default_roaster(&app).await;
+
+    let response = client
+        .put(app.api_url(&image_url("roaster", roaster.id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .multipart(image_form(png_bytes(4, 4)))
+        .send()
+        .await
+        .expect("failed to send request");
+    assert_eq!(response.status(), 204);
+
+    let response = client
+        .get(app.api_url(&image_url("roaster", roaster.id)))
+        .send()
+        .await
+        .expect("failed to send request");
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers()["content-type"], "image/jpeg");
+}
+
+#[tokio::test]
+async fn upload_multipart_file_larger_than_the_file_size_limit_returns_400() {
+    let app = spawn_app_with_image_settings(ImageSettings {
+        max_file_size: 64,
+        ..ImageSettings::default()
+    })
+    .await;
+    let roaster = create_default_roaster(&app).await;
+
+    let response = reqwest::Client::new()
+        .put(app.api_url(&image_url("roaster", roaster.id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .multipart(image_form(png_bytes(64, 64)))
+        .send()
+        .await
+        .expect("failed to send request");
+
+    assert_eq!(response.status(), 400);
+    let body: ErrorResponse = response.json().await.unwrap();
+    assert_eq!(body.code, ErrorCode::ImageTooLarge);
+}
+
+#[tokio::test]
+async fn upload_multipart_without_an_image_returns_400() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+
+    let response = reqwest::Client::new()
+        .put(app.api_url(&image_url("roaster", roaster.id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .multipart(reqwest::multipart::Form::new().text("caption", "no image"))
+        .send()
+        .await
+        .expect("failed to send request");
+
+    assert_eq!(response.status(), 400);
+}
+
+#[tokio::test]
+async fn upload_image_is_downscaled_to_the_configured_dimension() {
+    let app = spawn_app_with_image_settings(ImageSettings {
+        max_dimension: 20,
+        thumbnail_size: 5,
+        ..ImageSettings::default()
+    })
+    .await;
+    let client = reqwest::Client::new();
+    let roaster = create_default_roaster(&app).await;
+
+    let response = client
+        .put(app.api_url(&image_url("roaster", roaster.id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&serde_json::json!({ "image": png_data_url(40, 40) }))
+        .send()
+        .await
+        .expect("failed to send request");
+    assert_eq!(response.status(), 204);
+
+    for (path, size) in [
+        (image_url("roaster", roaster.id), 20),
+        (thumbnail_url("roaster", roaster.id), 5),
+    ] {
+        let bytes = client
+            .get(app.api_url(&path))
+            .send()
+            .await
+            .expect("failed to get image")
+            .bytes()
+            .await
+            .expect("failed to read body");
+        let stored = image::load_from_memory(&bytes).expect("stored image should decode");
+        assert_eq!((stored.width(), stored.height()), (size, size));
+    }
+}
+
 #[tokio::test]
 async fn upload_image_nonexistent_entity_returns_404() {
     let app = spawn_app_with_auth().await;
@@ -345,6 +603,90 @@ async fn get_image_invalid_entity_type_returns_400() {
     assert_eq!(response.status(), 400);
 }
 
+// ===========================================================================
+// List thumbnails
+// ===========================================================================
+
+async fn data_page(app: &crate::helpers::TestApp, data_type: &str) -> String {
+    reqwest::Client::new()
+        .get(app.page_url(&format!("/data?type={data_type}")))
+        .send()
+        .await
+        .expect("failed to fetch data page")
+        .text()
+        .await
+        .expect("failed to read body")
+}
+
+#[tokio::test]
+async fn roast_list_shows_thumbnails_for_roasts_with_artwork() {
+    let app = spawn_app_with_auth().await;
+    let client = reqwest::Client::new();
+    let roaster = create_default_roaster(&app).await;
+    let roast = create_default_roast(&app, roaster.id).await;
+    let thumbnail = format!("/api/v1{}", thumbnail_url("roast", roast.id));
+
+    assert!(!data_page(&app, "roasts").await.contains(&thumbnail));
+
+    upload_image(&client, &app, "roast", roast.id).await;
+
+    let body = data_page(&app, "roasts").await;
+    assert!(body.contains(&format!(r#"src="{thumbnail}""#)));
+    assert!(body.contains(r#"loading="lazy""#));
+}
+
+#[tokio::test]
+async fn deleted_image_leaves_the_list_straight_away() {
+    let app = spawn_app_with_auth().await;
+    let client = reqwest::Client::new();
+    let roaster = create_default_roaster(&app).await;
+    let roast = create_default_roast(&app, roaster.id).await;
+    let thumbnail = format!("/api/v1{}", thumbnail_url("roast", roast.id));
+
+    upload_image(&client, &app, "roast", roast.id).await;
+    assert!(data_page(&app, "roasts").await.contains(&thumbnail));
+
+    let response = client
+        .delete(app.api_url(&image_url("roast", roast.id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .send()
+        .await
+        .expect("failed to delete image");
+    assert_eq!(response.status(), 204);
+
+    assert!(!data_page(&app, "roasts").await.contains(&thumbnail));
+}
+
+#[tokio::test]
+async fn bag_list_falls_back_to_the_roast_artwork() {
+    let app = spawn_app_with_auth().await;
+    let client = reqwest::Client::new();
+    let roaster = create_default_roaster(&app).await;
+    let roast = create_default_roast(&app, roaster.id).await;
+    let bag = create_default_bag(&app, roast.id).await;
+
+    upload_image(&client, &app, "roast", roast.id).await;
+    let body = data_page(&app, "bags").await;
+    assert!(body.contains(&format!("/api/v1{}", thumbnail_url("roast", roast.id))));
+
+    upload_image(&client, &app, "bag", bag.id).await;
+    let body = data_page(&app, "bags").await;
+    assert!(body.contains(&format!("/api/v1{}", thumbnail_url("bag", bag.id))));
+    assert!(!body.contains(&format!("/api/v1{}", thumbnail_url("roast", roast.id))));
+}
+
+#[tokio::test]
+async fn cafe_list_shows_thumbnails_for_cafes_with_photos() {
+    let app = spawn_app_with_auth().await;
+    let client = reqwest::Client::new();
+    let cafe = create_default_cafe(&app).await;
+
+    upload_image(&client, &app, "cafe", cafe.id).await;
+
+    let body = data_page(&app, "cafes").await;
+    assert!(body.contains(&format!("/api/v1{}", thumbnail_url("cafe", cafe.id))));
+}
+
 // ===========================================================================
 // Datastar
 // ===========================================================================
@@ -496,7 +838,7 @@ async fn checkin_with_cafe_image_saves_image() {
 
     // Cafe image should be retrievable
     let img_response = client
-        .get(app.api_url(&image_url("cafe", cup.cafe_id)))
+        .get(app.api_url(&image_url("cafe", cup.cafe_id.unwrap())))
         .send()
         .await
         .expect("failed to get cafe image");
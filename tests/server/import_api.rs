@@ -0,0 +1,181 @@
+use std::io::{Cursor, Write};
+
+use brewlog::application::services::ImportReport;
+use brewlog::infrastructure::backup::BackupData;
+use reqwest::Client;
+use serde_json::{Value, json};
+
+use crate::helpers::{
+    TestApp, create_default_gear, create_default_roaster, spawn_app, spawn_app_with_auth,
+};
+
+fn export() -> Value {
+    json!({
+        "VERSION": [],
+        "SETTINGS": [],
+        "WATER": [{ "name": "Third Wave Water" }],
+        "BEANS": [{
+            "name": "Halo Beriti",
+            "roaster": "Square Mile",
+            "roastingDate": "2024-03-01T00:00:00.000+01:00",
+            "weight": 250,
+            "cost": 14.5,
+            "aromatics": "Jasmine, Peach",
+            "finished": true,
+            "bean_information": [{
+                "country": "Ethiopia",
+                "region": "Yirgacheffe",
+                "farmer": "Halo Beriti",
+                "processing": "Washed"
+            }],
+            "config": { "uuid": "bean-1", "unix_timestamp": 1_709_300_000 }
+        }],
+        "MILL": [{ "name": "Comandante C40", "config": { "uuid": "mill-1" } }],
+        "PREPARATION": [{ "name": "Hario V60", "config": { "uuid": "prep-1" } }],
+        "BREWS": [
+            {
+                "bean": "bean-1",
+                "mill": "mill-1",
+                "method_of_preparation": "prep-1",
+                "grind_size": "24",
+                "grind_weight": 15,
+                "brew_temperature": 94,
+                "brew_time": 180,
+                "brew_quantity": 250,
+                "note": "Bright and floral",
+                "config": { "uuid": "brew-1", "unix_timestamp": 1_709_400_000 }
+            },
+            {
+                "bean": "bean-1",
+                "grind_weight": 15,
+                "brew_quantity": 250,
+                "config": { "uuid": "brew-2", "unix_timestamp": 1_709_500_000 }
+            }
+        ]
+    })
+}
+
+async fn import(app: &TestApp, body: Vec<u8>, roaster_country: &str) -> reqwest::Response {
+    Client::new()
+        .post(app.api_url("/import/beanconqueror"))
+        .query(&[("roaster_country", roaster_country)])
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .body(body)
+        .send()
+        .await
+        .expect("failed to import")
+}
+
+async fn backup(app: &TestApp) -> BackupData {
+    Client::new()
+        .get(app.api_url("/backup"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn importing_requires_authentication() {
+    let app = spawn_app().await;
+
+    let response = Client::new()
+        .post(app.api_url("/import/beanconqueror?roaster_country=UK"))
+        .body(serde_json::to_vec(&export()).unwrap())
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 401);
+}
+
+#[tokio::test]
+async fn beanconqueror_export_is_imported_and_reported() {
+    let app = spawn_app_with_auth().await;
+
+    let response = import(&app, serde_json::to_vec(&export()).unwrap(), "UK").await;
+    assert_eq!(response.status(), 200);
+    let report: ImportReport = response.json().await.unwrap();
+
+    assert_eq!(
+        (
+            report.roasters,
+            report.roasts,
+            report.bags,
+            report.gear,
+            report.brews
+        ),
+        (1, 1, 1, 2, 1)
+    );
+    assert_eq!(report.skipped.get("no water temperature"), Some(&1));
+    assert_eq!(report.unmapped.get("water"), Some(&1));
+    assert_eq!(report.unmapped.get("brews.note"), Some(&1));
+
+    let data = backup(&app).await;
+    assert_eq!(data.roasters[0].name, "Square Mile");
+    assert_eq!(data.roasters[0].country, "United Kingdom");
+    assert_eq!(data.roasts[0].tasting_notes, ["Jasmine", "Peach"]);
+    let bag = &data.bags[0];
+    assert_eq!(bag.price, Some(14.5));
+    assert!(bag.closed);
+    assert!((bag.remaining - 235.0).abs() < f64::EPSILON);
+    assert_eq!(data.brews[0].grind_setting, 24.0);
+    assert_eq!(data.brews[0].brew_time, Some(180));
+}
+
+#[tokio::test]
+async fn known_roasters_and_gear_are_reused() {
+    let app = spawn_app_with_auth().await;
+    create_default_roaster(&app).await;
+    create_default_gear(&app, "grinder", "Comandante", "C40").await;
+
+    let mut export = export();
+    export["BEANS"][0]["roaster"] = json!("test roasters");
+    let report: ImportReport = import(&app, serde_json::to_vec(&export).unwrap(), "UK")
+        .await
+        .json()
+        .await
+        .unwrap();
+
+    assert_eq!(report.roasters, 0);
+    assert_eq!(report.gear, 1);
+    assert_eq!(backup(&app).await.roasters.len(), 1);
+}
+
+#[tokio::test]
+async fn zipped_exports_are_accepted() {
+    let app = spawn_app_with_auth().await;
+
+    let mut archive = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    archive
+        .start_file(
+            "Beanconqueror.json",
+            zip::write::SimpleFileOptions::default(),
+        )
+        .unwrap();
+    archive
+        .write_all(&serde_json::to_vec(&export()).unwrap())
+        .unwrap();
+    let body = archive.finish().unwrap().into_inner();
+
+    let response = import(&app, body, "UK").await;
+
+    assert_eq!(response.status(), 200);
+    let report: ImportReport = response.json().await.unwrap();
+    assert_eq!(report.brews, 1);
+}
+
+#[tokio::test]
+async fn bad_uploads_are_rejected() {
+    let app = spawn_app_with_auth().await;
+
+    let not_an_export = import(&app, b"{\"hello\": []}".to_vec(), "UK").await;
+    let unknown_country = import(&app, serde_json::to_vec(&export()).unwrap(), "Atlantis").await;
+
+    assert_eq!(not_an_export.status(), 400);
+    assert_eq!(unknown_country.status(), 400);
+    assert!(backup(&app).await.roasters.is_empty());
+}
@@ -0,0 +1,198 @@
+use brewlog::infrastructure::database::{Database, DatabasePool};
+use brewlog::infrastructure::integrity::{IntegrityIssueKind, IntegrityReport, IntegrityService};
+
+use super::helpers::{spawn_app, spawn_app_with_auth};
+
+async fn create_pool() -> DatabasePool {
+    Database::connect("sqlite::memory:")
+        .await
+        .expect("Failed to connect to in-memory database")
+        .clone_pool()
+}
+
+async fn execute(pool: &DatabasePool, sql: &str) {
+    sqlx::query(sqlx::AssertSqlSafe(sql.to_string()))
+        .execute(pool)
+        .await
+        .expect("failed to execute test SQL");
+}
+
+/// Seed one consistent roaster → roast → bag → brew chain with a grinder and brewer.
+async fn seed_consistent_data(pool: &DatabasePool) {
+    execute(
+        pool,
+        "INSERT INTO roasters (id, name, country) VALUES (1, 'Roaster', 'UK')",
+    )
+    .await;
+    execute(
+        pool,
+        "INSERT INTO roasts (id, roaster_id, name) VALUES (1, 1, 'Roast')",
+    )
+    .await;
+    execute(
+        pool,
+        "INSERT INTO bags (id, roast_id, amount, remaining) VALUES (1, 1, 250, 200)",
+    )
+    .await;
+    execute(
+        pool,
+        "INSERT INTO gear (id, category, make, model) VALUES (1, 'grinder', 'Comandante', 'C40'), (2, 'brewer', 'Hario', 'V60')",
+    )
+    .await;
+    execute(
+        pool,
+        "INSERT INTO brews (id, bag_id, coffee_weight, grinder_id, grind_setting, brewer_id, water_volume, water_temp) \
+         VALUES (1, 1, 15, 1, 24, 2, 250, 92)",
+    )
+    .await;
+    execute(
+        pool,
+        "INSERT INTO timeline_events (entity_type, entity_id, action, title) VALUES ('roaster', 1, 'added', 'Roaster')",
+    )
+    .await;
+}
+
+fn count_kind(report: &IntegrityReport, kind: IntegrityIssueKind) -> usize {
+    report.issues.iter().filter(|i| i.kind == kind).count()
+}
+
+#[tokio::test]
+async fn consistent_database_has_no_issues() {
+    let pool = create_pool().await;
+    seed_consistent_data(&pool).await;
+
+    let report = IntegrityService::new(pool)
+        .check()
+        .await
+        .expect("failed to check integrity");
+
+    assert!(report.is_clean(), "unexpected issues: {:?}", report.issues);
+}
+
+#[tokio::test]
+async fn check_reports_each_kind_of_issue() {
+    let pool = create_pool().await;
+    seed_consistent_data(&pool).await;
+
+    execute(
+        &pool,
+        "INSERT INTO entity_images (entity_type, entity_id, content_type, image_data, thumbnail_data) \
+         VALUES ('cafe', 99, 'image/png', x'00', x'00')",
+    )
+    .await;
+    execute(
+        &pool,
+        "INSERT INTO timeline_events (entity_type, entity_id, action, title) VALUES ('roast', 42, 'added', 'Gone')",
+    )
+    .await;
+    execute(
+        &pool,
+        "INSERT INTO bag_ledger (bag_id, kind, delta, reason) VALUES (1, 'adjustment', 50, 'Bad import')",
+    )
+    .await;
+    execute(&pool, "UPDATE brews SET brewer_id = 1 WHERE id = 1").await;
+
+    let report = IntegrityService::new(pool)
+        .check()
+        .await
+        .expect("failed to check integrity");
+
+    assert_eq!(report.issues.len(), 4);
+    assert_eq!(report.repaired, 0);
+    assert_eq!(count_kind(&report, IntegrityIssueKind::OrphanedImage), 1);
+    assert_eq!(
+        count_kind(&report, IntegrityIssueKind::OrphanedTimelineEvent),
+        1
+    );
+    assert_eq!(
+        count_kind(&report, IntegrityIssueKind::BagRemainingExceedsAmount),
+        1
+    );
+    assert_eq!(
+        count_kind(&report, IntegrityIssueKind::BrewGearCategoryMismatch),
+        1
+    );
+
+    let mismatch = report
+        .issues
+        .iter()
+        .find(|i| i.kind == IntegrityIssueKind::BrewGearCategoryMismatch)
+        .unwrap();
+    assert!(!mismatch.repairable);
+    assert_eq!(mismatch.entity_type, "brew");
+    assert_eq!(mismatch.entity_id, 1);
+}
+
+#[tokio::test]
+async fn repair_fixes_repairable_issues() {
+    let pool = create_pool().await;
+    seed_consistent_data(&pool).await;
+
+    execute(
+        &pool,
+        "INSERT INTO timeline_events (entity_type, entity_id, action, title) \
+         VALUES ('bag', 7, 'added', 'Gone'), ('bag', 7, 'finished', 'Gone')",
+    )
+    .await;
+    execute(
+        &pool,
+        "INSERT INTO bag_ledger (bag_id, kind, delta, reason) VALUES (1, 'adjustment', 50, 'Bad import')",
+    )
+    .await;
+    execute(&pool, "UPDATE brews SET grinder_id = 2 WHERE id = 1").await;
+
+    let service = IntegrityService::new(pool.clone());
+    let report = service.repair().await.expect("failed to repair");
+
+    assert_eq!(report.issues.len(), 4);
+    assert_eq!(report.repaired, 3);
+
+    let after = service.check().await.expect("failed to check integrity");
+    assert_eq!(after.issues.len(), 1);
+    assert_eq!(
+        after.issues[0].kind,
+        IntegrityIssueKind::BrewGearCategoryMismatch
+    );
+
+    let (remaining,): (f64,) = sqlx::query_as("SELECT remaining FROM bags WHERE id = 1")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert!((remaining - 250.0).abs() < f64::EPSILON);
+}
+
+#[tokio::test]
+async fn integrity_check_requires_auth() {
+    let app = spawn_app().await;
+    let client = reqwest::Client::new();
+
+    let check = client
+        .get(app.api_url("/admin/integrity"))
+        .send()
+        .await
+        .expect("failed to send request");
+    assert_eq!(check.status(), reqwest::StatusCode::UNAUTHORIZED);
+
+    let repair = client
+        .post(app.api_url("/admin/integrity/repair"))
+        .send()
+        .await
+        .expect("failed to send request");
+    assert_eq!(repair.status(), reqwest::StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn integrity_check_via_api_returns_report() {
+    let app = spawn_app_with_auth().await;
+
+    let response = reqwest::Client::new()
+        .get(app.api_url("/admin/integrity"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .send()
+        .await
+        .expect("failed to send request");
+
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    let report: IntegrityReport = response.json().await.expect("failed to parse report");
+    assert!(report.is_clean());
+}
@@ -0,0 +1,196 @@
+use brewlog::domain::brews::{BrewWithDetails, NewBrew};
+use brewlog::domain::gear::Gear;
+use reqwest::Client;
+
+use crate::helpers::{
+    TestApp, create_default_bag, create_default_gear, create_default_roast, create_default_roaster,
+    create_entity, create_session, spawn_app_with_auth,
+};
+
+async fn create_kettle(app: &TestApp, temp_offset: f64) -> Gear {
+    create_entity(
+        app,
+        "/gear",
+        &serde_json::json!({
+            "category": "kettle",
+            "make": "Fellow",
+            "model": "Stagg EKG",
+            "temp_offset": temp_offset,
+            "water_hardness": 80
+        }),
+    )
+    .await
+}
+
+/// A brew at 96°C ready to post, with its gear and bag created.
+async fn brew_setup(app: &TestApp) -> NewBrew {
+    let roaster = create_default_roaster(app).await;
+    let roast = create_default_roast(app, roaster.id).await;
+    let bag = create_default_bag(app, roast.id).await;
+    let grinder = create_default_gear(app, "grinder", "Comandante", "C40 MK4").await;
+    let brewer = create_default_gear(app, "brewer", "Hario", "Switch").await;
+    NewBrew {
+        bag_id: bag.id,
+        coffee_weight: 15.0,
+        grinder_id: grinder.id,
+        grind_setting: 24.0,
+        brewer_id: brewer.id,
+        filter_paper_id: None,
+        water_volume: 250,
+        water_temp: 96.0,
+        bypass_water_ml: None,
+        kettle_id: None,
+        actual_water_temp: None,
+        quick_notes: Vec::new(),
+        brew_time: None,
+        created_at: None,
+    }
+}
+
+async fn post_brew(app: &TestApp, brew: &NewBrew) -> reqwest::Response {
+    Client::new()
+        .post(app.api_url("/brews"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(brew)
+        .send()
+        .await
+        .expect("Failed to execute request")
+}
+
+#[tokio::test]
+async fn kettles_keep_their_calibration_and_water_hardness() {
+    let app = spawn_app_with_auth().await;
+    let kettle = create_kettle(&app, -2.5).await;
+    assert_eq!(kettle.temp_offset, Some(-2.5));
+    assert_eq!(kettle.water_hardness, Some(80));
+
+    let response = Client::new()
+        .put(app.api_url(&format!("/gear/{}", kettle.id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&serde_json::json!({ "temp_offset": -3.0 }))
+        .send()
+        .await
+        .expect("Failed to execute request");
+    assert_eq!(response.status(), 200);
+    let updated: Gear = response.json().await.expect("Failed to parse response");
+    assert_eq!(updated.temp_offset, Some(-3.0));
+    assert_eq!(updated.water_hardness, Some(80));
+}
+
+#[tokio::test]
+async fn only_kettles_take_a_temperature_offset() {
+    let app = spawn_app_with_auth().await;
+    let client = Client::new();
+
+    let response = client
+        .post(app.api_url("/gear"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&serde_json::json!({
+            "category": "brewer",
+            "make": "Hario",
+            "model": "V60",
+            "temp_offset": -2.0
+        }))
+        .send()
+        .await
+        .expect("Failed to execute request");
+    assert_eq!(response.status(), 422);
+
+    let brewer = create_default_gear(&app, "brewer", "Hario", "V60").await;
+    let response = client
+        .put(app.api_url(&format!("/gear/{}", brewer.id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&serde_json::json!({ "water_hardness": 80 }))
+        .send()
+        .await
+        .expect("Failed to execute request");
+    assert_eq!(response.status(), 400);
+}
+
+#[tokio::test]
+async fn brews_take_the_actual_temperature_from_the_kettle() {
+    let app = spawn_app_with_auth().await;
+    let kettle = create_kettle(&app, -3.0).await;
+    let mut brew = brew_setup(&app).await;
+    brew.kettle_id = Some(kettle.id);
+
+    let response = post_brew(&app, &brew).await;
+    assert_eq!(response.status(), 201);
+    let created: BrewWithDetails = response.json().await.expect("Failed to parse response");
+    assert_eq!(created.brew.water_temp, 96.0);
+    assert_eq!(created.brew.actual_water_temp, Some(93.0));
+    assert_eq!(created.kettle_name.as_deref(), Some("Fellow Stagg EKG"));
+
+    // A measured temperature is kept over the kettle's offset.
+    brew.actual_water_temp = Some(94.5);
+    let created: BrewWithDetails = post_brew(&app, &brew)
+        .await
+        .json()
+        .await
+        .expect("Failed to parse response");
+    assert_eq!(created.brew.actual_water_temp, Some(94.5));
+}
+
+#[tokio::test]
+async fn changing_the_set_temperature_moves_the_actual_one() {
+    let app = spawn_app_with_auth().await;
+    let kettle = create_kettle(&app, -3.0).await;
+    let mut brew = brew_setup(&app).await;
+    brew.kettle_id = Some(kettle.id);
+    let created: BrewWithDetails = post_brew(&app, &brew)
+        .await
+        .json()
+        .await
+        .expect("Failed to parse response");
+
+    let response = Client::new()
+        .put(app.api_url(&format!("/brews/{}", created.brew.id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&serde_json::json!({ "water_temp": 92.0 }))
+        .send()
+        .await
+        .expect("Failed to execute request");
+    assert_eq!(response.status(), 200);
+    let updated: BrewWithDetails = response.json().await.expect("Failed to parse response");
+    assert_eq!(updated.brew.water_temp, 92.0);
+    assert_eq!(updated.brew.actual_water_temp, Some(89.0));
+}
+
+#[tokio::test]
+async fn a_brew_kettle_must_be_a_kettle() {
+    let app = spawn_app_with_auth().await;
+    let mut brew = brew_setup(&app).await;
+    brew.kettle_id = Some(brew.brewer_id);
+
+    let response = post_brew(&app, &brew).await;
+    assert_eq!(response.status(), 400);
+    let body: serde_json::Value = response.json().await.expect("Failed to parse response");
+    assert!(body.to_string().contains("not a kettle"));
+}
+
+#[tokio::test]
+async fn the_brew_page_shows_the_set_and_actual_temperatures() {
+    let app = spawn_app_with_auth().await;
+    let kettle = create_kettle(&app, -3.0).await;
+    let mut brew = brew_setup(&app).await;
+    brew.kettle_id = Some(kettle.id);
+    let created: BrewWithDetails = post_brew(&app, &brew)
+        .await
+        .json()
+        .await
+        .expect("Failed to parse response");
+    let session_token = create_session(&app).await;
+
+    let body = Client::new()
+        .get(app.page_url(&format!("/brews/{}", created.brew.id)))
+        .header("Cookie", format!("brewlog_session={session_token}"))
+        .send()
+        .await
+        .expect("Failed to execute request")
+        .text()
+        .await
+        .expect("Failed to read body");
+
+    assert!(body.contains("93.0°C (set 96.0°C)"));
+    assert!(body.contains("Fellow Stagg EKG"));
+}
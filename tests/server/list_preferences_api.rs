@@ -0,0 +1,183 @@
+use brewlog::domain::list_preferences::{ListName, ListPreference};
+use brewlog::domain::listing::SortDirection;
+use serde_json::json;
+
+use super::helpers::{create_roaster_with_name, create_session, spawn_app, spawn_app_with_auth};
+
+#[tokio::test]
+async fn list_preferences_require_auth() {
+    let app = spawn_app().await;
+
+    let response = reqwest::Client::new()
+        .put(app.api_url("/list-preferences/roasts"))
+        .json(&json!({ "page_size": 20 }))
+        .send()
+        .await
+        .expect("failed to send request");
+
+    assert_eq!(response.status(), reqwest::StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn saving_a_list_preference_returns_it() {
+    let app = spawn_app_with_auth().await;
+    let client = reqwest::Client::new();
+
+    let response = client
+        .put(app.api_url("/list-preferences/roasts"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&json!({ "page_size": 20, "sort_key": "origin", "sort_direction": "asc" }))
+        .send()
+        .await
+        .expect("failed to send request");
+
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    let saved: ListPreference = response.json().await.expect("failed to parse preference");
+    assert_eq!(saved.list, ListName::Roasts);
+    assert_eq!(saved.page_size, Some(20));
+    assert_eq!(saved.sort_key.as_deref(), Some("origin"));
+    assert_eq!(saved.sort_direction, Some(SortDirection::Asc));
+
+    // Saving again replaces the previous defaults
+    client
+        .put(app.api_url("/list-preferences/roasts"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&json!({ "page_size": 0 }))
+        .send()
+        .await
+        .expect("failed to send request");
+
+    let preferences: Vec<ListPreference> = client
+        .get(app.api_url("/list-preferences"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .send()
+        .await
+        .expect("failed to send request")
+        .json()
+        .await
+        .expect("failed to parse preferences");
+
+    assert_eq!(preferences.len(), 1);
+    assert_eq!(preferences[0].page_size, Some(0));
+    assert_eq!(preferences[0].sort_key, None);
+}
+
+#[tokio::test]
+async fn saving_a_list_preference_validates_input() {
+    let app = spawn_app_with_auth().await;
+    let client = reqwest::Client::new();
+
+    for (path, body) in [
+        ("/list-preferences/timeline", json!({ "page_size": 20 })),
+        (
+            "/list-preferences/roasters",
+            json!({ "sort_key": "origin" }),
+        ),
+        ("/list-preferences/brews", json!({ "page_size": 500 })),
+        (
+            "/list-preferences/brews",
+            json!({ "sort_direction": "asc" }),
+        ),
+    ] {
+        let response = client
+            .put(app.api_url(path))
+            .bearer_auth(app.auth_token.as_ref().unwrap())
+            .json(&body)
+            .send()
+            .await
+            .expect("failed to send request");
+
+        assert_eq!(
+            response.status(),
+            reqwest::StatusCode::BAD_REQUEST,
+            "{path} {body}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn deleting_a_list_preference_removes_it() {
+    let app = spawn_app_with_auth().await;
+    let client = reqwest::Client::new();
+
+    client
+        .put(app.api_url("/list-preferences/brews"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&json!({ "page_size": 5 }))
+        .send()
+        .await
+        .expect("failed to send request");
+
+    let response = client
+        .delete(app.api_url("/list-preferences/brews"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .send()
+        .await
+        .expect("failed to send request");
+    assert_eq!(response.status(), reqwest::StatusCode::NO_CONTENT);
+
+    let response = client
+        .delete(app.api_url("/list-preferences/brews"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .send()
+        .await
+        .expect("failed to send request");
+    assert_eq!(response.status(), reqwest::StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn data_page_applies_saved_defaults_for_signed_in_user() {
+    let app = spawn_app_with_auth().await;
+    for name in ["Bravo Roasters", "Alpha Roasters", "Charlie Roasters"] {
+        create_roaster_with_name(&app, name).await;
+    }
+
+    let client = reqwest::Client::new();
+    client
+        .put(app.api_url("/list-preferences/roasters"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&json!({ "page_size": 2, "sort_key": "name", "sort_direction": "asc" }))
+        .send()
+        .await
+        .expect("failed to send request");
+
+    let session_token = create_session(&app).await;
+    let body = client
+        .get(app.page_url("/data?type=roasters"))
+        .header("Cookie", format!("brewlog_session={session_token}"))
+        .send()
+        .await
+        .expect("failed to send request")
+        .text()
+        .await
+        .expect("failed to read body");
+
+    assert!(body.contains("Alpha Roasters"));
+    assert!(body.contains("Bravo Roasters"));
+    assert!(!body.contains("Charlie Roasters"));
+
+    // Explicit query parameters take precedence over saved defaults
+    let body = client
+        .get(app.page_url("/data?type=roasters&page_size=10"))
+        .header("Cookie", format!("brewlog_session={session_token}"))
+        .send()
+        .await
+        .expect("failed to send request")
+        .text()
+        .await
+        .expect("failed to read body");
+
+    assert!(body.contains("Charlie Roasters"));
+
+    // Anonymous visitors see the built-in defaults
+    let body = client
+        .get(app.page_url("/data?type=roasters"))
+        .send()
+        .await
+        .expect("failed to send request")
+        .text()
+        .await
+        .expect("failed to read body");
+
+    assert!(body.contains("Charlie Roasters"));
+}
@@ -1,23 +1,70 @@
+pub mod admin_api;
+pub mod ai_prices_api;
+pub mod ai_resilience_api;
+pub mod aliases_api;
+pub mod api_versions;
+pub mod attribution;
 pub mod auth_api;
+pub mod autocomplete_api;
+pub mod backfill_api;
 pub mod backup;
+pub mod bag_finish_api;
+pub mod bag_images_api;
+pub mod bag_ledger_api;
 pub mod bags_api;
+pub mod brew_parsing_api;
+pub mod brew_shares_api;
 pub mod brews_api;
+pub mod bulk_api;
 pub mod cafes_api;
 pub mod checkin_api;
+pub mod comments_api;
+pub mod compare_api;
 pub mod cups_api;
+pub mod currencies_api;
 pub mod datastar;
+pub mod demo;
+pub mod events_api;
+pub mod export_api;
 pub mod extraction_api;
+pub mod favourites_api;
+pub mod filter_paper_stock_api;
 pub mod form_submissions;
 pub mod gear_api;
+pub mod grinder_calibrations_api;
 pub mod helpers;
 pub mod images_api;
+pub mod import_api;
+pub mod integrity;
+pub mod kettles_api;
+pub mod list_preferences_api;
+pub mod map_tiles_api;
+pub mod nav_search_api;
 pub mod nearby_api;
+pub mod notifications_api;
 pub mod pages;
+pub mod processes_api;
+pub mod profile_api;
+pub mod qr_api;
+pub mod quick_actions_api;
+pub mod quick_notes_api;
+pub mod ratings_api;
+pub mod registration_tokens_api;
+pub mod roast_blends_api;
+pub mod roast_lots_api;
 pub mod roasters_api;
+pub mod roasting_api;
 pub mod roasts_api;
+pub mod route_auth;
+pub mod saved_searches_api;
 pub mod scan_api;
+pub mod sessions_api;
+pub mod settings_api;
+pub mod slugs_api;
 pub mod static_assets;
 pub mod stats_api;
+pub mod tenants_api;
 pub mod test_macros;
 pub mod timeline;
+pub mod trips_api;
 pub mod webauthn_api;
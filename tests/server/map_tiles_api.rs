@@ -0,0 +1,160 @@
+use brewlog::domain::settings::Settings;
+use reqwest::Client;
+use serde_json::{Value, json};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use super::helpers::{TestApp, create_default_cafe, create_session, spawn_app_with_auth};
+
+async fn put_settings(app: &TestApp, payload: Value) -> reqwest::Response {
+    Client::new()
+        .put(app.api_url("/settings"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&payload)
+        .send()
+        .await
+        .expect("failed to execute request")
+}
+
+async fn get_tile(app: &TestApp, tile: &str) -> reqwest::Response {
+    Client::new()
+        .get(app.page_url(&format!("/map-tiles/{tile}")))
+        .send()
+        .await
+        .expect("failed to execute request")
+}
+
+async fn page(app: &TestApp, url: &str, session: &str) -> String {
+    Client::new()
+        .get(app.page_url(url))
+        .header("Cookie", format!("brewlog_session={session}"))
+        .send()
+        .await
+        .expect("failed to fetch page")
+        .text()
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn tile_url_must_be_a_template() {
+    let app = spawn_app_with_auth().await;
+
+    let response = put_settings(
+        &app,
+        json!({ "map_tile_url": "https://tiles.example.com/{z}/{x}.png" }),
+    )
+    .await;
+    assert_eq!(response.status(), 400);
+
+    let response = put_settings(
+        &app,
+        json!({ "map_tile_url": "javascript:alert(1)//{z}/{x}/{y}" }),
+    )
+    .await;
+    assert_eq!(response.status(), 400);
+
+    let response = put_settings(
+        &app,
+        json!({
+            "map_tile_url": " https://tiles.example.com/{z}/{x}/{y}.png ",
+            "map_attribution": "© OpenStreetMap contributors",
+        }),
+    )
+    .await;
+    assert_eq!(response.status(), 200);
+    let settings: Settings = response.json().await.unwrap();
+    assert_eq!(
+        settings.map_tile_url.as_deref(),
+        Some("https://tiles.example.com/{z}/{x}/{y}.png")
+    );
+
+    let response = put_settings(&app, json!({ "map_tile_url": "" })).await;
+    let settings: Settings = response.json().await.unwrap();
+    assert_eq!(settings.map_tile_url, None);
+}
+
+#[tokio::test]
+async fn tiles_are_proxied_from_the_configured_server() {
+    let app = spawn_app_with_auth().await;
+    let tiles = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/tiles/3/4/2.png"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(b"PNG".to_vec(), "image/png"))
+        .expect(1)
+        .mount(&tiles)
+        .await;
+
+    assert_eq!(get_tile(&app, "3/4/2").await.status(), 404);
+
+    put_settings(
+        &app,
+        json!({ "map_tile_url": format!("{}/tiles/{{z}}/{{x}}/{{y}}.png", tiles.uri()) }),
+    )
+    .await;
+
+    let response = get_tile(&app, "3/4/2").await;
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers()["content-type"], "image/png");
+    assert!(
+        response.headers()["cache-control"]
+            .to_str()
+            .unwrap()
+            .starts_with("public")
+    );
+    assert_eq!(response.bytes().await.unwrap().as_ref(), b"PNG");
+
+    // Off the grid at zoom 3.
+    assert_eq!(get_tile(&app, "3/8/0").await.status(), 404);
+}
+
+#[tokio::test]
+async fn non_image_tiles_are_refused() {
+    let app = spawn_app_with_auth().await;
+    let tiles = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_raw(b"<script></script>".to_vec(), "text/html"),
+        )
+        .mount(&tiles)
+        .await;
+    put_settings(
+        &app,
+        json!({ "map_tile_url": format!("{}/{{z}}/{{x}}/{{y}}", tiles.uri()) }),
+    )
+    .await;
+
+    assert_eq!(get_tile(&app, "0/0/0").await.status(), 502);
+}
+
+#[tokio::test]
+async fn cafe_pages_switch_to_tile_maps_when_configured() {
+    let app = spawn_app_with_auth().await;
+    let cafe = create_default_cafe(&app).await;
+    let session = create_session(&app).await;
+    let detail_url = format!("/cafes/{}", cafe.slug);
+    let edit_url = format!("/cafes/{}/edit", cafe.id);
+
+    assert!(
+        page(&app, &detail_url, &session)
+            .await
+            .contains("<world-map")
+    );
+    assert!(!page(&app, &edit_url, &session).await.contains("<tile-map"));
+
+    put_settings(
+        &app,
+        json!({
+            "map_tile_url": "https://tiles.example.com/{z}/{x}/{y}.png",
+            "map_attribution": "Example Tiles",
+        }),
+    )
+    .await;
+
+    let detail = page(&app, &detail_url, &session).await;
+    assert!(detail.contains("<tile-map"));
+    assert!(!detail.contains("<world-map"));
+    assert!(detail.contains("Example Tiles"));
+    let edit = page(&app, &edit_url, &session).await;
+    assert!(edit.contains("data-pickable"));
+}
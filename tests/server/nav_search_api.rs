@@ -0,0 +1,78 @@
+use reqwest::Client;
+
+use crate::helpers::{
+    assert_datastar_headers, create_default_roast, create_default_roaster, create_session,
+    spawn_app_with_auth,
+};
+
+#[tokio::test]
+async fn nav_search_matches_roasters_roasts_and_pages() {
+    let app = spawn_app_with_auth().await;
+    let client = Client::new();
+    let roaster = create_default_roaster(&app).await;
+    let roast = create_default_roast(&app, roaster.id).await;
+
+    let response = client
+        .get(app.api_url("/nav-search?q=roast"))
+        .header("datastar-request", "true")
+        .send()
+        .await
+        .expect("failed to search");
+
+    assert_eq!(response.status(), 200);
+    assert_datastar_headers(&response, "#nav-search-results");
+    let body = response.text().await.unwrap();
+    assert!(body.contains(&format!("href=\"/roasters/{}\"", roaster.slug)));
+    assert!(body.contains(&format!(
+        "href=\"/roasters/{}/roasts/{}\"",
+        roaster.slug, roast.slug
+    )));
+    assert!(body.contains("href=\"/roasting\""));
+}
+
+#[tokio::test]
+async fn nav_search_hides_signed_in_pages_from_visitors() {
+    let app = spawn_app_with_auth().await;
+    let client = Client::new();
+
+    let body = client
+        .get(app.api_url("/nav-search?q=profile"))
+        .send()
+        .await
+        .expect("failed to search")
+        .text()
+        .await
+        .unwrap();
+    assert!(!body.contains("href=\"/profile\""));
+    assert!(body.contains("Nothing matches"));
+
+    let session = create_session(&app).await;
+    let body = client
+        .get(app.api_url("/nav-search?q=profile"))
+        .header("Cookie", format!("brewlog_session={session}"))
+        .send()
+        .await
+        .expect("failed to search")
+        .text()
+        .await
+        .unwrap();
+    assert!(body.contains("href=\"/profile\""));
+}
+
+#[tokio::test]
+async fn nav_search_with_an_empty_query_renders_nothing() {
+    let app = spawn_app_with_auth().await;
+    let client = Client::new();
+    create_default_roaster(&app).await;
+
+    let body = client
+        .get(app.api_url("/nav-search?q=%20"))
+        .send()
+        .await
+        .expect("failed to search")
+        .text()
+        .await
+        .unwrap();
+    assert!(!body.contains("data-role=\"nav-result\""));
+    assert!(!body.contains("Nothing matches"));
+}
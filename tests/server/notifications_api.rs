@@ -0,0 +1,244 @@
+use brewlog::domain::brews::{Brew, NewBrew};
+use brewlog::domain::notifications::{Notification, NotificationKind};
+use reqwest::Client;
+use serde_json::{Value, json};
+
+use super::helpers::{
+    TestApp, assert_datastar_headers, create_default_brew, create_entity, create_second_user,
+    create_session, spawn_app_with_auth,
+};
+
+async fn list_notifications(app: &TestApp, token: &str) -> Value {
+    Client::new()
+        .get(app.api_url("/notifications"))
+        .bearer_auth(token)
+        .send()
+        .await
+        .expect("failed to execute request")
+        .json()
+        .await
+        .unwrap()
+}
+
+fn notifications(body: &Value) -> Vec<Notification> {
+    serde_json::from_value(body["notifications"].clone()).unwrap()
+}
+
+/// Brew again from the same bag and kit as `brew`, using `coffee_weight` grams.
+async fn brew_again(app: &TestApp, brew: &Brew, coffee_weight: f64) -> Brew {
+    create_entity(
+        app,
+        "/brews",
+        &NewBrew {
+            bag_id: brew.bag_id,
+            coffee_weight,
+            grinder_id: brew.grinder_id,
+            grind_setting: 24.0,
+            brewer_id: brew.brewer_id,
+            filter_paper_id: None,
+            water_volume: 250,
+            water_temp: 92.0,
+            bypass_water_ml: None,
+            kettle_id: None,
+            actual_water_temp: None,
+            quick_notes: Vec::new(),
+            brew_time: None,
+            created_at: None,
+        },
+    )
+    .await
+}
+
+#[tokio::test]
+async fn notifications_require_authentication() {
+    let app = spawn_app_with_auth().await;
+
+    let response = Client::new()
+        .get(app.api_url("/notifications"))
+        .send()
+        .await
+        .expect("failed to execute request");
+    assert_eq!(response.status(), 401);
+
+    let response = Client::new()
+        .get(app.page_url("/notifications/menu"))
+        .send()
+        .await
+        .expect("failed to execute request");
+    assert_eq!(response.status(), 401);
+}
+
+#[tokio::test]
+async fn brewing_a_bag_below_the_threshold_notifies_once() {
+    let app = spawn_app_with_auth().await;
+    let token = app.auth_token.clone().unwrap();
+
+    // The default bag holds 250g and the default brew uses 15g.
+    let brew = create_default_brew(&app).await;
+    assert_eq!(list_notifications(&app, &token).await["unread"], 0);
+
+    brew_again(&app, &brew, 190.0).await;
+    let body = list_notifications(&app, &token).await;
+    assert_eq!(body["unread"], 1);
+    let items = notifications(&body);
+    assert_eq!(items[0].kind, NotificationKind::BagRunningLow);
+    assert!(items[0].title.contains("45g left"));
+    assert_eq!(
+        items[0].link.as_deref(),
+        Some(format!("/bags/{}", brew.bag_id).as_str())
+    );
+
+    // Already below the threshold, so no repeat alert.
+    brew_again(&app, &brew, 15.0).await;
+    assert_eq!(list_notifications(&app, &token).await["unread"], 1);
+}
+
+#[tokio::test]
+async fn the_configured_threshold_decides_when_a_bag_runs_low() {
+    let app = spawn_app_with_auth().await;
+    let token = app.auth_token.clone().unwrap();
+    Client::new()
+        .put(app.api_url("/settings"))
+        .bearer_auth(&token)
+        .json(&json!({ "low_stock_threshold": 100 }))
+        .send()
+        .await
+        .expect("failed to execute request");
+
+    let brew = create_default_brew(&app).await;
+    brew_again(&app, &brew, 140.0).await;
+
+    let items = notifications(&list_notifications(&app, &token).await);
+    assert_eq!(items.len(), 1);
+    assert!(items[0].title.contains("95g left"));
+}
+
+#[tokio::test]
+async fn comments_notify_the_owner_and_other_participants() {
+    let app = spawn_app_with_auth().await;
+    let token = app.auth_token.clone().unwrap();
+    let housemate = create_second_user(&app, "housemate").await.token;
+    let brew = create_default_brew(&app).await;
+    let path = app.api_url(&format!("/brews/{}/comments", brew.id));
+
+    let response = Client::new()
+        .post(&path)
+        .bearer_auth(&housemate)
+        .json(&json!({ "body": "Looks tasty" }))
+        .send()
+        .await
+        .expect("failed to execute request");
+    assert_eq!(response.status(), 201);
+
+    let items = notifications(&list_notifications(&app, &token).await);
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0].kind, NotificationKind::CommentReceived);
+    assert!(items[0].title.starts_with("housemate commented on"));
+    assert_eq!(
+        items[0].link.as_deref(),
+        Some(format!("/brews/{}#comments", brew.id).as_str())
+    );
+    assert_eq!(list_notifications(&app, &housemate).await["unread"], 0);
+
+    // The owner replying notifies the housemate but not themselves.
+    Client::new()
+        .post(&path)
+        .bearer_auth(&token)
+        .json(&json!({ "body": "It was!" }))
+        .send()
+        .await
+        .expect("failed to execute request");
+    assert_eq!(list_notifications(&app, &token).await["unread"], 1);
+    assert_eq!(list_notifications(&app, &housemate).await["unread"], 1);
+}
+
+#[tokio::test]
+async fn notifications_can_be_marked_read() {
+    let app = spawn_app_with_auth().await;
+    let token = app.auth_token.clone().unwrap();
+    let housemate = create_second_user(&app, "housemate").await.token;
+    let brew = create_default_brew(&app).await;
+    for body in ["One", "Two"] {
+        Client::new()
+            .post(app.api_url(&format!("/brews/{}/comments", brew.id)))
+            .bearer_auth(&housemate)
+            .json(&json!({ "body": body }))
+            .send()
+            .await
+            .expect("failed to execute request");
+    }
+    let items = notifications(&list_notifications(&app, &token).await);
+    assert_eq!(items.len(), 2);
+
+    // Other users cannot touch your notifications.
+    let response = Client::new()
+        .post(app.api_url(&format!("/notifications/{}/read", items[0].id)))
+        .bearer_auth(&housemate)
+        .send()
+        .await
+        .expect("failed to execute request");
+    assert_eq!(response.status(), 404);
+
+    let response = Client::new()
+        .post(app.api_url(&format!("/notifications/{}/read", items[0].id)))
+        .bearer_auth(&token)
+        .send()
+        .await
+        .expect("failed to execute request");
+    assert_eq!(response.status(), 200);
+    let read: Notification = response.json().await.unwrap();
+    assert!(read.is_read());
+    assert_eq!(list_notifications(&app, &token).await["unread"], 1);
+
+    let response = Client::new()
+        .post(app.api_url("/notifications/read-all"))
+        .bearer_auth(&token)
+        .send()
+        .await
+        .expect("failed to execute request");
+    assert_eq!(response.status(), 200);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["updated"], 1);
+    assert_eq!(list_notifications(&app, &token).await["unread"], 0);
+}
+
+#[tokio::test]
+async fn bell_menu_shows_unread_notifications() {
+    let app = spawn_app_with_auth().await;
+    let housemate = create_second_user(&app, "housemate").await.token;
+    let brew = create_default_brew(&app).await;
+    Client::new()
+        .post(app.api_url(&format!("/brews/{}/comments", brew.id)))
+        .bearer_auth(&housemate)
+        .json(&json!({ "body": "Nice one" }))
+        .send()
+        .await
+        .expect("failed to execute request");
+    let session = create_session(&app).await;
+
+    let response = Client::new()
+        .get(app.page_url("/notifications/menu"))
+        .header("Cookie", format!("brewlog_session={session}"))
+        .header("datastar-request", "true")
+        .send()
+        .await
+        .expect("failed to execute request");
+    assert_eq!(response.status(), 200);
+    assert_datastar_headers(&response, "#notifications-menu");
+
+    let body = response.text().await.unwrap();
+    assert!(body.contains("data-role=\"notifications-unread\""));
+    assert!(body.contains("housemate commented on"));
+    assert!(body.contains("Mark all read"));
+
+    let page = Client::new()
+        .get(app.page_url("/"))
+        .header("Cookie", format!("brewlog_session={session}"))
+        .send()
+        .await
+        .expect("failed to fetch page")
+        .text()
+        .await
+        .unwrap();
+    assert!(page.contains("@get('/notifications/menu')"));
+}
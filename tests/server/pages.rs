@@ -232,6 +232,7 @@ async fn admin_page_returns_200_when_authenticated() {
 
     let body = response.text().await.expect("Failed to read body");
     assert_full_page(&body);
+    assert!(body.contains("Database Size"));
 }
 
 #[tokio::test]
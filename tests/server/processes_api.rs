@@ -0,0 +1,316 @@
+use brewlog::domain::ids::RoasterId;
+use brewlog::domain::processes::{CustomProcess, NewCustomProcess, ProcessOption};
+use brewlog::domain::roasts::{FieldSuggestion, NewRoast, Roast};
+use brewlog::infrastructure::backup::BackupData;
+
+use super::helpers::{
+    TestApp, create_default_brew, create_default_roaster, create_entity, create_session, spawn_app,
+    spawn_app_with_auth,
+};
+
+fn new_process(name: &str, family: Option<&str>) -> NewCustomProcess {
+    NewCustomProcess {
+        name: name.to_string(),
+        family: family.map(String::from),
+        created_at: None,
+    }
+}
+
+async fn post_process(app: &TestApp, name: &str, family: Option<&str>) -> reqwest::Response {
+    reqwest::Client::new()
+        .post(app.api_url("/processes"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&new_process(name, family))
+        .send()
+        .await
+        .expect("failed to execute request")
+}
+
+fn roast_with_process(roaster_id: RoasterId, name: &str, process: &str) -> NewRoast {
+    NewRoast {
+        roaster_id,
+        name: name.to_string(),
+        origin: "Colombia".to_string(),
+        region: "Huila".to_string(),
+        producer: "Finca Milan".to_string(),
+        tasting_notes: vec!["Plum".to_string()],
+        process: process.to_string(),
+        components: Vec::new(),
+        harvest_year: None,
+        created_at: None,
+    }
+}
+
+async fn post_roast(app: &TestApp, roast: &NewRoast) -> reqwest::Response {
+    reqwest::Client::new()
+        .post(app.api_url("/roasts"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(roast)
+        .send()
+        .await
+        .expect("failed to execute request")
+}
+
+async fn list_processes(app: &TestApp) -> Vec<ProcessOption> {
+    reqwest::Client::new()
+        .get(app.api_url("/processes"))
+        .send()
+        .await
+        .expect("failed to execute request")
+        .json()
+        .await
+        .expect("failed to parse processes")
+}
+
+#[tokio::test]
+async fn built_in_processes_are_listed_publicly() {
+    let app = spawn_app().await;
+
+    let processes = list_processes(&app).await;
+
+    let washed = processes.iter().find(|p| p.name == "Washed").unwrap();
+    assert_eq!(washed.family, "Washed");
+    assert!(washed.id.is_none());
+    assert!(processes.iter().any(|p| p.name == "Anaerobic Natural"));
+}
+
+#[tokio::test]
+async fn creating_a_process_requires_authentication() {
+    let app = spawn_app().await;
+
+    let response = reqwest::Client::new()
+        .post(app.api_url("/processes"))
+        .json(&new_process("Koji", None))
+        .send()
+        .await
+        .expect("failed to execute request");
+
+    assert_eq!(response.status(), 401);
+}
+
+#[tokio::test]
+async fn custom_processes_join_the_list() {
+    let app = spawn_app_with_auth().await;
+
+    let response = post_process(&app, " Lactic  Natural ", None).await;
+    assert_eq!(response.status(), 201);
+    let created: CustomProcess = response.json().await.expect("failed to parse");
+    assert_eq!(created.name, "Lactic Natural");
+    assert_eq!(created.family, "Natural");
+
+    let processes = list_processes(&app).await;
+    let last = processes.last().unwrap();
+    assert_eq!(last.name, "Lactic Natural");
+    assert_eq!(last.id, Some(created.id));
+}
+
+#[tokio::test]
+async fn process_names_must_be_unique_and_not_built_in() {
+    let app = spawn_app_with_auth().await;
+    let _: CustomProcess = create_entity(&app, "/processes", &new_process("Koji", None)).await;
+
+    assert_eq!(post_process(&app, "koji", None).await.status(), 409);
+    assert_eq!(post_process(&app, "Fully Washed", None).await.status(), 400);
+    assert_eq!(
+        post_process(&app, "Lactic", Some("Fermented"))
+            .await
+            .status(),
+        400
+    );
+}
+
+#[tokio::test]
+async fn roasts_store_the_canonical_process_name() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+
+    let roast: Roast = create_entity(
+        &app,
+        "/roasts",
+        &roast_with_process(roaster.id, "Milan", "fully-washed process"),
+    )
+    .await;
+
+    assert_eq!(roast.process.as_deref(), Some("Washed"));
+}
+
+#[tokio::test]
+async fn roasts_reject_processes_outside_the_taxonomy() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+    let roast = roast_with_process(roaster.id, "Milan", "Koji");
+
+    assert_eq!(post_roast(&app, &roast).await.status(), 400);
+
+    let _: CustomProcess =
+        create_entity(&app, "/processes", &new_process("Koji", Some("Other"))).await;
+    let response = post_roast(&app, &roast).await;
+    assert_eq!(response.status(), 201);
+    let created: Roast = response.json().await.expect("failed to parse");
+    assert_eq!(created.process.as_deref(), Some("Koji"));
+}
+
+#[tokio::test]
+async fn deleting_a_process_keeps_it_on_existing_roasts() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+    let process: CustomProcess =
+        create_entity(&app, "/processes", &new_process("Koji", None)).await;
+    let roast: Roast = create_entity(
+        &app,
+        "/roasts",
+        &roast_with_process(roaster.id, "Milan", "Koji"),
+    )
+    .await;
+
+    let response = reqwest::Client::new()
+        .delete(app.api_url(&format!("/processes/{}", process.id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .send()
+        .await
+        .expect("failed to execute request");
+    assert_eq!(response.status(), 204);
+
+    // Editing other fields leaves the retired process in place.
+    let response = reqwest::Client::new()
+        .put(app.api_url(&format!("/roasts/{}", roast.id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&serde_json::json!({ "name": "Milan Koji", "process": "Koji" }))
+        .send()
+        .await
+        .expect("failed to execute request");
+    assert!(response.status().is_success());
+
+    let response = reqwest::Client::new()
+        .put(app.api_url(&format!("/roasts/{}", roast.id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&serde_json::json!({ "process": "Shio Koji" }))
+        .send()
+        .await
+        .expect("failed to execute request");
+    assert_eq!(response.status(), 400);
+}
+
+#[tokio::test]
+async fn process_autocomplete_offers_the_taxonomy_by_use() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+    let _: Roast = create_entity(
+        &app,
+        "/roasts",
+        &roast_with_process(roaster.id, "One", "Honey"),
+    )
+    .await;
+    let _: Roast = create_entity(
+        &app,
+        "/roasts",
+        &roast_with_process(roaster.id, "Two", "pulped natural"),
+    )
+    .await;
+
+    let suggestions: Vec<FieldSuggestion> = reqwest::Client::new()
+        .get(app.api_url("/autocomplete?field=process&q=hon"))
+        .send()
+        .await
+        .expect("failed to execute request")
+        .json()
+        .await
+        .expect("failed to parse");
+
+    assert_eq!(suggestions[0].value, "Honey");
+    assert_eq!(suggestions[0].uses, 2);
+    assert!(suggestions.iter().any(|s| s.value == "Black Honey"));
+}
+
+#[tokio::test]
+async fn stats_group_custom_processes_by_their_family() {
+    let app = spawn_app_with_auth().await;
+    let brew = create_default_brew(&app).await;
+    let _: CustomProcess =
+        create_entity(&app, "/processes", &new_process("Koji", Some("Natural"))).await;
+    let client = reqwest::Client::new();
+
+    let bag: serde_json::Value = client
+        .get(app.api_url(&format!("/bags/{}", brew.bag_id)))
+        .send()
+        .await
+        .expect("failed to execute request")
+        .json()
+        .await
+        .expect("failed to parse bag");
+    let response = client
+        .put(app.api_url(&format!("/roasts/{}", bag["roast_id"])))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&serde_json::json!({ "process": "koji" }))
+        .send()
+        .await
+        .expect("failed to execute request");
+    assert!(response.status().is_success());
+
+    let stats: serde_json::Value = client
+        .get(app.api_url("/stats"))
+        .send()
+        .await
+        .expect("failed to execute request")
+        .json()
+        .await
+        .expect("failed to parse stats");
+
+    assert_eq!(
+        stats["tasting"]["process_counts"],
+        serde_json::json!([["Natural", 1]])
+    );
+}
+
+#[tokio::test]
+async fn admin_page_lists_custom_processes() {
+    let app = spawn_app_with_auth().await;
+    let _: CustomProcess =
+        create_entity(&app, "/processes", &new_process("Koji", Some("Other"))).await;
+    let session_token = create_session(&app).await;
+
+    let body = reqwest::Client::new()
+        .get(app.page_url("/admin"))
+        .header("Cookie", format!("brewlog_session={session_token}"))
+        .send()
+        .await
+        .expect("failed to execute request")
+        .text()
+        .await
+        .expect("failed to read body");
+
+    assert!(body.contains("Koji"));
+    assert!(body.contains("addProcess"));
+}
+
+#[tokio::test]
+async fn custom_processes_survive_backup_and_restore() {
+    let source = spawn_app_with_auth().await;
+    let _: CustomProcess =
+        create_entity(&source, "/processes", &new_process("Koji", Some("Other"))).await;
+    let client = reqwest::Client::new();
+
+    let backup: BackupData = client
+        .get(source.api_url("/backup"))
+        .bearer_auth(source.auth_token.as_ref().unwrap())
+        .send()
+        .await
+        .expect("failed to export backup")
+        .json()
+        .await
+        .expect("failed to parse backup");
+    assert_eq!(backup.processes.len(), 1);
+
+    let target = spawn_app_with_auth().await;
+    let response = client
+        .post(target.api_url("/backup/restore"))
+        .bearer_auth(target.auth_token.as_ref().unwrap())
+        .json(&backup)
+        .send()
+        .await
+        .expect("failed to restore backup");
+    assert_eq!(response.status(), 204);
+
+    let processes = list_processes(&target).await;
+    assert_eq!(processes.last().unwrap().name, "Koji");
+}
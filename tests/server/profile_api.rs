@@ -0,0 +1,157 @@
+use serde_json::{Value, json};
+
+use super::helpers::{TestApp, create_session, spawn_app_with_auth};
+
+async fn update_profile(app: &TestApp, body: Value) -> reqwest::Response {
+    reqwest::Client::new()
+        .put(app.api_url("/me"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&body)
+        .send()
+        .await
+        .expect("failed to execute request")
+}
+
+#[tokio::test]
+async fn getting_the_profile_requires_authentication() {
+    let app = spawn_app_with_auth().await;
+
+    let response = reqwest::Client::new()
+        .get(app.api_url("/me"))
+        .send()
+        .await
+        .expect("failed to execute request");
+
+    assert_eq!(response.status(), 401);
+}
+
+#[tokio::test]
+async fn profile_defaults_to_the_username() {
+    let app = spawn_app_with_auth().await;
+
+    let response = reqwest::Client::new()
+        .get(app.api_url("/me"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .send()
+        .await
+        .expect("failed to execute request");
+    assert_eq!(response.status(), 200);
+
+    let body: Value = response.json().await.expect("failed to parse response");
+    assert!(body["display_name"].is_null());
+    assert_eq!(body["name"], body["username"]);
+    assert!(body["avatar_url"].is_null());
+}
+
+#[tokio::test]
+async fn updating_the_display_name_trims_and_clears() {
+    let app = spawn_app_with_auth().await;
+
+    let response = update_profile(&app, json!({ "display_name": "  Alice Smith " })).await;
+    assert_eq!(response.status(), 200);
+    let body: Value = response.json().await.expect("failed to parse response");
+    assert_eq!(body["display_name"], "Alice Smith");
+    assert_eq!(body["name"], "Alice Smith");
+
+    let response = update_profile(&app, json!({ "display_name": "   " })).await;
+    assert_eq!(response.status(), 200);
+    let body: Value = response.json().await.expect("failed to parse response");
+    assert!(body["display_name"].is_null());
+    assert_eq!(body["name"], body["username"]);
+}
+
+#[tokio::test]
+async fn overlong_display_names_are_rejected() {
+    let app = spawn_app_with_auth().await;
+
+    let response = update_profile(&app, json!({ "display_name": "a".repeat(65) })).await;
+
+    assert_eq!(response.status(), 400);
+}
+
+#[tokio::test]
+async fn uploaded_avatar_is_reported_on_the_profile() {
+    use base64::Engine;
+    use image::{ImageBuffer, Rgba};
+
+    let app = spawn_app_with_auth().await;
+    let client = reqwest::Client::new();
+
+    let me: Value = client
+        .get(app.api_url("/me"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .send()
+        .await
+        .expect("failed to execute request")
+        .json()
+        .await
+        .expect("failed to parse response");
+    let id = me["id"].as_i64().expect("missing id");
+
+    let img = ImageBuffer::from_pixel(1, 1, Rgba([255u8, 0, 0, 255]));
+    let mut buf = Vec::new();
+    let encoder = image::codecs::png::PngEncoder::new(&mut buf);
+    image::ImageEncoder::write_image(encoder, img.as_raw(), 1, 1, image::ColorType::Rgba8.into())
+        .expect("failed to encode test PNG");
+    let data_url = format!(
+        "data:image/png;base64,{}",
+        base64::engine::general_purpose::STANDARD.encode(&buf)
+    );
+
+    let response = client
+        .put(app.api_url(&format!("/user/{id}/image")))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&json!({ "image": data_url }))
+        .send()
+        .await
+        .expect("failed to upload image");
+    assert_eq!(response.status(), 204);
+
+    let me: Value = client
+        .get(app.api_url("/me"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .send()
+        .await
+        .expect("failed to execute request")
+        .json()
+        .await
+        .expect("failed to parse response");
+    assert_eq!(me["avatar_url"], format!("/api/v1/user/{id}/image"));
+}
+
+#[tokio::test]
+async fn profile_page_lists_passkeys_and_display_name() {
+    let app = spawn_app_with_auth().await;
+    update_profile(&app, json!({ "display_name": "Alice Smith" })).await;
+
+    let session_token = create_session(&app).await;
+    let body = reqwest::Client::new()
+        .get(app.page_url("/profile"))
+        .header("Cookie", format!("brewlog_session={session_token}"))
+        .send()
+        .await
+        .expect("failed to execute request")
+        .text()
+        .await
+        .expect("failed to read body");
+
+    assert!(body.contains("Alice Smith"));
+    assert!(body.contains("Passkeys"));
+}
+
+#[tokio::test]
+async fn profile_page_redirects_without_a_session() {
+    let app = spawn_app_with_auth().await;
+
+    let response = reqwest::Client::builder()
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .unwrap()
+        .get(app.page_url("/profile"))
+        .send()
+        .await
+        .expect("failed to execute request");
+
+    assert_eq!(response.status(), 303);
+    assert_eq!(response.headers()["location"], "/login");
+}
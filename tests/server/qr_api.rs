@@ -0,0 +1,53 @@
+use super::helpers::{spawn_app, spawn_app_with_auth};
+
+#[tokio::test]
+async fn qr_code_requires_authentication() {
+    let app = spawn_app().await;
+
+    let response = reqwest::Client::new()
+        .get(app.api_url("/qr?data=https://example.com"))
+        .send()
+        .await
+        .expect("failed to execute request");
+
+    assert_eq!(response.status(), 401);
+}
+
+#[tokio::test]
+async fn qr_code_is_rendered_as_svg() {
+    let app = spawn_app_with_auth().await;
+
+    let response = reqwest::Client::new()
+        .get(app.api_url("/qr"))
+        .query(&[("data", "https://example.com/share/abc")])
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .send()
+        .await
+        .expect("failed to execute request");
+
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response.headers()["content-type"].to_str().unwrap(),
+        "image/svg+xml"
+    );
+    let body = response.text().await.expect("failed to read body");
+    assert!(body.contains("<svg"));
+}
+
+#[tokio::test]
+async fn qr_code_rejects_empty_and_oversized_data() {
+    let app = spawn_app_with_auth().await;
+    let client = reqwest::Client::new();
+
+    for data in [String::new(), "a".repeat(2000)] {
+        let response = client
+            .get(app.api_url("/qr"))
+            .query(&[("data", data)])
+            .bearer_auth(app.auth_token.as_ref().unwrap())
+            .send()
+            .await
+            .expect("failed to execute request");
+
+        assert_eq!(response.status(), 400);
+    }
+}
@@ -0,0 +1,153 @@
+use brewlog::domain::cups::{Cup, NewCup};
+use brewlog::domain::quick_actions::{QuickAction, QuickActionKind};
+use reqwest::Client;
+
+use super::helpers::{
+    TestApp, assert_datastar_headers, create_default_brew, create_default_cafe,
+    create_default_roast, create_entity, create_roaster_with_name, create_session,
+    spawn_app_with_auth,
+};
+
+async fn check_in(app: &TestApp) -> Cup {
+    let roaster = create_roaster_with_name(app, "Cafe Roasters").await;
+    let roast = create_default_roast(app, roaster.id).await;
+    let cafe = create_default_cafe(app).await;
+    create_entity(
+        app,
+        "/cups",
+        &NewCup {
+            roast_id: roast.id,
+            cafe_id: Some(cafe.id),
+            bag_id: None,
+            rating: None,
+            created_at: None,
+        },
+    )
+    .await
+}
+
+async fn list_quick_actions(app: &TestApp) -> Vec<QuickAction> {
+    let response = Client::new()
+        .get(app.api_url("/quick-actions"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .send()
+        .await
+        .expect("failed to execute request");
+    assert_eq!(response.status(), 200);
+    response.json().await.unwrap()
+}
+
+#[tokio::test]
+async fn quick_actions_are_empty_without_history() {
+    let app = spawn_app_with_auth().await;
+
+    assert!(list_quick_actions(&app).await.is_empty());
+}
+
+#[tokio::test]
+async fn quick_actions_suggest_recent_brews_and_cafes() {
+    let app = spawn_app_with_auth().await;
+    let brew = create_default_brew(&app).await;
+    let cup = check_in(&app).await;
+
+    let actions = list_quick_actions(&app).await;
+
+    let brew_again = actions
+        .iter()
+        .find(|a| a.kind == QuickActionKind::BrewAgain)
+        .expect("expected a brew again action");
+    assert_eq!(brew_again.label, "Brew Test Roast again");
+    assert!(brew_again.href.contains(&format!("bag_id={}", brew.bag_id)));
+
+    let check_in = actions
+        .iter()
+        .find(|a| a.kind == QuickActionKind::CheckIn)
+        .expect("expected a check-in action");
+    assert_eq!(check_in.label, "Check in at Blue Bottle");
+    assert_eq!(
+        check_in.href,
+        format!("/check-in?cafe_id={}", cup.cafe_id.unwrap())
+    );
+}
+
+#[tokio::test]
+async fn quick_actions_skip_finished_bags() {
+    let app = spawn_app_with_auth().await;
+    let brew = create_default_brew(&app).await;
+
+    let response = Client::new()
+        .put(app.api_url(&format!("/bags/{}", brew.bag_id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&serde_json::json!({ "closed": true }))
+        .send()
+        .await
+        .expect("failed to execute request");
+    assert_eq!(response.status(), 200);
+
+    assert!(list_quick_actions(&app).await.is_empty());
+}
+
+#[tokio::test]
+async fn quick_actions_fragment_renders_suggestions() {
+    let app = spawn_app_with_auth().await;
+    create_default_brew(&app).await;
+    let session = create_session(&app).await;
+
+    let response = Client::new()
+        .get(app.page_url("/quick-actions"))
+        .header("Cookie", format!("brewlog_session={session}"))
+        .header("datastar-request", "true")
+        .send()
+        .await
+        .expect("failed to execute request");
+    assert_eq!(response.status(), 200);
+    assert_datastar_headers(&response, "#quick-actions");
+
+    let body = response.text().await.unwrap();
+    assert!(body.contains("data-role=\"quick-action\""));
+    assert!(body.contains("Brew Test Roast again"));
+
+    let page = Client::new()
+        .get(app.page_url("/"))
+        .header("Cookie", format!("brewlog_session={session}"))
+        .send()
+        .await
+        .expect("failed to fetch page")
+        .text()
+        .await
+        .unwrap();
+    assert!(page.contains("@get('/quick-actions')"));
+}
+
+#[tokio::test]
+async fn quick_actions_fragment_requires_a_session() {
+    let app = spawn_app_with_auth().await;
+
+    let response = Client::new()
+        .get(app.page_url("/quick-actions"))
+        .send()
+        .await
+        .expect("failed to execute request");
+
+    assert_eq!(response.status(), 401);
+}
+
+#[tokio::test]
+async fn check_in_page_preselects_the_cafe_from_a_quick_action() {
+    let app = spawn_app_with_auth().await;
+    let cafe = create_default_cafe(&app).await;
+    let session = create_session(&app).await;
+
+    let body = Client::new()
+        .get(app.page_url(&format!("/check-in?cafe_id={}", cafe.id)))
+        .header("Cookie", format!("brewlog_session={session}"))
+        .send()
+        .await
+        .expect("failed to fetch page")
+        .text()
+        .await
+        .unwrap();
+
+    assert!(body.contains("data-signals:_step=\"2\""));
+    assert!(body.contains(&format!("data-signals:_cafe-id=\"'{}'\"", cafe.id)));
+}
@@ -0,0 +1,213 @@
+use brewlog::domain::brews::{BrewWithDetails, QuickNote};
+use brewlog::domain::quick_notes::{CustomQuickNote, NewCustomQuickNote};
+use brewlog::infrastructure::backup::BackupData;
+
+use super::helpers::{
+    TestApp, create_default_brew, create_entity, create_session, spawn_app, spawn_app_with_auth,
+};
+
+fn new_note(label: &str, emoji: &str) -> NewCustomQuickNote {
+    NewCustomQuickNote {
+        label: label.to_string(),
+        emoji: emoji.to_string(),
+        created_at: None,
+    }
+}
+
+async fn post_note(app: &TestApp, label: &str, emoji: &str) -> reqwest::Response {
+    reqwest::Client::new()
+        .post(app.api_url("/quick-notes"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&new_note(label, emoji))
+        .send()
+        .await
+        .expect("failed to execute request")
+}
+
+async fn set_brew_notes(app: &TestApp, brew_id: i64, notes: &[&str]) -> reqwest::Response {
+    reqwest::Client::new()
+        .put(app.api_url(&format!("/brews/{brew_id}")))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&serde_json::json!({ "quick_notes": notes }))
+        .send()
+        .await
+        .expect("failed to execute request")
+}
+
+async fn get_brew(app: &TestApp, brew_id: i64) -> BrewWithDetails {
+    reqwest::Client::new()
+        .get(app.api_url(&format!("/brews/{brew_id}")))
+        .send()
+        .await
+        .expect("failed to execute request")
+        .json()
+        .await
+        .expect("failed to parse brew")
+}
+
+#[tokio::test]
+async fn creating_a_quick_note_requires_authentication() {
+    let app = spawn_app().await;
+
+    let response = reqwest::Client::new()
+        .post(app.api_url("/quick-notes"))
+        .json(&new_note("Floral", "🌸"))
+        .send()
+        .await
+        .expect("failed to execute request");
+
+    assert_eq!(response.status(), 401);
+}
+
+#[tokio::test]
+async fn created_quick_notes_are_listed() {
+    let app = spawn_app_with_auth().await;
+
+    let response = post_note(&app, " Floral ", "🌸").await;
+    assert_eq!(response.status(), 201);
+    let created: CustomQuickNote = response.json().await.expect("failed to parse");
+    assert_eq!(created.label, "Floral");
+
+    let notes: Vec<CustomQuickNote> = reqwest::Client::new()
+        .get(app.api_url("/quick-notes"))
+        .send()
+        .await
+        .expect("failed to execute request")
+        .json()
+        .await
+        .expect("failed to parse");
+    assert_eq!(notes.len(), 1);
+    assert_eq!(notes[0].emoji, "🌸");
+}
+
+#[tokio::test]
+async fn quick_note_labels_must_be_unique_and_not_built_in() {
+    let app = spawn_app_with_auth().await;
+    let _: CustomQuickNote = create_entity(&app, "/quick-notes", &new_note("Floral", "🌸")).await;
+
+    assert_eq!(post_note(&app, "floral", "💐").await.status(), 409);
+    assert_eq!(post_note(&app, "Too Fast", "⏩").await.status(), 400);
+    assert_eq!(post_note(&app, "Sweet, juicy", "🍑").await.status(), 400);
+}
+
+#[tokio::test]
+async fn brews_accept_defined_custom_notes() {
+    let app = spawn_app_with_auth().await;
+    let brew = create_default_brew(&app).await;
+    let _: CustomQuickNote = create_entity(&app, "/quick-notes", &new_note("Floral", "🌸")).await;
+
+    let response = set_brew_notes(&app, brew.id.into_inner(), &["good", "floral"]).await;
+    assert!(response.status().is_success());
+
+    let updated = get_brew(&app, brew.id.into_inner()).await;
+    assert_eq!(
+        updated.brew.quick_notes,
+        vec![QuickNote::Good, QuickNote::Custom("Floral".to_string())]
+    );
+}
+
+#[tokio::test]
+async fn brews_reject_undefined_custom_notes() {
+    let app = spawn_app_with_auth().await;
+    let brew = create_default_brew(&app).await;
+
+    let response = set_brew_notes(&app, brew.id.into_inner(), &["Muddy"]).await;
+
+    assert_eq!(response.status(), 400);
+}
+
+#[tokio::test]
+async fn deleting_a_quick_note_keeps_it_on_existing_brews() {
+    let app = spawn_app_with_auth().await;
+    let brew = create_default_brew(&app).await;
+    let note: CustomQuickNote =
+        create_entity(&app, "/quick-notes", &new_note("Floral", "🌸")).await;
+    set_brew_notes(&app, brew.id.into_inner(), &["Floral"]).await;
+
+    let response = reqwest::Client::new()
+        .delete(app.api_url(&format!("/quick-notes/{}", note.id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .send()
+        .await
+        .expect("failed to execute request");
+    assert_eq!(response.status(), 204);
+
+    let updated = get_brew(&app, brew.id.into_inner()).await;
+    assert_eq!(
+        updated.brew.quick_notes,
+        vec![QuickNote::Custom("Floral".to_string())]
+    );
+}
+
+#[tokio::test]
+async fn brew_form_and_admin_page_offer_custom_notes() {
+    let app = spawn_app_with_auth().await;
+    create_default_brew(&app).await;
+    let _: CustomQuickNote = create_entity(&app, "/quick-notes", &new_note("Floral", "🌸")).await;
+    let session_token = create_session(&app).await;
+    let client = reqwest::Client::new();
+
+    for path in ["/add?type=brew", "/admin"] {
+        let body = client
+            .get(app.page_url(path))
+            .header("Cookie", format!("brewlog_session={session_token}"))
+            .send()
+            .await
+            .expect("failed to execute request")
+            .text()
+            .await
+            .expect("failed to read body");
+
+        assert!(body.contains("Floral"), "{path} should list the note");
+        assert!(body.contains("🌸"), "{path} should show the emoji");
+    }
+}
+
+#[tokio::test]
+async fn custom_quick_notes_survive_backup_and_restore() {
+    let source = spawn_app_with_auth().await;
+    let brew = create_default_brew(&source).await;
+    let _: CustomQuickNote =
+        create_entity(&source, "/quick-notes", &new_note("Floral", "🌸")).await;
+    set_brew_notes(&source, brew.id.into_inner(), &["Floral"]).await;
+    let client = reqwest::Client::new();
+
+    let backup: BackupData = client
+        .get(source.api_url("/backup"))
+        .bearer_auth(source.auth_token.as_ref().unwrap())
+        .send()
+        .await
+        .expect("failed to export backup")
+        .json()
+        .await
+        .expect("failed to parse backup");
+    assert_eq!(backup.quick_notes.len(), 1);
+
+    let target = spawn_app_with_auth().await;
+    let response = client
+        .post(target.api_url("/backup/restore"))
+        .bearer_auth(target.auth_token.as_ref().unwrap())
+        .json(&backup)
+        .send()
+        .await
+        .expect("failed to restore backup");
+    assert_eq!(response.status(), 204);
+
+    let notes: Vec<CustomQuickNote> = client
+        .get(target.api_url("/quick-notes"))
+        .send()
+        .await
+        .expect("failed to execute request")
+        .json()
+        .await
+        .expect("failed to parse");
+    assert_eq!(notes.len(), 1);
+    assert_eq!(notes[0].label, "Floral");
+    assert_eq!(notes[0].emoji, "🌸");
+
+    let restored = get_brew(&target, brew.id.into_inner()).await;
+    assert_eq!(
+        restored.brew.quick_notes,
+        vec![QuickNote::Custom("Floral".to_string())]
+    );
+}
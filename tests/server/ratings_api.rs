@@ -0,0 +1,166 @@
+use brewlog::domain::brews::{Brew, NewBrew, QuickNote};
+use brewlog::domain::cups::{Cup, NewCup};
+use brewlog::domain::ids::{CafeId, RoastId};
+use brewlog::domain::roasts::RoastWithRoaster;
+
+use super::helpers::{
+    TestApp, create_default_bag, create_default_cafe, create_default_gear, create_default_roast,
+    create_default_roaster, create_entity, spawn_app_with_auth,
+};
+
+async fn create_rated_cup(
+    app: &TestApp,
+    roast_id: RoastId,
+    cafe_id: CafeId,
+    rating: Option<u8>,
+) -> Cup {
+    create_entity(
+        app,
+        "/cups",
+        &NewCup {
+            roast_id,
+            cafe_id: Some(cafe_id),
+            bag_id: None,
+            rating,
+            created_at: None,
+        },
+    )
+    .await
+}
+
+async fn recompute(app: &TestApp) {
+    let response = reqwest::Client::new()
+        .post(app.api_url("/stats/recompute"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .send()
+        .await
+        .expect("failed to execute request");
+    assert_eq!(response.status(), 200);
+}
+
+async fn get_roast(app: &TestApp, roast_id: RoastId) -> RoastWithRoaster {
+    reqwest::Client::new()
+        .get(app.api_url(&format!("/roasts/{roast_id}")))
+        .send()
+        .await
+        .expect("failed to execute request")
+        .json()
+        .await
+        .expect("failed to parse roast")
+}
+
+#[tokio::test]
+async fn a_cup_keeps_its_rating() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+    let roast = create_default_roast(&app, roaster.id).await;
+    let cafe = create_default_cafe(&app).await;
+
+    let cup = create_rated_cup(&app, roast.id, cafe.id, Some(4)).await;
+
+    assert_eq!(cup.rating, Some(4));
+}
+
+#[tokio::test]
+async fn a_rating_above_five_stars_is_unprocessable() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+    let roast = create_default_roast(&app, roaster.id).await;
+    let cafe = create_default_cafe(&app).await;
+
+    let response = reqwest::Client::new()
+        .post(app.api_url("/cups"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&serde_json::json!({
+            "roast_id": roast.id,
+            "cafe_id": cafe.id,
+            "rating": 6,
+        }))
+        .send()
+        .await
+        .expect("failed to execute request");
+
+    assert_eq!(response.status(), 422);
+}
+
+#[tokio::test]
+async fn a_cup_rating_can_be_cleared() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+    let roast = create_default_roast(&app, roaster.id).await;
+    let cafe = create_default_cafe(&app).await;
+    let cup = create_rated_cup(&app, roast.id, cafe.id, Some(2)).await;
+
+    let response = reqwest::Client::new()
+        .put(app.api_url(&format!("/cups/{}", cup.id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&serde_json::json!({ "rating": null }))
+        .send()
+        .await
+        .expect("failed to execute request");
+
+    assert_eq!(response.status(), 200);
+    let updated: Cup = response.json().await.expect("failed to parse cup");
+    assert_eq!(updated.rating, None);
+}
+
+#[tokio::test]
+async fn roast_rating_averages_its_brews_and_cups() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+    let roast = create_default_roast(&app, roaster.id).await;
+    let bag = create_default_bag(&app, roast.id).await;
+    let grinder = create_default_gear(&app, "grinder", "Comandante", "C40 MK4").await;
+    let brewer = create_default_gear(&app, "brewer", "Hario", "V60 02").await;
+    let cafe = create_default_cafe(&app).await;
+
+    let _: Brew = create_entity(
+        &app,
+        "/brews",
+        &NewBrew {
+            bag_id: bag.id,
+            coffee_weight: 15.0,
+            grinder_id: grinder.id,
+            grind_setting: 24.0,
+            brewer_id: brewer.id,
+            filter_paper_id: None,
+            water_volume: 250,
+            water_temp: 92.0,
+            bypass_water_ml: None,
+            kettle_id: None,
+            actual_water_temp: None,
+            quick_notes: vec![QuickNote::Good],
+            brew_time: None,
+            created_at: None,
+        },
+    )
+    .await;
+    create_rated_cup(&app, roast.id, cafe.id, Some(3)).await;
+    create_rated_cup(&app, roast.id, cafe.id, None).await;
+
+    assert_eq!(get_roast(&app, roast.id).await.roast.rating, None);
+    recompute(&app).await;
+
+    assert_eq!(get_roast(&app, roast.id).await.roast.rating, Some(4.0));
+}
+
+#[tokio::test]
+async fn roast_list_shows_the_rating_as_stars() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+    let roast = create_default_roast(&app, roaster.id).await;
+    let cafe = create_default_cafe(&app).await;
+    create_rated_cup(&app, roast.id, cafe.id, Some(5)).await;
+    recompute(&app).await;
+
+    let body = reqwest::Client::new()
+        .get(app.page_url("/data?type=roasts"))
+        .send()
+        .await
+        .expect("failed to execute request")
+        .text()
+        .await
+        .expect("failed to read body");
+
+    assert!(body.contains("5.0 out of 5 stars"));
+}
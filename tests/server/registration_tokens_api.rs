@@ -0,0 +1,148 @@
+use brewlog::domain::registration_tokens::RegistrationToken;
+use serde_json::{Value, json};
+
+use super::helpers::{TestApp, create_session, spawn_app_with_auth};
+
+/// Create an invite and return the stored token along with its raw value.
+async fn create_invite(app: &TestApp, body: Value) -> (RegistrationToken, String) {
+    let response = reqwest::Client::new()
+        .post(app.api_url("/registration-tokens"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&body)
+        .send()
+        .await
+        .expect("failed to execute request");
+    assert_eq!(response.status(), 201);
+
+    let body: Value = response.json().await.expect("failed to parse response");
+    let url = body["url"].as_str().expect("missing url");
+    let raw = url.rsplit('/').next().expect("unexpected invite url");
+    let token = serde_json::from_value(body["token"].clone()).expect("failed to parse token");
+    (token, raw.to_string())
+}
+
+async fn register_start(app: &TestApp, token: &str, name: &str) -> reqwest::StatusCode {
+    reqwest::Client::new()
+        .post(app.webauthn_url("/register/start"))
+        .json(&json!({ "token": token, "display_name": name }))
+        .send()
+        .await
+        .expect("failed to execute request")
+        .status()
+}
+
+#[tokio::test]
+async fn creating_an_invite_requires_authentication() {
+    let app = spawn_app_with_auth().await;
+
+    let response = reqwest::Client::new()
+        .post(app.api_url("/registration-tokens"))
+        .json(&json!({}))
+        .send()
+        .await
+        .expect("failed to execute request");
+
+    assert_eq!(response.status(), 401);
+}
+
+#[tokio::test]
+async fn invites_default_to_a_single_use() {
+    let app = spawn_app_with_auth().await;
+
+    let (token, raw) = create_invite(&app, json!({})).await;
+    assert_eq!(token.max_uses, 1);
+    assert_eq!(token.use_count, 0);
+
+    assert_eq!(register_start(&app, &raw, "Alice").await, 200);
+    assert_eq!(register_start(&app, &raw, "Bob").await, 410);
+}
+
+#[tokio::test]
+async fn multi_use_invites_register_several_users() {
+    let app = spawn_app_with_auth().await;
+    let (_, raw) = create_invite(&app, json!({ "max_uses": 2, "expires_in_hours": 72 })).await;
+
+    assert_eq!(register_start(&app, &raw, "Alice").await, 200);
+    assert_eq!(register_start(&app, &raw, "Bob").await, 200);
+    assert_eq!(register_start(&app, &raw, "Carol").await, 410);
+
+    let tokens: Vec<RegistrationToken> = reqwest::Client::new()
+        .get(app.api_url("/registration-tokens"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .send()
+        .await
+        .expect("failed to execute request")
+        .json()
+        .await
+        .expect("failed to parse tokens");
+    let invite = tokens
+        .iter()
+        .find(|t| t.max_uses == 2)
+        .expect("missing invite");
+    assert_eq!(invite.use_count, 2);
+    assert!(invite.used_by_user_id.is_some());
+}
+
+#[tokio::test]
+async fn invites_reject_out_of_range_settings() {
+    let app = spawn_app_with_auth().await;
+    let client = reqwest::Client::new();
+
+    for body in [
+        json!({ "max_uses": 0 }),
+        json!({ "max_uses": 21 }),
+        json!({ "expires_in_hours": 169 }),
+    ] {
+        let response = client
+            .post(app.api_url("/registration-tokens"))
+            .bearer_auth(app.auth_token.as_ref().unwrap())
+            .json(&body)
+            .send()
+            .await
+            .expect("failed to execute request");
+
+        assert_eq!(response.status(), 400, "{body}");
+    }
+}
+
+#[tokio::test]
+async fn revoked_invites_cannot_be_used() {
+    let app = spawn_app_with_auth().await;
+    let (token, raw) = create_invite(&app, json!({ "max_uses": 5 })).await;
+
+    let response = reqwest::Client::new()
+        .post(app.api_url(&format!("/registration-tokens/{}/revoke", token.id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .send()
+        .await
+        .expect("failed to execute request");
+    assert_eq!(response.status(), 200);
+
+    let page = reqwest::Client::new()
+        .get(app.page_url(&format!("/register/{raw}")))
+        .send()
+        .await
+        .expect("failed to execute request");
+    assert_eq!(page.status(), 410);
+    assert_eq!(register_start(&app, &raw, "Alice").await, 410);
+}
+
+#[tokio::test]
+async fn admin_page_lists_invites_with_usage() {
+    let app = spawn_app_with_auth().await;
+    create_invite(&app, json!({ "max_uses": 3 })).await;
+    let session_token = create_session(&app).await;
+
+    let body = reqwest::Client::new()
+        .get(app.page_url("/admin"))
+        .header("Cookie", format!("brewlog_session={session_token}"))
+        .send()
+        .await
+        .expect("failed to execute request")
+        .text()
+        .await
+        .expect("failed to read body");
+
+    assert!(body.contains("Invites"));
+    assert!(body.contains("0 of 3 used"));
+}
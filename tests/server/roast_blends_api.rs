@@ -0,0 +1,179 @@
+use brewlog::domain::blends::BlendComponent;
+use brewlog::domain::ids::RoasterId;
+use brewlog::domain::roasts::{NewRoast, Roast, RoastWithRoaster};
+use reqwest::Client;
+
+use crate::helpers::{TestApp, create_default_roaster, spawn_app_with_auth};
+
+fn blend_payload(roaster_id: RoasterId, components: serde_json::Value) -> serde_json::Value {
+    serde_json::json!({
+        "roaster_id": roaster_id,
+        "name": "Red Brick",
+        "origin": "",
+        "region": "Various",
+        "producer": "Various",
+        "tasting_notes": ["Chocolate"],
+        "process": "Washed",
+        "components": components,
+    })
+}
+
+async fn post_roast(app: &TestApp, payload: &serde_json::Value) -> reqwest::Response {
+    Client::new()
+        .post(app.api_url("/roasts"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(payload)
+        .send()
+        .await
+        .expect("failed to create roast")
+}
+
+async fn put_roast(app: &TestApp, roast: &Roast, payload: serde_json::Value) -> RoastWithRoaster {
+    let response = Client::new()
+        .put(app.api_url(&format!("/roasts/{}", roast.id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&payload)
+        .send()
+        .await
+        .expect("failed to update roast");
+    assert_eq!(response.status(), 200);
+    response.json().await.expect("failed to parse roast")
+}
+
+#[tokio::test]
+async fn a_blend_takes_its_origin_from_its_components() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+
+    let response = post_roast(
+        &app,
+        &blend_payload(
+            roaster.id,
+            serde_json::json!([
+                {"origin": "Brazil", "percentage": 70},
+                {"origin": "Ethiopia", "percentage": 30}
+            ]),
+        ),
+    )
+    .await;
+    assert_eq!(response.status(), 201);
+    let created: RoastWithRoaster = response.json().await.unwrap();
+
+    assert_eq!(created.roast.origin.as_deref(), Some("Brazil, Ethiopia"));
+    assert_eq!(
+        created.roast.components,
+        [
+            BlendComponent::new("Brazil", Some(70.0)),
+            BlendComponent::new("Ethiopia", Some(30.0))
+        ]
+    );
+}
+
+#[tokio::test]
+async fn blends_can_be_written_as_text_and_cleared() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+    let response = post_roast(
+        &app,
+        &blend_payload(roaster.id, serde_json::json!("Brazil 60%, Colombia 40%")),
+    )
+    .await;
+    assert_eq!(response.status(), 201);
+    let created: Roast = response.json().await.unwrap();
+    assert_eq!(created.components.len(), 2);
+
+    let reblended = put_roast(
+        &app,
+        &created,
+        serde_json::json!({ "components": "Brazil 50%, Colombia 30%, Kenya 20%" }),
+    )
+    .await;
+    assert_eq!(
+        reblended.roast.origin.as_deref(),
+        Some("Brazil, Colombia, Kenya")
+    );
+    assert_eq!(reblended.roast.components[2].origin, "Kenya");
+
+    let single = put_roast(
+        &app,
+        &created,
+        serde_json::json!({ "origin": "Brazil", "components": "" }),
+    )
+    .await;
+    assert!(single.roast.components.is_empty());
+    assert_eq!(single.roast.origin.as_deref(), Some("Brazil"));
+}
+
+#[tokio::test]
+async fn blend_shares_must_fit_in_the_whole() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+
+    let response = post_roast(
+        &app,
+        &blend_payload(roaster.id, serde_json::json!("Brazil 70%, Colombia 40%")),
+    )
+    .await;
+    assert_eq!(response.status(), 422);
+
+    let response = post_roast(
+        &app,
+        &blend_payload(roaster.id, serde_json::json!("70%, Colombia")),
+    )
+    .await;
+    assert_eq!(response.status(), 400);
+}
+
+#[tokio::test]
+async fn single_origin_roasts_leave_components_out() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+    let payload = NewRoast {
+        roaster_id: roaster.id,
+        name: "Ruiru".to_string(),
+        origin: "Kenya".to_string(),
+        region: "Kiambu".to_string(),
+        producer: "Ruiru Estate".to_string(),
+        tasting_notes: vec!["Blackcurrant".to_string()],
+        process: "Washed".to_string(),
+        components: Vec::new(),
+        harvest_year: None,
+        created_at: None,
+    };
+
+    let body: serde_json::Value = post_roast(&app, &serde_json::to_value(&payload).unwrap())
+        .await
+        .json()
+        .await
+        .unwrap();
+
+    assert!(body.get("components").is_none());
+}
+
+#[tokio::test]
+async fn roast_pages_show_each_origin_of_a_blend() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+    let roast: Roast = post_roast(
+        &app,
+        &blend_payload(roaster.id, serde_json::json!("Brazil 80%, Ethiopia 20%")),
+    )
+    .await
+    .json()
+    .await
+    .unwrap();
+
+    let body = Client::new()
+        .get(app.page_url(&format!("/roasters/{}/roasts/{}", roaster.slug, roast.slug)))
+        .send()
+        .await
+        .expect("failed to load roast page")
+        .text()
+        .await
+        .unwrap();
+
+    assert!(body.contains("Brazil 80%, Ethiopia 20%"));
+    assert!(body.contains("\u{1f1e7}\u{1f1f7} \u{1f1ea}\u{1f1f9}"));
+    assert!(body.contains("BR:200"));
+    assert!(body.contains("ET:50"));
+}
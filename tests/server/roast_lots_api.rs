@@ -0,0 +1,191 @@
+use brewlog::domain::ids::RoasterId;
+use brewlog::domain::roasts::{NewRoast, Roast, RoastWithRoaster};
+use reqwest::Client;
+
+use crate::helpers::{
+    TestApp, create_default_bag, create_default_roaster, create_roast_with_payload, create_session,
+    spawn_app_with_auth,
+};
+
+async fn create_lot(app: &TestApp, roaster_id: RoasterId, harvest_year: Option<i32>) -> Roast {
+    create_roast_with_payload(
+        app,
+        NewRoast {
+            roaster_id,
+            name: "Halo Beriti".to_string(),
+            origin: "Ethiopia".to_string(),
+            region: "Yirgacheffe".to_string(),
+            producer: "Halo Beriti".to_string(),
+            tasting_notes: vec!["Jasmine".to_string()],
+            process: "Washed".to_string(),
+            components: Vec::new(),
+            harvest_year,
+            created_at: None,
+        },
+    )
+    .await
+}
+
+async fn start_lot(app: &TestApp, roast: &Roast, harvest_year: i32) -> reqwest::Response {
+    Client::new()
+        .post(app.api_url(&format!("/roasts/{}/lots", roast.id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&serde_json::json!({ "harvest_year": harvest_year }))
+        .send()
+        .await
+        .expect("failed to start lot")
+}
+
+#[tokio::test]
+async fn roasts_with_a_harvest_year_get_it_in_their_slug() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+
+    let first = create_lot(&app, roaster.id, Some(2025)).await;
+    let second = create_lot(&app, roaster.id, Some(2026)).await;
+
+    assert_eq!(first.harvest_year, Some(2025));
+    assert_eq!(first.slug, "halo-beriti-2025");
+    assert_eq!(second.slug, "halo-beriti-2026");
+}
+
+#[tokio::test]
+async fn implausible_harvest_years_are_rejected() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+    let roast = create_lot(&app, roaster.id, None).await;
+
+    let response = start_lot(&app, &roast, 26).await;
+
+    assert_eq!(response.status(), 422);
+}
+
+#[tokio::test]
+async fn starting_a_lot_copies_the_roast_for_the_new_harvest() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+    let roast = create_lot(&app, roaster.id, Some(2025)).await;
+
+    let response = start_lot(&app, &roast, 2026).await;
+    assert_eq!(response.status(), 201);
+    let lot: RoastWithRoaster = response.json().await.unwrap();
+
+    assert_ne!(lot.roast.id, roast.id);
+    assert_eq!(lot.roast.name, roast.name);
+    assert_eq!(lot.roast.origin, roast.origin);
+    assert_eq!(lot.roast.tasting_notes, roast.tasting_notes);
+    assert_eq!(lot.roast.harvest_year, Some(2026));
+    assert_eq!(lot.roast.slug, "halo-beriti-2026");
+}
+
+#[tokio::test]
+async fn starting_the_same_lot_twice_conflicts() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+    let roast = create_lot(&app, roaster.id, Some(2025)).await;
+
+    assert_eq!(start_lot(&app, &roast, 2026).await.status(), 201);
+    assert_eq!(start_lot(&app, &roast, 2026).await.status(), 409);
+}
+
+#[tokio::test]
+async fn starting_a_lot_requires_authentication() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+    let roast = create_lot(&app, roaster.id, None).await;
+
+    let response = Client::new()
+        .post(app.api_url(&format!("/roasts/{}/lots", roast.id)))
+        .json(&serde_json::json!({ "harvest_year": 2026 }))
+        .send()
+        .await
+        .expect("failed to start lot");
+
+    assert_eq!(response.status(), 401);
+}
+
+#[tokio::test]
+async fn harvest_year_can_be_set_from_the_edit_form() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+    let roast = create_lot(&app, roaster.id, None).await;
+
+    let response = Client::new()
+        .put(app.api_url(&format!("/roasts/{}", roast.id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .form(&[("name", "Halo Beriti"), ("harvest_year", "2024")])
+        .send()
+        .await
+        .expect("failed to update roast");
+    assert!(response.status().is_success() || response.status().is_redirection());
+
+    let updated: RoastWithRoaster = Client::new()
+        .get(app.api_url(&format!("/roasts/{}", roast.id)))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(updated.roast.harvest_year, Some(2024));
+}
+
+#[tokio::test]
+async fn detail_page_links_the_other_lots_and_keeps_their_brews() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+    let old = create_lot(&app, roaster.id, Some(2025)).await;
+    let bag = create_default_bag(&app, old.id).await;
+    let response = start_lot(&app, &old, 2026).await;
+    let new: RoastWithRoaster = response.json().await.unwrap();
+
+    // The old lot's bag stays with it rather than moving to the new harvest.
+    let bag_roast: serde_json::Value = Client::new()
+        .get(app.api_url(&format!("/bags/{}", bag.id)))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(bag_roast["roast_id"], old.id.into_inner());
+
+    let body = Client::new()
+        .get(app.page_url(&format!(
+            "/roasters/{}/roasts/{}",
+            roaster.slug, new.roast.slug
+        )))
+        .send()
+        .await
+        .unwrap()
+        .text()
+        .await
+        .unwrap();
+
+    assert!(body.contains("2026 harvest"));
+    assert!(body.contains(&format!(
+        "href=\"/roasters/{}/roasts/{}\"",
+        roaster.slug, old.slug
+    )));
+    assert_eq!(body.matches("data-role=\"roast-lot\"").count(), 2);
+}
+
+#[tokio::test]
+async fn detail_page_offers_a_new_lot_when_signed_in() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+    let roast = create_lot(&app, roaster.id, None).await;
+    let session = create_session(&app).await;
+
+    let body = Client::new()
+        .get(app.page_url(&format!("/roasters/{}/roasts/{}", roaster.slug, roast.slug)))
+        .header("Cookie", format!("brewlog_session={session}"))
+        .send()
+        .await
+        .unwrap()
+        .text()
+        .await
+        .unwrap();
+
+    assert!(body.contains(&format!("/api/v1/roasts/{}/lots", roast.id)));
+}
@@ -1,5 +1,9 @@
-use crate::helpers::spawn_app_with_auth;
+use crate::helpers::{
+    create_default_bag, create_default_roast, create_default_roaster, spawn_app_with_auth,
+    spawn_app_with_memory_repositories,
+};
 use crate::test_macros::define_crud_tests;
+use brewlog::domain::delete_preview::DeletePreview;
 use brewlog::domain::roasters::{NewRoaster, Roaster, UpdateRoaster};
 
 define_crud_tests!(
@@ -224,7 +228,7 @@ async fn updating_a_roaster_returns_a_200_for_valid_data() {
 
     let updated_roaster: Roaster = response.json().await.expect("Failed to parse response");
     assert_eq!(updated_roaster.name, "Updated Name");
-    assert_eq!(updated_roaster.country, "UK"); // Should remain unchanged
+    assert_eq!(updated_roaster.country, "United Kingdom"); // Should remain unchanged
     assert_eq!(updated_roaster.city, Some("Liverpool".to_string()));
     assert_eq!(
         updated_roaster.homepage,
@@ -356,12 +360,12 @@ async fn deleting_a_roaster_returns_a_204_for_valid_id() {
 }
 
 #[tokio::test]
-async fn creating_a_roaster_with_empty_name_returns_a_201_after_normalization() {
+async fn creating_a_roaster_with_blank_name_returns_422() {
     // Arrange
     let app = spawn_app_with_auth().await;
     let client = reqwest::Client::new();
 
-    // The normalize function trims whitespace, so empty/whitespace names become empty
+    // The normalize function trims whitespace, leaving an empty name
     let new_roaster = NewRoaster {
         name: "   ".to_string(),
         country: "UK".to_string(),
@@ -379,6 +383,245 @@ async fn creating_a_roaster_with_empty_name_returns_a_201_after_normalization()
         .await
         .expect("Failed to execute request");
 
-    // Assert - The API accepts this but normalizes to empty string
+    // Assert
+    assert_eq!(response.status(), 422);
+}
+
+#[tokio::test]
+async fn delete_preview_counts_dependent_records() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+    let roast = create_default_roast(&app, roaster.id).await;
+    create_default_bag(&app, roast.id).await;
+    create_default_bag(&app, roast.id).await;
+
+    let response = reqwest::Client::new()
+        .get(app.api_url(&format!("/roasters/{}/delete-preview", roaster.id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .send()
+        .await
+        .expect("Failed to execute request");
+
+    assert_eq!(response.status(), 200);
+    let preview: DeletePreview = response.json().await.expect("Failed to parse preview");
+    assert_eq!(
+        preview,
+        DeletePreview {
+            roasts: 1,
+            bags: 2,
+            ..DeletePreview::default()
+        }
+    );
+}
+
+#[tokio::test]
+async fn delete_preview_for_nonexistent_roaster_returns_a_404() {
+    let app = spawn_app_with_auth().await;
+
+    let response = reqwest::Client::new()
+        .get(app.api_url("/roasters/999999/delete-preview"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .send()
+        .await
+        .expect("Failed to execute request");
+
+    assert_eq!(response.status(), 404);
+}
+
+#[tokio::test]
+async fn delete_preview_requires_authentication() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+
+    let response = reqwest::Client::new()
+        .get(app.api_url(&format!("/roasters/{}/delete-preview", roaster.id)))
+        .send()
+        .await
+        .expect("Failed to execute request");
+
+    assert_eq!(response.status(), 401);
+}
+
+#[tokio::test]
+async fn creating_a_roaster_stores_the_canonical_country_name() {
+    let app = spawn_app_with_auth().await;
+
+    let response = reqwest::Client::new()
+        .post(app.api_url("/roasters"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&serde_json::json!({ "name": "Square Mile", "country": "uk" }))
+        .send()
+        .await
+        .expect("failed to send request");
+
     assert_eq!(response.status(), 201);
+    let roaster: Roaster = response.json().await.expect("failed to parse roaster");
+    assert_eq!(roaster.country, "United Kingdom");
+}
+
+#[tokio::test]
+async fn creating_a_roaster_accepts_any_iso_country() {
+    let app = spawn_app_with_auth().await;
+
+    let response = reqwest::Client::new()
+        .post(app.api_url("/roasters"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&serde_json::json!({ "name": "Vilnius Roasters", "country": "lithuania" }))
+        .send()
+        .await
+        .expect("failed to send request");
+
+    assert_eq!(response.status(), 201);
+    let roaster: Roaster = response.json().await.expect("failed to parse roaster");
+    assert_eq!(roaster.country, "Lithuania");
+}
+
+#[tokio::test]
+async fn creating_a_roaster_with_an_unknown_country_returns_a_422() {
+    let app = spawn_app_with_auth().await;
+
+    let response = reqwest::Client::new()
+        .post(app.api_url("/roasters"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&serde_json::json!({ "name": "Nowhere Roasters", "country": "Atlantis" }))
+        .send()
+        .await
+        .expect("failed to send request");
+
+    assert_eq!(response.status(), 422);
+    let body = response.text().await.expect("failed to read body");
+    assert!(body.contains("unknown country 'Atlantis'"), "{body}");
+}
+
+#[tokio::test]
+async fn listing_countries_suggests_matches() {
+    let app = spawn_app_with_auth().await;
+
+    let response = reqwest::Client::new()
+        .get(app.api_url("/countries?q=uni"))
+        .send()
+        .await
+        .expect("failed to send request");
+
+    assert_eq!(response.status(), 200);
+    let countries: Vec<serde_json::Value> = response.json().await.expect("failed to parse");
+    let names: Vec<&str> = countries
+        .iter()
+        .filter_map(|c| c["name"].as_str())
+        .collect();
+    assert!(names.contains(&"United Kingdom"));
+    assert!(names.contains(&"United States"));
+}
+
+#[tokio::test]
+async fn listing_roasters_reflects_updates_and_deletes_immediately() {
+    // Arrange
+    let app = spawn_app_with_auth().await;
+    let client = reqwest::Client::new();
+    let roaster = create_default_roaster(&app).await;
+
+    let list = |client: reqwest::Client| {
+        let url = app.api_url("/roasters");
+        async move {
+            client
+                .get(url)
+                .send()
+                .await
+                .expect("Failed to list roasters")
+                .json::<Vec<Roaster>>()
+                .await
+                .expect("Failed to parse roasters")
+        }
+    };
+    assert_eq!(list(client.clone()).await[0].name, "Test Roasters");
+
+    // Act
+    let update = UpdateRoaster {
+        name: Some("Renamed Roasters".to_string()),
+        ..UpdateRoaster::default()
+    };
+    client
+        .put(app.api_url(&format!("/roasters/{}", roaster.id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&update)
+        .send()
+        .await
+        .expect("Failed to update roaster");
+
+    // Assert
+    assert_eq!(list(client.clone()).await[0].name, "Renamed Roasters");
+
+    client
+        .delete(app.api_url(&format!("/roasters/{}", roaster.id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .send()
+        .await
+        .expect("Failed to delete roaster");
+
+    assert!(list(client).await.is_empty());
+}
+
+#[tokio::test]
+async fn roasters_can_be_served_from_the_in_memory_backend() {
+    // Arrange
+    let app = spawn_app_with_memory_repositories().await;
+    let roaster = create_default_roaster(&app).await;
+    create_default_roast(&app, roaster.id).await;
+
+    // Act
+    let fetched = app
+        .roaster_repo
+        .get(roaster.id)
+        .await
+        .expect("Failed to fetch roaster");
+    let page = reqwest::Client::new()
+        .get(app.page_url(&format!("/roasters/{}", roaster.slug)))
+        .send()
+        .await
+        .expect("Failed to execute request");
+
+    // Assert
+    assert_eq!(fetched.name, "Test Roasters");
+    assert_eq!(page.status(), 200);
+    let body = page.text().await.expect("Failed to read body");
+    assert!(body.contains("Test Roasters"));
+    assert!(body.contains("Test Roast"));
+}
+
+#[tokio::test]
+async fn creating_a_roaster_lists_every_invalid_field() {
+    let app = spawn_app_with_auth().await;
+
+    let response = reqwest::Client::new()
+        .post(app.api_url("/roasters"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&serde_json::json!({ "name": "  ", "country": "Atlantis" }))
+        .send()
+        .await
+        .expect("failed to send request");
+
+    assert_eq!(response.status(), 422);
+    let body: brewlog::application::errors::ErrorResponse =
+        response.json().await.expect("failed to parse error");
+    let fields: Vec<&str> = body.fields.iter().map(|f| f.field.as_str()).collect();
+    assert_eq!(fields, ["name", "country"]);
+}
+
+#[tokio::test]
+async fn creating_a_duplicate_roaster_returns_the_duplicate_slug_code() {
+    let app = spawn_app_with_auth().await;
+    create_default_roaster(&app).await;
+
+    let response = reqwest::Client::new()
+        .post(app.api_url("/roasters"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&serde_json::json!({ "name": "Test Roasters", "country": "UK" }))
+        .send()
+        .await
+        .expect("failed to send request");
+
+    assert_eq!(response.status(), 409);
+    let body: brewlog::application::errors::ErrorResponse =
+        response.json().await.expect("failed to parse error");
+    assert_eq!(body.code, brewlog::domain::ErrorCode::DuplicateSlug);
 }
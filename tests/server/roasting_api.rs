@@ -0,0 +1,303 @@
+use brewlog::application::errors::ErrorResponse;
+use brewlog::domain::ErrorCode;
+use brewlog::domain::green_coffees::{GreenCoffee, NewGreenCoffee};
+use brewlog::domain::ids::GreenCoffeeId;
+use brewlog::domain::roast_batches::{NewRoastBatch, RoastBatch};
+use brewlog::infrastructure::backup::BackupData;
+use futures_util::future::join_all;
+
+use super::helpers::{
+    create_default_bag, create_default_roast, create_default_roaster, create_entity,
+    create_session, spawn_app, spawn_app_with_auth,
+};
+
+fn green(name: &str, amount: f64) -> NewGreenCoffee {
+    NewGreenCoffee {
+        name: name.to_string(),
+        origin: Some("Ethiopia".to_string()),
+        process: Some("Washed".to_string()),
+        supplier: None,
+        amount,
+        price: Some(18.5),
+        purchased_on: None,
+        notes: None,
+        created_at: None,
+    }
+}
+
+fn batch(green_coffee_id: GreenCoffeeId, weight_in: f64, weight_out: f64) -> NewRoastBatch {
+    NewRoastBatch {
+        green_coffee_id,
+        bag_id: None,
+        roasted_at: None,
+        weight_in,
+        weight_out,
+        total_time: Some(600),
+        development_time: Some(120),
+        curve_notes: None,
+        created_at: None,
+    }
+}
+
+async fn get_green(app: &crate::helpers::TestApp, id: GreenCoffeeId) -> GreenCoffee {
+    reqwest::Client::new()
+        .get(app.api_url(&format!("/green-coffees/{id}")))
+        .send()
+        .await
+        .expect("failed to execute request")
+        .json()
+        .await
+        .expect("failed to parse")
+}
+
+async fn post_batch(app: &crate::helpers::TestApp, payload: &NewRoastBatch) -> reqwest::Response {
+    reqwest::Client::new()
+        .post(app.api_url("/roast-batches"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(payload)
+        .send()
+        .await
+        .expect("failed to execute request")
+}
+
+#[tokio::test]
+async fn adding_green_coffee_requires_authentication() {
+    let app = spawn_app().await;
+
+    let response = reqwest::Client::new()
+        .post(app.api_url("/green-coffees"))
+        .json(&green("Guji", 1000.0))
+        .send()
+        .await
+        .expect("failed to execute request");
+
+    assert_eq!(response.status(), 401);
+}
+
+#[tokio::test]
+async fn batches_draw_down_the_green_coffee() {
+    let app = spawn_app_with_auth().await;
+    let created: GreenCoffee = create_entity(&app, "/green-coffees", &green("Guji", 1000.0)).await;
+    assert_eq!(created.remaining, 1000.0);
+
+    let roasted: RoastBatch =
+        create_entity(&app, "/roast-batches", &batch(created.id, 250.0, 212.5)).await;
+    assert_eq!(roasted.weight_loss(), 15.0);
+    assert_eq!(roasted.development_ratio(), Some(20.0));
+
+    assert_eq!(get_green(&app, created.id).await.remaining, 750.0);
+
+    let batches: Vec<RoastBatch> = reqwest::Client::new()
+        .get(app.api_url(&format!("/roast-batches?green_coffee={}", created.id)))
+        .send()
+        .await
+        .expect("failed to execute request")
+        .json()
+        .await
+        .expect("failed to parse");
+    assert_eq!(batches.len(), 1);
+    assert_eq!(batches[0].id, roasted.id);
+}
+
+#[tokio::test]
+async fn a_batch_cannot_use_more_green_coffee_than_is_left() {
+    let app = spawn_app_with_auth().await;
+    let created: GreenCoffee = create_entity(&app, "/green-coffees", &green("Guji", 300.0)).await;
+    let _: RoastBatch =
+        create_entity(&app, "/roast-batches", &batch(created.id, 250.0, 212.0)).await;
+
+    let response = post_batch(&app, &batch(created.id, 100.0, 85.0)).await;
+
+    assert_eq!(response.status(), 400);
+    let body: serde_json::Value = response.json().await.expect("failed to parse");
+    assert!(body["message"].as_str().unwrap().contains("50g"));
+}
+
+#[tokio::test]
+async fn concurrent_batches_cannot_overdraw_the_green_coffee() {
+    let app = spawn_app_with_auth().await;
+    let created: GreenCoffee = create_entity(&app, "/green-coffees", &green("Guji", 500.0)).await;
+
+    let payload = batch(created.id, 200.0, 170.0);
+    let responses = join_all((0..6).map(|_| post_batch(&app, &payload))).await;
+
+    let roasted = responses
+        .iter()
+        .filter(|response| response.status() == 201)
+        .count();
+    assert_eq!(roasted, 2);
+    assert_eq!(get_green(&app, created.id).await.remaining, 100.0);
+}
+
+#[tokio::test]
+async fn a_batch_cannot_grow_past_the_green_coffee_left() {
+    let app = spawn_app_with_auth().await;
+    let created: GreenCoffee = create_entity(&app, "/green-coffees", &green("Guji", 300.0)).await;
+    let first: RoastBatch =
+        create_entity(&app, "/roast-batches", &batch(created.id, 200.0, 170.0)).await;
+    let _: RoastBatch = create_entity(&app, "/roast-batches", &batch(created.id, 50.0, 42.0)).await;
+
+    let put = |weight_in: f64| {
+        reqwest::Client::new()
+            .put(app.api_url(&format!("/roast-batches/{}", first.id)))
+            .bearer_auth(app.auth_token.as_ref().unwrap())
+            .json(&serde_json::json!({ "weight_in": weight_in }))
+            .send()
+    };
+
+    let response = put(260.0).await.expect("failed to execute request");
+    assert_eq!(response.status(), 400);
+    let body: serde_json::Value = response.json().await.expect("failed to parse");
+    assert!(body["message"].as_str().unwrap().contains("250g"));
+
+    let response = put(250.0).await.expect("failed to execute request");
+    assert_eq!(response.status(), 200);
+    assert_eq!(get_green(&app, created.id).await.remaining, 0.0);
+}
+
+#[tokio::test]
+async fn green_coffee_cannot_shrink_below_what_was_roasted() {
+    let app = spawn_app_with_auth().await;
+    let created: GreenCoffee = create_entity(&app, "/green-coffees", &green("Guji", 1000.0)).await;
+    let _: RoastBatch =
+        create_entity(&app, "/roast-batches", &batch(created.id, 400.0, 340.0)).await;
+
+    let put = |amount: f64| {
+        reqwest::Client::new()
+            .put(app.api_url(&format!("/green-coffees/{}", created.id)))
+            .bearer_auth(app.auth_token.as_ref().unwrap())
+            .json(&serde_json::json!({ "amount": amount }))
+            .send()
+    };
+
+    let response = put(300.0).await.expect("failed to execute request");
+    assert_eq!(response.status(), 409);
+    let body: ErrorResponse = response.json().await.expect("failed to parse");
+    assert_eq!(body.code, ErrorCode::GreenCoffeeInsufficient);
+    assert_eq!(get_green(&app, created.id).await.remaining, 600.0);
+
+    let response = put(400.0).await.expect("failed to execute request");
+    assert_eq!(response.status(), 200);
+    assert_eq!(get_green(&app, created.id).await.remaining, 0.0);
+}
+
+#[tokio::test]
+async fn a_batch_cannot_gain_weight_in_the_roaster() {
+    let app = spawn_app_with_auth().await;
+    let created: GreenCoffee = create_entity(&app, "/green-coffees", &green("Guji", 1000.0)).await;
+
+    let response = post_batch(&app, &batch(created.id, 200.0, 210.0)).await;
+
+    assert_eq!(response.status(), 422);
+}
+
+#[tokio::test]
+async fn a_batch_links_to_the_bag_it_filled() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+    let roast = create_default_roast(&app, roaster.id).await;
+    let bag = create_default_bag(&app, roast.id).await;
+    let created: GreenCoffee = create_entity(&app, "/green-coffees", &green("Guji", 1000.0)).await;
+
+    let mut payload = batch(created.id, 250.0, 212.0);
+    payload.bag_id = Some(brewlog::domain::ids::BagId::new(9999));
+    assert_eq!(post_batch(&app, &payload).await.status(), 400);
+
+    payload.bag_id = Some(bag.id);
+    let roasted: RoastBatch = create_entity(&app, "/roast-batches", &payload).await;
+    assert_eq!(roasted.bag_id, Some(bag.id));
+
+    let response = reqwest::Client::new()
+        .put(app.api_url(&format!("/roast-batches/{}", roasted.id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&serde_json::json!({ "bag_id": null }))
+        .send()
+        .await
+        .expect("failed to execute request");
+    assert_eq!(response.status(), 200);
+    let updated: RoastBatch = response.json().await.expect("failed to parse");
+    assert_eq!(updated.bag_id, None);
+}
+
+#[tokio::test]
+async fn deleting_green_coffee_removes_its_batches() {
+    let app = spawn_app_with_auth().await;
+    let created: GreenCoffee = create_entity(&app, "/green-coffees", &green("Guji", 1000.0)).await;
+    let roasted: RoastBatch =
+        create_entity(&app, "/roast-batches", &batch(created.id, 250.0, 212.0)).await;
+    let client = reqwest::Client::new();
+
+    let response = client
+        .delete(app.api_url(&format!("/green-coffees/{}", created.id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .send()
+        .await
+        .expect("failed to execute request");
+    assert_eq!(response.status(), 204);
+
+    let response = client
+        .get(app.api_url(&format!("/roast-batches/{}", roasted.id)))
+        .send()
+        .await
+        .expect("failed to execute request");
+    assert_eq!(response.status(), 404);
+}
+
+#[tokio::test]
+async fn roasting_page_lists_green_coffee_and_batches() {
+    let app = spawn_app_with_auth().await;
+    let created: GreenCoffee = create_entity(&app, "/green-coffees", &green("Guji", 1000.0)).await;
+    let _: RoastBatch =
+        create_entity(&app, "/roast-batches", &batch(created.id, 250.0, 212.5)).await;
+    let session_token = create_session(&app).await;
+
+    let body = reqwest::Client::new()
+        .get(app.page_url("/roasting"))
+        .header("Cookie", format!("brewlog_session={session_token}"))
+        .send()
+        .await
+        .expect("failed to execute request")
+        .text()
+        .await
+        .expect("failed to read body");
+
+    assert!(body.contains("Guji"));
+    assert!(body.contains("750g left"));
+    assert!(body.contains("15.0% loss"));
+    assert!(body.contains("20.0% DTR"));
+    assert!(body.contains("roast-batch-form"));
+}
+
+#[tokio::test]
+async fn backups_carry_green_coffee_and_batches() {
+    let source = spawn_app_with_auth().await;
+    let created: GreenCoffee =
+        create_entity(&source, "/green-coffees", &green("Guji", 1000.0)).await;
+    let _: RoastBatch =
+        create_entity(&source, "/roast-batches", &batch(created.id, 250.0, 212.0)).await;
+    let client = reqwest::Client::new();
+
+    let backup: BackupData = client
+        .get(source.api_url("/backup"))
+        .bearer_auth(source.auth_token.as_ref().unwrap())
+        .send()
+        .await
+        .expect("failed to export backup")
+        .json()
+        .await
+        .expect("failed to parse backup");
+    assert_eq!(backup.green_coffees.len(), 1);
+    assert_eq!(backup.roast_batches.len(), 1);
+
+    let target = spawn_app_with_auth().await;
+    let response = client
+        .post(target.api_url("/backup/restore"))
+        .bearer_auth(target.auth_token.as_ref().unwrap())
+        .json(&backup)
+        .send()
+        .await
+        .expect("failed to restore backup");
+    assert_eq!(response.status(), 204);
+
+    assert_eq!(get_green(&target, created.id).await.remaining, 750.0);
+}
@@ -1,5 +1,12 @@
-use crate::helpers::{create_default_roaster, create_roaster_with_name, spawn_app_with_auth};
+use crate::helpers::{
+    create_default_bag, create_default_brew, create_default_cafe, create_default_gear,
+    create_default_roast, create_default_roaster, create_entity, create_roaster_with_name,
+    spawn_app_with_auth,
+};
 use crate::test_macros::define_crud_tests;
+use brewlog::domain::bags::{Bag, NewBag};
+use brewlog::domain::brews::{Brew, NewBrew, QuickNote, RoastBrewStats};
+use brewlog::domain::delete_preview::DeletePreview;
 use brewlog::domain::ids::RoasterId;
 use brewlog::domain::roasts::{NewRoast, Roast, RoastWithRoaster};
 
@@ -29,6 +36,8 @@ async fn creating_a_roast_returns_a_201_for_valid_data() {
             "Citrus".to_string(),
         ],
         process: "Washed".to_string(),
+        components: Vec::new(),
+        harvest_year: None,
         created_at: None,
     };
 
@@ -69,6 +78,8 @@ async fn creating_a_roast_persists_the_data() {
         producer: "Farm Co-op".to_string(),
         tasting_notes: vec!["Caramel".to_string(), "Nuts".to_string()],
         process: "Natural".to_string(),
+        components: Vec::new(),
+        harvest_year: None,
         created_at: None,
     };
 
@@ -109,6 +120,8 @@ async fn creating_a_roast_with_nonexistent_roaster_returns_a_404() {
         producer: "Unknown Producer".to_string(),
         tasting_notes: vec!["Bitter".to_string()],
         process: "Unknown".to_string(),
+        components: Vec::new(),
+        harvest_year: None,
         created_at: None,
     };
 
@@ -140,6 +153,8 @@ async fn getting_a_roast_returns_a_200_for_valid_id() {
         producer: "Estate".to_string(),
         tasting_notes: vec!["Blackcurrant".to_string()],
         process: "Washed".to_string(),
+        components: Vec::new(),
+        harvest_year: None,
         created_at: None,
     };
 
@@ -187,6 +202,8 @@ async fn listing_roasts_returns_a_200_with_multiple_roasts() {
         producer: "Farm A".to_string(),
         tasting_notes: vec!["Chocolate".to_string()],
         process: "Natural".to_string(),
+        components: Vec::new(),
+        harvest_year: None,
         created_at: None,
     };
 
@@ -198,6 +215,8 @@ async fn listing_roasts_returns_a_200_with_multiple_roasts() {
         producer: "Farm B".to_string(),
         tasting_notes: vec!["Caramel".to_string()],
         process: "Washed".to_string(),
+        components: Vec::new(),
+        harvest_year: None,
         created_at: None,
     };
 
@@ -249,6 +268,8 @@ async fn listing_roasts_by_roaster_returns_a_200_with_filtered_list() {
         producer: "Farm A".to_string(),
         tasting_notes: vec!["Chocolate".to_string()],
         process: "Natural".to_string(),
+        components: Vec::new(),
+        harvest_year: None,
         created_at: None,
     };
 
@@ -260,6 +281,8 @@ async fn listing_roasts_by_roaster_returns_a_200_with_filtered_list() {
         producer: "Farm B".to_string(),
         tasting_notes: vec!["Caramel".to_string()],
         process: "Washed".to_string(),
+        components: Vec::new(),
+        harvest_year: None,
         created_at: None,
     };
 
@@ -309,6 +332,8 @@ async fn deleting_a_roast_returns_a_204_for_valid_id() {
         producer: "Temporary Co-op".to_string(),
         tasting_notes: vec!["Fleeting".to_string()],
         process: "Washed".to_string(),
+        components: Vec::new(),
+        harvest_year: None,
         created_at: None,
     };
 
@@ -347,7 +372,7 @@ async fn deleting_a_roast_returns_a_204_for_valid_id() {
 }
 
 #[tokio::test]
-async fn creating_a_roast_with_empty_name_returns_a_400() {
+async fn creating_a_roast_with_empty_name_returns_a_422() {
     // Arrange
     let app = spawn_app_with_auth().await;
     let roaster_id = create_default_roaster(&app).await.id;
@@ -375,7 +400,7 @@ async fn creating_a_roast_with_empty_name_returns_a_400() {
         .expect("Failed to execute request");
 
     // Assert
-    assert_eq!(response.status(), 400);
+    assert_eq!(response.status(), 422);
 }
 
 #[tokio::test]
@@ -410,7 +435,7 @@ async fn creating_a_roast_with_missing_required_fields_returns_a_400() {
 }
 
 #[tokio::test]
-async fn creating_a_roast_with_empty_tasting_notes_returns_a_400() {
+async fn creating_a_roast_with_empty_tasting_notes_returns_a_422() {
     // Arrange
     let app = spawn_app_with_auth().await;
     let roaster_id = create_default_roaster(&app).await.id;
@@ -438,5 +463,351 @@ async fn creating_a_roast_with_empty_tasting_notes_returns_a_400() {
         .expect("Failed to execute request");
 
     // Assert
+    assert_eq!(response.status(), 422);
+}
+
+#[tokio::test]
+async fn delete_preview_counts_bags_brews_and_blocking_cups() {
+    let app = spawn_app_with_auth().await;
+    let brew = create_default_brew(&app).await;
+    let bag: brewlog::domain::bags::Bag = reqwest::Client::new()
+        .get(app.api_url(&format!("/bags/{}", brew.bag_id)))
+        .send()
+        .await
+        .expect("Failed to execute request")
+        .json()
+        .await
+        .expect("Failed to parse bag");
+
+    let response = reqwest::Client::new()
+        .get(app.api_url(&format!("/roasts/{}/delete-preview", bag.roast_id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .send()
+        .await
+        .expect("Failed to execute request");
+
+    assert_eq!(response.status(), 200);
+    let preview: DeletePreview = response.json().await.expect("Failed to parse preview");
+    assert_eq!(preview.bags, 1);
+    assert_eq!(preview.brews, 1);
+    assert_eq!(preview.cups, 0);
+
+    let cafe = create_default_cafe(&app).await;
+    let _cup: brewlog::domain::cups::Cup = create_entity(
+        &app,
+        "/cups",
+        &brewlog::domain::cups::NewCup {
+            roast_id: bag.roast_id,
+            cafe_id: Some(cafe.id),
+            bag_id: None,
+            rating: None,
+            created_at: None,
+        },
+    )
+    .await;
+    let preview: DeletePreview = reqwest::Client::new()
+        .get(app.api_url(&format!("/roasts/{}/delete-preview", bag.roast_id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .send()
+        .await
+        .expect("Failed to execute request")
+        .json()
+        .await
+        .expect("Failed to parse preview");
+    assert_eq!(preview.cups, 1);
+    assert!(preview.is_blocked());
+}
+
+#[tokio::test]
+async fn creating_a_roast_normalizes_a_known_region() {
+    let app = spawn_app_with_auth().await;
+    let roaster_id = create_default_roaster(&app).await.id;
+
+    let mut new_roast = default_new_roast(roaster_id);
+    new_roast.region = "yirga cheffe".to_string();
+    let roast: Roast = create_entity(&app, "/roasts", &new_roast).await;
+
+    assert_eq!(roast.region, Some("Yirgacheffe".to_string()));
+}
+
+#[tokio::test]
+async fn creating_a_roast_with_a_region_from_another_country_returns_a_400() {
+    let app = spawn_app_with_auth().await;
+    let roaster_id = create_default_roaster(&app).await.id;
+
+    let mut new_roast = default_new_roast(roaster_id);
+    new_roast.region = "Huila".to_string();
+    let response = reqwest::Client::new()
+        .post(app.api_url("/roasts"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&new_roast)
+        .send()
+        .await
+        .expect("Failed to execute request");
+
     assert_eq!(response.status(), 400);
+    let body = response.text().await.expect("Failed to read body");
+    assert!(body.contains("region 'Huila' is in Colombia, not Ethiopia"));
+}
+
+#[tokio::test]
+async fn updating_a_roast_origin_checks_the_stored_region() {
+    let app = spawn_app_with_auth().await;
+    let roaster_id = create_default_roaster(&app).await.id;
+    let roast: Roast = create_entity(&app, "/roasts", &default_new_roast(roaster_id)).await;
+    let client = reqwest::Client::new();
+
+    let response = client
+        .put(app.api_url(&format!("/roasts/{}", roast.id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&serde_json::json!({ "origin": "Kenya" }))
+        .send()
+        .await
+        .expect("Failed to execute request");
+    assert_eq!(response.status(), 400);
+
+    let response = client
+        .put(app.api_url(&format!("/roasts/{}", roast.id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&serde_json::json!({ "origin": "Kenya", "region": "nyeri" }))
+        .send()
+        .await
+        .expect("Failed to execute request");
+    assert_eq!(response.status(), 200);
+    let updated: RoastWithRoaster = response.json().await.expect("Failed to parse roast");
+    assert_eq!(updated.roast.region, Some("Nyeri".to_string()));
+}
+
+#[tokio::test]
+async fn listing_regions_filters_by_query_and_origin() {
+    let app = spawn_app_with_auth().await;
+
+    let regions: Vec<serde_json::Value> = reqwest::Client::new()
+        .get(app.api_url("/regions?q=nye&origin=Kenya"))
+        .send()
+        .await
+        .expect("Failed to execute request")
+        .json()
+        .await
+        .expect("Failed to parse regions");
+
+    assert_eq!(regions.len(), 1);
+    assert_eq!(regions[0]["name"], "Nyeri");
+    assert_eq!(regions[0]["country"], "Kenya");
+    assert_eq!(regions[0]["elevation_min_m"], 1500);
+}
+
+#[tokio::test]
+async fn roast_detail_page_shows_region_info() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+    let roast: Roast = create_entity(&app, "/roasts", &default_new_roast(roaster.id)).await;
+
+    let body = reqwest::Client::new()
+        .get(app.page_url(&format!("/roasters/{}/roasts/{}", roaster.slug, roast.slug)))
+        .send()
+        .await
+        .expect("Failed to execute request")
+        .text()
+        .await
+        .expect("Failed to read body");
+
+    assert!(body.contains("Typical Elevation"));
+    assert!(body.contains("1,750\u{2013}2,200 m"));
+    assert!(body.contains("Heirloom, 74110, 74112"));
+}
+
+fn default_new_roast(roaster_id: RoasterId) -> NewRoast {
+    NewRoast {
+        roaster_id,
+        name: "Ethiopian Yirgacheffe".to_string(),
+        origin: "Ethiopia".to_string(),
+        region: "Yirgacheffe".to_string(),
+        producer: "Local Cooperative".to_string(),
+        tasting_notes: vec!["Blueberry".to_string()],
+        process: "Washed".to_string(),
+        components: Vec::new(),
+        harvest_year: None,
+        created_at: None,
+    }
+}
+
+#[tokio::test]
+async fn roast_stats_aggregate_brews_across_bags() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+    let roast = create_default_roast(&app, roaster.id).await;
+    let first_bag = create_default_bag(&app, roast.id).await;
+    let second_bag = create_default_bag(&app, roast.id).await;
+    let grinder = create_default_gear(&app, "grinder", "Comandante", "C40 MK4").await;
+    let brewer = create_default_gear(&app, "brewer", "Hario", "V60 02").await;
+
+    let mut brew_ids = Vec::new();
+    for (bag_id, coffee_weight, grind_setting, water_volume, quick_notes) in [
+        (
+            first_bag.id,
+            15.0,
+            22.0,
+            240,
+            vec![QuickNote::Good, QuickNote::TooFast],
+        ),
+        (first_bag.id, 15.0, 24.0, 255, vec![QuickNote::Good]),
+        (second_bag.id, 20.0, 26.0, 340, vec![QuickNote::TooSlow]),
+    ] {
+        let brew: Brew = create_entity(
+            &app,
+            "/brews",
+            &NewBrew {
+                bag_id,
+                coffee_weight,
+                grinder_id: grinder.id,
+                grind_setting,
+                brewer_id: brewer.id,
+                filter_paper_id: None,
+                water_volume,
+                water_temp: 94.0,
+                bypass_water_ml: None,
+                kettle_id: None,
+                actual_water_temp: None,
+                quick_notes,
+                brew_time: None,
+                created_at: None,
+            },
+        )
+        .await;
+        brew_ids.push(brew.id);
+    }
+
+    let response = reqwest::Client::new()
+        .get(app.api_url(&format!("/roasts/{}/stats", roast.id)))
+        .send()
+        .await
+        .expect("failed to send request");
+
+    assert_eq!(response.status(), 200);
+    let stats: RoastBrewStats = response.json().await.expect("failed to parse stats");
+    assert_eq!(stats.brew_count, 3);
+    assert_eq!(stats.good_brew_count, 2);
+    assert!((stats.total_coffee_grams - 50.0).abs() < f64::EPSILON);
+    let average_ratio = stats.average_ratio.expect("average ratio");
+    assert!((average_ratio - 16.67).abs() < 0.01, "{average_ratio}");
+    assert_eq!(stats.grind_min, Some(22.0));
+    assert_eq!(stats.grind_max, Some(26.0));
+    // The brew marked only "Good" beats the one that was also "Too Fast"
+    let best = stats.best_brew.expect("best brew");
+    assert_eq!(best.brew.id, brew_ids[1]);
+}
+
+#[tokio::test]
+async fn roast_stats_are_empty_for_an_unbrewed_roast() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+    let roast = create_default_roast(&app, roaster.id).await;
+
+    let stats: RoastBrewStats = reqwest::Client::new()
+        .get(app.api_url(&format!("/roasts/{}/stats", roast.id)))
+        .send()
+        .await
+        .expect("failed to send request")
+        .json()
+        .await
+        .expect("failed to parse stats");
+
+    assert_eq!(stats.brew_count, 0);
+    assert_eq!(stats.average_ratio, None);
+    assert!(stats.best_brew.is_none());
+}
+
+#[tokio::test]
+async fn roast_stats_for_a_missing_roast_returns_a_404() {
+    let app = spawn_app_with_auth().await;
+
+    let response = reqwest::Client::new()
+        .get(app.api_url("/roasts/999999/stats"))
+        .send()
+        .await
+        .expect("failed to send request");
+
+    assert_eq!(response.status(), 404);
+}
+
+#[tokio::test]
+async fn roast_detail_page_shows_brewing_summary() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+    let roast = create_default_roast(&app, roaster.id).await;
+    let bag = create_default_bag(&app, roast.id).await;
+    let grinder = create_default_gear(&app, "grinder", "Comandante", "C40 MK4").await;
+    let brewer = create_default_gear(&app, "brewer", "Hario", "V60 02").await;
+    let brew: Brew = create_entity(
+        &app,
+        "/brews",
+        &NewBrew {
+            bag_id: bag.id,
+            coffee_weight: 15.0,
+            grinder_id: grinder.id,
+            grind_setting: 24.0,
+            brewer_id: brewer.id,
+            filter_paper_id: None,
+            water_volume: 250,
+            water_temp: 92.0,
+            bypass_water_ml: None,
+            kettle_id: None,
+            actual_water_temp: None,
+            quick_notes: vec![QuickNote::Good],
+            brew_time: None,
+            created_at: None,
+        },
+    )
+    .await;
+
+    let body = reqwest::Client::new()
+        .get(app.page_url(&format!("/roasters/{}/roasts/{}", roaster.slug, roast.slug)))
+        .send()
+        .await
+        .expect("failed to send request")
+        .text()
+        .await
+        .expect("failed to read body");
+
+    assert!(body.contains("Brewing"));
+    assert!(body.contains("1:16.7"));
+    assert!(body.contains(&format!("/brews/{}", brew.id)));
+}
+
+#[tokio::test]
+async fn roast_detail_page_lists_purchases_with_the_total_bought() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+    let roast = create_default_roast(&app, roaster.id).await;
+    for (amount, price) in [(250.0, Some(14.5)), (1000.0, None)] {
+        let _: Bag = create_entity(
+            &app,
+            "/bags",
+            &NewBag {
+                roast_id: roast.id,
+                roast_date: None,
+                amount,
+                price,
+                currency: None,
+                best_before: None,
+                batch_code: None,
+                created_at: None,
+            },
+        )
+        .await;
+    }
+
+    let body = reqwest::Client::new()
+        .get(app.page_url(&format!("/roasters/{}/roasts/{}", roaster.slug, roast.slug)))
+        .send()
+        .await
+        .expect("failed to send request")
+        .text()
+        .await
+        .expect("failed to read body");
+
+    assert_eq!(body.matches("data-role=\"roast-purchase\"").count(), 2);
+    assert!(body.contains("1.25kg bought"));
+    assert!(body.contains("14.50"));
 }
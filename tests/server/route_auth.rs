@@ -0,0 +1,100 @@
+use brewlog::application::routes::route_table::{API_ROUTES, AuthLevel, RouteInfo};
+use reqwest::{Client, Method, StatusCode};
+use serde_json::{Value, json};
+
+use super::helpers::{TestApp, spawn_app_with_auth};
+
+/// Fill in a route's path parameters with values its extractors accept.
+fn concrete_path(route: &RouteInfo) -> String {
+    route
+        .path
+        .replace("{entity_type}", "roasters")
+        .replace("{list}", "brews")
+        .replace("{id}", "1")
+}
+
+async fn call_anonymously(app: &TestApp, route: &RouteInfo, prefix: &str) -> StatusCode {
+    let method = Method::from_bytes(route.method.as_bytes()).unwrap();
+    let path = concrete_path(route).replacen("/api/v1", prefix, 1);
+    let url = format!("{}{path}", app.address);
+    let mut request = Client::new().request(method, url);
+    if route.is_mutating() {
+        request = request.json(&json!({}));
+    }
+    request
+        .send()
+        .await
+        .expect("failed to execute request")
+        .status()
+}
+
+/// Calls every route anonymously under `prefix` and lists those whose status
+/// doesn't match the access the route table claims.
+async fn access_failures(prefix: &str) -> Vec<String> {
+    let app = spawn_app_with_auth().await;
+
+    let mut failures = Vec::new();
+    for route in API_ROUTES {
+        let status = call_anonymously(&app, route, prefix).await;
+        let expected = match route.auth {
+            AuthLevel::Authenticated => status == StatusCode::UNAUTHORIZED,
+            // Anything but a missing route or a login prompt.
+            AuthLevel::Public => {
+                status != StatusCode::UNAUTHORIZED && status != StatusCode::METHOD_NOT_ALLOWED
+            }
+        };
+        if !expected {
+            failures.push(format!(
+                "{} {prefix}{} ({:?}) returned {status}",
+                route.method,
+                route.path.trim_start_matches("/api/v1"),
+                route.auth
+            ));
+        }
+    }
+    failures
+}
+
+#[tokio::test]
+async fn every_protected_route_rejects_anonymous_requests() {
+    let failures = access_failures("/api/v1").await;
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+#[tokio::test]
+async fn v2_routes_require_the_same_access_as_v1() {
+    let failures = access_failures("/api/v2").await;
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+#[tokio::test]
+async fn route_table_is_served_to_signed_in_users() {
+    let app = spawn_app_with_auth().await;
+
+    let response = Client::new()
+        .get(app.api_url("/admin/routes"))
+        .send()
+        .await
+        .expect("failed to execute request");
+    assert_eq!(response.status(), 401);
+
+    let response = Client::new()
+        .get(app.api_url("/admin/routes"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .send()
+        .await
+        .expect("failed to execute request");
+    assert_eq!(response.status(), 200);
+    let routes: Vec<Value> = response.json().await.unwrap();
+    assert_eq!(routes.len(), API_ROUTES.len());
+    assert!(routes.contains(&json!({
+        "method": "POST",
+        "path": "/api/v1/bags/{id}/transfers",
+        "auth": "authenticated",
+    })));
+    assert!(routes.contains(&json!({
+        "method": "GET",
+        "path": "/api/v1/bags",
+        "auth": "public",
+    })));
+}
@@ -0,0 +1,214 @@
+use brewlog::domain::gear::Gear;
+use brewlog::domain::list_preferences::ListName;
+use brewlog::domain::saved_searches::SavedSearch;
+use serde_json::json;
+
+use super::helpers::{create_entity, create_session, spawn_app, spawn_app_with_auth};
+
+fn open_bags_search() -> serde_json::Value {
+    json!({
+        "name": "Light Ethiopian, unopened bags",
+        "list": "bags",
+        "search": "Ethiopia",
+        "filters": { "status": "open" },
+        "sort_key": "roaster",
+        "sort_direction": "asc"
+    })
+}
+
+#[tokio::test]
+async fn saved_searches_require_auth() {
+    let app = spawn_app().await;
+
+    let response = reqwest::Client::new()
+        .get(app.api_url("/saved-searches"))
+        .send()
+        .await
+        .expect("failed to send request");
+
+    assert_eq!(response.status(), reqwest::StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn creating_a_saved_search_returns_it() {
+    let app = spawn_app_with_auth().await;
+    let client = reqwest::Client::new();
+
+    let response = client
+        .post(app.api_url("/saved-searches"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&open_bags_search())
+        .send()
+        .await
+        .expect("failed to send request");
+
+    assert_eq!(response.status(), reqwest::StatusCode::CREATED);
+    let created: SavedSearch = response.json().await.expect("failed to parse search");
+    assert_eq!(created.name, "Light Ethiopian, unopened bags");
+    assert_eq!(created.list, ListName::Bags);
+    assert_eq!(created.search.as_deref(), Some("Ethiopia"));
+    assert_eq!(
+        created.filters.get("status").map(String::as_str),
+        Some("open")
+    );
+
+    let fetched: SavedSearch = client
+        .get(app.api_url(&format!("/saved-searches/{}", created.id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .send()
+        .await
+        .expect("failed to send request")
+        .json()
+        .await
+        .expect("failed to parse search");
+    assert_eq!(fetched, created);
+
+    let all: Vec<SavedSearch> = client
+        .get(app.api_url("/saved-searches"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .send()
+        .await
+        .expect("failed to send request")
+        .json()
+        .await
+        .expect("failed to parse searches");
+    assert_eq!(all.len(), 1);
+}
+
+#[tokio::test]
+async fn creating_a_saved_search_with_duplicate_name_conflicts() {
+    let app = spawn_app_with_auth().await;
+    let client = reqwest::Client::new();
+
+    for expected in [reqwest::StatusCode::CREATED, reqwest::StatusCode::CONFLICT] {
+        let response = client
+            .post(app.api_url("/saved-searches"))
+            .bearer_auth(app.auth_token.as_ref().unwrap())
+            .json(&open_bags_search())
+            .send()
+            .await
+            .expect("failed to send request");
+        assert_eq!(response.status(), expected);
+    }
+}
+
+#[tokio::test]
+async fn creating_a_saved_search_validates_input() {
+    let app = spawn_app_with_auth().await;
+    let client = reqwest::Client::new();
+
+    for body in [
+        json!({ "name": "  ", "list": "bags" }),
+        json!({ "name": "Timeline", "list": "timeline" }),
+        json!({ "name": "Open roasts", "list": "roasts", "filters": { "status": "open" } }),
+        json!({ "name": "Scales", "list": "gear", "filters": { "category": "scale" } }),
+        json!({ "name": "By origin", "list": "roasters", "sort_key": "origin" }),
+    ] {
+        let response = client
+            .post(app.api_url("/saved-searches"))
+            .bearer_auth(app.auth_token.as_ref().unwrap())
+            .json(&body)
+            .send()
+            .await
+            .expect("failed to send request");
+
+        assert_eq!(
+            response.status(),
+            reqwest::StatusCode::BAD_REQUEST,
+            "{body}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn updating_and_deleting_a_saved_search() {
+    let app = spawn_app_with_auth().await;
+    let client = reqwest::Client::new();
+
+    let created: SavedSearch = client
+        .post(app.api_url("/saved-searches"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&open_bags_search())
+        .send()
+        .await
+        .expect("failed to send request")
+        .json()
+        .await
+        .expect("failed to parse search");
+
+    let response = client
+        .put(app.api_url(&format!("/saved-searches/{}", created.id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&json!({ "name": "Grinders", "list": "gear", "filters": { "category": "grinder" } }))
+        .send()
+        .await
+        .expect("failed to send request");
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    let updated: SavedSearch = response.json().await.expect("failed to parse search");
+    assert_eq!(updated.id, created.id);
+    assert_eq!(updated.list, ListName::Gear);
+    assert_eq!(updated.search, None);
+    assert_eq!(updated.sort_key, None);
+
+    let response = client
+        .delete(app.api_url(&format!("/saved-searches/{}", created.id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .send()
+        .await
+        .expect("failed to send request");
+    assert_eq!(response.status(), reqwest::StatusCode::NO_CONTENT);
+
+    let response = client
+        .get(app.api_url(&format!("/saved-searches/{}", created.id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .send()
+        .await
+        .expect("failed to send request");
+    assert_eq!(response.status(), reqwest::StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn data_page_applies_filters_and_lists_saved_searches() {
+    let app = spawn_app_with_auth().await;
+    let _: Gear = create_entity(
+        &app,
+        "/gear",
+        &json!({ "category": "grinder", "make": "Comandante", "model": "C40" }),
+    )
+    .await;
+    let _: Gear = create_entity(
+        &app,
+        "/gear",
+        &json!({ "category": "brewer", "make": "Hario", "model": "V60" }),
+    )
+    .await;
+
+    let client = reqwest::Client::new();
+    client
+        .post(app.api_url("/saved-searches"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&json!({ "name": "Grinders", "list": "gear", "filters": { "category": "grinder" } }))
+        .send()
+        .await
+        .expect("failed to send request");
+
+    let session_token = create_session(&app).await;
+    let body = client
+        .get(app.page_url("/data?type=gear&category=grinder"))
+        .header("Cookie", format!("brewlog_session={session_token}"))
+        .send()
+        .await
+        .expect("failed to send request")
+        .text()
+        .await
+        .expect("failed to read body");
+
+    assert!(body.contains("Comandante"));
+    assert!(!body.contains("Hario"));
+    // Pagination links keep the filter
+    assert!(body.contains("category=grinder#gear-list"));
+    // The saved search appears in the picker
+    assert!(body.contains("Gear · Grinders"));
+    let unescaped = body.replace("&#38;", "&").replace("&amp;", "&");
+    assert!(unescaped.contains("/data?type=gear&page=1&category=grinder"));
+}
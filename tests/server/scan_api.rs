@@ -52,6 +52,39 @@ async fn scan_creates_roaster_and_roast() {
     );
 }
 
+#[tokio::test]
+async fn scan_accepts_a_multipart_form() {
+    let app = spawn_app_with_auth().await;
+
+    let form = reqwest::multipart::Form::new()
+        .text("roaster_name", "Multipart Roasters")
+        .text("roaster_country", "UK")
+        .text("roast_name", "Multipart Roast")
+        .text("origin", "Kenya")
+        .text("region", "Nyeri")
+        .text("producer", "Test Farm")
+        .text("process", "Washed")
+        .text("tasting_notes", "Blackcurrant")
+        .text("open_bag", "true")
+        .text("bag_amount", "250");
+
+    let response = reqwest::Client::new()
+        .post(app.api_url("/scan"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .multipart(form)
+        .send()
+        .await
+        .expect("Failed to execute request");
+
+    assert_eq!(response.status(), 201);
+    let result: ScanResult = response.json().await.expect("Failed to parse response");
+    assert!(
+        result.redirect.contains("multipart-roast"),
+        "{}",
+        result.redirect
+    );
+}
+
 #[tokio::test]
 async fn scan_reuses_existing_roaster_by_slug() {
     let app = spawn_app_with_auth().await;
@@ -91,6 +124,35 @@ async fn scan_reuses_existing_roaster_by_slug() {
     );
 }
 
+#[tokio::test]
+async fn scan_uses_the_roaster_picked_in_review() {
+    let app = spawn_app_with_auth().await;
+    let client = reqwest::Client::new();
+    let existing = create_default_roaster(&app).await;
+
+    // The label's spelling is kept, but the review picked the existing roaster
+    let mut payload = scan_payload("Test Roastrs", "Scan Roast", "Caramel, Nutty");
+    payload["matched_roaster_id"] = serde_json::json!(existing.id.to_string());
+
+    let response = client
+        .post(app.api_url("/scan"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&payload)
+        .send()
+        .await
+        .expect("Failed to execute request");
+
+    assert_eq!(response.status(), 201);
+    let result: ScanResult = response.json().await.expect("Failed to parse response");
+    assert!(
+        result
+            .redirect
+            .starts_with(&format!("/roasters/{}/", existing.slug)),
+        "Redirect should use the picked roaster, got: {}",
+        result.redirect
+    );
+}
+
 #[tokio::test]
 async fn scan_creates_bag_when_open_bag_is_true() {
     let app = spawn_app_with_auth().await;
@@ -121,6 +183,37 @@ async fn scan_creates_bag_when_open_bag_is_true() {
     assert_eq!(bags.len(), 1, "A bag should have been created");
 }
 
+#[tokio::test]
+async fn scan_opens_the_bag_with_its_label_details() {
+    let app = spawn_app_with_auth().await;
+    let client = reqwest::Client::new();
+
+    let mut payload = scan_payload("Label Roasters", "Label Roast", "Cherry");
+    payload["open_bag"] = serde_json::Value::String("true".to_string());
+    payload["best_before"] = serde_json::json!("2025-09-30");
+    payload["batch_code"] = serde_json::json!("LR-42");
+
+    let response = client
+        .post(app.api_url("/scan"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&payload)
+        .send()
+        .await
+        .expect("Failed to execute request");
+    assert_eq!(response.status(), 201);
+
+    let bags: Vec<serde_json::Value> = client
+        .get(app.api_url("/bags"))
+        .send()
+        .await
+        .expect("Failed to list bags")
+        .json()
+        .await
+        .expect("Failed to parse bags");
+    assert_eq!(bags[0]["best_before"], "2025-09-30");
+    assert_eq!(bags[0]["batch_code"], "LR-42");
+}
+
 #[tokio::test]
 async fn scan_skips_bag_when_open_bag_absent() {
     let app = spawn_app_with_auth().await;
@@ -0,0 +1,147 @@
+use std::time::Duration;
+
+use reqwest::Client;
+use serde_json::Value;
+
+use super::helpers::{TestApp, create_second_user, create_session, spawn_app_with_auth};
+
+const FIREFOX: &str = "Mozilla/5.0 (X11; Linux x86_64; rv:128.0) Gecko/20100101 Firefox/128.0";
+const FIREFOX_UPDATED: &str =
+    "Mozilla/5.0 (X11; Linux x86_64; rv:129.0) Gecko/20100101 Firefox/129.0";
+const CHROME: &str = "Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 \
+    (KHTML, like Gecko) Chrome/126.0.0.0 Safari/537.36";
+
+async fn list_sessions(app: &TestApp, session: &str, user_agent: &str) -> reqwest::Response {
+    Client::new()
+        .get(app.api_url("/sessions"))
+        .header("Cookie", format!("brewlog_session={session}"))
+        .header("User-Agent", user_agent)
+        .send()
+        .await
+        .expect("failed to execute request")
+}
+
+async fn sessions(app: &TestApp, session: &str, user_agent: &str) -> Vec<Value> {
+    let response = list_sessions(app, session, user_agent).await;
+    assert_eq!(response.status(), 200);
+    response.json().await.expect("failed to parse response")
+}
+
+#[tokio::test]
+async fn sessions_are_bound_to_the_first_device() {
+    let app = spawn_app_with_auth().await;
+    let session = create_session(&app).await;
+
+    let listed = sessions(&app, &session, FIREFOX).await;
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0]["current"], true);
+
+    // Another browser with the same cookie ends the session for both.
+    let response = list_sessions(&app, &session, CHROME).await;
+    assert_eq!(response.status(), 401);
+    let response = list_sessions(&app, &session, FIREFOX).await;
+    assert_eq!(response.status(), 401);
+}
+
+#[tokio::test]
+async fn browser_updates_keep_the_session() {
+    let app = spawn_app_with_auth().await;
+    let session = create_session(&app).await;
+
+    sessions(&app, &session, FIREFOX).await;
+    let listed = sessions(&app, &session, FIREFOX_UPDATED).await;
+    assert_eq!(listed[0]["device"], "Firefox on Linux");
+}
+
+#[tokio::test]
+async fn last_seen_address_comes_from_the_local_proxy() {
+    let app = spawn_app_with_auth().await;
+    let session = create_session(&app).await;
+
+    // Last seen is recorded in the background.
+    let mut listed = Value::Null;
+    for _ in 0..50 {
+        listed = Client::new()
+            .get(app.api_url("/sessions"))
+            .header("Cookie", format!("brewlog_session={session}"))
+            .header("User-Agent", FIREFOX)
+            .header("X-Forwarded-For", "203.0.113.7, 10.0.0.1")
+            .send()
+            .await
+            .expect("failed to execute request")
+            .json()
+            .await
+            .expect("failed to parse response");
+        if listed[0]["last_seen_ip"] == "203.0.113.7" {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    assert_eq!(listed[0]["last_seen_ip"], "203.0.113.7");
+    assert!(listed[0]["last_seen_at"].is_string());
+}
+
+#[tokio::test]
+async fn other_sessions_can_be_signed_out() {
+    let app = spawn_app_with_auth().await;
+    let laptop = create_session(&app).await;
+    let phone = create_session(&app).await;
+
+    let listed = sessions(&app, &laptop, FIREFOX).await;
+    assert_eq!(listed.len(), 2);
+    let other = listed
+        .iter()
+        .find(|session| session["current"] == false)
+        .expect("the other session is listed");
+
+    let response = Client::new()
+        .delete(app.api_url(&format!("/sessions/{}", other["id"])))
+        .header("Cookie", format!("brewlog_session={laptop}"))
+        .header("User-Agent", FIREFOX)
+        .send()
+        .await
+        .expect("failed to execute request");
+    assert_eq!(response.status(), 204);
+
+    assert_eq!(sessions(&app, &laptop, FIREFOX).await.len(), 1);
+    assert_eq!(list_sessions(&app, &phone, CHROME).await.status(), 401);
+}
+
+#[tokio::test]
+async fn sessions_of_other_users_cannot_be_signed_out() {
+    let app = spawn_app_with_auth().await;
+    let session = create_session(&app).await;
+    let listed = sessions(&app, &session, FIREFOX).await;
+
+    let token = create_second_user(&app, "housemate").await.token;
+
+    let response = Client::new()
+        .delete(app.api_url(&format!("/sessions/{}", listed[0]["id"])))
+        .bearer_auth(&token)
+        .send()
+        .await
+        .expect("failed to execute request");
+    assert_eq!(response.status(), 403);
+    assert_eq!(sessions(&app, &session, FIREFOX).await.len(), 1);
+}
+
+#[tokio::test]
+async fn profile_lists_sessions() {
+    let app = spawn_app_with_auth().await;
+    let session = create_session(&app).await;
+
+    let page = Client::new()
+        .get(app.page_url("/profile"))
+        .header("Cookie", format!("brewlog_session={session}"))
+        .header("User-Agent", FIREFOX)
+        .send()
+        .await
+        .expect("failed to fetch profile page")
+        .text()
+        .await
+        .unwrap();
+
+    assert!(page.contains("Sessions"));
+    assert!(page.contains("Firefox on Linux"));
+    assert!(page.contains("This device"));
+}
@@ -0,0 +1,171 @@
+use brewlog::domain::bags::{Bag, BagWithRoast};
+use brewlog::domain::settings::Settings;
+use reqwest::Client;
+use serde_json::json;
+
+use super::helpers::{
+    TestApp, create_default_bag, create_default_roast, create_default_roaster, create_session,
+    spawn_app_with_auth,
+};
+
+async fn put_settings(app: &TestApp, threshold: f64) -> reqwest::Response {
+    Client::new()
+        .put(app.api_url("/settings"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&json!({ "low_stock_threshold": threshold }))
+        .send()
+        .await
+        .expect("failed to execute request")
+}
+
+async fn update_bag(app: &TestApp, bag: &Bag, payload: serde_json::Value) -> reqwest::Response {
+    Client::new()
+        .put(app.api_url(&format!("/bags/{}", bag.id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&payload)
+        .send()
+        .await
+        .expect("failed to execute request")
+}
+
+async fn home_page(app: &TestApp, session: &str) -> String {
+    Client::new()
+        .get(app.page_url("/"))
+        .header("Cookie", format!("brewlog_session={session}"))
+        .send()
+        .await
+        .expect("failed to fetch page")
+        .text()
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn settings_default_until_changed() {
+    let app = spawn_app_with_auth().await;
+
+    let settings: Settings = Client::new()
+        .get(app.api_url("/settings"))
+        .send()
+        .await
+        .expect("failed to execute request")
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(settings, Settings::default());
+
+    let response = put_settings(&app, 60.0).await;
+    assert_eq!(response.status(), 200);
+    let updated: Settings = response.json().await.unwrap();
+    assert!((updated.low_stock_threshold - 60.0).abs() < f64::EPSILON);
+
+    let settings: Settings = Client::new()
+        .get(app.api_url("/settings"))
+        .send()
+        .await
+        .expect("failed to execute request")
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(settings, updated);
+}
+
+#[tokio::test]
+async fn changing_settings_requires_auth_and_a_valid_threshold() {
+    let app = spawn_app_with_auth().await;
+
+    let response = Client::new()
+        .put(app.api_url("/settings"))
+        .json(&json!({ "low_stock_threshold": 60 }))
+        .send()
+        .await
+        .expect("failed to execute request");
+    assert_eq!(response.status(), 401);
+
+    let response = put_settings(&app, -5.0).await;
+    assert_eq!(response.status(), 400);
+
+    let response = Client::new()
+        .put(app.api_url("/settings"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&json!({}))
+        .send()
+        .await
+        .expect("failed to execute request");
+    assert_eq!(response.status(), 400);
+
+    let response = Client::new()
+        .put(app.api_url("/settings"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&json!({ "dust_threshold": 60 }))
+        .send()
+        .await
+        .expect("failed to execute request");
+    assert_eq!(response.status(), 400);
+}
+
+#[tokio::test]
+async fn open_bags_below_the_threshold_are_flagged_for_reorder() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+    let roast = create_default_roast(&app, roaster.id).await;
+    let bag = create_default_bag(&app, roast.id).await;
+    let session = create_session(&app).await;
+
+    let response = update_bag(&app, &bag, json!({ "remaining": 55.0 })).await;
+    assert!(response.status().is_success());
+    assert!(!home_page(&app, &session).await.contains("Reorder soon"));
+
+    put_settings(&app, 60.0).await;
+    assert!(home_page(&app, &session).await.contains("Reorder soon"));
+
+    let list = Client::new()
+        .get(app.page_url("/data?type=bags"))
+        .header("Cookie", format!("brewlog_session={session}"))
+        .send()
+        .await
+        .expect("failed to fetch page")
+        .text()
+        .await
+        .unwrap();
+    assert!(list.contains("data-role=\"reorder-soon\""));
+}
+
+#[tokio::test]
+async fn bags_can_override_the_threshold() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+    let roast = create_default_roast(&app, roaster.id).await;
+    let bag = create_default_bag(&app, roast.id).await;
+    let session = create_session(&app).await;
+    update_bag(&app, &bag, json!({ "remaining": 40.0 })).await;
+    assert!(home_page(&app, &session).await.contains("Reorder soon"));
+
+    let response = update_bag(&app, &bag, json!({ "low_stock_threshold": 0.0 })).await;
+    assert!(response.status().is_success());
+    let updated: BagWithRoast = response.json().await.unwrap();
+    assert_eq!(updated.bag.low_stock_threshold, Some(0.0));
+    assert!(!home_page(&app, &session).await.contains("Reorder soon"));
+
+    let response = update_bag(&app, &bag, json!({ "low_stock_threshold": -1.0 })).await;
+    assert_eq!(response.status(), 422);
+}
+
+#[tokio::test]
+async fn admin_page_shows_the_current_threshold() {
+    let app = spawn_app_with_auth().await;
+    put_settings(&app, 75.0).await;
+    let session = create_session(&app).await;
+
+    let body = Client::new()
+        .get(app.page_url("/admin"))
+        .header("Cookie", format!("brewlog_session={session}"))
+        .send()
+        .await
+        .expect("failed to fetch page")
+        .text()
+        .await
+        .unwrap();
+    assert!(body.contains("id=\"low-stock\""));
+    assert!(body.contains("value=\"75\""));
+}
@@ -0,0 +1,173 @@
+use brewlog::application::errors::ErrorResponse;
+use brewlog::domain::ErrorCode;
+use brewlog::domain::cafes::{Cafe, NewCafe};
+use brewlog::domain::roasters::{NewRoaster, Roaster};
+use brewlog::domain::roasts::{NewRoast, Roast};
+use brewlog::domain::settings::Settings;
+use brewlog::domain::slugs::SlugStrategy;
+use chrono::{Datelike, Utc};
+use reqwest::Client;
+use serde::Serialize;
+use serde_json::json;
+
+use crate::helpers::{TestApp, create_default_roaster, spawn_app_with_auth};
+
+async fn set_strategy(app: &TestApp, strategy: &str) -> reqwest::Response {
+    Client::new()
+        .put(app.api_url("/settings"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&json!({ "slug_strategy": strategy }))
+        .send()
+        .await
+        .expect("failed to execute request")
+}
+
+async fn post<P: Serialize>(app: &TestApp, path: &str, payload: &P) -> reqwest::Response {
+    Client::new()
+        .post(app.api_url(path))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(payload)
+        .send()
+        .await
+        .expect("failed to execute request")
+}
+
+fn roaster(name: &str) -> NewRoaster {
+    NewRoaster {
+        name: name.to_string(),
+        country: "UK".to_string(),
+        city: None,
+        homepage: None,
+        created_at: None,
+    }
+}
+
+fn roast(roaster: &Roaster, harvest_year: Option<i32>) -> NewRoast {
+    NewRoast {
+        roaster_id: roaster.id,
+        name: "Ethiopia Natural".to_string(),
+        origin: "Ethiopia".to_string(),
+        region: "Guji".to_string(),
+        producer: "Coop".to_string(),
+        tasting_notes: vec!["Strawberry".to_string()],
+        process: "Natural".to_string(),
+        components: Vec::new(),
+        harvest_year,
+        created_at: None,
+    }
+}
+
+fn cafe() -> NewCafe {
+    NewCafe {
+        name: "Prufrock".to_string(),
+        city: "London".to_string(),
+        country: "UK".to_string(),
+        latitude: 51.5196,
+        longitude: -0.1096,
+        website: None,
+        created_at: None,
+    }
+}
+
+#[tokio::test]
+async fn duplicates_are_refused_by_default() {
+    let app = spawn_app_with_auth().await;
+
+    assert_eq!(
+        post(&app, "/roasters", &roaster("Square Mile"))
+            .await
+            .status(),
+        201
+    );
+    let response = post(&app, "/roasters", &roaster("Square Mile")).await;
+
+    assert_eq!(response.status(), 409);
+    let body: ErrorResponse = response.json().await.unwrap();
+    assert_eq!(body.code, ErrorCode::DuplicateSlug);
+}
+
+#[tokio::test]
+async fn numeric_suffixes_count_up() {
+    let app = spawn_app_with_auth().await;
+    assert_eq!(set_strategy(&app, "numeric_suffix").await.status(), 200);
+
+    let mut slugs = Vec::new();
+    for _ in 0..3 {
+        let response = post(&app, "/roasters", &roaster("Square Mile")).await;
+        assert_eq!(response.status(), 201);
+        slugs.push(response.json::<Roaster>().await.unwrap().slug);
+    }
+
+    assert_eq!(slugs, ["square-mile", "square-mile-2", "square-mile-3"]);
+}
+
+#[tokio::test]
+async fn roasts_take_the_year_they_were_added() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+    set_strategy(&app, "append_year").await;
+
+    post(&app, "/roasts", &roast(&roaster, None)).await;
+    let response = post(&app, "/roasts", &roast(&roaster, None)).await;
+
+    assert_eq!(response.status(), 201);
+    let second: Roast = response.json().await.unwrap();
+    assert_eq!(
+        second.slug,
+        format!("ethiopia-natural-{}", Utc::now().year())
+    );
+}
+
+#[tokio::test]
+async fn a_harvest_year_is_numbered_rather_than_repeated() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+    set_strategy(&app, "append_year").await;
+
+    post(&app, "/roasts", &roast(&roaster, Some(2025))).await;
+    let response = post(&app, "/roasts", &roast(&roaster, Some(2025))).await;
+
+    let second: Roast = response.json().await.unwrap();
+    assert_eq!(second.slug, "ethiopia-natural-2025-2");
+}
+
+#[tokio::test]
+async fn roasts_can_take_their_roaster() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+    set_strategy(&app, "append_roaster").await;
+
+    post(&app, "/roasts", &roast(&roaster, None)).await;
+    let response = post(&app, "/roasts", &roast(&roaster, None)).await;
+
+    let second: Roast = response.json().await.unwrap();
+    assert_eq!(second.slug, "ethiopia-natural-test-roasters");
+}
+
+#[tokio::test]
+async fn cafes_without_a_roaster_fall_back_to_numbers() {
+    let app = spawn_app_with_auth().await;
+    set_strategy(&app, "append_roaster").await;
+
+    post(&app, "/cafes", &cafe()).await;
+    let response = post(&app, "/cafes", &cafe()).await;
+
+    assert_eq!(response.status(), 201);
+    let second: Cafe = response.json().await.unwrap();
+    assert_eq!(second.slug, "prufrock-london-2");
+}
+
+#[tokio::test]
+async fn the_strategy_is_a_setting() {
+    let app = spawn_app_with_auth().await;
+
+    let response = set_strategy(&app, "append_month").await;
+    assert_eq!(response.status(), 422);
+
+    let settings: Settings = set_strategy(&app, "append_year")
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(settings.slug_strategy, SlugStrategy::AppendYear);
+}
@@ -71,6 +71,16 @@ define_static_asset_test!(
     "/static/js/components/chip-scroll.js",
     "application/javascript; charset=utf-8"
 );
+define_static_asset_test!(
+    autocomplete_input_js,
+    "/static/js/components/autocomplete-input.js",
+    "application/javascript; charset=utf-8"
+);
+define_static_asset_test!(
+    grind_converter_js,
+    "/static/js/components/grind-converter.js",
+    "application/javascript; charset=utf-8"
+);
 define_static_asset_test!(
     world_map_js,
     "/static/js/components/world-map.js",
@@ -81,6 +91,11 @@ define_static_asset_test!(
     "/static/js/components/donut-chart.js",
     "application/javascript; charset=utf-8"
 );
+define_static_asset_test!(
+    line_chart_js,
+    "/static/js/components/line-chart.js",
+    "application/javascript; charset=utf-8"
+);
 define_static_asset_test!(
     image_upload_js,
     "/static/js/components/image-upload.js",
@@ -96,3 +111,68 @@ define_static_asset_test!(
     "/static/site.webmanifest",
     "application/manifest+json; charset=utf-8"
 );
+
+#[tokio::test]
+async fn hashed_asset_urls_are_immutable() {
+    let app = spawn_app().await;
+    let client = reqwest::Client::new();
+
+    let url = brewlog::asset_url("css/styles.css");
+    assert!(url.starts_with("/static/css/styles."));
+    assert_ne!(url, "/static/css/styles.css");
+
+    let response = client
+        .get(app.page_url(&url))
+        .send()
+        .await
+        .expect("Failed to execute request");
+
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response
+            .headers()
+            .get("cache-control")
+            .and_then(|v| v.to_str().ok()),
+        Some("public, max-age=31536000, immutable")
+    );
+}
+
+#[tokio::test]
+async fn pages_link_hashed_assets() {
+    let app = spawn_app().await;
+    let client = reqwest::Client::new();
+
+    let body = client
+        .get(app.page_url("/login"))
+        .send()
+        .await
+        .expect("Failed to execute request")
+        .text()
+        .await
+        .expect("Failed to read body");
+
+    assert!(body.contains(&brewlog::asset_url("css/styles.css")));
+    assert!(body.contains(&brewlog::asset_url("js/webauthn.js")));
+}
+
+#[tokio::test]
+async fn assets_are_brotli_compressed_when_accepted() {
+    let app = spawn_app().await;
+    let client = reqwest::Client::new();
+
+    let response = client
+        .get(app.page_url("/static/js/components/searchable-select.js"))
+        .header("accept-encoding", "br")
+        .send()
+        .await
+        .expect("Failed to execute request");
+
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response
+            .headers()
+            .get("content-encoding")
+            .and_then(|v| v.to_str().ok()),
+        Some("br")
+    );
+}
@@ -1,9 +1,12 @@
+use brewlog::domain::brews::UpdateBrew;
+use brewlog::domain::stats::{BrewingHabits, CachedStats, RoasterLeaderboardEntry};
+use chrono::{Datelike, TimeZone, Utc};
 use reqwest::Client;
 
 use crate::helpers::{
-    assert_datastar_headers_with_mode, assert_full_page, assert_html_fragment, create_default_brew,
-    create_default_cafe, create_default_roast, create_default_roaster, spawn_app,
-    spawn_app_with_auth,
+    assert_datastar_headers_with_mode, assert_full_page, assert_html_fragment, create_default_bag,
+    create_default_brew, create_default_cafe, create_default_roast, create_default_roaster,
+    create_roaster_with_name, spawn_app, spawn_app_with_auth,
 };
 
 #[tokio::test]
@@ -134,15 +137,15 @@ async fn recompute_stats_reflects_created_data() {
 
     let body: serde_json::Value = response.json().await.expect("Failed to parse JSON");
 
-    // Roaster is in UK
+    // Roaster is in the United Kingdom
     let geo_roasters = &body["geo_roasters"]["entries"];
     assert!(
         geo_roasters
             .as_array()
             .unwrap()
             .iter()
-            .any(|e| e["country_name"] == "UK"),
-        "geo_roasters should contain UK: {geo_roasters}"
+            .any(|e| e["country_name"] == "United Kingdom"),
+        "geo_roasters should contain United Kingdom: {geo_roasters}"
     );
 
     // Roast origin is Ethiopia
@@ -257,3 +260,163 @@ async fn stats_page_loads_after_recompute() {
     let body = response.text().await.expect("Failed to read body");
     assert_full_page(&body);
 }
+
+#[tokio::test]
+async fn stats_api_is_public_and_counts_this_years_tasting_notes() {
+    let app = spawn_app_with_auth().await;
+    let _brew = create_default_brew(&app).await;
+
+    let response = Client::new()
+        .get(app.api_url("/stats"))
+        .send()
+        .await
+        .expect("Failed to execute request");
+
+    assert_eq!(response.status(), 200);
+    let stats: CachedStats = response.json().await.expect("Failed to parse stats");
+    assert_eq!(stats.tasting.year, Utc::now().year());
+    assert_eq!(
+        stats.tasting.tasting_note_counts,
+        [("Blueberry".to_string(), 1)]
+    );
+    assert_eq!(stats.tasting.process_counts, [("Washed".to_string(), 1)]);
+}
+
+#[tokio::test]
+async fn stats_page_shows_tasting_note_and_process_charts() {
+    let app = spawn_app_with_auth().await;
+    let _brew = create_default_brew(&app).await;
+
+    let body = Client::new()
+        .get(app.page_url("/stats"))
+        .send()
+        .await
+        .expect("Failed to execute request")
+        .text()
+        .await
+        .expect("Failed to read body");
+
+    assert!(body.contains("Tasting Notes Brewed in"));
+    assert!(body.contains("Washed:1"));
+}
+
+#[tokio::test]
+async fn habits_api_buckets_brews_by_weekday_and_hour() {
+    let app = spawn_app_with_auth().await;
+    let client = Client::new();
+    let brew = create_default_brew(&app).await;
+
+    // 2024-03-09 was a Saturday
+    let update = UpdateBrew {
+        created_at: Some(Utc.with_ymd_and_hms(2024, 3, 9, 7, 30, 0).unwrap()),
+        ..Default::default()
+    };
+    let response = client
+        .put(app.api_url(&format!("/brews/{}", brew.id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&update)
+        .send()
+        .await
+        .expect("Failed to update brew");
+    assert_eq!(response.status(), 200);
+
+    let response = client
+        .get(app.api_url("/stats/habits"))
+        .send()
+        .await
+        .expect("Failed to execute request");
+
+    assert_eq!(response.status(), 200);
+    let habits: BrewingHabits = response.json().await.expect("Failed to parse habits");
+    assert_eq!(habits.weekday_counts.len(), 7);
+    assert_eq!(habits.weekday_counts[5], ("Sat".to_string(), 1));
+    assert_eq!(habits.max_weekday_count, 1);
+    assert_eq!(habits.hour_counts.len(), 24);
+    assert_eq!(habits.hour_counts[7], ("07".to_string(), 1));
+}
+
+#[tokio::test]
+async fn stats_page_shows_brewing_habit_charts() {
+    let app = spawn_app_with_auth().await;
+    let _brew = create_default_brew(&app).await;
+
+    let body = Client::new()
+        .get(app.page_url("/stats"))
+        .send()
+        .await
+        .expect("Failed to execute request")
+        .text()
+        .await
+        .expect("Failed to read body");
+
+    assert!(body.contains("Brews by Day of Week"));
+    assert!(body.contains("Brews by Hour (UTC)"));
+}
+
+#[tokio::test]
+async fn roaster_leaderboard_ranks_by_grams_brewed() {
+    let app = spawn_app_with_auth().await;
+    let client = Client::new();
+
+    // More roasts but nothing brewed yet
+    let unbrewed = create_roaster_with_name(&app, "Shelf Roasters").await;
+    let roast = create_default_roast(&app, unbrewed.id).await;
+    create_default_bag(&app, roast.id).await;
+    create_default_bag(&app, roast.id).await;
+    let _brew = create_default_brew(&app).await;
+    let _no_bags = create_roaster_with_name(&app, "Bagless Roasters").await;
+
+    let response = client
+        .get(app.api_url("/stats/roasters"))
+        .send()
+        .await
+        .expect("Failed to execute request");
+    assert_eq!(response.status(), 200);
+    let entries: Vec<RoasterLeaderboardEntry> =
+        response.json().await.expect("Failed to parse leaderboard");
+
+    let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(names, ["Test Roasters", "Shelf Roasters"]);
+    assert!((entries[0].grams_brewed - 15.0).abs() < f64::EPSILON);
+    assert_eq!(entries[0].brew_count, 1);
+    assert!(entries[1].last_purchased_at.is_some());
+
+    let response = client
+        .get(app.api_url("/stats/roasters?sort=name&dir=desc"))
+        .send()
+        .await
+        .expect("Failed to execute request");
+    let entries: Vec<RoasterLeaderboardEntry> =
+        response.json().await.expect("Failed to parse leaderboard");
+    assert_eq!(entries[0].name, "Test Roasters");
+}
+
+#[tokio::test]
+async fn stats_page_leaderboard_resorts_as_a_fragment() {
+    let app = spawn_app_with_auth().await;
+    let client = Client::new();
+    let _brew = create_default_brew(&app).await;
+
+    let body = client
+        .get(app.page_url("/stats"))
+        .send()
+        .await
+        .expect("Failed to execute request")
+        .text()
+        .await
+        .expect("Failed to read body");
+    assert!(body.contains("Roaster Leaderboard"));
+    assert!(body.contains("id=\"roaster-leaderboard\""));
+
+    let response = client
+        .get(app.page_url("/stats?sort=brews&dir=asc"))
+        .header("datastar-request", "true")
+        .send()
+        .await
+        .expect("Failed to execute request");
+    assert_eq!(response.status(), 200);
+    assert_datastar_headers_with_mode(&response, "#roaster-leaderboard", "replace");
+    let body = response.text().await.expect("Failed to read body");
+    assert_html_fragment(&body);
+    assert!(body.contains("Test Roasters"));
+}
@@ -0,0 +1,227 @@
+use std::sync::Arc;
+
+use brewlog::application::ServerConfig;
+use brewlog::application::tenants::{
+    TenantRegistration, TenantRegistry, TenantRouting, TenantsConfig, tenant_router,
+};
+use brewlog::infrastructure::auth::TokenHasher;
+use brewlog::infrastructure::image_processing::ImageSettings;
+use brewlog::infrastructure::listener::BindAddress;
+use tokio::net::TcpListener;
+
+const ADMIN_TOKEN: &str = "tenant-admin-secret";
+
+struct TenantServer {
+    address: String,
+    port: u16,
+    dir: tempfile::TempDir,
+    client: reqwest::Client,
+}
+
+impl TenantServer {
+    fn url(&self, path: &str) -> String {
+        format!("{}{path}", self.address)
+    }
+
+    async fn create(&self, name: &str) -> TenantRegistration {
+        let response = self
+            .client
+            .post(self.url("/api/v1/tenants"))
+            .bearer_auth(ADMIN_TOKEN)
+            .json(&serde_json::json!({ "name": name }))
+            .send()
+            .await
+            .expect("failed to execute request");
+        assert_eq!(response.status(), 201);
+        response.json().await.expect("failed to parse tenant")
+    }
+
+    /// GET `path` as though sent to `<tenant>.localhost`.
+    async fn get_on(&self, tenant: &str, path: &str) -> reqwest::Response {
+        self.client
+            .get(self.url(path))
+            .header("Host", format!("{tenant}.localhost:{}", self.port))
+            .send()
+            .await
+            .expect("failed to execute request")
+    }
+}
+
+async fn spawn_tenant_server(routing: TenantRouting) -> TenantServer {
+    let dir = tempfile::tempdir().expect("failed to create tenants dir");
+    let listener = TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("failed to bind to random port");
+    let port = listener.local_addr().unwrap().port();
+
+    let config = ServerConfig {
+        bind_address: BindAddress::Tcp(listener.local_addr().unwrap()),
+        http: Default::default(),
+        database_url: String::new(),
+        rp_id: "localhost".to_string(),
+        rp_origin: format!("http://localhost:{port}"),
+        insecure_cookies: true,
+        demo: false,
+        token_hasher: TokenHasher::sha256(),
+        openrouter_api_key: String::new(),
+        openrouter_model: "openrouter/free".to_string(),
+        foursquare_api_key: String::new(),
+        images: ImageSettings::default(),
+        maintenance: None,
+        tenants: Some(TenantsConfig {
+            dir: dir.path().to_path_buf(),
+            routing,
+            admin_token: ADMIN_TOKEN.to_string(),
+        }),
+    };
+    let registry = TenantRegistry::new(config).expect("failed to open tenants");
+    let app = tenant_router(Arc::new(registry));
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    TenantServer {
+        address: format!("http://localhost:{port}"),
+        port,
+        dir,
+        client: reqwest::Client::new(),
+    }
+}
+
+/// The token from a registration link.
+fn registration_token(registration: &TenantRegistration) -> &str {
+    registration
+        .registration_url
+        .rsplit('/')
+        .next()
+        .expect("a registration token")
+}
+
+#[tokio::test]
+async fn the_tenant_admin_api_requires_the_admin_token() {
+    let server = spawn_tenant_server(TenantRouting::Subdomain).await;
+
+    for token in [None, Some("wrong")] {
+        let mut request = server.client.get(server.url("/api/v1/tenants"));
+        if let Some(token) = token {
+            request = request.bearer_auth(token);
+        }
+        let response = request.send().await.expect("failed to execute request");
+        assert_eq!(response.status(), 401);
+    }
+}
+
+#[tokio::test]
+async fn creating_a_tenant_gives_it_a_database_and_a_registration_link() {
+    let server = spawn_tenant_server(TenantRouting::Subdomain).await;
+
+    let alice = server.create("alice").await;
+
+    assert_eq!(alice.url, format!("http://alice.localhost:{}", server.port));
+    assert!(
+        alice
+            .registration_url
+            .starts_with(&format!("{}/register/", alice.url))
+    );
+    assert!(server.dir.path().join("alice.db").is_file());
+
+    let tenants: Vec<serde_json::Value> = server
+        .client
+        .get(server.url("/api/v1/tenants"))
+        .bearer_auth(ADMIN_TOKEN)
+        .send()
+        .await
+        .expect("failed to execute request")
+        .json()
+        .await
+        .expect("failed to parse tenants");
+    assert_eq!(tenants.len(), 1);
+    assert_eq!(tenants[0]["name"], "alice");
+}
+
+#[tokio::test]
+async fn tenant_names_must_be_valid_and_unique() {
+    let server = spawn_tenant_server(TenantRouting::Subdomain).await;
+    server.create("alice").await;
+
+    for (name, status) in [("alice", 409), ("Not Valid", 400), ("../etc", 400)] {
+        let response = server
+            .client
+            .post(server.url("/api/v1/tenants"))
+            .bearer_auth(ADMIN_TOKEN)
+            .json(&serde_json::json!({ "name": name }))
+            .send()
+            .await
+            .expect("failed to execute request");
+        assert_eq!(response.status(), status, "{name}");
+    }
+}
+
+#[tokio::test]
+async fn subdomains_reach_their_own_tenant() {
+    let server = spawn_tenant_server(TenantRouting::Subdomain).await;
+    let alice = server.create("alice").await;
+    server.create("bob").await;
+    let register = format!("/register/{}", registration_token(&alice));
+
+    assert_eq!(server.get_on("alice", &register).await.status(), 200);
+    assert_eq!(
+        server.get_on("bob", &register).await.status(),
+        404,
+        "alice's registration link is no use on bob"
+    );
+    assert_eq!(server.get_on("carol", &register).await.status(), 404);
+}
+
+#[tokio::test]
+async fn path_prefixes_reach_their_tenant_and_remember_it() {
+    let server = spawn_tenant_server(TenantRouting::Path).await;
+    let alice = server.create("alice").await;
+    let token = registration_token(&alice);
+    assert_eq!(
+        alice.registration_url,
+        format!("http://localhost:{}/t/alice/register/{token}", server.port)
+    );
+
+    let response = server
+        .client
+        .get(server.url(&format!("/t/alice/register/{token}")))
+        .send()
+        .await
+        .expect("failed to execute request");
+    assert_eq!(response.status(), 200);
+    assert!(
+        response
+            .headers()
+            .get_all("set-cookie")
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .any(|cookie| cookie.starts_with("brewlog_tenant=alice;"))
+    );
+
+    let response = server
+        .client
+        .get(server.url(&format!("/register/{token}")))
+        .header("Cookie", "brewlog_tenant=alice")
+        .send()
+        .await
+        .expect("failed to execute request");
+    assert_eq!(response.status(), 200);
+}
+
+#[tokio::test]
+async fn deleting_a_tenant_removes_its_database() {
+    let server = spawn_tenant_server(TenantRouting::Subdomain).await;
+    let alice = server.create("alice").await;
+
+    let response = server
+        .client
+        .delete(server.url("/api/v1/tenants/alice"))
+        .bearer_auth(ADMIN_TOKEN)
+        .send()
+        .await
+        .expect("failed to execute request");
+    assert_eq!(response.status(), 204);
+
+    assert!(!server.dir.path().join("alice.db").exists());
+    let register = format!("/register/{}", registration_token(&alice));
+    assert_eq!(server.get_on("alice", &register).await.status(), 404);
+}
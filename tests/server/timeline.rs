@@ -1,13 +1,14 @@
 use crate::helpers::{
-    create_cafe_with_payload, create_default_bag, create_default_cafe, create_default_gear,
-    create_default_roast, create_default_roaster, create_roaster_with_payload, spawn_app_with_auth,
-    spawn_app_with_timeline_sync,
+    create_cafe_with_payload, create_default_bag, create_default_cafe, create_default_cup,
+    create_default_gear, create_default_roast, create_default_roaster, create_roaster_with_payload,
+    spawn_app_with_auth, spawn_app_with_timeline_sync,
 };
 use brewlog::domain::brews::NewBrew;
 use brewlog::domain::cafes::NewCafe;
 use brewlog::domain::ids::RoasterId;
 use brewlog::domain::roasters::NewRoaster;
 use brewlog::domain::roasts::NewRoast;
+use brewlog::infrastructure::backup::DryRunReport;
 use reqwest::Client;
 use tokio::time::{Duration, sleep};
 
@@ -21,6 +22,8 @@ async fn create_roast(app: &crate::helpers::TestApp, roaster_id: RoasterId, name
         producer: "Chelbesa Cooperative".to_string(),
         tasting_notes: vec!["Blueberry".to_string(), "Jasmine".to_string()],
         process: "Washed".to_string(),
+        components: Vec::new(),
+        harvest_year: None,
         created_at: None,
     };
 
@@ -634,6 +637,9 @@ async fn creating_a_brew_surfaces_on_the_timeline() {
         filter_paper_id: None,
         water_volume: 250,
         water_temp: 92.0,
+        bypass_water_ml: None,
+        kettle_id: None,
+        actual_water_temp: None,
         quick_notes: Vec::new(),
         brew_time: None,
         created_at: None,
@@ -828,6 +834,26 @@ async fn timeline_rebuild_endpoint_returns_204() {
     assert_eq!(response.status(), 204);
 }
 
+#[tokio::test]
+async fn timeline_rebuild_dry_run_reports_events_to_rebuild() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+    create_default_roast(&app, roaster.id).await;
+
+    let response = Client::new()
+        .post(app.api_url("/timeline/rebuild?dry_run=true"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .send()
+        .await
+        .expect("failed to send rebuild request");
+    assert_eq!(response.status(), 200);
+
+    let report: DryRunReport = response.json().await.expect("failed to parse report");
+    assert_eq!(report.deleted[0].table, "timeline_events");
+    assert_eq!(report.deleted[0].rows, 2);
+    assert_eq!(report.written[0].rows, 2);
+}
+
 #[tokio::test]
 async fn editing_a_roaster_cascades_to_roast_timeline_event() {
     let app = spawn_app_with_timeline_sync().await;
@@ -894,3 +920,242 @@ async fn editing_a_roaster_cascades_to_roast_timeline_event() {
         "Expected cascaded roaster name '{updated_name}' in roast timeline event, got: {body}"
     );
 }
+
+/// The timeline as (type, id, action, occurred at), for comparing it with a
+/// rebuilt one.
+async fn timeline_entries(app: &crate::helpers::TestApp) -> Vec<(String, i64, String, String)> {
+    let mut entries: Vec<_> = app
+        .timeline_repo
+        .list_all()
+        .await
+        .expect("failed to list timeline events")
+        .into_iter()
+        .map(|event| {
+            (
+                event.entity_type.as_str().to_string(),
+                event.entity_id,
+                event.action,
+                event.occurred_at.to_rfc3339(),
+            )
+        })
+        .collect();
+    entries.sort();
+    entries
+}
+
+#[tokio::test]
+async fn deleting_a_roaster_removes_events_for_everything_under_it() {
+    let app = spawn_app_with_timeline_sync().await;
+    let client = Client::new();
+
+    let kept = create_default_cup(&app).await;
+    let roaster = create_roaster_with_payload(
+        &app,
+        NewRoaster {
+            name: "Doomed Roasters".to_string(),
+            country: "UK".to_string(),
+            city: None,
+            homepage: None,
+            created_at: None,
+        },
+    )
+    .await;
+    let roast = create_default_roast(&app, roaster.id).await;
+    create_default_bag(&app, roast.id).await;
+
+    let response = client
+        .delete(app.api_url(&format!("/roasters/{}", roaster.id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .send()
+        .await
+        .expect("failed to delete roaster");
+    assert_eq!(response.status(), 204);
+
+    let live = timeline_entries(&app).await;
+    assert!(
+        live.iter().all(|(kind, id, _, _)| {
+            !(kind == "roaster" && *id == roaster.id.into_inner()
+                || kind == "roast" && *id == roast.id.into_inner()
+                || kind == "bag")
+        }),
+        "events under the deleted roaster should be gone: {live:?}"
+    );
+    assert!(
+        live.iter()
+            .any(|(kind, id, _, _)| kind == "cup" && *id == kept.id.into_inner())
+    );
+
+    let response = client
+        .post(app.api_url("/timeline/rebuild"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .send()
+        .await
+        .expect("failed to send rebuild request");
+    assert_eq!(response.status(), 204);
+    sleep(Duration::from_millis(300)).await;
+
+    assert_eq!(timeline_entries(&app).await, live);
+}
+
+#[tokio::test]
+async fn backdating_a_cup_moves_its_timeline_event() {
+    let app = spawn_app_with_timeline_sync().await;
+    let cup = create_default_cup(&app).await;
+
+    let response = Client::new()
+        .put(app.api_url(&format!("/cups/{}", cup.id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&serde_json::json!({ "created_at": "2024-02-03T10:00:00Z" }))
+        .send()
+        .await
+        .expect("failed to update cup");
+    assert_eq!(response.status(), 200);
+    sleep(Duration::from_millis(300)).await;
+
+    let events = app
+        .timeline_repo
+        .list_all()
+        .await
+        .expect("failed to list timeline events");
+    let event = events
+        .iter()
+        .find(|event| event.entity_type.as_str() == "cup")
+        .expect("cup should be on the timeline");
+    assert_eq!(
+        event.occurred_at,
+        "2024-02-03T10:00:00Z"
+            .parse::<chrono::DateTime<chrono::Utc>>()
+            .unwrap()
+    );
+}
+
+async fn create_roaster_on(app: &crate::helpers::TestApp, name: &str, created_at: &str) {
+    create_roaster_with_payload(
+        app,
+        NewRoaster {
+            name: name.to_string(),
+            country: "UK".to_string(),
+            city: None,
+            homepage: None,
+            created_at: Some(created_at.parse().expect("valid timestamp")),
+        },
+    )
+    .await;
+}
+
+async fn seed_archive(app: &crate::helpers::TestApp) {
+    create_roaster_on(app, "December Roasters", "2024-12-20T09:00:00Z").await;
+    create_roaster_on(app, "Early March Roasters", "2025-03-02T09:00:00Z").await;
+    create_roaster_on(app, "Late March Roasters", "2025-03-28T09:00:00Z").await;
+    create_roaster_on(app, "May Roasters", "2025-05-11T09:00:00Z").await;
+}
+
+async fn get_page(app: &crate::helpers::TestApp, path: &str) -> reqwest::Response {
+    Client::new()
+        .get(format!("{}{path}", app.address))
+        .send()
+        .await
+        .expect("failed to fetch page")
+}
+
+#[tokio::test]
+async fn timeline_archive_lists_months_with_event_counts() {
+    let app = spawn_app_with_auth().await;
+    seed_archive(&app).await;
+
+    let response = get_page(&app, "/timeline/archive").await;
+    assert_eq!(response.status(), 200);
+    let body = response.text().await.expect("failed to read body");
+
+    assert!(body.contains("href=\"/timeline/2025\""));
+    assert!(body.contains("href=\"/timeline/2024\""));
+    assert!(body.contains("href=\"/timeline/2025/03\""));
+    assert!(body.contains("href=\"/timeline/2025/05\""));
+    assert!(body.contains("href=\"/timeline/2024/12\""));
+    assert!(body.contains("3 events"), "2025 should hold three events");
+    assert!(body.contains("1 event<"), "2024 should hold one event");
+}
+
+#[tokio::test]
+async fn timeline_year_page_lists_only_that_year_and_links_its_neighbours() {
+    let app = spawn_app_with_auth().await;
+    seed_archive(&app).await;
+
+    let response = get_page(&app, "/timeline/2025").await;
+    assert_eq!(response.status(), 200);
+    let body = response.text().await.expect("failed to read body");
+
+    assert!(body.contains("href=\"/timeline/2025/03\""));
+    assert!(!body.contains("href=\"/timeline/2024/12\""));
+    assert!(body.contains("rel=\"prev\""));
+    assert!(body.contains("href=\"/timeline/2024\""));
+}
+
+#[tokio::test]
+async fn timeline_month_page_shows_only_that_months_events() {
+    let app = spawn_app_with_auth().await;
+    seed_archive(&app).await;
+
+    let response = get_page(&app, "/timeline/2025/03").await;
+    assert_eq!(response.status(), 200);
+    let body = response.text().await.expect("failed to read body");
+
+    assert!(body.contains("March 2025"));
+    assert!(body.contains("Early March Roasters"));
+    assert!(body.contains("Late March Roasters"));
+    assert!(!body.contains("May Roasters"));
+    assert!(!body.contains("December Roasters"));
+    // Neighbours skip the empty months in between.
+    assert!(body.contains("href=\"/timeline/2024/12\""));
+    assert!(body.contains("href=\"/timeline/2025/05\""));
+}
+
+#[tokio::test]
+async fn timeline_month_page_rejects_an_invalid_month() {
+    let app = spawn_app_with_auth().await;
+
+    let response = get_page(&app, "/timeline/2025/13").await;
+
+    assert_eq!(response.status(), 404);
+}
+
+#[tokio::test]
+async fn timeline_api_returns_events_logged_after_a_given_id() {
+    let app = spawn_app_with_auth().await;
+    create_roaster_on(&app, "First Logged Roasters", "2025-05-01T09:00:00Z").await;
+    // Logged later but backdated: still comes after the first.
+    create_roaster_on(&app, "Second Logged Roasters", "2024-01-01T09:00:00Z").await;
+
+    let events: Vec<serde_json::Value> = get_page(&app, "/api/v1/timeline")
+        .await
+        .json()
+        .await
+        .expect("failed to parse timeline");
+    let titles: Vec<&str> = events
+        .iter()
+        .map(|event| event["title"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        titles,
+        ["First Logged Roasters", "Second Logged Roasters"],
+        "events should be in the order they were logged"
+    );
+
+    let first_id = events[0]["id"].as_i64().unwrap();
+    let newer: Vec<serde_json::Value> =
+        get_page(&app, &format!("/api/v1/timeline?after={first_id}"))
+            .await
+            .json()
+            .await
+            .expect("failed to parse timeline");
+    assert_eq!(newer.len(), 1);
+    assert_eq!(newer[0]["title"], "Second Logged Roasters");
+
+    let latest: Vec<serde_json::Value> = get_page(&app, "/api/v1/timeline?limit=1")
+        .await
+        .json()
+        .await
+        .expect("failed to parse timeline");
+    assert_eq!(latest.len(), 1);
+    assert_eq!(latest[0]["title"], "Second Logged Roasters");
+}
@@ -0,0 +1,261 @@
+use brewlog::domain::cafes::Cafe;
+use brewlog::domain::cups::Cup;
+use brewlog::domain::entity_type::EntityType;
+use brewlog::domain::ids::{CafeId, RoastId};
+use brewlog::domain::trips::{Trip, TripSummary};
+use brewlog::infrastructure::backup::BackupData;
+use reqwest::Client;
+use serde_json::{Value, json};
+
+use super::helpers::{
+    TestApp, create_default_roast, create_default_roaster, create_entity, create_session,
+    spawn_app, spawn_app_with_auth,
+};
+
+fn lisbon() -> Value {
+    json!({
+        "name": "Lisbon long weekend",
+        "location": "Lisbon, Portugal",
+        "start_date": "2026-03-06",
+        "end_date": "2026-03-09",
+    })
+}
+
+async fn cafe(app: &TestApp, name: &str, country: &str, created_at: &str) -> Cafe {
+    create_entity(
+        app,
+        "/cafes",
+        &json!({
+            "name": name,
+            "city": "Lisbon",
+            "country": country,
+            "latitude": 38.71,
+            "longitude": -9.14,
+            "created_at": created_at,
+        }),
+    )
+    .await
+}
+
+async fn cup(app: &TestApp, roast_id: RoastId, cafe_id: Option<CafeId>, created_at: &str) -> Cup {
+    create_entity(
+        app,
+        "/cups",
+        &json!({ "roast_id": roast_id, "cafe_id": cafe_id, "created_at": created_at }),
+    )
+    .await
+}
+
+async fn summary(app: &TestApp, trip: &Trip) -> TripSummary {
+    Client::new()
+        .get(app.api_url(&format!("/trips/{}/summary", trip.id)))
+        .send()
+        .await
+        .expect("failed to execute request")
+        .json()
+        .await
+        .expect("failed to parse")
+}
+
+#[tokio::test]
+async fn logging_a_trip_requires_auth_and_a_forward_date_range() {
+    let app = spawn_app().await;
+    let response = Client::new()
+        .post(app.api_url("/trips"))
+        .json(&lisbon())
+        .send()
+        .await
+        .expect("failed to execute request");
+    assert_eq!(response.status(), 401);
+
+    let app = spawn_app_with_auth().await;
+    let mut backwards = lisbon();
+    backwards["end_date"] = json!("2026-03-01");
+    let response = Client::new()
+        .post(app.api_url("/trips"))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&backwards)
+        .send()
+        .await
+        .expect("failed to execute request");
+    assert_eq!(response.status(), 422);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["fields"][0]["field"], "end_date");
+}
+
+#[tokio::test]
+async fn a_trip_gathers_the_cups_and_cafes_from_its_dates() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+    let roast = create_default_roast(&app, roaster.id).await;
+    let home = cafe(&app, "Local Spot", "United Kingdom", "2025-01-10T09:00:00Z").await;
+    let visited = cafe(&app, "Fabrica", "Portugal", "2025-06-01T09:00:00Z").await;
+    let found = cafe(
+        &app,
+        "Copenhagen Coffee Lab",
+        "Portugal",
+        "2026-03-08T10:00:00Z",
+    )
+    .await;
+
+    let before = cup(&app, roast.id, Some(home.id), "2026-03-05T23:30:00Z").await;
+    let first = cup(&app, roast.id, Some(visited.id), "2026-03-06T09:00:00Z").await;
+    let last = cup(&app, roast.id, None, "2026-03-09T18:00:00Z").await;
+    let after = cup(&app, roast.id, Some(home.id), "2026-03-10T08:00:00Z").await;
+
+    let trip: Trip = create_entity(&app, "/trips", &lisbon()).await;
+    let summary = summary(&app, &trip).await;
+
+    let cup_ids: Vec<_> = summary.cups.iter().map(|cwd| cwd.cup.id).collect();
+    assert_eq!(cup_ids, [first.id, last.id]);
+    assert!(!cup_ids.contains(&before.id) && !cup_ids.contains(&after.id));
+
+    let cafe_ids: Vec<_> = summary.cafes.iter().map(|cafe| cafe.id).collect();
+    assert!(cafe_ids.contains(&visited.id));
+    assert!(cafe_ids.contains(&found.id));
+    assert!(!cafe_ids.contains(&home.id));
+
+    let timeline = app
+        .timeline_repo
+        .list_all()
+        .await
+        .expect("failed to list timeline events");
+    let entry = timeline
+        .iter()
+        .find(|entry| entry.entity_type == EntityType::Trip)
+        .expect("trip should be on the timeline");
+    assert_eq!(entry.entity_id, i64::from(trip.id));
+    assert_eq!(entry.occurred_at.date_naive(), trip.start_date);
+}
+
+#[tokio::test]
+async fn moving_a_trip_end_before_its_start_is_rejected() {
+    let app = spawn_app_with_auth().await;
+    let trip: Trip = create_entity(&app, "/trips", &lisbon()).await;
+    let client = Client::new();
+
+    let response = client
+        .put(app.api_url(&format!("/trips/{}", trip.id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&json!({ "end_date": "2026-03-01" }))
+        .send()
+        .await
+        .expect("failed to execute request");
+    assert_eq!(response.status(), 422);
+
+    let response = client
+        .put(app.api_url(&format!("/trips/{}", trip.id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .json(&json!({ "end_date": "2026-03-12" }))
+        .send()
+        .await
+        .expect("failed to execute request");
+    assert_eq!(response.status(), 200);
+    let updated: Trip = response.json().await.unwrap();
+    assert_eq!(updated.days(), 7);
+}
+
+#[tokio::test]
+async fn deleting_a_trip_removes_it_from_the_timeline() {
+    let app = spawn_app_with_auth().await;
+    let trip: Trip = create_entity(&app, "/trips", &lisbon()).await;
+
+    let response = Client::new()
+        .delete(app.api_url(&format!("/trips/{}", trip.id)))
+        .bearer_auth(app.auth_token.as_ref().unwrap())
+        .send()
+        .await
+        .expect("failed to execute request");
+    assert_eq!(response.status(), 204);
+
+    let timeline = app
+        .timeline_repo
+        .list_all()
+        .await
+        .expect("failed to list timeline events");
+    assert!(
+        timeline
+            .iter()
+            .all(|entry| entry.entity_type != EntityType::Trip)
+    );
+}
+
+#[tokio::test]
+async fn trip_pages_show_the_cafes_and_coffees() {
+    let app = spawn_app_with_auth().await;
+    let roaster = create_default_roaster(&app).await;
+    let roast = create_default_roast(&app, roaster.id).await;
+    let visited = cafe(&app, "Fabrica", "Portugal", "2025-06-01T09:00:00Z").await;
+    cup(&app, roast.id, Some(visited.id), "2026-03-07T09:00:00Z").await;
+    let trip: Trip = create_entity(&app, "/trips", &lisbon()).await;
+    let session = create_session(&app).await;
+    let client = Client::new();
+
+    let page = |path: String| {
+        let client = client.clone();
+        let session = session.clone();
+        let url = app.page_url(&path);
+        async move {
+            client
+                .get(url)
+                .header("Cookie", format!("brewlog_session={session}"))
+                .send()
+                .await
+                .expect("failed to fetch page")
+                .text()
+                .await
+                .unwrap()
+        }
+    };
+
+    let list = page("/trips".to_string()).await;
+    assert!(list.contains("Lisbon long weekend"));
+    assert!(list.contains("trip-form"));
+
+    let detail = page(format!("/trips/{}", trip.id)).await;
+    assert!(detail.contains("Fabrica"));
+    assert!(detail.contains(&roast.name));
+    assert!(detail.contains("PT:1"), "map should shade Portugal");
+
+    let timeline = page("/timeline".to_string()).await;
+    assert!(timeline.contains(&format!("/trips/{}", trip.id)));
+}
+
+#[tokio::test]
+async fn backups_carry_trips() {
+    let source = spawn_app_with_auth().await;
+    let _: Trip = create_entity(&source, "/trips", &lisbon()).await;
+    let client = Client::new();
+
+    let backup: BackupData = client
+        .get(source.api_url("/backup"))
+        .bearer_auth(source.auth_token.as_ref().unwrap())
+        .send()
+        .await
+        .expect("failed to export backup")
+        .json()
+        .await
+        .expect("failed to parse backup");
+    assert_eq!(backup.trips.len(), 1);
+
+    let target = spawn_app_with_auth().await;
+    let response = client
+        .post(target.api_url("/backup/restore"))
+        .bearer_auth(target.auth_token.as_ref().unwrap())
+        .json(&backup)
+        .send()
+        .await
+        .expect("failed to restore backup");
+    assert_eq!(response.status(), 204);
+
+    let trips: Vec<Trip> = client
+        .get(target.api_url("/trips"))
+        .send()
+        .await
+        .expect("failed to list trips")
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(trips.len(), 1);
+    assert_eq!(trips[0].location, "Lisbon, Portugal");
+}